@@ -1,11 +1,76 @@
+#include <fstream>
 #include <iostream>
+#include <string_view>
 #include <windows.h>
 #include <appmodel.h>
+#include <winrt/Windows.Data.Xml.Dom.h>
+#include <winrt/Windows.UI.Notifications.h>
 #include <winrt/Microsoft.Windows.ApplicationModel.WindowsAppRuntime.h>
 
-int main() {
+// Escapes a string for a JSON string literal
+static std::string JsonEscape(const std::string& value) {
+    std::string escaped;
+    for (char c : value) {
+        if (c == '"' || c == '\\') {
+            escaped += '\\';
+            escaped += c;
+        } else if (static_cast<unsigned char>(c) < 0x20) {
+            escaped += ' ';
+        } else {
+            escaped += c;
+        }
+    }
+    return escaped;
+}
+
+// The family name of the package identity, or an empty string when the app runs without one
+static std::wstring GetPackageFamilyName() {
+    UINT32 length = 0;
+    if (GetCurrentPackageFamilyName(&length, nullptr) != ERROR_INSUFFICIENT_BUFFER) {
+        return {};
+    }
+    std::wstring familyName(length, L'\0');
+    if (GetCurrentPackageFamilyName(&length, familyName.data()) != ERROR_SUCCESS) {
+        return {};
+    }
+    // length includes the null terminator
+    familyName.resize(length - 1);
+    return familyName;
+}
+
+static void ShowNotification(const std::wstring& message) {
+    using namespace winrt::Windows::UI::Notifications;
+
+    auto toastXml = ToastNotificationManager::GetTemplateContent(ToastTemplateType::ToastText01);
+    toastXml.GetElementsByTagName(L"text").Item(0).AppendChild(toastXml.CreateTextNode(message));
+    ToastNotificationManager::CreateToastNotifier().Show(ToastNotification(toastXml));
+}
+
+// Shows a notification and writes the package family name and the notification's outcome to the path,
+// for scripts/test-e2e-samples.ps1, which launches the installed package with --winapp-check <path>
+static int WriteCheck(const wchar_t* path) {
+    std::string notification = "shown";
+    try {
+        ShowNotification(L"hello from cpp-app");
+    } catch (const winrt::hresult_error& ex) {
+        notification = winrt::to_string(ex.message());
+    }
+
+    std::ofstream file(path);
+    file << "{\"packageFamilyName\":\"" << JsonEscape(winrt::to_string(GetPackageFamilyName()))
+         << "\",\"notification\":\"" << JsonEscape(notification) << "\"}";
+    return file ? 0 : 1;
+}
+
+int wmain(int argc, wchar_t* argv[]) {
     // Initialize WinRT
     winrt::init_apartment();
+
+    for (int i = 1; i + 1 < argc; ++i) {
+        if (std::wstring_view(argv[i]) == L"--winapp-check") {
+            return WriteCheck(argv[i + 1]);
+        }
+    }
     
     UINT32 length = 0;
     LONG result = GetCurrentPackageFamilyName(&length, nullptr);
@@ -1,6 +1,7 @@
 #include <napi.h>
 #include <shobjidl_core.h>
 #include <windows.h>
+#include <appmodel.h>
 
 #include <winrt/Windows.Foundation.h>
 #include <winrt/Windows.UI.Notifications.h>
@@ -46,9 +47,29 @@ void ShowNotification(const Napi::CallbackInfo& info) {
     }
 }
 
+// Function to get the family name of the package identity, or null when the app runs without one
+Napi::Value GetPackageFamilyName(const Napi::CallbackInfo& info) {
+    Napi::Env env = info.Env();
+
+    UINT32 length = 0;
+    if (GetCurrentPackageFamilyName(&length, nullptr) != ERROR_INSUFFICIENT_BUFFER) {
+        return env.Null();
+    }
+
+    std::wstring familyName(length, L'\0');
+    if (GetCurrentPackageFamilyName(&length, familyName.data()) != ERROR_SUCCESS) {
+        return env.Null();
+    }
+
+    // length includes the null terminator
+    familyName.resize(length - 1);
+    return Napi::String::New(env, winrt::to_string(familyName));
+}
+
 // Initialize the module
 Napi::Object Init(Napi::Env env, Napi::Object exports) {
     exports.Set(Napi::String::New(env, "showNotification"), Napi::Function::New(env, ShowNotification));
+    exports.Set(Napi::String::New(env, "getPackageFamilyName"), Napi::Function::New(env, GetPackageFamilyName));
     return exports;
 }
 
@@ -1,4 +1,5 @@
 const { app, BrowserWindow, ipcMain} = require('electron');
+const fs = require('node:fs');
 const path = require('node:path');
 
 const addon = require('../addon/build/Release/addon.node');
@@ -18,6 +19,20 @@ if (require('electron-squirrel-startup')) {
   app.quit();
 }
 
+// Shows a notification and writes the package family name and the notification's outcome to the path,
+// for scripts/test-e2e-samples.ps1, which launches the installed package with --winapp-check <path>
+const writeCheck = (checkPath) => {
+  let notification = 'shown';
+  try {
+    addon.showNotification('Electron winapp Sample', 'hello from electron');
+  } catch (error) {
+    notification = error.message;
+  }
+  fs.writeFileSync(checkPath, JSON.stringify({ packageFamilyName: addon.getPackageFamilyName(), notification }));
+};
+
+const checkIndex = process.argv.indexOf('--winapp-check');
+
 const createWindow = () => {
   // Create the browser window.
   const mainWindow = new BrowserWindow({
@@ -36,6 +51,12 @@ const createWindow = () => {
 // initialization and is ready to create browser windows.
 // Some APIs can only be used after this event occurs.
 app.whenReady().then(() => {
+  if (checkIndex !== -1) {
+    writeCheck(process.argv[checkIndex + 1]);
+    app.quit();
+    return;
+  }
+
   createWindow();
 
   // On OS X it's common to re-create a window in the app when the
@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use windows::ApplicationModel::Package;
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager, ToastTemplateType};
+use windows::core::HSTRING;
+
+/// The family name of the package identity, or None when the app runs without one
+pub fn package_family_name() -> Option<String> {
+    Package::Current()
+        .and_then(|package| package.Id())
+        .and_then(|id| id.FamilyName())
+        .ok()
+        .map(|name| name.to_string())
+}
+
+pub fn show_notification(message: &str) -> windows::core::Result<()> {
+    let toast_xml = ToastNotificationManager::GetTemplateContent(ToastTemplateType::ToastText01)?;
+    let text_nodes = toast_xml.GetElementsByTagName(&HSTRING::from("text"))?;
+    text_nodes
+        .Item(0)?
+        .AppendChild(&toast_xml.CreateTextNode(&HSTRING::from(message))?)?;
+
+    let toast = ToastNotification::CreateToastNotification(&toast_xml)?;
+    ToastNotificationManager::CreateToastNotifier()?.Show(&toast)?;
+    Ok(())
+}
+
+/// The path after --winapp-check, which scripts/test-e2e-samples.ps1 launches the installed package with
+pub fn check_path() -> Option<PathBuf> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--winapp-check")
+        .and_then(|index| args.get(index + 1))
+        .map(PathBuf::from)
+}
+
+/// Shows a notification and writes the package family name and the notification's outcome to the path
+pub fn write_check(path: &Path) -> std::io::Result<()> {
+    let notification = match show_notification("hello from rust-winit-app") {
+        Ok(()) => "shown".to_string(),
+        Err(e) => e.to_string(),
+    };
+    let result = serde_json::json!({
+        "packageFamilyName": package_family_name(),
+        "notification": notification,
+    });
+    std::fs::write(path, result.to_string())
+}
@@ -0,0 +1,61 @@
+// Prevents an additional console window on Windows in release
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+mod identity;
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::window::{Window, WindowId};
+
+#[derive(Default)]
+struct App {
+    window: Option<Window>,
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+
+        let title = match identity::package_family_name() {
+            Some(name) => {
+                if let Err(e) = identity::show_notification("hello from rust-winit-app") {
+                    println!("Error showing notification: {}", e);
+                }
+                format!("rust-winit-app - {}", name)
+            }
+            None => "rust-winit-app - Not packaged".to_string(),
+        };
+        let attributes = Window::default_attributes().with_title(title);
+        self.window = Some(
+            event_loop
+                .create_window(attributes)
+                .expect("error while creating the window"),
+        );
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if let WindowEvent::CloseRequested = event {
+            event_loop.exit();
+        }
+    }
+}
+
+fn main() {
+    if let Some(path) = identity::check_path() {
+        identity::write_check(&path).expect("error while writing the check result");
+        return;
+    }
+
+    let event_loop = EventLoop::new().expect("error while creating the event loop");
+    event_loop
+        .run_app(&mut App::default())
+        .expect("error while running the app");
+}
@@ -78,8 +78,32 @@ fn show_notification(title: &str, body: &str) -> Result<(), String> {
     }
 }
 
+// Shows a notification and writes the package family name and the notification's outcome to the path,
+// for scripts/test-e2e-samples.ps1, which launches the installed package with --winapp-check <path>
+fn write_check(path: &str) -> std::io::Result<()> {
+    let notification = match show_notification("tauri-app", "hello from tauri") {
+        Ok(()) => "shown".to_string(),
+        Err(e) => e,
+    };
+    let result = serde_json::json!({
+        "packageFamilyName": get_package_family_name(),
+        "notification": notification,
+    });
+    std::fs::write(path, result.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(path) = args
+        .iter()
+        .position(|arg| arg == "--winapp-check")
+        .and_then(|index| args.get(index + 1))
+    {
+        write_check(path).expect("error while writing the check result");
+        return;
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![greet, get_package_family_name, show_notification])
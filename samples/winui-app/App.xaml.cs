@@ -0,0 +1,32 @@
+using Microsoft.UI.Xaml;
+
+namespace winui_app;
+
+/// <summary>
+/// Interaction logic for App.xaml
+/// </summary>
+public partial class App : Application
+{
+    private Window? _window;
+
+    public App()
+    {
+        InitializeComponent();
+    }
+
+    protected override void OnLaunched(LaunchActivatedEventArgs args)
+    {
+        // scripts/test-e2e-samples.ps1 launches the installed package with --winapp-check <path>
+        var arguments = Environment.GetCommandLineArgs();
+        var check = Array.IndexOf(arguments, "--winapp-check");
+        if (check != -1 && check + 1 < arguments.Length)
+        {
+            Identity.WriteCheck(arguments[check + 1]);
+            Exit();
+            return;
+        }
+
+        _window = new MainWindow();
+        _window.Activate();
+    }
+}
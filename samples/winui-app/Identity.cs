@@ -0,0 +1,51 @@
+using System.Text.Json;
+using Windows.ApplicationModel;
+using Windows.UI.Notifications;
+
+namespace winui_app;
+
+internal static class Identity
+{
+    /// <summary>
+    /// The family name of the package identity, or null when the app runs without one
+    /// </summary>
+    public static string? PackageFamilyName
+    {
+        get
+        {
+            try
+            {
+                return Package.Current.Id.FamilyName;
+            }
+            catch (InvalidOperationException)
+            {
+                // Thrown when app doesn't have package identity
+                return null;
+            }
+        }
+    }
+
+    public static void ShowNotification(string message)
+    {
+        var toastXml = ToastNotificationManager.GetTemplateContent(ToastTemplateType.ToastText01);
+        toastXml.GetElementsByTagName("text")[0].AppendChild(toastXml.CreateTextNode(message));
+        ToastNotificationManager.CreateToastNotifier().Show(new ToastNotification(toastXml));
+    }
+
+    /// <summary>
+    /// Shows a notification and writes the package family name and the notification's outcome to the path
+    /// </summary>
+    public static void WriteCheck(string path)
+    {
+        var notification = "shown";
+        try
+        {
+            ShowNotification("hello from winui-app");
+        }
+        catch (Exception ex)
+        {
+            notification = ex.Message;
+        }
+        File.WriteAllText(path, JsonSerializer.Serialize(new { packageFamilyName = PackageFamilyName, notification }));
+    }
+}
@@ -0,0 +1,33 @@
+using Microsoft.UI.Xaml;
+
+namespace winui_app;
+
+/// <summary>
+/// Interaction logic for MainWindow.xaml
+/// </summary>
+public sealed partial class MainWindow : Window
+{
+    public MainWindow()
+    {
+        InitializeComponent();
+
+        if (Identity.PackageFamilyName is { } familyName)
+        {
+            StatusTextBlock.Text = $"Package Family Name: {familyName}";
+
+            // Get Windows App Runtime version using the API
+            var runtimeVersion = Microsoft.Windows.ApplicationModel.WindowsAppRuntime.RuntimeInfo.AsString;
+            StatusTextBlock.Text += $"\nWindows App Runtime Version: {runtimeVersion}";
+        }
+        else
+        {
+            StatusTextBlock.Text = "Not packaged";
+            NotificationButton.IsEnabled = false;
+        }
+    }
+
+    private void NotificationButton_Click(object sender, RoutedEventArgs e)
+    {
+        Identity.ShowNotification("hello from winui-app");
+    }
+}
@@ -309,7 +309,7 @@ try {
 
     # Use --use-defaults for non-interactive initialization
     # Setup stable SDKs for packaging
-    $initCommand = "npx winapp init . --use-defaults --setup-sdks=stable"
+    $initCommand = "npx winapp init . --use-defaults --setup-sdks=stable --accept-licenses"
     Assert-Command $initCommand "Failed to initialize winapp workspace"
 
     # Verify workspace was created
@@ -0,0 +1,306 @@
+<#
+.SYNOPSIS
+End-to-end test of a sample of the sample matrix with the WinApp CLI.
+
+.DESCRIPTION
+This script tests one sample of the sample matrix (see samples/README.md) with the locally-built CLI:
+1. Copies the sample to a 'test-wd' directory in the repo root
+2. Restores its Windows App SDK packages and runtime, and its npm packages
+3. Generates a development certificate for the manifest's publisher
+4. Builds and packs it with 'winapp pack --build', installing the certificate
+5. Installs the package
+6. Launches the installed app through its execution alias with --winapp-check <path>
+7. Checks that the app ran with the identity of the installed package and showed a notification
+
+The app writes the check result to the path as JSON:
+{ "packageFamilyName": "<family name of its package identity, or null>", "notification": "shown" | "<error>" }
+
+The package and the test directory are removed after completion.
+
+.PARAMETER Sample
+Name of the sample folder to test.
+
+.PARAMETER CliPath
+Folder containing the built winapp.exe, which is put first on the PATH.
+Default: "$PSScriptRoot\..\artifacts\cli\win-x64"
+
+.PARAMETER TimeoutSeconds
+How long to wait for the app to write the check result.
+Default: 60
+
+.PARAMETER SkipCleanup
+If specified, does not uninstall the package or delete the test directory after completion (useful for debugging).
+
+.PARAMETER Verbose
+Enable verbose output for debugging.
+
+.EXAMPLE
+.\test-e2e-samples.ps1 -Sample rust-winit-app
+Test the Rust winit sample with the CLI in artifacts\cli\win-x64.
+
+.EXAMPLE
+.\test-e2e-samples.ps1 -Sample winui-app -CliPath .\artifacts\cli\win-arm64 -SkipCleanup -Verbose
+Test the WinUI sample on an ARM64 machine, keep the package installed, and show detailed output.
+#>
+
+param(
+    [Parameter(Mandatory = $true)]
+    [ValidateSet('rust-winit-app', 'tauri-app', 'cpp-app', 'winui-app', 'electron')]
+    [string]$Sample,
+    [string]$CliPath = "$PSScriptRoot\..\artifacts\cli\win-x64",
+    [int]$TimeoutSeconds = 60,
+    [switch]$SkipCleanup,
+    [switch]$Verbose
+)
+
+# Enable strict mode
+Set-StrictMode -Version Latest
+$ErrorActionPreference = 'Stop'
+
+$VerbosePreference = if ($Verbose) { 'Continue' } else { 'SilentlyContinue' }
+
+# ============================================================================
+# Helper Functions
+# ============================================================================
+
+function Write-TestHeader {
+    param([string]$Message)
+    Write-Host "`n$('='*80)" -ForegroundColor Cyan
+    Write-Host "TEST: $Message" -ForegroundColor Cyan
+    Write-Host "$('='*80)`n" -ForegroundColor Cyan
+}
+
+function Write-TestStep {
+    param([string]$Message, [int]$Step)
+    Write-Host "[$Step] $Message" -ForegroundColor Yellow
+}
+
+function Write-TestSuccess {
+    param([string]$Message)
+    Write-Host "✓ $Message" -ForegroundColor Green
+}
+
+function Write-TestError {
+    param([string]$Message)
+    Write-Host "✗ $Message" -ForegroundColor Red
+}
+
+function Assert-Command {
+    param(
+        [string]$Command,
+        [string]$FailMessage
+    )
+    Write-Verbose "Running: $Command"
+    $result = Invoke-Expression $Command
+    if ($LASTEXITCODE -ne 0) {
+        Write-TestError $FailMessage
+        throw $FailMessage
+    }
+    Write-TestSuccess "$Command"
+    return $result
+}
+
+function Assert-FileExists {
+    param(
+        [string]$Path,
+        [string]$Description
+    )
+    if (-not (Test-Path $Path)) {
+        Write-TestError "$Description not found at $Path"
+        throw "$Description not found at $Path"
+    }
+    Write-TestSuccess "$Description exists: $Path"
+}
+
+function Assert-Equal {
+    param(
+        [object]$Expected,
+        [object]$Actual,
+        [string]$Description
+    )
+    if ($Expected -ne $Actual) {
+        Write-TestError "$Description was '$Actual', expected '$Expected'"
+        throw "$Description was '$Actual', expected '$Expected'"
+    }
+    Write-TestSuccess "$Description is '$Actual'"
+}
+
+# ============================================================================
+# Validation
+# ============================================================================
+
+Write-TestHeader "E2E Sample Test ($Sample) - Validation Phase"
+
+Write-TestStep "Validating prerequisites..." 1
+
+$repoRoot = (Resolve-Path "$PSScriptRoot\..").Path
+$sampleDir = Join-Path $repoRoot "samples" $Sample
+Assert-FileExists (Join-Path $sampleDir "appxmanifest.xml") "Sample manifest"
+
+Assert-FileExists (Join-Path $CliPath "winapp.exe") "winapp CLI"
+$resolvedCliPath = (Resolve-Path $CliPath).Path
+
+# Put the built CLI first on the PATH, so the samples' builds use it too
+$env:PATH = "$resolvedCliPath;$env:PATH"
+$winappVersion = winapp --version
+Write-TestSuccess "winapp found: $winappVersion"
+
+# The identity and the execution alias the harness launches the app through
+[xml]$manifest = Get-Content (Join-Path $sampleDir "appxmanifest.xml")
+$packageName = $manifest.Package.Identity.Name
+$aliasNode = Select-Xml -Xml $manifest -XPath "//*[local-name()='ExecutionAlias']" | Select-Object -First 1
+if (-not $aliasNode) {
+    Write-TestError "The manifest of $Sample has no execution alias to launch it with"
+    throw "The manifest of $Sample has no execution alias"
+}
+$alias = $aliasNode.Node.Alias
+Write-TestSuccess "Package $packageName, launched as $alias"
+
+# The Appx cmdlets don't load in PowerShell 7 on every Windows version
+if ($PSVersionTable.PSEdition -eq 'Core') {
+    Import-Module Appx -UseWindowsPowerShell -WarningAction SilentlyContinue
+}
+
+# ============================================================================
+# Setup Test Environment
+# ============================================================================
+
+Write-TestHeader "E2E Sample Test ($Sample) - Setup Phase"
+
+Write-TestStep "Copying the sample to the test directory..." 2
+
+$testDir = Join-Path $repoRoot "test-wd"
+$appDir = Join-Path $testDir $Sample
+# Outside the app's AppData, which packaged apps see virtualized
+$resultPath = Join-Path $testDir "$Sample-check.json"
+$msixPath = Join-Path $testDir "$Sample.msix"
+
+# Clean up any existing test directory
+if (Test-Path $testDir) {
+    Remove-Item -Path $testDir -Recurse -Force -ErrorAction SilentlyContinue
+}
+
+$null = New-Item -ItemType Directory -Path $testDir -Force
+Copy-Item -Path $sampleDir -Destination $appDir -Recurse
+Write-TestSuccess "Sample copied to $appDir"
+
+# Save original location to restore on exit
+$originalLocation = Get-Location
+
+try {
+    Push-Location $appDir
+    Write-Verbose "Working directory: $(Get-Location)"
+
+    # ========================================================================
+    # Restore
+    # ========================================================================
+
+    Write-TestHeader "E2E Sample Test ($Sample) - Restore Phase"
+
+    Write-TestStep "Restoring packages..." 3
+
+    if (Test-Path "package.json") {
+        # Set a unique npm cache directory to avoid ECOMPROMISED errors in CI
+        $env:npm_config_cache = Join-Path $testDir ".npm-cache"
+
+        # Without the sample's postinstall, which restores with the published CLI; 'npm rebuild' still runs the
+        # install scripts of its dependencies, such as Electron's download
+        Assert-Command "npm ci --ignore-scripts" "Failed to install npm packages"
+        Assert-Command "npm rebuild" "Failed to run the install scripts of npm packages"
+    }
+
+    if (Test-Path "winapp.yaml") {
+        # Also installs the Windows App Runtime the package depends on
+        Assert-Command "winapp restore --accept-licenses" "Failed to restore the Windows App SDK"
+    }
+
+    Write-TestStep "Generating a development certificate..." 4
+
+    Assert-Command "winapp cert generate --if-exists skip" "Failed to generate a development certificate"
+    Assert-FileExists "devcert.pfx" "Development certificate"
+
+    # ========================================================================
+    # Build and Package
+    # ========================================================================
+
+    Write-TestHeader "E2E Sample Test ($Sample) - Package Phase"
+
+    Write-TestStep "Building and packing with 'winapp pack --build'..." 5
+
+    Assert-Command "winapp pack --build --cert devcert.pfx --install-cert --output `"$msixPath`"" "Failed to build and pack $Sample"
+    Assert-FileExists $msixPath "MSIX package"
+
+    # ========================================================================
+    # Install
+    # ========================================================================
+
+    Write-TestHeader "E2E Sample Test ($Sample) - Install Phase"
+
+    Write-TestStep "Installing the package..." 6
+
+    # Packages of earlier runs, and debug identities the sample registered, would get in the way
+    Get-AppxPackage -Name $packageName | Remove-AppxPackage
+    Add-AppxPackage -Path $msixPath
+    $package = Get-AppxPackage -Name $packageName
+    if (-not $package) {
+        Write-TestError "Package $packageName is not installed"
+        throw "Package $packageName is not installed"
+    }
+    Write-TestSuccess "Installed $($package.PackageFullName)"
+
+    # ========================================================================
+    # Launch and Check
+    # ========================================================================
+
+    Write-TestHeader "E2E Sample Test ($Sample) - Check Phase"
+
+    Write-TestStep "Launching $alias with --winapp-check..." 7
+
+    $aliasPath = Join-Path $env:LOCALAPPDATA "Microsoft\WindowsApps\$alias"
+    $deadline = (Get-Date).AddSeconds($TimeoutSeconds)
+    while (-not (Test-Path $aliasPath) -and (Get-Date) -lt $deadline) {
+        Start-Sleep -Milliseconds 500
+    }
+    Assert-FileExists $aliasPath "Execution alias"
+
+    $process = Start-Process -FilePath $aliasPath -ArgumentList "--winapp-check", "`"$resultPath`"" -PassThru
+    while (-not (Test-Path $resultPath) -and (Get-Date) -lt $deadline) {
+        Start-Sleep -Milliseconds 500
+    }
+    if (-not (Test-Path $resultPath)) {
+        Write-TestError "$Sample wrote no check result within $TimeoutSeconds seconds (exit code: $(if ($process.HasExited) { $process.ExitCode } else { 'still running' }))"
+        throw "$Sample wrote no check result"
+    }
+
+    # The app may still be writing the file
+    $result = $null
+    while (-not $result -and (Get-Date) -lt $deadline) {
+        try {
+            $result = Get-Content $resultPath -Raw | ConvertFrom-Json
+        } catch {
+            Start-Sleep -Milliseconds 500
+        }
+    }
+    if (-not $result) {
+        Write-TestError "The check result of $Sample is not valid JSON: $(Get-Content $resultPath -Raw)"
+        throw "The check result of $Sample is not valid JSON"
+    }
+    Write-Verbose "Check result: $($result | ConvertTo-Json -Compress)"
+
+    Write-TestStep "Checking identity and notification..." 8
+
+    Assert-Equal $package.PackageFamilyName $result.packageFamilyName "Package family name"
+    Assert-Equal "shown" $result.notification "Notification"
+
+    Write-TestHeader "E2E Sample Test ($Sample) - PASSED"
+} finally {
+    Set-Location $originalLocation
+
+    if (-not $SkipCleanup) {
+        Write-Verbose "Cleaning up..."
+        Get-AppxPackage -Name $packageName | Remove-AppxPackage -ErrorAction SilentlyContinue
+        Remove-Item -Path $testDir -Recurse -Force -ErrorAction SilentlyContinue
+    } else {
+        Write-Host "Skipping cleanup. Test directory: $testDir" -ForegroundColor Yellow
+    }
+}
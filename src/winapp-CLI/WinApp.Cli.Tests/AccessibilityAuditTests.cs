@@ -0,0 +1,157 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Drawing.Imaging;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class AccessibilityAuditTests : BaseCommandTests
+{
+    public AccessibilityAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private void CreateLogo(string name, Color color)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "Assets", name);
+        Directory.CreateDirectory(Path.GetDirectoryName(path)!);
+
+        // Logo in the middle of a transparent canvas
+        using var bitmap = new Bitmap(48, 48);
+        using (var graphics = Graphics.FromImage(bitmap))
+        {
+            graphics.Clear(Color.Transparent);
+            using var brush = new SolidBrush(color);
+            graphics.FillEllipse(brush, 8, 8, 32, 32);
+        }
+        bitmap.Save(path, ImageFormat.Png);
+    }
+
+    private FileInfo CreateManifest(string backgroundColor, string defaultTile)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, $"""
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="{backgroundColor}" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png">
+                    {defaultTile}
+                  </uap:VisualElements>
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private async Task<AccessibilityReport> AnalyzeAsync(FileInfo manifest)
+    {
+        var service = GetRequiredService<IAccessibilityAuditService>();
+        return await service.AnalyzeAsync(manifest, TestTaskContext, TestContext.CancellationToken);
+    }
+
+    [TestMethod]
+    public void ContrastRatio_FollowsWcag()
+    {
+        // Act & Assert
+        Assert.AreEqual(21.0, AccessibilityAuditService.ContrastRatio(Color.Black, Color.White), 0.01);
+        Assert.AreEqual(1.0, AccessibilityAuditService.ContrastRatio(ColorTranslator.FromHtml("#0078D4"), ColorTranslator.FromHtml("#0078D4")), 0.01);
+        Assert.AreEqual(4.53, AccessibilityAuditService.ContrastRatio(Color.White, ColorTranslator.FromHtml("#0078D4")), 0.01);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_PassesCompleteAccessibleAssets()
+    {
+        // Arrange
+        CreateLogo("Square150x150Logo.png", Color.White);
+        CreateLogo("Square150x150Logo.contrast-black.png", Color.White);
+        CreateLogo("Square44x44Logo.png", Color.White);
+        CreateLogo("Square44x44Logo.contrast-white.png", Color.Black);
+        foreach (var size in new[] { 16, 24, 32, 48, 256 })
+        {
+            CreateLogo($"Square44x44Logo.targetsize-{size}_altform-unplated.png", Color.White);
+            CreateLogo($"Square44x44Logo.targetsize-{size}_altform-lightunplated.png", Color.Black);
+        }
+        CreateLogo("StoreLogo.png", Color.White);
+        CreateLogo("StoreLogo.contrast-high.png", Color.Yellow);
+        var manifest = CreateManifest("#0063B1", """<uap:DefaultTile ShortName="Notes" />""");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "Notes" }, report.Applications.ToArray());
+        Assert.HasCount(0, report.Issues, string.Join(Environment.NewLine, report.Issues));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsContrastNameAndMissingVariants()
+    {
+        // Arrange
+        CreateLogo("Square150x150Logo.png", Color.FromArgb(0xEE, 0xEE, 0xEE));
+        CreateLogo("Square44x44Logo.png", Color.White);
+        CreateLogo("StoreLogo.png", Color.White);
+        var manifest = CreateManifest("#FFFFFF", """
+            <uap:DefaultTile ShortName="Contoso Notes for Teams">
+              <uap:ShowNameOnTiles><uap:ShowOn Tile="square150x150Logo" /></uap:ShowNameOnTiles>
+            </uap:DefaultTile>
+            """);
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        var checks = report.Issues.Select(i => $"{i.Severity} {i.Check} {i.Subject}").ToList();
+        CollectionAssert.Contains(checks, "Warning contrast Square150x150Logo");
+        CollectionAssert.Contains(checks, "Warning contrast BackgroundColor");
+        CollectionAssert.Contains(checks, "Warning short-name ShortName");
+        CollectionAssert.Contains(checks, "Warning unplated Square44x44Logo");
+        CollectionAssert.Contains(checks, "Warning high-contrast Square44x44Logo");
+        CollectionAssert.Contains(checks, "Info high-contrast Logo");
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ChecksUnplatedIconOnBothTaskbarThemes()
+    {
+        // Arrange
+        CreateLogo("Square150x150Logo.png", Color.White);
+        CreateLogo("Square44x44Logo.png", Color.White);
+        CreateLogo("Square44x44Logo.targetsize-24_altform-unplated.png", Color.White);
+        var manifest = CreateManifest("transparent", "");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        var unplated = report.Issues.Where(i => i.Check is "unplated" or "contrast" && i.Subject == "Square44x44Logo").ToList();
+        Assert.HasCount(2, unplated);
+        Assert.Contains("16, 32, 48, 256", unplated[0].Message);
+        Assert.Contains("light taskbar", unplated[1].Message);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsMissingAssetsAsErrors()
+    {
+        // Arrange
+        CreateLogo("Square44x44Logo.scale-200.png", Color.White);
+        var manifest = CreateManifest("transparent", "");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        var errors = report.Issues.Where(i => i.Severity == AccessibilitySeverity.Error).Select(i => i.Subject).ToArray();
+        CollectionAssert.AreEquivalent(new[] { "Square150x150Logo", "Logo" }, errors);
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class AffectedTests : BaseCommandTests
+{
+    public AffectedTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static readonly List<ProjectConfig> Projects =
+    [
+        new() { Name = "Editor", Path = "apps/editor", Inputs = ["libs/shared"] },
+        new() { Name = "Viewer", Path = "apps/viewer" },
+        new() { Name = "Viewer.Plugins", Path = "apps/viewer/plugins" },
+    ];
+
+    [TestMethod]
+    public void Match_MapsFilesToTheMostSpecificProjectAndItsInputs()
+    {
+        // Act
+        var affected = AffectedService.Match(Projects, ["apps/viewer/plugins/Pdf.cs", "libs/shared/Theme.cs", "docs/README.md"]);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "Editor", "Viewer.Plugins" }, affected.Select(p => p.Name).ToArray());
+        CollectionAssert.AreEqual(new[] { "libs/shared/Theme.cs" }, affected[0].Files.ToArray());
+    }
+
+    [TestMethod]
+    public void Match_ConfigChangeAffectsEveryProject()
+    {
+        // Act
+        var affected = AffectedService.Match(Projects, ["winapp.yaml"]);
+
+        // Assert
+        Assert.HasCount(3, affected);
+    }
+
+    [TestMethod]
+    public void Match_DoesNotMatchFoldersSharingAPrefix()
+    {
+        Assert.HasCount(0, AffectedService.Match(Projects, ["apps/editor-legacy/App.cs"]));
+    }
+
+    [TestMethod]
+    public void DiscoverProjects_UsesManifestFolders()
+    {
+        // Act
+        var projects = AffectedService.DiscoverProjects(["apps/editor/Package.appxmanifest", "apps/viewer/appxmanifest.xml", "appxmanifest.xml"]);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "", "apps/editor", "apps/viewer" }, projects.Select(p => p.Path).ToArray());
+        CollectionAssert.AreEqual(new[] { ".", "editor", "viewer" }, projects.Select(p => p.Name).ToArray());
+    }
+
+    [TestMethod]
+    public void ConfigService_RoundTripsProjects()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig { Projects = Projects };
+        config.SetVersion("Microsoft.WindowsAppSDK", "1.8.0");
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual("1.8.0", loaded.GetVersion("Microsoft.WindowsAppSDK"));
+        Assert.HasCount(3, loaded.Projects);
+        Assert.AreEqual("apps/editor", loaded.Projects[0].Path);
+        CollectionAssert.AreEqual(new[] { "libs/shared" }, loaded.Projects[0].Inputs);
+        Assert.HasCount(0, loaded.Projects[1].Inputs);
+    }
+
+    [TestMethod]
+    public async Task FindAsync_ComparesWithTheMergeBase()
+    {
+        // Arrange
+        var git = GetRequiredService<IGitService>();
+        await GitAsync(git, "init", "--quiet", "--initial-branch=main");
+        WriteFile("apps/editor/Package.appxmanifest");
+        WriteFile("apps/viewer/Package.appxmanifest");
+        await CommitAllAsync(git, "initial");
+        await GitAsync(git, "checkout", "--quiet", "-b", "feature");
+        WriteFile("apps/viewer/MainWindow.cs");
+        await CommitAllAsync(git, "change viewer");
+        await GitAsync(git, "checkout", "--quiet", "main");
+        WriteFile("apps/editor/MainWindow.cs");
+        await CommitAllAsync(git, "change editor on main");
+        await GitAsync(git, "checkout", "--quiet", "feature");
+        WriteFile("apps/editor/Draft.cs");
+
+        var affectedService = GetRequiredService<IAffectedService>();
+
+        // Act
+        var committed = await affectedService.FindAsync("main", includeUncommitted: false, TestTaskContext, TestContext.CancellationToken);
+        var withWorkingTree = await affectedService.FindAsync("main", includeUncommitted: true, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(committed.Discovered);
+        CollectionAssert.AreEqual(new[] { "apps/viewer" }, committed.Affected.Select(p => p.Path).ToArray());
+        CollectionAssert.AreEqual(new[] { "apps/editor", "apps/viewer" }, withWorkingTree.Affected.Select(p => p.Path).ToArray());
+    }
+
+    private void WriteFile(string path)
+    {
+        var file = new FileInfo(Path.Combine(_tempDirectory.FullName, path));
+        file.Directory!.Create();
+        File.WriteAllText(file.FullName, path);
+    }
+
+    private Task GitAsync(IGitService git, params string[] arguments) =>
+        git.RunAsync(_tempDirectory.FullName, arguments, TestContext.CancellationToken);
+
+    private async Task CommitAllAsync(IGitService git, string subject)
+    {
+        await GitAsync(git, "add", "-A");
+        await GitAsync(git, "-c", "user.name=Test", "-c", "user.email=test@example.com", "commit", "--quiet", "-m", subject);
+    }
+}
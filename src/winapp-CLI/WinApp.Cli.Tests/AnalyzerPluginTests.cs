@@ -0,0 +1,130 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class AnalyzerPluginTests : BaseCommandTests
+{
+    public AnalyzerPluginTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateManifest()
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Fabrikam" Version="1.0.0.0" />
+              <Properties>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    [TestMethod]
+    public void ParseDiagnostics_PrefixesRulesWithTheAnalyzerId()
+    {
+        // Arrange
+        var output = """
+            { "diagnostics": [
+              { "rule": "publisher", "severity": "error", "element": "Package/Identity", "subject": "Publisher", "message": "Not Contoso" },
+              { "rule": "contoso/logo", "message": "Use the 2024 logo" }
+            ] }
+            """;
+
+        // Act
+        var diagnostics = AnalyzerPluginService.ParseDiagnostics("contoso", output);
+
+        // Assert
+        Assert.HasCount(2, diagnostics);
+        Assert.AreEqual(new AnalyzerDiagnostic(AccessibilitySeverity.Error, "contoso/publisher", "Package/Identity", "Publisher", "Not Contoso"), diagnostics[0]);
+        Assert.AreEqual("contoso/logo", diagnostics[1].Rule);
+        Assert.AreEqual(AccessibilitySeverity.Warning, diagnostics[1].Severity, "Warning is the default");
+        Assert.HasCount(0, AnalyzerPluginService.ParseDiagnostics("contoso", ""));
+        Assert.ThrowsExactly<InvalidOperationException>(() => AnalyzerPluginService.ParseDiagnostics("contoso", """{ "diagnostics": [ { "rule": "x", "severity": "fatal", "message": "m" } ] }"""));
+        Assert.ThrowsExactly<InvalidOperationException>(() => AnalyzerPluginService.ParseDiagnostics("contoso", "Publisher is wrong"));
+    }
+
+    [TestMethod]
+    public void BuildInput_ListsElementsAndPayloadFiles()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Assets"));
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "Assets", "StoreLogo.scale-100.png"), [1, 2, 3]);
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "bin"));
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "bin", "Notes.exe"), [0]);
+
+        // Act
+        var input = AnalyzerPluginService.BuildInput("contoso", manifest, new FileInfo(Path.Combine(_tempDirectory.FullName, "winapp.yaml")), "");
+
+        // Assert
+        var identity = input["manifest"]!["elements"]!.AsArray().First(e => e!["name"]!.GetValue<string>() == "Identity")!;
+        Assert.AreEqual("Package/Identity", identity["path"]!.GetValue<string>());
+        Assert.AreEqual("CN=Fabrikam", identity["attributes"]!["Publisher"]!.GetValue<string>());
+        Assert.AreEqual(3, identity["line"]!.GetValue<int>());
+        var payload = input["payload"]!.AsArray().Select(f => f!["path"]!.GetValue<string>()).ToList();
+        Assert.Contains("Assets/StoreLogo.scale-100.png", payload);
+        Assert.DoesNotContain("bin/Notes.exe", payload);
+    }
+
+    [TestMethod]
+    public async Task ValidateAsync_ReportsAnalyzerDiagnosticsWithRuleOverrides()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "publisher.ps1"), """
+            $context = [Console]::In.ReadToEnd() | ConvertFrom-Json
+            $identity = $context.manifest.elements | Where-Object { $_.name -eq 'Identity' }
+            if ($identity.attributes.Publisher -ne 'CN=Contoso') {
+                @{ diagnostics = @(@{ rule = 'publisher'; element = $identity.path; subject = 'Publisher'; message = 'Sign with the Contoso certificate' }) } | ConvertTo-Json -Depth 5
+            }
+            """);
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Analyzers.Add(new AnalyzerConfig { Id = "contoso", Script = "publisher.ps1" });
+        config.Rules["contoso/publisher"] = "error";
+        configService.Save(config);
+
+        // Act
+        var service = GetRequiredService<IValidationService>();
+        var findings = await service.ValidateAsync(manifest, service.DefaultBaselinePath, allowScriptAnalyzers: true, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("publisher.ps1", configService.Load().Analyzers[0].Script);
+        var finding = findings.Single(f => f.Rule == "contoso/publisher");
+        Assert.AreEqual(AccessibilitySeverity.Error, finding.Severity);
+        Assert.AreEqual("Package/Identity", finding.Element);
+        Assert.AreEqual("Sign with the Contoso certificate", finding.Message);
+    }
+
+    [TestMethod]
+    public async Task ValidateAsync_SkipsScriptAnalyzersUnlessAllowed()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "publisher.ps1"), "exit 1");
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Analyzers.Add(new AnalyzerConfig { Id = "contoso", Script = "publisher.ps1" });
+        configService.Save(config);
+
+        // Act
+        var service = GetRequiredService<IValidationService>();
+        var findings = await service.ValidateAsync(manifest, service.DefaultBaselinePath, allowScriptAnalyzers: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsFalse(findings.Any(f => f.Rule.StartsWith("contoso/", StringComparison.Ordinal)), "The failing script isn't run");
+        Assert.IsTrue(AnalyzerPluginService.IsScript(new FileInfo("publisher.ps1")));
+        Assert.IsTrue(AnalyzerPluginService.IsScript(new FileInfo("publisher.exe")));
+        Assert.IsFalse(AnalyzerPluginService.IsScript(new FileInfo("publisher.WASM")));
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class AppStateTests : BaseCommandTests
+{
+    private const string FamilyName = "Contoso.Notes_8wekyb3d8bbwe";
+
+    private DirectoryInfo _appData = null!;
+
+    public AppStateTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestInitialize]
+    public void Setup()
+    {
+        _appData = _tempDirectory.CreateSubdirectory(FamilyName);
+        WriteState("LocalState", "notes.db", "three notes");
+        WriteState(Path.Combine("LocalCache", "Roaming", "Contoso"), "config.json", "{\"signedIn\":true}");
+        WriteState("Settings", "settings.dat", "hive");
+        WriteState(Path.Combine("SystemAppData", "Helium"), "User.dat", "virtual HKCU");
+        WriteState("TempState", "upload.tmp", "partial");
+        WriteState("AC", "container.dat", "app container");
+    }
+
+    private void WriteState(string folder, string file, string contents)
+    {
+        var directory = Directory.CreateDirectory(Path.Combine(_appData.FullName, folder));
+        File.WriteAllText(Path.Combine(directory.FullName, file), contents);
+    }
+
+    private string ReadState(params string[] path) => File.ReadAllText(Path.Combine([_appData.FullName, .. path]));
+
+    [TestMethod]
+    public void ClearState_DeletesTheFilesButKeepsTheFoldersAndTheAppContainer()
+    {
+        // Act
+        var count = AppStateService.ClearState(_appData);
+
+        // Assert
+        Assert.AreEqual(5, count);
+        Assert.IsTrue(Directory.Exists(Path.Combine(_appData.FullName, "LocalState")));
+        Assert.IsEmpty(Directory.GetFileSystemEntries(Path.Combine(_appData.FullName, "LocalCache")));
+        Assert.IsEmpty(Directory.GetFiles(Path.Combine(_appData.FullName, "Settings")));
+        Assert.IsEmpty(Directory.GetFiles(Path.Combine(_appData.FullName, "TempState")));
+        Assert.AreEqual("app container", ReadState("AC", "container.dat"));
+    }
+
+    [TestMethod]
+    public void RestoreSnapshot_ReturnsTheStateToTheSnapshot()
+    {
+        // Arrange
+        var file = new FileInfo(Path.Combine(_tempDirectory.FullName, "snapshots", "signed-in.zip"));
+        var saved = AppStateService.WriteSnapshot(_appData, file, FamilyName, "1.2.0.0");
+        WriteState("LocalState", "notes.db", "five notes");
+        WriteState("LocalState", "draft.txt", "unsaved");
+        File.Delete(Path.Combine(_appData.FullName, "Settings", "settings.dat"));
+
+        // Act
+        var restored = AppStateService.RestoreSnapshot(file, _appData);
+
+        // Assert
+        Assert.AreEqual(4, saved.FileCount, "TempState and AC are not saved");
+        Assert.AreEqual(FamilyName, restored.PackageFamilyName);
+        Assert.AreEqual("1.2.0.0", restored.Version);
+        Assert.AreEqual("three notes", ReadState("LocalState", "notes.db"));
+        Assert.IsFalse(File.Exists(Path.Combine(_appData.FullName, "LocalState", "draft.txt")));
+        Assert.AreEqual("hive", ReadState("Settings", "settings.dat"));
+        Assert.AreEqual("virtual HKCU", ReadState("SystemAppData", "Helium", "User.dat"));
+        Assert.AreEqual("{\"signedIn\":true}", ReadState("LocalCache", "Roaming", "Contoso", "config.json"));
+        Assert.IsEmpty(Directory.GetFiles(Path.Combine(_appData.FullName, "TempState")));
+    }
+
+    [TestMethod]
+    public void RestoreSnapshot_RejectsFilesOutsideTheStateFolders()
+    {
+        // Arrange
+        var file = new FileInfo(Path.Combine(_tempDirectory.FullName, "evil.zip"));
+        AppStateService.WriteSnapshot(_appData, file, FamilyName, "1.2.0.0");
+        using (var zip = ZipFile.Open(file.FullName, ZipArchiveMode.Update))
+        {
+            using var writer = new StreamWriter(zip.CreateEntry("LocalState/../../outside.txt").Open());
+            writer.Write("escaped");
+        }
+
+        // Act & Assert
+        var exception = Assert.ThrowsExactly<InvalidOperationException>(() => AppStateService.RestoreSnapshot(file, _appData));
+        StringAssert.Contains(exception.Message, "outside the app's state folders");
+        Assert.AreEqual("three notes", ReadState("LocalState", "notes.db"), "Nothing is cleared when the snapshot is rejected");
+        Assert.IsFalse(File.Exists(Path.Combine(_tempDirectory.FullName, "outside.txt")));
+    }
+
+    [TestMethod]
+    public void GetSnapshotFile_KeepsNamedSnapshotsInTheWinappFolder()
+    {
+        // Arrange
+        var appStateService = GetRequiredService<IAppStateService>();
+
+        // Act
+        var named = appStateService.GetSnapshotFile("signed-in");
+        var path = appStateService.GetSnapshotFile(Path.Combine("fixtures", "signed-in.zip"));
+
+        // Assert
+        Assert.AreEqual(Path.Combine(_testWinappDirectory.FullName, "snapshots", "signed-in.zip"), named.FullName);
+        Assert.AreEqual(Path.Combine(_tempDirectory.FullName, "fixtures", "signed-in.zip"), path.FullName);
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class Arm64Tests : BaseCommandTests
+{
+    private static readonly HashSet<string> NoImports = [];
+
+    public Arm64Tests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static Arm64AuditService.Arm64Context Context(string architecture = "x64", bool isRust = false, bool hasEmulatedExecutable = true, string[]? shellExtensions = null) =>
+        new(architecture, (shellExtensions ?? []).ToHashSet(StringComparer.OrdinalIgnoreCase), isRust, hasEmulatedExecutable, HasEmulatedDriver: false);
+
+    [TestMethod]
+    public void Findings_ReportsDriversAndShellExtensions()
+    {
+        var driver = new PeImage(ImageArchitecture.X64, IsDll: false, IsDriver: true, IsManaged: false);
+        var extension = new PeImage(ImageArchitecture.Arm64EC, IsDll: true, IsDriver: false, IsManaged: false);
+        var arm64X = new PeImage(ImageArchitecture.Arm64, IsDll: true, IsDriver: false, IsManaged: false);
+        var context = Context(shellExtensions: ["ContextMenu.dll"]);
+
+        var driverIssue = Arm64AuditService.Findings("contoso.sys", driver, NoImports, context, false, false).Single();
+        var extensionIssue = Arm64AuditService.Findings("ContextMenu.dll", extension, NoImports, context, false, false).Single();
+
+        Assert.AreEqual("driver", driverIssue.Check);
+        Assert.AreEqual(AccessibilitySeverity.Error, driverIssue.Severity);
+        Assert.AreEqual("shell-extension", extensionIssue.Check);
+        Assert.Contains("ARM64X", extensionIssue.Message);
+        Assert.IsFalse(Arm64AuditService.Findings("ContextMenu.dll", arm64X, NoImports, context, false, false).Any());
+    }
+
+    [TestMethod]
+    public void Findings_ReportsDriverClientsMixedAndEmulatedBinaries()
+    {
+        var exe = new PeImage(ImageArchitecture.X64, IsDll: false, IsDriver: false, IsManaged: false);
+        var dll = new PeImage(ImageArchitecture.X64, IsDll: true, IsDriver: false, IsManaged: false);
+
+        var client = Arm64AuditService.Findings("tool.exe", exe, new HashSet<string> { "kernel32.dll!DeviceIoControl", "user32.dll!MessageBoxW" }, Context(), false, false).Single();
+        var mixed = Arm64AuditService.Findings("plugin.dll", dll, NoImports, Context("arm64", hasEmulatedExecutable: false), false, false).Single();
+        var emulated = Arm64AuditService.Findings("plugin.dll", dll, NoImports, Context(), false, false).Single();
+
+        Assert.AreEqual("kernel-adjacent", client.Check);
+        Assert.Contains("DeviceIoControl", client.Message);
+        Assert.DoesNotContain("MessageBoxW", client.Message);
+        Assert.AreEqual("mixed", mixed.Check);
+        Assert.AreEqual("emulated", emulated.Check);
+        Assert.AreEqual(AccessibilitySeverity.Info, emulated.Severity);
+    }
+
+    [TestMethod]
+    public void Suggestion_MatchesTheToolchain()
+    {
+        var native = new PeImage(ImageArchitecture.X64, IsDll: false, IsDriver: false, IsManaged: false);
+        var managed = new PeImage(ImageArchitecture.X64, IsDll: true, IsDriver: false, IsManaged: true);
+
+        Assert.Contains("AnyCPU", Arm64AuditService.Suggestion("Contoso.Core.dll", managed, Context(), false, false));
+        Assert.Contains("npm rebuild --arch=arm64", Arm64AuditService.Suggestion("build/Release/addon.node", native, Context(), false, false));
+        Assert.Contains("dotnet publish -r win-arm64", Arm64AuditService.Suggestion("Contoso.exe", native, Context(), true, false));
+        Assert.Contains("electron-builder --arm64", Arm64AuditService.Suggestion("Contoso.exe", native, Context(), false, true));
+        Assert.Contains("aarch64-pc-windows-msvc", Arm64AuditService.Suggestion("contoso.exe", native, Context(isRust: true), false, false));
+        Assert.Contains("ARM64EC", Arm64AuditService.Suggestion("contoso.exe", native, Context(), false, false));
+    }
+
+    [TestMethod]
+    public void FindShellExtensions_MapsHandlerClsidsToComServerDlls()
+    {
+        var root = XElement.Parse("""
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:com="http://schemas.microsoft.com/appx/manifest/com/windows10"
+                     xmlns:desktop4="http://schemas.microsoft.com/appx/manifest/desktop/windows10/4">
+              <Applications>
+                <Application Id="App">
+                  <Extensions>
+                    <desktop4:Extension Category="windows.fileExplorerContextMenus">
+                      <desktop4:FileExplorerContextMenus>
+                        <desktop4:ItemType Type=".txt">
+                          <desktop4:Verb Id="Open" Clsid="11111111-1111-1111-1111-111111111111" />
+                        </desktop4:ItemType>
+                      </desktop4:FileExplorerContextMenus>
+                    </desktop4:Extension>
+                    <com:Extension Category="windows.comServer">
+                      <com:ComServer>
+                        <com:SurrogateServer DisplayName="Context menu">
+                          <com:Class Id="11111111-1111-1111-1111-111111111111" Path="Shell/ContextMenu.dll" ThreadingModel="STA" />
+                        </com:SurrogateServer>
+                        <com:SurrogateServer DisplayName="Other">
+                          <com:Class Id="22222222-2222-2222-2222-222222222222" Path="Other.dll" ThreadingModel="STA" />
+                        </com:SurrogateServer>
+                      </com:ComServer>
+                    </com:Extension>
+                  </Extensions>
+                </Application>
+              </Applications>
+            </Package>
+            """);
+
+        var extensions = Arm64AuditService.FindShellExtensions(root);
+
+        Assert.HasCount(1, extensions);
+        Assert.Contains(Path.Combine("Shell", "ContextMenu.dll"), extensions);
+    }
+
+    [TestMethod]
+    public void PeArchitecture_ReturnsNullForFilesThatArentImages()
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "fake.dll");
+        File.WriteAllText(path, "not a PE image");
+
+        Assert.IsNull(PeArchitecture.Read(path));
+    }
+}
@@ -67,6 +67,11 @@ public abstract class BaseCommandTests(bool configPaths = true, bool verboseLogg
         RenderLock = new Lock();
         TestTaskContext = new TaskContext(dummyTask, null, TestAnsiConsole, GetRequiredService<ILogger<TaskContext>>(), RenderLock);
 
+        // Isolate tests from the machine and user configuration of the machine running them
+        var settingsService = GetRequiredService<ISettingsService>();
+        settingsService.MachineConfigPath = new FileInfo(Path.Combine(_tempDirectory.FullName, "machine", "config.yaml"));
+        settingsService.UserConfigPath = new FileInfo(Path.Combine(_tempDirectory.FullName, "user", "config.yaml"));
+
         // Set up services with test cache directory
         if (configPaths)
         {
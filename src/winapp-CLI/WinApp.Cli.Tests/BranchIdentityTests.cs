@@ -0,0 +1,111 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class BranchIdentityTests : BaseCommandTests
+{
+    public BranchIdentityTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private const string Manifest = """
+        <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+          <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.2.0.0" />
+          <Properties>
+            <DisplayName>Contoso Notes &amp; Tasks</DisplayName>
+            <PublisherDisplayName>Contoso</PublisherDisplayName>
+          </Properties>
+          <Applications>
+            <Application Id="App" Executable="Notes.exe">
+              <uap:VisualElements DisplayName="Notes" Description="Notes" />
+            </Application>
+            <Application Id="Settings" Executable="Settings.exe">
+              <uap:VisualElements DisplayName="ms-resource:SettingsName" Description="Settings" />
+            </Application>
+          </Applications>
+        </Package>
+        """;
+
+    [TestMethod]
+    public void BranchIdentity_IsDeterministicPerBranch()
+    {
+        // Act
+        var first = new BranchIdentity("feature/dark-mode");
+        var second = new BranchIdentity("feature/dark-mode");
+        var other = new BranchIdentity("feature/sync");
+
+        // Assert
+        Assert.AreEqual(first.NameSuffix, second.NameSuffix);
+        Assert.AreNotEqual(first.NameSuffix, other.NameSuffix);
+        Assert.AreEqual($".br{first.Hash}", first.NameSuffix);
+        Assert.AreEqual($"CN=WinApp Branch {first.Hash}", first.Publisher);
+    }
+
+    [TestMethod]
+    public void ApplyToName_StaysWithinTheIdentityNameLimit()
+    {
+        // Arrange
+        var identity = new BranchIdentity("main");
+
+        // Act
+        var name = identity.ApplyToName(new string('A', 50));
+
+        // Assert
+        Assert.AreEqual(50, name.Length);
+        Assert.EndsWith(identity.NameSuffix, name);
+    }
+
+    [TestMethod]
+    public void ApplyToManifest_RenamesIdentityAndDisplayNames()
+    {
+        // Arrange
+        var identity = new BranchIdentity("fix/<crash>");
+
+        // Act
+        var manifest = identity.ApplyToManifest(Manifest);
+
+        // Assert
+        Assert.Contains($"Name=\"Contoso.Notes{identity.NameSuffix}\"", manifest);
+        Assert.Contains($"Publisher=\"{identity.Publisher}\"", manifest);
+        Assert.Contains("<DisplayName>Contoso Notes &amp; Tasks (fix/&lt;crash&gt;)</DisplayName>", manifest);
+        Assert.Contains("DisplayName=\"Notes (fix/&lt;crash&gt;)\"", manifest);
+        Assert.Contains("DisplayName=\"ms-resource:SettingsName\"", manifest);
+        Assert.Contains("<PublisherDisplayName>Contoso</PublisherDisplayName>", manifest);
+    }
+
+    [TestMethod]
+    public async Task ResolveAsync_UsesTheGivenBranch()
+    {
+        // Arrange
+        var branchIdentityService = GetRequiredService<IBranchIdentityService>();
+
+        // Act
+        var identity = await branchIdentityService.ResolveAsync("refs/heads/feature/dark-mode", TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("feature/dark-mode", identity.Branch);
+        Assert.AreEqual(new BranchIdentity("feature/dark-mode").NameSuffix, identity.NameSuffix);
+    }
+
+    [TestMethod]
+    public async Task ResolveAsync_ReadsTheCheckedOutBranch()
+    {
+        // Arrange
+        var git = GetRequiredService<IGitService>();
+        await git.RunAsync(_tempDirectory.FullName, ["init", "--quiet", "--initial-branch=feature/sync"], TestContext.CancellationToken);
+        await git.RunAsync(_tempDirectory.FullName, ["-c", "user.name=Test", "-c", "user.email=test@example.com", "commit", "--quiet", "--allow-empty", "-m", "initial"], TestContext.CancellationToken);
+        var branchIdentityService = GetRequiredService<IBranchIdentityService>();
+
+        // Act
+        var identity = await branchIdentityService.ResolveAsync(null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("feature/sync", identity.Branch);
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Build;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class BuildAdapterTests : BaseCommandTests
+{
+    public BuildAdapterTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private BuildContext Context(DirectoryInfo project, BuildConfig? config = null, string architecture = "x64") =>
+        BuildService.CreateContext(project, config ?? new BuildConfig(), architecture, new DirectoryInfo(Path.Combine(_tempDirectory.FullName, ".winapp")));
+
+    private DirectoryInfo Project(string name, params string[] files)
+    {
+        var project = _tempDirectory.CreateSubdirectory(name);
+        foreach (var file in files)
+        {
+            var path = Path.Combine(project.FullName, file);
+            Directory.CreateDirectory(Path.GetDirectoryName(path)!);
+            File.WriteAllText(path, file.EndsWith(".sln", StringComparison.Ordinal) ? "Project(\"{8BC9CEB8}\") = \"App\", \"App\\App.vcxproj\", \"{1}\"" : "");
+        }
+        return project;
+    }
+
+    [TestMethod]
+    public void Resolve_DetectsTheBuildSystemOfTheProjectFiles()
+    {
+        Assert.AreEqual(BuildSystem.Tauri, BuildService.Resolve(Context(Project("tauri", "package.json", @"src-tauri/Cargo.toml", @"src-tauri/tauri.conf.json"))).System);
+        Assert.AreEqual(BuildSystem.Cargo, BuildService.Resolve(Context(Project("cargo", "Cargo.toml"))).System);
+        Assert.AreEqual(BuildSystem.Dotnet, BuildService.Resolve(Context(Project("dotnet", "App.csproj"))).System);
+        Assert.AreEqual(BuildSystem.MSBuild, BuildService.Resolve(Context(Project("cpp", "App.sln"))).System);
+        Assert.AreEqual(BuildSystem.CMake, BuildService.Resolve(Context(Project("cmake", "CMakeLists.txt"))).System);
+        Assert.AreEqual(BuildSystem.Npm, BuildService.Resolve(Context(Project("electron", "package.json"))).System);
+        Assert.AreEqual(BuildSystem.Custom, BuildService.Resolve(Context(Project("custom", "package.json"), new BuildConfig { System = BuildSystem.Custom })).System);
+        Assert.ThrowsExactly<InvalidOperationException>(() => BuildService.Resolve(Context(Project("empty"))));
+    }
+
+    [TestMethod]
+    public void GetSteps_TargetsTheArchitectureOfTheManifest()
+    {
+        var cargo = new CargoBuildAdapter().GetSteps(Context(Project("cargo", "Cargo.toml"), architecture: "arm64")).Single();
+        var debug = new CargoBuildAdapter().GetSteps(Context(Project("cargo-debug", "Cargo.toml"), new BuildConfig { Configuration = "Debug" }, "neutral")).Single();
+        var dotnet = new DotnetBuildAdapter().GetSteps(Context(Project("dotnet", "App.csproj"), new BuildConfig { Arguments = "-p:Version=1.2.3" })).Single();
+        var cmake = new CMakeBuildAdapter().GetSteps(Context(Project("cmake", "CMakeLists.txt"), architecture: "x86"));
+
+        Assert.AreEqual("build --release --target aarch64-pc-windows-msvc", cargo.Arguments);
+        Assert.AreEqual("build", debug.Arguments);
+        Assert.Contains("-c Release -r win-x64", dotnet.Arguments);
+        Assert.EndsWith(" -p:Version=1.2.3", dotnet.Arguments);
+        Assert.HasCount(2, cmake);
+        Assert.Contains("-A Win32", cmake[0].Arguments);
+        Assert.Contains("--config Release", cmake[1].Arguments);
+    }
+
+    [TestMethod]
+    public void LocateOutputs_FindsTheBinariesOfTheBuild()
+    {
+        var cargo = Project("cargo", "Cargo.toml", @"target/x86_64-pc-windows-msvc/release/app.exe", @"target/x86_64-pc-windows-msvc/release/app.pdb", @"target/x86_64-pc-windows-msvc/release/deps/app.d");
+        var electron = Project("electron", "package.json", @"dist/win-arm64-unpacked/App.exe", @"dist/win-arm64-unpacked/resources/app.asar");
+
+        var cargoOutputs = new CargoBuildAdapter().LocateOutputs(Context(cargo));
+        var electronOutputs = new NpmBuildAdapter().LocateOutputs(Context(electron, architecture: "arm64"));
+
+        CollectionAssert.AreEqual(new[] { "app.exe" }, cargoOutputs.Select(o => o.PackagePath).ToArray());
+        CollectionAssert.AreEquivalent(new[] { "App.exe", Path.Combine("resources", "app.asar") }, electronOutputs.Select(o => o.PackagePath).ToArray());
+        Assert.ThrowsExactly<InvalidOperationException>(() => new CustomBuildAdapter().LocateOutputs(Context(cargo)));
+    }
+
+    [TestMethod]
+    public void Stage_CopiesTheOutputsToTheirPackagePaths()
+    {
+        var project = Project("custom", @"out/bin/app.exe");
+        var config = new BuildConfig { System = BuildSystem.Custom, Command = "make dist", Output = "out" };
+        var payload = new DirectoryInfo(Path.Combine(_tempDirectory.FullName, "payload"));
+
+        BuildService.Stage(new CustomBuildAdapter().LocateOutputs(Context(project, config)), payload);
+
+        Assert.IsTrue(File.Exists(Path.Combine(payload.FullName, "bin", "app.exe")));
+    }
+
+    [TestMethod]
+    public void ConfigService_RoundTripsTheBuildSection()
+    {
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(new WinappConfig
+        {
+            Build = new BuildConfig { System = BuildSystem.Tauri, Project = "src-tauri", Configuration = "Debug", Arguments = "--features devtools" },
+        });
+
+        var build = configService.Load().Build;
+
+        Assert.IsNotNull(build);
+        Assert.AreEqual(BuildSystem.Tauri, build.System);
+        Assert.AreEqual("src-tauri", build.Project);
+        Assert.IsTrue(build.IsDebug);
+        Assert.AreEqual("--features devtools", build.Arguments);
+        Assert.IsNull(build.Output);
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using WinApp.Cli.BuildCache;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class BuildCacheTests : BaseCommandTests
+{
+    private static readonly Dictionary<string, string> Configuration = new()
+    {
+        ["engine"] = "Sdk",
+        ["digest"] = "Sha256",
+    };
+
+    [TestMethod]
+    public async Task ComputeAsync_DependsOnContentNotLocation()
+    {
+        // Arrange
+        var first = CreatePayload("first");
+        var second = CreatePayload("second");
+
+        // Act
+        var firstKey = await PackCacheKey.ComputeAsync(first, Configuration, null, TestContext.CancellationToken);
+        var secondKey = await PackCacheKey.ComputeAsync(second, Configuration, null, TestContext.CancellationToken);
+        await File.WriteAllTextAsync(Path.Combine(second.FullName, "Assets", "Logo.png"), "new logo", TestContext.CancellationToken);
+        var changedKey = await PackCacheKey.ComputeAsync(second, Configuration, null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(64, firstKey.Length);
+        Assert.AreEqual(firstKey, secondKey);
+        Assert.AreNotEqual(firstKey, changedKey);
+    }
+
+    [TestMethod]
+    public async Task ComputeAsync_DependsOnConfiguration()
+    {
+        // Arrange
+        var payload = CreatePayload("payload");
+        var sha384 = new Dictionary<string, string>(Configuration) { ["digest"] = "Sha384" };
+
+        // Act
+        var key = await PackCacheKey.ComputeAsync(payload, Configuration, null, TestContext.CancellationToken);
+        var sha384Key = await PackCacheKey.ComputeAsync(payload, sha384, null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreNotEqual(key, sha384Key);
+    }
+
+    [TestMethod]
+    public async Task ComputeAsync_LeavesOutTheExcludedFile()
+    {
+        // Arrange
+        var payload = CreatePayload("payload");
+        var key = await PackCacheKey.ComputeAsync(payload, Configuration, null, TestContext.CancellationToken);
+        var output = new FileInfo(Path.Combine(payload.FullName, "App.msix"));
+        await File.WriteAllTextAsync(output.FullName, "package", TestContext.CancellationToken);
+
+        // Act
+        var keyWithOutput = await PackCacheKey.ComputeAsync(payload, Configuration, output, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(key, keyWithOutput);
+    }
+
+    [TestMethod]
+    public async Task HttpBuildCache_DownloadsAndUploadsUnderTheKey()
+    {
+        // Arrange
+        var handler = new RecordingHandler(request => request.Method == HttpMethod.Get && request.RequestUri!.AbsolutePath.EndsWith("/missing.msix")
+            ? new HttpResponseMessage(HttpStatusCode.NotFound)
+            : new HttpResponseMessage(HttpStatusCode.OK) { Content = new StringContent("cached package") });
+        var cache = new HttpBuildCache(new Uri("https://cache.contoso.com/winapp"), "secret", new HttpClient(handler));
+        var destination = new FileInfo(Path.Combine(_tempDirectory.FullName, "out", "App.msix"));
+
+        // Act
+        var missing = await cache.TryDownloadAsync("missing", destination, null, TestContext.CancellationToken);
+        var found = await cache.TryDownloadAsync("abc123", destination, null, TestContext.CancellationToken);
+        await cache.UploadAsync("def456", destination, null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsFalse(missing);
+        Assert.IsTrue(found);
+        Assert.AreEqual("cached package", await File.ReadAllTextAsync(destination.FullName, TestContext.CancellationToken));
+        Assert.IsFalse(File.Exists(destination.FullName + ".download"));
+        Assert.HasCount(3, handler.Requests);
+        Assert.AreEqual("https://cache.contoso.com/winapp/msix/abc123.msix", handler.Requests[1].Uri.AbsoluteUri);
+        Assert.AreEqual(HttpMethod.Put, handler.Requests[2].Method);
+        Assert.AreEqual("https://cache.contoso.com/winapp/msix/def456.msix", handler.Requests[2].Uri.AbsoluteUri);
+        Assert.AreEqual("application/msix", handler.Requests[2].ContentType);
+        Assert.AreEqual("Bearer secret", handler.Requests[2].Authorization);
+    }
+
+    [TestMethod]
+    public void Create_PicksTheBackendFromTheLocation()
+    {
+        // Arrange
+        var buildCacheService = GetRequiredService<IBuildCacheService>();
+
+        // Act & Assert
+        Assert.IsInstanceOfType<AzureBlobBuildCache>(buildCacheService.Create("https://contoso.blob.core.windows.net/cache?sv=2021-08-06&sig=abc"));
+        Assert.IsInstanceOfType<HttpBuildCache>(buildCacheService.Create("https://cache.contoso.com/winapp/"));
+        Assert.ThrowsExactly<ArgumentException>(() => buildCacheService.Create("ftp://cache.contoso.com/"));
+    }
+
+    [TestMethod]
+    public async Task CreateMsixPackageAsync_RestoresCachedPackage()
+    {
+        // Arrange
+        var payload = CreatePayload("payload");
+        await File.WriteAllTextAsync(_configService.ConfigPath.FullName, "packages: []", TestContext.CancellationToken);
+        var cache = new MemoryBuildCache();
+        var msixService = GetRequiredService<IMsixService>();
+        var output = new FileInfo(Path.Combine(_tempDirectory.FullName, "out", "TestPackage.msix"));
+
+        // Act
+        var result = await msixService.CreateMsixPackageAsync(
+            inputFolder: payload,
+            outputPath: output,
+            TestTaskContext,
+            packageName: "TestPackage",
+            skipPri: true,
+            remoteCache: new RemoteBuildCacheOptions(cache, ReadOnly: true),
+            cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(result.FromCache);
+        Assert.IsFalse(result.Signed);
+        Assert.HasCount(1, cache.Downloads);
+        Assert.AreEqual("cached package", await File.ReadAllTextAsync(output.FullName, TestContext.CancellationToken));
+    }
+
+    [TestMethod]
+    public async Task CreateMsixPackageAsync_CachesStampedPackagesPerProvenanceAndRecordsPack()
+    {
+        // Arrange
+        var payload = CreatePayload("payload");
+        await File.WriteAllTextAsync(_configService.ConfigPath.FullName, "packages: []", TestContext.CancellationToken);
+        var cache = new MemoryBuildCache();
+        var msixService = GetRequiredService<IMsixService>();
+        var output = new FileInfo(Path.Combine(_tempDirectory.FullName, "out", "TestPackage.msix"));
+
+        // Act
+        foreach (var sha in new[] { "aaa111", "bbb222" })
+        {
+            await msixService.CreateMsixPackageAsync(
+                inputFolder: payload,
+                outputPath: output,
+                TestTaskContext,
+                packageName: "TestPackage",
+                skipPri: true,
+                remoteCache: new RemoteBuildCacheOptions(cache, ReadOnly: true),
+                provenance: new BuildProvenance { GitSha = sha, Profile = "Release", CliVersion = "1.0.0" },
+                cancellationToken: TestContext.CancellationToken);
+        }
+        var state = await GetRequiredService<IProjectStateService>().LoadAsync(TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(2, cache.Downloads);
+        Assert.AreNotEqual(cache.Downloads[0], cache.Downloads[1]);
+        Assert.IsFalse(File.Exists(Path.Combine(payload.FullName, BuildProvenance.FileName)));
+        Assert.IsNotNull(state.LastPack);
+        Assert.AreEqual(output.FullName, state.LastPack.Output);
+        Assert.IsTrue(state.LastPack.FromCache);
+        Assert.AreEqual("bbb222", state.LastPack.Provenance?.GitSha);
+        Assert.AreEqual(64, state.LastPack.Sha256.Length);
+    }
+
+    private DirectoryInfo CreatePayload(string name)
+    {
+        var payload = _tempDirectory.CreateSubdirectory(name);
+        payload.CreateSubdirectory("Assets");
+        File.WriteAllText(Path.Combine(payload.FullName, "Assets", "Logo.png"), "logo");
+        File.WriteAllText(Path.Combine(payload.FullName, "TestApp.exe"), "app");
+        File.WriteAllText(Path.Combine(payload.FullName, "appxmanifest.xml"), """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+              <Identity Name="TestPackage" Publisher="CN=TestPublisher" Version="1.0.0.0" />
+              <Applications>
+                <Application Id="TestApp" Executable="TestApp.exe" EntryPoint="Windows.FullTrustApplication" />
+              </Applications>
+            </Package>
+            """);
+        return payload;
+    }
+
+    private sealed class MemoryBuildCache : IRemoteBuildCache
+    {
+        public List<string> Downloads { get; } = [];
+
+        public string Name => "memory";
+
+        public async Task<bool> TryDownloadAsync(string key, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken)
+        {
+            Downloads.Add(key);
+            destination.Directory!.Create();
+            await File.WriteAllTextAsync(destination.FullName, "cached package", cancellationToken);
+            return true;
+        }
+
+        public Task UploadAsync(string key, FileInfo source, Action<string>? onRetry, CancellationToken cancellationToken) =>
+            throw new InvalidOperationException("The cache is read-only");
+    }
+
+    private sealed class RecordingHandler(Func<HttpRequestMessage, HttpResponseMessage> respond) : HttpMessageHandler
+    {
+        public List<(HttpMethod Method, Uri Uri, string? ContentType, string? Authorization)> Requests { get; } = [];
+
+        protected override Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+        {
+            Requests.Add((request.Method, request.RequestUri!, request.Content?.Headers.ContentType?.MediaType, request.Headers.Authorization?.ToString()));
+            return Task.FromResult(respond(request));
+        }
+    }
+}
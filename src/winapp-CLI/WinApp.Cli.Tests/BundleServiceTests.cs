@@ -0,0 +1,133 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class BundleServiceTests : BaseCommandTests
+{
+    private const string TestBundleManifest = @"<?xml version=""1.0"" encoding=""utf-8""?>
+<Bundle xmlns=""http://schemas.microsoft.com/appx/2013/bundle"" SchemaVersion=""5.0"">
+  <Identity Name=""TestPackage"" Publisher=""CN=TestPublisher"" Version=""1.0.0.0"" />
+  <Packages>
+    <Package Type=""application"" Version=""1.0.0.0"" Architecture=""x64"" FileName=""TestApp_1.0.0.0_x64.msix"" Offset=""0"" Size=""1"">
+      <Resources>
+        <Resource Language=""en-US"" />
+        <Resource Scale=""100"" />
+      </Resources>
+    </Package>
+    <Package Type=""application"" Version=""1.0.0.0"" Architecture=""arm64"" FileName=""TestApp_1.0.0.0_arm64.msix"" Offset=""0"" Size=""1"">
+      <Resources>
+        <Resource Language=""en-US"" />
+        <Resource Scale=""100"" />
+      </Resources>
+    </Package>
+    <Package Type=""resource"" Version=""1.0.0.0"" ResourceId=""split.language-fr"" FileName=""TestApp_1.0.0.0_language-fr.msix"" Offset=""0"" Size=""1"">
+      <Resources>
+        <Resource Language=""fr-FR"" />
+      </Resources>
+    </Package>
+    <Package Type=""resource"" Version=""1.0.0.0"" ResourceId=""split.scale-200"" FileName=""TestApp_1.0.0.0_scale-200.msix"" Offset=""0"" Size=""1"">
+      <Resources>
+        <Resource Scale=""200"" />
+      </Resources>
+    </Package>
+  </Packages>
+</Bundle>";
+
+    [TestMethod]
+    public void ParseBundleManifestShouldReturnAllPackages()
+    {
+        // Act
+        var packages = BundleService.ParseBundleManifest(TestBundleManifest);
+
+        // Assert
+        Assert.HasCount(4, packages);
+        Assert.AreEqual("x64", packages[0].Architecture);
+        Assert.IsTrue(packages[0].IsApplication);
+        Assert.AreEqual("split.language-fr", packages[2].ResourceId);
+        CollectionAssert.AreEqual(new[] { "fr-FR" }, packages[2].Languages.ToArray());
+        CollectionAssert.AreEqual(new[] { 200 }, packages[3].Scales.ToArray());
+    }
+
+    [TestMethod]
+    public void SelectPackagesShouldPickArchitectureAndMatchingLanguage()
+    {
+        // Arrange
+        var packages = BundleService.ParseBundleManifest(TestBundleManifest);
+
+        // Act
+        var (application, resources) = BundleService.SelectPackages(packages, "arm64", ["fr"], []);
+
+        // Assert
+        Assert.AreEqual("TestApp_1.0.0.0_arm64.msix", application.FileName);
+        Assert.HasCount(1, resources);
+        Assert.AreEqual("TestApp_1.0.0.0_language-fr.msix", resources[0].FileName);
+    }
+
+    [TestMethod]
+    public void SelectPackagesShouldSkipLanguagesAlreadyInApplicationPackage()
+    {
+        // Arrange
+        var packages = BundleService.ParseBundleManifest(TestBundleManifest);
+
+        // Act
+        var (_, resources) = BundleService.SelectPackages(packages, "x64", ["en-US"], [200]);
+
+        // Assert
+        Assert.HasCount(1, resources);
+        Assert.AreEqual("split.scale-200", resources[0].ResourceId);
+    }
+
+    [TestMethod]
+    public void SelectPackagesWithUnknownArchitectureShouldThrow()
+    {
+        // Arrange
+        var packages = BundleService.ParseBundleManifest(TestBundleManifest);
+
+        // Act & Assert
+        var ex = Assert.ThrowsExactly<InvalidOperationException>(() => BundleService.SelectPackages(packages, "x86", [], []));
+        Assert.Contains("x64", ex.Message, "Error should list the available architectures");
+    }
+
+    [TestMethod]
+    public void SelectPackagesWithUnknownLanguageShouldThrow()
+    {
+        // Arrange
+        var packages = BundleService.ParseBundleManifest(TestBundleManifest);
+
+        // Act & Assert
+        Assert.ThrowsExactly<InvalidOperationException>(() => BundleService.SelectPackages(packages, "x64", ["ja-JP"], []));
+    }
+
+    [TestMethod]
+    public void LanguageMatchesShouldTreatNeutralLanguagesAsMatching()
+    {
+        Assert.IsTrue(BundleService.LanguageMatches("en-US", "en"));
+        Assert.IsTrue(BundleService.LanguageMatches("en", "en-GB"));
+        Assert.IsTrue(BundleService.LanguageMatches("EN-us", "en-US"));
+        Assert.IsFalse(BundleService.LanguageMatches("en-US", "en-GB"));
+        Assert.IsFalse(BundleService.LanguageMatches("fr-FR", "en"));
+    }
+
+    [TestMethod]
+    public async Task ReadBundlePackagesShouldRejectNonBundleArchives()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "NotABundle.msix"));
+        using (var archive = ZipFile.Open(packagePath.FullName, ZipArchiveMode.Create))
+        {
+            var entry = archive.CreateEntry("AppxManifest.xml");
+            await using var writer = new StreamWriter(await entry.OpenAsync(TestContext.CancellationToken));
+            await writer.WriteAsync("<Package />");
+        }
+
+        var bundleService = GetRequiredService<IBundleService>();
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => bundleService.ReadBundlePackagesAsync(packagePath, TestContext.CancellationToken));
+    }
+}
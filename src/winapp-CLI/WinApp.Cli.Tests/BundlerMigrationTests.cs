@@ -0,0 +1,170 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class BundlerMigrationTests : BaseCommandTests
+{
+    public BundlerMigrationTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateElectronProject()
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "package.json");
+        File.WriteAllText(path, """
+            {
+              "name": "contoso-notes",
+              "version": "2.1.0-beta.3",
+              "description": "Notes for teams",
+              "author": { "name": "Contoso Ltd" },
+              "build": {
+                "appId": "com.contoso.notes",
+                "productName": "Contoso Notes",
+                "win": { "certificateFile": "certs/contoso.pfx" },
+                "appx": {
+                  "identityName": "Contoso.Notes",
+                  "publisher": "CN=Contoso, O=Contoso, C=US",
+                  "applicationId": "ContosoNotes",
+                  "backgroundColor": "#1E1E1E",
+                  "languages": ["en-US", "de-DE"],
+                  "capabilities": ["internetClient", "webcam", "picturesLibrary"],
+                  "addAutoLaunchExtension": true
+                }
+              }
+            }
+            """);
+
+        var appx = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "build", "appx"));
+        foreach (var name in new[] { "StoreLogo.png", "Square44x44Logo.png", "Square44x44Logo.targetsize-44_altform-unplated.png", "LargeTile.png", "SplashScreen.png" })
+        {
+            File.WriteAllBytes(Path.Combine(appx.FullName, name), [0]);
+        }
+        return new FileInfo(path);
+    }
+
+    [TestMethod]
+    public void FindConfig_SkipsPackageJsonWithoutBuildSection()
+    {
+        // Arrange
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "package.json"), """{ "name": "contoso-notes" }""");
+        var tauri = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "src-tauri"));
+        File.WriteAllText(Path.Combine(tauri.FullName, "tauri.conf.json"), "{}");
+        var service = GetRequiredService<IBundlerMigrationService>();
+
+        // Act & Assert
+        Assert.IsNull(service.FindConfig(MigrationSource.ElectronBuilder, _tempDirectory));
+        Assert.AreEqual(Path.Combine(tauri.FullName, "tauri.conf.json"), service.FindConfig(MigrationSource.Tauri, _tempDirectory)!.FullName);
+    }
+
+    [TestMethod]
+    public async Task MigrateAsync_ElectronBuilder_MapsAppxOptionsAndAssets()
+    {
+        // Arrange
+        var packageJson = CreateElectronProject();
+
+        // Act
+        var migration = await GetRequiredService<IBundlerMigrationService>().MigrateAsync(MigrationSource.ElectronBuilder, packageJson, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        var root = XDocument.Load(migration.Manifest.FullName).Root!;
+        var identity = root.Elements().Single(e => e.Name.LocalName == "Identity");
+        Assert.AreEqual("Contoso.Notes", identity.Attribute("Name")!.Value);
+        Assert.AreEqual("CN=Contoso, O=Contoso, C=US", identity.Attribute("Publisher")!.Value);
+        Assert.AreEqual("2.1.0.0", identity.Attribute("Version")!.Value);
+        Assert.AreEqual("Contoso Ltd", root.Descendants().Single(e => e.Name.LocalName == "PublisherDisplayName").Value);
+
+        var application = root.Descendants().Single(e => e.Name.LocalName == "Application");
+        Assert.AreEqual("ContosoNotes", application.Attribute("Id")!.Value);
+        Assert.AreEqual("Contoso Notes.exe", application.Attribute("Executable")!.Value);
+        var visualElements = application.Elements().Single(e => e.Name.LocalName == "VisualElements");
+        Assert.AreEqual("Contoso Notes", visualElements.Attribute("DisplayName")!.Value);
+        Assert.AreEqual("#1E1E1E", visualElements.Attribute("BackgroundColor")!.Value);
+        Assert.AreEqual(@"Assets\LargeTile.png", visualElements.Elements().Single(e => e.Name.LocalName == "DefaultTile").Attribute("Square310x310Logo")!.Value);
+        Assert.IsNotNull(application.Descendants().SingleOrDefault(e => e.Name.LocalName == "StartupTask"));
+
+        CollectionAssert.AreEqual(new[] { "en-US", "de-DE" }, root.Descendants().Where(e => e.Name.LocalName == "Resource").Select(e => e.Attribute("Language")!.Value).ToArray());
+        var capabilities = root.Descendants().Single(e => e.Name.LocalName == "Capabilities").Elements().ToList();
+        Assert.AreEqual("webcam", capabilities.Last().Attribute("Name")!.Value, "Device capabilities come last");
+        Assert.AreEqual("DeviceCapability", capabilities.Last().Name.LocalName);
+        Assert.IsTrue(capabilities.Any(c => c.Name.LocalName == "Capability" && c.Attribute("Name")!.Value == "picturesLibrary"));
+
+        Assert.AreEqual(5, migration.Assets);
+        Assert.IsTrue(File.Exists(Path.Combine(_tempDirectory.FullName, "Assets", "Square44x44Logo.targetsize-44_altform-unplated.png")));
+        Assert.IsFalse(File.Exists(Path.Combine(_tempDirectory.FullName, "Assets", "Square44x44Logo.scale-200.png")), "Generated variants of the project's images are replaced");
+
+        var config = GetRequiredService<IConfigService>().Load();
+        Assert.AreEqual(".", config.Projects.Single().Path);
+        Assert.AreEqual(@"Assets\SplashScreen.png", config.Splash!.Image);
+        Assert.AreEqual("#1E1E1E", config.Splash.BackgroundColor);
+        Assert.IsTrue(migration.Manual.Any(m => m.StartsWith("certificateFile", StringComparison.Ordinal)));
+        Assert.IsTrue(migration.Manual.Any(m => m.Contains("2.1.0-beta.3")));
+    }
+
+    [TestMethod]
+    public void ReadTauri_ReadsTauri2ConfigAndTheCargoPackageName()
+    {
+        // Arrange
+        var tauri = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "src-tauri"));
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "package.json"), """{ "version": "0.4.2" }""");
+        File.WriteAllText(Path.Combine(tauri.FullName, "Cargo.toml"), """
+            [package]
+            name = "contoso-notes"
+            version = "0.1.0"
+
+            [dependencies]
+            name = "not-this"
+            """);
+        var confPath = Path.Combine(tauri.FullName, "tauri.conf.json");
+        File.WriteAllText(confPath, """
+            {
+              // Tauri 2
+              "productName": "Contoso Notes",
+              "version": "../package.json",
+              "identifier": "com.contoso.notes",
+              "bundle": {
+                "publisher": "Contoso",
+                "shortDescription": "Notes for teams",
+                "icon": ["icons/32x32.png", "icons/icon.ico"],
+                "resources": ["data/*"],
+              }
+            }
+            """);
+        var manual = new List<string>();
+
+        // Act
+        var settings = BundlerMigrationService.ReadTauri(new FileInfo(confPath), manual);
+
+        // Assert
+        Assert.AreEqual("com.contoso.notes", settings.PackageName);
+        Assert.AreEqual("0.4.2.0", settings.Version);
+        Assert.AreEqual("contoso-notes.exe", settings.Executable);
+        Assert.AreEqual("Contoso Notes", settings.DisplayName);
+        Assert.AreEqual("Notes for teams", settings.Description);
+        Assert.AreEqual(_tempDirectory.FullName.TrimEnd(Path.DirectorySeparatorChar), settings.ProjectDirectory.FullName.TrimEnd(Path.DirectorySeparatorChar));
+        Assert.IsNull(settings.Icon, "The PNG icon doesn't exist");
+        Assert.IsTrue(manual.Any(m => m.StartsWith("resources", StringComparison.Ordinal)));
+        Assert.IsTrue(manual.Any(m => m.StartsWith("bundle.publisher", StringComparison.Ordinal)));
+    }
+
+    [TestMethod]
+    public void ToPackageVersion_PadsSemverAndReportsWhatItDrops()
+    {
+        // Arrange
+        var manual = new List<string>();
+
+        // Act & Assert
+        Assert.AreEqual("1.2.3.0", BundlerMigrationService.ToPackageVersion("1.2.3", manual));
+        Assert.AreEqual("1.0.0.0", BundlerMigrationService.ToPackageVersion(null, manual));
+        Assert.HasCount(0, manual);
+        Assert.AreEqual("1.2.0.0", BundlerMigrationService.ToPackageVersion("1.2.0-rc.1+build.5", manual));
+        Assert.AreEqual("1.0.0.0", BundlerMigrationService.ToPackageVersion("70000.1", manual));
+        Assert.HasCount(2, manual);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using System.Xml;
+using WinApp.Cli.Changelog;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ChangelogTests : BaseCommandTests
+{
+    public ChangelogTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    [DataRow("feat(editor): add dark mode", "", ChangelogSection.Features, "editor", "add dark mode")]
+    [DataRow("fix: crash on save", "", ChangelogSection.Fixes, null, "crash on save")]
+    [DataRow("perf: faster startup", "", ChangelogSection.Performance, null, "faster startup")]
+    [DataRow("feat!: drop Windows 10 1809", "", ChangelogSection.Breaking, null, "drop Windows 10 1809")]
+    [DataRow("refactor(api): rename settings", "BREAKING CHANGE: settings moved", ChangelogSection.Breaking, "api", "rename settings")]
+    public void Parse_MapsConventionalTypesToSections(string subject, string body, ChangelogSection section, string? scope, string description)
+    {
+        // Act
+        var entry = ConventionalCommit.Parse("abc1234", subject, body, null, includeAll: false);
+
+        // Assert
+        Assert.IsNotNull(entry);
+        Assert.AreEqual(section, entry.Section);
+        Assert.AreEqual(scope, entry.Scope);
+        Assert.AreEqual(description, entry.Description);
+    }
+
+    [TestMethod]
+    [DataRow("docs: update readme")]
+    [DataRow("chore(deps): bump packages")]
+    [DataRow("Update readme")]
+    public void Parse_SkipsChangesThatAreNotUserFacing(string subject)
+    {
+        Assert.IsNull(ConventionalCommit.Parse("abc1234", subject, string.Empty, null, includeAll: false));
+        Assert.AreEqual(ChangelogSection.Other, ConventionalCommit.Parse("abc1234", subject, string.Empty, null, includeAll: true)?.Section);
+    }
+
+    [TestMethod]
+    public void Parse_ReadsChannelTrailers()
+    {
+        // Act
+        var entry = ConventionalCommit.Parse("abc1234", "feat: sync settings", "Long description.\n\nChannel: beta, insiders\n", null, includeAll: false)!;
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "beta", "insiders" }, entry.Channels.ToArray());
+        Assert.IsTrue(entry.AppliesTo("Beta"));
+        Assert.IsFalse(entry.AppliesTo("stable"));
+        Assert.IsTrue(entry.AppliesTo(null));
+    }
+
+    [TestMethod]
+    public void TryGetPullRequest_RecognizesMergeAndSquashCommits()
+    {
+        Assert.IsTrue(ConventionalCommit.TryGetPullRequest("Merge pull request #42 from contoso/dark-mode", "feat: add dark mode\n\nDetails", out var number, out var title, out _));
+        Assert.AreEqual(42, number);
+        Assert.AreEqual("feat: add dark mode", title);
+
+        Assert.IsTrue(ConventionalCommit.TryGetPullRequest("fix: crash on save (#43)", string.Empty, out number, out title, out _));
+        Assert.AreEqual(43, number);
+        Assert.AreEqual("fix: crash on save", title);
+
+        Assert.IsFalse(ConventionalCommit.TryGetPullRequest("fix: crash on save", string.Empty, out _, out _, out _));
+    }
+
+    [TestMethod]
+    public void Render_GroupsEntriesBySection()
+    {
+        // Arrange
+        ChangelogEntry[] entries =
+        [
+            new(ChangelogSection.Fixes, null, "crash on save", "1111111aaaa", 43, []),
+            new(ChangelogSection.Features, "editor", "add dark mode", "2222222bbbb", null, []),
+        ];
+
+        // Act
+        var markdown = ReleaseNotes.Render(entries, ChangelogFormat.Markdown, "v1.1.0");
+        var text = ReleaseNotes.Render(entries, ChangelogFormat.Text);
+        var json = JsonNode.Parse(ReleaseNotes.Render(entries, ChangelogFormat.Json))!.AsArray();
+
+        // Assert
+        Assert.AreEqual("## v1.1.0\n\n### New features\n\n- **editor:** Add dark mode (2222222)\n\n### Bug fixes\n\n- Crash on save (#43)\n", markdown);
+        Assert.AreEqual("New features:\n• Add dark mode\n\nBug fixes:\n• Crash on save", text);
+        Assert.HasCount(2, json);
+        Assert.AreEqual("fixes", json[0]!["section"]!.GetValue<string>());
+    }
+
+    [TestMethod]
+    public async Task ReswFile_AddsAndReplacesString()
+    {
+        // Arrange
+        var resw = new FileInfo(Path.Combine(_tempDirectory.FullName, "Strings", "en-US", "Resources.resw"));
+
+        // Act
+        var added = await ReswFile.SetStringAsync(resw, "WhatsNew", "• First", null, TestContext.CancellationToken);
+        var addedAgain = await ReswFile.SetStringAsync(resw, "WhatsNew", "• Second", "Release notes", TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(added);
+        Assert.IsFalse(addedAgain);
+        var xmlDoc = new XmlDocument();
+        xmlDoc.Load(resw.FullName);
+        var data = xmlDoc.SelectNodes("/root/data[@name='WhatsNew']")!;
+        Assert.AreEqual(1, data.Count);
+        Assert.AreEqual("• Second", data[0]!["value"]!.InnerText);
+        Assert.AreEqual("Release notes", data[0]!["comment"]!.InnerText);
+        Assert.IsNotNull(xmlDoc.SelectSingleNode("/root/resheader[@name='resmimetype']"));
+    }
+
+    [TestMethod]
+    public async Task BuildAsync_ReadsCommitsSinceLatestTagForChannel()
+    {
+        // Arrange
+        var git = GetRequiredService<IGitService>();
+        await GitAsync(git, "init", "--quiet");
+        await CommitAsync(git, "feat: first release");
+        await GitAsync(git, "tag", "v1.0.0");
+        await CommitAsync(git, "feat(editor): add dark mode");
+        await CommitAsync(git, "fix: crash on save", "Channel: beta");
+        await CommitAsync(git, "docs: update readme");
+        await CommitAsync(git, "perf: faster startup (#12)");
+
+        var changelogService = GetRequiredService<IChangelogService>();
+
+        // Act
+        var result = await changelogService.BuildAsync(new ChangelogOptions { Channel = "stable" }, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("v1.0.0", result.From);
+        Assert.AreEqual(4, result.CommitCount);
+        CollectionAssert.AreEqual(new[] { "add dark mode", "faster startup" }, result.Entries.Select(e => e.Description).ToArray());
+        Assert.AreEqual(12, result.Entries[1].PullRequest);
+    }
+
+    private Task GitAsync(IGitService git, params string[] arguments) =>
+        git.RunAsync(_tempDirectory.FullName, arguments, TestContext.CancellationToken);
+
+    private Task CommitAsync(IGitService git, string subject, string? body = null)
+    {
+        List<string> arguments = ["-c", "user.name=Test", "-c", "user.email=test@example.com", "commit", "--quiet", "--allow-empty", "-m", subject];
+        if (body != null)
+        {
+            arguments.AddRange(["-m", body]);
+        }
+        return GitAsync(git, [.. arguments]);
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class CleanupTests : BaseCommandTests
+{
+    public CleanupTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static readonly string ProjectDirectory = Path.Combine(Path.GetTempPath(), "Notes");
+    private static readonly string OtherDirectory = Path.Combine(Path.GetTempPath(), "Paint");
+
+    private static CleanupScope ProjectScope(bool allWinapp = false) => new(
+        ProjectDirectory,
+        new HashSet<string>(StringComparer.OrdinalIgnoreCase) { "Contoso.Notes" },
+        new HashSet<string>(StringComparer.OrdinalIgnoreCase) { "CN=Contoso" },
+        allWinapp);
+
+    private static InstalledPackage Package(string name, string? installLocation, bool exists = true, bool developmentMode = true, string publisher = "CN=Contoso", string status = "Ok", bool staged = false, string? externalLocation = null, bool externalExists = true) =>
+        new(
+            FullName: $"{name}_1.0.0.0_x64__abc",
+            Name: name,
+            Publisher: publisher,
+            Version: "1.0.0.0",
+            FamilyName: $"{name}_abc",
+            InstallLocation: installLocation,
+            InstallLocationExists: exists,
+            ExternalLocation: externalLocation,
+            ExternalLocationExists: externalLocation != null && externalExists,
+            DevelopmentMode: developmentMode,
+            IsFramework: false,
+            SignatureKind: developmentMode ? "None" : "Developer",
+            Status: status,
+            Staged: staged);
+
+    [TestMethod]
+    public void Classify_FindsProjectRegistrations()
+    {
+        // Arrange
+        InstalledPackage[] packages =
+        [
+            Package("Contoso.Notes", Path.Combine(ProjectDirectory, "bin", "Debug")),
+            Package("Contoso.Notes.debug", Path.Combine(ProjectDirectory, ".winapp", "debug"), externalLocation: Path.Combine(ProjectDirectory, "bin"), externalExists: false),
+            Package("Contoso.Notes.Beta", Path.Combine(ProjectDirectory, "old"), exists: false),
+            Package("Contoso.Paint", Path.Combine(OtherDirectory, "bin"), exists: false),
+            Package("Microsoft.WindowsCalculator", @"C:\Program Files\WindowsApps\Calculator", developmentMode: false, publisher: "CN=Microsoft"),
+        ];
+
+        // Act
+        var items = CleanupService.Classify(packages, [], ProjectScope());
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { CleanupItemKind.LoosePackage, CleanupItemKind.StaleRegistration, CleanupItemKind.StaleRegistration },
+            items.Select(i => i.Kind).ToArray());
+        Assert.StartsWith("external location is gone", items[1].Description);
+        Assert.StartsWith("folder is gone", items[2].Description);
+    }
+
+    [TestMethod]
+    public void Classify_LeavesSignedInstallsOfTheProjectAlone()
+    {
+        // Arrange
+        InstalledPackage[] packages = [Package("Contoso.Notes", @"C:\Program Files\WindowsApps\Contoso.Notes", developmentMode: false)];
+
+        // Act & Assert
+        Assert.HasCount(0, CleanupService.Classify(packages, [], ProjectScope()));
+    }
+
+    [TestMethod]
+    public void Classify_AllWinappIncludesDebugAndBranchPackagesElsewhere()
+    {
+        // Arrange
+        InstalledPackage[] packages =
+        [
+            Package("Contoso.Paint.debug", Path.Combine(OtherDirectory, ".winapp", "debug")),
+            Package("Contoso.Paint.br1a2b3c4d", @"C:\Program Files\WindowsApps\Paint", developmentMode: false, status: "Tampered"),
+            Package("Fabrikam.Tool", Path.Combine(OtherDirectory, "bin")),
+        ];
+
+        // Act
+        var projectItems = CleanupService.Classify(packages, [], ProjectScope());
+        var allItems = CleanupService.Classify(packages, [], ProjectScope(allWinapp: true));
+
+        // Assert
+        Assert.HasCount(0, projectItems);
+        CollectionAssert.AreEqual(
+            new[] { "Contoso.Paint.debug_1.0.0.0_x64__abc", "Contoso.Paint.br1a2b3c4d_1.0.0.0_x64__abc" },
+            allItems.Select(i => i.Id).ToArray());
+    }
+
+    [TestMethod]
+    public void Classify_FindsStagedPackages()
+    {
+        // Arrange
+        InstalledPackage[] packages = [Package("Contoso.Notes", Path.Combine(ProjectDirectory, "bin"), developmentMode: false, staged: true)];
+
+        // Act
+        var items = CleanupService.Classify(packages, [], ProjectScope());
+
+        // Assert
+        Assert.HasCount(1, items);
+        Assert.AreEqual(CleanupItemKind.StagedPackage, items[0].Kind);
+    }
+
+    [TestMethod]
+    public void Classify_RemovesCertificatesNoRemainingPackageUses()
+    {
+        // Arrange
+        InstalledPackage[] packages =
+        [
+            Package("Contoso.Notes.debug", Path.Combine(ProjectDirectory, ".winapp", "debug"), exists: false),
+            Package("Fabrikam.Tool", Path.Combine(OtherDirectory, "bin"), publisher: "CN=Fabrikam"),
+        ];
+        StoreCertificate[] certificates =
+        [
+            new(@"CurrentUser\My", "AAAA", "CN=Contoso", "MSIX Dev Certificate"),
+            new(@"CurrentUser\My", "BBBB", "CN=Fabrikam", "MSIX Dev Certificate"),
+            new(@"CurrentUser\My", "CCCC", "CN=Contoso", "Code signing"),
+            new(@"LocalMachine\TrustedPeople", "DDDD", "CN=WinApp Branch 1a2b3c4d", ""),
+        ];
+
+        // Act
+        var projectItems = CleanupService.Classify(packages, certificates, ProjectScope());
+        var allItems = CleanupService.Classify(packages, certificates, ProjectScope(allWinapp: true));
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { "Contoso.Notes.debug_1.0.0.0_x64__abc", @"CurrentUser\My\AAAA" },
+            projectItems.Select(i => i.Id).ToArray());
+        CollectionAssert.AreEqual(
+            new[] { "Contoso.Notes.debug_1.0.0.0_x64__abc", @"CurrentUser\My\AAAA", @"LocalMachine\TrustedPeople\DDDD" },
+            allItems.Select(i => i.Id).ToArray());
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using WinApp.Cli.Crashes;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class CrashReportTests : BaseCommandTests
+{
+    public CrashReportTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo WriteFile(string relativePath, string contents = "MDMP")
+    {
+        var file = new FileInfo(Path.Combine(_tempDirectory.FullName, relativePath));
+        file.Directory!.Create();
+        File.WriteAllText(file.FullName, contents);
+        return file;
+    }
+
+    [TestMethod]
+    public void CrashesSection_RoundTripsThroughWinappYaml()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig
+        {
+            Crashes = new CrashesConfig
+            {
+                Endpoint = "https://o1.ingest.sentry.io/api/7/minidump/?sentry_key=abc",
+                SymbolsEndpoint = "https://contoso.bugsplat.com/symbols",
+                Product = "Contoso Notes"
+            }
+        };
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.IsNotNull(loaded.Crashes);
+        Assert.AreEqual("https://o1.ingest.sentry.io/api/7/minidump/?sentry_key=abc", loaded.Crashes.Endpoint);
+        Assert.AreEqual("https://contoso.bugsplat.com/symbols", loaded.Crashes.SymbolsEndpoint);
+        Assert.AreEqual("Contoso Notes", loaded.Crashes.Product);
+        Assert.AreEqual("Contoso Notes", loaded.WithoutPackages().Crashes?.Product);
+    }
+
+    [TestMethod]
+    public void FindDumps_ListsTheDumpsThatWereNotUploadedOldestFirst()
+    {
+        // Arrange
+        var newer = WriteFile(Path.Combine("LocalState", "CrashDumps", "notes-1760000100-12.dmp"));
+        var older = WriteFile(Path.Combine("LocalState", "CrashDumps", "notes-1760000000-7.dmp"));
+        newer.LastWriteTimeUtc = new DateTime(2025, 10, 9, 8, 0, 0, DateTimeKind.Utc);
+        older.LastWriteTimeUtc = new DateTime(2025, 10, 9, 7, 0, 0, DateTimeKind.Utc);
+        WriteFile(Path.Combine("LocalState", "CrashDumps", "Uploaded", "notes-1750000000-3.dmp"));
+        WriteFile(Path.Combine("LocalState", "CrashDumps", "notes.log"), "log");
+
+        // Act
+        var dumps = CrashReportService.FindDumps(_tempDirectory);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { older.Name, newer.Name }, dumps.Select(d => d.Name).ToArray());
+        Assert.IsEmpty(CrashReportService.FindDumps(_tempDirectory.CreateSubdirectory("empty")));
+    }
+
+    [TestMethod]
+    public void FindSymbolFiles_AddsTheBinaryOfEachPdb()
+    {
+        // Arrange
+        WriteFile(Path.Combine("release", "notes.pdb"), "pdb");
+        WriteFile(Path.Combine("release", "notes.exe"), "exe");
+        WriteFile(Path.Combine("release", "deps", "sync.pdb"), "pdb");
+        WriteFile(Path.Combine("release", "deps", "sync.dll"), "dll");
+        WriteFile(Path.Combine("release", "WebView2Loader.dll"), "no symbols");
+
+        // Act
+        var files = CrashReportService.FindSymbolFiles(new DirectoryInfo(Path.Combine(_tempDirectory.FullName, "release")));
+
+        // Assert
+        CollectionAssert.AreEquivalent(new[] { "notes.pdb", "notes.exe", "sync.pdb", "sync.dll" }, files.Select(f => f.Name).ToArray());
+    }
+
+    [TestMethod]
+    public void CreateUploader_RequiresAnEndpointUrl()
+    {
+        using var http = new HttpClient();
+
+        Assert.ThrowsExactly<InvalidOperationException>(() => CrashReportService.CreateUploader(null, http));
+        Assert.ThrowsExactly<InvalidOperationException>(() => CrashReportService.CreateUploader(new CrashesConfig { Endpoint = "crashes.contoso.com" }, http));
+        Assert.IsFalse(CrashReportService.CreateUploader(new CrashesConfig { Endpoint = "https://crashes.contoso.com/post" }, http).HasSymbolStore);
+    }
+
+    [TestMethod]
+    public async Task UploadDumpAsync_PostsTheFormFieldsOfCrashpad()
+    {
+        // Arrange
+        var dump = WriteFile("notes-1760000000-7.dmp");
+        string? body = null;
+        var handler = new RecordingHandler(request =>
+        {
+            body = request.Content!.ReadAsStringAsync().GetAwaiter().GetResult();
+            return new HttpResponseMessage(HttpStatusCode.OK) { Content = new StringContent("d3e2b6a1\n") };
+        });
+        var uploader = new CrashUploader(new Uri("https://crashes.contoso.com/post"), null, "secret", new HttpClient(handler));
+
+        // Act
+        var answer = await uploader.UploadDumpAsync(dump, "Contoso Notes", "1.2.0.0", null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("d3e2b6a1", answer);
+        Assert.HasCount(1, handler.Requests);
+        Assert.AreEqual(HttpMethod.Post, handler.Requests[0].Method);
+        Assert.AreEqual("multipart/form-data", handler.Requests[0].ContentType);
+        Assert.AreEqual("Bearer secret", handler.Requests[0].Authorization);
+        Assert.Contains("name=upload_file_minidump", body!);
+        Assert.Contains("filename=notes-1760000000-7.dmp", body!);
+        Assert.Contains("Contoso Notes", body!);
+        Assert.Contains("1.2.0.0", body!);
+    }
+
+    [TestMethod]
+    public async Task UploadSymbolFileAsync_FailsWithTheAnswerOfTheService()
+    {
+        // Arrange
+        var pdb = WriteFile("notes.pdb", "pdb");
+        var handler = new RecordingHandler(_ => new HttpResponseMessage(HttpStatusCode.Forbidden) { Content = new StringContent("bad key") });
+        var uploader = new CrashUploader(new Uri("https://crashes.contoso.com/post"), new Uri("https://crashes.contoso.com/symbols"), null, new HttpClient(handler))
+        {
+            Retry = new TransientHttpRetry { MaxAttempts = 1 }
+        };
+
+        // Act
+        var exception = await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => uploader.UploadSymbolFileAsync(pdb, "Contoso Notes", "1.2.0.0", null, TestContext.CancellationToken));
+
+        // Assert
+        Assert.Contains("bad key", exception.Message);
+        Assert.AreEqual(new Uri("https://crashes.contoso.com/symbols"), handler.Requests[0].Uri);
+        Assert.IsNull(handler.Requests[0].Authorization);
+    }
+
+    [TestMethod]
+    public void MoveToUploaded_KeepsTheDumpNextToTheNewOnes()
+    {
+        // Arrange
+        var dump = WriteFile(Path.Combine("CrashDumps", "notes-1760000000-7.dmp"));
+
+        // Act
+        CrashReportService.MoveToUploaded(dump);
+
+        // Assert
+        Assert.IsTrue(File.Exists(Path.Combine(_tempDirectory.FullName, "CrashDumps", "Uploaded", "notes-1760000000-7.dmp")));
+        Assert.IsFalse(File.Exists(Path.Combine(_tempDirectory.FullName, "CrashDumps", "notes-1760000000-7.dmp")));
+    }
+
+    private sealed class RecordingHandler(Func<HttpRequestMessage, HttpResponseMessage> respond) : HttpMessageHandler
+    {
+        public List<(HttpMethod Method, Uri Uri, string? ContentType, string? Authorization)> Requests { get; } = [];
+
+        protected override Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+        {
+            Requests.Add((request.Method, request.RequestUri!, request.Content?.Headers.ContentType?.MediaType, request.Headers.Authorization?.ToString()));
+            return Task.FromResult(respond(request));
+        }
+    }
+}
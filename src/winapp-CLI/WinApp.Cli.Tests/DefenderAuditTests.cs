@@ -0,0 +1,67 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class DefenderAuditTests : BaseCommandTests
+{
+    public DefenderAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void ParseStatus_TreatsHiddenExclusionsAsUnknown()
+    {
+        var status = DefenderAuditService.ParseStatus(JsonNode.Parse("""
+            {"active":true,"realTimeProtection":true,"runningMode":"Normal","controlledFolderAccess":1,
+             "exclusionPaths":["N/A: Must be an administrator to view exclusions"],"allowedApplications":[],
+             "events":[{"time":"2026-10-14T08:00:00.0000000Z","blocked":true,"process":"C:\\tools\\makeappx.exe","path":"C:\\Users\\dev\\Documents\\app.msix"}]}
+            """)!);
+
+        Assert.IsNull(status.ExclusionPaths);
+        Assert.IsNull(DefenderAuditService.IsExcluded(_tempDirectory.FullName, status.ExclusionPaths));
+        Assert.AreEqual(ControlledFolderAccessMode.Enabled, status.ControlledFolderAccess);
+        Assert.IsTrue(status.Events.Single().Blocked);
+    }
+
+    [TestMethod]
+    public void IsExcluded_MatchesParentFoldersAndWildcards()
+    {
+        var folder = _tempDirectory.CreateSubdirectory(Path.Combine("project", ".winapp")).FullName;
+
+        Assert.IsTrue(DefenderAuditService.IsExcluded(folder, [_tempDirectory.FullName + Path.DirectorySeparatorChar]));
+        Assert.IsTrue(DefenderAuditService.IsExcluded(folder, [Path.Combine(_tempDirectory.FullName, "*", ".winapp")]));
+        Assert.IsFalse(DefenderAuditService.IsExcluded(folder, [Path.Combine(_tempDirectory.FullName, "proj")]));
+        Assert.IsFalse(DefenderAuditService.IsExcluded(folder, []));
+    }
+
+    [TestMethod]
+    public void Findings_ReportSlowFoldersAndBlockedTools()
+    {
+        // Arrange
+        var slow = Path.Combine(_tempDirectory.FullName, "slow");
+        var fast = Path.Combine(_tempDirectory.FullName, "fast");
+        var status = new DefenderStatus(true, true, "Normal", ControlledFolderAccessMode.Enabled, [fast], [],
+            [new ControlledFolderAccessEvent(DateTimeOffset.UtcNow, true, @"C:\sdk\makeappx.exe", @"C:\Users\dev\Documents\app.msix")]);
+        DefenderFolderTiming[] timings = [new(slow, false, 6.5), new(fast, true, 0.4)];
+        string[] applications = [@"C:\tools\winapp.exe", @"C:\sdk\makeappx.exe"];
+
+        // Act
+        var findings = DefenderAuditService.Findings(status, timings, applications);
+        var script = DefenderAuditService.ExclusionScript(new DefenderAudit(status, timings, applications, findings), remove: false);
+
+        // Assert
+        Assert.AreEqual(AccessibilitySeverity.Warning, findings.Single(f => f.Subject == slow).Severity);
+        Assert.AreEqual(AccessibilitySeverity.Info, findings.Single(f => f.Subject == fast).Severity);
+        var blocked = findings.Single(f => f.Severity == AccessibilitySeverity.Error);
+        Assert.Contains("blocked makeappx.exe", blocked.Message);
+        Assert.Contains($"Add-MpPreference -ExclusionPath '{slow}'", script);
+        Assert.Contains(@"-ControlledFolderAccessAllowedApplications 'C:\tools\winapp.exe','C:\sdk\makeappx.exe'", script);
+    }
+}
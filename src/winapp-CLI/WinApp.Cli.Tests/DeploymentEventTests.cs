@@ -0,0 +1,84 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+using WinApp.Cli.TestMatrix;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class DeploymentEventTests : BaseCommandTests
+{
+    private const string DeploymentServer = "Microsoft-Windows-AppXDeploymentServer/Operational";
+
+    private static DeploymentEvent CreateEvent(int seconds, int id, string message) =>
+        new(new DateTime(2026, 10, 15, 9, 0, seconds, DateTimeKind.Utc), DeploymentServer, id, 2, message, DeploymentEventService.FindErrorCode(message));
+
+    [TestMethod]
+    public void Filter_KeepsTheEventsOfThePackageOldestFirst()
+    {
+        // Arrange
+        var events = new[]
+        {
+            CreateEvent(5, 404, "AppX Deployment operation failed for package Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe with error 0x80073CF3."),
+            CreateEvent(2, 401, "Deployment Register operation with target volume C: on Package Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe failed with error 0x80073CF3."),
+            CreateEvent(3, 404, "AppX Deployment operation failed for package Contoso.NotesSync_1.0.0.0_x64__8wekyb3d8bbwe with error 0x80073D02."),
+            CreateEvent(4, 5961, "Activation of app Contoso.Notes_8wekyb3d8bbwe!App failed with error: 0x80070005."),
+        };
+
+        // Act
+        var filtered = DeploymentEventService.Filter(events, "Contoso.Notes");
+
+        // Assert
+        Assert.HasCount(3, filtered);
+        CollectionAssert.AreEqual(new[] { 401, 5961, 404 }, filtered.Select(e => e.Id).ToArray());
+    }
+
+    [TestMethod]
+    public void Format_ExplainsCommonErrorCodes()
+    {
+        // Arrange
+        var events = new[]
+        {
+            CreateEvent(2, 401, "Deployment Register operation on Package Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe failed with error 0x80073cf3.\r\n\r\nNOTE: For additional information, look for [ActivityId] in the Event Log."),
+            CreateEvent(3, 404, "AppX Deployment operation failed for package Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe with error 0x80004005."),
+        };
+
+        // Act
+        var formatted = DeploymentEventService.Format(events);
+
+        // Assert
+        Assert.AreEqual("0x80073CF3", events[0].ErrorCode);
+        StringAssert.Contains(formatted, "AppXDeploymentServer 401: Deployment Register operation on Package Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe failed with error 0x80073cf3.");
+        StringAssert.Contains(formatted, "0x80073CF3: A dependency is missing");
+        Assert.DoesNotContain("NOTE:", formatted, "Only the first paragraph of the message is kept");
+        Assert.DoesNotContain("0x80004005:", formatted, "Unknown errors get no explanation");
+        Assert.AreEqual("", DeploymentEventService.Format([]));
+    }
+
+    [TestMethod]
+    public void CreateQuery_ReadsErrorsAndWarningsSinceTheOperationStarted()
+    {
+        // Act
+        var query = DeploymentEventService.CreateQuery(new DateTime(2026, 10, 15, 9, 0, 10, DateTimeKind.Utc));
+
+        // Assert
+        Assert.AreEqual("*[System[(Level=1 or Level=2 or Level=3) and TimeCreated[@SystemTime>='2026-10-15T09:00:09.000Z']]]", query);
+    }
+
+    [TestMethod]
+    public void TestMatrixResult_ExplainsTheErrorCodeOfAFailedInstall()
+    {
+        // Arrange
+        var script = TestMachineScript.Create("Contoso.Notes", "Contoso.Notes.msix", [], null, null);
+
+        // Act
+        var (_, error) = TestMachineScript.ParseResult("Deployment failed with HRESULT: 0x80073D06, The package could not be installed because a higher version of this package is already installed.\n##winapp-test deploy=failed\n", hasSmokeTest: false);
+
+        // Assert
+        StringAssert.Contains(script, "Get-WinEvent");
+        StringAssert.Contains(script, "'*Contoso.Notes_*'");
+        Assert.AreEqual($"The package didn't install (0x80073D06): {DeploymentEventService.CommonErrors["0x80073D06"]}", error);
+    }
+}
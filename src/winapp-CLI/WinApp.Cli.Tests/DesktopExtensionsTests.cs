@@ -0,0 +1,159 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class DesktopExtensionsTests : BaseCommandTests
+{
+    public DesktopExtensionsTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static string Manifest(string minVersion) => $"""
+        <?xml version="1.0" encoding="utf-8"?>
+        <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10" IgnorableNamespaces="uap">
+          <Identity Name="Contoso.Tools" Publisher="CN=Contoso" Version="1.0.0.0" />
+          <Dependencies>
+            <TargetDeviceFamily Name="Windows.Desktop" MinVersion="{minVersion}" MaxVersionTested="10.0.26100.0" />
+          </Dependencies>
+          <Applications>
+            <Application Id="Editor" Executable="Editor.exe" EntryPoint="Windows.FullTrustApplication" />
+            <Application Id="Admin" Executable="Admin.exe" EntryPoint="Windows.FullTrustApplication" />
+          </Applications>
+        </Package>
+        """;
+
+    private static readonly List<EnvironmentVariableConfig> Environment =
+    [
+        new() { Name = "CONTOSO_HOME", Value = @"C:\ProgramData\Contoso" },
+        new() { Name = "CONTOSO_MODE", Value = "packaged" },
+    ];
+
+    private static XmlNamespaceManager Namespaces(XmlDocument xmlDoc)
+    {
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("m", "http://schemas.microsoft.com/appx/manifest/foundation/windows10");
+        nsManager.AddNamespace("desktop6", AppxDesktopExtensions.Desktop6Namespace);
+        nsManager.AddNamespace("desktop7", AppxDesktopExtensions.Desktop7Namespace);
+        return nsManager;
+    }
+
+    [TestMethod]
+    public void Apply_AddsEnvironmentVariablesToPackageExtensions()
+    {
+        // Arrange
+        var warnings = new List<string>();
+
+        // Act
+        var manifest = AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), Environment, [], warnings);
+
+        // Assert
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(manifest);
+        var nsManager = Namespaces(xmlDoc);
+        var variables = xmlDoc.SelectNodes("/m:Package/m:Extensions/desktop6:Extension[@Category='windows.environmentVariables']/desktop6:EnvironmentVariables/desktop6:EnvironmentVariable", nsManager)!;
+        Assert.AreEqual(2, variables.Count);
+        Assert.AreEqual(@"C:\ProgramData\Contoso", ((XmlElement)variables[0]!).GetAttribute("Value"));
+        Assert.AreEqual("uap", xmlDoc.DocumentElement!.GetAttribute("IgnorableNamespaces"));
+        Assert.HasCount(0, warnings);
+    }
+
+    [TestMethod]
+    public void Apply_MakesExtensionsIgnorableForOlderWindows()
+    {
+        // Arrange
+        var warnings = new List<string>();
+        ShortcutConfig[] shortcuts = [new() { File = @"[{Common Programs}]\Contoso\Editor.lnk" }];
+
+        // Act
+        var manifest = AppxDesktopExtensions.Apply(Manifest("10.0.17763.0"), Environment, shortcuts, warnings);
+
+        // Assert
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(manifest);
+        Assert.AreEqual("uap desktop6 desktop7", xmlDoc.DocumentElement!.GetAttribute("IgnorableNamespaces"));
+        Assert.HasCount(1, warnings);
+        Assert.Contains("10.0.17763.0", warnings[0]);
+    }
+
+    [TestMethod]
+    public void Apply_AddsShortcutsToTheirApplications()
+    {
+        // Arrange
+        ShortcutConfig[] shortcuts =
+        [
+            new() { File = @"[{Common Programs}]\Contoso\Editor.lnk" },
+            new() { File = @"[{Desktop}]\Contoso Admin.lnk", Application = "admin", Arguments = "--elevated", Icon = "Assets/Admin.ico" },
+        ];
+
+        // Act
+        var manifest = AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), [], shortcuts, []);
+
+        // Assert
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(manifest);
+        var nsManager = Namespaces(xmlDoc);
+        var editor = (XmlElement)xmlDoc.SelectSingleNode("//m:Application[@Id='Editor']/m:Extensions/desktop7:Extension[@Category='windows.shortcut']/desktop7:Shortcut", nsManager)!;
+        var admin = (XmlElement)xmlDoc.SelectSingleNode("//m:Application[@Id='Admin']/m:Extensions/desktop7:Extension[@Category='windows.shortcut']/desktop7:Shortcut", nsManager)!;
+        Assert.AreEqual(@"[{Common Programs}]\Contoso\Editor.lnk", editor.GetAttribute("File"));
+        Assert.IsFalse(editor.HasAttribute("Icon"));
+        Assert.AreEqual("--elevated", admin.GetAttribute("Arguments"));
+        Assert.AreEqual(@"[{Package}]\Assets\Admin.ico", admin.GetAttribute("Icon"));
+    }
+
+    [TestMethod]
+    public void Apply_ReplacesExtensionsOfEarlierRuns()
+    {
+        // Arrange
+        ShortcutConfig[] shortcuts = [new() { File = @"[{Common Programs}]\Contoso\Editor.lnk" }];
+
+        // Act
+        var once = AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), Environment, shortcuts, []);
+        var twice = AppxDesktopExtensions.Apply(once, Environment, shortcuts, []);
+
+        // Assert
+        Assert.AreEqual(once, twice);
+    }
+
+    [TestMethod]
+    public void Apply_RejectsInvalidDeclarations()
+    {
+        // Act & Assert
+        Assert.ThrowsExactly<InvalidOperationException>(() => AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), [], [new() { File = @"C:\Users\Public\Desktop\Editor.lnk" }], []));
+        Assert.ThrowsExactly<InvalidOperationException>(() => AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), [], [new() { File = @"[{Desktop}]\Viewer.lnk", Application = "Viewer" }], []));
+        Assert.ThrowsExactly<InvalidOperationException>(() => AppxDesktopExtensions.Apply(Manifest("10.0.19041.0"), [new() { Name = "A=B", Value = "x" }], [], []));
+    }
+
+    [TestMethod]
+    public void ConfigService_RoundTripsEnvironmentAndShortcuts()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig
+        {
+            Environment = [new() { Name = "CONTOSO_HOME", Value = @"C:\Program Files\Contoso's Tools" }],
+            Shortcuts = [new() { File = @"[{Common Programs}]\Contoso\Admin.lnk", Application = "Admin", Arguments = "--profile \"ops\"" }],
+        };
+        config.SetVersion("Microsoft.WindowsAppSDK", "1.8.0");
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual("1.8.0", loaded.GetVersion("Microsoft.WindowsAppSDK"));
+        Assert.AreEqual(@"C:\Program Files\Contoso's Tools", loaded.Environment.Single().Value);
+        var shortcut = loaded.Shortcuts.Single();
+        Assert.AreEqual(@"[{Common Programs}]\Contoso\Admin.lnk", shortcut.File);
+        Assert.AreEqual("Admin", shortcut.Application);
+        Assert.AreEqual("--profile \"ops\"", shortcut.Arguments);
+        Assert.IsNull(shortcut.Icon);
+    }
+}
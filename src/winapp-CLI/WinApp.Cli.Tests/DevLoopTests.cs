@@ -0,0 +1,113 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Dev;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class DevLoopTests : BaseCommandTests
+{
+    public DevLoopTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private DirectoryInfo Source => new(Path.Combine(_tempDirectory.FullName, "bin"));
+
+    private DirectoryInfo LayoutFolder => new(Path.Combine(_tempDirectory.FullName, ".winapp", "dev"));
+
+    private FileInfo WriteFile(string relativePath, string contents)
+    {
+        var file = new FileInfo(Path.Combine(Source.FullName, relativePath));
+        file.Directory!.Create();
+        File.WriteAllText(file.FullName, contents);
+        return file;
+    }
+
+    [TestMethod]
+    public void Layout_CopiesOnlyTheFilesThatChanged()
+    {
+        // Arrange
+        WriteFile("notes.exe", "v1");
+        WriteFile(Path.Combine("Assets", "logo.png"), "logo");
+        var layout = new DevLayout(Source, LayoutFolder);
+        layout.Apply(layout.FindChanges());
+        File.WriteAllText(Path.Combine(LayoutFolder.FullName, "resources.pri"), "copied from the manifest");
+
+        // Act
+        var exe = WriteFile("notes.exe", "version 2");
+        exe.LastWriteTimeUtc = DateTime.UtcNow.AddMinutes(1);
+        WriteFile("notes.dll", "new");
+        File.Delete(Path.Combine(Source.FullName, "Assets", "logo.png"));
+        var changes = layout.FindChanges();
+        layout.Apply(changes);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "notes.dll", "notes.exe" }, changes.Changed.ToArray());
+        CollectionAssert.AreEqual(new[] { Path.Combine("Assets", "logo.png") }, changes.Deleted.ToArray());
+        Assert.AreEqual("version 2", File.ReadAllText(Path.Combine(LayoutFolder.FullName, "notes.exe")));
+        Assert.IsFalse(File.Exists(Path.Combine(LayoutFolder.FullName, "Assets", "logo.png")));
+        Assert.IsTrue(File.Exists(Path.Combine(LayoutFolder.FullName, "resources.pri")), "Files that didn't come from the source are kept");
+        Assert.IsTrue(layout.FindChanges().IsEmpty);
+    }
+
+    [TestMethod]
+    public void Layout_SkipsCargoIntermediates()
+    {
+        // Arrange
+        WriteFile("notes.exe", "exe");
+        WriteFile("notes.pdb", "pdb");
+        WriteFile("notes.d", "deps list");
+        WriteFile(Path.Combine("deps", "libserde-1a2b.rlib"), "rlib");
+        WriteFile(Path.Combine("incremental", "notes-3c4d", "s-1", "dep-graph.bin"), "graph");
+        WriteFile(Path.Combine(".fingerprint", "notes-5e6f", "bin-notes"), "fingerprint");
+        var layout = new DevLayout(Source, LayoutFolder);
+
+        // Act
+        var changes = layout.FindChanges();
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "notes.exe", "notes.pdb" }, changes.Changed.ToArray());
+    }
+
+    [TestMethod]
+    public void Classify_SeparatesPayloadManifestAndOwnFiles()
+    {
+        // Arrange
+        var manifest = new FileInfo(Path.Combine(_tempDirectory.FullName, "appxmanifest.xml"));
+        var winappDir = new DirectoryInfo(Path.Combine(_tempDirectory.FullName, ".winapp"));
+        DevChangeKind Classify(params string[] path) => DevWatcher.Classify(Path.Combine([_tempDirectory.FullName, .. path]), Source, manifest, winappDir);
+
+        // Act & Assert
+        Assert.AreEqual(DevChangeKind.Payload, Classify("bin", "notes.exe"));
+        Assert.AreEqual(DevChangeKind.Ignored, Classify("bin", "deps", "notes-1a2b.exe"));
+        Assert.AreEqual(DevChangeKind.Manifest, Classify("appxmanifest.xml"));
+        Assert.AreEqual(DevChangeKind.Manifest, Classify("Assets", "Square44x44Logo.png"));
+        Assert.AreEqual(DevChangeKind.Ignored, Classify("src", "main.rs"));
+        Assert.AreEqual(DevChangeKind.Ignored, Classify(".winapp", "dev", "notes.exe"));
+    }
+
+    [TestMethod]
+    public void FindExecutable_ReadsTheApplicationOfTheManifest()
+    {
+        // Arrange
+        var manifest = new FileInfo(Path.Combine(_tempDirectory.FullName, "appxmanifest.xml"));
+        File.WriteAllText(manifest.FullName, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Applications>
+                <Application Id="Notes" Executable="bin/notes.exe" EntryPoint="Windows.FullTrustApplication" />
+              </Applications>
+            </Package>
+            """);
+
+        // Act
+        var executable = DevLoopService.FindExecutable(manifest);
+
+        // Assert
+        Assert.AreEqual(Path.Combine("bin", "notes.exe"), executable);
+    }
+}
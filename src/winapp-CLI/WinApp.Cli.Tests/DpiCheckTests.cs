@@ -0,0 +1,94 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class DpiCheckTests : BaseCommandTests
+{
+    public DpiCheckTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static XElement CreateApplicationManifest(string windowsSettings)
+    {
+        return XDocument.Parse($"""
+            <assembly xmlns="urn:schemas-microsoft-com:asm.v1" manifestVersion="1.0">
+              <application xmlns="urn:schemas-microsoft-com:asm.v3">
+                <windowsSettings>
+                  {windowsSettings}
+                </windowsSettings>
+              </application>
+            </assembly>
+            """).Root!;
+    }
+
+    [TestMethod]
+    public void ReadDpiAwareness_PrefersDpiAwarenessAndReportsIgnoredSettings()
+    {
+        var both = SxsManifest.ReadDpiAwareness(CreateApplicationManifest("""
+            <dpiAware xmlns="http://schemas.microsoft.com/SMI/2005/WindowsSettings">true/pm</dpiAware>
+            <dpiAwareness xmlns="http://schemas.microsoft.com/SMI/2016/WindowsSettings">PerMonitorV3, PerMonitorV2, PerMonitor</dpiAwareness>
+            """));
+        var wrongNamespace = SxsManifest.ReadDpiAwareness(CreateApplicationManifest("""
+            <dpiAware xmlns="http://schemas.microsoft.com/SMI/2005/WindowsSettings">true</dpiAware>
+            <dpiAwareness xmlns="http://schemas.microsoft.com/SMI/2005/WindowsSettings">PerMonitorV2</dpiAwareness>
+            """));
+
+        Assert.AreEqual(DpiAwareness.PerMonitorV2, both.Awareness);
+        Assert.AreEqual(0, both.Problems.Count);
+        Assert.AreEqual(DpiAwareness.System, wrongNamespace.Awareness);
+        Assert.AreEqual(1, wrongNamespace.Problems.Count);
+        Assert.IsNull(SxsManifest.ReadDpiAwareness(CreateApplicationManifest("")).Awareness);
+    }
+
+    [TestMethod]
+    public void Findings_WarnAboutFrameworksAndUnawareApps()
+    {
+        static AppxDpiCheck.DpiApp App(DpiAwareness? awareness, bool inCode = false, bool dotNet = false, bool xaml = false, bool webView2 = false) =>
+            new(new DpiDeclaration(awareness, []), inCode, dotNet, xaml, webView2);
+
+        (string Rule, AccessibilitySeverity Severity)[] Check(AppxDpiCheck.DpiApp app) =>
+            AppxDpiCheck.Findings("app.exe", app).Select(f => (f.Rule, f.Severity)).ToArray();
+
+        CollectionAssert.AreEqual(new[] { ("dpi/framework", AccessibilitySeverity.Warning) }, Check(App(DpiAwareness.PerMonitor, webView2: true)));
+        CollectionAssert.AreEqual(new[] { ("dpi/awareness", AccessibilitySeverity.Warning) }, Check(App(null)));
+        CollectionAssert.AreEqual(new[] { ("dpi/awareness", AccessibilitySeverity.Info) }, Check(App(null, dotNet: true)));
+        CollectionAssert.AreEqual(new[] { ("dpi/awareness", AccessibilitySeverity.Info) }, Check(App(DpiAwareness.System)));
+        Assert.AreEqual(0, Check(App(null, inCode: true, xaml: true)).Length);
+        Assert.AreEqual(0, Check(App(DpiAwareness.PerMonitorV2, xaml: true, webView2: true)).Length);
+    }
+
+    [TestMethod]
+    public void Check_ReadsTheManifestNextToTheExecutableOfDesktopApps()
+    {
+        // Arrange
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "notes.exe"), "not a PE image");
+        CreateApplicationManifest("""<dpiAware xmlns="http://schemas.microsoft.com/SMI/2005/WindowsSettings">true</dpiAware>""")
+            .Save(Path.Combine(_tempDirectory.FullName, "notes.exe.manifest"));
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "MainWindow.xbf"), "");
+        var root = XDocument.Parse("""
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:uap10="http://schemas.microsoft.com/appx/manifest/uap/windows10/10"
+                     IgnorableNamespaces="uap10">
+              <Applications>
+                <Application Id="Notes" Executable="notes.exe" EntryPoint="Windows.FullTrustApplication" />
+                <Application Id="Uwp" Executable="notes.exe" EntryPoint="Notes.App" />
+                <Application Id="Container" Executable="notes.exe" uap10:TrustLevel="appContainer" uap10:RuntimeBehavior="packagedClassicApp" />
+              </Applications>
+            </Package>
+            """).Root!;
+        var notes = new List<(string Id, string Rule)>();
+
+        // Act
+        AppxDpiCheck.Check(root, _tempDirectory, (element, rule, severity, subject, message) => notes.Add((element.Attribute("Id")!.Value, rule)));
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { ("Notes", "dpi/framework"), ("Container", "dpi/framework") }, notes);
+    }
+}
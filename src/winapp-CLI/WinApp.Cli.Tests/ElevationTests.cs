@@ -0,0 +1,100 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.ComponentModel;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ElevationTests : BaseCommandTests
+{
+    public ElevationTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void BuildScript_RunsEveryStepAndRecordsItsError()
+    {
+        // Arrange
+        ElevatedStep[] steps =
+        [
+            new("Remove staged package A", "Remove-AppxPackage -Package 'A' -AllUsers"),
+            new("Trust certificate CN=Contoso", "Import-Certificate -FilePath 'C:\\temp\\a.cer' -CertStoreLocation Cert:\\LocalMachine\\TrustedPeople"),
+        ];
+        var resultsPath = Path.Combine(_tempDirectory.FullName, "it's.json");
+
+        // Act
+        var script = ElevationService.BuildScript(steps, resultsPath);
+        var results = ElevationService.ParseResults(steps, """[{"error":null},{"error":"Access is denied."}]""");
+
+        // Assert
+        Assert.AreEqual(2, script.Split("try {").Length - 1);
+        Assert.Contains("Remove-AppxPackage -Package 'A' -AllUsers", script);
+        Assert.Contains($"Set-Content -LiteralPath '{resultsPath.Replace("'", "''")}'", script);
+        Assert.IsTrue(results[0].Succeeded);
+        Assert.AreEqual("Access is denied.", results[1].Error);
+        Assert.AreEqual("The step didn't run", ElevationService.ParseResults(steps, """{"error":null}""")[1].Error);
+    }
+
+    [TestMethod]
+    public void Cleanup_BatchesStagedPackagesAndMachineCertificates()
+    {
+        var staged = new CleanupItem(CleanupItemKind.StagedPackage, "Contoso.Notes_1.0.0.0_x64__abc", "staged for no user");
+        var machine = new CleanupItem(CleanupItemKind.OrphanedCertificate, @"LocalMachine\TrustedPeople\ABC123", "CN=Contoso");
+        var user = new CleanupItem(CleanupItemKind.OrphanedCertificate, @"CurrentUser\My\ABC123", "CN=Contoso");
+        var loose = new CleanupItem(CleanupItemKind.LoosePackage, "Contoso.Notes.debug_1.0.0.0_x64__abc", "registered from .winapp");
+
+        Assert.IsTrue(CleanupService.NeedsElevation(staged));
+        Assert.IsTrue(CleanupService.NeedsElevation(machine));
+        Assert.IsFalse(CleanupService.NeedsElevation(user));
+        Assert.IsFalse(CleanupService.NeedsElevation(loose));
+        Assert.AreEqual("Remove-AppxPackage -Package 'Contoso.Notes_1.0.0.0_x64__abc' -AllUsers", CleanupService.ToElevatedStep(staged).Script);
+        Assert.AreEqual(@"Remove-Item -LiteralPath 'Cert:\LocalMachine\TrustedPeople\ABC123'", CleanupService.ToElevatedStep(machine).Script);
+    }
+
+    [TestMethod]
+    public async Task RunAsync_FailsEveryStepWhenThePromptIsDeclined()
+    {
+        // Arrange
+        ElevatedStep[] steps = [new("Enable Developer Mode", "exit 0"), new("Remove staged package A", "exit 0")];
+        var service = new ElevationService(new DecliningPowerShellService());
+
+        // Act
+        var results = await service.RunAsync(steps, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(2, results);
+        Assert.IsTrue(results.All(r => r.Error == "User Account Control was declined"));
+    }
+
+    [TestMethod]
+    public void TrustScript_CarriesThePublicCertificateInTheScript()
+    {
+        // Arrange
+        using var key = RSA.Create(2048);
+        var request = new CertificateRequest("CN=Contoso", key, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1);
+        using var certificate = request.CreateSelfSigned(DateTimeOffset.Now, DateTimeOffset.Now.AddDays(1));
+
+        // Act
+        var script = CertificateService.TrustScript(certificate);
+
+        // Assert
+        StringAssert.Contains(script, Convert.ToBase64String(certificate.Export(X509ContentType.Cert)));
+        StringAssert.Contains(script, "X509Store]::new('TrustedPeople', 'LocalMachine')");
+        Assert.DoesNotContain("-FilePath", script);
+    }
+
+    private sealed class DecliningPowerShellService : IPowerShellService
+    {
+        public Task<(int exitCode, string output)> RunCommandAsync(string command, TaskContext taskContext, bool elevated = false, Dictionary<string, string>? environmentVariables = null, CancellationToken cancellationToken = default)
+        {
+            throw new Win32Exception(1223, "The operation was canceled by the user");
+        }
+    }
+}
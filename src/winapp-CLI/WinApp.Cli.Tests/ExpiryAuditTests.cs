@@ -0,0 +1,97 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ExpiryAuditTests : BaseCommandTests
+{
+    private static readonly DateTimeOffset Now = new(2026, 10, 1, 0, 0, 0, TimeSpan.Zero);
+
+    public ExpiryAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void Classify_UsesWarningAndErrorThresholds()
+    {
+        // Arrange
+        var options = new ExpiryAuditOptions { WarningDays = 30, ErrorDays = 7 };
+
+        // Act & Assert
+        Assert.AreEqual(AccessibilitySeverity.Info, ExpiryAuditService.Classify("cert", Now.AddDays(90), Now, options).Severity);
+        Assert.AreEqual(AccessibilitySeverity.Warning, ExpiryAuditService.Classify("cert", Now.AddDays(20), Now, options).Severity);
+        Assert.AreEqual(AccessibilitySeverity.Error, ExpiryAuditService.Classify("cert", Now.AddDays(3), Now, options).Severity);
+        var expired = ExpiryAuditService.Classify("cert", Now.AddDays(-1), Now, options);
+        Assert.AreEqual(AccessibilitySeverity.Error, expired.Severity);
+        Assert.AreEqual("expired on 2026-09-30", expired.Message);
+    }
+
+    [TestMethod]
+    public void CheckCertificate_ReadsExpiryOfPfx()
+    {
+        // Arrange
+        using var rsa = RSA.Create(2048);
+        var notAfter = DateTimeOffset.UtcNow.AddDays(10);
+        using var certificate = new CertificateRequest("CN=Contoso", rsa, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1)
+            .CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), notAfter);
+        var path = Path.Combine(_tempDirectory.FullName, "signing.pfx");
+        File.WriteAllBytes(path, certificate.Export(X509ContentType.Pfx, "secret"));
+
+        // Act
+        var check = ExpiryAuditService.CheckCertificate(new FileInfo(path), "secret", DateTimeOffset.UtcNow, new ExpiryAuditOptions());
+
+        // Assert
+        Assert.AreEqual(AccessibilitySeverity.Warning, check.Severity);
+        Assert.Contains("CN=Contoso", check.Subject);
+        Assert.AreEqual(notAfter.UtcDateTime.Date, check.ExpiresOn!.Value.UtcDateTime.Date);
+    }
+
+    [TestMethod]
+    public void CheckSecret_MatchesSecretByHint()
+    {
+        // Arrange
+        var application = new JsonObject
+        {
+            ["displayName"] = "Contoso Store CI",
+            ["passwordCredentials"] = new JsonArray(
+                new JsonObject { ["hint"] = "abc", ["displayName"] = "old", ["endDateTime"] = "2026-10-05T00:00:00Z" },
+                new JsonObject { ["hint"] = "xyz", ["displayName"] = "current", ["endDateTime"] = "2027-04-01T00:00:00Z" })
+        };
+
+        // Act
+        var check = ExpiryAuditService.CheckSecret(application, "client", "xyz~secret", Now, new ExpiryAuditOptions());
+        var unknown = ExpiryAuditService.CheckSecret(application, "client", "def~secret", Now, new ExpiryAuditOptions());
+
+        // Assert
+        Assert.AreEqual("Contoso Store CI client secret 'current'", check.Subject);
+        Assert.AreEqual(AccessibilitySeverity.Info, check.Severity);
+        Assert.AreEqual(new DateTimeOffset(2027, 4, 1, 0, 0, 0, TimeSpan.Zero), check.ExpiresOn);
+        Assert.AreEqual(AccessibilitySeverity.Error, unknown.Severity);
+    }
+
+    [TestMethod]
+    public void CheckProfile_WithSuspendedProfile_IsError()
+    {
+        // Arrange
+        var profile = new JsonObject
+        {
+            ["name"] = "contoso-public",
+            ["properties"] = new JsonObject { ["status"] = "Suspended", ["provisioningState"] = "Succeeded" }
+        };
+
+        // Act
+        var check = ExpiryAuditService.CheckProfile(profile, "/subscriptions/s/resourceGroups/rg/providers/Microsoft.CodeSigning/codeSigningAccounts/contoso/certificateProfiles/contoso-public");
+
+        // Assert
+        Assert.AreEqual(AccessibilitySeverity.Error, check.Severity);
+        Assert.Contains("Suspended", check.Message);
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Text;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+/// <summary>
+/// Answers Azure AD token requests and routes Partner Center API calls to registered responses,
+/// recording every request so tests can check what was sent
+/// </summary>
+internal class FakePartnerCenterHandler : HttpMessageHandler
+{
+    private readonly Dictionary<string, Func<HttpRequestMessage, string?, HttpResponseMessage>> routes = new(StringComparer.OrdinalIgnoreCase);
+
+    public List<(HttpMethod Method, string PathAndQuery, string? Body)> Requests { get; } = [];
+
+    public static PartnerCenterClient CreateClient(FakePartnerCenterHandler handler) =>
+        new(new HttpClient(handler), new StoreCredentials("tenant", "client", "secret"))
+        {
+            Retry = new TransientHttpRetry { MaxAttempts = 1 }
+        };
+
+    /// <summary>
+    /// Registers a JSON response for a method and a path relative to the API root; the query string is ignored
+    /// unless <paramref name="path"/> contains one
+    /// </summary>
+    public FakePartnerCenterHandler On(HttpMethod method, string path, Func<HttpRequestMessage, string?, JsonNode?> respond)
+    {
+        routes[$"{method} {path}"] = (request, body) => Json(respond(request, body));
+        return this;
+    }
+
+    public FakePartnerCenterHandler On(HttpMethod method, string path, HttpStatusCode statusCode)
+    {
+        routes[$"{method} {path}"] = (_, _) => new HttpResponseMessage(statusCode);
+        return this;
+    }
+
+    protected override async Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+    {
+        var uri = request.RequestUri!;
+        if (uri.Host == PartnerCenterClient.DefaultAuthorityUri.Host)
+        {
+            return Json(new JsonObject { ["access_token"] = "token", ["expires_in"] = "3600" });
+        }
+
+        var body = request.Content == null ? null : await request.Content.ReadAsStringAsync(cancellationToken);
+        var path = uri.AbsolutePath[PartnerCenterClient.DefaultServiceUri.AbsolutePath.Length..];
+        Requests.Add((request.Method, path + uri.Query, body));
+
+        if (routes.TryGetValue($"{request.Method} {path}{uri.Query}", out var route) || routes.TryGetValue($"{request.Method} {path}", out route))
+        {
+            return route(request, body);
+        }
+
+        return new HttpResponseMessage(HttpStatusCode.NotFound);
+    }
+
+    private static HttpResponseMessage Json(JsonNode? node) => new(HttpStatusCode.OK)
+    {
+        Content = node == null ? new StringContent(string.Empty) : new StringContent(node.ToJsonString(), Encoding.UTF8, "application/json")
+    };
+}
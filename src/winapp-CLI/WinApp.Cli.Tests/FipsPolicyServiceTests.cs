@@ -0,0 +1,108 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class FipsPolicyServiceTests : BaseCommandTests
+{
+    public FipsPolicyServiceTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void EnsurePkcs12_WithLegacyEncryption_Throws()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = true;
+        var pfxPath = WriteCertificate("legacy.pfx", RSA.Create(2048), Pkcs12ExportPbeParameters.Pkcs12TripleDesSha1);
+
+        // Act & Assert
+        var exception = Assert.ThrowsExactly<InvalidOperationException>(() => fipsPolicyService.EnsurePkcs12(pfxPath));
+        Assert.Contains("legacy PKCS#12 encryption", exception.Message);
+    }
+
+    [TestMethod]
+    public void EnsurePkcs12_WithAesEncryption_Succeeds()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = true;
+        var pfxPath = WriteCertificate("aes.pfx", RSA.Create(2048), fipsPolicyService.Pkcs12ExportParameters);
+
+        // Act & Assert
+        fipsPolicyService.EnsurePkcs12(pfxPath);
+    }
+
+    [TestMethod]
+    public void EnsurePkcs12_WhenDisabled_AcceptsLegacyEncryption()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = false;
+        var pfxPath = WriteCertificate("legacy.pfx", RSA.Create(2048), Pkcs12ExportPbeParameters.Pkcs12TripleDesSha1);
+
+        // Act & Assert
+        fipsPolicyService.EnsurePkcs12(pfxPath);
+        Assert.AreEqual(Pkcs12ExportPbeParameters.Default, fipsPolicyService.Pkcs12ExportParameters);
+    }
+
+    [TestMethod]
+    public void EnsureSigningCertificate_WithShortRsaKey_Throws()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = true;
+        using var rsa = RSA.Create(1024);
+        using var certificate = new CertificateRequest("CN=Test", rsa, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1)
+            .CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(1));
+
+        // Act & Assert
+        var exception = Assert.ThrowsExactly<InvalidOperationException>(() => fipsPolicyService.EnsureSigningCertificate(certificate));
+        Assert.Contains("RSA 1024-bit", exception.Message);
+    }
+
+    [TestMethod]
+    public void EnsureSigningCertificate_WithApprovedCurve_Succeeds()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = true;
+        using var ecdsa = ECDsa.Create(ECCurve.NamedCurves.nistP384);
+        using var certificate = new CertificateRequest("CN=Test", ecdsa, HashAlgorithmName.SHA384)
+            .CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(1));
+
+        // Act & Assert
+        fipsPolicyService.EnsureSigningCertificate(certificate);
+    }
+
+    [TestMethod]
+    public void EnsureHashAlgorithm_WithSha1_Throws()
+    {
+        // Arrange
+        var fipsPolicyService = GetRequiredService<IFipsPolicyService>();
+        fipsPolicyService.IsEnabled = true;
+
+        // Act & Assert
+        Assert.ThrowsExactly<InvalidOperationException>(() => fipsPolicyService.EnsureHashAlgorithm(HashAlgorithmName.SHA1, "sign"));
+        fipsPolicyService.EnsureHashAlgorithm(HashAlgorithmName.SHA384, "sign");
+    }
+
+    private FileInfo WriteCertificate(string fileName, RSA rsa, Pkcs12ExportPbeParameters exportParameters)
+    {
+        using (rsa)
+        {
+            using var certificate = new CertificateRequest("CN=Test", rsa, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1)
+                .CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(1));
+            var path = new FileInfo(Path.Combine(_tempDirectory.FullName, fileName));
+            File.WriteAllBytes(path.FullName, certificate.ExportPkcs12(exportParameters, "password"));
+            return path;
+        }
+    }
+}
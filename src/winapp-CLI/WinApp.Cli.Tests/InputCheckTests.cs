@@ -0,0 +1,71 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class InputCheckTests : BaseCommandTests
+{
+    public InputCheckTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static XElement CreateManifest(string applications)
+    {
+        return XDocument.Parse($"""
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:uap10="http://schemas.microsoft.com/appx/manifest/uap/windows10/10"
+                     IgnorableNamespaces="uap10">
+              <Applications>
+                {applications}
+              </Applications>
+            </Package>
+            """).Root!;
+    }
+
+    [TestMethod]
+    public void Check_ReportsHooksAndRawInputOfAppsInTheAppContainer()
+    {
+        // Arrange
+        var root = CreateManifest("""
+            <Application Id="Keys" Executable="keys.exe" EntryPoint="Windows.FullTrustApplication" uap10:TrustLevel="appContainer" uap10:RuntimeBehavior="packagedClassicApp" />
+            <Application Id="Tray" Executable="tray.exe" EntryPoint="Windows.FullTrustApplication" />
+            <Application Id="Missing" Executable="missing.exe" EntryPoint="Keys.App" />
+            """);
+        var imports = new Dictionary<string, IReadOnlySet<string>>
+        {
+            ["keys.exe"] = new HashSet<string> { "user32.dll!SetWindowsHookExW", "user32.dll!RegisterRawInputDevices", "kernel32.dll!ExitProcess" },
+            ["tray.exe"] = new HashSet<string> { "user32.dll!SetWindowsHookExW" },
+        };
+        var notes = new List<(string Id, string Rule, AccessibilitySeverity Severity)>();
+
+        // Act
+        AppxInputCheck.Check(root, executable => imports.GetValueOrDefault(executable),
+            (element, rule, severity, subject, message) => notes.Add((element.Attribute("Id")!.Value, rule, severity)));
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { ("Keys", "input/low-level-hook", AccessibilitySeverity.Warning), ("Keys", "input/background-raw-input", AccessibilitySeverity.Info) },
+            notes);
+    }
+
+    [TestMethod]
+    public void RunsInAppContainer_FollowsTrustLevelThenEntryPoint()
+    {
+        var applications = CreateManifest("""
+            <Application Id="Uwp" Executable="uwp.exe" EntryPoint="Uwp.App" />
+            <Application Id="Desktop" Executable="desktop.exe" EntryPoint="Windows.FullTrustApplication" />
+            <Application Id="Medium" Executable="medium.exe" uap10:TrustLevel="mediumIL" uap10:RuntimeBehavior="win32App" />
+            <Application Id="Container" Executable="container.exe" EntryPoint="Windows.FullTrustApplication" uap10:TrustLevel="appContainer" />
+            """).Descendants(XName.Get("Application", AppxManifestSchema.FoundationNamespace));
+
+        var inAppContainer = applications.Where(AppxInputCheck.RunsInAppContainer).Select(a => a.Attribute("Id")!.Value).ToArray();
+
+        CollectionAssert.AreEqual(new[] { "Uwp", "Container" }, inAppContainer);
+    }
+}
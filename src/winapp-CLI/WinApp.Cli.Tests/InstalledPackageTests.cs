@@ -0,0 +1,169 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class InstalledPackageTests : BaseCommandTests
+{
+    public InstalledPackageTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static InstalledPackage Package(string name, string publisher, string signatureKind = "Developer", bool developmentMode = false, bool isFramework = false) => new(
+        FullName: $"{name}_1.0.0.0_x64__abc",
+        Name: name,
+        Publisher: publisher,
+        Version: "1.0.0.0",
+        FamilyName: $"{name}_abc",
+        InstallLocation: $@"C:\Program Files\WindowsApps\{name}",
+        InstallLocationExists: true,
+        ExternalLocation: null,
+        ExternalLocationExists: false,
+        DevelopmentMode: developmentMode,
+        IsFramework: isFramework,
+        SignatureKind: signatureKind,
+        Status: "Ok",
+        Staged: false);
+
+    private static readonly InstalledPackage[] Packages =
+    [
+        Package("Contoso.Notes", "CN=Contoso, O=Contoso Ltd, C=US", signatureKind: "Store"),
+        Package("Contoso.Paint", "CN=\"Contoso, Ltd\"", developmentMode: true, signatureKind: "None"),
+        Package("Fabrikam.Tool", "CN=Fabrikam", signatureKind: "Enterprise"),
+        Package("Microsoft.WindowsCalculator", "CN=Microsoft Corporation, O=Microsoft Corporation", signatureKind: "System"),
+        Package("Microsoft.VCLibs.140.00", "CN=Microsoft Corporation, O=Microsoft Corporation", signatureKind: "Store", isFramework: true),
+    ];
+
+    [TestMethod]
+    public void Registration_MapsSignatureKind()
+    {
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { PackageRegistrationKind.Store, PackageRegistrationKind.Development, PackageRegistrationKind.Sideloaded, PackageRegistrationKind.System, PackageRegistrationKind.Store },
+            Packages.Select(p => p.Registration).ToArray());
+    }
+
+    [TestMethod]
+    public void Filter_HidesFrameworkAndWindowsPackagesByDefault()
+    {
+        // Act
+        var packages = InstalledPackageService.Filter(Packages, name: null, publisher: null, kind: null, all: false);
+        var all = InstalledPackageService.Filter(Packages, name: null, publisher: null, kind: null, all: true);
+        var system = InstalledPackageService.Filter(Packages, name: null, publisher: null, PackageRegistrationKind.System, all: false);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "Contoso.Notes", "Contoso.Paint", "Fabrikam.Tool" }, packages.Select(p => p.Name).ToArray());
+        Assert.HasCount(5, all);
+        Assert.AreEqual("Microsoft.WindowsCalculator", system.Single().Name);
+    }
+
+    [TestMethod]
+    public void Filter_MatchesNamePatterns()
+    {
+        // Act
+        var packages = InstalledPackageService.Filter(Packages, name: "contoso.*", publisher: null, kind: null, all: false);
+        var exact = InstalledPackageService.Filter(Packages, name: "Contoso.Notes", publisher: null, kind: null, all: false);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "Contoso.Notes", "Contoso.Paint" }, packages.Select(p => p.Name).ToArray());
+        Assert.AreEqual("Contoso.Notes", exact.Single().Name);
+    }
+
+    [TestMethod]
+    public void Filter_MatchesPublisherCommonNameOrDistinguishedName()
+    {
+        // Act
+        var byCommonName = InstalledPackageService.Filter(Packages, name: null, publisher: "Contoso*", kind: null, all: false);
+        var byDistinguishedName = InstalledPackageService.Filter(Packages, name: null, publisher: "CN=Contoso, O=*", kind: null, all: false);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "Contoso.Notes", "Contoso.Paint" }, byCommonName.Select(p => p.Name).ToArray());
+        Assert.AreEqual("Contoso.Notes", byDistinguishedName.Single().Name);
+    }
+
+    [TestMethod]
+    public void Filter_ByKind()
+    {
+        // Act
+        var packages = InstalledPackageService.Filter(Packages, name: null, publisher: null, PackageRegistrationKind.Development, all: false);
+
+        // Assert
+        Assert.AreEqual("Contoso.Paint", packages.Single().Name);
+    }
+
+    [TestMethod]
+    public void FindProjectPackages_FindsDebugAndBranchIdentitiesDevelopmentFirst()
+    {
+        // Arrange
+        InstalledPackage[] packages =
+        [
+            Package("Contoso.Notes", "CN=Contoso", signatureKind: "Store"),
+            Package("Contoso.Notes.debug", "CN=Contoso", developmentMode: true, signatureKind: "None"),
+            Package("Contoso.Notes.br1a2b3c4d", "CN=WinApp Branch 1a2b3c4d"),
+            Package("Contoso.NotesWidget", "CN=Contoso"),
+            Package("Contoso.Notes.Beta", "CN=Contoso"),
+        ];
+
+        // Act
+        var matches = InstalledPackageService.FindProjectPackages(packages, "Contoso.Notes");
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { "Contoso.Notes.debug", "Contoso.Notes", "Contoso.Notes.br1a2b3c4d" },
+            matches.Select(p => p.Name).ToArray());
+    }
+
+    [TestMethod]
+    public void GetLocations_ResolvesFoldersOfPackage()
+    {
+        // Arrange
+        var package = Package("Contoso.Notes", "CN=Contoso") with { ExternalLocation = @"C:\src\Notes\bin" };
+        var appData = Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.LocalApplicationData), "Packages", "Contoso.Notes_abc");
+
+        // Act
+        var appDataLocations = InstalledPackageService.GetLocations(package, PackageLocation.AppData);
+        var installLocations = InstalledPackageService.GetLocations(package, PackageLocation.Install);
+        var logLocations = InstalledPackageService.GetLocations(package, PackageLocation.Logs);
+
+        // Assert
+        Assert.AreEqual(appData, appDataLocations.Single());
+        CollectionAssert.AreEqual(new[] { @"C:\src\Notes\bin", @"C:\Program Files\WindowsApps\Contoso.Notes" }, installLocations);
+        Assert.AreEqual(Path.Combine(appData, "LocalState", "Logs"), logLocations[0]);
+        Assert.IsTrue(logLocations.All(l => l.StartsWith(appData, StringComparison.Ordinal)));
+    }
+
+    [TestMethod]
+    public void FindPackageProcesses_FindsTheProcessTreeOfThePackage()
+    {
+        // Arrange
+        var package = Package("Contoso.Notes", "CN=Contoso") with { ExternalLocation = @"C:\src\Notes\bin" };
+        var start = new DateTimeOffset(2026, 10, 15, 9, 0, 0, TimeSpan.Zero);
+        PackageProcess Process(int id, int parentId, string name, string? path, string? commandLine = null, int minute = 1) =>
+            new(id, parentId, name, path, commandLine, start.AddMinutes(minute));
+        PackageProcess[] processes =
+        [
+            Process(4, 0, "System", null, minute: 0),
+            Process(100, 4, "explorer.exe", @"C:\Windows\explorer.exe", minute: 0),
+            Process(200, 100, "Notes.exe", @"C:\src\Notes\bin\Notes.exe"),
+            Process(201, 200, "msedgewebview2.exe", @"C:\Program Files (x86)\Microsoft\EdgeWebView\Application\msedgewebview2.exe", "--embedded-browser-webview=1"),
+            Process(202, 201, "msedgewebview2.exe", @"C:\Program Files (x86)\Microsoft\EdgeWebView\Application\msedgewebview2.exe", "--type=renderer"),
+            Process(300, 4, "dllhost.exe", @"C:\Windows\System32\dllhost.exe", @"C:\Windows\System32\dllhost.exe /Processid:{AB8902B4-09CA-4BB6-B78D-A8F59079A8D5} C:\Users\dev\AppData\Local\Packages\Contoso.Notes_abc\LocalState\thumbs"),
+            Process(400, 200, "notepad.exe", @"C:\Windows\notepad.exe", minute: 0),
+            Process(500, 100, "winapp.exe", @"C:\src\Notes\bin\tools\winapp.exe"),
+            Process(501, 500, "powershell.exe", @"C:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe"),
+            Process(600, 100, "Notes.Helper.exe", @"C:\src\Notes\binaries\Notes.Helper.exe"),
+        ];
+
+        // Act
+        var found = InstalledPackageService.FindPackageProcesses(processes, package, currentProcessId: 500);
+
+        // Assert
+        CollectionAssert.AreEquivalent(new[] { 200, 201, 202, 300 }, found.Select(p => p.Id).ToArray());
+        Assert.AreEqual("Notes.exe, msedgewebview2.exe (2), dllhost.exe", InstalledPackageService.DescribeProcesses(found.OrderBy(p => p.Id)));
+    }
+}
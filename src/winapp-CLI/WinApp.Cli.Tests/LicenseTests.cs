@@ -0,0 +1,155 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.AccessControl;
+using System.Security.Cryptography;
+using System.Security.Principal;
+using System.Text;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Commands;
+using WinApp.Cli.Licensing;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class LicenseTests : BaseCommandTests
+{
+    private const string DeviceId = "0123456789ABCDEF0123456789ABCDEF";
+    private const string FamilyName = "Contoso.Tools_8wekyb3d8bbwe";
+
+    private static readonly DateTimeOffset Issued = new(2026, 10, 1, 0, 0, 0, TimeSpan.Zero);
+
+    public LicenseTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static LicenseClaims Claims(string? deviceId = DeviceId, string? familyName = null, DateTimeOffset? expires = null) =>
+        new("Contoso Ltd", deviceId, familyName, Issued, expires, ["pro", "export"]);
+
+    [TestMethod]
+    public void Verify_AcceptsLicenseOnItsDevice()
+    {
+        // Arrange
+        using var key = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+        var license = DeviceLicense.Create(Claims(), key);
+
+        // Act
+        var check = DeviceLicense.Verify(license, key, DeviceId.ToLowerInvariant(), null, Issued.AddDays(1));
+
+        // Assert
+        Assert.IsTrue(check.IsValid, check.Problem);
+        Assert.AreEqual("Contoso Ltd", check.Claims.Licensee);
+        CollectionAssert.AreEqual(new[] { "pro", "export" }, check.Claims.Features.ToArray());
+    }
+
+    [TestMethod]
+    public void Verify_RejectsOtherDevicePackageOrExpiredLicense()
+    {
+        // Arrange
+        using var key = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+        var license = DeviceLicense.Create(Claims(familyName: FamilyName, expires: Issued.AddDays(30)), key);
+
+        // Act
+        var otherDevice = DeviceLicense.Verify(license, key, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF", FamilyName, Issued.AddDays(1));
+        var otherPackage = DeviceLicense.Verify(license, key, DeviceId, "Fabrikam.Tools_8wekyb3d8bbwe", Issued.AddDays(1));
+        var noPackage = DeviceLicense.Verify(license, key, DeviceId, null, Issued.AddDays(1));
+        var expired = DeviceLicense.Verify(license, key, DeviceId, FamilyName, Issued.AddDays(31));
+
+        // Assert
+        Assert.Contains("bound to device", otherDevice.Problem!);
+        Assert.Contains("Contoso.Tools_8wekyb3d8bbwe, not Fabrikam.Tools_8wekyb3d8bbwe", otherPackage.Problem!);
+        Assert.Contains("no package family name was given", noPackage.Problem!);
+        Assert.Contains("expired", expired.Problem!);
+    }
+
+    [TestMethod]
+    public void Verify_ThrowsForChangedLicenseOrOtherKey()
+    {
+        // Arrange
+        using var key = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+        using var otherKey = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+        var license = DeviceLicense.Create(Claims(), key);
+        var json = JsonNode.Parse(license)!.AsObject();
+        var payload = Encoding.UTF8.GetString(Convert.FromBase64String(json["payload"]!.GetValue<string>()));
+        json["payload"] = Convert.ToBase64String(Encoding.UTF8.GetBytes(payload.Replace(DeviceId, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF")));
+
+        // Act & Assert
+        Assert.ThrowsExactly<CryptographicException>(() => DeviceLicense.Verify(json.ToJsonString(), key, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF", null, Issued));
+        Assert.ThrowsExactly<CryptographicException>(() => DeviceLicense.Verify(license, otherKey, DeviceId, null, Issued));
+        Assert.ThrowsExactly<InvalidDataException>(() => DeviceLicense.Verify("{}", key, DeviceId, null, Issued));
+    }
+
+    [TestMethod]
+    public void Create_RequiresDeviceOrPackageBinding()
+    {
+        // Arrange
+        using var key = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+
+        // Act & Assert
+        Assert.ThrowsExactly<ArgumentException>(() => DeviceLicense.Create(Claims(deviceId: null), key));
+    }
+
+    [TestMethod]
+    public void GetPackageFamilyName_MatchesWindows()
+    {
+        // Act
+        var familyName = DeviceLicense.GetPackageFamilyName("Microsoft.WindowsTerminal", "CN=Microsoft Corporation, O=Microsoft Corporation, L=Redmond, S=Washington, C=US");
+
+        // Assert
+        Assert.AreEqual("Microsoft.WindowsTerminal_8wekyb3d8bbwe", familyName);
+    }
+
+    [TestMethod]
+    public void GetDeviceId_IsStableAndHidesMachineGuid()
+    {
+        // Act
+        var deviceId = DeviceLicense.GetDeviceId("4C4C4544-0042-3510-8048-B4C04F4E3332");
+
+        // Assert
+        Assert.AreEqual(deviceId, DeviceLicense.GetDeviceId(" 4c4c4544-0042-3510-8048-b4c04f4e3332 "));
+        Assert.AreEqual(32, deviceId.Length);
+        Assert.DoesNotContain("4C4C4544", deviceId);
+    }
+
+    [TestMethod]
+    public async Task LicenseService_IssuesWithEncryptedKeyAndVerifies()
+    {
+        // Arrange
+        var licenseService = GetRequiredService<ILicenseService>();
+        var privateKey = new FileInfo(Path.Combine(_tempDirectory.FullName, "keys", DeviceLicense.DefaultKeyFileName));
+        var output = new FileInfo(Path.Combine(_tempDirectory.FullName, "contoso.license.json"));
+
+        // Act
+        var publicKey = await licenseService.GenerateKeyAsync(privateKey, "secret", TestContext.CancellationToken);
+        await licenseService.IssueAsync(Claims(familyName: FamilyName) with { Issued = DateTimeOffset.UtcNow }, privateKey, "secret", output, TestContext.CancellationToken);
+        var check = await licenseService.VerifyAsync(output, publicKey, DeviceId, FamilyName, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("license-signing-key.pub.pem", publicKey.Name);
+        Assert.Contains("ENCRYPTED PRIVATE KEY", await File.ReadAllTextAsync(privateKey.FullName, TestContext.CancellationToken));
+        Assert.IsTrue(check.IsValid, check.Problem);
+        await Assert.ThrowsAsync<CryptographicException>(() => licenseService.IssueAsync(Claims(), privateKey, "wrong", output, TestContext.CancellationToken));
+    }
+
+    [TestMethod]
+    public async Task KeygenCommand_IgnoresThePrivateKeyAndRestrictsItToTheUser()
+    {
+        // Arrange
+        var command = GetRequiredService<LicenseKeygenCommand>();
+        var privateKey = new FileInfo(Path.Combine(_tempDirectory.FullName, DeviceLicense.DefaultKeyFileName));
+
+        // Act
+        var exitCode = await ParseAndInvokeWithCaptureAsync(command, []);
+
+        // Assert
+        Assert.AreEqual(0, exitCode);
+        var gitignore = await File.ReadAllLinesAsync(Path.Combine(_tempDirectory.FullName, ".gitignore"), TestContext.CancellationToken);
+        Assert.Contains(DeviceLicense.DefaultKeyFileName, gitignore);
+        Assert.DoesNotContain("license-signing-key.pub.pem", gitignore);
+        var rules = privateKey.GetAccessControl().GetAccessRules(includeExplicit: true, includeInherited: true, typeof(SecurityIdentifier));
+        using var identity = WindowsIdentity.GetCurrent();
+        Assert.AreEqual(identity.User, rules.Cast<FileSystemAccessRule>().Single().IdentityReference);
+    }
+}
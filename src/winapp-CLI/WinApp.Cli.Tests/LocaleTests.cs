@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class LocaleTests : BaseCommandTests
+{
+    public LocaleTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void CodePageFinding_ReportsAnsiFileFunctionsWithoutUtf8()
+    {
+        var ansi = new HashSet<string> { "kernel32.dll!CreateFileA", "api-ms-win-crt-stdio-l1-1-0.dll!fopen", "kernel32.dll!ExitProcess" };
+        var wide = new HashSet<string> { "kernel32.dll!CreateFileW", "api-ms-win-crt-stdio-l1-1-0.dll!_wfopen" };
+        string[] names = [@"Notes\Résumé.md"];
+
+        var message = AppxCodePageCheck.Finding("notes.exe", ansi, null, names);
+
+        Assert.IsNotNull(message);
+        Assert.Contains("CreateFileA, fopen", message);
+        Assert.Contains(@"Notes\Résumé.md", message);
+        Assert.IsNull(AppxCodePageCheck.Finding("notes.exe", ansi, "UTF-8", names));
+        Assert.IsNull(AppxCodePageCheck.Finding("notes.exe", wide, null, names));
+        Assert.IsNull(AppxCodePageCheck.Finding("notes.exe", ansi, null, []));
+    }
+
+    [TestMethod]
+    public void GetPackageEnvironment_AddsTheLocaleOverridesOfWinappYaml()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(new WinappConfig
+        {
+            Environment = [new EnvironmentVariableConfig { Name = "PYTHONUTF8", Value = "0" }],
+            Locale = new LocaleConfig { Language = "fr-FR", Utf8Mode = true },
+        });
+
+        // Act
+        var environment = configService.Load().GetPackageEnvironment();
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { ("PYTHONUTF8", "0"), ("WINAPP_LOCALE", "fr-FR") },
+            environment.Select(e => (e.Name, e.Value)).ToArray());
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Collections.Concurrent;
+using System.IO.Compression;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class LogsTests : BaseCommandTests
+{
+    private DirectoryInfo _appData = null!;
+
+    [TestInitialize]
+    public void Setup()
+    {
+        _appData = _tempDirectory.CreateSubdirectory("Contoso.Notes_8wekyb3d8bbwe");
+    }
+
+    private FileInfo WriteLog(string relativePath, string content)
+    {
+        var file = new FileInfo(Path.Combine(_appData.FullName, relativePath.Replace('\\', Path.DirectorySeparatorChar)));
+        file.Directory!.Create();
+        File.WriteAllText(file.FullName, content);
+        return file;
+    }
+
+    [TestMethod]
+    public void FindLogFiles_ReadsLogFoldersAndLogFilesOfRedirectedFolders()
+    {
+        // Arrange
+        WriteLog(@"LocalState\Logs\notes.log", "");
+        WriteLog(@"LocalState\Logs\crash\dump.txt", "");
+        WriteLog(@"LocalCache\Roaming\Notes\logs\main.log", "");
+        WriteLog(@"LocalCache\Roaming\Notes\config.json", "");
+        WriteLog(@"LocalState\notes.db", "");
+
+        // Act
+        var files = LogsService.FindLogFiles(_appData);
+
+        // Assert
+        var names = files.Select(f => Path.GetRelativePath(_appData.FullName, f.FullName).Replace('\\', '/')).Order().ToArray();
+        CollectionAssert.AreEqual(new[] { "LocalCache/Roaming/Notes/logs/main.log", "LocalState/Logs/crash/dump.txt", "LocalState/Logs/notes.log" }, names);
+    }
+
+    [TestMethod]
+    public void IsCurrentLog_SkipsRotatedFiles()
+    {
+        Assert.IsTrue(LogsService.IsCurrentLog(new FileInfo("notes.log")));
+        Assert.IsTrue(LogsService.IsCurrentLog(new FileInfo("notes.v2.log")));
+        Assert.IsFalse(LogsService.IsCurrentLog(new FileInfo("notes.1.log")));
+        Assert.IsFalse(LogsService.IsCurrentLog(new FileInfo("dump.txt")));
+    }
+
+    [TestMethod]
+    public async Task TailAsync_WithoutFollowPrintsTheLastLinesOfCurrentLogs()
+    {
+        // Arrange
+        WriteLog(@"LocalState\Logs\notes.log", "one\r\ntwo\nthree\nfour\n");
+        WriteLog(@"LocalState\Logs\notes.1.log", "older\n");
+        var lines = new List<LogLine>();
+
+        // Act
+        await new LogsService().TailAsync(_appData, 2, follow: false, lines.Add, TestContext.CancellationToken);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "three", "four" }, lines.Select(l => l.Text).ToArray());
+    }
+
+    [TestMethod]
+    public async Task TailAsync_FollowsNewLinesAcrossRotationAndNewFiles()
+    {
+        // Arrange
+        var log = WriteLog(@"LocalState\Logs\notes.log", "started\n");
+        var lines = new ConcurrentQueue<string>();
+        using var cancellation = CancellationTokenSource.CreateLinkedTokenSource(TestContext.CancellationToken);
+        var service = new LogsService { PollInterval = TimeSpan.FromMilliseconds(10) };
+        var tail = service.TailAsync(_appData, 10, follow: true, line => lines.Enqueue($"{line.File.Name}: {line.Text}"), cancellation.Token);
+
+        async Task WaitForAsync(string expected)
+        {
+            for (var i = 0; i < 500 && !lines.Contains(expected); i++)
+            {
+                await Task.Delay(10, TestContext.CancellationToken);
+            }
+        }
+
+        // Act
+        await File.AppendAllTextAsync(log.FullName, "opened a no", TestContext.CancellationToken);
+        await WaitForAsync("notes.log: started");
+        await File.AppendAllTextAsync(log.FullName, "te\nsaved\n", TestContext.CancellationToken);
+        await WaitForAsync("notes.log: saved");
+        await File.AppendAllTextAsync(log.FullName, "before rotation\n", TestContext.CancellationToken);
+        File.Move(log.FullName, Path.Combine(log.DirectoryName!, "notes.1.log"));
+        await File.WriteAllTextAsync(log.FullName, "after\n", TestContext.CancellationToken);
+        WriteLog(@"LocalCache\Roaming\Notes\logs\main.log", "renderer\n");
+        await WaitForAsync("notes.log: after");
+        await WaitForAsync("main.log: renderer");
+        await cancellation.CancelAsync();
+        try
+        {
+            await tail;
+        }
+        catch (OperationCanceledException)
+        {
+        }
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { "notes.log: started", "notes.log: opened a note", "notes.log: saved", "notes.log: before rotation", "notes.log: after", "main.log: renderer" },
+            lines.ToArray());
+    }
+
+    [TestMethod]
+    public void WriteCollection_KeepsThePathsInTheAppDataFolder()
+    {
+        // Arrange
+        var files = new List<FileInfo>
+        {
+            WriteLog(@"LocalState\Logs\notes.log", "current\n"),
+            WriteLog(@"LocalState\Logs\notes.1.log", "older\n"),
+        };
+        var output = new FileInfo(Path.Combine(_tempDirectory.FullName, "out", "logs.zip"));
+
+        // Act
+        LogCollection collection;
+        using (File.Open(files[0].FullName, FileMode.Open, FileAccess.ReadWrite, FileShare.ReadWrite))
+        {
+            collection = LogsService.WriteCollection(_appData, files, output);
+        }
+
+        // Assert
+        Assert.AreEqual(2, collection.FileCount);
+        Assert.AreEqual(14, collection.Size);
+        using var zip = ZipFile.OpenRead(output.FullName);
+        CollectionAssert.AreEqual(new[] { "LocalState/Logs/notes.log", "LocalState/Logs/notes.1.log" }, zip.Entries.Select(e => e.FullName).ToArray());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ManifestExplainTests : BaseCommandTests
+{
+    public ManifestExplainTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateManifest(string ignorableNamespaces)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, $"""
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10"
+                     xmlns:rescap="http://schemas.microsoft.com/windows/2019/manifest/foundation/windows10/restrictedcapabilities"
+                     xmlns:desktop6="http://schemas.microsoft.com/appx/manifest/desktop/windows10/6"
+                     IgnorableNamespaces="{ignorableNamespaces}">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                <PublisherDisplayName>Contoso</PublisherDisplayName>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Dependencies>
+                <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.17763.0" MaxVersionTested="10.0.22621.0" />
+              </Dependencies>
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />
+                  <Extensions>
+                    <desktop6:Extension Category="windows.environmentVariables">
+                      <desktop6:EnvironmentVariables>
+                        <desktop6:EnvironmentVariable Name="CONTOSO_HOME" Value="C:\Contoso" />
+                      </desktop6:EnvironmentVariables>
+                    </desktop6:Extension>
+                  </Extensions>
+                </Application>
+              </Applications>
+              <Capabilities>
+                <rescap:Capability Name="runFullTrust" />
+              </Capabilities>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private async Task<ManifestExplanation> ExplainAsync(FileInfo manifest, bool runChecks)
+    {
+        var service = GetRequiredService<IManifestExplainService>();
+        return await service.ExplainAsync(manifest, null, runChecks, allowScriptAnalyzers: false, TestTaskContext, TestContext.CancellationToken);
+    }
+
+    private static string CommentBefore(XDocument document, string localName)
+    {
+        var element = document.Descendants().First(e => e.Name.LocalName == localName);
+        var node = element.PreviousNode is XText ? element.PreviousNode.PreviousNode : element.PreviousNode;
+        return (node as XComment)?.Value ?? "";
+    }
+
+    [TestMethod]
+    public async Task ExplainAsync_AnnotatesElementsAndStaysValidXml()
+    {
+        // Arrange
+        var manifest = CreateManifest("uap rescap");
+
+        // Act
+        var explanation = await ExplainAsync(manifest, runChecks: false);
+
+        // Assert
+        var document = XDocument.Parse(explanation.Text);
+        Assert.Contains("Publisher must equal the subject of the signing certificate", CommentBefore(document, "Identity"));
+        Assert.Contains("runFullTrust", CommentBefore(document, "Capability"));
+        Assert.Contains("windows.environmentVariables", CommentBefore(document, "Extension"));
+        Assert.Contains("Windows 10 version 2004", CommentBefore(document, "Extension"));
+        Assert.AreEqual(document.Descendants().Count(), XDocument.Load(manifest.FullName).Descendants().Count(), "Comments don't change the elements");
+    }
+
+    [TestMethod]
+    public async Task ExplainAsync_WarnsAboutSchemasNewerThanMinVersionUnlessIgnorable()
+    {
+        // Arrange
+        var strict = CreateManifest("uap rescap");
+
+        // Act
+        var strictExplanation = await ExplainAsync(strict, runChecks: false);
+        var ignorable = CreateManifest("uap rescap desktop6");
+        var ignorableExplanation = await ExplainAsync(ignorable, runChecks: false);
+
+        // Assert
+        Assert.IsTrue(strictExplanation.Notes.Any(n => n.Rule == "manifest/version" && n.Severity == AccessibilitySeverity.Warning));
+        Assert.IsFalse(ignorableExplanation.Notes.Any(n => n.Rule == "manifest/version"));
+        Assert.Contains("ignored by older releases", ignorableExplanation.Text);
+    }
+
+    [TestMethod]
+    public async Task ExplainAsync_PlacesAnalyzeIssuesOnTheirElements()
+    {
+        // Arrange
+        var manifest = CreateManifest("uap rescap desktop6");
+
+        // Act
+        var explanation = await ExplainAsync(manifest, runChecks: true);
+
+        // Assert
+        var document = XDocument.Parse(explanation.Text);
+        Assert.Contains("error (a11y/asset)", CommentBefore(document, "Logo"));
+        Assert.Contains("error (scales/asset)", CommentBefore(document, "Logo"));
+        Assert.Contains("(a11y/asset)", CommentBefore(document, "VisualElements"));
+        Assert.IsTrue(explanation.Notes.Any(n => n.Rule.StartsWith("splash/", StringComparison.Ordinal)));
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Text.Json.Nodes;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ManifestSchemaTests : BaseCommandTests
+{
+    public ManifestSchemaTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static XElement CreateManifest(string application, string minVersion = "10.0.17763.0", string capabilities = "<rescap:Capability Name=\"runFullTrust\" />", string logo = @"Assets\StoreLogo.png")
+    {
+        return XDocument.Parse($"""
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10"
+                     xmlns:uap10="http://schemas.microsoft.com/appx/manifest/uap/windows10/10"
+                     xmlns:rescap="http://schemas.microsoft.com/windows/2019/manifest/foundation/windows10/restrictedcapabilities"
+                     IgnorableNamespaces="uap uap10 rescap">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.2.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                <PublisherDisplayName>Contoso</PublisherDisplayName>
+                <Logo>{logo}</Logo>
+              </Properties>
+              <Dependencies>
+                <TargetDeviceFamily Name="Windows.Desktop" MinVersion="{minVersion}" MaxVersionTested="10.0.22621.0" />
+              </Dependencies>
+              <Resources>
+                <Resource Language="en-us" />
+              </Resources>
+              <Applications>
+                {application}
+              </Applications>
+              <Capabilities>
+                {capabilities}
+              </Capabilities>
+            </Package>
+            """).Root!;
+    }
+
+    private const string VisualElements = """<uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />""";
+
+    private static List<(string Element, string Rule, AccessibilitySeverity Severity, string Message)> CheckStructure(XElement root)
+    {
+        var notes = new List<(string, string, AccessibilitySeverity, string)>();
+        AppxManifestSchema.CheckStructure(root, (element, rule, severity, message) => notes.Add((element.Name.LocalName, rule, severity, message)));
+        return notes;
+    }
+
+    [TestMethod]
+    public void CheckStructure_AcceptsAValidDesktopApp()
+    {
+        // Arrange
+        var root = CreateManifest($"""<Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">{VisualElements}</Application>""");
+
+        // Act
+        var notes = CheckStructure(root);
+
+        // Assert
+        Assert.IsEmpty(notes);
+    }
+
+    [TestMethod]
+    public void CheckStructure_ReportsMissingAndMalformedValues()
+    {
+        // Arrange
+        var root = CreateManifest("""<Application Id="1Notes"><uap:VisualElements DisplayName="Contoso Notes" /></Application>""", minVersion: "10.0.17763");
+        root.Element(XName.Get("Identity", AppxManifestSchema.FoundationNamespace))!.SetAttributeValue("Version", "1.2.70000.0");
+        root.Element(XName.Get("Resources", AppxManifestSchema.FoundationNamespace))!.RemoveNodes();
+
+        // Act
+        var notes = CheckStructure(root);
+
+        // Assert
+        Assert.IsTrue(notes.All(n => n.Rule == "manifest/schema" && n.Severity == AccessibilitySeverity.Error));
+        Assert.IsTrue(notes.Any(n => n.Element == "Identity" && n.Message.StartsWith("Version '1.2.70000.0'", StringComparison.Ordinal)));
+        Assert.IsTrue(notes.Any(n => n.Element == "TargetDeviceFamily" && n.Message.StartsWith("MinVersion '10.0.17763'", StringComparison.Ordinal)));
+        Assert.IsTrue(notes.Any(n => n.Element == "Resources"));
+        Assert.IsTrue(notes.Any(n => n.Element == "Application" && n.Message.StartsWith("Id '1Notes'", StringComparison.Ordinal)));
+        Assert.IsTrue(notes.Any(n => n.Element == "Application" && n.Message == "Application has no Executable, which the schema requires"));
+        Assert.IsTrue(notes.Any(n => n.Element == "VisualElements" && n.Message == "VisualElements has no Square44x44Logo, which the schema requires"));
+    }
+
+    [TestMethod]
+    public void CheckStructure_ReportsUap10DesktopAppsThatOlderReleasesStartInTheAppContainer()
+    {
+        // Arrange
+        var application = $"""<Application Id="Notes" Executable="Notes.exe" uap10:TrustLevel="mediumIL" uap10:RuntimeBehavior="packagedClassicApp">{VisualElements}</Application>""";
+
+        // Act
+        var older = CheckStructure(CreateManifest(application, capabilities: ""));
+        var newer = CheckStructure(CreateManifest(application, minVersion: "10.0.19041.0"));
+
+        // Assert
+        StringAssert.Contains(older.Single(n => n.Rule == "manifest/entrypoint").Message, "EntryPoint=\"Windows.FullTrustApplication\"");
+        Assert.AreEqual(AccessibilitySeverity.Error, older.Single(n => n.Rule == "manifest/capability").Severity);
+        Assert.IsEmpty(newer);
+    }
+
+    [TestMethod]
+    public void CheckStructure_ReportsAFullTrustEntryPointInTheAppContainer()
+    {
+        // Arrange
+        var root = CreateManifest($"""<Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication" uap10:TrustLevel="appContainer" uap10:RuntimeBehavior="windowsApp">{VisualElements}</Application>""");
+        root.Descendants().Single(e => e.Name.LocalName == "TargetDeviceFamily").SetAttributeValue("Name", "Windows.Universal");
+
+        // Act
+        var notes = CheckStructure(root);
+
+        // Assert
+        StringAssert.Contains(notes.Single(n => n.Rule == "manifest/entrypoint").Message, "TrustLevel appContainer");
+        Assert.AreEqual(AccessibilitySeverity.Warning, notes.Single(n => n.Rule == "manifest/device-family").Severity);
+    }
+
+    [TestMethod]
+    public void CheckLogos_ResolvesResourcesInReswFilesAndResourcesPri()
+    {
+        // Arrange
+        var application = """<Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication"><uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="ms-resource:TileLogo" Square44x44Logo="ms-resource:/Files/Assets/AppIcon.png" /></Application>""";
+        var root = CreateManifest(application, logo: "ms-resource:StoreLogo");
+        var notes = new List<(string Subject, AccessibilitySeverity Severity, string Message)>();
+        void Check() => AppxManifestSchema.CheckLogos(root, _tempDirectory, (element, rule, severity, subject, message) => notes.Add((subject, severity, message)));
+
+        // Act
+        Check();
+        var withoutResources = notes.ToList();
+        notes.Clear();
+        var strings = _tempDirectory.CreateSubdirectory(Path.Combine("Strings", "en-us"));
+        File.WriteAllText(Path.Combine(strings.FullName, "Resources.resw"), """<root><data name="StoreLogo" xml:space="preserve"><value>Assets\StoreLogo.png</value></data></root>""");
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "resources.pri"), [.. Encoding.ASCII.GetBytes("mrm_pri2"), 0, .. Encoding.Unicode.GetBytes("TileLogo")]);
+        Check();
+
+        // Assert
+        Assert.IsTrue(withoutResources.Where(n => n.Subject != "Square44x44Logo").All(n => n.Severity == AccessibilitySeverity.Warning));
+        var remaining = notes.Single();
+        Assert.AreEqual("Square44x44Logo", remaining.Subject, "The file resource doesn't exist");
+        Assert.AreEqual(AccessibilitySeverity.Error, remaining.Severity);
+    }
+
+    [TestMethod]
+    public void CheckLogos_ReportsPathsOutsideThePackage()
+    {
+        // Arrange
+        var root = CreateManifest($"""<Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">{VisualElements}</Application>""", logo: @"..\Shared\StoreLogo.png");
+        var notes = new List<string>();
+
+        // Act
+        AppxManifestSchema.CheckLogos(root, _tempDirectory, (element, rule, severity, subject, message) => notes.Add(message));
+
+        // Assert
+        StringAssert.StartsWith(notes.Single(), @"..\Shared\StoreLogo.png isn't a path in the package");
+    }
+
+    [TestMethod]
+    public void ToJson_ListsNewFindingsFirstWithWhetherTheBaselineRecordsThem()
+    {
+        // Arrange
+        var result = new ValidationResult(
+            [new ValidationFinding(AccessibilitySeverity.Warning, "manifest/device-family", "appxmanifest.xml", "Package/Dependencies/TargetDeviceFamily[Windows.Desktop]", null, "MinVersion is higher")],
+            [new ValidationFinding(AccessibilitySeverity.Error, "a11y/asset", "appxmanifest.xml", "Package/Properties/Logo", "Logo", "Missing")],
+            2);
+
+        // Act
+        var json = JsonNode.Parse(ValidationService.ToJson(result, AccessibilitySeverity.Warning))!;
+
+        // Assert
+        Assert.IsFalse(json["passed"]!.GetValue<bool>());
+        Assert.AreEqual(2, json["fixed"]!.GetValue<int>());
+        var findings = json["findings"]!.AsArray();
+        Assert.HasCount(2, findings);
+        Assert.AreEqual("manifest/device-family", findings[0]!["rule"]!.GetValue<string>());
+        Assert.IsFalse(findings[0]!["baselined"]!.GetValue<bool>());
+        Assert.AreEqual("error", findings[1]!["severity"]!.GetValue<string>());
+        Assert.IsTrue(findings[1]!["baselined"]!.GetValue<bool>());
+    }
+}
@@ -2,6 +2,8 @@
 // Licensed under the MIT License.
 
 using WinApp.Cli.Commands;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Tests;
 
@@ -258,4 +260,95 @@ public class ManifestUpdateAssetsCommandTests : BaseCommandTests
         var assetsDir = Path.Combine(_tempDirectory.FullName, "Assets");
         Assert.IsTrue(Directory.Exists(assetsDir), "Assets directory should be created");
     }
+
+    [TestMethod]
+    public async Task ManifestUpdateAssetsCommandShouldDrawBadgeOnGeneratedAssets()
+    {
+        // Arrange
+        var updateAssetsCommand = GetRequiredService<ManifestUpdateAssetsCommand>();
+        var args = new[]
+        {
+            _testImagePath,
+            "--manifest", _testManifestPath,
+            "--badge", "BETA",
+            "--badge-color", "#107C10"
+        };
+
+        // Act
+        var exitCode = await updateAssetsCommand.Parse(args).InvokeAsync();
+
+        // Assert
+        Assert.AreEqual(0, exitCode, "Update-assets command should complete successfully with a badge");
+        var assetsDir = Path.Combine(_tempDirectory.FullName, "Assets");
+        foreach (var asset in new[] { "Square150x150Logo.scale-200.png", "Wide310x150Logo.png", "Square44x44Logo.targetsize-16_altform-unplated.png" })
+        {
+            using var bitmap = new System.Drawing.Bitmap(Path.Combine(assetsDir, asset));
+            Assert.AreEqual(System.Drawing.ColorTranslator.FromHtml("#107C10").ToArgb(), bitmap.GetPixel(0, bitmap.Height - 1).ToArgb(), $"{asset} should have the ribbon at the bottom");
+            Assert.AreEqual(0, bitmap.GetPixel(0, 0).A, $"{asset} should keep the image above the ribbon");
+        }
+    }
+
+    [TestMethod]
+    public async Task ManifestUpdateAssetsCommandShouldFailWithInvalidBadgeColor()
+    {
+        // Arrange
+        var updateAssetsCommand = GetRequiredService<ManifestUpdateAssetsCommand>();
+        var args = new[]
+        {
+            _testImagePath,
+            "--manifest", _testManifestPath,
+            "--badge", "DEV",
+            "--badge-color", "not-a-color"
+        };
+
+        // Act
+        var exitCode = await updateAssetsCommand.Parse(args).InvokeAsync();
+
+        // Assert
+        Assert.AreEqual(1, exitCode, "Update-assets command should fail with an invalid badge color");
+    }
+
+    [TestMethod]
+    public async Task ApplyBadgeShouldReturnOriginalsAndSkipGlyphsAndHighContrast()
+    {
+        // Arrange
+        var manifestService = GetRequiredService<IManifestService>();
+        await manifestService.UpdateManifestAssetsAsync(new FileInfo(_testManifestPath), new FileInfo(_testImagePath), null, TestTaskContext, TestContext.CancellationToken);
+        var assetsDir = Path.Combine(_tempDirectory.FullName, "Assets");
+        var logo = Path.Combine(assetsDir, "Square150x150Logo.png");
+        var highContrast = Path.Combine(assetsDir, "Square150x150Logo.contrast-black.png");
+        File.Copy(logo, highContrast);
+        var original = File.ReadAllBytes(logo);
+
+        // Act
+        var originals = await manifestService.ApplyBadgeAsync(new FileInfo(_testManifestPath), new AssetBadge("DEV", null), TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        CollectionAssert.AreEqual(original, originals[logo]);
+        CollectionAssert.AreNotEqual(original, File.ReadAllBytes(logo));
+        Assert.IsFalse(originals.ContainsKey(highContrast), "High contrast variants should keep their palette");
+        Assert.IsTrue(PngHelper.IsFullyTransparent(highContrast));
+        Assert.IsTrue(originals.Keys.Any(k => k.EndsWith("Square44x44Logo.targetsize-256_altform-unplated.png", StringComparison.OrdinalIgnoreCase)));
+    }
+
+    [TestMethod]
+    public void ConfigServiceShouldRoundTripBadges()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig
+        {
+            Badges = [new() { Profile = "Beta", Text = "BETA", Color = "#107C10" }, new() { Profile = "Dev", Text = "DEV" }],
+        };
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual("BETA", loaded.GetBadge("beta")?.Text);
+        Assert.AreEqual("#107C10", loaded.GetBadge("Beta")?.Color);
+        Assert.IsNull(loaded.GetBadge("Dev")?.Color);
+        Assert.IsNull(loaded.GetBadge("Release"));
+    }
 }
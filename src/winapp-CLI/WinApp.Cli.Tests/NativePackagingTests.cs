@@ -0,0 +1,523 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class NativePackagingTests : BaseCommandTests
+{
+    private const string TestManifest = @"<?xml version=""1.0"" encoding=""utf-8""?>
+<Package xmlns=""http://schemas.microsoft.com/appx/manifest/foundation/windows10"">
+  <Identity Name=""TestPackage"" Publisher=""CN=TestPublisher"" Version=""1.0.0.0"" />
+</Package>";
+
+    private DirectoryInfo _layoutDirectory = null!;
+    private byte[] _largeFileContent = null!;
+
+    [TestInitialize]
+    public async Task Setup()
+    {
+        _layoutDirectory = _tempDirectory.CreateSubdirectory("layout");
+        await File.WriteAllTextAsync(Path.Combine(_layoutDirectory.FullName, "appxmanifest.xml"), TestManifest, TestContext.CancellationToken);
+        await File.WriteAllTextAsync(Path.Combine(_layoutDirectory.FullName, "TestApp.exe"), "fake exe content", TestContext.CancellationToken);
+
+        var assets = _layoutDirectory.CreateSubdirectory("Assets");
+        await File.WriteAllBytesAsync(Path.Combine(assets.FullName, "Empty File.png"), [], TestContext.CancellationToken);
+
+        // Spans three 64 KB blocks, the last one partial
+        _largeFileContent = new byte[(AppxBlockMap.BlockSize * 2) + 1234];
+        RandomNumberGenerator.Fill(_largeFileContent.AsSpan(0, AppxBlockMap.BlockSize));
+        await File.WriteAllBytesAsync(Path.Combine(assets.FullName, "data.bin"), _largeFileContent, TestContext.CancellationToken);
+    }
+
+    [TestMethod]
+    public async Task PackAsyncShouldProduceReadableArchiveWithFootprintFiles()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+
+        // Act
+        var result = await nativePackagingService.PackAsync(_layoutDirectory, packagePath, TestTaskContext, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(4, result.PayloadFileCount);
+        using var archive = ZipFile.OpenRead(packagePath.FullName);
+        var names = archive.Entries.Select(e => e.FullName).ToList();
+        CollectionAssert.Contains(names, "AppxManifest.xml", "Manifest should be stored with its canonical name");
+        CollectionAssert.Contains(names, "Assets/Empty%20File.png");
+        Assert.AreEqual(AppxBlockMap.PartName, names[^2]);
+        Assert.AreEqual(AppxContentTypes.PartName, names[^1]);
+
+        await using var dataStream = await archive.GetEntry("Assets/data.bin")!.OpenAsync(TestContext.CancellationToken);
+        using var data = new MemoryStream();
+        await dataStream.CopyToAsync(data, TestContext.CancellationToken);
+        CollectionAssert.AreEqual(_largeFileContent, data.ToArray());
+    }
+
+    [TestMethod]
+    public async Task PackAsyncShouldWriteBlockMapMatchingArchiveRecords()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+
+        // Act
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+
+        // Assert
+        await using var stream = packagePath.OpenRead();
+        var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(stream, TestContext.CancellationToken);
+        var blockMapEntry = centralDirectory.Entries.Single(e => e.Name == AppxBlockMap.PartName);
+        var blockMap = AppxBlockMap.Parse(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, blockMapEntry, TestContext.CancellationToken))).Files;
+
+        Assert.HasCount(4, blockMap);
+
+        var dataFile = blockMap.Single(f => f.Name == @"Assets\data.bin");
+        Assert.AreEqual(_largeFileContent.Length, dataFile.Size);
+        Assert.HasCount(3, dataFile.Blocks);
+        for (var i = 0; i < dataFile.Blocks.Count; i++)
+        {
+            var block = _largeFileContent.AsSpan(i * AppxBlockMap.BlockSize, Math.Min(AppxBlockMap.BlockSize, _largeFileContent.Length - (i * AppxBlockMap.BlockSize)));
+            CollectionAssert.AreEqual(SHA256.HashData(block), dataFile.Blocks[i].Hash, $"Block {i} hash mismatch");
+        }
+
+        var dataEntry = centralDirectory.Entries.Single(e => e.Name == "Assets/data.bin");
+        Assert.AreEqual(dataEntry.CompressedSize, dataFile.Blocks.Sum(b => b.CompressedSize ?? 0), "Compressed block sizes should add up to the entry size");
+        Assert.AreEqual(await ZipPackageReader.ReadLocalHeaderSizeAsync(stream, dataEntry, TestContext.CancellationToken), dataFile.LfhSize);
+
+        var emptyFile = blockMap.Single(f => f.Name == @"Assets\Empty File.png");
+        Assert.AreEqual(0L, emptyFile.Size);
+        Assert.IsEmpty(emptyFile.Blocks);
+    }
+
+    [TestMethod]
+    public async Task PackAsyncShouldProduceSameBlocksForAnyThreadCount()
+    {
+        // Arrange
+        var singleThreadPath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Single.msix"));
+        var parallelPath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Parallel.msix"));
+
+        // Act
+        var single = await AppxPacker.PackAsync(_layoutDirectory, singleThreadPath, new AppxPackOptions(Threads: 1), TestContext.CancellationToken);
+        var parallel = await AppxPacker.PackAsync(_layoutDirectory, parallelPath, new AppxPackOptions(Threads: 8), TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(1, single.Threads);
+        Assert.AreEqual(8, parallel.Threads);
+        Assert.AreEqual(single.PayloadSize, parallel.PayloadSize);
+
+        var singleResult = await AppxPackageVerifier.VerifyAsync(singleThreadPath, TestContext.CancellationToken);
+        var parallelResult = await AppxPackageVerifier.VerifyAsync(parallelPath, TestContext.CancellationToken);
+        Assert.IsEmpty(parallelResult.Errors, string.Join(Environment.NewLine, parallelResult.Errors));
+        Assert.AreEqual(singleResult.VerifiedFileCount, parallelResult.VerifiedFileCount);
+
+        using var archive = ZipFile.OpenRead(parallelPath.FullName);
+        await using var dataStream = await archive.GetEntry("Assets/data.bin")!.OpenAsync(TestContext.CancellationToken);
+        using var data = new MemoryStream();
+        await dataStream.CopyToAsync(data, TestContext.CancellationToken);
+        CollectionAssert.AreEqual(_largeFileContent, data.ToArray());
+    }
+
+    [TestMethod]
+    public async Task PackAsyncShouldReuseCompressedBlocksAcrossVariants()
+    {
+        // Arrange - a second variant that only differs by its manifest
+        var arm64Layout = _tempDirectory.CreateSubdirectory("layout-arm64");
+        foreach (var file in _layoutDirectory.EnumerateFiles("*", SearchOption.AllDirectories))
+        {
+            var target = new FileInfo(Path.Combine(arm64Layout.FullName, Path.GetRelativePath(_layoutDirectory.FullName, file.FullName)));
+            target.Directory!.Create();
+            file.CopyTo(target.FullName);
+        }
+        await File.WriteAllTextAsync(
+            Path.Combine(arm64Layout.FullName, "appxmanifest.xml"),
+            TestManifest.Replace(@"Version=""1.0.0.0""", @"Version=""1.0.0.0"" ProcessorArchitecture=""arm64""", StringComparison.Ordinal),
+            TestContext.CancellationToken);
+
+        var cacheDirectory = new DirectoryInfo(Path.Combine(_tempDirectory.FullName, "blocks"));
+        var cache = new AppxBlockCache(cacheDirectory);
+
+        // Act
+        var x64 = await AppxPacker.PackAsync(_layoutDirectory, new FileInfo(Path.Combine(_tempDirectory.FullName, "x64.msix")), new AppxPackOptions(BlockCache: cache), TestContext.CancellationToken);
+        var arm64Path = new FileInfo(Path.Combine(_tempDirectory.FullName, "arm64.msix"));
+        var arm64 = await AppxPacker.PackAsync(arm64Layout, arm64Path, new AppxPackOptions(BlockCache: cache), TestContext.CancellationToken);
+
+        // A later run starts with an empty in-memory cache and finds the blocks on disk
+        var repack = await AppxPacker.PackAsync(_layoutDirectory, new FileInfo(Path.Combine(_tempDirectory.FullName, "x64-again.msix")), new AppxPackOptions(BlockCache: new AppxBlockCache(cacheDirectory)), TestContext.CancellationToken);
+
+        // Assert - TestApp.exe and the three data.bin blocks are shared; the manifests differ
+        Assert.AreEqual(0L, x64.ReusedBlocks);
+        Assert.AreEqual(4L, arm64.ReusedBlocks);
+        Assert.AreEqual(x64.PayloadBlocks - 1, arm64.ReusedBlocks);
+        Assert.AreEqual(repack.PayloadBlocks, repack.ReusedBlocks);
+
+        var verification = await AppxPackageVerifier.VerifyAsync(arm64Path, TestContext.CancellationToken);
+        Assert.IsEmpty(verification.Errors, string.Join(Environment.NewLine, verification.Errors));
+    }
+
+    [TestMethod]
+    public void BlockCacheShouldDiscardCorruptDiskEntries()
+    {
+        // Arrange
+        var cacheDirectory = new DirectoryInfo(Path.Combine(_tempDirectory.FullName, "blocks"));
+        var hash = SHA256.HashData(Encoding.UTF8.GetBytes("block"));
+        new AppxBlockCache(cacheDirectory).Add(hash, [1, 2, 3, 4]);
+
+        var entry = cacheDirectory.EnumerateFiles("*", SearchOption.AllDirectories).Single();
+        var content = File.ReadAllBytes(entry.FullName);
+        content[^1] ^= 0xFF;
+        File.WriteAllBytes(entry.FullName, content);
+
+        // Act
+        var found = new AppxBlockCache(cacheDirectory).TryGet(hash, out _);
+
+        // Assert
+        Assert.IsFalse(found);
+        Assert.IsFalse(File.Exists(entry.FullName), "Corrupt entries should be deleted");
+    }
+
+    [TestMethod]
+    public void Crc32CombineShouldMatchCrcOfConcatenatedData()
+    {
+        var first = Encoding.UTF8.GetBytes("The quick brown fox ");
+        var second = Encoding.UTF8.GetBytes("jumps over the lazy dog");
+
+        var combined = Crc32.Combine(Crc32.Compute(first), Crc32.Compute(second), second.Length);
+
+        Assert.AreEqual(Crc32.Compute([.. first, .. second]), combined);
+        Assert.AreEqual(0x414FA339u, combined);
+    }
+
+    [TestMethod]
+    public void ToZipNameShouldPercentEncodeReservedAndNonAsciiCharacters()
+    {
+        Assert.AreEqual("Assets/My%20Logo.png", AppxPacker.ToZipName("Assets/My Logo.png"));
+        Assert.AreEqual("caf%C3%A9/100%25.txt", AppxPacker.ToZipName("café/100%.txt"));
+        Assert.AreEqual(@"café\100%.txt", AppxBlockMap.ToBlockMapName("caf%C3%A9/100%25.txt"));
+    }
+
+    [TestMethod]
+    public void EncodeCentralDirectoryShouldMoveOnlyOverflowingFieldsToZip64ExtraField()
+    {
+        // Arrange - 0xFFFFFFFE still fits; 0xFFFFFFFF is the Zip64 marker and must not be written as a value
+        var fits = new ZipEntryRecord("small.bin", ZipFormat.MethodStored, 0, 0, 0, uint.MaxValue - 1, uint.MaxValue - 1, 0);
+        var overflows = new ZipEntryRecord("large.bin", ZipFormat.MethodDeflated, 0, 0, 0, 1024, uint.MaxValue, 5L * 1024 * 1024 * 1024);
+
+        // Act
+        var centralDirectory = ZipFormat.EncodeCentralDirectory([fits, overflows]);
+        var (entries, rawRecords) = ZipPackageReader.ParseCentralDirectory(centralDirectory, 2, 0);
+
+        // Assert
+        Assert.AreEqual(ZipFormat.CentralDirectoryHeaderFixedSize + "small.bin".Length, rawRecords[0].Length);
+        Assert.AreEqual(ZipFormat.CentralDirectoryHeaderFixedSize + "large.bin".Length + 4 + 16, rawRecords[1].Length, "Only the uncompressed size and offset should be in the extra field");
+        Assert.AreEqual(fits, entries[0]);
+        Assert.AreEqual(overflows, entries[1]);
+    }
+
+    [TestMethod]
+    public void EncodeEndOfCentralDirectoryShouldAddZip64RecordsAtEntryCountLimit()
+    {
+        Assert.HasCount(ZipFormat.EndOfCentralDirectorySize, ZipFormat.EncodeEndOfCentralDirectory(ushort.MaxValue - 1, 1024, 1024));
+        Assert.HasCount(
+            ZipFormat.Zip64EndOfCentralDirectorySize + ZipFormat.Zip64EndOfCentralDirectoryLocatorSize + ZipFormat.EndOfCentralDirectorySize,
+            ZipFormat.EncodeEndOfCentralDirectory(ushort.MaxValue, 1024, 1024));
+        Assert.HasCount(
+            ZipFormat.Zip64EndOfCentralDirectorySize + ZipFormat.Zip64EndOfCentralDirectoryLocatorSize + ZipFormat.EndOfCentralDirectorySize,
+            ZipFormat.EncodeEndOfCentralDirectory(1, 1024, uint.MaxValue));
+    }
+
+    [TestMethod]
+    public void RequiresZip64LocalHeaderShouldAccountForDeflateExpansion()
+    {
+        Assert.IsFalse(ZipFormat.RequiresZip64LocalHeader(0));
+        Assert.IsFalse(ZipFormat.RequiresZip64LocalHeader(uint.MaxValue - (1024L * 1024)));
+        Assert.IsTrue(ZipFormat.RequiresZip64LocalHeader(uint.MaxValue - 1L), "Incompressible data close to 4 GB can grow past it");
+        Assert.IsTrue(ZipFormat.RequiresZip64LocalHeader(6L * 1024 * 1024 * 1024));
+        Assert.IsTrue(ZipFormat.RequiresZip64LocalHeader(null), "Streams of unknown length must reserve the Zip64 sizes");
+    }
+
+    [TestMethod]
+    public async Task ZipPackageWriterShouldWriteMoreThan65535Entries()
+    {
+        // Arrange
+        const int entryCount = ushort.MaxValue + 1;
+        using var output = new MemoryStream();
+        var writer = new ZipPackageWriter(output);
+
+        // Act
+        for (var i = 0; i < entryCount; i++)
+        {
+            await writer.AddEntryAsync($"f/{i}.txt", [], compress: true, TestContext.CancellationToken);
+        }
+        await writer.FinishAsync(TestContext.CancellationToken);
+
+        // Assert
+        var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(output, TestContext.CancellationToken);
+        Assert.HasCount(entryCount, centralDirectory.Entries);
+        Assert.AreEqual($"f/{entryCount - 1}.txt", centralDirectory.Entries[^1].Name);
+
+        output.Position = 0;
+        using var archive = new ZipArchive(output, ZipArchiveMode.Read, leaveOpen: true);
+        Assert.HasCount(entryCount, archive.Entries);
+    }
+
+    [TestMethod]
+    public async Task ZipPackageWriterShouldReserveZip64SizesForStreamsOfUnknownLength()
+    {
+        // Arrange - a decompressing stream cannot report its length up front
+        using var gzipped = new MemoryStream();
+        await using (var gzip = new GZipStream(gzipped, CompressionLevel.Fastest, leaveOpen: true))
+        {
+            await gzip.WriteAsync(_largeFileContent, TestContext.CancellationToken);
+        }
+        gzipped.Position = 0;
+        await using var source = new GZipStream(gzipped, CompressionMode.Decompress);
+        using var output = new MemoryStream();
+        var writer = new ZipPackageWriter(output);
+
+        // Act
+        var blockMapFile = await writer.AddEntryAsync("data.bin", source, compress: true, DateTime.Now, TestContext.CancellationToken);
+        await writer.FinishAsync(TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(ZipFormat.GetLocalHeaderSize("data.bin", zip64: true), blockMapFile.LfhSize);
+        Assert.AreEqual(blockMapFile.LfhSize, await ZipPackageReader.ReadLocalHeaderSizeAsync(output, writer.Entries[0], TestContext.CancellationToken));
+        Assert.AreEqual((long)_largeFileContent.Length, blockMapFile.Size);
+
+        output.Position = 0;
+        using var archive = new ZipArchive(output, ZipArchiveMode.Read, leaveOpen: true);
+        await using var entryStream = await archive.Entries[0].OpenAsync(TestContext.CancellationToken);
+        using var data = new MemoryStream();
+        await entryStream.CopyToAsync(data, TestContext.CancellationToken);
+        CollectionAssert.AreEqual(_largeFileContent, data.ToArray());
+    }
+
+    [TestMethod]
+    public async Task SignAsyncShouldAppendAuthenticodeSignature()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+        var (certificatePath, thumbprint) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+
+        // Act - signing twice must replace, not duplicate, the signature
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        using var archive = ZipFile.OpenRead(packagePath.FullName);
+        Assert.AreEqual(AppxPacker.SignaturePartName, archive.Entries[^1].FullName);
+        Assert.AreEqual(1, archive.Entries.Count(e => e.FullName == AppxPacker.SignaturePartName));
+
+        using var contentTypesReader = new StreamReader(await archive.GetEntry(AppxContentTypes.PartName)!.OpenAsync(TestContext.CancellationToken));
+        Assert.Contains("/AppxSignature.p7x", await contentTypesReader.ReadToEndAsync(TestContext.CancellationToken));
+
+        await using var signatureStream = await archive.Entries[^1].OpenAsync(TestContext.CancellationToken);
+        using var signature = new MemoryStream();
+        await signatureStream.CopyToAsync(signature, TestContext.CancellationToken);
+        var p7x = signature.ToArray();
+        Assert.AreEqual("PKCX", Encoding.ASCII.GetString(p7x, 0, 4));
+
+        var signedCms = new SignedCms();
+        signedCms.Decode(p7x.AsSpan(4));
+        Assert.AreEqual("1.3.6.1.4.1.311.2.1.4", signedCms.ContentInfo.ContentType.Value, "Content should be SpcIndirectDataContent");
+        Assert.AreEqual(thumbprint, signedCms.SignerInfos[0].Certificate!.Thumbprint);
+        Assert.AreEqual("2.16.840.1.101.3.4.2.1", signedCms.SignerInfos[0].DigestAlgorithm.Value);
+    }
+
+    [TestMethod]
+    public async Task SignAsyncShouldRejectNonPackageFiles()
+    {
+        // Arrange
+        var exePath = new FileInfo(Path.Combine(_layoutDirectory.FullName, "TestApp.exe"));
+        var (certificatePath, _) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+
+        // Act & Assert
+        var ex = await Assert.ThrowsExactlyAsync<InvalidOperationException>(() =>
+            nativePackagingService.SignAsync(exePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken));
+        Assert.Contains("--engine sdk", ex.Message);
+    }
+
+    [TestMethod]
+    public async Task VerifyAsyncShouldAcceptNativelySignedPackage()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+        var (certificatePath, thumbprint) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+
+        // Act
+        var result = await nativePackagingService.VerifyAsync(packagePath, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsEmpty(result.Errors, string.Join(Environment.NewLine, result.Errors));
+        Assert.IsEmpty(result.Warnings);
+        Assert.AreEqual(4, result.VerifiedFileCount);
+        Assert.IsNotNull(result.Signer);
+        Assert.AreEqual(thumbprint, result.Signer.Thumbprint);
+        Assert.AreEqual("SHA256", result.Signer.DigestAlgorithm);
+    }
+
+    [TestMethod]
+    public async Task VerifyAsyncShouldDetectPayloadModifiedAfterSigning()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+        var (certificatePath, _) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+
+        using (var archive = ZipFile.Open(packagePath.FullName, ZipArchiveMode.Update))
+        {
+            var entry = archive.GetEntry("TestApp.exe")!;
+            entry.Delete();
+            await using var tampered = await archive.CreateEntry("TestApp.exe").OpenAsync(TestContext.CancellationToken);
+            await tampered.WriteAsync(Encoding.UTF8.GetBytes("FAKE EXE CONTENT"), TestContext.CancellationToken);
+        }
+
+        // Act
+        var result = await nativePackagingService.VerifyAsync(packagePath, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsFalse(result.IsValid);
+        Assert.IsTrue(result.Errors.Any(e => e.Contains("TestApp.exe") && e.Contains("does not match the block map")), string.Join(Environment.NewLine, result.Errors));
+        Assert.IsTrue(result.Errors.Any(e => e.StartsWith("AXPC", StringComparison.Ordinal)), "Package digest should no longer match the signature");
+    }
+
+    [TestMethod]
+    public async Task VerifyAsyncShouldWarnForUnsignedPackage()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+
+        // Act
+        var result = await AppxPackageVerifier.VerifyAsync(packagePath, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(result.IsValid);
+        Assert.IsNull(result.Signer);
+        CollectionAssert.Contains(result.Warnings.ToList(), "Package is not signed");
+    }
+
+    [TestMethod]
+    public async Task PackAndSignShouldUseRequestedDigestAlgorithmThroughout()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        var (certificatePath, _) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+
+        // Act
+        await nativePackagingService.PackAsync(_layoutDirectory, packagePath, TestTaskContext, digest: DigestAlgorithm.Sha384, cancellationToken: TestContext.CancellationToken);
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+        var result = await nativePackagingService.VerifyAsync(packagePath, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        await using (var stream = packagePath.OpenRead())
+        {
+            var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(stream, TestContext.CancellationToken);
+            var blockMapEntry = centralDirectory.Entries.Single(e => e.Name == AppxBlockMap.PartName);
+            var blockMap = AppxBlockMap.Parse(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, blockMapEntry, TestContext.CancellationToken)));
+            Assert.AreEqual(AppxBlockMap.Sha384HashMethod, blockMap.HashMethod);
+            Assert.IsTrue(blockMap.Files.SelectMany(f => f.Blocks).All(b => b.Hash.Length == 48), "Every block hash should be SHA-384");
+        }
+
+        Assert.IsEmpty(result.Errors, string.Join(Environment.NewLine, result.Errors));
+        Assert.AreEqual(4, result.VerifiedFileCount);
+        Assert.AreEqual("SHA384", result.Signer?.DigestAlgorithm, "Signature digests should follow the block map algorithm");
+    }
+
+    [TestMethod]
+    public void TargetVersionIssueShouldRejectStrongDigestsBelowWindows10()
+    {
+        // Arrange
+        const string manifest = @"<?xml version=""1.0"" encoding=""utf-8""?>
+<Package xmlns=""http://schemas.microsoft.com/appx/manifest/foundation/windows10"">
+  <Dependencies>
+    <TargetDeviceFamily Name=""Windows.Desktop"" MinVersion=""10.0.17763.0"" MaxVersionTested=""10.0.26100.0"" />
+    <TargetDeviceFamily Name=""Windows.Desktop"" MinVersion=""6.1.7601.0"" MaxVersionTested=""10.0.26100.0"" />
+  </Dependencies>
+</Package>";
+
+        // Act
+        var minVersion = AppxDigestAlgorithms.ReadMinTargetVersion(manifest);
+
+        // Assert
+        Assert.AreEqual(new Version(6, 1, 7601, 0), minVersion);
+        Assert.IsNotNull(AppxDigestAlgorithms.GetTargetVersionIssue(HashAlgorithmName.SHA512, minVersion));
+        Assert.IsNull(AppxDigestAlgorithms.GetTargetVersionIssue(HashAlgorithmName.SHA256, minVersion));
+        Assert.IsNull(AppxDigestAlgorithms.GetTargetVersionIssue(HashAlgorithmName.SHA384, new Version(10, 0, 17763, 0)));
+    }
+
+    [TestMethod]
+    public async Task InspectDigestsAsyncShouldReportAlgorithmsWeakerThanRequired()
+    {
+        // Arrange
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(_layoutDirectory, packagePath, TestContext.CancellationToken);
+        var (certificatePath, _) = CreateTestCertificate();
+        var nativePackagingService = GetRequiredService<INativePackagingService>();
+        await nativePackagingService.SignAsync(packagePath, certificatePath, TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+
+        // Act
+        var report = await nativePackagingService.InspectDigestsAsync(packagePath, TestTaskContext, cancellationToken: TestContext.CancellationToken);
+        var strictReport = await nativePackagingService.InspectDigestsAsync(packagePath, TestTaskContext, DigestAlgorithm.Sha384, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(HashAlgorithmName.SHA256, report.BlockMapAlgorithm);
+        Assert.AreEqual(HashAlgorithmName.SHA256, report.SignatureDigestAlgorithm);
+        Assert.AreEqual("RSA 2048-bit", report.SignatureKey);
+        Assert.AreEqual("sha256RSA", report.CertificateSignatureAlgorithm);
+        Assert.IsNull(report.TimestampDigestAlgorithm);
+        Assert.IsEmpty(report.Issues, string.Join(Environment.NewLine, report.Issues));
+
+        Assert.IsTrue(strictReport.Issues.Any(i => i.StartsWith("Block map hash", StringComparison.Ordinal)), string.Join(Environment.NewLine, strictReport.Issues));
+        Assert.IsTrue(strictReport.Issues.Any(i => i.StartsWith("Signature digest", StringComparison.Ordinal)));
+        Assert.IsTrue(strictReport.Issues.Any(i => i.StartsWith("Certificate signature", StringComparison.Ordinal)));
+    }
+
+    [TestMethod]
+    public void AuditShouldReportNativePackAndSignForNativeEngine()
+    {
+        // Arrange
+        var auditService = GetRequiredService<IToolchainAuditService>();
+
+        // Act
+        var sdkEntries = auditService.GetAuditEntries(PackagingEngine.Sdk);
+        var nativeEntries = auditService.GetAuditEntries(PackagingEngine.Native);
+
+        // Assert
+        Assert.AreEqual("makeappx.exe", sdkEntries.Single(e => e.Operation == "Pack MSIX").ExternalTool);
+        Assert.IsTrue(nativeEntries.Single(e => e.Operation == "Pack MSIX").IsNative);
+        Assert.IsTrue(nativeEntries.Single(e => e.Operation == "Generate block map").IsNative);
+        Assert.IsTrue(nativeEntries.Single(e => e.Operation == "Sign MSIX").IsNative);
+        Assert.IsTrue(nativeEntries.Single(e => e.Operation == "Generate PRI resources").RequiresWindowsSdk, "PRI generation has no native implementation");
+    }
+
+    private (FileInfo Path, string Thumbprint) CreateTestCertificate()
+    {
+        using var rsa = RSA.Create(2048);
+        var request = new CertificateRequest("CN=TestPublisher", rsa, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1);
+        request.CertificateExtensions.Add(new X509EnhancedKeyUsageExtension([new Oid("1.3.6.1.5.5.7.3.3")], critical: false));
+        using var certificate = request.CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(30));
+
+        var path = new FileInfo(Path.Combine(_tempDirectory.FullName, "native-test.pfx"));
+        File.WriteAllBytes(path.FullName, certificate.Export(X509ContentType.Pfx, "password"));
+        return (path, certificate.Thumbprint);
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class PackageLicenseTests : BaseCommandTests
+{
+    private const string WindowsAppSdk = "Microsoft.WindowsAppSDK";
+    private const string Wil = "Microsoft.Windows.ImplementationLibrary";
+
+    private DirectoryInfo _packagesDir = null!;
+
+    public PackageLicenseTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestInitialize]
+    public void Setup()
+    {
+        _packagesDir = _tempDirectory.CreateSubdirectory("packages");
+    }
+
+    private FileInfo CreatePackage(string package, string version, bool requireLicenseAcceptance, string license)
+    {
+        var packageDir = _packagesDir.CreateSubdirectory($"{package}.{version}");
+        var nuspec = new FileInfo(Path.Combine(packageDir.FullName, $"{package}.nuspec"));
+        File.WriteAllText(nuspec.FullName, $"""
+            <?xml version="1.0" encoding="utf-8"?>
+            <package xmlns="http://schemas.microsoft.com/packaging/2013/05/nuspec.xsd">
+              <metadata>
+                <id>{package}</id>
+                <version>{version}</version>
+                <requireLicenseAcceptance>{(requireLicenseAcceptance ? "true" : "false")}</requireLicenseAcceptance>
+                {license}
+                <licenseUrl>https://aka.ms/deprecateLicenseUrl</licenseUrl>
+              </metadata>
+            </package>
+            """);
+        return nuspec;
+    }
+
+    private Dictionary<string, string> CreatePackages()
+    {
+        var nuspec = CreatePackage(WindowsAppSdk, "1.8.250916003", true, "<license type=\"file\">license.txt</license>");
+        File.WriteAllText(Path.Combine(nuspec.DirectoryName!, "license.txt"), "MICROSOFT SOFTWARE LICENSE TERMS");
+        CreatePackage(Wil, "1.0.250325.1", false, "<license type=\"expression\">MIT</license>");
+        return new Dictionary<string, string>(StringComparer.OrdinalIgnoreCase)
+        {
+            [WindowsAppSdk] = "1.8.250916003",
+            [Wil] = "1.0.250325.1",
+        };
+    }
+
+    [TestMethod]
+    public void ReadLicense_ReadsTheLicenseOfPackagesThatRequireAcceptance()
+    {
+        // Arrange
+        var file = CreatePackage("Contoso.File", "1.0.0", true, "<license type=\"file\">LICENSE.txt</license>");
+        File.WriteAllText(Path.Combine(file.DirectoryName!, "LICENSE.txt"), "terms");
+        var expression = CreatePackage("Contoso.Expression", "1.0.0", true, "<license type=\"expression\">MIT OR Apache-2.0</license>");
+        var url = CreatePackage("Contoso.Url", "1.0.0", true, "");
+        var notRequired = CreatePackage("Contoso.Free", "1.0.0", false, "<license type=\"expression\">MIT</license>");
+
+        // Act
+        var fileLicense = PackageLicenseService.ReadLicense(file, "Contoso.File", "1.0.0");
+        var expressionLicense = PackageLicenseService.ReadLicense(expression, "Contoso.Expression", "1.0.0");
+        var urlLicense = PackageLicenseService.ReadLicense(url, "Contoso.Url", "1.0.0");
+
+        // Assert
+        Assert.IsNotNull(fileLicense);
+        Assert.AreEqual("LICENSE.txt", fileLicense.License);
+        Assert.AreEqual(64, fileLicense.Sha256?.Length);
+        Assert.AreEqual(Path.Combine(file.DirectoryName!, "LICENSE.txt"), fileLicense.Location);
+        Assert.AreEqual("MIT OR Apache-2.0", expressionLicense?.License);
+        Assert.AreEqual("https://licenses.nuget.org/MIT%20OR%20Apache-2.0", expressionLicense?.Location);
+        Assert.AreEqual("https://aka.ms/deprecateLicenseUrl", urlLicense?.License);
+        Assert.IsNull(PackageLicenseService.ReadLicense(notRequired, "Contoso.Free", "1.0.0"));
+    }
+
+    [TestMethod]
+    public async Task EnsureAccepted_FailsUntilTheLicenseIsAcceptedAndRecordsIt()
+    {
+        // Arrange
+        var versions = CreatePackages();
+        var licenseService = GetRequiredService<IPackageLicenseService>();
+        var configService = GetRequiredService<IConfigService>();
+
+        // Act
+        var withoutConsent = await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(false, []), TestTaskContext, TestContext.CancellationToken);
+        var otherPackage = await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(false, [Wil]), TestTaskContext, TestContext.CancellationToken);
+        var configAfterRefusal = configService.Exists();
+        var accepted = await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(false, ["microsoft.windowsappsdk"]), TestTaskContext, TestContext.CancellationToken);
+        var afterwards = await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(false, []), TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(1, withoutConsent);
+        Assert.AreEqual(WindowsAppSdk, withoutConsent[0].Package);
+        Assert.HasCount(1, otherPackage);
+        Assert.IsFalse(configAfterRefusal, "Nothing is recorded without consent");
+        Assert.IsEmpty(accepted);
+        Assert.IsEmpty(afterwards, "The record in winapp.yaml accepts the license on later runs");
+
+        var record = configService.Load().Licenses.Single();
+        Assert.AreEqual(WindowsAppSdk, record.Package);
+        Assert.AreEqual("1.8.250916003", record.Version);
+        Assert.AreEqual("license.txt", record.License);
+        Assert.IsNotNull(record.Sha256);
+        Assert.AreEqual(Environment.UserName, record.AcceptedBy);
+        Assert.IsTrue(DateTimeOffset.TryParse(record.AcceptedAt, out _));
+    }
+
+    [TestMethod]
+    public async Task EnsureAccepted_AsksAgainWhenTheLicenseTextChanges()
+    {
+        // Arrange
+        var versions = CreatePackages();
+        var licenseService = GetRequiredService<IPackageLicenseService>();
+        await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(true, []), TestTaskContext, TestContext.CancellationToken);
+
+        var newVersion = CreatePackage(WindowsAppSdk, "1.9.0", true, "<license type=\"file\">license.txt</license>");
+        File.WriteAllText(Path.Combine(newVersion.DirectoryName!, "license.txt"), "MICROSOFT SOFTWARE LICENSE TERMS (REVISED)");
+        versions[WindowsAppSdk] = "1.9.0";
+
+        // Act
+        var pending = await licenseService.EnsureAcceptedAsync(_packagesDir, versions, new LicenseConsent(false, []), TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(1, pending);
+        Assert.AreEqual("1.9.0", pending[0].Version);
+    }
+
+    [TestMethod]
+    public void Save_KeepsLicensesOutOfThePackagePins()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.SetVersion(WindowsAppSdk, "1.8.250916003");
+        config.Licenses.Add(new LicenseAcceptanceConfig
+        {
+            Package = WindowsAppSdk,
+            Version = "1.8.250916003",
+            License = "license.txt",
+            Sha256 = "00ff",
+            AcceptedBy = "build agent",
+            AcceptedAt = "2026-10-15T08:00:00Z",
+        });
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.HasCount(1, loaded.Packages);
+        var license = loaded.Licenses.Single();
+        Assert.AreEqual("1.8.250916003", license.Version);
+        Assert.AreEqual("00ff", license.Sha256);
+        Assert.AreEqual("build agent", license.AcceptedBy);
+        Assert.AreEqual("2026-10-15T08:00:00Z", license.AcceptedAt);
+        Assert.HasCount(1, loaded.WithoutPackages().Licenses);
+    }
+}
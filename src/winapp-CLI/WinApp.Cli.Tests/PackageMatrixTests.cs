@@ -0,0 +1,159 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class PackageMatrixTests : BaseCommandTests
+{
+    private const string Manifest = """
+        <?xml version="1.0" encoding="utf-8"?>
+        <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+          <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+          <Properties>
+            <DisplayName>Contoso Notes</DisplayName>
+            <PublisherDisplayName>Contoso</PublisherDisplayName>
+            <Logo>Assets\StoreLogo.png</Logo>
+          </Properties>
+          <Applications>
+            <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+              <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />
+              <Extensions>
+                <uap:Extension Category="windows.protocol">
+                  <uap:Protocol Name="contoso-notes" />
+                </uap:Extension>
+              </Extensions>
+            </Application>
+          </Applications>
+        </Package>
+        """;
+
+    public PackageMatrixTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static WinappConfig CreateConfig() => new()
+    {
+        Matrix = new MatrixConfig
+        {
+            Brandings =
+            {
+                new BrandingConfig { Name = "contoso" },
+                new BrandingConfig
+                {
+                    Name = "fabrikam",
+                    IdentityName = "Fabrikam.Notes",
+                    Publisher = "CN=Fabrikam",
+                    PublisherDisplayName = "Fabrikam",
+                    DisplayName = "Fabrikam Notes",
+                    Assets = "brands/fabrikam",
+                    Protocols = { "fabrikam-notes" }
+                }
+            },
+            Channels =
+            {
+                new ChannelConfig { Name = "stable" },
+                new ChannelConfig { Name = "beta", NameSuffix = ".Beta", DisplayNameSuffix = " (Beta)" }
+            },
+            Architectures = { new MatrixArchitectureConfig { Name = "x64" }, new MatrixArchitectureConfig { Name = "arm64", Input = "bin/arm64" } }
+        }
+    };
+
+    [TestMethod]
+    public void GetVariants_ExpandsAndFiltersTheMatrix()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(CreateConfig());
+        var service = GetRequiredService<IPackageMatrixService>();
+
+        // Act
+        var all = service.GetVariants([]);
+        var fabrikamBeta = service.GetVariants(["fabrikam-beta-x64", "fabrikam-beta-arm64"]);
+        var arm64 = service.GetVariants(["arm64"]);
+
+        // Assert
+        Assert.HasCount(8, all);
+        Assert.AreEqual("contoso-stable-x64", all[0].Name);
+        CollectionAssert.AreEqual(new[] { "fabrikam-beta-x64", "fabrikam-beta-arm64" }, fabrikamBeta.Select(v => v.Name).ToArray());
+        Assert.HasCount(4, arm64);
+        Assert.ThrowsExactly<InvalidOperationException>(() => service.GetVariants(["linux"]));
+    }
+
+    [TestMethod]
+    public void Apply_SetsIdentityDisplayNamesArchitectureAndProtocols()
+    {
+        // Arrange
+        var config = CreateConfig().Matrix!;
+        var variant = new MatrixVariant(config.Brandings[1], config.Channels[1], config.Architectures[1]);
+
+        // Act
+        var root = XDocument.Parse(AppxMatrixVariant.Apply(Manifest, variant)).Root!;
+
+        // Assert
+        XNamespace m = "http://schemas.microsoft.com/appx/manifest/foundation/windows10";
+        XNamespace uap = AppxMatrixVariant.UapNamespace;
+        var identity = root.Element(m + "Identity")!;
+        Assert.AreEqual("Fabrikam.Notes.Beta", identity.Attribute("Name")!.Value);
+        Assert.AreEqual("CN=Fabrikam", identity.Attribute("Publisher")!.Value);
+        Assert.AreEqual("arm64", identity.Attribute("ProcessorArchitecture")!.Value);
+        Assert.AreEqual("Fabrikam Notes (Beta)", root.Element(m + "Properties")!.Element(m + "DisplayName")!.Value);
+        Assert.AreEqual("Fabrikam", root.Element(m + "Properties")!.Element(m + "PublisherDisplayName")!.Value);
+        Assert.AreEqual("Fabrikam Notes (Beta)", root.Descendants(uap + "VisualElements").Single().Attribute("DisplayName")!.Value);
+        CollectionAssert.AreEqual(new[] { "fabrikam-notes" }, root.Descendants(uap + "Protocol").Select(p => p.Attribute("Name")!.Value).ToArray());
+    }
+
+    [TestMethod]
+    public async Task PrepareAsync_OverlaysAssetsAndRestoresThem()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(CreateConfig());
+        var manifestPath = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(manifestPath, Manifest);
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Assets"));
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "Assets", "StoreLogo.png"), [1]);
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "brands", "fabrikam", "Assets"));
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "brands", "fabrikam", "Assets", "StoreLogo.png"), [2]);
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "bin", "arm64"));
+        var service = GetRequiredService<IPackageMatrixService>();
+        var variant = service.GetVariants(["fabrikam-stable-arm64"]).Single();
+
+        // Act
+        using (var layout = await service.PrepareAsync(variant, _tempDirectory, new FileInfo(manifestPath), TestTaskContext, TestContext.CancellationToken))
+        {
+            // Assert
+            Assert.AreEqual(Path.Combine(_tempDirectory.FullName, "bin", "arm64"), layout.InputFolder.FullName);
+            CollectionAssert.AreEqual(new byte[] { 2 }, File.ReadAllBytes(Path.Combine(_tempDirectory.FullName, "Assets", "StoreLogo.png")));
+            Assert.Contains("Fabrikam.Notes", File.ReadAllText(manifestPath));
+        }
+        CollectionAssert.AreEqual(new byte[] { 1 }, File.ReadAllBytes(Path.Combine(_tempDirectory.FullName, "Assets", "StoreLogo.png")));
+        Assert.AreEqual(Manifest, File.ReadAllText(manifestPath));
+    }
+
+    [TestMethod]
+    public void MatrixSection_RoundTripsThroughWinappYaml()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+
+        // Act
+        configService.Save(CreateConfig());
+        var matrix = configService.Load().Matrix;
+
+        // Assert
+        Assert.IsNotNull(matrix);
+        Assert.HasCount(2, matrix.Brandings);
+        Assert.AreEqual("CN=Fabrikam", matrix.Brandings[1].Publisher);
+        CollectionAssert.AreEqual(new[] { "fabrikam-notes" }, matrix.Brandings[1].Protocols);
+        Assert.AreEqual(" (Beta)", matrix.Channels[1].DisplayNameSuffix);
+        Assert.AreEqual("bin/arm64", matrix.Architectures[1].Input);
+        Assert.IsNull(matrix.Architectures[0].Input);
+    }
+}
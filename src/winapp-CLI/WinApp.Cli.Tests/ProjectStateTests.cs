@@ -0,0 +1,123 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ProjectStateTests : BaseCommandTests
+{
+    public ProjectStateTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void RecordDeployment_ReplacesEarlierRegistrationOfPackage()
+    {
+        // Arrange
+        var state = new ProjectState();
+
+        // Act
+        state.RecordDeployment(new DeploymentRecord { PackageName = "Contoso.Notes.debug", ExternalLocation = @"C:\src\Notes\bin\Debug" });
+        state.RecordDeployment(new DeploymentRecord { PackageName = "Contoso.Paint.debug", ExternalLocation = @"C:\src\Paint\bin" });
+        state.RecordDeployment(new DeploymentRecord { PackageName = "contoso.notes.debug", ExternalLocation = @"C:\src\Notes\bin\Release" });
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[] { @"C:\src\Paint\bin", @"C:\src\Notes\bin\Release" },
+            state.Deployments.Select(d => d.ExternalLocation).ToArray());
+    }
+
+    [TestMethod]
+    public void RecordIdentity_KeepsOneIdentityPerKindAndName()
+    {
+        // Arrange
+        var state = new ProjectState();
+
+        // Act
+        state.RecordIdentity(new IdentityRecord { Kind = ProjectIdentityKind.Debug, Name = "Contoso.Notes.debug", Publisher = "CN=Contoso" });
+        state.RecordIdentity(new IdentityRecord { Kind = ProjectIdentityKind.Branch, Name = "Contoso.Notes.br1a2b3c4d", Branch = "feature/a" });
+        state.RecordIdentity(new IdentityRecord { Kind = ProjectIdentityKind.Debug, Name = "Contoso.Notes.debug", Publisher = "CN=Fabrikam" });
+
+        // Assert
+        Assert.HasCount(2, state.Identities);
+        Assert.AreEqual("CN=Fabrikam", state.Identities.Single(i => i.Kind == ProjectIdentityKind.Debug).Publisher);
+    }
+
+    [TestMethod]
+    public async Task UpdateAsync_SavesStateInLocalWinappDirectory()
+    {
+        // Arrange
+        var projectStateService = GetRequiredService<IProjectStateService>();
+
+        // Act
+        await projectStateService.UpdateAsync(state => state.RecordIdentity(new IdentityRecord { Kind = ProjectIdentityKind.Debug, Name = "Contoso.Notes.debug" }), TestTaskContext, TestContext.CancellationToken);
+        await projectStateService.UpdateAsync(state => state.LastPack = new PackRecord { PackageName = "Contoso.Notes", Provenance = new BuildProvenance { GitSha = "abc123", Profile = "Release" } }, TestTaskContext, TestContext.CancellationToken);
+        var loaded = await projectStateService.LoadAsync(TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(Path.Combine(_testWinappDirectory.FullName, "state.json"), projectStateService.StatePath.FullName);
+        Assert.AreEqual("Contoso.Notes.debug", loaded.Identities.Single().Name);
+        Assert.AreEqual("Release", loaded.LastPack?.Provenance?.Profile);
+        var json = await File.ReadAllTextAsync(projectStateService.StatePath.FullName, TestContext.CancellationToken);
+        Assert.Contains("\"lastPack\"", json);
+        Assert.Contains("\"kind\": \"Debug\"", json);
+    }
+
+    [TestMethod]
+    public async Task LoadAsync_IgnoresCorruptState()
+    {
+        // Arrange
+        var projectStateService = GetRequiredService<IProjectStateService>();
+        await File.WriteAllTextAsync(projectStateService.StatePath.FullName, "{ not json", TestContext.CancellationToken);
+
+        // Act
+        var state = await projectStateService.LoadAsync(TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsNull(state.LastPack);
+        Assert.HasCount(0, state.Deployments);
+    }
+
+    [TestMethod]
+    public async Task WriteProvenanceAsync_WritesStampAtPackageRoot()
+    {
+        // Arrange
+        var projectStateService = GetRequiredService<IProjectStateService>();
+        var provenance = new BuildProvenance { GitSha = "0123456789abcdef", GitDirty = true, Branch = "main", Profile = "Nightly", CliVersion = "1.0.0" };
+
+        // Act
+        var stamp = await projectStateService.WriteProvenanceAsync(provenance, _tempDirectory, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(Path.Combine(_tempDirectory.FullName, "winapp-build-info.json"), stamp.FullName);
+        var json = await File.ReadAllTextAsync(stamp.FullName, TestContext.CancellationToken);
+        Assert.Contains("\"gitSha\": \"0123456789abcdef\"", json);
+        Assert.Contains("\"gitDirty\": true", json);
+        Assert.Contains("\"profile\": \"Nightly\"", json);
+    }
+
+    [TestMethod]
+    public async Task WriteProvenanceAsync_RecordsThePackageFolderRelativeToTheSourceRoot()
+    {
+        // Arrange
+        var projectStateService = GetRequiredService<IProjectStateService>();
+        var packageFolder = _tempDirectory.CreateSubdirectory(Path.Combine("target", "winapp", "release"));
+        var provenance = new BuildProvenance { SourceRoot = _tempDirectory.FullName, CliVersion = "1.0.0" };
+
+        // Act
+        var stamp = await projectStateService.WriteProvenanceAsync(provenance, packageFolder, TestContext.CancellationToken);
+        var outside = new BuildProvenance { SourceRoot = packageFolder.FullName };
+        await projectStateService.WriteProvenanceAsync(outside, _tempDirectory, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("target/winapp/release", provenance.PackageFolder);
+        Assert.IsNull(outside.PackageFolder);
+        var json = await File.ReadAllTextAsync(stamp.FullName, TestContext.CancellationToken);
+        Assert.Contains("\"packageFolder\": \"target/winapp/release\"", json);
+        Assert.Contains("\"sourceRoot\"", json);
+    }
+}
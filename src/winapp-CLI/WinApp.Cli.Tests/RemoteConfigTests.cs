@@ -0,0 +1,102 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.RemoteConfig;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class RemoteConfigTests : BaseCommandTests
+{
+    private static readonly string[] Channels = ["stable", "beta"];
+
+    public RemoteConfigTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public void Validate_AcceptsDocumentTheRuntimeReads()
+    {
+        // Arrange
+        const string json = """
+            {
+              "schemaVersion": 1,
+              "flags": {
+                "new-editor": {
+                  "description": "The rewritten editor",
+                  "default": false,
+                  "rules": [
+                    { "channels": ["Beta"], "value": true },
+                    { "minVersion": "1.4", "maxVersion": "1.4.65535.0", "rollout": 25, "value": true }
+                  ]
+                },
+                "upload.limit_mb": { "default": 10 }
+              }
+            }
+            """;
+
+        // Act
+        var findings = RemoteConfigValidator.Validate(json, Channels);
+
+        // Assert
+        Assert.AreEqual(0, findings.Count, string.Join(Environment.NewLine, findings));
+    }
+
+    [TestMethod]
+    public void Validate_ReportsThePathOfEachProblem()
+    {
+        // Arrange
+        const string json = """
+            {
+              "schemaVersion": 2,
+              "flags": {
+                "-theme": { "default": "light", "rules": [{ "value": 1 }] },
+                "new-editor": {
+                  "default": false,
+                  "rules": [
+                    { "channels": [], "value": true },
+                    { "minVersion": "2.0", "maxVersion": "1.9", "rollout": 120, "value": true, "percent": 5 }
+                  ]
+                },
+                "upload-limit": { "default": [10] },
+                "search": { "rules": [{ "channels": ["canary"] }] }
+              }
+            }
+            """;
+
+        // Act
+        var findings = RemoteConfigValidator.Validate(json, Channels);
+
+        // Assert
+        string[] expected =
+        [
+            "Error schemaVersion",
+            "Error flags.-theme",
+            "Error flags.-theme.rules[0].value",
+            "Error flags.new-editor.rules[0].channels",
+            "Error flags.new-editor.rules[1].percent",
+            "Error flags.new-editor.rules[1].maxVersion",
+            "Error flags.new-editor.rules[1].rollout",
+            "Error flags.search.default",
+            "Error flags.search.rules[0].value",
+            "Warning flags.search.rules[0].channels",
+            "Error flags.upload-limit.default",
+        ];
+        CollectionAssert.AreEquivalent(expected, findings.Select(f => $"{f.Severity} {f.Path}").ToArray(), string.Join(Environment.NewLine, findings));
+        StringAssert.Contains(findings.Single(f => f.Path == "flags.-theme.rules[0].value").Message, "number but the default is a string");
+    }
+
+    [TestMethod]
+    public void Validate_ReportsJsonThatDoesNotParse()
+    {
+        // Act
+        var findings = RemoteConfigValidator.Validate("{ \"schemaVersion\": 1, \"flags\": { ", channels: null);
+
+        // Assert
+        Assert.AreEqual(1, findings.Count);
+        Assert.AreEqual(AccessibilitySeverity.Error, findings[0].Severity);
+        Assert.AreEqual("$", findings[0].Path);
+    }
+}
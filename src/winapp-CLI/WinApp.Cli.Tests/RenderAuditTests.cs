@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class RenderAuditTests : BaseCommandTests
+{
+    private static readonly DateTime Today = new(2026, 10, 15);
+
+    private static readonly GpuAdapter BasicRender = new("Microsoft Basic Render Driver", "Microsoft", "10.0.26100.1", new DateTime(2006, 6, 21));
+
+    public RenderAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static RenderAuditService.RenderContext Context(GpuAdapter[] adapters, bool isRemoteSession = false, Dictionary<string, string>? browserArguments = null, bool wpfSoftwareRendering = false) =>
+        new(adapters, isRemoteSession, browserArguments ?? new Dictionary<string, string>(), wpfSoftwareRendering, Today);
+
+    [TestMethod]
+    public void Findings_ReportsSoftwareAdaptersAndRemoteSessions()
+    {
+        var hardware = new GpuAdapter("NVIDIA GeForce RTX 4070", "NVIDIA", "32.0.15.6094", new DateTime(2026, 8, 1));
+
+        var software = RenderAuditService.Findings(Context([BasicRender])).Single();
+        var remote = RenderAuditService.Findings(Context([new GpuAdapter("Microsoft Remote Display Adapter", "Microsoft", "10.0.26100.1", null)], isRemoteSession: true)).Single();
+
+        Assert.AreEqual("software-adapter", software.Check);
+        Assert.AreEqual(AccessibilitySeverity.Warning, software.Severity);
+        Assert.Contains("Microsoft Basic Render Driver", software.Message);
+        Assert.AreEqual("remote-session", remote.Check);
+        Assert.AreEqual(AccessibilitySeverity.Info, remote.Severity);
+        Assert.IsFalse(RenderAuditService.Findings(Context([hardware, BasicRender])).Any());
+        Assert.IsFalse(RenderAuditService.Findings(Context([])).Any());
+    }
+
+    [TestMethod]
+    public void Findings_ReportsVirtualMachinesAndOldDrivers()
+    {
+        var virtualMachine = new GpuAdapter("Microsoft Hyper-V Video", "Microsoft", "10.0.26100.1", new DateTime(2006, 6, 21));
+        var old = new GpuAdapter("Intel(R) HD Graphics 4000", "Intel Corporation", "10.18.10.4358", new DateTime(2015, 12, 10));
+
+        var virtualIssue = RenderAuditService.Findings(Context([virtualMachine])).Single();
+        var oldIssue = RenderAuditService.Findings(Context([old])).Single();
+
+        Assert.AreEqual("virtual-machine", virtualIssue.Check);
+        Assert.AreEqual("old-driver", oldIssue.Check);
+        Assert.Contains("10.18.10.4358", oldIssue.Message);
+        Assert.Contains("2015-12-10", oldIssue.Message);
+    }
+
+    [TestMethod]
+    public void Findings_ReportsSettingsThatTurnOffTheGpu()
+    {
+        var hardware = new GpuAdapter("AMD Radeon RX 7800 XT", "Advanced Micro Devices, Inc.", "32.0.21013.1000", new DateTime(2026, 9, 1));
+        var arguments = new Dictionary<string, string>
+        {
+            [RenderAuditService.BrowserArgumentsVariable] = "--disable-gpu --enable-features=msEdgeDevTools",
+            ["tauri.conf.json"] = "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection",
+        };
+
+        var issues = RenderAuditService.Findings(Context([hardware], browserArguments: arguments, wpfSoftwareRendering: true));
+
+        Assert.HasCount(2, issues);
+        Assert.AreEqual(RenderAuditService.BrowserArgumentsVariable, issues[0].Subject);
+        Assert.Contains("--disable-gpu", issues[0].Message);
+        Assert.DoesNotContain("msEdgeDevTools", issues[0].Message);
+        Assert.AreEqual("DisableHWAcceleration", issues[1].Subject);
+    }
+
+    [TestMethod]
+    public void FindRenderers_FindsWebView2WinUIAndWpf()
+    {
+        var tauri = _tempDirectory.CreateSubdirectory("tauri");
+        var winui = _tempDirectory.CreateSubdirectory("winui");
+        var wpf = _tempDirectory.CreateSubdirectory("wpf");
+        File.WriteAllText(Path.Combine(winui.FullName, "App.csproj"), "<Project><PropertyGroup><UseWinUI>true</UseWinUI></PropertyGroup></Project>");
+        Directory.CreateDirectory(Path.Combine(wpf.FullName, "bin"));
+        File.WriteAllText(Path.Combine(wpf.FullName, "bin", "PresentationFramework.dll"), "");
+        File.WriteAllText(Path.Combine(wpf.FullName, "bin", "WebView2Loader.dll"), "");
+
+        CollectionAssert.AreEqual(new[] { "WebView2" }, RenderAuditService.FindRenderers(tauri, isTauri: true));
+        CollectionAssert.AreEqual(new[] { "WinUI" }, RenderAuditService.FindRenderers(winui, isTauri: false));
+        CollectionAssert.AreEqual(new[] { "WebView2", "WPF" }, RenderAuditService.FindRenderers(wpf, isTauri: false));
+    }
+
+    [TestMethod]
+    public void ReadTauriBrowserArguments_JoinsTheArgumentsOfTheWindows()
+    {
+        var config = new FileInfo(Path.Combine(_tempDirectory.FullName, "tauri.conf.json"));
+        File.WriteAllText(config.FullName, """
+            {
+              "identifier": "com.contoso.app",
+              "app": {
+                "windows": [
+                  { "title": "Main", "additionalBrowserArgs": "--disable-gpu" },
+                  { "title": "Settings" }
+                ]
+              }
+            }
+            """);
+
+        Assert.AreEqual("--disable-gpu", RenderAuditService.ReadTauriBrowserArguments(config));
+        Assert.AreEqual(new DateTime(2015, 12, 10), RenderAuditService.ParseDriverDate("12-10-2015"));
+        Assert.AreEqual(new DateTime(2006, 6, 21), RenderAuditService.ParseDriverDate("6-21-2006"));
+        Assert.IsNull(RenderAuditService.ParseDriverDate(null));
+        Assert.IsTrue(RenderAuditService.IsRemoteSession("RDP-Tcp#3"));
+        Assert.IsFalse(RenderAuditService.IsRemoteSession("Console"));
+    }
+}
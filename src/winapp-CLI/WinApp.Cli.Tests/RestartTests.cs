@@ -0,0 +1,72 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class RestartTests : BaseCommandTests
+{
+    public RestartTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static RestartRequest Request(string token, RestartReason reason, int processId, long savedAt) =>
+        new(token, reason, DateTimeOffset.FromUnixTimeSeconds(savedAt), processId, @"C:\apps\notes\notes.exe", [], null);
+
+    [TestMethod]
+    public void FindRequests_ReadsTheStateFilesOfTheRuntime()
+    {
+        // Arrange
+        var folder = _tempDirectory.CreateSubdirectory("RestartState");
+        File.WriteAllText(Path.Combine(folder.FullName, "0a1b2c.json"), """
+            {"reason":"update","savedAt":1760520600,"processId":4242,"executable":"C:\\apps\\notes\\notes.exe","arguments":["--open","C:\\My Notes\\todo.md"],"appUserModelId":"Contoso.Notes_8wekyb3d8bbwe!App","payload":{"open":["todo.md"]}}
+            """);
+        File.WriteAllText(Path.Combine(folder.FullName, "3d4e5f.json"), """{"reason":"crash","savedAt":1760520000,"processId":4242,"executable":"C:\\apps\\notes\\notes.exe","appUserModelId":null}""");
+        File.WriteAllText(Path.Combine(folder.FullName, "half-written.json"), """{"reason":"upd""");
+
+        // Act
+        var requests = RestartService.FindRequests(folder);
+
+        // Assert
+        Assert.AreEqual(2, requests.Count);
+        var update = requests[0];
+        Assert.AreEqual("0a1b2c", update.Token);
+        Assert.AreEqual(RestartReason.Update, update.Reason);
+        Assert.AreEqual(4242, update.ProcessId);
+        Assert.AreEqual("Contoso.Notes_8wekyb3d8bbwe!App", update.AppUserModelId);
+        CollectionAssert.AreEqual(new[] { "--open", @"C:\My Notes\todo.md", "--winapp-restore-state=0a1b2c" }, RestartService.RestartArguments(update));
+        Assert.AreEqual(RestartReason.Crash, requests[1].Reason);
+        Assert.IsNull(requests[1].AppUserModelId);
+    }
+
+    [TestMethod]
+    public void PickRequest_TakesTheNewestRestartOfARunningProcess()
+    {
+        // Arrange
+        RestartRequest[] requests =
+        [
+            Request("aa", RestartReason.Update, processId: 100, savedAt: 300),
+            Request("bb", RestartReason.Update, processId: 200, savedAt: 200),
+            Request("cc", RestartReason.Update, processId: 200, savedAt: 100),
+            Request("dd", RestartReason.Crash, processId: 200, savedAt: 400),
+        ];
+        PackageProcess[] processes = [new(200, 1, "notes.exe", null, null, null)];
+
+        // Act & Assert
+        Assert.AreEqual("bb", RestartService.PickRequest(requests, processes, RestartReason.Update)?.Token);
+        Assert.AreEqual("dd", RestartService.PickRequest(requests, processes, RestartReason.Crash)?.Token);
+        Assert.IsNull(RestartService.PickRequest(requests, [], RestartReason.Update));
+    }
+
+    [TestMethod]
+    public void JoinArguments_QuotesTheWayWindowsSplitsCommandLines()
+    {
+        string[] arguments = ["--open", @"C:\My Notes\", "say \"hi\"", "", "--winapp-restore-state=0a1b"];
+
+        Assert.AreEqual(@"--open ""C:\My Notes\\"" ""say \""hi\"""" """" --winapp-restore-state=0a1b", RestartService.JoinArguments(arguments));
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class RuleSetTests : BaseCommandTests
+{
+    public RuleSetTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateManifest()
+    {
+        // Line numbers matter: Logo is on line 5, VisualElements on 10 and DefaultTile on 11
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <!-- winapp-ignore-file splash/* -->
+              <Properties>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <!-- winapp-ignore a11y/contrast: brand colors -->
+                  <uap:VisualElements DisplayName="Contoso Notes" BackgroundColor="#FFFFFF" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png">
+                    <uap:DefaultTile ShortName="Notes" />
+                  </uap:VisualElements>
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private RuleSet Load(FileInfo manifest) => GetRequiredService<IRuleSetService>().Load(manifest, TestTaskContext);
+
+    [TestMethod]
+    public void Load_SuppressesRulesFromInlineComments()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+
+        // Act
+        var rules = Load(manifest);
+
+        // Assert
+        Assert.IsNull(rules.Resolve("a11y/contrast", AccessibilitySeverity.Warning, "BackgroundColor"));
+        Assert.IsNull(rules.Resolve("a11y/contrast", AccessibilitySeverity.Warning, "ShortName"), "winapp-ignore covers the children of the element");
+        Assert.AreEqual(AccessibilitySeverity.Warning, rules.Resolve("a11y/contrast", AccessibilitySeverity.Warning, "Logo"));
+        Assert.AreEqual(AccessibilitySeverity.Warning, rules.Resolve("a11y/high-contrast", AccessibilitySeverity.Warning, "Square150x150Logo"));
+        Assert.IsNull(rules.Resolve("splash/edge", AccessibilitySeverity.Warning, "Notes"));
+    }
+
+    [TestMethod]
+    public void Load_AppliesWinappYamlOverridesAndSuppressions()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Rules["a11y/short-name"] = "off";
+        config.Rules["scales/*"] = "info";
+        config.Rules["scales/upscaled"] = "error";
+        config.Suppressions.Add(new SuppressionConfig { Rule = "a11y/asset", File = "appxmanifest.xml", Line = 5 });
+        config.Suppressions.Add(new SuppressionConfig { Rule = "scales/dpi", File = "other.appxmanifest" });
+        configService.Save(config);
+
+        // Act
+        var rules = Load(manifest);
+
+        // Assert
+        Assert.IsNull(rules.Resolve("a11y/short-name", AccessibilitySeverity.Warning, "ShortName"));
+        Assert.AreEqual(AccessibilitySeverity.Info, rules.Resolve("scales/dpi", AccessibilitySeverity.Warning, @"Assets\Square44x44Logo.png"), "The suppression is for another manifest");
+        Assert.AreEqual(AccessibilitySeverity.Error, rules.Resolve("scales/upscaled", AccessibilitySeverity.Warning, "Square44x44Logo.scale-200.png"), "A rule's own level wins over its source's");
+        Assert.IsNull(rules.Resolve("a11y/asset", AccessibilitySeverity.Error, "Logo"));
+        Assert.AreEqual(AccessibilitySeverity.Error, rules.Resolve("a11y/asset", AccessibilitySeverity.Error, "Square150x150Logo"), "Only line 5 is suppressed");
+    }
+
+    [TestMethod]
+    public void Load_RejectsUnknownLevels()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Rules["a11y/contrast"] = "loud";
+        configService.Save(config);
+
+        // Act & Assert
+        Assert.ThrowsExactly<InvalidOperationException>(() => Load(manifest));
+    }
+
+    [TestMethod]
+    public void ValidationRules_ExplainFindsRulesByIdOrCheckName()
+    {
+        // Act
+        var byCheck = ValidationRules.Explain("dpi", "scales");
+        var byId = ValidationRules.Explain("a11y/contrast");
+
+        // Assert
+        Assert.StartsWith("scales/dpi:", byCheck[0]);
+        Assert.StartsWith("a11y/contrast:", byId[0]);
+        Assert.ThrowsExactly<InvalidOperationException>(() => ValidationRules.Explain("dpi", "a11y"));
+    }
+
+    [TestMethod]
+    public void ConfigService_RoundTripsRulesAndSuppressions()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Rules["scales/*"] = "warning";
+        config.Suppressions.Add(new SuppressionConfig { Rule = "*", File = "Package.appxmanifest", Line = 12, Reason = "Legacy assets, see #42" });
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual("warning", loaded.Rules["scales/*"]);
+        Assert.HasCount(1, loaded.Suppressions);
+        Assert.AreEqual("*", loaded.Suppressions[0].Rule);
+        Assert.AreEqual("Package.appxmanifest", loaded.Suppressions[0].File);
+        Assert.AreEqual(12, loaded.Suppressions[0].Line);
+        Assert.AreEqual("Legacy assets, see #42", loaded.Suppressions[0].Reason);
+    }
+}
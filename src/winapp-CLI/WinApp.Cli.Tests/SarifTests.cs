@@ -0,0 +1,98 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class SarifTests : BaseCommandTests
+{
+    public SarifTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateManifest()
+    {
+        // Line numbers matter: Logo is on line 4 and VisualElements on 8
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Properties>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" BackgroundColor="#FFFFFF" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private async Task<JsonArray> WriteAsync(FileInfo manifest, params SarifFinding[] findings)
+    {
+        var sarifPath = new FileInfo(Path.Combine(_tempDirectory.FullName, "out", "winapp.sarif"));
+        await GetRequiredService<ISarifService>().WriteAsync(sarifPath, manifest, findings, TestTaskContext, TestContext.CancellationToken);
+        var log = JsonNode.Parse(File.ReadAllText(sarifPath.FullName))!;
+        Assert.AreEqual("2.1.0", log["version"]!.GetValue<string>());
+        return log["runs"]![0]!["results"]!.AsArray();
+    }
+
+    private static (string Uri, int? Line) LocationOf(JsonNode result, int index = 0)
+    {
+        var physical = result["locations"]![index]!["physicalLocation"]!;
+        return (physical["artifactLocation"]!["uri"]!.GetValue<string>(), physical["region"]?["startLine"]?.GetValue<int>());
+    }
+
+    [TestMethod]
+    public async Task WriteAsync_PointsAtManifestLinesAndPayloadFiles()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Assets"));
+        File.WriteAllBytes(Path.Combine(_tempDirectory.FullName, "Assets", "Square44x44Logo.scale-200.png"), [0]);
+
+        // Act
+        var results = await WriteAsync(manifest,
+            new SarifFinding(AccessibilitySeverity.Error, "a11y/asset", "Logo", null, "Not found"),
+            new SarifFinding(AccessibilitySeverity.Warning, "a11y/contrast", "BackgroundColor", null, "Low contrast"),
+            new SarifFinding(AccessibilitySeverity.Info, "scales/size", "Square44x44Logo.scale-200.png", null, "Larger than its qualifier"));
+
+        // Assert
+        Assert.HasCount(3, results);
+        Assert.AreEqual(("appxmanifest.xml", (int?)4), LocationOf(results[0]!));
+        Assert.AreEqual("error", results[0]!["level"]!.GetValue<string>());
+        Assert.AreEqual(("appxmanifest.xml", (int?)8), LocationOf(results[1]!));
+        Assert.AreEqual(("Assets/Square44x44Logo.scale-200.png", (int?)null), LocationOf(results[2]!), "The variant file is where the fix goes");
+        Assert.AreEqual(("appxmanifest.xml", (int?)8), LocationOf(results[2]!, 1));
+        Assert.AreEqual("note", results[2]!["level"]!.GetValue<string>());
+    }
+
+    [TestMethod]
+    public async Task WriteAsync_PointsSplashFindingsAtWinappYamlAndMarksBaselinedOnesSuppressed()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        GetRequiredService<IConfigService>().Save(new WinappConfig { Splash = new SplashConfig { BackgroundColor = "#FFFFFF" } });
+        var splashLine = Array.FindIndex(File.ReadAllLines(Path.Combine(_tempDirectory.FullName, "winapp.yaml")), l => l == "splash:") + 1;
+
+        // Act
+        var results = await WriteAsync(manifest,
+            new SarifFinding(AccessibilitySeverity.Info, "splash/extended", "Notes", null, "Not extended"),
+            new SarifFinding(AccessibilitySeverity.Warning, "manifest/version", null, "Package/Applications/Application[Notes]/VisualElements", "Needs a newer release", Baselined: true));
+
+        // Assert
+        Assert.AreEqual(("winapp.yaml", (int?)splashLine), LocationOf(results[0]!));
+        Assert.AreEqual("appxmanifest.xml", LocationOf(results[0]!, 1).Uri);
+        Assert.IsNull(results[0]!["suppressions"]);
+        Assert.AreEqual(("appxmanifest.xml", (int?)8), LocationOf(results[1]!));
+        Assert.AreEqual("external", results[1]!["suppressions"]![0]!["kind"]!.GetValue<string>());
+        Assert.Contains("Package/Applications/Application[Notes]/VisualElements", results[1]!["partialFingerprints"]!["winappElement/v1"]!.GetValue<string>());
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Drawing.Drawing2D;
+using System.Drawing.Imaging;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ScaleAuditTests : BaseCommandTests
+{
+    public ScaleAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static Bitmap DrawLogo(int size)
+    {
+        // A ring and a bar, drawn at the size itself so every edge is as sharp as the size allows
+        var bitmap = new Bitmap(size, size);
+        using var graphics = Graphics.FromImage(bitmap);
+        graphics.SmoothingMode = SmoothingMode.AntiAlias;
+        graphics.Clear(Color.Transparent);
+        using var pen = new Pen(Color.White, size / 8f);
+        graphics.DrawEllipse(pen, size * 0.2f, size * 0.2f, size * 0.6f, size * 0.6f);
+        graphics.FillRectangle(Brushes.White, size * 0.45f, size * 0.1f, size * 0.1f, size * 0.8f);
+        return bitmap;
+    }
+
+    private static Bitmap Upscale(Bitmap image, int size)
+    {
+        var bitmap = new Bitmap(size, size);
+        using var graphics = Graphics.FromImage(bitmap);
+        graphics.InterpolationMode = InterpolationMode.HighQualityBicubic;
+        graphics.DrawImage(image, 0, 0, size, size);
+        return bitmap;
+    }
+
+    private void SaveAsset(string name, Bitmap bitmap)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "Assets", name);
+        Directory.CreateDirectory(Path.GetDirectoryName(path)!);
+        using (bitmap)
+        {
+            bitmap.Save(path, ImageFormat.Png);
+        }
+    }
+
+    private FileInfo CreateManifest()
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square44x44Logo="Assets\Square44x44Logo.png" />
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private async Task<ScaleReport> AnalyzeAsync(FileInfo manifest)
+    {
+        var service = GetRequiredService<IScaleAuditService>();
+        return await service.AnalyzeAsync(manifest, TestTaskContext, TestContext.CancellationToken);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_PassesCrispVariantsForEveryScaleAndTargetSize()
+    {
+        // Arrange
+        SaveAsset("Square44x44Logo.scale-100.png", DrawLogo(44));
+        SaveAsset("Square44x44Logo.scale-200.png", DrawLogo(88));
+        SaveAsset("Square44x44Logo.scale-400.png", DrawLogo(176));
+        foreach (var size in ScaleAuditService.StandardTargetSizes)
+        {
+            SaveAsset($"Square44x44Logo.targetsize-{size}_altform-unplated.png", DrawLogo(size));
+        }
+        var manifest = CreateManifest();
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.HasCount(0, report.Issues, string.Join(Environment.NewLine, report.Issues));
+        Assert.AreEqual(3 + ScaleAuditService.StandardTargetSizes.Length, report.Variants);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsWrongSizesAndUpscalesAtHighScales()
+    {
+        // Arrange
+        SaveAsset("Square44x44Logo.scale-100.png", DrawLogo(44));
+        SaveAsset("Square44x44Logo.scale-200.png", DrawLogo(80));
+        var manifest = CreateManifest();
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "size" && i.Asset == "Square44x44Logo.scale-200.png" && i.Message.Contains("80x80")));
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "dpi" && i.Severity == AccessibilitySeverity.Warning && i.Message.Contains("200%") && i.Message.Contains("scale-400")));
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "targetsize" && i.Message.Contains("No targetsize variants")));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_FlagsArtworkUpscaledFromHalfSize()
+    {
+        // Arrange
+        SaveAsset("Square44x44Logo.scale-100.png", DrawLogo(44));
+        using (var small = DrawLogo(44))
+        {
+            SaveAsset("Square44x44Logo.scale-200.png", Upscale(small, 88));
+        }
+        var manifest = CreateManifest();
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "upscaled" && i.Asset == "Square44x44Logo.scale-200.png"));
+        Assert.IsFalse(report.Issues.Any(i => i.Check == "size"));
+    }
+
+    [TestMethod]
+    public void LooksUpscaled_IgnoresCrispAndFlatImages()
+    {
+        // Arrange
+        using var crisp = DrawLogo(128);
+        using var flat = new Bitmap(128, 128);
+        using (var graphics = Graphics.FromImage(flat))
+        {
+            graphics.Clear(Color.SteelBlue);
+        }
+
+        // Act & Assert
+        Assert.IsFalse(ScaleAuditService.LooksUpscaled(crisp));
+        Assert.IsFalse(ScaleAuditService.LooksUpscaled(flat));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsTargetSizesTooSmallForHighScales()
+    {
+        // Arrange
+        SaveAsset("Square44x44Logo.scale-400.png", DrawLogo(176));
+        SaveAsset("Square44x44Logo.targetsize-16_altform-unplated.png", DrawLogo(16));
+        SaveAsset("Square44x44Logo.targetsize-24_altform-unplated.png", DrawLogo(24));
+        SaveAsset("Square44x44Logo.targetsize-48.png", DrawLogo(48));
+        var manifest = CreateManifest();
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "targetsize" && i.Severity == AccessibilitySeverity.Warning
+            && i.Message.Contains("largest target size is 24px") && i.Message.Contains("taskbar from 125%")));
+        Assert.IsFalse(report.Issues.Any(i => i.Check == "dpi"));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsMissingAsset()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "asset" && i.Severity == AccessibilitySeverity.Error && i.Asset == @"Assets\Square44x44Logo.png"));
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Commands;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class SettingsTests : BaseCommandTests
+{
+    public SettingsTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static void WriteConfig(FileInfo file, string contents)
+    {
+        file.Directory!.Create();
+        File.WriteAllText(file.FullName, contents);
+    }
+
+    [TestMethod]
+    public void Get_LaterLayersOverrideEarlierOnes()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        var configService = GetRequiredService<IConfigService>();
+        WriteConfig(settingsService.MachineConfigPath, """
+            settings:
+              timestampServer: 'http://timestamp.contoso.com'
+              nugetFeed: 'https://pkgs.contoso.com/nuget/v3/index.json'
+              remoteCache: '\\build\cache'
+            """);
+        WriteConfig(settingsService.UserConfigPath, """
+            settings:
+              nugetFeed: 'https://nuget.example.org/v3/index.json'
+            """);
+        var config = new WinappConfig();
+        config.Settings["timestampServer"] = "http://timestamp.fabrikam.com";
+        config.Settings["remoteCache"] = "";
+        configService.Save(config);
+
+        // Act
+        var timestamp = settingsService.Get(SettingsService.TimestampServer);
+        var feed = settingsService.Get(SettingsService.NugetFeed);
+        var cache = settingsService.Get(SettingsService.RemoteCache);
+        var fromCommandLine = settingsService.Get(SettingsService.TimestampServer, "http://timestamp.digicert.com", "--timestamp");
+
+        // Assert
+        Assert.AreEqual("http://timestamp.fabrikam.com", timestamp.Value);
+        Assert.AreEqual(SettingOrigin.Repository, timestamp.Origin);
+        Assert.AreEqual(configService.ConfigPath.FullName, timestamp.Source);
+        Assert.AreEqual("https://nuget.example.org/v3/index.json", feed.Value);
+        Assert.AreEqual(SettingOrigin.User, feed.Origin);
+        Assert.IsNull(cache.Value, "An empty value turns off the machine's remote cache");
+        Assert.AreEqual(SettingOrigin.Repository, cache.Origin);
+        Assert.AreEqual("http://timestamp.digicert.com", fromCommandLine.Value);
+        Assert.AreEqual(SettingOrigin.CommandLine, fromCommandLine.Origin);
+        Assert.AreEqual("--timestamp", fromCommandLine.Source);
+    }
+
+    [TestMethod]
+    public void Get_FallsBackToBuiltInDefaultsAndEnvironmentVariables()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        WriteConfig(settingsService.MachineConfigPath, """
+            settings:
+              remoteCache: '\\build\cache'
+            """);
+        Environment.SetEnvironmentVariable(BuildCacheService.LocationVariable, @"\\ci\cache");
+        try
+        {
+            // Act
+            var feed = settingsService.Get(SettingsService.NugetFeed);
+            var cache = settingsService.Get(SettingsService.RemoteCache);
+
+            // Assert
+            Assert.AreEqual(SettingsService.DefaultNugetFeed, feed.Value);
+            Assert.AreEqual(SettingOrigin.BuiltIn, feed.Origin);
+            Assert.AreEqual(@"\\ci\cache", cache.Value);
+            Assert.AreEqual(SettingOrigin.Environment, cache.Origin);
+            Assert.AreEqual(BuildCacheService.LocationVariable, cache.Source);
+            Assert.IsNull(settingsService.Get(SettingsService.TimestampServer).Value);
+            Assert.ThrowsExactly<ArgumentException>(() => settingsService.Get("timestamp"));
+        }
+        finally
+        {
+            Environment.SetEnvironmentVariable(BuildCacheService.LocationVariable, null);
+        }
+    }
+
+    [TestMethod]
+    public void Get_ResolvesPathsRelativeToTheFileThatSetsThem()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        WriteConfig(settingsService.MachineConfigPath, """
+            settings:
+              policy: 'policies\release.yaml'
+              signingKey: 'contoso'
+            """);
+
+        // Act
+        var policy = settingsService.Get(SettingsService.Policy);
+        var unknown = settingsService.GetUnknownKeys();
+
+        // Assert
+        Assert.AreEqual(Path.GetFullPath(Path.Combine(settingsService.MachineConfigPath.DirectoryName!, "policies", "release.yaml")), policy.Value);
+        Assert.AreEqual(SettingOrigin.Machine, policy.Origin);
+        Assert.HasCount(1, unknown);
+        Assert.AreEqual("signingKey", unknown[0].Key);
+        Assert.AreEqual(settingsService.MachineConfigPath.FullName, unknown[0].File.FullName);
+    }
+
+    [TestMethod]
+    public void RuleSet_AppliesThePolicyBelowWinappYaml()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        var configService = GetRequiredService<IConfigService>();
+        WriteConfig(new FileInfo(Path.Combine(settingsService.MachineConfigPath.DirectoryName!, "policy.yaml")), """
+            rules:
+              a11y/contrast: error
+              scales/*: error
+            """);
+        WriteConfig(settingsService.MachineConfigPath, """
+            settings:
+              policy: 'policy.yaml'
+            """);
+        var config = new WinappConfig();
+        config.Rules["scales/*"] = "info";
+        configService.Save(config);
+        var manifest = new FileInfo(Path.Combine(_tempDirectory.FullName, "appxmanifest.xml"));
+        File.WriteAllText(manifest.FullName, "<Package />");
+
+        // Act
+        var rules = GetRequiredService<IRuleSetService>().Load(manifest, TestTaskContext);
+
+        // Assert
+        Assert.AreEqual(AccessibilitySeverity.Error, rules.Resolve("a11y/contrast", AccessibilitySeverity.Warning, "BackgroundColor"));
+        Assert.AreEqual(AccessibilitySeverity.Info, rules.Resolve("scales/dpi", AccessibilitySeverity.Warning, "Logo"), "winapp.yaml overrides the policy");
+    }
+
+    [TestMethod]
+    public void RuleSet_FailsWhenThePolicyIsMissing()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        WriteConfig(settingsService.UserConfigPath, """
+            settings:
+              policy: 'missing.yaml'
+            """);
+        var manifest = new FileInfo(Path.Combine(_tempDirectory.FullName, "appxmanifest.xml"));
+        File.WriteAllText(manifest.FullName, "<Package />");
+
+        // Act & Assert
+        var exception = Assert.ThrowsExactly<FileNotFoundException>(() => GetRequiredService<IRuleSetService>().Load(manifest, TestTaskContext));
+        StringAssert.Contains(exception.Message, settingsService.UserConfigPath.FullName);
+    }
+
+    [TestMethod]
+    public void Save_RoundTripsSettings()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig();
+        config.Settings["nugetFeed"] = "https://pkgs.contoso.com/nuget/v3/index.json";
+        config.Settings["remoteCache"] = "";
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual("https://pkgs.contoso.com/nuget/v3/index.json", loaded.Settings["NUGETFEED"]);
+        Assert.AreEqual("", loaded.Settings["remoteCache"]);
+    }
+
+    [TestMethod]
+    public async Task ConfigShow_ListsValuesWithTheirOrigins()
+    {
+        // Arrange
+        var settingsService = GetRequiredService<ISettingsService>();
+        WriteConfig(settingsService.UserConfigPath, """
+            settings:
+              timestampServer: 'http://timestamp.contoso.com'
+            """);
+        var command = GetRequiredService<ConfigShowCommand>();
+
+        // Act
+        var exitCode = await ParseAndInvokeWithCaptureAsync(command, ["--origins"]);
+
+        // Assert
+        Assert.AreEqual(0, exitCode);
+        var output = TestAnsiConsole.Output;
+        StringAssert.Contains(output, "timestampServer = http://timestamp.contoso.com");
+        StringAssert.Contains(output, "[built-in]");
+        StringAssert.Contains(output, "user:");
+    }
+}
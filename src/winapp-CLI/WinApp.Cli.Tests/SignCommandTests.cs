@@ -606,7 +606,7 @@ public class SignCommandTests : BaseCommandTests
 
         var errorMessage = ConsoleStdErr.ToString().Trim();
 
-        Assert.Contains("Failed to sign file: error 0x8007000B: The app manifest publisher name (CN=Right) must match the subject name of the signing certificate (CN=Wrong).", errorMessage,
-            "Expected specific error message about publisher mismatch with error code 0x8007000B");
+        Assert.Contains("Failed to sign file: The certificate subject 'CN=Wrong' doesn't match the Publisher 'CN=Right' of TestPackage.msix", errorMessage,
+            "Expected specific error message about publisher mismatch before signing");
     }
 }
@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+using WinApp.Cli.Signing;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class SigningTests : BaseCommandTests
+{
+    private const string TestManifest = @"<?xml version=""1.0"" encoding=""utf-8""?>
+<Package xmlns=""http://schemas.microsoft.com/appx/manifest/foundation/windows10"">
+  <Identity Name=""TestPackage"" Publisher=""CN=TestPublisher"" Version=""1.0.0.0"" />
+</Package>";
+
+    [TestMethod]
+    public void EnsurePublisherMatches_AcceptsTheSubjectRegardlessOfSpacingAndCase()
+    {
+        // Arrange
+        using var certificate = CreateCertificate("CN=Contoso Ltd, O=Contoso, C=US");
+
+        // Act & Assert
+        SigningService.EnsurePublisherMatches(new FileInfo("App.msix"), "cn=Contoso Ltd,  o=Contoso, c=US", certificate);
+        SigningService.EnsurePublisherMatches(new FileInfo("App.msix"), null, certificate);
+    }
+
+    [TestMethod]
+    public void EnsurePublisherMatches_ExplainsAMismatch()
+    {
+        // Arrange
+        using var certificate = CreateCertificate("CN=Wrong");
+
+        // Act
+        var exception = Assert.ThrowsExactly<InvalidOperationException>(() =>
+            SigningService.EnsurePublisherMatches(new FileInfo("App.msix"), "CN=Right", certificate));
+
+        // Assert
+        Assert.Contains("The certificate subject 'CN=Wrong' doesn't match the Publisher 'CN=Right' of App.msix", exception.Message);
+        Assert.Contains("set the Identity Publisher in appxmanifest.xml to 'CN=Wrong'", exception.Message);
+    }
+
+    [TestMethod]
+    public void Parse_ReadsAndWritesTheSignSection()
+    {
+        // Arrange
+        var yaml = """
+            packages:
+            sign:
+              provider: trustedSigning
+              endpoint: https://wus2.codesigning.azure.net
+              account: contoso
+              profile: release
+            """;
+
+        // Act
+        var config = ConfigService.Parse(yaml);
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.IsNotNull(loaded.Sign);
+        Assert.AreEqual(SigningProvider.TrustedSigning, loaded.Sign.Provider);
+        Assert.AreEqual("https://wus2.codesigning.azure.net", loaded.Sign.Endpoint);
+        Assert.AreEqual("contoso", loaded.Sign.Account);
+        Assert.AreEqual("release", loaded.Sign.Profile);
+        Assert.IsNull(loaded.Sign.Vault);
+    }
+
+    [TestMethod]
+    public void ReadPackageFileNames_ReadsMainAndDependencyPackages()
+    {
+        // Arrange
+        var xml = """
+            <AppInstaller xmlns="http://schemas.microsoft.com/appx/appinstaller/2021" Uri="https://contoso.com/app/App.appinstaller" Version="1.0.0.0">
+              <MainBundle Name="App" Publisher="CN=Contoso" Version="1.0.0.0" Uri="https://contoso.com/app/App_1.0.0.0.msixbundle" />
+              <Dependencies>
+                <Package Name="Runtime" Publisher="CN=Contoso" Version="1.0.0.0" ProcessorArchitecture="x64" Uri="https://contoso.com/app/Runtime_x64.msix" />
+                <Package Name="Runtime" Publisher="CN=Contoso" Version="1.0.0.0" ProcessorArchitecture="x64" Uri="https://contoso.com/app/Runtime_x64.msix" />
+              </Dependencies>
+            </AppInstaller>
+            """;
+
+        // Act
+        var names = AppInstallerFile.ReadPackageFileNames(xml);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "App_1.0.0.0.msixbundle", "Runtime_x64.msix" }, names);
+    }
+
+    [TestMethod]
+    public void ReadResult_DecodesTheSignatureAndCertificate()
+    {
+        // Arrange
+        using var certificate = CreateCertificate("CN=Contoso");
+        var operation = new JsonObject
+        {
+            ["status"] = "Succeeded",
+            ["signature"] = Convert.ToBase64String([1, 2, 3]),
+            ["signingCertificate"] = Convert.ToBase64String(certificate.RawData)
+        };
+
+        // Act
+        var result = TrustedSigningClient.ReadResult(operation);
+
+        // Assert
+        CollectionAssert.AreEqual(new byte[] { 1, 2, 3 }, result.Signature);
+        Assert.AreEqual(certificate.Thumbprint, result.Certificate.Thumbprint);
+        Assert.HasCount(0, result.Chain);
+    }
+
+    [TestMethod]
+    public void ReadCertificateBundle_ReadsTheKeyPathOfTheCertificate()
+    {
+        // Arrange
+        using var certificate = CreateCertificate("CN=Contoso");
+        var bundle = new JsonObject
+        {
+            ["cer"] = Convert.ToBase64String(certificate.RawData),
+            ["kid"] = "https://contoso.vault.azure.net/keys/release/0123456789abcdef",
+            ["attributes"] = new JsonObject { ["enabled"] = true }
+        };
+
+        // Act
+        var (loaded, keyPath) = KeyVaultSigningClient.ReadCertificateBundle(bundle, "release");
+
+        // Assert
+        Assert.AreEqual(certificate.Thumbprint, loaded.Thumbprint);
+        Assert.AreEqual("keys/release/0123456789abcdef", keyPath);
+    }
+
+    [TestMethod]
+    public async Task SignAsync_WithARemoteSignerProducesAVerifiablePackage()
+    {
+        // Arrange
+        var layout = _tempDirectory.CreateSubdirectory("layout");
+        await File.WriteAllTextAsync(Path.Combine(layout.FullName, "appxmanifest.xml"), TestManifest, TestContext.CancellationToken);
+        await File.WriteAllTextAsync(Path.Combine(layout.FullName, "TestApp.exe"), "fake exe content", TestContext.CancellationToken);
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Test.msix"));
+        await AppxPacker.PackAsync(layout, packagePath, TestContext.CancellationToken);
+
+        using var rsa = RSA.Create(2048);
+        using var certificate = CreateCertificate("CN=TestPublisher", rsa);
+        var signedHashes = 0;
+        RemoteHashSigner signer = (hash, hashAlgorithm, _) =>
+        {
+            signedHashes++;
+            return Task.FromResult(new RemoteSignature(rsa.SignHash(hash, hashAlgorithm, RSASignaturePadding.Pkcs1), certificate, []));
+        };
+
+        // Act
+        await GetRequiredService<INativePackagingService>().SignAsync(packagePath, signer, TestTaskContext, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(1, signedHashes, "The service should sign a single hash");
+        var result = await AppxPackageVerifier.VerifyAsync(packagePath, TestContext.CancellationToken);
+        Assert.IsEmpty(result.Errors, string.Join(Environment.NewLine, result.Errors));
+        Assert.AreEqual(certificate.Thumbprint, result.Signer?.Thumbprint);
+    }
+
+    private static X509Certificate2 CreateCertificate(string subject, RSA? key = null)
+    {
+        using var rsa = key == null ? RSA.Create(2048) : null;
+        var request = new CertificateRequest(subject, key ?? rsa!, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1);
+        request.CertificateExtensions.Add(new X509EnhancedKeyUsageExtension([new Oid("1.3.6.1.5.5.7.3.3")], critical: false));
+        using var certificate = request.CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(30));
+        return X509CertificateLoader.LoadCertificate(certificate.RawData);
+    }
+}
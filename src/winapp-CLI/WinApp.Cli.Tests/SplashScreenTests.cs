@@ -0,0 +1,192 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Drawing.Imaging;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class SplashScreenTests : BaseCommandTests
+{
+    private const string UwpEntryPoint = """EntryPoint="Contoso.Notes.App" """;
+    private const string DesktopEntryPoint = """EntryPoint="Windows.FullTrustApplication" """;
+
+    public SplashScreenTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private void CreateSplashImage(string name, int width, int height, Color edge)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "Assets", name);
+        Directory.CreateDirectory(Path.GetDirectoryName(path)!);
+
+        // Logo in the middle of a canvas of the edge color
+        using var bitmap = new Bitmap(width, height);
+        using (var graphics = Graphics.FromImage(bitmap))
+        {
+            graphics.Clear(edge);
+            graphics.FillEllipse(Brushes.White, width / 3, height / 4, width / 3, height / 2);
+        }
+        bitmap.Save(path, ImageFormat.Png);
+    }
+
+    private FileInfo CreateManifest(string entryPoint, string splashScreen)
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, $"""
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" {entryPoint}>
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png">
+                    {splashScreen}
+                  </uap:VisualElements>
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private async Task<SplashScreenReport> AnalyzeAsync(FileInfo manifest, SplashConfig? splash = null)
+    {
+        var service = GetRequiredService<ISplashScreenService>();
+        return await service.AnalyzeAsync(manifest, splash, TestTaskContext, TestContext.CancellationToken);
+    }
+
+    [TestMethod]
+    public void Apply_AddsSplashScreenBeforeRotationSettings()
+    {
+        // Arrange
+        var manifest = File.ReadAllText(CreateManifest(UwpEntryPoint, """<uap:InitialRotationPreference><uap:Rotation Preference="landscape" /></uap:InitialRotationPreference>""").FullName);
+
+        // Act
+        var result = AppxSplashScreen.Apply(manifest, new SplashConfig { Image = "Assets/SplashScreen.png", BackgroundColor = "#1E1E1E" });
+
+        // Assert
+        var visualElements = XDocument.Parse(result).Descendants().Single(e => e.Name.LocalName == "VisualElements");
+        var children = visualElements.Elements().ToList();
+        Assert.AreEqual("SplashScreen", children[0].Name.LocalName, "SplashScreen goes before InitialRotationPreference");
+        Assert.AreEqual("http://schemas.microsoft.com/appx/manifest/uap/windows10", children[0].Name.NamespaceName);
+        Assert.AreEqual(@"Assets\SplashScreen.png", children[0].Attribute("Image")?.Value);
+        Assert.AreEqual("#1E1E1E", children[0].Attribute("BackgroundColor")?.Value);
+    }
+
+    [TestMethod]
+    public void Apply_KeepsAttributesWinappYamlLeavesOut()
+    {
+        // Arrange
+        var manifest = File.ReadAllText(CreateManifest(UwpEntryPoint, """<uap:SplashScreen Image="Assets\Splash.png" BackgroundColor="#000000" />""").FullName);
+
+        // Act
+        var result = AppxSplashScreen.Apply(manifest, new SplashConfig { BackgroundColor = "navy" });
+
+        // Assert
+        var splashScreen = XDocument.Parse(result).Descendants().Single(e => e.Name.LocalName == "SplashScreen");
+        Assert.AreEqual(@"Assets\Splash.png", splashScreen.Attribute("Image")?.Value);
+        Assert.AreEqual("navy", splashScreen.Attribute("BackgroundColor")?.Value);
+    }
+
+    [TestMethod]
+    public void Apply_RejectsColorsTheManifestDoesNotAccept()
+    {
+        // Arrange
+        var manifest = File.ReadAllText(CreateManifest(UwpEntryPoint, "").FullName);
+
+        // Act & Assert
+        Assert.ThrowsExactly<InvalidOperationException>(() => AppxSplashScreen.Apply(manifest, new SplashConfig { BackgroundColor = "#1E1E" }));
+        Assert.IsFalse(AppxSplashScreen.IsManifestColor("rgb(0, 0, 0)"));
+        Assert.IsTrue(AppxSplashScreen.IsManifestColor("transparent"));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_PassesMatchingSplashScreen()
+    {
+        // Arrange
+        CreateSplashImage("SplashScreen.png", 620, 300, Color.Transparent);
+        CreateSplashImage("SplashScreen.scale-200.png", 1240, 600, ColorTranslator.FromHtml("#1E1E1E"));
+        var manifest = CreateManifest(UwpEntryPoint, """<uap:SplashScreen Image="Assets\SplashScreen.png" BackgroundColor="#1E1E1E" />""");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.HasCount(0, report.Issues, string.Join(Environment.NewLine, report.Issues));
+        CollectionAssert.AreEqual(new[] { "Notes" }, report.Applications.ToList());
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsEdgeSizeAndScaleMismatches()
+    {
+        // Arrange
+        CreateSplashImage("SplashScreen.png", 600, 600, Color.White);
+        var manifest = CreateManifest(UwpEntryPoint, """<uap:SplashScreen Image="Assets\SplashScreen.png" BackgroundColor="#1E1E1E" />""");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "edge" && i.Severity == AccessibilitySeverity.Warning && i.Message.Contains("#FFFFFF")));
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "size" && i.Message.Contains("600x600")));
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "scale" && i.Severity == AccessibilitySeverity.Info));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsMissingImageAndAccentBackground()
+    {
+        // Arrange
+        var manifest = CreateManifest(UwpEntryPoint, """<uap:SplashScreen Image="Assets\SplashScreen.png" />""");
+
+        // Act
+        var report = await AnalyzeAsync(manifest);
+
+        // Assert
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "image" && i.Severity == AccessibilitySeverity.Error));
+        Assert.IsTrue(report.Issues.Any(i => i.Check == "background" && i.Severity == AccessibilitySeverity.Warning));
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_AppliesWinappYamlAndRequiresBackgroundForExtendedSplash()
+    {
+        // Arrange
+        CreateSplashImage("Splash.png", 1240, 600, Color.Transparent);
+        var manifest = CreateManifest(DesktopEntryPoint, "");
+
+        // Act
+        var withoutExtended = await AnalyzeAsync(manifest);
+        var extended = await AnalyzeAsync(manifest, new SplashConfig { Image = @"Assets\Splash.png", Extended = true });
+
+        // Assert
+        Assert.IsTrue(withoutExtended.Issues.Any(i => i.Check == "extended" && i.Severity == AccessibilitySeverity.Info));
+        Assert.IsTrue(withoutExtended.Issues.Any(i => i.Check == "missing" && i.Severity == AccessibilitySeverity.Info));
+        Assert.IsFalse(extended.Issues.Any(i => i.Check is "extended" or "missing" or "image"));
+        Assert.IsTrue(extended.Issues.Any(i => i.Check == "background" && i.Severity == AccessibilitySeverity.Error));
+    }
+
+    [TestMethod]
+    public void ConfigService_RoundTripsSplash()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig
+        {
+            Splash = new SplashConfig { Image = @"Assets\Splash.png", BackgroundColor = "#1E1E1E", Extended = true },
+        };
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.AreEqual(@"Assets\Splash.png", loaded.Splash?.Image);
+        Assert.AreEqual("#1E1E1E", loaded.Splash?.BackgroundColor);
+        Assert.IsTrue(loaded.Splash?.Extended);
+    }
+}
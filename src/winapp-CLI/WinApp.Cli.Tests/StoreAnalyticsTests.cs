@@ -0,0 +1,72 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreAnalyticsTests : BaseCommandTests
+{
+    public StoreAnalyticsTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public async Task QueryAsync_WithNextLink_ReadsEveryPage()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, "analytics/appacquisitions", (request, _) => request.RequestUri!.Query.Contains("skip=2")
+                ? new JsonObject { ["Value"] = new JsonArray(Row("2026-09-03", 7)), ["TotalCount"] = 3 }
+                : new JsonObject
+                {
+                    ["Value"] = new JsonArray(Row("2026-09-01", 5), Row("2026-09-02", 6)),
+                    ["@nextLink"] = "appacquisitions?applicationId=9NBLGGH4R315&top=2&skip=2",
+                    ["TotalCount"] = 3
+                });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+        var query = new StoreStatsQuery("9NBLGGH4R315", StoreStatsReport.Acquisitions, new DateOnly(2026, 9, 1), new DateOnly(2026, 9, 3), StoreStatsAggregation.Day, "market eq 'US'");
+
+        // Act
+        var rows = await PartnerCenterAnalytics.QueryAsync(client, query, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(3, rows);
+        Assert.AreEqual(7, rows[2]["acquisitionQuantity"]!.GetValue<int>());
+        Assert.HasCount(2, handler.Requests);
+        Assert.Contains("startDate=2026-09-01", handler.Requests[0].PathAndQuery);
+        Assert.Contains("aggregationLevel=day", handler.Requests[0].PathAndQuery);
+        Assert.Contains("filter=market%20eq%20%27US%27", handler.Requests[0].PathAndQuery);
+    }
+
+    [TestMethod]
+    public void ToCsv_WithDifferingAndNestedProperties_QuotesAndFlattensValues()
+    {
+        // Arrange
+        var rows = new List<JsonObject>
+        {
+            new() { ["date"] = "2026-09-01", ["market"] = "US", ["count"] = 5 },
+            new() { ["date"] = "2026-09-02", ["count"] = 6, ["osVersion"] = "Windows 11, 24H2", ["details"] = new JsonObject { ["a"] = 1 } },
+        };
+
+        // Act
+        var csv = PartnerCenterAnalytics.ToCsv(rows);
+
+        // Assert
+        var lines = csv.Split("\r\n");
+        Assert.AreEqual("date,market,count,osVersion,details", lines[0]);
+        Assert.AreEqual("2026-09-01,US,5,,", lines[1]);
+        Assert.AreEqual("2026-09-02,,6,\"Windows 11, 24H2\",\"{\"\"a\"\":1}\"", lines[2]);
+    }
+
+    private static JsonObject Row(string date, int quantity) => new()
+    {
+        ["date"] = date,
+        ["applicationId"] = "9NBLGGH4R315",
+        ["acquisitionQuantity"] = quantity
+    };
+}
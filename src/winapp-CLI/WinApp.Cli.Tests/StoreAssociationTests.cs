@@ -0,0 +1,110 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreAssociationTests : BaseCommandTests
+{
+    public StoreAssociationTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static JsonObject Application(string id, string name) => new()
+    {
+        ["id"] = id,
+        ["primaryName"] = name,
+        ["packageIdentityName"] = $"12345Contoso.{name.Replace(" ", "")}",
+        ["publisherName"] = "CN=0B5C4F4E-1A2B-4C3D-8E9F-001122334455",
+        ["packageFamilyName"] = $"12345Contoso.{name.Replace(" ", "")}_8wekyb3d8bbwe"
+    };
+
+    [TestMethod]
+    public async Task ResolveAsync_WithName_FindsAppOnLaterPage()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, "applications", (request, _) => request.RequestUri!.Query.Contains("skip=1")
+                ? new JsonObject { ["value"] = new JsonArray(Application("9NBLGGH4R316", "Contoso Notes")) }
+                : new JsonObject
+                {
+                    ["value"] = new JsonArray(Application("9NBLGGH4R315", "Contoso Paint")),
+                    ["@nextLink"] = "applications?skip=1&top=1"
+                });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var application = await StoreApplications.ResolveAsync(client, null, "contoso notes", TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("9NBLGGH4R316", application.ApplicationId);
+        Assert.AreEqual("12345Contoso.ContosoNotes", application.PackageIdentityName);
+        Assert.HasCount(2, handler.Requests);
+        var ex = await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => StoreApplications.ResolveAsync(client, null, null, TestContext.CancellationToken));
+        Assert.Contains("Contoso Paint (9NBLGGH4R315)", ex.Message);
+    }
+
+    [TestMethod]
+    public void ApplyToManifest_SetsIdentityAndDisplayNames()
+    {
+        // Arrange
+        var root = XElement.Parse("""
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Notes</DisplayName>
+                <PublisherDisplayName>Contoso</PublisherDisplayName>
+              </Properties>
+              <Applications>
+                <Application Id="App">
+                  <uap:VisualElements DisplayName="Notes" />
+                </Application>
+                <Application Id="Sync">
+                  <uap:VisualElements DisplayName="ms-resource:SyncName" />
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        var application = new StoreApplication("9NBLGGH4R316", "Contoso Notes", "12345Contoso.ContosoNotes", "CN=0B5C4F4E", "12345Contoso.ContosoNotes_8wekyb3d8bbwe");
+
+        // Act
+        var changes = StoreApplications.ApplyToManifest(root, application);
+
+        // Assert
+        Assert.HasCount(4, changes);
+        Assert.AreEqual(new StoreIdentityChange("Package/Identity", "Name", "Notes", "12345Contoso.ContosoNotes"), changes[0]);
+        Assert.AreEqual("CN=0B5C4F4E", root.Elements().First(e => e.Name.LocalName == "Identity").Attribute("Publisher")!.Value);
+        var visualElements = root.Descendants().Where(e => e.Name.LocalName == "VisualElements").ToList();
+        Assert.AreEqual("Contoso Notes", visualElements[0].Attribute("DisplayName")!.Value);
+        Assert.AreEqual("ms-resource:SyncName", visualElements[1].Attribute("DisplayName")!.Value);
+        Assert.HasCount(0, StoreApplications.ApplyToManifest(root, application), "The second association changes nothing");
+    }
+
+    [TestMethod]
+    public void StoreSection_RoundTripsThroughWinappYaml()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig
+        {
+            Store = new StoreConfig { AppId = "9NBLGGH4R316", PackageFamilyName = "12345Contoso.ContosoNotes_8wekyb3d8bbwe" }
+        };
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load();
+
+        // Assert
+        Assert.IsNotNull(loaded.Store);
+        Assert.AreEqual("9NBLGGH4R316", loaded.Store.AppId);
+        Assert.AreEqual("12345Contoso.ContosoNotes_8wekyb3d8bbwe", loaded.Store.PackageFamilyName);
+        Assert.AreEqual("9NBLGGH4R316", loaded.WithoutPackages().Store?.AppId);
+    }
+}
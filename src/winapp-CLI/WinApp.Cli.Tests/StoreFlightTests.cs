@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreFlightTests : BaseCommandTests
+{
+    public StoreFlightTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public async Task ResolveAsync_WithName_FindsFlightOnLaterPage()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, "applications/9NBLGGH4R315/listflights", (request, _) => request.RequestUri!.Query.Contains("skip=1")
+                ? new JsonObject { ["value"] = new JsonArray(Flight("43", "Insiders")), ["totalCount"] = 2 }
+                : new JsonObject
+                {
+                    ["value"] = new JsonArray(Flight("42", "Beta")),
+                    ["@nextLink"] = "applications/9NBLGGH4R315/listflights?skip=1&top=1",
+                    ["totalCount"] = 2
+                });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var flight = await StoreFlights.ResolveAsync(client, "9NBLGGH4R315", "insiders", TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("43", flight.FlightId);
+        Assert.HasCount(2, handler.Requests);
+    }
+
+    [TestMethod]
+    public async Task CreateAsync_WithoutRank_RanksAboveNonFlightedSubmission()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Post, "applications/9NBLGGH4R315/flights", (_, body) => Flight("44", "Insiders"));
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var flight = await StoreFlights.CreateAsync(client, "9NBLGGH4R315", "Insiders", ["1152921504606962205"], null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("44", flight.FlightId);
+        var body = JsonNode.Parse(handler.Requests[0].Body!)!;
+        Assert.AreEqual("Non-flighted submission", body["rankHigherThan"]!.GetValue<string>());
+        Assert.AreEqual("1152921504606962205", body["groupIds"]![0]!.GetValue<string>());
+    }
+
+    [TestMethod]
+    public void UpdatePackages_ForFlightSubmission_UsesFlightPackages()
+    {
+        // Arrange
+        var submission = new JsonObject { ["flightPackages"] = new JsonArray() };
+        var packages = new[] { new FileInfo(Path.Combine(_tempDirectory.FullName, "MyApp_x64.msixupload")) };
+
+        // Act
+        StoreSubmissionService.UpdatePackages(submission, packages, replacePackages: false, "flightPackages");
+
+        // Assert
+        Assert.HasCount(1, submission["flightPackages"]!.AsArray());
+        Assert.IsNull(submission["applicationPackages"]);
+    }
+
+    [TestMethod]
+    public void ReadMembersCsv_WithHeader_ReadsEmailColumn()
+    {
+        // Arrange
+        var csv = "Name,Email,Team\r\n\"Doe, Alex\",alex@contoso.com,QA\r\nSam,not-an-address,QA\r\n";
+
+        // Act
+        var members = StoreFlights.ReadMembersCsv(csv);
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "alex@contoso.com" }, members);
+    }
+
+    [TestMethod]
+    public void ReadMembersCsv_WithoutHeader_ReadsEveryAddress()
+    {
+        // Act
+        var members = StoreFlights.ReadMembersCsv("alex@contoso.com\nsam@fabrikam.com;kim@fabrikam.com\n");
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "alex@contoso.com", "sam@fabrikam.com", "kim@fabrikam.com" }, members);
+    }
+
+    [TestMethod]
+    public void ToMembersCsv_WithDuplicates_WritesSortedDistinctAddresses()
+    {
+        // Act
+        var csv = StoreFlights.ToMembersCsv(["sam@fabrikam.com", "Alex@contoso.com", "alex@contoso.com "]);
+
+        // Assert
+        Assert.AreEqual("Alex@contoso.com\r\nsam@fabrikam.com\r\n", csv);
+    }
+
+    private static JsonObject Flight(string id, string name) => new()
+    {
+        ["flightId"] = id,
+        ["friendlyName"] = name,
+        ["groupIds"] = new JsonArray("1152921504606962205"),
+        ["rankHigherThan"] = "Non-flighted submission"
+    };
+}
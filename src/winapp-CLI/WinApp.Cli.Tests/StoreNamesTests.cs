@@ -0,0 +1,68 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreNamesTests : BaseCommandTests
+{
+    public StoreNamesTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public async Task ReserveAsync_PostsPrimaryName()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Post, "applications", (_, body) => new JsonObject
+            {
+                ["id"] = "9NBLGGH4R317",
+                ["primaryName"] = JsonNode.Parse(body!)!["primaryName"]!.GetValue<string>(),
+                ["packageIdentityName"] = "12345Contoso.ContosoApp",
+                ["publisherName"] = "CN=0B5C4F4E-1A2B-4C3D-8E9F-001122334455",
+                ["packageFamilyName"] = "12345Contoso.ContosoApp_8wekyb3d8bbwe"
+            });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var application = await StoreApplications.ReserveAsync(client, "Contoso App", TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("9NBLGGH4R317", application.ApplicationId);
+        Assert.AreEqual("Contoso App", application.PrimaryName);
+        Assert.AreEqual("12345Contoso.ContosoApp", application.PackageIdentityName);
+        Assert.HasCount(1, handler.Requests);
+    }
+
+    [TestMethod]
+    public async Task ReserveAsync_WithTakenName_Throws()
+    {
+        // Arrange
+        var client = FakePartnerCenterHandler.CreateClient(new FakePartnerCenterHandler()
+            .On(HttpMethod.Post, "applications", HttpStatusCode.Conflict));
+
+        // Act & Assert
+        var ex = await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => StoreApplications.ReserveAsync(client, "Contoso App", TestContext.CancellationToken));
+        Assert.Contains("reserved already", ex.Message);
+    }
+
+    [TestMethod]
+    public async Task ReleaseAsync_WithSubmissions_Throws()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Delete, "applications/9NBLGGH4R317", HttpStatusCode.Conflict);
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act & Assert
+        var ex = await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => StoreApplications.ReleaseAsync(client, "9NBLGGH4R317", TestContext.CancellationToken));
+        Assert.Contains("9NBLGGH4R317", ex.Message);
+        Assert.AreEqual(HttpMethod.Delete, handler.Requests[0].Method);
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreReviewsTests : BaseCommandTests
+{
+    public StoreReviewsTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public async Task ListAsync_WithFilters_QueriesApiAndDropsAnsweredReviews()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, "analytics/reviews", (_, _) => new JsonObject
+            {
+                ["Value"] = new JsonArray(Review("r1", 1, responseText: null), Review("r2", 2, responseText: "Thanks, fixed in 1.4.1")),
+                ["TotalCount"] = 2
+            });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+        var query = new StoreReviewQuery("9NBLGGH4R315", new DateOnly(2026, 9, 1), new DateOnly(2026, 9, 30), [2, 1], Market: "us", PackageVersion: "1.4.0.0", UnansweredOnly: true);
+
+        // Act
+        var reviews = await StoreReviews.ListAsync(client, query, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(1, reviews);
+        Assert.AreEqual("r1", StoreReviews.GetString(reviews[0], "id"));
+        Assert.AreEqual("(rating eq 1 or rating eq 2) and market eq 'US' and packageVersion eq '1.4.0.0'", StoreReviews.BuildFilter(query));
+        Assert.Contains("orderby=date%20desc", handler.Requests[0].PathAndQuery);
+    }
+
+    [TestMethod]
+    public void RenderTemplate_WithPlaceholders_ReplacesReviewValues()
+    {
+        // Act
+        var text = StoreReviews.RenderTemplate("Hi {ReviewerName}, sorry about the {rating}-star experience in {packageVersion}. {{beta}}", Review("r1", 2, responseText: null));
+
+        // Assert
+        Assert.AreEqual("Hi Alex, sorry about the 2-star experience in 1.4.0.0. {beta}", text);
+    }
+
+    [TestMethod]
+    public void RenderTemplate_WithUnknownPlaceholder_Throws()
+    {
+        Assert.ThrowsExactly<InvalidOperationException>(() => StoreReviews.RenderTemplate("Hi {nickname}", Review("r1", 2, responseText: null)));
+    }
+
+    [TestMethod]
+    public async Task RespondAsync_WithResults_ReportsRejectedReviews()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Post, "reviews/responses", (_, _) => new JsonObject
+            {
+                ["Result"] = new JsonArray(
+                    new JsonObject { ["ReviewId"] = "r1", ["Successful"] = "true" },
+                    new JsonObject { ["ReviewId"] = "r2", ["Successful"] = "false", ["FailureReason"] = "Review was deleted" })
+            });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var results = await StoreReviews.RespondAsync(client, "9NBLGGH4R315", [("r1", "Thanks!"), ("r2", "Thanks!")], "support@contoso.com", isPublic: false, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(2, results);
+        Assert.IsTrue(results[0].Successful);
+        Assert.IsFalse(results[1].Successful);
+        Assert.AreEqual("Review was deleted", results[1].FailureReason);
+        var body = JsonNode.Parse(handler.Requests[0].Body!)!;
+        Assert.AreEqual("support@contoso.com", body["Responses"]![0]!["SupportEmail"]!.GetValue<string>());
+        Assert.IsFalse(body["Responses"]![1]!["IsPublic"]!.GetValue<bool>());
+    }
+
+    [TestMethod]
+    public async Task RespondAsync_WithTooLongResponse_ThrowsBeforePosting()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler();
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => StoreReviews.RespondAsync(client, "9NBLGGH4R315", [("r1", new string('a', StoreReviews.MaxResponseLength + 1))], null, isPublic: true, TestContext.CancellationToken));
+        Assert.IsEmpty(handler.Requests);
+    }
+
+    private static JsonObject Review(string id, int rating, string? responseText) => new()
+    {
+        ["id"] = id,
+        ["date"] = "2026-09-10T08:00:00",
+        ["applicationId"] = "9NBLGGH4R315",
+        ["market"] = "US",
+        ["packageVersion"] = "1.4.0.0",
+        ["rating"] = rating,
+        ["reviewerName"] = "Alex",
+        ["reviewTitle"] = "Crashes on start",
+        ["responseText"] = responseText
+    };
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreRolloutTests : BaseCommandTests
+{
+    private const string SubmissionPath = "applications/9NBLGGH4R315/submissions/1152921504621243610";
+
+    public StoreRolloutTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    [DataRow("25%", 25.0)]
+    [DataRow("12.5", 12.5)]
+    [DataRow(" 100 % ", 100.0)]
+    public void ParsePercentage_WithValidValue_ReturnsPercentage(string value, double expected)
+    {
+        Assert.AreEqual(expected, StoreRollout.ParsePercentage(value));
+    }
+
+    [TestMethod]
+    [DataRow("101%")]
+    [DataRow("-5")]
+    [DataRow("half")]
+    public void ParsePercentage_WithInvalidValue_Throws(string value)
+    {
+        Assert.ThrowsExactly<FormatException>(() => StoreRollout.ParsePercentage(value));
+    }
+
+    [TestMethod]
+    public void Apply_WithoutDeliveryOptions_EnablesRollout()
+    {
+        // Arrange
+        var submission = new JsonObject { ["id"] = "1152921504621243610" };
+
+        // Act
+        StoreRollout.Apply(submission, 10);
+
+        // Assert
+        var rollout = StoreRollout.ReadRollout(submission);
+        Assert.IsTrue(rollout.IsPackageRollout);
+        Assert.AreEqual(10, rollout.Percentage);
+    }
+
+    [TestMethod]
+    public async Task SetPercentageAsync_WithHigherPercentage_PostsUpdate()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, $"{SubmissionPath}/packagerollout", (_, _) => Rollout(10))
+            .On(HttpMethod.Post, $"{SubmissionPath}/updatepackagerolloutpercentage", (_, _) => Rollout(25));
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var rollout = await StoreRollout.SetPercentageAsync(client, "9NBLGGH4R315", "1152921504621243610", 25, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(25, rollout.Percentage);
+        Assert.AreEqual($"{SubmissionPath}/updatepackagerolloutpercentage?percentage=25", handler.Requests[1].PathAndQuery);
+    }
+
+    [TestMethod]
+    public async Task SetPercentageAsync_WithLowerPercentage_ThrowsWithoutPosting()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, $"{SubmissionPath}/packagerollout", (_, _) => Rollout(50));
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => StoreRollout.SetPercentageAsync(client, "9NBLGGH4R315", "1152921504621243610", 25, TestContext.CancellationToken));
+        Assert.HasCount(1, handler.Requests);
+    }
+
+    [TestMethod]
+    public async Task ResolveSubmissionIdAsync_WithoutId_UsesLastPublishedSubmission()
+    {
+        // Arrange
+        var handler = new FakePartnerCenterHandler()
+            .On(HttpMethod.Get, "applications/9NBLGGH4R315", (_, _) => new JsonObject
+            {
+                ["lastPublishedApplicationSubmission"] = new JsonObject { ["id"] = "1152921504621243610" }
+            });
+        var client = FakePartnerCenterHandler.CreateClient(handler);
+
+        // Act
+        var submissionId = await StoreRollout.ResolveSubmissionIdAsync(client, "9NBLGGH4R315", null, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual("1152921504621243610", submissionId);
+    }
+
+    private static JsonObject Rollout(double percentage) => new()
+    {
+        ["isPackageRollout"] = true,
+        ["packageRolloutPercentage"] = percentage,
+        ["packageRolloutStatus"] = "PackageRolloutInProgress",
+        ["fallbackSubmissionId"] = "1152921504621243487"
+    };
+}
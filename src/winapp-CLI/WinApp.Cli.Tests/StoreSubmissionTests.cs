@@ -0,0 +1,252 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Text;
+using System.Text.Json.Nodes;
+using System.Xml;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class StoreSubmissionTests : BaseCommandTests
+{
+    private const int TestBlockSize = 1024;
+    private static readonly Uri SasUri = new("https://example.blob.core.windows.net/uploads/package.zip?sv=2021-08-06&sig=test");
+
+    public StoreSubmissionTests()
+        : base(configPaths: false)
+    {
+    }
+
+    [TestMethod]
+    public async Task UploadAsync_AfterConnectionDrop_ResumesWithMissingBlocks()
+    {
+        // Arrange
+        var file = WriteFile("upload.zip", TestBlockSize * 9 + 100);
+        var blob = new FakeBlockBlobHandler { DropConnectionAfterBlocks = 4 };
+        var uploader = new BlockBlobUploader(new HttpClient(blob));
+        var options = CreateOptions(maxAttempts: 2);
+
+        // Act
+        await Assert.ThrowsExactlyAsync<HttpRequestException>(() => uploader.UploadAsync(file, SasUri, options, cancellationToken: TestContext.CancellationToken));
+        blob.DropConnectionAfterBlocks = null;
+        var result = await uploader.UploadAsync(file, SasUri, options, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(10, result.BlockCount);
+        Assert.AreEqual(4, result.ResumedBlocks);
+        Assert.AreEqual(10, blob.BlockPuts);
+        CollectionAssert.AreEqual(File.ReadAllBytes(file.FullName), blob.CommittedContent);
+    }
+
+    [TestMethod]
+    public async Task UploadAsync_WithTransientErrors_RetriesBlocks()
+    {
+        // Arrange
+        var file = WriteFile("upload.zip", TestBlockSize * 3);
+        var blob = new FakeBlockBlobHandler { ServiceUnavailableResponses = 2 };
+        var uploader = new BlockBlobUploader(new HttpClient(blob));
+        var retries = new List<string>();
+
+        // Act
+        var result = await uploader.UploadAsync(file, SasUri, CreateOptions(maxAttempts: 3), onRetry: retries.Add, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(0, result.ResumedBlocks);
+        Assert.HasCount(2, retries);
+        Assert.Contains("503", retries[0]);
+        CollectionAssert.AreEqual(File.ReadAllBytes(file.FullName), blob.CommittedContent);
+    }
+
+    [TestMethod]
+    public async Task UploadAsync_WhenAlreadyCommitted_SkipsUpload()
+    {
+        // Arrange
+        var file = WriteFile("upload.zip", TestBlockSize * 2 + 1);
+        var blob = new FakeBlockBlobHandler();
+        var uploader = new BlockBlobUploader(new HttpClient(blob));
+        var options = CreateOptions(maxAttempts: 1);
+        await uploader.UploadAsync(file, SasUri, options, cancellationToken: TestContext.CancellationToken);
+
+        // Act
+        var result = await uploader.UploadAsync(file, SasUri, options, cancellationToken: TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(result.AlreadyCommitted);
+        Assert.AreEqual(3, blob.BlockPuts);
+    }
+
+    [TestMethod]
+    public void UpdatePackages_ReplacesSameNamedPackagesAndMarksOthersForDeletion()
+    {
+        // Arrange
+        var submission = JsonNode.Parse("""
+            {
+              "id": "1152921504621243610",
+              "applicationPackages": [
+                { "fileName": "MyApp_x64.msixupload", "fileStatus": "Uploaded", "id": "1" },
+                { "fileName": "MyApp_arm64.msixupload", "fileStatus": "Uploaded", "id": "2" }
+              ]
+            }
+            """)!;
+        var packages = new[] { new FileInfo(Path.Combine(_tempDirectory.FullName, "MyApp_x64.msixupload")) };
+
+        // Act
+        StoreSubmissionService.UpdatePackages(submission, packages, replacePackages: true);
+
+        // Assert
+        var applicationPackages = submission["applicationPackages"]!.AsArray();
+        Assert.HasCount(2, applicationPackages);
+        Assert.AreEqual("MyApp_arm64.msixupload", applicationPackages[0]!["fileName"]!.GetValue<string>());
+        Assert.AreEqual("PendingDelete", applicationPackages[0]!["fileStatus"]!.GetValue<string>());
+        Assert.AreEqual("MyApp_x64.msixupload", applicationPackages[1]!["fileName"]!.GetValue<string>());
+        Assert.AreEqual("PendingUpload", applicationPackages[1]!["fileStatus"]!.GetValue<string>());
+    }
+
+    [TestMethod]
+    public void ApplyReleaseNotes_SetsEveryListingLanguage()
+    {
+        // Arrange
+        var submission = JsonNode.Parse("""
+            {
+              "listings": {
+                "en-us": { "baseListing": { "description": "Notes", "releaseNotes": "Old" } },
+                "de-de": { "baseListing": { "description": "Notizen" } }
+              }
+            }
+            """)!;
+
+        // Act
+        var languages = StoreSubmissionService.ApplyReleaseNotes(submission, "• Add dark mode");
+
+        // Assert
+        CollectionAssert.AreEqual(new[] { "en-us", "de-de" }, languages.ToArray());
+        Assert.AreEqual("• Add dark mode", submission["listings"]!["en-us"]!["baseListing"]!["releaseNotes"]!.GetValue<string>());
+        Assert.AreEqual("• Add dark mode", submission["listings"]!["de-de"]!["baseListing"]!["releaseNotes"]!.GetValue<string>());
+        Assert.AreEqual("Notizen", submission["listings"]!["de-de"]!["baseListing"]!["description"]!.GetValue<string>());
+    }
+
+    [TestMethod]
+    public void SubmissionState_WhenPackageIsRebuilt_DoesNotMatch()
+    {
+        // Arrange
+        var package = WriteFile("MyApp.msixupload", 100);
+        var state = new StoreSubmissionState
+        {
+            ApplicationId = "9NBLGGH4R315",
+            Packages = [StoreSubmissionPackage.FromFile(package)],
+        };
+        var matchedBefore = state.Matches("9NBLGGH4R315", [package]);
+
+        // Act
+        File.WriteAllBytes(package.FullName, new byte[200]);
+
+        // Assert
+        Assert.IsTrue(matchedBefore);
+        Assert.IsFalse(state.Matches("9NBLGGH4R315", [package]));
+        Assert.IsFalse(state.Matches("9WZDNCRFJ3TJ", [package]));
+    }
+
+    private static BlockBlobUploadOptions CreateOptions(int maxAttempts) => new()
+    {
+        BlockSize = TestBlockSize,
+        Concurrency = 1,
+        Retry = new TransientHttpRetry { MaxAttempts = maxAttempts, InitialDelay = TimeSpan.Zero },
+    };
+
+    private FileInfo WriteFile(string fileName, int length)
+    {
+        var content = new byte[length];
+        new Random(length).NextBytes(content);
+        var file = new FileInfo(Path.Combine(_tempDirectory.FullName, fileName));
+        File.WriteAllBytes(file.FullName, content);
+        return file;
+    }
+
+    /// <summary>
+    /// In-memory stand-in for the Put Block, Put Block List and Get Block List operations of Azure Blob Storage
+    /// </summary>
+    private sealed class FakeBlockBlobHandler : HttpMessageHandler
+    {
+        private readonly Dictionary<string, byte[]> uncommitted = new(StringComparer.Ordinal);
+        private List<(string Id, byte[] Data)> committed = [];
+
+        public int? DropConnectionAfterBlocks { get; set; }
+
+        public int ServiceUnavailableResponses { get; set; }
+
+        public int BlockPuts { get; private set; }
+
+        public byte[] CommittedContent => committed.SelectMany(b => b.Data).ToArray();
+
+        protected override async Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+        {
+            var query = request.RequestUri!.Query.TrimStart('?').Split('&')
+                .Select(p => p.Split('=', 2))
+                .ToDictionary(p => p[0], p => Uri.UnescapeDataString(p.Length > 1 ? p[1] : string.Empty));
+            var comp = query.GetValueOrDefault("comp");
+
+            if (request.Method == HttpMethod.Get && comp == "blocklist")
+            {
+                if (uncommitted.Count == 0 && committed.Count == 0)
+                {
+                    return new HttpResponseMessage(HttpStatusCode.NotFound);
+                }
+
+                var xml = new StringBuilder("<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList><CommittedBlocks>");
+                foreach (var (id, data) in committed)
+                {
+                    xml.Append($"<Block><Name>{id}</Name><Size>{data.Length}</Size></Block>");
+                }
+                xml.Append("</CommittedBlocks><UncommittedBlocks>");
+                foreach (var (id, data) in uncommitted)
+                {
+                    xml.Append($"<Block><Name>{id}</Name><Size>{data.Length}</Size></Block>");
+                }
+                xml.Append("</UncommittedBlocks></BlockList>");
+                return new HttpResponseMessage(HttpStatusCode.OK) { Content = new StringContent(xml.ToString()) };
+            }
+
+            if (request.Method == HttpMethod.Put && comp == "block")
+            {
+                if (DropConnectionAfterBlocks is int limit && BlockPuts >= limit)
+                {
+                    throw new HttpRequestException("The connection was reset");
+                }
+                if (ServiceUnavailableResponses > 0)
+                {
+                    ServiceUnavailableResponses--;
+                    return new HttpResponseMessage(HttpStatusCode.ServiceUnavailable);
+                }
+
+                uncommitted[query["blockid"]] = await request.Content!.ReadAsByteArrayAsync(cancellationToken);
+                BlockPuts++;
+                return new HttpResponseMessage(HttpStatusCode.Created);
+            }
+
+            if (request.Method == HttpMethod.Put && comp == "blocklist")
+            {
+                var xmlDoc = new XmlDocument();
+                xmlDoc.LoadXml(await request.Content!.ReadAsStringAsync(cancellationToken));
+                var blocks = new List<(string, byte[])>();
+                foreach (XmlElement latest in xmlDoc.SelectNodes("/BlockList/Latest")!)
+                {
+                    if (!uncommitted.TryGetValue(latest.InnerText, out var data))
+                    {
+                        return new HttpResponseMessage(HttpStatusCode.BadRequest);
+                    }
+                    blocks.Add((latest.InnerText, data));
+                }
+
+                committed = blocks;
+                uncommitted.Clear();
+                return new HttpResponseMessage(HttpStatusCode.Created);
+            }
+
+            return new HttpResponseMessage(HttpStatusCode.BadRequest);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Extensions.DependencyInjection;
+using System.Collections.Concurrent;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+using WinApp.Cli.TestMatrix;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class TestMatrixTests : BaseCommandTests
+{
+    private const string TestManifest = @"<?xml version=""1.0"" encoding=""utf-8""?>
+<Package xmlns=""http://schemas.microsoft.com/appx/manifest/foundation/windows10"">
+  <Identity Name=""Contoso.Notes"" Publisher=""CN=Contoso"" Version=""1.2.0.0"" ProcessorArchitecture=""x64"" />
+</Package>";
+
+    private readonly RecordingPowerShellService _powerShell = new();
+
+    public TestMatrixTests()
+        : base(configPaths: false)
+    {
+    }
+
+    protected override IServiceCollection ConfigureServices(IServiceCollection services)
+    {
+        return services
+            .AddSingleton<IPowerShellService>(_powerShell);
+    }
+
+    [TestInitialize]
+    public void Setup()
+    {
+        Environment.SetEnvironmentVariable(TestMachineScript.UserNameVariable, "tester");
+        Environment.SetEnvironmentVariable(TestMachineScript.PasswordVariable, "P@ssw0rd!");
+    }
+
+    [TestCleanup]
+    public void Cleanup()
+    {
+        Environment.SetEnvironmentVariable(TestMachineScript.UserNameVariable, null);
+        Environment.SetEnvironmentVariable(TestMachineScript.PasswordVariable, null);
+    }
+
+    private void SaveTargets(params TestTargetConfig[] targets)
+    {
+        var config = new WinappConfig { TestMatrix = new TestMatrixConfig { SmokeTest = "smoke.ps1" } };
+        config.TestMatrix.Targets.AddRange(targets);
+        GetRequiredService<IConfigService>().Save(config);
+    }
+
+    private TestTargetConfig CreateHyperVTarget(string name)
+    {
+        var image = Path.Combine(_tempDirectory.FullName, $"{name}.vhdx");
+        File.WriteAllText(image, "disk");
+        return new TestTargetConfig { Name = name, Provider = TestTargetConfig.HyperV, Image = $"{name}.vhdx" };
+    }
+
+    private async Task<FileInfo> CreateSignedPackageAsync()
+    {
+        var layout = _tempDirectory.CreateSubdirectory("layout");
+        await File.WriteAllTextAsync(Path.Combine(layout.FullName, "appxmanifest.xml"), TestManifest, TestContext.CancellationToken);
+        await File.WriteAllTextAsync(Path.Combine(layout.FullName, "Notes.exe"), "fake exe content", TestContext.CancellationToken);
+
+        var packagePath = new FileInfo(Path.Combine(_tempDirectory.FullName, "Contoso.Notes.msix"));
+        await AppxPacker.PackAsync(layout, packagePath, TestContext.CancellationToken);
+
+        using var rsa = RSA.Create(2048);
+        var request = new CertificateRequest("CN=Contoso", rsa, HashAlgorithmName.SHA256, RSASignaturePadding.Pkcs1);
+        request.CertificateExtensions.Add(new X509EnhancedKeyUsageExtension([new Oid("1.3.6.1.5.5.7.3.3")], critical: false));
+        using var certificate = request.CreateSelfSigned(DateTimeOffset.UtcNow.AddDays(-1), DateTimeOffset.UtcNow.AddDays(30));
+        var certificatePath = Path.Combine(_tempDirectory.FullName, "contoso.pfx");
+        await File.WriteAllBytesAsync(certificatePath, certificate.Export(X509ContentType.Pfx, "password"), TestContext.CancellationToken);
+        await GetRequiredService<INativePackagingService>().SignAsync(packagePath, new FileInfo(certificatePath), TestTaskContext, "password", cancellationToken: TestContext.CancellationToken);
+
+        return packagePath;
+    }
+
+    [TestMethod]
+    public void Save_RoundTripsTheTestMatrix()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        var config = new WinappConfig { TestMatrix = new TestMatrixConfig { SmokeTest = @"tests\smoke.ps1", Timeout = 60 } };
+        config.TestMatrix.Targets.Add(new TestTargetConfig { Name = "win10-21h2", Provider = TestTargetConfig.HyperV, Image = @"D:\images\win10.vhdx", Switch = "External", Memory = 8 });
+        config.TestMatrix.Targets.Add(new TestTargetConfig { Name = "server-2022", Provider = TestTargetConfig.Azure, Image = "Windows Server 2022 Datacenter", Lab = "contoso-test", ResourceGroup = "contoso-rg", Size = "Standard_D4s_v5" });
+
+        // Act
+        configService.Save(config);
+        var loaded = configService.Load().TestMatrix;
+
+        // Assert
+        Assert.IsNotNull(loaded);
+        Assert.AreEqual(@"tests\smoke.ps1", loaded.SmokeTest);
+        Assert.AreEqual(60, loaded.Timeout);
+        Assert.HasCount(2, loaded.Targets);
+        Assert.AreEqual(@"D:\images\win10.vhdx", loaded.Targets[0].Image);
+        Assert.AreEqual("External", loaded.Targets[0].Switch);
+        Assert.AreEqual(8, loaded.Targets[0].Memory);
+        Assert.AreEqual(TestTargetConfig.Azure, loaded.Targets[1].Provider);
+        Assert.AreEqual("contoso-test", loaded.Targets[1].Lab);
+        Assert.AreEqual("contoso-rg", loaded.Targets[1].ResourceGroup);
+        Assert.AreEqual("Standard_D4s_v5", loaded.Targets[1].Size);
+    }
+
+    [TestMethod]
+    public void GetTargets_SelectsByNameAndRejectsUnknownOrIncompleteTargets()
+    {
+        // Arrange
+        SaveTargets(
+            new TestTargetConfig { Name = "win10-21h2", Provider = TestTargetConfig.HyperV, Image = "win10.vhdx" },
+            new TestTargetConfig { Name = "win11-23h2", Provider = TestTargetConfig.HyperV, Image = "win11.vhdx" },
+            new TestTargetConfig { Name = "server-2022", Provider = TestTargetConfig.Azure, Image = "Windows Server 2022 Datacenter" });
+        var testMatrixService = GetRequiredService<ITestMatrixService>();
+
+        // Act
+        var selected = testMatrixService.GetTargets(["WIN11-23H2"]);
+
+        // Assert
+        Assert.HasCount(1, selected);
+        Assert.AreEqual("win11-23h2", selected[0].Name);
+        var unknown = Assert.ThrowsExactly<InvalidOperationException>(() => testMatrixService.GetTargets(["win7"]));
+        StringAssert.Contains(unknown.Message, "win10-21h2, win11-23h2, server-2022");
+        var incomplete = Assert.ThrowsExactly<InvalidOperationException>(() => testMatrixService.GetTargets([]));
+        StringAssert.Contains(incomplete.Message, "'server-2022' needs the lab and resourceGroup");
+    }
+
+    [TestMethod]
+    public void Script_InstallsThePackageAndReportsEachStage()
+    {
+        // Act
+        var script = TestMachineScript.Create("Contoso.Notes", "Contoso.Notes.msix", ["Microsoft.WindowsAppRuntime.1.8.msix"], "Contoso.Notes.cer", "smoke.ps1");
+
+        // Assert
+        StringAssert.Contains(script, @"Import-Certificate -FilePath 'Contoso.Notes.cer' -CertStoreLocation Cert:\LocalMachine\TrustedPeople");
+        StringAssert.Contains(script, "Add-AppxPackage -Path 'Contoso.Notes.msix' -DependencyPath 'Microsoft.WindowsAppRuntime.1.8.msix'");
+        StringAssert.Contains(script, "-File 'smoke.ps1'");
+        Assert.IsNull(TestMachineScript.ParseResult("Installed Contoso.Notes\n##winapp-test deploy=passed\nok\n##winapp-test smoke=0\n", hasSmokeTest: true).FailedStage);
+        Assert.IsNull(TestMachineScript.ParseResult("##winapp-test deploy=passed\r\n", hasSmokeTest: false).FailedStage);
+        Assert.AreEqual(TestMatrixStage.Deploy, TestMachineScript.ParseResult("Deployment failed with HRESULT: 0x80073CF3\n##winapp-test deploy=failed\n", hasSmokeTest: true).FailedStage);
+        Assert.AreEqual(TestMatrixStage.Deploy, TestMachineScript.ParseResult("", hasSmokeTest: false).FailedStage);
+        var smokeTest = TestMachineScript.ParseResult("##winapp-test deploy=passed\n##winapp-test smoke=3\n", hasSmokeTest: true);
+        Assert.AreEqual(TestMatrixStage.SmokeTest, smokeTest.FailedStage);
+        Assert.AreEqual("The smoke test exited with code 3", smokeTest.Error);
+        Assert.AreEqual(TestMatrixStage.SmokeTest, TestMachineScript.ParseResult("##winapp-test deploy=passed\n", hasSmokeTest: true).FailedStage);
+    }
+
+    [TestMethod]
+    public async Task Run_TestsEachTargetInANewMachineAndDeletesIt()
+    {
+        // Arrange
+        SaveTargets(CreateHyperVTarget("win10-21h2"), CreateHyperVTarget("win11-23h2"));
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "smoke.ps1"), "exit 0");
+        var package = await CreateSignedPackageAsync();
+        _powerShell.Respond = command => command.Contains("[scriptblock]::Create")
+            ? (0, command.Contains("-VMName 'wawin11")
+                ? "##winapp-test deploy=passed\nNotes.exe crashed\n##winapp-test smoke=3\n"
+                : "##winapp-test deploy=passed\n##winapp-test smoke=0\n")
+            : (0, "");
+
+        // Act
+        var results = await GetRequiredService<ITestMatrixService>().RunAsync(new TestMatrixOptions { Package = package, Parallel = 2 }, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(2, results);
+        Assert.AreEqual("win10-21h2", results[0].Target);
+        Assert.IsTrue(results[0].Passed);
+        Assert.AreEqual("Hyper-V", results[0].Provider);
+        Assert.AreEqual(TestMatrixStage.SmokeTest, results[1].FailedStage);
+        StringAssert.Contains(results[1].Output, "Notes.exe crashed");
+        Assert.IsNull(results[1].Machine);
+
+        var commands = _powerShell.Commands.ToList();
+        Assert.HasCount(2, commands.Where(c => c.Contains("New-VHD")));
+        Assert.HasCount(2, commands.Where(c => c.Contains("Remove-VM")));
+        Assert.IsTrue(commands.Any(c => c.Contains("Contoso.Notes.cer") && c.Contains("Copy-Item")), "The self-signed signer's certificate is copied to the machine");
+        Assert.IsTrue(commands.Any(c => c.Contains("smoke.ps1") && c.Contains("Copy-Item")));
+        Assert.IsFalse(commands.Any(c => c.Contains("P@ssw0rd!")), "The password stays in the environment");
+    }
+
+    [TestMethod]
+    public async Task Run_ReportsTheStageThatFailedAndKeepsMachinesWhenAsked()
+    {
+        // Arrange
+        SaveTargets(CreateHyperVTarget("win10-21h2"));
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "smoke.ps1"), "exit 0");
+        var package = await CreateSignedPackageAsync();
+        _powerShell.Respond = command => command.Contains("New-VHD") ? (1, "New-VM : The virtual switch 'Default Switch' doesn't exist") : (0, "");
+
+        // Act
+        var results = await GetRequiredService<ITestMatrixService>().RunAsync(new TestMatrixOptions { Package = package, KeepMachines = true }, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        var result = results.Single();
+        Assert.AreEqual(TestMatrixStage.Provision, result.FailedStage);
+        StringAssert.Contains(result.Error, "Default Switch");
+        Assert.IsNotNull(result.Machine);
+        Assert.IsFalse(_powerShell.Commands.Any(c => c.Contains("Remove-VM")));
+    }
+
+    [TestMethod]
+    public void CreateJUnitReport_WritesATestCasePerTarget()
+    {
+        // Arrange
+        List<TestTargetResult> results =
+        [
+            new("win10-21h2", "Hyper-V", null, null, TimeSpan.FromSeconds(90), "##winapp-test deploy=passed"),
+            new("server-2022", "Azure DevTest Labs", TestMatrixStage.Deploy, "The package didn't install", TimeSpan.FromSeconds(30.5), "0x80073CF3"),
+        ];
+
+        // Act
+        var report = TestMatrixService.CreateJUnitReport(results);
+
+        // Assert
+        var suite = report.Root!.Element("testsuite")!;
+        Assert.AreEqual("2", (string?)suite.Attribute("tests"));
+        Assert.AreEqual("1", (string?)suite.Attribute("failures"));
+        Assert.AreEqual("120.5", (string?)suite.Attribute("time"));
+        var failure = suite.Elements("testcase").Single(t => (string?)t.Attribute("name") == "server-2022").Element("failure")!;
+        Assert.AreEqual("install", (string?)failure.Attribute("type"));
+        Assert.AreEqual("The package didn't install", (string?)failure.Attribute("message"));
+        Assert.AreEqual("0x80073CF3", failure.Value);
+        Assert.IsTrue(TestMatrixService.CreateMachineName("windows-server-2022-datacenter").Length <= 15);
+    }
+
+    private sealed class RecordingPowerShellService : IPowerShellService
+    {
+        public ConcurrentQueue<string> Commands { get; } = new();
+
+        public Func<string, (int, string)> Respond { get; set; } = _ => (0, "");
+
+        public Task<(int exitCode, string output)> RunCommandAsync(string command, TaskContext taskContext, bool elevated = false, Dictionary<string, string>? environmentVariables = null, CancellationToken cancellationToken = default)
+        {
+            Commands.Enqueue(command);
+            return Task.FromResult(Respond(command));
+        }
+    }
+}
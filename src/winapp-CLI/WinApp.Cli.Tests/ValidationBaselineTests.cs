@@ -0,0 +1,120 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ValidationBaselineTests : BaseCommandTests
+{
+    private const string VisualElements = "Package/Applications/Application[Notes]/VisualElements";
+
+    public ValidationBaselineTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateManifest(string name = "appxmanifest.xml")
+    {
+        var path = Path.Combine(_tempDirectory.FullName, name);
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Dependencies>
+                <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.17763.0" MaxVersionTested="10.0.22621.0" />
+              </Dependencies>
+              <Applications>
+                <Application Id="Notes" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />
+                </Application>
+              </Applications>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private FileInfo BaselinePath => new(Path.Combine(_tempDirectory.FullName, "winapp-baseline.json"));
+
+    private static ValidationFinding Finding(string rule, string element, string message, string file = "appxmanifest.xml") =>
+        new(AccessibilitySeverity.Error, rule, file, element, null, message);
+
+    [TestMethod]
+    public async Task WriteBaselineAsync_RecordsFindingsSoTheyAreNotNew()
+    {
+        // Arrange
+        var service = GetRequiredService<IValidationService>();
+        var manifest = CreateManifest();
+        var findings = await service.ValidateAsync(manifest, BaselinePath, allowScriptAnalyzers: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Act
+        await service.WriteBaselineAsync(BaselinePath, manifest, findings, TestContext.CancellationToken);
+        var result = await service.CompareAsync(BaselinePath, manifest, findings, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(findings.Any(f => f.Rule == "a11y/asset" && f.Element == "Package/Properties/Logo"), "The assets don't exist");
+        Assert.IsTrue(findings.All(f => f.File == "appxmanifest.xml"));
+        Assert.HasCount(0, result.New);
+        Assert.HasCount(findings.Count, result.Baselined);
+        Assert.Contains("\"rule\": \"a11y/asset\"", File.ReadAllText(BaselinePath.FullName));
+    }
+
+    [TestMethod]
+    public async Task CompareAsync_ReportsFindingsBeyondTheRecordedCountAsNew()
+    {
+        // Arrange
+        var service = GetRequiredService<IValidationService>();
+        var manifest = CreateManifest();
+        await service.WriteBaselineAsync(BaselinePath, manifest,
+            [Finding("a11y/contrast", VisualElements, "Tile contrast is 2.1:1"), Finding("a11y/asset", "Package/Properties/Logo", "Missing")],
+            TestContext.CancellationToken);
+
+        // Act
+        var result = await service.CompareAsync(BaselinePath, manifest,
+            [Finding("a11y/contrast", VisualElements, "Tile name contrast is 1.9:1"), Finding("a11y/contrast", VisualElements, "Tile contrast is 2.1:1")],
+            TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(1, result.New);
+        Assert.AreEqual("Tile name contrast is 1.9:1", result.New[0].Message, "The finding whose message was recorded is the baselined one");
+        Assert.HasCount(1, result.Baselined);
+        Assert.AreEqual(1, result.Fixed, "The asset was added");
+    }
+
+    [TestMethod]
+    public async Task WriteBaselineAsync_KeepsTheFindingsOfOtherManifests()
+    {
+        // Arrange
+        var service = GetRequiredService<IValidationService>();
+        var notes = CreateManifest();
+        var viewer = CreateManifest("viewer.appxmanifest");
+        await service.WriteBaselineAsync(BaselinePath, viewer, [Finding("scales/dpi", "Package/Properties/Logo", "Blurry", "viewer.appxmanifest")], TestContext.CancellationToken);
+        await service.WriteBaselineAsync(BaselinePath, notes, [Finding("scales/dpi", "Package/Properties/Logo", "Blurry")], TestContext.CancellationToken);
+
+        // Act
+        await service.WriteBaselineAsync(BaselinePath, notes, [], TestContext.CancellationToken);
+        var viewerResult = await service.CompareAsync(BaselinePath, viewer, [Finding("scales/dpi", "Package/Properties/Logo", "Blurry", "viewer.appxmanifest")], TestContext.CancellationToken);
+        var notesResult = await service.CompareAsync(BaselinePath, notes, [Finding("scales/dpi", "Package/Properties/Logo", "Blurry")], TestContext.CancellationToken);
+
+        // Assert
+        Assert.HasCount(0, viewerResult.New);
+        Assert.HasCount(1, notesResult.New, "Writing an empty baseline for a manifest tightens it");
+    }
+
+    [TestMethod]
+    public async Task CompareAsync_RejectsAnUnreadableBaseline()
+    {
+        // Arrange
+        var service = GetRequiredService<IValidationService>();
+        var manifest = CreateManifest();
+        File.WriteAllText(BaselinePath.FullName, "not json");
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => service.CompareAsync(BaselinePath, manifest, [], TestContext.CancellationToken));
+    }
+}
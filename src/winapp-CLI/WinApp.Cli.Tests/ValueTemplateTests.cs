@@ -0,0 +1,89 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class ValueTemplateTests : BaseCommandTests
+{
+    public ValueTemplateTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static TemplateVariables CreateVariables() => new TemplateVariables()
+        .SetVersion("1.4.2.0")
+        .SetProvenance(new BuildProvenance { GitSha = "3f9c2d0e5b1a7c4f8e2d", Branch = "main", Profile = "Beta" })
+        .SetVariant(new MatrixVariant(new BrandingConfig { Name = "fabrikam", DisplayName = "Fabrikam Notes & Tasks" }, new ChannelConfig { Name = "beta" }, null));
+
+    [TestMethod]
+    public void Render_ReplacesVariablesAndAppliesFilters()
+    {
+        // Arrange
+        var variables = CreateVariables();
+
+        // Act
+        var text = ValueTemplate.Render("{{ branding.displayName|xml }} {{version.major}}.{{Version.Minor}} ({{git.sha|short}}, {{profile|lower}}{{channel.nameSuffix}}) {{'{{'}}", variables, "test");
+
+        // Assert
+        Assert.AreEqual("Fabrikam Notes &amp; Tasks 1.4 (3f9c2d0, beta) {{", text);
+        Assert.AreEqual("no placeholders {single}", ValueTemplate.Render("no placeholders {single}", variables, "test"));
+        Assert.AreEqual("false", ValueTemplate.Render("{{git.dirty}}", variables, "test"));
+        Assert.AreEqual("fabrikam-beta", ValueTemplate.Render("{{variant}}", variables, "test"));
+    }
+
+    [TestMethod]
+    public void Render_UndefinedVariablesAreErrorsUnlessDefaulted()
+    {
+        // Arrange
+        var variables = CreateVariables();
+        Environment.SetEnvironmentVariable("WINAPP_TEMPLATE_TEST", "42");
+        try
+        {
+            // Act & Assert
+            Assert.AreEqual("42", ValueTemplate.Render("{{env.WINAPP_TEMPLATE_TEST}}", variables, "test"));
+            Assert.AreEqual("0", ValueTemplate.Render("{{env.WINAPP_TEMPLATE_MISSING|upper|default:0}}", variables, "test"));
+            Assert.AreEqual("", ValueTemplate.Render("{{architecture|default:}}", variables, "test"));
+
+            var undefined = Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.Render("Name=\"{{branding.identityname}}\"", variables, "appxmanifest.xml"));
+            StringAssert.Contains(undefined.Message, "appxmanifest.xml line 1: branding.identityname is not defined");
+            StringAssert.Contains(undefined.Message, "version.major");
+            Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.Render("{{env.WINAPP_TEMPLATE_MISSING}}", variables, "test"));
+            Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.Render("{{version|reverse}}", variables, "test"));
+            Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.Render("{{version + 1}}", variables, "test"));
+            Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.Render("line 1\n{{version\n}}", variables, "test"));
+        }
+        finally
+        {
+            Environment.SetEnvironmentVariable("WINAPP_TEMPLATE_TEST", null);
+        }
+    }
+
+    [TestMethod]
+    public void RenderManifest_RendersTheIdentityFirst()
+    {
+        // Arrange
+        var variables = new TemplateVariables().Set("build", "17");
+        var manifest = """
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.4.{{build}}.0" />
+              <Properties>
+                <DisplayName>Notes {{version.major}}.{{version.minor}} build {{version.build}}</DisplayName>
+                <PublisherDisplayName>{{package.publisher}}</PublisherDisplayName>
+              </Properties>
+            </Package>
+            """;
+
+        // Act
+        var rendered = ValueTemplate.RenderManifest(manifest, variables, "appxmanifest.xml");
+
+        // Assert
+        Assert.Contains("Version=\"1.4.17.0\"", rendered);
+        Assert.Contains("<DisplayName>Notes 1.4 build 17</DisplayName>", rendered);
+        Assert.Contains("<PublisherDisplayName>CN=Contoso</PublisherDisplayName>", rendered);
+        Assert.ThrowsExactly<InvalidOperationException>(() => ValueTemplate.RenderManifest("<Package><Identity Name=\"{{package.name}}\" /></Package>", new TemplateVariables(), "appxmanifest.xml"));
+    }
+}
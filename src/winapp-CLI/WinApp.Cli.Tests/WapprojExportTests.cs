@@ -0,0 +1,129 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class WapprojExportTests : BaseCommandTests
+{
+    public WapprojExportTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreateProject()
+    {
+        var appDirectory = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Notes"));
+        File.WriteAllText(Path.Combine(appDirectory.FullName, "Notes.csproj"), """<Project Sdk="Microsoft.NET.Sdk" />""");
+
+        var assets = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Assets"));
+        foreach (var name in new[] { "StoreLogo.png", "Square44x44Logo.scale-100.png", "Square44x44Logo.scale-200.png", "Square150x150Logo.png" })
+        {
+            File.WriteAllBytes(Path.Combine(assets.FullName, name), [0]);
+        }
+
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                <PublisherDisplayName>Contoso</PublisherDisplayName>
+                <Logo>Assets\StoreLogo.png</Logo>
+              </Properties>
+              <Dependencies>
+                <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.19041.0" MaxVersionTested="10.0.26100.0" />
+              </Dependencies>
+              <Resources>
+                <Resource Language="de-DE" />
+              </Resources>
+              <Applications>
+                <Application Id="App" Executable="Notes.exe" EntryPoint="Windows.FullTrustApplication">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Assets\Square150x150Logo.png" Square44x44Logo="Assets\Square44x44Logo.png" />
+                </Application>
+              </Applications>
+            </Package>
+            """);
+
+        var config = new WinappConfig();
+        config.Projects.Add(new ProjectConfig { Name = "Notes", Path = "Notes" });
+        config.SetVersion("Microsoft.WindowsAppSDK", "1.6.250108002");
+        config.Environment.Add(new EnvironmentVariableConfig { Name = "NOTES_HOME", Value = "%LOCALAPPDATA%\\Notes" });
+        config.Rules["a11y/contrast"] = "off";
+        GetRequiredService<IConfigService>().Save(config);
+        return new FileInfo(path);
+    }
+
+    [TestMethod]
+    public async Task ExportAsync_WritesPackagingProjectForTheEntryPoint()
+    {
+        // Arrange
+        var manifest = CreateProject();
+
+        // Act
+        var export = await GetRequiredService<IWapprojExportService>().ExportAsync(manifest, null, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.AreEqual(Path.Combine(_tempDirectory.FullName, "Notes.Package", "Notes.Package.wapproj"), export.Project.FullName);
+        var project = XDocument.Load(export.Project.FullName).Root!;
+        string? Property(string name) => project.Descendants().FirstOrDefault(e => e.Name.LocalName == name)?.Value;
+        List<string> Items(string type) => project.Descendants().Where(e => e.Name.LocalName == type).Select(e => e.Attribute("Include")!.Value).ToList();
+        Assert.AreEqual("10.0.26100.0", Property("TargetPlatformVersion"));
+        Assert.AreEqual("10.0.19041.0", Property("TargetPlatformMinVersion"));
+        Assert.AreEqual("de-DE", Property("DefaultLanguage"));
+        Assert.AreEqual(@"..\Notes\Notes.csproj", Property("EntryPointProjectUniqueName"));
+        CollectionAssert.AreEqual(new[] { @"..\Notes\Notes.csproj" }, Items("ProjectReference"));
+        CollectionAssert.AreEqual(new[] { "Microsoft.WindowsAppSDK" }, Items("PackageReference"));
+        Assert.Contains(@"Images\Square44x44Logo.scale-200.png", Items("Content"));
+        Assert.HasCount(6, Items("ProjectConfiguration"));
+        Assert.AreEqual(4, export.Images);
+
+        var root = XDocument.Load(Path.Combine(_tempDirectory.FullName, "Notes.Package", "Package.appxmanifest")).Root!;
+        var application = root.Descendants().Single(e => e.Name.LocalName == "Application");
+        Assert.AreEqual("$targetnametoken$.exe", application.Attribute("Executable")!.Value);
+        Assert.AreEqual("$targetentrypoint$", application.Attribute("EntryPoint")!.Value);
+        Assert.AreEqual(@"Images\Square44x44Logo.png", root.Descendants().Single(e => e.Name.LocalName == "VisualElements").Attribute("Square44x44Logo")!.Value);
+        Assert.AreEqual("NOTES_HOME", root.Descendants().Single(e => e.Name.LocalName == "EnvironmentVariable").Attribute("Name")!.Value);
+        Assert.IsTrue(File.Exists(Path.Combine(_tempDirectory.FullName, "Notes.Package", "Images", "Square44x44Logo.scale-100.png")));
+        Assert.IsTrue(export.Manual.Any(m => m.Contains("winapp validate")), "Visual Studio doesn't run the rules of winapp.yaml");
+    }
+
+    [TestMethod]
+    public async Task ExportAsync_MigratesBackToTheSameManifest()
+    {
+        // Arrange
+        var manifest = CreateProject();
+        var export = await GetRequiredService<IWapprojExportService>().ExportAsync(manifest, null, force: false, TestTaskContext, TestContext.CancellationToken);
+        File.Delete(manifest.FullName);
+        Directory.Delete(Path.Combine(_tempDirectory.FullName, "Assets"), recursive: true);
+
+        // Act
+        var migration = await GetRequiredService<IWapprojMigrationService>().MigrateAsync(export.Project, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        var root = XDocument.Load(migration.Manifest.FullName).Root!;
+        Assert.AreEqual("Notes.exe", root.Descendants().Single(e => e.Name.LocalName == "Application").Attribute("Executable")!.Value);
+        Assert.AreEqual(@"Assets\Square150x150Logo.png", root.Descendants().Single(e => e.Name.LocalName == "VisualElements").Attribute("Square150x150Logo")!.Value);
+        Assert.AreEqual(4, migration.Assets);
+        Assert.AreEqual("Notes", GetRequiredService<IConfigService>().Load().Projects.Single().Path);
+    }
+
+    [TestMethod]
+    public async Task ExportAsync_KeepsAnExistingProjectUnlessForced()
+    {
+        // Arrange
+        var manifest = CreateProject();
+        var service = GetRequiredService<IWapprojExportService>();
+        var export = await service.ExportAsync(manifest, null, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => service.ExportAsync(manifest, null, force: false, TestTaskContext, TestContext.CancellationToken));
+        var forced = await service.ExportAsync(manifest, null, force: true, TestTaskContext, TestContext.CancellationToken);
+        Assert.AreEqual(export.Project.FullName, forced.Project.FullName);
+    }
+}
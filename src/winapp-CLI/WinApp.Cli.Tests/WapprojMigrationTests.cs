@@ -0,0 +1,160 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class WapprojMigrationTests : BaseCommandTests
+{
+    public WapprojMigrationTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private FileInfo CreatePackagingProject(string extraProperties = "")
+    {
+        var packageDirectory = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Notes.Package"));
+        var appDirectory = Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "Notes"));
+        File.WriteAllText(Path.Combine(appDirectory.FullName, "Notes.csproj"), """
+            <Project Sdk="Microsoft.NET.Sdk">
+              <PropertyGroup>
+                <OutputType>WinExe</OutputType>
+                <AssemblyName>ContosoNotes</AssemblyName>
+              </PropertyGroup>
+            </Project>
+            """);
+
+        var images = Directory.CreateDirectory(Path.Combine(packageDirectory.FullName, "Images"));
+        foreach (var name in new[] { "StoreLogo.png", "Square44x44Logo.scale-100.png", "Square44x44Logo.scale-200.png", "Square44x44Logo.targetsize-24_altform-unplated.png", "SplashScreen.scale-200.png" })
+        {
+            File.WriteAllBytes(Path.Combine(images.FullName, name), [0]);
+        }
+
+        File.WriteAllText(Path.Combine(packageDirectory.FullName, "Package.appxmanifest"), """
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10" xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10" xmlns:desktop6="http://schemas.microsoft.com/appx/manifest/desktop/windows10/6">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                <PublisherDisplayName>Contoso</PublisherDisplayName>
+                <Logo>Images\StoreLogo.png</Logo>
+              </Properties>
+              <Dependencies>
+                <TargetDeviceFamily Name="Windows.Desktop" MinVersion="10.0.0.0" MaxVersionTested="10.0.0.0" />
+              </Dependencies>
+              <Applications>
+                <Application Id="App" Executable="$targetnametoken$.exe" EntryPoint="$targetentrypoint$">
+                  <uap:VisualElements DisplayName="Contoso Notes" Description="Notes" BackgroundColor="transparent" Square150x150Logo="Images\Square150x150Logo.png" Square44x44Logo="Images\Square44x44Logo.png">
+                    <uap:SplashScreen Image="Images\SplashScreen.png" />
+                  </uap:VisualElements>
+                  <Extensions>
+                    <desktop6:Extension Category="windows.environmentVariables">
+                      <desktop6:EnvironmentVariables>
+                        <desktop6:EnvironmentVariable Name="NOTES_HOME" Value="%LOCALAPPDATA%\Notes" />
+                      </desktop6:EnvironmentVariables>
+                    </desktop6:Extension>
+                  </Extensions>
+                </Application>
+              </Applications>
+            </Package>
+            """);
+
+        var wapprojPath = Path.Combine(packageDirectory.FullName, "Notes.Package.wapproj");
+        File.WriteAllText(wapprojPath, $"""
+            <Project ToolsVersion="15.0" xmlns="http://schemas.microsoft.com/developer/msbuild/2003">
+              <PropertyGroup>
+                <TargetPlatformVersion>10.0.22621.0</TargetPlatformVersion>
+                <TargetPlatformMinVersion>10.0.17763.0</TargetPlatformMinVersion>
+                <EntryPointProjectUniqueName>..\Notes\Notes.csproj</EntryPointProjectUniqueName>
+                {extraProperties}
+              </PropertyGroup>
+              <ItemGroup>
+                <AppxManifest Include="Package.appxmanifest" />
+                <Content Include="Images\StoreLogo.png" />
+                <Content Include="Images\Square44x44Logo.scale-200.png" />
+                <Content Include="README.txt" />
+              </ItemGroup>
+              <ItemGroup>
+                <PackageReference Include="Microsoft.WindowsAppSDK" Version="1.6.250108002" />
+                <PackageReference Include="Contoso.Telemetry" Version="2.*" />
+              </ItemGroup>
+              <ItemGroup>
+                <ProjectReference Include="..\Notes\Notes.csproj" />
+              </ItemGroup>
+            </Project>
+            """);
+        return new FileInfo(wapprojPath);
+    }
+
+    [TestMethod]
+    public async Task MigrateAsync_ResolvesTokensAndMovesImagesToAssets()
+    {
+        // Arrange
+        var wapproj = CreatePackagingProject();
+
+        // Act
+        var migration = await GetRequiredService<IWapprojMigrationService>().MigrateAsync(wapproj, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        var root = XDocument.Load(migration.Manifest.FullName).Root!;
+        var application = root.Descendants().Single(e => e.Name.LocalName == "Application");
+        Assert.AreEqual("ContosoNotes.exe", application.Attribute("Executable")!.Value);
+        Assert.AreEqual("Windows.FullTrustApplication", application.Attribute("EntryPoint")!.Value);
+        var family = root.Descendants().Single(e => e.Name.LocalName == "TargetDeviceFamily");
+        Assert.AreEqual("10.0.17763.0", family.Attribute("MinVersion")!.Value);
+        Assert.AreEqual("10.0.22621.0", family.Attribute("MaxVersionTested")!.Value);
+        Assert.AreEqual(@"Assets\StoreLogo.png", root.Descendants().Single(e => e.Name.LocalName == "Logo").Value);
+        Assert.AreEqual(@"Assets\Square44x44Logo.png", root.Descendants().Single(e => e.Name.LocalName == "VisualElements").Attribute("Square44x44Logo")!.Value);
+
+        var assets = Path.Combine(_tempDirectory.FullName, "Assets");
+        Assert.IsTrue(File.Exists(Path.Combine(assets, "Square44x44Logo.targetsize-24_altform-unplated.png")));
+        Assert.IsTrue(File.Exists(Path.Combine(assets, "SplashScreen.scale-200.png")));
+        Assert.AreEqual(5, migration.Assets);
+        Assert.IsTrue(migration.Manual.Any(m => m.Contains("Square150x150Logo")), "The missing logo is reported");
+        Assert.IsTrue(migration.Manual.Any(m => m.Contains("README.txt")));
+        Assert.IsFalse(migration.Manual.Any(m => m.Contains("StoreLogo")), "Content that was copied isn't reported");
+    }
+
+    [TestMethod]
+    public async Task MigrateAsync_WritesWinappYamlAndReportsWhatItCannotMigrate()
+    {
+        // Arrange
+        var wapproj = CreatePackagingProject("""
+            <AppxBundle>Always</AppxBundle>
+            <PackageCertificateKeyFile>Notes_TemporaryKey.pfx</PackageCertificateKeyFile>
+            """);
+
+        // Act
+        var migration = await GetRequiredService<IWapprojMigrationService>().MigrateAsync(wapproj, force: false, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        var config = GetRequiredService<IConfigService>().Load();
+        Assert.AreEqual("1.6.250108002", config.GetVersion("Microsoft.WindowsAppSDK"));
+        Assert.IsNull(config.GetVersion("Contoso.Telemetry"));
+        Assert.AreEqual("Notes", config.Projects.Single().Path);
+        Assert.AreEqual(@"Assets\SplashScreen.png", config.Splash!.Image);
+        Assert.AreEqual("NOTES_HOME", config.Environment.Single().Name);
+        Assert.IsTrue(migration.Manual.Any(m => m.StartsWith("Package Contoso.Telemetry", StringComparison.Ordinal)));
+        Assert.IsTrue(migration.Manual.Any(m => m.StartsWith("AppxBundle", StringComparison.Ordinal) && m.Contains("makeappx.exe bundle")));
+        Assert.IsTrue(migration.Manual.Any(m => m.StartsWith("PackageCertificateKeyFile", StringComparison.Ordinal)));
+    }
+
+    [TestMethod]
+    public async Task MigrateAsync_KeepsAnExistingManifestUnlessForced()
+    {
+        // Arrange
+        var wapproj = CreatePackagingProject();
+        var existing = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(existing, "<Package />");
+        var service = GetRequiredService<IWapprojMigrationService>();
+
+        // Act & Assert
+        await Assert.ThrowsExactlyAsync<InvalidOperationException>(() => service.MigrateAsync(wapproj, force: false, TestTaskContext, TestContext.CancellationToken));
+        Assert.AreEqual("<Package />", File.ReadAllText(existing));
+        await service.MigrateAsync(wapproj, force: true, TestTaskContext, TestContext.CancellationToken);
+        Assert.Contains("Contoso.Notes", File.ReadAllText(existing));
+    }
+}
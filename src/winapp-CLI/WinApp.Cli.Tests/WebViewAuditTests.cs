@@ -0,0 +1,147 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Extensions.DependencyInjection;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class WebViewAuditTests : BaseCommandTests
+{
+    public WebViewAuditTests()
+        : base(configPaths: false)
+    {
+    }
+
+    protected override IServiceCollection ConfigureServices(IServiceCollection services)
+    {
+        return services.AddSingleton<IInstalledPackageService, NoInstalledPackages>();
+    }
+
+    private sealed class NoInstalledPackages : IInstalledPackageService
+    {
+        public Task<InstalledPackageList> GetPackagesAsync(TaskContext taskContext, CancellationToken cancellationToken = default) =>
+            Task.FromResult(new InstalledPackageList([], false));
+
+        public Task UninstallAsync(string fullName, bool allUsers, TaskContext taskContext, CancellationToken cancellationToken = default) => Task.CompletedTask;
+
+        public Task<IReadOnlyList<PackageProcess>> GetProcessesAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+            Task.FromResult<IReadOnlyList<PackageProcess>>([]);
+
+        public Task StopProcessesAsync(IReadOnlyList<PackageProcess> processes, TaskContext taskContext, CancellationToken cancellationToken = default) => Task.CompletedTask;
+
+        public void OpenFolder(string path)
+        {
+        }
+    }
+
+    private FileInfo CreateManifest(string properties = "")
+    {
+        var path = Path.Combine(_tempDirectory.FullName, "appxmanifest.xml");
+        File.WriteAllText(path, $"""
+            <?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+                     xmlns:desktop6="http://schemas.microsoft.com/appx/manifest/desktop/windows10/6">
+              <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.0.0.0" />
+              <Properties>
+                <DisplayName>Contoso Notes</DisplayName>
+                {properties}
+              </Properties>
+            </Package>
+            """);
+        return new FileInfo(path);
+    }
+
+    private void CreateTauriProject()
+    {
+        var tauri = _tempDirectory.CreateSubdirectory("src-tauri");
+        File.WriteAllText(Path.Combine(tauri.FullName, "tauri.conf.json"), """
+            {
+              "productName": "Contoso Notes",
+              "version": "1.2.0",
+              "identifier": "com.contoso.notes"
+            }
+            """);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_ReportsAProfileInThePackageFolderAndSharedTauriProfile()
+    {
+        // Arrange
+        CreateTauriProject();
+        var manifest = CreateManifest("<desktop6:FileSystemWriteVirtualization>disabled</desktop6:FileSystemWriteVirtualization>");
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "bin", "Notes.exe.WebView2", "EBWebView", "Default"));
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "node_modules", "demo", "EBWebView"));
+
+        // Act
+        var report = await GetRequiredService<IWebViewAuditService>().AnalyzeAsync(manifest, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(report.UsesWebView2);
+        Assert.AreEqual(@"%LOCALAPPDATA%\com.contoso.notes\EBWebView", report.ExpectedProfile);
+        var packaged = report.Issues.Single(i => i.Check == "packaged-profile");
+        Assert.AreEqual(AccessibilitySeverity.Error, packaged.Severity);
+        StringAssert.StartsWith(packaged.Message, Path.Combine("bin", "Notes.exe.WebView2") + " is a WebView2 profile");
+        StringAssert.Contains(report.Issues.Single(i => i.Check == "virtualization").Message, @"%LOCALAPPDATA%\com.contoso.notes\EBWebView");
+        Assert.IsEmpty(report.Profiles);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_SkipsAppsWithoutWebView2()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "Notes.exe"), "");
+
+        // Act
+        var report = await GetRequiredService<IWebViewAuditService>().AnalyzeAsync(manifest, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsFalse(report.UsesWebView2);
+        Assert.IsEmpty(report.Issues);
+    }
+
+    [TestMethod]
+    public async Task AnalyzeAsync_FindsTheWebView2LoaderOfSdkApps()
+    {
+        // Arrange
+        var manifest = CreateManifest();
+        Directory.CreateDirectory(Path.Combine(_tempDirectory.FullName, "runtimes", "win-x64", "native"));
+        File.WriteAllText(Path.Combine(_tempDirectory.FullName, "runtimes", "win-x64", "native", "WebView2Loader.dll"), "");
+
+        // Act
+        var report = await GetRequiredService<IWebViewAuditService>().AnalyzeAsync(manifest, TestTaskContext, TestContext.CancellationToken);
+
+        // Assert
+        Assert.IsTrue(report.UsesWebView2);
+        Assert.AreEqual(@"<ApplicationData>\LocalState\EBWebView", report.ExpectedProfile);
+        Assert.IsFalse(report.Issues.Any(i => i.Check is "packaged-profile" or "virtualization"));
+    }
+
+    [TestMethod]
+    public void FindInstalledProfiles_FindsTheDefaultAndRedirectedProfiles()
+    {
+        // Arrange
+        var appData = _tempDirectory.CreateSubdirectory("Contoso.Notes_abc");
+        Directory.CreateDirectory(Path.Combine(appData.FullName, "LocalState", "EBWebView"));
+        Directory.CreateDirectory(Path.Combine(appData.FullName, "LocalCache", "Local", "com.contoso.notes", "EBWebView"));
+        Directory.CreateDirectory(Path.Combine(appData.FullName, "LocalCache", "Local", "Temp"));
+
+        // Act
+        var profiles = WebViewAuditService.FindInstalledProfiles(appData.FullName);
+
+        // Assert
+        CollectionAssert.AreEqual(
+            new[]
+            {
+                Path.Combine(appData.FullName, "LocalState", "EBWebView"),
+                Path.Combine(appData.FullName, "LocalCache", "Local", "com.contoso.notes", "EBWebView"),
+            },
+            profiles);
+        Assert.IsNull(WebViewAuditService.EnvironmentIssue(null, "Contoso.Notes"));
+        Assert.AreEqual("environment", WebViewAuditService.EnvironmentIssue(@"D:\profiles", "Contoso.Notes")!.Check);
+    }
+}
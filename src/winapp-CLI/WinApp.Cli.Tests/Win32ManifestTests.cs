@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Tests;
+
+[TestClass]
+public class Win32ManifestTests : BaseCommandTests
+{
+    public Win32ManifestTests()
+        : base(configPaths: false)
+    {
+    }
+
+    private static readonly XElement AppxRoot = XDocument.Parse("""
+        <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+          <Identity Name="Contoso.Notes" Publisher="CN=Contoso" Version="1.2.0.0" ProcessorArchitecture="x64" />
+        </Package>
+        """).Root!;
+
+    [TestMethod]
+    public void Generate_WritesTheSettingsOfWinappYaml()
+    {
+        // Arrange
+        var configService = GetRequiredService<IConfigService>();
+        configService.Save(new WinappConfig
+        {
+            Win32Manifest = new Win32ManifestConfig { DpiAwareness = "permonitorv2", LongPathAware = false, ActiveCodePage = null, SupportedOS = ["windows10", "windows8.1"] },
+        });
+        var config = configService.Load().Win32Manifest!;
+
+        // Act
+        var manifest = Win32Manifest.Generate(config, AppxRoot).Root!;
+
+        // Assert
+        Assert.AreEqual(DpiAwareness.PerMonitorV2, SxsManifest.ReadDpiAwareness(manifest).Awareness);
+        Assert.AreEqual(0, SxsManifest.ReadDpiAwareness(manifest).Problems.Count);
+        var identity = manifest.Element(XName.Get("assemblyIdentity", "urn:schemas-microsoft-com:asm.v1"))!;
+        Assert.AreEqual("Contoso.Notes", identity.Attribute("name")!.Value);
+        Assert.AreEqual("1.0.0.0", identity.Attribute("version")!.Value);
+        CollectionAssert.AreEqual(
+            new[] { "{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}", "{1f676c76-80e1-4239-95bb-83d0f6d0da78}" },
+            manifest.Descendants().Where(e => e.Name.LocalName == "supportedOS").Select(e => e.Attribute("Id")!.Value).ToArray());
+        Assert.IsFalse(manifest.Descendants().Any(e => e.Name.LocalName is "longPathAware" or "activeCodePage"));
+    }
+
+    [TestMethod]
+    public void Generate_DefaultsToPerMonitorV2LongPathsAndUtf8()
+    {
+        var manifest = Win32Manifest.Generate(new Win32ManifestConfig(), AppxRoot).Root!;
+
+        Assert.AreEqual("true/pm", manifest.Descendants().Single(e => e.Name.LocalName == "dpiAware").Value);
+        Assert.AreEqual("true", manifest.Descendants().Single(e => e.Name.LocalName == "longPathAware").Value);
+        Assert.AreEqual("UTF-8", manifest.Descendants().Single(e => e.Name.LocalName == "activeCodePage").Value);
+        Assert.ThrowsExactly<InvalidOperationException>(() => Win32Manifest.Generate(new Win32ManifestConfig { SupportedOS = ["windows11"] }, AppxRoot));
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Lists the files of build output folders for <see cref="IBuildAdapter.LocateOutputs"/>
+/// </summary>
+internal static class BuildOutputs
+{
+    // What compilers and linkers leave next to their outputs, which has no place in a package
+    private static readonly string[] ByProducts = [".pdb", ".ilk", ".exp", ".lib", ".iobj", ".ipdb", ".obj", ".idb", ".log", ".lastbuildstate", ".d", ".rlib"];
+
+    /// <summary>
+    /// The 'build: output:' folder of winapp.yaml, when it sets one
+    /// </summary>
+    public static IReadOnlyList<BuildOutput>? Configured(BuildContext context) =>
+        context.Config.Output is { } output ? Folder(new DirectoryInfo(Path.GetFullPath(output, context.Folder.FullName))) : null;
+
+    /// <summary>
+    /// Every file of the folder, keeping its layout
+    /// </summary>
+    public static IReadOnlyList<BuildOutput> Folder(DirectoryInfo folder)
+    {
+        EnsureExists(folder);
+        return [.. folder.EnumerateFiles("*", SearchOption.AllDirectories)
+            .Select(f => new BuildOutput(f, Path.GetRelativePath(folder.FullName, f.FullName)))];
+    }
+
+    /// <summary>
+    /// The files directly in the folder but build by-products such as .pdb and .lib files, for builds that keep their
+    /// intermediate files in subfolders of the output folder
+    /// </summary>
+    public static IReadOnlyList<BuildOutput> TopLevel(DirectoryInfo folder)
+    {
+        EnsureExists(folder);
+        return [.. folder.EnumerateFiles()
+            .Where(f => !ByProducts.Contains(f.Extension, StringComparer.OrdinalIgnoreCase))
+            .Select(f => new BuildOutput(f, f.Name))];
+    }
+
+    private static void EnsureExists(DirectoryInfo folder)
+    {
+        if (!folder.Exists)
+        {
+            throw new DirectoryNotFoundException($"The build output folder {folder.FullName} doesn't exist; set 'build: output:' in winapp.yaml to where the build puts the app");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Configures and builds a CMake project in a build folder of .winapp
+/// </summary>
+internal sealed class CMakeBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.CMake;
+
+    public bool Detect(BuildContext context) => context.Has("CMakeLists.txt");
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var configure = $"-S \"{context.Folder.FullName}\" -B \"{context.Intermediate.FullName}\""
+            + (MSBuildBuildAdapter.ProjectPlatform(context.Architecture) is { } platform ? $" -A {platform}" : "")
+            + (context.Config.Arguments is { } extra ? $" {extra}" : "");
+        var build = $"--build \"{context.Intermediate.FullName}\" --config {context.Config.Configuration}";
+        return [new BuildStep("cmake", configure, context.Folder), new BuildStep("cmake", build, context.Folder)];
+    }
+
+    /// <summary>
+    /// The executables and DLLs in the build folder, or in its Release or Debug folder with multi-config generators
+    /// such as Visual Studio
+    /// </summary>
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context)
+    {
+        if (BuildOutputs.Configured(context) is { } configured)
+        {
+            return configured;
+        }
+        var multiConfig = new DirectoryInfo(Path.Combine(context.Intermediate.FullName, context.Config.Configuration));
+        return BuildOutputs.TopLevel(multiConfig.Exists ? multiConfig : context.Intermediate)
+            .Where(o => o.Source.Extension.Equals(".exe", StringComparison.OrdinalIgnoreCase) || o.Source.Extension.Equals(".dll", StringComparison.OrdinalIgnoreCase))
+            .ToList();
+    }
+}
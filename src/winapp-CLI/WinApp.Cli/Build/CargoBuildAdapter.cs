@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+internal sealed class CargoBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.Cargo;
+
+    public bool Detect(BuildContext context) => context.Has("Cargo.toml");
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var arguments = "build"
+            + (context.Config.IsDebug ? "" : " --release")
+            + (TargetTriple(context.Architecture) is { } target ? $" --target {target}" : "")
+            + (context.ProjectFile != null ? $" --manifest-path \"{context.ProjectFile.FullName}\"" : "")
+            + (context.Config.Arguments is { } extra ? $" {extra}" : "");
+        return [new BuildStep("cargo", arguments, context.Folder)];
+    }
+
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context) =>
+        BuildOutputs.Configured(context) ?? BuildOutputs.TopLevel(ProfileFolder(context.Folder, context));
+
+    /// <returns>null for neutral, to build for the machine</returns>
+    internal static string? TargetTriple(string architecture) => architecture.ToLowerInvariant() switch
+    {
+        "x64" => "x86_64-pc-windows-msvc",
+        "x86" => "i686-pc-windows-msvc",
+        "arm64" => "aarch64-pc-windows-msvc",
+        _ => null,
+    };
+
+    /// <summary>
+    /// target\[triple\]release of the workspace the crate belongs to, or of CARGO_TARGET_DIR, where cargo puts the
+    /// executables and DLLs it links
+    /// </summary>
+    internal static DirectoryInfo ProfileFolder(DirectoryInfo crate, BuildContext context)
+    {
+        var targetDirectory = Environment.GetEnvironmentVariable("CARGO_TARGET_DIR") is { Length: > 0 } configured
+            ? Path.GetFullPath(configured, crate.FullName)
+            : Path.Combine(FindWorkspace(crate).FullName, "target");
+        var triple = TargetTriple(context.Architecture);
+        return new DirectoryInfo(Path.Combine(targetDirectory, triple ?? "", context.Config.IsDebug ? "debug" : "release"));
+    }
+
+    // The outermost folder up the tree whose Cargo.toml declares a [workspace], which owns the target folder
+    private static DirectoryInfo FindWorkspace(DirectoryInfo crate)
+    {
+        var workspace = crate;
+        for (var directory = crate.Parent; directory != null; directory = directory.Parent)
+        {
+            var manifest = Path.Combine(directory.FullName, "Cargo.toml");
+            if (File.Exists(manifest) && File.ReadLines(manifest).Any(l => l.Trim() == "[workspace]"))
+            {
+                workspace = directory;
+            }
+        }
+        return workspace;
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Runs the 'build: command:' of winapp.yaml and packs its 'build: output:' folder. Never detected
+/// </summary>
+internal sealed class CustomBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.Custom;
+
+    public bool Detect(BuildContext context) => false;
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var command = context.Config.Command
+            ?? throw new InvalidOperationException("winapp.yaml: custom builds need 'build: command:', the command line that builds the app");
+        var extra = context.Config.Arguments is { } arguments ? $" {arguments}" : "";
+        return [BuildStep.Cmd(command + extra, context.Folder)];
+    }
+
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context) =>
+        BuildOutputs.Configured(context)
+            ?? throw new InvalidOperationException("winapp.yaml: custom builds need 'build: output:', the folder the command puts the app in");
+}
@@ -0,0 +1,39 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Publishes a .NET project to a folder of .winapp, which becomes the payload
+/// </summary>
+internal sealed class DotnetBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.Dotnet;
+
+    public bool Detect(BuildContext context) =>
+        context.Has("*.csproj") || context.Has("*.fsproj") || context.Has("*.vbproj")
+        || (context.Has("*.sln") && !MSBuildBuildAdapter.HasCppProjects(context));
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var arguments = "publish"
+            + (context.ProjectFile != null ? $" \"{context.ProjectFile.FullName}\"" : "")
+            + $" -c {context.Config.Configuration}"
+            + (RuntimeIdentifier(context.Architecture) is { } rid ? $" -r {rid}" : "")
+            + $" -o \"{context.Intermediate.FullName}\""
+            + (context.Config.Arguments is { } extra ? $" {extra}" : "");
+        return [new BuildStep("dotnet", arguments, context.Folder)];
+    }
+
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context) =>
+        BuildOutputs.Configured(context) ?? BuildOutputs.Folder(context.Intermediate);
+
+    /// <returns>null for neutral, to publish for the machine</returns>
+    internal static string? RuntimeIdentifier(string architecture) => architecture.ToLowerInvariant() switch
+    {
+        "x64" or "x86" or "arm64" => $"win-{architecture.ToLowerInvariant()}",
+        _ => null,
+    };
+}
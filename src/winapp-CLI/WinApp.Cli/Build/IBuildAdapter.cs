@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Enumeration;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Runs the build of one build system for 'winapp pack --build', and knows where it puts its outputs
+/// </summary>
+internal interface IBuildAdapter
+{
+    public BuildSystem System { get; }
+
+    /// <summary>
+    /// Whether the project is built with this build system
+    /// </summary>
+    public bool Detect(BuildContext context);
+
+    /// <summary>
+    /// The command lines that build the project, in order
+    /// </summary>
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context);
+
+    /// <summary>
+    /// The files of the build that go in the package, by their path in it
+    /// </summary>
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context);
+}
+
+/// <param name="Folder">Project folder, which the build runs in</param>
+/// <param name="ProjectFile">The project file 'build: project:' names, if it names a file</param>
+/// <param name="Architecture">ProcessorArchitecture of the manifest: x86, x64, arm64, or neutral to build for the machine</param>
+/// <param name="Intermediate">Folder of .winapp for the builds that are told where to put their outputs</param>
+internal sealed record BuildContext(DirectoryInfo Folder, FileInfo? ProjectFile, BuildConfig Config, string Architecture, DirectoryInfo Intermediate)
+{
+    public bool IsNeutral => Architecture.Equals("neutral", StringComparison.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// Whether the project file is named, or the folder holds one, with this name or pattern such as *.csproj
+    /// </summary>
+    public bool Has(string pattern) =>
+        ProjectFile != null
+            ? FileSystemName.MatchesSimpleExpression(pattern, ProjectFile.Name)
+            : Folder.EnumerateFiles(pattern).Any();
+}
+
+/// <param name="Arguments">Command line arguments, quoted</param>
+internal sealed record BuildStep(string FileName, string Arguments, DirectoryInfo WorkingDirectory)
+{
+    public override string ToString() => $"{FileName} {Arguments}";
+
+    /// <summary>
+    /// Runs a command of a Node.js package manager, which are batch files that only cmd.exe starts
+    /// </summary>
+    public static BuildStep Cmd(string command, DirectoryInfo workingDirectory) => new("cmd.exe", $"/d /s /c \"{command}\"", workingDirectory);
+}
+
+/// <param name="PackagePath">Path in the package, relative to its root</param>
+internal sealed record BuildOutput(FileInfo Source, string PackagePath);
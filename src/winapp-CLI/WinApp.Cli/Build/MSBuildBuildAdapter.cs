@@ -0,0 +1,101 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Builds a Visual Studio solution or C++ project with the MSBuild of the latest Visual Studio
+/// </summary>
+internal sealed class MSBuildBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.MSBuild;
+
+    public bool Detect(BuildContext context) => context.Has("*.vcxproj") || HasCppProjects(context);
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var project = context.ProjectFile ?? context.Folder.EnumerateFiles("*.sln").Concat(context.Folder.EnumerateFiles("*.vcxproj")).FirstOrDefault()
+            ?? throw new InvalidOperationException($"No solution or C++ project in {context.Folder.FullName}; set 'build: project:' in winapp.yaml");
+        var isSolution = project.Extension.Equals(".sln", StringComparison.OrdinalIgnoreCase);
+        var platform = isSolution ? SolutionPlatform(context.Architecture) : ProjectPlatform(context.Architecture);
+        var arguments = $"\"{project.FullName}\" /restore /m /p:Configuration={context.Config.Configuration}"
+            + (platform != null ? $" /p:Platform={platform}" : "")
+            + (context.Config.Arguments is { } extra ? $" {extra}" : "");
+        return [new BuildStep(FindMSBuild(), arguments, context.Folder)];
+    }
+
+    /// <summary>
+    /// The files of [platform\]configuration next to the solution or project, the default output folder of C++
+    /// projects, where x86 builds have no platform folder
+    /// </summary>
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context)
+    {
+        if (BuildOutputs.Configured(context) is { } configured)
+        {
+            return configured;
+        }
+        var folder = context.ProjectFile?.Directory ?? context.Folder;
+        var platform = ProjectPlatform(context.Architecture);
+        string[] candidates = platform switch
+        {
+            "Win32" => [Path.Combine(folder.FullName, context.Config.Configuration)],
+            null => [Path.Combine(folder.FullName, "x64", context.Config.Configuration), Path.Combine(folder.FullName, context.Config.Configuration)],
+            _ => [Path.Combine(folder.FullName, platform, context.Config.Configuration)],
+        };
+        var output = candidates.Select(c => new DirectoryInfo(c)).FirstOrDefault(d => d.Exists) ?? new DirectoryInfo(candidates[0]);
+        return BuildOutputs.TopLevel(output);
+    }
+
+    /// <summary>
+    /// The platform of C++ projects, which CMake's -A takes too
+    /// </summary>
+    /// <returns>null for neutral, to build the default platform</returns>
+    internal static string? ProjectPlatform(string architecture) => architecture.ToLowerInvariant() switch
+    {
+        "x64" => "x64",
+        "x86" => "Win32",
+        "arm64" => "ARM64",
+        _ => null,
+    };
+
+    // Solutions name the Win32 platform of their C++ projects x86
+    private static string? SolutionPlatform(string architecture) =>
+        ProjectPlatform(architecture) is "Win32" ? "x86" : ProjectPlatform(architecture);
+
+    /// <summary>
+    /// Whether the solution of the folder has C++ projects, which dotnet can't build
+    /// </summary>
+    internal static bool HasCppProjects(BuildContext context)
+    {
+        var solutions = context.ProjectFile == null
+            ? context.Folder.EnumerateFiles("*.sln")
+            : new[] { context.ProjectFile }.Where(f => f.Extension.Equals(".sln", StringComparison.OrdinalIgnoreCase));
+        return solutions.Any(s => File.ReadAllText(s.FullName).Contains(".vcxproj\"", StringComparison.OrdinalIgnoreCase));
+    }
+
+    /// <summary>
+    /// MSBuild.exe of the latest Visual Studio with MSBuild, found with vswhere, or msbuild on PATH
+    /// </summary>
+    internal static string FindMSBuild()
+    {
+        var vswhere = Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.ProgramFilesX86), "Microsoft Visual Studio", "Installer", "vswhere.exe");
+        if (!File.Exists(vswhere))
+        {
+            return "msbuild";
+        }
+
+        var psi = new ProcessStartInfo(vswhere, @"-latest -prerelease -products * -requires Microsoft.Component.MSBuild -find MSBuild\**\Bin\MSBuild.exe")
+        {
+            UseShellExecute = false,
+            RedirectStandardOutput = true,
+            CreateNoWindow = true,
+        };
+        using var process = Process.Start(psi);
+        var path = process?.StandardOutput.ReadLine();
+        process?.WaitForExit();
+        return string.IsNullOrWhiteSpace(path) ? "msbuild" : path.Trim();
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Runs the build script of a Node.js or Electron app with the package manager of its lock file
+/// </summary>
+internal sealed class NpmBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.Npm;
+
+    public bool Detect(BuildContext context) => context.Has("package.json");
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var packageManager = File.Exists(Path.Combine(context.Folder.FullName, "pnpm-lock.yaml")) ? "pnpm"
+            : File.Exists(Path.Combine(context.Folder.FullName, "yarn.lock")) ? "yarn"
+            : "npm";
+        var extra = context.Config.Arguments is { } arguments ? $" -- {arguments}" : "";
+        return [BuildStep.Cmd($"{packageManager} run build{extra}", context.Folder)];
+    }
+
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context) =>
+        BuildOutputs.Configured(context) ?? BuildOutputs.Folder(FindOutput(context.Folder, context.Architecture));
+
+    /// <summary>
+    /// The unpacked app electron-builder (dist\win-unpacked) or Electron Forge (out\&lt;name&gt;-win32-&lt;arch&gt;)
+    /// writes, or else dist
+    /// </summary>
+    internal static DirectoryInfo FindOutput(DirectoryInfo folder, string architecture)
+    {
+        var electronArch = architecture.ToLowerInvariant() switch
+        {
+            "x86" => "ia32",
+            "arm64" => "arm64",
+            _ => "x64",
+        };
+        var builder = new DirectoryInfo(Path.Combine(folder.FullName, "dist", electronArch == "x64" ? "win-unpacked" : $"win-{electronArch}-unpacked"));
+        if (builder.Exists)
+        {
+            return builder;
+        }
+
+        var forge = new DirectoryInfo(Path.Combine(folder.FullName, "out"));
+        var forgeApp = forge.Exists ? forge.EnumerateDirectories($"*-win32-{electronArch}").FirstOrDefault() : null;
+        return forgeApp ?? new DirectoryInfo(Path.Combine(folder.FullName, "dist"));
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Build;
+
+/// <summary>
+/// Builds a Tauri 2 app with the Tauri CLI, which builds the frontend first, without its MSI and NSIS installers
+/// </summary>
+internal sealed class TauriBuildAdapter : IBuildAdapter
+{
+    public BuildSystem System => BuildSystem.Tauri;
+
+    public bool Detect(BuildContext context) => FindCrate(context.Folder) != null;
+
+    public IReadOnlyList<BuildStep> GetSteps(BuildContext context)
+    {
+        var crate = FindCrate(context.Folder) ?? context.Folder;
+        var app = crate.Name.Equals("src-tauri", StringComparison.OrdinalIgnoreCase) && crate.Parent != null ? crate.Parent : crate;
+        var arguments = "build --no-bundle"
+            + (context.Config.IsDebug ? " --debug" : "")
+            + (CargoBuildAdapter.TargetTriple(context.Architecture) is { } target ? $" --target {target}" : "")
+            + (context.Config.Arguments is { } extra ? $" {extra}" : "");
+
+        // The CLI is a devDependency of most apps; others install it with 'cargo install tauri-cli'
+        var packageJson = Path.Combine(app.FullName, "package.json");
+        return File.Exists(packageJson) && File.ReadAllText(packageJson).Contains("\"@tauri-apps/cli\"", StringComparison.Ordinal)
+            ? [BuildStep.Cmd($"npx tauri {arguments}", app)]
+            : [new BuildStep("cargo", $"tauri {arguments}", app)];
+    }
+
+    public IReadOnlyList<BuildOutput> LocateOutputs(BuildContext context) =>
+        BuildOutputs.Configured(context) ?? BuildOutputs.TopLevel(CargoBuildAdapter.ProfileFolder(FindCrate(context.Folder) ?? context.Folder, context));
+
+    // The app's src-tauri folder, or the folder itself when it is src-tauri
+    private static DirectoryInfo? FindCrate(DirectoryInfo folder) =>
+        File.Exists(Path.Combine(folder.FullName, "tauri.conf.json")) ? folder
+        : File.Exists(Path.Combine(folder.FullName, "src-tauri", "tauri.conf.json")) ? new DirectoryInfo(Path.Combine(folder.FullName, "src-tauri"))
+        : null;
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.BuildCache;
+
+/// <summary>
+/// Keeps packages in an Azure Storage container
+/// </summary>
+/// <param name="containerSasUri">Container URL with a SAS token that allows reading blobs, and writing them unless the cache is read-only</param>
+internal sealed class AzureBlobBuildCache(Uri containerSasUri, HttpClient http) : IRemoteBuildCache
+{
+    private readonly string containerPath = containerSasUri.GetLeftPart(UriPartial.Path).TrimEnd('/');
+
+    public string Name => $"Azure Blob Storage ({containerPath})";
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public Task<bool> TryDownloadAsync(string key, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var blobUri = GetBlobUri(key);
+        return RemoteBuildCacheHttp.DownloadAsync(http, Retry, () => new HttpRequestMessage(HttpMethod.Get, blobUri), destination, onRetry, cancellationToken);
+    }
+
+    public async Task UploadAsync(string key, FileInfo source, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var options = new BlockBlobUploadOptions
+        {
+            ContentType = RemoteBuildCacheHttp.PackageContentType,
+            Resume = false,
+            Retry = Retry
+        };
+        await new BlockBlobUploader(http).UploadAsync(source, GetBlobUri(key), options, onRetry: onRetry, cancellationToken: cancellationToken);
+    }
+
+    private Uri GetBlobUri(string key) => new($"{containerPath}/{RemoteBuildCacheHttp.GetObjectName(key)}{containerSasUri.Query}");
+}
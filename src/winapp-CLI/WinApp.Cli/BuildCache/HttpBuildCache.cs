@@ -0,0 +1,55 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net.Http.Headers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.BuildCache;
+
+/// <summary>
+/// Keeps packages on any HTTP server that answers GET and PUT for a path, such as bazel-remote, nginx with
+/// WebDAV or Artifactory
+/// </summary>
+/// <param name="baseUri">Folder the packages are stored under</param>
+/// <param name="token">Sent as a bearer token when set</param>
+internal sealed class HttpBuildCache(Uri baseUri, string? token, HttpClient http) : IRemoteBuildCache
+{
+    public const string TokenVariable = "WINAPP_REMOTE_CACHE_TOKEN";
+
+    private readonly Uri folderUri = baseUri.AbsoluteUri.EndsWith('/') ? baseUri : new Uri(baseUri.AbsoluteUri + "/");
+
+    public string Name => $"HTTP ({folderUri.GetLeftPart(UriPartial.Path)})";
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public Task<bool> TryDownloadAsync(string key, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var objectUri = new Uri(folderUri, RemoteBuildCacheHttp.GetObjectName(key));
+        return RemoteBuildCacheHttp.DownloadAsync(http, Retry, () => Authorize(new HttpRequestMessage(HttpMethod.Get, objectUri)), destination, onRetry, cancellationToken);
+    }
+
+    public async Task UploadAsync(string key, FileInfo source, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var objectUri = new Uri(folderUri, RemoteBuildCacheHttp.GetObjectName(key));
+        using var response = await Retry.SendAsync(http, () =>
+        {
+            var request = new HttpRequestMessage(HttpMethod.Put, objectUri)
+            {
+                Content = new StreamContent(source.OpenRead())
+            };
+            request.Content.Headers.ContentType = new MediaTypeHeaderValue(RemoteBuildCacheHttp.PackageContentType);
+            request.Content.Headers.ContentLength = source.Length;
+            return Authorize(request);
+        }, onRetry, cancellationToken);
+        await RemoteBuildCacheHttp.EnsureSuccessAsync(response, "Uploading to", cancellationToken);
+    }
+
+    private HttpRequestMessage Authorize(HttpRequestMessage request)
+    {
+        if (token != null)
+        {
+            request.Headers.Authorization = new AuthenticationHeaderValue("Bearer", token);
+        }
+        return request;
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.BuildCache;
+
+/// <summary>
+/// Shared storage for pack outputs, so CI agents can reuse packages another agent already built from the same
+/// payload and configuration
+/// </summary>
+internal interface IRemoteBuildCache
+{
+    /// <summary>
+    /// Shown in messages, e.g. "Amazon S3 (s3://ci-cache/winapp)"
+    /// </summary>
+    public string Name { get; }
+
+    /// <summary>
+    /// Downloads the package stored under <paramref name="key"/>
+    /// </summary>
+    /// <returns>False when the cache does not hold the key</returns>
+    public Task<bool> TryDownloadAsync(string key, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken);
+
+    public Task UploadAsync(string key, FileInfo source, Action<string>? onRetry, CancellationToken cancellationToken);
+}
+
+/// <param name="ReadOnly">Only download; pull request builds typically should not write to a cache that release builds read</param>
+internal sealed record RemoteBuildCacheOptions(IRemoteBuildCache Cache, bool ReadOnly);
@@ -0,0 +1,47 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Text;
+
+namespace WinApp.Cli.BuildCache;
+
+/// <summary>
+/// Identifies a pack operation by what goes into it: the payload files and the settings that change the package
+/// </summary>
+internal static class PackCacheKey
+{
+    // Bump when the package produced from the same inputs changes, so older entries are no longer used
+    private const string FormatVersion = "winapp-pack-cache/1";
+
+    /// <summary>
+    /// Lowercase hex SHA-256 over every file of <paramref name="payload"/> (relative path, size and content hash,
+    /// in ordinal path order) and the <paramref name="configuration"/> entries
+    /// </summary>
+    /// <param name="exclude">A file inside the payload to leave out, such as the package being written there</param>
+    public static async Task<string> ComputeAsync(DirectoryInfo payload, IReadOnlyDictionary<string, string> configuration, FileInfo? exclude, CancellationToken cancellationToken)
+    {
+        using var hash = IncrementalHash.CreateHash(HashAlgorithmName.SHA256);
+        Append(hash, FormatVersion + "\n");
+        foreach (var (name, value) in configuration.OrderBy(e => e.Key, StringComparer.Ordinal))
+        {
+            Append(hash, $"{name}={value}\n");
+        }
+
+        Append(hash, "payload\n");
+        var files = payload.EnumerateFiles("*", SearchOption.AllDirectories)
+            .Where(f => exclude == null || !f.FullName.Equals(exclude.FullName, StringComparison.OrdinalIgnoreCase))
+            .Select(f => (File: f, Path: Path.GetRelativePath(payload.FullName, f.FullName).Replace('\\', '/')))
+            .OrderBy(f => f.Path, StringComparer.Ordinal);
+        foreach (var (file, path) in files)
+        {
+            await using var stream = file.OpenRead();
+            var contentHash = Convert.ToHexStringLower(await SHA256.HashDataAsync(stream, cancellationToken));
+            Append(hash, $"{path}\0{file.Length}\0{contentHash}\n");
+        }
+
+        return Convert.ToHexStringLower(hash.GetHashAndReset());
+    }
+
+    private static void Append(IncrementalHash hash, string text) => hash.AppendData(Encoding.UTF8.GetBytes(text));
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.BuildCache;
+
+internal static class RemoteBuildCacheHttp
+{
+    public const string PackageContentType = "application/msix";
+
+    /// <summary>
+    /// Name of a cached package within the cache's container, bucket or base URL
+    /// </summary>
+    public static string GetObjectName(string key) => $"msix/{key}.msix";
+
+    /// <summary>
+    /// Sends a GET and saves the body to <paramref name="destination"/>, through a temporary file so an interrupted
+    /// download never leaves a partial package behind
+    /// </summary>
+    /// <returns>False when the server answers 404</returns>
+    public static async Task<bool> DownloadAsync(HttpClient http, TransientHttpRetry retry, Func<HttpRequestMessage> requestFactory, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        using var response = await retry.SendAsync(http, requestFactory, onRetry, cancellationToken);
+        if (response.StatusCode == HttpStatusCode.NotFound)
+        {
+            return false;
+        }
+        await EnsureSuccessAsync(response, "Downloading", cancellationToken);
+
+        destination.Directory?.Create();
+        var temporary = new FileInfo(destination.FullName + ".download");
+        try
+        {
+            await using (var stream = temporary.Create())
+            {
+                await response.Content.CopyToAsync(stream, cancellationToken);
+            }
+            temporary.MoveTo(destination.FullName, overwrite: true);
+        }
+        finally
+        {
+            temporary.Refresh();
+            if (temporary.Exists)
+            {
+                temporary.Delete();
+            }
+        }
+
+        destination.Refresh();
+        return true;
+    }
+
+    public static async Task EnsureSuccessAsync(HttpResponseMessage response, string action, CancellationToken cancellationToken)
+    {
+        if (!response.IsSuccessStatusCode)
+        {
+            var error = await response.Content.ReadAsStringAsync(cancellationToken);
+            throw new InvalidOperationException($"{action} {response.RequestMessage?.RequestUri?.GetLeftPart(UriPartial.Path)} failed with {(int)response.StatusCode} {response.ReasonPhrase}: {error}");
+        }
+    }
+}
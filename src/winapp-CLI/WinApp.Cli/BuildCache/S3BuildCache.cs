@@ -0,0 +1,71 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net.Http.Headers;
+using System.Security.Cryptography;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.BuildCache;
+
+/// <summary>
+/// Keeps packages in an Amazon S3 bucket, or any service that speaks the S3 API with Signature Version 4
+/// </summary>
+internal sealed class S3BuildCache : IRemoteBuildCache
+{
+    private const string Service = "s3";
+    private static readonly string EmptyPayloadHash = Convert.ToHexStringLower(SHA256.HashData([]));
+
+    private readonly HttpClient http;
+    private readonly AwsCredentials credentials;
+    private readonly string region;
+    private readonly Uri prefixUri;
+
+    /// <param name="location">s3://bucket or s3://bucket/prefix</param>
+    public S3BuildCache(Uri location, string region, AwsCredentials credentials, HttpClient http)
+    {
+        if (location.Scheme != "s3" || string.IsNullOrEmpty(location.Host))
+        {
+            throw new ArgumentException($"'{location}' is not an S3 location; use s3://bucket or s3://bucket/prefix", nameof(location));
+        }
+
+        Name = $"Amazon S3 ({location})";
+        this.region = region;
+        this.credentials = credentials;
+        this.http = http;
+        prefixUri = S3PublishTarget.GetPrefixUri(location.Host, location.AbsolutePath.Trim('/'), region);
+    }
+
+    public string Name { get; }
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public Task<bool> TryDownloadAsync(string key, FileInfo destination, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var objectUri = new Uri(prefixUri, RemoteBuildCacheHttp.GetObjectName(key));
+        return RemoteBuildCacheHttp.DownloadAsync(http, Retry, () =>
+        {
+            var request = new HttpRequestMessage(HttpMethod.Get, objectUri);
+            AwsSignatureV4.Sign(request, credentials, region, Service, EmptyPayloadHash, DateTimeOffset.UtcNow);
+            return request;
+        }, destination, onRetry, cancellationToken);
+    }
+
+    public async Task UploadAsync(string key, FileInfo source, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var objectUri = new Uri(prefixUri, RemoteBuildCacheHttp.GetObjectName(key));
+        var payloadHash = await AwsSignatureV4.HashFileAsync(source, cancellationToken);
+        using var response = await Retry.SendAsync(http, () =>
+        {
+            var request = new HttpRequestMessage(HttpMethod.Put, objectUri)
+            {
+                Content = new StreamContent(source.OpenRead())
+            };
+            request.Content.Headers.ContentType = new MediaTypeHeaderValue(RemoteBuildCacheHttp.PackageContentType);
+            request.Content.Headers.ContentLength = source.Length;
+            AwsSignatureV4.Sign(request, credentials, region, Service, payloadHash, DateTimeOffset.UtcNow);
+            return request;
+        }, onRetry, cancellationToken);
+        await RemoteBuildCacheHttp.EnsureSuccessAsync(response, "Uploading to", cancellationToken);
+    }
+}
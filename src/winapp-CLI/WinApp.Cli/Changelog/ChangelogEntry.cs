@@ -0,0 +1,123 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.RegularExpressions;
+
+namespace WinApp.Cli.Changelog;
+
+/// <summary>
+/// Release notes section, in the order sections are shown
+/// </summary>
+internal enum ChangelogSection
+{
+    Breaking,
+    Features,
+    Fixes,
+    Performance,
+    Other
+}
+
+/// <param name="Sha">Commit the entry comes from; the merge or squash commit for pull requests</param>
+/// <param name="PullRequest">Number of the pull request; null for commits pushed directly</param>
+/// <param name="Channels">Release channels the entry is limited to; empty when it applies to every channel</param>
+internal sealed record ChangelogEntry(ChangelogSection Section, string? Scope, string Description, string Sha, int? PullRequest, IReadOnlyList<string> Channels)
+{
+    public bool AppliesTo(string? channel) =>
+        channel == null || Channels.Count == 0 || Channels.Contains(channel, StringComparer.OrdinalIgnoreCase);
+}
+
+/// <summary>
+/// Turns commit messages into release notes entries, following the Conventional Commits specification
+/// (https://www.conventionalcommits.org)
+/// </summary>
+internal static partial class ConventionalCommit
+{
+    [GeneratedRegex(@"^(?<type>[A-Za-z]+)(\((?<scope>[^)]+)\))?(?<breaking>!)?:\s+(?<description>.+)$")]
+    private static partial Regex HeaderRegex();
+
+    [GeneratedRegex(@"^BREAKING[ -]CHANGE:\s*(?<description>.*)$", RegexOptions.Multiline)]
+    private static partial Regex BreakingFooterRegex();
+
+    [GeneratedRegex(@"^(Release-)?Channels?:\s*(?<channels>.+)$", RegexOptions.Multiline | RegexOptions.IgnoreCase)]
+    private static partial Regex ChannelTrailerRegex();
+
+    [GeneratedRegex(@"^Merge pull request #(?<number>\d+) from \S+")]
+    private static partial Regex MergeCommitRegex();
+
+    [GeneratedRegex(@"^(?<title>.+?)\s+\(#(?<number>\d+)\)$")]
+    private static partial Regex SquashCommitRegex();
+
+    /// <summary>
+    /// Parses a conventional commit message
+    /// </summary>
+    /// <param name="includeAll">Also return types that are not user-facing (docs, chore, ci, ...) and messages that do not follow the convention, in the Other section</param>
+    /// <returns>The entry, or null when the commit does not belong in release notes</returns>
+    public static ChangelogEntry? Parse(string sha, string subject, string body, int? pullRequest, bool includeAll)
+    {
+        var channels = GetChannels(body);
+        var match = HeaderRegex().Match(subject.Trim());
+        if (!match.Success)
+        {
+            return includeAll ? new ChangelogEntry(ChangelogSection.Other, null, subject.Trim(), sha, pullRequest, channels) : null;
+        }
+
+        var scope = match.Groups["scope"].Success ? match.Groups["scope"].Value : null;
+        var description = match.Groups["description"].Value.Trim();
+        if (match.Groups["breaking"].Success || BreakingFooterRegex().IsMatch(body))
+        {
+            return new ChangelogEntry(ChangelogSection.Breaking, scope, description, sha, pullRequest, channels);
+        }
+
+        ChangelogSection? section = match.Groups["type"].Value.ToLowerInvariant() switch
+        {
+            "feat" or "feature" => ChangelogSection.Features,
+            "fix" => ChangelogSection.Fixes,
+            "perf" => ChangelogSection.Performance,
+            _ => includeAll ? ChangelogSection.Other : null
+        };
+        return section is ChangelogSection s ? new ChangelogEntry(s, scope, description, sha, pullRequest, channels) : null;
+    }
+
+    public static bool IsConventional(string subject) => HeaderRegex().IsMatch(subject.Trim());
+
+    /// <summary>
+    /// Recognizes the commits GitHub and Azure DevOps create when merging a pull request: merge commits, whose
+    /// body starts with the title, and squash commits, whose subject ends with the number
+    /// </summary>
+    /// <returns>False for commits that were not made by merging a pull request</returns>
+    public static bool TryGetPullRequest(string subject, string body, out int number, out string title, out string description)
+    {
+        var merge = MergeCommitRegex().Match(subject);
+        if (merge.Success)
+        {
+            number = int.Parse(merge.Groups["number"].Value, System.Globalization.CultureInfo.InvariantCulture);
+            var lines = body.Split('\n');
+            title = lines.Select(l => l.Trim()).FirstOrDefault(l => l.Length > 0) ?? subject;
+            description = string.Join('\n', lines.SkipWhile(l => l.Trim().Length == 0).Skip(1));
+            return true;
+        }
+
+        var squash = SquashCommitRegex().Match(subject.Trim());
+        if (squash.Success)
+        {
+            number = int.Parse(squash.Groups["number"].Value, System.Globalization.CultureInfo.InvariantCulture);
+            title = squash.Groups["title"].Value;
+            description = body;
+            return true;
+        }
+
+        number = 0;
+        title = subject;
+        description = body;
+        return false;
+    }
+
+    /// <summary>
+    /// Reads "Channel: beta, stable" trailers, which limit an entry to some release channels
+    /// </summary>
+    private static IReadOnlyList<string> GetChannels(string body) =>
+        ChannelTrailerRegex().Matches(body)
+            .SelectMany(m => m.Groups["channels"].Value.Split(',', StringSplitOptions.RemoveEmptyEntries | StringSplitOptions.TrimEntries))
+            .Distinct(StringComparer.OrdinalIgnoreCase)
+            .ToList();
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Changelog;
+
+internal sealed record ChangelogOptions
+{
+    /// <summary>
+    /// Tag or commit the release notes start after; null uses the latest tag before <see cref="To"/>, or the
+    /// start of history when there is none
+    /// </summary>
+    public string? From { get; init; }
+
+    public string To { get; init; } = "HEAD";
+
+    /// <summary>
+    /// Glob the tags considered for <see cref="From"/> must match, e.g. v*-beta* for a beta channel
+    /// </summary>
+    public string? TagPattern { get; init; }
+
+    public ChangelogSource Source { get; init; } = ChangelogSource.Commits;
+
+    /// <summary>
+    /// Release channel to build notes for; entries with a Channel trailer naming other channels are left out
+    /// </summary>
+    public string? Channel { get; init; }
+
+    /// <summary>
+    /// Keep entries that are not user-facing, such as docs: and chore: commits
+    /// </summary>
+    public bool IncludeAll { get; init; }
+}
+
+/// <param name="From">Resolved start of the range; null when it starts at the first commit</param>
+/// <param name="CommitCount">Commits in the range, including those that did not become entries</param>
+internal sealed record ChangelogResult(string? From, string To, IReadOnlyList<ChangelogEntry> Entries, int CommitCount);
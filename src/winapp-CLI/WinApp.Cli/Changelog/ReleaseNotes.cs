@@ -0,0 +1,111 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Changelog;
+
+/// <summary>
+/// Renders changelog entries as release notes
+/// </summary>
+internal static class ReleaseNotes
+{
+    /// <summary>
+    /// Partner Center limits the release notes of a Store listing to this many characters
+    /// </summary>
+    public const int StoreMaxLength = 1500;
+
+    /// <param name="title">Heading of Markdown notes, e.g. the version; ignored by the other formats</param>
+    public static string Render(IReadOnlyList<ChangelogEntry> entries, ChangelogFormat format, string? title = null)
+    {
+        return format switch
+        {
+            ChangelogFormat.Markdown => RenderMarkdown(entries, title),
+            ChangelogFormat.Text => RenderText(entries),
+            ChangelogFormat.Json => RenderJson(entries),
+            _ => throw new ArgumentOutOfRangeException(nameof(format))
+        };
+    }
+
+    public static string GetHeading(ChangelogSection section) => section switch
+    {
+        ChangelogSection.Breaking => "Breaking changes",
+        ChangelogSection.Features => "New features",
+        ChangelogSection.Fixes => "Bug fixes",
+        ChangelogSection.Performance => "Performance improvements",
+        _ => "Other changes"
+    };
+
+    private static string RenderMarkdown(IReadOnlyList<ChangelogEntry> entries, string? title)
+    {
+        var markdown = new StringBuilder();
+        if (title != null)
+        {
+            markdown.Append("## ").Append(title).Append("\n\n");
+        }
+
+        foreach (var group in entries.GroupBy(e => e.Section).OrderBy(g => g.Key))
+        {
+            markdown.Append("### ").Append(GetHeading(group.Key)).Append("\n\n");
+            foreach (var entry in group)
+            {
+                markdown.Append("- ");
+                if (entry.Scope != null)
+                {
+                    markdown.Append("**").Append(entry.Scope).Append(":** ");
+                }
+                markdown.Append(Capitalize(entry.Description))
+                    .Append(entry.PullRequest is int number ? $" (#{number})" : $" ({entry.Sha[..Math.Min(7, entry.Sha.Length)]})")
+                    .Append('\n');
+            }
+            markdown.Append('\n');
+        }
+
+        return markdown.ToString().TrimEnd() + "\n";
+    }
+
+    // Users of the app read these, so commit hashes, scopes and pull request numbers are left out
+    private static string RenderText(IReadOnlyList<ChangelogEntry> entries)
+    {
+        var groups = entries.GroupBy(e => e.Section).OrderBy(g => g.Key).ToList();
+        var text = new StringBuilder();
+        foreach (var group in groups)
+        {
+            if (groups.Count > 1)
+            {
+                text.Append(GetHeading(group.Key)).Append(":\n");
+            }
+            foreach (var entry in group)
+            {
+                text.Append("• ").Append(Capitalize(entry.Description)).Append('\n');
+            }
+            text.Append('\n');
+        }
+
+        return text.ToString().TrimEnd();
+    }
+
+    private static string RenderJson(IReadOnlyList<ChangelogEntry> entries)
+    {
+        var array = new JsonArray();
+        foreach (var entry in entries)
+        {
+            array.Add(new JsonObject
+            {
+                ["section"] = entry.Section.ToString().ToLowerInvariant(),
+                ["scope"] = entry.Scope,
+                ["description"] = entry.Description,
+                ["sha"] = entry.Sha,
+                ["pullRequest"] = entry.PullRequest,
+                ["channels"] = new JsonArray(entry.Channels.Select(c => (JsonNode?)JsonValue.Create(c)).ToArray())
+            });
+        }
+
+        return array.ToJsonString(new System.Text.Json.JsonSerializerOptions { WriteIndented = true });
+    }
+
+    private static string Capitalize(string value) =>
+        value.Length > 0 && char.IsLower(value[0]) ? char.ToUpperInvariant(value[0]) + value[1..] : value;
+}
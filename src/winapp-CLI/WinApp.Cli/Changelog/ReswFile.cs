@@ -0,0 +1,82 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml;
+
+namespace WinApp.Cli.Changelog;
+
+/// <summary>
+/// Edits strings in a .resw resource file, e.g. the What's New text an app shows after an update
+/// </summary>
+internal static class ReswFile
+{
+    private const string EmptyResw = """
+        <?xml version="1.0" encoding="utf-8"?>
+        <root>
+          <resheader name="resmimetype">
+            <value>text/microsoft-resx</value>
+          </resheader>
+          <resheader name="version">
+            <value>2.0</value>
+          </resheader>
+          <resheader name="reader">
+            <value>System.Resources.ResXResourceReader, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+          </resheader>
+          <resheader name="writer">
+            <value>System.Resources.ResXResourceWriter, System.Windows.Forms, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089</value>
+          </resheader>
+        </root>
+        """;
+
+    /// <summary>
+    /// Adds or replaces a string, creating the file when it does not exist
+    /// </summary>
+    /// <returns>True when the string was added, false when an existing one was replaced</returns>
+    public static async Task<bool> SetStringAsync(FileInfo file, string name, string value, string? comment, CancellationToken cancellationToken)
+    {
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        if (file.Exists)
+        {
+            xmlDoc.LoadXml(await File.ReadAllTextAsync(file.FullName, cancellationToken));
+        }
+        else
+        {
+            xmlDoc.LoadXml(EmptyResw);
+        }
+
+        var root = xmlDoc.DocumentElement ?? throw new InvalidOperationException($"{file.Name} has no root element");
+        var data = root.SelectNodes("data")?.OfType<XmlElement>().FirstOrDefault(e => e.GetAttribute("name") == name);
+        var added = data == null;
+        if (data == null)
+        {
+            data = xmlDoc.CreateElement("data");
+            data.SetAttribute("name", name);
+            data.SetAttribute("space", "http://www.w3.org/XML/1998/namespace", "preserve");
+            root.AppendChild(xmlDoc.CreateWhitespace("  "));
+            root.AppendChild(data);
+            root.AppendChild(xmlDoc.CreateWhitespace("\n"));
+        }
+
+        SetChild(xmlDoc, data, "value", value);
+        if (comment != null)
+        {
+            SetChild(xmlDoc, data, "comment", comment);
+        }
+
+        file.Directory?.Create();
+        var settings = new XmlWriterSettings { Encoding = new System.Text.UTF8Encoding(encoderShouldEmitUTF8Identifier: true) };
+        using var writer = XmlWriter.Create(file.FullName, settings);
+        xmlDoc.Save(writer);
+        return added;
+    }
+
+    private static void SetChild(XmlDocument xmlDoc, XmlElement parent, string name, string text)
+    {
+        if (parent[name] is not XmlElement child)
+        {
+            child = xmlDoc.CreateElement(name);
+            parent.AppendChild(child);
+        }
+        child.InnerText = text;
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AffectedCommand : Command
+{
+    public static Option<string> SinceOption { get; }
+    public static Option<bool> IncludeUncommittedOption { get; }
+    public static Option<AffectedFormat> FormatOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static AffectedCommand()
+    {
+        SinceOption = new Option<string>("--since")
+        {
+            Description = "Branch, tag or commit to compare with, e.g. origin/main; changes are counted from its merge base with HEAD",
+            Required = true
+        };
+        IncludeUncommittedOption = new Option<bool>("--include-uncommitted")
+        {
+            Description = "Also count staged, unstaged and untracked changes in the working tree"
+        };
+        FormatOption = new Option<AffectedFormat>("--format")
+        {
+            Description = "Output format: text (one project path per line) or json",
+            DefaultValueFactory = (argumentResult) => AffectedFormat.Text
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "File to write the affected projects to (default: print them)"
+        };
+    }
+
+    public AffectedCommand()
+        : base("affected", "List the package projects of the workspace that changed since a git ref and need re-packing")
+    {
+        Options.Add(SinceOption);
+        Options.Add(IncludeUncommittedOption);
+        Options.Add(FormatOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(IAffectedService affectedService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var since = parseResult.GetRequiredValue(SinceOption);
+            var includeUncommitted = parseResult.GetValue(IncludeUncommittedOption);
+            var format = parseResult.GetValue(FormatOption);
+            var output = parseResult.GetValue(OutputOption);
+
+            return await statusService.ExecuteWithStatusAsync("Finding affected projects...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var result = await affectedService.FindAsync(since, includeUncommitted, taskContext, cancellationToken);
+                    if (result.Projects.Count == 0)
+                    {
+                        return (1, $"{UiSymbols.Error} No package projects found; declare them under 'projects:' in winapp.yaml");
+                    }
+
+                    foreach (var project in result.Affected)
+                    {
+                        taskContext.AddDebugMessage($"{project.Name}: {string.Join(", ", project.Files)}");
+                    }
+
+                    var text = format == AffectedFormat.Json
+                        ? ToJson(result)
+                        : string.Concat(result.Affected.Select(p => (p.Path.Length == 0 ? "." : p.Path) + "\n"));
+
+                    if (output != null)
+                    {
+                        output.Directory?.Create();
+                        await File.WriteAllTextAsync(output.FullName, text, cancellationToken);
+                        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote the affected projects to {output.FullName}");
+                    }
+                    else
+                    {
+                        foreach (var line in text.TrimEnd('\n').Split('\n').Where(l => l.Length > 0))
+                        {
+                            taskContext.AddStatusMessage(line);
+                        }
+                    }
+
+                    return (0, $"{result.Affected.Count} of {result.Projects.Count} project(s) affected by {result.ChangedFiles.Count} changed file(s) since {since}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to find affected projects: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+
+        private static string ToJson(AffectedResult result)
+        {
+            var json = new JsonObject
+            {
+                ["since"] = result.Since,
+                ["changedFiles"] = result.ChangedFiles.Count,
+                ["projects"] = new JsonArray(result.Affected.Select(p => (JsonNode?)new JsonObject
+                {
+                    ["name"] = p.Name,
+                    ["path"] = p.Path.Length == 0 ? "." : p.Path,
+                    ["files"] = new JsonArray(p.Files.Select(f => (JsonNode?)JsonValue.Create(f)).ToArray())
+                }).ToArray())
+            };
+            return json.ToJsonString(new System.Text.Json.JsonSerializerOptions { WriteIndented = true });
+        }
+    }
+}
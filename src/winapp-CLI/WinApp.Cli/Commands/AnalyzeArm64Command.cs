@@ -0,0 +1,116 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AnalyzeArm64Command : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+
+    static AnalyzeArm64Command()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml of the app; its folder is checked as the package folder (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when an issue of this severity or higher is found (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+    }
+
+    public AnalyzeArm64Command()
+        : base("arm64", "Check how the package runs on Windows on ARM devices: drivers and File Explorer extensions that don't load, code that talks to drivers and binaries that run emulated, with how to build each for ARM64 or ARM64EC")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(FailOnOption);
+        Options.Add(AnalyzeCommand.ExplainOption);
+        Options.Add(AnalyzeCommand.SarifOption);
+    }
+
+    public class Handler(IArm64AuditService arm64AuditService, IRuleSetService ruleSetService, ISarifService sarifService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var failOn = parseResult.GetRequiredValue(FailOnOption);
+            var explain = parseResult.GetValue(AnalyzeCommand.ExplainOption);
+            var sarif = parseResult.GetValue(AnalyzeCommand.SarifOption);
+
+            return await statusService.ExecuteWithStatusAsync("Checking the binaries for ARM64...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (explain != null)
+                    {
+                        var lines = ValidationRules.Explain(explain, "arm64");
+                        foreach (var line in lines.Skip(1))
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {line}");
+                        }
+                        return (0, lines[0]);
+                    }
+
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var report = await arm64AuditService.AnalyzeAsync(manifest, taskContext, cancellationToken);
+                    if (report.Binaries.Count == 0)
+                    {
+                        return (0, $"{UiSymbols.Check} No executables or DLLs found next to {manifest.Name}");
+                    }
+
+                    var rules = ruleSetService.Load(manifest, taskContext);
+                    var issues = report.Issues
+                        .Select(i => (Issue: i, Severity: rules.Resolve($"arm64/{i.Check}", i.Severity, i.Binary)))
+                        .Where(r => r.Severity != null)
+                        .Select(r => r.Issue with { Severity = r.Severity!.Value })
+                        .ToList();
+                    var suppressed = report.Issues.Count - issues.Count;
+                    if (sarif != null)
+                    {
+                        await sarifService.WriteAsync(sarif, manifest, [.. issues.Select(i => new SarifFinding(i.Severity, $"arm64/{i.Check}", i.Binary, null, i.Message))], taskContext, cancellationToken);
+                    }
+                    foreach (var issue in issues.OrderByDescending(i => i.Severity))
+                    {
+                        var symbol = issue.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Info,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} [arm64/{issue.Check}] {issue.Message}");
+                    }
+
+                    var native = report.Binaries.Count(b => b.Architecture is ImageArchitecture.Arm64 or ImageArchitecture.Arm64EC or ImageArchitecture.AnyCpu);
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} The package is {report.ProcessorArchitecture}; {native} of {report.Binaries.Count} binaries run natively on ARM64");
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{issues.Count(i => i.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"))
+                        + (suppressed > 0 ? $" ({suppressed} suppressed)" : "");
+                    return issues.Any(i => i.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Found {counts}")
+                        : (0, issues.Count == 0 ? $"{UiSymbols.Check} No ARM64 issues found" : $"Found {counts}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check the binaries for ARM64: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class AnalyzeCommand : Command
+{
+    /// <summary>
+    /// Shared by the analyze subcommands, which explain their own rules by check name too
+    /// </summary>
+    public static Option<string> ExplainOption { get; } = new("--explain")
+    {
+        Description = "Explain a rule, e.g. a11y/contrast, and how to change its severity or suppress it in winapp.yaml, instead of checking"
+    };
+
+    /// <summary>
+    /// Shared by the analyze subcommands and 'validate'
+    /// </summary>
+    public static Option<FileInfo> SarifOption { get; } = new("--sarif")
+    {
+        Description = "Also write the findings as SARIF to this file, for GitHub code scanning and Azure DevOps"
+    };
+
+    public AnalyzeCommand(AnalyzeA11yCommand a11yCommand, AnalyzeSplashCommand splashCommand, AnalyzeScalesCommand scalesCommand, AnalyzeWebViewCommand webViewCommand, AnalyzeGpuCommand gpuCommand, AnalyzeArm64Command arm64Command)
+        : base("analyze", "Check a project's manifest and assets for problems that store and accessibility reviews flag, for a jarring splash screen, for assets Windows draws blurry, for WebView2 profiles that break once packaged, for what makes the app render in software and for binaries that run poorly on ARM64 devices")
+    {
+        Subcommands.Add(a11yCommand);
+        Subcommands.Add(splashCommand);
+        Subcommands.Add(scalesCommand);
+        Subcommands.Add(webViewCommand);
+        Subcommands.Add(gpuCommand);
+        Subcommands.Add(arm64Command);
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Globalization;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AnalyzeGpuCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+
+    static AnalyzeGpuCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml of the app; its folder is checked as the package folder (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when an issue of this severity or higher is found (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+    }
+
+    public AnalyzeGpuCommand()
+        : base("gpu", "Check why a packaged WebView2, WinUI or WPF app may render in software on this machine: display adapters and drivers, Remote Desktop, virtual machines and settings that turn off the GPU")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(FailOnOption);
+        Options.Add(AnalyzeCommand.ExplainOption);
+        Options.Add(AnalyzeCommand.SarifOption);
+    }
+
+    public class Handler(IRenderAuditService renderAuditService, IRuleSetService ruleSetService, ISarifService sarifService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var failOn = parseResult.GetRequiredValue(FailOnOption);
+            var explain = parseResult.GetValue(AnalyzeCommand.ExplainOption);
+            var sarif = parseResult.GetValue(AnalyzeCommand.SarifOption);
+
+            return await statusService.ExecuteWithStatusAsync("Checking GPU rendering...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (explain != null)
+                    {
+                        var lines = ValidationRules.Explain(explain, "gpu");
+                        foreach (var line in lines.Skip(1))
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {line}");
+                        }
+                        return (0, lines[0]);
+                    }
+
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var report = await renderAuditService.AnalyzeAsync(manifest, taskContext, cancellationToken);
+                    var rules = ruleSetService.Load(manifest, taskContext);
+                    var issues = report.Issues
+                        .Select(i => (Issue: i, Severity: rules.Resolve($"gpu/{i.Check}", i.Severity, i.Subject)))
+                        .Where(r => r.Severity != null)
+                        .Select(r => r.Issue with { Severity = r.Severity!.Value })
+                        .ToList();
+                    var suppressed = report.Issues.Count - issues.Count;
+                    if (sarif != null)
+                    {
+                        await sarifService.WriteAsync(sarif, manifest, [.. issues.Select(i => new SarifFinding(i.Severity, $"gpu/{i.Check}", i.Subject, null, i.Message))], taskContext, cancellationToken);
+                    }
+                    foreach (var issue in issues.OrderByDescending(i => i.Severity))
+                    {
+                        var symbol = issue.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Info,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} [gpu/{issue.Check}] {issue.Subject}: {issue.Message}");
+                    }
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} Windows {report.OsVersion}, rendering with {(report.Renderers.Count > 0 ? string.Join(", ", report.Renderers) : "no WebView2, WinUI or WPF found")}");
+                    foreach (var adapter in report.Adapters)
+                    {
+                        var date = adapter.DriverDate?.ToString("yyyy-MM-dd", CultureInfo.InvariantCulture) ?? "no date";
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} {adapter.Name}: {adapter.Provider} driver {adapter.DriverVersion} ({date})");
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{issues.Count(i => i.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"))
+                        + (suppressed > 0 ? $" ({suppressed} suppressed)" : "");
+                    return issues.Any(i => i.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Found {counts}")
+                        : (0, issues.Count == 0 ? $"{UiSymbols.Check} Nothing makes the app render in software on this machine" : $"Found {counts}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check GPU rendering: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
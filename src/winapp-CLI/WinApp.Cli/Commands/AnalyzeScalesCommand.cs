@@ -0,0 +1,108 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AnalyzeScalesCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+
+    static AnalyzeScalesCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to check; assets are resolved relative to it (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when an issue of this severity or higher is found (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+    }
+
+    public AnalyzeScalesCommand()
+        : base("scales", "Load every scale and target size variant of the manifest's assets and check which one Windows draws at each display scale from 100% to 450%, flagging blurry upscales")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(FailOnOption);
+        Options.Add(AnalyzeCommand.ExplainOption);
+        Options.Add(AnalyzeCommand.SarifOption);
+    }
+
+    public class Handler(IScaleAuditService scaleAuditService, IRuleSetService ruleSetService, ISarifService sarifService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var failOn = parseResult.GetRequiredValue(FailOnOption);
+            var explain = parseResult.GetValue(AnalyzeCommand.ExplainOption);
+            var sarif = parseResult.GetValue(AnalyzeCommand.SarifOption);
+
+            return await statusService.ExecuteWithStatusAsync("Checking asset scales...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (explain != null)
+                    {
+                        var lines = ValidationRules.Explain(explain, "scales");
+                        foreach (var line in lines.Skip(1))
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {line}");
+                        }
+                        return (0, lines[0]);
+                    }
+
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var report = await scaleAuditService.AnalyzeAsync(manifest, taskContext, cancellationToken);
+                    var rules = ruleSetService.Load(manifest, taskContext);
+                    var issues = report.Issues
+                        .Select(i => (Issue: i, Severity: rules.Resolve($"scales/{i.Check}", i.Severity, i.Asset)))
+                        .Where(r => r.Severity != null)
+                        .Select(r => r.Issue with { Severity = r.Severity!.Value })
+                        .ToList();
+                    var suppressed = report.Issues.Count - issues.Count;
+                    if (sarif != null)
+                    {
+                        await sarifService.WriteAsync(sarif, manifest, [.. issues.Select(i => new SarifFinding(i.Severity, $"scales/{i.Check}", i.Asset, null, i.Message))], taskContext, cancellationToken);
+                    }
+                    foreach (var issue in issues.OrderByDescending(i => i.Severity))
+                    {
+                        var symbol = issue.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Info,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} [scales/{issue.Check}] {issue.Asset}: {issue.Message}");
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{issues.Count(i => i.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"))
+                        + (suppressed > 0 ? $" ({suppressed} suppressed)" : "");
+                    var checkedAssets = $"{report.Assets.Count} asset(s) and {report.Variants} variant(s)";
+                    return issues.Any(i => i.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Found {counts} in {checkedAssets}")
+                        : (0, issues.Count == 0 ? $"{UiSymbols.Check} No scale issues found in {checkedAssets}" : $"Found {counts} in {checkedAssets}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check asset scales: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AnalyzeSplashCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+
+    static AnalyzeSplashCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to check; the image is resolved relative to it (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when an issue of this severity or higher is found (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+    }
+
+    public AnalyzeSplashCommand()
+        : base("splash", "Check the splash screen image, scales and background color, with the splash declared in winapp.yaml applied")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(FailOnOption);
+        Options.Add(AnalyzeCommand.ExplainOption);
+        Options.Add(AnalyzeCommand.SarifOption);
+    }
+
+    public class Handler(ISplashScreenService splashScreenService, IConfigService configService, IRuleSetService ruleSetService, ISarifService sarifService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var failOn = parseResult.GetRequiredValue(FailOnOption);
+            var explain = parseResult.GetValue(AnalyzeCommand.ExplainOption);
+            var sarif = parseResult.GetValue(AnalyzeCommand.SarifOption);
+
+            return await statusService.ExecuteWithStatusAsync("Checking the splash screen...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (explain != null)
+                    {
+                        var lines = ValidationRules.Explain(explain, "splash");
+                        foreach (var line in lines.Skip(1))
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {line}");
+                        }
+                        return (0, lines[0]);
+                    }
+
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var splash = configService.Exists() ? configService.Load().Splash : null;
+                    var report = await splashScreenService.AnalyzeAsync(manifest, splash, taskContext, cancellationToken);
+                    var rules = ruleSetService.Load(manifest, taskContext);
+                    var issues = report.Issues
+                        .Select(i => (Issue: i, Severity: rules.Resolve($"splash/{i.Check}", i.Severity, i.Application)))
+                        .Where(r => r.Severity != null)
+                        .Select(r => r.Issue with { Severity = r.Severity!.Value })
+                        .ToList();
+                    var suppressed = report.Issues.Count - issues.Count;
+                    if (sarif != null)
+                    {
+                        await sarifService.WriteAsync(sarif, manifest, [.. issues.Select(i => new SarifFinding(i.Severity, $"splash/{i.Check}", i.Application, null, i.Message))], taskContext, cancellationToken);
+                    }
+                    foreach (var issue in issues.OrderByDescending(i => i.Severity))
+                    {
+                        var symbol = issue.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Info,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} [splash/{issue.Check}] {issue.Application}: {issue.Message}");
+                    }
+
+                    if (splash?.Extended == true)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Extended splash: show the splash image centered on its background color in the app's first window, and keep that color until the app has painted its first frame");
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{issues.Count(i => i.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"))
+                        + (suppressed > 0 ? $" ({suppressed} suppressed)" : "");
+                    var checkedApps = $"{report.Applications.Count} application(s)";
+                    return issues.Any(i => i.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Found {counts} in {checkedApps}")
+                        : (0, issues.Count == 0 ? $"{UiSymbols.Check} No splash screen issues found in {checkedApps}" : $"Found {counts} in {checkedApps}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check the splash screen: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class AuditCommand : Command
+{
+    public AuditCommand(AuditToolsCommand auditToolsCommand, AuditDigestsCommand auditDigestsCommand, AuditExpiryCommand auditExpiryCommand, AuditDefenderCommand auditDefenderCommand)
+        : base("audit", "Inspect how the CLI and your project depend on external tooling, which algorithms packages use, when signing and Store credentials expire, and how Microsoft Defender affects the dev loop")
+    {
+        Subcommands.Add(auditToolsCommand);
+        Subcommands.Add(auditDigestsCommand);
+        Subcommands.Add(auditExpiryCommand);
+        Subcommands.Add(auditDefenderCommand);
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Spectre.Console;
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AuditDefenderCommand : Command
+{
+    public static Option<DirectoryInfo[]> PathOption { get; }
+    public static Option<bool> ExcludeOption { get; }
+    public static Option<bool> YesOption { get; }
+    public static Option<FileInfo> ReportOption { get; }
+
+    static AuditDefenderCommand()
+    {
+        PathOption = new Option<DirectoryInfo[]>("--path")
+        {
+            Description = "Folder(s) the dev loop writes to besides the .winapp folders, e.g. the build output",
+            AllowMultipleArgumentsPerToken = true
+        };
+        PathOption.AcceptExistingOnly();
+        ExcludeOption = new Option<bool>("--exclude")
+        {
+            Description = "Exclude the folders from real-time scanning and, with Controlled Folder Access on, allow winapp and the SDK tools (requires elevation)"
+        };
+        YesOption = new Option<bool>("--yes")
+        {
+            Description = "Add the exclusions of --exclude without asking first"
+        };
+        ReportOption = new Option<FileInfo>("--report")
+        {
+            Description = "Write the findings and the commands that add the exclusions for IT, as Markdown or, for a .json file, JSON"
+        };
+    }
+
+    public AuditDefenderCommand()
+        : base("defender", "Check whether Microsoft Defender's real-time scanning or Controlled Folder Access slows down or blocks pack and register, and exclude the folders of the dev loop")
+    {
+        Options.Add(PathOption);
+        Options.Add(ExcludeOption);
+        Options.Add(YesOption);
+        Options.Add(ReportOption);
+    }
+
+    public class Handler(IDefenderAuditService defenderAuditService, IStatusService statusService, IAnsiConsole ansiConsole) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var folders = parseResult.GetValue(PathOption) ?? [];
+            var exclude = parseResult.GetValue(ExcludeOption);
+            var yes = parseResult.GetValue(YesOption);
+            var report = parseResult.GetValue(ReportOption);
+
+            DefenderAudit? audit = null;
+            var exitCode = await statusService.ExecuteWithStatusAsync("Checking Microsoft Defender...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    audit = await defenderAuditService.AuditAsync(folders, taskContext, cancellationToken);
+                    foreach (var finding in audit.Findings.OrderByDescending(f => f.Severity))
+                    {
+                        var symbol = finding.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Note,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} {finding.Subject} {finding.Message}");
+                    }
+                    if (report != null)
+                    {
+                        await defenderAuditService.WriteReportAsync(audit, report, cancellationToken);
+                        taskContext.AddStatusMessage($"{UiSymbols.Files} Wrote the report for IT to {report.FullName}");
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{audit.Findings.Count(f => f.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"));
+                    return audit.Findings.Any(f => f.Severity == AccessibilitySeverity.Error)
+                        ? (1, $"{UiSymbols.Error} Checked Microsoft Defender: {counts}")
+                        : (0, $"{UiSymbols.Check} Checked Microsoft Defender: {counts}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check Microsoft Defender: {ex.Message}");
+                }
+            }, cancellationToken);
+
+            if (!exclude || audit == null || !audit.Status.Active)
+            {
+                return exitCode;
+            }
+            var scope = string.Join(", ", audit.Folders.Where(f => f.Excluded != true).Select(f => f.Folder).DefaultIfEmpty("winapp and the SDK tools"));
+            if (!yes && !await ansiConsole.PromptAsync(new ConfirmationPrompt($"Add Microsoft Defender exclusions for {Markup.Escape(scope)} (requires elevation and you will be prompted by User Account Control)?"), cancellationToken))
+            {
+                return exitCode;
+            }
+
+            return await statusService.ExecuteWithStatusAsync("Adding Microsoft Defender exclusions...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    await defenderAuditService.ExcludeAsync(audit, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Excluded the dev loop from Microsoft Defender scanning; run 'winapp audit defender' again to compare");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to add Microsoft Defender exclusions: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AuditDigestsCommand : Command
+{
+    public static Argument<FileInfo> PackagePathArgument { get; }
+    public static Option<DigestAlgorithm?> RequireOption { get; }
+
+    static AuditDigestsCommand()
+    {
+        PackagePathArgument = new Argument<FileInfo>("package-path")
+        {
+            Description = "Path to the .msix/.appx package (or bundle) to inspect"
+        };
+        PackagePathArgument.AcceptExistingOnly();
+        RequireOption = new Option<DigestAlgorithm?>("--require")
+        {
+            Description = "Exit with an error if any hash used by the package is weaker than this algorithm (sha256, sha384 or sha512)"
+        };
+    }
+
+    public AuditDigestsCommand()
+        : base("digests", "Report the hash and signature algorithms an existing package uses")
+    {
+        Arguments.Add(PackagePathArgument);
+        Options.Add(RequireOption);
+    }
+
+    public class Handler(INativePackagingService nativePackagingService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var packagePath = parseResult.GetRequiredValue(PackagePathArgument);
+            var required = parseResult.GetValue(RequireOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Inspecting {packagePath.Name}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var report = await nativePackagingService.InspectDigestsAsync(packagePath, taskContext, required, cancellationToken);
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Files} Block map: {report.BlockMapAlgorithm?.Name ?? report.BlockMapHashMethod}");
+                    if (report.IsSigned)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Lock} Signature: {report.SignatureDigestAlgorithm?.Name}, {report.SignatureKey ?? "unknown key"}");
+                        taskContext.AddStatusMessage($"{UiSymbols.Id} Certificate: {report.CertificateSignatureAlgorithm ?? "not embedded"}");
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Timestamp: {report.TimestampDigestAlgorithm ?? "none"}");
+                    }
+                    else
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Lock} Signature: none");
+                    }
+
+                    if (report.MinTargetVersion != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Info} Lowest target: {report.MinTargetVersion}");
+                    }
+
+                    foreach (var issue in report.Issues)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {issue}");
+                    }
+
+                    if (required != null && report.Issues.Count > 0)
+                    {
+                        return (1, $"{UiSymbols.Error} Package does not meet the required {required.Value.ToString().ToUpperInvariant()} strength ({report.Issues.Count} issue(s)).");
+                    }
+
+                    return (0, report.Issues.Count == 0 ? "No algorithm issues found." : $"Found {report.Issues.Count} algorithm issue(s).");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to inspect package: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AuditExpiryCommand : Command
+{
+    public static Option<FileInfo[]> CertOption { get; }
+    public static Option<string> CertPasswordOption { get; }
+    public static Option<bool> StoreSecretOption { get; }
+    public static Option<string> TrustedSigningProfileOption { get; }
+    public static Option<int> WarnDaysOption { get; }
+    public static Option<int> ErrorDaysOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+
+    static AuditExpiryCommand()
+    {
+        CertOption = new Option<FileInfo[]>("--cert")
+        {
+            Description = "Signing certificate(s) to check, .pfx or .cer (default: devcert.pfx next to winapp.yaml, if there is one)",
+            AllowMultipleArgumentsPerToken = true
+        };
+        CertOption.AcceptExistingOnly();
+        CertPasswordOption = new Option<string>("--cert-password")
+        {
+            Description = "Password of the .pfx certificates",
+            DefaultValueFactory = (argumentResult) => "password"
+        };
+        StoreSecretOption = new Option<bool>("--store-secret")
+        {
+            Description = "Check the client secret in WINAPP_STORE_CLIENT_SECRET; the Azure AD application needs the Application.Read.All permission in Microsoft Graph"
+        };
+        TrustedSigningProfileOption = new Option<string>("--trusted-signing-profile")
+        {
+            Description = "Azure resource ID of a Trusted Signing certificate profile to check, read with the AZURE_TENANT_ID, AZURE_CLIENT_ID and AZURE_CLIENT_SECRET credentials"
+        };
+        WarnDaysOption = new Option<int>("--warn-days")
+        {
+            Description = "Warn about credentials that expire within this many days",
+            DefaultValueFactory = (argumentResult) => 30
+        };
+        ErrorDaysOption = new Option<int>("--error-days")
+        {
+            Description = "Report credentials that expire within this many days as errors",
+            DefaultValueFactory = (argumentResult) => 7
+        };
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when a check has this severity or higher (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+    }
+
+    public AuditExpiryCommand()
+        : base("expiry", "Check when signing certificates, the Store automation's client secret and Trusted Signing profiles expire, for a scheduled CI job")
+    {
+        Options.Add(CertOption);
+        Options.Add(CertPasswordOption);
+        Options.Add(StoreSecretOption);
+        Options.Add(TrustedSigningProfileOption);
+        Options.Add(WarnDaysOption);
+        Options.Add(ErrorDaysOption);
+        Options.Add(FailOnOption);
+    }
+
+    public class Handler(IExpiryAuditService expiryAuditService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var options = new ExpiryAuditOptions
+            {
+                Certificates = parseResult.GetValue(CertOption) ?? [],
+                CertificatePassword = parseResult.GetRequiredValue(CertPasswordOption),
+                StoreSecret = parseResult.GetValue(StoreSecretOption),
+                TrustedSigningProfile = parseResult.GetValue(TrustedSigningProfileOption),
+                WarningDays = parseResult.GetValue(WarnDaysOption),
+                ErrorDays = parseResult.GetValue(ErrorDaysOption)
+            };
+            var failOn = parseResult.GetValue(FailOnOption);
+
+            return await statusService.ExecuteWithStatusAsync("Checking credential expiry...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var checks = await expiryAuditService.CheckAsync(options, taskContext, cancellationToken);
+                    if (checks.Count == 0)
+                    {
+                        return (1, $"{UiSymbols.Error} Nothing to check; pass --cert, --store-secret or --trusted-signing-profile");
+                    }
+
+                    foreach (var check in checks.OrderByDescending(c => c.Severity).ThenBy(c => c.ExpiresOn))
+                    {
+                        var symbol = check.Severity switch
+                        {
+                            AccessibilitySeverity.Error => UiSymbols.Error,
+                            AccessibilitySeverity.Warning => UiSymbols.Warning,
+                            _ => UiSymbols.Check,
+                        };
+                        taskContext.AddStatusMessage($"{symbol} {check.Subject} {check.Message}");
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{checks.Count(c => c.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"));
+                    return checks.Any(c => c.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Checked {checks.Count} credential(s): {counts}")
+                        : (0, $"{UiSymbols.Check} Checked {checks.Count} credential(s): {counts}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to check credential expiry: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
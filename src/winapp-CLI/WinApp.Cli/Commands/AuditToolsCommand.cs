@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Extensions.Logging;
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class AuditToolsCommand : Command
+{
+    public static Option<PackagingEngine> EngineOption { get; }
+    public static Option<bool> StrictOption { get; }
+
+    static AuditToolsCommand()
+    {
+        EngineOption = new Option<PackagingEngine>("--engine")
+        {
+            Description = "Packaging engine to audit for pack and sign operations",
+            DefaultValueFactory = (argumentResult) => PackagingEngine.Sdk
+        };
+        StrictOption = new Option<bool>("--strict")
+        {
+            Description = "Exit with an error if any operation still requires the Windows SDK build tools"
+        };
+    }
+
+    public AuditToolsCommand()
+        : base("tools", "Report which operations still shell out to Windows SDK tools versus native implementations")
+    {
+        Options.Add(EngineOption);
+        Options.Add(StrictOption);
+    }
+
+    public class Handler(IToolchainAuditService toolchainAuditService, ILogger<AuditToolsCommand> logger) : AsynchronousCommandLineAction
+    {
+        public override Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var engine = parseResult.GetValue(EngineOption);
+            var strict = parseResult.GetValue(StrictOption);
+
+            var entries = toolchainAuditService.GetAuditEntries(engine);
+
+            logger.LogInformation("{UISymbol} Toolchain audit (packaging engine: {Engine})", UiSymbols.Search, engine.ToString().ToLowerInvariant());
+            logger.LogInformation("");
+
+            foreach (var entry in entries)
+            {
+                if (entry.IsNative)
+                {
+                    logger.LogInformation("{UISymbol} {Operation} [{Commands}]: native", UiSymbols.Check, entry.Operation, entry.Commands);
+                }
+                else if (entry.RequiresWindowsSdk)
+                {
+                    var availability = entry.ToolAvailable == true ? "installed" : "not installed";
+                    logger.LogInformation("{UISymbol} {Operation} [{Commands}]: {Tool} (Windows SDK, {Availability})", UiSymbols.Warning, entry.Operation, entry.Commands, entry.ExternalTool, availability);
+                }
+                else
+                {
+                    logger.LogInformation("{UISymbol} {Operation} [{Commands}]: {Tool}", UiSymbols.Note, entry.Operation, entry.Commands, entry.ExternalTool);
+                }
+
+                if (!string.IsNullOrEmpty(entry.Remarks))
+                {
+                    logger.LogInformation("   {Remarks}", entry.Remarks);
+                }
+            }
+
+            var nativeCount = entries.Count(e => e.IsNative);
+            var sdkCount = entries.Count(e => e.RequiresWindowsSdk);
+            logger.LogInformation("");
+            logger.LogInformation("{NativeCount} of {Total} operations run natively; {SdkCount} still require the Windows SDK build tools.", nativeCount, entries.Count, sdkCount);
+
+            if (strict && sdkCount > 0)
+            {
+                logger.LogError("{UISymbol} Windows SDK tools are still required (--strict)", UiSymbols.Error);
+                return Task.FromResult(1);
+            }
+
+            return Task.FromResult(0);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class BundleCommand : Command
+{
+    public BundleCommand(BundleThinCommand bundleThinCommand)
+        : base("bundle", "Inspect and transform MSIX bundles")
+    {
+        Subcommands.Add(bundleThinCommand);
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class BundleThinCommand : Command
+{
+    public static Argument<FileInfo> BundlePathArgument { get; }
+    public static Option<string> ArchOption { get; }
+    public static Option<string[]> LangOption { get; }
+    public static Option<int[]> ScaleOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+    public static Option<FileInfo> CertOption { get; }
+    public static Option<string> CertPasswordOption { get; }
+
+    static BundleThinCommand()
+    {
+        BundlePathArgument = new Argument<FileInfo>("bundle-path")
+        {
+            Description = "Path to the .msixbundle or .appxbundle file to thin"
+        };
+        BundlePathArgument.AcceptExistingOnly();
+        ArchOption = new Option<string>("--arch")
+        {
+            Description = "Target architecture of the application package to keep (x64, x86, arm64, neutral)",
+            Required = true
+        };
+        LangOption = new Option<string[]>("--lang")
+        {
+            Description = "Language(s) to keep, e.g. en-US. Matching language resource packages are merged into the output package",
+            AllowMultipleArgumentsPerToken = true
+        };
+        ScaleOption = new Option<int[]>("--scale")
+        {
+            Description = "Scale resource package(s) to merge, e.g. 200. By default only the scale assets already in the application package are kept",
+            AllowMultipleArgumentsPerToken = true
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Output msix file name (defaults to <bundle>_<arch>.msix in the current directory)"
+        };
+        CertOption = new Option<FileInfo>("--cert")
+        {
+            Description = "Path to signing certificate (will sign the thinned package if provided)"
+        };
+        CertOption.AcceptExistingOnly();
+        CertPasswordOption = new Option<string>("--cert-password")
+        {
+            Description = "Certificate password (default: password)",
+            DefaultValueFactory = (argumentResult) => "password"
+        };
+    }
+
+    public BundleThinCommand()
+        : base("thin", "Extract the minimal applicable set for an architecture/language from a bundle into a single installable package")
+    {
+        Arguments.Add(BundlePathArgument);
+        Options.Add(ArchOption);
+        Options.Add(LangOption);
+        Options.Add(ScaleOption);
+        Options.Add(OutputOption);
+        Options.Add(CertOption);
+        Options.Add(CertPasswordOption);
+    }
+
+    public class Handler(IBundleService bundleService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var bundlePath = parseResult.GetRequiredValue(BundlePathArgument);
+            var arch = parseResult.GetRequiredValue(ArchOption);
+            var languages = parseResult.GetValue(LangOption) ?? [];
+            var scales = parseResult.GetValue(ScaleOption) ?? [];
+            var output = parseResult.GetValue(OutputOption);
+            var certPath = parseResult.GetValue(CertOption);
+            var certPassword = parseResult.GetRequiredValue(CertPasswordOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Thinning bundle {bundlePath.Name}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var result = await bundleService.ThinBundleAsync(bundlePath, arch, languages, scales, output, certPath, certPassword, taskContext, cancellationToken);
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Package} Application package: {result.ApplicationPackage}");
+                    foreach (var resourcePackage in result.ResourcePackages)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Add} Merged resource package: {resourcePackage}");
+                    }
+                    taskContext.AddStatusMessage($"{UiSymbols.Package} Package: {result.MsixPath}");
+                    if (result.Signed)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Lock} Package has been signed");
+                    }
+                    else
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Package is unsigned. Use 'winapp sign' before sideloading it");
+                    }
+
+                    return (0, "Bundle thinned successfully.");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to thin bundle: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
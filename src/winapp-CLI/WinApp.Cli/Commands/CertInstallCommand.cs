@@ -49,20 +49,20 @@ internal class CertInstallCommand : Command
             var password = parseResult.GetRequiredValue(PasswordOption);
             var force = parseResult.GetRequiredValue(ForceOption);
 
-            return await statusService.ExecuteWithStatusAsync("Installing certificate...", (taskContext, cancellationToken) =>
+            return await statusService.ExecuteWithStatusAsync("Installing certificate...", async (taskContext, cancellationToken) =>
             {
                 try
                 {
-                    var result = certificateService.InstallCertificate(certPath, password, force, taskContext);
+                    var result = await certificateService.InstallCertificateAsync(certPath, password, force, taskContext, cancellationToken);
                     var message = !result
                         ? "Certificate is already installed."
                         : "Certificate installed successfully!";
 
-                    return Task.FromResult((0, message));
+                    return (0, message);
                 }
                 catch (Exception error)
                 {
-                    return Task.FromResult((1, $"{UiSymbols.Error} Failed to install certificate: {error.Message}"));
+                    return (1, $"{UiSymbols.Error} Failed to install certificate: {error.Message}");
                 }
             }, cancellationToken);
         }
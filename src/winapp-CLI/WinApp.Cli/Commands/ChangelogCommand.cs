@@ -0,0 +1,158 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Changelog;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ChangelogCommand : Command
+{
+    public static Option<string> FromOption { get; }
+    public static Option<string> ToOption { get; }
+    public static Option<string> TagPatternOption { get; }
+    public static Option<ChangelogSource> SourceOption { get; }
+    public static Option<string> ChannelOption { get; }
+    public static Option<bool> IncludeAllOption { get; }
+    public static Option<ChangelogFormat> FormatOption { get; }
+    public static Option<string> TitleOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+    public static Option<FileInfo> ReswOption { get; }
+    public static Option<string> ReswNameOption { get; }
+
+    static ChangelogCommand()
+    {
+        FromOption = new Option<string>("--from")
+        {
+            Description = "Tag or commit to start after (default: the latest tag before --to)"
+        };
+        ToOption = new Option<string>("--to")
+        {
+            Description = "Tag or commit to end at",
+            DefaultValueFactory = (argumentResult) => "HEAD"
+        };
+        TagPatternOption = new Option<string>("--tag-pattern")
+        {
+            Description = "Only consider tags matching this glob when finding --from, e.g. 'v*' to skip pre-release tags of other channels"
+        };
+        SourceOption = new Option<ChangelogSource>("--source")
+        {
+            Description = "Build the notes from conventional 'commits' or from the titles of merged pull requests ('prs')",
+            DefaultValueFactory = (argumentResult) => ChangelogSource.Commits
+        };
+        ChannelOption = new Option<string>("--channel")
+        {
+            Description = "Release channel to build notes for; leaves out changes whose 'Channel:' trailer names only other channels"
+        };
+        IncludeAllOption = new Option<bool>("--include-all")
+        {
+            Description = "Also include changes that are not user-facing (docs, chore, ci, refactor, ...) and commits that do not follow the convention"
+        };
+        FormatOption = new Option<ChangelogFormat>("--format")
+        {
+            Description = "Output format: markdown, text (for Store listings and in-app What's New) or json",
+            DefaultValueFactory = (argumentResult) => ChangelogFormat.Markdown
+        };
+        TitleOption = new Option<string>("--title")
+        {
+            Description = "Heading of Markdown notes (default: --to, unless it is HEAD)"
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "File to write the notes to, e.g. for 'store submit --release-notes' or 'distribute web --release-notes' (default: print them)"
+        };
+        ReswOption = new Option<FileInfo>("--resw")
+        {
+            Description = "Also write the notes as plain text to a string of this .resw file, for an in-app What's New screen"
+        };
+        ReswNameOption = new Option<string>("--resw-name")
+        {
+            Description = "Name of the --resw string",
+            DefaultValueFactory = (argumentResult) => "WhatsNew"
+        };
+    }
+
+    public ChangelogCommand()
+        : base("changelog", "Build release notes from the git history between two tags")
+    {
+        Options.Add(FromOption);
+        Options.Add(ToOption);
+        Options.Add(TagPatternOption);
+        Options.Add(SourceOption);
+        Options.Add(ChannelOption);
+        Options.Add(IncludeAllOption);
+        Options.Add(FormatOption);
+        Options.Add(TitleOption);
+        Options.Add(OutputOption);
+        Options.Add(ReswOption);
+        Options.Add(ReswNameOption);
+    }
+
+    public class Handler(IChangelogService changelogService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var options = new ChangelogOptions
+            {
+                From = parseResult.GetValue(FromOption),
+                To = parseResult.GetRequiredValue(ToOption),
+                TagPattern = parseResult.GetValue(TagPatternOption),
+                Source = parseResult.GetValue(SourceOption),
+                Channel = parseResult.GetValue(ChannelOption),
+                IncludeAll = parseResult.GetValue(IncludeAllOption),
+            };
+            var format = parseResult.GetValue(FormatOption);
+            var output = parseResult.GetValue(OutputOption);
+            var resw = parseResult.GetValue(ReswOption);
+
+            return await statusService.ExecuteWithStatusAsync("Building release notes...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var result = await changelogService.BuildAsync(options, taskContext, cancellationToken);
+                    var title = parseResult.GetValue(TitleOption)
+                        ?? (options.To.Equals("HEAD", StringComparison.OrdinalIgnoreCase) ? null : options.To);
+                    var notes = ReleaseNotes.Render(result.Entries, format, title);
+
+                    if (output != null)
+                    {
+                        output.Directory?.Create();
+                        await File.WriteAllTextAsync(output.FullName, notes, cancellationToken);
+                        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote the release notes to {output.FullName}");
+                    }
+                    else
+                    {
+                        foreach (var line in notes.Split('\n'))
+                        {
+                            taskContext.AddStatusMessage(line);
+                        }
+                    }
+
+                    if (resw != null)
+                    {
+                        var text = ReleaseNotes.Render(result.Entries, ChangelogFormat.Text);
+                        var added = await ReswFile.SetStringAsync(resw, parseResult.GetRequiredValue(ReswNameOption), text, $"Release notes for {title ?? result.To}, generated by winapp changelog", cancellationToken);
+                        taskContext.AddStatusMessage($"{UiSymbols.Save} {(added ? "Added" : "Updated")} {parseResult.GetRequiredValue(ReswNameOption)} in {resw.FullName}");
+                    }
+
+                    if (format == ChangelogFormat.Text && notes.Length > ReleaseNotes.StoreMaxLength)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} The notes are {notes.Length:N0} characters; Store listings accept at most {ReleaseNotes.StoreMaxLength:N0}");
+                    }
+
+                    var range = result.From == null ? $"up to {result.To}" : $"{result.From}..{result.To}";
+                    return (0, $"{result.Entries.Count} change(s) from {result.CommitCount} commit(s) {range}{(output != null ? $", saved to {output.FullName}" : "")}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to build release notes: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class CleanupCommand : Command
+{
+    public static Option<bool> AllWinappOption { get; }
+    public static Option<bool> DryRunOption { get; }
+    public static Option<string> BranchOption { get; }
+
+    static CleanupCommand()
+    {
+        AllWinappOption = new Option<bool>("--all-winapp")
+        {
+            Description = "Clean up everything winapp created on this machine, not only for the project in the current directory"
+        };
+        DryRunOption = new Option<bool>("--dry-run")
+        {
+            Description = "List what would be removed without removing it"
+        };
+        BranchOption = new Option<string>("--branch")
+        {
+            Description = "Remove the packages and certificate that 'pack --branch-identity' created for a git branch (default: the current branch)",
+            Arity = ArgumentArity.ZeroOrOne
+        };
+    }
+
+    public CleanupCommand()
+        : base("cleanup", "Remove packages and certificates that development builds left on this machine")
+    {
+        Options.Add(AllWinappOption);
+        Options.Add(DryRunOption);
+        Options.Add(BranchOption);
+    }
+
+    public class Handler(ICleanupService cleanupService, IBranchIdentityService branchIdentityService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var allWinapp = parseResult.GetValue(AllWinappOption);
+            var dryRun = parseResult.GetValue(DryRunOption);
+            var cleanBranch = parseResult.GetResult(BranchOption) != null;
+            var branch = parseResult.GetValue(BranchOption);
+
+            return await statusService.ExecuteWithStatusAsync("Cleaning up...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    return cleanBranch
+                        ? await CleanupBranchAsync(branch, taskContext, cancellationToken)
+                        : await CleanupAsync(allWinapp, dryRun, taskContext, cancellationToken);
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to clean up: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+
+        private async Task<(int, string)> CleanupAsync(bool allWinapp, bool dryRun, TaskContext taskContext, CancellationToken cancellationToken)
+        {
+            var plan = await cleanupService.FindAsync(allWinapp, taskContext, cancellationToken);
+            if (!plan.StagedChecked)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Note} Staged packages can only be found as administrator; run from an elevated prompt to include them");
+            }
+
+            if (plan.Items.Count == 0)
+            {
+                return (0, $"{UiSymbols.Check} Nothing to clean up{(allWinapp ? "" : " for this project")}");
+            }
+
+            if (dryRun)
+            {
+                foreach (var item in plan.Items)
+                {
+                    taskContext.AddStatusMessage($"{Describe(item.Kind)} {item.Id} ({item.Description}){(CleanupService.NeedsElevation(item) ? ", needs administrator rights" : "")}");
+                }
+                return (0, $"{plan.Items.Count} item(s) would be removed");
+            }
+
+            var result = await cleanupService.RemoveAsync(plan.Items, taskContext, cancellationToken);
+            foreach (var item in result.Removed)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Trash} Removed {Describe(item.Kind).ToLowerInvariant()} {item.Id} ({item.Description})");
+            }
+            foreach (var (item, error) in result.Failed)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not remove {Describe(item.Kind).ToLowerInvariant()} {item.Id}: {error}");
+            }
+
+            return result.Failed.Count == 0
+                ? (0, $"Removed {result.Removed.Count} item(s)")
+                : (1, $"{UiSymbols.Error} Removed {result.Removed.Count} item(s); {result.Failed.Count} could not be removed");
+        }
+
+        private async Task<(int, string)> CleanupBranchAsync(string? branch, TaskContext taskContext, CancellationToken cancellationToken)
+        {
+            var identity = await branchIdentityService.ResolveAsync(branch, cancellationToken);
+            var result = await branchIdentityService.CleanupAsync(identity, taskContext, cancellationToken);
+
+            foreach (var package in result.Packages)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Trash} Removed package {package}");
+            }
+            foreach (var certificate in result.Certificates)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Trash} Removed certificate {certificate}");
+            }
+
+            return (0, $"Removed {result.Packages.Count} package(s) and {result.Certificates.Count} certificate(s) of branch '{identity.Branch}'");
+        }
+
+        private static string Describe(CleanupItemKind kind) => kind switch
+        {
+            CleanupItemKind.LoosePackage => "Loose package",
+            CleanupItemKind.StaleRegistration => "Stale registration",
+            CleanupItemKind.StagedPackage => "Staged package",
+            _ => "Orphaned certificate",
+        };
+    }
+}
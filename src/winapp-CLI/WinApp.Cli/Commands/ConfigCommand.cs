@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class ConfigCommand : Command
+{
+    public ConfigCommand(ConfigShowCommand configShowCommand)
+        : base("config", "Inspect the settings that machine, user and project configuration set, such as the timestamp server")
+    {
+        Subcommands.Add(configShowCommand);
+    }
+}
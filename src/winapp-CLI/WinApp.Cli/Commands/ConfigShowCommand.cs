@@ -0,0 +1,83 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ConfigShowCommand : Command
+{
+    public static Option<bool> OriginsOption { get; }
+
+    static ConfigShowCommand()
+    {
+        OriginsOption = new Option<bool>("--origins")
+        {
+            Description = "Show the layer and file each value comes from, and the configuration files that were read"
+        };
+    }
+
+    public ConfigShowCommand()
+        : base("show", "Show the effective value of every setting: built-in defaults, overridden by the machine configuration, the user configuration, winapp.yaml and environment variables")
+    {
+        Options.Add(OriginsOption);
+    }
+
+    public class Handler(ISettingsService settingsService, IConfigService configService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var origins = parseResult.GetValue(OriginsOption);
+
+            return await statusService.ExecuteWithStatusAsync("Resolving settings...", (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (origins)
+                    {
+                        foreach (var (layer, file) in new[] { ("machine", settingsService.MachineConfigPath), ("user", settingsService.UserConfigPath), ("repository", configService.ConfigPath) })
+                        {
+                            file.Refresh();
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {layer}: {file.FullName}{(file.Exists ? "" : " (not found)")}");
+                        }
+                    }
+
+                    var settings = settingsService.GetAll();
+                    foreach (var setting in settings)
+                    {
+                        var value = setting.Value ?? "(not set)";
+                        taskContext.AddStatusMessage(origins
+                            ? $"{UiSymbols.Bullet} {setting.Key} = {value}  [{Describe(setting)}]"
+                            : $"{UiSymbols.Bullet} {setting.Key} = {value}");
+                    }
+
+                    foreach (var (key, file) in settingsService.GetUnknownKeys())
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {file.FullName} sets '{key}', which is not a setting. Settings: {string.Join(", ", SettingsService.Definitions.Select(d => d.Key))}");
+                    }
+
+                    return Task.FromResult((0, $"{settings.Count(s => s.Origin != SettingOrigin.BuiltIn)} of {settings.Count} setting(s) are configured; command line options override them"));
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return Task.FromResult((1, $"{UiSymbols.Error} Failed to resolve settings: {ex.Message}"));
+                }
+            }, cancellationToken);
+        }
+
+        internal static string Describe(SettingValue setting) => setting.Origin switch
+        {
+            SettingOrigin.BuiltIn => "built-in",
+            SettingOrigin.Machine => $"machine: {setting.Source}",
+            SettingOrigin.User => $"user: {setting.Source}",
+            SettingOrigin.Repository => $"repository: {setting.Source}",
+            SettingOrigin.Environment => $"environment: {setting.Source}",
+            _ => $"command line: {setting.Source}",
+        };
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class CrashesCommand : Command
+{
+    public static Option<string?> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+
+    static CrashesCommand()
+    {
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name of the installed package (default: the Identity Name of the project's appxmanifest.xml, or its .debug or branch identity)"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml that identifies the package (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+    }
+
+    public CrashesCommand(CrashesUploadCommand crashesUploadCommand)
+        : base("crashes", "Send the crash dumps the winapp-runtime crate wrote for the installed package, and the symbols to read them, to the crash reporting service of winapp.yaml")
+    {
+        Subcommands.Add(crashesUploadCommand);
+    }
+}
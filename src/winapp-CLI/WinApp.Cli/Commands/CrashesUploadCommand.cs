@@ -0,0 +1,69 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class CrashesUploadCommand : Command
+{
+    public static Option<DirectoryInfo?> SymbolsOption { get; }
+    public static Option<bool> KeepOption { get; }
+
+    static CrashesUploadCommand()
+    {
+        SymbolsOption = new Option<DirectoryInfo?>("--symbols")
+        {
+            Description = "Build output folder whose .pdb files, and the binaries next to them, are sent to the symbols endpoint first"
+        };
+        SymbolsOption.AcceptExistingOnly();
+        KeepOption = new Option<bool>("--keep")
+        {
+            Description = "Leave the uploaded dumps in CrashDumps instead of moving them to CrashDumps\\Uploaded"
+        };
+    }
+
+    public CrashesUploadCommand()
+        : base("upload", "Upload the package's new crash dumps to the endpoint under 'crashes:' in winapp.yaml, with the package name and version")
+    {
+        Options.Add(CrashesCommand.NameOption);
+        Options.Add(CrashesCommand.ManifestOption);
+        Options.Add(SymbolsOption);
+        Options.Add(KeepOption);
+    }
+
+    public class Handler(IAppStateService appStateService, ICrashReportService crashReportService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var name = parseResult.GetValue(CrashesCommand.NameOption);
+            var manifest = parseResult.GetValue(CrashesCommand.ManifestOption);
+            var symbols = parseResult.GetValue(SymbolsOption);
+            var keep = parseResult.GetValue(KeepOption);
+
+            return await statusService.ExecuteWithStatusAsync("Uploading crash dumps...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var dumps = crashReportService.FindDumps(package);
+                    if (dumps.Count == 0 && symbols == null)
+                    {
+                        return (0, $"{UiSymbols.Check} {package.Name} has no new crash dumps");
+                    }
+
+                    var result = await crashReportService.UploadAsync(package, dumps, symbols, keep, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Uploaded {result.DumpCount} crash dump(s) and {result.SymbolFileCount} symbol file(s) of {package.Name} {package.Version}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to upload crash dumps: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Diagnostics;
+using WinApp.Cli.Dev;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class DevCommand : Command
+{
+    public static Argument<DirectoryInfo> InputFolderArgument { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<bool> WatchOption { get; }
+    public static Option<bool> NoLaunchOption { get; }
+    public static Option<string?> ArgsOption { get; }
+
+    /// <summary>
+    /// How long the build output has to stay unchanged before the layout is updated
+    /// </summary>
+    private static readonly TimeSpan QuietPeriod = TimeSpan.FromMilliseconds(500);
+
+    static DevCommand()
+    {
+        InputFolderArgument = new Argument<DirectoryInfo>("input-folder")
+        {
+            Description = "Build output folder to run, such as bin\\Debug\\net10.0-windows10.0.26100.0 or target\\debug",
+            Arity = ArgumentArity.ExactlyOne
+        };
+        InputFolderArgument.AcceptExistingOnly();
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to AppX manifest file (default: auto-detect from input folder or current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        WatchOption = new Option<bool>("--watch", "-w")
+        {
+            Description = "Watch the input folder and the manifest, copy the files that change, register the package again when the manifest changes, and relaunch the app"
+        };
+        NoLaunchOption = new Option<bool>("--no-launch")
+        {
+            Description = "Register the layout without starting the app"
+        };
+        ArgsOption = new Option<string?>("--args")
+        {
+            Description = "Command line arguments to start the app with"
+        };
+    }
+
+    public DevCommand()
+        : base("dev", "Run a build output folder with the debug identity of its manifest, from a copy in .winapp\\dev that '--watch' keeps up to date as you rebuild")
+    {
+        Arguments.Add(InputFolderArgument);
+        Options.Add(ManifestOption);
+        Options.Add(WatchOption);
+        Options.Add(NoLaunchOption);
+        Options.Add(ArgsOption);
+    }
+
+    public class Handler(IDevLoopService devLoopService, IWinappDirectoryService winappDirectoryService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var inputFolder = parseResult.GetRequiredValue(InputFolderArgument);
+            var manifest = parseResult.GetValue(ManifestOption) ?? FindManifest(inputFolder);
+            var watch = parseResult.GetValue(WatchOption);
+            var noLaunch = parseResult.GetValue(NoLaunchOption);
+            var arguments = parseResult.GetValue(ArgsOption);
+
+            DevSession? started = null;
+            var exitCode = await statusService.ExecuteWithStatusAsync("Registering the build output with a debug identity...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    started = await devLoopService.StartAsync(inputFolder, manifest, taskContext, cancellationToken);
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Package} Package: {started.Identity.PackageName}");
+                    taskContext.AddStatusMessage($"{UiSymbols.Id} App ID: {started.Identity.ApplicationId}");
+                    return (0, $"{UiSymbols.Check} Registered {started.Layout.Folder.FullName}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to register the build output: {ex.Message}");
+                }
+            }, cancellationToken);
+            if (exitCode != 0 || started == null)
+            {
+                return exitCode;
+            }
+            var session = started;
+
+            if (!watch)
+            {
+                if (noLaunch)
+                {
+                    return 0;
+                }
+
+                // The app's output streams after the status display, as 'logs tail' does
+                using var app = Launch(session, arguments);
+                try
+                {
+                    await app.WaitForExitAsync(cancellationToken);
+                    return app.ExitCode;
+                }
+                catch (OperationCanceledException) when (cancellationToken.IsCancellationRequested)
+                {
+                    Stop(app);
+                    return 0;
+                }
+            }
+
+            using var watcher = new DevWatcher(inputFolder, manifest, winappDirectoryService.GetLocalWinappDirectory());
+            Console.Out.WriteLine($"{UiSymbols.Search} Watching {inputFolder.FullName} and {manifest.Name}; press Ctrl+C to stop");
+            var running = noLaunch ? null : Launch(session, arguments);
+            try
+            {
+                while (true)
+                {
+                    var manifestChanged = await watcher.WaitForChangesAsync(QuietPeriod, cancellationToken);
+                    if (!manifestChanged && session.Layout.FindChanges().IsEmpty)
+                    {
+                        continue;
+                    }
+
+                    Stop(running);
+                    running?.Dispose();
+                    running = null;
+
+                    exitCode = await statusService.ExecuteWithStatusAsync("Updating the layout...", async (taskContext, cancellationToken) =>
+                    {
+                        try
+                        {
+                            var update = await devLoopService.UpdateAsync(session, manifestChanged, taskContext, cancellationToken);
+                            var reregistered = update.Reregistered ? "; registered the package again" : "";
+                            return (0, $"{UiSymbols.Sync} Copied {update.CopiedCount} and deleted {update.DeletedCount} file(s){reregistered}");
+                        }
+                        catch (Exception ex)
+                        {
+                            taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                            return (1, $"{UiSymbols.Error} Failed to update the layout: {ex.Message}");
+                        }
+                    }, cancellationToken);
+
+                    // A failed update, such as one that raced a build, is tried again on the next change
+                    if (exitCode == 0 && !noLaunch)
+                    {
+                        running = Launch(session, arguments);
+                    }
+                }
+            }
+            catch (OperationCanceledException) when (cancellationToken.IsCancellationRequested)
+            {
+            }
+            finally
+            {
+                Stop(running);
+                running?.Dispose();
+            }
+            return 0;
+        }
+
+        private FileInfo FindManifest(DirectoryInfo inputFolder)
+        {
+            var inputFolderManifest = new FileInfo(Path.Combine(inputFolder.FullName, "appxmanifest.xml"));
+            return inputFolderManifest.Exists
+                ? inputFolderManifest
+                : new FileInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), "appxmanifest.xml"));
+        }
+
+        /// <summary>
+        /// Starts the executable of the layout, which has the debug identity embedded, and copies its output to the console
+        /// </summary>
+        private static Process Launch(DevSession session, string? arguments)
+        {
+            var psi = new ProcessStartInfo
+            {
+                FileName = session.Executable.FullName,
+                Arguments = arguments ?? string.Empty,
+                WorkingDirectory = session.Executable.DirectoryName,
+                UseShellExecute = false,
+                RedirectStandardOutput = true,
+                RedirectStandardError = true,
+            };
+            var app = Process.Start(psi) ?? throw new InvalidOperationException($"Failed to start {session.Executable.Name}");
+            app.OutputDataReceived += (_, e) =>
+            {
+                if (e.Data != null)
+                {
+                    Console.Out.WriteLine(e.Data);
+                }
+            };
+            app.ErrorDataReceived += (_, e) =>
+            {
+                if (e.Data != null)
+                {
+                    Console.Error.WriteLine(e.Data);
+                }
+            };
+            app.Exited += (_, _) => Console.Out.WriteLine($"{UiSymbols.Info} {session.Executable.Name} exited with code {app.ExitCode}");
+            app.EnableRaisingEvents = true;
+            app.BeginOutputReadLine();
+            app.BeginErrorReadLine();
+            Console.Out.WriteLine($"{UiSymbols.Rocket} Started {session.Executable.Name} (PID {app.Id})");
+            return app;
+        }
+
+        private static void Stop(Process? app)
+        {
+            if (app == null)
+            {
+                return;
+            }
+
+            try
+            {
+                if (!app.HasExited)
+                {
+                    // Stopping the app isn't worth reporting as an exit
+                    app.EnableRaisingEvents = false;
+                    app.Kill(entireProcessTree: true);
+                    app.WaitForExit();
+                }
+            }
+            catch (InvalidOperationException)
+            {
+                // The app exited on its own in the meantime
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class DistributeCommand : Command
+{
+    public DistributeCommand(DistributeWebCommand distributeWebCommand, DistributeVerifyCommand distributeVerifyCommand)
+        : base("distribute", "Distribute packages outside the Microsoft Store")
+    {
+        Subcommands.Add(distributeWebCommand);
+        Subcommands.Add(distributeVerifyCommand);
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Security.Cryptography;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class DistributeVerifyCommand : Command
+{
+    public static Argument<string> AppInstallerArgument { get; }
+    public static Option<string> SignerThumbprintOption { get; }
+    public static Option<bool> SkipPackagesOption { get; }
+
+    static DistributeVerifyCommand()
+    {
+        AppInstallerArgument = new Argument<string>("appinstaller")
+        {
+            Description = "URL or path of the .appinstaller file of a site published with 'winapp distribute web --sign-feed'"
+        };
+        SignerThumbprintOption = new Option<string>("--signer-thumbprint")
+        {
+            Description = "SHA-1 thumbprint the manifest signer must have (default: accept a signer whose subject is the package publisher)"
+        };
+        SkipPackagesOption = new Option<bool>("--skip-packages")
+        {
+            Description = "Check the .appinstaller file, page and certificate only, without downloading the packages"
+        };
+    }
+
+    public DistributeVerifyCommand()
+        : base("verify", "Check the signed integrity manifest of a distribution site against the files it serves")
+    {
+        Arguments.Add(AppInstallerArgument);
+        Options.Add(SignerThumbprintOption);
+        Options.Add(SkipPackagesOption);
+    }
+
+    public class Handler(IDistributionService distributionService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var appInstaller = parseResult.GetRequiredValue(AppInstallerArgument);
+            var signerThumbprint = parseResult.GetValue(SignerThumbprintOption);
+            var skipPackages = parseResult.GetValue(SkipPackagesOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Verifying {appInstaller}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var result = await distributionService.VerifyFeedAsync(appInstaller, signerThumbprint, !skipPackages, taskContext, cancellationToken);
+                    taskContext.AddStatusMessage($"{UiSymbols.Lock} Manifest of {result.Manifest.PackageName} {result.Manifest.Version} signed by {result.Signer.Subject} ({result.Signer.Thumbprint})");
+                    foreach (var file in result.Files)
+                    {
+                        taskContext.AddStatusMessage(file.Skipped
+                            ? $"{UiSymbols.Skip} {file.Name}: skipped"
+                            : file.Problem == null
+                                ? $"{UiSymbols.Check} {file.Name}"
+                                : $"{UiSymbols.Error} {file.Name}: {file.Problem}");
+                    }
+
+                    return result.Succeeded
+                        ? (0, $"{result.Files.Count(f => !f.Skipped)} files match the signed manifest")
+                        : (1, $"{UiSymbols.Error} {result.Files.Count(f => f.Problem != null)} files do not match the signed manifest; the site may have been tampered with");
+                }
+                catch (CryptographicException ex)
+                {
+                    return (1, $"{UiSymbols.Error} The integrity manifest signature is not valid: {ex.Message}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to verify {appInstaller}: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
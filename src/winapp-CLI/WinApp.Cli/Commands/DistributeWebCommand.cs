@@ -0,0 +1,236 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class DistributeWebCommand : Command
+{
+    public static Argument<FileInfo> PackagePathArgument { get; }
+    public static Option<Uri> BaseUrlOption { get; }
+    public static Option<DirectoryInfo> OutputOption { get; }
+    public static Option<string> TitleOption { get; }
+    public static Option<int> UpdateCheckHoursOption { get; }
+    public static Option<FileInfo> ReleaseNotesOption { get; }
+    public static Option<FileInfo> AppInstallerTemplateOption { get; }
+    public static Option<FileInfo> SignFeedOption { get; }
+    public static Option<string> SignFeedPasswordOption { get; }
+    public static Option<Uri> BlobContainerOption { get; }
+    public static Option<Uri> S3BucketOption { get; }
+    public static Option<string> S3RegionOption { get; }
+    public static Option<string> GitHubReleaseOption { get; }
+    public static Option<string> GitHubRepoOption { get; }
+    public static Option<bool> GitHubPagesOption { get; }
+    public static Option<string> PagesRemoteOption { get; }
+    public static Option<string> PagesBranchOption { get; }
+
+    static DistributeWebCommand()
+    {
+        PackagePathArgument = new Argument<FileInfo>("package-path")
+        {
+            Description = "Signed .msix, .msixbundle, .appx or .appxbundle to distribute"
+        };
+        PackagePathArgument.AcceptExistingOnly();
+        BaseUrlOption = new Option<Uri>("--base-url")
+        {
+            Description = "HTTPS URL the site will be served from, e.g. https://contoso.github.io/myapp/ or a CDN endpoint (default with --blob-container, --s3-bucket or --github-release: the URL of the uploaded files)"
+        };
+        OutputOption = new Option<DirectoryInfo>("--output")
+        {
+            Description = "Folder to write the site to (default: dist-web in the current directory)"
+        };
+        TitleOption = new Option<string>("--title")
+        {
+            Description = "Page heading, which may use placeholders such as {{version}} (default: the app's display name)"
+        };
+        UpdateCheckHoursOption = new Option<int>("--update-check-hours")
+        {
+            Description = "Hours between update checks when the app is launched, 0 to 255 (default: 0, every launch)"
+        };
+        ReleaseNotesOption = new Option<FileInfo>("--release-notes")
+        {
+            Description = "Markdown or text file with what's new in this version to show on the page, e.g. from 'winapp changelog'"
+        };
+        ReleaseNotesOption.AcceptExistingOnly();
+        AppInstallerTemplateOption = new Option<FileInfo>("--appinstaller-template")
+        {
+            Description = ".appinstaller file to write instead of the generated one, with placeholders such as {{package.uri}} and {{version}}"
+        };
+        AppInstallerTemplateOption.AcceptExistingOnly();
+        SignFeedOption = new Option<FileInfo>("--sign-feed")
+        {
+            Description = "Certificate (PFX) to sign an integrity manifest of the site with, so 'winapp distribute verify' and apps can detect tampered files; usually the package's certificate"
+        };
+        SignFeedOption.AcceptExistingOnly();
+        SignFeedPasswordOption = new Option<string>("--sign-feed-password")
+        {
+            Description = "Password of the --sign-feed certificate",
+            DefaultValueFactory = (argumentResult) => "password"
+        };
+        BlobContainerOption = new Option<Uri>("--blob-container")
+        {
+            Description = "Azure Storage container URL with a SAS token to upload the site to"
+        };
+        S3BucketOption = new Option<Uri>("--s3-bucket")
+        {
+            Description = "S3 location to upload the site to, as s3://bucket or s3://bucket/prefix; credentials come from AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY and AWS_SESSION_TOKEN"
+        };
+        S3RegionOption = new Option<string>("--s3-region")
+        {
+            Description = "AWS region of the bucket (default: AWS_REGION)"
+        };
+        GitHubReleaseOption = new Option<string>("--github-release")
+        {
+            Description = "Tag of the GitHub release to attach the .appinstaller file and package to; the release is created when missing. The token comes from GITHUB_TOKEN or GH_TOKEN"
+        };
+        GitHubRepoOption = new Option<string>("--github-repo")
+        {
+            Description = "Repository of --github-release as owner/name (default: GITHUB_REPOSITORY)"
+        };
+        GitHubPagesOption = new Option<bool>("--github-pages")
+        {
+            Description = "Push the site to the GitHub Pages branch of the current repository"
+        };
+        PagesRemoteOption = new Option<string>("--pages-remote")
+        {
+            Description = "Git remote name or URL to push the GitHub Pages branch to",
+            DefaultValueFactory = (argumentResult) => "origin"
+        };
+        PagesBranchOption = new Option<string>("--pages-branch")
+        {
+            Description = "GitHub Pages branch; its content is replaced",
+            DefaultValueFactory = (argumentResult) => "gh-pages"
+        };
+    }
+
+    public DistributeWebCommand()
+        : base("web", "Generate a static install page with an .appinstaller file for a package, and optionally publish it")
+    {
+        Arguments.Add(PackagePathArgument);
+        Options.Add(BaseUrlOption);
+        Options.Add(OutputOption);
+        Options.Add(TitleOption);
+        Options.Add(UpdateCheckHoursOption);
+        Options.Add(ReleaseNotesOption);
+        Options.Add(AppInstallerTemplateOption);
+        Options.Add(SignFeedOption);
+        Options.Add(SignFeedPasswordOption);
+        Options.Add(BlobContainerOption);
+        Options.Add(S3BucketOption);
+        Options.Add(S3RegionOption);
+        Options.Add(GitHubReleaseOption);
+        Options.Add(GitHubRepoOption);
+        Options.Add(GitHubPagesOption);
+        Options.Add(PagesRemoteOption);
+        Options.Add(PagesBranchOption);
+    }
+
+    public class Handler(IDistributionService distributionService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var packagePath = parseResult.GetRequiredValue(PackagePathArgument);
+            var blobContainer = parseResult.GetValue(BlobContainerOption);
+            var s3Bucket = parseResult.GetValue(S3BucketOption);
+            var gitHubRelease = parseResult.GetValue(GitHubReleaseOption);
+            var gitHubPages = parseResult.GetValue(GitHubPagesOption);
+            var output = parseResult.GetValue(OutputOption)
+                ?? new DirectoryInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), "dist-web"));
+
+            return await statusService.ExecuteWithStatusAsync($"Building the distribution site for {packagePath.Name}...", async (taskContext, cancellationToken) =>
+            {
+                if (new[] { blobContainer != null, s3Bucket != null, gitHubRelease != null, gitHubPages }.Count(selected => selected) > 1)
+                {
+                    return (1, $"{UiSymbols.Error} Use only one of --blob-container, --s3-bucket, --github-release and --github-pages");
+                }
+
+                try
+                {
+                    IDistributionPublishTarget? target = null;
+                    if (blobContainer != null)
+                    {
+                        target = distributionService.CreateAzureBlobTarget(blobContainer);
+                    }
+                    else if (s3Bucket != null)
+                    {
+                        target = distributionService.CreateS3Target(s3Bucket, parseResult.GetValue(S3RegionOption));
+                    }
+                    else if (gitHubRelease != null)
+                    {
+                        target = distributionService.CreateGitHubReleasesTarget(parseResult.GetValue(GitHubRepoOption), gitHubRelease);
+                    }
+                    else if (gitHubPages)
+                    {
+                        target = distributionService.CreateGitHubPagesTarget(parseResult.GetRequiredValue(PagesRemoteOption), parseResult.GetRequiredValue(PagesBranchOption));
+                    }
+
+                    var baseUrl = parseResult.GetValue(BaseUrlOption) ?? target?.DefaultBaseUri;
+                    if (baseUrl == null || !baseUrl.IsAbsoluteUri)
+                    {
+                        return (1, $"{UiSymbols.Error} --base-url is required: the .appinstaller file must contain the absolute URLs the site is served from");
+                    }
+
+                    var releaseNotesFile = parseResult.GetValue(ReleaseNotesOption);
+                    var appInstallerTemplateFile = parseResult.GetValue(AppInstallerTemplateOption);
+                    var options = new DistributionWebOptions
+                    {
+                        BaseUri = baseUrl,
+                        Title = parseResult.GetValue(TitleOption),
+                        HoursBetweenUpdateChecks = parseResult.GetValue(UpdateCheckHoursOption),
+                        GitHubPages = gitHubPages,
+                        ReleaseNotes = releaseNotesFile != null ? await File.ReadAllTextAsync(releaseNotesFile.FullName, cancellationToken) : null,
+                        AppInstallerTemplate = appInstallerTemplateFile != null ? await File.ReadAllTextAsync(appInstallerTemplateFile.FullName, cancellationToken) : null,
+                        FeedSigningCertificate = parseResult.GetValue(SignFeedOption),
+                        FeedSigningCertificatePassword = parseResult.GetValue(SignFeedPasswordOption),
+                    };
+                    var site = await distributionService.BuildWebSiteAsync(packagePath, output, options, taskContext, cancellationToken);
+                    taskContext.AddStatusMessage($"{UiSymbols.Save} Wrote {site.Files.Count} files to {site.OutputDirectory.FullName}");
+
+                    if (target == null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Serve .appinstaller files as {AppInstallerFile.ContentType} and packages as application/msix (or application/msixbundle); most broken install links come from servers that send them as application/octet-stream or text/plain");
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Serve the .appinstaller file with 'Cache-Control: {DistributionCacheControl.Revalidate}' so installed apps see updates as soon as they are published");
+                        return (0, $"Site is ready in {site.OutputDirectory.FullName}; host it at {site.PageUri}");
+                    }
+
+                    taskContext.UpdateSubStatus($"Publishing to {target.Name}");
+                    var mismatches = await distributionService.PublishAsync(site, target, taskContext, cancellationToken);
+                    foreach (var mismatch in mismatches)
+                    {
+                        taskContext.AddStatusMessage(mismatch.Actual == null
+                            ? $"{UiSymbols.Warning} {mismatch.Uri} could not be downloaded; App Installer needs anonymous read access to it"
+                            : $"{UiSymbols.Warning} {mismatch.Uri} is served as {mismatch.Actual} instead of {mismatch.Expected}; check the content type rules of your CDN or server");
+                    }
+
+                    if (!target.SupportsCacheControl)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} {target.Name} sets its own caching headers, so installed apps may see a new version a few minutes after it is published");
+                    }
+                    else if (parseResult.GetValue(BaseUrlOption) != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Files were uploaded with Cache-Control headers; make sure your CDN honors the origin's Cache-Control instead of overriding it");
+                    }
+
+                    if (target.HostsPage)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Windows disables ms-appinstaller: links by default, so the page's main button downloads the .appinstaller file instead; users open it to install");
+                    }
+
+                    return (0, target.HostsPage
+                        ? $"Published {site.PageUri}"
+                        : $"Published {site.AppInstallerUri}; {target.Name} does not host index.html, so link to the .appinstaller file directly");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to distribute {packagePath.Name}: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
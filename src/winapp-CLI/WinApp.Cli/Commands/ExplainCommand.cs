@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class ExplainCommand : Command
+{
+    public ExplainCommand(ExplainManifestCommand manifestCommand)
+        : base("explain", "Explain project files, with what winapp's checks find in them")
+    {
+        Subcommands.Add(manifestCommand);
+    }
+}
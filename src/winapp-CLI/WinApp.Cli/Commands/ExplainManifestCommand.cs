@@ -0,0 +1,89 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ExplainManifestCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+    public static Option<bool> NoChecksOption { get; }
+
+    static ExplainManifestCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to explain (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "File to write the annotated manifest to (default: print it)"
+        };
+        NoChecksOption = new Option<bool>("--no-checks")
+        {
+            Description = "Only explain the elements and the Windows releases they need, without running the analyze checks on the assets"
+        };
+    }
+
+    public ExplainManifestCommand()
+        : base("manifest", "Print the manifest with a comment before each element explaining what it does, which Windows releases need it and what the analyze checks find in it")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(OutputOption);
+        Options.Add(NoChecksOption);
+    }
+
+    public class Handler(IManifestExplainService manifestExplainService, IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var output = parseResult.GetValue(OutputOption);
+            var runChecks = !parseResult.GetValue(NoChecksOption);
+
+            return await statusService.ExecuteWithStatusAsync("Explaining the manifest...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var splash = configService.Exists() ? configService.Load().Splash : null;
+                    var explanation = await manifestExplainService.ExplainAsync(manifest, splash, runChecks, allowScriptAnalyzers: false, taskContext, cancellationToken);
+                    if (output != null)
+                    {
+                        output.Directory?.Create();
+                        await File.WriteAllTextAsync(output.FullName, explanation.Text, cancellationToken);
+                        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote the annotated manifest to {output.FullName}");
+                    }
+                    else
+                    {
+                        foreach (var line in explanation.Text.Split('\n'))
+                        {
+                            taskContext.AddStatusMessage(line.TrimEnd('\r'));
+                        }
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{explanation.Notes.Count(n => n.Severity == s)} {s.ToString().ToLowerInvariant()}(s)"));
+                    return (0, $"Explained {explanation.Elements} element(s), with {counts}{(output != null ? $", saved to {output.FullName}" : "")}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to explain the manifest: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
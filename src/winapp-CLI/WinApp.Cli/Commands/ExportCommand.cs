@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class ExportCommand : Command
+{
+    public ExportCommand(ExportWapprojCommand wapprojCommand)
+        : base("export", "Write the project's configuration in the format of another packaging tool")
+    {
+        Subcommands.Add(wapprojCommand);
+    }
+}
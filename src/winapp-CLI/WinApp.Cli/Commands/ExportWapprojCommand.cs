@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ExportWapprojCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<DirectoryInfo> OutputOption { get; }
+    public static Option<bool> ForceOption { get; }
+
+    static ExportWapprojCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to export (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        OutputOption = new Option<DirectoryInfo>("--output")
+        {
+            Description = "Folder to write the packaging project to (default: <name>.Package next to winapp.yaml)"
+        };
+        ForceOption = new Option<bool>("--force")
+        {
+            Description = "Overwrite an existing .wapproj"
+        };
+    }
+
+    public ExportWapprojCommand()
+        : base("wapproj", "Generate a Visual Studio Windows Application Packaging Project (.wapproj) equivalent to winapp.yaml and the manifest, for Visual Studio workflows such as Store association")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(OutputOption);
+        Options.Add(ForceOption);
+    }
+
+    public class Handler(IWapprojExportService wapprojExportService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var output = parseResult.GetValue(OutputOption);
+            var force = parseResult.GetValue(ForceOption);
+
+            return await statusService.ExecuteWithStatusAsync("Exporting a packaging project...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var export = await wapprojExportService.ExportAsync(manifest, output, force, taskContext, cancellationToken);
+                    foreach (var item in export.Exported)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Check} {item}");
+                    }
+                    foreach (var item in export.Manual)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {item}");
+                    }
+
+                    return (0, $"{UiSymbols.Check} Exported {manifest.Name} to {export.Project.FullName}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to export a packaging project: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 using System.CommandLine;
 using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
 using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Commands;
@@ -17,6 +18,7 @@ internal class InitCommand : Command
     public static Option<bool> UseDefaults { get; }
     public static Option<bool> NoCertOption { get; }
     public static Option<bool> ConfigOnlyOption { get; }
+    public static Option<string> StoreOption { get; }
 
     static InitCommand()
     {
@@ -56,6 +58,11 @@ internal class InitCommand : Command
         {
             Description = "Only handle configuration file operations (create if missing, validate if exists). Skip package installation, certificate generation, and other workspace setup steps."
         };
+        StoreOption = new Option<string>("--store")
+        {
+            Description = "Reserve this name in Partner Center (or use the account's app that has it) and associate the manifest with it, as 'winapp store names reserve' and 'winapp store associate' do",
+            HelpName = "name"
+        };
     }
 
     public InitCommand() : base("init", "Initializes a directory with required assets (manifest, certs, libraries) for building a modern Windows app. ")
@@ -68,9 +75,12 @@ internal class InitCommand : Command
         Options.Add(UseDefaults);
         Options.Add(NoCertOption);
         Options.Add(ConfigOnlyOption);
+        Options.Add(StoreOption);
+        Options.Add(RestoreCommand.AcceptLicensesOption);
+        Options.Add(RestoreCommand.AcceptLicenseOption);
     }
 
-    public class Handler(IWorkspaceSetupService workspaceSetupService, ICurrentDirectoryProvider currentDirectoryProvider) : AsynchronousCommandLineAction
+    public class Handler(IWorkspaceSetupService workspaceSetupService, IStoreNameService storeNameService, IStoreAssociationService storeAssociationService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
     {
         public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
         {
@@ -82,6 +92,7 @@ internal class InitCommand : Command
             var useDefaults = parseResult.GetValue(UseDefaults);
             var noCert = parseResult.GetValue(NoCertOption);
             var configOnly = parseResult.GetValue(ConfigOnlyOption);
+            var storeName = parseResult.GetValue(StoreOption);
 
             var options = new WorkspaceSetupOptions
             {
@@ -94,10 +105,38 @@ internal class InitCommand : Command
                 RequireExistingConfig = false,
                 ForceLatestBuildTools = true,
                 NoCert = noCert,
-                ConfigOnly = configOnly
+                ConfigOnly = configOnly,
+                LicenseConsent = RestoreCommand.GetLicenseConsent(parseResult, prompt: !useDefaults)
             };
 
-            return await workspaceSetupService.SetupWorkspaceAsync(options, cancellationToken);
+            var exitCode = await workspaceSetupService.SetupWorkspaceAsync(options, cancellationToken);
+            if (exitCode != 0 || storeName == null)
+            {
+                return exitCode;
+            }
+
+            return await statusService.ExecuteWithStatusAsync($"Associating with '{storeName}' in the Store...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var (application, reserved) = await storeNameService.ReserveAsync(storeName, taskContext, cancellationToken);
+                    taskContext.AddStatusMessage($"{UiSymbols.Check} {(reserved ? "Reserved" : "Using the reserved")} {StoreNamesCommand.Describe(application)}");
+
+                    var manifest = MsixService.FindProjectManifest(currentDirectoryProvider, options.BaseDirectory);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml to associate; generate one with 'winapp manifest generate', then run 'winapp store associate --app-id {application.ApplicationId}'");
+                    }
+
+                    await storeAssociationService.AssociateAsync(manifest, application.ApplicationId, null, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Associated {manifest.Name} with {application.PrimaryName} ({application.ApplicationId})");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to associate with the Store: {ex.Message}");
+                }
+            }, cancellationToken);
         }
     }
 }
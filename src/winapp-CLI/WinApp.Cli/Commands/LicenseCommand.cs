@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class LicenseCommand : Command
+{
+    public LicenseCommand(LicenseKeygenCommand keygenCommand, LicenseIssueCommand issueCommand, LicenseVerifyCommand verifyCommand, LicenseDeviceIdCommand deviceIdCommand)
+        : base("license", "Issue and check device-bound license files for apps distributed outside the Store")
+    {
+        Subcommands.Add(keygenCommand);
+        Subcommands.Add(issueCommand);
+        Subcommands.Add(verifyCommand);
+        Subcommands.Add(deviceIdCommand);
+    }
+}
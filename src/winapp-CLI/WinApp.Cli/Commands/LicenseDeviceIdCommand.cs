@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Extensions.Logging;
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LicenseDeviceIdCommand : Command
+{
+    public LicenseDeviceIdCommand()
+        : base("device-id", "Print the device ID of this machine, to request a license bound to it")
+    {
+    }
+
+    public class Handler(ILicenseService licenseService, ILogger<LicenseDeviceIdCommand> logger) : AsynchronousCommandLineAction
+    {
+        public override Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            try
+            {
+                // Output just the ID for easy consumption by scripts
+                logger.LogInformation("{DeviceId}", licenseService.GetDeviceId());
+                return Task.FromResult(0);
+            }
+            catch (Exception ex)
+            {
+                logger.LogError("{UISymbol} Error reading the device ID: {ErrorMessage}", UiSymbols.Error, ex.Message);
+                return Task.FromResult(1);
+            }
+        }
+    }
+}
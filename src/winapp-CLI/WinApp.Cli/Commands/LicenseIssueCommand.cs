@@ -0,0 +1,144 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Licensing;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LicenseIssueCommand : Command
+{
+    public static Option<FileInfo> KeyOption { get; }
+    public static Option<string> KeyPasswordOption { get; }
+    public static Option<string> LicenseeOption { get; }
+    public static Option<string> DeviceIdOption { get; }
+    public static Option<string> PackageFamilyNameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<DateTimeOffset?> ExpiresOption { get; }
+    public static Option<string[]> FeatureOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static LicenseIssueCommand()
+    {
+        KeyOption = new Option<FileInfo>("--key")
+        {
+            Description = $"Private key from 'winapp license keygen' (default: {DeviceLicense.DefaultKeyFileName})"
+        };
+        KeyPasswordOption = new Option<string>("--key-password")
+        {
+            Description = "Password of an encrypted private key"
+        };
+        LicenseeOption = new Option<string>("--licensee")
+        {
+            Description = "Customer the license is issued to",
+            Required = true
+        };
+        DeviceIdOption = new Option<string>("--device-id")
+        {
+            Description = "Bind the license to a device, by the ID 'winapp license device-id' prints on it"
+        };
+        PackageFamilyNameOption = new Option<string>("--package-family-name")
+        {
+            Description = "Bind the license to a package family, e.g. Contoso.Tools_8wekyb3d8bbwe"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Bind the license to the package family of this appxmanifest.xml"
+        };
+        ManifestOption.AcceptExistingOnly();
+        ExpiresOption = new Option<DateTimeOffset?>("--expires")
+        {
+            Description = "Date and time the license expires, e.g. 2027-12-31 (default: never)"
+        };
+        FeatureOption = new Option<string[]>("--feature")
+        {
+            Description = "Feature(s) the license enables, for the app to check",
+            AllowMultipleArgumentsPerToken = true
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Path of the license file",
+            Required = true
+        };
+        OutputOption.AcceptLegalFilePathsOnly();
+    }
+
+    public LicenseIssueCommand()
+        : base("issue", "Sign a license bound to a device, a package family, or both")
+    {
+        Options.Add(KeyOption);
+        Options.Add(KeyPasswordOption);
+        Options.Add(LicenseeOption);
+        Options.Add(DeviceIdOption);
+        Options.Add(PackageFamilyNameOption);
+        Options.Add(ManifestOption);
+        Options.Add(ExpiresOption);
+        Options.Add(FeatureOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(ILicenseService licenseService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var key = parseResult.GetValue(KeyOption) ?? new FileInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), DeviceLicense.DefaultKeyFileName));
+            var keyPassword = parseResult.GetValue(KeyPasswordOption);
+            var licensee = parseResult.GetRequiredValue(LicenseeOption);
+            var deviceId = parseResult.GetValue(DeviceIdOption);
+            var packageFamilyName = parseResult.GetValue(PackageFamilyNameOption);
+            var manifest = parseResult.GetValue(ManifestOption);
+            var expires = parseResult.GetValue(ExpiresOption);
+            var features = parseResult.GetValue(FeatureOption) ?? [];
+            var output = parseResult.GetRequiredValue(OutputOption);
+
+            return await statusService.ExecuteWithStatusAsync("Issuing license...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (!key.Exists)
+                    {
+                        return (1, $"{UiSymbols.Error} Private key not found: {key}. Run 'winapp license keygen' first or pass --key");
+                    }
+
+                    if (manifest != null)
+                    {
+                        if (packageFamilyName != null)
+                        {
+                            return (1, $"{UiSymbols.Error} Pass either --package-family-name or --manifest, not both");
+                        }
+
+                        var identity = await MsixService.ParseAppxManifestFromPathAsync(manifest, cancellationToken);
+                        packageFamilyName = DeviceLicense.GetPackageFamilyName(identity.PackageName, identity.Publisher);
+                    }
+
+                    if (deviceId == null && packageFamilyName == null)
+                    {
+                        return (1, $"{UiSymbols.Error} Bind the license with --device-id, --package-family-name or --manifest");
+                    }
+
+                    var claims = new LicenseClaims(licensee, deviceId, packageFamilyName, DateTimeOffset.UtcNow, expires, features);
+                    await licenseService.IssueAsync(claims, key, keyPassword, output, cancellationToken);
+
+                    if (deviceId != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Id} Device: {deviceId.ToUpperInvariant()}");
+                    }
+                    if (packageFamilyName != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Package} Package family: {packageFamilyName}");
+                    }
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} {(expires is DateTimeOffset date ? $"Expires {date:u}" : "Does not expire")}");
+                    return (0, $"{UiSymbols.Check} License for {licensee} written to {output}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to issue the license: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Licensing;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LicenseKeygenCommand : Command
+{
+    public static Option<FileInfo> OutputOption { get; }
+    public static Option<string> PasswordOption { get; }
+    public static Option<IfExists> IfExistsOption { get; }
+
+    static LicenseKeygenCommand()
+    {
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = $"Path of the private key; the public key is written next to it as <name>{DeviceLicense.PublicKeySuffix} (default: {DeviceLicense.DefaultKeyFileName})"
+        };
+        OutputOption.AcceptLegalFilePathsOnly();
+        PasswordOption = new Option<string>("--password")
+        {
+            Description = "Encrypt the private key with this password"
+        };
+        IfExistsOption = new Option<IfExists>("--if-exists")
+        {
+            Description = "What to do when the private key already exists",
+            DefaultValueFactory = (argumentResult) => IfExists.Error,
+        };
+    }
+
+    public LicenseKeygenCommand()
+        : base("keygen", "Generate the key pair that signs licenses")
+    {
+        Options.Add(OutputOption);
+        Options.Add(PasswordOption);
+        Options.Add(IfExistsOption);
+    }
+
+    public class Handler(ILicenseService licenseService, IGitignoreService gitignoreService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var output = parseResult.GetValue(OutputOption) ?? new FileInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), DeviceLicense.DefaultKeyFileName));
+            var password = parseResult.GetValue(PasswordOption);
+            var ifExists = parseResult.GetRequiredValue(IfExistsOption);
+
+            return await statusService.ExecuteWithStatusAsync("Generating license signing key...", async (taskContext, cancellationToken) =>
+            {
+                if (output.Exists)
+                {
+                    if (ifExists == IfExists.Error)
+                    {
+                        return (1, $"{UiSymbols.Error} {output} already exists; licenses issued with it would no longer verify with a new key. Pass --if-exists overwrite to replace it");
+                    }
+                    if (ifExists == IfExists.Skip)
+                    {
+                        return (0, $"{UiSymbols.Skip} {output} already exists");
+                    }
+                }
+
+                try
+                {
+                    var publicKey = await licenseService.GenerateKeyAsync(output, password, cancellationToken);
+                    taskContext.AddStatusMessage($"{UiSymbols.Lock} Private key: {output}");
+                    taskContext.AddStatusMessage($"{UiSymbols.Files} Public key: {publicKey}");
+                    if (await gitignoreService.AddPrivateKeyToGitignoreAsync(output.Directory!, output.Name, taskContext, cancellationToken))
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Check} Added {output.Name} to .gitignore");
+                    }
+                    if (string.IsNullOrEmpty(password))
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} The private key isn't encrypted; pass --password to encrypt it");
+                    }
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} Keep the private key out of source control; ship the public key with the app to verify licenses");
+                    return (0, "License signing key generated");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to generate the license signing key: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Licensing;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LicenseVerifyCommand : Command
+{
+    public static Argument<FileInfo> LicenseArgument { get; }
+    public static Option<FileInfo> PublicKeyOption { get; }
+    public static Option<string> DeviceIdOption { get; }
+    public static Option<string> PackageFamilyNameOption { get; }
+
+    static LicenseVerifyCommand()
+    {
+        LicenseArgument = new Argument<FileInfo>("license")
+        {
+            Description = "License file to check"
+        };
+        LicenseArgument.AcceptExistingOnly();
+        PublicKeyOption = new Option<FileInfo>("--public-key")
+        {
+            Description = $"Public key from 'winapp license keygen' (default: {Path.ChangeExtension(DeviceLicense.DefaultKeyFileName, null)}{DeviceLicense.PublicKeySuffix})"
+        };
+        DeviceIdOption = new Option<string>("--device-id")
+        {
+            Description = "Device to check a device-bound license against (default: this device)"
+        };
+        PackageFamilyNameOption = new Option<string>("--package-family-name")
+        {
+            Description = "Package family to check a package-bound license against"
+        };
+    }
+
+    public LicenseVerifyCommand()
+        : base("verify", "Check a license's signature, expiry and binding offline, as the app would")
+    {
+        Arguments.Add(LicenseArgument);
+        Options.Add(PublicKeyOption);
+        Options.Add(DeviceIdOption);
+        Options.Add(PackageFamilyNameOption);
+    }
+
+    public class Handler(ILicenseService licenseService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var license = parseResult.GetRequiredValue(LicenseArgument);
+            var publicKey = parseResult.GetValue(PublicKeyOption)
+                ?? new FileInfo(DeviceLicense.GetPublicKeyPath(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), DeviceLicense.DefaultKeyFileName)));
+            var deviceId = parseResult.GetValue(DeviceIdOption);
+            var packageFamilyName = parseResult.GetValue(PackageFamilyNameOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Verifying {license.Name}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    if (!publicKey.Exists)
+                    {
+                        return (1, $"{UiSymbols.Error} Public key not found: {publicKey}. Pass --public-key");
+                    }
+
+                    var check = await licenseService.VerifyAsync(license, publicKey, deviceId, packageFamilyName, cancellationToken);
+                    var claims = check.Claims;
+                    taskContext.AddStatusMessage($"{UiSymbols.User} Licensee: {claims.Licensee}");
+                    if (claims.DeviceId != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Id} Device: {claims.DeviceId}");
+                    }
+                    if (claims.PackageFamilyName != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Package} Package family: {claims.PackageFamilyName}");
+                    }
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} Issued {claims.Issued:u}, {(claims.Expires is DateTimeOffset expires ? $"expires {expires:u}" : "does not expire")}");
+                    if (claims.Features.Count > 0)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Bullet} Features: {string.Join(", ", claims.Features)}");
+                    }
+
+                    return check.IsValid
+                        ? (0, $"{UiSymbols.Check} The license is valid")
+                        : (1, $"{UiSymbols.Error} The license is signed correctly, but {check.Problem}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to verify the license: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ListCommand : Command
+{
+    public static Option<bool> InstalledOption { get; }
+    public static Option<string?> NameOption { get; }
+    public static Option<string?> PublisherOption { get; }
+    public static Option<PackageRegistrationKind?> KindOption { get; }
+    public static Option<bool> AllOption { get; }
+    public static Option<InstalledPackageFormat> FormatOption { get; }
+    public static Option<bool> OpenInstallOption { get; }
+    public static Option<bool> OpenDataOption { get; }
+    public static Option<bool> UninstallOption { get; }
+
+    static ListCommand()
+    {
+        InstalledOption = new Option<bool>("--installed")
+        {
+            Description = "List the packages installed or staged on this machine"
+        };
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name pattern, e.g. 'Contoso.*' (wildcards * and ?)"
+        };
+        PublisherOption = new Option<string?>("--publisher")
+        {
+            Description = "Publisher pattern, matched against the common name unless it contains '=', e.g. 'Contoso*' or 'CN=Contoso, O=*'"
+        };
+        KindOption = new Option<PackageRegistrationKind?>("--kind")
+        {
+            Description = "Only list packages installed from the Store, sideloaded, registered in development mode, or part of Windows"
+        };
+        AllOption = new Option<bool>("--all")
+        {
+            Description = "Include framework packages and packages that are part of Windows"
+        };
+        FormatOption = new Option<InstalledPackageFormat>("--format")
+        {
+            Description = "Output format",
+            DefaultValueFactory = (argumentResult) => InstalledPackageFormat.Text
+        };
+        OpenInstallOption = new Option<bool>("--open-install")
+        {
+            Description = "Open the install folder of the matching package in File Explorer"
+        };
+        OpenDataOption = new Option<bool>("--open-data")
+        {
+            Description = "Open the app data folder (LocalState, RoamingState, ...) of the matching package in File Explorer"
+        };
+        UninstallOption = new Option<bool>("--uninstall")
+        {
+            Description = "Uninstall the matching package (staged packages need an elevated prompt)"
+        };
+    }
+
+    public ListCommand()
+        : base("list", "List the packages registered on this machine with their version, kind and folders")
+    {
+        Options.Add(InstalledOption);
+        Options.Add(NameOption);
+        Options.Add(PublisherOption);
+        Options.Add(KindOption);
+        Options.Add(AllOption);
+        Options.Add(FormatOption);
+        Options.Add(OpenInstallOption);
+        Options.Add(OpenDataOption);
+        Options.Add(UninstallOption);
+    }
+
+    public class Handler(IInstalledPackageService installedPackageService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var installed = parseResult.GetValue(InstalledOption);
+            var name = parseResult.GetValue(NameOption);
+            var publisher = parseResult.GetValue(PublisherOption);
+            var kind = parseResult.GetValue(KindOption);
+            var all = parseResult.GetValue(AllOption);
+            var format = parseResult.GetValue(FormatOption);
+            var openInstall = parseResult.GetValue(OpenInstallOption);
+            var openData = parseResult.GetValue(OpenDataOption);
+            var uninstall = parseResult.GetValue(UninstallOption);
+
+            return await statusService.ExecuteWithStatusAsync("Listing packages...", async (taskContext, cancellationToken) =>
+            {
+                if (!installed)
+                {
+                    return (1, $"{UiSymbols.Error} Specify what to list: --installed");
+                }
+
+                try
+                {
+                    var (packages, stagedChecked) = await installedPackageService.GetPackagesAsync(taskContext, cancellationToken);
+                    var matches = InstalledPackageService.Filter(packages, name, publisher, kind, all);
+                    if (!stagedChecked)
+                    {
+                        taskContext.AddDebugMessage("Staged packages can only be listed as administrator");
+                    }
+
+                    if (openInstall || openData || uninstall)
+                    {
+                        return await RunActionAsync(matches, openInstall, openData, uninstall, taskContext, cancellationToken);
+                    }
+
+                    if (format == InstalledPackageFormat.Json)
+                    {
+                        foreach (var line in ToJson(matches).Split('\n'))
+                        {
+                            taskContext.AddStatusMessage(line);
+                        }
+                    }
+                    else
+                    {
+                        foreach (var package in matches)
+                        {
+                            taskContext.AddStatusMessage($"{package.Name} {package.Version} ({Describe(package)})");
+                            taskContext.AddStatusMessage($"    {package.Publisher}");
+                            taskContext.AddStatusMessage($"    {package.ExternalLocation ?? package.InstallLocation}");
+                        }
+                    }
+
+                    return (0, $"{matches.Count} package(s)");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to list packages: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+
+        private async Task<(int, string)> RunActionAsync(List<InstalledPackage> matches, bool openInstall, bool openData, bool uninstall, TaskContext taskContext, CancellationToken cancellationToken)
+        {
+            if (matches.Count != 1)
+            {
+                foreach (var package in matches)
+                {
+                    taskContext.AddStatusMessage(package.FullName);
+                }
+                return (1, matches.Count == 0
+                    ? $"{UiSymbols.Error} No package matches"
+                    : $"{UiSymbols.Error} {matches.Count} packages match; narrow them down to one with --name or --publisher");
+            }
+
+            var match = matches[0];
+            if (openInstall)
+            {
+                installedPackageService.OpenFolder(match.InstallLocation ?? throw new InvalidOperationException($"{match.Name} has no install folder"));
+                taskContext.AddStatusMessage($"{UiSymbols.Folder} Opened {match.InstallLocation}");
+            }
+            if (openData)
+            {
+                installedPackageService.OpenFolder(match.AppDataPath);
+                taskContext.AddStatusMessage($"{UiSymbols.Folder} Opened {match.AppDataPath}");
+            }
+            if (uninstall)
+            {
+                await installedPackageService.UninstallAsync(match.FullName, allUsers: match.Staged, taskContext, cancellationToken);
+                return (0, $"{UiSymbols.Trash} Uninstalled {match.FullName}");
+            }
+
+            return (0, match.FullName);
+        }
+
+        private static string Describe(InstalledPackage package)
+        {
+            var kind = package.Registration.ToString().ToLowerInvariant();
+            return kind
+                + (package.IsFramework ? ", framework" : "")
+                + (package.Staged ? ", staged" : "")
+                + (package.Status.Equals("Ok", StringComparison.OrdinalIgnoreCase) ? "" : $", {package.Status}");
+        }
+
+        private static string ToJson(IEnumerable<InstalledPackage> packages)
+        {
+            var json = new JsonArray(packages.Select(p => (JsonNode?)new JsonObject
+            {
+                ["name"] = p.Name,
+                ["fullName"] = p.FullName,
+                ["familyName"] = p.FamilyName,
+                ["publisher"] = p.Publisher,
+                ["version"] = p.Version,
+                ["kind"] = p.Registration.ToString(),
+                ["installLocation"] = p.InstallLocation,
+                ["externalLocation"] = p.ExternalLocation,
+                ["appDataPath"] = p.AppDataPath,
+                ["framework"] = p.IsFramework,
+                ["staged"] = p.Staged,
+                ["status"] = p.Status,
+            }).ToArray());
+            return json.ToJsonString(new System.Text.Json.JsonSerializerOptions { WriteIndented = true });
+        }
+    }
+}
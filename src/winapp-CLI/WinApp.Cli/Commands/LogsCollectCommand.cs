@@ -0,0 +1,56 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LogsCollectCommand : Command
+{
+    public static Option<FileInfo> OutputOption { get; }
+
+    static LogsCollectCommand()
+    {
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Path of the .zip to write (default: <package name>-logs-<date and time>.zip in the current directory)"
+        };
+    }
+
+    public LogsCollectCommand()
+        : base("collect", "Zip the app's log files, rotated ones included, to attach to a bug report")
+    {
+        Options.Add(OutputOption);
+        Options.Add(LogsCommand.NameOption);
+        Options.Add(LogsCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, ILogsService logsService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var output = parseResult.GetValue(OutputOption);
+            var name = parseResult.GetValue(LogsCommand.NameOption);
+            var manifest = parseResult.GetValue(LogsCommand.ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Collecting the app's logs...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    output ??= new FileInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), $"{package.Name}-logs-{DateTime.Now:yyyyMMdd-HHmmss}.zip"));
+                    var collection = logsService.Collect(package, output);
+                    return (0, $"{UiSymbols.Check} Collected {collection.FileCount} log file(s) of {package.Name} ({collection.Size / 1024.0:0.#} KB) into {output.FullName}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to collect the app's logs: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class LogsCommand : Command
+{
+    public static Option<string?> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+
+    static LogsCommand()
+    {
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name of the installed package (default: the Identity Name of the project's appxmanifest.xml, or its .debug or branch identity)"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml that identifies the package (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+    }
+
+    public LogsCommand(LogsTailCommand logsTailCommand, LogsCollectCommand logsCollectCommand)
+        : base("logs", "Follow and collect the logs the current project's installed package writes to the Logs folder of its ApplicationData")
+    {
+        Subcommands.Add(logsTailCommand);
+        Subcommands.Add(logsCollectCommand);
+    }
+}
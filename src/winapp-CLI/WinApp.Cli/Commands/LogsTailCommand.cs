@@ -0,0 +1,99 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class LogsTailCommand : Command
+{
+    public static Option<int> LinesOption { get; }
+    public static Option<bool> NoFollowOption { get; }
+
+    static LogsTailCommand()
+    {
+        LinesOption = new Option<int>("--lines", "-n")
+        {
+            Description = "Number of lines to print from the end of each log before following it",
+            DefaultValueFactory = (argumentResult) => 20
+        };
+        NoFollowOption = new Option<bool>("--no-follow")
+        {
+            Description = "Print the last lines and exit, instead of printing new lines until Ctrl+C"
+        };
+    }
+
+    public LogsTailCommand()
+        : base("tail", "Print the end of the app's current log files and stream the lines it writes, across log rotation")
+    {
+        Options.Add(LinesOption);
+        Options.Add(NoFollowOption);
+        Options.Add(LogsCommand.NameOption);
+        Options.Add(LogsCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, ILogsService logsService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var lines = parseResult.GetValue(LinesOption);
+            var follow = !parseResult.GetValue(NoFollowOption);
+            var name = parseResult.GetValue(LogsCommand.NameOption);
+            var manifest = parseResult.GetValue(LogsCommand.ManifestOption);
+
+            InstalledPackage? package = null;
+            var exitCode = await statusService.ExecuteWithStatusAsync("Finding the app's logs...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var files = logsService.FindLogFiles(package).Where(LogsService.IsCurrentLog).ToList();
+                    if (files.Count == 0 && !follow)
+                    {
+                        return (1, $"{UiSymbols.Error} {package.Name} has not written any logs yet (looked in {package.AppDataPath})");
+                    }
+                    foreach (var file in files)
+                    {
+                        taskContext.AddDebugMessage($"Log: {file.FullName}");
+                    }
+
+                    return (0, follow
+                        ? $"{UiSymbols.Search} Following {files.Count} log file(s) of {package.Name}; press Ctrl+C to stop"
+                        : $"{UiSymbols.Search} {files.Count} log file(s) of {package.Name}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to find the app's logs: {ex.Message}");
+                }
+            }, cancellationToken);
+            if (exitCode != 0 || package == null)
+            {
+                return exitCode;
+            }
+
+            // Lines stream after the status display, which would redraw over them; a header marks each change of file, as tail does
+            FileInfo? current = null;
+            try
+            {
+                await logsService.TailAsync(package, lines, follow, line =>
+                {
+                    if (current?.FullName != line.File.FullName)
+                    {
+                        current = line.File;
+                        Console.Out.WriteLine($"==> {line.File.FullName} <==");
+                    }
+                    Console.Out.WriteLine(line.Text);
+                }, cancellationToken);
+            }
+            catch (OperationCanceledException) when (cancellationToken.IsCancellationRequested)
+            {
+            }
+            return 0;
+        }
+    }
+}
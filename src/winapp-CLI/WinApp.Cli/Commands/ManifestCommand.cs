@@ -7,10 +7,11 @@ namespace WinApp.Cli.Commands;
 
 internal class ManifestCommand : Command
 {
-    public ManifestCommand(ManifestGenerateCommand manifestGenerateCommand, ManifestUpdateAssetsCommand manifestUpdateAssetsCommand)
+    public ManifestCommand(ManifestGenerateCommand manifestGenerateCommand, ManifestUpdateAssetsCommand manifestUpdateAssetsCommand, ManifestWin32Command manifestWin32Command)
         : base("manifest", "AppxManifest.xml management")
     {
         Subcommands.Add(manifestGenerateCommand);
         Subcommands.Add(manifestUpdateAssetsCommand);
+        Subcommands.Add(manifestWin32Command);
     }
 }
@@ -6,6 +6,7 @@ using System.CommandLine;
 using System.CommandLine.Invocation;
 using WinApp.Cli.Helpers;
 using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
 using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Commands;
@@ -83,7 +84,7 @@ internal class ManifestGenerateCommand : Command
         Options.Add(CertGenerateCommand.IfExistsOption);
     }
 
-    public class Handler(IManifestService manifestService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService, ILogger<ManifestGenerateCommand> logger) : AsynchronousCommandLineAction
+    public class Handler(IManifestService manifestService, IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService, ILogger<ManifestGenerateCommand> logger) : AsynchronousCommandLineAction
     {
         public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
         {
@@ -132,6 +133,15 @@ internal class ManifestGenerateCommand : Command
                         taskContext,
                         cancellationToken);
 
+                    // The Win32 manifest of the executable comes from the same winapp.yaml, next to AppxManifest.xml
+                    if (configService.Exists() && configService.Load().Win32Manifest is { } win32Manifest
+                        && MsixService.FindProjectManifest(currentDirectoryProvider, directory) is { } generatedManifest)
+                    {
+                        var win32ManifestPath = new FileInfo(Path.Combine(directory.FullName, Win32Manifest.FileName));
+                        await Win32Manifest.WriteAsync(win32Manifest, await File.ReadAllTextAsync(generatedManifest.FullName, cancellationToken), win32ManifestPath, cancellationToken);
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Generated {Win32Manifest.FileName} from 'win32Manifest:' in winapp.yaml");
+                    }
+
                     return (0, $"Manifest generated successfully in: {directory}");
                 }
                 catch (Exception ex)
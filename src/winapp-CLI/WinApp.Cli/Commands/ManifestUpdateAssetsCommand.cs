@@ -13,6 +13,8 @@ internal class ManifestUpdateAssetsCommand : Command
 {
     public static Argument<FileInfo> ImageArgument { get; }
     public static Option<FileInfo> ManifestOption { get; }
+    public static Option<string?> BadgeOption { get; }
+    public static Option<string?> BadgeColorOption { get; }
 
     static ManifestUpdateAssetsCommand()
     {
@@ -27,12 +29,24 @@ internal class ManifestUpdateAssetsCommand : Command
             Description = "Path to AppxManifest.xml file (default: search current directory)"
         };
         ManifestOption.AcceptExistingOnly();
+
+        BadgeOption = new Option<string?>("--badge")
+        {
+            Description = "Draw a ribbon with this text, such as BETA or DEV, across the bottom of the generated icons"
+        };
+
+        BadgeColorOption = new Option<string?>("--badge-color")
+        {
+            Description = "Ribbon color as #RRGGBB or a color name (default: #D83B01)"
+        };
     }
 
     public ManifestUpdateAssetsCommand() : base("update-assets", "Update image assets in AppxManifest.xml from a source image")
     {
         Arguments.Add(ImageArgument);
         Options.Add(ManifestOption);
+        Options.Add(BadgeOption);
+        Options.Add(BadgeColorOption);
     }
 
     public class Handler(IManifestService manifestService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService, ILogger<ManifestUpdateAssetsCommand> logger) : AsynchronousCommandLineAction
@@ -41,6 +55,8 @@ internal class ManifestUpdateAssetsCommand : Command
         {
             var imagePath = parseResult.GetValue(ImageArgument);
             var manifestPath = parseResult.GetValue(ManifestOption);
+            var badgeText = parseResult.GetValue(BadgeOption);
+            var badgeColor = parseResult.GetValue(BadgeColorOption);
 
             // If manifest path is not provided, try to find it in the current directory
             if (manifestPath == null)
@@ -60,11 +76,18 @@ internal class ManifestUpdateAssetsCommand : Command
                 return 1;
             }
 
+            if (badgeColor != null && badgeText == null)
+            {
+                logger.LogError("{UISymbol} --badge-color needs --badge", UiSymbols.Error);
+                return 1;
+            }
+            var badge = badgeText != null ? new AssetBadge(badgeText, badgeColor) : null;
+
             return await statusService.ExecuteWithStatusAsync("Updating manifest assets", async (taskContext, cancellationToken) =>
             {
                 try
                 {
-                    await manifestService.UpdateManifestAssetsAsync(manifestPath, imagePath, taskContext, cancellationToken);
+                    await manifestService.UpdateManifestAssetsAsync(manifestPath, imagePath, badge, taskContext, cancellationToken);
                     return (0, "Successfully updated assets for manifest.");
                 }
                 catch (Exception ex)
@@ -0,0 +1,73 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Extensions.Logging;
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ManifestWin32Command : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static ManifestWin32Command()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to AppxManifest.xml file (default: search current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = $"Where to write the manifest (default: {Win32Manifest.FileName} next to AppxManifest.xml)"
+        };
+    }
+
+    public ManifestWin32Command() : base("win32", "Generate the Win32 application manifest of the executable (dpiAwareness, longPathAware, supportedOS, activeCodePage) from 'win32Manifest:' in winapp.yaml")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService, ILogger<ManifestWin32Command> logger) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifestPath = parseResult.GetValue(ManifestOption) ?? MsixService.FindProjectManifest(currentDirectoryProvider);
+            if (manifestPath == null)
+            {
+                logger.LogError("{UISymbol} Could not find AppxManifest.xml in current directory or parent directories", UiSymbols.Error);
+                return 1;
+            }
+            var outputPath = parseResult.GetValue(OutputOption) ?? new FileInfo(Path.Combine(manifestPath.DirectoryName!, Win32Manifest.FileName));
+
+            return await statusService.ExecuteWithStatusAsync("Generating the Win32 manifest", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var config = configService.Exists() ? configService.Load().Win32Manifest : null;
+                    if (config == null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} winapp.yaml has no 'win32Manifest:', so the defaults are used, and 'package' doesn't embed the manifest");
+                    }
+
+                    var appxManifest = await File.ReadAllTextAsync(manifestPath.FullName, cancellationToken);
+                    await Win32Manifest.WriteAsync(config ?? new Win32ManifestConfig(), appxManifest, outputPath, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Wrote {outputPath.FullName}. Embed it in the build, e.g. <ApplicationManifest> in a .NET project, or let 'package' embed it into an executable without one");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to generate the Win32 manifest: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
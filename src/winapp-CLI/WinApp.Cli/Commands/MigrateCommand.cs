@@ -0,0 +1,16 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class MigrateCommand : Command
+{
+    public MigrateCommand(MigrateWapprojCommand wapprojCommand, MigrateFromCommand fromCommand)
+        : base("migrate", "Move a project from another packaging tool to winapp")
+    {
+        Subcommands.Add(wapprojCommand);
+        Subcommands.Add(fromCommand);
+    }
+}
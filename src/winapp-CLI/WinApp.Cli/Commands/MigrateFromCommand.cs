@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class MigrateFromCommand : Command
+{
+    public static Argument<string> BundlerArgument { get; }
+    public static Option<FileInfo> ConfigOption { get; }
+
+    static MigrateFromCommand()
+    {
+        BundlerArgument = new Argument<string>("bundler")
+        {
+            Description = "Bundler to migrate from: electron-builder or tauri"
+        };
+        BundlerArgument.AcceptOnlyFromAmong("electron-builder", "tauri");
+        ConfigOption = new Option<FileInfo>("--config")
+        {
+            Description = "The bundler's configuration: package.json or electron-builder.json, or tauri.conf.json (default: found in the current directory)"
+        };
+        ConfigOption.AcceptExistingOnly();
+    }
+
+    public MigrateFromCommand()
+        : base("from", "Convert the MSIX configuration of electron-builder or the Tauri bundler to winapp.yaml, appxmanifest.xml and Assets in the current directory, and list what needs to be migrated by hand")
+    {
+        Arguments.Add(BundlerArgument);
+        Options.Add(ConfigOption);
+        Options.Add(MigrateWapprojCommand.ForceOption);
+    }
+
+    public class Handler(IBundlerMigrationService bundlerMigrationService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var bundler = parseResult.GetRequiredValue(BundlerArgument);
+            var configPath = parseResult.GetValue(ConfigOption);
+            var force = parseResult.GetValue(MigrateWapprojCommand.ForceOption);
+            var source = bundler == "tauri" ? MigrationSource.Tauri : MigrationSource.ElectronBuilder;
+
+            return await statusService.ExecuteWithStatusAsync($"Migrating from {bundler}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    configPath ??= bundlerMigrationService.FindConfig(source, currentDirectoryProvider.GetCurrentDirectoryInfo());
+                    if (configPath == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No {bundler} configuration found in the current directory; pass --config");
+                    }
+
+                    var migration = await bundlerMigrationService.MigrateAsync(source, configPath, force, taskContext, cancellationToken);
+                    foreach (var item in migration.Migrated)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Check} {item}");
+                    }
+                    foreach (var item in migration.Manual)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {item}");
+                    }
+
+                    return (0, migration.Manual.Count == 0
+                        ? $"{UiSymbols.Check} Migrated {configPath.Name} to {migration.Manifest.FullName}"
+                        : $"Migrated {configPath.Name} to {migration.Manifest.FullName}; {migration.Manual.Count} item(s) need migrating by hand");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to migrate from {bundler}: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class MigrateWapprojCommand : Command
+{
+    public static Argument<FileInfo> WapprojPathArgument { get; }
+    public static Option<bool> ForceOption { get; }
+
+    static MigrateWapprojCommand()
+    {
+        WapprojPathArgument = new Argument<FileInfo>("wapproj-path")
+        {
+            Description = "Path to the Windows Application Packaging Project (.wapproj) to migrate"
+        };
+        WapprojPathArgument.AcceptExistingOnly();
+        ForceOption = new Option<bool>("--force")
+        {
+            Description = "Overwrite an existing appxmanifest.xml"
+        };
+    }
+
+    public MigrateWapprojCommand()
+        : base("wapproj", "Convert a Visual Studio packaging project to winapp.yaml, appxmanifest.xml and Assets in the current directory, and list what needs to be migrated by hand")
+    {
+        Arguments.Add(WapprojPathArgument);
+        Options.Add(ForceOption);
+    }
+
+    public class Handler(IWapprojMigrationService wapprojMigrationService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var wapprojPath = parseResult.GetRequiredValue(WapprojPathArgument);
+            var force = parseResult.GetValue(ForceOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Migrating {wapprojPath.Name}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var migration = await wapprojMigrationService.MigrateAsync(wapprojPath, force, taskContext, cancellationToken);
+                    foreach (var item in migration.Migrated)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Check} {item}");
+                    }
+                    foreach (var item in migration.Manual)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {item}");
+                    }
+
+                    return (0, migration.Manual.Count == 0
+                        ? $"{UiSymbols.Check} Migrated {wapprojPath.Name} to {migration.Manifest.FullName}"
+                        : $"Migrated {wapprojPath.Name} to {migration.Manifest.FullName}; {migration.Manual.Count} item(s) need migrating by hand");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to migrate {wapprojPath.Name}: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
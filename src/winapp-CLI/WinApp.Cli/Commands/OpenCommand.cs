@@ -0,0 +1,143 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class OpenCommand : Command
+{
+    public static Argument<PackageLocation> LocationArgument { get; }
+    public static Option<string?> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<bool> PrintOption { get; }
+
+    static OpenCommand()
+    {
+        LocationArgument = new Argument<PackageLocation>("location")
+        {
+            Description = "Folder to open: appdata (ApplicationData), install (the app's files) or logs"
+        };
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name of the installed package (default: the Identity Name of the project's appxmanifest.xml, or its .debug or branch identity)"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml that identifies the package (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        PrintOption = new Option<bool>("--print")
+        {
+            Description = "Print the folder instead of opening it in File Explorer"
+        };
+    }
+
+    public OpenCommand()
+        : base("open", "Open the app data, install or log folder of the current project's installed package")
+    {
+        Arguments.Add(LocationArgument);
+        Options.Add(NameOption);
+        Options.Add(ManifestOption);
+        Options.Add(PrintOption);
+    }
+
+    public class Handler(IInstalledPackageService installedPackageService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var location = parseResult.GetRequiredValue(LocationArgument);
+            var name = parseResult.GetValue(NameOption);
+            var manifest = parseResult.GetValue(ManifestOption);
+            var print = parseResult.GetValue(PrintOption);
+
+            return await statusService.ExecuteWithStatusAsync("Finding the package...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var (packages, _) = await installedPackageService.GetPackagesAsync(taskContext, cancellationToken);
+                    List<InstalledPackage> matches;
+                    if (name != null)
+                    {
+                        matches = [.. packages.Where(p => !p.Staged && p.Name.Equals(name, StringComparison.OrdinalIgnoreCase))];
+                    }
+                    else
+                    {
+                        manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                        if (manifest == null)
+                        {
+                            return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest or --name");
+                        }
+
+                        var identity = await MsixService.ParseAppxManifestFromPathAsync(manifest, cancellationToken);
+                        name = identity.PackageName;
+                        matches = InstalledPackageService.FindProjectPackages(packages, identity.PackageName);
+                    }
+
+                    if (matches.Count == 0)
+                    {
+                        return (1, $"{UiSymbols.Error} {name} is not installed; install it, or register it with 'winapp create-debug-identity'");
+                    }
+
+                    var package = matches[0];
+                    if (matches.Count > 1)
+                    {
+                        taskContext.AddDebugMessage($"Also installed: {string.Join(", ", matches.Skip(1).Select(p => p.Name))}; pass --name to pick one");
+                    }
+                    taskContext.AddDebugMessage($"Using {package.FullName}");
+
+                    var candidates = InstalledPackageService.GetLocations(package, location);
+                    var folders = candidates.Where(Directory.Exists).ToList();
+                    if (location == PackageLocation.Install && candidates.Any(InstalledPackageService.IsInWindowsApps))
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Files under WindowsApps are read-only and owned by TrustedInstaller; copy them elsewhere to edit them");
+                    }
+
+                    if (folders.Count == 0)
+                    {
+                        // WindowsApps folders can exist and still fail Directory.Exists when listing them is denied
+                        var fallback = candidates.FirstOrDefault(InstalledPackageService.IsInWindowsApps);
+                        if (fallback != null)
+                        {
+                            taskContext.AddStatusMessage(fallback);
+                            return (0, $"{UiSymbols.Warning} {fallback} can't be opened from this account; open it from an elevated prompt");
+                        }
+
+                        foreach (var candidate in candidates)
+                        {
+                            taskContext.AddDebugMessage($"Not found: {candidate}");
+                        }
+                        return (1, location == PackageLocation.Logs
+                            ? $"{UiSymbols.Error} {package.Name} has not written any logs yet (looked in {package.AppDataPath})"
+                            : $"{UiSymbols.Error} {candidates.FirstOrDefault() ?? package.Name} does not exist");
+                    }
+
+                    if (print)
+                    {
+                        foreach (var folder in folders)
+                        {
+                            taskContext.AddStatusMessage(folder);
+                        }
+                        return (0, package.FullName);
+                    }
+
+                    installedPackageService.OpenFolder(folders[0]);
+                    foreach (var other in folders.Skip(1))
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Also: {other}");
+                    }
+                    return (0, $"{UiSymbols.Folder} Opened {folders[0]}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to open the {location.ToString().ToLowerInvariant()} folder: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
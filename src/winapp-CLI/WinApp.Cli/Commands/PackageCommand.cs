@@ -3,7 +3,10 @@
 
 using System.CommandLine;
 using System.CommandLine.Invocation;
+using System.Xml.Linq;
+using WinApp.Cli.BuildCache;
 using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
 using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Commands;
@@ -21,13 +24,26 @@ internal class PackageCommand : Command
     public static Option<string?> PublisherOption { get; }
     public static Option<FileInfo> ManifestOption { get; }
     public static Option<bool> SelfContainedOption { get; }
+    public static Option<PackagingEngine> EngineOption { get; }
+    public static Option<int> ThreadsOption { get; }
+    public static Option<bool> NoBlockCacheOption { get; }
+    public static Option<DigestAlgorithm> DigestOption { get; }
+    public static Option<string> RemoteCacheOption { get; }
+    public static Option<bool> RemoteCacheReadOnlyOption { get; }
+    public static Option<string> BranchIdentityOption { get; }
+    public static Option<bool> ProvenanceOption { get; }
+    public static Option<string?> ProfileOption { get; }
+    public static Option<bool> MatrixOption { get; }
+    public static Option<string[]> VariantOption { get; }
+    public static Option<bool> ValidateOption { get; }
+    public static Option<bool> BuildOption { get; }
 
     static PackageCommand()
     {
         InputFolderArgument = new Argument<DirectoryInfo>("input-folder")
         {
-            Description = "Input folder with package layout",
-            Arity = ArgumentArity.ExactlyOne
+            Description = "Input folder with package layout (with --build: the folder the build outputs are copied to, default .winapp\\payload)",
+            Arity = ArgumentArity.ZeroOrOne
         };
         InputFolderArgument.AcceptExistingOnly();
         OutputOption = new Option<FileInfo>("--output")
@@ -45,7 +61,7 @@ internal class PackageCommand : Command
         };
         CertOption = new Option<FileInfo>("--cert")
         {
-            Description = "Path to signing certificate (will auto-sign if provided)"
+            Description = "Path to signing certificate (will auto-sign if provided; without it, packages are signed as 'sign:' in winapp.yaml configures)"
         };
         CertOption.AcceptExistingOnly();
         CertPasswordOption = new Option<string>("--cert-password")
@@ -74,6 +90,62 @@ internal class PackageCommand : Command
         {
             Description = "Bundle Windows App SDK runtime for self-contained deployment"
         };
+        EngineOption = new Option<PackagingEngine>("--engine")
+        {
+            Description = "Packaging engine: 'sdk' uses makeappx/signtool, 'native' packs and signs without the Windows SDK",
+            DefaultValueFactory = (argumentResult) => PackagingEngine.Sdk
+        };
+        ThreadsOption = new Option<int>("--threads")
+        {
+            Description = "Number of threads the native engine uses to hash and compress the payload (default: one per logical processor)"
+        };
+        NoBlockCacheOption = new Option<bool>("--no-block-cache")
+        {
+            Description = "Compress every block instead of reusing blocks the native engine compressed for earlier packages"
+        };
+        DigestOption = new Option<DigestAlgorithm>("--digest")
+        {
+            Description = "Hash algorithm for the block map and signature: sha256, sha384 or sha512 (sha384/sha512 require a manifest targeting Windows 10 or later)",
+            DefaultValueFactory = (argumentResult) => DigestAlgorithm.Sha256
+        };
+        RemoteCacheOption = new Option<string>("--remote-cache")
+        {
+            Description = $"Share packages between CI agents through this cache: an Azure container URL with a SAS token, s3://bucket/prefix or an http(s) URL (default: {BuildCacheService.LocationVariable}, or the remoteCache setting)"
+        };
+        RemoteCacheReadOnlyOption = new Option<bool>("--remote-cache-read-only")
+        {
+            Description = "Download packages from the remote cache but do not store new ones, e.g. for pull request builds"
+        };
+        BranchIdentityOption = new Option<string>("--branch-identity")
+        {
+            Description = "Give the package a throwaway identity and certificate of a git branch (default: the current branch), so it installs next to main builds. Remove it with 'winapp cleanup --branch'",
+            Arity = ArgumentArity.ZeroOrOne
+        };
+        ProvenanceOption = new Option<bool>("--provenance")
+        {
+            Description = $"Embed {BuildProvenance.FileName} with the git commit, build profile and CLI version in the package"
+        };
+        ProfileOption = new Option<string?>("--profile")
+        {
+            Description = "Build profile to record in the provenance stamp, e.g. Release or Nightly (implies --provenance); draws the profile's badge from winapp.yaml on the icons"
+        };
+        MatrixOption = new Option<bool>("--matrix")
+        {
+            Description = "Pack every variant of the matrix in winapp.yaml (brandings × channels × architectures) to <variant>.msix in the --output folder"
+        };
+        VariantOption = new Option<string[]>("--variant")
+        {
+            Description = "Pack only these matrix variants, or the variants of these brandings, channels or architectures (implies --matrix)",
+            AllowMultipleArgumentsPerToken = true
+        };
+        ValidateOption = new Option<bool>("--validate")
+        {
+            Description = "Run the checks of 'winapp validate' first, and don't pack when they find errors that aren't in the baseline"
+        };
+        BuildOption = new Option<bool>("--build")
+        {
+            Description = "Build the app first with the build system of 'build:' in winapp.yaml, or the one its project files are for (cargo, tauri, npm, dotnet, cmake or msbuild), and pack its outputs"
+        };
     }
 
     public PackageCommand()
@@ -91,13 +163,26 @@ internal class PackageCommand : Command
         Options.Add(PublisherOption);
         Options.Add(ManifestOption);
         Options.Add(SelfContainedOption);
+        Options.Add(EngineOption);
+        Options.Add(ThreadsOption);
+        Options.Add(NoBlockCacheOption);
+        Options.Add(DigestOption);
+        Options.Add(RemoteCacheOption);
+        Options.Add(RemoteCacheReadOnlyOption);
+        Options.Add(BranchIdentityOption);
+        Options.Add(ProvenanceOption);
+        Options.Add(ProfileOption);
+        Options.Add(MatrixOption);
+        Options.Add(VariantOption);
+        Options.Add(ValidateOption);
+        Options.Add(BuildOption);
     }
 
-    public class Handler(IMsixService msixService, IStatusService statusService) : AsynchronousCommandLineAction
+    public class Handler(IMsixService msixService, IBuildCacheService buildCacheService, IBranchIdentityService branchIdentityService, IProjectStateService projectStateService, IPackageMatrixService packageMatrixService, IValidationService validationService, IBuildService buildService, ISigningService signingService, ISettingsService settingsService, IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
     {
         public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
         {
-            var inputFolder = parseResult.GetRequiredValue(InputFolderArgument);
+            var inputFolder = parseResult.GetValue(InputFolderArgument);
             var output = parseResult.GetValue(OutputOption);
             var name = parseResult.GetValue(NameOption);
             var skipPri = parseResult.GetValue(SkipPriOption);
@@ -108,17 +193,115 @@ internal class PackageCommand : Command
             var publisher = parseResult.GetValue(PublisherOption);
             var manifestPath = parseResult.GetValue(ManifestOption);
             var selfContained = parseResult.GetValue(SelfContainedOption);
+            var engine = parseResult.GetValue(EngineOption);
+            var threads = parseResult.GetValue(ThreadsOption);
+            var noBlockCache = parseResult.GetValue(NoBlockCacheOption);
+            var digest = parseResult.GetValue(DigestOption);
+            var remoteCacheLocation = settingsService.Get(SettingsService.RemoteCache, parseResult.GetValue(RemoteCacheOption), RemoteCacheOption.Name).Value;
+            var remoteCacheReadOnly = parseResult.GetValue(RemoteCacheReadOnlyOption);
+            var useBranchIdentity = parseResult.GetResult(BranchIdentityOption) != null;
+            var branch = parseResult.GetValue(BranchIdentityOption);
+            var profile = parseResult.GetValue(ProfileOption);
+            var stampProvenance = parseResult.GetValue(ProvenanceOption) || profile != null;
+            var variantFilters = parseResult.GetValue(VariantOption) ?? [];
+            var matrix = parseResult.GetValue(MatrixOption) || variantFilters.Length > 0;
+            var validate = parseResult.GetValue(ValidateOption);
+            var build = parseResult.GetValue(BuildOption);
 
             return await statusService.ExecuteWithStatusAsync("Creating MSIX package...", async (taskContext, cancellationToken) =>
             {
                 try
                 {
+                    if (build)
+                    {
+                        if (matrix)
+                        {
+                            return (1, $"{UiSymbols.Error} --build builds the architecture of the manifest; build the input folder of each matrix architecture first, then pack with --matrix");
+                        }
+                        // The payload folder has no manifest of its own, so pack finds the project's
+                        manifestPath = FindManifest(inputFolder, manifestPath, currentDirectoryProvider);
+                        var architecture = ReadArchitecture(manifestPath);
+                        inputFolder = await buildService.BuildAsync(architecture, inputFolder, taskContext, cancellationToken);
+                    }
+                    else if (inputFolder == null)
+                    {
+                        return (1, $"{UiSymbols.Error} Pass the input folder to pack, or --build to build the app first");
+                    }
+
+                    if (validate)
+                    {
+                        var validatedManifest = FindManifest(inputFolder, manifestPath, currentDirectoryProvider);
+                        taskContext.UpdateSubStatus($"Validating {validatedManifest.Name}");
+                        var baseline = validationService.DefaultBaselinePath;
+                        var findings = await validationService.ValidateAsync(validatedManifest, baseline, allowScriptAnalyzers: false, taskContext, cancellationToken);
+                        var errors = (await validationService.CompareAsync(baseline, validatedManifest, findings, cancellationToken)).New
+                            .Where(f => f.Severity == AccessibilitySeverity.Error)
+                            .ToList();
+                        taskContext.UpdateSubStatus(null);
+                        foreach (var error in errors)
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Error} [{error.Rule}] {error.Element}: {error.Message}");
+                        }
+                        if (errors.Count > 0)
+                        {
+                            return (1, $"{UiSymbols.Error} {validatedManifest.Name} has {errors.Count} validation error(s); fix them, or record them with 'winapp validate --write-baseline'");
+                        }
+                    }
+
                     // Auto-sign if certificate is provided or if generate-cert is specified
                     var autoSign = certPath != null || generateCert;
 
-                    var result = await msixService.CreateMsixPackageAsync(inputFolder, output, taskContext, name, skipPri, autoSign, certPath, certPassword, generateCert, installCert, publisher, manifestPath, selfContained, cancellationToken);
+                    var remoteCache = string.IsNullOrWhiteSpace(remoteCacheLocation)
+                        ? null
+                        : new RemoteBuildCacheOptions(buildCacheService.Create(remoteCacheLocation), remoteCacheReadOnly);
+
+                    var branchIdentity = useBranchIdentity ? await branchIdentityService.ResolveAsync(branch, cancellationToken) : null;
+                    if (branchIdentity != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Packing with the identity of branch '{branchIdentity.Branch}' ({branchIdentity.NameSuffix})");
+                    }
+
+                    var provenance = stampProvenance ? await projectStateService.GetProvenanceAsync(profile, cancellationToken) : null;
+
+                    var badgeConfig = profile != null && configService.Exists() ? configService.Load().GetBadge(profile) : null;
+                    var badge = badgeConfig != null ? new AssetBadge(badgeConfig.Text, badgeConfig.Color) : null;
+                    if (badge != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Note} Badging the icons '{badge.Text}' for profile '{profile}'");
+                    }
+
+                    if (matrix)
+                    {
+                        // Variants share the input folders, so the block cache hashes each payload file once
+                        var variants = packageMatrixService.GetVariants(variantFilters);
+                        var outputFolder = output != null ? new DirectoryInfo(output.FullName) : currentDirectoryProvider.GetCurrentDirectoryInfo();
+                        var projectManifest = FindManifest(inputFolder, manifestPath, currentDirectoryProvider);
+                        foreach (var variant in variants)
+                        {
+                            taskContext.UpdateSubStatus($"Packing {variant.Name}");
+                            using var layout = await packageMatrixService.PrepareAsync(variant, inputFolder, projectManifest, taskContext, cancellationToken);
+                            var variantCert = layout.Certificate ?? certPath;
+                            var variantOutput = new FileInfo(Path.Combine(outputFolder.FullName, $"{variant.Name}.msix"));
+                            var variantResult = await msixService.CreateMsixPackageAsync(layout.InputFolder, variantOutput, taskContext, name, skipPri, variantCert != null || generateCert, variantCert, certPassword, generateCert, installCert, publisher, layout.ManifestPath, selfContained, engine, threads, !noBlockCache, digest, remoteCache, branchIdentity, provenance, badge, variant, cancellationToken);
+                            taskContext.AddStatusMessage($"{UiSymbols.Package} {variant.Name}: {variantResult.MsixPath}{(variantResult.FromCache ? " (from the remote cache)" : "")}{(variantResult.Signed ? ", signed" : "")}");
+                        }
+                        taskContext.UpdateSubStatus(null);
+
+                        return (0, $"Packed {variants.Count} matrix variant(s) to {outputFolder.FullName}.");
+                    }
+
+                    var result = await msixService.CreateMsixPackageAsync(inputFolder, output, taskContext, name, skipPri, autoSign, certPath, certPassword, generateCert, installCert, publisher, manifestPath, selfContained, engine, threads, !noBlockCache, digest, remoteCache, branchIdentity, provenance, badge, cancellationToken);
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Package} Package: {result.MsixPath}{(result.FromCache ? " (from the remote cache)" : "")}");
+
+                    // Without --cert, sign as 'sign:' in winapp.yaml configures, e.g. with Trusted Signing in release pipelines
+                    if (!result.Signed && configService.Exists() && configService.Load().Sign != null)
+                    {
+                        var timestamp = settingsService.Get(SettingsService.TimestampServer).Value;
+                        await signingService.SignAsync(result.MsixPath, new SigningRequest { Password = certPassword, TimestampUrl = timestamp, Engine = engine, Trust = installCert }, taskContext, cancellationToken);
+                        result = result with { Signed = true };
+                    }
 
-                    taskContext.AddStatusMessage($"{UiSymbols.Package} Package: {result.MsixPath}");
                     if (result.Signed)
                     {
                         taskContext.AddStatusMessage($"{UiSymbols.Lock} Package has been signed");
@@ -133,5 +316,15 @@ internal class PackageCommand : Command
                 }
             }, cancellationToken);
         }
+
+        private static FileInfo FindManifest(DirectoryInfo? inputFolder, FileInfo? manifestPath, ICurrentDirectoryProvider currentDirectoryProvider) =>
+            manifestPath
+                ?? (inputFolder != null && File.Exists(Path.Combine(inputFolder.FullName, "appxmanifest.xml")) ? new FileInfo(Path.Combine(inputFolder.FullName, "appxmanifest.xml")) : null)
+                ?? MsixService.FindProjectManifest(currentDirectoryProvider)
+                ?? throw new FileNotFoundException("No appxmanifest.xml found in the input folder, the current directory or its parents; pass --manifest");
+
+        // The build targets the architecture the package is for; neutral builds for the machine
+        private static string ReadArchitecture(FileInfo manifest) =>
+            XDocument.Load(manifest.FullName).Root?.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity")?.Attribute("ProcessorArchitecture")?.Value ?? "neutral";
     }
 }
@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class RemoteConfigCommand : Command
+{
+    public RemoteConfigCommand(RemoteConfigValidateCommand remoteConfigValidateCommand)
+        : base("remote-config", "Check the feature flag documents that the RemoteConfig of the winapp-runtime crate fetches, before publishing them")
+    {
+        Subcommands.Add(remoteConfigValidateCommand);
+    }
+}
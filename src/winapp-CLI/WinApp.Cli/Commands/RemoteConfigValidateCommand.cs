@@ -0,0 +1,68 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.RemoteConfig;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class RemoteConfigValidateCommand : Command
+{
+    public static Argument<FileInfo> FileArgument { get; }
+
+    static RemoteConfigValidateCommand()
+    {
+        FileArgument = new Argument<FileInfo>("file")
+        {
+            Description = "Remote configuration document to check, as it will be published",
+            Arity = ArgumentArity.ExactlyOne
+        };
+        FileArgument.AcceptExistingOnly();
+    }
+
+    public RemoteConfigValidateCommand()
+        : base("validate", "Check the schema of a remote configuration document, that rule values match the type of their flag's default, and that rules name the channels of winapp.yaml")
+    {
+        Arguments.Add(FileArgument);
+    }
+
+    public class Handler(IConfigService configService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var file = parseResult.GetRequiredValue(FileArgument);
+
+            return await statusService.ExecuteWithStatusAsync("Validating the remote configuration...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var json = await File.ReadAllTextAsync(file.FullName, cancellationToken);
+                    // Without channels in winapp.yaml, any channel the app is built with may be named
+                    var channels = configService.Exists() ? configService.Load().Matrix?.Channels.Select(c => c.Name).ToList() : null;
+                    var findings = RemoteConfigValidator.Validate(json, channels is { Count: > 0 } ? channels : null);
+
+                    foreach (var finding in findings.OrderByDescending(f => f.Severity))
+                    {
+                        var symbol = finding.Severity == AccessibilitySeverity.Error ? UiSymbols.Error : UiSymbols.Warning;
+                        taskContext.AddStatusMessage($"{symbol} {finding.Path}: {finding.Message}");
+                    }
+
+                    var errors = findings.Count(f => f.Severity == AccessibilitySeverity.Error);
+                    var warnings = findings.Count - errors;
+                    return errors > 0
+                        ? (1, $"{UiSymbols.Error} Found {errors} error(s) and {warnings} warning(s) in {file.Name}")
+                        : (0, $"{UiSymbols.Check} {file.Name} is valid{(warnings > 0 ? $", with {warnings} warning(s)" : "")}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to validate the remote configuration: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
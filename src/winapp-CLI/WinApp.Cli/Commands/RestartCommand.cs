@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class RestartCommand : Command
+{
+    public static Option<string?> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+
+    static RestartCommand()
+    {
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name of the installed package (default: the Identity Name of the project's appxmanifest.xml, or its .debug or branch identity)"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml that identifies the package (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+    }
+
+    public RestartCommand(RestartSimulateCommand restartSimulateCommand)
+        : base("restart", "Test how the current project's installed package restores the state it hands to restarts with the winapp-runtime crate")
+    {
+        Subcommands.Add(restartSimulateCommand);
+    }
+}
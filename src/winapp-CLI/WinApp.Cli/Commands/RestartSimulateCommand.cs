@@ -0,0 +1,58 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class RestartSimulateCommand : Command
+{
+    public static Option<RestartReason> ReasonOption { get; }
+
+    static RestartSimulateCommand()
+    {
+        ReasonOption = new Option<RestartReason>("--reason")
+        {
+            Description = "Restart to simulate: update (after request_restart_for_update) or crash (after request_restart_after_crash)",
+            DefaultValueFactory = (argumentResult) => RestartReason.Update
+        };
+    }
+
+    public RestartSimulateCommand()
+        : base("simulate", "Stop the running app and start it again with the state it saved for a restart, as Windows does after an update or a crash")
+    {
+        Options.Add(ReasonOption);
+        Options.Add(RestartCommand.NameOption);
+        Options.Add(RestartCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, IRestartService restartService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var reason = parseResult.GetValue(ReasonOption);
+            var name = parseResult.GetValue(RestartCommand.NameOption);
+            var manifest = parseResult.GetValue(RestartCommand.ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Restarting the app...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var simulation = await restartService.SimulateAsync(package, reason, taskContext, cancellationToken);
+                    taskContext.AddStatusMessage($"{UiSymbols.Note} Stopped {simulation.StoppedProcesses} process(es) of {package.Name}");
+                    return (0, $"{UiSymbols.Check} Restarted {package.Name} after a simulated {reason.ToString().ToLowerInvariant()} with the state it saved at {simulation.Request.SavedAt.ToLocalTime():T}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to restart the app: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
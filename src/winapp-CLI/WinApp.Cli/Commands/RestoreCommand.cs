@@ -3,6 +3,7 @@
 
 using System.CommandLine;
 using System.CommandLine.Invocation;
+using WinApp.Cli.Models;
 using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Commands;
@@ -11,6 +12,9 @@ internal class RestoreCommand : Command
 {
     public static Argument<DirectoryInfo> BaseDirectoryArgument { get; }
     public static Option<DirectoryInfo> ConfigDirOption { get; }
+    public static Option<bool> AcceptLicensesOption { get; }
+    public static Option<string[]> AcceptLicenseOption { get; }
+
     static RestoreCommand()
     {
         BaseDirectoryArgument = new Argument<DirectoryInfo>("base-directory")
@@ -25,12 +29,27 @@ internal class RestoreCommand : Command
             Description = "Directory to read configuration from (default: current directory)"
         };
         ConfigDirOption.AcceptExistingOnly();
+
+        AcceptLicensesOption = new Option<bool>("--accept-licenses")
+        {
+            Description = "Accept the licenses of every package that requires license acceptance, and record them in winapp.yaml"
+        };
+        AcceptLicenseOption = new Option<string[]>("--accept-license")
+        {
+            Description = "Accept the license of this package and record it in winapp.yaml. Can be repeated",
+            HelpName = "package"
+        };
     }
 
+    internal static LicenseConsent GetLicenseConsent(ParseResult parseResult, bool prompt = false)
+        => new(parseResult.GetValue(AcceptLicensesOption), parseResult.GetValue(AcceptLicenseOption) ?? [], prompt);
+
     public RestoreCommand() : base("restore", "Restore packages from winapp.yaml and ensure workspace is ready")
     {
         Arguments.Add(BaseDirectoryArgument);
         Options.Add(ConfigDirOption);
+        Options.Add(AcceptLicensesOption);
+        Options.Add(AcceptLicenseOption);
     }
 
     public class Handler(IWorkspaceSetupService workspaceSetupService, ICurrentDirectoryProvider currentDirectoryProvider) : AsynchronousCommandLineAction
@@ -45,7 +64,8 @@ internal class RestoreCommand : Command
                 BaseDirectory = baseDirectory,
                 ConfigDir = configDir,
                 RequireExistingConfig = true,
-                ForceLatestBuildTools = false // Will be determined from config
+                ForceLatestBuildTools = false, // Will be determined from config
+                LicenseConsent = GetLicenseConsent(parseResult)
             };
 
             return await workspaceSetupService.SetupWorkspaceAsync(options, cancellationToken);
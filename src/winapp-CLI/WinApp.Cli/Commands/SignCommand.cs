@@ -3,6 +3,7 @@
 
 using System.CommandLine;
 using System.CommandLine.Invocation;
+using WinApp.Cli.Models;
 using WinApp.Cli.Services;
 
 namespace WinApp.Cli.Commands;
@@ -13,17 +14,21 @@ internal class SignCommand : Command
     public static Argument<FileInfo> CertPathArgument { get; }
     public static Option<string> PasswordOption { get; }
     public static Option<string> TimestampOption { get; }
+    public static Option<PackagingEngine> EngineOption { get; }
+    public static Option<SigningProvider?> ProviderOption { get; }
+    public static Option<bool> TrustOption { get; }
 
     static SignCommand()
     {
         FilePathArgument = new Argument<FileInfo>("file-path")
         {
-            Description = "Path to the file/package to sign"
+            Description = "Path to the file/package to sign, or an .appinstaller file whose packages to sign"
         };
         FilePathArgument.AcceptExistingOnly();
         CertPathArgument = new Argument<FileInfo>("cert-path")
         {
-            Description = "Path to the certificate file (PFX format)"
+            Description = "Path to the certificate file (PFX format) (default: 'certificate' under 'sign:' in winapp.yaml, or devcert.pfx, generated for the package's publisher when missing)",
+            Arity = ArgumentArity.ZeroOrOne
         };
         CertPathArgument.AcceptExistingOnly();
         PasswordOption = new Option<string>("--password")
@@ -33,38 +38,56 @@ internal class SignCommand : Command
         };
         TimestampOption = new Option<string>("--timestamp")
         {
-            Description = "Timestamp server URL"
+            Description = "Timestamp server URL (default: the timestampServer setting)"
+        };
+        EngineOption = new Option<PackagingEngine>("--engine")
+        {
+            Description = "Signing engine: 'sdk' uses signtool, 'native' signs .msix/.appx packages without the Windows SDK",
+            DefaultValueFactory = (argumentResult) => PackagingEngine.Sdk
+        };
+        ProviderOption = new Option<SigningProvider?>("--provider")
+        {
+            Description = "Where the signing key lives: 'pfx', 'trustedsigning' (Azure Trusted Signing) or 'keyvault' (Azure Key Vault) (default: 'provider' under 'sign:' in winapp.yaml, or pfx). The Azure providers read their settings from winapp.yaml and the AZURE_TENANT_ID, AZURE_CLIENT_ID and AZURE_CLIENT_SECRET environment variables"
+        };
+        TrustOption = new Option<bool>("--trust")
+        {
+            Description = "Install the development certificate that signing generates into the trusted store (requires elevation)"
         };
     }
 
-    public SignCommand() : base("sign", "Sign a file/package with a certificate")
+    public SignCommand() : base("sign", "Sign a file/package with a PFX certificate, Azure Trusted Signing or Azure Key Vault, checking that the certificate matches the package publisher")
     {
         Arguments.Add(FilePathArgument);
         Arguments.Add(CertPathArgument);
         Options.Add(PasswordOption);
         Options.Add(TimestampOption);
+        Options.Add(EngineOption);
+        Options.Add(ProviderOption);
+        Options.Add(TrustOption);
     }
 
-    public class Handler(ICertificateService certificateService, IStatusService statusService) : AsynchronousCommandLineAction
+    public class Handler(ISigningService signingService, ISettingsService settingsService, IStatusService statusService) : AsynchronousCommandLineAction
     {
         public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
         {
             var filePath = parseResult.GetRequiredValue(FilePathArgument);
-            var certPath = parseResult.GetRequiredValue(CertPathArgument);
-            var password = parseResult.GetValue(PasswordOption);
-            var timestamp = parseResult.GetValue(TimestampOption);
+            var request = new SigningRequest
+            {
+                Provider = parseResult.GetValue(ProviderOption),
+                Certificate = parseResult.GetValue(CertPathArgument),
+                Password = parseResult.GetValue(PasswordOption) ?? "password",
+                TimestampUrl = settingsService.Get(SettingsService.TimestampServer, parseResult.GetValue(TimestampOption), TimestampOption.Name).Value,
+                Engine = parseResult.GetValue(EngineOption),
+                Trust = parseResult.GetValue(TrustOption)
+            };
 
             return await statusService.ExecuteWithStatusAsync($"Signing file: {filePath}", async (taskContext, cancellationToken) =>
             {
                 try
                 {
-                    await certificateService.SignFileAsync(filePath, certPath, taskContext, password, timestamp, cancellationToken);
+                    var signed = await signingService.SignAsync(filePath, request, taskContext, cancellationToken);
 
-                    return (0, "Signed file: {filePath}");
-                }
-                catch (InvalidOperationException error)
-                {
-                    return (1, $"Failed to sign file: {error.Message}");
+                    return (0, signed.Count == 1 ? $"Signed file: {signed[0].FullName}" : $"Signed {signed.Count} packages of {filePath.Name}");
                 }
                 catch (Exception error)
                 {
@@ -0,0 +1,33 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class StateCommand : Command
+{
+    public static Option<string?> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+
+    static StateCommand()
+    {
+        NameOption = new Option<string?>("--name")
+        {
+            Description = "Package name of the installed package (default: the Identity Name of the project's appxmanifest.xml, or its .debug or branch identity)"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml that identifies the package (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+    }
+
+    public StateCommand(StateResetCommand stateResetCommand, StateSnapshotCommand stateSnapshotCommand, StateRestoreCommand stateRestoreCommand)
+        : base("state", "Reset, save and restore the app data, settings and virtualized registry of the current project's installed package, so tests start from a known state")
+    {
+        Subcommands.Add(stateResetCommand);
+        Subcommands.Add(stateSnapshotCommand);
+        Subcommands.Add(stateRestoreCommand);
+    }
+}
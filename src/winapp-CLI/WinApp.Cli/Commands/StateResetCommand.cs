@@ -0,0 +1,43 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StateResetCommand : Command
+{
+    public StateResetCommand()
+        : base("reset", "Stop the app and delete its app data, settings and virtualized registry, as if it was just installed")
+    {
+        Options.Add(StateCommand.NameOption);
+        Options.Add(StateCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var name = parseResult.GetValue(StateCommand.NameOption);
+            var manifest = parseResult.GetValue(StateCommand.ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Resetting the app's state...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var count = await appStateService.ResetAsync(package, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Reset the state of {package.Name}: deleted {count} file(s)");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to reset the app's state: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
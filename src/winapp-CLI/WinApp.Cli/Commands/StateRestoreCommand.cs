@@ -0,0 +1,58 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using System.Globalization;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StateRestoreCommand : Command
+{
+    public static Argument<string> SnapshotArgument { get; }
+
+    static StateRestoreCommand()
+    {
+        SnapshotArgument = new Argument<string>("snapshot")
+        {
+            Description = "Name of a snapshot in .winapp/snapshots, or the path of a snapshot .zip",
+            DefaultValueFactory = (argumentResult) => "default"
+        };
+    }
+
+    public StateRestoreCommand()
+        : base("restore", "Stop the app and replace its app data, settings and virtualized registry with a snapshot")
+    {
+        Arguments.Add(SnapshotArgument);
+        Options.Add(StateCommand.NameOption);
+        Options.Add(StateCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var snapshotName = parseResult.GetRequiredValue(SnapshotArgument);
+            var name = parseResult.GetValue(StateCommand.NameOption);
+            var manifest = parseResult.GetValue(StateCommand.ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Restoring the app's state...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var file = appStateService.GetSnapshotFile(snapshotName);
+                    var snapshot = await appStateService.RestoreAsync(package, file, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Restored {snapshot.FileCount} file(s) of {package.Name} from {file.Name}, saved {snapshot.CreatedAt.ToLocalTime().ToString("g", CultureInfo.CurrentCulture)}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to restore the app's state: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
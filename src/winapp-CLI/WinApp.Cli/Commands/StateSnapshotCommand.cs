@@ -0,0 +1,57 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StateSnapshotCommand : Command
+{
+    public static Argument<string> SnapshotArgument { get; }
+
+    static StateSnapshotCommand()
+    {
+        SnapshotArgument = new Argument<string>("snapshot")
+        {
+            Description = "Name of the snapshot, kept in .winapp/snapshots, or the path of a .zip to write",
+            DefaultValueFactory = (argumentResult) => "default"
+        };
+    }
+
+    public StateSnapshotCommand()
+        : base("snapshot", "Stop the app and save its app data, settings and virtualized registry, to restore them before each test")
+    {
+        Arguments.Add(SnapshotArgument);
+        Options.Add(StateCommand.NameOption);
+        Options.Add(StateCommand.ManifestOption);
+    }
+
+    public class Handler(IAppStateService appStateService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var snapshotName = parseResult.GetRequiredValue(SnapshotArgument);
+            var name = parseResult.GetValue(StateCommand.NameOption);
+            var manifest = parseResult.GetValue(StateCommand.ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Saving the app's state...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var package = await appStateService.FindPackageAsync(name, manifest, taskContext, cancellationToken);
+                    var file = appStateService.GetSnapshotFile(snapshotName);
+                    var snapshot = await appStateService.SnapshotAsync(package, file, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Saved {snapshot.FileCount} file(s) of {package.Name} to {file.FullName}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to save the app's state: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreAssociateCommand : Command
+{
+    public static Option<string> AppIdOption { get; }
+    public static Option<string> NameOption { get; }
+    public static Option<FileInfo> ManifestOption { get; }
+
+    static StoreAssociateCommand()
+    {
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (default: the one in winapp.yaml, or the account's only app)"
+        };
+        NameOption = new Option<string>("--name")
+        {
+            Description = "Reserved name of the app, to find it by instead of its Store ID"
+        };
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to update (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+    }
+
+    public StoreAssociateCommand()
+        : base("associate", "Associate the project with a Store app: write the identity Name, Publisher and reserved name from Partner Center into the manifest, and the Store ID and package family name into winapp.yaml")
+    {
+        Options.Add(AppIdOption);
+        Options.Add(NameOption);
+        Options.Add(ManifestOption);
+    }
+
+    public class Handler(IStoreAssociationService storeAssociationService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var appId = parseResult.GetValue(AppIdOption);
+            var name = parseResult.GetValue(NameOption);
+            var manifest = parseResult.GetValue(ManifestOption);
+
+            return await statusService.ExecuteWithStatusAsync("Associating with the Store...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    var association = await storeAssociationService.AssociateAsync(manifest, appId, name, taskContext, cancellationToken);
+                    foreach (var change in association.Changes)
+                    {
+                        var subject = change.Attribute.Length > 0 ? $"{change.Element}@{change.Attribute}" : change.Element;
+                        taskContext.AddStatusMessage($"{UiSymbols.Check} {subject}: {change.OldValue ?? "(none)"} -> {change.NewValue}");
+                    }
+
+                    var application = association.Application;
+                    return association.Changes.Count == 0
+                        ? (0, $"{UiSymbols.Check} {manifest.Name} is associated with {application.PrimaryName} ({application.ApplicationId}) already")
+                        : (0, $"{UiSymbols.Check} Associated {manifest.Name} with {application.PrimaryName} ({application.ApplicationId})");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to associate with the Store: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
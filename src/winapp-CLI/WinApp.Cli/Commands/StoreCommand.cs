@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreCommand : Command
+{
+    public StoreCommand(StoreSubmitCommand storeSubmitCommand, StoreStatsCommand storeStatsCommand, StoreReviewsCommand storeReviewsCommand, StoreRolloutCommand storeRolloutCommand, StoreFlightCommand storeFlightCommand, StoreAssociateCommand storeAssociateCommand, StoreNamesCommand storeNamesCommand)
+        : base("store", "Reserve names, associate projects with Store apps, publish packages to the Microsoft Store, manage rollouts and flights, read Store analytics and respond to reviews through Partner Center")
+    {
+        Subcommands.Add(storeNamesCommand);
+        Subcommands.Add(storeAssociateCommand);
+        Subcommands.Add(storeSubmitCommand);
+        Subcommands.Add(storeRolloutCommand);
+        Subcommands.Add(storeFlightCommand);
+        Subcommands.Add(storeStatsCommand);
+        Subcommands.Add(storeReviewsCommand);
+    }
+}
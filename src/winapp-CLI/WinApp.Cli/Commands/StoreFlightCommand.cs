@@ -0,0 +1,37 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreFlightCommand : Command
+{
+    public static Option<string> AppIdOption { get; }
+
+    static StoreFlightCommand()
+    {
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (e.g. 9NBLGGH4R315)",
+            Required = true
+        };
+    }
+
+    public StoreFlightCommand(StoreFlightListCommand listCommand, StoreFlightCreateCommand createCommand, StoreFlightDeleteCommand deleteCommand, StoreFlightMembersCommand membersCommand)
+        : base("flight", "Manage package flights, which give pre-release packages to members of flight groups")
+    {
+        Subcommands.Add(listCommand);
+        Subcommands.Add(createCommand);
+        Subcommands.Add(deleteCommand);
+        Subcommands.Add(membersCommand);
+    }
+
+    internal static string Describe(StoreFlight flight)
+    {
+        var published = flight.LastPublishedSubmissionId != null ? $", published submission {flight.LastPublishedSubmissionId}" : "";
+        var pending = flight.PendingSubmissionId != null ? $", pending submission {flight.PendingSubmissionId}" : "";
+        return $"{flight.FriendlyName} [{flight.FlightId}] groups: {string.Join(", ", flight.GroupIds)}{published}{pending}";
+    }
+}
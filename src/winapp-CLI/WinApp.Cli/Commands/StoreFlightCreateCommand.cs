@@ -0,0 +1,68 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreFlightCreateCommand : Command
+{
+    public static Argument<string> NameArgument { get; }
+    public static Option<string[]> GroupOption { get; }
+    public static Option<string> RankHigherThanOption { get; }
+
+    static StoreFlightCreateCommand()
+    {
+        NameArgument = new Argument<string>("name")
+        {
+            Description = "Friendly name of the flight, e.g. Insiders"
+        };
+        GroupOption = new Option<string[]>("--group")
+        {
+            Description = "ID(s) of the flight groups whose members get the flight, as shown in the URL of the group in Partner Center",
+            AllowMultipleArgumentsPerToken = true,
+            Required = true
+        };
+        RankHigherThanOption = new Option<string>("--rank-higher-than")
+        {
+            Description = "Name or ID of a flight this one takes precedence over, for customers in both (default: only above the non-flighted packages)"
+        };
+    }
+
+    public StoreFlightCreateCommand()
+        : base("create", "Create a package flight for existing flight groups")
+    {
+        Arguments.Add(NameArgument);
+        Options.Add(StoreFlightCommand.AppIdOption);
+        Options.Add(GroupOption);
+        Options.Add(RankHigherThanOption);
+    }
+
+    public class Handler(IStoreFlightService storeFlightService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var name = parseResult.GetRequiredValue(NameArgument);
+            var appId = parseResult.GetRequiredValue(StoreFlightCommand.AppIdOption);
+            var groupIds = parseResult.GetRequiredValue(GroupOption);
+            var rankHigherThan = parseResult.GetValue(RankHigherThanOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Creating flight '{name}' for {appId}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var flight = await storeFlightService.CreateAsync(appId, name, groupIds, rankHigherThan, taskContext, cancellationToken);
+                    return (0, $"Created flight {StoreFlightCommand.Describe(flight)}. Submit packages to it with 'winapp store submit --flight {flight.FlightId}'");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to create the flight: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
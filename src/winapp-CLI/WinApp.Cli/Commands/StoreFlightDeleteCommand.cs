@@ -0,0 +1,52 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreFlightDeleteCommand : Command
+{
+    public static Argument<string> FlightArgument { get; }
+
+    static StoreFlightDeleteCommand()
+    {
+        FlightArgument = new Argument<string>("flight")
+        {
+            Description = "Name or ID of the flight to delete"
+        };
+    }
+
+    public StoreFlightDeleteCommand()
+        : base("delete", "Delete a package flight; its members get the non-flighted packages again")
+    {
+        Arguments.Add(FlightArgument);
+        Options.Add(StoreFlightCommand.AppIdOption);
+    }
+
+    public class Handler(IStoreFlightService storeFlightService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var flightIdOrName = parseResult.GetRequiredValue(FlightArgument);
+            var appId = parseResult.GetRequiredValue(StoreFlightCommand.AppIdOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Deleting flight '{flightIdOrName}' of {appId}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var flight = await storeFlightService.DeleteAsync(appId, flightIdOrName, taskContext, cancellationToken);
+                    return (0, $"Deleted flight {flight.FriendlyName} [{flight.FlightId}].");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to delete the flight: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreFlightListCommand : Command
+{
+    public StoreFlightListCommand()
+        : base("list", "List the package flights of an app")
+    {
+        Options.Add(StoreFlightCommand.AppIdOption);
+    }
+
+    public class Handler(IStoreFlightService storeFlightService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var appId = parseResult.GetRequiredValue(StoreFlightCommand.AppIdOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Reading flights of {appId}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var flights = await storeFlightService.ListAsync(appId, taskContext, cancellationToken);
+                    foreach (var flight in flights)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Bullet} {StoreFlightCommand.Describe(flight)}");
+                    }
+
+                    return (0, $"{appId} has {flights.Count} flight(s).");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to list flights: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreFlightMembersCommand : Command
+{
+    public static Option<FileInfo[]> FromCsvOption { get; }
+    public static Option<string[]> AadGroupOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static StoreFlightMembersCommand()
+    {
+        FromCsvOption = new Option<FileInfo[]>("--from-csv")
+        {
+            Description = "CSV file(s) of members: the email, mail or userPrincipalName column, or every email address if there is no such header",
+            AllowMultipleArgumentsPerToken = true
+        };
+        FromCsvOption.AcceptExistingOnly();
+        AadGroupOption = new Option<string[]>("--aad-group")
+        {
+            Description = "Object ID(s) of Azure AD groups whose users, including those of nested groups, become members. The Azure AD app needs the GroupMember.Read.All permission",
+            AllowMultipleArgumentsPerToken = true
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Member list to upload to the flight group in Partner Center",
+            Required = true
+        };
+    }
+
+    public StoreFlightMembersCommand()
+        : base("members", "Build the member list of a flight group from CSV files and Azure AD groups")
+    {
+        Options.Add(FromCsvOption);
+        Options.Add(AadGroupOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(IStoreFlightService storeFlightService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var csvFiles = parseResult.GetValue(FromCsvOption) ?? [];
+            var aadGroupIds = parseResult.GetValue(AadGroupOption) ?? [];
+            var output = parseResult.GetRequiredValue(OutputOption);
+
+            return await statusService.ExecuteWithStatusAsync("Building the flight group member list...", async (taskContext, cancellationToken) =>
+            {
+                if (csvFiles.Length == 0 && aadGroupIds.Length == 0)
+                {
+                    return (1, $"{UiSymbols.Error} Specify members with --from-csv and/or --aad-group");
+                }
+
+                try
+                {
+                    var count = await storeFlightService.ExportGroupMembersAsync(csvFiles, aadGroupIds, output, taskContext, cancellationToken);
+                    return (0, $"Wrote {count:N0} member(s) to {output.FullName}. Upload it to the flight group (a known user group) in Partner Center");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to build the member list: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
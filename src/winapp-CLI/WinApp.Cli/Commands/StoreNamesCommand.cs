@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreNamesCommand : Command
+{
+    public StoreNamesCommand(StoreNamesListCommand listCommand, StoreNamesReserveCommand reserveCommand, StoreNamesReleaseCommand releaseCommand)
+        : base("names", "Reserve and release app names in Partner Center, the first step of publishing a new app")
+    {
+        Subcommands.Add(listCommand);
+        Subcommands.Add(reserveCommand);
+        Subcommands.Add(releaseCommand);
+    }
+
+    internal static string Describe(StoreApplication application) =>
+        $"{application.PrimaryName} [{application.ApplicationId}] identity: {application.PackageIdentityName}";
+}
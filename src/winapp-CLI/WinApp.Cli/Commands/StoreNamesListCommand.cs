@@ -0,0 +1,42 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreNamesListCommand : Command
+{
+    public StoreNamesListCommand()
+        : base("list", "List the apps of the Partner Center account with their reserved names")
+    {
+    }
+
+    public class Handler(IStoreNameService storeNameService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            return await statusService.ExecuteWithStatusAsync("Reading reserved names...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var applications = await storeNameService.ListAsync(taskContext, cancellationToken);
+                    foreach (var application in applications)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Bullet} {StoreNamesCommand.Describe(application)}");
+                    }
+
+                    return (0, $"The account has {applications.Count} app(s).");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to list reserved names: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
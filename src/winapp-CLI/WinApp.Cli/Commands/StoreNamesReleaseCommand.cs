@@ -0,0 +1,50 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreNamesReleaseCommand : Command
+{
+    public static Argument<string> AppArgument { get; }
+
+    static StoreNamesReleaseCommand()
+    {
+        AppArgument = new Argument<string>("app")
+        {
+            Description = "Reserved name or Store ID of the app to release"
+        };
+    }
+
+    public StoreNamesReleaseCommand()
+        : base("release", "Release the name of an app that was never published, so others can reserve it; this deletes the app from Partner Center")
+    {
+        Arguments.Add(AppArgument);
+    }
+
+    public class Handler(IStoreNameService storeNameService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var app = parseResult.GetRequiredValue(AppArgument);
+
+            return await statusService.ExecuteWithStatusAsync($"Releasing '{app}'...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var application = await storeNameService.ReleaseAsync(app, taskContext, cancellationToken);
+                    return (0, $"{UiSymbols.Check} Released '{application.PrimaryName}' ({application.ApplicationId})");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to release '{app}': {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreNamesReserveCommand : Command
+{
+    public static Argument<string> NameArgument { get; }
+
+    static StoreNamesReserveCommand()
+    {
+        NameArgument = new Argument<string>("name")
+        {
+            Description = "Name to reserve, as customers see it in the Store, e.g. \"Contoso App\""
+        };
+    }
+
+    public StoreNamesReserveCommand()
+        : base("reserve", "Reserve a name for a new app; Partner Center assigns its Store ID and package identity")
+    {
+        Arguments.Add(NameArgument);
+    }
+
+    public class Handler(IStoreNameService storeNameService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var name = parseResult.GetRequiredValue(NameArgument);
+
+            return await statusService.ExecuteWithStatusAsync($"Reserving '{name}'...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var (application, reserved) = await storeNameService.ReserveAsync(name, taskContext, cancellationToken);
+                    var verb = reserved ? "Reserved" : "The account has reserved";
+                    return (0, $"{UiSymbols.Check} {verb} {StoreNamesCommand.Describe(application)}. Write its identity into the manifest with 'winapp store associate --app-id {application.ApplicationId}'");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to reserve '{name}': {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreReviewsCommand : Command
+{
+    private const int DefaultDays = 30;
+
+    // Options shared by 'reviews list' and 'reviews respond' to select reviews
+    public static Option<string> AppIdOption { get; }
+    public static Option<DateOnly?> StartDateOption { get; }
+    public static Option<DateOnly?> EndDateOption { get; }
+    public static Option<int[]> RatingOption { get; }
+    public static Option<string> MarketOption { get; }
+    public static Option<string> PackageVersionOption { get; }
+
+    static StoreReviewsCommand()
+    {
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (e.g. 9NBLGGH4R315)",
+            Required = true
+        };
+        StartDateOption = new Option<DateOnly?>("--start-date")
+        {
+            Description = $"Only reviews from this day on, e.g. 2026-01-01 (default: {DefaultDays} days before the end date)"
+        };
+        EndDateOption = new Option<DateOnly?>("--end-date")
+        {
+            Description = "Only reviews up to this day (default: today, UTC)"
+        };
+        RatingOption = new Option<int[]>("--rating")
+        {
+            Description = "Star rating(s) to include, 1 to 5, e.g. --rating 1 2",
+            AllowMultipleArgumentsPerToken = true
+        };
+        MarketOption = new Option<string>("--market")
+        {
+            Description = "Two-letter market (country) code of the reviewers, e.g. US"
+        };
+        PackageVersionOption = new Option<string>("--version")
+        {
+            Description = "Package version the reviewers had installed, e.g. 1.2.0.0"
+        };
+    }
+
+    public StoreReviewsCommand(StoreReviewsListCommand storeReviewsListCommand, StoreReviewsRespondCommand storeReviewsRespondCommand)
+        : base("reviews", "Triage Microsoft Store reviews and respond to them")
+    {
+        Subcommands.Add(storeReviewsListCommand);
+        Subcommands.Add(storeReviewsRespondCommand);
+    }
+
+    internal static void AddQueryOptions(Command command)
+    {
+        command.Options.Add(AppIdOption);
+        command.Options.Add(StartDateOption);
+        command.Options.Add(EndDateOption);
+        command.Options.Add(RatingOption);
+        command.Options.Add(MarketOption);
+        command.Options.Add(PackageVersionOption);
+    }
+
+    internal static StoreReviewQuery GetQuery(ParseResult parseResult, bool unansweredOnly)
+    {
+        var endDate = parseResult.GetValue(EndDateOption) ?? DateOnly.FromDateTime(DateTime.UtcNow);
+        var startDate = parseResult.GetValue(StartDateOption) ?? endDate.AddDays(-DefaultDays);
+
+        return new StoreReviewQuery(
+            parseResult.GetRequiredValue(AppIdOption),
+            startDate,
+            endDate,
+            parseResult.GetValue(RatingOption) ?? [],
+            parseResult.GetValue(MarketOption),
+            parseResult.GetValue(PackageVersionOption),
+            unansweredOnly);
+    }
+
+    /// <summary>
+    /// Checks the parts of a query the API would reject, returning an error message for the user
+    /// </summary>
+    internal static string? Validate(StoreReviewQuery query)
+    {
+        if (query.EndDate < query.StartDate)
+        {
+            return $"--end-date {query.EndDate:yyyy-MM-dd} is before --start-date {query.StartDate:yyyy-MM-dd}";
+        }
+        if (query.Ratings.Any(r => r is < 1 or > 5))
+        {
+            return "--rating must be between 1 and 5";
+        }
+
+        return null;
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreReviewsListCommand : Command
+{
+    public static Option<bool> UnansweredOption { get; }
+    public static Option<StoreExportFormat> FormatOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static StoreReviewsListCommand()
+    {
+        UnansweredOption = new Option<bool>("--unanswered")
+        {
+            Description = "Only list reviews that have no developer response yet"
+        };
+        FormatOption = new Option<StoreExportFormat>("--format")
+        {
+            Description = "Format of the --output file: json or csv",
+            DefaultValueFactory = (argumentResult) => StoreExportFormat.Json
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Also write the full reviews to this file, e.g. for a support dashboard"
+        };
+    }
+
+    public StoreReviewsListCommand()
+        : base("list", "List Store reviews of an app, newest first, filtered by rating, market or package version")
+    {
+        StoreReviewsCommand.AddQueryOptions(this);
+        Options.Add(UnansweredOption);
+        Options.Add(FormatOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(IStoreReviewService storeReviewService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var query = StoreReviewsCommand.GetQuery(parseResult, parseResult.GetValue(UnansweredOption));
+            var format = parseResult.GetValue(FormatOption);
+            var output = parseResult.GetValue(OutputOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Reading reviews of {query.ApplicationId}...", async (taskContext, cancellationToken) =>
+            {
+                if (StoreReviewsCommand.Validate(query) is string error)
+                {
+                    return (1, $"{UiSymbols.Error} {error}");
+                }
+
+                try
+                {
+                    var reviews = await storeReviewService.ListAsync(query, taskContext, cancellationToken);
+                    foreach (var review in reviews)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Bullet} {StoreReviews.Describe(review)}");
+                    }
+
+                    if (output != null)
+                    {
+                        output.Directory?.Create();
+                        await using var stream = File.Create(output.FullName);
+                        await PartnerCenterAnalytics.WriteAsync(reviews, format, stream, cancellationToken);
+                        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {reviews.Count:N0} reviews to {output.FullName}");
+                    }
+
+                    return (0, $"Found {reviews.Count:N0} review(s) from {query.StartDate:yyyy-MM-dd} to {query.EndDate:yyyy-MM-dd}{(output != null ? $", saved to {output.FullName}" : "")}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to read reviews: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreReviewsRespondCommand : Command
+{
+    public static Argument<string[]> ReviewIdsArgument { get; }
+    public static Option<string> TemplateOption { get; }
+    public static Option<FileInfo> TemplateFileOption { get; }
+    public static Option<string> SupportEmailOption { get; }
+    public static Option<bool> PrivateOption { get; }
+    public static Option<bool> DryRunOption { get; }
+
+    static StoreReviewsRespondCommand()
+    {
+        ReviewIdsArgument = new Argument<string[]>("review-ids")
+        {
+            Description = "IDs of the reviews to respond to. Without IDs, every unanswered review matching the filters gets a response",
+            Arity = ArgumentArity.ZeroOrMore
+        };
+        TemplateOption = new Option<string>("--template")
+        {
+            Description = "Response text. Placeholders such as {reviewerName}, {rating}, {packageVersion} or {market} are replaced with the review's values"
+        };
+        TemplateFileOption = new Option<FileInfo>("--template-file")
+        {
+            Description = "File containing the response text, with the same placeholders as --template"
+        };
+        TemplateFileOption.AcceptExistingOnly();
+        SupportEmailOption = new Option<string>("--support-email")
+        {
+            Description = "Support address shown with the responses so customers can follow up"
+        };
+        PrivateOption = new Option<bool>("--private")
+        {
+            Description = "Only email the responses to the reviewers instead of showing them in the Store"
+        };
+        DryRunOption = new Option<bool>("--dry-run")
+        {
+            Description = "Print the responses that would be posted without posting them"
+        };
+    }
+
+    public StoreReviewsRespondCommand()
+        : base("respond", "Post developer responses to unanswered Store reviews from a text template")
+    {
+        Arguments.Add(ReviewIdsArgument);
+        StoreReviewsCommand.AddQueryOptions(this);
+        Options.Add(TemplateOption);
+        Options.Add(TemplateFileOption);
+        Options.Add(SupportEmailOption);
+        Options.Add(PrivateOption);
+        Options.Add(DryRunOption);
+    }
+
+    public class Handler(IStoreReviewService storeReviewService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var reviewIds = parseResult.GetValue(ReviewIdsArgument) ?? [];
+            var query = StoreReviewsCommand.GetQuery(parseResult, unansweredOnly: true);
+            var template = parseResult.GetValue(TemplateOption);
+            var templateFile = parseResult.GetValue(TemplateFileOption);
+            var supportEmail = parseResult.GetValue(SupportEmailOption);
+            var isPublic = !parseResult.GetValue(PrivateOption);
+            var dryRun = parseResult.GetValue(DryRunOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Responding to reviews of {query.ApplicationId}...", async (taskContext, cancellationToken) =>
+            {
+                if (StoreReviewsCommand.Validate(query) is string error)
+                {
+                    return (1, $"{UiSymbols.Error} {error}");
+                }
+                if ((template == null) == (templateFile == null))
+                {
+                    return (1, $"{UiSymbols.Error} Specify exactly one of --template or --template-file");
+                }
+
+                try
+                {
+                    template ??= await File.ReadAllTextAsync(templateFile!.FullName, cancellationToken);
+
+                    var reviews = await storeReviewService.ListAsync(query, taskContext, cancellationToken);
+                    if (reviewIds.Length > 0)
+                    {
+                        var missing = reviewIds.Where(id => !reviews.Any(review => StoreReviews.GetString(review, "id") == id)).ToList();
+                        if (missing.Count > 0)
+                        {
+                            return (1, $"{UiSymbols.Error} No unanswered reviews matching the filters with ID(s) {string.Join(", ", missing)}. Check --start-date and --end-date");
+                        }
+                        reviews.RemoveAll(review => !reviewIds.Contains(StoreReviews.GetString(review, "id") ?? string.Empty));
+                    }
+
+                    if (reviews.Count == 0)
+                    {
+                        return (0, "No unanswered reviews match the filters; nothing to respond to.");
+                    }
+
+                    var responses = reviews
+                        .Select(review => (ReviewId: StoreReviews.GetString(review, "id") ?? string.Empty, Text: StoreReviews.RenderTemplate(template, review)))
+                        .ToList();
+
+                    if (dryRun)
+                    {
+                        foreach (var (review, response) in reviews.Zip(responses))
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Bullet} {StoreReviews.Describe(review)}");
+                            taskContext.AddStatusMessage($"   {response.Text.ReplaceLineEndings(" ")}");
+                        }
+                        return (0, $"Would post {responses.Count} {(isPublic ? "public" : "private")} response(s) (--dry-run).");
+                    }
+
+                    var results = await storeReviewService.RespondAsync(query.ApplicationId, responses, supportEmail, isPublic, taskContext, cancellationToken);
+                    var failed = results.Where(result => !result.Successful).ToList();
+                    foreach (var result in failed)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} Review {result.ReviewId}: {result.FailureReason ?? "rejected"}");
+                    }
+
+                    return failed.Count == 0
+                        ? (0, $"Posted {results.Count} {(isPublic ? "public" : "private")} response(s).")
+                        : (1, $"{UiSymbols.Error} {failed.Count} of {results.Count} response(s) were rejected");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to respond to reviews: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
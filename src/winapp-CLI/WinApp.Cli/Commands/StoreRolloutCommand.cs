@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreRolloutCommand : Command
+{
+    // Options shared by the rollout subcommands to select the submission
+    public static Option<string> AppIdOption { get; }
+    public static Option<string> SubmissionIdOption { get; }
+
+    static StoreRolloutCommand()
+    {
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (e.g. 9NBLGGH4R315)",
+            Required = true
+        };
+        SubmissionIdOption = new Option<string>("--submission-id")
+        {
+            Description = "Submission to manage (default: the app's last published submission)"
+        };
+    }
+
+    public StoreRolloutCommand(StoreRolloutStatusCommand statusCommand, StoreRolloutSetCommand setCommand, StoreRolloutHaltCommand haltCommand, StoreRolloutFinalizeCommand finalizeCommand)
+        : base("rollout", "Manage the gradual package rollout of a Store submission")
+    {
+        Subcommands.Add(statusCommand);
+        Subcommands.Add(setCommand);
+        Subcommands.Add(haltCommand);
+        Subcommands.Add(finalizeCommand);
+    }
+
+    internal static void AddSubmissionOptions(Command command)
+    {
+        command.Options.Add(AppIdOption);
+        command.Options.Add(SubmissionIdOption);
+    }
+
+    internal static string Describe(StoreRolloutStatus status)
+    {
+        var rollout = status.Rollout;
+        if (!rollout.IsPackageRollout)
+        {
+            return $"Submission {status.SubmissionId} is {status.SubmissionStatus} and is not a gradual rollout";
+        }
+
+        var fallback = rollout.FallbackSubmissionId != null ? $"; other customers get submission {rollout.FallbackSubmissionId}" : "";
+        return $"Submission {status.SubmissionId} is {status.SubmissionStatus}, rolled out to {rollout.Percentage:0.##}% ({rollout.Status ?? "unknown rollout status"}){fallback}";
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreRolloutHaltCommand : Command
+{
+    public StoreRolloutHaltCommand()
+        : base("halt", "Halt a gradual rollout; customers without the new packages keep getting the previous submission's packages")
+    {
+        StoreRolloutCommand.AddSubmissionOptions(this);
+    }
+
+    public class Handler(IStoreRolloutService storeRolloutService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var appId = parseResult.GetRequiredValue(StoreRolloutCommand.AppIdOption);
+            var submissionId = parseResult.GetValue(StoreRolloutCommand.SubmissionIdOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Halting the rollout of {appId}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var status = await storeRolloutService.HaltAsync(appId, submissionId, taskContext, cancellationToken);
+                    return (0, StoreRolloutCommand.Describe(status));
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to halt the rollout: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreRolloutSetCommand : Command
+{
+    public static Argument<string> PercentageArgument { get; }
+
+    static StoreRolloutSetCommand()
+    {
+        PercentageArgument = new Argument<string>("percentage")
+        {
+            Description = "Share of customers that get the submission's packages, e.g. 25% or 12.5. It can only be increased"
+        };
+    }
+
+    public StoreRolloutSetCommand()
+        : base("set", "Change the percentage of customers a gradual rollout reaches")
+    {
+        Arguments.Add(PercentageArgument);
+        StoreRolloutCommand.AddSubmissionOptions(this);
+    }
+
+    public class Handler(IStoreRolloutService storeRolloutService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var percentageText = parseResult.GetRequiredValue(PercentageArgument);
+            var appId = parseResult.GetRequiredValue(StoreRolloutCommand.AppIdOption);
+            var submissionId = parseResult.GetValue(StoreRolloutCommand.SubmissionIdOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Updating the rollout of {appId}...", async (taskContext, cancellationToken) =>
+            {
+                double percentage;
+                try
+                {
+                    percentage = StoreRollout.ParsePercentage(percentageText);
+                }
+                catch (FormatException ex)
+                {
+                    return (1, $"{UiSymbols.Error} {ex.Message}");
+                }
+
+                try
+                {
+                    var status = await storeRolloutService.SetPercentageAsync(appId, submissionId, percentage, taskContext, cancellationToken);
+                    return (0, StoreRolloutCommand.Describe(status));
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to update the rollout: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreRolloutStatusCommand : Command
+{
+    public static Option<bool> WaitOption { get; }
+
+    static StoreRolloutStatusCommand()
+    {
+        WaitOption = new Option<bool>("--wait")
+        {
+            Description = "Poll until the Store has finished processing the submission (certification and publishing)"
+        };
+    }
+
+    public StoreRolloutStatusCommand()
+        : base("status", "Show the status and rollout percentage of a submission")
+    {
+        StoreRolloutCommand.AddSubmissionOptions(this);
+        Options.Add(WaitOption);
+    }
+
+    public class Handler(IStoreRolloutService storeRolloutService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var appId = parseResult.GetRequiredValue(StoreRolloutCommand.AppIdOption);
+            var submissionId = parseResult.GetValue(StoreRolloutCommand.SubmissionIdOption);
+            var wait = parseResult.GetValue(WaitOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Reading the rollout of {appId}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var status = await storeRolloutService.GetStatusAsync(appId, submissionId, wait, taskContext, cancellationToken);
+
+                    // Waiting is for scripts, so a submission the Store rejected fails the command
+                    var failed = wait && status.SubmissionStatus.EndsWith("Failed", StringComparison.OrdinalIgnoreCase);
+                    return (failed ? 1 : 0, failed ? $"{UiSymbols.Error} {StoreRolloutCommand.Describe(status)}" : StoreRolloutCommand.Describe(status));
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to read the rollout: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
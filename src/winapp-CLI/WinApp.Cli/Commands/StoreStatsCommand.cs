@@ -0,0 +1,112 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreStatsCommand : Command
+{
+    private const int DefaultDays = 30;
+
+    public static Argument<StoreStatsReport> ReportArgument { get; }
+    public static Option<string> AppIdOption { get; }
+    public static Option<DateOnly?> StartDateOption { get; }
+    public static Option<DateOnly?> EndDateOption { get; }
+    public static Option<StoreStatsAggregation> AggregationOption { get; }
+    public static Option<string> FilterOption { get; }
+    public static Option<StoreExportFormat> FormatOption { get; }
+    public static Option<FileInfo> OutputOption { get; }
+
+    static StoreStatsCommand()
+    {
+        ReportArgument = new Argument<StoreStatsReport>("report")
+        {
+            Description = "Report to export: acquisitions, health (crash and failure hits) or ratings"
+        };
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (e.g. 9NBLGGH4R315)",
+            Required = true
+        };
+        StartDateOption = new Option<DateOnly?>("--start-date")
+        {
+            Description = $"First day of the report, e.g. 2026-01-01 (default: {DefaultDays} days before the end date)"
+        };
+        EndDateOption = new Option<DateOnly?>("--end-date")
+        {
+            Description = "Last day of the report (default: today, UTC)"
+        };
+        AggregationOption = new Option<StoreStatsAggregation>("--aggregation")
+        {
+            Description = "Period each row covers: day, week or month",
+            DefaultValueFactory = (argumentResult) => StoreStatsAggregation.Day
+        };
+        FilterOption = new Option<string>("--filter")
+        {
+            Description = "OData filter passed to the analytics API, e.g. \"market eq 'US' and deviceType eq 'PC'\""
+        };
+        FormatOption = new Option<StoreExportFormat>("--format")
+        {
+            Description = "Output format: json or csv",
+            DefaultValueFactory = (argumentResult) => StoreExportFormat.Json
+        };
+        OutputOption = new Option<FileInfo>("--output")
+        {
+            Description = "Output file (defaults to <report>-<app-id>.<format> in the current directory)"
+        };
+    }
+
+    public StoreStatsCommand()
+        : base("stats", "Export acquisition, health or rating analytics from Partner Center to JSON or CSV")
+    {
+        Arguments.Add(ReportArgument);
+        Options.Add(AppIdOption);
+        Options.Add(StartDateOption);
+        Options.Add(EndDateOption);
+        Options.Add(AggregationOption);
+        Options.Add(FilterOption);
+        Options.Add(FormatOption);
+        Options.Add(OutputOption);
+    }
+
+    public class Handler(IStoreAnalyticsService storeAnalyticsService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var report = parseResult.GetValue(ReportArgument);
+            var appId = parseResult.GetRequiredValue(AppIdOption);
+            var endDate = parseResult.GetValue(EndDateOption) ?? DateOnly.FromDateTime(DateTime.UtcNow);
+            var startDate = parseResult.GetValue(StartDateOption) ?? endDate.AddDays(-DefaultDays);
+            var format = parseResult.GetValue(FormatOption);
+            var output = parseResult.GetValue(OutputOption)
+                ?? new FileInfo(Path.Combine(currentDirectoryProvider.GetCurrentDirectory(), $"{report.ToString().ToLowerInvariant()}-{appId}.{format.ToString().ToLowerInvariant()}"));
+
+            return await statusService.ExecuteWithStatusAsync($"Exporting {report.ToString().ToLowerInvariant()} for {appId}...", async (taskContext, cancellationToken) =>
+            {
+                if (endDate < startDate)
+                {
+                    return (1, $"{UiSymbols.Error} --end-date {endDate:yyyy-MM-dd} is before --start-date {startDate:yyyy-MM-dd}");
+                }
+
+                try
+                {
+                    var query = new StoreStatsQuery(appId, report, startDate, endDate, parseResult.GetValue(AggregationOption), parseResult.GetValue(FilterOption));
+                    var rows = await storeAnalyticsService.ExportAsync(query, format, output, taskContext, cancellationToken);
+
+                    return (0, $"Exported {rows:N0} rows ({startDate:yyyy-MM-dd} to {endDate:yyyy-MM-dd}) to {output.FullName}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to export {report.ToString().ToLowerInvariant()}: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Changelog;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Commands;
+
+internal class StoreSubmitCommand : Command
+{
+    public static Argument<FileInfo[]> PackagePathsArgument { get; }
+    public static Option<string> AppIdOption { get; }
+    public static Option<int> BlockSizeOption { get; }
+    public static Option<int> ConcurrencyOption { get; }
+    public static Option<bool> ReplacePendingOption { get; }
+    public static Option<bool> ReplacePackagesOption { get; }
+    public static Option<string> RolloutOption { get; }
+    public static Option<string> FlightOption { get; }
+    public static Option<FileInfo> ReleaseNotesOption { get; }
+    public static Option<bool> NoCommitOption { get; }
+    public static Option<bool> WaitOption { get; }
+    public static Option<bool> RestartOption { get; }
+
+    static StoreSubmitCommand()
+    {
+        PackagePathsArgument = new Argument<FileInfo[]>("package-paths")
+        {
+            Description = "Package files to submit (.msixupload, .msix, .msixbundle or their .appx equivalents)",
+            Arity = ArgumentArity.OneOrMore
+        };
+        PackagePathsArgument.AcceptExistingOnly();
+        AppIdOption = new Option<string>("--app-id")
+        {
+            Description = "Store ID of the app, as shown in Partner Center (e.g. 9NBLGGH4R315)",
+            Required = true
+        };
+        BlockSizeOption = new Option<int>("--block-size")
+        {
+            Description = "Upload block size in MB. Smaller blocks lose less progress when the connection drops (default: 8)",
+            DefaultValueFactory = (argumentResult) => BlockBlobUploadOptions.DefaultBlockSize / 1048576
+        };
+        ConcurrencyOption = new Option<int>("--concurrency")
+        {
+            Description = "Number of blocks uploaded at the same time (default: 4)",
+            DefaultValueFactory = (argumentResult) => 4
+        };
+        ReplacePendingOption = new Option<bool>("--replace-pending")
+        {
+            Description = "Delete a pending submission of the app that was created outside this command"
+        };
+        ReplacePackagesOption = new Option<bool>("--replace-packages")
+        {
+            Description = "Remove the packages of the previous submission instead of adding to them"
+        };
+        RolloutOption = new Option<string>("--rollout")
+        {
+            Description = "Publish the packages as a gradual rollout to this percentage of customers, e.g. 10%. Increase it later with 'store rollout set'"
+        };
+        FlightOption = new Option<string>("--flight")
+        {
+            Description = "Name or ID of a package flight to submit the packages to, instead of the app's public listing"
+        };
+        ReleaseNotesOption = new Option<FileInfo>("--release-notes")
+        {
+            Description = "Text file with the release notes to show on every language of the listing, e.g. from 'winapp changelog --format text'"
+        };
+        ReleaseNotesOption.AcceptExistingOnly();
+        NoCommitOption = new Option<bool>("--no-commit")
+        {
+            Description = "Upload the packages but leave the submission uncommitted, e.g. to review it in Partner Center first"
+        };
+        WaitOption = new Option<bool>("--wait")
+        {
+            Description = "Wait until the Store has finished processing the commit"
+        };
+        RestartOption = new Option<bool>("--restart")
+        {
+            Description = "Ignore progress saved by an earlier, interrupted run and start a new submission"
+        };
+    }
+
+    public StoreSubmitCommand()
+        : base("submit", "Upload packages to a new Store submission and commit it. Interrupted uploads resume when the command is run again")
+    {
+        Arguments.Add(PackagePathsArgument);
+        Options.Add(AppIdOption);
+        Options.Add(BlockSizeOption);
+        Options.Add(ConcurrencyOption);
+        Options.Add(ReplacePendingOption);
+        Options.Add(ReplacePackagesOption);
+        Options.Add(RolloutOption);
+        Options.Add(FlightOption);
+        Options.Add(ReleaseNotesOption);
+        Options.Add(NoCommitOption);
+        Options.Add(WaitOption);
+        Options.Add(RestartOption);
+    }
+
+    public class Handler(IStoreSubmissionService storeSubmissionService, IStoreFlightService storeFlightService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var packagePaths = parseResult.GetRequiredValue(PackagePathsArgument);
+            var appId = parseResult.GetRequiredValue(AppIdOption);
+            var blockSize = parseResult.GetValue(BlockSizeOption);
+            var concurrency = parseResult.GetValue(ConcurrencyOption);
+            var rollout = parseResult.GetValue(RolloutOption);
+            var flight = parseResult.GetValue(FlightOption);
+            var releaseNotesFile = parseResult.GetValue(ReleaseNotesOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Submitting {packagePaths.Length} package(s) to the Microsoft Store...", async (taskContext, cancellationToken) =>
+            {
+                if (blockSize <= 0 || blockSize > BlockBlobUploadOptions.MaxBlockSize / 1048576)
+                {
+                    return (1, $"{UiSymbols.Error} --block-size must be between 1 and {BlockBlobUploadOptions.MaxBlockSize / 1048576} MB");
+                }
+                if (concurrency <= 0)
+                {
+                    return (1, $"{UiSymbols.Error} --concurrency must be a positive number");
+                }
+
+                if (releaseNotesFile != null && flight != null)
+                {
+                    return (1, $"{UiSymbols.Error} --release-notes cannot be used with --flight: flight submissions have no listing");
+                }
+
+                double? rolloutPercentage = null;
+                if (rollout != null)
+                {
+                    try
+                    {
+                        rolloutPercentage = StoreRollout.ParsePercentage(rollout);
+                    }
+                    catch (FormatException ex)
+                    {
+                        return (1, $"{UiSymbols.Error} --rollout: {ex.Message}");
+                    }
+                }
+
+                try
+                {
+                    string? releaseNotes = null;
+                    if (releaseNotesFile != null)
+                    {
+                        releaseNotes = (await File.ReadAllTextAsync(releaseNotesFile.FullName, cancellationToken)).Trim();
+                        if (releaseNotes.Length > ReleaseNotes.StoreMaxLength)
+                        {
+                            return (1, $"{UiSymbols.Error} The release notes are {releaseNotes.Length:N0} characters; the Store accepts at most {ReleaseNotes.StoreMaxLength:N0}");
+                        }
+                    }
+
+                    string? flightId = null;
+                    if (flight != null)
+                    {
+                        flightId = (await storeFlightService.ResolveAsync(appId, flight, taskContext, cancellationToken)).FlightId;
+                        taskContext.AddDebugMessage($"Submitting to flight {flightId}");
+                    }
+
+                    var options = new StoreSubmitOptions
+                    {
+                        BlockSize = blockSize * 1048576,
+                        Concurrency = concurrency,
+                        ReplacePending = parseResult.GetValue(ReplacePendingOption),
+                        ReplacePackages = parseResult.GetValue(ReplacePackagesOption),
+                        FlightId = flightId,
+                        RolloutPercentage = rolloutPercentage,
+                        ReleaseNotes = releaseNotes,
+                        Commit = !parseResult.GetValue(NoCommitOption),
+                        Wait = parseResult.GetValue(WaitOption),
+                        Restart = parseResult.GetValue(RestartOption),
+                    };
+
+                    var result = await storeSubmissionService.SubmitAsync(appId, packagePaths, options, taskContext, cancellationToken);
+
+                    return (0, result.Status == "PendingCommit"
+                        ? $"Submission {result.SubmissionId} is uploaded and ready to commit."
+                        : $"Submission {result.SubmissionId} committed ({result.Status}).");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to submit to the Store: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
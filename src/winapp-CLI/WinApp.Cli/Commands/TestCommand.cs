@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+
+namespace WinApp.Cli.Commands;
+
+internal class TestCommand : Command
+{
+    public TestCommand(TestMatrixCommand testMatrixCommand)
+        : base("test", "Test the package on clean Windows installations")
+    {
+        Subcommands.Add(testMatrixCommand);
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class TestMatrixCommand : Command
+{
+    public static Argument<FileInfo> PackagePathArgument { get; }
+    public static Option<FileInfo[]> DependencyOption { get; }
+    public static Option<FileInfo> SmokeTestOption { get; }
+    public static Option<string[]> TargetOption { get; }
+    public static Option<int> ParallelOption { get; }
+    public static Option<bool> KeepMachinesOption { get; }
+    public static Option<FileInfo> ResultsOption { get; }
+
+    static TestMatrixCommand()
+    {
+        PackagePathArgument = new Argument<FileInfo>("package-path")
+        {
+            Description = "Path to the signed .msix/.appx package (or bundle) to test"
+        };
+        PackagePathArgument.AcceptExistingOnly();
+        DependencyOption = new Option<FileInfo[]>("--dependency")
+        {
+            Description = "Framework package to install with the package, such as the Windows App Runtime; repeat for several",
+            AllowMultipleArgumentsPerToken = true
+        };
+        DependencyOption.AcceptExistingOnly();
+        SmokeTestOption = new Option<FileInfo>("--smoke-test")
+        {
+            Description = "PowerShell script to run in each machine after installing (default: testMatrix.smokeTest in winapp.yaml)"
+        };
+        SmokeTestOption.AcceptExistingOnly();
+        TargetOption = new Option<string[]>("--target")
+        {
+            Description = "Name of a target in winapp.yaml to test; repeat for several (default: every target)",
+            AllowMultipleArgumentsPerToken = true
+        };
+        ParallelOption = new Option<int>("--parallel")
+        {
+            Description = "Number of targets to test at the same time",
+            DefaultValueFactory = (argumentResult) => 1
+        };
+        KeepMachinesOption = new Option<bool>("--keep-machines")
+        {
+            Description = "Keep the virtual machines after testing, to investigate failures; delete them yourself afterwards"
+        };
+        ResultsOption = new Option<FileInfo>("--results")
+        {
+            Description = "Write the results as JUnit XML to this file, for CI test reports"
+        };
+    }
+
+    public TestMatrixCommand()
+        : base("matrix", "Install the package and run its smoke test in a new virtual machine for each Windows version of the test matrix in winapp.yaml")
+    {
+        Arguments.Add(PackagePathArgument);
+        Options.Add(DependencyOption);
+        Options.Add(SmokeTestOption);
+        Options.Add(TargetOption);
+        Options.Add(ParallelOption);
+        Options.Add(KeepMachinesOption);
+        Options.Add(ResultsOption);
+    }
+
+    public class Handler(ITestMatrixService testMatrixService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var options = new TestMatrixOptions
+            {
+                Package = parseResult.GetRequiredValue(PackagePathArgument),
+                Dependencies = parseResult.GetValue(DependencyOption) ?? [],
+                SmokeTest = parseResult.GetValue(SmokeTestOption),
+                Targets = parseResult.GetValue(TargetOption) ?? [],
+                Parallel = parseResult.GetValue(ParallelOption),
+                KeepMachines = parseResult.GetValue(KeepMachinesOption)
+            };
+            var resultsFile = parseResult.GetValue(ResultsOption);
+
+            return await statusService.ExecuteWithStatusAsync("Testing the package on the test matrix...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var results = await testMatrixService.RunAsync(options, taskContext, cancellationToken);
+
+                    foreach (var result in results.Where(r => !r.Passed))
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Error} {result.Target} ({result.Provider}): {TestMatrixService.FormatStage(result.FailedStage!.Value)} failed: {result.Error}");
+                    }
+                    foreach (var machine in results.Select(r => r.Machine).OfType<string>())
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Bullet} Kept {machine}");
+                    }
+
+                    if (resultsFile != null)
+                    {
+                        resultsFile.Directory?.Create();
+                        TestMatrixService.CreateJUnitReport(results).Save(resultsFile.FullName);
+                        taskContext.AddStatusMessage($"{UiSymbols.Files} Results: {resultsFile.FullName}");
+                    }
+
+                    var failed = results.Count(r => !r.Passed);
+                    return failed > 0
+                        ? (1, $"{UiSymbols.Error} {failed} of {results.Count} target(s) failed")
+                        : (0, $"{UiSymbols.Check} The package passed on {results.Count} target(s)");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to test the package: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
@@ -14,6 +14,8 @@ internal class UpdateCommand : Command
     public UpdateCommand() : base("update", "Update packages in winapp.yaml and install/update build tools in cache")
     {
         Options.Add(InitCommand.SetupSdksOption);
+        Options.Add(RestoreCommand.AcceptLicensesOption);
+        Options.Add(RestoreCommand.AcceptLicenseOption);
     }
 
     public class Handler(
@@ -23,11 +25,13 @@ internal class UpdateCommand : Command
         IPackageInstallationService packageInstallationService,
         IBuildToolsService buildToolsService,
         IWorkspaceSetupService workspaceSetupService,
+        IPackageLicenseService packageLicenseService,
         IStatusService statusService) : AsynchronousCommandLineAction
     {
         public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
         {
             var setupSdks = parseResult.GetValue(InitCommand.SetupSdksOption) ?? SdkInstallMode.Stable;
+            var licenseConsent = RestoreCommand.GetLicenseConsent(parseResult);
 
             return await statusService.ExecuteWithStatusAsync("Updating packages and build tools...", async (taskContext, cancellationToken) =>
             {
@@ -49,7 +53,7 @@ internal class UpdateCommand : Command
                         {
                             taskContext.AddStatusMessage($"{UiSymbols.Package} Found winapp.yaml with {config.Packages.Count} packages, checking for updates...");
 
-                            var updatedConfig = new WinappConfig();
+                            var updatedConfig = config.WithoutPackages();
                             bool hasUpdates = false;
                             await taskContext.AddSubTaskAsync("Checking for package updates", async (taskContext, cancellationToken) =>
                             {
@@ -104,6 +108,19 @@ internal class UpdateCommand : Command
                                     cancellationToken: cancellationToken
                                 );
 
+                                var pendingLicenses = await packageLicenseService.EnsureAcceptedAsync(globalWinappDir.CreateSubdirectory("packages"), installedVersions, licenseConsent, taskContext, cancellationToken);
+                                if (pendingLicenses.Count > 0)
+                                {
+                                    // Pin the previous versions again, so the update finds the new ones when run again
+                                    var restoredConfig = configService.Load();
+                                    foreach (var package in config.Packages)
+                                    {
+                                        restoredConfig.SetVersion(package.Name, package.Version);
+                                    }
+                                    configService.Save(restoredConfig);
+                                    return (1, PackageLicenseService.ReportPending(pendingLicenses, taskContext));
+                                }
+
                                 taskContext.AddStatusMessage($"{UiSymbols.Check} Package installation completed");
                             }
                             else
@@ -0,0 +1,142 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class ValidateCommand : Command
+{
+    public static Option<FileInfo> ManifestOption { get; }
+    public static Option<AccessibilitySeverity> FailOnOption { get; }
+    public static Option<FileInfo> BaselineOption { get; }
+    public static Option<bool> WriteBaselineOption { get; }
+    public static Option<ValidationFormat> FormatOption { get; }
+    public static Option<bool> AllowScriptAnalyzersOption { get; }
+
+    static ValidateCommand()
+    {
+        ManifestOption = new Option<FileInfo>("--manifest")
+        {
+            Description = "Path to the appxmanifest.xml to validate (default: found from the current directory)"
+        };
+        ManifestOption.AcceptExistingOnly();
+        FailOnOption = new Option<AccessibilitySeverity>("--fail-on")
+        {
+            Description = "Exit with an error when a finding of this severity or higher isn't in the baseline (info, warning or error)",
+            DefaultValueFactory = (argumentResult) => AccessibilitySeverity.Error,
+        };
+        BaselineOption = new Option<FileInfo>("--baseline")
+        {
+            Description = "Baseline of recorded findings, which don't fail validation (default: winapp-baseline.json next to winapp.yaml)"
+        };
+        WriteBaselineOption = new Option<bool>("--write-baseline")
+        {
+            Description = "Record the current findings in the baseline, so only findings added later fail validation"
+        };
+        FormatOption = new Option<ValidationFormat>("--format")
+        {
+            Description = "Output format: text (one line per new finding) or json (every finding, with whether the baseline records it)",
+            DefaultValueFactory = (argumentResult) => ValidationFormat.Text
+        };
+        AllowScriptAnalyzersOption = new Option<bool>("--allow-script-analyzers")
+        {
+            Description = "Also run the .ps1, .js and executable analyzers of winapp.yaml, which can do anything you can; without it, only .wasm analyzers run"
+        };
+    }
+
+    public ValidateCommand()
+        : base("validate", "Run every manifest check (schema, logos, entry point, a11y, splash, scales and schema versions) and fail on findings that aren't in the baseline, so large projects can adopt the checks and clean up gradually")
+    {
+        Options.Add(ManifestOption);
+        Options.Add(FailOnOption);
+        Options.Add(BaselineOption);
+        Options.Add(WriteBaselineOption);
+        Options.Add(FormatOption);
+        Options.Add(AllowScriptAnalyzersOption);
+        Options.Add(AnalyzeCommand.SarifOption);
+    }
+
+    public class Handler(IValidationService validationService, ISarifService sarifService, ICurrentDirectoryProvider currentDirectoryProvider, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var manifest = parseResult.GetValue(ManifestOption);
+            var failOn = parseResult.GetRequiredValue(FailOnOption);
+            var baseline = parseResult.GetValue(BaselineOption);
+            var writeBaseline = parseResult.GetValue(WriteBaselineOption);
+            var format = parseResult.GetValue(FormatOption);
+            var allowScriptAnalyzers = parseResult.GetValue(AllowScriptAnalyzersOption);
+            var sarif = parseResult.GetValue(AnalyzeCommand.SarifOption);
+
+            return await statusService.ExecuteWithStatusAsync("Validating the manifest...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider);
+                    if (manifest == null)
+                    {
+                        return (1, $"{UiSymbols.Error} No appxmanifest.xml found in the current directory or its parents; pass --manifest");
+                    }
+
+                    baseline ??= validationService.DefaultBaselinePath;
+                    var findings = await validationService.ValidateAsync(manifest, baseline, allowScriptAnalyzers, taskContext, cancellationToken);
+
+                    if (writeBaseline)
+                    {
+                        await validationService.WriteBaselineAsync(baseline, manifest, findings, cancellationToken);
+                        return (0, $"{UiSymbols.Save} Recorded {findings.Count} finding(s) of {manifest.Name} in {baseline.FullName}");
+                    }
+
+                    var result = await validationService.CompareAsync(baseline, manifest, findings, cancellationToken);
+                    if (sarif != null)
+                    {
+                        static IEnumerable<SarifFinding> ToSarif(IEnumerable<ValidationFinding> matched, bool baselined) =>
+                            matched.Select(f => new SarifFinding(f.Severity, f.Rule, f.Subject, f.Element, f.Message, baselined));
+                        await sarifService.WriteAsync(sarif, manifest, [.. ToSarif(result.New, false), .. ToSarif(result.Baselined, true)], taskContext, cancellationToken);
+                    }
+                    if (format == ValidationFormat.Json)
+                    {
+                        foreach (var line in ValidationService.ToJson(result, failOn).Split('\n'))
+                        {
+                            taskContext.AddStatusMessage(line);
+                        }
+                    }
+                    else
+                    {
+                        foreach (var finding in result.New.OrderByDescending(f => f.Severity))
+                        {
+                            var symbol = finding.Severity switch
+                            {
+                                AccessibilitySeverity.Error => UiSymbols.Error,
+                                AccessibilitySeverity.Warning => UiSymbols.Warning,
+                                _ => UiSymbols.Info,
+                            };
+                            taskContext.AddStatusMessage($"{symbol} [{finding.Rule}] {finding.Element}: {finding.Message}");
+                        }
+                        if (result.Fixed > 0)
+                        {
+                            taskContext.AddStatusMessage($"{UiSymbols.Note} {result.Fixed} recorded finding(s) are fixed; run 'winapp validate --write-baseline' to tighten the baseline");
+                        }
+                    }
+
+                    var counts = string.Join(", ", Enum.GetValues<AccessibilitySeverity>().Reverse()
+                        .Select(s => $"{result.New.Count(f => f.Severity == s)} new {s.ToString().ToLowerInvariant()}(s)"))
+                        + (result.Baselined.Count > 0 ? $" ({result.Baselined.Count} in the baseline)" : "");
+                    return result.New.Any(f => f.Severity >= failOn)
+                        ? (1, $"{UiSymbols.Error} Found {counts} in {manifest.Name}")
+                        : (0, result.New.Count == 0 ? $"{UiSymbols.Check} No new findings in {manifest.Name}{(result.Baselined.Count > 0 ? $" ({result.Baselined.Count} in the baseline)" : "")}" : $"Found {counts} in {manifest.Name}");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to validate the manifest: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
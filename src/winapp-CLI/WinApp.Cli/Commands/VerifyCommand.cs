@@ -0,0 +1,87 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.CommandLine;
+using System.CommandLine.Invocation;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Commands;
+
+internal class VerifyCommand : Command
+{
+    public static Argument<FileInfo> PackagePathArgument { get; }
+    public static Option<bool> RequireSignatureOption { get; }
+
+    static VerifyCommand()
+    {
+        PackagePathArgument = new Argument<FileInfo>("package-path")
+        {
+            Description = "Path to the .msix/.appx package (or bundle) to verify"
+        };
+        PackagePathArgument.AcceptExistingOnly();
+        RequireSignatureOption = new Option<bool>("--require-signature")
+        {
+            Description = "Fail if the package is not signed"
+        };
+    }
+
+    public VerifyCommand()
+        : base("verify", "Verify a package's block map and signature without relying on Windows APIs")
+    {
+        Arguments.Add(PackagePathArgument);
+        Options.Add(RequireSignatureOption);
+    }
+
+    public class Handler(INativePackagingService nativePackagingService, IStatusService statusService) : AsynchronousCommandLineAction
+    {
+        public override async Task<int> InvokeAsync(ParseResult parseResult, CancellationToken cancellationToken = default)
+        {
+            var packagePath = parseResult.GetRequiredValue(PackagePathArgument);
+            var requireSignature = parseResult.GetValue(RequireSignatureOption);
+
+            return await statusService.ExecuteWithStatusAsync($"Verifying {packagePath.Name}...", async (taskContext, cancellationToken) =>
+            {
+                try
+                {
+                    var result = await nativePackagingService.VerifyAsync(packagePath, taskContext, cancellationToken);
+
+                    taskContext.AddStatusMessage($"{UiSymbols.Files} {result.VerifiedFileCount} payload files match the block map");
+                    if (result.Signer != null)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Lock} Signed by {result.Signer.Subject} ({result.Signer.Thumbprint}), {result.Signer.DigestAlgorithm}");
+                        taskContext.AddStatusMessage(result.Signer.Timestamp is DateTimeOffset timestamp
+                            ? $"{UiSymbols.Note} Timestamped {timestamp:u}"
+                            : $"{UiSymbols.Note} Not timestamped; the signature is valid until {result.Signer.NotAfter:d}");
+                    }
+
+                    foreach (var warning in result.Warnings)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Warning} {warning}");
+                    }
+                    foreach (var error in result.Errors)
+                    {
+                        taskContext.AddStatusMessage($"{UiSymbols.Error} {error}");
+                    }
+
+                    if (!result.IsValid)
+                    {
+                        return (1, $"{UiSymbols.Error} Package verification failed with {result.Errors.Count} error(s).");
+                    }
+
+                    if (requireSignature && result.Signer == null)
+                    {
+                        return (1, $"{UiSymbols.Error} Package is not signed (--require-signature).");
+                    }
+
+                    return (0, "Package verified successfully.");
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+                    return (1, $"{UiSymbols.Error} Failed to verify package: {ex.Message}");
+                }
+            }, cancellationToken);
+        }
+    }
+}
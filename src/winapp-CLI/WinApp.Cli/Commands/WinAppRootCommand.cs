@@ -19,6 +19,11 @@ internal class WinAppRootCommand : RootCommand
         Description = "Suppress progress messages"
     };
 
+    internal static Option<bool> FipsOption = new Option<bool>("--fips")
+    {
+        Description = "Restrict hashing and signing to FIPS 140 validated primitives, and fail operations that cannot comply"
+    };
+
     internal static readonly Option<bool> CliSchemaOption = new("--cli-schema")
     {
         Description = "Outputs the CLI command schema in JSON format",
@@ -46,9 +51,33 @@ internal class WinAppRootCommand : RootCommand
         ManifestCommand manifestCommand,
         UpdateCommand updateCommand,
         CreateDebugIdentityCommand createDebugIdentityCommand,
+        DevCommand devCommand,
         GetWinappPathCommand getWinappPathCommand,
         CertCommand certCommand,
         SignCommand signCommand,
+        VerifyCommand verifyCommand,
+        BundleCommand bundleCommand,
+        AuditCommand auditCommand,
+        AnalyzeCommand analyzeCommand,
+        ExplainCommand explainCommand,
+        ValidateCommand validateCommand,
+        MigrateCommand migrateCommand,
+        ExportCommand exportCommand,
+        StoreCommand storeCommand,
+        DistributeCommand distributeCommand,
+        TestCommand testCommand,
+        ChangelogCommand changelogCommand,
+        AffectedCommand affectedCommand,
+        CleanupCommand cleanupCommand,
+        ConfigCommand configCommand,
+        ListCommand listCommand,
+        OpenCommand openCommand,
+        StateCommand stateCommand,
+        CrashesCommand crashesCommand,
+        LogsCommand logsCommand,
+        LicenseCommand licenseCommand,
+        RemoteConfigCommand remoteConfigCommand,
+        RestartCommand restartCommand,
         ToolCommand toolCommand) : base("Setup Windows SDK and Windows App SDK for use in your app, create MSIX packages, generate manifests and certificates, and use build tools.")
     {
         Subcommands.Add(initCommand);
@@ -57,9 +86,33 @@ internal class WinAppRootCommand : RootCommand
         Subcommands.Add(manifestCommand);
         Subcommands.Add(updateCommand);
         Subcommands.Add(createDebugIdentityCommand);
+        Subcommands.Add(devCommand);
         Subcommands.Add(getWinappPathCommand);
         Subcommands.Add(certCommand);
         Subcommands.Add(signCommand);
+        Subcommands.Add(verifyCommand);
+        Subcommands.Add(bundleCommand);
+        Subcommands.Add(auditCommand);
+        Subcommands.Add(analyzeCommand);
+        Subcommands.Add(explainCommand);
+        Subcommands.Add(validateCommand);
+        Subcommands.Add(migrateCommand);
+        Subcommands.Add(exportCommand);
+        Subcommands.Add(storeCommand);
+        Subcommands.Add(distributeCommand);
+        Subcommands.Add(testCommand);
+        Subcommands.Add(changelogCommand);
+        Subcommands.Add(affectedCommand);
+        Subcommands.Add(cleanupCommand);
+        Subcommands.Add(configCommand);
+        Subcommands.Add(listCommand);
+        Subcommands.Add(openCommand);
+        Subcommands.Add(stateCommand);
+        Subcommands.Add(crashesCommand);
+        Subcommands.Add(logsCommand);
+        Subcommands.Add(licenseCommand);
+        Subcommands.Add(remoteConfigCommand);
+        Subcommands.Add(restartCommand);
         Subcommands.Add(toolCommand);
 
         Options.Add(CliSchemaOption);
@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net.Http.Headers;
+using WinApp.Cli.BuildCache;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Crashes;
+
+/// <summary>
+/// Posts minidumps as multipart forms with the fields Crashpad sends, which crash reporting services such as
+/// Sentry and BugSplat accept, and symbol files to a symbol store
+/// </summary>
+/// <param name="token">Sent as a bearer token when set</param>
+internal sealed class CrashUploader(Uri endpoint, Uri? symbolsEndpoint, string? token, HttpClient http)
+{
+    public const string TokenVariable = "WINAPP_CRASHES_TOKEN";
+
+    /// <summary>
+    /// Form field of the minidump in Crashpad's uploads
+    /// </summary>
+    public const string MinidumpField = "upload_file_minidump";
+
+    public const string SymbolField = "symbol_file";
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public bool HasSymbolStore => symbolsEndpoint != null;
+
+    /// <returns>What the service answered, such as the id of the crash report, or null for an empty answer</returns>
+    public async Task<string?> UploadDumpAsync(FileInfo dump, string product, string version, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        using var response = await Retry.SendAsync(http, () => CreateRequest(endpoint, MinidumpField, dump, product, version), onRetry, cancellationToken);
+        await RemoteBuildCacheHttp.EnsureSuccessAsync(response, "Uploading to", cancellationToken);
+        var answer = (await response.Content.ReadAsStringAsync(cancellationToken)).Trim();
+        return answer.Length == 0 ? null : answer;
+    }
+
+    public async Task UploadSymbolFileAsync(FileInfo file, string product, string version, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var uri = symbolsEndpoint ?? throw new InvalidOperationException("No symbols endpoint is configured; set 'symbolsEndpoint' under 'crashes:' in winapp.yaml");
+        using var response = await Retry.SendAsync(http, () => CreateRequest(uri, SymbolField, file, product, version), onRetry, cancellationToken);
+        await RemoteBuildCacheHttp.EnsureSuccessAsync(response, "Uploading to", cancellationToken);
+    }
+
+    private HttpRequestMessage CreateRequest(Uri uri, string field, FileInfo file, string product, string version)
+    {
+        var fileContent = new StreamContent(file.OpenRead());
+        fileContent.Headers.ContentType = new MediaTypeHeaderValue("application/octet-stream");
+        var form = new MultipartFormDataContent
+        {
+            { new StringContent(product), "product" },
+            { new StringContent(version), "version" },
+            { fileContent, field, file.Name }
+        };
+        var request = new HttpRequestMessage(HttpMethod.Post, uri) { Content = form };
+        if (token != null)
+        {
+            request.Headers.Authorization = new AuthenticationHeaderValue("Bearer", token);
+        }
+        return request;
+    }
+}
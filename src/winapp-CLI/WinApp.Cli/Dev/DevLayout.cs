@@ -0,0 +1,87 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Dev;
+
+/// <summary>
+/// Copy of a build output folder that 'winapp dev' registers and runs, so the build can overwrite its output while
+/// the app runs. Only the files that changed since the last sync are copied again
+/// </summary>
+internal sealed class DevLayout(DirectoryInfo source, DirectoryInfo layout)
+{
+    /// <summary>
+    /// Folders of a cargo target folder that hold intermediate build files rather than the app
+    /// </summary>
+    internal static readonly string[] ExcludedFolders = ["deps", "build", "incremental", ".fingerprint", "examples"];
+
+    internal static readonly string[] ExcludedExtensions = [".d", ".rlib", ".rmeta", ".cargo-lock"];
+
+    private readonly Dictionary<string, (long Length, DateTime LastWriteTimeUtc)> _synced = new(StringComparer.OrdinalIgnoreCase);
+
+    public DirectoryInfo Source => source;
+
+    public DirectoryInfo Folder => layout;
+
+    internal static bool IsExcluded(string relativePath)
+    {
+        var firstSegment = relativePath.Split(Path.DirectorySeparatorChar, Path.AltDirectorySeparatorChar)[0];
+        return ExcludedFolders.Contains(firstSegment, StringComparer.OrdinalIgnoreCase)
+            || ExcludedExtensions.Contains(Path.GetExtension(relativePath), StringComparer.OrdinalIgnoreCase);
+    }
+
+    /// <summary>
+    /// Compares the source folder with what the last <see cref="Apply"/> copied
+    /// </summary>
+    public LayoutChanges FindChanges()
+    {
+        var current = new Dictionary<string, FileInfo>(StringComparer.OrdinalIgnoreCase);
+        if (source.Exists)
+        {
+            foreach (var file in source.EnumerateFiles("*", SearchOption.AllDirectories))
+            {
+                var relativePath = Path.GetRelativePath(source.FullName, file.FullName);
+                if (!IsExcluded(relativePath))
+                {
+                    current[relativePath] = file;
+                }
+            }
+        }
+
+        var changed = current
+            .Where(entry => !_synced.TryGetValue(entry.Key, out var synced) || synced != (entry.Value.Length, entry.Value.LastWriteTimeUtc))
+            .Select(entry => entry.Key)
+            .Order(StringComparer.OrdinalIgnoreCase)
+            .ToList();
+        var deleted = _synced.Keys.Where(path => !current.ContainsKey(path)).Order(StringComparer.OrdinalIgnoreCase).ToList();
+        return new LayoutChanges(changed, deleted);
+    }
+
+    /// <summary>
+    /// Copies the changed files into the layout and deletes the ones that were removed from the source. Files the
+    /// layout got from elsewhere, such as the manifest's assets, are left alone
+    /// </summary>
+    public void Apply(LayoutChanges changes)
+    {
+        foreach (var relativePath in changes.Changed)
+        {
+            var from = new FileInfo(Path.Combine(source.FullName, relativePath));
+            var to = new FileInfo(Path.Combine(layout.FullName, relativePath));
+            to.Directory?.Create();
+            from.CopyTo(to.FullName, overwrite: true);
+            _synced[relativePath] = (from.Length, from.LastWriteTimeUtc);
+        }
+        foreach (var relativePath in changes.Deleted)
+        {
+            File.Delete(Path.Combine(layout.FullName, relativePath));
+            _synced.Remove(relativePath);
+        }
+    }
+}
+
+/// <summary>
+/// Files of the source folder, relative to it, that were added or changed, and that were deleted
+/// </summary>
+internal sealed record LayoutChanges(IReadOnlyList<string> Changed, IReadOnlyList<string> Deleted)
+{
+    public bool IsEmpty => Changed.Count == 0 && Deleted.Count == 0;
+}
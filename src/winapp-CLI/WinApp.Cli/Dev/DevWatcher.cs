@@ -0,0 +1,162 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Dev;
+
+internal enum DevChangeKind
+{
+    Ignored,
+    Payload,
+    Manifest,
+}
+
+/// <summary>
+/// Watches the build output and the manifest with its assets for 'winapp dev --watch', and reports a batch of changes
+/// once the build has stopped writing
+/// </summary>
+internal sealed class DevWatcher : IDisposable
+{
+    private static readonly string[] ManifestAssetExtensions = [".png", ".jpg", ".jpeg", ".gif", ".ico", ".svg"];
+
+    private readonly DirectoryInfo _inputFolder;
+    private readonly FileInfo _manifest;
+    private readonly DirectoryInfo _winappDir;
+    private readonly List<FileSystemWatcher> _watchers = [];
+    private readonly object _lock = new();
+    private TaskCompletionSource _changed = new(TaskCreationOptions.RunContinuationsAsynchronously);
+    private bool _manifestChanged;
+
+    public DevWatcher(DirectoryInfo inputFolder, FileInfo manifest, DirectoryInfo winappDir)
+    {
+        _inputFolder = inputFolder;
+        _manifest = manifest;
+        _winappDir = winappDir;
+
+        _watchers.Add(CreateWatcher(inputFolder.FullName));
+        var manifestFolder = manifest.DirectoryName!;
+        if (!IsUnder(manifestFolder, inputFolder.FullName))
+        {
+            _watchers.Add(CreateWatcher(manifestFolder));
+        }
+    }
+
+    /// <summary>
+    /// Whether a change to <paramref name="path"/> changes the files of the app, the manifest or its images, or
+    /// neither. The .winapp folder is ignored, since 'winapp dev' writes to it
+    /// </summary>
+    internal static DevChangeKind Classify(string path, DirectoryInfo inputFolder, FileInfo manifest, DirectoryInfo winappDir)
+    {
+        var fullPath = Path.GetFullPath(path);
+        if (IsUnder(fullPath, winappDir.FullName))
+        {
+            return DevChangeKind.Ignored;
+        }
+        if (string.Equals(fullPath, manifest.FullName, StringComparison.OrdinalIgnoreCase))
+        {
+            return DevChangeKind.Manifest;
+        }
+        if (IsUnder(fullPath, inputFolder.FullName))
+        {
+            return DevLayout.IsExcluded(Path.GetRelativePath(inputFolder.FullName, fullPath)) ? DevChangeKind.Ignored : DevChangeKind.Payload;
+        }
+        if (IsUnder(fullPath, manifest.DirectoryName!)
+            && ManifestAssetExtensions.Contains(Path.GetExtension(fullPath), StringComparer.OrdinalIgnoreCase))
+        {
+            return DevChangeKind.Manifest;
+        }
+        return DevChangeKind.Ignored;
+    }
+
+    /// <summary>
+    /// Waits for a change, then until nothing has changed for <paramref name="quietPeriod"/>, and returns whether the
+    /// manifest or its assets changed. Changes made while the caller handled the last batch end the wait at once
+    /// </summary>
+    public async Task<bool> WaitForChangesAsync(TimeSpan quietPeriod, CancellationToken cancellationToken)
+    {
+        Task changed;
+        lock (_lock)
+        {
+            changed = _changed.Task;
+        }
+        await changed.WaitAsync(cancellationToken);
+
+        while (true)
+        {
+            lock (_lock)
+            {
+                _changed = new(TaskCreationOptions.RunContinuationsAsynchronously);
+                changed = _changed.Task;
+            }
+            try
+            {
+                await changed.WaitAsync(quietPeriod, cancellationToken);
+            }
+            catch (TimeoutException)
+            {
+                break;
+            }
+        }
+
+        lock (_lock)
+        {
+            var manifestChanged = _manifestChanged;
+            _manifestChanged = false;
+            return manifestChanged;
+        }
+    }
+
+    public void Dispose()
+    {
+        foreach (var watcher in _watchers)
+        {
+            watcher.Dispose();
+        }
+    }
+
+    private FileSystemWatcher CreateWatcher(string folder)
+    {
+        var watcher = new FileSystemWatcher(folder)
+        {
+            IncludeSubdirectories = true,
+            NotifyFilter = NotifyFilters.FileName | NotifyFilters.DirectoryName | NotifyFilters.LastWrite | NotifyFilters.Size,
+            // Builds write a lot of files at once
+            InternalBufferSize = 64 * 1024,
+        };
+        watcher.Changed += (_, e) => OnChanged(e.FullPath);
+        watcher.Created += (_, e) => OnChanged(e.FullPath);
+        watcher.Deleted += (_, e) => OnChanged(e.FullPath);
+        watcher.Renamed += (_, e) =>
+        {
+            OnChanged(e.OldFullPath);
+            OnChanged(e.FullPath);
+        };
+        // Dropped events mean the payload has to be compared again
+        watcher.Error += (_, _) => Signal(DevChangeKind.Payload);
+        watcher.EnableRaisingEvents = true;
+        return watcher;
+    }
+
+    private void OnChanged(string path) => Signal(Classify(path, _inputFolder, _manifest, _winappDir));
+
+    private void Signal(DevChangeKind kind)
+    {
+        if (kind == DevChangeKind.Ignored)
+        {
+            return;
+        }
+
+        lock (_lock)
+        {
+            _manifestChanged |= kind == DevChangeKind.Manifest;
+            _changed.TrySetResult();
+        }
+    }
+
+    private static bool IsUnder(string path, string folder)
+    {
+        var relativePath = Path.GetRelativePath(folder, path);
+        return relativePath != ".."
+            && !relativePath.StartsWith(".." + Path.DirectorySeparatorChar, StringComparison.Ordinal)
+            && !Path.IsPathRooted(relativePath);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Writes .appinstaller files, which let App Installer install a package from a web server and keep it updated
+/// </summary>
+internal static class AppInstallerFile
+{
+    public const string Extension = ".appinstaller";
+    public const string ContentType = "application/appinstaller";
+
+    private const string Namespace = "http://schemas.microsoft.com/appx/appinstaller/2018";
+
+    /// <param name="appInstallerUri">Where the .appinstaller file itself is hosted; App Installer checks it for updates</param>
+    /// <param name="packageUri">Where the package is hosted</param>
+    /// <param name="hoursBetweenUpdateChecks">How often the app checks for updates when it is launched; 0 checks on every launch</param>
+    public static string Create(DistributionPackage package, Uri appInstallerUri, Uri packageUri, int hoursBetweenUpdateChecks)
+    {
+        var builder = new StringBuilder();
+        var settings = new XmlWriterSettings { Indent = true, Encoding = new UTF8Encoding(false), NewLineChars = "\r\n" };
+        using (var writer = XmlWriter.Create(new StringWriterWithEncoding(builder, settings.Encoding), settings))
+        {
+            writer.WriteStartDocument();
+            writer.WriteStartElement("AppInstaller", Namespace);
+            writer.WriteAttributeString("Version", package.Version);
+            writer.WriteAttributeString("Uri", appInstallerUri.AbsoluteUri);
+
+            writer.WriteStartElement(package.IsBundle ? "MainBundle" : "MainPackage", Namespace);
+            writer.WriteAttributeString("Name", package.Name);
+            writer.WriteAttributeString("Publisher", package.Publisher);
+            writer.WriteAttributeString("Version", package.Version);
+            if (package.ProcessorArchitecture != null)
+            {
+                writer.WriteAttributeString("ProcessorArchitecture", package.ProcessorArchitecture);
+            }
+            writer.WriteAttributeString("Uri", packageUri.AbsoluteUri);
+            writer.WriteEndElement();
+
+            writer.WriteStartElement("UpdateSettings", Namespace);
+            writer.WriteStartElement("OnLaunch", Namespace);
+            writer.WriteAttributeString("HoursBetweenUpdateChecks", hoursBetweenUpdateChecks.ToString(CultureInfo.InvariantCulture));
+            writer.WriteEndElement();
+            writer.WriteEndElement();
+
+            writer.WriteEndElement();
+            writer.WriteEndDocument();
+        }
+
+        return builder.ToString();
+    }
+
+    /// <summary>
+    /// File names of the packages an .appinstaller file installs: the main package or bundle, then its optional,
+    /// related and dependency packages
+    /// </summary>
+    public static List<string> ReadPackageFileNames(string xml)
+    {
+        var document = new XmlDocument();
+        document.LoadXml(xml);
+        var names = new List<string>();
+        foreach (XmlElement element in document.SelectNodes("//*[local-name()='MainPackage' or local-name()='MainBundle' or local-name()='Package' or local-name()='Bundle']")!)
+        {
+            var uri = element.GetAttribute("Uri");
+            if (string.IsNullOrEmpty(uri))
+            {
+                continue;
+            }
+            var path = Uri.TryCreate(uri, UriKind.Absolute, out var absolute) ? Uri.UnescapeDataString(absolute.AbsolutePath) : uri;
+            var name = Path.GetFileName(path.Replace('\\', '/').TrimEnd('/'));
+            if (!string.IsNullOrEmpty(name) && !names.Contains(name, StringComparer.OrdinalIgnoreCase))
+            {
+                names.Add(name);
+            }
+        }
+        return names;
+    }
+
+    // StringWriter reports UTF-16, which would end up in the XML declaration
+    private sealed class StringWriterWithEncoding(StringBuilder builder, Encoding encoding) : StringWriter(builder, CultureInfo.InvariantCulture)
+    {
+        public override Encoding Encoding => encoding;
+    }
+}
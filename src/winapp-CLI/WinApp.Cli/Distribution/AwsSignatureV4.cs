@@ -0,0 +1,117 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Security.Cryptography;
+using System.Text;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// AWS access key used to upload to S3
+/// </summary>
+/// <param name="SessionToken">Token of temporary credentials, e.g. from an assumed role or GitHub OIDC</param>
+internal sealed record AwsCredentials(string AccessKeyId, string SecretAccessKey, string? SessionToken)
+{
+    public const string AccessKeyIdVariable = "AWS_ACCESS_KEY_ID";
+    public const string SecretAccessKeyVariable = "AWS_SECRET_ACCESS_KEY";
+    public const string SessionTokenVariable = "AWS_SESSION_TOKEN";
+
+    /// <summary>
+    /// Reads the standard AWS environment variables, which CI actions such as aws-actions/configure-aws-credentials set
+    /// </summary>
+    public static AwsCredentials FromEnvironment()
+    {
+        var accessKeyId = Environment.GetEnvironmentVariable(AccessKeyIdVariable);
+        var secretAccessKey = Environment.GetEnvironmentVariable(SecretAccessKeyVariable);
+        if (string.IsNullOrWhiteSpace(accessKeyId) || string.IsNullOrWhiteSpace(secretAccessKey))
+        {
+            throw new InvalidOperationException($"AWS credentials are not configured. Set {AccessKeyIdVariable} and {SecretAccessKeyVariable}, and {SessionTokenVariable} for temporary credentials.");
+        }
+
+        var sessionToken = Environment.GetEnvironmentVariable(SessionTokenVariable);
+        return new AwsCredentials(accessKeyId, secretAccessKey, string.IsNullOrWhiteSpace(sessionToken) ? null : sessionToken);
+    }
+
+    // Keep the secret out of logs and exception messages
+    public override string ToString() => $"{nameof(AwsCredentials)} {{ AccessKeyId = {AccessKeyId} }}";
+}
+
+/// <summary>
+/// Signs requests with AWS Signature Version 4, for the few S3 calls the CLI makes without the AWS SDK
+/// </summary>
+internal static class AwsSignatureV4
+{
+    public const string UnsignedPayload = "UNSIGNED-PAYLOAD";
+    private const string Algorithm = "AWS4-HMAC-SHA256";
+
+    /// <summary>
+    /// Adds the x-amz-* and Authorization headers. Signs the host, every x-amz-* header and Range; other headers
+    /// such as Cache-Control are sent unsigned.
+    /// </summary>
+    /// <param name="payloadHash">Lowercase hex SHA-256 of the body, or <see cref="UnsignedPayload"/></param>
+    public static void Sign(HttpRequestMessage request, AwsCredentials credentials, string region, string service, string payloadHash, DateTimeOffset now)
+    {
+        var uri = request.RequestUri ?? throw new ArgumentException("The request has no URI", nameof(request));
+        var amzDate = now.UtcDateTime.ToString("yyyyMMdd'T'HHmmss'Z'", CultureInfo.InvariantCulture);
+        var date = amzDate[..8];
+
+        request.Headers.Remove("x-amz-date");
+        request.Headers.Remove("x-amz-content-sha256");
+        request.Headers.Remove("x-amz-security-token");
+        request.Headers.TryAddWithoutValidation("x-amz-date", amzDate);
+        request.Headers.TryAddWithoutValidation("x-amz-content-sha256", payloadHash);
+        if (credentials.SessionToken != null)
+        {
+            request.Headers.TryAddWithoutValidation("x-amz-security-token", credentials.SessionToken);
+        }
+
+        var headers = new SortedDictionary<string, string>(StringComparer.Ordinal)
+        {
+            ["host"] = uri.IsDefaultPort ? uri.Host : $"{uri.Host}:{uri.Port}"
+        };
+        foreach (var header in request.Headers)
+        {
+            var name = header.Key.ToLowerInvariant();
+            if (name.StartsWith("x-amz-", StringComparison.Ordinal) || name == "range")
+            {
+                headers[name] = string.Join(",", header.Value.Select(v => string.Join(' ', v.Split(' ', StringSplitOptions.RemoveEmptyEntries))));
+            }
+        }
+
+        var signedHeaders = string.Join(';', headers.Keys);
+        var canonicalRequest = string.Join('\n',
+            request.Method.Method,
+            uri.AbsolutePath,
+            GetCanonicalQuery(uri),
+            string.Concat(headers.Select(h => $"{h.Key}:{h.Value}\n")),
+            signedHeaders,
+            payloadHash);
+
+        var scope = $"{date}/{region}/{service}/aws4_request";
+        var stringToSign = string.Join('\n', Algorithm, amzDate, scope, Hex(SHA256.HashData(Encoding.UTF8.GetBytes(canonicalRequest))));
+
+        var key = HMACSHA256.HashData(Encoding.UTF8.GetBytes("AWS4" + credentials.SecretAccessKey), Encoding.UTF8.GetBytes(date));
+        key = HMACSHA256.HashData(key, Encoding.UTF8.GetBytes(region));
+        key = HMACSHA256.HashData(key, Encoding.UTF8.GetBytes(service));
+        key = HMACSHA256.HashData(key, "aws4_request"u8);
+        var signature = Hex(HMACSHA256.HashData(key, Encoding.UTF8.GetBytes(stringToSign)));
+
+        request.Headers.TryAddWithoutValidation("Authorization", $"{Algorithm} Credential={credentials.AccessKeyId}/{scope}, SignedHeaders={signedHeaders}, Signature={signature}");
+    }
+
+    public static async Task<string> HashFileAsync(FileInfo file, CancellationToken cancellationToken)
+    {
+        await using var stream = file.OpenRead();
+        return Hex(await SHA256.HashDataAsync(stream, cancellationToken));
+    }
+
+    // Query parameters sorted by name; the URI already holds them percent-encoded
+    private static string GetCanonicalQuery(Uri uri) =>
+        string.Join('&', uri.Query.TrimStart('?')
+            .Split('&', StringSplitOptions.RemoveEmptyEntries)
+            .Select(p => p.Contains('=') ? p : p + "=")
+            .Order(StringComparer.Ordinal));
+
+    private static string Hex(byte[] bytes) => Convert.ToHexStringLower(bytes);
+}
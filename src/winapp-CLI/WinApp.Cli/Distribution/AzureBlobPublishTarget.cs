@@ -0,0 +1,46 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Uploads the site to an Azure Storage container, optionally fronted by Azure CDN or Front Door
+/// </summary>
+/// <param name="containerSasUri">Container URL with a SAS token that allows writing blobs</param>
+internal sealed class AzureBlobPublishTarget(Uri containerSasUri, HttpClient http) : IDistributionPublishTarget
+{
+    public string Name => "Azure Blob Storage";
+
+    public Uri? DefaultBaseUri { get; } = new(containerSasUri.GetLeftPart(UriPartial.Path).TrimEnd('/') + "/");
+
+    public bool HostsPage => true;
+
+    public bool SupportsCacheControl => true;
+
+    public async Task PublishAsync(DistributionSite site, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var uploader = new BlockBlobUploader(http);
+        var containerPath = containerSasUri.GetLeftPart(UriPartial.Path).TrimEnd('/');
+
+        foreach (var file in site.Files)
+        {
+            var blobUri = new Uri($"{containerPath}/{Uri.EscapeDataString(file)}{containerSasUri.Query}");
+            var options = new BlockBlobUploadOptions
+            {
+                ContentType = DistributionContentTypes.Get(file),
+                CacheControl = DistributionCacheControl.Get(file, site.Package),
+                Resume = false
+            };
+
+            taskContext.UpdateSubStatus($"Uploading {file}");
+            await uploader.UploadAsync(new FileInfo(Path.Combine(site.OutputDirectory.FullName, file)), blobUri, options, onRetry: message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}"), cancellationToken: cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Check} {file} ({options.ContentType}; {options.CacheControl})");
+        }
+
+        taskContext.UpdateSubStatus(null);
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Cache-Control headers for the files of a distribution site. App Installer downloads the .appinstaller file on
+/// every update check, so a cached copy hides new versions; a package that is replaced under the same name while
+/// a cache still holds the old one makes App Installer fail with a version mismatch.
+/// </summary>
+internal static class DistributionCacheControl
+{
+    /// <summary>
+    /// Caches may keep the file but must ask the origin whether it changed before using it
+    /// </summary>
+    public const string Revalidate = "no-cache";
+
+    /// <summary>
+    /// For packages whose file name contains their version, which are never replaced in place
+    /// </summary>
+    public const string Immutable = "public, max-age=31536000, immutable";
+
+    public const string Certificate = "public, max-age=86400";
+
+    public static string Get(string fileName, DistributionPackage package)
+    {
+        if (Path.GetExtension(fileName).Equals(".cer", StringComparison.OrdinalIgnoreCase))
+        {
+            return Certificate;
+        }
+
+        return fileName == package.File.Name && fileName.Contains(package.Version, StringComparison.OrdinalIgnoreCase)
+            ? Immutable
+            : Revalidate;
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Distribution;
+
+/// <param name="Actual">Content type the server sent; null when it sent none or the request failed</param>
+internal sealed record ContentTypeMismatch(string File, Uri Uri, string Expected, string? Actual);
+
+/// <summary>
+/// Content types App Installer and browsers expect for the files of a distribution site. Servers that send
+/// packages or .appinstaller files as application/octet-stream or text/plain break the install links.
+/// </summary>
+internal static class DistributionContentTypes
+{
+    private static readonly Dictionary<string, string> ByExtension = new(StringComparer.OrdinalIgnoreCase)
+    {
+        [AppInstallerFile.Extension] = AppInstallerFile.ContentType,
+        [".msix"] = "application/msix",
+        [".msixbundle"] = "application/msixbundle",
+        [".appx"] = "application/appx",
+        [".appxbundle"] = "application/appxbundle",
+        [".cer"] = "application/pkix-cert",
+        [".html"] = "text/html; charset=utf-8",
+        [".json"] = "application/json",
+        [FeedIntegrity.SignatureExtension] = "application/pkcs7-signature",
+    };
+
+    public static string Get(string fileName) =>
+        ByExtension.TryGetValue(Path.GetExtension(fileName), out var contentType) ? contentType : "application/octet-stream";
+
+    /// <summary>
+    /// Requests the published files and returns those the server sends with another content type than the one
+    /// they were uploaded with, e.g. because a CDN rule or web server configuration overrides it
+    /// </summary>
+    public static async Task<IReadOnlyList<ContentTypeMismatch>> FindMismatchesAsync(HttpClient http, Uri baseUri, IEnumerable<string> files, CancellationToken cancellationToken)
+    {
+        var mismatches = new List<ContentTypeMismatch>();
+        foreach (var file in files)
+        {
+            var uri = new Uri(baseUri, Uri.EscapeDataString(file));
+            var expected = Get(file);
+            string? actual = null;
+            try
+            {
+                using var request = new HttpRequestMessage(HttpMethod.Head, uri);
+                using var response = await http.SendAsync(request, HttpCompletionOption.ResponseHeadersRead, cancellationToken);
+                actual = response.IsSuccessStatusCode ? response.Content.Headers.ContentType?.MediaType : null;
+            }
+            catch (HttpRequestException)
+            {
+                // Reported as a mismatch without a content type
+            }
+
+            if (!string.Equals(actual, expected.Split(';')[0], StringComparison.OrdinalIgnoreCase))
+            {
+                mismatches.Add(new ContentTypeMismatch(file, uri, expected, actual));
+            }
+        }
+
+        return mismatches;
+    }
+}
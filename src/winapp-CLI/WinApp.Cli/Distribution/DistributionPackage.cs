@@ -0,0 +1,85 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Xml;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Identity and signer of a package or bundle that is distributed outside the Store
+/// </summary>
+/// <param name="ProcessorArchitecture">Architecture of a package; null for bundles, which carry several</param>
+/// <param name="DisplayName">Display name from the manifest; null for bundles and localized (ms-resource:) names</param>
+/// <param name="SignerCertificate">Certificate the package is signed with; null when it is unsigned</param>
+internal sealed record DistributionPackage(
+    FileInfo File,
+    string Name,
+    string Publisher,
+    string Version,
+    string? ProcessorArchitecture,
+    bool IsBundle,
+    string? DisplayName,
+    X509Certificate2? SignerCertificate)
+{
+    private const string BundleManifestPartName = "AppxMetadata/AppxBundleManifest.xml";
+
+    /// <summary>
+    /// Whether the signer is not issued by a certificate authority, so users have to trust it before installing
+    /// </summary>
+    public bool IsSelfSigned => SignerCertificate != null && SignerCertificate.SubjectName.RawData.AsSpan().SequenceEqual(SignerCertificate.IssuerName.RawData);
+
+    public static async Task<DistributionPackage> ReadAsync(FileInfo file, CancellationToken cancellationToken = default)
+    {
+        await using var stream = file.OpenRead();
+        var entries = (await ZipPackageReader.ReadCentralDirectoryAsync(stream, cancellationToken)).Entries;
+
+        var manifestEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.ManifestPartName)
+            ?? entries.FirstOrDefault(e => e.Name == BundleManifestPartName)
+            ?? throw new InvalidOperationException($"{file.Name} is not an MSIX/APPX package or bundle: it has no manifest");
+        var isBundle = manifestEntry.Name == BundleManifestPartName;
+
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, manifestEntry, cancellationToken)));
+        var identity = xmlDoc.SelectSingleNode("/*[local-name()='Package' or local-name()='Bundle']/*[local-name()='Identity']") as XmlElement
+            ?? throw new InvalidOperationException($"The manifest of {file.Name} has no Identity element");
+
+        var displayName = xmlDoc.SelectSingleNode("/*[local-name()='Package']/*[local-name()='Properties']/*[local-name()='DisplayName']")?.InnerText.Trim();
+        if (string.IsNullOrEmpty(displayName) || displayName.StartsWith("ms-resource:", StringComparison.OrdinalIgnoreCase))
+        {
+            displayName = null;
+        }
+
+        X509Certificate2? signer = null;
+        var signatureEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.SignaturePartName);
+        if (signatureEntry != null)
+        {
+            var p7x = await ZipPackageReader.ReadEntryAsync(stream, signatureEntry, cancellationToken);
+            try
+            {
+                var signedCms = new SignedCms();
+                signedCms.Decode(p7x.AsSpan(AppxSignature.P7xMagic.Length));
+                signer = signedCms.SignerInfos.Count > 0 ? signedCms.SignerInfos[0].Certificate : null;
+            }
+            catch (CryptographicException)
+            {
+                // 'winapp verify' reports broken signatures; the page only needs the signer when there is one
+            }
+        }
+
+        var architecture = identity.GetAttribute("ProcessorArchitecture");
+        return new DistributionPackage(
+            file,
+            identity.GetAttribute("Name"),
+            identity.GetAttribute("Publisher"),
+            identity.GetAttribute("Version"),
+            isBundle || string.IsNullOrEmpty(architecture) ? null : architecture,
+            isBundle,
+            displayName,
+            signer);
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Text;
+
+namespace WinApp.Cli.Distribution;
+
+/// <param name="Title">Heading of the page, usually the app's display name</param>
+/// <param name="AppInstallerFileName">File name of the .appinstaller file next to the page</param>
+/// <param name="AppInstallerUri">Absolute URL of the .appinstaller file, used by the ms-appinstaller: link</param>
+/// <param name="CertificateFileName">File name of the signing certificate next to the page; null when the package is signed by a trusted authority</param>
+/// <param name="ReleaseNotes">What's new in this version, as Markdown or plain text; null leaves the section out</param>
+internal sealed record DistributionPageModel(
+    string Title,
+    DistributionPackage Package,
+    string AppInstallerFileName,
+    Uri AppInstallerUri,
+    string PackageFileName,
+    string? CertificateFileName,
+    string? ReleaseNotes = null);
+
+/// <summary>
+/// Renders the static install page of a sideloaded app
+/// </summary>
+/// <remarks>
+/// The ms-appinstaller: protocol is disabled by default since App Installer 1.21.3421.0 (December 2023) and can
+/// only be turned back on by policy, so the main button downloads the .appinstaller file, which App Installer
+/// opens and which still keeps the app updated. The protocol link is offered as a secondary option.
+/// </remarks>
+internal static class DistributionPage
+{
+    public const string FileName = "index.html";
+
+    public static string Render(DistributionPageModel model)
+    {
+        var package = model.Package;
+        var protocolUri = $"ms-appinstaller:?source={Uri.EscapeDataString(model.AppInstallerUri.AbsoluteUri)}";
+
+        var html = new StringBuilder();
+        html.Append("<!DOCTYPE html>\n")
+            .Append("<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n")
+            .Append("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n")
+            .Append("<meta name=\"generator\" content=\"winapp distribute web\">\n")
+            .Append("<title>").Append(Encode(model.Title)).Append("</title>\n")
+            .Append("<style>\n")
+            .Append("body{font-family:\"Segoe UI\",system-ui,sans-serif;max-width:44rem;margin:3rem auto;padding:0 1rem;color:#1b1b1b;line-height:1.5}\n")
+            .Append(".button{display:inline-block;padding:.6rem 1.4rem;border-radius:.3rem;background:#0067c0;color:#fff;text-decoration:none;font-weight:600}\n")
+            .Append(".secondary{background:#f3f3f3;color:#1b1b1b;border:1px solid #d1d1d1}\n")
+            .Append(".note{background:#f7f7f7;border-left:4px solid #0067c0;padding:.5rem 1rem}\n")
+            .Append("code{background:#f3f3f3;padding:.1rem .3rem;border-radius:.2rem}\n")
+            .Append("</style>\n</head>\n<body>\n");
+
+        html.Append("<h1>").Append(Encode(model.Title)).Append("</h1>\n")
+            .Append("<p>Version ").Append(Encode(package.Version))
+            .Append(package.ProcessorArchitecture != null ? $" ({Encode(package.ProcessorArchitecture)})" : string.Empty)
+            .Append(" &middot; ").Append(Encode(DescribePublisher(package.Publisher))).Append("</p>\n");
+
+        html.Append("<p><a class=\"button\" href=\"").Append(Encode(model.AppInstallerFileName)).Append("\" download>Install</a></p>\n")
+            .Append("<p>Open the downloaded <code>").Append(Encode(model.AppInstallerFileName)).Append("</code> file to install the app with App Installer. ")
+            .Append("The app then checks this site for updates.</p>\n");
+
+        if (!string.IsNullOrWhiteSpace(model.ReleaseNotes))
+        {
+            html.Append("<h2>What's new</h2>\n");
+            AppendReleaseNotes(html, model.ReleaseNotes);
+        }
+
+        html.Append("<h2>Other ways to install</h2>\n<ul>\n")
+            .Append("<li><a href=\"").Append(Encode(protocolUri)).Append("\">Install directly with App Installer</a>. ")
+            .Append("This link only works where an administrator has enabled the <code>ms-appinstaller</code> protocol, which Windows disables by default.</li>\n")
+            .Append("<li><a href=\"").Append(Encode(model.PackageFileName)).Append("\" download>Download the package</a> and open it. Installed this way, the app does not update automatically.</li>\n")
+            .Append("</ul>\n");
+
+        if (model.CertificateFileName != null)
+        {
+            html.Append("<h2>Trust the publisher first</h2>\n")
+                .Append("<div class=\"note\">\n<p>This app is signed with a certificate Windows does not trust yet, so installation fails until you trust it. ")
+                .Append("Only do this if you trust the publisher.</p>\n<ol>\n")
+                .Append("<li><a href=\"").Append(Encode(model.CertificateFileName)).Append("\" download>Download the certificate</a>.</li>\n")
+                .Append("<li>In an administrator PowerShell, run <code>Import-Certificate -FilePath .\\").Append(Encode(model.CertificateFileName))
+                .Append(" -CertStoreLocation Cert:\\LocalMachine\\TrustedPeople</code>.</li>\n")
+                .Append("<li>Install the app as above.</li>\n</ol>\n");
+            if (package.SignerCertificate != null)
+            {
+                html.Append("<p>Certificate thumbprint: <code>").Append(Encode(package.SignerCertificate.Thumbprint)).Append("</code></p>\n");
+            }
+            html.Append("</div>\n");
+        }
+
+        html.Append("<h2>Requirements</h2>\n")
+            .Append("<p>Windows 10 version 1809 or later with App Installer, which is included in Windows and updated through the Microsoft Store.</p>\n")
+            .Append("</body>\n</html>\n");
+
+        return html.ToString();
+    }
+
+    /// <summary>
+    /// Shows the organization or common name of a publisher such as "CN=Contoso, O=Contoso Ltd, C=US"
+    /// </summary>
+    internal static string DescribePublisher(string publisher)
+    {
+        string? commonName = null;
+        foreach (var part in publisher.Split(','))
+        {
+            var pair = part.Split('=', 2);
+            if (pair.Length != 2)
+            {
+                continue;
+            }
+
+            var key = pair[0].Trim();
+            var value = pair[1].Trim().Trim('"');
+            if (key.Equals("O", StringComparison.OrdinalIgnoreCase))
+            {
+                return value;
+            }
+            if (key.Equals("CN", StringComparison.OrdinalIgnoreCase))
+            {
+                commonName ??= value;
+            }
+        }
+
+        return commonName ?? publisher;
+    }
+
+    /// <summary>
+    /// Renders the Markdown 'winapp changelog' writes: headings, bullet lists and paragraphs. Everything else,
+    /// including inline formatting, is shown as text.
+    /// </summary>
+    private static void AppendReleaseNotes(StringBuilder html, string releaseNotes)
+    {
+        var inList = false;
+        foreach (var rawLine in releaseNotes.Replace("\r\n", "\n").Split('\n'))
+        {
+            var line = rawLine.Trim();
+            var isItem = line.StartsWith("- ", StringComparison.Ordinal) || line.StartsWith("* ", StringComparison.Ordinal) || line.StartsWith("• ", StringComparison.Ordinal);
+            if (inList && !isItem)
+            {
+                html.Append("</ul>\n");
+                inList = false;
+            }
+
+            if (isItem)
+            {
+                if (!inList)
+                {
+                    html.Append("<ul>\n");
+                    inList = true;
+                }
+                html.Append("<li>").Append(Encode(line[2..].Replace("**", string.Empty).Trim())).Append("</li>\n");
+            }
+            else if (line.StartsWith('#'))
+            {
+                html.Append("<h3>").Append(Encode(line.TrimStart('#').Trim())).Append("</h3>\n");
+            }
+            else if (line.Length > 0)
+            {
+                html.Append("<p>").Append(Encode(line)).Append("</p>\n");
+            }
+        }
+
+        if (inList)
+        {
+            html.Append("</ul>\n");
+        }
+    }
+
+    private static string Encode(string value) => WebUtility.HtmlEncode(value);
+}
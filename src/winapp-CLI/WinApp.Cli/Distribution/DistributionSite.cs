@@ -0,0 +1,50 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Distribution;
+
+internal sealed record DistributionWebOptions
+{
+    /// <summary>
+    /// URL the site is served from. The .appinstaller file needs absolute URLs, so it must be known up front.
+    /// </summary>
+    public required Uri BaseUri { get; init; }
+
+    /// <summary>
+    /// Page heading; defaults to the app's display name, or the package name
+    /// </summary>
+    public string? Title { get; init; }
+
+    /// <summary>
+    /// How often the installed app checks for updates when it is launched; 0 checks on every launch
+    /// </summary>
+    public int HoursBetweenUpdateChecks { get; init; }
+
+    /// <summary>
+    /// Adds the files GitHub Pages needs to serve the site as is
+    /// </summary>
+    public bool GitHubPages { get; init; }
+
+    /// <summary>
+    /// What's new in this version, shown on the page; e.g. from 'winapp changelog'
+    /// </summary>
+    public string? ReleaseNotes { get; init; }
+
+    /// <summary>
+    /// .appinstaller file with {{placeholders}} to write instead of the generated one, e.g. to add dependencies or
+    /// related packages; null generates the file
+    /// </summary>
+    public string? AppInstallerTemplate { get; init; }
+
+    /// <summary>
+    /// PFX to sign the site's integrity manifest with; null skips the manifest. Usually the package's own
+    /// certificate, because verification accepts the package publisher by default.
+    /// </summary>
+    public FileInfo? FeedSigningCertificate { get; init; }
+
+    public string? FeedSigningCertificatePassword { get; init; }
+}
+
+/// <param name="Files">Every file of the site, relative to <paramref name="OutputDirectory"/></param>
+/// <param name="CertificateIncluded">Whether the site offers the signing certificate because the package is self-signed</param>
+internal sealed record DistributionSite(DirectoryInfo OutputDirectory, IReadOnlyList<string> Files, DistributionPackage Package, Uri PageUri, Uri AppInstallerUri, bool CertificateIncluded);
@@ -0,0 +1,199 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Text.Json.Nodes;
+
+namespace WinApp.Cli.Distribution;
+
+/// <param name="Sha256">Lowercase hex SHA-256 of the file</param>
+internal sealed record FeedIntegrityFile(string Name, long Size, string Sha256);
+
+/// <param name="Problem">Why the file does not match its manifest entry; null when it matches or was skipped</param>
+internal sealed record FeedFileCheck(string Name, string? Problem, bool Skipped = false);
+
+/// <param name="Signer">Certificate the manifest is signed with</param>
+internal sealed record FeedVerificationResult(FeedIntegrityManifest Manifest, X509Certificate2 Signer, IReadOnlyList<FeedFileCheck> Files)
+{
+    public bool Succeeded => Files.All(f => f.Problem == null);
+}
+
+/// <summary>
+/// Hash list of the files of a distribution site, so an app or a user can detect an .appinstaller file or
+/// package that was replaced on the server or CDN
+/// </summary>
+internal sealed record FeedIntegrityManifest(string PackageName, string Publisher, string Version, IReadOnlyList<FeedIntegrityFile> Files);
+
+/// <summary>
+/// Writes and checks the integrity manifest of a distribution site (&lt;package-name&gt;.integrity.json) and its
+/// detached CMS signature (&lt;package-name&gt;.integrity.json.p7s)
+/// </summary>
+/// <remarks>
+/// App Installer does not check a signature on .appinstaller files; it only requires packages to be signed by a
+/// trusted certificate matching the publisher. The manifest covers the rest of the site, such as the .appinstaller
+/// file and the certificate offered for self-signed packages.
+/// </remarks>
+internal static class FeedIntegrity
+{
+    public const int SchemaVersion = 1;
+    public const string ManifestSuffix = ".integrity.json";
+    public const string SignatureExtension = ".p7s";
+
+    private static readonly string[] PackageExtensions = [".msix", ".msixbundle", ".appx", ".appxbundle"];
+
+    public static string GetManifestFileName(string packageName) => packageName + ManifestSuffix;
+
+    public static string GetSignatureFileName(string packageName) => GetManifestFileName(packageName) + SignatureExtension;
+
+    public static async Task<byte[]> CreateManifestAsync(DistributionPackage package, DirectoryInfo directory, IEnumerable<string> files, CancellationToken cancellationToken)
+    {
+        var entries = new JsonArray();
+        foreach (var file in files)
+        {
+            var info = new FileInfo(Path.Combine(directory.FullName, file));
+            entries.Add(new JsonObject
+            {
+                ["name"] = file,
+                ["size"] = info.Length,
+                ["sha256"] = await HashFileAsync(info, cancellationToken)
+            });
+        }
+
+        var manifest = new JsonObject
+        {
+            ["schemaVersion"] = SchemaVersion,
+            ["package"] = new JsonObject
+            {
+                ["name"] = package.Name,
+                ["publisher"] = package.Publisher,
+                ["version"] = package.Version
+            },
+            ["files"] = entries
+        };
+        return Encoding.UTF8.GetBytes(manifest.ToJsonString());
+    }
+
+    public static FeedIntegrityManifest ReadManifest(byte[] manifest)
+    {
+        var json = JsonNode.Parse(manifest) as JsonObject ?? throw new InvalidDataException("The integrity manifest is not a JSON object");
+        var schemaVersion = json["schemaVersion"]?.GetValue<int>();
+        if (schemaVersion != SchemaVersion)
+        {
+            throw new InvalidDataException($"Unsupported integrity manifest schema version {schemaVersion}");
+        }
+
+        var package = json["package"] as JsonObject ?? throw new InvalidDataException("The integrity manifest has no package");
+        var files = (json["files"] as JsonArray ?? [])
+            .Select(f => new FeedIntegrityFile(
+                f?["name"]?.GetValue<string>() ?? throw new InvalidDataException("A file in the integrity manifest has no name"),
+                f["size"]?.GetValue<long>() ?? -1,
+                f["sha256"]?.GetValue<string>() ?? string.Empty))
+            .ToList();
+
+        return new FeedIntegrityManifest(
+            package["name"]?.GetValue<string>() ?? string.Empty,
+            package["publisher"]?.GetValue<string>() ?? string.Empty,
+            package["version"]?.GetValue<string>() ?? string.Empty,
+            files);
+    }
+
+    /// <summary>
+    /// Creates a detached CMS (PKCS #7) signature of the manifest that includes the signing certificate
+    /// </summary>
+    public static byte[] Sign(byte[] manifest, X509Certificate2 certificate)
+    {
+        var signedCms = new SignedCms(new ContentInfo(manifest), detached: true);
+        var signer = new CmsSigner(SubjectIdentifierType.IssuerAndSerialNumber, certificate)
+        {
+            DigestAlgorithm = new Oid("2.16.840.1.101.3.4.2.1"),
+            IncludeOption = X509IncludeOption.EndCertOnly
+        };
+        signer.SignedAttributes.Add(new Pkcs9SigningTime());
+        signedCms.ComputeSignature(signer);
+        return signedCms.Encode();
+    }
+
+    /// <summary>
+    /// Checks the signature of the manifest and that the signer may speak for the package: either its thumbprint
+    /// is <paramref name="expectedThumbprint"/>, or, when none is given, its subject is the package publisher,
+    /// the same rule Windows applies to the package signature
+    /// </summary>
+    /// <returns>The signing certificate</returns>
+    /// <exception cref="CryptographicException">The signature does not match the manifest or the signer is not accepted</exception>
+    public static X509Certificate2 VerifySignature(byte[] manifest, byte[] signature, string? expectedThumbprint)
+    {
+        var signedCms = new SignedCms(new ContentInfo(manifest), detached: true);
+        signedCms.Decode(signature);
+        if (signedCms.SignerInfos.Count != 1)
+        {
+            throw new CryptographicException($"The integrity manifest has {signedCms.SignerInfos.Count} signers instead of one");
+        }
+
+        var signerInfo = signedCms.SignerInfos[0];
+        signerInfo.CheckSignature(verifySignatureOnly: true);
+        var signer = signerInfo.Certificate ?? throw new CryptographicException("The integrity manifest signature does not include the signing certificate");
+
+        if (expectedThumbprint != null)
+        {
+            if (!string.Equals(signer.Thumbprint, expectedThumbprint.Replace(":", "").Replace(" ", ""), StringComparison.OrdinalIgnoreCase))
+            {
+                throw new CryptographicException($"The integrity manifest is signed by {signer.Subject} ({signer.Thumbprint}), not by the expected certificate {expectedThumbprint}");
+            }
+        }
+        else
+        {
+            var publisher = ReadManifest(manifest).Publisher;
+            if (!IsSameName(signer.SubjectName, publisher))
+            {
+                throw new CryptographicException($"The integrity manifest is signed by {signer.Subject}, not by the package publisher {publisher}");
+            }
+        }
+
+        return signer;
+    }
+
+    /// <summary>
+    /// Compares a file against its manifest entry
+    /// </summary>
+    /// <returns>Null when the file matches, otherwise why it does not</returns>
+    public static string? Check(FeedIntegrityFile expected, long size, string sha256)
+    {
+        if (expected.Size >= 0 && size != expected.Size)
+        {
+            return $"size is {size} bytes instead of {expected.Size}";
+        }
+
+        return string.Equals(sha256, expected.Sha256, StringComparison.OrdinalIgnoreCase)
+            ? null
+            : $"SHA-256 is {sha256} instead of {expected.Sha256}";
+    }
+
+    public static bool IsPackageFile(string name) =>
+        PackageExtensions.Contains(Path.GetExtension(name), StringComparer.OrdinalIgnoreCase);
+
+    public static async Task<string> HashFileAsync(FileInfo file, CancellationToken cancellationToken)
+    {
+        await using var stream = file.OpenRead();
+        return Convert.ToHexStringLower(await SHA256.HashDataAsync(stream, cancellationToken));
+    }
+
+    /// <summary>
+    /// Compares a certificate subject with a package publisher, which are the same distinguished name written in
+    /// possibly different ways
+    /// </summary>
+    public static bool IsSameName(X500DistinguishedName subject, string publisher)
+    {
+        try
+        {
+            var flags = X500DistinguishedNameFlags.UseCommas;
+            return string.Equals(subject.Decode(flags), new X500DistinguishedName(publisher).Decode(flags), StringComparison.Ordinal);
+        }
+        catch (CryptographicException)
+        {
+            return false;
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Replaces the content of a GitHub Pages branch with the site
+/// </summary>
+/// <param name="repositoryDirectory">Repository whose remotes <paramref name="remote"/> is resolved against</param>
+/// <param name="remote">Git remote name or URL</param>
+internal sealed class GitHubPagesPublishTarget(IGitService git, string repositoryDirectory, string remote, string branch) : IDistributionPublishTarget
+{
+    public string Name => "GitHub Pages";
+
+    // The URL depends on the repository settings and custom domains, so --base-url is required
+    public Uri? DefaultBaseUri => null;
+
+    public bool HostsPage => true;
+
+    // GitHub Pages serves every file with a fixed 10 minute max-age and picks content types by extension
+    public bool SupportsCacheControl => false;
+
+    public async Task PublishAsync(DistributionSite site, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        // Resolve remote names in the current repository, because the push runs from a scratch repository
+        var remoteUrl = remote.Contains(':') || remote.Contains('/')
+            ? remote
+            : (await git.RunAsync(repositoryDirectory, ["remote", "get-url", remote], cancellationToken)).Trim();
+
+        var workDirectory = Directory.CreateTempSubdirectory("winapp-pages-");
+        try
+        {
+            foreach (var file in site.Files)
+            {
+                File.Copy(Path.Combine(site.OutputDirectory.FullName, file), Path.Combine(workDirectory.FullName, file));
+            }
+
+            // The branch only ever holds the latest site, so its history is replaced instead of growing with every package
+            await git.RunAsync(workDirectory.FullName, ["init", "--quiet"], cancellationToken);
+            await git.RunAsync(workDirectory.FullName, ["checkout", "--quiet", "-b", branch], cancellationToken);
+            await git.RunAsync(workDirectory.FullName, ["add", "--all"], cancellationToken);
+            await git.RunAsync(workDirectory.FullName, ["-c", "user.name=winapp", "-c", "user.email=winapp@users.noreply.github.com", "commit", "--quiet", "-m", $"Publish {site.Package.Name} {site.Package.Version}"], cancellationToken);
+
+            taskContext.UpdateSubStatus($"Pushing to {branch}");
+            await git.RunAsync(workDirectory.FullName, ["push", "--quiet", "--force", remoteUrl, $"HEAD:refs/heads/{branch}"], cancellationToken);
+            taskContext.UpdateSubStatus(null);
+        }
+        finally
+        {
+            try
+            {
+                workDirectory.Delete(recursive: true);
+            }
+            catch (IOException ex)
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Warning} Failed to delete {workDirectory.FullName}: {ex.Message}");
+            }
+        }
+    }
+}
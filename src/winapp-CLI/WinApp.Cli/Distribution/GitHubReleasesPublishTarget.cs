@@ -0,0 +1,151 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Net.Http.Headers;
+using System.Text;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Attaches the site's files to a GitHub release, creating the release when the tag has none. GitHub stores
+/// the content type each asset is uploaded with and serves it from its download CDN.
+/// </summary>
+/// <remarks>
+/// The default base URL uses the releases/latest/download redirect, so the .appinstaller file installed
+/// apps check always resolves to the newest release.
+/// </remarks>
+/// <param name="repository">owner/name</param>
+internal sealed class GitHubReleasesPublishTarget(string repository, string tag, string token, HttpClient http) : IDistributionPublishTarget
+{
+    public const string TokenVariable = "GITHUB_TOKEN";
+    public const string AlternateTokenVariable = "GH_TOKEN";
+    public const string RepositoryVariable = "GITHUB_REPOSITORY";
+
+    public static readonly Uri DefaultApiUri = new("https://api.github.com/");
+
+    public Uri ApiUri { get; init; } = DefaultApiUri;
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public string Name => "GitHub Releases";
+
+    public Uri? DefaultBaseUri { get; } = new($"https://github.com/{repository}/releases/latest/download/");
+
+    public bool HostsPage => false;
+
+    // Release downloads are served with GitHub's own caching headers
+    public bool SupportsCacheControl => false;
+
+    /// <summary>
+    /// Reads the token from GITHUB_TOKEN or GH_TOKEN, as GitHub Actions and the gh CLI provide it
+    /// </summary>
+    public static string GetTokenFromEnvironment()
+    {
+        var token = Environment.GetEnvironmentVariable(TokenVariable);
+        if (string.IsNullOrWhiteSpace(token))
+        {
+            token = Environment.GetEnvironmentVariable(AlternateTokenVariable);
+        }
+
+        return string.IsNullOrWhiteSpace(token)
+            ? throw new InvalidOperationException($"A GitHub token is not configured. Set {TokenVariable} or {AlternateTokenVariable} to a token that can write the repository's releases.")
+            : token;
+    }
+
+    public async Task PublishAsync(DistributionSite site, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var onRetry = (string message) => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}");
+
+        var release = await SendAsync(HttpMethod.Get, new Uri(ApiUri, $"repos/{repository}/releases/tags/{Uri.EscapeDataString(tag)}"), null, onRetry, cancellationToken);
+        if (release == null)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.New} Creating release {tag} in {repository}");
+            release = await SendAsync(HttpMethod.Post, new Uri(ApiUri, $"repos/{repository}/releases"), new JsonObject { ["tag_name"] = tag, ["name"] = tag }, onRetry, cancellationToken)
+                ?? throw new InvalidOperationException($"GitHub did not return the release it created for {tag}");
+        }
+
+        // upload_url is a URI template such as https://uploads.github.com/repos/o/r/releases/1/assets{?name,label}
+        var uploadUrl = release["upload_url"]?.GetValue<string>() ?? throw new InvalidOperationException($"The release for {tag} has no upload URL");
+        uploadUrl = uploadUrl[..(uploadUrl.IndexOf('{') is var brace and >= 0 ? brace : uploadUrl.Length)];
+        var existing = (release["assets"] as JsonArray ?? [])
+            .Where(a => a?["name"] != null)
+            .ToDictionary(a => a!["name"]!.GetValue<string>(), a => a!["id"]!.GetValue<long>(), StringComparer.OrdinalIgnoreCase);
+
+        foreach (var file in site.Files)
+        {
+            if (file == DistributionPage.FileName || file.StartsWith('.'))
+            {
+                continue;
+            }
+
+            // Assets cannot be overwritten, so a re-run replaces them
+            if (existing.TryGetValue(file, out var assetId))
+            {
+                await SendAsync(HttpMethod.Delete, new Uri(ApiUri, $"repos/{repository}/releases/assets/{assetId}"), null, onRetry, cancellationToken);
+            }
+
+            taskContext.UpdateSubStatus($"Uploading {file}");
+            var source = new FileInfo(Path.Combine(site.OutputDirectory.FullName, file));
+            var contentType = DistributionContentTypes.Get(file);
+            using var response = await Retry.SendAsync(http, () =>
+            {
+                var request = CreateRequest(HttpMethod.Post, new Uri($"{uploadUrl}?name={Uri.EscapeDataString(file)}"));
+                request.Content = new StreamContent(source.OpenRead());
+                request.Content.Headers.ContentType = MediaTypeHeaderValue.Parse(contentType);
+                request.Content.Headers.ContentLength = source.Length;
+                return request;
+            }, onRetry, cancellationToken);
+            await EnsureSuccessAsync(response, $"Uploading {file}", cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Check} {file} ({contentType})");
+        }
+
+        taskContext.UpdateSubStatus(null);
+    }
+
+    /// <returns>The response JSON, or null for 404 Not Found and empty responses</returns>
+    private async Task<JsonNode?> SendAsync(HttpMethod method, Uri uri, JsonNode? body, Action<string> onRetry, CancellationToken cancellationToken)
+    {
+        using var response = await Retry.SendAsync(http, () =>
+        {
+            var request = CreateRequest(method, uri);
+            if (body != null)
+            {
+                request.Content = new StringContent(body.ToJsonString(), Encoding.UTF8, "application/json");
+            }
+            return request;
+        }, onRetry, cancellationToken);
+
+        if (response.StatusCode == HttpStatusCode.NotFound && method == HttpMethod.Get)
+        {
+            return null;
+        }
+
+        await EnsureSuccessAsync(response, $"{method} {uri.AbsolutePath}", cancellationToken);
+        var json = await response.Content.ReadAsStringAsync(cancellationToken);
+        return string.IsNullOrWhiteSpace(json) ? null : JsonNode.Parse(json);
+    }
+
+    private HttpRequestMessage CreateRequest(HttpMethod method, Uri uri)
+    {
+        var request = new HttpRequestMessage(method, uri);
+        request.Headers.Authorization = new AuthenticationHeaderValue("Bearer", token);
+        request.Headers.Accept.ParseAdd("application/vnd.github+json");
+        request.Headers.UserAgent.ParseAdd("winapp-cli");
+        request.Headers.TryAddWithoutValidation("X-GitHub-Api-Version", "2022-11-28");
+        return request;
+    }
+
+    private static async Task EnsureSuccessAsync(HttpResponseMessage response, string operation, CancellationToken cancellationToken)
+    {
+        if (!response.IsSuccessStatusCode)
+        {
+            var error = await response.Content.ReadAsStringAsync(cancellationToken);
+            throw new InvalidOperationException($"{operation} failed with {(int)response.StatusCode} {response.ReasonPhrase}: {error}");
+        }
+    }
+}
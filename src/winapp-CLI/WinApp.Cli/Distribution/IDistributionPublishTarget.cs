@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// A place a distribution site is uploaded to and served from
+/// </summary>
+internal interface IDistributionPublishTarget
+{
+    /// <summary>
+    /// Name shown in status messages, e.g. "Azure Blob Storage"
+    /// </summary>
+    string Name { get; }
+
+    /// <summary>
+    /// URL the uploaded files are served from when no CDN or custom domain fronts the target, or null when it
+    /// cannot be derived from the target
+    /// </summary>
+    Uri? DefaultBaseUri { get; }
+
+    /// <summary>
+    /// Whether the target serves index.html as a web page; GitHub Releases only offers files as downloads
+    /// </summary>
+    bool HostsPage { get; }
+
+    /// <summary>
+    /// Whether the target sends the Cache-Control header each file is uploaded with
+    /// </summary>
+    bool SupportsCacheControl { get; }
+
+    Task PublishAsync(DistributionSite site, TaskContext taskContext, CancellationToken cancellationToken);
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net.Http.Headers;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Distribution;
+
+/// <summary>
+/// Uploads the site to an Amazon S3 bucket, optionally fronted by CloudFront. Each file is sent in a single
+/// PUT, which S3 accepts up to 5 GB.
+/// </summary>
+internal sealed class S3PublishTarget : IDistributionPublishTarget
+{
+    private const string Service = "s3";
+
+    private readonly HttpClient http;
+    private readonly AwsCredentials credentials;
+    private readonly Uri prefixUri;
+
+    /// <param name="location">s3://bucket or s3://bucket/prefix</param>
+    public S3PublishTarget(Uri location, string region, AwsCredentials credentials, HttpClient http)
+    {
+        if (location.Scheme != "s3" || string.IsNullOrEmpty(location.Host))
+        {
+            throw new ArgumentException($"'{location}' is not an S3 location; use s3://bucket or s3://bucket/prefix", nameof(location));
+        }
+
+        Bucket = location.Host;
+        Prefix = location.AbsolutePath.Trim('/');
+        Region = region;
+        this.credentials = credentials;
+        this.http = http;
+
+        prefixUri = GetPrefixUri(Bucket, Prefix, region);
+    }
+
+    /// <summary>
+    /// HTTPS URL of a folder in a bucket, ending in a slash
+    /// </summary>
+    internal static Uri GetPrefixUri(string bucket, string prefix, string region)
+    {
+        // Bucket names with dots do not match the wildcard certificate of virtual-hosted URLs
+        var bucketUri = bucket.Contains('.')
+            ? $"https://s3.{region}.amazonaws.com/{bucket}/"
+            : $"https://{bucket}.s3.{region}.amazonaws.com/";
+        return new Uri(bucketUri + string.Concat(prefix.Split('/', StringSplitOptions.RemoveEmptyEntries).Select(s => Uri.EscapeDataString(s) + "/")));
+    }
+
+    public string Bucket { get; }
+
+    public string Prefix { get; }
+
+    public string Region { get; }
+
+    public string Name => "Amazon S3";
+
+    public Uri? DefaultBaseUri => prefixUri;
+
+    public bool HostsPage => true;
+
+    public bool SupportsCacheControl => true;
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    public async Task PublishAsync(DistributionSite site, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        foreach (var file in site.Files)
+        {
+            var source = new FileInfo(Path.Combine(site.OutputDirectory.FullName, file));
+            var objectUri = new Uri(prefixUri, Uri.EscapeDataString(file));
+            var contentType = DistributionContentTypes.Get(file);
+            var cacheControl = DistributionCacheControl.Get(file, site.Package);
+
+            taskContext.UpdateSubStatus($"Uploading {file}");
+            var payloadHash = await AwsSignatureV4.HashFileAsync(source, cancellationToken);
+            using var response = await Retry.SendAsync(http, () =>
+            {
+                var request = new HttpRequestMessage(HttpMethod.Put, objectUri)
+                {
+                    Content = new StreamContent(source.OpenRead())
+                };
+                request.Content.Headers.ContentType = MediaTypeHeaderValue.Parse(contentType);
+                request.Content.Headers.ContentLength = source.Length;
+                request.Headers.TryAddWithoutValidation("Cache-Control", cacheControl);
+                AwsSignatureV4.Sign(request, credentials, Region, Service, payloadHash, DateTimeOffset.UtcNow);
+                return request;
+            }, message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}"), cancellationToken);
+
+            if (!response.IsSuccessStatusCode)
+            {
+                var error = await response.Content.ReadAsStringAsync(cancellationToken);
+                throw new InvalidOperationException($"Uploading {file} to s3://{Bucket}/{Prefix} failed with {(int)response.StatusCode} {response.ReasonPhrase}: {error}");
+            }
+
+            taskContext.AddDebugMessage($"{UiSymbols.Check} {file} ({contentType}; {cacheControl})");
+        }
+
+        taskContext.UpdateSubStatus(null);
+    }
+}
@@ -117,7 +117,7 @@ internal static class BannerHelper
     /// <summary>
     /// Gets the version string from the assembly.
     /// </summary>
-    private static string GetVersionString()
+    internal static string GetVersionString()
     {
         var assembly = Assembly.GetExecutingAssembly();
         
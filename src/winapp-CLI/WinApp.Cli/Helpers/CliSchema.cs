@@ -22,6 +22,19 @@ namespace WinApp.Cli.Helpers;
 [JsonSerializable(typeof(Dictionary<string, CliSchema.CommandDetails>))]
 [JsonSerializable(typeof(IfExists))]
 [JsonSerializable(typeof(ManifestTemplates))]
+[JsonSerializable(typeof(PackagingEngine))]
+[JsonSerializable(typeof(DigestAlgorithm))]
+[JsonSerializable(typeof(SigningProvider))]
+[JsonSerializable(typeof(StoreStatsAggregation))]
+[JsonSerializable(typeof(StoreExportFormat))]
+[JsonSerializable(typeof(ChangelogSource))]
+[JsonSerializable(typeof(ChangelogFormat))]
+[JsonSerializable(typeof(AffectedFormat))]
+[JsonSerializable(typeof(ValidationFormat))]
+[JsonSerializable(typeof(PackageRegistrationKind))]
+[JsonSerializable(typeof(InstalledPackageFormat))]
+[JsonSerializable(typeof(PackageLocation))]
+[JsonSerializable(typeof(AccessibilitySeverity))]
 [JsonSourceGenerationOptions(
     WriteIndented = true,
     NewLine = "\n",
@@ -17,21 +17,71 @@ internal static class StoreHostBuilderExtensions
     {
         return services
             .AddSingleton<ICurrentDirectoryProvider>(sp => new CurrentDirectoryProvider(Directory.GetCurrentDirectory()))
+            .AddSingleton<IAccessibilityAuditService, AccessibilityAuditService>()
+            .AddSingleton<IAffectedService, AffectedService>()
+            .AddSingleton<IAnalyzerPluginService, AnalyzerPluginService>()
+            .AddSingleton<IAppStateService, AppStateService>()
+            .AddSingleton<IArm64AuditService, Arm64AuditService>()
+            .AddSingleton<IBranchIdentityService, BranchIdentityService>()
+            .AddSingleton<IBuildCacheService, BuildCacheService>()
+            .AddSingleton<IBuildService, BuildService>()
             .AddSingleton<IBuildToolsService, BuildToolsService>()
+            .AddSingleton<IBundleService, BundleService>()
+            .AddSingleton<IBundlerMigrationService, BundlerMigrationService>()
             .AddSingleton<ICertificateService, CertificateService>()
+            .AddSingleton<IChangelogService, ChangelogService>()
+            .AddSingleton<ICleanupService, CleanupService>()
             .AddSingleton<IConfigService, ConfigService>()
             .AddSingleton<ICppWinrtService, CppWinrtService>()
+            .AddSingleton<ICrashReportService, CrashReportService>()
+            .AddSingleton<IDefenderAuditService, DefenderAuditService>()
+            .AddSingleton<IDeploymentEventService, DeploymentEventService>()
+            .AddSingleton<IDevLoopService, DevLoopService>()
             .AddSingleton<IDevModeService, DevModeService>()
             .AddSingleton<IDirectoryPackagesService, DirectoryPackagesService>()
+            .AddSingleton<IDistributionService, DistributionService>()
+            .AddSingleton<IElevationService, ElevationService>()
+            .AddSingleton<IExpiryAuditService, ExpiryAuditService>()
+            .AddSingleton<IFipsPolicyService, FipsPolicyService>()
+            .AddSingleton<IGitService, GitService>()
+            .AddSingleton<IManifestExplainService, ManifestExplainService>()
             .AddSingleton<IManifestTemplateService, ManifestTemplateService>()
             .AddSingleton<IManifestService, ManifestService>()
             .AddSingleton<IImageAssetService, ImageAssetService>()
+            .AddSingleton<IInstalledPackageService, InstalledPackageService>()
+            .AddSingleton<ILicenseService, LicenseService>()
+            .AddSingleton<ILogsService, LogsService>()
             .AddSingleton<IMsixService, MsixService>()
+            .AddSingleton<INativePackagingService, NativePackagingService>()
             .AddSingleton<INugetService, NugetService>()
             .AddSingleton<IPackageCacheService, PackageCacheService>()
             .AddSingleton<IPackageInstallationService, PackageInstallationService>()
             .AddSingleton<IPackageLayoutService, PackageLayoutService>()
+            .AddSingleton<IPackageLicenseService, PackageLicenseService>()
+            .AddSingleton<IPackageMatrixService, PackageMatrixService>()
             .AddSingleton<IPowerShellService, PowerShellService>()
+            .AddSingleton<IProjectStateService, ProjectStateService>()
+            .AddSingleton<IRenderAuditService, RenderAuditService>()
+            .AddSingleton<IRestartService, RestartService>()
+            .AddSingleton<IRuleSetService, RuleSetService>()
+            .AddSingleton<ISarifService, SarifService>()
+            .AddSingleton<IScaleAuditService, ScaleAuditService>()
+            .AddSingleton<ISettingsService, SettingsService>()
+            .AddSingleton<ISigningService, SigningService>()
+            .AddSingleton<ISplashScreenService, SplashScreenService>()
+            .AddSingleton<IStoreAnalyticsService, StoreAnalyticsService>()
+            .AddSingleton<IStoreAssociationService, StoreAssociationService>()
+            .AddSingleton<IStoreFlightService, StoreFlightService>()
+            .AddSingleton<IStoreNameService, StoreNameService>()
+            .AddSingleton<IStoreReviewService, StoreReviewService>()
+            .AddSingleton<IStoreRolloutService, StoreRolloutService>()
+            .AddSingleton<IStoreSubmissionService, StoreSubmissionService>()
+            .AddSingleton<ITestMatrixService, TestMatrixService>()
+            .AddSingleton<IToolchainAuditService, ToolchainAuditService>()
+            .AddSingleton<IValidationService, ValidationService>()
+            .AddSingleton<IWapprojExportService, WapprojExportService>()
+            .AddSingleton<IWapprojMigrationService, WapprojMigrationService>()
+            .AddSingleton<IWebViewAuditService, WebViewAuditService>()
             .AddSingleton<IWinappDirectoryService, WinappDirectoryService>()
             .AddSingleton<IWorkspaceSetupService, WorkspaceSetupService>()
             .AddSingleton<IGitignoreService, GitignoreService>()
@@ -50,13 +100,89 @@ internal static class StoreHostBuilderExtensions
                 .ConfigureCommand<ManifestCommand>()
                 .UseCommandHandler<ManifestGenerateCommand, ManifestGenerateCommand.Handler>()
                 .UseCommandHandler<ManifestUpdateAssetsCommand, ManifestUpdateAssetsCommand.Handler>()
+                .UseCommandHandler<ManifestWin32Command, ManifestWin32Command.Handler>()
                 .UseCommandHandler<UpdateCommand, UpdateCommand.Handler>()
                 .UseCommandHandler<CreateDebugIdentityCommand, CreateDebugIdentityCommand.Handler>()
+                .UseCommandHandler<DevCommand, DevCommand.Handler>()
                 .UseCommandHandler<GetWinappPathCommand, GetWinappPathCommand.Handler>()
                 .ConfigureCommand<CertCommand>()
                 .UseCommandHandler<CertGenerateCommand, CertGenerateCommand.Handler>()
                 .UseCommandHandler<CertInstallCommand, CertInstallCommand.Handler>()
                 .UseCommandHandler<SignCommand, SignCommand.Handler>()
+                .UseCommandHandler<VerifyCommand, VerifyCommand.Handler>()
+                .ConfigureCommand<BundleCommand>()
+                .UseCommandHandler<BundleThinCommand, BundleThinCommand.Handler>()
+                .ConfigureCommand<AuditCommand>()
+                .UseCommandHandler<AuditToolsCommand, AuditToolsCommand.Handler>()
+                .UseCommandHandler<AuditDigestsCommand, AuditDigestsCommand.Handler>()
+                .UseCommandHandler<AuditExpiryCommand, AuditExpiryCommand.Handler>()
+                .UseCommandHandler<AuditDefenderCommand, AuditDefenderCommand.Handler>()
+                .ConfigureCommand<AnalyzeCommand>()
+                .UseCommandHandler<AnalyzeA11yCommand, AnalyzeA11yCommand.Handler>()
+                .UseCommandHandler<AnalyzeSplashCommand, AnalyzeSplashCommand.Handler>()
+                .UseCommandHandler<AnalyzeScalesCommand, AnalyzeScalesCommand.Handler>()
+                .UseCommandHandler<AnalyzeWebViewCommand, AnalyzeWebViewCommand.Handler>()
+                .UseCommandHandler<AnalyzeGpuCommand, AnalyzeGpuCommand.Handler>()
+                .UseCommandHandler<AnalyzeArm64Command, AnalyzeArm64Command.Handler>()
+                .ConfigureCommand<ExplainCommand>()
+                .UseCommandHandler<ExplainManifestCommand, ExplainManifestCommand.Handler>()
+                .UseCommandHandler<ValidateCommand, ValidateCommand.Handler>()
+                .ConfigureCommand<MigrateCommand>()
+                .UseCommandHandler<MigrateWapprojCommand, MigrateWapprojCommand.Handler>()
+                .UseCommandHandler<MigrateFromCommand, MigrateFromCommand.Handler>()
+                .ConfigureCommand<ExportCommand>()
+                .UseCommandHandler<ExportWapprojCommand, ExportWapprojCommand.Handler>()
+                .ConfigureCommand<StoreCommand>()
+                .ConfigureCommand<StoreNamesCommand>()
+                .UseCommandHandler<StoreNamesListCommand, StoreNamesListCommand.Handler>()
+                .UseCommandHandler<StoreNamesReserveCommand, StoreNamesReserveCommand.Handler>()
+                .UseCommandHandler<StoreNamesReleaseCommand, StoreNamesReleaseCommand.Handler>()
+                .UseCommandHandler<StoreAssociateCommand, StoreAssociateCommand.Handler>()
+                .UseCommandHandler<StoreSubmitCommand, StoreSubmitCommand.Handler>()
+                .ConfigureCommand<StoreRolloutCommand>()
+                .UseCommandHandler<StoreRolloutStatusCommand, StoreRolloutStatusCommand.Handler>()
+                .UseCommandHandler<StoreRolloutSetCommand, StoreRolloutSetCommand.Handler>()
+                .UseCommandHandler<StoreRolloutHaltCommand, StoreRolloutHaltCommand.Handler>()
+                .UseCommandHandler<StoreRolloutFinalizeCommand, StoreRolloutFinalizeCommand.Handler>()
+                .ConfigureCommand<StoreFlightCommand>()
+                .UseCommandHandler<StoreFlightListCommand, StoreFlightListCommand.Handler>()
+                .UseCommandHandler<StoreFlightCreateCommand, StoreFlightCreateCommand.Handler>()
+                .UseCommandHandler<StoreFlightDeleteCommand, StoreFlightDeleteCommand.Handler>()
+                .UseCommandHandler<StoreFlightMembersCommand, StoreFlightMembersCommand.Handler>()
+                .UseCommandHandler<StoreStatsCommand, StoreStatsCommand.Handler>()
+                .ConfigureCommand<StoreReviewsCommand>()
+                .UseCommandHandler<StoreReviewsListCommand, StoreReviewsListCommand.Handler>()
+                .UseCommandHandler<StoreReviewsRespondCommand, StoreReviewsRespondCommand.Handler>()
+                .ConfigureCommand<DistributeCommand>()
+                .UseCommandHandler<DistributeWebCommand, DistributeWebCommand.Handler>()
+                .UseCommandHandler<DistributeVerifyCommand, DistributeVerifyCommand.Handler>()
+                .ConfigureCommand<TestCommand>()
+                .UseCommandHandler<TestMatrixCommand, TestMatrixCommand.Handler>()
+                .UseCommandHandler<ChangelogCommand, ChangelogCommand.Handler>()
+                .UseCommandHandler<AffectedCommand, AffectedCommand.Handler>()
+                .UseCommandHandler<CleanupCommand, CleanupCommand.Handler>()
+                .ConfigureCommand<ConfigCommand>()
+                .UseCommandHandler<ConfigShowCommand, ConfigShowCommand.Handler>()
+                .UseCommandHandler<ListCommand, ListCommand.Handler>()
+                .UseCommandHandler<OpenCommand, OpenCommand.Handler>()
+                .ConfigureCommand<StateCommand>()
+                .UseCommandHandler<StateResetCommand, StateResetCommand.Handler>()
+                .UseCommandHandler<StateSnapshotCommand, StateSnapshotCommand.Handler>()
+                .UseCommandHandler<StateRestoreCommand, StateRestoreCommand.Handler>()
+                .ConfigureCommand<CrashesCommand>()
+                .UseCommandHandler<CrashesUploadCommand, CrashesUploadCommand.Handler>()
+                .ConfigureCommand<LogsCommand>()
+                .UseCommandHandler<LogsTailCommand, LogsTailCommand.Handler>()
+                .UseCommandHandler<LogsCollectCommand, LogsCollectCommand.Handler>()
+                .ConfigureCommand<LicenseCommand>()
+                .UseCommandHandler<LicenseKeygenCommand, LicenseKeygenCommand.Handler>()
+                .UseCommandHandler<LicenseIssueCommand, LicenseIssueCommand.Handler>()
+                .UseCommandHandler<LicenseVerifyCommand, LicenseVerifyCommand.Handler>()
+                .UseCommandHandler<LicenseDeviceIdCommand, LicenseDeviceIdCommand.Handler>()
+                .ConfigureCommand<RemoteConfigCommand>()
+                .UseCommandHandler<RemoteConfigValidateCommand, RemoteConfigValidateCommand.Handler>()
+                .ConfigureCommand<RestartCommand>()
+                .UseCommandHandler<RestartSimulateCommand, RestartSimulateCommand.Handler>()
                 .UseCommandHandler<ToolCommand, ToolCommand.Handler>();
     }
 
@@ -71,6 +197,7 @@ internal static class StoreHostBuilderExtensions
                 var command = ActivatorUtilities.CreateInstance<TCommand>(sp);
                 command.Options.Add(WinAppRootCommand.VerboseOption);
                 command.Options.Add(WinAppRootCommand.QuietOption);
+                command.Options.Add(WinAppRootCommand.FipsOption);
                 command.SetAction((parseResult, ct) => sp.GetRequiredService<THandler>().InvokeAsync(parseResult, ct));
                 return command;
             });
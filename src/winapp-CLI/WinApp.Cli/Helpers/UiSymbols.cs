@@ -32,4 +32,5 @@ internal static class UiSymbols
     public static string Id => Emoji.Known.IdButton;
     public static string Clipboard => Emoji.Known.Clipboard;
     public static string Verbose => Emoji.Known.MagnifyingGlassTiltedLeft;
+    public static string Timer => Emoji.Known.Stopwatch;
 }
@@ -0,0 +1,189 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security;
+using System.Text;
+using System.Text.RegularExpressions;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Helpers;
+
+/// <summary>
+/// Renders {{placeholders}} such as {{version.major}}, {{git.sha|short}} or {{branding.displayName}} in manifests,
+/// winapp.yaml values and .appinstaller templates. A placeholder is a variable followed by filters, or a quoted
+/// literal such as {{'{{'}}. Variables that aren't defined are errors, unless a default filter supplies a value.
+/// </summary>
+internal static partial class ValueTemplate
+{
+    private const string Open = "{{";
+    private const string Close = "}}";
+
+    /// <summary>
+    /// Filters, applied left to right. default:text only applies to an undefined value.
+    /// </summary>
+    public static readonly IReadOnlyList<string> Filters = ["short", "lower", "upper", "xml", "default:<text>"];
+
+    [GeneratedRegex(@"^[A-Za-z_][A-Za-z0-9_]*(\.[A-Za-z_][A-Za-z0-9_]*)*$")]
+    private static partial Regex VariableNameRegex();
+
+    [GeneratedRegex(@"<Identity\b[^>]*>", RegexOptions.IgnoreCase)]
+    private static partial Regex IdentityElementRegex();
+
+    [GeneratedRegex(@"\b(?<name>Name|Publisher|Version)\s*=\s*[""'](?<value>[^""']*)[""']")]
+    private static partial Regex IdentityAttributeRegex();
+
+    public static bool HasPlaceholders(string? text) => text?.Contains(Open, StringComparison.Ordinal) == true;
+
+    /// <param name="source">What the template is, for error messages, e.g. appxmanifest.xml</param>
+    /// <exception cref="InvalidOperationException">A placeholder is unclosed, or uses an undefined variable or an unknown filter</exception>
+    public static string Render(string template, TemplateVariables variables, string source)
+    {
+        if (!HasPlaceholders(template))
+        {
+            return template;
+        }
+
+        var result = new StringBuilder(template.Length);
+        var position = 0;
+        while (position < template.Length)
+        {
+            var start = template.IndexOf(Open, position, StringComparison.Ordinal);
+            if (start < 0)
+            {
+                result.Append(template, position, template.Length - position);
+                break;
+            }
+
+            result.Append(template, position, start - position);
+            var end = FindClose(template, start + Open.Length);
+            if (end < 0)
+            {
+                throw new InvalidOperationException($"{source}: {{{{ at line {GetLine(template, start)} is never closed with }}}}");
+            }
+
+            var expression = template[(start + Open.Length)..end];
+            result.Append(Evaluate(expression.Trim(), variables, $"{source} line {GetLine(template, start)}"));
+            position = end + Close.Length;
+        }
+
+        return result.ToString();
+    }
+
+    /// <summary>
+    /// Renders a manifest. The Identity element is rendered first, so the rest can use its version, name and publisher
+    /// as {{version}}, {{package.name}} and {{package.publisher}}.
+    /// </summary>
+    public static string RenderManifest(string manifestXml, TemplateVariables variables, string source)
+    {
+        var identity = IdentityElementRegex().Match(manifestXml);
+        if (!identity.Success)
+        {
+            return Render(manifestXml, variables, source);
+        }
+
+        var renderedIdentity = Render(identity.Value, variables, source);
+        foreach (Match attribute in IdentityAttributeRegex().Matches(renderedIdentity))
+        {
+            var value = attribute.Groups["value"].Value;
+            switch (attribute.Groups["name"].Value)
+            {
+                case "Name":
+                    variables.Set("package.name", value);
+                    break;
+                case "Publisher":
+                    variables.Set("package.publisher", value);
+                    break;
+                case "Version":
+                    variables.SetVersion(value);
+                    break;
+            }
+        }
+
+        return Render(manifestXml[..identity.Index], variables, source)
+            + renderedIdentity
+            + Render(manifestXml[(identity.Index + identity.Length)..], variables, source);
+    }
+
+    private static int FindClose(string template, int from)
+    {
+        // A quoted literal may contain }}, as in {{'}}'}}
+        var inLiteral = false;
+        for (var i = from; i < template.Length - 1; i++)
+        {
+            if (template[i] == '\'')
+            {
+                inLiteral = !inLiteral;
+            }
+            else if (!inLiteral && template[i] == '}' && template[i + 1] == '}')
+            {
+                return i;
+            }
+            else if (template[i] == '\n')
+            {
+                return -1;
+            }
+        }
+
+        return -1;
+    }
+
+    private static string Evaluate(string expression, TemplateVariables variables, string location)
+    {
+        string? value;
+        string subject;
+        string rest;
+        if (expression.StartsWith('\''))
+        {
+            var closing = expression.IndexOf('\'', 1);
+            value = expression[1..closing];
+            subject = expression[..(closing + 1)];
+            rest = expression[(closing + 1)..].TrimStart();
+        }
+        else
+        {
+            var pipe = expression.IndexOf('|');
+            subject = (pipe < 0 ? expression : expression[..pipe]).Trim();
+            rest = pipe < 0 ? string.Empty : expression[pipe..];
+            if (!VariableNameRegex().IsMatch(subject))
+            {
+                throw new InvalidOperationException($"{location}: '{subject}' in {{{{{expression}}}}} is not a variable name");
+            }
+            value = variables.Get(subject);
+        }
+
+        if (rest.Length > 0 && !rest.StartsWith('|'))
+        {
+            throw new InvalidOperationException($"{location}: expected | after {subject} in {{{{{expression}}}}}");
+        }
+
+        foreach (var filter in rest.Length == 0 ? [] : rest[1..].Split('|'))
+        {
+            var trimmed = filter.Trim();
+            if (trimmed.StartsWith("default:", StringComparison.Ordinal))
+            {
+                value ??= trimmed["default:".Length..];
+                continue;
+            }
+
+            Func<string, string> apply = trimmed switch
+            {
+                "short" => v => v.Length > 7 ? v[..7] : v,
+                "lower" => v => v.ToLowerInvariant(),
+                "upper" => v => v.ToUpperInvariant(),
+                "xml" => v => SecurityElement.Escape(v),
+                _ => throw new InvalidOperationException($"{location}: unknown filter '{trimmed}' in {{{{{expression}}}}}. Filters: {string.Join(", ", Filters)}"),
+            };
+
+            // Filters before a default pass an undefined value through
+            if (value != null)
+            {
+                value = apply(value);
+            }
+        }
+
+        return value ?? throw new InvalidOperationException(
+            $"{location}: {subject} is not defined. Defined: {string.Join(", ", variables.Names)}, env.<NAME>; add |default:<text> to make it optional");
+    }
+
+    private static int GetLine(string text, int index) => text.AsSpan(0, index).Count('\n') + 1;
+}
@@ -0,0 +1,210 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Text;
+using System.Text.Json.Nodes;
+
+namespace WinApp.Cli.Licensing;
+
+/// <summary>
+/// What a license grants and to whom. A license is bound to a device, a package family, or both
+/// </summary>
+/// <param name="DeviceId">Device ID the license is bound to, as reported by <see cref="DeviceLicense.GetDeviceId"/></param>
+/// <param name="PackageFamilyName">Package family the license is bound to</param>
+/// <param name="Features">Features the app enables for the licensee; their meaning is up to the app</param>
+internal sealed record LicenseClaims(
+    string Licensee,
+    string? DeviceId,
+    string? PackageFamilyName,
+    DateTimeOffset Issued,
+    DateTimeOffset? Expires,
+    IReadOnlyList<string> Features);
+
+/// <param name="Problem">Why the license is not valid here; null when it is</param>
+internal sealed record LicenseCheck(LicenseClaims Claims, string? Problem)
+{
+    public bool IsValid => Problem == null;
+}
+
+/// <summary>
+/// Creates and checks device-bound license files: the claims as JSON, signed with an ECDSA P-256 key the
+/// publisher keeps, so the app can check them offline with the public key it ships with
+/// </summary>
+/// <remarks>
+/// The file is a JSON object with the base64 UTF-8 claims in <c>payload</c> and the base64 IEEE P1363 ECDSA
+/// signature of those bytes in <c>signature</c>. The runtime helpers of the npm package read the same format.
+/// </remarks>
+internal static class DeviceLicense
+{
+    public const int SchemaVersion = 1;
+    public const string DefaultKeyFileName = "license-signing-key.pem";
+    public const string PublicKeySuffix = ".pub.pem";
+
+    private const string DeviceIdContext = "winapp-license-device:";
+
+    public static string GetPublicKeyPath(string privateKeyPath) =>
+        Path.ChangeExtension(privateKeyPath, null) + PublicKeySuffix;
+
+    /// <summary>
+    /// Derives the device ID from the machine GUID Windows generates at setup, so the GUID itself is not
+    /// handed out to license issuers
+    /// </summary>
+    public static string GetDeviceId(string machineGuid)
+    {
+        var hash = SHA256.HashData(Encoding.UTF8.GetBytes(DeviceIdContext + machineGuid.Trim().ToLowerInvariant()));
+        return Convert.ToHexString(hash, 0, 16);
+    }
+
+    public static string Create(LicenseClaims claims, ECDsa key)
+    {
+        if (claims.DeviceId == null && claims.PackageFamilyName == null)
+        {
+            throw new ArgumentException("A license must be bound to a device ID, a package family name, or both", nameof(claims));
+        }
+
+        var json = new JsonObject
+        {
+            ["schemaVersion"] = SchemaVersion,
+            ["licensee"] = claims.Licensee,
+            ["issued"] = claims.Issued.UtcDateTime.ToString("yyyy-MM-ddTHH:mm:ssZ"),
+        };
+        if (claims.DeviceId != null)
+        {
+            json["deviceId"] = claims.DeviceId.ToUpperInvariant();
+        }
+        if (claims.PackageFamilyName != null)
+        {
+            json["packageFamilyName"] = claims.PackageFamilyName;
+        }
+        if (claims.Expires is DateTimeOffset expires)
+        {
+            json["expires"] = expires.UtcDateTime.ToString("yyyy-MM-ddTHH:mm:ssZ");
+        }
+        if (claims.Features.Count > 0)
+        {
+            json["features"] = new JsonArray(claims.Features.Select(f => (JsonNode?)f).ToArray());
+        }
+
+        var payload = Encoding.UTF8.GetBytes(json.ToJsonString());
+        var signature = key.SignData(payload, HashAlgorithmName.SHA256, DSASignatureFormat.IeeeP1363FixedFieldConcatenation);
+        var license = new JsonObject
+        {
+            ["payload"] = Convert.ToBase64String(payload),
+            ["signature"] = Convert.ToBase64String(signature),
+        };
+        return license.ToJsonString(new System.Text.Json.JsonSerializerOptions { WriteIndented = true });
+    }
+
+    /// <summary>
+    /// Checks the signature of a license and that it is valid on the device and for the package given
+    /// </summary>
+    /// <param name="deviceId">Device to check a device-bound license against</param>
+    /// <param name="packageFamilyName">Package family to check a package-bound license against</param>
+    /// <exception cref="InvalidDataException">The file is not a license</exception>
+    /// <exception cref="CryptographicException">The signature does not match the public key</exception>
+    public static LicenseCheck Verify(string license, ECDsa publicKey, string? deviceId, string? packageFamilyName, DateTimeOffset now)
+    {
+        var (payload, signature) = ReadEnvelope(license);
+        if (!publicKey.VerifyData(payload, signature, HashAlgorithmName.SHA256, DSASignatureFormat.IeeeP1363FixedFieldConcatenation))
+        {
+            throw new CryptographicException("The license signature does not match the public key; it was changed or issued with another key");
+        }
+
+        var claims = ReadClaims(payload);
+        return new LicenseCheck(claims, Check(claims, deviceId, packageFamilyName, now));
+    }
+
+    /// <summary>
+    /// Reads the claims of a license without checking its signature
+    /// </summary>
+    public static LicenseClaims Read(string license) => ReadClaims(ReadEnvelope(license).Payload);
+
+    private static string? Check(LicenseClaims claims, string? deviceId, string? packageFamilyName, DateTimeOffset now)
+    {
+        if (claims.Expires is DateTimeOffset expires && now >= expires)
+        {
+            return $"the license expired on {expires:u}";
+        }
+        if (now < claims.Issued.AddMinutes(-5))
+        {
+            return $"the license is issued on {claims.Issued:u}, after the device clock";
+        }
+        if (claims.DeviceId != null && !string.Equals(claims.DeviceId, deviceId, StringComparison.OrdinalIgnoreCase))
+        {
+            return deviceId == null
+                ? $"the license is bound to device {claims.DeviceId}, and no device ID was given"
+                : $"the license is bound to device {claims.DeviceId}, not {deviceId}";
+        }
+        if (claims.PackageFamilyName != null && !string.Equals(claims.PackageFamilyName, packageFamilyName, StringComparison.OrdinalIgnoreCase))
+        {
+            return packageFamilyName == null
+                ? $"the license is bound to package {claims.PackageFamilyName}, and no package family name was given"
+                : $"the license is bound to package {claims.PackageFamilyName}, not {packageFamilyName}";
+        }
+
+        return null;
+    }
+
+    private static (byte[] Payload, byte[] Signature) ReadEnvelope(string license)
+    {
+        try
+        {
+            var json = JsonNode.Parse(license) as JsonObject ?? throw new InvalidDataException("The license is not a JSON object");
+            var payload = json["payload"]?.GetValue<string>() ?? throw new InvalidDataException("The license has no payload");
+            var signature = json["signature"]?.GetValue<string>() ?? throw new InvalidDataException("The license has no signature");
+            return (Convert.FromBase64String(payload), Convert.FromBase64String(signature));
+        }
+        catch (Exception ex) when (ex is System.Text.Json.JsonException or FormatException or InvalidOperationException)
+        {
+            throw new InvalidDataException($"The file is not a license: {ex.Message}", ex);
+        }
+    }
+
+    private static LicenseClaims ReadClaims(byte[] payload)
+    {
+        try
+        {
+            var json = JsonNode.Parse(payload) as JsonObject ?? throw new InvalidDataException("The license payload is not a JSON object");
+            var schemaVersion = json["schemaVersion"]?.GetValue<int>();
+            if (schemaVersion != SchemaVersion)
+            {
+                throw new InvalidDataException($"Unsupported license schema version {schemaVersion}");
+            }
+
+            return new LicenseClaims(
+                json["licensee"]?.GetValue<string>() ?? string.Empty,
+                json["deviceId"]?.GetValue<string>(),
+                json["packageFamilyName"]?.GetValue<string>(),
+                DateTimeOffset.Parse(json["issued"]?.GetValue<string>() ?? throw new InvalidDataException("The license has no issue date"), System.Globalization.CultureInfo.InvariantCulture),
+                json["expires"]?.GetValue<string>() is string expires ? DateTimeOffset.Parse(expires, System.Globalization.CultureInfo.InvariantCulture) : null,
+                (json["features"] as JsonArray ?? []).Select(f => f?.GetValue<string>() ?? string.Empty).ToList());
+        }
+        catch (Exception ex) when (ex is System.Text.Json.JsonException or FormatException or InvalidOperationException)
+        {
+            throw new InvalidDataException($"The license payload is invalid: {ex.Message}", ex);
+        }
+    }
+
+    /// <summary>
+    /// Package family name Windows gives a package: its Identity Name and the publisher ID, which is the first
+    /// 64 bits of the SHA-256 of the UTF-16 publisher, in Crockford's base32
+    /// </summary>
+    public static string GetPackageFamilyName(string identityName, string publisher)
+    {
+        const string alphabet = "0123456789abcdefghjkmnpqrstvwxyz";
+        var hash = SHA256.HashData(Encoding.Unicode.GetBytes(publisher));
+        var bits = System.Buffers.Binary.BinaryPrimitives.ReadUInt64BigEndian(hash);
+
+        // 64 bits padded with a zero bit to 13 groups of 5
+        var publisherId = new StringBuilder(13);
+        for (var i = 0; i < 13; i++)
+        {
+            var shift = 59 - (i * 5);
+            var group = shift >= 0 ? (bits >> shift) & 0x1F : (bits << -shift) & 0x1F;
+            publisherId.Append(alphabet[(int)group]);
+        }
+
+        return $"{identityName}_{publisherId}";
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// How serious an issue 'winapp analyze a11y' reports is
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<AccessibilitySeverity>))]
+public enum AccessibilitySeverity
+{
+    /// <summary>
+    /// Suggestion that improves the experience for some users
+    /// </summary>
+    Info,
+
+    /// <summary>
+    /// Likely to be flagged by an accessibility review
+    /// </summary>
+    Warning,
+
+    /// <summary>
+    /// Missing or invalid asset or metadata
+    /// </summary>
+    Error
+}
+
+/// <param name="Check">Which check found the issue: contrast, unplated, short-name, high-contrast or asset</param>
+/// <param name="Subject">Manifest attribute or asset the issue is about</param>
+internal sealed record AccessibilityIssue(AccessibilitySeverity Severity, string Check, string Subject, string Message);
+
+/// <param name="Applications">Ids of the applications that were checked</param>
+internal sealed record AccessibilityReport(IReadOnlyList<string> Applications, IReadOnlyList<AccessibilityIssue> Issues);
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Output format of 'winapp affected'
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<AffectedFormat>))]
+public enum AffectedFormat
+{
+    /// <summary>
+    /// One project path per line, for shell loops
+    /// </summary>
+    Text,
+
+    /// <summary>
+    /// Projects with the changed files that affect them, e.g. for a CI matrix
+    /// </summary>
+    Json
+}
@@ -0,0 +1,11 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <param name="Files">Changed files, relative to winapp.yaml, that make the project need re-packing</param>
+internal sealed record AffectedProject(string Name, string Path, IReadOnlyList<string> Files);
+
+/// <param name="ChangedFiles">All changed files, relative to winapp.yaml</param>
+/// <param name="Discovered">The projects were found from their manifests because winapp.yaml declares none</param>
+internal sealed record AffectedResult(string Since, IReadOnlyList<string> ChangedFiles, IReadOnlyList<ProjectConfig> Projects, IReadOnlyList<AffectedProject> Affected, bool Discovered);
@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A custom analyzer declared under 'analyzers:' in winapp.yaml: a script or WASM module that reads the manifest,
+/// winapp.yaml and the payload as JSON on stdin and writes diagnostics as JSON on stdout
+/// </summary>
+internal sealed class AnalyzerConfig
+{
+    /// <summary>
+    /// Source of the analyzer's rules, e.g. contoso for contoso/publisher
+    /// </summary>
+    public string Id { get; set; } = "";
+
+    /// <summary>
+    /// Script or module, relative to winapp.yaml: .ps1 runs in PowerShell, .js in Node.js, .wasm in wasmtime and
+    /// anything else as an executable
+    /// </summary>
+    public string Script { get; set; } = "";
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A diagnostic a custom analyzer of winapp.yaml reported
+/// </summary>
+/// <param name="Rule">Rule id, prefixed with the analyzer's id, e.g. contoso/publisher</param>
+/// <param name="Element">Path of the manifest element it is about, as the analyzer's input lists them, or null for the root</param>
+/// <param name="Subject">Attribute, element or file it is about, shown before the message</param>
+internal sealed record AnalyzerDiagnostic(AccessibilitySeverity Severity, string Rule, string? Element, string? Subject, string Message);
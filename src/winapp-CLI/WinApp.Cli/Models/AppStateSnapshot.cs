@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Description 'state snapshot' stores next to the files of a snapshot, to check it against the package it is
+/// restored to
+/// </summary>
+internal sealed class AppStateSnapshot
+{
+    public string PackageFamilyName { get; set; } = "";
+
+    public string Version { get; set; } = "";
+
+    public DateTimeOffset CreatedAt { get; set; }
+
+    public int FileCount { get; set; }
+
+    public long Size { get; set; }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An issue 'winapp analyze arm64' reports, with the severities of 'winapp analyze a11y'
+/// </summary>
+/// <param name="Check">Which check found the issue: driver, shell-extension, kernel-adjacent, mixed or emulated</param>
+/// <param name="Binary">Path of the executable or DLL, relative to the package folder</param>
+internal sealed record Arm64Issue(AccessibilitySeverity Severity, string Check, string Binary, string Message);
+
+/// <param name="Path">Relative to the package folder</param>
+internal sealed record Arm64Binary(string Path, ImageArchitecture Architecture);
+
+/// <param name="ProcessorArchitecture">The ProcessorArchitecture of the manifest's Identity</param>
+/// <param name="Binaries">The executables, DLLs, drivers and Node.js addons of the package folder</param>
+internal sealed record Arm64Report(string ProcessorArchitecture, IReadOnlyList<Arm64Binary> Binaries, IReadOnlyList<Arm64Issue> Issues);
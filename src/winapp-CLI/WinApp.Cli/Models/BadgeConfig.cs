@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A ribbon 'pack --profile' draws across the package's icons, declared under 'badges:' in winapp.yaml
+/// </summary>
+internal sealed class BadgeConfig
+{
+    /// <summary>
+    /// Build profile the badge is for, as passed to 'pack --profile'
+    /// </summary>
+    public string Profile { get; set; } = "";
+
+    /// <summary>
+    /// Text of the ribbon, such as BETA or DEV
+    /// </summary>
+    public string Text { get; set; } = "";
+
+    /// <summary>
+    /// Ribbon color as #RRGGBB or a color name (default: orange)
+    /// </summary>
+    public string? Color { get; set; }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security;
+using System.Security.Cryptography;
+using System.Text;
+using System.Text.RegularExpressions;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Throwaway package identity for builds of a git branch. The Identity Name gets a suffix and the publisher a
+/// certificate of their own, both derived from the branch name, so a branch build installs next to the main build
+/// and every build of the branch updates the same package.
+/// </summary>
+internal sealed partial record BranchIdentity(string Branch)
+{
+    public const string PublisherPrefix = "CN=WinApp Branch ";
+
+    private const int MaxNameLength = 50;
+
+    /// <summary>
+    /// First 8 hex digits of the SHA-256 of the branch name
+    /// </summary>
+    public string Hash { get; } = Convert.ToHexStringLower(SHA256.HashData(Encoding.UTF8.GetBytes(Branch)))[..8];
+
+    /// <summary>
+    /// Appended to the Identity Name; Identity Names only allow letters, digits, '.' and '-'
+    /// </summary>
+    public string NameSuffix => $".br{Hash}";
+
+    public string Publisher => PublisherPrefix + Hash;
+
+    public string ApplyToName(string name)
+    {
+        var maxLength = MaxNameLength - NameSuffix.Length;
+        return (name.Length > maxLength ? name[..maxLength] : name) + NameSuffix;
+    }
+
+    // The manifest holds display names XML-escaped already, so only the branch needs escaping
+    private string EscapedBranchSuffix => SecurityElement.Escape($" ({Branch})");
+
+    /// <summary>
+    /// Renames the package identity and publisher, and adds the branch to the display names so testers can tell the
+    /// builds apart in Start. Display names that come from resources are left alone.
+    /// </summary>
+    public string ApplyToManifest(string manifestContent)
+    {
+        manifestContent = IdentityNameRegex().Replace(manifestContent, m => m.Groups[1].Value + m.Groups[2].Value + ApplyToName(m.Groups[3].Value) + m.Groups[2].Value, 1);
+        manifestContent = IdentityPublisherRegex().Replace(manifestContent, m => m.Groups[1].Value + m.Groups[2].Value + Publisher + m.Groups[2].Value, 1);
+        manifestContent = PropertiesDisplayNameRegex().Replace(manifestContent, m => IsResource(m.Groups[2].Value)
+            ? m.Value
+            : m.Groups[1].Value + m.Groups[2].Value + EscapedBranchSuffix + m.Groups[3].Value, 1);
+        manifestContent = VisualElementsDisplayNameRegex().Replace(manifestContent, m => IsResource(m.Groups[3].Value)
+            ? m.Value
+            : m.Groups[1].Value + m.Groups[2].Value + m.Groups[3].Value + EscapedBranchSuffix + m.Groups[2].Value);
+        return manifestContent;
+    }
+
+    private static bool IsResource(string value) => value.StartsWith("ms-resource:", StringComparison.OrdinalIgnoreCase);
+
+    [GeneratedRegex(@"(<Identity\b[^>]*?\sName\s*=\s*)([""'])([^""']*)\2", RegexOptions.IgnoreCase)]
+    private static partial Regex IdentityNameRegex();
+
+    [GeneratedRegex(@"(<Identity\b[^>]*?\sPublisher\s*=\s*)([""'])([^""']*)\2", RegexOptions.IgnoreCase)]
+    private static partial Regex IdentityPublisherRegex();
+
+    [GeneratedRegex(@"(<Properties>[\s\S]*?<DisplayName>)([^<]*)(</DisplayName>)", RegexOptions.IgnoreCase)]
+    private static partial Regex PropertiesDisplayNameRegex();
+
+    [GeneratedRegex(@"(<uap:VisualElements\b[^>]*?\sDisplayName\s*=\s*)([""'])([^""']*)\2", RegexOptions.IgnoreCase)]
+    private static partial Regex VisualElementsDisplayNameRegex();
+}
@@ -0,0 +1,39 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A white-label brand of the app, under 'matrix: brandings:' in winapp.yaml. What it leaves out is kept from the
+/// manifest.
+/// </summary>
+internal sealed class BrandingConfig
+{
+    public string Name { get; set; } = "";
+
+    public string? IdentityName { get; set; }
+
+    public string? Publisher { get; set; }
+
+    public string? PublisherDisplayName { get; set; }
+
+    /// <summary>
+    /// Display name of the package and of each application
+    /// </summary>
+    public string? DisplayName { get; set; }
+
+    /// <summary>
+    /// Folder, relative to winapp.yaml, whose files replace the files at the same path relative to the manifest
+    /// </summary>
+    public string? Assets { get; set; }
+
+    /// <summary>
+    /// Certificate to sign the brand's packages with, relative to winapp.yaml, whose subject is <see cref="Publisher"/>
+    /// </summary>
+    public string? Certificate { get; set; }
+
+    /// <summary>
+    /// URI schemes the brand registers in place of the manifest's
+    /// </summary>
+    public List<string> Protocols { get; set; } = new();
+}
@@ -0,0 +1,44 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// How 'winapp pack --build' builds the app, under 'build:' in winapp.yaml. Without it, the build system is detected
+/// from the project files next to winapp.yaml
+/// </summary>
+internal sealed class BuildConfig
+{
+    /// <summary>
+    /// Build system (default: detected from the project files)
+    /// </summary>
+    public BuildSystem? System { get; set; }
+
+    /// <summary>
+    /// Project folder or file, such as src-tauri or App.sln, relative to winapp.yaml (default: the folder of winapp.yaml)
+    /// </summary>
+    public string? Project { get; set; }
+
+    /// <summary>
+    /// Release or Debug
+    /// </summary>
+    public string Configuration { get; set; } = "Release";
+
+    /// <summary>
+    /// Command line of the custom build, run in the project folder
+    /// </summary>
+    public string? Command { get; set; }
+
+    /// <summary>
+    /// Further arguments to the build tool, such as --features or /p:Property=Value
+    /// </summary>
+    public string? Arguments { get; set; }
+
+    /// <summary>
+    /// Folder of the build output that becomes the payload, relative to the project folder. Required for custom builds;
+    /// replaces where the other build systems look for their outputs
+    /// </summary>
+    public string? Output { get; set; }
+
+    public bool IsDebug => Configuration.Equals("Debug", StringComparison.OrdinalIgnoreCase);
+}
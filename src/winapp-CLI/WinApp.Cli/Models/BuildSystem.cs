@@ -0,0 +1,48 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The build system 'winapp pack --build' runs, under 'build: system:' in winapp.yaml
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<BuildSystem>))]
+public enum BuildSystem
+{
+    /// <summary>
+    /// A Rust crate or workspace, built with cargo
+    /// </summary>
+    Cargo,
+
+    /// <summary>
+    /// A Tauri app, built with the Tauri CLI without its installers
+    /// </summary>
+    Tauri,
+
+    /// <summary>
+    /// A Node.js or Electron app, built with its npm build script
+    /// </summary>
+    Npm,
+
+    /// <summary>
+    /// A .NET project, published with dotnet publish
+    /// </summary>
+    Dotnet,
+
+    /// <summary>
+    /// A CMake project, configured and built with cmake
+    /// </summary>
+    CMake,
+
+    /// <summary>
+    /// A Visual Studio solution or C++ project, built with MSBuild
+    /// </summary>
+    MSBuild,
+
+    /// <summary>
+    /// Any other build, run as a command line
+    /// </summary>
+    Custom
+}
@@ -0,0 +1,6 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+internal record BundleThinResult(FileInfo MsixPath, string ApplicationPackage, IReadOnlyList<string> ResourcePackages, bool Signed);
@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// File format of generated release notes
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<ChangelogFormat>))]
+public enum ChangelogFormat
+{
+    Markdown,
+
+    /// <summary>
+    /// Plain text, as the Store listing's release notes and What's New strings expect
+    /// </summary>
+    Text,
+
+    Json
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// What release notes are built from
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<ChangelogSource>))]
+public enum ChangelogSource
+{
+    /// <summary>
+    /// Conventional commits (feat:, fix:, ...) on the branch, excluding merge commits
+    /// </summary>
+    Commits,
+
+    /// <summary>
+    /// Titles of the pull requests merged into the branch, from merge or squash commits
+    /// </summary>
+    Prs
+}
@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A release channel, under 'matrix: channels:' in winapp.yaml, such as beta next to stable
+/// </summary>
+internal sealed class ChannelConfig
+{
+    public string Name { get; set; } = "";
+
+    /// <summary>
+    /// Appended to the Identity Name, so the channel installs next to the others, e.g. .Beta
+    /// </summary>
+    public string? NameSuffix { get; set; }
+
+    /// <summary>
+    /// Appended to the display names, e.g. " (Beta)"
+    /// </summary>
+    public string? DisplayNameSuffix { get; set; }
+}
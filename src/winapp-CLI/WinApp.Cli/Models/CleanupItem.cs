@@ -0,0 +1,46 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+internal enum CleanupItemKind
+{
+    /// <summary>
+    /// Package registered from a folder in development mode (Add-AppxPackage -Register, create-debug-identity)
+    /// </summary>
+    LoosePackage,
+
+    /// <summary>
+    /// Registration whose folder or external location is gone, or that Windows reports as broken
+    /// </summary>
+    StaleRegistration,
+
+    /// <summary>
+    /// Package staged on the machine but installed for no user
+    /// </summary>
+    StagedPackage,
+
+    /// <summary>
+    /// Development certificate whose publisher no installed package uses
+    /// </summary>
+    OrphanedCertificate
+}
+
+/// <param name="Id">Package full name, or store and thumbprint of a certificate</param>
+internal sealed record CleanupItem(CleanupItemKind Kind, string Id, string Description);
+
+internal sealed record StoreCertificate(string Store, string Thumbprint, string Subject, string FriendlyName);
+
+/// <summary>
+/// What 'winapp cleanup' looks at
+/// </summary>
+/// <param name="ProjectDirectory">Packages registered from under this folder belong to the project</param>
+/// <param name="IdentityNames">Identity Names of the project's manifest</param>
+/// <param name="Publishers">Publishers of the project's manifest</param>
+/// <param name="AllWinapp">Include everything winapp created on the machine, not only for this project</param>
+internal sealed record CleanupScope(string ProjectDirectory, IReadOnlySet<string> IdentityNames, IReadOnlySet<string> Publishers, bool AllWinapp);
+
+/// <param name="StagedChecked">Staged packages can only be listed as administrator</param>
+internal sealed record CleanupPlan(IReadOnlyList<CleanupItem> Items, bool StagedChecked);
+
+internal sealed record CleanupResult(IReadOnlyList<CleanupItem> Removed, IReadOnlyList<(CleanupItem Item, string Error)> Failed);
@@ -0,0 +1,10 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// What 'crashes upload' sent
+/// </summary>
+/// <param name="ReportIds">What the service answered for each dump, such as the id of its crash report</param>
+internal sealed record CrashUploadResult(int DumpCount, int SymbolFileCount, IReadOnlyList<string> ReportIds);
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Where 'winapp crashes upload' sends crash dumps and symbols, under 'crashes:' in winapp.yaml
+/// </summary>
+internal sealed class CrashesConfig
+{
+    /// <summary>
+    /// URL that takes minidumps the way Crashpad uploads them, e.g. a Sentry project's minidump endpoint or a
+    /// BugSplat database's crash post URL
+    /// </summary>
+    public string Endpoint { get; set; } = "";
+
+    /// <summary>
+    /// URL that takes the .pdb files and binaries of a version, one per request
+    /// </summary>
+    public string? SymbolsEndpoint { get; set; }
+
+    /// <summary>
+    /// Product name reported with each dump (default: the package name)
+    /// </summary>
+    public string? Product { get; set; }
+}
@@ -3,4 +3,5 @@
 
 namespace WinApp.Cli.Models;
 
-internal record CreateMsixPackageResult(FileInfo MsixPath, bool Signed);
+/// <param name="FromCache">The package was downloaded from the remote cache instead of packed</param>
+internal record CreateMsixPackageResult(FileInfo MsixPath, bool Signed, bool FromCache = false);
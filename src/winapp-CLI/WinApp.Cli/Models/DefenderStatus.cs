@@ -0,0 +1,55 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The EnableControlledFolderAccess values of Get-MpPreference
+/// </summary>
+internal enum ControlledFolderAccessMode
+{
+    Disabled = 0,
+    Enabled = 1,
+    Audit = 2,
+    BlockDiskModification = 3,
+    AuditDiskModification = 4,
+}
+
+/// <summary>
+/// The Microsoft Defender settings that affect the dev loop, read by 'winapp audit defender'
+/// </summary>
+/// <param name="Active">Whether Defender is the active antivirus; false when it isn't installed or runs in passive mode next to another one</param>
+/// <param name="ExclusionPaths">The excluded folders and files; null when they can't be read, as they need administrator rights</param>
+/// <param name="Events">Controlled Folder Access events of the last week</param>
+internal sealed record DefenderStatus(
+    bool Active,
+    bool RealTimeProtection,
+    string? RunningMode,
+    ControlledFolderAccessMode ControlledFolderAccess,
+    IReadOnlyList<string>? ExclusionPaths,
+    IReadOnlyList<string> AllowedApplications,
+    IReadOnlyList<ControlledFolderAccessEvent> Events);
+
+/// <param name="Blocked">Whether the write was blocked (event 1123) rather than audited (event 1124)</param>
+/// <param name="Process">The executable that wrote</param>
+/// <param name="Path">The protected file it wrote to</param>
+internal sealed record ControlledFolderAccessEvent(DateTimeOffset Time, bool Blocked, string Process, string Path);
+
+/// <summary>
+/// How long writing, reading back and deleting small files takes in a folder of the dev loop
+/// </summary>
+/// <param name="Excluded">Whether the folder is excluded from scanning; null when the exclusions can't be read</param>
+internal sealed record DefenderFolderTiming(string Folder, bool? Excluded, double MillisecondsPerFile);
+
+/// <summary>
+/// The result of 'winapp audit defender', which --report writes for IT
+/// </summary>
+/// <param name="Folders">The folders the dev loop writes to, which --exclude excludes from scanning</param>
+/// <param name="Applications">The tools that write to them, which --exclude allows through Controlled Folder Access</param>
+internal sealed record DefenderAudit(
+    DefenderStatus Status,
+    IReadOnlyList<DefenderFolderTiming> Folders,
+    IReadOnlyList<string> Applications,
+    IReadOnlyList<DefenderFinding> Findings);
+
+internal sealed record DefenderFinding(AccessibilitySeverity Severity, string Subject, string Message);
@@ -0,0 +1,12 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An error or warning that the deployment or app model runtime logged about a package
+/// </summary>
+/// <param name="LogName">Event log channel, such as Microsoft-Windows-AppXDeploymentServer/Operational</param>
+/// <param name="Level">1 critical, 2 error, 3 warning</param>
+/// <param name="ErrorCode">HRESULT the message reports, such as 0x80073CF3</param>
+internal sealed record DeploymentEvent(DateTime TimeCreated, string LogName, int Id, int Level, string Message, string? ErrorCode);
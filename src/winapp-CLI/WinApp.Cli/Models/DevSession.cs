@@ -0,0 +1,30 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Dev;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The layout 'winapp dev' registered with the debug identity of the manifest, and the executable it runs from it
+/// </summary>
+internal sealed class DevSession(DevLayout layout, FileInfo manifest)
+{
+    public DevLayout Layout => layout;
+
+    public FileInfo Manifest => manifest;
+
+    /// <summary>
+    /// The Executable of the manifest's Application, in the layout
+    /// </summary>
+    public required FileInfo Executable { get; set; }
+
+    public required MsixIdentityResult Identity { get; set; }
+}
+
+/// <summary>
+/// What 'winapp dev --watch' did after a change
+/// </summary>
+/// <param name="Reregistered">Whether the manifest changed, so the package was registered again</param>
+/// <param name="IdentityEmbedded">Whether the executable was rebuilt, so the identity was embedded into it again</param>
+internal sealed record DevUpdate(int CopiedCount, int DeletedCount, bool Reregistered, bool IdentityEmbedded);
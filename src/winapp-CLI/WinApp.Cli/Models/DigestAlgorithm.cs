@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Hash algorithm for package block maps and signature digests
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<DigestAlgorithm>))]
+public enum DigestAlgorithm
+{
+    Sha256,
+    Sha384,
+    Sha512
+}
+
+internal static class DigestAlgorithmExtensions
+{
+    public static HashAlgorithmName ToHashAlgorithmName(this DigestAlgorithm digest) => digest switch
+    {
+        DigestAlgorithm.Sha384 => HashAlgorithmName.SHA384,
+        DigestAlgorithm.Sha512 => HashAlgorithmName.SHA512,
+        _ => HashAlgorithmName.SHA256
+    };
+}
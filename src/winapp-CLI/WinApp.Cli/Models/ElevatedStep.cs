@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A step of a command that needs administrator rights, which the elevation broker runs together with the command's
+/// other such steps after a single User Account Control prompt
+/// </summary>
+/// <param name="Description">What the step does, listed before the prompt, e.g. "Trust certificate CN=Contoso"</param>
+/// <param name="Script">PowerShell that performs it, failing by throwing</param>
+internal sealed record ElevatedStep(string Description, string Script);
+
+/// <param name="Error">Why the step failed; null when it succeeded</param>
+internal sealed record ElevatedStepResult(ElevatedStep Step, string? Error)
+{
+    public bool Succeeded => Error == null;
+}
@@ -0,0 +1,14 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An environment variable the package sets for its processes, declared under 'environment:' in winapp.yaml
+/// </summary>
+internal sealed class EnvironmentVariableConfig
+{
+    public string Name { get; set; } = "";
+
+    public string Value { get; set; } = "";
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+internal sealed class ExpiryAuditOptions
+{
+    /// <summary>
+    /// PFX or CER files to check; empty checks devcert.pfx next to winapp.yaml, if there is one
+    /// </summary>
+    public IReadOnlyList<FileInfo> Certificates { get; init; } = [];
+
+    public string CertificatePassword { get; init; } = "password";
+
+    /// <summary>
+    /// Check the secret of the Azure AD application in WINAPP_STORE_CLIENT_ID, which needs Application.Read.All in Microsoft Graph
+    /// </summary>
+    public bool StoreSecret { get; init; }
+
+    /// <summary>
+    /// Azure resource ID of a Trusted Signing certificate profile, read with the AZURE_* credentials
+    /// </summary>
+    public string? TrustedSigningProfile { get; init; }
+
+    public int WarningDays { get; init; } = 30;
+
+    public int ErrorDays { get; init; } = 7;
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A credential checked by 'winapp audit expiry'
+/// </summary>
+/// <param name="Subject">What was checked, e.g. the certificate file or the Azure AD application</param>
+/// <param name="ExpiresOn">When it expires; null when it has no expiry, such as a Trusted Signing profile</param>
+/// <param name="Severity">Error when it has expired, is inactive or expires within the error threshold; Warning within the warning threshold</param>
+internal sealed record ExpiryCheck(string Subject, DateTimeOffset? ExpiresOn, AccessibilitySeverity Severity, string Message);
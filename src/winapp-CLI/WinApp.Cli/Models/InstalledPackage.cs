@@ -0,0 +1,72 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// How a package got onto the machine
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<PackageRegistrationKind>))]
+public enum PackageRegistrationKind
+{
+    /// <summary>
+    /// Installed from the Microsoft Store
+    /// </summary>
+    Store,
+
+    /// <summary>
+    /// Installed from a signed .msix or .appinstaller outside the Store
+    /// </summary>
+    Sideloaded,
+
+    /// <summary>
+    /// Registered from a folder in development mode (Add-AppxPackage -Register, create-debug-identity)
+    /// </summary>
+    Development,
+
+    /// <summary>
+    /// Part of Windows
+    /// </summary>
+    System
+}
+
+/// <summary>
+/// A package as Get-AppxPackage reports it
+/// </summary>
+/// <param name="SignatureKind">None, Developer, Enterprise, Store or System</param>
+/// <param name="Staged">Staged on the machine but installed for no user</param>
+internal sealed record InstalledPackage(
+    string FullName,
+    string Name,
+    string Publisher,
+    string Version,
+    string FamilyName,
+    string? InstallLocation,
+    bool InstallLocationExists,
+    string? ExternalLocation,
+    bool ExternalLocationExists,
+    bool DevelopmentMode,
+    bool IsFramework,
+    string SignatureKind,
+    string Status,
+    bool Staged)
+{
+    public PackageRegistrationKind Registration => DevelopmentMode
+        ? PackageRegistrationKind.Development
+        : SignatureKind switch
+        {
+            "Store" => PackageRegistrationKind.Store,
+            "System" => PackageRegistrationKind.System,
+            _ => PackageRegistrationKind.Sideloaded,
+        };
+
+    /// <summary>
+    /// Folder Windows gives the package for ApplicationData (LocalState, RoamingState, TempState, ...)
+    /// </summary>
+    public string AppDataPath => Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.LocalApplicationData), "Packages", FamilyName);
+}
+
+/// <param name="StagedChecked">Staged packages can only be listed as administrator</param>
+internal sealed record InstalledPackageList(IReadOnlyList<InstalledPackage> Packages, bool StagedChecked);
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Output format of 'winapp list --installed'
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<InstalledPackageFormat>))]
+public enum InstalledPackageFormat
+{
+    /// <summary>
+    /// Name, version, kind and folder of each package
+    /// </summary>
+    Text,
+
+    /// <summary>
+    /// Every field Get-AppxPackage reports, for scripts
+    /// </summary>
+    Json
+}
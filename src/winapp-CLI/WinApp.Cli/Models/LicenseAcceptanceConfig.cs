@@ -0,0 +1,34 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Record in winapp.yaml that the license of a package that requires license acceptance was accepted
+/// </summary>
+internal sealed class LicenseAcceptanceConfig
+{
+    public string Package { get; set; } = "";
+
+    /// <summary>
+    /// Version the license was accepted for. Later versions with the same license don't need accepting again
+    /// </summary>
+    public string Version { get; set; } = "";
+
+    /// <summary>
+    /// SPDX expression, file in the package or URL of the license
+    /// </summary>
+    public string License { get; set; } = "";
+
+    /// <summary>
+    /// SHA-256 of the license file, for licenses in a file, so a changed text needs accepting again
+    /// </summary>
+    public string? Sha256 { get; set; }
+
+    public string AcceptedBy { get; set; } = "";
+
+    /// <summary>
+    /// UTC time of the acceptance, in ISO 8601
+    /// </summary>
+    public string AcceptedAt { get; set; } = "";
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Locale overrides of the packaged app, under 'locale:' in winapp.yaml, which 'package' sets as environment variables
+/// of the package
+/// </summary>
+internal sealed class LocaleConfig
+{
+    /// <summary>
+    /// Environment variable winapp-runtime reads the language override from
+    /// </summary>
+    public const string LanguageVariable = "WINAPP_LOCALE";
+
+    /// <summary>
+    /// Language the app runs in whatever the user's settings, as a BCP-47 tag such as fr-FR
+    /// </summary>
+    public string? Language { get; set; }
+
+    /// <summary>
+    /// Whether runtimes with a UTF-8 mode use it regardless of the code page, such as Python with PYTHONUTF8 (PEP 540)
+    /// </summary>
+    public bool Utf8Mode { get; set; }
+
+    public IEnumerable<EnvironmentVariableConfig> GetEnvironmentVariables()
+    {
+        if (!string.IsNullOrWhiteSpace(Language))
+        {
+            yield return new EnvironmentVariableConfig { Name = LanguageVariable, Value = Language };
+        }
+        if (Utf8Mode)
+        {
+            yield return new EnvironmentVariableConfig { Name = "PYTHONUTF8", Value = "1" };
+        }
+    }
+}
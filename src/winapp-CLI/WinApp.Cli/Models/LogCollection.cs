@@ -0,0 +1,9 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The log files 'logs collect' put in a .zip
+/// </summary>
+internal sealed record LogCollection(int FileCount, long Size);
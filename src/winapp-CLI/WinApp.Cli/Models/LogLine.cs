@@ -0,0 +1,9 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A line of a log file that 'logs tail' prints
+/// </summary>
+internal sealed record LogLine(FileInfo File, string Text);
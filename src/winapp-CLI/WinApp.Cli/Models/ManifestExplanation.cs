@@ -0,0 +1,17 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A finding placed on the manifest element it is about
+/// </summary>
+/// <param name="Rule">Id of the rule that found it, e.g. a11y/contrast or manifest/version</param>
+/// <param name="Element">Path of the element, e.g. Package/Applications/Application[Notes]/VisualElements, which stays the same when lines move</param>
+/// <param name="Subject">What the analyze check reported the finding about: attribute, application id or asset; null for schema versions</param>
+internal sealed record ManifestNote(AccessibilitySeverity Severity, string Rule, string Element, string? Subject, string Message);
+
+/// <param name="Text">The manifest with a comment before each element, explaining it and listing its findings</param>
+/// <param name="Elements">Number of elements that were explained</param>
+/// <param name="Notes">Findings, in the order of the elements they were placed on</param>
+internal sealed record ManifestExplanation(string Text, int Elements, IReadOnlyList<ManifestNote> Notes);
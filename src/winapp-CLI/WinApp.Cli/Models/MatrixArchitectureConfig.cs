@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An architecture, under 'matrix: architectures:' in winapp.yaml
+/// </summary>
+internal sealed class MatrixArchitectureConfig
+{
+    /// <summary>
+    /// ProcessorArchitecture of the package: x86, x64, arm64 or neutral
+    /// </summary>
+    public string Name { get; set; } = "";
+
+    /// <summary>
+    /// Package layout built for the architecture, relative to winapp.yaml (default: the input folder of 'pack')
+    /// </summary>
+    public string? Input { get; set; }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The variants 'pack --matrix' builds, under 'matrix:' in winapp.yaml: every branding in every channel for every
+/// architecture
+/// </summary>
+internal sealed class MatrixConfig
+{
+    public List<BrandingConfig> Brandings { get; set; } = new();
+
+    public List<ChannelConfig> Channels { get; set; } = new();
+
+    public List<MatrixArchitectureConfig> Architectures { get; set; } = new();
+
+    /// <summary>
+    /// Every combination of the dimensions; a dimension without entries doesn't multiply the variants
+    /// </summary>
+    public List<MatrixVariant> Expand()
+    {
+        BrandingConfig?[] brandings = Brandings.Count > 0 ? [.. Brandings] : [null];
+        ChannelConfig?[] channels = Channels.Count > 0 ? [.. Channels] : [null];
+        MatrixArchitectureConfig?[] architectures = Architectures.Count > 0 ? [.. Architectures] : [null];
+        return [.. from branding in brandings
+                   from channel in channels
+                   from architecture in architectures
+                   select new MatrixVariant(branding, channel, architecture)];
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// One package of the build matrix; null dimensions are the ones winapp.yaml doesn't declare
+/// </summary>
+internal sealed record MatrixVariant(BrandingConfig? Branding, ChannelConfig? Channel, MatrixArchitectureConfig? Architecture)
+{
+    /// <summary>
+    /// The dimension names joined with '-', e.g. contoso-beta-arm64; also the name of its package file
+    /// </summary>
+    public string Name => string.Join('-', new[] { Branding?.Name, Channel?.Name, Architecture?.Name }.Where(n => !string.IsNullOrEmpty(n)));
+
+    /// <summary>
+    /// Whether <paramref name="filter"/> is the variant's name or the name of one of its dimensions
+    /// </summary>
+    public bool Matches(string filter) =>
+        new[] { Name, Branding?.Name, Channel?.Name, Architecture?.Name }.Any(n => string.Equals(n, filter, StringComparison.OrdinalIgnoreCase));
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The manifest and assets of a build matrix variant, written over the project's own while its package is packed.
+/// Disposing it puts the originals back.
+/// </summary>
+internal sealed class MatrixVariantLayout(DirectoryInfo inputFolder, FileInfo manifestPath, FileInfo? certificate, IReadOnlyDictionary<string, byte[]?> originals) : IDisposable
+{
+    /// <summary>
+    /// Package layout of the variant's architecture
+    /// </summary>
+    public DirectoryInfo InputFolder { get; } = inputFolder;
+
+    /// <summary>
+    /// The project's manifest, which holds the variant's identity until disposed
+    /// </summary>
+    public FileInfo ManifestPath { get; } = manifestPath;
+
+    /// <summary>
+    /// Certificate of the variant's branding, if it has one
+    /// </summary>
+    public FileInfo? Certificate { get; } = certificate;
+
+    public void Dispose()
+    {
+        foreach (var (path, original) in originals)
+        {
+            if (original != null)
+            {
+                File.WriteAllBytes(path, original);
+            }
+            else if (File.Exists(path))
+            {
+                File.Delete(path);
+            }
+        }
+    }
+}
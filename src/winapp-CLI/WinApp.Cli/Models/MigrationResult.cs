@@ -0,0 +1,13 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Result of the 'winapp migrate' commands
+/// </summary>
+/// <param name="Manifest">The appxmanifest.xml written next to winapp.yaml</param>
+/// <param name="Assets">Number of image files copied to Assets</param>
+/// <param name="Migrated">What was carried over to winapp.yaml and the manifest</param>
+/// <param name="Manual">What couldn't be migrated automatically, and what to use instead</param>
+internal sealed record MigrationResult(FileInfo Manifest, int Assets, IReadOnlyList<string> Migrated, IReadOnlyList<string> Manual);
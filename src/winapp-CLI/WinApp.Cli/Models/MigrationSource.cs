@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Bundler whose MSIX configuration 'winapp migrate from' reads
+/// </summary>
+public enum MigrationSource
+{
+    /// <summary>
+    /// The 'build' section of package.json, or electron-builder.json, with its 'appx' options and build/appx assets
+    /// </summary>
+    ElectronBuilder,
+
+    /// <summary>
+    /// tauri.conf.json of Tauri 1 or 2, with its bundle identifier, publisher and icons
+    /// </summary>
+    Tauri
+}
@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// License of an installed package whose .nuspec requires license acceptance
+/// </summary>
+/// <param name="License">SPDX expression, file in the package or URL of the license</param>
+/// <param name="Sha256">SHA-256 of the license file, for licenses in a file</param>
+/// <param name="Location">Where to read the license: licenses.nuget.org for expressions, otherwise the file or URL</param>
+internal sealed record PackageLicense(string Package, string Version, string License, string? Sha256, string Location);
+
+/// <summary>
+/// Which licenses a command may accept: all with --accept-licenses, or the packages of --accept-license. With
+/// <paramref name="Prompt"/>, the others are asked about instead of failing the command.
+/// </summary>
+internal sealed record LicenseConsent(bool AcceptAll, IReadOnlyCollection<string> Packages, bool Prompt = false)
+{
+    public bool Covers(string package) => AcceptAll || Packages.Contains(package, StringComparer.OrdinalIgnoreCase);
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Folder of an installed package that 'winapp open' resolves
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<PackageLocation>))]
+public enum PackageLocation
+{
+    /// <summary>
+    /// The package's ApplicationData folder under %LOCALAPPDATA%\Packages
+    /// </summary>
+    AppData,
+
+    /// <summary>
+    /// Where the app's files are: the external location of a sparse package, or the install folder
+    /// </summary>
+    Install,
+
+    /// <summary>
+    /// Log folders inside the ApplicationData folder
+    /// </summary>
+    Logs
+}
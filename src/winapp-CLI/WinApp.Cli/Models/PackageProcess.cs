@@ -0,0 +1,10 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A running process, as Win32_Process reports it
+/// </summary>
+/// <param name="StartedAt">When the process started, to tell a child from an unrelated process that reused its parent's id</param>
+internal sealed record PackageProcess(int Id, int ParentId, string Name, string? Path, string? CommandLine, DateTimeOffset? StartedAt);
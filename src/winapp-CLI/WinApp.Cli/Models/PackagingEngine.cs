@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Which implementation creates and signs MSIX packages
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<PackagingEngine>))]
+public enum PackagingEngine
+{
+    /// <summary>
+    /// makeappx.exe and signtool.exe from the Windows SDK build tools
+    /// </summary>
+    Sdk,
+
+    /// <summary>
+    /// Built-in packer, block map generator and Authenticode signer; no Windows SDK install required
+    /// </summary>
+    Native
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A package project of the workspace, declared under 'projects:' in winapp.yaml
+/// </summary>
+internal sealed class ProjectConfig
+{
+    public string Name { get; set; } = "";
+
+    /// <summary>
+    /// Folder of the project, relative to winapp.yaml
+    /// </summary>
+    public string Path { get; set; } = "";
+
+    /// <summary>
+    /// Other folders or files, relative to winapp.yaml, whose changes require re-packing the project, such as
+    /// shared libraries
+    /// </summary>
+    public List<string> Inputs { get; set; } = new();
+}
@@ -0,0 +1,162 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// What winapp did for a project, saved in .winapp/state.json so later commands and tools don't have to
+/// rediscover it
+/// </summary>
+internal sealed class ProjectState
+{
+    public PackRecord? LastPack { get; set; }
+
+    /// <summary>
+    /// Packages registered on this machine from the project, one per package name
+    /// </summary>
+    public List<DeploymentRecord> Deployments { get; set; } = [];
+
+    /// <summary>
+    /// Identities winapp derived from the project's manifest, one per kind and name
+    /// </summary>
+    public List<IdentityRecord> Identities { get; set; } = [];
+
+    public void RecordDeployment(DeploymentRecord deployment)
+    {
+        Deployments.RemoveAll(d => string.Equals(d.PackageName, deployment.PackageName, StringComparison.OrdinalIgnoreCase));
+        Deployments.Add(deployment);
+    }
+
+    public void RecordIdentity(IdentityRecord identity)
+    {
+        Identities.RemoveAll(i => i.Kind == identity.Kind && string.Equals(i.Name, identity.Name, StringComparison.OrdinalIgnoreCase));
+        Identities.Add(identity);
+    }
+}
+
+/// <summary>
+/// Inputs and output of the last 'winapp pack'
+/// </summary>
+internal sealed class PackRecord
+{
+    public DateTimeOffset PackedAt { get; set; }
+
+    public string Input { get; set; } = string.Empty;
+
+    public string Manifest { get; set; } = string.Empty;
+
+    public string Output { get; set; } = string.Empty;
+
+    /// <summary>
+    /// SHA-256 of the package file, hex encoded
+    /// </summary>
+    public string Sha256 { get; set; } = string.Empty;
+
+    public string PackageName { get; set; } = string.Empty;
+
+    public string? Publisher { get; set; }
+
+    public bool Signed { get; set; }
+
+    /// <summary>
+    /// Whether the package was restored from the remote build cache instead of packed
+    /// </summary>
+    public bool FromCache { get; set; }
+
+    /// <summary>
+    /// Stamp embedded in the package, when it was packed with --provenance
+    /// </summary>
+    public BuildProvenance? Provenance { get; set; }
+}
+
+/// <summary>
+/// A package registered from the project with 'winapp create-debug-identity'
+/// </summary>
+internal sealed class DeploymentRecord
+{
+    public string PackageName { get; set; } = string.Empty;
+
+    public string Publisher { get; set; } = string.Empty;
+
+    public string Manifest { get; set; } = string.Empty;
+
+    public string ExternalLocation { get; set; } = string.Empty;
+
+    public DateTimeOffset RegisteredAt { get; set; }
+}
+
+[JsonConverter(typeof(JsonStringEnumConverter<ProjectIdentityKind>))]
+internal enum ProjectIdentityKind
+{
+    /// <summary>
+    /// The '.debug' identity of create-debug-identity
+    /// </summary>
+    Debug,
+
+    /// <summary>
+    /// The identity of 'pack --branch-identity'
+    /// </summary>
+    Branch
+}
+
+internal sealed class IdentityRecord
+{
+    public ProjectIdentityKind Kind { get; set; }
+
+    public string Name { get; set; } = string.Empty;
+
+    public string Publisher { get; set; } = string.Empty;
+
+    public string? Branch { get; set; }
+
+    /// <summary>
+    /// Certificate that signs packages of this identity
+    /// </summary>
+    public string? Certificate { get; set; }
+
+    public DateTimeOffset CreatedAt { get; set; }
+}
+
+/// <summary>
+/// Where a package came from, embedded in it as <see cref="FileName"/> by 'pack --provenance'
+/// </summary>
+internal sealed class BuildProvenance
+{
+    /// <summary>
+    /// File at the root of the package, readable at runtime from Package.Current.InstalledPath
+    /// </summary>
+    public const string FileName = "winapp-build-info.json";
+
+    /// <summary>
+    /// Commit the package was built from; null outside a git repository
+    /// </summary>
+    public string? GitSha { get; set; }
+
+    /// <summary>
+    /// Whether the working tree had uncommitted changes
+    /// </summary>
+    public bool GitDirty { get; set; }
+
+    public string? Branch { get; set; }
+
+    /// <summary>
+    /// Build profile given with --profile, such as Debug, Release or Nightly
+    /// </summary>
+    public string? Profile { get; set; }
+
+    public string CliVersion { get; set; } = string.Empty;
+
+    /// <summary>
+    /// Folder the package was built in: the root of the git repository, or the current directory. The source paths of
+    /// debug info start with it, which the winapp-runtime crate makes relative again in panic reports
+    /// </summary>
+    public string? SourceRoot { get; set; }
+
+    /// <summary>
+    /// Input folder that became the root of the package, relative to <see cref="SourceRoot"/> with / separators; null
+    /// when it is outside of it
+    /// </summary>
+    public string? PackageFolder { get; set; }
+}
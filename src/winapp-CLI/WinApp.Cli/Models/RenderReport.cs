@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An issue 'winapp analyze gpu' reports, with the severities of 'winapp analyze a11y'
+/// </summary>
+/// <param name="Check">Which check found the issue: software-adapter, virtual-machine, remote-session, old-driver or disabled</param>
+/// <param name="Subject">The display adapter, or the setting, the issue is about</param>
+internal sealed record RenderIssue(AccessibilitySeverity Severity, string Check, string Subject, string Message);
+
+/// <summary>
+/// A display adapter and its driver, as Windows records them for the adapter's device
+/// </summary>
+/// <param name="DriverDate">null when the driver doesn't record it</param>
+internal sealed record GpuAdapter(string Name, string Provider, string DriverVersion, DateTime? DriverDate);
+
+/// <param name="OsVersion">Version and build of Windows</param>
+/// <param name="Renderers">What the app renders with, found in the package folder: WebView2, WinUI or WPF</param>
+internal sealed record RenderReport(string OsVersion, IReadOnlyList<GpuAdapter> Adapters, IReadOnlyList<string> Renderers, IReadOnlyList<RenderIssue> Issues);
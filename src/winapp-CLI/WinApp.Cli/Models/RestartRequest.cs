@@ -0,0 +1,42 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Why Windows restarts an app, RestartReason of the winapp-runtime crate
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<RestartReason>))]
+public enum RestartReason
+{
+    /// <summary>
+    /// An installer closed the app to update it (request_restart_for_update)
+    /// </summary>
+    Update,
+
+    /// <summary>
+    /// The app crashed or stopped responding (request_restart_after_crash)
+    /// </summary>
+    Crash
+}
+
+/// <summary>
+/// A restart a running app asked Windows for, as the winapp-runtime crate keeps it in LocalState\RestartState until
+/// the restarted app restores its payload
+/// </summary>
+/// <param name="Token">Names the state file, and is passed to the restarted app as --winapp-restore-state=&lt;token&gt;</param>
+/// <param name="Arguments">Arguments the app was started with, which the restart keeps</param>
+/// <param name="AppUserModelId">Application the app ran as, or null when it had no package identity</param>
+internal sealed record RestartRequest(
+    string Token,
+    RestartReason Reason,
+    DateTimeOffset SavedAt,
+    int ProcessId,
+    string Executable,
+    IReadOnlyList<string> Arguments,
+    string? AppUserModelId);
+
+/// <param name="StoppedProcesses">Processes of the package that were stopped, as an update or crash would</param>
+internal sealed record RestartSimulation(RestartRequest Request, int StoppedProcesses);
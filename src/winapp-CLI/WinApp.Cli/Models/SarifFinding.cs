@@ -0,0 +1,13 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A finding of the analyze commands or 'validate' to write as a SARIF result
+/// </summary>
+/// <param name="Rule">Rule id, e.g. a11y/contrast</param>
+/// <param name="Subject">What the check reported the finding about: attribute, application id or asset; null for schema versions</param>
+/// <param name="Element">Manifest element path, as <see cref="ManifestNote.Element"/>, when the finding was placed on one already</param>
+/// <param name="Baselined">Whether the validation baseline records the finding, so code scanning shows it as suppressed</param>
+internal sealed record SarifFinding(AccessibilitySeverity Severity, string Rule, string? Subject, string? Element, string Message, bool Baselined = false);
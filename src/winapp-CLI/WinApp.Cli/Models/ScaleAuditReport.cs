@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An issue 'winapp analyze scales' reports, with the severities of 'winapp analyze a11y'
+/// </summary>
+/// <param name="Check">Which check found the issue: size, dpi, upscaled, targetsize or asset</param>
+/// <param name="Asset">Asset path from the manifest, or the variant file the issue is about</param>
+internal sealed record ScaleIssue(AccessibilitySeverity Severity, string Check, string Asset, string Message);
+
+/// <param name="Assets">Asset paths from the manifest that were checked</param>
+/// <param name="Variants">Number of scale and target size variant files that were loaded</param>
+internal sealed record ScaleReport(IReadOnlyList<string> Assets, int Variants, IReadOnlyList<ScaleIssue> Issues);
@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Where a setting's effective value came from; later layers override earlier ones
+/// </summary>
+internal enum SettingOrigin
+{
+    BuiltIn,
+    Machine,
+    User,
+    Repository,
+    Environment,
+    CommandLine,
+}
+
+/// <param name="EnvironmentVariable">Variable that overrides the configuration files, if any</param>
+/// <param name="IsPath">Relative values are resolved against the folder of the file that sets them</param>
+internal sealed record SettingDefinition(string Key, string Description, string? DefaultValue = null, string? EnvironmentVariable = null, bool IsPath = false);
+
+/// <param name="Value">Effective value; null when no layer sets it</param>
+/// <param name="Source">File, environment variable or option the value came from; null for built-in defaults</param>
+internal sealed record SettingValue(string Key, string? Value, SettingOrigin Origin, string? Source);
@@ -0,0 +1,27 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A shortcut to one of the package's apps, declared under 'shortcuts:' in winapp.yaml
+/// </summary>
+internal sealed class ShortcutConfig
+{
+    /// <summary>
+    /// Where Windows creates the .lnk file, starting with a known folder such as [{Common Programs}] or [{Desktop}]
+    /// </summary>
+    public string File { get; set; } = "";
+
+    /// <summary>
+    /// Id of the Application the shortcut starts (default: the first one)
+    /// </summary>
+    public string? Application { get; set; }
+
+    public string? Arguments { get; set; }
+
+    /// <summary>
+    /// Icon file, relative to the package root
+    /// </summary>
+    public string? Icon { get; set; }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// How 'winapp sign' and 'winapp pack' sign packages, under 'sign:' in winapp.yaml. Secrets aren't kept here:
+/// PFX passwords come from --password and Azure credentials from the AZURE_* environment variables.
+/// </summary>
+internal sealed class SigningConfig
+{
+    public SigningProvider Provider { get; set; } = SigningProvider.Pfx;
+
+    /// <summary>
+    /// PFX file, relative to winapp.yaml (default: devcert.pfx)
+    /// </summary>
+    public string? Certificate { get; set; }
+
+    /// <summary>
+    /// Trusted Signing endpoint of the account's region, e.g. https://eus.codesigning.azure.net
+    /// </summary>
+    public string? Endpoint { get; set; }
+
+    /// <summary>
+    /// Trusted Signing account name
+    /// </summary>
+    public string? Account { get; set; }
+
+    /// <summary>
+    /// Trusted Signing certificate profile name
+    /// </summary>
+    public string? Profile { get; set; }
+
+    /// <summary>
+    /// Key Vault URI, e.g. https://contoso.vault.azure.net
+    /// </summary>
+    public string? Vault { get; set; }
+
+    /// <summary>
+    /// Name of the Key Vault certificate
+    /// </summary>
+    public string? CertificateName { get; set; }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Where the signing key of 'winapp sign' lives
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<SigningProvider>))]
+public enum SigningProvider
+{
+    /// <summary>
+    /// A PFX file, such as the development certificate 'winapp cert generate' creates
+    /// </summary>
+    Pfx,
+
+    /// <summary>
+    /// A certificate profile of Azure Trusted Signing
+    /// </summary>
+    TrustedSigning,
+
+    /// <summary>
+    /// A certificate in Azure Key Vault
+    /// </summary>
+    KeyVault
+}
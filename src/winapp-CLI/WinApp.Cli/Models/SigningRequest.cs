@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Command line options of a signing; what they leave out comes from 'sign:' in winapp.yaml
+/// </summary>
+internal sealed record SigningRequest
+{
+    public SigningProvider? Provider { get; init; }
+
+    /// <summary>
+    /// PFX file to sign with; implies the PFX provider
+    /// </summary>
+    public FileInfo? Certificate { get; init; }
+
+    public string Password { get; init; } = "password";
+
+    public string? TimestampUrl { get; init; }
+
+    /// <summary>
+    /// Engine for PFX signing; signing services always sign with the native engine
+    /// </summary>
+    public PackagingEngine Engine { get; init; } = PackagingEngine.Sdk;
+
+    /// <summary>
+    /// Install a development certificate that signing generates into the trusted store
+    /// </summary>
+    public bool Trust { get; init; }
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The splash screen of every app in the package, declared under 'splash:' in winapp.yaml
+/// </summary>
+internal sealed class SplashConfig
+{
+    /// <summary>
+    /// Splash screen image, relative to the package root (620x300 at scale 100)
+    /// </summary>
+    public string? Image { get; set; }
+
+    /// <summary>
+    /// Color behind the image as #RRGGBB or a color name
+    /// </summary>
+    public string? BackgroundColor { get; set; }
+
+    /// <summary>
+    /// Whether the app shows an extended splash screen of its own while it starts, which then has to match
+    /// the system one
+    /// </summary>
+    public bool Extended { get; set; }
+}
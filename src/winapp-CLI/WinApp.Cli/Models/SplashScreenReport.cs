@@ -0,0 +1,14 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An issue 'winapp analyze splash' reports, with the severities of 'winapp analyze a11y'
+/// </summary>
+/// <param name="Check">Which check found the issue: missing, image, scale, background, edge or extended</param>
+/// <param name="Application">Id of the application the issue is about</param>
+internal sealed record SplashScreenIssue(AccessibilitySeverity Severity, string Check, string Application, string Message);
+
+/// <param name="Applications">Ids of the applications that were checked</param>
+internal sealed record SplashScreenReport(IReadOnlyList<string> Applications, IReadOnlyList<SplashScreenIssue> Issues);
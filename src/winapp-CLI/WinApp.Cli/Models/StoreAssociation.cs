@@ -0,0 +1,13 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Result of 'winapp store associate'
+/// </summary>
+/// <param name="Application">The Store app the manifest is now associated with</param>
+/// <param name="Changes">The manifest values that changed; empty if it was associated already</param>
+internal sealed record StoreAssociation(StoreApplication Application, IReadOnlyList<StoreIdentityChange> Changes);
@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The Store app the project is associated with, under 'store:' in winapp.yaml, as written by
+/// 'winapp store associate'
+/// </summary>
+internal sealed class StoreConfig
+{
+    /// <summary>
+    /// Store ID of the app in Partner Center, e.g. 9NBLGGH4R315
+    /// </summary>
+    public string AppId { get; set; } = "";
+
+    /// <summary>
+    /// Package family name the Store assigned, which the manifest's identity has to produce
+    /// </summary>
+    public string? PackageFamilyName { get; set; }
+}
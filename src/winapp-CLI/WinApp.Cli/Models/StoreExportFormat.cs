@@ -0,0 +1,16 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// File format of exported Store analytics and reviews
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<StoreExportFormat>))]
+public enum StoreExportFormat
+{
+    Json,
+    Csv
+}
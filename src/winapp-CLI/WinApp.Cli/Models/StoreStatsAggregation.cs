@@ -0,0 +1,17 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Time period each row of an analytics report covers
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<StoreStatsAggregation>))]
+public enum StoreStatsAggregation
+{
+    Day,
+    Week,
+    Month
+}
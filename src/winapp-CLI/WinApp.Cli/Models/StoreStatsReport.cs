@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Partner Center analytics report exported by 'store stats'
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<StoreStatsReport>))]
+public enum StoreStatsReport
+{
+    /// <summary>
+    /// App acquisitions (installs and purchases)
+    /// </summary>
+    Acquisitions,
+
+    /// <summary>
+    /// Crash and other failure hits
+    /// </summary>
+    Health,
+
+    /// <summary>
+    /// Star ratings
+    /// </summary>
+    Ratings
+}
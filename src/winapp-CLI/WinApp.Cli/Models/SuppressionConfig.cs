@@ -0,0 +1,30 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Issues of a rule to leave out of the analyze reports, declared under 'suppressions:' in winapp.yaml
+/// </summary>
+internal sealed class SuppressionConfig
+{
+    /// <summary>
+    /// Rule id such as a11y/contrast, a source such as a11y/* or * for every rule
+    /// </summary>
+    public string Rule { get; set; } = "";
+
+    /// <summary>
+    /// Manifest the suppression applies to, relative to winapp.yaml (default: every manifest)
+    /// </summary>
+    public string? File { get; set; }
+
+    /// <summary>
+    /// Line the start tag of the element the issues are about begins on (default: the whole file)
+    /// </summary>
+    public int? Line { get; set; }
+
+    /// <summary>
+    /// Why the issues are accepted, for reviewers
+    /// </summary>
+    public string? Reason { get; set; }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Values of the {{placeholders}} of a template. Names are case-insensitive; env.NAME reads the environment variable
+/// NAME. Null values are left undefined, so placeholders that use them fail instead of rendering empty.
+/// </summary>
+internal sealed class TemplateVariables
+{
+    private const string EnvironmentPrefix = "env.";
+
+    private readonly Dictionary<string, string> _values = new(StringComparer.OrdinalIgnoreCase);
+
+    public IEnumerable<string> Names => _values.Keys.Order(StringComparer.OrdinalIgnoreCase);
+
+    public string? Get(string name)
+    {
+        if (_values.TryGetValue(name, out var value))
+        {
+            return value;
+        }
+
+        return name.StartsWith(EnvironmentPrefix, StringComparison.OrdinalIgnoreCase)
+            ? Environment.GetEnvironmentVariable(name[EnvironmentPrefix.Length..])
+            : null;
+    }
+
+    public TemplateVariables Set(string name, string? value)
+    {
+        if (value != null)
+        {
+            _values[name] = value;
+        }
+        return this;
+    }
+
+    /// <summary>
+    /// version, and its parts version.major, version.minor, version.build and version.revision
+    /// </summary>
+    public TemplateVariables SetVersion(string version)
+    {
+        Set("version", version);
+        var parts = version.Split('.');
+        string[] names = ["major", "minor", "build", "revision"];
+        for (var i = 0; i < names.Length; i++)
+        {
+            Set($"version.{names[i]}", i < parts.Length ? parts[i] : "0");
+        }
+        return this;
+    }
+
+    /// <summary>
+    /// git.sha, git.branch and git.dirty of the provenance stamp, and the build profile; outside a git repository
+    /// only profile is defined
+    /// </summary>
+    public TemplateVariables SetProvenance(BuildProvenance provenance)
+    {
+        Set("git.sha", provenance.GitSha);
+        Set("git.branch", provenance.Branch);
+        Set("git.dirty", provenance.GitSha != null ? (provenance.GitDirty ? "true" : "false") : null);
+        Set("profile", provenance.Profile);
+        return this;
+    }
+
+    /// <summary>
+    /// variant, and the branding.*, channel.* and architecture values of a matrix variant
+    /// </summary>
+    public TemplateVariables SetVariant(MatrixVariant variant)
+    {
+        Set("variant", variant.Name);
+        if (variant.Branding is { } branding)
+        {
+            Set("branding.name", branding.Name);
+            Set("branding.identityName", branding.IdentityName);
+            Set("branding.publisher", branding.Publisher);
+            Set("branding.publisherDisplayName", branding.PublisherDisplayName);
+            Set("branding.displayName", branding.DisplayName);
+        }
+        if (variant.Channel is { } channel)
+        {
+            Set("channel.name", channel.Name);
+            Set("channel.nameSuffix", channel.NameSuffix ?? string.Empty);
+            Set("channel.displayNameSuffix", channel.DisplayNameSuffix ?? string.Empty);
+        }
+        Set("architecture", variant.Architecture?.Name);
+        return this;
+    }
+}
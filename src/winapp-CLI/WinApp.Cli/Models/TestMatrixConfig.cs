@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Windows versions that 'test matrix' installs the package on, declared under 'testMatrix:' in winapp.yaml
+/// </summary>
+internal sealed class TestMatrixConfig
+{
+    public const int DefaultTimeout = 45;
+
+    /// <summary>
+    /// PowerShell script, relative to winapp.yaml, that runs in each machine after the package is installed. Exit
+    /// code 0 passes. Without it, a target passes when the package installs
+    /// </summary>
+    public string? SmokeTest { get; set; }
+
+    /// <summary>
+    /// Minutes a target may take, from provisioning the machine to the end of the smoke test
+    /// </summary>
+    public int Timeout { get; set; } = DefaultTimeout;
+
+    public List<TestTargetConfig> Targets { get; set; } = new();
+}
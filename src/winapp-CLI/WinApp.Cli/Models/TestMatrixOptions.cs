@@ -0,0 +1,37 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+internal sealed class TestMatrixOptions
+{
+    /// <summary>
+    /// .msix, .appx, .msixbundle or .appxbundle to install
+    /// </summary>
+    public required FileInfo Package { get; init; }
+
+    /// <summary>
+    /// Framework packages the package depends on, such as the Windows App Runtime, installed with it
+    /// </summary>
+    public IReadOnlyList<FileInfo> Dependencies { get; init; } = [];
+
+    /// <summary>
+    /// Overrides the smoke test of winapp.yaml
+    /// </summary>
+    public FileInfo? SmokeTest { get; init; }
+
+    /// <summary>
+    /// Names of the targets to test; empty tests every target
+    /// </summary>
+    public IReadOnlyList<string> Targets { get; init; } = [];
+
+    /// <summary>
+    /// Number of targets tested at the same time
+    /// </summary>
+    public int Parallel { get; init; } = 1;
+
+    /// <summary>
+    /// Leave the machines running after the test, to investigate failures
+    /// </summary>
+    public bool KeepMachines { get; init; }
+}
@@ -0,0 +1,54 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A Windows version of the test matrix and the virtual machine it is tested on
+/// </summary>
+internal sealed class TestTargetConfig
+{
+    public const string HyperV = "hyperv";
+    public const string Azure = "azure";
+
+    /// <summary>
+    /// Name of the target, e.g. win10-21h2, for --target and the results
+    /// </summary>
+    public string Name { get; set; } = "";
+
+    /// <summary>
+    /// hyperv, for a local Hyper-V virtual machine, or azure, for an Azure DevTest Labs virtual machine
+    /// </summary>
+    public string Provider { get; set; } = "";
+
+    /// <summary>
+    /// Hyper-V: the .vhdx of a Hyper-V Quick Create or generalized virtual machine, relative to winapp.yaml. Each run
+    /// boots a differencing disk of it, so the image is never changed. Azure: the name of a gallery image of the lab
+    /// </summary>
+    public string Image { get; set; } = "";
+
+    /// <summary>
+    /// Hyper-V virtual switch (default: Default Switch)
+    /// </summary>
+    public string? Switch { get; set; }
+
+    /// <summary>
+    /// Hyper-V startup memory in GB (default: 4)
+    /// </summary>
+    public int? Memory { get; set; }
+
+    /// <summary>
+    /// Azure DevTest Labs lab
+    /// </summary>
+    public string? Lab { get; set; }
+
+    /// <summary>
+    /// Resource group of the lab
+    /// </summary>
+    public string? ResourceGroup { get; set; }
+
+    /// <summary>
+    /// Azure virtual machine size (default: Standard_D2s_v5)
+    /// </summary>
+    public string? Size { get; set; }
+}
@@ -0,0 +1,25 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Steps of testing a target, in order
+/// </summary>
+internal enum TestMatrixStage
+{
+    Provision,
+    Deploy,
+    SmokeTest,
+}
+
+/// <summary>
+/// Outcome of testing the package on one target of the test matrix
+/// </summary>
+/// <param name="FailedStage">Step that failed, or null when the target passed</param>
+/// <param name="Output">Output of the install and the smoke test in the machine</param>
+/// <param name="Machine">Name of the virtual machine, when it was kept for investigation</param>
+internal sealed record TestTargetResult(string Target, string Provider, TestMatrixStage? FailedStage, string? Error, TimeSpan Duration, string Output, string? Machine = null)
+{
+    public bool Passed => FailedStage == null;
+}
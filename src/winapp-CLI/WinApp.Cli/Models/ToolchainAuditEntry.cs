@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// How a single CLI operation is implemented
+/// </summary>
+/// <param name="Operation">Human readable operation name</param>
+/// <param name="Commands">CLI commands that perform the operation</param>
+/// <param name="ExternalTool">Tool the operation shells out to, or null when implemented natively</param>
+/// <param name="RequiresWindowsSdk">Whether the external tool comes from the Windows SDK build tools</param>
+/// <param name="ToolAvailable">Whether the Windows SDK tool is present in the current installation; null when not applicable</param>
+/// <param name="Remarks">Optional hint, e.g. how to avoid the dependency</param>
+internal record ToolchainAuditEntry(string Operation, string Commands, string? ExternalTool, bool RequiresWindowsSdk, bool? ToolAvailable, string? Remarks = null)
+{
+    public bool IsNative => ExternalTool == null;
+}
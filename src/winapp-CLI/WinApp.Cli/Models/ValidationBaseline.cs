@@ -0,0 +1,56 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Findings 'winapp validate --write-baseline' recorded, saved in winapp-baseline.json so 'winapp validate' only
+/// fails on findings that aren't in it
+/// </summary>
+internal sealed class ValidationBaseline
+{
+    public int Version { get; set; } = 1;
+
+    public List<BaselineEntry> Findings { get; set; } = [];
+}
+
+/// <summary>
+/// Findings of one rule on one element. They are matched by file, rule and element, so moving lines or a changed
+/// message doesn't make them new, while another finding of the rule on the element does
+/// </summary>
+internal sealed class BaselineEntry
+{
+    /// <summary>
+    /// Manifest path, relative to the baseline's directory and with forward slashes
+    /// </summary>
+    public string File { get; set; } = "";
+
+    public string Rule { get; set; } = "";
+
+    /// <summary>
+    /// Element path, as <see cref="ManifestNote.Element"/>
+    /// </summary>
+    public string Element { get; set; } = "";
+
+    public int Count { get; set; }
+
+    /// <summary>
+    /// Messages of the findings when they were recorded, for reviewers of the baseline
+    /// </summary>
+    public List<string> Messages { get; set; } = [];
+}
+
+/// <summary>
+/// A finding of 'winapp validate', after rule overrides and suppressions
+/// </summary>
+/// <param name="File">Manifest path, relative to the baseline's directory and with forward slashes</param>
+/// <param name="Subject">As <see cref="ManifestNote.Subject"/></param>
+internal sealed record ValidationFinding(AccessibilitySeverity Severity, string Rule, string File, string Element, string? Subject, string Message);
+
+/// <summary>
+/// Findings split by whether the baseline covers them
+/// </summary>
+/// <param name="New">Findings the baseline doesn't cover</param>
+/// <param name="Baselined">Findings the baseline covers</param>
+/// <param name="Fixed">Recorded findings no longer found, so the baseline can be tightened</param>
+internal sealed record ValidationResult(IReadOnlyList<ValidationFinding> New, IReadOnlyList<ValidationFinding> Baselined, int Fixed);
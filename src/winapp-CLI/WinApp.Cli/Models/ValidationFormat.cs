@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Output format of 'winapp validate'
+/// </summary>
+[JsonConverter(typeof(JsonStringEnumConverter<ValidationFormat>))]
+public enum ValidationFormat
+{
+    /// <summary>
+    /// One line per new finding, and a summary
+    /// </summary>
+    Text,
+
+    /// <summary>
+    /// Every finding with its rule, element and whether the baseline records it, for CI gates
+    /// </summary>
+    Json
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// A check of the analyze commands or 'explain manifest', which winapp.yaml can raise, lower, turn off or suppress
+/// </summary>
+/// <param name="Id">The source and the check, e.g. a11y/contrast</param>
+/// <param name="Severity">Severity the check reports at, before overrides</param>
+/// <param name="Description">What the check looks for, and why it matters</param>
+internal sealed record ValidationRule(string Id, string Severity, string Description);
+
+/// <summary>
+/// Every rule the analyze commands and 'explain manifest' report
+/// </summary>
+internal static class ValidationRules
+{
+    public static readonly IReadOnlyList<ValidationRule> All =
+    [
+        new("a11y/asset", "error", "An image the manifest names exists in no scale or variant, so Windows shows a blank tile or icon"),
+        new("a11y/contrast", "warning", "A tile, splash screen or tile name is below the contrast ratio an accessibility review asks for: 3:1 for graphics and 4.5:1 for text"),
+        new("a11y/unplated", "warning for no unplated variant, info for a missing size", "Square44x44Logo has no altform-unplated target sizes, so the taskbar shows the icon on a plate of the accent color"),
+        new("a11y/high-contrast", "warning for Square150x150Logo and Square44x44Logo, info for others", "An image has no contrast-black and contrast-white variants for high contrast themes"),
+        new("a11y/short-name", "warning, or info for names from resources", "The tile name is longer than medium tiles and Start show without truncating it"),
+        new("splash/missing", "warning, or info for desktop apps", "An app declares no uap:SplashScreen"),
+        new("splash/image", "error", "The splash screen has no image, or one that exists in no scale or that Windows can't read"),
+        new("splash/size", "warning, or info for desktop apps", "The image isn't 620:300, so Windows scales it to fit and letterboxes it"),
+        new("splash/scale", "warning, or info for desktop apps", "No image of 1240x600 or larger, so the splash screen is blurry on high DPI displays"),
+        new("splash/background", "warning, or error for an invalid color or an extended splash", "No background color, or transparent, which fills the splash screen with the accent color"),
+        new("splash/edge", "warning, or info for desktop apps", "The opaque edges of the image don't match the background color, so the image shows as a box"),
+        new("splash/extended", "info", "A desktop app without 'extended: true' in winapp.yaml, for which Windows never shows the manifest splash screen"),
+        new("scales/asset", "error", "An image the manifest names exists in no scale or variant, or a variant Windows can't read"),
+        new("scales/size", "warning when smaller, info when larger", "A variant's pixel size doesn't match its scale or target size qualifier"),
+        new("scales/dpi", "warning up to 200%, info above", "At some display scales the variant Windows picks has fewer pixels than the display needs, so it is upscaled and blurry"),
+        new("scales/upscaled", "warning", "A variant has no more detail than at half its size, so it was exported upscaled from smaller artwork"),
+        new("scales/targetsize", "warning, or info for missing standard sizes", "Square44x44Logo has no target sizes, none large enough for the taskbar at high display scales, or not the standard sizes the display scales need"),
+        new("webview/packaged-profile", "error", "A WebView2 profile (EBWebView or <app>.exe.WebView2) is in the package folder, so it would be packed, and the installed app can't write to it"),
+        new("webview/environment", "warning", "WEBVIEW2_USER_DATA_FOLDER is set, so every WebView2 app, packaged or not, shares one profile folder"),
+        new("webview/virtualization", "warning", "A Tauri app turns off file system write virtualization, so the packaged app and 'tauri dev' share one WebView2 profile"),
+        new("webview/identity", "info", "Several installed identities of the project, such as the .debug identity and the release, each have their own WebView2 profile"),
+        new("gpu/software-adapter", "warning", "Only the Microsoft Basic Display Adapter or Basic Render Driver is installed, so Direct3D renders on the CPU and WebView2 turns off GPU acceleration"),
+        new("gpu/remote-session", "info", "The session is a Remote Desktop session, where apps render for the remote display adapter, usually in software"),
+        new("gpu/virtual-machine", "info", "The display adapter is a virtual machine's, which passes through little or no Direct3D acceleration"),
+        new("gpu/old-driver", "warning", "The display driver is more than three years old, so WebView2's GPU blocklist may turn off GPU features for it"),
+        new("gpu/disabled", "warning", "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS, the Tauri configuration or WPF's DisableHWAcceleration turns off GPU rendering"),
+        new("arm64/driver", "error", "A kernel-mode driver is x64 or x86; Windows on ARM emulates only user-mode code, so it doesn't load on ARM64 devices"),
+        new("arm64/shell-extension", "warning", "A File Explorer extension isn't ARM64 or ARM64X, so File Explorer on ARM64 devices can't load it"),
+        new("arm64/kernel-adjacent", "warning", "An x64 or x86 binary talks to or installs drivers, which works on ARM64 devices only where the driver ships for ARM64"),
+        new("arm64/mixed", "warning", "An ARM64 package has x64 or x86 DLLs without an executable that can load them"),
+        new("arm64/emulated", "info", "An executable or DLL is x64 or x86 and runs under emulation on ARM64 devices"),
+        new("manifest/version", "warning, or error without a MinVersion", "An element or attribute comes from a schema newer than TargetDeviceFamily MinVersion and isn't ignorable, so older releases refuse the package"),
+        new("manifest/schema", "error", "An element or attribute the foundation and uap schemas require is missing, or a value such as the identity name, version or application id has a format MakeAppx rejects"),
+        new("manifest/logo", "error, or warning when there is no resources.pri to check", "A Logo or Square*Logo value isn't a path in the package, or names an ms-resource: that resources.pri and the .resw files don't define"),
+        new("manifest/entrypoint", "error", "A desktop app's EntryPoint, uap10:TrustLevel and uap10:RuntimeBehavior disagree, or releases before 2004 that ignore uap10 would start it in the app container"),
+        new("manifest/capability", "error", "A desktop app doesn't declare the runFullTrust capability, so Windows refuses to start it"),
+        new("manifest/device-family", "warning", "A desktop app doesn't target Windows.Desktop, or a MinVersion is higher than its MaxVersionTested"),
+        new("input/low-level-hook", "warning", "An app that runs in the app container imports SetWindowsHookEx, and low-level keyboard and mouse hooks install there without an error but are never called"),
+        new("input/background-raw-input", "info", "An app that runs in the app container imports RegisterRawInputDevices, and only gets raw input there while it has the foreground"),
+        new("dpi/manifest", "warning", "The application manifest of a desktop app's executable has a dpiAware or dpiAwareness that Windows ignores, from the wrong namespace or with an unknown value"),
+        new("dpi/framework", "warning", "A desktop app that uses WinUI or WebView2 isn't per monitor v2 DPI aware, so it renders blurry and places popups and the WebView2 window at the wrong coordinates"),
+        new("dpi/awareness", "warning for unaware apps, or info for .NET apps and system or per monitor (v1) awareness", "A desktop app isn't per monitor v2 DPI aware, so its windows are blurry at high display scales or on monitors of another scale"),
+        new("locale/code-page", "warning", "A desktop app's executable opens files with ANSI functions such as CreateFileA or fopen, the package has file names outside ASCII, and its application manifest doesn't set activeCodePage UTF-8"),
+    ];
+
+    /// <summary>
+    /// Finds a rule by id, or by check name when <paramref name="source"/> is given, e.g. contrast for a11y
+    /// </summary>
+    public static ValidationRule? Find(string id, string? source = null) =>
+        All.FirstOrDefault(r => r.Id.Equals(id, StringComparison.OrdinalIgnoreCase))
+        ?? (source != null ? All.FirstOrDefault(r => r.Id.Equals($"{source}/{id}", StringComparison.OrdinalIgnoreCase)) : null);
+
+    /// <summary>
+    /// Lines explaining a rule and how to change or suppress it, for '--explain'
+    /// </summary>
+    /// <exception cref="InvalidOperationException">No rule has the id</exception>
+    public static IReadOnlyList<string> Explain(string id, string? source = null)
+    {
+        var rule = Find(id, source)
+            ?? throw new InvalidOperationException($"No rule '{id}'; rules are {string.Join(", ", All.Where(r => source == null || r.Id.StartsWith(source + "/", StringComparison.Ordinal)).Select(r => r.Id))}");
+        return
+        [
+            $"{rule.Id}: {rule.Description}",
+            $"Severity: {rule.Severity}",
+            $"Change it for the project under 'rules:' in winapp.yaml, e.g. '{rule.Id}: off', or suppress it on one element with <!-- winapp-ignore {rule.Id} --> before the element",
+        ];
+    }
+}
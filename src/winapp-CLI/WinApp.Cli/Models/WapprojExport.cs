@@ -0,0 +1,13 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// Result of 'winapp export wapproj'
+/// </summary>
+/// <param name="Project">The .wapproj written, with Package.appxmanifest and Images next to it</param>
+/// <param name="Images">Number of image files copied to Images</param>
+/// <param name="Exported">What was carried over from winapp.yaml and the manifest</param>
+/// <param name="Manual">What Visual Studio has no equivalent for, or what is left to do there</param>
+internal sealed record WapprojExport(FileInfo Project, int Images, IReadOnlyList<string> Exported, IReadOnlyList<string> Manual);
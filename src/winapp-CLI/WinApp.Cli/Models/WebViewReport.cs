@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// An issue 'winapp analyze webview' reports, with the severities of 'winapp analyze a11y'
+/// </summary>
+/// <param name="Check">Which check found the issue: packaged-profile, environment, virtualization or identity</param>
+/// <param name="Application">Identity name of the package the issue is about</param>
+internal sealed record WebViewIssue(AccessibilitySeverity Severity, string Check, string Application, string Message);
+
+/// <summary>
+/// A WebView2 user data folder of an installed package
+/// </summary>
+/// <param name="Package">Identity name of the installed package, such as the .debug identity of the project</param>
+internal sealed record WebViewProfile(string Package, string Path);
+
+/// <param name="UsesWebView2">The project is a Tauri app, ships the WebView2 loader or has a WebView2 profile</param>
+/// <param name="ExpectedProfile">Where the packaged app keeps its WebView2 profile; &lt;ApplicationData&gt; is the package's folder under %LOCALAPPDATA%\Packages</param>
+/// <param name="Profiles">Profiles of the project's installed packages</param>
+internal sealed record WebViewReport(bool UsesWebView2, string ExpectedProfile, IReadOnlyList<WebViewProfile> Profiles, IReadOnlyList<WebViewIssue> Issues);
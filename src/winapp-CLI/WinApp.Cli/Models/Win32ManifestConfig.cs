@@ -0,0 +1,32 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Models;
+
+/// <summary>
+/// The classic Win32 application manifest of the app's executable, generated next to AppxManifest.xml and embedded
+/// at pack time when the build didn't embed one, under 'win32Manifest:' in winapp.yaml
+/// </summary>
+internal sealed class Win32ManifestConfig
+{
+    /// <summary>
+    /// dpiAwareness of the process: PerMonitorV2, PerMonitor, System or Unaware
+    /// </summary>
+    public string DpiAwareness { get; set; } = "PerMonitorV2";
+
+    /// <summary>
+    /// Whether the app takes paths longer than MAX_PATH, where long paths are enabled
+    /// </summary>
+    public bool LongPathAware { get; set; } = true;
+
+    /// <summary>
+    /// Code page of the -A functions and the C runtime, e.g. UTF-8, or null for the system's
+    /// </summary>
+    public string? ActiveCodePage { get; set; } = "UTF-8";
+
+    /// <summary>
+    /// The releases the app declares it supports, by name: windows10 (which includes Windows 11), windows8.1,
+    /// windows8, windows7 and vista
+    /// </summary>
+    public List<string> SupportedOS { get; set; } = ["windows10"];
+}
@@ -7,6 +7,88 @@ internal sealed class WinappConfig
 {
     public List<PackagePin> Packages { get; set; } = new();
 
+    /// <summary>
+    /// Accepted licenses of the packages that require license acceptance, for restore without prompts
+    /// </summary>
+    public List<LicenseAcceptanceConfig> Licenses { get; set; } = new();
+
+    public List<ProjectConfig> Projects { get; set; } = new();
+
+    public List<EnvironmentVariableConfig> Environment { get; set; } = new();
+
+    public List<ShortcutConfig> Shortcuts { get; set; } = new();
+
+    public List<BadgeConfig> Badges { get; set; } = new();
+
+    public SplashConfig? Splash { get; set; }
+
+    /// <summary>
+    /// Severity overrides of the analyze rules by rule id: off, info, warning or error
+    /// </summary>
+    public Dictionary<string, string> Rules { get; set; } = new(StringComparer.OrdinalIgnoreCase);
+
+    public List<SuppressionConfig> Suppressions { get; set; } = new();
+
+    /// <summary>
+    /// Defaults such as the timestamp server by setting key, which override the machine and user configuration
+    /// </summary>
+    public Dictionary<string, string> Settings { get; set; } = new(StringComparer.OrdinalIgnoreCase);
+
+    public List<AnalyzerConfig> Analyzers { get; set; } = new();
+
+    public StoreConfig? Store { get; set; }
+
+    public MatrixConfig? Matrix { get; set; }
+
+    public TestMatrixConfig? TestMatrix { get; set; }
+
+    public CrashesConfig? Crashes { get; set; }
+
+    public SigningConfig? Sign { get; set; }
+
+    public Win32ManifestConfig? Win32Manifest { get; set; }
+
+    public LocaleConfig? Locale { get; set; }
+
+    public BuildConfig? Build { get; set; }
+
+    /// <summary>
+    /// Copy of everything but the package pins, for commands that pin the packages again
+    /// </summary>
+    public WinappConfig WithoutPackages() => new()
+    {
+        Licenses = Licenses,
+        Projects = Projects,
+        Environment = Environment,
+        Shortcuts = Shortcuts,
+        Badges = Badges,
+        Splash = Splash,
+        Rules = Rules,
+        Suppressions = Suppressions,
+        Settings = Settings,
+        Analyzers = Analyzers,
+        Store = Store,
+        Matrix = Matrix,
+        TestMatrix = TestMatrix,
+        Crashes = Crashes,
+        Sign = Sign,
+        Win32Manifest = Win32Manifest,
+        Locale = Locale,
+        Build = Build,
+    };
+
+    /// <summary>
+    /// The environment variables of the package: 'environment:', and those of 'locale:' that it doesn't set itself
+    /// </summary>
+    public List<EnvironmentVariableConfig> GetPackageEnvironment() =>
+    [
+        .. Environment,
+        .. (Locale?.GetEnvironmentVariables() ?? []).Where(l => !Environment.Any(e => e.Name.Equals(l.Name, StringComparison.OrdinalIgnoreCase))),
+    ];
+
+    public BadgeConfig? GetBadge(string profile)
+        => Badges.FirstOrDefault(b => b.Profile.Equals(profile, StringComparison.OrdinalIgnoreCase));
+
     public string? GetVersion(string name)
         => Packages.FirstOrDefault(p => p.Name.Equals(name, StringComparison.OrdinalIgnoreCase))?.Version;
 
@@ -0,0 +1,179 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers.Binary;
+using System.Collections.Concurrent;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Content-addressed store of compressed payload blocks, keyed by the hash of the uncompressed block.
+/// </summary>
+/// <remarks>
+/// The native packer deflates every 64 KB block into a standalone fragment, so a fragment compressed for
+/// one package is valid in any other package containing the same block. Identical assets across
+/// architecture variants, channels or duplicated folders are therefore compressed once. Fragments are
+/// kept in memory for the lifetime of the process and, when a directory is given, on disk for later runs.
+/// SHA-256, SHA-384 and SHA-512 hashes differ in length, so packs using different block map algorithms
+/// share the store without their keys colliding.
+/// </remarks>
+internal sealed class AppxBlockCache
+{
+    /// <summary>
+    /// Default limit of the on-disk cache, enforced by <see cref="Trim"/>
+    /// </summary>
+    public const long DefaultMaxDiskSize = 2L * 1024 * 1024 * 1024;
+
+    private const long MaxMemorySize = 256L * 1024 * 1024;
+    private const int DiskHeaderSize = 4;
+
+    private readonly ConcurrentDictionary<string, byte[]> memory = new(StringComparer.Ordinal);
+    private readonly DirectoryInfo? directory;
+    private long memorySize;
+
+    /// <param name="directory">Folder for the persistent cache, or null to only deduplicate within this process</param>
+    public AppxBlockCache(DirectoryInfo? directory = null)
+    {
+        this.directory = directory;
+    }
+
+    public DirectoryInfo? CacheDirectory => directory;
+
+    /// <summary>
+    /// Looks up the compressed fragment of a block. Disk entries are checked against their stored CRC,
+    /// and corrupt ones are discarded.
+    /// </summary>
+    public bool TryGet(byte[] blockHash, out byte[] fragment)
+    {
+        var key = Convert.ToHexStringLower(blockHash);
+        if (memory.TryGetValue(key, out fragment!))
+        {
+            return true;
+        }
+
+        if (directory == null)
+        {
+            return false;
+        }
+
+        var path = GetPath(key);
+        if (!File.Exists(path))
+        {
+            return false;
+        }
+
+        try
+        {
+            var content = File.ReadAllBytes(path);
+            if (content.Length > DiskHeaderSize &&
+                BinaryPrimitives.ReadUInt32LittleEndian(content) == Crc32.Compute(content.AsSpan(DiskHeaderSize)))
+            {
+                fragment = content[DiskHeaderSize..];
+                AddToMemory(key, fragment);
+                return true;
+            }
+
+            File.Delete(path);
+        }
+        catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+        {
+            // An entry deleted or locked by a concurrent pack is simply a miss
+        }
+
+        fragment = null!;
+        return false;
+    }
+
+    public void Add(byte[] blockHash, byte[] fragment)
+    {
+        var key = Convert.ToHexStringLower(blockHash);
+        AddToMemory(key, fragment);
+
+        if (directory == null)
+        {
+            return;
+        }
+
+        var path = GetPath(key);
+        if (File.Exists(path))
+        {
+            return;
+        }
+
+        // Write to a unique temporary name first so concurrent packs never observe a partial entry
+        var tempPath = $"{path}.{Environment.ProcessId}.{Environment.CurrentManagedThreadId}.tmp";
+        try
+        {
+            Directory.CreateDirectory(Path.GetDirectoryName(path)!);
+            var content = new byte[DiskHeaderSize + fragment.Length];
+            BinaryPrimitives.WriteUInt32LittleEndian(content, Crc32.Compute(fragment));
+            fragment.CopyTo(content, DiskHeaderSize);
+            File.WriteAllBytes(tempPath, content);
+            File.Move(tempPath, path, overwrite: true);
+        }
+        catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+        {
+            // The cache is an optimization; failing to persist an entry must not fail the pack
+            try
+            {
+                File.Delete(tempPath);
+            }
+            catch (Exception cleanupEx) when (cleanupEx is IOException or UnauthorizedAccessException)
+            {
+                // Left for the next Trim
+            }
+        }
+    }
+
+    /// <summary>
+    /// Deletes the oldest disk entries until the cache is at most <paramref name="maxSize"/> bytes
+    /// </summary>
+    /// <returns>Number of entries deleted</returns>
+    public int Trim(long maxSize = DefaultMaxDiskSize)
+    {
+        if (directory == null || !directory.Exists)
+        {
+            return 0;
+        }
+
+        var files = directory.EnumerateFiles("*", SearchOption.AllDirectories).ToList();
+        var total = files.Sum(f => f.Length);
+        var deleted = 0;
+        foreach (var file in files.OrderBy(f => f.LastWriteTimeUtc))
+        {
+            if (total <= maxSize)
+            {
+                break;
+            }
+
+            try
+            {
+                var length = file.Length;
+                file.Delete();
+                total -= length;
+                deleted++;
+            }
+            catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+            {
+                // In use by a concurrent pack; try the next one
+            }
+        }
+
+        return deleted;
+    }
+
+    private void AddToMemory(string key, byte[] fragment)
+    {
+        if (Interlocked.Read(ref memorySize) + fragment.Length > MaxMemorySize)
+        {
+            return;
+        }
+
+        if (memory.TryAdd(key, fragment))
+        {
+            Interlocked.Add(ref memorySize, fragment.Length);
+        }
+    }
+
+    private string GetPath(string key) => Path.Combine(directory!.FullName, key[..2], key);
+}
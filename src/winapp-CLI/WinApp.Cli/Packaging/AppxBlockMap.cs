@@ -0,0 +1,148 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Security.Cryptography;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Hash of one 64 KB block of a payload file
+/// </summary>
+/// <param name="CompressedSize">Size of the block in the archive; null when the file is stored uncompressed</param>
+internal sealed record AppxBlock(byte[] Hash, long? CompressedSize);
+
+/// <param name="Name">Path of the file inside the package, using '\' separators</param>
+/// <param name="LfhSize">Size of the file's local file header in the archive</param>
+internal sealed record AppxBlockMapFile(string Name, long Size, int LfhSize, IReadOnlyList<AppxBlock> Blocks);
+
+/// <param name="HashMethod">Hash algorithm URI declared on the BlockMap element</param>
+internal sealed record AppxBlockMapDocument(string HashMethod, IReadOnlyList<AppxBlockMapFile> Files);
+
+/// <summary>
+/// Reads and writes AppxBlockMap.xml
+/// </summary>
+internal static class AppxBlockMap
+{
+    public const string PartName = "AppxBlockMap.xml";
+    public const string Namespace = "http://schemas.microsoft.com/appx/2010/blockmap";
+    public const string Sha256HashMethod = "http://www.w3.org/2001/04/xmlenc#sha256";
+    public const string Sha384HashMethod = "http://www.w3.org/2001/04/xmldsig-more#sha384";
+    public const string Sha512HashMethod = "http://www.w3.org/2001/04/xmlenc#sha512";
+    public const int BlockSize = 64 * 1024;
+
+    public static byte[] Serialize(IEnumerable<AppxBlockMapFile> files) => Serialize(files, HashAlgorithmName.SHA256);
+
+    /// <param name="hashAlgorithm">Algorithm the block hashes were computed with</param>
+    public static byte[] Serialize(IEnumerable<AppxBlockMapFile> files, HashAlgorithmName hashAlgorithm)
+    {
+        var hashMethod = GetHashMethod(hashAlgorithm);
+
+        var settings = new XmlWriterSettings
+        {
+            Encoding = new UTF8Encoding(encoderShouldEmitUTF8Identifier: false),
+            Indent = false
+        };
+
+        using var stream = new MemoryStream();
+        using (var writer = XmlWriter.Create(stream, settings))
+        {
+            writer.WriteStartDocument(standalone: false);
+            writer.WriteStartElement("BlockMap", Namespace);
+            writer.WriteAttributeString("HashMethod", hashMethod);
+
+            foreach (var file in files)
+            {
+                writer.WriteStartElement("File", Namespace);
+                writer.WriteAttributeString("Name", file.Name);
+                writer.WriteAttributeString("Size", file.Size.ToString(CultureInfo.InvariantCulture));
+                writer.WriteAttributeString("LfhSize", file.LfhSize.ToString(CultureInfo.InvariantCulture));
+                foreach (var block in file.Blocks)
+                {
+                    writer.WriteStartElement("Block", Namespace);
+                    writer.WriteAttributeString("Hash", Convert.ToBase64String(block.Hash));
+                    if (block.CompressedSize is long compressedSize)
+                    {
+                        writer.WriteAttributeString("Size", compressedSize.ToString(CultureInfo.InvariantCulture));
+                    }
+                    writer.WriteEndElement();
+                }
+                writer.WriteEndElement();
+            }
+
+            writer.WriteEndElement();
+            writer.WriteEndDocument();
+        }
+
+        return stream.ToArray();
+    }
+
+    public static AppxBlockMapDocument Parse(string xml)
+    {
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(xml);
+
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("b", Namespace);
+
+        var root = xmlDoc.SelectSingleNode("/b:BlockMap", nsManager) as XmlElement
+            ?? throw new InvalidDataException("AppxBlockMap.xml does not contain a BlockMap element");
+
+        var files = new List<AppxBlockMapFile>();
+        foreach (XmlElement fileElement in root.SelectNodes("b:File", nsManager)!)
+        {
+            var blocks = new List<AppxBlock>();
+            foreach (XmlElement blockElement in fileElement.SelectNodes("b:Block", nsManager)!)
+            {
+                var sizeAttribute = blockElement.GetAttribute("Size");
+                blocks.Add(new AppxBlock(
+                    Convert.FromBase64String(blockElement.GetAttribute("Hash")),
+                    string.IsNullOrEmpty(sizeAttribute) ? null : long.Parse(sizeAttribute, CultureInfo.InvariantCulture)));
+            }
+
+            files.Add(new AppxBlockMapFile(
+                fileElement.GetAttribute("Name"),
+                long.Parse(fileElement.GetAttribute("Size"), CultureInfo.InvariantCulture),
+                int.Parse(fileElement.GetAttribute("LfhSize"), CultureInfo.InvariantCulture),
+                blocks));
+        }
+
+        return new AppxBlockMapDocument(root.GetAttribute("HashMethod"), files);
+    }
+
+    public static string GetHashMethod(HashAlgorithmName hashAlgorithm)
+    {
+        if (hashAlgorithm == HashAlgorithmName.SHA256)
+        {
+            return Sha256HashMethod;
+        }
+        if (hashAlgorithm == HashAlgorithmName.SHA384)
+        {
+            return Sha384HashMethod;
+        }
+        if (hashAlgorithm == HashAlgorithmName.SHA512)
+        {
+            return Sha512HashMethod;
+        }
+
+        throw new NotSupportedException($"Unsupported block map hash algorithm {hashAlgorithm.Name}");
+    }
+
+    /// <summary>
+    /// Maps a BlockMap HashMethod URI to its algorithm, or null when the URI is not one the format allows
+    /// </summary>
+    public static HashAlgorithmName? GetHashAlgorithm(string hashMethod) => hashMethod.ToLowerInvariant() switch
+    {
+        Sha256HashMethod => HashAlgorithmName.SHA256,
+        Sha384HashMethod => HashAlgorithmName.SHA384,
+        Sha512HashMethod => HashAlgorithmName.SHA512,
+        _ => null
+    };
+
+    /// <summary>
+    /// Converts a zip item name ("Assets/My%20Logo.png") to the block map file name ("Assets\My Logo.png")
+    /// </summary>
+    public static string ToBlockMapName(string zipName) => Uri.UnescapeDataString(zipName).Replace('/', '\\');
+}
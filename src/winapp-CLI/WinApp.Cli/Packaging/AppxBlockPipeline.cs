@@ -0,0 +1,318 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers;
+using System.IO.Compression;
+using System.IO.MemoryMappedFiles;
+using System.Runtime.CompilerServices;
+using System.Security.Cryptography;
+using System.Threading.Channels;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// A 64 KB payload block after hashing and compression
+/// </summary>
+/// <param name="Length">Uncompressed length of the block</param>
+/// <param name="Data">Bytes to write to the archive: a sync-flushed deflate fragment, or the block itself when stored</param>
+internal sealed record AppxProcessedBlock(byte[] Hash, uint Crc32, int Length, ReadOnlyMemory<byte> Data);
+
+/// <summary>
+/// A payload file opened by <see cref="AppxBlockPipeline"/>. Its blocks are produced in order on <see cref="Blocks"/>.
+/// </summary>
+internal sealed class AppxPipelineFile : IDisposable
+{
+    private readonly MemoryMappedFile? map;
+    private readonly MemoryMappedViewAccessor? view;
+
+    private AppxPipelineFile(string zipName, long size, DateTime lastWriteTime, MemoryMappedFile? map, MemoryMappedViewAccessor? view)
+    {
+        ZipName = zipName;
+        Size = size;
+        LastWriteTime = lastWriteTime;
+        this.map = map;
+        this.view = view;
+    }
+
+    public string ZipName { get; }
+
+    /// <summary>
+    /// Size when the file was opened; the archive records describe exactly this many bytes
+    /// </summary>
+    public long Size { get; }
+
+    public DateTime LastWriteTime { get; }
+
+    internal Channel<Task<AppxProcessedBlock>> Blocks { get; } =
+        Channel.CreateUnbounded<Task<AppxProcessedBlock>>(new UnboundedChannelOptions { SingleReader = true, SingleWriter = true });
+
+    public static AppxPipelineFile Open(FileInfo file, string zipName)
+    {
+        file.Refresh();
+        var stream = new FileStream(file.FullName, FileMode.Open, FileAccess.Read, FileShare.Read);
+        var size = stream.Length;
+        if (size == 0)
+        {
+            // Empty files cannot be mapped, and have no blocks to read anyway
+            stream.Dispose();
+            return new AppxPipelineFile(zipName, 0, file.LastWriteTime, null, null);
+        }
+
+        var map = MemoryMappedFile.CreateFromFile(stream, mapName: null, capacity: 0, MemoryMappedFileAccess.Read, HandleInheritability.None, leaveOpen: false);
+        try
+        {
+            return new AppxPipelineFile(zipName, size, file.LastWriteTime, map, map.CreateViewAccessor(0, size, MemoryMappedFileAccess.Read));
+        }
+        catch
+        {
+            map.Dispose();
+            throw;
+        }
+    }
+
+    /// <summary>
+    /// Copies a range of the mapped file. Safe to call from several workers at once.
+    /// </summary>
+    public void Read(long offset, byte[] destination, int count) => view!.ReadArray(offset, destination, 0, count);
+
+    public void Dispose()
+    {
+        view?.Dispose();
+        map?.Dispose();
+    }
+}
+
+/// <summary>
+/// Parallel read → hash → compress stage of the native packer. Files are memory-mapped and split into
+/// 64 KB blocks that a pool of workers hashes and deflates independently, while the caller writes the
+/// results strictly in order. A fixed number of blocks may be in flight, which bounds memory use
+/// regardless of the payload size.
+/// </summary>
+/// <remarks>
+/// Each block is compressed into a standalone deflate fragment ending in a sync flush, so the fragments
+/// concatenate into a valid deflate stream once <see cref="FinalDeflateBlock"/> is appended (as pigz does).
+/// </remarks>
+internal sealed class AppxBlockPipeline
+{
+    /// <summary>
+    /// An empty, final fixed-Huffman deflate block that terminates a stream of sync-flushed fragments
+    /// </summary>
+    public static readonly byte[] FinalDeflateBlock = [0x03, 0x00];
+
+    private const int BlocksInFlightPerThread = 4;
+
+    private readonly int threads;
+    private readonly bool compress;
+    private readonly AppxBlockCache? cache;
+    private readonly HashAlgorithmName hashAlgorithm;
+    private long processedBlocks;
+    private long reusedBlocks;
+    private long reusedBytes;
+
+    /// <param name="cache">Store of previously compressed blocks to reuse instead of compressing again</param>
+    /// <param name="hashAlgorithm">Algorithm for the block hashes; SHA-256 when not given</param>
+    public AppxBlockPipeline(int threads, bool compress, AppxBlockCache? cache = null, HashAlgorithmName? hashAlgorithm = null)
+    {
+        this.threads = ResolveThreadCount(threads);
+        this.compress = compress;
+        this.cache = cache;
+        this.hashAlgorithm = hashAlgorithm ?? HashAlgorithmName.SHA256;
+    }
+
+    public int Threads => threads;
+
+    public long ProcessedBlocks => Interlocked.Read(ref processedBlocks);
+
+    /// <summary>
+    /// Blocks whose compressed fragment came from the <see cref="AppxBlockCache"/>
+    /// </summary>
+    public long ReusedBlocks => Interlocked.Read(ref reusedBlocks);
+
+    /// <summary>
+    /// Uncompressed size of the <see cref="ReusedBlocks"/>
+    /// </summary>
+    public long ReusedBytes => Interlocked.Read(ref reusedBytes);
+
+    /// <summary>
+    /// Returns the number of workers to use; 0 means one per logical processor
+    /// </summary>
+    public static int ResolveThreadCount(int threads)
+    {
+        ArgumentOutOfRangeException.ThrowIfNegative(threads);
+        return threads > 0 ? threads : Environment.ProcessorCount;
+    }
+
+    /// <summary>
+    /// Processes the payload files, calling <paramref name="writeFileAsync"/> for each one in order with an
+    /// enumeration of its processed blocks. The callback must enumerate all blocks before returning.
+    /// </summary>
+    public async Task RunAsync(
+        IReadOnlyList<(FileInfo File, string ZipName)> payload,
+        Func<AppxPipelineFile, IAsyncEnumerable<AppxProcessedBlock>, Task> writeFileAsync,
+        CancellationToken cancellationToken = default)
+    {
+        using var cancellation = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+        using var inFlight = new SemaphoreSlim(threads * BlocksInFlightPerThread);
+        var token = cancellation.Token;
+
+        var files = Channel.CreateUnbounded<AppxPipelineFile>(new UnboundedChannelOptions { SingleReader = true, SingleWriter = true });
+        var work = Channel.CreateUnbounded<BlockWork>(new UnboundedChannelOptions { SingleWriter = true });
+        var opened = new List<AppxPipelineFile>();
+
+        var workers = Enumerable.Range(0, threads).Select(_ => Task.Run(() => RunWorkerAsync(work.Reader, token), token)).ToList();
+        var producer = Task.Run(() => ProduceAsync(payload, files.Writer, work.Writer, inFlight, opened, token), token);
+
+        try
+        {
+            await foreach (var file in files.Reader.ReadAllAsync(token))
+            {
+                await writeFileAsync(file, ReadBlocksAsync(file, inFlight, token));
+
+                // Every block has been awaited, so no worker reads this file any more
+                file.Dispose();
+            }
+
+            await producer;
+        }
+        finally
+        {
+            // Stop the producer and workers if writing failed, then wait for them before unmapping any file
+            await cancellation.CancelAsync();
+            work.Writer.TryComplete();
+            try
+            {
+                await Task.WhenAll(workers.Append(producer));
+            }
+            catch
+            {
+                // Already surfaced through the block tasks or the files channel
+            }
+
+            // Disposing is idempotent; this releases the files that were opened but never written
+            foreach (var file in opened)
+            {
+                file.Dispose();
+            }
+        }
+    }
+
+    private static async Task ProduceAsync(
+        IReadOnlyList<(FileInfo File, string ZipName)> payload,
+        ChannelWriter<AppxPipelineFile> files,
+        ChannelWriter<BlockWork> work,
+        SemaphoreSlim inFlight,
+        List<AppxPipelineFile> opened,
+        CancellationToken cancellationToken)
+    {
+        AppxPipelineFile? file = null;
+        try
+        {
+            foreach (var (fileInfo, zipName) in payload)
+            {
+                file = AppxPipelineFile.Open(fileInfo, zipName);
+                opened.Add(file);
+                await files.WriteAsync(file, cancellationToken);
+
+                for (long offset = 0; offset < file.Size; offset += AppxBlockMap.BlockSize)
+                {
+                    await inFlight.WaitAsync(cancellationToken);
+                    var block = new BlockWork(file, offset, (int)Math.Min(AppxBlockMap.BlockSize, file.Size - offset));
+                    file.Blocks.Writer.TryWrite(block.Result.Task);
+                    await work.WriteAsync(block, cancellationToken);
+                }
+
+                file.Blocks.Writer.TryComplete();
+            }
+
+            files.TryComplete();
+        }
+        catch (Exception ex)
+        {
+            // Fail the file being written too, or the writer would wait for its remaining blocks forever
+            file?.Blocks.Writer.TryComplete(ex);
+            files.TryComplete(ex);
+            throw;
+        }
+        finally
+        {
+            work.TryComplete();
+        }
+    }
+
+    private async Task RunWorkerAsync(ChannelReader<BlockWork> work, CancellationToken cancellationToken)
+    {
+        await foreach (var block in work.ReadAllAsync(cancellationToken))
+        {
+            try
+            {
+                block.Result.TrySetResult(ProcessBlock(block.File, block.Offset, block.Length));
+            }
+            catch (Exception ex)
+            {
+                block.Result.TrySetException(ex);
+            }
+        }
+    }
+
+    private static async IAsyncEnumerable<AppxProcessedBlock> ReadBlocksAsync(
+        AppxPipelineFile file,
+        SemaphoreSlim inFlight,
+        [EnumeratorCancellation] CancellationToken cancellationToken = default)
+    {
+        await foreach (var pending in file.Blocks.Reader.ReadAllAsync(cancellationToken))
+        {
+            var block = await pending.WaitAsync(cancellationToken);
+            inFlight.Release();
+            yield return block;
+        }
+    }
+
+    private AppxProcessedBlock ProcessBlock(AppxPipelineFile file, long offset, int length)
+    {
+        var buffer = ArrayPool<byte>.Shared.Rent(length);
+        try
+        {
+            file.Read(offset, buffer, length);
+            var data = buffer.AsSpan(0, length);
+
+            var hash = AppxDigestAlgorithms.Hash(hashAlgorithm, data);
+            var crc = Crc32.Compute(data);
+            Interlocked.Increment(ref processedBlocks);
+            if (!compress)
+            {
+                return new AppxProcessedBlock(hash, crc, length, data.ToArray());
+            }
+
+            if (cache != null && cache.TryGet(hash, out var cached))
+            {
+                Interlocked.Increment(ref reusedBlocks);
+                Interlocked.Add(ref reusedBytes, length);
+                return new AppxProcessedBlock(hash, crc, length, cached);
+            }
+
+            var fragment = Deflate(data);
+            cache?.Add(hash, fragment);
+            return new AppxProcessedBlock(hash, crc, length, fragment);
+        }
+        finally
+        {
+            ArrayPool<byte>.Shared.Return(buffer);
+        }
+    }
+
+    private static byte[] Deflate(ReadOnlySpan<byte> data)
+    {
+        using var compressed = new MemoryStream((data.Length / 2) + 64);
+        using var deflate = new DeflateStream(compressed, CompressionLevel.Optimal, leaveOpen: true);
+        deflate.Write(data);
+        deflate.Flush();
+
+        // Keep only the sync-flushed fragment; disposing appends a final block that is not wanted here
+        return compressed.ToArray();
+    }
+
+    private sealed record BlockWork(AppxPipelineFile File, long Offset, int Length)
+    {
+        public TaskCompletionSource<AppxProcessedBlock> Result { get; } = new(TaskCreationOptions.RunContinuationsAsynchronously);
+    }
+}
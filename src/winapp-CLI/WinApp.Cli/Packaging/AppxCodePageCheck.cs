@@ -0,0 +1,99 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The check of 'winapp validate' for executables that open files through ANSI functions, which convert the path to
+/// the system code page, in a package with file names that code page may not have
+/// </summary>
+internal static class AppxCodePageCheck
+{
+    // Functions that take paths in the ANSI code page: the -A functions of Windows and the narrow C runtime functions
+    private static readonly HashSet<string> AnsiPathFunctions = new(StringComparer.Ordinal)
+    {
+        "CreateFileA", "CreateDirectoryA", "DeleteFileA", "CopyFileA", "MoveFileA", "MoveFileExA", "FindFirstFileA",
+        "FindFirstFileExA", "GetFileAttributesA", "GetFileAttributesExA", "GetFullPathNameA", "GetModuleFileNameA",
+        "LoadLibraryA", "LoadLibraryExA", "SetCurrentDirectoryA", "ShellExecuteA",
+        "fopen", "fopen_s", "_open", "_sopen_s", "_access", "_stat64", "_stat64i32", "_findfirst64", "_mkdir", "remove", "rename",
+    };
+
+    // Folders of a project next to its manifest that aren't packed
+    private static readonly string[] SkippedFolders = ["node_modules", ".git", ".winapp"];
+
+    private static readonly EnumerationOptions Enumeration = new() { IgnoreInaccessible = true };
+
+    /// <summary>
+    /// Reports desktop apps whose executable imports ANSI path functions, in a package with non-ASCII file names,
+    /// without activeCodePage UTF-8 in its application manifest, as locale/code-page
+    /// </summary>
+    public static void Check(XElement root, DirectoryInfo manifestDirectory, Action<XElement, string, AccessibilitySeverity, string, string> addNote)
+    {
+        var applications = root.Element(XName.Get("Applications", AppxManifestSchema.FoundationNamespace))?.Elements(XName.Get("Application", AppxManifestSchema.FoundationNamespace)) ?? [];
+        List<string>? nonAsciiNames = null;
+        foreach (var application in applications)
+        {
+            var executable = application.Attribute("Executable")?.Value;
+            if (string.IsNullOrWhiteSpace(executable) || !AppxDpiCheck.IsWin32App(application))
+            {
+                continue;
+            }
+            var path = Path.Combine(manifestDirectory.FullName, executable);
+            if (PeImports.Read(path) is not { } imports)
+            {
+                continue;
+            }
+
+            nonAsciiNames ??= FindNonAsciiNames(manifestDirectory);
+            var codePage = SxsManifest.ReadActiveCodePage(SxsManifest.Read(path));
+            if (Finding(executable, imports, codePage, nonAsciiNames) is { } message)
+            {
+                addNote(application, "locale/code-page", AccessibilitySeverity.Warning, executable, message);
+            }
+        }
+    }
+
+    /// <param name="imports">The dll!function imports of the executable</param>
+    /// <param name="codePage">The activeCodePage of its application manifest</param>
+    /// <param name="nonAsciiNames">Files of the package, relative to its root, whose names aren't ASCII</param>
+    internal static string? Finding(string executable, IReadOnlySet<string> imports, string? codePage, IReadOnlyList<string> nonAsciiNames)
+    {
+        if (nonAsciiNames.Count == 0 || string.Equals(codePage, "UTF-8", StringComparison.OrdinalIgnoreCase))
+        {
+            return null;
+        }
+        var functions = imports.Select(i => i[(i.IndexOf('!') + 1)..]).Where(AnsiPathFunctions.Contains).Distinct().Order(StringComparer.Ordinal).ToList();
+        if (functions.Count == 0)
+        {
+            return null;
+        }
+
+        var names = string.Join(", ", nonAsciiNames.Take(3)) + (nonAsciiNames.Count > 3 ? $" and {nonAsciiNames.Count - 3} more" : "");
+        return $"{executable} opens files with {string.Join(", ", functions.Take(3))}, which convert paths to the {(codePage == null ? "system code page" : $"code page {codePage}")}, and the package has file names outside ASCII ({names}). Where the code page lacks their characters, the app can't open them. Set activeCodePage UTF-8 in its application manifest, e.g. with 'win32Manifest:' in winapp.yaml";
+    }
+
+    private static List<string> FindNonAsciiNames(DirectoryInfo directory)
+    {
+        var names = new List<string>();
+        var pending = new Stack<DirectoryInfo>([directory]);
+        while (pending.TryPop(out var current))
+        {
+            foreach (var entry in current.EnumerateFileSystemInfos("*", Enumeration))
+            {
+                if (!entry.Name.All(char.IsAscii))
+                {
+                    names.Add(Path.GetRelativePath(directory.FullName, entry.FullName));
+                }
+                if (entry is DirectoryInfo child && !SkippedFolders.Contains(child.Name, StringComparer.OrdinalIgnoreCase))
+                {
+                    pending.Push(child);
+                }
+            }
+        }
+        names.Sort(StringComparer.Ordinal);
+        return names;
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Builds the OPC [Content_Types].xml part of a package
+/// </summary>
+internal static class AppxContentTypes
+{
+    public const string PartName = "[Content_Types].xml";
+    public const string Namespace = "http://schemas.openxmlformats.org/package/2006/content-types";
+
+    private const string DefaultContentType = "application/octet-stream";
+
+    private static readonly Dictionary<string, string> FootprintContentTypes = new(StringComparer.OrdinalIgnoreCase)
+    {
+        ["/AppxManifest.xml"] = "application/vnd.ms-appx.manifest+xml",
+        ["/AppxBlockMap.xml"] = "application/vnd.ms-appx.blockmap+xml",
+        ["/AppxSignature.p7x"] = "application/vnd.ms-appx.signature",
+        ["/AppxMetadata/CodeIntegrity.cat"] = "application/vnd.ms-pkiseccat",
+    };
+
+    private static readonly Dictionary<string, string> ExtensionContentTypes = new(StringComparer.OrdinalIgnoreCase)
+    {
+        ["dll"] = "application/x-msdownload",
+        ["exe"] = "application/x-msdownload",
+        ["png"] = "image/png",
+        ["jpg"] = "image/jpeg",
+        ["jpeg"] = "image/jpeg",
+        ["gif"] = "image/gif",
+        ["bmp"] = "image/bmp",
+        ["ico"] = "image/vnd.microsoft.icon",
+        ["svg"] = "image/svg+xml",
+        ["xml"] = "text/xml",
+        ["json"] = "application/json",
+        ["txt"] = "text/plain",
+        ["htm"] = "text/html",
+        ["html"] = "text/html",
+        ["css"] = "text/css",
+        ["js"] = "application/javascript",
+        ["wasm"] = "application/wasm",
+        ["pdf"] = "application/pdf",
+    };
+
+    /// <summary>
+    /// Generates [Content_Types].xml covering the given zip item names
+    /// </summary>
+    public static byte[] Serialize(IEnumerable<string> zipNames)
+    {
+        var defaults = new SortedDictionary<string, string>(StringComparer.OrdinalIgnoreCase);
+        var overrides = new List<(string PartName, string ContentType)>();
+
+        foreach (var zipName in zipNames)
+        {
+            var partName = "/" + zipName;
+            if (FootprintContentTypes.TryGetValue(partName, out var footprintType))
+            {
+                overrides.Add((partName, footprintType));
+                continue;
+            }
+
+            var extension = Path.GetExtension(Uri.UnescapeDataString(zipName)).TrimStart('.');
+            if (string.IsNullOrEmpty(extension))
+            {
+                overrides.Add((partName, DefaultContentType));
+                continue;
+            }
+
+            if (!defaults.ContainsKey(extension))
+            {
+                defaults[extension] = ExtensionContentTypes.GetValueOrDefault(extension, DefaultContentType);
+            }
+        }
+
+        var settings = new XmlWriterSettings
+        {
+            Encoding = new UTF8Encoding(encoderShouldEmitUTF8Identifier: false),
+            Indent = false
+        };
+
+        using var stream = new MemoryStream();
+        using (var writer = XmlWriter.Create(stream, settings))
+        {
+            writer.WriteStartDocument();
+            writer.WriteStartElement("Types", Namespace);
+            foreach (var (extension, contentType) in defaults)
+            {
+                writer.WriteStartElement("Default", Namespace);
+                writer.WriteAttributeString("Extension", extension);
+                writer.WriteAttributeString("ContentType", contentType);
+                writer.WriteEndElement();
+            }
+            foreach (var (partName, contentType) in overrides)
+            {
+                writer.WriteStartElement("Override", Namespace);
+                writer.WriteAttributeString("PartName", partName);
+                writer.WriteAttributeString("ContentType", contentType);
+                writer.WriteEndElement();
+            }
+            writer.WriteEndElement();
+            writer.WriteEndDocument();
+        }
+
+        return stream.ToArray();
+    }
+
+    /// <summary>
+    /// Adds an Override for <paramref name="zipName"/> to an existing [Content_Types].xml, if it is not covered yet
+    /// </summary>
+    public static byte[] AddOverride(byte[] contentTypes, string zipName)
+    {
+        var partName = "/" + zipName;
+
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        using (var reader = new MemoryStream(contentTypes))
+        {
+            xmlDoc.Load(reader);
+        }
+
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("ct", Namespace);
+
+        var root = xmlDoc.SelectSingleNode("/ct:Types", nsManager) as XmlElement
+            ?? throw new InvalidDataException("[Content_Types].xml does not contain a Types element");
+
+        foreach (XmlElement existing in root.SelectNodes("ct:Override", nsManager)!)
+        {
+            if (string.Equals(existing.GetAttribute("PartName"), partName, StringComparison.OrdinalIgnoreCase))
+            {
+                return contentTypes;
+            }
+        }
+
+        var element = xmlDoc.CreateElement("Override", Namespace);
+        element.SetAttribute("PartName", partName);
+        element.SetAttribute("ContentType", FootprintContentTypes.GetValueOrDefault(partName, DefaultContentType));
+        root.AppendChild(element);
+
+        using var stream = new MemoryStream();
+        using (var writer = XmlWriter.Create(stream, new XmlWriterSettings { Encoding = new UTF8Encoding(encoderShouldEmitUTF8Identifier: false) }))
+        {
+            xmlDoc.Save(writer);
+        }
+        return stream.ToArray();
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Writes the environment variables and shortcuts declared in winapp.yaml into a package manifest
+/// </summary>
+internal static class AppxDesktopExtensions
+{
+    public const string Desktop6Namespace = "http://schemas.microsoft.com/appx/manifest/desktop/windows10/6";
+    public const string Desktop7Namespace = "http://schemas.microsoft.com/appx/manifest/desktop/windows10/7";
+
+    /// <summary>
+    /// Windows 10 version 2004, the first release that reads the desktop6 and desktop7 schemas
+    /// </summary>
+    public static readonly Version MinVersion = new(10, 0, 19041, 0);
+
+    private const string EnvironmentVariablesCategory = "windows.environmentVariables";
+    private const string ShortcutCategory = "windows.shortcut";
+
+    /// <summary>
+    /// Replaces the manifest's environment variable and shortcut extensions with the declared ones. When the
+    /// manifest also targets Windows releases before <see cref="MinVersion"/>, the namespaces are made ignorable
+    /// so the package still installs there, without them.
+    /// </summary>
+    /// <param name="warnings">Receives a warning when the extensions are ignored on some of the targeted releases</param>
+    /// <exception cref="InvalidOperationException">A declaration is invalid or names an Application the manifest doesn't have</exception>
+    public static string Apply(string manifestXml, IReadOnlyList<EnvironmentVariableConfig> environment, IReadOnlyList<ShortcutConfig> shortcuts, ICollection<string> warnings)
+    {
+        if (environment.Count == 0 && shortcuts.Count == 0)
+        {
+            return manifestXml;
+        }
+
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        xmlDoc.LoadXml(manifestXml);
+        var root = xmlDoc.DocumentElement ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("m", root.NamespaceURI);
+
+        var minVersion = AppxDigestAlgorithms.ReadMinTargetVersion(manifestXml);
+        var ignorable = minVersion == null || minVersion < MinVersion;
+
+        if (environment.Count > 0)
+        {
+            var prefix = DeclareNamespace(root, "desktop6", Desktop6Namespace, ignorable);
+            var extensions = GetOrCreateExtensions(xmlDoc, root, nsManager);
+            RemoveExtensions(extensions, Desktop6Namespace, EnvironmentVariablesCategory);
+
+            var variables = xmlDoc.CreateElement(prefix, "EnvironmentVariables", Desktop6Namespace);
+            foreach (var variable in environment)
+            {
+                if (string.IsNullOrWhiteSpace(variable.Name) || variable.Name.Contains('='))
+                {
+                    throw new InvalidOperationException($"winapp.yaml: '{variable.Name}' is not a valid environment variable name");
+                }
+
+                var element = xmlDoc.CreateElement(prefix, "EnvironmentVariable", Desktop6Namespace);
+                element.SetAttribute("Name", variable.Name);
+                element.SetAttribute("Value", variable.Value);
+                variables.AppendChild(element);
+            }
+
+            var extension = xmlDoc.CreateElement(prefix, "Extension", Desktop6Namespace);
+            extension.SetAttribute("Category", EnvironmentVariablesCategory);
+            extension.AppendChild(variables);
+            extensions.AppendChild(extension);
+        }
+
+        if (shortcuts.Count > 0)
+        {
+            var prefix = DeclareNamespace(root, "desktop7", Desktop7Namespace, ignorable);
+            var applications = root.SelectNodes("m:Applications/m:Application", nsManager)!.OfType<XmlElement>().ToList();
+            if (applications.Count == 0)
+            {
+                throw new InvalidOperationException("winapp.yaml declares shortcuts, but the manifest has no Application for them to start");
+            }
+
+            foreach (var application in applications)
+            {
+                if (application.SelectSingleNode("m:Extensions", nsManager) is XmlElement existing)
+                {
+                    RemoveExtensions(existing, Desktop7Namespace, ShortcutCategory);
+                }
+            }
+
+            foreach (var shortcut in shortcuts)
+            {
+                if (!shortcut.File.StartsWith("[{", StringComparison.Ordinal) || !shortcut.File.EndsWith(".lnk", StringComparison.OrdinalIgnoreCase))
+                {
+                    throw new InvalidOperationException($"winapp.yaml: shortcut '{shortcut.File}' must start with a known folder such as [{{Common Programs}}] and end in .lnk");
+                }
+
+                var application = shortcut.Application == null
+                    ? applications[0]
+                    : applications.FirstOrDefault(a => a.GetAttribute("Id").Equals(shortcut.Application, StringComparison.OrdinalIgnoreCase))
+                        ?? throw new InvalidOperationException($"winapp.yaml: shortcut '{shortcut.File}' starts Application '{shortcut.Application}', which the manifest doesn't have");
+
+                var element = xmlDoc.CreateElement(prefix, "Shortcut", Desktop7Namespace);
+                element.SetAttribute("File", shortcut.File);
+                if (!string.IsNullOrEmpty(shortcut.Arguments))
+                {
+                    element.SetAttribute("Arguments", shortcut.Arguments);
+                }
+                if (!string.IsNullOrEmpty(shortcut.Icon))
+                {
+                    element.SetAttribute("Icon", shortcut.Icon.StartsWith("[{", StringComparison.Ordinal) ? shortcut.Icon : $@"[{{Package}}]\{shortcut.Icon.Replace('/', '\\')}");
+                }
+
+                var extension = xmlDoc.CreateElement(prefix, "Extension", Desktop7Namespace);
+                extension.SetAttribute("Category", ShortcutCategory);
+                extension.AppendChild(element);
+                GetOrCreateExtensions(xmlDoc, application, nsManager).AppendChild(extension);
+            }
+        }
+
+        if (ignorable)
+        {
+            warnings.Add($"Environment variables and shortcuts need Windows 10 version 2004 ({MinVersion}); the manifest targets {minVersion?.ToString() ?? "an unspecified version"}, where the package installs without them");
+        }
+
+        return xmlDoc.OuterXml;
+    }
+
+    /// <returns>The prefix the manifest uses for <paramref name="namespaceUri"/></returns>
+    private static string DeclareNamespace(XmlElement root, string prefix, string namespaceUri, bool ignorable)
+    {
+        var existing = root.GetPrefixOfNamespace(namespaceUri);
+        if (!string.IsNullOrEmpty(existing))
+        {
+            prefix = existing;
+        }
+        else
+        {
+            root.SetAttribute($"xmlns:{prefix}", namespaceUri);
+        }
+
+        var ignorableNamespaces = root.GetAttribute("IgnorableNamespaces").Split(' ', StringSplitOptions.RemoveEmptyEntries).ToList();
+        if (ignorable && !ignorableNamespaces.Contains(prefix))
+        {
+            ignorableNamespaces.Add(prefix);
+            root.SetAttribute("IgnorableNamespaces", string.Join(' ', ignorableNamespaces));
+        }
+
+        return prefix;
+    }
+
+    private static XmlElement GetOrCreateExtensions(XmlDocument xmlDoc, XmlElement parent, XmlNamespaceManager nsManager)
+    {
+        if (parent.SelectSingleNode("m:Extensions", nsManager) is XmlElement extensions)
+        {
+            return extensions;
+        }
+
+        extensions = xmlDoc.CreateElement("Extensions", parent.NamespaceURI);
+        parent.AppendChild(extensions);
+        return extensions;
+    }
+
+    private static void RemoveExtensions(XmlElement extensions, string namespaceUri, string category)
+    {
+        foreach (var extension in extensions.ChildNodes.OfType<XmlElement>()
+            .Where(e => e.LocalName == "Extension" && e.NamespaceURI == namespaceUri && e.GetAttribute("Category") == category)
+            .ToList())
+        {
+            extensions.RemoveChild(extension);
+        }
+    }
+}
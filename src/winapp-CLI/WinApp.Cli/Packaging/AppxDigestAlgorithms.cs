@@ -0,0 +1,98 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Hash algorithms the APPX format allows for block maps and package signatures, and which Windows
+/// versions accept them
+/// </summary>
+/// <remarks>
+/// The block map declares one hash method for every block, and the signature digests must use the same
+/// algorithm, so a package uses a single algorithm end to end. Windows 10 and later accept SHA-256, SHA-384
+/// and SHA-512; Windows 8.x and MSIX Core, which serve packages targeting versions below 10.0.10240.0,
+/// only deploy SHA-256 packages.
+/// </remarks>
+internal static class AppxDigestAlgorithms
+{
+    /// <summary>
+    /// Lowest TargetDeviceFamily MinVersion at which SHA-384 and SHA-512 packages deploy
+    /// </summary>
+    public static readonly Version StrongDigestMinVersion = new(10, 0, 10240, 0);
+
+    public static IReadOnlyList<HashAlgorithmName> Supported { get; } =
+        [HashAlgorithmName.SHA256, HashAlgorithmName.SHA384, HashAlgorithmName.SHA512];
+
+    public static bool IsSupported(HashAlgorithmName algorithm) => Supported.Contains(algorithm);
+
+    public static byte[] Hash(HashAlgorithmName algorithm, ReadOnlySpan<byte> data)
+    {
+        if (algorithm == HashAlgorithmName.SHA256)
+        {
+            return SHA256.HashData(data);
+        }
+        if (algorithm == HashAlgorithmName.SHA384)
+        {
+            return SHA384.HashData(data);
+        }
+        if (algorithm == HashAlgorithmName.SHA512)
+        {
+            return SHA512.HashData(data);
+        }
+
+        throw new NotSupportedException($"Unsupported package digest algorithm {algorithm.Name}");
+    }
+
+    /// <summary>
+    /// Digest length in bytes of an algorithm named like "SHA384" or "sha256RSA", or null when unknown.
+    /// Used to tell whether a package meets a required strength.
+    /// </summary>
+    public static int? GetDigestLength(string? algorithmName)
+    {
+        var name = algorithmName?.Replace("-", "").ToLowerInvariant() ?? string.Empty;
+        return name.Contains("sha512") ? 64
+            : name.Contains("sha384") ? 48
+            : name.Contains("sha256") ? 32
+            : name.Contains("sha1") ? 20
+            : name.Contains("md5") ? 16
+            : null;
+    }
+
+    /// <summary>
+    /// Returns why packages hashed with <paramref name="algorithm"/> cannot deploy to
+    /// <paramref name="minVersion"/>, or null when they can
+    /// </summary>
+    public static string? GetTargetVersionIssue(HashAlgorithmName algorithm, Version? minVersion)
+    {
+        if (algorithm == HashAlgorithmName.SHA256 || minVersion == null || minVersion >= StrongDigestMinVersion)
+        {
+            return null;
+        }
+
+        return $"{algorithm.Name} packages require Windows 10 ({StrongDigestMinVersion}) or later, but the manifest targets {minVersion}. " +
+            "Raise TargetDeviceFamily MinVersion or use SHA256.";
+    }
+
+    /// <summary>
+    /// Reads the lowest TargetDeviceFamily MinVersion a package manifest declares
+    /// </summary>
+    public static Version? ReadMinTargetVersion(string manifestXml)
+    {
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(manifestXml);
+
+        Version? lowest = null;
+        foreach (XmlElement family in xmlDoc.SelectNodes("//*[local-name()='Dependencies']/*[local-name()='TargetDeviceFamily']")!)
+        {
+            if (Version.TryParse(family.GetAttribute("MinVersion"), out var version) && (lowest == null || version < lowest))
+            {
+                lowest = version;
+            }
+        }
+
+        return lowest;
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Formats.Asn1;
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Cryptographic algorithms found in a package
+/// </summary>
+/// <param name="BlockMapAlgorithm">Block map hash algorithm, or null when the HashMethod is not one the format allows</param>
+/// <param name="SignatureDigestAlgorithm">Digest algorithm of the package signature; null when unsigned</param>
+/// <param name="SignatureKey">Signer key type and size, such as "RSA 3072-bit"</param>
+/// <param name="CertificateSignatureAlgorithm">Algorithm the signing certificate was issued with, such as "sha256RSA"</param>
+/// <param name="TimestampDigestAlgorithm">Hash algorithm of the RFC 3161 timestamp; null when not timestamped</param>
+/// <param name="MinTargetVersion">Lowest TargetDeviceFamily MinVersion of the manifest; null for bundles</param>
+/// <param name="Issues">Algorithms that are weaker than required, or that the targeted Windows versions do not accept</param>
+internal sealed record AppxDigestReport(
+    string BlockMapHashMethod,
+    HashAlgorithmName? BlockMapAlgorithm,
+    HashAlgorithmName? SignatureDigestAlgorithm,
+    string? SignatureKey,
+    string? CertificateSignatureAlgorithm,
+    string? TimestampDigestAlgorithm,
+    Version? MinTargetVersion,
+    IReadOnlyList<string> Issues)
+{
+    public bool IsSigned => SignatureDigestAlgorithm != null;
+}
+
+/// <summary>
+/// Reports the hash and signature algorithms an existing package uses, for security compliance reviews
+/// </summary>
+internal static class AppxDigestInspector
+{
+    private const int MinimumRsaKeySize = 2048;
+    private const string Rfc3161CounterSignatureOid = "1.3.6.1.4.1.311.3.3.1";
+
+    /// <param name="required">Weakest hash algorithm that is acceptable; anything weaker is reported as an issue</param>
+    public static async Task<AppxDigestReport> InspectAsync(FileInfo packagePath, HashAlgorithmName? required = null, CancellationToken cancellationToken = default)
+    {
+        await using var stream = packagePath.OpenRead();
+        var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(stream, cancellationToken);
+        var entries = centralDirectory.Entries;
+
+        var blockMapEntry = entries.FirstOrDefault(e => e.Name == AppxBlockMap.PartName)
+            ?? throw new InvalidDataException($"{AppxBlockMap.PartName} is missing");
+        string hashMethod;
+        try
+        {
+            hashMethod = AppxBlockMap.Parse(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, blockMapEntry, cancellationToken))).HashMethod;
+        }
+        catch (XmlException ex)
+        {
+            throw new InvalidDataException($"{AppxBlockMap.PartName} is malformed: {ex.Message}", ex);
+        }
+
+        var issues = new List<string>();
+        var blockMapAlgorithm = AppxBlockMap.GetHashAlgorithm(hashMethod);
+        if (blockMapAlgorithm == null)
+        {
+            issues.Add($"Block map uses an unsupported hash method: {hashMethod}");
+        }
+        else
+        {
+            CheckStrength("Block map hash", blockMapAlgorithm.Value.Name, required, issues);
+        }
+
+        Version? minTargetVersion = null;
+        var manifestEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.ManifestPartName);
+        if (manifestEntry != null)
+        {
+            try
+            {
+                minTargetVersion = AppxDigestAlgorithms.ReadMinTargetVersion(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, manifestEntry, cancellationToken)));
+            }
+            catch (XmlException)
+            {
+                // A malformed manifest is reported by verify; the algorithms can still be listed
+            }
+        }
+
+        if (blockMapAlgorithm != null && AppxDigestAlgorithms.GetTargetVersionIssue(blockMapAlgorithm.Value, minTargetVersion) is string versionIssue)
+        {
+            issues.Add(versionIssue);
+        }
+
+        var signatureEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.SignaturePartName);
+        if (signatureEntry == null)
+        {
+            return new AppxDigestReport(hashMethod, blockMapAlgorithm, null, null, null, null, minTargetVersion, issues);
+        }
+
+        var p7x = await ZipPackageReader.ReadEntryAsync(stream, signatureEntry, cancellationToken);
+        AppxSignatureContent content;
+        var signedCms = new SignedCms();
+        try
+        {
+            content = AppxSignature.DecodeContent(p7x);
+            signedCms.Decode(p7x.AsSpan(AppxSignature.P7xMagic.Length));
+        }
+        catch (Exception ex) when (ex is AsnContentException or CryptographicException or NotSupportedException)
+        {
+            throw new InvalidDataException($"{AppxPacker.SignaturePartName} is malformed: {ex.Message}", ex);
+        }
+
+        var signatureDigest = content.DigestAlgorithm;
+        CheckStrength("Signature digest", signatureDigest.Name, required, issues);
+        if (blockMapAlgorithm != null && blockMapAlgorithm != signatureDigest)
+        {
+            issues.Add($"Signature digest {signatureDigest.Name} does not match the block map hash {blockMapAlgorithm.Value.Name}");
+        }
+
+        string? signatureKey = null;
+        string? certificateAlgorithm = null;
+        string? timestampAlgorithm = null;
+        if (signedCms.SignerInfos.Count > 0)
+        {
+            var signerInfo = signedCms.SignerInfos[0];
+            if (signerInfo.Certificate is X509Certificate2 certificate)
+            {
+                signatureKey = DescribeKey(certificate, issues);
+                certificateAlgorithm = certificate.SignatureAlgorithm.FriendlyName ?? certificate.SignatureAlgorithm.Value;
+                CheckStrength("Certificate signature", certificateAlgorithm, required, issues);
+            }
+
+            timestampAlgorithm = GetTimestampAlgorithm(signerInfo);
+            if (timestampAlgorithm != null)
+            {
+                CheckStrength("Timestamp digest", timestampAlgorithm, required, issues);
+            }
+        }
+
+        return new AppxDigestReport(hashMethod, blockMapAlgorithm, signatureDigest, signatureKey, certificateAlgorithm, timestampAlgorithm, minTargetVersion, issues);
+    }
+
+    private static void CheckStrength(string usage, string? algorithmName, HashAlgorithmName? required, List<string> issues)
+    {
+        var length = AppxDigestAlgorithms.GetDigestLength(algorithmName);
+        if (length is < 32)
+        {
+            issues.Add($"{usage} uses {algorithmName}, which is no longer considered secure");
+        }
+        else if (required is HashAlgorithmName minimum && length < AppxDigestAlgorithms.GetDigestLength(minimum.Name))
+        {
+            issues.Add($"{usage} uses {algorithmName}, which is weaker than the required {minimum.Name}");
+        }
+    }
+
+    private static string? DescribeKey(X509Certificate2 certificate, List<string> issues)
+    {
+        using (var rsa = certificate.GetRSAPublicKey())
+        {
+            if (rsa != null)
+            {
+                if (rsa.KeySize < MinimumRsaKeySize)
+                {
+                    issues.Add($"Signing key is RSA {rsa.KeySize}-bit; at least {MinimumRsaKeySize} bits are required");
+                }
+                return $"RSA {rsa.KeySize}-bit";
+            }
+        }
+
+        using (var ecdsa = certificate.GetECDsaPublicKey())
+        {
+            if (ecdsa != null)
+            {
+                return $"ECDSA {ecdsa.KeySize}-bit";
+            }
+        }
+
+        return certificate.PublicKey.Oid.FriendlyName;
+    }
+
+    private static string? GetTimestampAlgorithm(SignerInfo signerInfo)
+    {
+        foreach (var attribute in signerInfo.UnsignedAttributes)
+        {
+            if (attribute.Oid.Value != Rfc3161CounterSignatureOid)
+            {
+                continue;
+            }
+
+            foreach (var value in attribute.Values)
+            {
+                if (Rfc3161TimestampToken.TryDecode(value.RawData, out var token, out _))
+                {
+                    var oid = token.TokenInfo.HashAlgorithmId;
+                    return oid.FriendlyName ?? oid.Value;
+                }
+            }
+        }
+
+        return null;
+    }
+}
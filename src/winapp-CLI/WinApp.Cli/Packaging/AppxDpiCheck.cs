@@ -0,0 +1,145 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The checks of 'winapp validate' for the DPI awareness the side-by-side manifest of a desktop app's executable
+/// declares, together with the trust level of the package and the UI frameworks the app ships, for combinations that
+/// render blurry on high DPI and mixed DPI monitors or give other processes and child windows wrong coordinates
+/// </summary>
+internal static class AppxDpiCheck
+{
+    // Functions that set the awareness in code instead of the manifest
+    private static readonly string[] DpiFunctions =
+    [
+        "user32.dll!SetProcessDpiAwarenessContext",
+        "user32.dll!SetProcessDPIAware",
+        "shcore.dll!SetProcessDpiAwareness",
+    ];
+
+    // WinUI 3, and the compiled XAML of WinUI and XAML islands
+    private static readonly string[] XamlFiles = ["Microsoft.UI.Xaml.dll", "Microsoft.WinUI.dll"];
+    private static readonly string[] WebView2Files = ["WebView2Loader.dll", "Microsoft.Web.WebView2.Core.dll"];
+
+    /// <summary>
+    /// Reports ignored DPI settings as dpi/manifest, WinUI or WebView2 apps that aren't per monitor v2 aware as
+    /// dpi/framework, and other apps that aren't per monitor v2 aware as dpi/awareness. UWP apps, which CoreWindow
+    /// makes per monitor aware, and executables that aren't in the folder yet aren't checked.
+    /// </summary>
+    public static void Check(XElement root, DirectoryInfo manifestDirectory, Action<XElement, string, AccessibilitySeverity, string, string> addNote)
+    {
+        var applications = root.Element(XName.Get("Applications", AppxManifestSchema.FoundationNamespace))?.Elements(XName.Get("Application", AppxManifestSchema.FoundationNamespace)) ?? [];
+        foreach (var application in applications)
+        {
+            var executable = application.Attribute("Executable")?.Value;
+            if (string.IsNullOrWhiteSpace(executable) || !IsWin32App(application))
+            {
+                continue;
+            }
+            var path = new FileInfo(Path.Combine(manifestDirectory.FullName, executable));
+            if (!path.Exists)
+            {
+                continue;
+            }
+
+            var imports = PeImports.Read(path.FullName) ?? new HashSet<string>();
+            var app = new DpiApp(
+                SxsManifest.ReadDpiAwareness(SxsManifest.Read(path.FullName)),
+                SetsAwarenessInCode: DpiFunctions.Any(imports.Contains) || IsTauriApp(manifestDirectory),
+                IsDotNet: File.Exists(Path.ChangeExtension(path.FullName, ".runtimeconfig.json")),
+                UsesXaml: UsesXaml(path.Directory!),
+                UsesWebView2: WebView2Files.Any(f => File.Exists(Path.Combine(path.DirectoryName!, f))) || imports.Any(i => i.StartsWith("webview2loader.dll!", StringComparison.Ordinal)));
+            foreach (var (rule, severity, message) in Findings(executable, app))
+            {
+                addNote(application, rule, severity, executable, message);
+            }
+        }
+    }
+
+    /// <param name="Declaration">What the executable's manifest declares</param>
+    /// <param name="SetsAwarenessInCode">Whether the app imports a function that sets its awareness, or is a Tauri app,
+    /// which sets it when it starts</param>
+    /// <param name="IsDotNet">Whether the executable is a .NET app host, which may set its awareness at startup, e.g.
+    /// with Application.SetHighDpiMode</param>
+    internal sealed record DpiApp(DpiDeclaration Declaration, bool SetsAwarenessInCode, bool IsDotNet, bool UsesXaml, bool UsesWebView2);
+
+    internal static IEnumerable<(string Rule, AccessibilitySeverity Severity, string Message)> Findings(string executable, DpiApp app)
+    {
+        foreach (var problem in app.Declaration.Problems)
+        {
+            yield return ("dpi/manifest", AccessibilitySeverity.Warning, $"The application manifest of {executable}: {problem}");
+        }
+
+        if (app.Declaration.Awareness == null && app.SetsAwarenessInCode)
+        {
+            yield break;
+        }
+        var awareness = app.Declaration.Awareness ?? DpiAwareness.Unaware;
+        if (awareness == DpiAwareness.PerMonitorV2)
+        {
+            yield break;
+        }
+        var declared = app.Declaration.Awareness == null ? "declares no DPI awareness, so it is DPI unaware" : $"is {Describe(awareness)}";
+
+        var framework = (app.UsesXaml, app.UsesWebView2) switch
+        {
+            (true, true) => "WinUI and WebView2",
+            (true, false) => "WinUI",
+            (false, true) => "WebView2",
+            _ => null,
+        };
+        if (framework != null)
+        {
+            yield return ("dpi/framework", AccessibilitySeverity.Warning,
+                $"{executable} uses {framework} and {declared}. They need per monitor v2 awareness: otherwise content is bitmap scaled and blurry, and popups, flyouts and the WebView2 window are placed and hit-tested at the wrong coordinates on monitors of another scale. Declare <dpiAwareness>PerMonitorV2</dpiAwareness> in its application manifest");
+            yield break;
+        }
+
+        if (awareness == DpiAwareness.Unaware)
+        {
+            yield return ("dpi/awareness", app.IsDotNet ? AccessibilitySeverity.Info : AccessibilitySeverity.Warning,
+                $"{executable} {declared}: Windows bitmap scales its windows, which are blurry at any display scale above 100%{(app.IsDotNet ? ", unless it sets its awareness at startup, e.g. with Application.SetHighDpiMode" : "")}. Declare <dpiAwareness>PerMonitorV2</dpiAwareness> in its application manifest");
+        }
+        else
+        {
+            yield return ("dpi/awareness", AccessibilitySeverity.Info, awareness == DpiAwareness.System
+                ? $"{executable} {declared}: its windows are blurry on monitors whose scale differs from the primary monitor's, and other processes see its coordinates scaled. Declare <dpiAwareness>PerMonitorV2</dpiAwareness> to scale on every monitor"
+                : $"{executable} {declared}: Windows doesn't scale its title bar, dialogs and child windows when it moves to a monitor of another scale. Declare <dpiAwareness>PerMonitorV2, PerMonitor</dpiAwareness> to let Windows scale them");
+        }
+    }
+
+    /// <summary>
+    /// Whether the application is a Win32 app, whose executable's manifest decides its DPI awareness, also when
+    /// uap10:TrustLevel runs it in the app container
+    /// </summary>
+    internal static bool IsWin32App(XElement application)
+    {
+        var runtimeBehavior = application.Attribute(XName.Get("RuntimeBehavior", AppxManifestSchema.Uap10Namespace))?.Value;
+        if (runtimeBehavior != null)
+        {
+            return runtimeBehavior != "windowsApp";
+        }
+        return application.Attribute("EntryPoint")?.Value == AppxManifestSchema.FullTrustEntryPoint;
+    }
+
+    private static string Describe(DpiAwareness awareness) => awareness switch
+    {
+        DpiAwareness.Unaware => "DPI unaware",
+        DpiAwareness.System => "system DPI aware",
+        DpiAwareness.PerMonitor => "per monitor (v1) DPI aware",
+        _ => "per monitor v2 DPI aware",
+    };
+
+    private static bool UsesXaml(DirectoryInfo directory) =>
+        XamlFiles.Any(f => File.Exists(Path.Combine(directory.FullName, f))) || directory.EnumerateFiles("*.xbf").Any();
+
+    // tao, the windowing library of Tauri, makes the process per monitor v2 aware when it starts
+    private static bool IsTauriApp(DirectoryInfo manifestDirectory) =>
+        File.Exists(Path.Combine(manifestDirectory.FullName, "tauri.conf.json"))
+        || File.Exists(Path.Combine(manifestDirectory.FullName, "src-tauri", "tauri.conf.json"))
+        || (manifestDirectory.Parent is { } parent && File.Exists(Path.Combine(parent.FullName, "tauri.conf.json")));
+}
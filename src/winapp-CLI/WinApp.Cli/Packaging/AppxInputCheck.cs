@@ -0,0 +1,65 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The checks of 'winapp validate' for global input APIs that an app's executable imports but that don't work under
+/// the trust level of its manifest, such as a low-level keyboard hook in the app container, which installs without an
+/// error and is never called
+/// </summary>
+internal static class AppxInputCheck
+{
+    private static readonly string[] HookFunctions = ["user32.dll!SetWindowsHookExW", "user32.dll!SetWindowsHookExA"];
+    private static readonly string[] RawInputFunctions = ["user32.dll!RegisterRawInputDevices"];
+
+    /// <summary>
+    /// Reports the executables of applications that run in the app container and import SetWindowsHookEx as
+    /// input/low-level-hook, and those that import RegisterRawInputDevices as input/background-raw-input. Executables
+    /// that aren't in the folder yet, such as before a build, aren't checked.
+    /// </summary>
+    public static void Check(XElement root, DirectoryInfo manifestDirectory, Action<XElement, string, AccessibilitySeverity, string, string> addNote) =>
+        Check(root, executable => PeImports.Read(Path.Combine(manifestDirectory.FullName, executable)), addNote);
+
+    /// <param name="readImports">The dll!function imports of an executable path of the manifest, or null when it can't be read</param>
+    internal static void Check(XElement root, Func<string, IReadOnlySet<string>?> readImports, Action<XElement, string, AccessibilitySeverity, string, string> addNote)
+    {
+        var applications = root.Element(XName.Get("Applications", AppxManifestSchema.FoundationNamespace))?.Elements(XName.Get("Application", AppxManifestSchema.FoundationNamespace)) ?? [];
+        foreach (var application in applications)
+        {
+            var executable = application.Attribute("Executable")?.Value;
+            if (string.IsNullOrWhiteSpace(executable) || !RunsInAppContainer(application) || readImports(executable) is not { } imports)
+            {
+                continue;
+            }
+
+            if (HookFunctions.Any(imports.Contains))
+            {
+                addNote(application, "input/low-level-hook", AccessibilitySeverity.Warning, executable,
+                    $"{executable} imports SetWindowsHookEx, but the app runs in the app container, where low-level keyboard and mouse hooks (WH_KEYBOARD_LL, WH_MOUSE_LL) install without an error and are never called. Run it as a desktop app with uap10:TrustLevel=\"mediumIL\", or use keyboard accelerators of its own windows");
+            }
+            if (RawInputFunctions.Any(imports.Contains))
+            {
+                addNote(application, "input/background-raw-input", AccessibilitySeverity.Info, executable,
+                    $"{executable} imports RegisterRawInputDevices, and the app runs in the app container, which only gets raw input while it has the foreground, even with RIDEV_INPUTSINK");
+            }
+        }
+    }
+
+    /// <summary>
+    /// Whether Windows starts the application in the app container: a UWP app, or a Win32 app with
+    /// uap10:TrustLevel="appContainer"
+    /// </summary>
+    internal static bool RunsInAppContainer(XElement application)
+    {
+        var trustLevel = application.Attribute(XName.Get("TrustLevel", AppxManifestSchema.Uap10Namespace))?.Value;
+        if (trustLevel != null)
+        {
+            return trustLevel == "appContainer";
+        }
+        return application.Attribute("EntryPoint")?.Value != AppxManifestSchema.FullTrustEntryPoint;
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// What the elements of a package manifest do, and which Windows release first reads each schema namespace
+/// </summary>
+internal static class AppxManifestGuide
+{
+    /// <summary>
+    /// The first Windows 10 release, which reads the foundation and uap schemas
+    /// </summary>
+    public static readonly Version Windows10 = new(10, 0, 10240, 0);
+
+    private static readonly Dictionary<string, string> Elements = new(StringComparer.Ordinal)
+    {
+        ["Package"] = "Root of the manifest. Its xmlns declarations decide which schemas, and so which Windows releases, the manifest needs; IgnorableNamespaces lists the ones older releases skip instead of refusing the package",
+        ["Identity"] = "Package identity. Name, Publisher, Version and ProcessorArchitecture make up the package full name. Publisher must equal the subject of the signing certificate exactly, and Store packages keep the last part of Version at 0",
+        ["Properties"] = "Installer and Store metadata: DisplayName, PublisherDisplayName and Logo",
+        ["DisplayName"] = "Package name shown by the installer, Settings and the Store; ms-resource: names it in resources.pri for each language",
+        ["PublisherDisplayName"] = "Publisher name shown by the installer and Settings",
+        ["Logo"] = "Store logo, 50x50 at scale 100. The manifest names the logical file, e.g. Assets\\StoreLogo.png, while the folder may hold only qualified variants such as StoreLogo.scale-200.png: MRT resolves them through resources.pri, and 'winapp pack' copies every variant with the manifest. A logo that exists in no variant fails the install",
+        ["Description"] = "Package description for the installer",
+        ["Dependencies"] = "Windows releases and framework packages the package needs",
+        ["TargetDeviceFamily"] = "Windows.Desktop for PCs. MinVersion is the oldest build the package installs on; MaxVersionTested is the newest it was tested on, and Windows applies compatibility behaviors to builds above it",
+        ["PackageDependency"] = "Framework package the app loads from, such as the Windows App Runtime. Store installs add it; a sideloaded package needs it installed already, at MinVersion or later",
+        ["Resources"] = "Languages, scales and DirectX feature levels the package provides resources for; the first language is the default",
+        ["Resource"] = "A language (Language), scale (uap:Scale) or DirectX feature level (uap:DXFeatureLevel) the package provides",
+        ["Applications"] = "Apps of the package, each with its own Start entry",
+        ["Application"] = "One app; Id is unique in the package. EntryPoint Windows.FullTrustApplication, or uap10:RuntimeBehavior packagedClassicApp, makes it a desktop app that runs outside the app container",
+        ["VisualElements"] = "How the app looks in Start, the taskbar and Settings: DisplayName, Description, BackgroundColor (the tile plate; transparent uses the accent color), Square150x150Logo (the Start tile) and Square44x44Logo (the app list and, through its targetsize variants, the taskbar and Alt+Tab)",
+        ["DefaultTile"] = "Optional tile sizes (Square71x71Logo, Wide310x150Logo, Square310x310Logo) and ShortName, the name tiles show",
+        ["ShowNameOnTiles"] = "Tile sizes that show the name",
+        ["ShowOn"] = "A tile size that shows ShortName, or DisplayName without one",
+        ["SplashScreen"] = "Image of 620x300 at scale 100 on BackgroundColor, shown while a UWP app starts. Windows doesn't show it for desktop apps",
+        ["LockScreen"] = "BadgeLogo, 24x24 white on transparent, for apps that show their status on the lock screen",
+        ["InitialRotationPreference"] = "Orientations a tablet turns to when the app starts",
+        ["Rotation"] = "One preferred orientation",
+        ["Capabilities"] = "What the app may access. Desktop apps run with full trust and aren't limited by most capabilities, but they are still listed in Settings and the Store",
+        ["Capability"] = "General capability, such as internetClient",
+        ["DeviceCapability"] = "Access to a device such as the webcam, microphone or location; Windows asks the user first",
+        ["Extensions"] = "Integration points with Windows. Under Application they belong to that app; under Package they are shared by the package, like COM proxy stubs",
+        ["Extension"] = "Integration point with Windows",
+    };
+
+    private static readonly Dictionary<string, string> ExtensionCategories = new(StringComparer.Ordinal)
+    {
+        ["windows.protocol"] = "Registers a URI scheme that starts the app",
+        ["windows.fileTypeAssociation"] = "Associates file types with the app, so it opens them and is listed in Open with",
+        ["windows.startupTask"] = "Starts the app at sign-in; users can turn it off in Task Manager and Settings",
+        ["windows.appExecutionAlias"] = "Adds a command, such as contoso.exe, that starts the app from a command prompt or Run",
+        ["windows.comServer"] = "Registers COM classes the app serves out of process",
+        ["windows.comInterface"] = "Registers COM interfaces and their proxy stubs",
+        ["windows.toastNotificationActivation"] = "COM class that Windows starts when the user clicks a toast notification of a desktop app",
+        ["windows.fullTrustProcess"] = "Lets a UWP app start a full trust process from the package",
+        ["windows.appService"] = "App service that other apps can call",
+        ["windows.backgroundTasks"] = "Background tasks the app registers",
+        ["windows.shareTarget"] = "Lists the app in the Share dialog",
+        ["windows.firewallRules"] = "Opens firewall ports for the app while it is installed",
+        ["windows.environmentVariables"] = "Sets environment variables while the package is installed; written from the environment: section of winapp.yaml",
+        ["windows.shortcut"] = "Adds a Start menu or desktop shortcut; written from the shortcuts: section of winapp.yaml",
+    };
+
+    private static readonly Dictionary<string, string> RestrictedCapabilities = new(StringComparer.Ordinal)
+    {
+        ["runFullTrust"] = "Restricted capability every desktop app needs to run with full trust. The Store asks why a package declares restricted capabilities",
+        ["allowElevation"] = "Restricted capability that lets the app run elevated, as an administrator",
+        ["packageManagement"] = "Restricted capability that lets the app install and remove other packages",
+        ["broadFileSystemAccess"] = "Restricted capability to access all files the user can, once the user allows it in Settings",
+        ["unvirtualizedResources"] = "Restricted capability that turns off registry and file system virtualization for the app",
+    };
+
+    /// <summary>
+    /// Windows release that first reads each schema namespace; a manifest that uses a namespace its MinVersion
+    /// predates only installs there when the namespace is ignorable
+    /// </summary>
+    internal static readonly Dictionary<string, Version> NamespaceMinVersions = new(StringComparer.Ordinal)
+    {
+        ["http://schemas.microsoft.com/appx/manifest/foundation/windows10"] = Windows10,
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10"] = Windows10,
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/2"] = new(10, 0, 10586, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/3"] = new(10, 0, 14393, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/4"] = new(10, 0, 15063, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/5"] = new(10, 0, 16299, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/6"] = new(10, 0, 17134, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/7"] = new(10, 0, 17763, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/8"] = new(10, 0, 17763, 0),
+        ["http://schemas.microsoft.com/appx/manifest/uap/windows10/10"] = new(10, 0, 19041, 0),
+        ["http://schemas.microsoft.com/appx/manifest/desktop/windows10"] = new(10, 0, 14393, 0),
+        ["http://schemas.microsoft.com/appx/manifest/desktop/windows10/2"] = new(10, 0, 14393, 0),
+        ["http://schemas.microsoft.com/appx/manifest/desktop/windows10/4"] = new(10, 0, 16299, 0),
+        [AppxDesktopExtensions.Desktop6Namespace] = AppxDesktopExtensions.MinVersion,
+        [AppxDesktopExtensions.Desktop7Namespace] = AppxDesktopExtensions.MinVersion,
+    };
+
+    private static readonly Dictionary<int, string> ReleaseNames = new()
+    {
+        [10240] = "1507",
+        [10586] = "1511",
+        [14393] = "1607",
+        [15063] = "1703",
+        [16299] = "1709",
+        [17134] = "1803",
+        [17763] = "1809",
+        [19041] = "2004",
+    };
+
+    /// <summary>
+    /// What an element does, or null for elements the guide doesn't cover
+    /// </summary>
+    /// <param name="localName">Element name without its namespace prefix</param>
+    /// <param name="category">Category attribute of an Extension, or Name attribute of a Capability</param>
+    public static string? Describe(string localName, string? category)
+    {
+        if (localName == "Extension" && category != null && ExtensionCategories.TryGetValue(category, out var extension))
+        {
+            return extension;
+        }
+        if (localName == "Capability" && category != null && RestrictedCapabilities.TryGetValue(category, out var capability))
+        {
+            return capability;
+        }
+        return Elements.GetValueOrDefault(localName);
+    }
+
+    /// <summary>
+    /// Display name of a Windows build, e.g. "Windows 10 version 1607 (10.0.14393.0)"
+    /// </summary>
+    public static string FormatRelease(Version version) =>
+        ReleaseNames.TryGetValue(version.Build, out var release) ? $"Windows 10 version {release} ({version})" : $"Windows {version}";
+}
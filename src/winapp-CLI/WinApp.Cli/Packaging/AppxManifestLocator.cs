@@ -0,0 +1,71 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Finds the manifest element an analyze issue is about, from the subject the analyze checks report
+/// </summary>
+internal static class AppxManifestLocator
+{
+    /// <summary>
+    /// The element an issue is about, or the root when the manifest has no such element
+    /// </summary>
+    /// <param name="rule">Rule id of the issue; its source decides what the subject names</param>
+    /// <param name="subject">Attribute or element for a11y, application id for splash, asset for scales</param>
+    public static XElement Locate(XElement root, string rule, string subject) => rule.Split('/')[0] switch
+    {
+        "a11y" => FindBySubject(root, subject),
+        "splash" => FindByApplication(root, subject),
+        "scales" => FindByAsset(root, subject),
+        _ => root,
+    };
+
+    /// <summary>
+    /// Path of an element by local names, with the Id, Category or Name that tells siblings apart, e.g.
+    /// Package/Applications/Application[Notes]/Extensions/Extension[windows.protocol]
+    /// </summary>
+    public static string PathOf(XElement element) =>
+        string.Join("/", element.AncestorsAndSelf().Reverse().Select(e =>
+            (e.Attribute("Id") ?? e.Attribute("Category") ?? e.Attribute("Name")) is XAttribute key && e.Name.LocalName != "Identity"
+                ? $"{e.Name.LocalName}[{key.Value}]"
+                : e.Name.LocalName));
+
+    /// <summary>
+    /// The first element with a path from <see cref="PathOf"/>, or the root when the manifest no longer has it
+    /// </summary>
+    public static XElement FindByPath(XElement root, string path) =>
+        root.DescendantsAndSelf().FirstOrDefault(e => PathOf(e) == path) ?? root;
+
+    // a11y issues name the attribute (Square44x44Logo, DisplayName) or the element (Logo, SplashScreen) they are about
+    private static XElement FindBySubject(XElement root, string subject) =>
+        root.Descendants().FirstOrDefault(e => e.Attribute(subject) != null)
+        ?? root.Descendants().FirstOrDefault(e => e.Name.LocalName == subject)
+        ?? root;
+
+    private static XElement FindByApplication(XElement root, string id)
+    {
+        var application = root.Descendants().FirstOrDefault(e => e.Name.LocalName == "Application" && e.Attribute("Id")?.Value == id);
+        return application?.Descendants().FirstOrDefault(e => e.Name.LocalName == "SplashScreen")
+            ?? application?.Descendants().FirstOrDefault(e => e.Name.LocalName == "VisualElements")
+            ?? application
+            ?? root;
+    }
+
+    // Scale issues name the asset as the manifest does, or one of its qualified variant files
+    private static XElement FindByAsset(XElement root, string asset)
+    {
+        var variantName = Path.GetFileNameWithoutExtension(asset);
+        bool Matches(string value) =>
+            value.Equals(asset, StringComparison.OrdinalIgnoreCase)
+            || (Path.GetExtension(value).Equals(Path.GetExtension(asset), StringComparison.OrdinalIgnoreCase)
+                && MsixService.IsMrtVariantName(Path.GetFileNameWithoutExtension(value.Replace('\\', '/').Split('/')[^1]), variantName));
+
+        return root.Descendants().FirstOrDefault(e => e.Attributes().Any(a => Matches(a.Value)))
+            ?? root.Descendants().FirstOrDefault(e => !e.HasElements && Matches(e.Value.Trim()))
+            ?? root;
+    }
+}
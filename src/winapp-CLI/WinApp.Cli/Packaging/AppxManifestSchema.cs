@@ -0,0 +1,321 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Text.RegularExpressions;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The checks of 'winapp validate' that MakeAppx, signing or the installer would otherwise fail on: elements and
+/// attributes the foundation and uap schemas require, logos that resolve to no file or resource, and desktop apps
+/// whose entry point, capabilities and device families don't agree
+/// </summary>
+internal static partial class AppxManifestSchema
+{
+    internal const string FoundationNamespace = "http://schemas.microsoft.com/appx/manifest/foundation/windows10";
+    internal const string Uap10Namespace = "http://schemas.microsoft.com/appx/manifest/uap/windows10/10";
+    internal const string FullTrustEntryPoint = "Windows.FullTrustApplication";
+
+    private const string ResourcePrefix = "ms-resource:";
+
+    // The release that reads uap10:TrustLevel and uap10:RuntimeBehavior (20H1)
+    private static readonly Version Windows2004 = new(10, 0, 19041, 0);
+
+    private static readonly string[] ProcessorArchitectures = ["x86", "x64", "arm", "arm64", "neutral", "x86a64"];
+
+    private static readonly string[] VisualElementsAttributes = ["DisplayName", "Description", "BackgroundColor", "Square150x150Logo", "Square44x44Logo"];
+
+    private static readonly EnumerationOptions Enumeration = new() { IgnoreInaccessible = true, RecurseSubdirectories = true };
+
+    [GeneratedRegex(@"^[-.A-Za-z0-9]{3,50}$")]
+    private static partial Regex IdentityNameRegex();
+
+    [GeneratedRegex(@"^[A-Za-z][A-Za-z0-9]*(\.[A-Za-z][A-Za-z0-9]*)*$")]
+    private static partial Regex ApplicationIdRegex();
+
+    [GeneratedRegex(@"^(0|[1-9][0-9]{0,4})(\.(0|[1-9][0-9]{0,4})){3}$")]
+    private static partial Regex VersionRegex();
+
+    /// <summary>
+    /// Whether a manifest value names a resource in resources.pri instead of a file, e.g. ms-resource:AppLogo
+    /// </summary>
+    public static bool IsResourceReference(string value) => value.StartsWith(ResourcePrefix, StringComparison.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// Reports missing and malformed elements and attributes as manifest/schema, and desktop apps whose entry point,
+    /// runFullTrust capability and device families disagree as manifest/entrypoint, manifest/capability and
+    /// manifest/device-family
+    /// </summary>
+    public static void CheckStructure(XElement root, Action<XElement, string, AccessibilitySeverity, string> addNote)
+    {
+        void Schema(XElement element, string message) => addNote(element, "manifest/schema", AccessibilitySeverity.Error, message);
+
+        if (root.Name != XName.Get("Package", FoundationNamespace))
+        {
+            Schema(root, $"The root element is {root.Name.LocalName} in {root.Name.NamespaceName}, where MakeAppx expects Package in {FoundationNamespace}");
+            return;
+        }
+
+        XElement? Child(XElement parent, string name)
+        {
+            var child = parent.Element(XName.Get(name, FoundationNamespace));
+            if (child == null)
+            {
+                Schema(parent, $"{parent.Name.LocalName} has no {name}, which the schema requires");
+            }
+            return child;
+        }
+
+        string? Attribute(XElement element, string name)
+        {
+            var value = element.Attribute(name)?.Value;
+            if (string.IsNullOrWhiteSpace(value))
+            {
+                Schema(element, $"{element.Name.LocalName} has no {name}, which the schema requires");
+                return null;
+            }
+            return value;
+        }
+
+        if (Child(root, "Identity") is { } identity)
+        {
+            if (Attribute(identity, "Name") is { } name && !IdentityNameRegex().IsMatch(name))
+            {
+                Schema(identity, $"Name '{name}' must be 3 to 50 letters, digits, periods and dashes");
+            }
+            if (Attribute(identity, "Publisher") is { } publisher && !publisher.Contains('='))
+            {
+                Schema(identity, $"Publisher '{publisher}' isn't a distinguished name such as CN=Contoso; it must equal the subject of the signing certificate");
+            }
+            if (Attribute(identity, "Version") is { } version && !IsPackageVersion(version))
+            {
+                Schema(identity, $"Version '{version}' must be four numbers from 0 to 65535, e.g. 1.2.0.0");
+            }
+            if (identity.Attribute("ProcessorArchitecture")?.Value is { } architecture && !ProcessorArchitectures.Contains(architecture))
+            {
+                Schema(identity, $"ProcessorArchitecture '{architecture}' must be one of {string.Join(", ", ProcessorArchitectures)}");
+            }
+        }
+
+        if (Child(root, "Properties") is { } properties)
+        {
+            Child(properties, "DisplayName");
+            Child(properties, "PublisherDisplayName");
+            Child(properties, "Logo");
+        }
+
+        if (Child(root, "Resources") is { } resources && !resources.Elements(XName.Get("Resource", FoundationNamespace)).Any())
+        {
+            Schema(resources, "Resources has no Resource; declare the default language, e.g. <Resource Language=\"en-us\" />");
+        }
+
+        var families = new List<XElement>();
+        if (Child(root, "Dependencies") is { } dependencies)
+        {
+            families = [.. dependencies.Elements(XName.Get("TargetDeviceFamily", FoundationNamespace))];
+            if (families.Count == 0)
+            {
+                Schema(dependencies, "Dependencies has no TargetDeviceFamily, so Windows can't tell which devices and releases the package installs on");
+            }
+            foreach (var family in families)
+            {
+                Attribute(family, "Name");
+                var minVersion = Attribute(family, "MinVersion");
+                var maxVersionTested = Attribute(family, "MaxVersionTested");
+                foreach (var (attribute, value) in new[] { ("MinVersion", minVersion), ("MaxVersionTested", maxVersionTested) })
+                {
+                    if (value != null && !IsPackageVersion(value))
+                    {
+                        Schema(family, $"{attribute} '{value}' must be four numbers from 0 to 65535, e.g. 10.0.17763.0");
+                    }
+                }
+                if (Version.TryParse(minVersion, out var min) && Version.TryParse(maxVersionTested, out var max) && min > max)
+                {
+                    addNote(family, "manifest/device-family", AccessibilitySeverity.Warning,
+                        $"MinVersion {min} is higher than MaxVersionTested {max}; raise MaxVersionTested to the newest release the app was tested on");
+                }
+            }
+        }
+
+        var applications = root.Element(XName.Get("Applications", FoundationNamespace))?.Elements(XName.Get("Application", FoundationNamespace)).ToList() ?? [];
+        foreach (var duplicate in applications.GroupBy(a => a.Attribute("Id")?.Value, StringComparer.OrdinalIgnoreCase).Where(g => g.Key != null && g.Count() > 1))
+        {
+            Schema(duplicate.Last(), $"Application Id '{duplicate.Key}' is used {duplicate.Count()} times; each application needs its own");
+        }
+
+        var minTargetVersion = families.Select(f => Version.TryParse(f.Attribute("MinVersion")?.Value, out var v) ? v : null).Where(v => v != null).Min();
+        var hasDesktopFamily = families.Any(f => f.Attribute("Name")?.Value is "Windows.Desktop");
+        var hasRunFullTrust = root.Descendants().Any(e => e.Name.LocalName == "Capability" && e.Attribute("Name")?.Value == "runFullTrust");
+        foreach (var application in applications)
+        {
+            if (Attribute(application, "Id") is { } id && (id.Length > 64 || !ApplicationIdRegex().IsMatch(id)))
+            {
+                Schema(application, $"Id '{id}' must be at most 64 letters and digits, starting with a letter, in parts separated by periods");
+            }
+
+            // Apps hosted by another package's executable name the host instead of an executable
+            if (application.Attribute(XName.Get("HostId", Uap10Namespace)) == null)
+            {
+                Attribute(application, "Executable");
+            }
+
+            var visualElements = application.Elements().FirstOrDefault(e => e.Name.LocalName == "VisualElements");
+            if (visualElements == null)
+            {
+                Schema(application, "Application has no uap:VisualElements, which the schema requires");
+            }
+            else
+            {
+                foreach (var attribute in VisualElementsAttributes)
+                {
+                    Attribute(visualElements, attribute);
+                }
+            }
+
+            CheckEntryPoint(root, application, minTargetVersion, hasDesktopFamily, hasRunFullTrust, addNote);
+        }
+    }
+
+    private static void CheckEntryPoint(XElement root, XElement application, Version? minTargetVersion, bool hasDesktopFamily, bool hasRunFullTrust, Action<XElement, string, AccessibilitySeverity, string> addNote)
+    {
+        var entryPoint = application.Attribute("EntryPoint")?.Value;
+        var trustLevel = application.Attribute(XName.Get("TrustLevel", Uap10Namespace))?.Value;
+        var runtimeBehavior = application.Attribute(XName.Get("RuntimeBehavior", Uap10Namespace))?.Value;
+        var isFullTrustEntryPoint = entryPoint == FullTrustEntryPoint;
+
+        if ((trustLevel == null) != (runtimeBehavior == null))
+        {
+            addNote(application, "manifest/entrypoint", AccessibilitySeverity.Error,
+                $"uap10:{(trustLevel == null ? "RuntimeBehavior" : "TrustLevel")} is set without uap10:{(trustLevel == null ? "TrustLevel" : "RuntimeBehavior")}; Windows reads them together, e.g. TrustLevel=\"mediumIL\" RuntimeBehavior=\"packagedClassicApp\"");
+        }
+        if (isFullTrustEntryPoint && (trustLevel == "appContainer" || runtimeBehavior == "windowsApp"))
+        {
+            addNote(application, "manifest/entrypoint", AccessibilitySeverity.Error,
+                $"EntryPoint {FullTrustEntryPoint} makes the app a desktop app, while uap10:{(trustLevel == "appContainer" ? "TrustLevel appContainer" : "RuntimeBehavior windowsApp")} runs it in the app container; remove one of them");
+        }
+
+        // When uap10 isn't ignorable, older releases refuse the package, which manifest/version reports; when it is,
+        // they install it and skip the attributes, and start the app without EntryPoint as a UWP app
+        var desktopByUap10 = trustLevel == "mediumIL" || runtimeBehavior is "packagedClassicApp" or "win32App";
+        var prefix = root.GetPrefixOfNamespace(Uap10Namespace);
+        var uap10Ignorable = prefix != null && (root.Attribute("IgnorableNamespaces")?.Value.Split(' ', StringSplitOptions.RemoveEmptyEntries).Contains(prefix) ?? false);
+        if (desktopByUap10 && !isFullTrustEntryPoint && uap10Ignorable && minTargetVersion != null && minTargetVersion < Windows2004)
+        {
+            addNote(application, "manifest/entrypoint", AccessibilitySeverity.Error,
+                $"MinVersion is {minTargetVersion}, and releases before {AppxManifestGuide.FormatRelease(Windows2004)} ignore uap10:TrustLevel and uap10:RuntimeBehavior, so they start the app in the app container, where it fails. Add EntryPoint=\"{FullTrustEntryPoint}\", or raise MinVersion");
+        }
+
+        if ((isFullTrustEntryPoint || desktopByUap10) && !hasRunFullTrust)
+        {
+            addNote(application, "manifest/capability", AccessibilitySeverity.Error,
+                "The app runs as a desktop app, which needs <rescap:Capability Name=\"runFullTrust\" /> under Capabilities, or Windows refuses to start it");
+        }
+        if ((isFullTrustEntryPoint || desktopByUap10) && !hasDesktopFamily)
+        {
+            addNote(application, "manifest/device-family", AccessibilitySeverity.Warning,
+                "The app runs as a desktop app, which only Windows.Desktop devices can run; declare TargetDeviceFamily Windows.Desktop so the package isn't offered to Xbox, HoloLens or Surface Hub");
+        }
+    }
+
+    /// <summary>
+    /// Reports Logo and Square*Logo values as manifest/logo when they aren't package-relative paths, or when an
+    /// ms-resource: value names no resource in resources.pri or the .resw files of the project
+    /// </summary>
+    /// <remarks>Whether package-relative files exist in some scale or variant is the a11y/asset and scales/asset check</remarks>
+    public static void CheckLogos(XElement root, DirectoryInfo manifestDirectory, Action<XElement, string, AccessibilitySeverity, string, string> addNote)
+    {
+        var logos = root.Descendants()
+            .Where(e => e.Name.LocalName == "Logo" && e.Parent?.Name.LocalName == "Properties")
+            .Select(e => (Element: e, Name: "Logo", Value: e.Value.Trim()))
+            .Concat(root.Descendants()
+                .Where(e => e.Name.LocalName is "VisualElements" or "DefaultTile")
+                .SelectMany(e => e.Attributes()
+                    .Where(a => a.Name.LocalName == "Logo" || (a.Name.LocalName.StartsWith("Square", StringComparison.Ordinal) && a.Name.LocalName.EndsWith("Logo", StringComparison.Ordinal)) || a.Name.LocalName == "Wide310x150Logo")
+                    .Select(a => (Element: e, Name: a.Name.LocalName, Value: a.Value.Trim()))))
+            .Where(l => l.Value.Length > 0)
+            .ToList();
+
+        byte[]? pri = null;
+        List<string>? reswNames = null;
+        foreach (var (element, name, value) in logos)
+        {
+            if (!IsResourceReference(value))
+            {
+                if (Path.IsPathRooted(value) || value.Contains("://", StringComparison.Ordinal) || value.Replace('\\', '/').Split('/').Contains(".."))
+                {
+                    addNote(element, "manifest/logo", AccessibilitySeverity.Error, name, $"{value} isn't a path in the package; logos are relative to the package root, e.g. Assets\\StoreLogo.png");
+                }
+                continue;
+            }
+
+            var key = value[ResourcePrefix.Length..].TrimStart('/');
+            if (key.StartsWith("Files/", StringComparison.OrdinalIgnoreCase))
+            {
+                // ms-resource:/Files/Assets/Logo.png names a file by its path, which any of its variants resolve
+                var path = key["Files/".Length..];
+                if (AccessibilityAuditService.FindVariants(manifestDirectory, path, name, []).Count == 0)
+                {
+                    addNote(element, "manifest/logo", AccessibilitySeverity.Error, name, $"{value} names {path}, which exists in no scale or variant");
+                }
+                continue;
+            }
+
+            var resourceName = key.StartsWith("Resources/", StringComparison.OrdinalIgnoreCase) ? key["Resources/".Length..] : key;
+            var priPath = Path.Combine(manifestDirectory.FullName, "resources.pri");
+            pri ??= File.Exists(priPath) ? File.ReadAllBytes(priPath) : [];
+            reswNames ??= ReadReswNames(manifestDirectory);
+            if (ContainsResourceName(pri, resourceName) || reswNames.Contains(resourceName, StringComparer.OrdinalIgnoreCase))
+            {
+                continue;
+            }
+
+            if (pri.Length == 0 && reswNames.Count == 0)
+            {
+                addNote(element, "manifest/logo", AccessibilitySeverity.Warning, name,
+                    $"{value} names a resource, but there is no resources.pri or .resw file to resolve it in; validate the package folder after 'winapp package' generates resources.pri");
+            }
+            else
+            {
+                addNote(element, "manifest/logo", AccessibilitySeverity.Error, name,
+                    $"{value} names resource '{resourceName}', which neither resources.pri nor the .resw files define, so the installer can't find the logo");
+            }
+        }
+    }
+
+    /// <summary>
+    /// Whether a resource name is among the names of a PRI file, which stores them as UTF-16 or ASCII strings
+    /// </summary>
+    internal static bool ContainsResourceName(byte[] pri, string name)
+    {
+        if (pri.Length == 0)
+        {
+            return false;
+        }
+
+        // UTF-16 names may start at an odd offset, so decode at both alignments
+        return Encoding.Latin1.GetString(pri).Contains(name, StringComparison.OrdinalIgnoreCase)
+            || new[] { 0, 1 }.Any(offset => Encoding.Unicode.GetString(pri, offset, (pri.Length - offset) & ~1).Contains(name, StringComparison.OrdinalIgnoreCase));
+    }
+
+    private static List<string> ReadReswNames(DirectoryInfo manifestDirectory) =>
+        [.. manifestDirectory.EnumerateFiles("*.resw", Enumeration)
+            .Where(f => !Path.GetRelativePath(manifestDirectory.FullName, f.FullName).Split(Path.DirectorySeparatorChar).Any(p => p is "bin" or "obj" or "node_modules"))
+            .SelectMany(f =>
+            {
+                try
+                {
+                    return XDocument.Load(f.FullName).Root?.Elements("data").Select(d => d.Attribute("name")?.Value).OfType<string>() ?? [];
+                }
+                catch (System.Xml.XmlException)
+                {
+                    return [];
+                }
+            })];
+
+    private static bool IsPackageVersion(string value) =>
+        VersionRegex().IsMatch(value) && value.Split('.').All(p => int.Parse(p, System.Globalization.CultureInfo.InvariantCulture) <= ushort.MaxValue);
+}
@@ -0,0 +1,130 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.RegularExpressions;
+using System.Xml;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Writes the branding, channel and architecture of a build matrix variant into a package manifest
+/// </summary>
+internal static partial class AppxMatrixVariant
+{
+    public const string UapNamespace = "http://schemas.microsoft.com/appx/manifest/uap/windows10";
+
+    private const string ProtocolCategory = "windows.protocol";
+
+    private static readonly string[] Architectures = ["x86", "x64", "arm64", "neutral"];
+
+    /// <summary>
+    /// Sets the identity, display names, processor architecture and protocols of the variant. Display names that
+    /// come from resources are left alone.
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The architecture or a protocol is invalid, or the manifest has no Identity</exception>
+    public static string Apply(string manifestXml, MatrixVariant variant)
+    {
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        xmlDoc.LoadXml(manifestXml);
+        var root = xmlDoc.DocumentElement ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("m", root.NamespaceURI);
+        var branding = variant.Branding;
+        var channel = variant.Channel;
+
+        var identity = root.SelectSingleNode("m:Identity", nsManager) as XmlElement
+            ?? throw new InvalidOperationException("AppxManifest.xml has no Identity element");
+        identity.SetAttribute("Name", (branding?.IdentityName ?? identity.GetAttribute("Name")) + channel?.NameSuffix);
+        if (branding?.Publisher != null)
+        {
+            identity.SetAttribute("Publisher", branding.Publisher);
+        }
+        if (variant.Architecture is { } architecture)
+        {
+            if (!Architectures.Contains(architecture.Name, StringComparer.OrdinalIgnoreCase))
+            {
+                throw new InvalidOperationException($"winapp.yaml: matrix architecture '{architecture.Name}' must be one of {string.Join(", ", Architectures)}");
+            }
+            identity.SetAttribute("ProcessorArchitecture", architecture.Name.ToLowerInvariant());
+        }
+
+        if (root.SelectSingleNode("m:Properties/m:DisplayName", nsManager) is XmlElement displayName)
+        {
+            displayName.InnerText = DisplayName(displayName.InnerText, branding, channel);
+        }
+        if (branding?.PublisherDisplayName != null && root.SelectSingleNode("m:Properties/m:PublisherDisplayName", nsManager) is XmlElement publisherDisplayName)
+        {
+            publisherDisplayName.InnerText = branding.PublisherDisplayName;
+        }
+        foreach (var visualElements in xmlDoc.GetElementsByTagName("*").OfType<XmlElement>().Where(e => e.LocalName == "VisualElements").ToList())
+        {
+            visualElements.SetAttribute("DisplayName", DisplayName(visualElements.GetAttribute("DisplayName"), branding, channel));
+        }
+
+        if (branding is { Protocols.Count: > 0 })
+        {
+            ApplyProtocols(xmlDoc, root, nsManager, branding.Protocols);
+        }
+
+        return xmlDoc.OuterXml;
+    }
+
+    private static string DisplayName(string current, BrandingConfig? branding, ChannelConfig? channel)
+    {
+        if (current.StartsWith("ms-resource:", StringComparison.OrdinalIgnoreCase))
+        {
+            return current;
+        }
+        return (branding?.DisplayName ?? current) + channel?.DisplayNameSuffix;
+    }
+
+    // The brand's schemes replace the manifest's, so two brands installed side by side don't claim the same links
+    private static void ApplyProtocols(XmlDocument xmlDoc, XmlElement root, XmlNamespaceManager nsManager, IReadOnlyList<string> protocols)
+    {
+        var applications = root.SelectNodes("m:Applications/m:Application", nsManager)!.OfType<XmlElement>().ToList();
+        if (applications.Count == 0)
+        {
+            throw new InvalidOperationException("winapp.yaml: the branding declares protocols, but the manifest has no Application to handle them");
+        }
+
+        foreach (var extensions in applications.Select(a => a.SelectSingleNode("m:Extensions", nsManager)).OfType<XmlElement>())
+        {
+            foreach (var extension in extensions.ChildNodes.OfType<XmlElement>()
+                .Where(e => e.LocalName == "Extension" && e.NamespaceURI == UapNamespace && e.GetAttribute("Category") == ProtocolCategory)
+                .ToList())
+            {
+                extensions.RemoveChild(extension);
+            }
+        }
+
+        var prefix = root.GetPrefixOfNamespace(UapNamespace);
+        if (string.IsNullOrEmpty(prefix))
+        {
+            prefix = "uap";
+            root.SetAttribute("xmlns:uap", UapNamespace);
+        }
+        if (applications[0].SelectSingleNode("m:Extensions", nsManager) is not XmlElement target)
+        {
+            target = xmlDoc.CreateElement("Extensions", root.NamespaceURI);
+            applications[0].AppendChild(target);
+        }
+        foreach (var protocol in protocols)
+        {
+            if (!ProtocolNameRegex().IsMatch(protocol))
+            {
+                throw new InvalidOperationException($"winapp.yaml: protocol '{protocol}' must be 2 to 39 lowercase letters, digits, '.', '+' or '-', starting with a letter");
+            }
+
+            var element = xmlDoc.CreateElement(prefix, "Protocol", UapNamespace);
+            element.SetAttribute("Name", protocol);
+            var extension = xmlDoc.CreateElement(prefix, "Extension", UapNamespace);
+            extension.SetAttribute("Category", ProtocolCategory);
+            extension.AppendChild(element);
+            target.AppendChild(extension);
+        }
+    }
+
+    [GeneratedRegex(@"^[a-z][a-z0-9.+\-]{1,38}$")]
+    private static partial Regex ProtocolNameRegex();
+}
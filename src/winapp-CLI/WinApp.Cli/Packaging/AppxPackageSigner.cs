@@ -0,0 +1,186 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Embeds an AppxSignature.p7x into an MSIX/APPX package without signtool.exe
+/// </summary>
+internal static class AppxPackageSigner
+{
+    /// <summary>
+    /// Signs a package in place. Any existing signature is replaced. The digests use the hash algorithm
+    /// of the package's block map, as Windows rejects signatures whose digest algorithm differs from it.
+    /// </summary>
+    /// <returns>Hash algorithm the signature was created with</returns>
+    public static Task<HashAlgorithmName> SignAsync(
+        FileInfo packagePath,
+        X509Certificate2 certificate,
+        IEnumerable<X509Certificate2> additionalCertificates,
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider,
+        CancellationToken cancellationToken = default) =>
+        SignCoreAsync(packagePath, (digests, hashAlgorithm, ct) => AppxSignature.CreateAsync(digests, hashAlgorithm, certificate, additionalCertificates, timestampProvider, ct), cancellationToken);
+
+    /// <summary>
+    /// Signs a package in place with a key held by a signing service. The package is left untouched when the
+    /// signer throws.
+    /// </summary>
+    /// <returns>Hash algorithm the signature was created with</returns>
+    public static Task<HashAlgorithmName> SignAsync(
+        FileInfo packagePath,
+        RemoteHashSigner signer,
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider,
+        CancellationToken cancellationToken = default) =>
+        SignCoreAsync(packagePath, (digests, hashAlgorithm, ct) => AppxSignature.CreateAsync(digests, hashAlgorithm, signer, timestampProvider, ct), cancellationToken);
+
+    private static async Task<HashAlgorithmName> SignCoreAsync(
+        FileInfo packagePath,
+        Func<AppxPackageDigests, HashAlgorithmName, CancellationToken, Task<byte[]>> createSignature,
+        CancellationToken cancellationToken)
+    {
+        var tempPath = new FileInfo(packagePath.FullName + ".signing");
+        HashAlgorithmName hashAlgorithm;
+        try
+        {
+            await using (var source = packagePath.OpenRead())
+            await using (var output = new FileStream(tempPath.FullName, FileMode.Create, FileAccess.ReadWrite, FileShare.None))
+            {
+                var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(source, cancellationToken);
+                var ordered = centralDirectory.Entries.OrderBy(e => e.LocalHeaderOffset).ToList();
+
+                var blockMapEntry = ordered.FirstOrDefault(e => e.Name == AppxBlockMap.PartName)
+                    ?? throw new InvalidOperationException($"{packagePath.Name} is not an MSIX/APPX package: {AppxBlockMap.PartName} is missing");
+                var contentTypesEntry = ordered.FirstOrDefault(e => e.Name == AppxContentTypes.PartName)
+                    ?? throw new InvalidOperationException($"{packagePath.Name} is not an MSIX/APPX package: {AppxContentTypes.PartName} is missing");
+                var codeIntegrityEntry = ordered.FirstOrDefault(e => e.Name == AppxPacker.CodeIntegrityPartName);
+
+                var blockMap = await ZipPackageReader.ReadEntryAsync(source, blockMapEntry, cancellationToken);
+                hashAlgorithm = ReadBlockMapHashAlgorithm(blockMap, packagePath);
+                var contentTypes = AppxContentTypes.AddOverride(
+                    await ZipPackageReader.ReadEntryAsync(source, contentTypesEntry, cancellationToken),
+                    AppxPacker.SignaturePartName);
+                var codeIntegrity = codeIntegrityEntry != null ? await ZipPackageReader.ReadEntryAsync(source, codeIntegrityEntry, cancellationToken) : null;
+
+                // Copy every payload record verbatim; [Content_Types].xml is rewritten last and the old signature dropped
+                var writer = new ZipPackageWriter(output);
+                for (var i = 0; i < ordered.Count; i++)
+                {
+                    var entry = ordered[i];
+                    if (entry == contentTypesEntry || entry.Name == AppxPacker.SignaturePartName)
+                    {
+                        continue;
+                    }
+
+                    var recordEnd = i + 1 < ordered.Count ? ordered[i + 1].LocalHeaderOffset : centralDirectory.Offset;
+                    await writer.CopyEntryAsync(source, entry, recordEnd - entry.LocalHeaderOffset, cancellationToken);
+                }
+
+                await writer.AddEntryAsync(AppxContentTypes.PartName, contentTypes, compress: true, cancellationToken);
+
+                var signatureOffset = writer.Position;
+                var unsignedCentralDirectory = ZipFormat.EncodeCentralDirectory(writer.Entries);
+
+                var digests = new AppxPackageDigests(
+                    Axpc: await HashRangeAsync(output, 0, signatureOffset, hashAlgorithm, cancellationToken),
+                    Axcd: HashCentralDirectory([unsignedCentralDirectory], writer.Entries.Count, signatureOffset, hashAlgorithm),
+                    Axct: AppxDigestAlgorithms.Hash(hashAlgorithm, contentTypes),
+                    Axbm: AppxDigestAlgorithms.Hash(hashAlgorithm, blockMap),
+                    Axci: codeIntegrity != null ? AppxDigestAlgorithms.Hash(hashAlgorithm, codeIntegrity) : null);
+
+                var p7x = await createSignature(digests, hashAlgorithm, cancellationToken);
+
+                output.Position = signatureOffset;
+                await writer.AddEntryAsync(AppxPacker.SignaturePartName, p7x, compress: false, cancellationToken);
+                await writer.FinishAsync(cancellationToken);
+            }
+
+            File.Move(tempPath.FullName, packagePath.FullName, overwrite: true);
+            return hashAlgorithm;
+        }
+        finally
+        {
+            tempPath.Refresh();
+            if (tempPath.Exists)
+            {
+                tempPath.Delete();
+            }
+        }
+    }
+
+    /// <summary>
+    /// Reads the hash algorithm declared by a package's block map
+    /// </summary>
+    public static async Task<HashAlgorithmName> ReadHashAlgorithmAsync(FileInfo packagePath, CancellationToken cancellationToken = default)
+    {
+        await using var source = packagePath.OpenRead();
+        var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(source, cancellationToken);
+        var blockMapEntry = centralDirectory.Entries.FirstOrDefault(e => e.Name == AppxBlockMap.PartName)
+            ?? throw new InvalidOperationException($"{packagePath.Name} is not an MSIX/APPX package: {AppxBlockMap.PartName} is missing");
+        return ReadBlockMapHashAlgorithm(await ZipPackageReader.ReadEntryAsync(source, blockMapEntry, cancellationToken), packagePath);
+    }
+
+    private static HashAlgorithmName ReadBlockMapHashAlgorithm(byte[] blockMap, FileInfo packagePath)
+    {
+        string hashMethod;
+        try
+        {
+            hashMethod = AppxBlockMap.Parse(Encoding.UTF8.GetString(blockMap)).HashMethod;
+        }
+        catch (Exception ex) when (ex is XmlException or InvalidDataException or FormatException)
+        {
+            throw new InvalidOperationException($"{packagePath.Name} has a malformed {AppxBlockMap.PartName}: {ex.Message}", ex);
+        }
+
+        return AppxBlockMap.GetHashAlgorithm(hashMethod)
+            ?? throw new InvalidOperationException($"{packagePath.Name} uses an unsupported block map hash method: {hashMethod}");
+    }
+
+    /// <summary>
+    /// Computes AXCD: the central directory records of every entry but the signature, followed by the
+    /// end of central directory record as it reads when the central directory starts where the signature does
+    /// </summary>
+    internal static byte[] HashCentralDirectory(IEnumerable<ReadOnlyMemory<byte>> records, int entryCount, long signatureOffset, HashAlgorithmName hashAlgorithm)
+    {
+        using var hash = IncrementalHash.CreateHash(hashAlgorithm);
+        long size = 0;
+        foreach (var record in records)
+        {
+            hash.AppendData(record.Span);
+            size += record.Length;
+        }
+        hash.AppendData(ZipFormat.EncodeEndOfCentralDirectory(entryCount, size, signatureOffset));
+        return hash.GetHashAndReset();
+    }
+
+    internal static async Task<byte[]> HashRangeAsync(Stream stream, long offset, long length, HashAlgorithmName hashAlgorithm, CancellationToken cancellationToken)
+    {
+        using var hash = IncrementalHash.CreateHash(hashAlgorithm);
+        var buffer = ArrayPool<byte>.Shared.Rent(81920);
+        try
+        {
+            stream.Position = offset;
+            var remaining = length;
+            while (remaining > 0)
+            {
+                var read = await stream.ReadAsync(buffer.AsMemory(0, (int)Math.Min(buffer.Length, remaining)), cancellationToken);
+                if (read == 0)
+                {
+                    throw new EndOfStreamException("Unexpected end of package while computing digests");
+                }
+                hash.AppendData(buffer, 0, read);
+                remaining -= read;
+            }
+        }
+        finally
+        {
+            ArrayPool<byte>.Shared.Return(buffer);
+        }
+        return hash.GetHashAndReset();
+    }
+}
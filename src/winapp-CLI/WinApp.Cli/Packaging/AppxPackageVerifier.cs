@@ -0,0 +1,359 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Formats.Asn1;
+using System.IO.Compression;
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Packaging;
+
+internal sealed record AppxSignerDetails(
+    string Subject,
+    string Thumbprint,
+    DateTime NotBefore,
+    DateTime NotAfter,
+    DateTimeOffset? Timestamp,
+    string DigestAlgorithm);
+
+internal sealed record AppxVerificationResult(
+    int VerifiedFileCount,
+    AppxSignerDetails? Signer,
+    IReadOnlyList<string> Errors,
+    IReadOnlyList<string> Warnings)
+{
+    public bool IsValid => Errors.Count == 0;
+}
+
+/// <summary>
+/// Verifies the block map and AppxSignature.p7x of a package without WinVerifyTrust, so it runs on any OS.
+/// Certificate trust is not evaluated; only integrity and the signature value are.
+/// </summary>
+internal static class AppxPackageVerifier
+{
+    private const string Rfc3161CounterSignatureOid = "1.3.6.1.4.1.311.3.3.1";
+
+    private static readonly HashSet<string> FootprintPartNames = new(StringComparer.OrdinalIgnoreCase)
+    {
+        AppxBlockMap.PartName,
+        AppxContentTypes.PartName,
+        AppxPacker.SignaturePartName,
+    };
+
+    public static async Task<AppxVerificationResult> VerifyAsync(FileInfo packagePath, CancellationToken cancellationToken = default)
+    {
+        var errors = new List<string>();
+        var warnings = new List<string>();
+
+        await using var stream = packagePath.OpenRead();
+        var centralDirectory = await ZipPackageReader.ReadCentralDirectoryAsync(stream, cancellationToken);
+        var entries = centralDirectory.Entries;
+
+        var blockMapEntry = entries.FirstOrDefault(e => e.Name == AppxBlockMap.PartName);
+        var contentTypesEntry = entries.FirstOrDefault(e => e.Name == AppxContentTypes.PartName);
+        if (blockMapEntry == null || contentTypesEntry == null)
+        {
+            errors.Add($"{packagePath.Name} is not an MSIX/APPX package: {(blockMapEntry == null ? AppxBlockMap.PartName : AppxContentTypes.PartName)} is missing");
+            return new AppxVerificationResult(0, null, errors, warnings);
+        }
+
+        var blockMapBytes = await ZipPackageReader.ReadEntryAsync(stream, blockMapEntry, cancellationToken);
+        var (verifiedFiles, blockMapHashAlgorithm) = await VerifyBlockMapAsync(stream, packagePath, centralDirectory, blockMapBytes, errors, cancellationToken);
+
+        AppxSignerDetails? signer = null;
+        var signatureEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.SignaturePartName);
+        if (signatureEntry == null)
+        {
+            warnings.Add("Package is not signed");
+        }
+        else
+        {
+            signer = await VerifySignatureAsync(stream, centralDirectory, signatureEntry, contentTypesEntry, blockMapBytes, blockMapHashAlgorithm, errors, warnings, cancellationToken);
+        }
+
+        return new AppxVerificationResult(verifiedFiles, signer, errors, warnings);
+    }
+
+    private static async Task<(int VerifiedFiles, HashAlgorithmName? HashAlgorithm)> VerifyBlockMapAsync(
+        Stream stream,
+        FileInfo packagePath,
+        ZipCentralDirectory centralDirectory,
+        byte[] blockMapBytes,
+        List<string> errors,
+        CancellationToken cancellationToken)
+    {
+        AppxBlockMapDocument blockMap;
+        try
+        {
+            blockMap = AppxBlockMap.Parse(Encoding.UTF8.GetString(blockMapBytes));
+        }
+        catch (Exception ex) when (ex is XmlException or InvalidDataException or FormatException)
+        {
+            errors.Add($"{AppxBlockMap.PartName} is malformed: {ex.Message}");
+            return (0, null);
+        }
+
+        if (AppxBlockMap.GetHashAlgorithm(blockMap.HashMethod) is not HashAlgorithmName hashAlgorithm)
+        {
+            errors.Add($"Unsupported block map hash method: {blockMap.HashMethod}");
+            return (0, null);
+        }
+
+        var entriesByName = centralDirectory.Entries
+            .Where(e => !FootprintPartNames.Contains(e.Name))
+            .ToDictionary(e => AppxBlockMap.ToBlockMapName(e.Name), StringComparer.OrdinalIgnoreCase);
+        var covered = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+
+        // ZipArchive does the decompression; a separate handle keeps its seeks away from the record reads
+        await using var archiveStream = packagePath.OpenRead();
+        using var archive = new ZipArchive(archiveStream, ZipArchiveMode.Read);
+
+        var buffer = new byte[AppxBlockMap.BlockSize];
+        var verified = 0;
+        foreach (var file in blockMap.Files)
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+
+            if (!entriesByName.TryGetValue(file.Name, out var entry))
+            {
+                errors.Add($"'{file.Name}' is listed in the block map but missing from the package");
+                continue;
+            }
+            covered.Add(file.Name);
+
+            if (entry.UncompressedSize != file.Size)
+            {
+                errors.Add($"'{file.Name}' is {entry.UncompressedSize} bytes but the block map declares {file.Size}");
+                continue;
+            }
+
+            var lfhSize = await ZipPackageReader.ReadLocalHeaderSizeAsync(stream, entry, cancellationToken);
+            if (lfhSize != file.LfhSize)
+            {
+                errors.Add($"'{file.Name}' local file header is {lfhSize} bytes but the block map declares {file.LfhSize}");
+            }
+
+            var expectedBlocks = (int)((file.Size + AppxBlockMap.BlockSize - 1) / AppxBlockMap.BlockSize);
+            if (file.Blocks.Count != expectedBlocks)
+            {
+                errors.Add($"'{file.Name}' should have {expectedBlocks} blocks but the block map lists {file.Blocks.Count}");
+                continue;
+            }
+
+            if (file.Blocks.All(b => b.CompressedSize != null) && file.Blocks.Sum(b => b.CompressedSize!.Value) != entry.CompressedSize)
+            {
+                errors.Add($"'{file.Name}' compressed size does not match the block map");
+            }
+
+            var archiveEntry = archive.GetEntry(entry.Name);
+            if (archiveEntry == null)
+            {
+                errors.Add($"'{file.Name}' cannot be read from the package");
+                continue;
+            }
+
+            await using var content = await archiveEntry.OpenAsync(cancellationToken);
+            var intact = true;
+            for (var i = 0; i < file.Blocks.Count && intact; i++)
+            {
+                var read = await content.ReadAtLeastAsync(buffer, buffer.Length, throwOnEndOfStream: false, cancellationToken);
+                if (!AppxDigestAlgorithms.Hash(hashAlgorithm, buffer.AsSpan(0, read)).AsSpan().SequenceEqual(file.Blocks[i].Hash))
+                {
+                    errors.Add($"Block {i} of '{file.Name}' does not match the block map; the file was modified after packing");
+                    intact = false;
+                }
+            }
+
+            if (intact)
+            {
+                verified++;
+            }
+        }
+
+        foreach (var name in entriesByName.Keys.Where(n => !covered.Contains(n)))
+        {
+            errors.Add($"'{name}' is not covered by the block map");
+        }
+
+        return (verified, hashAlgorithm);
+    }
+
+    private static async Task<AppxSignerDetails?> VerifySignatureAsync(
+        Stream stream,
+        ZipCentralDirectory centralDirectory,
+        ZipEntryRecord signatureEntry,
+        ZipEntryRecord contentTypesEntry,
+        byte[] blockMapBytes,
+        HashAlgorithmName? blockMapHashAlgorithm,
+        List<string> errors,
+        List<string> warnings,
+        CancellationToken cancellationToken)
+    {
+        var p7x = await ZipPackageReader.ReadEntryAsync(stream, signatureEntry, cancellationToken);
+
+        AppxSignatureContent content;
+        var signedCms = new SignedCms();
+        try
+        {
+            content = AppxSignature.DecodeContent(p7x);
+            signedCms.Decode(p7x.AsSpan(AppxSignature.P7xMagic.Length));
+        }
+        catch (Exception ex) when (ex is InvalidDataException or AsnContentException or CryptographicException or NotSupportedException)
+        {
+            errors.Add($"{AppxPacker.SignaturePartName} is malformed: {ex.Message}");
+            return null;
+        }
+
+        if (content.SipGuid != AppxSignature.AppxSipGuid)
+        {
+            errors.Add($"Signature was not produced for an APPX/MSIX package (SIP {content.SipGuid})");
+        }
+
+        var hashAlgorithm = content.DigestAlgorithm;
+        if (blockMapHashAlgorithm != null && blockMapHashAlgorithm != hashAlgorithm)
+        {
+            errors.Add($"Signature digest algorithm {hashAlgorithm.Name} does not match the block map hash algorithm {blockMapHashAlgorithm.Value.Name}; Windows rejects such packages");
+        }
+        var recordsWithoutSignature = centralDirectory.Entries
+            .Select((entry, index) => (entry, record: centralDirectory.RawRecords[index]))
+            .Where(x => x.entry != signatureEntry)
+            .Select(x => x.record)
+            .ToList();
+
+        var entries = centralDirectory.Entries;
+        var codeIntegrityEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.CodeIntegrityPartName);
+
+        var actual = new AppxPackageDigests(
+            Axpc: await AppxPackageSigner.HashRangeAsync(stream, 0, signatureEntry.LocalHeaderOffset, hashAlgorithm, cancellationToken),
+            Axcd: AppxPackageSigner.HashCentralDirectory(recordsWithoutSignature, recordsWithoutSignature.Count, signatureEntry.LocalHeaderOffset, hashAlgorithm),
+            Axct: Hash(hashAlgorithm, await ZipPackageReader.ReadEntryAsync(stream, contentTypesEntry, cancellationToken)),
+            Axbm: Hash(hashAlgorithm, blockMapBytes),
+            Axci: codeIntegrityEntry != null ? Hash(hashAlgorithm, await ZipPackageReader.ReadEntryAsync(stream, codeIntegrityEntry, cancellationToken)) : null);
+
+        CompareDigest("AXPC", "package file records", content.Digests.Axpc, actual.Axpc, errors);
+        CompareDigest("AXCD", "central directory", content.Digests.Axcd, actual.Axcd, errors);
+        CompareDigest("AXCT", AppxContentTypes.PartName, content.Digests.Axct, actual.Axct, errors);
+        CompareDigest("AXBM", AppxBlockMap.PartName, content.Digests.Axbm, actual.Axbm, errors);
+        if (content.Digests.Axci != null || actual.Axci != null)
+        {
+            CompareDigest("AXCI", AppxPacker.CodeIntegrityPartName, content.Digests.Axci, actual.Axci, errors);
+        }
+
+        if (signedCms.SignerInfos.Count == 0)
+        {
+            errors.Add("Signature has no signer");
+            return null;
+        }
+
+        var signerInfo = signedCms.SignerInfos[0];
+        try
+        {
+            signerInfo.CheckSignature(verifySignatureOnly: true);
+        }
+        catch (CryptographicException ex)
+        {
+            errors.Add($"Signature value is invalid: {ex.Message}");
+        }
+
+        var certificate = signerInfo.Certificate;
+        if (certificate == null)
+        {
+            errors.Add("Signing certificate is not embedded in the signature");
+            return null;
+        }
+
+        var timestamp = GetTimestamp(signerInfo);
+        if (timestamp == null && certificate.NotAfter < DateTime.Now)
+        {
+            warnings.Add($"Signing certificate expired on {certificate.NotAfter:d} and the signature is not timestamped");
+        }
+
+        var publisher = await ReadManifestPublisherAsync(stream, entries, cancellationToken);
+        if (publisher != null && !PublisherMatches(publisher, certificate))
+        {
+            errors.Add($"Manifest publisher '{publisher}' does not match the signing certificate subject '{certificate.Subject}'");
+        }
+
+        return new AppxSignerDetails(
+            certificate.Subject,
+            certificate.Thumbprint,
+            certificate.NotBefore,
+            certificate.NotAfter,
+            timestamp,
+            hashAlgorithm.Name ?? "unknown");
+    }
+
+    private static byte[] Hash(HashAlgorithmName algorithm, byte[] data)
+    {
+        using var hash = IncrementalHash.CreateHash(algorithm);
+        hash.AppendData(data);
+        return hash.GetHashAndReset();
+    }
+
+    private static void CompareDigest(string tag, string description, byte[]? expected, byte[]? actual, List<string> errors)
+    {
+        if (expected == null || actual == null || !expected.AsSpan().SequenceEqual(actual))
+        {
+            errors.Add($"{tag} digest mismatch: {description} changed after signing");
+        }
+    }
+
+    private static DateTimeOffset? GetTimestamp(SignerInfo signerInfo)
+    {
+        foreach (var attribute in signerInfo.UnsignedAttributes)
+        {
+            if (attribute.Oid.Value != Rfc3161CounterSignatureOid)
+            {
+                continue;
+            }
+
+            foreach (var value in attribute.Values)
+            {
+                if (Rfc3161TimestampToken.TryDecode(value.RawData, out var token, out _))
+                {
+                    return token.TokenInfo.Timestamp;
+                }
+            }
+        }
+
+        return null;
+    }
+
+    private static async Task<string?> ReadManifestPublisherAsync(Stream stream, IReadOnlyList<ZipEntryRecord> entries, CancellationToken cancellationToken)
+    {
+        var manifestEntry = entries.FirstOrDefault(e => e.Name == AppxPacker.ManifestPartName)
+            ?? entries.FirstOrDefault(e => e.Name == "AppxMetadata/AppxBundleManifest.xml");
+        if (manifestEntry == null)
+        {
+            return null;
+        }
+
+        try
+        {
+            var xmlDoc = new XmlDocument();
+            xmlDoc.LoadXml(Encoding.UTF8.GetString(await ZipPackageReader.ReadEntryAsync(stream, manifestEntry, cancellationToken)));
+            return (xmlDoc.SelectSingleNode("/*[local-name()='Package' or local-name()='Bundle']/*[local-name()='Identity']") as XmlElement)?.GetAttribute("Publisher");
+        }
+        catch (XmlException)
+        {
+            return null;
+        }
+    }
+
+    internal static bool PublisherMatches(string publisher, X509Certificate2 certificate)
+    {
+        try
+        {
+            var expected = new X500DistinguishedName(publisher).Decode(X500DistinguishedNameFlags.UseCommas);
+            var actual = certificate.SubjectName.Decode(X500DistinguishedNameFlags.UseCommas);
+            return string.Equals(expected, actual, StringComparison.OrdinalIgnoreCase);
+        }
+        catch (CryptographicException)
+        {
+            return string.Equals(publisher, certificate.Subject, StringComparison.OrdinalIgnoreCase);
+        }
+    }
+}
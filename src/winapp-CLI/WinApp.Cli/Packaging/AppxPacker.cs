@@ -0,0 +1,195 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using System.Globalization;
+using System.Security.Cryptography;
+using System.Text;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Options for packing a folder with the native packaging engine
+/// </summary>
+/// <param name="Threads">Hash/compress workers; 0 uses one per logical processor</param>
+/// <param name="BlockCache">Compressed blocks shared with other packs, so identical content is compressed once</param>
+internal sealed record AppxPackOptions(int Threads = 0, AppxBlockCache? BlockCache = null)
+{
+    /// <summary>
+    /// Block map hash algorithm, which the signature digests will use as well
+    /// </summary>
+    public HashAlgorithmName HashAlgorithm { get; init; } = HashAlgorithmName.SHA256;
+}
+
+/// <summary>
+/// Result of packing a folder with the native packaging engine
+/// </summary>
+/// <param name="ReusedBlocks">Payload blocks taken from the block cache instead of being compressed</param>
+/// <param name="ReusedSize">Uncompressed size of the reused blocks</param>
+internal sealed record AppxPackResult(
+    FileInfo PackagePath,
+    int PayloadFileCount,
+    long PayloadSize,
+    int Threads,
+    TimeSpan Elapsed,
+    long PayloadBlocks = 0,
+    long ReusedBlocks = 0,
+    long ReusedSize = 0)
+{
+    /// <summary>
+    /// Uncompressed payload bytes packed per second
+    /// </summary>
+    public double Throughput => Elapsed > TimeSpan.Zero ? PayloadSize / Elapsed.TotalSeconds : 0;
+}
+
+/// <summary>
+/// Creates an unsigned MSIX package from a package layout folder without makeappx.exe
+/// </summary>
+internal static class AppxPacker
+{
+    public const string ManifestPartName = "AppxManifest.xml";
+    public const string SignaturePartName = "AppxSignature.p7x";
+    public const string CodeIntegrityPartName = "AppxMetadata/CodeIntegrity.cat";
+
+    // Pack-time generated parts; copies of these in the layout folder are ignored
+    private static readonly HashSet<string> FootprintPartNames = new(StringComparer.OrdinalIgnoreCase)
+    {
+        AppxBlockMap.PartName,
+        AppxContentTypes.PartName,
+        SignaturePartName,
+    };
+
+    public static Task<AppxPackResult> PackAsync(DirectoryInfo inputFolder, FileInfo outputPath, CancellationToken cancellationToken = default) =>
+        PackAsync(inputFolder, outputPath, new AppxPackOptions(), cancellationToken);
+
+    public static async Task<AppxPackResult> PackAsync(DirectoryInfo inputFolder, FileInfo outputPath, AppxPackOptions options, CancellationToken cancellationToken = default)
+    {
+        if (!AppxDigestAlgorithms.IsSupported(options.HashAlgorithm))
+        {
+            throw new NotSupportedException($"Unsupported block map hash algorithm {options.HashAlgorithm.Name}");
+        }
+
+        var stopwatch = Stopwatch.StartNew();
+        var payload = GetPayloadFiles(inputFolder);
+        if (!payload.Any(p => p.ZipName == ManifestPartName))
+        {
+            throw new FileNotFoundException($"No AppxManifest.xml found in {inputFolder.FullName}");
+        }
+
+        outputPath.Directory?.Create();
+
+        var pipeline = new AppxBlockPipeline(options.Threads, compress: true, options.BlockCache, options.HashAlgorithm);
+        var blockMapFiles = new List<AppxBlockMapFile>(payload.Count);
+        long payloadSize = 0;
+        try
+        {
+            await using (var output = new FileStream(outputPath.FullName, FileMode.Create, FileAccess.ReadWrite, FileShare.None))
+            {
+                var writer = new ZipPackageWriter(output, options.HashAlgorithm);
+                await pipeline.RunAsync(payload, async (file, blocks) =>
+                {
+                    var blockMapFile = await writer.AddEntryAsync(file.ZipName, file.Size, compressed: true, file.LastWriteTime, blocks, cancellationToken);
+                    blockMapFiles.Add(blockMapFile);
+                    payloadSize += blockMapFile.Size;
+                }, cancellationToken);
+
+                await writer.AddEntryAsync(AppxBlockMap.PartName, AppxBlockMap.Serialize(blockMapFiles, options.HashAlgorithm), compress: true, cancellationToken);
+
+                var partNames = payload.Select(p => p.ZipName).Append(AppxBlockMap.PartName);
+                await writer.AddEntryAsync(AppxContentTypes.PartName, AppxContentTypes.Serialize(partNames), compress: true, cancellationToken);
+
+                await writer.FinishAsync(cancellationToken);
+            }
+        }
+        catch
+        {
+            TryDelete(outputPath);
+            throw;
+        }
+
+        outputPath.Refresh();
+        return new AppxPackResult(
+            outputPath,
+            payload.Count,
+            payloadSize,
+            pipeline.Threads,
+            stopwatch.Elapsed,
+            pipeline.ProcessedBlocks,
+            pipeline.ReusedBlocks,
+            pipeline.ReusedBytes);
+    }
+
+    /// <summary>
+    /// Enumerates the layout folder in a stable order, mapping each file to its zip item name.
+    /// The manifest is always written last so that readers streaming the package see the payload first.
+    /// </summary>
+    internal static List<(FileInfo File, string ZipName)> GetPayloadFiles(DirectoryInfo inputFolder)
+    {
+        var files = new List<(FileInfo File, string ZipName)>();
+        (FileInfo File, string ZipName)? manifest = null;
+
+        foreach (var file in inputFolder.EnumerateFiles("*", SearchOption.AllDirectories).OrderBy(f => f.FullName, StringComparer.OrdinalIgnoreCase))
+        {
+            var relativePath = Path.GetRelativePath(inputFolder.FullName, file.FullName).Replace('\\', '/');
+            if (FootprintPartNames.Contains(relativePath))
+            {
+                continue;
+            }
+
+            if (string.Equals(relativePath, ManifestPartName, StringComparison.OrdinalIgnoreCase))
+            {
+                manifest = (file, ManifestPartName);
+                continue;
+            }
+
+            files.Add((file, ToZipName(relativePath)));
+        }
+
+        if (manifest != null)
+        {
+            files.Add(manifest.Value);
+        }
+
+        return files;
+    }
+
+    /// <summary>
+    /// Percent-encodes a relative path into an OPC part name (RFC 3986 pchar set, '/' separated)
+    /// </summary>
+    internal static string ToZipName(string relativePath)
+    {
+        var builder = new StringBuilder(relativePath.Length);
+        Span<byte> utf8 = stackalloc byte[4];
+        foreach (var rune in relativePath.EnumerateRunes())
+        {
+            if (rune.IsAscii && (char.IsAsciiLetterOrDigit((char)rune.Value) || "-._~!$&'()*+,;=:@/[]".Contains((char)rune.Value)))
+            {
+                builder.Append((char)rune.Value);
+                continue;
+            }
+
+            var length = rune.EncodeToUtf8(utf8);
+            foreach (var b in utf8[..length])
+            {
+                builder.Append('%').Append(b.ToString("X2", CultureInfo.InvariantCulture));
+            }
+        }
+        return builder.ToString();
+    }
+
+    private static void TryDelete(FileInfo path)
+    {
+        try
+        {
+            path.Refresh();
+            if (path.Exists)
+            {
+                path.Delete();
+            }
+        }
+        catch
+        {
+            // Ignore cleanup failures
+        }
+    }
+}
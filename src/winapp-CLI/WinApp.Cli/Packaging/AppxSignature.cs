@@ -0,0 +1,432 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Formats.Asn1;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The package digests an APPX/MSIX signature covers
+/// </summary>
+/// <param name="Axpc">Hash of all local file records preceding AppxSignature.p7x</param>
+/// <param name="Axcd">Hash of the central directory (and end records) without the signature entry</param>
+/// <param name="Axct">Hash of the uncompressed [Content_Types].xml</param>
+/// <param name="Axbm">Hash of the uncompressed AppxBlockMap.xml</param>
+/// <param name="Axci">Hash of the uncompressed AppxMetadata/CodeIntegrity.cat, when present</param>
+internal sealed record AppxPackageDigests(byte[] Axpc, byte[] Axcd, byte[] Axct, byte[] Axbm, byte[]? Axci)
+{
+    /// <summary>
+    /// Encodes the digests in the layout the APPX SIP expects: "APPX" followed by tagged hashes
+    /// </summary>
+    public byte[] Encode()
+    {
+        using var stream = new MemoryStream();
+        stream.Write("APPX"u8);
+        Write(stream, "AXPC"u8, Axpc);
+        Write(stream, "AXCD"u8, Axcd);
+        Write(stream, "AXCT"u8, Axct);
+        Write(stream, "AXBM"u8, Axbm);
+        if (Axci != null)
+        {
+            Write(stream, "AXCI"u8, Axci);
+        }
+        return stream.ToArray();
+
+        static void Write(Stream stream, ReadOnlySpan<byte> tag, byte[] hash)
+        {
+            stream.Write(tag);
+            stream.Write(hash);
+        }
+    }
+
+    public static AppxPackageDigests Decode(ReadOnlySpan<byte> blob, int hashLength)
+    {
+        if (blob.Length < 4 || !blob[..4].SequenceEqual("APPX"u8))
+        {
+            throw new InvalidDataException("Signature digest does not start with the APPX marker");
+        }
+
+        var digests = new Dictionary<string, byte[]>(StringComparer.Ordinal);
+        for (var position = 4; position < blob.Length; position += 4 + hashLength)
+        {
+            if (position + 4 + hashLength > blob.Length)
+            {
+                throw new InvalidDataException("Signature digest is truncated");
+            }
+            digests[Encoding.ASCII.GetString(blob.Slice(position, 4))] = blob.Slice(position + 4, hashLength).ToArray();
+        }
+
+        return new AppxPackageDigests(
+            Get("AXPC"),
+            Get("AXCD"),
+            Get("AXCT"),
+            Get("AXBM"),
+            digests.GetValueOrDefault("AXCI"));
+
+        byte[] Get(string tag) => digests.TryGetValue(tag, out var hash)
+            ? hash
+            : throw new InvalidDataException($"Signature digest does not contain {tag}");
+    }
+}
+
+/// <summary>
+/// The SIP information and digests carried by a decoded AppxSignature.p7x
+/// </summary>
+internal sealed record AppxSignatureContent(Guid SipGuid, HashAlgorithmName DigestAlgorithm, AppxPackageDigests Digests);
+
+/// <summary>
+/// Builds AppxSignature.p7x: the "PKCX" magic followed by an Authenticode PKCS#7 SignedData whose
+/// SpcIndirectDataContent carries the APPX SIP identifier and the package digests.
+/// </summary>
+internal static class AppxSignature
+{
+    public static ReadOnlySpan<byte> P7xMagic => "PKCX"u8;
+
+    /// <summary>
+    /// Subject interface package GUID registered for APPX/MSIX packages
+    /// </summary>
+    public static readonly Guid AppxSipGuid = new("0AC5DF4B-CE07-4DE2-B76E-23C839A09FD1");
+
+    private const int SipVersion = 0x01010000;
+
+    private const string SignedDataOid = "1.2.840.113549.1.7.2";
+    private const string SpcIndirectDataOid = "1.3.6.1.4.1.311.2.1.4";
+    private const string SpcSipInfoOid = "1.3.6.1.4.1.311.2.1.30";
+    private const string SpcSpOpusInfoOid = "1.3.6.1.4.1.311.2.1.12";
+    private const string SpcStatementTypeOid = "1.3.6.1.4.1.311.2.1.11";
+    private const string SpcIndividualCodeSigningOid = "1.3.6.1.4.1.311.2.1.21";
+    private const string ContentTypeOid = "1.2.840.113549.1.9.3";
+    private const string MessageDigestOid = "1.2.840.113549.1.9.4";
+    private const string Rfc3161CounterSignatureOid = "1.3.6.1.4.1.311.3.3.1";
+    private const string Sha256Oid = "2.16.840.1.101.3.4.2.1";
+    private const string Sha384Oid = "2.16.840.1.101.3.4.2.2";
+    private const string Sha512Oid = "2.16.840.1.101.3.4.2.3";
+    private const string RsaEncryptionOid = "1.2.840.113549.1.1.1";
+    private const string EcdsaWithSha256Oid = "1.2.840.10045.4.3.2";
+    private const string EcdsaWithSha384Oid = "1.2.840.10045.4.3.3";
+    private const string EcdsaWithSha512Oid = "1.2.840.10045.4.3.4";
+
+    /// <summary>
+    /// Creates the contents of AppxSignature.p7x
+    /// </summary>
+    /// <param name="digests">Package digests to sign</param>
+    /// <param name="hashAlgorithm">Algorithm the digests were computed with; also used for the message digest and signature</param>
+    /// <param name="certificate">Signing certificate with private key</param>
+    /// <param name="additionalCertificates">Chain certificates to embed alongside the signer</param>
+    /// <param name="timestampProvider">Optional callback returning an encoded RFC 3161 timestamp token for the signature value, hashed with the given algorithm</param>
+    /// <param name="cancellationToken">Cancellation token</param>
+    public static Task<byte[]> CreateAsync(
+        AppxPackageDigests digests,
+        HashAlgorithmName hashAlgorithm,
+        X509Certificate2 certificate,
+        IEnumerable<X509Certificate2> additionalCertificates,
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider,
+        CancellationToken cancellationToken = default) =>
+        EncodeAsync(digests, hashAlgorithm, (signedAttributes, _) =>
+        {
+            var (algorithmOid, signature) = Sign(certificate, signedAttributes, hashAlgorithm);
+            return Task.FromResult(new SignerResult(algorithmOid, signature, certificate, additionalCertificates));
+        }, timestampProvider, cancellationToken);
+
+    /// <summary>
+    /// Creates the contents of AppxSignature.p7x with a key held by a signing service. The certificate comes back
+    /// with the signature, so a service that renews it between calls still produces a consistent signature.
+    /// </summary>
+    /// <param name="signer">Signs the hash of the signed attributes with RSA PKCS#1 v1.5</param>
+    public static Task<byte[]> CreateAsync(
+        AppxPackageDigests digests,
+        HashAlgorithmName hashAlgorithm,
+        RemoteHashSigner signer,
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider,
+        CancellationToken cancellationToken = default) =>
+        EncodeAsync(digests, hashAlgorithm, async (signedAttributes, ct) =>
+        {
+            var remote = await signer(AppxDigestAlgorithms.Hash(hashAlgorithm, signedAttributes), hashAlgorithm, ct);
+            return new SignerResult(RsaEncryptionOid, remote.Signature, remote.Certificate, remote.Chain);
+        }, timestampProvider, cancellationToken);
+
+    private sealed record SignerResult(string AlgorithmOid, byte[] Signature, X509Certificate2 Certificate, IEnumerable<X509Certificate2> AdditionalCertificates);
+
+    private static async Task<byte[]> EncodeAsync(
+        AppxPackageDigests digests,
+        HashAlgorithmName hashAlgorithm,
+        Func<byte[], CancellationToken, Task<SignerResult>> sign,
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider,
+        CancellationToken cancellationToken)
+    {
+        var hashOid = GetHashOid(hashAlgorithm);
+        var indirectData = EncodeIndirectData(digests, hashAlgorithm);
+
+        // Authenticode hashes the SpcIndirectDataContent value without its outer tag and length
+        AsnDecoder.ReadSequence(indirectData, AsnEncodingRules.DER, out var contentOffset, out var contentLength, out _);
+        var messageDigest = AppxDigestAlgorithms.Hash(hashAlgorithm, indirectData.AsSpan(contentOffset, contentLength));
+
+        var signedAttributes = EncodeSignedAttributes(messageDigest, tag: null);
+        var (signatureAlgorithmOid, signatureValue, certificate, additionalCertificates) = await sign(signedAttributes, cancellationToken);
+
+        byte[]? timestampToken = null;
+        if (timestampProvider != null)
+        {
+            timestampToken = await timestampProvider(signatureValue, hashAlgorithm, cancellationToken);
+        }
+
+        var writer = new AsnWriter(AsnEncodingRules.DER);
+        using (writer.PushSequence())
+        {
+            writer.WriteObjectIdentifier(SignedDataOid);
+            using (writer.PushSequence(new Asn1Tag(TagClass.ContextSpecific, 0)))
+            using (writer.PushSequence())
+            {
+                writer.WriteInteger(1);
+
+                using (writer.PushSetOf())
+                {
+                    WriteAlgorithmIdentifier(writer, hashOid);
+                }
+
+                using (writer.PushSequence())
+                {
+                    writer.WriteObjectIdentifier(SpcIndirectDataOid);
+                    using (writer.PushSequence(new Asn1Tag(TagClass.ContextSpecific, 0)))
+                    {
+                        writer.WriteEncodedValue(indirectData);
+                    }
+                }
+
+                using (writer.PushSetOf(new Asn1Tag(TagClass.ContextSpecific, 0)))
+                {
+                    writer.WriteEncodedValue(certificate.RawData);
+                    foreach (var additional in additionalCertificates)
+                    {
+                        writer.WriteEncodedValue(additional.RawData);
+                    }
+                }
+
+                using (writer.PushSetOf())
+                using (writer.PushSequence())
+                {
+                    writer.WriteInteger(1);
+
+                    using (writer.PushSequence())
+                    {
+                        writer.WriteEncodedValue(certificate.IssuerName.RawData);
+                        writer.WriteInteger(certificate.SerialNumberBytes.Span);
+                    }
+
+                    WriteAlgorithmIdentifier(writer, hashOid);
+                    writer.WriteEncodedValue(EncodeSignedAttributes(messageDigest, new Asn1Tag(TagClass.ContextSpecific, 0, isConstructed: true)));
+                    WriteAlgorithmIdentifier(writer, signatureAlgorithmOid, includeNullParameters: signatureAlgorithmOid == RsaEncryptionOid);
+                    writer.WriteOctetString(signatureValue);
+
+                    if (timestampToken != null)
+                    {
+                        using (writer.PushSetOf(new Asn1Tag(TagClass.ContextSpecific, 1)))
+                        using (writer.PushSequence())
+                        {
+                            writer.WriteObjectIdentifier(Rfc3161CounterSignatureOid);
+                            using (writer.PushSetOf())
+                            {
+                                writer.WriteEncodedValue(timestampToken);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        var signedData = writer.Encode();
+        var p7x = new byte[P7xMagic.Length + signedData.Length];
+        P7xMagic.CopyTo(p7x);
+        signedData.CopyTo(p7x.AsSpan(P7xMagic.Length));
+        return p7x;
+    }
+
+    /// <summary>
+    /// Extracts the SpcIndirectDataContent of a p7x file (including the "PKCX" magic)
+    /// </summary>
+    public static AppxSignatureContent DecodeContent(ReadOnlySpan<byte> p7x)
+    {
+        if (p7x.Length < P7xMagic.Length || !p7x[..P7xMagic.Length].SequenceEqual(P7xMagic))
+        {
+            throw new InvalidDataException("AppxSignature.p7x does not start with the PKCX header");
+        }
+
+        var contentInfo = new AsnReader(p7x[P7xMagic.Length..].ToArray(), AsnEncodingRules.BER).ReadSequence();
+        if (contentInfo.ReadObjectIdentifier() != SignedDataOid)
+        {
+            throw new InvalidDataException("AppxSignature.p7x is not a PKCS#7 SignedData structure");
+        }
+
+        var signedData = contentInfo.ReadSequence(new Asn1Tag(TagClass.ContextSpecific, 0)).ReadSequence();
+        signedData.ReadInteger(); // version
+        signedData.ReadSetOf(); // digestAlgorithms
+
+        var encapsulatedContent = signedData.ReadSequence();
+        if (encapsulatedContent.ReadObjectIdentifier() != SpcIndirectDataOid)
+        {
+            throw new InvalidDataException("AppxSignature.p7x does not contain SpcIndirectDataContent");
+        }
+
+        var indirectData = encapsulatedContent.ReadSequence(new Asn1Tag(TagClass.ContextSpecific, 0)).ReadSequence();
+
+        var attribute = indirectData.ReadSequence();
+        if (attribute.ReadObjectIdentifier() != SpcSipInfoOid)
+        {
+            throw new InvalidDataException("SpcIndirectDataContent does not carry SIP information");
+        }
+        var sipInfo = attribute.ReadSequence();
+        sipInfo.ReadInteger(); // version
+        var sipGuid = new Guid(sipInfo.ReadOctetString());
+
+        var digestInfo = indirectData.ReadSequence();
+        var algorithm = GetHashAlgorithm(digestInfo.ReadSequence().ReadObjectIdentifier());
+        var digests = AppxPackageDigests.Decode(digestInfo.ReadOctetString(), GetHashLength(algorithm));
+
+        return new AppxSignatureContent(sipGuid, algorithm, digests);
+    }
+
+    internal static HashAlgorithmName GetHashAlgorithm(string oid) => oid switch
+    {
+        Sha256Oid => HashAlgorithmName.SHA256,
+        Sha384Oid => HashAlgorithmName.SHA384,
+        Sha512Oid => HashAlgorithmName.SHA512,
+        _ => throw new NotSupportedException($"Unsupported signature digest algorithm {oid}")
+    };
+
+    internal static string GetHashOid(HashAlgorithmName algorithm)
+    {
+        if (algorithm == HashAlgorithmName.SHA256)
+        {
+            return Sha256Oid;
+        }
+        if (algorithm == HashAlgorithmName.SHA384)
+        {
+            return Sha384Oid;
+        }
+        if (algorithm == HashAlgorithmName.SHA512)
+        {
+            return Sha512Oid;
+        }
+
+        throw new NotSupportedException($"Unsupported signature digest algorithm {algorithm.Name}");
+    }
+
+    internal static int GetHashLength(HashAlgorithmName algorithm) =>
+        algorithm == HashAlgorithmName.SHA512 ? 64 : algorithm == HashAlgorithmName.SHA384 ? 48 : 32;
+
+    internal static byte[] EncodeIndirectData(AppxPackageDigests digests, HashAlgorithmName hashAlgorithm)
+    {
+        var writer = new AsnWriter(AsnEncodingRules.DER);
+        using (writer.PushSequence())
+        {
+            using (writer.PushSequence())
+            {
+                writer.WriteObjectIdentifier(SpcSipInfoOid);
+                using (writer.PushSequence())
+                {
+                    writer.WriteInteger(SipVersion);
+                    writer.WriteOctetString(AppxSipGuid.ToByteArray());
+                    for (var i = 0; i < 5; i++)
+                    {
+                        writer.WriteInteger(0);
+                    }
+                }
+            }
+
+            using (writer.PushSequence())
+            {
+                WriteAlgorithmIdentifier(writer, GetHashOid(hashAlgorithm));
+                writer.WriteOctetString(digests.Encode());
+            }
+        }
+        return writer.Encode();
+    }
+
+    /// <summary>
+    /// Encodes the authenticated attributes. The signature is computed over the universal SET encoding,
+    /// while the SignerInfo embeds the same content under an implicit [0] tag.
+    /// </summary>
+    private static byte[] EncodeSignedAttributes(byte[] messageDigest, Asn1Tag? tag)
+    {
+        var writer = new AsnWriter(AsnEncodingRules.DER);
+        using (writer.PushSetOf(tag))
+        {
+            using (writer.PushSequence())
+            {
+                writer.WriteObjectIdentifier(ContentTypeOid);
+                using (writer.PushSetOf())
+                {
+                    writer.WriteObjectIdentifier(SpcIndirectDataOid);
+                }
+            }
+
+            using (writer.PushSequence())
+            {
+                writer.WriteObjectIdentifier(SpcSpOpusInfoOid);
+                using (writer.PushSetOf())
+                using (writer.PushSequence())
+                {
+                }
+            }
+
+            using (writer.PushSequence())
+            {
+                writer.WriteObjectIdentifier(SpcStatementTypeOid);
+                using (writer.PushSetOf())
+                using (writer.PushSequence())
+                {
+                    writer.WriteObjectIdentifier(SpcIndividualCodeSigningOid);
+                }
+            }
+
+            using (writer.PushSequence())
+            {
+                writer.WriteObjectIdentifier(MessageDigestOid);
+                using (writer.PushSetOf())
+                {
+                    writer.WriteOctetString(messageDigest);
+                }
+            }
+        }
+        return writer.Encode();
+    }
+
+    private static (string AlgorithmOid, byte[] Signature) Sign(X509Certificate2 certificate, byte[] signedAttributes, HashAlgorithmName hashAlgorithm)
+    {
+        using (var rsa = certificate.GetRSAPrivateKey())
+        {
+            if (rsa != null)
+            {
+                return (RsaEncryptionOid, rsa.SignData(signedAttributes, hashAlgorithm, RSASignaturePadding.Pkcs1));
+            }
+        }
+
+        using (var ecdsa = certificate.GetECDsaPrivateKey())
+        {
+            if (ecdsa != null)
+            {
+                var oid = hashAlgorithm == HashAlgorithmName.SHA512 ? EcdsaWithSha512Oid
+                    : hashAlgorithm == HashAlgorithmName.SHA384 ? EcdsaWithSha384Oid
+                    : EcdsaWithSha256Oid;
+                return (oid, ecdsa.SignData(signedAttributes, hashAlgorithm, DSASignatureFormat.Rfc3279DerSequence));
+            }
+        }
+
+        throw new InvalidOperationException($"Certificate '{certificate.Subject}' does not have an RSA or ECDSA private key");
+    }
+
+    private static void WriteAlgorithmIdentifier(AsnWriter writer, string oid, bool includeNullParameters = true)
+    {
+        using (writer.PushSequence())
+        {
+            writer.WriteObjectIdentifier(oid);
+            if (includeNullParameters)
+            {
+                writer.WriteNull();
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Text.RegularExpressions;
+using System.Xml;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Writes the splash screen declared in winapp.yaml into a package manifest
+/// </summary>
+internal static partial class AppxSplashScreen
+{
+    /// <summary>
+    /// Splash screen image size at scale 100
+    /// </summary>
+    public const int Width = 620;
+    public const int Height = 300;
+
+    /// <summary>
+    /// Sets the image and background color of the SplashScreen of every Application, adding the element where
+    /// it is missing. Attributes winapp.yaml leaves out are kept as they are in the manifest.
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The background color is not one a manifest accepts, or the manifest has no VisualElements</exception>
+    public static string Apply(string manifestXml, SplashConfig? splash)
+    {
+        if (splash == null || (splash.Image == null && splash.BackgroundColor == null))
+        {
+            return manifestXml;
+        }
+
+        if (splash.BackgroundColor != null && !IsManifestColor(splash.BackgroundColor))
+        {
+            throw new InvalidOperationException($"winapp.yaml: splash backgroundColor '{splash.BackgroundColor}' must be #RRGGBB, a color name or transparent");
+        }
+
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        xmlDoc.LoadXml(manifestXml);
+        var visualElements = xmlDoc.GetElementsByTagName("*").OfType<XmlElement>().Where(e => e.LocalName == "VisualElements").ToList();
+        if (visualElements.Count == 0)
+        {
+            throw new InvalidOperationException("winapp.yaml declares a splash screen, but the manifest has no VisualElements to add it to");
+        }
+
+        foreach (var element in visualElements)
+        {
+            var splashScreen = element.ChildNodes.OfType<XmlElement>().FirstOrDefault(e => e.LocalName == "SplashScreen");
+            if (splashScreen == null)
+            {
+                splashScreen = xmlDoc.CreateElement(element.Prefix, "SplashScreen", element.NamespaceURI);
+
+                // Keep the schema's order: after DefaultTile and LockScreen, before the rotation and view settings
+                var next = element.ChildNodes.OfType<XmlElement>().FirstOrDefault(e => e.LocalName is "InitialRotationPreference" or "ApplicationView");
+                element.InsertBefore(splashScreen, next);
+            }
+
+            if (splash.Image != null)
+            {
+                splashScreen.SetAttribute("Image", splash.Image.Replace('/', '\\'));
+            }
+            if (splash.BackgroundColor != null)
+            {
+                splashScreen.SetAttribute("BackgroundColor", splash.BackgroundColor);
+            }
+        }
+
+        return xmlDoc.OuterXml;
+    }
+
+    /// <summary>
+    /// Whether the manifest schema accepts the color: #RRGGBB, a named color or transparent
+    /// </summary>
+    public static bool IsManifestColor(string value) =>
+        HexColorRegex().IsMatch(value) || Color.FromName(value).IsKnownColor;
+
+    [GeneratedRegex("^#[0-9A-Fa-f]{6}$")]
+    private static partial Regex HexColorRegex();
+}
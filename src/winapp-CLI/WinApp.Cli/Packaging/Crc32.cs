@@ -0,0 +1,113 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// CRC-32 (IEEE 802.3) as required by the ZIP local and central directory headers
+/// </summary>
+internal static class Crc32
+{
+    private static readonly uint[] Table = CreateTable();
+
+    private static uint[] CreateTable()
+    {
+        var table = new uint[256];
+        for (uint i = 0; i < table.Length; i++)
+        {
+            var value = i;
+            for (var bit = 0; bit < 8; bit++)
+            {
+                value = (value & 1) != 0 ? 0xEDB88320u ^ (value >> 1) : value >> 1;
+            }
+            table[i] = value;
+        }
+        return table;
+    }
+
+    /// <summary>
+    /// Continues a running CRC with more data. Start with 0 and pass the previous result for each chunk.
+    /// </summary>
+    public static uint Append(uint crc, ReadOnlySpan<byte> data)
+    {
+        crc = ~crc;
+        foreach (var b in data)
+        {
+            crc = Table[(crc ^ b) & 0xFF] ^ (crc >> 8);
+        }
+        return ~crc;
+    }
+
+    public static uint Compute(ReadOnlySpan<byte> data) => Append(0, data);
+
+    /// <summary>
+    /// Combines the CRCs of two consecutive chunks, so chunks can be checksummed independently (zlib's crc32_combine)
+    /// </summary>
+    public static uint Combine(uint crc1, uint crc2, long length2)
+    {
+        if (length2 <= 0)
+        {
+            return crc1;
+        }
+
+        // Operators that append one (odd) and two (even) zero bits to a CRC, squared repeatedly below
+        var even = new uint[32];
+        var odd = new uint[32];
+        odd[0] = 0xEDB88320u;
+        uint row = 1;
+        for (var n = 1; n < odd.Length; n++)
+        {
+            odd[n] = row;
+            row <<= 1;
+        }
+
+        Square(even, odd); // two zero bits
+        Square(odd, even); // four zero bits
+
+        // Apply length2 zero bytes to crc1, one bit of the length at a time
+        do
+        {
+            Square(even, odd);
+            if ((length2 & 1) != 0)
+            {
+                crc1 = Times(even, crc1);
+            }
+            length2 >>= 1;
+            if (length2 == 0)
+            {
+                break;
+            }
+
+            Square(odd, even);
+            if ((length2 & 1) != 0)
+            {
+                crc1 = Times(odd, crc1);
+            }
+            length2 >>= 1;
+        }
+        while (length2 != 0);
+
+        return crc1 ^ crc2;
+    }
+
+    private static uint Times(uint[] matrix, uint vector)
+    {
+        uint sum = 0;
+        for (var i = 0; vector != 0; i++, vector >>= 1)
+        {
+            if ((vector & 1) != 0)
+            {
+                sum ^= matrix[i];
+            }
+        }
+        return sum;
+    }
+
+    private static void Square(uint[] square, uint[] matrix)
+    {
+        for (var n = 0; n < square.Length; n++)
+        {
+            square[n] = Times(matrix, matrix[n]);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Reflection.PortableExecutable;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// The instruction set a PE image runs with on Windows on ARM
+/// </summary>
+internal enum ImageArchitecture
+{
+    /// <summary>
+    /// IL-only .NET assembly without a preferred architecture, which runs natively everywhere
+    /// </summary>
+    AnyCpu,
+    X86,
+    X64,
+    Arm64,
+
+    /// <summary>
+    /// ARM64EC or ARM64X: x64 compatible code that runs natively on ARM64
+    /// </summary>
+    Arm64EC,
+    Other,
+}
+
+/// <param name="IsDriver">A kernel-mode driver: the native subsystem, or a .sys file</param>
+/// <param name="IsManaged">An IL-only .NET assembly</param>
+internal sealed record PeImage(ImageArchitecture Architecture, bool IsDll, bool IsDriver, bool IsManaged);
+
+/// <summary>
+/// Reads the architecture of executables and DLLs from their headers
+/// </summary>
+internal static class PeArchitecture
+{
+    // IMAGE_LOAD_CONFIG_DIRECTORY64.CHPEMetadataPointer, set in ARM64EC and ARM64X images
+    private const int ChpeMetadataOffset = 200;
+
+    /// <returns>null when the file isn't a PE image</returns>
+    public static PeImage? Read(string path)
+    {
+        try
+        {
+            using var stream = File.OpenRead(path);
+            using var reader = new PEReader(stream);
+            var headers = reader.PEHeaders;
+            if (headers.PEHeader == null)
+            {
+                return null;
+            }
+
+            var ilOnly = headers.CorHeader != null && headers.CorHeader.Flags.HasFlag(CorFlags.ILOnly);
+            var architecture = headers.CoffHeader.Machine switch
+            {
+                Machine.I386 when ilOnly && !headers.CorHeader!.Flags.HasFlag(CorFlags.Requires32Bit) && !headers.CorHeader.Flags.HasFlag(CorFlags.Prefers32Bit) => ImageArchitecture.AnyCpu,
+                Machine.I386 => ImageArchitecture.X86,
+                Machine.Amd64 when HasChpeMetadata(reader, headers.PEHeader) => ImageArchitecture.Arm64EC,
+                Machine.Amd64 => ImageArchitecture.X64,
+                Machine.Arm64 => ImageArchitecture.Arm64,
+                _ => ImageArchitecture.Other,
+            };
+            var isDriver = headers.PEHeader.Subsystem == Subsystem.Native || Path.GetExtension(path).Equals(".sys", StringComparison.OrdinalIgnoreCase);
+            return new PeImage(architecture, headers.IsDll, isDriver, ilOnly);
+        }
+        catch (Exception ex) when (ex is BadImageFormatException or IOException or UnauthorizedAccessException or InvalidOperationException)
+        {
+            return null;
+        }
+    }
+
+    private static bool HasChpeMetadata(PEReader reader, PEHeader header)
+    {
+        var rva = header.LoadConfigTableDirectory.RelativeVirtualAddress;
+        if (rva == 0 || header.Magic != PEMagic.PE32Plus)
+        {
+            return false;
+        }
+
+        var loadConfig = reader.GetSectionData(rva).GetReader();
+        if (loadConfig.Length < ChpeMetadataOffset + sizeof(ulong))
+        {
+            return false;
+        }
+        var size = loadConfig.ReadInt32();
+        if (size < ChpeMetadataOffset + sizeof(ulong))
+        {
+            return false;
+        }
+        loadConfig.Offset = ChpeMetadataOffset;
+        return loadConfig.ReadUInt64() != 0;
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Reflection.Metadata;
+using System.Reflection.PortableExecutable;
+using System.Text;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Reads the functions an executable imports by name, from its import table and its delay-load import table, the
+/// way the loader binds them
+/// </summary>
+internal static class PeImports
+{
+    // IMAGE_IMPORT_DESCRIPTOR and the delay-load ImgDelayDescr
+    private const int ImportDescriptorSize = 20;
+    private const int DelayImportDescriptorSize = 32;
+
+    /// <summary>
+    /// The functions imported by name, as dll!function with the dll in lower case, e.g. user32.dll!SetWindowsHookExW;
+    /// null when the file isn't a PE image
+    /// </summary>
+    public static IReadOnlySet<string>? Read(string path)
+    {
+        try
+        {
+            using var stream = File.OpenRead(path);
+            using var reader = new PEReader(stream);
+            var headers = reader.PEHeaders;
+            if (headers.PEHeader == null)
+            {
+                return null;
+            }
+
+            var is64Bit = headers.PEHeader.Magic == PEMagic.PE32Plus;
+            var imports = new HashSet<string>(StringComparer.Ordinal);
+            ReadDescriptors(reader, headers.PEHeader.ImportTableDirectory.RelativeVirtualAddress, ImportDescriptorSize, nameOffset: 12, thunkOffset: 0, is64Bit, imports);
+            ReadDescriptors(reader, headers.PEHeader.DelayImportTableDirectory.RelativeVirtualAddress, DelayImportDescriptorSize, nameOffset: 4, thunkOffset: 16, is64Bit, imports);
+            return imports;
+        }
+        catch (Exception ex) when (ex is BadImageFormatException or IOException or UnauthorizedAccessException or InvalidOperationException)
+        {
+            return null;
+        }
+    }
+
+    /// <param name="thunkOffset">Offset of the import name table, whose entries are RVAs of a hint and a name, or
+    /// ordinals with the high bit set</param>
+    private static void ReadDescriptors(PEReader reader, int tableRva, int descriptorSize, int nameOffset, int thunkOffset, bool is64Bit, HashSet<string> imports)
+    {
+        if (tableRva == 0)
+        {
+            return;
+        }
+
+        for (var descriptor = tableRva; ; descriptor += descriptorSize)
+        {
+            var table = reader.GetSectionData(descriptor).GetReader();
+            if (table.Length < descriptorSize)
+            {
+                return;
+            }
+            var fields = table.ReadBytes(descriptorSize);
+            var nameRva = BitConverter.ToInt32(fields, nameOffset);
+            var thunkRva = BitConverter.ToInt32(fields, thunkOffset);
+            if (nameRva == 0)
+            {
+                return;
+            }
+
+            var dll = ReadString(reader, nameRva).ToLowerInvariant();
+            if (thunkRva == 0 && descriptorSize == ImportDescriptorSize)
+            {
+                // Some linkers leave out the import name table; the import address table has the same entries on disk
+                thunkRva = BitConverter.ToInt32(fields, 16);
+            }
+            if (thunkRva == 0)
+            {
+                continue;
+            }
+            var thunks = reader.GetSectionData(thunkRva).GetReader();
+            while (thunks.RemainingBytes >= (is64Bit ? 8 : 4))
+            {
+                var thunk = is64Bit ? thunks.ReadUInt64() : thunks.ReadUInt32();
+                if (thunk == 0)
+                {
+                    break;
+                }
+                var byOrdinal = is64Bit ? (thunk & 0x8000_0000_0000_0000) != 0 : (thunk & 0x8000_0000) != 0;
+                if (!byOrdinal)
+                {
+                    // IMAGE_IMPORT_BY_NAME: a 2-byte hint, then the name
+                    imports.Add($"{dll}!{ReadString(reader, (int)thunk + 2)}");
+                }
+            }
+        }
+    }
+
+    private static string ReadString(PEReader reader, int rva)
+    {
+        var blob = reader.GetSectionData(rva).GetReader();
+        var bytes = new List<byte>();
+        while (blob.RemainingBytes > 0 && blob.ReadByte() is var b && b != 0)
+        {
+            bytes.Add(b);
+        }
+        return Encoding.ASCII.GetString([.. bytes]);
+    }
+}
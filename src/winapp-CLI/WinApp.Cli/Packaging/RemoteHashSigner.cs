@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Signature from a key that never leaves a signing service, such as Azure Key Vault or Trusted Signing
+/// </summary>
+/// <param name="Signature">RSA PKCS#1 v1.5 signature of the hash</param>
+/// <param name="Certificate">Certificate of the key</param>
+/// <param name="Chain">Intermediate certificates to embed alongside the signer</param>
+internal sealed record RemoteSignature(byte[] Signature, X509Certificate2 Certificate, IReadOnlyList<X509Certificate2> Chain);
+
+/// <summary>
+/// Signs a hash with a key held by a signing service
+/// </summary>
+internal delegate Task<RemoteSignature> RemoteHashSigner(byte[] hash, HashAlgorithmName hashAlgorithm, CancellationToken cancellationToken);
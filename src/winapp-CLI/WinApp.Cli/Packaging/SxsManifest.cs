@@ -0,0 +1,212 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Reflection.Metadata;
+using System.Reflection.PortableExecutable;
+using System.Text;
+using System.Xml;
+using System.Xml.Linq;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// DPI awareness of a process, from least to most aware
+/// </summary>
+internal enum DpiAwareness
+{
+    Unaware,
+    System,
+    PerMonitor,
+    PerMonitorV2,
+}
+
+/// <param name="Awareness">What the manifest declares, or null when it declares nothing</param>
+/// <param name="Problems">Settings Windows ignores, such as a dpiAwareness in the namespace of dpiAware</param>
+internal sealed record DpiDeclaration(DpiAwareness? Awareness, IReadOnlyList<string> Problems);
+
+/// <summary>
+/// Reads the side-by-side application manifest of an executable: the RT_MANIFEST resource the linker embeds, or an
+/// &lt;exe&gt;.manifest file next to it, the way Windows looks for it when it starts the process
+/// </summary>
+internal static class SxsManifest
+{
+    internal const string WindowsSettings2005Namespace = "http://schemas.microsoft.com/SMI/2005/WindowsSettings";
+    internal const string WindowsSettings2016Namespace = "http://schemas.microsoft.com/SMI/2016/WindowsSettings";
+    internal const string WindowsSettings2019Namespace = "http://schemas.microsoft.com/SMI/2019/WindowsSettings";
+
+    // RT_MANIFEST, and CREATEPROCESS_MANIFEST_RESOURCE_ID, the manifest of an executable
+    private const uint ManifestResourceType = 24;
+    private const uint ProcessManifestId = 1;
+    private const uint SubdirectoryFlag = 0x8000_0000;
+
+    /// <summary>
+    /// The root element of the manifest; null when there is none or it isn't XML
+    /// </summary>
+    public static XElement? Read(string executablePath)
+    {
+        try
+        {
+            var text = ReadEmbedded(executablePath)
+                ?? (File.Exists(executablePath + ".manifest") ? File.ReadAllText(executablePath + ".manifest") : null);
+            return text == null ? null : XDocument.Parse(text.TrimStart('\uFEFF')).Root;
+        }
+        catch (Exception ex) when (ex is XmlException or IOException or UnauthorizedAccessException)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Whether the executable embeds a manifest, which Windows then reads instead of an &lt;exe&gt;.manifest file
+    /// </summary>
+    public static bool HasEmbedded(string executablePath) => ReadEmbedded(executablePath) != null;
+
+    /// <summary>
+    /// The DPI awareness the manifest declares. dpiAwareness, in the 2016 namespace, takes the first value Windows
+    /// knows and overrides dpiAware, in the 2005 namespace, which is true for system awareness and true/pm for per
+    /// monitor
+    /// </summary>
+    internal static DpiDeclaration ReadDpiAwareness(XElement? manifest)
+    {
+        var problems = new List<string>();
+        if (manifest == null)
+        {
+            return new DpiDeclaration(null, problems);
+        }
+
+        XElement? dpiAware = null, dpiAwareness = null;
+        foreach (var element in manifest.Descendants())
+        {
+            var expected = element.Name.LocalName switch
+            {
+                "dpiAware" => WindowsSettings2005Namespace,
+                "dpiAwareness" => WindowsSettings2016Namespace,
+                _ => null,
+            };
+            if (expected == null)
+            {
+                continue;
+            }
+            if (element.Name.NamespaceName != expected)
+            {
+                problems.Add($"<{element.Name.LocalName}> is in the namespace '{element.Name.NamespaceName}' instead of '{expected}', so Windows ignores it");
+            }
+            else if (element.Name.LocalName == "dpiAware")
+            {
+                dpiAware ??= element;
+            }
+            else
+            {
+                dpiAwareness ??= element;
+            }
+        }
+
+        if (dpiAwareness != null)
+        {
+            var values = dpiAwareness.Value.Split(',', StringSplitOptions.TrimEntries | StringSplitOptions.RemoveEmptyEntries);
+            foreach (var value in values)
+            {
+                if (ParseDpiAwareness(value) is { } awareness)
+                {
+                    return new DpiDeclaration(awareness, problems);
+                }
+            }
+            problems.Add($"<dpiAwareness> is '{dpiAwareness.Value.Trim()}', which has none of unaware, system, permonitor or permonitorv2, so Windows treats the app as DPI unaware");
+            return new DpiDeclaration(DpiAwareness.Unaware, problems);
+        }
+
+        if (dpiAware != null)
+        {
+            var awareness = dpiAware.Value.Trim().ToLowerInvariant() switch
+            {
+                "true" => DpiAwareness.System,
+                "true/pm" or "per monitor" => DpiAwareness.PerMonitor,
+                "false" => DpiAwareness.Unaware,
+                _ => (DpiAwareness?)null,
+            };
+            if (awareness == null)
+            {
+                problems.Add($"<dpiAware> is '{dpiAware.Value.Trim()}' instead of true, true/pm or false, so Windows treats the app as DPI unaware");
+            }
+            return new DpiDeclaration(awareness ?? DpiAwareness.Unaware, problems);
+        }
+
+        return new DpiDeclaration(null, problems);
+    }
+
+    /// <summary>
+    /// The activeCodePage the manifest declares, such as UTF-8, or null for the system's code page
+    /// </summary>
+    internal static string? ReadActiveCodePage(XElement? manifest) =>
+        manifest?.Descendants(XName.Get("activeCodePage", WindowsSettings2019Namespace)).FirstOrDefault()?.Value.Trim();
+
+    private static DpiAwareness? ParseDpiAwareness(string value) => value.ToLowerInvariant() switch
+    {
+        "unaware" => DpiAwareness.Unaware,
+        "system" => DpiAwareness.System,
+        "permonitor" => DpiAwareness.PerMonitor,
+        "permonitorv2" => DpiAwareness.PerMonitorV2,
+        _ => null,
+    };
+
+    /// <summary>
+    /// The text of the RT_MANIFEST resource with id 1; null when the executable has none or isn't a PE image
+    /// </summary>
+    private static string? ReadEmbedded(string path)
+    {
+        try
+        {
+            using var stream = File.OpenRead(path);
+            using var reader = new PEReader(stream);
+            var resourcesRva = reader.PEHeaders.PEHeader?.ResourceTableDirectory.RelativeVirtualAddress ?? 0;
+            if (resourcesRva == 0)
+            {
+                return null;
+            }
+
+            // Type, name and language directories, whose offsets are from the start of the resources
+            var resources = reader.GetSectionData(resourcesRva).GetReader();
+            if (FindEntry(resources, 0, ManifestResourceType) is not { } names
+                || (names & SubdirectoryFlag) == 0
+                || FindEntry(resources, (int)(names & ~SubdirectoryFlag), ProcessManifestId) is not { } languages
+                || (languages & SubdirectoryFlag) == 0
+                || FindEntry(resources, (int)(languages & ~SubdirectoryFlag), null) is not { } data
+                || (data & SubdirectoryFlag) != 0)
+            {
+                return null;
+            }
+
+            // IMAGE_RESOURCE_DATA_ENTRY: the RVA and size of the resource
+            resources.Offset = (int)data;
+            var dataRva = resources.ReadInt32();
+            var size = resources.ReadInt32();
+            var bytes = reader.GetSectionData(dataRva).GetReader().ReadBytes(size);
+            return Encoding.UTF8.GetString(bytes);
+        }
+        catch (Exception ex) when (ex is BadImageFormatException or IOException or UnauthorizedAccessException or InvalidOperationException)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// The offset of the entry with the id in an IMAGE_RESOURCE_DIRECTORY, or of its first entry with an id when
+    /// <paramref name="id"/> is null
+    /// </summary>
+    private static uint? FindEntry(BlobReader resources, int directory, uint? id)
+    {
+        resources.Offset = directory + 12;
+        var named = resources.ReadUInt16();
+        var ids = resources.ReadUInt16();
+        for (var i = 0; i < named + ids; i++)
+        {
+            var name = resources.ReadUInt32();
+            var offset = resources.ReadUInt32();
+            if (i >= named && (id == null || name == id))
+            {
+                return offset;
+            }
+        }
+        return null;
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Generates the classic Win32 application manifest of an executable from 'win32Manifest:' in winapp.yaml and the
+/// identity of AppxManifest.xml, so the two manifests of a packaged app describe the same app
+/// </summary>
+internal static class Win32Manifest
+{
+    /// <summary>
+    /// File the manifest is generated in, next to AppxManifest.xml
+    /// </summary>
+    public const string FileName = "app.manifest";
+
+    private static readonly XNamespace AsmV1 = "urn:schemas-microsoft-com:asm.v1";
+    private static readonly XNamespace AsmV3 = "urn:schemas-microsoft-com:asm.v3";
+    private static readonly XNamespace Compatibility = "urn:schemas-microsoft-com:compatibility.v1";
+    private static readonly XNamespace WindowsSettings2005 = SxsManifest.WindowsSettings2005Namespace;
+    private static readonly XNamespace WindowsSettings2016 = SxsManifest.WindowsSettings2016Namespace;
+    private static readonly XNamespace WindowsSettings2019 = SxsManifest.WindowsSettings2019Namespace;
+
+    // The supportedOS ids of the releases, which turn off the compatibility shims of older ones
+    private static readonly Dictionary<string, string> SupportedOSIds = new(StringComparer.OrdinalIgnoreCase)
+    {
+        ["windows10"] = "{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}",
+        ["windows8.1"] = "{1f676c76-80e1-4239-95bb-83d0f6d0da78}",
+        ["windows8"] = "{4a2f28e3-53b9-4441-ba9c-d69d4a4a6e38}",
+        ["windows7"] = "{35138b9a-5d96-4fbd-8e2d-a2440225f93a}",
+        ["vista"] = "{e2011457-1546-43c5-a5fe-008deee3d3f0}",
+    };
+
+    private static readonly string[] DpiAwarenessValues = ["PerMonitorV2", "PerMonitor", "System", "Unaware"];
+
+    /// <summary>
+    /// The manifest for <paramref name="config"/>, with the package's Identity Name as its assemblyIdentity
+    /// </summary>
+    /// <exception cref="InvalidOperationException">A dpiAwareness or supportedOS winapp.yaml doesn't know</exception>
+    public static XDocument Generate(Win32ManifestConfig config, XElement appxRoot)
+    {
+        var dpiAwareness = DpiAwarenessValues.FirstOrDefault(v => v.Equals(config.DpiAwareness, StringComparison.OrdinalIgnoreCase))
+            ?? throw new InvalidOperationException($"win32Manifest dpiAwareness '{config.DpiAwareness}' isn't one of {string.Join(", ", DpiAwarenessValues)}");
+        var supportedOS = config.SupportedOS
+            .Select(os => SupportedOSIds.TryGetValue(os, out var id) ? id : throw new InvalidOperationException($"win32Manifest supportedOS '{os}' isn't one of {string.Join(", ", SupportedOSIds.Keys)}"))
+            .Distinct()
+            .Select(id => new XElement(Compatibility + "supportedOS", new XAttribute("Id", id)));
+
+        var assembly = new XElement(AsmV1 + "assembly", new XAttribute("manifestVersion", "1.0"));
+        // The version stays fixed, so a manifest the build embeds doesn't go stale when the package version changes
+        var name = appxRoot.Element(XName.Get("Identity", AppxManifestSchema.FoundationNamespace))?.Attribute("Name")?.Value;
+        if (!string.IsNullOrEmpty(name) && !name.Contains("{{", StringComparison.Ordinal))
+        {
+            assembly.Add(new XElement(AsmV1 + "assemblyIdentity", new XAttribute("type", "win32"), new XAttribute("name", name), new XAttribute("version", "1.0.0.0")));
+        }
+
+        assembly.Add(new XElement(Compatibility + "compatibility",
+            new XElement(Compatibility + "application", supportedOS)));
+
+        var windowsSettings = new XElement(AsmV3 + "windowsSettings",
+            // Releases before Windows 10 1607 only read dpiAware
+            new XElement(WindowsSettings2005 + "dpiAware", dpiAwareness switch
+            {
+                "PerMonitorV2" or "PerMonitor" => "true/pm",
+                "System" => "true",
+                _ => "false",
+            }),
+            new XElement(WindowsSettings2016 + "dpiAwareness", dpiAwareness == "PerMonitorV2" ? "PerMonitorV2, PerMonitor" : dpiAwareness),
+            config.LongPathAware ? new XElement(WindowsSettings2016 + "longPathAware", "true") : null,
+            config.ActiveCodePage != null ? new XElement(WindowsSettings2019 + "activeCodePage", config.ActiveCodePage) : null);
+        assembly.Add(new XElement(AsmV3 + "application", windowsSettings));
+
+        return new XDocument(new XDeclaration("1.0", "UTF-8", "yes"), assembly);
+    }
+
+    /// <summary>
+    /// Writes the manifest for <paramref name="config"/> and the AppxManifest.xml <paramref name="appxManifest"/> to
+    /// <paramref name="outputPath"/>
+    /// </summary>
+    public static async Task WriteAsync(Win32ManifestConfig config, string appxManifest, FileInfo outputPath, CancellationToken cancellationToken = default)
+    {
+        var appxRoot = XDocument.Parse(appxManifest).Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var document = Generate(config, appxRoot);
+        await File.WriteAllTextAsync(outputPath.FullName, $"{document.Declaration}{Environment.NewLine}{document}{Environment.NewLine}", new UTF8Encoding(encoderShouldEmitUTF8Identifier: false), cancellationToken);
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// A single record of a ZIP archive, as it appears in the central directory
+/// </summary>
+/// <param name="Name">Zip item name (percent-encoded OPC part name without the leading slash)</param>
+internal sealed record ZipEntryRecord(
+    string Name,
+    ushort Method,
+    ushort Flags,
+    uint DosDateTime,
+    uint Crc32,
+    long CompressedSize,
+    long UncompressedSize,
+    long LocalHeaderOffset);
+
+/// <summary>
+/// Encoding of the ZIP structures used by MSIX/APPX packages (APPNOTE.TXT). Version 2.0 records are used
+/// unless a size, offset or the entry count needs the Zip64 extensions (version 4.5).
+/// </summary>
+internal static class ZipFormat
+{
+    public const uint LocalFileHeaderSignature = 0x04034b50;
+    public const uint CentralDirectoryHeaderSignature = 0x02014b50;
+    public const uint EndOfCentralDirectorySignature = 0x06054b50;
+    public const uint Zip64EndOfCentralDirectorySignature = 0x06064b50;
+    public const uint Zip64EndOfCentralDirectoryLocatorSignature = 0x07064b50;
+
+    public const int LocalFileHeaderFixedSize = 30;
+    public const int CentralDirectoryHeaderFixedSize = 46;
+    public const int EndOfCentralDirectorySize = 22;
+    public const int Zip64EndOfCentralDirectorySize = 56;
+    public const int Zip64EndOfCentralDirectoryLocatorSize = 20;
+
+    public const ushort MethodStored = 0;
+    public const ushort MethodDeflated = 8;
+    public const ushort FlagUtf8 = 0x0800;
+
+    public const ushort Zip64ExtraFieldTag = 0x0001;
+
+    /// <summary>
+    /// Value stored in a 32-bit field whose actual value lives in the Zip64 extra field
+    /// </summary>
+    public const uint Zip64Marker32 = uint.MaxValue;
+    public const ushort Zip64Marker16 = ushort.MaxValue;
+
+    /// <summary>
+    /// Size of a local header Zip64 extra field carrying both sizes
+    /// </summary>
+    public const int LocalZip64ExtraFieldSize = 20;
+
+    private const ushort Version = 20;
+    private const ushort Zip64Version = 45;
+
+    public static byte[] EncodeName(string name) => Encoding.UTF8.GetBytes(name);
+
+    public static string DecodeName(ReadOnlySpan<byte> name) => Encoding.UTF8.GetString(name);
+
+    public static ushort GetNameFlags(string name) => name.Any(c => c > 0x7F) ? FlagUtf8 : (ushort)0;
+
+    public static int GetLocalHeaderSize(string name, bool zip64 = false) =>
+        LocalFileHeaderFixedSize + EncodeName(name).Length + (zip64 ? LocalZip64ExtraFieldSize : 0);
+
+    /// <summary>
+    /// Worst case size of <paramref name="uncompressedSize"/> bytes after deflate with a sync flush per block.
+    /// Incompressible blocks fall back to stored deflate blocks, which add a few bytes per 16 KB.
+    /// </summary>
+    public static long GetMaxCompressedSize(long uncompressedSize) =>
+        uncompressedSize + (((uncompressedSize / AppxBlockMap.BlockSize) + 1) * 64);
+
+    /// <summary>
+    /// Whether an entry of the given size must be written with a Zip64 local header. The local header is written
+    /// before the data, so entries of unknown size always reserve room for the Zip64 sizes.
+    /// </summary>
+    public static bool RequiresZip64LocalHeader(long? uncompressedSize) =>
+        uncompressedSize is not long size || GetMaxCompressedSize(size) >= Zip64Marker32;
+
+    public static uint ToDosDateTime(DateTime timestamp)
+    {
+        if (timestamp.Year < 1980)
+        {
+            timestamp = new DateTime(1980, 1, 1, 0, 0, 0, DateTimeKind.Local);
+        }
+        else if (timestamp.Year > 2107)
+        {
+            timestamp = new DateTime(2107, 12, 31, 23, 59, 58, DateTimeKind.Local);
+        }
+
+        var date = (uint)(((timestamp.Year - 1980) << 9) | (timestamp.Month << 5) | timestamp.Day);
+        var time = (uint)((timestamp.Hour << 11) | (timestamp.Minute << 5) | (timestamp.Second / 2));
+        return (date << 16) | time;
+    }
+
+    /// <summary>
+    /// Encodes the local file header of an entry. With <paramref name="zip64"/>, both sizes are written to a
+    /// Zip64 extra field so the header keeps its size when it is patched after the data has been written.
+    /// </summary>
+    public static byte[] EncodeLocalHeader(ZipEntryRecord entry, bool zip64 = false)
+    {
+        if (!zip64 && (entry.CompressedSize >= Zip64Marker32 || entry.UncompressedSize >= Zip64Marker32))
+        {
+            throw new InvalidOperationException($"'{entry.Name}' grew past 4 GB but its local header was written without room for Zip64 sizes");
+        }
+
+        var name = EncodeName(entry.Name);
+
+        using var stream = new MemoryStream(GetLocalHeaderSize(entry.Name, zip64));
+        using var writer = new BinaryWriter(stream);
+        writer.Write(LocalFileHeaderSignature);
+        writer.Write(zip64 ? Zip64Version : Version);
+        writer.Write(entry.Flags);
+        writer.Write(entry.Method);
+        writer.Write(entry.DosDateTime);
+        writer.Write(entry.Crc32);
+        writer.Write(zip64 ? Zip64Marker32 : (uint)entry.CompressedSize);
+        writer.Write(zip64 ? Zip64Marker32 : (uint)entry.UncompressedSize);
+        writer.Write((ushort)name.Length);
+        writer.Write((ushort)(zip64 ? LocalZip64ExtraFieldSize : 0));
+        writer.Write(name);
+        if (zip64)
+        {
+            writer.Write(Zip64ExtraFieldTag);
+            writer.Write((ushort)(LocalZip64ExtraFieldSize - 4));
+            writer.Write(entry.UncompressedSize);
+            writer.Write(entry.CompressedSize);
+        }
+        writer.Flush();
+        return stream.ToArray();
+    }
+
+    public static byte[] EncodeCentralDirectory(IEnumerable<ZipEntryRecord> entries)
+    {
+        using var stream = new MemoryStream();
+        using var writer = new BinaryWriter(stream);
+        foreach (var entry in entries)
+        {
+            var name = EncodeName(entry.Name);
+
+            // Only the fields that overflow go to the extra field, in the order APPNOTE 4.5.3 defines
+            var zip64Uncompressed = entry.UncompressedSize >= Zip64Marker32;
+            var zip64Compressed = entry.CompressedSize >= Zip64Marker32;
+            var zip64Offset = entry.LocalHeaderOffset >= Zip64Marker32;
+            var extraLength = (zip64Uncompressed ? 8 : 0) + (zip64Compressed ? 8 : 0) + (zip64Offset ? 8 : 0);
+            var zip64 = extraLength > 0;
+
+            writer.Write(CentralDirectoryHeaderSignature);
+            writer.Write(zip64 ? Zip64Version : Version); // version made by
+            writer.Write(zip64 ? Zip64Version : Version); // version needed to extract
+            writer.Write(entry.Flags);
+            writer.Write(entry.Method);
+            writer.Write(entry.DosDateTime);
+            writer.Write(entry.Crc32);
+            writer.Write(zip64Compressed ? Zip64Marker32 : (uint)entry.CompressedSize);
+            writer.Write(zip64Uncompressed ? Zip64Marker32 : (uint)entry.UncompressedSize);
+            writer.Write((ushort)name.Length);
+            writer.Write((ushort)(zip64 ? extraLength + 4 : 0)); // extra field length
+            writer.Write((ushort)0); // file comment length
+            writer.Write((ushort)0); // disk number start
+            writer.Write((ushort)0); // internal file attributes
+            writer.Write(0u); // external file attributes
+            writer.Write(zip64Offset ? Zip64Marker32 : (uint)entry.LocalHeaderOffset);
+            writer.Write(name);
+
+            if (zip64)
+            {
+                writer.Write(Zip64ExtraFieldTag);
+                writer.Write((ushort)extraLength);
+                if (zip64Uncompressed)
+                {
+                    writer.Write(entry.UncompressedSize);
+                }
+                if (zip64Compressed)
+                {
+                    writer.Write(entry.CompressedSize);
+                }
+                if (zip64Offset)
+                {
+                    writer.Write(entry.LocalHeaderOffset);
+                }
+            }
+        }
+        writer.Flush();
+        return stream.ToArray();
+    }
+
+    /// <summary>
+    /// Encodes the records that follow the central directory: the end of central directory record, preceded by
+    /// the Zip64 end of central directory record and locator when the entry count, size or offset overflow it.
+    /// </summary>
+    public static byte[] EncodeEndOfCentralDirectory(int entryCount, long centralDirectorySize, long centralDirectoryOffset)
+    {
+        var zip64 = entryCount >= Zip64Marker16 || centralDirectorySize >= Zip64Marker32 || centralDirectoryOffset >= Zip64Marker32;
+
+        using var stream = new MemoryStream();
+        using var writer = new BinaryWriter(stream);
+        if (zip64)
+        {
+            var zip64RecordOffset = centralDirectoryOffset + centralDirectorySize;
+
+            writer.Write(Zip64EndOfCentralDirectorySignature);
+            writer.Write((ulong)(Zip64EndOfCentralDirectorySize - 12)); // size of the remaining record
+            writer.Write(Zip64Version); // version made by
+            writer.Write(Zip64Version); // version needed to extract
+            writer.Write(0u); // number of this disk
+            writer.Write(0u); // disk where central directory starts
+            writer.Write((long)entryCount);
+            writer.Write((long)entryCount);
+            writer.Write(centralDirectorySize);
+            writer.Write(centralDirectoryOffset);
+
+            writer.Write(Zip64EndOfCentralDirectoryLocatorSignature);
+            writer.Write(0u); // disk with the Zip64 end of central directory record
+            writer.Write(zip64RecordOffset);
+            writer.Write(1u); // total number of disks
+        }
+
+        writer.Write(EndOfCentralDirectorySignature);
+        writer.Write((ushort)0); // number of this disk
+        writer.Write((ushort)0); // disk where central directory starts
+        writer.Write(zip64 ? Zip64Marker16 : (ushort)entryCount);
+        writer.Write(zip64 ? Zip64Marker16 : (ushort)entryCount);
+        writer.Write(zip64 ? Zip64Marker32 : (uint)centralDirectorySize);
+        writer.Write(zip64 ? Zip64Marker32 : (uint)centralDirectoryOffset);
+        writer.Write((ushort)0); // comment length
+        writer.Flush();
+        return stream.ToArray();
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers.Binary;
+using System.IO.Compression;
+
+namespace WinApp.Cli.Packaging;
+
+/// <param name="Entries">Entries in central directory order</param>
+/// <param name="RawRecords">The encoded central directory header of each entry, parallel to <paramref name="Entries"/></param>
+internal sealed record ZipCentralDirectory(IReadOnlyList<ZipEntryRecord> Entries, IReadOnlyList<ReadOnlyMemory<byte>> RawRecords, long Offset, long Size);
+
+/// <summary>
+/// Reads the raw ZIP records of a package. Unlike <see cref="ZipArchive"/>, this keeps record offsets,
+/// which the package signature digests are computed over.
+/// </summary>
+internal static class ZipPackageReader
+{
+    private const int MaxCommentLength = ushort.MaxValue;
+
+    public static async Task<ZipCentralDirectory> ReadCentralDirectoryAsync(Stream stream, CancellationToken cancellationToken = default)
+    {
+        var tailLength = (int)Math.Min(stream.Length, ZipFormat.EndOfCentralDirectorySize + MaxCommentLength);
+        var tail = new byte[tailLength];
+        stream.Position = stream.Length - tailLength;
+        await stream.ReadExactlyAsync(tail, cancellationToken);
+
+        var eocd = -1;
+        for (var i = tailLength - ZipFormat.EndOfCentralDirectorySize; i >= 0; i--)
+        {
+            if (BinaryPrimitives.ReadUInt32LittleEndian(tail.AsSpan(i)) == ZipFormat.EndOfCentralDirectorySignature)
+            {
+                eocd = i;
+                break;
+            }
+        }
+
+        if (eocd < 0)
+        {
+            throw new InvalidDataException("Not a ZIP archive: end of central directory record not found");
+        }
+
+        var record = tail.AsSpan(eocd);
+        long entryCount = BinaryPrimitives.ReadUInt16LittleEndian(record[10..]);
+        long size = BinaryPrimitives.ReadUInt32LittleEndian(record[12..]);
+        long offset = BinaryPrimitives.ReadUInt32LittleEndian(record[16..]);
+
+        if (entryCount == ZipFormat.Zip64Marker16 || size == ZipFormat.Zip64Marker32 || offset == ZipFormat.Zip64Marker32)
+        {
+            (entryCount, size, offset) = await ReadZip64EndOfCentralDirectoryAsync(stream, stream.Length - tailLength + eocd, cancellationToken);
+        }
+
+        if (entryCount < 0 || size < 0 || offset < 0 || offset + size > stream.Length)
+        {
+            throw new InvalidDataException("Central directory lies outside of the archive");
+        }
+
+        if (size > int.MaxValue)
+        {
+            throw new InvalidDataException("Central directory is too large");
+        }
+
+        var centralDirectory = new byte[size];
+        stream.Position = offset;
+        await stream.ReadExactlyAsync(centralDirectory, cancellationToken);
+
+        var (entries, rawRecords) = ParseCentralDirectory(centralDirectory, entryCount, offset);
+        return new ZipCentralDirectory(entries, rawRecords, offset, size);
+    }
+
+    internal static (List<ZipEntryRecord> Entries, List<ReadOnlyMemory<byte>> RawRecords) ParseCentralDirectory(byte[] centralDirectory, long entryCount, long offset)
+    {
+        // Every record takes at least its fixed header, which bounds what a corrupt count can make us allocate
+        var capacity = (int)Math.Min(entryCount, centralDirectory.Length / ZipFormat.CentralDirectoryHeaderFixedSize);
+        var entries = new List<ZipEntryRecord>(capacity);
+        var rawRecords = new List<ReadOnlyMemory<byte>>(capacity);
+        var position = 0;
+        for (long i = 0; i < entryCount; i++)
+        {
+            var header = centralDirectory.AsSpan(position);
+            if (header.Length < ZipFormat.CentralDirectoryHeaderFixedSize ||
+                BinaryPrimitives.ReadUInt32LittleEndian(header) != ZipFormat.CentralDirectoryHeaderSignature)
+            {
+                throw new InvalidDataException($"Corrupt central directory record at offset {offset + position}");
+            }
+
+            var nameLength = BinaryPrimitives.ReadUInt16LittleEndian(header[28..]);
+            var extraLength = BinaryPrimitives.ReadUInt16LittleEndian(header[30..]);
+            var commentLength = BinaryPrimitives.ReadUInt16LittleEndian(header[32..]);
+            var recordLength = ZipFormat.CentralDirectoryHeaderFixedSize + nameLength + extraLength + commentLength;
+            if (header.Length < recordLength)
+            {
+                throw new InvalidDataException($"Corrupt central directory record at offset {offset + position}");
+            }
+
+            var entry = new ZipEntryRecord(
+                Name: ZipFormat.DecodeName(header.Slice(ZipFormat.CentralDirectoryHeaderFixedSize, nameLength)),
+                Method: BinaryPrimitives.ReadUInt16LittleEndian(header[10..]),
+                Flags: BinaryPrimitives.ReadUInt16LittleEndian(header[8..]),
+                DosDateTime: BinaryPrimitives.ReadUInt32LittleEndian(header[12..]),
+                Crc32: BinaryPrimitives.ReadUInt32LittleEndian(header[16..]),
+                CompressedSize: BinaryPrimitives.ReadUInt32LittleEndian(header[20..]),
+                UncompressedSize: BinaryPrimitives.ReadUInt32LittleEndian(header[24..]),
+                LocalHeaderOffset: BinaryPrimitives.ReadUInt32LittleEndian(header[42..]));
+
+            entries.Add(ApplyZip64ExtraField(entry, header.Slice(ZipFormat.CentralDirectoryHeaderFixedSize + nameLength, extraLength)));
+            rawRecords.Add(centralDirectory.AsMemory(position, recordLength));
+            position += recordLength;
+        }
+
+        return (entries, rawRecords);
+    }
+
+    private static async Task<(long EntryCount, long Size, long Offset)> ReadZip64EndOfCentralDirectoryAsync(Stream stream, long eocdOffset, CancellationToken cancellationToken)
+    {
+        var locatorOffset = eocdOffset - ZipFormat.Zip64EndOfCentralDirectoryLocatorSize;
+        if (locatorOffset < 0)
+        {
+            throw new InvalidDataException("Zip64 end of central directory locator not found");
+        }
+
+        var locator = new byte[ZipFormat.Zip64EndOfCentralDirectoryLocatorSize];
+        stream.Position = locatorOffset;
+        await stream.ReadExactlyAsync(locator, cancellationToken);
+        if (BinaryPrimitives.ReadUInt32LittleEndian(locator) != ZipFormat.Zip64EndOfCentralDirectoryLocatorSignature)
+        {
+            throw new InvalidDataException("Zip64 end of central directory locator not found");
+        }
+
+        var recordOffset = BinaryPrimitives.ReadInt64LittleEndian(locator.AsSpan(8));
+        if (recordOffset < 0 || recordOffset + ZipFormat.Zip64EndOfCentralDirectorySize > locatorOffset)
+        {
+            throw new InvalidDataException("Zip64 end of central directory record lies outside of the archive");
+        }
+
+        var record = new byte[ZipFormat.Zip64EndOfCentralDirectorySize];
+        stream.Position = recordOffset;
+        await stream.ReadExactlyAsync(record, cancellationToken);
+        if (BinaryPrimitives.ReadUInt32LittleEndian(record) != ZipFormat.Zip64EndOfCentralDirectorySignature)
+        {
+            throw new InvalidDataException("Corrupt Zip64 end of central directory record");
+        }
+
+        return (
+            BinaryPrimitives.ReadInt64LittleEndian(record.AsSpan(32)),
+            BinaryPrimitives.ReadInt64LittleEndian(record.AsSpan(40)),
+            BinaryPrimitives.ReadInt64LittleEndian(record.AsSpan(48)));
+    }
+
+    /// <summary>
+    /// Replaces the 32-bit fields set to the Zip64 marker with the values from the Zip64 extra field
+    /// </summary>
+    private static ZipEntryRecord ApplyZip64ExtraField(ZipEntryRecord entry, ReadOnlySpan<byte> extra)
+    {
+        while (extra.Length >= 4)
+        {
+            var tag = BinaryPrimitives.ReadUInt16LittleEndian(extra);
+            var length = BinaryPrimitives.ReadUInt16LittleEndian(extra[2..]);
+            if (extra.Length < 4 + length)
+            {
+                break;
+            }
+
+            if (tag == ZipFormat.Zip64ExtraFieldTag)
+            {
+                var data = extra.Slice(4, length);
+                if (entry.UncompressedSize == ZipFormat.Zip64Marker32)
+                {
+                    entry = entry with { UncompressedSize = ReadZip64Value(ref data, entry.Name) };
+                }
+                if (entry.CompressedSize == ZipFormat.Zip64Marker32)
+                {
+                    entry = entry with { CompressedSize = ReadZip64Value(ref data, entry.Name) };
+                }
+                if (entry.LocalHeaderOffset == ZipFormat.Zip64Marker32)
+                {
+                    entry = entry with { LocalHeaderOffset = ReadZip64Value(ref data, entry.Name) };
+                }
+                return entry;
+            }
+
+            extra = extra[(4 + length)..];
+        }
+
+        return entry;
+    }
+
+    private static long ReadZip64Value(ref ReadOnlySpan<byte> data, string entryName)
+    {
+        if (data.Length < 8)
+        {
+            throw new InvalidDataException($"Truncated Zip64 extra field for '{entryName}'");
+        }
+
+        var value = BinaryPrimitives.ReadInt64LittleEndian(data);
+        data = data[8..];
+        return value;
+    }
+
+    /// <summary>
+    /// Returns the size of the local file header (including name and extra field) of an entry
+    /// </summary>
+    public static async Task<int> ReadLocalHeaderSizeAsync(Stream stream, ZipEntryRecord entry, CancellationToken cancellationToken = default)
+    {
+        var header = new byte[ZipFormat.LocalFileHeaderFixedSize];
+        stream.Position = entry.LocalHeaderOffset;
+        await stream.ReadExactlyAsync(header, cancellationToken);
+
+        if (BinaryPrimitives.ReadUInt32LittleEndian(header) != ZipFormat.LocalFileHeaderSignature)
+        {
+            throw new InvalidDataException($"Corrupt local file header for '{entry.Name}'");
+        }
+
+        var nameLength = BinaryPrimitives.ReadUInt16LittleEndian(header.AsSpan(26));
+        var extraLength = BinaryPrimitives.ReadUInt16LittleEndian(header.AsSpan(28));
+        return ZipFormat.LocalFileHeaderFixedSize + nameLength + extraLength;
+    }
+
+    /// <summary>
+    /// Reads and decompresses the contents of a (footprint-sized) entry into memory
+    /// </summary>
+    public static async Task<byte[]> ReadEntryAsync(Stream stream, ZipEntryRecord entry, CancellationToken cancellationToken = default)
+    {
+        if (entry.CompressedSize > Array.MaxLength || entry.UncompressedSize > Array.MaxLength)
+        {
+            throw new InvalidDataException($"'{entry.Name}' is too large to be read into memory");
+        }
+
+        var headerSize = await ReadLocalHeaderSizeAsync(stream, entry, cancellationToken);
+        var compressed = new byte[entry.CompressedSize];
+        stream.Position = entry.LocalHeaderOffset + headerSize;
+        await stream.ReadExactlyAsync(compressed, cancellationToken);
+
+        switch (entry.Method)
+        {
+            case ZipFormat.MethodStored:
+                return compressed;
+            case ZipFormat.MethodDeflated:
+                using (var deflate = new DeflateStream(new MemoryStream(compressed), CompressionMode.Decompress))
+                using (var output = new MemoryStream((int)Math.Min(entry.UncompressedSize, int.MaxValue)))
+                {
+                    await deflate.CopyToAsync(output, cancellationToken);
+                    return output.ToArray();
+                }
+            default:
+                throw new InvalidDataException($"Unsupported compression method {entry.Method} for '{entry.Name}'");
+        }
+    }
+}
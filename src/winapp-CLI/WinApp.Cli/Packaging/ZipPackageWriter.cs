@@ -0,0 +1,234 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers;
+using System.IO.Compression;
+using System.Security.Cryptography;
+
+namespace WinApp.Cli.Packaging;
+
+/// <summary>
+/// Streams ZIP entries to a seekable stream, hashing each 64 KB block as it goes so the
+/// block map can be produced without a second pass over the payload.
+/// </summary>
+/// <param name="blockHashAlgorithm">Algorithm for the block hashes of streamed entries; SHA-256 when not given</param>
+internal sealed class ZipPackageWriter(Stream stream, HashAlgorithmName? blockHashAlgorithm = null)
+{
+    private readonly List<ZipEntryRecord> entries = [];
+    private readonly HashAlgorithmName hashAlgorithm = blockHashAlgorithm ?? HashAlgorithmName.SHA256;
+
+    public IReadOnlyList<ZipEntryRecord> Entries => entries;
+
+    public long Position => stream.Position;
+
+    /// <summary>
+    /// Writes an entry and returns its block map description
+    /// </summary>
+    public async Task<AppxBlockMapFile> AddEntryAsync(string zipName, Stream source, bool compress, DateTime lastWriteTime, CancellationToken cancellationToken = default)
+    {
+        if (source.CanSeek && source.Length == 0)
+        {
+            // Empty files are always stored; a deflate stream would still emit a few bytes with no block to own them
+            compress = false;
+        }
+
+        var entry = new ZipEntryRecord(
+            zipName,
+            compress ? ZipFormat.MethodDeflated : ZipFormat.MethodStored,
+            ZipFormat.GetNameFlags(zipName),
+            ZipFormat.ToDosDateTime(lastWriteTime),
+            Crc32: 0,
+            CompressedSize: 0,
+            UncompressedSize: 0,
+            LocalHeaderOffset: stream.Position);
+
+        // The header is patched in place once the sizes are known, so whether it carries Zip64 sizes is decided up front
+        var zip64 = ZipFormat.RequiresZip64LocalHeader(source.CanSeek ? source.Length - source.Position : null);
+        var localHeader = ZipFormat.EncodeLocalHeader(entry, zip64);
+        await stream.WriteAsync(localHeader, cancellationToken);
+        var dataStart = stream.Position;
+
+        var blocks = new List<AppxBlock>();
+        uint crc = 0;
+        long size = 0;
+
+        var buffer = ArrayPool<byte>.Shared.Rent(AppxBlockMap.BlockSize);
+        var deflate = compress ? new DeflateStream(stream, CompressionLevel.Optimal, leaveOpen: true) : null;
+        try
+        {
+            int read;
+            while ((read = await source.ReadAtLeastAsync(buffer.AsMemory(0, AppxBlockMap.BlockSize), AppxBlockMap.BlockSize, throwOnEndOfStream: false, cancellationToken)) > 0)
+            {
+                var block = buffer.AsMemory(0, read);
+                crc = Crc32.Append(crc, block.Span);
+                size += read;
+
+                var blockStart = stream.Position;
+                if (deflate != null)
+                {
+                    // Sync-flush after every block so each block's compressed bytes have a well-defined size
+                    await deflate.WriteAsync(block, cancellationToken);
+                    await deflate.FlushAsync(cancellationToken);
+                }
+                else
+                {
+                    await stream.WriteAsync(block, cancellationToken);
+                }
+
+                blocks.Add(new AppxBlock(AppxDigestAlgorithms.Hash(hashAlgorithm, block.Span), deflate != null ? stream.Position - blockStart : null));
+            }
+
+            if (deflate != null)
+            {
+                var trailerStart = stream.Position;
+                await deflate.DisposeAsync();
+                deflate = null;
+
+                // The final deflate block terminator belongs to the last data block
+                var trailer = stream.Position - trailerStart;
+                if (trailer > 0 && blocks.Count > 0)
+                {
+                    blocks[^1] = blocks[^1] with { CompressedSize = blocks[^1].CompressedSize + trailer };
+                }
+            }
+        }
+        finally
+        {
+            if (deflate != null)
+            {
+                await deflate.DisposeAsync();
+            }
+            ArrayPool<byte>.Shared.Return(buffer);
+        }
+
+        await CompleteEntryAsync(entry, zip64, dataStart, crc, size, cancellationToken);
+        return new AppxBlockMapFile(AppxBlockMap.ToBlockMapName(zipName), size, localHeader.Length, blocks);
+    }
+
+    /// <summary>
+    /// Writes an entry from blocks that were already hashed and compressed by <see cref="AppxBlockPipeline"/>,
+    /// and returns its block map description
+    /// </summary>
+    /// <param name="size">Uncompressed size of the entry, which decides the local header layout up front</param>
+    /// <param name="compressed">Whether the blocks hold deflate fragments rather than the raw data</param>
+    public async Task<AppxBlockMapFile> AddEntryAsync(
+        string zipName,
+        long size,
+        bool compressed,
+        DateTime lastWriteTime,
+        IAsyncEnumerable<AppxProcessedBlock> processedBlocks,
+        CancellationToken cancellationToken = default)
+    {
+        // Same rule as for streamed entries: empty files are stored and have no blocks
+        compressed &= size > 0;
+
+        var entry = new ZipEntryRecord(
+            zipName,
+            compressed ? ZipFormat.MethodDeflated : ZipFormat.MethodStored,
+            ZipFormat.GetNameFlags(zipName),
+            ZipFormat.ToDosDateTime(lastWriteTime),
+            Crc32: 0,
+            CompressedSize: 0,
+            UncompressedSize: 0,
+            LocalHeaderOffset: stream.Position);
+
+        var zip64 = ZipFormat.RequiresZip64LocalHeader(size);
+        var localHeader = ZipFormat.EncodeLocalHeader(entry, zip64);
+        await stream.WriteAsync(localHeader, cancellationToken);
+        var dataStart = stream.Position;
+
+        var blocks = new List<AppxBlock>();
+        uint crc = 0;
+        long written = 0;
+        await foreach (var block in processedBlocks.WithCancellation(cancellationToken))
+        {
+            await stream.WriteAsync(block.Data, cancellationToken);
+            crc = Crc32.Combine(crc, block.Crc32, block.Length);
+            written += block.Length;
+            blocks.Add(new AppxBlock(block.Hash, compressed ? block.Data.Length : null));
+        }
+
+        if (written != size)
+        {
+            throw new InvalidOperationException($"'{zipName}' changed while it was being packed");
+        }
+
+        if (compressed)
+        {
+            await stream.WriteAsync(AppxBlockPipeline.FinalDeflateBlock, cancellationToken);
+            blocks[^1] = blocks[^1] with { CompressedSize = blocks[^1].CompressedSize + AppxBlockPipeline.FinalDeflateBlock.Length };
+        }
+
+        await CompleteEntryAsync(entry, zip64, dataStart, crc, size, cancellationToken);
+        return new AppxBlockMapFile(AppxBlockMap.ToBlockMapName(zipName), size, localHeader.Length, blocks);
+    }
+
+    public async Task<AppxBlockMapFile> AddEntryAsync(string zipName, byte[] data, bool compress, CancellationToken cancellationToken = default)
+    {
+        using var source = new MemoryStream(data, writable: false);
+        return await AddEntryAsync(zipName, source, compress, DateTime.Now, cancellationToken);
+    }
+
+    /// <summary>
+    /// Patches the local header with the final sizes and CRC and records the entry for the central directory
+    /// </summary>
+    private async Task CompleteEntryAsync(ZipEntryRecord entry, bool zip64, long dataStart, uint crc, long size, CancellationToken cancellationToken)
+    {
+        var dataEnd = stream.Position;
+        entry = entry with
+        {
+            Crc32 = crc,
+            CompressedSize = dataEnd - dataStart,
+            UncompressedSize = size
+        };
+
+        stream.Position = entry.LocalHeaderOffset;
+        await stream.WriteAsync(ZipFormat.EncodeLocalHeader(entry, zip64), cancellationToken);
+        stream.Position = dataEnd;
+
+        entries.Add(entry);
+    }
+
+    /// <summary>
+    /// Copies an existing entry's local record (header, data and any data descriptor) verbatim from another archive
+    /// </summary>
+    public async Task CopyEntryAsync(Stream sourceArchive, ZipEntryRecord entry, long recordLength, CancellationToken cancellationToken = default)
+    {
+        var copied = entry with { LocalHeaderOffset = stream.Position };
+
+        sourceArchive.Position = entry.LocalHeaderOffset;
+        var buffer = ArrayPool<byte>.Shared.Rent(81920);
+        try
+        {
+            var remaining = recordLength;
+            while (remaining > 0)
+            {
+                var read = await sourceArchive.ReadAsync(buffer.AsMemory(0, (int)Math.Min(buffer.Length, remaining)), cancellationToken);
+                if (read == 0)
+                {
+                    throw new EndOfStreamException($"Unexpected end of archive while copying '{entry.Name}'");
+                }
+                await stream.WriteAsync(buffer.AsMemory(0, read), cancellationToken);
+                remaining -= read;
+            }
+        }
+        finally
+        {
+            ArrayPool<byte>.Shared.Return(buffer);
+        }
+
+        entries.Add(copied);
+    }
+
+    /// <summary>
+    /// Writes the central directory and end of central directory records
+    /// </summary>
+    public async Task FinishAsync(CancellationToken cancellationToken = default)
+    {
+        var centralDirectoryOffset = stream.Position;
+        var centralDirectory = ZipFormat.EncodeCentralDirectory(entries);
+        await stream.WriteAsync(centralDirectory, cancellationToken);
+        await stream.WriteAsync(ZipFormat.EncodeEndOfCentralDirectory(entries.Count, centralDirectory.Length, centralDirectoryOffset), cancellationToken);
+        await stream.FlushAsync(cancellationToken);
+    }
+}
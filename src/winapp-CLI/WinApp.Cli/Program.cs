@@ -61,6 +61,11 @@ internal static class Program
 
         using var serviceProvider = services.BuildServiceProvider();
 
+        if (args.Contains(WinAppRootCommand.FipsOption.Name))
+        {
+            serviceProvider.GetRequiredService<IFipsPolicyService>().IsEnabled = true;
+        }
+
         var firstRunService = serviceProvider.GetRequiredService<IFirstRunService>();
         var didShowFirstRunNotice = firstRunService.CheckAndDisplayFirstRunNotice();
 
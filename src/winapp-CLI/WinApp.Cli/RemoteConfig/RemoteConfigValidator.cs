@@ -0,0 +1,232 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text.Json;
+using System.Text.RegularExpressions;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.RemoteConfig;
+
+/// <param name="Path">Where in the document the finding is, such as flags.new-editor.rules[1].rollout</param>
+internal sealed record RemoteConfigFinding(AccessibilitySeverity Severity, string Path, string Message);
+
+/// <summary>
+/// Checks a remote configuration document, which the RemoteConfig of the winapp-runtime crate evaluates, before it is
+/// published: a document the runtime can't read leaves every install on the flags it cached last
+/// </summary>
+/// <remarks>
+/// The document has a schemaVersion and flags, each with a default value and rules that change it for some channels,
+/// versions or a percentage of installs. The first rule that matches gives the value.
+/// </remarks>
+internal static partial class RemoteConfigValidator
+{
+    /// <summary>
+    /// The newest schemaVersion, SCHEMA_VERSION of the winapp-runtime crate
+    /// </summary>
+    public const int SchemaVersion = 1;
+
+    private static readonly string[] DocumentProperties = ["schemaVersion", "flags"];
+    private static readonly string[] FlagProperties = ["description", "default", "rules"];
+    private static readonly string[] RuleProperties = ["description", "channels", "minVersion", "maxVersion", "rollout", "value"];
+
+    /// <param name="channels">Channels of 'matrix: channels:' in winapp.yaml, which rules are expected to name; null to accept any</param>
+    public static IReadOnlyList<RemoteConfigFinding> Validate(string json, IReadOnlyCollection<string>? channels)
+    {
+        var findings = new List<RemoteConfigFinding>();
+        JsonDocument document;
+        try
+        {
+            document = JsonDocument.Parse(json, new JsonDocumentOptions { CommentHandling = JsonCommentHandling.Disallow });
+        }
+        catch (JsonException ex)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, "$", $"Not valid JSON: {ex.Message}"));
+            return findings;
+        }
+
+        using (document)
+        {
+            var root = document.RootElement;
+            if (root.ValueKind != JsonValueKind.Object)
+            {
+                findings.Add(new(AccessibilitySeverity.Error, "$", "Expected an object with schemaVersion and flags"));
+                return findings;
+            }
+            CheckProperties(root, "$", DocumentProperties, findings);
+
+            if (!root.TryGetProperty("schemaVersion", out var schemaVersion))
+            {
+                findings.Add(new(AccessibilitySeverity.Error, "schemaVersion", "Missing; the runtime doesn't read documents without one"));
+            }
+            else if (schemaVersion.ValueKind != JsonValueKind.Number || !schemaVersion.TryGetInt32(out var version) || version < 1)
+            {
+                findings.Add(new(AccessibilitySeverity.Error, "schemaVersion", "Expected a positive integer"));
+            }
+            else if (version > SchemaVersion)
+            {
+                findings.Add(new(AccessibilitySeverity.Error, "schemaVersion", $"{version} is newer than {SchemaVersion}, so the runtime ignores the document"));
+            }
+
+            if (!root.TryGetProperty("flags", out var flags) || flags.ValueKind != JsonValueKind.Object)
+            {
+                findings.Add(new(AccessibilitySeverity.Error, "flags", "Expected an object of flags"));
+                return findings;
+            }
+            foreach (var flag in flags.EnumerateObject())
+            {
+                CheckFlag(flag.Name, flag.Value, channels, findings);
+            }
+        }
+        return findings;
+    }
+
+    private static void CheckFlag(string name, JsonElement flag, IReadOnlyCollection<string>? channels, List<RemoteConfigFinding> findings)
+    {
+        var path = $"flags.{name}";
+        if (!FlagNameRegex().IsMatch(name))
+        {
+            findings.Add(new(AccessibilitySeverity.Error, path, "Flag names start with a letter or digit and have only letters, digits, '.', '_' and '-'"));
+        }
+        if (flag.ValueKind != JsonValueKind.Object)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, path, "Expected an object with a default and rules"));
+            return;
+        }
+        CheckProperties(flag, path, FlagProperties, findings);
+
+        JsonValueKind? kind = null;
+        if (!flag.TryGetProperty("default", out var defaultValue))
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.default", "Missing; installs that no rule matches get this value"));
+        }
+        else if (CheckScalar(defaultValue, $"{path}.default", findings))
+        {
+            kind = KindOf(defaultValue);
+        }
+
+        if (!flag.TryGetProperty("rules", out var rules))
+        {
+            return;
+        }
+        if (rules.ValueKind != JsonValueKind.Array)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.rules", "Expected an array of rules"));
+            return;
+        }
+        var index = 0;
+        foreach (var rule in rules.EnumerateArray())
+        {
+            CheckRule(rule, $"{path}.rules[{index++}]", kind, channels, findings);
+        }
+    }
+
+    private static void CheckRule(JsonElement rule, string path, JsonValueKind? kind, IReadOnlyCollection<string>? channels, List<RemoteConfigFinding> findings)
+    {
+        if (rule.ValueKind != JsonValueKind.Object)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, path, "Expected an object with a value"));
+            return;
+        }
+        CheckProperties(rule, path, RuleProperties, findings);
+
+        if (!rule.TryGetProperty("value", out var value))
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.value", "Missing; the runtime ignores the document"));
+        }
+        else if (CheckScalar(value, $"{path}.value", findings) && kind != null && KindOf(value) != kind)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.value", $"Is a {Describe(KindOf(value))} but the default is a {Describe(kind.Value)}"));
+        }
+
+        if (rule.TryGetProperty("channels", out var ruleChannels))
+        {
+            if (ruleChannels.ValueKind != JsonValueKind.Array || ruleChannels.GetArrayLength() == 0)
+            {
+                findings.Add(new(AccessibilitySeverity.Error, $"{path}.channels", "Expected a non-empty array of channel names"));
+            }
+            else
+            {
+                foreach (var channel in ruleChannels.EnumerateArray())
+                {
+                    if (channel.ValueKind != JsonValueKind.String || string.IsNullOrWhiteSpace(channel.GetString()))
+                    {
+                        findings.Add(new(AccessibilitySeverity.Error, $"{path}.channels", "Expected channel names"));
+                    }
+                    else if (channels != null && !channels.Contains(channel.GetString()!, StringComparer.OrdinalIgnoreCase))
+                    {
+                        findings.Add(new(AccessibilitySeverity.Warning, $"{path}.channels", $"'{channel.GetString()}' isn't one of the channels of winapp.yaml ({string.Join(", ", channels)}), so the rule may never match"));
+                    }
+                }
+            }
+        }
+
+        var minVersion = CheckVersion(rule, "minVersion", path, findings);
+        var maxVersion = CheckVersion(rule, "maxVersion", path, findings);
+        if (minVersion != null && maxVersion != null && minVersion > maxVersion)
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.maxVersion", $"Is lower than minVersion {minVersion}, so the rule never matches"));
+        }
+
+        if (rule.TryGetProperty("rollout", out var rollout)
+            && (rollout.ValueKind != JsonValueKind.Number || rollout.GetDouble() is < 0 or > 100))
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.rollout", "Expected the percentage of installs, from 0 to 100"));
+        }
+    }
+
+    private static void CheckProperties(JsonElement element, string path, string[] known, List<RemoteConfigFinding> findings)
+    {
+        foreach (var property in element.EnumerateObject())
+        {
+            if (!known.Contains(property.Name))
+            {
+                var propertyPath = path == "$" ? property.Name : $"{path}.{property.Name}";
+                findings.Add(new(AccessibilitySeverity.Error, propertyPath, $"Unknown property; expected {string.Join(", ", known)}"));
+            }
+        }
+    }
+
+    private static bool CheckScalar(JsonElement value, string path, List<RemoteConfigFinding> findings)
+    {
+        if (value.ValueKind is JsonValueKind.True or JsonValueKind.False or JsonValueKind.Number or JsonValueKind.String)
+        {
+            return true;
+        }
+        findings.Add(new(AccessibilitySeverity.Error, path, "Expected a boolean, number or string"));
+        return false;
+    }
+
+    /// <summary>
+    /// The runtime compares versions of up to four numeric parts, like package versions, with missing parts as 0
+    /// </summary>
+    private static Version? CheckVersion(JsonElement rule, string property, string path, List<RemoteConfigFinding> findings)
+    {
+        if (!rule.TryGetProperty(property, out var value))
+        {
+            return null;
+        }
+        var text = value.ValueKind == JsonValueKind.String ? value.GetString()! : "";
+        var parts = text.Split('.');
+        if (parts.Length > 4 || !parts.All(p => int.TryParse(p, NumberStyles.None, CultureInfo.InvariantCulture, out _)))
+        {
+            findings.Add(new(AccessibilitySeverity.Error, $"{path}.{property}", "Expected a version such as 1.2.0.0"));
+            return null;
+        }
+        var numbers = parts.Select(p => int.Parse(p, CultureInfo.InvariantCulture)).Concat(Enumerable.Repeat(0, 4 - parts.Length)).ToArray();
+        return new Version(numbers[0], numbers[1], numbers[2], numbers[3]);
+    }
+
+    private static JsonValueKind KindOf(JsonElement value) =>
+        value.ValueKind == JsonValueKind.False ? JsonValueKind.True : value.ValueKind;
+
+    private static string Describe(JsonValueKind kind) => kind switch
+    {
+        JsonValueKind.True => "boolean",
+        JsonValueKind.Number => "number",
+        _ => "string",
+    };
+
+    [GeneratedRegex("^[A-Za-z0-9][A-Za-z0-9._-]*$")]
+    private static partial Regex FlagNameRegex();
+}
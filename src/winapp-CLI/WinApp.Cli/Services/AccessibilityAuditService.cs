@@ -0,0 +1,342 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class AccessibilityAuditService : IAccessibilityAuditService
+{
+    // WCAG 2.1: 3:1 for graphical objects (1.4.11), 4.5:1 for text (1.4.3)
+    internal const double GraphicsContrast = 3.0;
+    internal const double TextContrast = 4.5;
+
+    // Medium tiles show about this many characters of the name before truncating it
+    internal const int TileNameLength = 13;
+
+    // Surfaces unplated icons are drawn on: the dark and light taskbar and Start
+    private static readonly (string Name, Color Color)[] ThemeSurfaces =
+    [
+        ("dark", Color.FromArgb(0x20, 0x20, 0x20)),
+        ("light", Color.FromArgb(0xF3, 0xF3, 0xF3)),
+    ];
+
+    private static readonly int[] UnplatedTargetSizes = [16, 24, 32, 48, 256];
+
+    private static readonly string[] HighContrastQualifiers = ["contrast-high", "contrast-black", "contrast-white"];
+
+    public Task<AccessibilityReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var root = XDocument.Load(manifestPath.FullName).Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var manifestDirectory = manifestPath.Directory!;
+        var issues = new List<AccessibilityIssue>();
+        var applications = new List<string>();
+
+        foreach (var application in Children(root, "Applications").SelectMany(a => Children(a, "Application")))
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+
+            var visualElements = Children(application, "VisualElements").FirstOrDefault();
+            if (visualElements == null)
+            {
+                continue;
+            }
+
+            var id = application.Attribute("Id")?.Value ?? "App";
+            applications.Add(id);
+            taskContext.AddDebugMessage($"{UiSymbols.Search} Checking the visual elements of {id}");
+
+            var background = ParseColor(visualElements.Attribute("BackgroundColor")?.Value);
+            var defaultTile = Children(visualElements, "DefaultTile").FirstOrDefault();
+            var splashScreen = Children(visualElements, "SplashScreen").FirstOrDefault();
+
+            // Tiles
+            foreach (var attribute in new[] { "Square150x150Logo", "Square71x71Logo", "Square310x310Logo", "Wide310x150Logo" })
+            {
+                var logo = (attribute == "Square150x150Logo" ? visualElements : defaultTile)?.Attribute(attribute)?.Value;
+                if (logo == null)
+                {
+                    continue;
+                }
+
+                var variants = FindVariants(manifestDirectory, logo, attribute, issues);
+                if (variants.Count == 0)
+                {
+                    continue;
+                }
+
+                if (background is Color tileBackground && tileBackground.A == 255)
+                {
+                    CheckContrast(PickImage(variants), tileBackground, attribute, "tile background", GraphicsContrast, issues);
+                }
+                CheckHighContrast(variants, attribute, issues, attribute == "Square150x150Logo" ? AccessibilitySeverity.Warning : AccessibilitySeverity.Info);
+            }
+
+            if (background is Color textBackground && textBackground.A == 255 && ShowsNameOnTiles(defaultTile))
+            {
+                var ratio = ContrastRatio(Color.White, textBackground);
+                if (ratio < TextContrast)
+                {
+                    issues.Add(new AccessibilityIssue(AccessibilitySeverity.Warning, "contrast", "BackgroundColor",
+                        $"The white tile name has a contrast of {ratio:0.0}:1 on {ToHex(textBackground)}; text needs {TextContrast}:1"));
+                }
+            }
+
+            // App list, taskbar and title bar icon
+            var iconPath = visualElements.Attribute("Square44x44Logo")?.Value;
+            if (iconPath != null)
+            {
+                var variants = FindVariants(manifestDirectory, iconPath, "Square44x44Logo", issues);
+                if (variants.Count > 0)
+                {
+                    CheckUnplated(variants, issues);
+                    CheckHighContrast(variants, "Square44x44Logo", issues, AccessibilitySeverity.Warning);
+                }
+            }
+
+            // Splash screen
+            if (splashScreen?.Attribute("Image")?.Value is string splashImage)
+            {
+                var variants = FindVariants(manifestDirectory, splashImage, "SplashScreen", issues);
+                var splashBackground = ParseColor(splashScreen.Attribute("BackgroundColor")?.Value) ?? background;
+                if (variants.Count > 0 && splashBackground is Color color && color.A == 255)
+                {
+                    CheckContrast(PickImage(variants), color, "SplashScreen", "splash screen background", GraphicsContrast, issues);
+                }
+                if (variants.Count > 0)
+                {
+                    CheckHighContrast(variants, "SplashScreen", issues, AccessibilitySeverity.Info);
+                }
+            }
+
+            // Tile name
+            var shortName = defaultTile?.Attribute("ShortName")?.Value;
+            var name = shortName ?? visualElements.Attribute("DisplayName")?.Value;
+            var nameAttribute = shortName != null ? "ShortName" : "DisplayName";
+            if (name != null && name.StartsWith("ms-resource:", StringComparison.OrdinalIgnoreCase))
+            {
+                issues.Add(new AccessibilityIssue(AccessibilitySeverity.Info, "short-name", nameAttribute,
+                    $"{nameAttribute} comes from resources ({name}); check the length of each translation"));
+            }
+            else if (name != null && name.Length > TileNameLength)
+            {
+                issues.Add(new AccessibilityIssue(AccessibilitySeverity.Warning, "short-name", nameAttribute,
+                    $"'{name}' has {name.Length} characters and is truncated on medium tiles and in Start; set a ShortName of at most {TileNameLength} characters on uap:DefaultTile"));
+            }
+        }
+
+        // Store listing and package logo
+        var storeLogo = Children(root, "Properties").SelectMany(p => Children(p, "Logo")).FirstOrDefault()?.Value;
+        if (storeLogo != null)
+        {
+            var variants = FindVariants(manifestDirectory, storeLogo, "Logo", issues);
+            if (variants.Count > 0)
+            {
+                CheckHighContrast(variants, "Logo", issues, AccessibilitySeverity.Info);
+            }
+        }
+
+        return Task.FromResult(new AccessibilityReport(applications, issues));
+    }
+
+    private static IEnumerable<XElement> Children(XElement element, string localName) =>
+        element.Elements().Where(e => e.Name.LocalName == localName);
+
+    private static bool ShowsNameOnTiles(XElement? defaultTile) =>
+        defaultTile != null && Children(defaultTile, "ShowNameOnTiles").SelectMany(s => Children(s, "ShowOn")).Any();
+
+    /// <summary>
+    /// Finds the file of an asset and its MRT qualified variants, e.g. Logo.png, Logo.scale-200.png and
+    /// Logo.targetsize-24_altform-unplated.png
+    /// </summary>
+    internal static List<FileInfo> FindVariants(DirectoryInfo manifestDirectory, string assetPath, string subject, List<AccessibilityIssue> issues)
+    {
+        // Resources are resolved through resources.pri, which manifest/logo checks
+        if (AppxManifestSchema.IsResourceReference(assetPath))
+        {
+            return [];
+        }
+
+        var logicalFile = new FileInfo(Path.Combine(manifestDirectory.FullName, assetPath.Replace('\\', Path.DirectorySeparatorChar)));
+        var baseName = Path.GetFileNameWithoutExtension(logicalFile.Name);
+        var variants = logicalFile.Directory is { Exists: true } directory
+            ? directory.EnumerateFiles(baseName + "*" + logicalFile.Extension)
+                .Where(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name)))
+                .OrderBy(f => f.Name, StringComparer.OrdinalIgnoreCase)
+                .ToList()
+            : [];
+
+        if (variants.Count == 0)
+        {
+            issues.Add(new AccessibilityIssue(AccessibilitySeverity.Error, "asset", subject, $"{assetPath} not found, in any scale or variant"));
+        }
+
+        return variants;
+    }
+
+    private static List<string> Qualifiers(FileInfo variant) =>
+        [.. Path.GetFileNameWithoutExtension(variant.Name).Split('.').Skip(1).SelectMany(q => q.Split('_'))];
+
+    // Scale or target size of a variant, to pick the most detailed one
+    private static int Scale(FileInfo variant) =>
+        Qualifiers(variant)
+            .Select(q => q.Split('-'))
+            .Where(p => p.Length == 2 && (p[0].Equals("scale", StringComparison.OrdinalIgnoreCase) || p[0].Equals("targetsize", StringComparison.OrdinalIgnoreCase)))
+            .Select(p => int.TryParse(p[1], out var value) ? value : 0)
+            .DefaultIfEmpty(100)
+            .Max();
+
+    /// <summary>
+    /// Picks the variant that is shown in standard contrast: the largest scale without an altform or contrast
+    /// qualifier
+    /// </summary>
+    private static FileInfo PickImage(List<FileInfo> variants)
+    {
+        var standard = variants
+            .Where(v => !Qualifiers(v).Any(q => q.StartsWith("altform-", StringComparison.OrdinalIgnoreCase) || (q.StartsWith("contrast-", StringComparison.OrdinalIgnoreCase) && !q.Equals("contrast-standard", StringComparison.OrdinalIgnoreCase))))
+            .ToList();
+        return (standard.Count > 0 ? standard : variants).MaxBy(Scale)!;
+    }
+
+    private static void CheckContrast(FileInfo image, Color background, string subject, string surface, double required, List<AccessibilityIssue> issues)
+    {
+        var foreground = GetForegroundColor(image);
+        if (foreground is not Color color)
+        {
+            issues.Add(new AccessibilityIssue(AccessibilitySeverity.Warning, "contrast", subject, $"{image.Name} is fully transparent"));
+            return;
+        }
+
+        var ratio = ContrastRatio(color, background);
+        if (ratio < required)
+        {
+            issues.Add(new AccessibilityIssue(AccessibilitySeverity.Warning, "contrast", subject,
+                $"{image.Name} has a contrast of {ratio:0.0}:1 on the {surface} {ToHex(background)}; graphics need {required}:1"));
+        }
+    }
+
+    private static void CheckUnplated(List<FileInfo> variants, List<AccessibilityIssue> issues)
+    {
+        var unplated = variants.Where(v => Qualifiers(v).Contains("altform-unplated", StringComparer.OrdinalIgnoreCase)).ToList();
+        var lightUnplated = variants.Where(v => Qualifiers(v).Contains("altform-lightunplated", StringComparer.OrdinalIgnoreCase)).ToList();
+        if (unplated.Count == 0)
+        {
+            issues.Add(new AccessibilityIssue(AccessibilitySeverity.Warning, "unplated", "Square44x44Logo",
+                "No altform-unplated variants; the taskbar and Start draw the icon on a plate of the tile color. Add e.g. Square44x44Logo.targetsize-24_altform-unplated.png"));
+            return;
+        }
+
+        var sizes = unplated.SelectMany(Qualifiers)
+            .Where(q => q.StartsWith("targetsize-", StringComparison.OrdinalIgnoreCase))
+            .Select(q => int.TryParse(q["targetsize-".Length..], out var size) ? size : 0)
+            .ToHashSet();
+        var missing = UnplatedTargetSizes.Where(s => !sizes.Contains(s)).ToList();
+        if (missing.Count > 0)
+        {
+            issues.Add(new AccessibilityIssue(AccessibilitySeverity.Info, "unplated", "Square44x44Logo",
+                $"No unplated icon for target size(s) {string.Join(", ", missing)}; Windows scales another size, which blurs the icon"));
+        }
+
+        // The unplated icon is drawn on both taskbar themes unless a light theme variant exists
+        var image = unplated.MaxBy(Scale)!;
+        foreach (var (theme, surface) in ThemeSurfaces)
+        {
+            if (theme == "light" && lightUnplated.Count > 0)
+            {
+                image = lightUnplated.MaxBy(Scale)!;
+            }
+            CheckContrast(image, surface, "Square44x44Logo", $"{theme} taskbar", GraphicsContrast, issues);
+        }
+    }
+
+    private static void CheckHighContrast(List<FileInfo> variants, string subject, List<AccessibilityIssue> issues, AccessibilitySeverity severity)
+    {
+        var hasHighContrast = variants.Any(v => Qualifiers(v).Any(q => HighContrastQualifiers.Contains(q, StringComparer.OrdinalIgnoreCase)));
+        if (!hasHighContrast)
+        {
+            issues.Add(new AccessibilityIssue(severity, "high-contrast", subject,
+                $"No contrast-black, contrast-white or contrast-high variant of {Path.GetFileNameWithoutExtension(variants[0].Name).Split('.')[0]}; high contrast themes show the standard asset"));
+        }
+    }
+
+    /// <returns>The color of the opaque pixel with the median luminance, or null when no pixel is opaque</returns>
+    internal static Color? GetForegroundColor(FileInfo image)
+    {
+        using var bitmap = new Bitmap(image.FullName);
+
+        // Sample up to 128x128 pixels, which is enough for the median of a logo
+        var stepX = Math.Max(1, bitmap.Width / 128);
+        var stepY = Math.Max(1, bitmap.Height / 128);
+        var pixels = new List<(double Luminance, Color Color)>();
+        for (var y = 0; y < bitmap.Height; y += stepY)
+        {
+            for (var x = 0; x < bitmap.Width; x += stepX)
+            {
+                var pixel = bitmap.GetPixel(x, y);
+                if (pixel.A >= 128)
+                {
+                    pixels.Add((RelativeLuminance(pixel), pixel));
+                }
+            }
+        }
+
+        if (pixels.Count == 0)
+        {
+            return null;
+        }
+
+        pixels.Sort((a, b) => a.Luminance.CompareTo(b.Luminance));
+        return pixels[pixels.Count / 2].Color;
+    }
+
+    /// <summary>
+    /// Parses a manifest color: #RRGGBB, a named color or transparent
+    /// </summary>
+    internal static Color? ParseColor(string? value)
+    {
+        if (string.IsNullOrWhiteSpace(value))
+        {
+            return null;
+        }
+
+        try
+        {
+            return ColorTranslator.FromHtml(value.Trim());
+        }
+        catch (Exception ex) when (ex is ArgumentException or FormatException)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// WCAG 2.1 relative luminance of an sRGB color
+    /// </summary>
+    internal static double RelativeLuminance(Color color)
+    {
+        static double Channel(byte value)
+        {
+            var c = value / 255.0;
+            return c <= 0.03928 ? c / 12.92 : Math.Pow((c + 0.055) / 1.055, 2.4);
+        }
+
+        return (0.2126 * Channel(color.R)) + (0.7152 * Channel(color.G)) + (0.0722 * Channel(color.B));
+    }
+
+    /// <summary>
+    /// WCAG 2.1 contrast ratio, from 1:1 for the same color to 21:1 for black on white
+    /// </summary>
+    internal static double ContrastRatio(Color first, Color second)
+    {
+        var a = RelativeLuminance(first);
+        var b = RelativeLuminance(second);
+        return (Math.Max(a, b) + 0.05) / (Math.Min(a, b) + 0.05);
+    }
+
+    private static string ToHex(Color color) => $"#{color.R:X2}{color.G:X2}{color.B:X2}";
+}
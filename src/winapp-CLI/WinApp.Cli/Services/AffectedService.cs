@@ -0,0 +1,105 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class AffectedService(IGitService gitService, IConfigService configService) : IAffectedService
+{
+    private static readonly string[] ManifestPatterns = [":(glob,icase)**/appxmanifest.xml", ":(glob,icase)**/package.appxmanifest"];
+
+    public async Task<AffectedResult> FindAsync(string since, bool includeUncommitted, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var directory = configService.ConfigPath.DirectoryName!;
+
+        var projects = configService.Load().Projects;
+        var discovered = projects.Count == 0;
+        if (discovered)
+        {
+            // Without declared projects, every folder with a manifest is one
+            var manifests = SplitPaths(await gitService.RunAsync(directory, ["ls-files", "-z", "--", .. ManifestPatterns], cancellationToken));
+            projects = DiscoverProjects(manifests);
+            taskContext.AddDebugMessage($"winapp.yaml declares no projects; found {projects.Count} from their manifests");
+        }
+
+        // Three dots compare with the merge base, so changes that landed on 'since' after branching are not counted
+        var changed = new SortedSet<string>(StringComparer.Ordinal);
+        changed.UnionWith(SplitPaths(await gitService.RunAsync(directory, ["diff", "--name-only", "--relative", "-z", $"{since}...HEAD"], cancellationToken)));
+        if (includeUncommitted)
+        {
+            changed.UnionWith(SplitPaths(await gitService.RunAsync(directory, ["diff", "--name-only", "--relative", "-z", "HEAD"], cancellationToken)));
+            changed.UnionWith(SplitPaths(await gitService.RunAsync(directory, ["ls-files", "-z", "--others", "--exclude-standard"], cancellationToken)));
+        }
+        taskContext.AddDebugMessage($"{changed.Count} file(s) changed since {since}");
+
+        return new AffectedResult(since, [.. changed], projects, Match(projects, changed), discovered);
+    }
+
+    /// <summary>
+    /// Maps changed files to the projects that contain them or list them as inputs. A file belongs to the most
+    /// specific project whose folder contains it, and a change to winapp.yaml affects every project.
+    /// </summary>
+    internal static List<AffectedProject> Match(IReadOnlyList<ProjectConfig> projects, IEnumerable<string> changedFiles)
+    {
+        var files = projects.ToDictionary(p => p, _ => new List<string>());
+        foreach (var file in changedFiles.Select(NormalizePath))
+        {
+            if (file.Equals("winapp.yaml", StringComparison.OrdinalIgnoreCase))
+            {
+                foreach (var list in files.Values)
+                {
+                    list.Add(file);
+                }
+                continue;
+            }
+
+            var owner = projects
+                .Where(p => IsUnder(file, p.Path))
+                .MaxBy(p => NormalizePath(p.Path).Length);
+            if (owner != null)
+            {
+                files[owner].Add(file);
+            }
+
+            foreach (var project in projects.Where(p => p != owner && p.Inputs.Any(i => IsUnder(file, i))))
+            {
+                files[project].Add(file);
+            }
+        }
+
+        return [.. projects
+            .Where(p => files[p].Count > 0)
+            .Select(p => new AffectedProject(p.Name, NormalizePath(p.Path), files[p]))];
+    }
+
+    internal static List<ProjectConfig> DiscoverProjects(IEnumerable<string> manifests)
+    {
+        return [.. manifests
+            .Select(m => NormalizePath(Path.GetDirectoryName(m) ?? ""))
+            .Distinct(StringComparer.OrdinalIgnoreCase)
+            .Order(StringComparer.OrdinalIgnoreCase)
+            .Select(path => new ProjectConfig
+            {
+                Name = path.Length == 0 ? "." : path[(path.LastIndexOf('/') + 1)..],
+                Path = path,
+            })];
+    }
+
+    private static bool IsUnder(string file, string path)
+    {
+        path = NormalizePath(path);
+        return path.Length == 0
+            || file.Equals(path, StringComparison.OrdinalIgnoreCase)
+            || file.StartsWith(path + "/", StringComparison.OrdinalIgnoreCase);
+    }
+
+    private static string NormalizePath(string path)
+    {
+        path = path.Replace('\\', '/').Trim('/');
+        return path == "." ? "" : path.StartsWith("./", StringComparison.Ordinal) ? path[2..] : path;
+    }
+
+    private static IEnumerable<string> SplitPaths(string output) => output.Split('\0', StringSplitOptions.RemoveEmptyEntries);
+}
@@ -0,0 +1,267 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.ComponentModel;
+using System.Diagnostics;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class AnalyzerPluginService(IConfigService configService) : IAnalyzerPluginService
+{
+    /// <summary>
+    /// Version of the JSON an analyzer reads; bumped when a property changes meaning or goes away
+    /// </summary>
+    internal const int ProtocolVersion = 1;
+
+    private static readonly TimeSpan Timeout = TimeSpan.FromMinutes(1);
+
+    // Build output and dependencies aren't payload the project authors
+    private static readonly HashSet<string> SkippedDirectories = new(StringComparer.OrdinalIgnoreCase) { "bin", "obj", "node_modules" };
+
+    public async Task<IReadOnlyList<AnalyzerDiagnostic>> RunAsync(FileInfo manifestPath, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        if (config.Analyzers.Count == 0)
+        {
+            return [];
+        }
+
+        var configText = await File.ReadAllTextAsync(configService.ConfigPath.FullName, cancellationToken);
+        var diagnostics = new List<AnalyzerDiagnostic>();
+        foreach (var analyzer in config.Analyzers)
+        {
+            var input = BuildInput(analyzer.Id, manifestPath, configService.ConfigPath, configText);
+            var script = new FileInfo(Path.Combine(configService.ConfigPath.DirectoryName!, analyzer.Script));
+            if (!script.Exists)
+            {
+                throw new InvalidOperationException($"Analyzer '{analyzer.Id}': {script.FullName} not found");
+            }
+            if (IsScript(script) && !allowScriptAnalyzers)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} Skipped analyzer '{analyzer.Id}': {script.Name} runs with the permissions of winapp; pass 'winapp validate --allow-script-analyzers' to run it");
+                continue;
+            }
+
+            taskContext.AddDebugMessage($"{UiSymbols.Search} Running analyzer '{analyzer.Id}': {script.FullName}");
+            var output = await RunScriptAsync(analyzer.Id, script, input.ToJsonString(), cancellationToken);
+            var reported = ParseDiagnostics(analyzer.Id, output);
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Analyzer '{analyzer.Id}' reported {reported.Count} diagnostic(s)");
+            diagnostics.AddRange(reported);
+        }
+        return diagnostics;
+    }
+
+    /// <summary>
+    /// Whether the analyzer is a script or executable, which can do anything the user can, rather than a WASM module
+    /// that wasmtime runs without access to the file system, the network or the environment
+    /// </summary>
+    internal static bool IsScript(FileInfo script) => !script.Extension.Equals(".wasm", StringComparison.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// What an analyzer reads on stdin: the manifest with its elements already parsed, winapp.yaml and an index of
+    /// the files next to the manifest
+    /// </summary>
+    internal static JsonObject BuildInput(string analyzerId, FileInfo manifestPath, FileInfo configPath, string configText)
+    {
+        var manifestText = File.ReadAllText(manifestPath.FullName, Encoding.UTF8);
+        var root = XDocument.Parse(manifestText, LoadOptions.SetLineInfo).Root
+            ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+
+        var elements = root.DescendantsAndSelf().Select(e =>
+        {
+            var attributes = new JsonObject();
+            foreach (var attribute in e.Attributes().Where(a => !a.IsNamespaceDeclaration))
+            {
+                attributes[attribute.Name.LocalName] = attribute.Value;
+            }
+            return (JsonNode?)new JsonObject
+            {
+                ["path"] = AppxManifestLocator.PathOf(e),
+                ["name"] = e.Name.LocalName,
+                ["namespace"] = e.Name.NamespaceName,
+                ["line"] = ((IXmlLineInfo)e).LineNumber,
+                ["attributes"] = attributes,
+                ["text"] = e.HasElements ? null : e.Value.Trim(),
+            };
+        }).ToArray();
+
+        var manifestDirectory = manifestPath.Directory!;
+        var payload = EnumeratePayload(manifestDirectory)
+            .Select(f => (JsonNode?)new JsonObject
+            {
+                ["path"] = Path.GetRelativePath(manifestDirectory.FullName, f.FullName).Replace('\\', '/'),
+                ["size"] = f.Length,
+            })
+            .ToArray();
+
+        return new JsonObject
+        {
+            ["version"] = ProtocolVersion,
+            ["analyzer"] = analyzerId,
+            ["manifest"] = new JsonObject
+            {
+                ["path"] = manifestPath.FullName,
+                ["text"] = manifestText,
+                ["elements"] = new JsonArray(elements),
+            },
+            ["config"] = new JsonObject
+            {
+                ["path"] = configPath.FullName,
+                ["text"] = configText,
+            },
+            ["payload"] = new JsonArray(payload),
+        };
+    }
+
+    /// <summary>
+    /// Reads { "diagnostics": [ { "rule", "severity", "message", "element", "subject" } ] }. Rule ids without the
+    /// analyzer's id are prefixed with it, so contoso's "publisher" is contoso/publisher.
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The output isn't diagnostics, or one has no rule or message</exception>
+    internal static IReadOnlyList<AnalyzerDiagnostic> ParseDiagnostics(string analyzerId, string output)
+    {
+        if (string.IsNullOrWhiteSpace(output))
+        {
+            return [];
+        }
+
+        JsonArray items;
+        try
+        {
+            items = JsonNode.Parse(output)?["diagnostics"]?.AsArray() ?? [];
+        }
+        catch (Exception ex) when (ex is JsonException or InvalidOperationException)
+        {
+            throw new InvalidOperationException($"Analyzer '{analyzerId}' wrote something other than {{ \"diagnostics\": [...] }}: {ex.Message}", ex);
+        }
+
+        var diagnostics = new List<AnalyzerDiagnostic>();
+        foreach (var item in items.OfType<JsonObject>())
+        {
+            var rule = item["rule"]?.GetValue<string>();
+            var message = item["message"]?.GetValue<string>();
+            if (string.IsNullOrWhiteSpace(rule) || string.IsNullOrWhiteSpace(message))
+            {
+                throw new InvalidOperationException($"Analyzer '{analyzerId}' reported a diagnostic without a rule or message");
+            }
+
+            var level = item["severity"]?.GetValue<string>() ?? "warning";
+            if (!Enum.TryParse<AccessibilitySeverity>(level, ignoreCase: true, out var severity))
+            {
+                throw new InvalidOperationException($"Analyzer '{analyzerId}': '{level}' is not a severity for {rule}; use info, warning or error");
+            }
+
+            diagnostics.Add(new AnalyzerDiagnostic(
+                severity,
+                rule.StartsWith(analyzerId + "/", StringComparison.OrdinalIgnoreCase) ? rule : $"{analyzerId}/{rule}",
+                item["element"]?.GetValue<string>(),
+                item["subject"]?.GetValue<string>(),
+                message));
+        }
+        return diagnostics;
+    }
+
+    private static async Task<string> RunScriptAsync(string analyzerId, FileInfo script, string input, CancellationToken cancellationToken)
+    {
+        var psi = new ProcessStartInfo
+        {
+            UseShellExecute = false,
+            RedirectStandardInput = true,
+            RedirectStandardOutput = true,
+            RedirectStandardError = true,
+            StandardInputEncoding = new UTF8Encoding(encoderShouldEmitUTF8Identifier: false),
+            StandardOutputEncoding = Encoding.UTF8,
+            StandardErrorEncoding = Encoding.UTF8,
+            CreateNoWindow = true,
+            WorkingDirectory = script.DirectoryName!,
+        };
+        switch (script.Extension.ToLowerInvariant())
+        {
+            case ".ps1":
+                psi.FileName = "powershell.exe";
+                foreach (var argument in new[] { "-NoLogo", "-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-File", script.FullName })
+                {
+                    psi.ArgumentList.Add(argument);
+                }
+                break;
+            case ".js":
+            case ".mjs":
+            case ".cjs":
+                psi.FileName = "node";
+                psi.ArgumentList.Add(script.FullName);
+                break;
+            case ".wasm":
+                // WASI modules read stdin and write stdout like any other analyzer. No --dir, --env or network
+                // options, so the module can't reach the file system, the environment or the network
+                psi.FileName = "wasmtime";
+                psi.ArgumentList.Add("run");
+                psi.ArgumentList.Add(script.FullName);
+                break;
+            default:
+                psi.FileName = script.FullName;
+                break;
+        }
+
+        Process? process;
+        try
+        {
+            process = Process.Start(psi);
+        }
+        catch (Win32Exception ex)
+        {
+            throw new InvalidOperationException($"Failed to start analyzer '{analyzerId}' with {psi.FileName}; make sure it is installed and on PATH", ex);
+        }
+
+        using var p = process ?? throw new InvalidOperationException($"Failed to start analyzer '{analyzerId}'");
+        using var timeout = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+        timeout.CancelAfter(Timeout);
+        try
+        {
+            var stdout = p.StandardOutput.ReadToEndAsync(timeout.Token);
+            var stderr = p.StandardError.ReadToEndAsync(timeout.Token);
+            await p.StandardInput.WriteAsync(input.AsMemory(), timeout.Token);
+            p.StandardInput.Close();
+            await p.WaitForExitAsync(timeout.Token);
+            if (p.ExitCode != 0)
+            {
+                throw new InvalidOperationException($"Analyzer '{analyzerId}' exited with {p.ExitCode}: {(await stderr).Trim()}");
+            }
+
+            await stderr;
+            return await stdout;
+        }
+        catch (OperationCanceledException) when (!cancellationToken.IsCancellationRequested)
+        {
+            p.Kill(entireProcessTree: true);
+            throw new InvalidOperationException($"Analyzer '{analyzerId}' didn't finish within {Timeout.TotalSeconds:0} seconds");
+        }
+    }
+
+    private static IEnumerable<FileInfo> EnumeratePayload(DirectoryInfo directory)
+    {
+        foreach (var file in directory.EnumerateFiles())
+        {
+            yield return file;
+        }
+        foreach (var child in directory.EnumerateDirectories())
+        {
+            if (child.Name.StartsWith('.') || SkippedDirectories.Contains(child.Name))
+            {
+                continue;
+            }
+            foreach (var file in EnumeratePayload(child))
+            {
+                yield return file;
+            }
+        }
+    }
+}
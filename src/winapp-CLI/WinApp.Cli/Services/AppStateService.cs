@@ -0,0 +1,232 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using System.Text.Json;
+using System.Text.Json.Serialization;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+[JsonSerializable(typeof(AppStateSnapshot))]
+[JsonSourceGenerationOptions(
+    WriteIndented = true,
+    PropertyNamingPolicy = JsonKnownNamingPolicy.CamelCase)]
+internal partial class AppStateJsonContext : JsonSerializerContext
+{
+}
+
+internal class AppStateService(
+    IInstalledPackageService installedPackageService,
+    IWinappDirectoryService winappDirectoryService,
+    ICurrentDirectoryProvider currentDirectoryProvider) : IAppStateService
+{
+    private const string SnapshotsFolderName = "snapshots";
+    private const string DescriptionEntryName = "winapp-state.json";
+    private const string TempFolder = "TempState";
+
+    /// <summary>
+    /// Folders of the ApplicationData folder that hold the app's state: LocalFolder, RoamingFolder and
+    /// LocalCacheFolder, where full-trust apps' writes to %LOCALAPPDATA% and %APPDATA% are redirected; the settings
+    /// containers (Settings\settings.dat); and the registry writes of full-trust apps (SystemAppData\Helium). TempState
+    /// is cleared but not saved, and AC belongs to the app container rather than the app.
+    /// </summary>
+    internal static readonly string[] StateFolders = ["LocalState", "RoamingState", "LocalCache", "Settings", Path.Combine("SystemAppData", "Helium")];
+
+    public async Task<InstalledPackage> FindPackageAsync(string? name, FileInfo? manifest, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var (packages, _) = await installedPackageService.GetPackagesAsync(taskContext, cancellationToken);
+        List<InstalledPackage> matches;
+        if (name != null)
+        {
+            matches = [.. packages.Where(p => !p.Staged && p.Name.Equals(name, StringComparison.OrdinalIgnoreCase))];
+        }
+        else
+        {
+            manifest ??= MsixService.FindProjectManifest(currentDirectoryProvider)
+                ?? throw new InvalidOperationException("No appxmanifest.xml found in the current directory or its parents; pass --manifest or --name");
+            var identity = await MsixService.ParseAppxManifestFromPathAsync(manifest, cancellationToken);
+            name = identity.PackageName;
+            matches = InstalledPackageService.FindProjectPackages(packages, identity.PackageName);
+        }
+
+        if (matches.Count == 0)
+        {
+            throw new InvalidOperationException($"{name} is not installed; install it, or register it with 'winapp create-debug-identity'");
+        }
+        if (matches.Count > 1)
+        {
+            taskContext.AddDebugMessage($"Also installed: {string.Join(", ", matches.Skip(1).Select(p => p.Name))}; pass --name to pick one");
+        }
+        taskContext.AddDebugMessage($"Using {matches[0].FullName}");
+        return matches[0];
+    }
+
+    public FileInfo GetSnapshotFile(string nameOrPath)
+    {
+        if (nameOrPath.EndsWith(".zip", StringComparison.OrdinalIgnoreCase)
+            || nameOrPath.IndexOfAny([Path.DirectorySeparatorChar, Path.AltDirectorySeparatorChar]) >= 0)
+        {
+            return new FileInfo(Path.GetFullPath(nameOrPath, currentDirectoryProvider.GetCurrentDirectory()));
+        }
+
+        return new FileInfo(Path.Combine(winappDirectoryService.GetLocalWinappDirectory().FullName, SnapshotsFolderName, $"{nameOrPath}.zip"));
+    }
+
+    public async Task<int> ResetAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        await StopAppAsync(package, taskContext, cancellationToken);
+        return ClearState(new DirectoryInfo(package.AppDataPath));
+    }
+
+    public async Task<AppStateSnapshot> SnapshotAsync(InstalledPackage package, FileInfo file, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        await StopAppAsync(package, taskContext, cancellationToken);
+        return WriteSnapshot(new DirectoryInfo(package.AppDataPath), file, package.FamilyName, package.Version);
+    }
+
+    public async Task<AppStateSnapshot> RestoreAsync(InstalledPackage package, FileInfo file, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        if (!file.Exists)
+        {
+            throw new FileNotFoundException($"No snapshot at {file.FullName}; save one with 'winapp state snapshot'");
+        }
+
+        await StopAppAsync(package, taskContext, cancellationToken);
+        var snapshot = RestoreSnapshot(file, new DirectoryInfo(package.AppDataPath));
+        if (!snapshot.PackageFamilyName.Equals(package.FamilyName, StringComparison.OrdinalIgnoreCase))
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Warning} The snapshot was taken from {snapshot.PackageFamilyName}, not {package.FamilyName}");
+        }
+        return snapshot;
+    }
+
+    /// <summary>
+    /// Stops the app's processes, which keep its settings and registry hives open, along with the processes they
+    /// started, such as WebView2, which keeps its profile in LocalState
+    /// </summary>
+    private async Task StopAppAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var processes = await installedPackageService.GetProcessesAsync(package, taskContext, cancellationToken);
+        if (processes.Count == 0)
+        {
+            return;
+        }
+
+        await installedPackageService.StopProcessesAsync(processes, taskContext, cancellationToken);
+        taskContext.AddStatusMessage($"{UiSymbols.Note} Stopped {processes.Count} process(es) of {package.Name}: {InstalledPackageService.DescribeProcesses(processes)}");
+    }
+
+    /// <summary>
+    /// Deletes the files of the state folders; the folders stay, as Windows created them with the package's permissions
+    /// </summary>
+    /// <returns>Number of files deleted</returns>
+    internal static int ClearState(DirectoryInfo appData)
+    {
+        var count = 0;
+        foreach (var folder in StateFolders.Append(TempFolder))
+        {
+            var directory = new DirectoryInfo(Path.Combine(appData.FullName, folder));
+            if (!directory.Exists)
+            {
+                continue;
+            }
+
+            foreach (var file in directory.GetFiles("*", SearchOption.AllDirectories))
+            {
+                WhenNotInUse(file.FullName, () =>
+                {
+                    file.Attributes = FileAttributes.Normal;
+                    file.Delete();
+                });
+                count++;
+            }
+            foreach (var subdirectory in directory.GetDirectories())
+            {
+                WhenNotInUse(subdirectory.FullName, () => subdirectory.Delete(recursive: true));
+            }
+        }
+        return count;
+    }
+
+    internal static AppStateSnapshot WriteSnapshot(DirectoryInfo appData, FileInfo file, string packageFamilyName, string version)
+    {
+        var snapshot = new AppStateSnapshot { PackageFamilyName = packageFamilyName, Version = version, CreatedAt = DateTimeOffset.UtcNow };
+        file.Directory!.Create();
+
+        // A failed snapshot leaves the previous one with the same name in place
+        var temporaryPath = $"{file.FullName}.tmp";
+        using (var zip = ZipFile.Open(temporaryPath, ZipArchiveMode.Create))
+        {
+            foreach (var folder in StateFolders)
+            {
+                var directory = new DirectoryInfo(Path.Combine(appData.FullName, folder));
+                if (!directory.Exists)
+                {
+                    continue;
+                }
+
+                foreach (var stateFile in directory.EnumerateFiles("*", SearchOption.AllDirectories))
+                {
+                    var entryName = Path.GetRelativePath(appData.FullName, stateFile.FullName).Replace('\\', '/');
+                    WhenNotInUse(stateFile.FullName, () => zip.CreateEntryFromFile(stateFile.FullName, entryName, CompressionLevel.Optimal));
+                    snapshot.FileCount++;
+                    snapshot.Size += stateFile.Length;
+                }
+            }
+
+            using var descriptionStream = zip.CreateEntry(DescriptionEntryName).Open();
+            JsonSerializer.Serialize(descriptionStream, snapshot, AppStateJsonContext.Default.AppStateSnapshot);
+        }
+        File.Move(temporaryPath, file.FullName, overwrite: true);
+        return snapshot;
+    }
+
+    internal static AppStateSnapshot RestoreSnapshot(FileInfo file, DirectoryInfo appData)
+    {
+        using var zip = ZipFile.OpenRead(file.FullName);
+        var descriptionEntry = zip.GetEntry(DescriptionEntryName)
+            ?? throw new InvalidOperationException($"{file.Name} is not a snapshot of 'winapp state snapshot'");
+        AppStateSnapshot snapshot;
+        using (var descriptionStream = descriptionEntry.Open())
+        {
+            snapshot = JsonSerializer.Deserialize(descriptionStream, AppStateJsonContext.Default.AppStateSnapshot)
+                ?? throw new InvalidOperationException($"{file.Name} has an empty {DescriptionEntryName}");
+        }
+
+        // Check every entry before clearing anything, so a bad snapshot leaves the state as it was
+        var stateRoots = StateFolders.Select(f => Path.Combine(appData.FullName, f) + Path.DirectorySeparatorChar).ToList();
+        var files = new List<(ZipArchiveEntry Entry, string Path)>();
+        foreach (var entry in zip.Entries.Where(e => e.FullName != DescriptionEntryName && !e.FullName.EndsWith('/')))
+        {
+            var target = Path.GetFullPath(Path.Combine(appData.FullName, entry.FullName));
+            if (!stateRoots.Any(r => target.StartsWith(r, StringComparison.OrdinalIgnoreCase)))
+            {
+                throw new InvalidOperationException($"{file.Name} contains {entry.FullName}, which is outside the app's state folders");
+            }
+            files.Add((entry, target));
+        }
+
+        ClearState(appData);
+        foreach (var (entry, target) in files)
+        {
+            Directory.CreateDirectory(Path.GetDirectoryName(target)!);
+            entry.ExtractToFile(target, overwrite: true);
+        }
+        return snapshot;
+    }
+
+    private static void WhenNotInUse(string path, Action action)
+    {
+        try
+        {
+            action();
+        }
+        catch (IOException ex)
+        {
+            throw new IOException($"{path} is in use; close the app, and anything else that opened its files, and try again", ex);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class Arm64AuditService : IArm64AuditService
+{
+    private static readonly string[] BinaryExtensions = [".exe", ".dll", ".sys", ".node"];
+
+    private static readonly string[] SkippedFolders = ["node_modules", ".git", ".winapp"];
+
+    private static readonly EnumerationOptions Enumeration = new() { IgnoreInaccessible = true };
+
+    // Functions through which user-mode code talks to or installs kernel-mode drivers, which emulation doesn't cover
+    private static readonly HashSet<string> DriverFunctions = new(StringComparer.Ordinal)
+    {
+        "DeviceIoControl", "NtDeviceIoControlFile", "NtLoadDriver", "ZwLoadDriver",
+        "FilterConnectCommunicationPort", "FilterSendMessage", "FilterLoad",
+        "UpdateDriverForPlugAndPlayDevicesW", "UpdateDriverForPlugAndPlayDevicesA", "DiInstallDriverW", "DiInstallDriverA", "DiInstallDevice",
+        "SetupDiCallClassInstaller", "SetupCopyOEMInfW", "SetupCopyOEMInfA",
+    };
+
+    public Task<Arm64Report> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var root = XDocument.Load(manifestPath.FullName).Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var architecture = root.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity")?.Attribute("ProcessorArchitecture")?.Value ?? "neutral";
+        var directory = manifestPath.Directory!;
+        var shellExtensions = FindShellExtensions(root);
+        var isRust = FindRustProject(directory);
+
+        var binaries = new List<Arm64Binary>();
+        var images = new List<(string Path, PeImage Image, IReadOnlySet<string>? Imports)>();
+        foreach (var file in EnumerateBinaries(directory))
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            if (PeArchitecture.Read(file.FullName) is not { } image)
+            {
+                continue;
+            }
+            var path = Path.GetRelativePath(directory.FullName, file.FullName);
+            taskContext.AddDebugMessage($"{UiSymbols.Note} {path}: {image.Architecture}");
+            binaries.Add(new Arm64Binary(path, image.Architecture));
+            images.Add((path, image, image.Architecture is ImageArchitecture.X64 or ImageArchitecture.X86 ? PeImports.Read(file.FullName) : null));
+        }
+
+        var context = new Arm64Context(
+            architecture,
+            shellExtensions,
+            isRust,
+            HasEmulatedExecutable: images.Any(i => !i.Image.IsDll && !i.Image.IsDriver && IsEmulated(i.Image.Architecture)),
+            HasEmulatedDriver: images.Any(i => i.Image.IsDriver && IsEmulated(i.Image.Architecture)));
+        var issues = images
+            .SelectMany(i => Findings(i.Path, i.Image, i.Imports ?? new HashSet<string>(), context, File.Exists(Path.Combine(directory.FullName, Path.ChangeExtension(i.Path, ".runtimeconfig.json"))), IsElectron(directory, i.Path)))
+            .ToList();
+        return Task.FromResult(new Arm64Report(architecture, binaries, issues));
+    }
+
+    /// <param name="ProcessorArchitecture">Of the package</param>
+    /// <param name="ShellExtensions">Relative paths of the DLLs the manifest registers for File Explorer to load</param>
+    /// <param name="IsRust">The project builds with cargo</param>
+    internal sealed record Arm64Context(string ProcessorArchitecture, IReadOnlySet<string> ShellExtensions, bool IsRust, bool HasEmulatedExecutable, bool HasEmulatedDriver);
+
+    internal static IEnumerable<Arm64Issue> Findings(string path, PeImage image, IReadOnlySet<string> imports, Arm64Context context, bool isDotNetHost, bool isElectron)
+    {
+        var kind = image.Architecture == ImageArchitecture.X86 ? "x86" : "x64";
+        if (image.IsDriver)
+        {
+            if (IsEmulated(image.Architecture))
+            {
+                yield return new Arm64Issue(AccessibilitySeverity.Error, "driver", path,
+                    $"{path} is an {kind} kernel-mode driver. Windows on ARM emulates only user-mode code, so it doesn't load at all on ARM64 devices. Build an ARM64 driver with the WDK and ship it in the package for ARM64");
+            }
+            yield break;
+        }
+
+        if (context.ShellExtensions.Contains(path) && image.Architecture is not (ImageArchitecture.Arm64 or ImageArchitecture.AnyCpu))
+        {
+            var built = image.Architecture == ImageArchitecture.Arm64EC ? "ARM64EC" : kind;
+            yield return new Arm64Issue(AccessibilitySeverity.Warning, "shell-extension", path,
+                $"{path} is a File Explorer extension built {built}. File Explorer runs as an ARM64 process on ARM64 devices and can't load it, so the extension is silently missing there. Build it ARM64X, ARM64 and ARM64EC in one DLL (BuildAsX in Visual Studio), so both ARM64 and x64 processes load it");
+            yield break;
+        }
+
+        if (!IsEmulated(image.Architecture))
+        {
+            yield break;
+        }
+
+        var driverFunctions = imports.Select(i => i[(i.IndexOf('!') + 1)..]).Where(DriverFunctions.Contains).Distinct().Order(StringComparer.Ordinal).ToList();
+        if (driverFunctions.Count > 0)
+        {
+            yield return new Arm64Issue(AccessibilitySeverity.Warning, "kernel-adjacent", path,
+                $"{path} talks to or installs kernel-mode drivers ({string.Join(", ", driverFunctions.Take(3))}). Emulation covers only its own code, so on ARM64 devices it works only where the driver it needs ships for ARM64{(context.HasEmulatedDriver ? ", and the package's drivers are x64 or x86" : "")}. {Suggestion(path, image, context, isDotNetHost, isElectron)}");
+            yield break;
+        }
+
+        if (image.IsDll && context.ProcessorArchitecture.Equals("arm64", StringComparison.OrdinalIgnoreCase) && !context.HasEmulatedExecutable)
+        {
+            yield return new Arm64Issue(AccessibilitySeverity.Warning, "mixed", path,
+                $"{path} is an {kind} DLL in an ARM64 package without {kind} executables. ARM64 processes can't load x64 or x86 DLLs, so LoadLibrary fails with ERROR_BAD_EXE_FORMAT. {Suggestion(path, image, context, isDotNetHost, isElectron)}");
+            yield break;
+        }
+
+        yield return new Arm64Issue(AccessibilitySeverity.Info, "emulated", path,
+            $"{path} is {kind} and runs under emulation on ARM64 devices, slower and drawing more power than native code. {Suggestion(path, image, context, isDotNetHost, isElectron)}");
+    }
+
+    /// <summary>
+    /// How to build the binary for ARM64, for the toolchain that likely built it
+    /// </summary>
+    internal static string Suggestion(string path, PeImage image, Arm64Context context, bool isDotNetHost, bool isElectron)
+    {
+        if (image.IsManaged)
+        {
+            return "It is a .NET assembly built for one architecture: build it AnyCPU, or for ARM64";
+        }
+        if (Path.GetExtension(path).Equals(".node", StringComparison.OrdinalIgnoreCase))
+        {
+            return "Node.js for ARM64 can't load x64 addons: rebuild it for arm64, e.g. with 'npm rebuild --arch=arm64', or ship a win32-arm64 prebuild";
+        }
+        if (isDotNetHost)
+        {
+            return "Publish the app for ARM64 as well with 'dotnet publish -r win-arm64'";
+        }
+        if (isElectron)
+        {
+            return "Package the Electron app for arm64 as well, e.g. with 'electron-builder --arm64' or 'electron-forge package --arch=arm64'";
+        }
+        if (context.IsRust)
+        {
+            return "Build it with 'cargo build --release --target aarch64-pc-windows-msvc', or arm64ec-pc-windows-msvc where it must load x64 DLLs you can't rebuild";
+        }
+        return "Build it for ARM64 (the ARM64 platform in Visual Studio, or -A ARM64 with CMake), or ARM64EC where it must load x64 DLLs or plugins you can't rebuild";
+    }
+
+    /// <summary>
+    /// The DLLs of the COM classes the manifest registers for File Explorer: those whose CLSID a shell extension
+    /// such as a context menu, preview, thumbnail or property handler names
+    /// </summary>
+    internal static HashSet<string> FindShellExtensions(XElement root)
+    {
+        var classes = root.Descendants()
+            .Where(e => e.Name.LocalName == "Class" && e.Parent?.Name.LocalName is "ComServer" or "SurrogateServer")
+            .Select(e => (Id: e.Attribute("Id")?.Value, Path: e.Attribute("Path")?.Value))
+            .Where(c => c.Id != null && c.Path != null)
+            .ToList();
+        var referenced = root.Descendants()
+            .Where(e => !e.Ancestors().Any(a => a.Name.LocalName == "ComServer"))
+            .Select(e => e.Attribute("Clsid")?.Value)
+            .OfType<string>()
+            .ToHashSet(StringComparer.OrdinalIgnoreCase);
+        return classes
+            .Where(c => referenced.Contains(c.Id!))
+            .Select(c => c.Path!.Replace('/', Path.DirectorySeparatorChar))
+            .ToHashSet(StringComparer.OrdinalIgnoreCase);
+    }
+
+    private static bool IsEmulated(ImageArchitecture architecture) => architecture is ImageArchitecture.X64 or ImageArchitecture.X86;
+
+    private static IEnumerable<FileInfo> EnumerateBinaries(DirectoryInfo directory)
+    {
+        var pending = new Stack<DirectoryInfo>([directory]);
+        while (pending.TryPop(out var current))
+        {
+            foreach (var entry in current.EnumerateFileSystemInfos("*", Enumeration))
+            {
+                if (entry is DirectoryInfo child)
+                {
+                    if (!SkippedFolders.Contains(child.Name, StringComparer.OrdinalIgnoreCase))
+                    {
+                        pending.Push(child);
+                    }
+                }
+                else if (entry is FileInfo file && BinaryExtensions.Contains(file.Extension, StringComparer.OrdinalIgnoreCase))
+                {
+                    yield return file;
+                }
+            }
+        }
+    }
+
+    // The manifest is usually next to the crate or src-tauri, or in it
+    private static bool FindRustProject(DirectoryInfo directory) =>
+        new[] { directory, directory.Parent }
+            .OfType<DirectoryInfo>()
+            .Any(d => File.Exists(Path.Combine(d.FullName, "Cargo.toml")) || File.Exists(Path.Combine(d.FullName, "src-tauri", "Cargo.toml")));
+
+    // Electron ships its app in resources\app.asar next to the executable
+    private static bool IsElectron(DirectoryInfo directory, string path) =>
+        Path.GetExtension(path).Equals(".exe", StringComparison.OrdinalIgnoreCase)
+        && File.Exists(Path.Combine(directory.FullName, Path.GetDirectoryName(path) ?? "", "resources", "app.asar"));
+}
@@ -0,0 +1,146 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class BranchIdentityService(
+    IGitService gitService,
+    ICertificateService certificateService,
+    IPowerShellService powerShellService,
+    IElevationService elevationService,
+    IWinappDirectoryService winappDirectoryService,
+    ICurrentDirectoryProvider currentDirectoryProvider) : IBranchIdentityService
+{
+    private const string CertificateDirectoryName = "branch-certs";
+
+    // Checked in order when the checkout is detached, as it is for pull request builds on most CI systems
+    private static readonly string[] BranchVariables =
+    [
+        "GITHUB_HEAD_REF",
+        "SYSTEM_PULLREQUEST_SOURCEBRANCH",
+        "CI_MERGE_REQUEST_SOURCE_BRANCH_NAME",
+        "GITHUB_REF_NAME",
+        "BUILD_SOURCEBRANCH",
+        "CI_COMMIT_REF_NAME",
+    ];
+
+    // Certificates are added to the current user's store when generated and to TrustedPeople when installed
+    private static readonly (StoreName Name, StoreLocation Location)[] CertificateStores =
+    [
+        (StoreName.My, StoreLocation.CurrentUser),
+        (StoreName.TrustedPeople, StoreLocation.LocalMachine),
+    ];
+
+    public async Task<BranchIdentity> ResolveAsync(string? branch, CancellationToken cancellationToken = default)
+    {
+        if (string.IsNullOrWhiteSpace(branch))
+        {
+            try
+            {
+                branch = (await gitService.RunAsync(currentDirectoryProvider.GetCurrentDirectory(), ["rev-parse", "--abbrev-ref", "HEAD"], cancellationToken)).Trim();
+            }
+            catch (InvalidOperationException)
+            {
+                branch = null;
+            }
+
+            if (string.IsNullOrEmpty(branch) || branch == "HEAD")
+            {
+                branch = BranchVariables
+                    .Select(Environment.GetEnvironmentVariable)
+                    .FirstOrDefault(value => !string.IsNullOrWhiteSpace(value))
+                    ?? throw new InvalidOperationException("Could not tell which branch is being built: the checkout is detached and no CI branch variable is set. Pass the branch name.");
+            }
+        }
+
+        branch = branch.Trim();
+        return new BranchIdentity(branch.StartsWith("refs/heads/", StringComparison.Ordinal) ? branch["refs/heads/".Length..] : branch);
+    }
+
+    public async Task<FileInfo> EnsureCertificateAsync(BranchIdentity identity, string password, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var certificate = GetCertificatePath(identity);
+        if (certificate.Exists)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Using the certificate of branch '{identity.Branch}': {certificate}");
+            return certificate;
+        }
+
+        taskContext.AddDebugMessage($"{UiSymbols.Package} Generating a certificate for branch '{identity.Branch}' ({identity.Publisher})");
+        await certificateService.GenerateDevCertificateAsync(identity.Publisher, certificate, taskContext, password, cancellationToken: cancellationToken);
+        certificate.Refresh();
+        return certificate;
+    }
+
+    public async Task<BranchCleanupResult> CleanupAsync(BranchIdentity identity, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        // Matching the publisher as well keeps packages that happen to end in the same suffix
+        var command = $"Get-AppxPackage -Name '*{identity.NameSuffix}' -Publisher '{identity.Publisher}' | ForEach-Object {{ Remove-AppxPackage -Package $_.PackageFullName; $_.PackageFullName }}";
+        var (exitCode, output) = await powerShellService.RunCommandAsync(command, taskContext, cancellationToken: cancellationToken);
+        if (exitCode != 0)
+        {
+            throw new InvalidOperationException($"Removing the packages of branch '{identity.Branch}' failed: {output.Trim()}");
+        }
+        var packages = output.Split('\n', StringSplitOptions.RemoveEmptyEntries | StringSplitOptions.TrimEntries).ToList();
+
+        var certificates = new List<string>();
+        var elevatedSteps = new List<(string Id, ElevatedStep Step)>();
+        foreach (var (name, location) in CertificateStores)
+        {
+            try
+            {
+                // Removing from LocalMachine needs administrator rights, so those are batched for one elevation prompt
+                var elevated = location == StoreLocation.LocalMachine;
+                using var store = new X509Store(name, location);
+                store.Open(elevated ? OpenFlags.ReadOnly : OpenFlags.ReadWrite);
+                foreach (var certificate in store.Certificates.Find(X509FindType.FindBySubjectDistinguishedName, identity.Publisher, validOnly: false))
+                {
+                    var id = $@"{location}\{name}: {certificate.Thumbprint}";
+                    if (elevated)
+                    {
+                        elevatedSteps.Add((id, new ElevatedStep($"Remove certificate {id}", $@"Remove-Item -LiteralPath 'Cert:\{location}\{name}\{certificate.Thumbprint}'")));
+                    }
+                    else
+                    {
+                        store.Remove(certificate);
+                        certificates.Add(id);
+                    }
+                    certificate.Dispose();
+                }
+            }
+            catch (CryptographicException ex)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not remove the certificate from {location}\\{name}: {ex.Message}");
+            }
+        }
+        var results = await elevationService.RunAsync(elevatedSteps.Select(s => s.Step).ToList(), taskContext, cancellationToken);
+        foreach (var ((id, _), result) in elevatedSteps.Zip(results))
+        {
+            if (result.Succeeded)
+            {
+                certificates.Add(id);
+            }
+            else
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} {result.Step.Description} failed: {result.Error}");
+            }
+        }
+
+        var certificateFile = GetCertificatePath(identity);
+        if (certificateFile.Exists)
+        {
+            certificateFile.Delete();
+        }
+
+        return new BranchCleanupResult(packages, certificates);
+    }
+
+    private FileInfo GetCertificatePath(BranchIdentity identity) =>
+        new(Path.Combine(winappDirectoryService.GetGlobalWinappDirectory().FullName, CertificateDirectoryName, $"{identity.Hash}.pfx"));
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.BuildCache;
+using WinApp.Cli.Distribution;
+
+namespace WinApp.Cli.Services;
+
+internal class BuildCacheService : IBuildCacheService
+{
+    public const string LocationVariable = "WINAPP_REMOTE_CACHE";
+
+    private static readonly HttpClient Http = new() { Timeout = TimeSpan.FromMinutes(30) };
+
+    public IRemoteBuildCache Create(string location)
+    {
+        if (!Uri.TryCreate(location, UriKind.Absolute, out var uri))
+        {
+            throw new ArgumentException($"'{location}' is not a remote cache URL; use an Azure container URL with a SAS token, s3://bucket/prefix or an http(s) URL", nameof(location));
+        }
+
+        if (uri.Scheme == "s3")
+        {
+            var region = Environment.GetEnvironmentVariable("AWS_REGION") ?? Environment.GetEnvironmentVariable("AWS_DEFAULT_REGION");
+            if (string.IsNullOrWhiteSpace(region))
+            {
+                throw new InvalidOperationException("The bucket's region is unknown. Set AWS_REGION.");
+            }
+            return new S3BuildCache(uri, region, AwsCredentials.FromEnvironment(), Http);
+        }
+
+        if (uri.Scheme != Uri.UriSchemeHttps && uri.Scheme != Uri.UriSchemeHttp)
+        {
+            throw new ArgumentException($"Unsupported remote cache scheme '{uri.Scheme}'; use https, http or s3", nameof(location));
+        }
+
+        // Azure containers are addressed with a SAS token; everything else is a plain GET/PUT server
+        if (uri.Host.EndsWith(".blob.core.windows.net", StringComparison.OrdinalIgnoreCase) || uri.Query.Contains("sig=", StringComparison.Ordinal))
+        {
+            return new AzureBlobBuildCache(uri, Http);
+        }
+
+        var token = Environment.GetEnvironmentVariable(HttpBuildCache.TokenVariable);
+        return new HttpBuildCache(uri, string.IsNullOrEmpty(token) ? null : token, Http);
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.ComponentModel;
+using System.Diagnostics;
+using WinApp.Cli.Build;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class BuildService(IConfigService configService, IWinappDirectoryService winappDirectoryService, ICurrentDirectoryProvider currentDirectoryProvider) : IBuildService
+{
+    /// <summary>
+    /// Folder of .winapp the outputs are copied to
+    /// </summary>
+    internal const string PayloadFolder = "payload";
+
+    /// <summary>
+    /// Folder of .winapp with a folder per build system and architecture, for builds that are told where to build
+    /// </summary>
+    internal const string BuildFolder = "build";
+
+    // How many lines of a failed build's output the error shows
+    private const int FailureOutputLines = 20;
+
+    /// <summary>
+    /// In the order they are detected: Tauri before cargo and npm, and C++ solutions before .NET solutions
+    /// </summary>
+    internal static readonly IReadOnlyList<IBuildAdapter> Adapters =
+    [
+        new TauriBuildAdapter(),
+        new CargoBuildAdapter(),
+        new MSBuildBuildAdapter(),
+        new DotnetBuildAdapter(),
+        new CMakeBuildAdapter(),
+        new NpmBuildAdapter(),
+        new CustomBuildAdapter(),
+    ];
+
+    public async Task<DirectoryInfo> BuildAsync(string architecture, DirectoryInfo? payloadFolder, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var exists = configService.Exists();
+        var config = (exists ? configService.Load().Build : null) ?? new BuildConfig();
+        var root = exists ? configService.ConfigPath.Directory! : currentDirectoryProvider.GetCurrentDirectoryInfo();
+        var winappDirectory = winappDirectoryService.GetLocalWinappDirectory();
+
+        var context = CreateContext(root, config, architecture, winappDirectory);
+        var adapter = Resolve(context);
+        context = context with { Intermediate = new DirectoryInfo(Path.Combine(winappDirectory.FullName, BuildFolder, $"{adapter.System.ToString().ToLowerInvariant()}-{architecture.ToLowerInvariant()}")) };
+        taskContext.AddStatusMessage($"{UiSymbols.Tools}Building {context.Folder.FullName} with {adapter.System} ({config.Configuration}, {architecture})");
+
+        foreach (var step in adapter.GetSteps(context))
+        {
+            taskContext.UpdateSubStatus(step.ToString());
+            await RunAsync(step, context, taskContext, cancellationToken);
+        }
+        taskContext.UpdateSubStatus(null);
+
+        var outputs = adapter.LocateOutputs(context);
+        if (outputs.Count == 0)
+        {
+            throw new InvalidOperationException($"The {adapter.System} build produced no files to pack; set 'build: output:' in winapp.yaml to where it puts the app");
+        }
+
+        var payload = payloadFolder ?? new DirectoryInfo(Path.Combine(winappDirectory.FullName, PayloadFolder));
+        if (payloadFolder == null && payload.Exists)
+        {
+            payload.Delete(recursive: true);
+        }
+        Stage(outputs, payload);
+        taskContext.AddStatusMessage($"{UiSymbols.Files} Copied {outputs.Count} file(s) of the build to {payload.FullName}");
+        return payload;
+    }
+
+    /// <param name="root">Folder of winapp.yaml, which 'build: project:' is relative to</param>
+    internal static BuildContext CreateContext(DirectoryInfo root, BuildConfig config, string architecture, DirectoryInfo winappDirectory)
+    {
+        var project = config.Project != null ? Path.GetFullPath(config.Project, root.FullName) : root.FullName;
+        var projectFile = File.Exists(project) ? new FileInfo(project) : null;
+        var folder = projectFile?.Directory ?? new DirectoryInfo(project);
+        if (!folder.Exists)
+        {
+            throw new DirectoryNotFoundException($"winapp.yaml: the build project {project} doesn't exist");
+        }
+        return new BuildContext(folder, projectFile, config, architecture, new DirectoryInfo(Path.Combine(winappDirectory.FullName, BuildFolder)));
+    }
+
+    /// <summary>
+    /// The adapter of 'build: system:', or the first one the project files are for
+    /// </summary>
+    internal static IBuildAdapter Resolve(BuildContext context) =>
+        context.Config.System is { } system
+            ? Adapters.First(a => a.System == system)
+            : Adapters.FirstOrDefault(a => a.Detect(context))
+                ?? throw new InvalidOperationException($"Couldn't tell how to build {context.Folder.FullName}: it has no tauri.conf.json, Cargo.toml, solution, project, CMakeLists.txt or package.json. Set 'build: system:' in winapp.yaml");
+
+    /// <summary>
+    /// Copies the outputs to their paths in the payload folder
+    /// </summary>
+    internal static void Stage(IReadOnlyList<BuildOutput> outputs, DirectoryInfo payload)
+    {
+        payload.Create();
+        foreach (var output in outputs)
+        {
+            var target = new FileInfo(Path.Combine(payload.FullName, output.PackagePath));
+            target.Directory!.Create();
+            output.Source.CopyTo(target.FullName, overwrite: true);
+        }
+    }
+
+    private static async Task RunAsync(BuildStep step, BuildContext context, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var psi = new ProcessStartInfo(step.FileName, step.Arguments)
+        {
+            UseShellExecute = false,
+            RedirectStandardOutput = true,
+            RedirectStandardError = true,
+            CreateNoWindow = true,
+            WorkingDirectory = step.WorkingDirectory.FullName,
+        };
+
+        // For custom builds, and build scripts that branch on them
+        psi.Environment["WINAPP_ARCHITECTURE"] = context.Architecture;
+        psi.Environment["WINAPP_CONFIGURATION"] = context.Config.Configuration;
+
+        Process? process;
+        try
+        {
+            process = Process.Start(psi);
+        }
+        catch (Win32Exception ex)
+        {
+            throw new InvalidOperationException($"Failed to start {step.FileName}; make sure it is installed and on PATH", ex);
+        }
+
+        using var p = process ?? throw new InvalidOperationException($"Failed to start {step.FileName}");
+        var output = new List<string>();
+        void Collect(object sender, DataReceivedEventArgs e)
+        {
+            if (e.Data == null)
+            {
+                return;
+            }
+            lock (output)
+            {
+                output.Add(e.Data);
+            }
+            taskContext.AddDebugMessage(e.Data);
+        }
+        p.OutputDataReceived += Collect;
+        p.ErrorDataReceived += Collect;
+        p.BeginOutputReadLine();
+        p.BeginErrorReadLine();
+        try
+        {
+            await p.WaitForExitAsync(cancellationToken);
+        }
+        catch (OperationCanceledException)
+        {
+            p.Kill(entireProcessTree: true);
+            throw;
+        }
+
+        if (p.ExitCode != 0)
+        {
+            string tail;
+            lock (output)
+            {
+                tail = string.Join(Environment.NewLine, output.TakeLast(FailureOutputLines));
+            }
+            throw new InvalidOperationException($"'{step}' exited with {p.ExitCode}:{Environment.NewLine}{tail}");
+        }
+    }
+}
@@ -0,0 +1,426 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.IO.Compression;
+using System.Text;
+using System.Xml;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Tools;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Reads MSIX bundles and flattens a subset of their packages into a single package
+/// </summary>
+internal class BundleService(
+    IMsixService msixService,
+    IBuildToolsService buildToolsService,
+    ICertificateService certificateService,
+    ICurrentDirectoryProvider currentDirectoryProvider) : IBundleService
+{
+    private const string BundleManifestEntryName = "AppxMetadata/AppxBundleManifest.xml";
+    private const string BundleNamespace = "http://schemas.microsoft.com/appx/2013/bundle";
+    private const string UapNamespace = "http://schemas.microsoft.com/appx/manifest/uap/windows10";
+
+    // Footprint files that are regenerated when the layout is packed again
+    private static readonly string[] FootprintFiles =
+    [
+        "AppxBlockMap.xml",
+        "AppxSignature.p7x",
+        "[Content_Types].xml",
+        "AppxMetadata/CodeIntegrity.cat"
+    ];
+
+    public async Task<IReadOnlyList<BundlePackageEntry>> ReadBundlePackagesAsync(FileInfo bundlePath, CancellationToken cancellationToken = default)
+    {
+        if (!bundlePath.Exists)
+        {
+            throw new FileNotFoundException($"Bundle not found: {bundlePath}");
+        }
+
+        using var archive = await ZipFile.OpenReadAsync(bundlePath.FullName, cancellationToken);
+        var manifestEntry = archive.GetEntry(BundleManifestEntryName)
+            ?? throw new InvalidOperationException($"{bundlePath.Name} is not a bundle: {BundleManifestEntryName} was not found");
+
+        using var stream = await manifestEntry.OpenAsync(cancellationToken);
+        using var reader = new StreamReader(stream, Encoding.UTF8);
+        var content = await reader.ReadToEndAsync(cancellationToken);
+
+        return ParseBundleManifest(content);
+    }
+
+    /// <summary>
+    /// Parses the content of an AppxBundleManifest.xml file
+    /// </summary>
+    /// <param name="bundleManifestContent">The bundle manifest XML</param>
+    /// <returns>The packages declared by the bundle manifest</returns>
+    internal static IReadOnlyList<BundlePackageEntry> ParseBundleManifest(string bundleManifestContent)
+    {
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(bundleManifestContent);
+
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("b", BundleNamespace);
+
+        var packageNodes = xmlDoc.SelectNodes("/b:Bundle/b:Packages/b:Package", nsManager);
+        if (packageNodes == null || packageNodes.Count == 0)
+        {
+            throw new InvalidOperationException("Bundle manifest does not declare any packages");
+        }
+
+        var packages = new List<BundlePackageEntry>();
+        foreach (XmlElement packageNode in packageNodes)
+        {
+            var languages = new List<string>();
+            var scales = new List<int>();
+            var resourceNodes = packageNode.SelectNodes("b:Resources/b:Resource", nsManager);
+            if (resourceNodes != null)
+            {
+                foreach (XmlElement resourceNode in resourceNodes)
+                {
+                    var language = resourceNode.GetAttribute("Language");
+                    if (!string.IsNullOrEmpty(language))
+                    {
+                        languages.Add(language);
+                    }
+
+                    var scale = resourceNode.GetAttribute("Scale");
+                    if (int.TryParse(scale, NumberStyles.Integer, CultureInfo.InvariantCulture, out var scaleValue))
+                    {
+                        scales.Add(scaleValue);
+                    }
+                }
+            }
+
+            var type = packageNode.GetAttribute("Type");
+            var architecture = packageNode.GetAttribute("Architecture");
+            var resourceId = packageNode.GetAttribute("ResourceId");
+
+            packages.Add(new BundlePackageEntry(
+                packageNode.GetAttribute("FileName"),
+                string.IsNullOrEmpty(type) ? "application" : type,
+                string.IsNullOrEmpty(architecture) ? null : architecture,
+                string.IsNullOrEmpty(resourceId) ? null : resourceId,
+                languages,
+                scales));
+        }
+
+        return packages;
+    }
+
+    /// <summary>
+    /// Selects the application package for the architecture and the resource packages
+    /// needed for the requested languages and scales.
+    /// </summary>
+    /// <param name="packages">Packages declared by the bundle</param>
+    /// <param name="architecture">Requested architecture</param>
+    /// <param name="languages">Requested languages</param>
+    /// <param name="scales">Requested scales</param>
+    /// <returns>The application package and resource packages to merge</returns>
+    /// <exception cref="InvalidOperationException">No application package matches the architecture</exception>
+    internal static (BundlePackageEntry Application, List<BundlePackageEntry> Resources) SelectPackages(
+        IReadOnlyList<BundlePackageEntry> packages,
+        string architecture,
+        IReadOnlyList<string> languages,
+        IReadOnlyList<int> scales)
+    {
+        var applicationPackages = packages.Where(p => p.IsApplication).ToList();
+
+        var application = applicationPackages.FirstOrDefault(p => string.Equals(p.Architecture, architecture, StringComparison.OrdinalIgnoreCase))
+            ?? applicationPackages.FirstOrDefault(p => string.Equals(p.Architecture, "neutral", StringComparison.OrdinalIgnoreCase));
+
+        if (application == null)
+        {
+            var available = string.Join(", ", applicationPackages.Select(p => p.Architecture ?? "neutral").Distinct());
+            throw new InvalidOperationException($"No application package for architecture '{architecture}' found in bundle. Available: {available}");
+        }
+
+        var resources = new List<BundlePackageEntry>();
+        foreach (var language in languages)
+        {
+            // The application package already carries its default language resources
+            if (application.Languages.Any(l => LanguageMatches(l, language)))
+            {
+                continue;
+            }
+
+            var languagePackage = packages
+                .Where(p => !p.IsApplication)
+                .FirstOrDefault(p => p.Languages.Any(l => LanguageMatches(l, language)));
+
+            if (languagePackage == null)
+            {
+                throw new InvalidOperationException($"No resource package for language '{language}' found in bundle");
+            }
+
+            if (!resources.Contains(languagePackage))
+            {
+                resources.Add(languagePackage);
+            }
+        }
+
+        foreach (var scale in scales)
+        {
+            if (application.Scales.Contains(scale))
+            {
+                continue;
+            }
+
+            var scalePackage = packages
+                .Where(p => !p.IsApplication)
+                .FirstOrDefault(p => p.Scales.Contains(scale))
+                ?? throw new InvalidOperationException($"No resource package for scale '{scale}' found in bundle");
+
+            if (!resources.Contains(scalePackage))
+            {
+                resources.Add(scalePackage);
+            }
+        }
+
+        return (application, resources);
+    }
+
+    /// <summary>
+    /// Checks whether a declared language satisfies a requested language.
+    /// "en" matches "en-US" and vice versa, "en-US" does not match "en-GB".
+    /// </summary>
+    internal static bool LanguageMatches(string declared, string requested)
+    {
+        if (string.Equals(declared, requested, StringComparison.OrdinalIgnoreCase))
+        {
+            return true;
+        }
+
+        var declaredPrimary = declared.Split('-')[0];
+        var requestedPrimary = requested.Split('-')[0];
+        var oneIsNeutral = !declared.Contains('-') || !requested.Contains('-');
+        return oneIsNeutral && string.Equals(declaredPrimary, requestedPrimary, StringComparison.OrdinalIgnoreCase);
+    }
+
+    public async Task<BundleThinResult> ThinBundleAsync(
+        FileInfo bundlePath,
+        string architecture,
+        IReadOnlyList<string> languages,
+        IReadOnlyList<int> scales,
+        FileInfo? outputPath,
+        FileInfo? certificatePath,
+        string certificatePassword,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default)
+    {
+        var packages = await ReadBundlePackagesAsync(bundlePath, cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Search} Bundle contains {packages.Count} package(s)");
+
+        var (application, resources) = SelectPackages(packages, architecture, languages, scales);
+
+        var outputMsixPath = outputPath ?? new FileInfo(Path.Combine(
+            currentDirectoryProvider.GetCurrentDirectory(),
+            $"{Path.GetFileNameWithoutExtension(bundlePath.Name)}_{architecture}.msix"));
+        outputMsixPath.Directory?.Create();
+
+        var layoutDir = new DirectoryInfo(Path.Combine(Path.GetTempPath(), $"winapp-thin-{Guid.NewGuid():N}"));
+        layoutDir.Create();
+
+        try
+        {
+            using (var bundle = await ZipFile.OpenReadAsync(bundlePath.FullName, cancellationToken))
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Package} Extracting application package: {application.FileName}");
+                await ExtractPackageAsync(bundle, application, layoutDir, isApplication: true, taskContext, cancellationToken);
+
+                foreach (var resource in resources)
+                {
+                    taskContext.AddDebugMessage($"{UiSymbols.Package} Merging resource package: {resource.FileName}");
+                    await ExtractPackageAsync(bundle, resource, layoutDir, isApplication: false, taskContext, cancellationToken);
+                }
+            }
+
+            var manifestPath = new FileInfo(Path.Combine(layoutDir.FullName, "AppxManifest.xml"));
+            if (!manifestPath.Exists)
+            {
+                throw new InvalidOperationException($"Application package {application.FileName} does not contain an AppxManifest.xml");
+            }
+
+            await AddManifestResourcesAsync(manifestPath, resources, cancellationToken);
+
+            if (resources.Count > 0)
+            {
+                // Merge the resource package PRI files into the application resources.pri
+                var tempFiles = new List<FileInfo>
+                {
+                    await msixService.CreatePriConfigAsync(layoutDir, taskContext, cancellationToken: cancellationToken)
+                };
+                tempFiles.AddRange(await msixService.GeneratePriFileAsync(layoutDir, taskContext, cancellationToken: cancellationToken));
+                tempFiles.Add(new FileInfo(Path.Combine(layoutDir.FullName, "pri.resfiles")));
+                foreach (var resource in resources)
+                {
+                    tempFiles.Add(new FileInfo(Path.Combine(layoutDir.FullName, GetMergedPriFileName(resource))));
+                }
+
+                foreach (var file in tempFiles.DistinctBy(f => f.FullName))
+                {
+                    file.Refresh();
+                    if (file.Exists && !string.Equals(file.Name, "resources.pri", StringComparison.OrdinalIgnoreCase))
+                    {
+                        file.Delete();
+                    }
+                }
+            }
+
+            var makeappxArguments = $@"pack /o /d ""{Path.TrimEndingDirectorySeparator(layoutDir.FullName)}"" /nv /p ""{outputMsixPath.FullName}""";
+            await buildToolsService.RunBuildToolAsync(new MakeAppxTool(), makeappxArguments, taskContext, cancellationToken: cancellationToken);
+
+            var signed = false;
+            if (certificatePath != null)
+            {
+                await CertificateService.ValidatePublisherMatchAsync(certificatePath, certificatePassword, manifestPath, cancellationToken);
+                await certificateService.SignFileAsync(outputMsixPath, certificatePath, taskContext, certificatePassword, cancellationToken: cancellationToken);
+                signed = true;
+            }
+
+            outputMsixPath.Refresh();
+            return new BundleThinResult(outputMsixPath, application.FileName, resources.Select(r => r.FileName).ToList(), signed);
+        }
+        finally
+        {
+            try
+            {
+                layoutDir.Delete(recursive: true);
+            }
+            catch
+            {
+                taskContext.AddDebugMessage($"Could not clean up {layoutDir}");
+            }
+        }
+    }
+
+    private static async Task ExtractPackageAsync(ZipArchive bundle, BundlePackageEntry package, DirectoryInfo layoutDir, bool isApplication, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var entry = bundle.GetEntry(package.FileName.Replace('\\', '/'))
+            ?? throw new InvalidOperationException($"Package {package.FileName} is declared in the bundle manifest but was not found in the bundle");
+
+        // Nested packages are stored uncompressed, but ZipArchive needs a seekable stream
+        var tempPackage = new FileInfo(Path.Combine(Path.GetTempPath(), $"winapp-thin-{Guid.NewGuid():N}.msix"));
+        try
+        {
+            await using (var source = await entry.OpenAsync(cancellationToken))
+            await using (var target = tempPackage.Create())
+            {
+                await source.CopyToAsync(target, cancellationToken);
+            }
+
+            using var packageArchive = await ZipFile.OpenReadAsync(tempPackage.FullName, cancellationToken);
+            foreach (var file in packageArchive.Entries)
+            {
+                var entryPath = Uri.UnescapeDataString(file.FullName);
+                if (entryPath.EndsWith('/') || IsFootprintFile(entryPath, isApplication))
+                {
+                    continue;
+                }
+
+                if (!isApplication && string.Equals(entryPath, "resources.pri", StringComparison.OrdinalIgnoreCase))
+                {
+                    entryPath = GetMergedPriFileName(package);
+                }
+
+                var destination = Path.GetFullPath(Path.Combine(layoutDir.FullName, entryPath));
+                if (!destination.StartsWith(layoutDir.FullName, StringComparison.OrdinalIgnoreCase))
+                {
+                    throw new InvalidOperationException($"Package entry {file.FullName} resolves outside of the package layout");
+                }
+
+                Directory.CreateDirectory(Path.GetDirectoryName(destination)!);
+                await file.ExtractToFileAsync(destination, overwrite: true, cancellationToken);
+                taskContext.AddDebugMessage($"{UiSymbols.Files} {entryPath}");
+            }
+        }
+        finally
+        {
+            try
+            {
+                tempPackage.Delete();
+            }
+            catch
+            {
+                // Ignore cleanup failures
+            }
+        }
+    }
+
+    private static bool IsFootprintFile(string entryPath, bool isApplication)
+    {
+        if (FootprintFiles.Contains(entryPath, StringComparer.OrdinalIgnoreCase))
+        {
+            return true;
+        }
+
+        // Resource packages carry their own manifest which is replaced by the application manifest
+        return !isApplication && string.Equals(entryPath, "AppxManifest.xml", StringComparison.OrdinalIgnoreCase);
+    }
+
+    private static string GetMergedPriFileName(BundlePackageEntry package)
+    {
+        var id = package.ResourceId ?? Path.GetFileNameWithoutExtension(package.FileName);
+        return $"resources.{id}.pri";
+    }
+
+    /// <summary>
+    /// Declares the merged languages/scales in the manifest Resources element so the
+    /// deployment stack applies them.
+    /// </summary>
+    private static async Task AddManifestResourcesAsync(FileInfo manifestPath, List<BundlePackageEntry> resources, CancellationToken cancellationToken)
+    {
+        if (resources.Count == 0)
+        {
+            return;
+        }
+
+        var xmlDoc = new XmlDocument { PreserveWhitespace = true };
+        xmlDoc.LoadXml(await File.ReadAllTextAsync(manifestPath.FullName, Encoding.UTF8, cancellationToken));
+        var root = xmlDoc.DocumentElement ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+
+        var nsManager = new XmlNamespaceManager(xmlDoc.NameTable);
+        nsManager.AddNamespace("m", root.NamespaceURI);
+        nsManager.AddNamespace("uap", UapNamespace);
+
+        var resourcesNode = root.SelectSingleNode("m:Resources", nsManager) as XmlElement;
+        if (resourcesNode == null)
+        {
+            resourcesNode = xmlDoc.CreateElement("Resources", root.NamespaceURI);
+            var dependencies = root.SelectSingleNode("m:Dependencies", nsManager);
+            root.InsertBefore(resourcesNode, dependencies);
+        }
+
+        foreach (var resource in resources)
+        {
+            foreach (var language in resource.Languages)
+            {
+                if (resourcesNode.SelectSingleNode($"m:Resource[@Language='{language}']", nsManager) == null)
+                {
+                    var node = xmlDoc.CreateElement("Resource", root.NamespaceURI);
+                    node.SetAttribute("Language", language);
+                    resourcesNode.AppendChild(node);
+                }
+            }
+
+            foreach (var scale in resource.Scales)
+            {
+                var scaleText = scale.ToString(CultureInfo.InvariantCulture);
+                if (resourcesNode.SelectSingleNode($"m:Resource[@uap:Scale='{scaleText}']", nsManager) == null)
+                {
+                    var node = xmlDoc.CreateElement("Resource", root.NamespaceURI);
+                    var scaleAttribute = xmlDoc.CreateAttribute("uap", "Scale", UapNamespace);
+                    scaleAttribute.Value = scaleText;
+                    node.Attributes.Append(scaleAttribute);
+                    resourcesNode.AppendChild(node);
+                }
+            }
+        }
+
+        await using var writer = new StreamWriter(manifestPath.FullName, append: false, new UTF8Encoding(encoderShouldEmitUTF8Identifier: false));
+        xmlDoc.Save(writer);
+    }
+}
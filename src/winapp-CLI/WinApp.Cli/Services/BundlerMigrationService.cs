@@ -0,0 +1,576 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class BundlerMigrationService(IManifestService manifestService, IConfigService configService) : IBundlerMigrationService
+{
+    private const string AssetsFolder = "Assets";
+
+    private static readonly XNamespace FoundationNamespace = "http://schemas.microsoft.com/appx/manifest/foundation/windows10";
+    private static readonly XNamespace UapNamespace = "http://schemas.microsoft.com/appx/manifest/uap/windows10";
+    private static readonly XNamespace DesktopNamespace = "http://schemas.microsoft.com/appx/manifest/desktop/windows10";
+    private static readonly XNamespace RescapNamespace = "http://schemas.microsoft.com/appx/manifest/foundation/windows10/restrictedcapabilities";
+
+    private static readonly JsonDocumentOptions JsonOptions = new() { CommentHandling = JsonCommentHandling.Skip, AllowTrailingCommas = true };
+
+    // Capabilities of electron-builder's appx.capabilities by the namespace the manifest declares them in; the rest are
+    // foundation capabilities such as internetClient
+    private static readonly HashSet<string> UapCapabilities = new(StringComparer.OrdinalIgnoreCase)
+    {
+        "appointments", "blockedChatMessages", "chat", "contacts", "enterpriseAuthentication", "musicLibrary", "objects3D",
+        "phoneCall", "picturesLibrary", "removableStorage", "sharedUserCertificates", "userAccountInformation", "videosLibrary", "voipCall",
+    };
+    private static readonly HashSet<string> DeviceCapabilities = new(StringComparer.OrdinalIgnoreCase)
+    {
+        "bluetooth", "location", "microphone", "proximity", "webcam",
+    };
+    private static readonly HashSet<string> RestrictedCapabilities = new(StringComparer.OrdinalIgnoreCase)
+    {
+        "allowElevation", "broadFileSystemAccess", "packageManagement", "runFullTrust", "unvirtualizedResources",
+    };
+
+    // Tile images of electron-builder's appx folder the template doesn't name, by the VisualElements or DefaultTile
+    // attribute they go in
+    private static readonly (string Image, string Attribute)[] OptionalTiles =
+    [
+        ("LargeTile", "Square310x310Logo"),
+        ("SmallTile", "Square71x71Logo"),
+    ];
+
+    public FileInfo? FindConfig(MigrationSource source, DirectoryInfo directory)
+    {
+        string[] candidates = source switch
+        {
+            MigrationSource.ElectronBuilder => ["electron-builder.json", "electron-builder.json5", "package.json"],
+            _ => [Path.Combine("src-tauri", "tauri.conf.json"), "tauri.conf.json"],
+        };
+        foreach (var candidate in candidates)
+        {
+            var file = new FileInfo(Path.Combine(directory.FullName, candidate));
+            if (!file.Exists)
+            {
+                continue;
+            }
+            // package.json configures electron-builder only with a build section
+            if (file.Name == "package.json" && ReadJson(file)["build"] is not JsonObject)
+            {
+                continue;
+            }
+            return file;
+        }
+        return null;
+    }
+
+    public async Task<MigrationResult> MigrateAsync(MigrationSource source, FileInfo configPath, bool force, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var outputDirectory = configService.ConfigPath.Directory!;
+        var manifestPath = new FileInfo(Path.Combine(outputDirectory.FullName, "appxmanifest.xml"));
+        if (manifestPath.Exists && !force)
+        {
+            throw new InvalidOperationException($"{manifestPath.FullName} exists already; pass --force to overwrite it");
+        }
+
+        var migrated = new List<string>();
+        var manual = new List<string>();
+        var settings = source == MigrationSource.ElectronBuilder
+            ? ReadElectronBuilder(configPath, manual)
+            : ReadTauri(configPath, manual);
+        taskContext.AddDebugMessage($"{UiSymbols.Note} {configPath.Name}: {settings.PackageName} {settings.Version} by {settings.Publisher ?? "(no publisher)"}, {settings.Executable}");
+
+        var info = await manifestService.PromptForManifestInfoAsync(outputDirectory, settings.PackageName, settings.Publisher, settings.Version, settings.Description, null, useDefaults: true, cancellationToken);
+        info = info with { EntryPoint = Path.Combine(outputDirectory.FullName, settings.Executable) };
+        if (settings.Publisher == null)
+        {
+            manual.Add($"{configPath.Name} has no publisher; the manifest has {info.PublisherName}, so set it to the subject of your signing certificate");
+        }
+        await manifestService.GenerateManifestAsync(outputDirectory, info, ManifestTemplates.Packaged, settings.Icon, taskContext, cancellationToken);
+        migrated.Add($"Identity {info.PackageName} {info.Version}, executable {settings.Executable}");
+        if (settings.Icon != null)
+        {
+            migrated.Add($"Images generated from {Path.GetRelativePath(outputDirectory.FullName, settings.Icon.FullName)}");
+        }
+
+        var manifest = XDocument.Load(manifestPath.FullName, LoadOptions.PreserveWhitespace);
+        var root = manifest.Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        ApplySettings(root, settings, migrated);
+
+        var assets = settings.AssetsDirectory != null ? CopyAssets(root, settings.AssetsDirectory, outputDirectory, migrated) : 0;
+
+        using (var stream = manifestPath.Open(FileMode.Create, FileAccess.Write))
+        {
+            await manifest.SaveAsync(stream, SaveOptions.DisableFormatting, cancellationToken);
+        }
+
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        var projectPath = Path.GetRelativePath(outputDirectory.FullName, settings.ProjectDirectory.FullName).Replace('\\', '/');
+        var projectName = settings.ProjectDirectory.Name;
+        if (!config.Projects.Any(p => p.Name.Equals(projectName, StringComparison.OrdinalIgnoreCase)))
+        {
+            config.Projects.Add(new ProjectConfig { Name = projectName, Path = projectPath });
+            migrated.Add($"Project {projectName} at {projectPath}");
+        }
+        if (config.Splash == null && root.Descendants(UapNamespace + "SplashScreen").FirstOrDefault() is XElement splash)
+        {
+            config.Splash = new SplashConfig
+            {
+                Image = splash.Attribute("Image")?.Value,
+                BackgroundColor = splash.Attribute("BackgroundColor")?.Value ?? settings.BackgroundColor,
+            };
+            migrated.Add($"Splash screen {config.Splash.Image}");
+        }
+        configService.Save(config);
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {manifestPath.FullName} and {configService.ConfigPath.FullName}");
+
+        manifestPath.Refresh();
+        return new MigrationResult(manifestPath, assets, migrated, manual);
+    }
+
+    /// <summary>
+    /// Reads the 'build' section of package.json, or electron-builder.json, with package.json next to it for the
+    /// name, version and author electron-builder falls back to
+    /// </summary>
+    internal static BundlerSettings ReadElectronBuilder(FileInfo configPath, List<string> manual)
+    {
+        var directory = configPath.Directory!;
+        var packageJsonPath = new FileInfo(Path.Combine(directory.FullName, "package.json"));
+        var packageJson = packageJsonPath.Exists ? ReadJson(packageJsonPath) : new JsonObject();
+        var build = configPath.Name == "package.json"
+            ? packageJson["build"] as JsonObject ?? throw new InvalidOperationException($"{configPath.FullName} has no 'build' section for electron-builder")
+            : ReadJson(configPath);
+        var appx = build["appx"] as JsonObject ?? new JsonObject();
+        var win = build["win"] as JsonObject ?? new JsonObject();
+
+        var productName = StringOf(build, "productName") ?? StringOf(packageJson, "productName") ?? StringOf(packageJson, "name")
+            ?? throw new InvalidOperationException($"{configPath.Name} has no productName or name");
+        var author = packageJson["author"] is JsonObject authorObject ? StringOf(authorObject, "name") : StringOf(packageJson, "author");
+
+        var buildResources = new DirectoryInfo(Path.Combine(directory.FullName, StringOf(build["directories"], "buildResources") ?? "build"));
+        var icon = StringOf(win, "icon") ?? StringOf(build, "icon");
+        var iconPath = icon != null
+            ? new FileInfo(Path.Combine(directory.FullName, icon))
+            : new FileInfo(Path.Combine(buildResources.FullName, "icon.png"));
+        if (!iconPath.Exists || !iconPath.Extension.Equals(".png", StringComparison.OrdinalIgnoreCase))
+        {
+            if (icon != null)
+            {
+                manual.Add($"Icon {icon} isn't a PNG; create the images with 'winapp manifest update-assets' from a PNG of at least 400x400");
+            }
+            iconPath = null;
+        }
+
+        foreach (var (node, property, instead) in new (JsonNode?, string, string)[]
+        {
+            (win, "certificateFile", "pass the certificate to 'winapp package --cert' or 'winapp sign'"),
+            (win, "certificateSubjectName", "export the certificate and pass it to 'winapp package --cert'"),
+            (win, "certificateSha1", "export the certificate and pass it to 'winapp package --cert'"),
+            (win, "sign", "sign with 'winapp sign', or in your own step after 'winapp package'"),
+            (appx, "customManifestPath", "compare your manifest with the migrated appxmanifest.xml and move what it adds by hand"),
+            (appx, "artifactName", "pass the file name to 'winapp package --output'"),
+            (appx, "setBuildNumber", "set the version of appxmanifest.xml in your build"),
+            (appx, "electronUpdaterAware", "electron-updater doesn't update MSIX; publish updates with 'winapp distribute web' or the Store"),
+        })
+        {
+            if (node?[property] is JsonNode value)
+            {
+                manual.Add($"{property} ({value.ToJsonString()}): {instead}");
+            }
+        }
+
+        var executable = (StringOf(build, "executableName") ?? productName) + ".exe";
+        var customExtensions = StringOf(appx, "customExtensionsPath");
+        return new BundlerSettings
+        {
+            PackageName = StringOf(appx, "identityName") ?? StringOf(packageJson, "name") ?? productName,
+            Publisher = StringOf(appx, "publisher"),
+            PublisherDisplayName = StringOf(appx, "publisherDisplayName") ?? author,
+            DisplayName = StringOf(appx, "displayName") ?? productName,
+            Description = StringOf(packageJson, "description"),
+            Version = ToPackageVersion(StringOf(build, "buildVersion") ?? StringOf(packageJson, "version"), manual),
+            Executable = executable,
+            ApplicationId = StringOf(appx, "applicationId"),
+            BackgroundColor = StringOf(appx, "backgroundColor"),
+            Languages = StringsOf(appx["languages"]),
+            Capabilities = StringsOf(appx["capabilities"]),
+            MinVersion = StringOf(appx, "minVersion"),
+            MaxVersionTested = StringOf(appx, "maxVersionTested"),
+            AutoLaunch = appx["addAutoLaunchExtension"]?.GetValue<bool>() == true,
+            CustomExtensions = customExtensions != null ? new FileInfo(Path.Combine(directory.FullName, customExtensions)) : null,
+            Icon = iconPath,
+            AssetsDirectory = new DirectoryInfo(Path.Combine(buildResources.FullName, "appx")) is { Exists: true } appxAssets ? appxAssets : null,
+            ProjectDirectory = directory,
+        };
+    }
+
+    /// <summary>
+    /// Reads tauri.conf.json of Tauri 2, or of Tauri 1 with its 'package' and 'tauri.bundle' sections
+    /// </summary>
+    internal static BundlerSettings ReadTauri(FileInfo configPath, List<string> manual)
+    {
+        var directory = configPath.Directory!;
+        var conf = ReadJson(configPath);
+        var bundle = conf["bundle"] as JsonObject ?? conf["tauri"]?["bundle"] as JsonObject ?? new JsonObject();
+        var windows = bundle["windows"] as JsonObject ?? new JsonObject();
+
+        var productName = StringOf(conf, "productName") ?? StringOf(conf["package"], "productName")
+            ?? throw new InvalidOperationException($"{configPath.Name} has no productName");
+        var identifier = StringOf(conf, "identifier") ?? StringOf(bundle, "identifier")
+            ?? throw new InvalidOperationException($"{configPath.Name} has no identifier");
+
+        // Tauri 2 can read the version from package.json
+        var version = StringOf(conf, "version") ?? StringOf(conf["package"], "version");
+        if (version != null && version.EndsWith(".json", StringComparison.OrdinalIgnoreCase))
+        {
+            version = StringOf(ReadJson(new FileInfo(Path.Combine(directory.FullName, version))), "version");
+        }
+
+        // The executable is named after the Cargo package unless mainBinaryName says otherwise
+        var binary = StringOf(conf, "mainBinaryName") ?? ReadCargoPackageName(new FileInfo(Path.Combine(directory.FullName, "Cargo.toml"))) ?? productName;
+
+        var icon = StringsOf(bundle["icon"])
+            .Where(i => i.EndsWith(".png", StringComparison.OrdinalIgnoreCase))
+            .Select(i => new FileInfo(Path.Combine(directory.FullName, i)))
+            .Where(f => f.Exists)
+            .OrderByDescending(f => f.Length)
+            .FirstOrDefault();
+        if (icon == null)
+        {
+            manual.Add("bundle.icon has no PNG; create the images with 'winapp manifest update-assets' from a PNG of at least 400x400");
+        }
+
+        foreach (var (node, property, instead) in new (JsonNode?, string, string)[]
+        {
+            (windows, "certificateThumbprint", "export the certificate and pass it to 'winapp package --cert'"),
+            (windows, "signCommand", "sign with 'winapp sign', or in your own step after 'winapp package'"),
+            (windows, "webviewInstallMode", "MSIX doesn't install WebView2; it ships with Windows 11, and the Evergreen bootstrapper installs it on Windows 10"),
+            (windows, "wix", "WiX settings don't apply to MSIX"),
+            (windows, "nsis", "NSIS settings don't apply to MSIX"),
+            (bundle, "resources", "copy the resources into the build output so 'winapp package' includes them"),
+            (bundle, "externalBin", "copy the sidecar binaries into the build output so 'winapp package' includes them"),
+            (bundle, "fileAssociations", "declare them as uap:FileTypeAssociation extensions in appxmanifest.xml"),
+        })
+        {
+            if (node?[property] is JsonNode value)
+            {
+                manual.Add($"{property} ({value.ToJsonString()}): {instead}");
+            }
+        }
+
+        var publisher = StringOf(bundle, "publisher");
+        if (publisher != null)
+        {
+            manual.Add($"bundle.publisher ({publisher}) is the publisher's name; the manifest's Publisher has to be the subject of your signing certificate");
+        }
+        return new BundlerSettings
+        {
+            PackageName = identifier,
+            Publisher = publisher,
+            PublisherDisplayName = publisher,
+            DisplayName = productName,
+            Description = StringOf(bundle, "shortDescription") ?? StringOf(bundle, "longDescription"),
+            Version = ToPackageVersion(version, manual),
+            Executable = binary + ".exe",
+            Icon = icon,
+            ProjectDirectory = directory.Name.Equals("src-tauri", StringComparison.OrdinalIgnoreCase) ? directory.Parent! : directory,
+        };
+    }
+
+    /// <summary>
+    /// MSIX version of a semver: 1.2.3 is 1.2.3.0; pre-release and build suffixes are dropped
+    /// </summary>
+    internal static string ToPackageVersion(string? version, List<string> manual)
+    {
+        if (string.IsNullOrWhiteSpace(version))
+        {
+            return "1.0.0.0";
+        }
+
+        var core = version.Split('-', '+')[0];
+        var parts = core.Split('.').ToList();
+        if (parts.Count > 4 || parts.Any(p => !ushort.TryParse(p, out _)))
+        {
+            manual.Add($"Version {version} isn't a package version; set Version in appxmanifest.xml to four numbers of at most 65535");
+            return "1.0.0.0";
+        }
+        if (core != version)
+        {
+            manual.Add($"Version {version} has a pre-release or build suffix, which MSIX versions can't have; it is {core} in appxmanifest.xml");
+        }
+        while (parts.Count < 4)
+        {
+            parts.Add("0");
+        }
+        return string.Join('.', parts);
+    }
+
+    private static void ApplySettings(XElement root, BundlerSettings settings, List<string> migrated)
+    {
+        var properties = root.Element(FoundationNamespace + "Properties");
+        var application = root.Descendants(FoundationNamespace + "Application").First();
+        var visualElements = application.Element(UapNamespace + "VisualElements")!;
+
+        properties?.Element(FoundationNamespace + "DisplayName")?.SetValue(settings.DisplayName);
+        visualElements.SetAttributeValue("DisplayName", settings.DisplayName);
+        if (settings.PublisherDisplayName != null)
+        {
+            properties?.Element(FoundationNamespace + "PublisherDisplayName")?.SetValue(settings.PublisherDisplayName);
+        }
+        migrated.Add($"Display name {settings.DisplayName}{(settings.PublisherDisplayName != null ? $" by {settings.PublisherDisplayName}" : "")}");
+
+        if (settings.ApplicationId != null)
+        {
+            application.SetAttributeValue("Id", settings.ApplicationId);
+        }
+        if (settings.BackgroundColor != null)
+        {
+            visualElements.SetAttributeValue("BackgroundColor", settings.BackgroundColor);
+            migrated.Add($"Background color {settings.BackgroundColor}");
+        }
+
+        if (root.Descendants(FoundationNamespace + "TargetDeviceFamily").FirstOrDefault() is XElement family)
+        {
+            if (settings.MinVersion != null)
+            {
+                family.SetAttributeValue("MinVersion", settings.MinVersion);
+            }
+            if (settings.MaxVersionTested != null)
+            {
+                family.SetAttributeValue("MaxVersionTested", settings.MaxVersionTested);
+            }
+            if (settings.MinVersion != null || settings.MaxVersionTested != null)
+            {
+                migrated.Add($"TargetDeviceFamily: MinVersion {family.Attribute("MinVersion")?.Value}, MaxVersionTested {family.Attribute("MaxVersionTested")?.Value}");
+            }
+        }
+
+        if (settings.Languages.Count > 0 && root.Element(FoundationNamespace + "Resources") is XElement resources)
+        {
+            resources.RemoveNodes();
+            foreach (var language in settings.Languages)
+            {
+                resources.Add(new XElement(FoundationNamespace + "Resource", new XAttribute("Language", language)));
+            }
+            migrated.Add($"Languages {string.Join(", ", settings.Languages)}");
+        }
+
+        var capabilities = root.Element(FoundationNamespace + "Capabilities");
+        foreach (var capability in settings.Capabilities)
+        {
+            if (capabilities == null || capabilities.Elements().Any(e => e.Attribute("Name")?.Value.Equals(capability, StringComparison.OrdinalIgnoreCase) == true))
+            {
+                continue;
+            }
+            if (DeviceCapabilities.Contains(capability))
+            {
+                // Device capabilities come after the others
+                capabilities.Add(new XElement(FoundationNamespace + "DeviceCapability", new XAttribute("Name", capability)));
+            }
+            else
+            {
+                var ns = UapCapabilities.Contains(capability) ? UapNamespace : RestrictedCapabilities.Contains(capability) ? RescapNamespace : FoundationNamespace;
+                var element = new XElement(ns + "Capability", new XAttribute("Name", capability));
+                if (capabilities.Element(FoundationNamespace + "DeviceCapability") is XElement firstDevice)
+                {
+                    firstDevice.AddBeforeSelf(element);
+                }
+                else
+                {
+                    capabilities.Add(element);
+                }
+            }
+            migrated.Add($"Capability {capability}");
+        }
+
+        if (settings.AutoLaunch)
+        {
+            Extensions(application).Add(new XElement(DesktopNamespace + "Extension",
+                new XAttribute("Category", "windows.startupTask"),
+                new XAttribute("Executable", application.Attribute("Executable")!.Value),
+                new XAttribute("EntryPoint", "Windows.FullTrustApplication"),
+                new XElement(DesktopNamespace + "StartupTask",
+                    new XAttribute("TaskId", $"{application.Attribute("Id")!.Value}Startup"),
+                    new XAttribute("Enabled", "true"),
+                    new XAttribute("DisplayName", settings.DisplayName))));
+            migrated.Add("Startup task from addAutoLaunchExtension");
+        }
+
+        if (settings.CustomExtensions != null)
+        {
+            AddCustomExtensions(root, application, settings.CustomExtensions, migrated);
+        }
+    }
+
+    /// <summary>
+    /// Adds the extensions of electron-builder's customExtensionsPath, which may use any namespace prefix the
+    /// manifest declares
+    /// </summary>
+    private static void AddCustomExtensions(XElement root, XElement application, FileInfo fragment, List<string> migrated)
+    {
+        if (!fragment.Exists)
+        {
+            throw new InvalidOperationException($"customExtensionsPath: {fragment.FullName} not found");
+        }
+
+        var declarations = string.Join(" ", root.Attributes().Where(a => a.IsNamespaceDeclaration)
+            .Select(a => a.Name.Namespace == XNamespace.None ? $"xmlns=\"{a.Value}\"" : $"xmlns:{a.Name.LocalName}=\"{a.Value}\""));
+        XElement wrapper;
+        try
+        {
+            wrapper = XElement.Parse($"<Fragment {declarations}>{File.ReadAllText(fragment.FullName)}</Fragment>");
+        }
+        catch (XmlException ex)
+        {
+            throw new InvalidOperationException($"customExtensionsPath: {fragment.Name} isn't XML the manifest can hold; declare any namespace prefix it uses other than {string.Join(", ", root.Attributes().Where(a => a.IsNamespaceDeclaration && a.Name.Namespace != XNamespace.None).Select(a => a.Name.LocalName))} in appxmanifest.xml: {ex.Message}", ex);
+        }
+
+        // The fragment may be the Extensions element itself or what goes in it
+        var extensions = wrapper.Elements().Count() == 1 && wrapper.Elements().Single().Name.LocalName == "Extensions"
+            ? wrapper.Elements().Single().Elements().ToList()
+            : wrapper.Elements().ToList();
+        Extensions(application).Add(extensions);
+        migrated.Add($"{extensions.Count} extension(s) from {fragment.Name}");
+    }
+
+    private static XElement Extensions(XElement application)
+    {
+        var extensions = application.Element(FoundationNamespace + "Extensions");
+        if (extensions == null)
+        {
+            extensions = new XElement(FoundationNamespace + "Extensions");
+            application.Element(UapNamespace + "VisualElements")!.AddAfterSelf(extensions);
+        }
+        return extensions;
+    }
+
+    /// <summary>
+    /// Replaces the generated images with the ones of electron-builder's build/appx folder, every scale and variant,
+    /// and names the tiles and splash screen it has in the manifest
+    /// </summary>
+    private static int CopyAssets(XElement root, DirectoryInfo source, DirectoryInfo outputDirectory, List<string> migrated)
+    {
+        var target = Directory.CreateDirectory(Path.Combine(outputDirectory.FullName, AssetsFolder));
+        var images = source.EnumerateFiles("*.png").ToList();
+        var baseNames = images.Select(f => Path.GetFileNameWithoutExtension(f.Name).Split('.')[0]).Distinct(StringComparer.OrdinalIgnoreCase);
+        foreach (var baseName in baseNames)
+        {
+            // A generated scale-200 variant would win over the project's unqualified image
+            foreach (var generated in target.EnumerateFiles("*.png").Where(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name))))
+            {
+                generated.Delete();
+            }
+        }
+        foreach (var image in images)
+        {
+            image.CopyTo(Path.Combine(target.FullName, image.Name), overwrite: true);
+        }
+
+        var visualElements = root.Descendants(UapNamespace + "VisualElements").First();
+        bool HasImage(string baseName) => images.Any(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name)));
+        foreach (var (image, attribute) in OptionalTiles.Where(t => HasImage(t.Image)))
+        {
+            var tile = visualElements.Element(UapNamespace + "DefaultTile");
+            if (tile == null)
+            {
+                tile = new XElement(UapNamespace + "DefaultTile");
+                visualElements.Add(tile);
+            }
+            tile.SetAttributeValue(attribute, $"{AssetsFolder}\\{image}.png");
+        }
+        if (HasImage("SplashScreen") && visualElements.Element(UapNamespace + "SplashScreen") == null)
+        {
+            visualElements.Add(new XElement(UapNamespace + "SplashScreen", new XAttribute("Image", $"{AssetsFolder}\\SplashScreen.png")));
+        }
+
+        migrated.Add($"{images.Count} image file(s) from {source.Name} to {AssetsFolder}");
+        return images.Count;
+    }
+
+    private static JsonObject ReadJson(FileInfo path)
+    {
+        try
+        {
+            return JsonNode.Parse(File.ReadAllText(path.FullName), documentOptions: JsonOptions) as JsonObject
+                ?? throw new InvalidOperationException($"{path.FullName} isn't a JSON object");
+        }
+        catch (JsonException ex)
+        {
+            throw new InvalidOperationException($"Failed to read {path.FullName}: {ex.Message}", ex);
+        }
+    }
+
+    private static string? StringOf(JsonNode? node, string property) =>
+        node?[property] is JsonValue value && value.TryGetValue<string>(out var text) && !string.IsNullOrWhiteSpace(text) ? text.Trim() : null;
+
+    /// <summary>
+    /// A string, or an array of them, as electron-builder accepts for most lists
+    /// </summary>
+    private static List<string> StringsOf(JsonNode? node) => node switch
+    {
+        JsonArray array => array.OfType<JsonValue>().Select(v => v.TryGetValue<string>(out var text) ? text : null).OfType<string>().ToList(),
+        JsonValue value when value.TryGetValue<string>(out var text) => [text],
+        _ => [],
+    };
+
+    private static string? ReadCargoPackageName(FileInfo cargoToml)
+    {
+        if (!cargoToml.Exists)
+        {
+            return null;
+        }
+
+        var inPackage = false;
+        foreach (var line in File.ReadLines(cargoToml.FullName).Select(l => l.Trim()))
+        {
+            if (line.StartsWith('['))
+            {
+                inPackage = line == "[package]";
+            }
+            else if (inPackage && line.StartsWith("name", StringComparison.Ordinal) && line.Split('=', 2) is [var key, var value] && key.Trim() == "name")
+            {
+                return value.Trim().Trim('"', '\'');
+            }
+        }
+        return null;
+    }
+
+    /// <summary>
+    /// What a bundler's configuration says about the package, in the terms of the manifest
+    /// </summary>
+    internal sealed class BundlerSettings
+    {
+        public required string PackageName { get; init; }
+        public string? Publisher { get; init; }
+        public string? PublisherDisplayName { get; init; }
+        public required string DisplayName { get; init; }
+        public string? Description { get; init; }
+        public required string Version { get; init; }
+        public required string Executable { get; init; }
+        public string? ApplicationId { get; init; }
+        public string? BackgroundColor { get; init; }
+        public List<string> Languages { get; init; } = [];
+        public List<string> Capabilities { get; init; } = [];
+        public string? MinVersion { get; init; }
+        public string? MaxVersionTested { get; init; }
+        public bool AutoLaunch { get; init; }
+        public FileInfo? CustomExtensions { get; init; }
+        public FileInfo? Icon { get; init; }
+
+        /// <summary>
+        /// electron-builder's build/appx folder of tile and logo images
+        /// </summary>
+        public DirectoryInfo? AssetsDirectory { get; init; }
+
+        /// <summary>
+        /// Folder of package.json, or of the Tauri app with its src-tauri folder
+        /// </summary>
+        public required DirectoryInfo ProjectDirectory { get; init; }
+    }
+}
@@ -7,6 +7,8 @@ using System.Security.Cryptography.X509Certificates;
 using System.Text.RegularExpressions;
 using WinApp.Cli.ConsoleTasks;
 using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
 using WinApp.Cli.Tools;
 
 namespace WinApp.Cli.Services;
@@ -14,10 +16,14 @@ namespace WinApp.Cli.Services;
 internal partial class CertificateService(
     IBuildToolsService buildToolsService,
     IGitignoreService gitignoreService,
-    ICurrentDirectoryProvider currentDirectoryProvider) : ICertificateService
+    ICurrentDirectoryProvider currentDirectoryProvider,
+    IFipsPolicyService fipsPolicyService,
+    IElevationService elevationService) : ICertificateService
 {
     public const string DefaultCertFileName = "devcert.pfx";
 
+    private static readonly string[] PackageExtensions = [".msix", ".appx"];
+
     public record CertificateResult(
         FileInfo CertificatePath,
         string Password,
@@ -34,6 +40,8 @@ internal partial class CertificateService(
         int validDays = 365,
         CancellationToken cancellationToken = default)
     {
+        fipsPolicyService.EnsureAvailable("generate certificates");
+
         // Ensure output directory exists
         outputPath.Directory?.Create();
 
@@ -79,7 +87,8 @@ internal partial class CertificateService(
                 store.Add(cert);
             }
 
-            var pfx = cert.Export(X509ContentType.Pfx, password);
+            // FIPS mode protects the PFX with AES-256/SHA-256 instead of the legacy 3DES default
+            var pfx = cert.ExportPkcs12(fipsPolicyService.Pkcs12ExportParameters, password);
             await File.WriteAllBytesAsync(outputPath.FullName, pfx, cancellationToken);
 
             taskContext.AddDebugMessage($"Certificate generated: {outputPath}");
@@ -98,7 +107,7 @@ internal partial class CertificateService(
         }
     }
 
-    public bool InstallCertificate(FileInfo certPath, string password, bool force, TaskContext taskContext)
+    public ElevatedStep? PlanCertificateTrust(FileInfo certPath, string password, bool force, TaskContext taskContext)
     {
         certPath.Refresh();
         if (!certPath.Exists)
@@ -106,72 +115,84 @@ internal partial class CertificateService(
             throw new FileNotFoundException($"Certificate file not found: {certPath}");
         }
 
-        taskContext.AddDebugMessage($"Installing development certificate: {certPath}");
+        fipsPolicyService.EnsureAvailable("install certificates");
+        fipsPolicyService.EnsurePkcs12(certPath);
 
-        try
-        {
-            // Check if certificate is already installed (unless force is true)
-            if (!force)
-            {
-                try
-                {
-                    // Load the certificate to get its thumbprint/subject for comparison
-                    using var certToCheck = X509CertificateLoader.LoadPkcs12FromFile(
-                        certPath.FullName,
-                        password,
-                        X509KeyStorageFlags.Exportable);
-
-                    // Check if this certificate is already in the TrustedPeople store
-                    using var store = new X509Store(StoreName.TrustedPeople, StoreLocation.LocalMachine);
-                    store.Open(OpenFlags.ReadOnly);
-
-                    var existingCerts = store.Certificates.Find(
-                        X509FindType.FindByThumbprint,
-                        certToCheck.Thumbprint,
-                        validOnly: false);
-
-                    if (existingCerts.Count > 0)
-                    {
-                        taskContext.AddDebugMessage("Certificate appears to already be installed");
-                        return false;
-                    }
-                }
-                catch (Exception ex)
-                {
-                    // Continue with installation if check fails
-                    taskContext.AddDebugMessage($"Could not check existing certificates: {ex.Message}");
-                }
-            }
-
-            // Install to TrustedPeople store (required for MSIX sideloading)
-            // Load the certificate from the PFX file
-            using var cert = X509CertificateLoader.LoadPkcs12FromFile(
-                certPath.FullName,
-                password,
-                X509KeyStorageFlags.MachineKeySet | X509KeyStorageFlags.PersistKeySet);
+        using var certificate = X509CertificateLoader.LoadPkcs12FromFile(
+            certPath.FullName,
+            password,
+            X509KeyStorageFlags.Exportable);
 
-            // Install to LocalMachine\TrustedPeople store (requires elevation)
+        // Check if certificate is already installed (unless force is true)
+        if (!force)
+        {
             try
             {
                 using var store = new X509Store(StoreName.TrustedPeople, StoreLocation.LocalMachine);
-                store.Open(OpenFlags.ReadWrite);
-                store.Add(cert);
+                store.Open(OpenFlags.ReadOnly);
+
+                var existingCerts = store.Certificates.Find(
+                    X509FindType.FindByThumbprint,
+                    certificate.Thumbprint,
+                    validOnly: false);
+
+                if (existingCerts.Count > 0)
+                {
+                    taskContext.AddDebugMessage("Certificate appears to already be installed");
+                    return null;
+                }
             }
-            catch (CryptographicException ex) when (ex.Message.Contains("Access is denied"))
+            catch (CryptographicException ex)
             {
-                throw new InvalidOperationException(
-                    "Failed to install certificate: Administrator privileges are required to install certificates to the LocalMachine store. " +
-                    "Please run this command as an administrator.", ex);
+                // Continue with installation if check fails
+                taskContext.AddDebugMessage($"Could not check existing certificates: {ex.Message}");
             }
+        }
+
+        return new ElevatedStep(
+            $@"Trust certificate {certificate.Subject} (LocalMachine\TrustedPeople)",
+            TrustScript(certificate));
+    }
+
+    /// <summary>
+    /// Adds the public certificate to LocalMachine\TrustedPeople, which MSIX sideloading trusts packages signed by.
+    /// The certificate is in the script rather than in a file, which another process could swap before the
+    /// elevated script reads it.
+    /// </summary>
+    internal static string TrustScript(X509Certificate2 certificate)
+    {
+        var der = Convert.ToBase64String(certificate.Export(X509ContentType.Cert));
+        return $"$certificate = [System.Security.Cryptography.X509Certificates.X509Certificate2]::new([Convert]::FromBase64String('{der}')); "
+            + "$store = [System.Security.Cryptography.X509Certificates.X509Store]::new('TrustedPeople', 'LocalMachine'); "
+            + "$store.Open('ReadWrite'); try { $store.Add($certificate) } finally { $store.Close() }";
+    }
 
-            taskContext.AddDebugMessage("Certificate installed successfully to TrustedPeople store");
+    public async Task<bool> InstallCertificateAsync(FileInfo certPath, string password, bool force, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        taskContext.AddDebugMessage($"Installing development certificate: {certPath}");
 
-            return true;
+        ElevatedStep? step;
+        try
+        {
+            step = PlanCertificateTrust(certPath, password, force, taskContext);
         }
-        catch (Exception error)
+        catch (Exception error) when (error is not FileNotFoundException)
         {
             throw new InvalidOperationException($"Failed to install development certificate: {error.Message}", error);
         }
+        if (step == null)
+        {
+            return false;
+        }
+
+        var result = (await elevationService.RunAsync([step], taskContext, cancellationToken)).Single();
+        if (!result.Succeeded)
+        {
+            throw new InvalidOperationException($"Failed to install development certificate: {result.Error}");
+        }
+
+        taskContext.AddDebugMessage("Certificate installed successfully to TrustedPeople store");
+        return true;
     }
 
     /// <summary>
@@ -197,11 +218,33 @@ internal partial class CertificateService(
             throw new FileNotFoundException($"Certificate file not found: {certificatePath}");
         }
 
-        var arguments = $@"sign /f ""{certificatePath}"" /p ""{password}"" /fd SHA256";
+        if (fipsPolicyService.IsEnabled)
+        {
+            fipsPolicyService.EnsureAvailable("sign files");
+            fipsPolicyService.EnsurePkcs12(certificatePath);
+            using var signingCertificate = X509CertificateLoader.LoadPkcs12FromFile(certificatePath.FullName, password, X509KeyStorageFlags.EphemeralKeySet);
+            fipsPolicyService.EnsureSigningCertificate(signingCertificate);
+        }
+
+        // signtool rejects packages whose signature digest differs from the block map hash algorithm
+        var digestAlgorithm = "SHA256";
+        if (PackageExtensions.Contains(filePath.Extension, StringComparer.OrdinalIgnoreCase))
+        {
+            try
+            {
+                digestAlgorithm = (await AppxPackageSigner.ReadHashAlgorithmAsync(filePath, cancellationToken)).Name!;
+            }
+            catch (Exception ex) when (ex is InvalidOperationException or InvalidDataException)
+            {
+                taskContext.AddDebugMessage($"Could not read the package hash algorithm, signing with SHA256: {ex.Message}");
+            }
+        }
+
+        var arguments = $@"sign /f ""{certificatePath}"" /p ""{password}"" /fd {digestAlgorithm}";
 
         if (!string.IsNullOrWhiteSpace(timestampUrl))
         {
-            arguments += $@" /tr ""{timestampUrl}"" /td SHA256";
+            arguments += $@" /tr ""{timestampUrl}"" /td {digestAlgorithm}";
         }
 
         arguments += $@" ""{filePath}""";
@@ -328,7 +371,7 @@ internal partial class CertificateService(
             {
                 taskContext.AddDebugMessage("Installing certificate...");
 
-                var installResult = InstallCertificate(result.CertificatePath, password, false, taskContext);
+                var installResult = await InstallCertificateAsync(result.CertificatePath, password, false, taskContext, cancellationToken);
                 if (installResult)
                 {
                     taskContext.AddStatusMessage($"{UiSymbols.Check} Certificate installed successfully!");
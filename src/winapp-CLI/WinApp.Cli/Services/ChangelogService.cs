@@ -0,0 +1,92 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Changelog;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class ChangelogService(IGitService gitService, ICurrentDirectoryProvider currentDirectoryProvider) : IChangelogService
+{
+    // Unit and record separators keep multi-line bodies intact
+    private const string LogFormat = "--format=%H%x1f%s%x1f%b%x1e";
+
+    public async Task<ChangelogResult> BuildAsync(ChangelogOptions options, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var directory = currentDirectoryProvider.GetCurrentDirectory();
+        var from = options.From ?? await FindPreviousTagAsync(directory, options.To, options.TagPattern, cancellationToken);
+        taskContext.AddDebugMessage(from == null
+            ? $"No earlier tag found; using the history up to {options.To}"
+            : $"Range {from}..{options.To}");
+
+        List<string> arguments = ["log", LogFormat, options.Source == ChangelogSource.Prs ? "--first-parent" : "--no-merges"];
+        arguments.Add(from == null ? options.To : $"{from}..{options.To}");
+        var log = await gitService.RunAsync(directory, arguments, cancellationToken);
+
+        var entries = new List<ChangelogEntry>();
+        var commitCount = 0;
+        foreach (var record in log.Split('\x1e', StringSplitOptions.RemoveEmptyEntries))
+        {
+            var fields = record.TrimStart('\n', '\r').Split('\x1f');
+            if (fields.Length < 3)
+            {
+                continue;
+            }
+
+            commitCount++;
+            var (sha, subject, body) = (fields[0], fields[1], fields[2].Replace("\r\n", "\n"));
+            var isPullRequest = ConventionalCommit.TryGetPullRequest(subject, body, out var number, out var title, out var description);
+            if (options.Source == ChangelogSource.Prs && !isPullRequest)
+            {
+                taskContext.AddDebugMessage($"Skipping {sha[..7]}, which was not merged from a pull request: {subject}");
+                continue;
+            }
+
+            var entry = ConventionalCommit.Parse(sha, title, description, isPullRequest ? number : null, options.IncludeAll);
+            // Pull request titles often do not follow the convention; they are user-facing either way
+            if (entry == null && options.Source == ChangelogSource.Prs && !ConventionalCommit.IsConventional(title))
+            {
+                entry = ConventionalCommit.Parse(sha, title, description, number, includeAll: true);
+            }
+
+            if (entry == null)
+            {
+                taskContext.AddDebugMessage($"Skipping {sha[..7]}: {subject}");
+            }
+            else if (!entry.AppliesTo(options.Channel))
+            {
+                taskContext.AddDebugMessage($"Skipping {sha[..7]}, which is limited to {string.Join(", ", entry.Channels)}: {subject}");
+            }
+            else
+            {
+                entries.Add(entry);
+            }
+        }
+
+        // git log lists the newest commit first; release notes read better in the order changes were made
+        entries.Reverse();
+        return new ChangelogResult(from, options.To, entries, commitCount);
+    }
+
+    private async Task<string?> FindPreviousTagAsync(string directory, string to, string? tagPattern, CancellationToken cancellationToken)
+    {
+        // The tag on 'to' itself is the release being described, so start looking at its parent
+        List<string> arguments = ["describe", "--tags", "--abbrev=0"];
+        if (tagPattern != null)
+        {
+            arguments.Add($"--match={tagPattern}");
+        }
+        arguments.Add($"{to}^");
+
+        try
+        {
+            return (await gitService.RunAsync(directory, arguments, cancellationToken)).Trim();
+        }
+        catch (InvalidOperationException)
+        {
+            // No tag before 'to', or 'to' is the first commit
+            return null;
+        }
+    }
+}
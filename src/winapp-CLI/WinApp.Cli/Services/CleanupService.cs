@@ -0,0 +1,240 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.RegularExpressions;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal partial class CleanupService(
+    IInstalledPackageService installedPackageService,
+    IElevationService elevationService,
+    ICurrentDirectoryProvider currentDirectoryProvider) : ICleanupService
+{
+    // Friendly name CertificateService gives the certificates it generates
+    private const string DevCertificateFriendlyName = "MSIX Dev Certificate";
+
+    private static readonly string[] ManifestFileNames = ["appxmanifest.xml", "Package.appxmanifest"];
+
+    private static readonly (StoreName Name, StoreLocation Location)[] CertificateStores =
+    [
+        (StoreName.My, StoreLocation.CurrentUser),
+        (StoreName.TrustedPeople, StoreLocation.LocalMachine),
+    ];
+
+    public async Task<CleanupPlan> FindAsync(bool allWinapp, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var (packages, stagedChecked) = await installedPackageService.GetPackagesAsync(taskContext, cancellationToken);
+        var scope = GetScope(allWinapp);
+        return new CleanupPlan(Classify(packages, GetStoreCertificates(taskContext), scope), stagedChecked);
+    }
+
+    public async Task<CleanupResult> RemoveAsync(IReadOnlyList<CleanupItem> items, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var removed = new List<CleanupItem>();
+        var failed = new List<(CleanupItem, string)>();
+        var elevatedItems = new List<CleanupItem>();
+
+        // Packages first, so their certificates are no longer in use when they are removed
+        foreach (var item in items.OrderBy(i => i.Kind == CleanupItemKind.OrphanedCertificate))
+        {
+            if (NeedsElevation(item))
+            {
+                elevatedItems.Add(item);
+                continue;
+            }
+            try
+            {
+                if (item.Kind == CleanupItemKind.OrphanedCertificate)
+                {
+                    RemoveCertificate(item.Id);
+                }
+                else
+                {
+                    await installedPackageService.UninstallAsync(item.Id, allUsers: false, taskContext, cancellationToken);
+                }
+                removed.Add(item);
+            }
+            catch (Exception ex) when (ex is InvalidOperationException or CryptographicException)
+            {
+                failed.Add((item, ex.Message));
+            }
+        }
+
+        // Staged packages and machine certificates go last, after a single elevation prompt
+        var results = await elevationService.RunAsync(elevatedItems.Select(ToElevatedStep).ToList(), taskContext, cancellationToken);
+        foreach (var (item, result) in elevatedItems.Zip(results))
+        {
+            if (result.Succeeded)
+            {
+                removed.Add(item);
+            }
+            else
+            {
+                failed.Add((item, result.Error!));
+            }
+        }
+
+        return new CleanupResult(removed, failed);
+    }
+
+    /// <summary>
+    /// Whether removing the item needs administrator rights: staged packages and certificates of LocalMachine stores
+    /// </summary>
+    internal static bool NeedsElevation(CleanupItem item) => item.Kind switch
+    {
+        CleanupItemKind.StagedPackage => true,
+        CleanupItemKind.OrphanedCertificate => item.Id.StartsWith($@"{StoreLocation.LocalMachine}\", StringComparison.Ordinal),
+        _ => false,
+    };
+
+    // The Id of a certificate, Location\Store\Thumbprint, is its path in the Cert: drive
+    internal static ElevatedStep ToElevatedStep(CleanupItem item) => item.Kind == CleanupItemKind.StagedPackage
+        ? new ElevatedStep($"Remove staged package {item.Id}", $"Remove-AppxPackage -Package '{item.Id}' -AllUsers")
+        : new ElevatedStep($@"Remove certificate {item.Description} from {Path.GetDirectoryName(item.Id)}", $@"Remove-Item -LiteralPath 'Cert:\{item.Id}'");
+
+    /// <summary>
+    /// Picks the packages and certificates to remove. Packages are in scope when they were registered from the
+    /// project folder or carry its Identity Name, or with <see cref="CleanupScope.AllWinapp"/>, when winapp created
+    /// them anywhere. Certificates are orphaned once no remaining package uses their publisher.
+    /// </summary>
+    internal static List<CleanupItem> Classify(IReadOnlyList<InstalledPackage> packages, IEnumerable<StoreCertificate> certificates, CleanupScope scope)
+    {
+        var items = new List<CleanupItem>();
+        foreach (var package in packages)
+        {
+            if (!IsInScope(package, scope))
+            {
+                continue;
+            }
+
+            if (package.Staged)
+            {
+                items.Add(new CleanupItem(CleanupItemKind.StagedPackage, package.FullName, "staged for no user"));
+            }
+            else if (!package.InstallLocationExists)
+            {
+                items.Add(new CleanupItem(CleanupItemKind.StaleRegistration, package.FullName, $"folder is gone: {package.InstallLocation}"));
+            }
+            else if (package.ExternalLocation != null && !package.ExternalLocationExists)
+            {
+                items.Add(new CleanupItem(CleanupItemKind.StaleRegistration, package.FullName, $"external location is gone: {package.ExternalLocation}"));
+            }
+            else if (!package.Status.Equals("Ok", StringComparison.OrdinalIgnoreCase))
+            {
+                items.Add(new CleanupItem(CleanupItemKind.StaleRegistration, package.FullName, $"status: {package.Status}"));
+            }
+            else if (package.DevelopmentMode)
+            {
+                items.Add(new CleanupItem(CleanupItemKind.LoosePackage, package.FullName, $"registered from {package.ExternalLocation ?? package.InstallLocation}"));
+            }
+        }
+
+        var removedPackages = items.Select(i => i.Id).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        var publishersInUse = packages
+            .Where(p => !p.Staged && !removedPackages.Contains(p.FullName))
+            .Select(p => p.Publisher)
+            .ToHashSet(StringComparer.OrdinalIgnoreCase);
+        foreach (var certificate in certificates)
+        {
+            var isWinapp = certificate.FriendlyName == DevCertificateFriendlyName || certificate.Subject.StartsWith(BranchIdentity.PublisherPrefix, StringComparison.OrdinalIgnoreCase);
+            var inScope = isWinapp && (scope.AllWinapp || scope.Publishers.Contains(certificate.Subject));
+            if (inScope && !publishersInUse.Contains(certificate.Subject))
+            {
+                items.Add(new CleanupItem(CleanupItemKind.OrphanedCertificate, $@"{certificate.Store}\{certificate.Thumbprint}", certificate.Subject));
+            }
+        }
+
+        return items;
+    }
+
+    private static bool IsInScope(InstalledPackage package, CleanupScope scope)
+    {
+        var inProject = IsUnder(package.InstallLocation, scope.ProjectDirectory)
+            || IsUnder(package.ExternalLocation, scope.ProjectDirectory)
+            || scope.IdentityNames.Contains(DebugOrBranchSuffixRegex().Replace(package.Name, ""));
+        if (inProject || !scope.AllWinapp)
+        {
+            return inProject;
+        }
+
+        // create-debug-identity registers from .winapp\debug and names packages *.debug; branch builds end in .br<hash>
+        return DebugOrBranchSuffixRegex().IsMatch(package.Name)
+            || (package.InstallLocation?.Replace('/', '\\').Contains(@"\.winapp\", StringComparison.OrdinalIgnoreCase) ?? false);
+    }
+
+    private static bool IsUnder(string? path, string directory)
+    {
+        if (string.IsNullOrEmpty(path))
+        {
+            return false;
+        }
+
+        var relative = Path.GetRelativePath(directory, path);
+        return relative == "." || (!relative.StartsWith("..", StringComparison.Ordinal) && !Path.IsPathRooted(relative));
+    }
+
+    private CleanupScope GetScope(bool allWinapp)
+    {
+        var directory = currentDirectoryProvider.GetCurrentDirectory();
+        var names = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+        var publishers = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+        foreach (var manifest in ManifestFileNames.Select(n => new FileInfo(Path.Combine(directory, n))).Where(f => f.Exists))
+        {
+            var identity = XDocument.Load(manifest.FullName).Root?.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity");
+            if (identity?.Attribute("Name")?.Value is { } name)
+            {
+                names.Add(name);
+            }
+            if (identity?.Attribute("Publisher")?.Value is { } publisher)
+            {
+                publishers.Add(publisher);
+            }
+        }
+
+        return new CleanupScope(directory, names, publishers, allWinapp);
+    }
+
+    private static List<StoreCertificate> GetStoreCertificates(TaskContext taskContext)
+    {
+        var certificates = new List<StoreCertificate>();
+        foreach (var (name, location) in CertificateStores)
+        {
+            try
+            {
+                using var store = new X509Store(name, location);
+                store.Open(OpenFlags.ReadOnly);
+                foreach (var certificate in store.Certificates)
+                {
+                    certificates.Add(new StoreCertificate($@"{location}\{name}", certificate.Thumbprint, certificate.Subject, certificate.FriendlyName));
+                    certificate.Dispose();
+                }
+            }
+            catch (CryptographicException ex)
+            {
+                taskContext.AddDebugMessage($"Could not read {location}\\{name}: {ex.Message}");
+            }
+        }
+        return certificates;
+    }
+
+    private static void RemoveCertificate(string id)
+    {
+        // Id is Location\Store\Thumbprint
+        var parts = id.Split('\\');
+        using var store = new X509Store(Enum.Parse<StoreName>(parts[1]), Enum.Parse<StoreLocation>(parts[0]));
+        store.Open(OpenFlags.ReadWrite);
+        foreach (var certificate in store.Certificates.Find(X509FindType.FindByThumbprint, parts[2], validOnly: false))
+        {
+            store.Remove(certificate);
+            certificate.Dispose();
+        }
+    }
+
+    [GeneratedRegex(@"\.(debug|br[0-9a-f]{8})$", RegexOptions.IgnoreCase)]
+    private static partial Regex DebugOrBranchSuffixRegex();
+}
@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 using System.Text;
+using System.Text.Json;
 using WinApp.Cli.Models;
 
 namespace WinApp.Cli.Services;
@@ -40,12 +41,27 @@ internal sealed class ConfigService : IConfigService
         ConfigPath.Refresh();
     }
 
-    private static WinappConfig Parse(string yaml)
+    internal static WinappConfig Parse(string yaml)
     {
         var cfg = new WinappConfig();
         using var sr = new StringReader(yaml);
         string? line;
+        string? section = null;
         string? currentName = null;
+        ProjectConfig? currentProject = null;
+        EnvironmentVariableConfig? currentVariable = null;
+        ShortcutConfig? currentShortcut = null;
+        BadgeConfig? currentBadge = null;
+        SuppressionConfig? currentSuppression = null;
+        LicenseAcceptanceConfig? currentLicense = null;
+        AnalyzerConfig? currentAnalyzer = null;
+        string? matrixList = null;
+        BrandingConfig? currentBranding = null;
+        ChannelConfig? currentChannel = null;
+        MatrixArchitectureConfig? currentArchitecture = null;
+        TestTargetConfig? currentTarget = null;
+        var inInputs = false;
+        var inProtocols = false;
         while ((line = sr.ReadLine()) != null)
         {
             var t = line.Trim();
@@ -54,22 +70,500 @@ internal sealed class ConfigService : IConfigService
                 continue;
             }
 
-            if (t.Equals("packages:", StringComparison.OrdinalIgnoreCase))
+            // Top-level keys start a section
+            if (!char.IsWhiteSpace(line[0]) && t.EndsWith(':'))
             {
+                section = t[..^1].ToLowerInvariant();
+                continue;
+            }
+
+            if (section == "projects")
+            {
+                if (t.StartsWith("- name:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentProject = new ProjectConfig { Name = Unquote(t["- name:".Length..]) };
+                    cfg.Projects.Add(currentProject);
+                    inInputs = false;
+                }
+                else if (currentProject is null)
+                {
+                    continue;
+                }
+                else if (t.StartsWith("path:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentProject.Path = Unquote(t["path:".Length..]);
+                    inInputs = false;
+                }
+                else if (t.Equals("inputs:", StringComparison.OrdinalIgnoreCase))
+                {
+                    inInputs = true;
+                }
+                else if (inInputs && t.StartsWith('-'))
+                {
+                    currentProject.Inputs.Add(Unquote(t[1..]));
+                }
+                continue;
+            }
+
+            if (section == "environment")
+            {
+                if (t.StartsWith("- name:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentVariable = new EnvironmentVariableConfig { Name = Unquote(t["- name:".Length..]) };
+                    cfg.Environment.Add(currentVariable);
+                }
+                else if (currentVariable is not null && t.StartsWith("value:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentVariable.Value = Unquote(t["value:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "shortcuts")
+            {
+                if (t.StartsWith("- file:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentShortcut = new ShortcutConfig { File = Unquote(t["- file:".Length..]) };
+                    cfg.Shortcuts.Add(currentShortcut);
+                }
+                else if (currentShortcut is null)
+                {
+                    continue;
+                }
+                else if (t.StartsWith("application:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentShortcut.Application = Unquote(t["application:".Length..]);
+                }
+                else if (t.StartsWith("arguments:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentShortcut.Arguments = Unquote(t["arguments:".Length..]);
+                }
+                else if (t.StartsWith("icon:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentShortcut.Icon = Unquote(t["icon:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "splash")
+            {
+                cfg.Splash ??= new SplashConfig();
+                if (t.StartsWith("image:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Splash.Image = Unquote(t["image:".Length..]);
+                }
+                else if (t.StartsWith("backgroundColor:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Splash.BackgroundColor = Unquote(t["backgroundColor:".Length..]);
+                }
+                else if (t.StartsWith("extended:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Splash.Extended = Unquote(t["extended:".Length..]).Equals("true", StringComparison.OrdinalIgnoreCase);
+                }
+                continue;
+            }
+
+            if (section == "badges")
+            {
+                if (t.StartsWith("- profile:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentBadge = new BadgeConfig { Profile = Unquote(t["- profile:".Length..]) };
+                    cfg.Badges.Add(currentBadge);
+                }
+                else if (currentBadge is null)
+                {
+                    continue;
+                }
+                else if (t.StartsWith("text:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentBadge.Text = Unquote(t["text:".Length..]);
+                }
+                else if (t.StartsWith("color:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentBadge.Color = Unquote(t["color:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "rules")
+            {
+                var separator = t.LastIndexOf(':');
+                if (separator > 0)
+                {
+                    cfg.Rules[Unquote(t[..separator])] = Unquote(t[(separator + 1)..]).ToLowerInvariant();
+                }
+                continue;
+            }
+
+            if (section == "settings")
+            {
+                // Values such as URLs contain colons, keys don't
+                var separator = t.IndexOf(':');
+                if (separator > 0)
+                {
+                    cfg.Settings[Unquote(t[..separator])] = Unquote(t[(separator + 1)..]);
+                }
+                continue;
+            }
+
+            if (section == "suppressions")
+            {
+                if (t.StartsWith("- rule:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentSuppression = new SuppressionConfig { Rule = Unquote(t["- rule:".Length..]) };
+                    cfg.Suppressions.Add(currentSuppression);
+                }
+                else if (currentSuppression is null)
+                {
+                    continue;
+                }
+                else if (t.StartsWith("file:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentSuppression.File = Unquote(t["file:".Length..]);
+                }
+                else if (t.StartsWith("line:", StringComparison.OrdinalIgnoreCase) && int.TryParse(Unquote(t["line:".Length..]), out var suppressedLine))
+                {
+                    currentSuppression.Line = suppressedLine;
+                }
+                else if (t.StartsWith("reason:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentSuppression.Reason = Unquote(t["reason:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "licenses")
+            {
+                if (t.StartsWith("- package:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense = new LicenseAcceptanceConfig { Package = Unquote(t["- package:".Length..]) };
+                    cfg.Licenses.Add(currentLicense);
+                }
+                else if (currentLicense is null)
+                {
+                    continue;
+                }
+                else if (t.StartsWith("version:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense.Version = Unquote(t["version:".Length..]);
+                }
+                else if (t.StartsWith("license:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense.License = Unquote(t["license:".Length..]);
+                }
+                else if (t.StartsWith("sha256:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense.Sha256 = Unquote(t["sha256:".Length..]);
+                }
+                else if (t.StartsWith("acceptedBy:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense.AcceptedBy = Unquote(t["acceptedBy:".Length..]);
+                }
+                else if (t.StartsWith("acceptedAt:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentLicense.AcceptedAt = Unquote(t["acceptedAt:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "analyzers")
+            {
+                if (t.StartsWith("- id:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentAnalyzer = new AnalyzerConfig { Id = Unquote(t["- id:".Length..]) };
+                    cfg.Analyzers.Add(currentAnalyzer);
+                }
+                else if (currentAnalyzer is not null && t.StartsWith("script:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentAnalyzer.Script = Unquote(t["script:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "store")
+            {
+                cfg.Store ??= new StoreConfig();
+                if (t.StartsWith("appId:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Store.AppId = Unquote(t["appId:".Length..]);
+                }
+                else if (t.StartsWith("packageFamilyName:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Store.PackageFamilyName = Unquote(t["packageFamilyName:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "crashes")
+            {
+                cfg.Crashes ??= new CrashesConfig();
+                if (t.StartsWith("endpoint:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Crashes.Endpoint = Unquote(t["endpoint:".Length..]);
+                }
+                else if (t.StartsWith("symbolsEndpoint:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Crashes.SymbolsEndpoint = Unquote(t["symbolsEndpoint:".Length..]);
+                }
+                else if (t.StartsWith("product:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Crashes.Product = Unquote(t["product:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "sign")
+            {
+                cfg.Sign ??= new SigningConfig();
+                var separator = t.IndexOf(':');
+                if (separator <= 0)
+                {
+                    continue;
+                }
+                var value = Unquote(t[(separator + 1)..]);
+                switch (t[..separator].ToLowerInvariant())
+                {
+                    case "provider" when Enum.TryParse<SigningProvider>(value, ignoreCase: true, out var provider):
+                        cfg.Sign.Provider = provider;
+                        break;
+                    case "certificate":
+                        cfg.Sign.Certificate = value;
+                        break;
+                    case "endpoint":
+                        cfg.Sign.Endpoint = value;
+                        break;
+                    case "account":
+                        cfg.Sign.Account = value;
+                        break;
+                    case "profile":
+                        cfg.Sign.Profile = value;
+                        break;
+                    case "vault":
+                        cfg.Sign.Vault = value;
+                        break;
+                    case "certificatename":
+                        cfg.Sign.CertificateName = value;
+                        break;
+                }
+                continue;
+            }
+
+            if (section == "win32manifest")
+            {
+                cfg.Win32Manifest ??= new Win32ManifestConfig();
+                var separator = t.IndexOf(':');
+                if (separator <= 0)
+                {
+                    continue;
+                }
+                var value = Unquote(t[(separator + 1)..]);
+                switch (t[..separator].ToLowerInvariant())
+                {
+                    case "dpiawareness":
+                        cfg.Win32Manifest.DpiAwareness = value;
+                        break;
+                    case "longpathaware":
+                        cfg.Win32Manifest.LongPathAware = value.Equals("true", StringComparison.OrdinalIgnoreCase);
+                        break;
+                    case "activecodepage":
+                        cfg.Win32Manifest.ActiveCodePage = value.Length == 0 || value.Equals("none", StringComparison.OrdinalIgnoreCase) ? null : value;
+                        break;
+                    case "supportedos":
+                        cfg.Win32Manifest.SupportedOS = [.. value.Split(',', StringSplitOptions.TrimEntries | StringSplitOptions.RemoveEmptyEntries)];
+                        break;
+                }
+                continue;
+            }
+
+            if (section == "locale")
+            {
+                cfg.Locale ??= new LocaleConfig();
+                if (t.StartsWith("language:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Locale.Language = Unquote(t["language:".Length..]);
+                }
+                else if (t.StartsWith("utf8Mode:", StringComparison.OrdinalIgnoreCase))
+                {
+                    cfg.Locale.Utf8Mode = Unquote(t["utf8Mode:".Length..]).Equals("true", StringComparison.OrdinalIgnoreCase);
+                }
+                continue;
+            }
+
+            if (section == "build")
+            {
+                cfg.Build ??= new BuildConfig();
+                var separator = t.IndexOf(':');
+                if (separator <= 0)
+                {
+                    continue;
+                }
+                var value = Unquote(t[(separator + 1)..]);
+                switch (t[..separator].ToLowerInvariant())
+                {
+                    case "system" when Enum.TryParse<BuildSystem>(value, ignoreCase: true, out var system):
+                        cfg.Build.System = system;
+                        break;
+                    case "project":
+                        cfg.Build.Project = value;
+                        break;
+                    case "configuration":
+                        cfg.Build.Configuration = value;
+                        break;
+                    case "command":
+                        cfg.Build.Command = value;
+                        break;
+                    case "arguments":
+                        cfg.Build.Arguments = value;
+                        break;
+                    case "output":
+                        cfg.Build.Output = value;
+                        break;
+                }
+                continue;
+            }
+
+            if (section == "testmatrix")
+            {
+                cfg.TestMatrix ??= new TestMatrixConfig();
+                if (t.StartsWith("- name:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget = new TestTargetConfig { Name = Unquote(t["- name:".Length..]) };
+                    cfg.TestMatrix.Targets.Add(currentTarget);
+                }
+                else if (currentTarget is null)
+                {
+                    if (t.StartsWith("smokeTest:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        cfg.TestMatrix.SmokeTest = Unquote(t["smokeTest:".Length..]);
+                    }
+                    else if (t.StartsWith("timeout:", StringComparison.OrdinalIgnoreCase) && int.TryParse(Unquote(t["timeout:".Length..]), out var timeout))
+                    {
+                        cfg.TestMatrix.Timeout = timeout;
+                    }
+                }
+                else if (t.StartsWith("provider:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.Provider = Unquote(t["provider:".Length..]).ToLowerInvariant();
+                }
+                else if (t.StartsWith("image:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.Image = Unquote(t["image:".Length..]);
+                }
+                else if (t.StartsWith("switch:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.Switch = Unquote(t["switch:".Length..]);
+                }
+                else if (t.StartsWith("memory:", StringComparison.OrdinalIgnoreCase) && int.TryParse(Unquote(t["memory:".Length..]), out var memory))
+                {
+                    currentTarget.Memory = memory;
+                }
+                else if (t.StartsWith("lab:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.Lab = Unquote(t["lab:".Length..]);
+                }
+                else if (t.StartsWith("resourceGroup:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.ResourceGroup = Unquote(t["resourceGroup:".Length..]);
+                }
+                else if (t.StartsWith("size:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentTarget.Size = Unquote(t["size:".Length..]);
+                }
+                continue;
+            }
+
+            if (section == "matrix")
+            {
+                cfg.Matrix ??= new MatrixConfig();
+                if (t is "brandings:" or "channels:" or "architectures:")
+                {
+                    matrixList = t[..^1];
+                    inProtocols = false;
+                }
+                else if (t.StartsWith("- name:", StringComparison.OrdinalIgnoreCase))
+                {
+                    var name = Unquote(t["- name:".Length..]);
+                    inProtocols = false;
+                    switch (matrixList)
+                    {
+                        case "brandings":
+                            currentBranding = new BrandingConfig { Name = name };
+                            cfg.Matrix.Brandings.Add(currentBranding);
+                            break;
+                        case "channels":
+                            currentChannel = new ChannelConfig { Name = name };
+                            cfg.Matrix.Channels.Add(currentChannel);
+                            break;
+                        case "architectures":
+                            currentArchitecture = new MatrixArchitectureConfig { Name = name };
+                            cfg.Matrix.Architectures.Add(currentArchitecture);
+                            break;
+                    }
+                }
+                else if (matrixList == "brandings" && currentBranding is not null)
+                {
+                    if (inProtocols && t.StartsWith('-'))
+                    {
+                        currentBranding.Protocols.Add(Unquote(t[1..]));
+                    }
+                    else if (t.Equals("protocols:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        inProtocols = true;
+                    }
+                    else if (t.StartsWith("identityName:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.IdentityName = Unquote(t["identityName:".Length..]);
+                    }
+                    else if (t.StartsWith("publisherDisplayName:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.PublisherDisplayName = Unquote(t["publisherDisplayName:".Length..]);
+                    }
+                    else if (t.StartsWith("publisher:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.Publisher = Unquote(t["publisher:".Length..]);
+                    }
+                    else if (t.StartsWith("displayName:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.DisplayName = Unquote(t["displayName:".Length..]);
+                    }
+                    else if (t.StartsWith("assets:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.Assets = Unquote(t["assets:".Length..]);
+                    }
+                    else if (t.StartsWith("certificate:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentBranding.Certificate = Unquote(t["certificate:".Length..]);
+                    }
+                }
+                else if (matrixList == "channels" && currentChannel is not null)
+                {
+                    if (t.StartsWith("nameSuffix:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentChannel.NameSuffix = Unquote(t["nameSuffix:".Length..]);
+                    }
+                    else if (t.StartsWith("displayNameSuffix:", StringComparison.OrdinalIgnoreCase))
+                    {
+                        currentChannel.DisplayNameSuffix = Unquote(t["displayNameSuffix:".Length..]);
+                    }
+                }
+                else if (matrixList == "architectures" && currentArchitecture is not null && t.StartsWith("input:", StringComparison.OrdinalIgnoreCase))
+                {
+                    currentArchitecture.Input = Unquote(t["input:".Length..]);
+                }
                 continue;
             }
 
             if (t.StartsWith("- name:", StringComparison.OrdinalIgnoreCase))
             {
-                currentName = t.Substring("- name:".Length).Trim().Trim('"', '\'');
+                currentName = Unquote(t["- name:".Length..]);
             }
             else if (t.StartsWith("name:", StringComparison.OrdinalIgnoreCase))
             {
-                currentName = t.Substring("name:".Length).Trim().Trim('"', '\'');
+                currentName = Unquote(t["name:".Length..]);
             }
             else if (t.StartsWith("version:", StringComparison.OrdinalIgnoreCase) && currentName is not null)
             {
-                var version = t.Substring("version:".Length).Trim().Trim('"', '\'');
+                var version = Unquote(t["version:".Length..]);
                 cfg.Packages.Add(new PackagePin { Name = currentName, Version = version });
                 currentName = null;
             }
@@ -77,6 +571,17 @@ internal sealed class ConfigService : IConfigService
         return cfg;
     }
 
+    private static string Unquote(string value)
+    {
+        value = value.Trim();
+        return value.Length >= 2 && value[0] == '\'' && value[^1] == '\''
+            ? value[1..^1].Replace("''", "'")
+            : value.Trim('"', '\'');
+    }
+
+    // Single quotes keep YAML from reading [{Known Folder}] paths as lists and backslashes as escapes
+    private static string Quote(string value) => $"'{value.Replace("'", "''")}'";
+
     private static string Stringify(WinappConfig cfg)
     {
         var sb = new StringBuilder();
@@ -86,6 +591,279 @@ internal sealed class ConfigService : IConfigService
             sb.AppendLine($"  - name: {p.Name}");
             sb.AppendLine($"    version: {p.Version}");
         }
+        if (cfg.Licenses.Count > 0)
+        {
+            sb.AppendLine("licenses:");
+            foreach (var license in cfg.Licenses)
+            {
+                sb.AppendLine($"  - package: {license.Package}");
+                sb.AppendLine($"    version: {license.Version}");
+                sb.AppendLine($"    license: {Quote(license.License)}");
+                if (license.Sha256 != null)
+                {
+                    sb.AppendLine($"    sha256: {license.Sha256}");
+                }
+                sb.AppendLine($"    acceptedBy: {Quote(license.AcceptedBy)}");
+                sb.AppendLine($"    acceptedAt: {Quote(license.AcceptedAt)}");
+            }
+        }
+        if (cfg.Projects.Count > 0)
+        {
+            sb.AppendLine("projects:");
+            foreach (var p in cfg.Projects)
+            {
+                sb.AppendLine($"  - name: {p.Name}");
+                sb.AppendLine($"    path: {p.Path}");
+                if (p.Inputs.Count > 0)
+                {
+                    sb.AppendLine("    inputs:");
+                    foreach (var input in p.Inputs)
+                    {
+                        sb.AppendLine($"      - {input}");
+                    }
+                }
+            }
+        }
+        if (cfg.Environment.Count > 0)
+        {
+            sb.AppendLine("environment:");
+            foreach (var variable in cfg.Environment)
+            {
+                sb.AppendLine($"  - name: {variable.Name}");
+                sb.AppendLine($"    value: {Quote(variable.Value)}");
+            }
+        }
+        if (cfg.Shortcuts.Count > 0)
+        {
+            sb.AppendLine("shortcuts:");
+            foreach (var shortcut in cfg.Shortcuts)
+            {
+                sb.AppendLine($"  - file: {Quote(shortcut.File)}");
+                if (shortcut.Application != null)
+                {
+                    sb.AppendLine($"    application: {shortcut.Application}");
+                }
+                if (shortcut.Arguments != null)
+                {
+                    sb.AppendLine($"    arguments: {Quote(shortcut.Arguments)}");
+                }
+                if (shortcut.Icon != null)
+                {
+                    sb.AppendLine($"    icon: {Quote(shortcut.Icon)}");
+                }
+            }
+        }
+        if (cfg.Splash is { } splash)
+        {
+            sb.AppendLine("splash:");
+            if (splash.Image != null)
+            {
+                sb.AppendLine($"  image: {Quote(splash.Image)}");
+            }
+            if (splash.BackgroundColor != null)
+            {
+                sb.AppendLine($"  backgroundColor: {Quote(splash.BackgroundColor)}");
+            }
+            if (splash.Extended)
+            {
+                sb.AppendLine("  extended: true");
+            }
+        }
+        if (cfg.Badges.Count > 0)
+        {
+            sb.AppendLine("badges:");
+            foreach (var badge in cfg.Badges)
+            {
+                sb.AppendLine($"  - profile: {badge.Profile}");
+                sb.AppendLine($"    text: {Quote(badge.Text)}");
+                if (badge.Color != null)
+                {
+                    sb.AppendLine($"    color: {Quote(badge.Color)}");
+                }
+            }
+        }
+        if (cfg.Rules.Count > 0)
+        {
+            sb.AppendLine("rules:");
+            foreach (var (rule, level) in cfg.Rules)
+            {
+                sb.AppendLine($"  {rule}: {level}");
+            }
+        }
+        if (cfg.Settings.Count > 0)
+        {
+            sb.AppendLine("settings:");
+            foreach (var (key, value) in cfg.Settings)
+            {
+                sb.AppendLine($"  {key}: {Quote(value)}");
+            }
+        }
+        if (cfg.Suppressions.Count > 0)
+        {
+            sb.AppendLine("suppressions:");
+            foreach (var suppression in cfg.Suppressions)
+            {
+                sb.AppendLine($"  - rule: {Quote(suppression.Rule)}");
+                if (suppression.File != null)
+                {
+                    sb.AppendLine($"    file: {Quote(suppression.File)}");
+                }
+                if (suppression.Line != null)
+                {
+                    sb.AppendLine($"    line: {suppression.Line}");
+                }
+                if (suppression.Reason != null)
+                {
+                    sb.AppendLine($"    reason: {Quote(suppression.Reason)}");
+                }
+            }
+        }
+        if (cfg.Analyzers.Count > 0)
+        {
+            sb.AppendLine("analyzers:");
+            foreach (var analyzer in cfg.Analyzers)
+            {
+                sb.AppendLine($"  - id: {analyzer.Id}");
+                sb.AppendLine($"    script: {Quote(analyzer.Script)}");
+            }
+        }
+        if (cfg.Store is { } store)
+        {
+            sb.AppendLine("store:");
+            sb.AppendLine($"  appId: {store.AppId}");
+            if (store.PackageFamilyName != null)
+            {
+                sb.AppendLine($"  packageFamilyName: {store.PackageFamilyName}");
+            }
+        }
+        if (cfg.Matrix is { } matrix)
+        {
+            sb.AppendLine("matrix:");
+            if (matrix.Brandings.Count > 0)
+            {
+                sb.AppendLine("  brandings:");
+                foreach (var branding in matrix.Brandings)
+                {
+                    sb.AppendLine($"    - name: {branding.Name}");
+                    AppendOptional(sb, "      identityName", branding.IdentityName);
+                    AppendOptional(sb, "      publisher", branding.Publisher);
+                    AppendOptional(sb, "      publisherDisplayName", branding.PublisherDisplayName);
+                    AppendOptional(sb, "      displayName", branding.DisplayName);
+                    AppendOptional(sb, "      assets", branding.Assets);
+                    AppendOptional(sb, "      certificate", branding.Certificate);
+                    if (branding.Protocols.Count > 0)
+                    {
+                        sb.AppendLine("      protocols:");
+                        foreach (var protocol in branding.Protocols)
+                        {
+                            sb.AppendLine($"        - {protocol}");
+                        }
+                    }
+                }
+            }
+            if (matrix.Channels.Count > 0)
+            {
+                sb.AppendLine("  channels:");
+                foreach (var channel in matrix.Channels)
+                {
+                    sb.AppendLine($"    - name: {channel.Name}");
+                    AppendOptional(sb, "      nameSuffix", channel.NameSuffix);
+                    AppendOptional(sb, "      displayNameSuffix", channel.DisplayNameSuffix);
+                }
+            }
+            if (matrix.Architectures.Count > 0)
+            {
+                sb.AppendLine("  architectures:");
+                foreach (var architecture in matrix.Architectures)
+                {
+                    sb.AppendLine($"    - name: {architecture.Name}");
+                    AppendOptional(sb, "      input", architecture.Input);
+                }
+            }
+        }
+        if (cfg.TestMatrix is { } testMatrix)
+        {
+            sb.AppendLine("testMatrix:");
+            AppendOptional(sb, "  smokeTest", testMatrix.SmokeTest);
+            if (testMatrix.Timeout != TestMatrixConfig.DefaultTimeout)
+            {
+                sb.AppendLine($"  timeout: {testMatrix.Timeout}");
+            }
+            if (testMatrix.Targets.Count > 0)
+            {
+                sb.AppendLine("  targets:");
+                foreach (var target in testMatrix.Targets)
+                {
+                    sb.AppendLine($"    - name: {target.Name}");
+                    sb.AppendLine($"      provider: {target.Provider}");
+                    sb.AppendLine($"      image: {Quote(target.Image)}");
+                    AppendOptional(sb, "      switch", target.Switch);
+                    if (target.Memory != null)
+                    {
+                        sb.AppendLine($"      memory: {target.Memory}");
+                    }
+                    AppendOptional(sb, "      lab", target.Lab);
+                    AppendOptional(sb, "      resourceGroup", target.ResourceGroup);
+                    AppendOptional(sb, "      size", target.Size);
+                }
+            }
+        }
+        if (cfg.Crashes is { } crashes)
+        {
+            sb.AppendLine("crashes:");
+            sb.AppendLine($"  endpoint: {Quote(crashes.Endpoint)}");
+            AppendOptional(sb, "  symbolsEndpoint", crashes.SymbolsEndpoint);
+            AppendOptional(sb, "  product", crashes.Product);
+        }
+        if (cfg.Sign is { } sign)
+        {
+            sb.AppendLine("sign:");
+            sb.AppendLine($"  provider: {JsonNamingPolicy.CamelCase.ConvertName(sign.Provider.ToString())}");
+            AppendOptional(sb, "  certificate", sign.Certificate);
+            AppendOptional(sb, "  endpoint", sign.Endpoint);
+            AppendOptional(sb, "  account", sign.Account);
+            AppendOptional(sb, "  profile", sign.Profile);
+            AppendOptional(sb, "  vault", sign.Vault);
+            AppendOptional(sb, "  certificateName", sign.CertificateName);
+        }
+        if (cfg.Win32Manifest is { } win32Manifest)
+        {
+            sb.AppendLine("win32Manifest:");
+            sb.AppendLine($"  dpiAwareness: {win32Manifest.DpiAwareness}");
+            sb.AppendLine($"  longPathAware: {(win32Manifest.LongPathAware ? "true" : "false")}");
+            sb.AppendLine($"  activeCodePage: {win32Manifest.ActiveCodePage ?? "none"}");
+            sb.AppendLine($"  supportedOS: {string.Join(", ", win32Manifest.SupportedOS)}");
+        }
+        if (cfg.Locale is { } locale)
+        {
+            sb.AppendLine("locale:");
+            AppendOptional(sb, "  language", locale.Language);
+            if (locale.Utf8Mode)
+            {
+                sb.AppendLine("  utf8Mode: true");
+            }
+        }
+        if (cfg.Build is { } build)
+        {
+            sb.AppendLine("build:");
+            if (build.System is { } system)
+            {
+                sb.AppendLine($"  system: {system.ToString().ToLowerInvariant()}");
+            }
+            AppendOptional(sb, "  project", build.Project);
+            sb.AppendLine($"  configuration: {build.Configuration}");
+            AppendOptional(sb, "  command", build.Command);
+            AppendOptional(sb, "  arguments", build.Arguments);
+            AppendOptional(sb, "  output", build.Output);
+        }
         return sb.ToString();
     }
+
+    private static void AppendOptional(StringBuilder sb, string key, string? value)
+    {
+        if (value != null)
+        {
+            sb.AppendLine($"{key}: {Quote(value)}");
+        }
+    }
 }
@@ -0,0 +1,113 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Crashes;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class CrashReportService(IConfigService configService) : ICrashReportService
+{
+    /// <summary>
+    /// Folder of the ApplicationData folder the winapp-runtime crate writes dumps to
+    /// </summary>
+    internal static readonly string DumpsFolder = Path.Combine("LocalState", "CrashDumps");
+
+    internal const string UploadedFolder = "Uploaded";
+
+    private static readonly HttpClient Http = new() { Timeout = TimeSpan.FromMinutes(30) };
+
+    public IReadOnlyList<FileInfo> FindDumps(InstalledPackage package) => FindDumps(new DirectoryInfo(package.AppDataPath));
+
+    internal static List<FileInfo> FindDumps(DirectoryInfo appData)
+    {
+        var folder = new DirectoryInfo(Path.Combine(appData.FullName, DumpsFolder));
+        return folder.Exists ? [.. folder.EnumerateFiles("*.dmp").OrderBy(f => f.LastWriteTimeUtc)] : [];
+    }
+
+    /// <summary>
+    /// The .pdb files under a build output folder, and the binaries next to them that have the same name, which
+    /// services need to unwind stacks without frame pointers
+    /// </summary>
+    internal static List<FileInfo> FindSymbolFiles(DirectoryInfo folder)
+    {
+        var files = new List<FileInfo>();
+        foreach (var pdb in folder.EnumerateFiles("*.pdb", SearchOption.AllDirectories).OrderBy(f => f.FullName, StringComparer.OrdinalIgnoreCase))
+        {
+            files.Add(pdb);
+            files.AddRange(new[] { ".exe", ".dll" }
+                .Select(extension => new FileInfo(Path.ChangeExtension(pdb.FullName, extension)))
+                .Where(binary => binary.Exists));
+        }
+        return files;
+    }
+
+    internal static CrashUploader CreateUploader(CrashesConfig? config, HttpClient http)
+    {
+        if (string.IsNullOrWhiteSpace(config?.Endpoint))
+        {
+            throw new InvalidOperationException("No crash reporting endpoint is configured; set 'endpoint' under 'crashes:' in winapp.yaml");
+        }
+        if (!Uri.TryCreate(config.Endpoint, UriKind.Absolute, out var endpoint))
+        {
+            throw new InvalidOperationException($"'{config.Endpoint}' under 'crashes:' in winapp.yaml is not a URL");
+        }
+        Uri? symbolsEndpoint = null;
+        if (!string.IsNullOrWhiteSpace(config.SymbolsEndpoint) && !Uri.TryCreate(config.SymbolsEndpoint, UriKind.Absolute, out symbolsEndpoint))
+        {
+            throw new InvalidOperationException($"'{config.SymbolsEndpoint}' under 'crashes:' in winapp.yaml is not a URL");
+        }
+
+        var token = Environment.GetEnvironmentVariable(CrashUploader.TokenVariable);
+        return new CrashUploader(endpoint, symbolsEndpoint, string.IsNullOrEmpty(token) ? null : token, http);
+    }
+
+    public async Task<CrashUploadResult> UploadAsync(InstalledPackage package, IReadOnlyList<FileInfo> dumps, DirectoryInfo? symbols, bool keep, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var config = configService.Exists() ? configService.Load().Crashes : null;
+        var uploader = CreateUploader(config, Http);
+        var product = config?.Product ?? package.Name;
+        void OnRetry(string message) => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}");
+
+        // Symbols first, so the service can read the stacks of the dumps as they arrive
+        var symbolFiles = symbols != null ? FindSymbolFiles(symbols) : [];
+        if (symbolFiles.Count > 0 && !uploader.HasSymbolStore)
+        {
+            throw new InvalidOperationException("No symbols endpoint is configured; set 'symbolsEndpoint' under 'crashes:' in winapp.yaml");
+        }
+        foreach (var file in symbolFiles)
+        {
+            taskContext.UpdateSubStatus($"Uploading {file.Name}");
+            await uploader.UploadSymbolFileAsync(file, product, package.Version, OnRetry, cancellationToken);
+        }
+
+        var reportIds = new List<string>();
+        foreach (var dump in dumps)
+        {
+            taskContext.UpdateSubStatus($"Uploading {dump.Name}");
+            var answer = await uploader.UploadDumpAsync(dump, product, package.Version, OnRetry, cancellationToken);
+            taskContext.AddStatusMessage($"{UiSymbols.Check} {dump.Name}{(answer != null ? $": {answer}" : "")}");
+            if (answer != null)
+            {
+                reportIds.Add(answer);
+            }
+            if (!keep)
+            {
+                MoveToUploaded(dump);
+            }
+        }
+        taskContext.UpdateSubStatus(null);
+        return new CrashUploadResult(dumps.Count, symbolFiles.Count, reportIds);
+    }
+
+    /// <summary>
+    /// Moves an uploaded dump out of the way of the next upload, keeping it for the developer to open
+    /// </summary>
+    internal static void MoveToUploaded(FileInfo dump)
+    {
+        var uploaded = Directory.CreateDirectory(Path.Combine(dump.DirectoryName!, UploadedFolder));
+        dump.MoveTo(Path.Combine(uploaded.FullName, dump.Name), overwrite: true);
+    }
+}
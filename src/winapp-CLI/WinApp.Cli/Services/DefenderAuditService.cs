@@ -0,0 +1,367 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using System.Globalization;
+using System.IO.Enumeration;
+using System.Security.Cryptography;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class DefenderAuditService(
+    IPowerShellService powerShellService,
+    IElevationService elevationService,
+    IConfigService configService,
+    IWinappDirectoryService winappDirectoryService,
+    IBuildToolsService buildToolsService) : IDefenderAuditService
+{
+    // Get-MpPreference lists the exclusions to administrators only; others get a single "N/A: Must be an administrator
+    // to view exclusions". Controlled Folder Access logs blocked writes as event 1123 and audited ones as 1124.
+    private const string StatusScript = """
+        $ErrorActionPreference = 'Stop'
+        try {
+            $status = Get-MpComputerStatus
+            $preference = Get-MpPreference
+        } catch {
+            [pscustomobject]@{ active = $false } | ConvertTo-Json -Compress
+            exit 0
+        }
+        $events = @()
+        try {
+            $events = @(Get-WinEvent -FilterHashtable @{ LogName = 'Microsoft-Windows-Windows Defender/Operational'; Id = 1123, 1124; StartTime = (Get-Date).AddDays(-7) } | ForEach-Object {
+                $data = @{}
+                ([xml]$_.ToXml()).Event.EventData.Data | ForEach-Object { $data[$_.Name] = $_.'#text' }
+                [pscustomobject]@{ time = $_.TimeCreated.ToUniversalTime().ToString('o'); blocked = $_.Id -eq 1123; process = $data['Process Name']; path = $data['Path'] }
+            })
+        } catch { }
+        [pscustomobject]@{
+            active = [bool]$status.AMServiceEnabled -and $status.AMRunningMode -notlike '*Passive*'
+            realTimeProtection = [bool]$status.RealTimeProtectionEnabled
+            runningMode = [string]$status.AMRunningMode
+            controlledFolderAccess = [int]$preference.EnableControlledFolderAccess
+            exclusionPaths = @($preference.ExclusionPath | Where-Object { $_ })
+            allowedApplications = @($preference.ControlledFolderAccessAllowedApplications | Where-Object { $_ })
+            events = $events
+        } | ConvertTo-Json -Depth 3 -Compress
+        """;
+
+    // The Windows SDK tools that pack and sign write packages with, besides winapp itself
+    private static readonly string[] SdkTools = ["makeappx.exe", "makepri.exe", "signtool.exe", "mt.exe"];
+
+    private const int ProbeFileCount = 100;
+    private const int ProbeFileSize = 64 * 1024;
+
+    // Without scanning, writing, reading back and deleting a 64 KB file takes well under a millisecond on an SSD
+    internal const double SlowMillisecondsPerFile = 2;
+
+    public async Task<DefenderAudit> AuditAsync(IReadOnlyList<DirectoryInfo> folders, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var status = await GetStatusAsync(taskContext, cancellationToken);
+
+        var candidates = new List<DirectoryInfo>
+        {
+            winappDirectoryService.GetLocalWinappDirectory(configService.ConfigPath.Directory),
+            winappDirectoryService.GetGlobalWinappDirectory(),
+        };
+        candidates.AddRange(folders);
+        var timings = new List<DefenderFolderTiming>();
+        foreach (var folder in candidates.Where(f => f.Exists).DistinctBy(f => Path.TrimEndingDirectorySeparator(f.FullName), StringComparer.OrdinalIgnoreCase))
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            taskContext.AddDebugMessage($"{UiSymbols.Timer} Timing {ProbeFileCount} file writes in {folder.FullName}");
+            timings.Add(new DefenderFolderTiming(folder.FullName, IsExcluded(folder.FullName, status.ExclusionPaths), Measure(folder)));
+        }
+
+        var applications = new List<string>();
+        if (Environment.ProcessPath is { } winapp)
+        {
+            applications.Add(winapp);
+        }
+        applications.AddRange(SdkTools.Select(buildToolsService.GetBuildToolPath).OfType<FileInfo>().Select(t => t.FullName));
+
+        return new DefenderAudit(status, timings, applications, Findings(status, timings, applications));
+    }
+
+    public async Task ExcludeAsync(DefenderAudit audit, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var script = ExclusionScript(audit, remove: false);
+        if (script.Length == 0)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Check} The folders and tools of the dev loop are already excluded");
+            return;
+        }
+
+        var result = (await elevationService.RunAsync([new ElevatedStep("Add Microsoft Defender exclusions for the dev loop", script)], taskContext, cancellationToken)).Single();
+        if (!result.Succeeded)
+        {
+            throw new InvalidOperationException($"{result.Error}; if Intune or Group Policy manages Defender, send the report of --report to IT");
+        }
+    }
+
+    public async Task WriteReportAsync(DefenderAudit audit, FileInfo output, CancellationToken cancellationToken = default)
+    {
+        var content = string.Equals(output.Extension, ".json", StringComparison.OrdinalIgnoreCase)
+            ? ToJson(audit).ToJsonString(new JsonSerializerOptions { WriteIndented = true })
+            : ToMarkdown(audit);
+        output.Directory?.Create();
+        await File.WriteAllTextAsync(output.FullName, content, cancellationToken);
+    }
+
+    private async Task<DefenderStatus> GetStatusAsync(TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync(StatusScript, taskContext, cancellationToken: cancellationToken);
+        if (exitCode != 0)
+        {
+            throw new InvalidOperationException("Reading the Microsoft Defender settings failed; run with --verbose for details");
+        }
+
+        var status = ParseStatus(JsonNode.Parse(output) ?? throw new InvalidOperationException("Get-MpPreference returned no output"));
+        taskContext.AddDebugMessage($"Defender {(status.Active ? "active" : "inactive")} ({status.RunningMode}), real-time protection {(status.RealTimeProtection ? "on" : "off")}, Controlled Folder Access {status.ControlledFolderAccess}");
+        return status;
+    }
+
+    internal static DefenderStatus ParseStatus(JsonNode json)
+    {
+        static List<string> Strings(JsonNode? node) => (node?.AsArray() ?? []).Select(n => n?.GetValue<string>()).OfType<string>().ToList();
+
+        List<string>? exclusionPaths = Strings(json["exclusionPaths"]);
+        if (exclusionPaths.Any(p => p.StartsWith("N/A", StringComparison.Ordinal)))
+        {
+            exclusionPaths = null;
+        }
+        var events = (json["events"]?.AsArray() ?? []).OfType<JsonNode>()
+            .Select(e => new ControlledFolderAccessEvent(
+                DateTimeOffset.Parse(e["time"]!.GetValue<string>(), CultureInfo.InvariantCulture),
+                e["blocked"]?.GetValue<bool>() ?? false,
+                e["process"]?.GetValue<string>() ?? "",
+                e["path"]?.GetValue<string>() ?? ""))
+            .ToList();
+        return new DefenderStatus(
+            json["active"]?.GetValue<bool>() ?? false,
+            json["realTimeProtection"]?.GetValue<bool>() ?? false,
+            json["runningMode"]?.GetValue<string>(),
+            (ControlledFolderAccessMode)(json["controlledFolderAccess"]?.GetValue<int>() ?? 0),
+            exclusionPaths,
+            Strings(json["allowedApplications"]),
+            events);
+    }
+
+    /// <summary>
+    /// Whether an exclusion of Defender, which may use environment variables and wildcards, covers the path
+    /// </summary>
+    /// <returns>null when the exclusions can't be read</returns>
+    internal static bool? IsExcluded(string path, IReadOnlyList<string>? exclusionPaths)
+    {
+        if (exclusionPaths == null)
+        {
+            return null;
+        }
+
+        var fullPath = Path.TrimEndingDirectorySeparator(Path.GetFullPath(path));
+        foreach (var exclusion in exclusionPaths)
+        {
+            var expanded = Path.TrimEndingDirectorySeparator(Environment.ExpandEnvironmentVariables(exclusion));
+            if (expanded.IndexOfAny(['*', '?']) >= 0
+                ? FileSystemName.MatchesSimpleExpression(expanded, fullPath)
+                : fullPath.Equals(expanded, StringComparison.OrdinalIgnoreCase)
+                    || fullPath.StartsWith(expanded + Path.DirectorySeparatorChar, StringComparison.OrdinalIgnoreCase))
+            {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    internal static List<DefenderFinding> Findings(DefenderStatus status, IReadOnlyList<DefenderFolderTiming> timings, IReadOnlyList<string> applications)
+    {
+        var findings = new List<DefenderFinding>();
+        if (!status.Active)
+        {
+            findings.Add(new DefenderFinding(AccessibilitySeverity.Info, "Microsoft Defender", $"isn't the active antivirus{(string.IsNullOrEmpty(status.RunningMode) ? "" : $" ({status.RunningMode})")}; another one may scan the dev loop, which this audit can't check"));
+            return findings;
+        }
+
+        if (!status.RealTimeProtection)
+        {
+            findings.Add(new DefenderFinding(AccessibilitySeverity.Info, "Real-time protection", "is off, so it doesn't slow down pack"));
+        }
+        foreach (var timing in timings)
+        {
+            var time = $"writing, reading back and deleting a file takes {timing.MillisecondsPerFile.ToString("0.0", CultureInfo.InvariantCulture)} ms";
+            if (timing.Excluded == true || !status.RealTimeProtection)
+            {
+                findings.Add(new DefenderFinding(AccessibilitySeverity.Info, timing.Folder, $"{(timing.Excluded == true ? "is excluded from scanning" : "isn't scanned")}; {time}"));
+                continue;
+            }
+
+            var slow = timing.MillisecondsPerFile >= SlowMillisecondsPerFile;
+            var excluded = timing.Excluded == null ? "may not be excluded from scanning (reading the exclusions needs administrator rights)" : "isn't excluded from scanning";
+            findings.Add(new DefenderFinding(
+                slow ? AccessibilitySeverity.Warning : AccessibilitySeverity.Info,
+                timing.Folder,
+                $"{excluded}; {time}{(slow ? $", where {SlowMillisecondsPerFile.ToString(CultureInfo.InvariantCulture)} ms or more means scanning slows down pack and register" : "")}"));
+        }
+
+        if (status.ControlledFolderAccess == ControlledFolderAccessMode.Disabled)
+        {
+            return findings;
+        }
+        var names = applications.Select(a => Path.GetFileName(a)).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        foreach (var group in status.Events.Where(e => names.Contains(Path.GetFileName(e.Process))).GroupBy(e => (Path.GetFileName(e.Process), e.Blocked)))
+        {
+            var (process, blocked) = group.Key;
+            var paths = group.Select(e => e.Path).Distinct(StringComparer.OrdinalIgnoreCase).ToList();
+            findings.Add(new DefenderFinding(
+                blocked ? AccessibilitySeverity.Error : AccessibilitySeverity.Warning,
+                "Controlled Folder Access",
+                $"{(blocked ? "blocked" : "would block")} {process} from writing to {string.Join(", ", paths.Take(3))}{(paths.Count > 3 ? $" and {paths.Count - 3} more" : "")} {group.Count()} time(s) in the last week"));
+        }
+        var allowed = status.AllowedApplications.Select(a => Path.GetFileName(a)).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        var blockable = applications.Select(a => Path.GetFileName(a)).Where(n => !allowed.Contains(n)).ToList();
+        if (blockable.Count > 0)
+        {
+            findings.Add(new DefenderFinding(
+                AccessibilitySeverity.Info,
+                "Controlled Folder Access",
+                $"is {(status.ControlledFolderAccess is ControlledFolderAccessMode.Audit or ControlledFolderAccessMode.AuditDiskModification ? "auditing" : "on")} and doesn't allow {string.Join(", ", blockable)}, which can't write to protected folders such as Documents and Desktop"));
+        }
+        return findings;
+    }
+
+    /// <summary>
+    /// The PowerShell that adds the exclusions of the audit, or removes them, scoped to the folders that aren't
+    /// excluded yet and, with Controlled Folder Access on, the tools that aren't allowed yet
+    /// </summary>
+    internal static string ExclusionScript(DefenderAudit audit, bool remove)
+    {
+        static string Quote(IEnumerable<string> values) => string.Join(",", values.Select(v => $"'{v.Replace("'", "''")}'"));
+
+        var verb = remove ? "Remove" : "Add";
+        var script = new StringBuilder();
+        var folders = audit.Folders.Where(f => f.Excluded != true).Select(f => f.Folder).ToList();
+        if (folders.Count > 0)
+        {
+            script.AppendLine($"{verb}-MpPreference -ExclusionPath {Quote(folders)}");
+        }
+        if (audit.Status.ControlledFolderAccess != ControlledFolderAccessMode.Disabled)
+        {
+            var applications = audit.Applications
+                .Where(a => !audit.Status.AllowedApplications.Contains(a, StringComparer.OrdinalIgnoreCase))
+                .ToList();
+            if (applications.Count > 0)
+            {
+                script.AppendLine($"{verb}-MpPreference -ControlledFolderAccessAllowedApplications {Quote(applications)}");
+            }
+        }
+        return script.ToString();
+    }
+
+    private static double Measure(DirectoryInfo folder)
+    {
+        var probe = folder.CreateSubdirectory($"defender-probe-{Guid.NewGuid():N}");
+        try
+        {
+            var content = RandomNumberGenerator.GetBytes(ProbeFileSize);
+            var stopwatch = Stopwatch.StartNew();
+            for (var i = 0; i < ProbeFileCount; i++)
+            {
+                var path = Path.Combine(probe.FullName, $"probe{i}.dll");
+                File.WriteAllBytes(path, content);
+                File.ReadAllBytes(path);
+                File.Delete(path);
+            }
+            return stopwatch.Elapsed.TotalMilliseconds / ProbeFileCount;
+        }
+        finally
+        {
+            probe.Delete(recursive: true);
+        }
+    }
+
+    private static JsonObject ToJson(DefenderAudit audit) => new()
+    {
+        ["generatedAt"] = DateTimeOffset.UtcNow.ToString("o", CultureInfo.InvariantCulture),
+        ["machine"] = Environment.MachineName,
+        ["active"] = audit.Status.Active,
+        ["runningMode"] = audit.Status.RunningMode,
+        ["realTimeProtection"] = audit.Status.RealTimeProtection,
+        ["controlledFolderAccess"] = audit.Status.ControlledFolderAccess.ToString(),
+        ["folders"] = new JsonArray(audit.Folders.Select(f => (JsonNode?)new JsonObject
+        {
+            ["path"] = f.Folder,
+            ["excluded"] = f.Excluded,
+            ["millisecondsPerFile"] = Math.Round(f.MillisecondsPerFile, 2),
+        }).ToArray()),
+        ["applications"] = new JsonArray(audit.Applications.Select(a => (JsonNode?)a).ToArray()),
+        ["findings"] = new JsonArray(audit.Findings.Select(f => (JsonNode?)new JsonObject
+        {
+            ["severity"] = f.Severity.ToString().ToLowerInvariant(),
+            ["subject"] = f.Subject,
+            ["message"] = f.Message,
+        }).ToArray()),
+        ["addExclusions"] = ExclusionScript(audit, remove: false),
+        ["removeExclusions"] = ExclusionScript(audit, remove: true),
+    };
+
+    private static string ToMarkdown(DefenderAudit audit)
+    {
+        var md = new StringBuilder();
+        md.AppendLine("# Microsoft Defender and the winapp dev loop");
+        md.AppendLine();
+        md.AppendLine(CultureInfo.InvariantCulture, $"Generated by `winapp audit defender` on {Environment.MachineName} at {DateTimeOffset.UtcNow:yyyy-MM-dd HH:mm} UTC.");
+        md.AppendLine();
+        md.AppendLine("| Setting | Value |");
+        md.AppendLine("|---|---|");
+        md.AppendLine(CultureInfo.InvariantCulture, $"| Active antivirus | {(audit.Status.Active ? "Microsoft Defender" : "Another one")} ({audit.Status.RunningMode}) |");
+        md.AppendLine(CultureInfo.InvariantCulture, $"| Real-time protection | {(audit.Status.RealTimeProtection ? "On" : "Off")} |");
+        md.AppendLine(CultureInfo.InvariantCulture, $"| Controlled Folder Access | {audit.Status.ControlledFolderAccess} |");
+        md.AppendLine();
+
+        md.AppendLine("## Folders");
+        md.AppendLine();
+        md.AppendLine(CultureInfo.InvariantCulture, $"Time to write, read back and delete a 64 KB file; above {SlowMillisecondsPerFile} ms, scanning slows down pack and register.");
+        md.AppendLine();
+        md.AppendLine("| Folder | Excluded | ms per file |");
+        md.AppendLine("|---|---|---|");
+        foreach (var folder in audit.Folders)
+        {
+            md.AppendLine(CultureInfo.InvariantCulture, $"| `{folder.Folder}` | {folder.Excluded switch { true => "Yes", false => "No", null => "Unknown" }} | {folder.MillisecondsPerFile:0.0} |");
+        }
+        md.AppendLine();
+
+        md.AppendLine("## Findings");
+        md.AppendLine();
+        foreach (var finding in audit.Findings)
+        {
+            md.AppendLine(CultureInfo.InvariantCulture, $"- **{finding.Severity}** {finding.Subject} {finding.Message}");
+        }
+        md.AppendLine();
+
+        md.AppendLine("## Requested exclusions");
+        md.AppendLine();
+        var add = ExclusionScript(audit, remove: false);
+        if (add.Length == 0)
+        {
+            md.AppendLine("None; the folders and tools of the dev loop are already excluded.");
+            return md.ToString();
+        }
+        md.AppendLine("The folders hold the packages, SDK tools and build output of this developer's projects, and the tools are those that write them. Run from an elevated PowerShell, or deploy through Intune or Group Policy where those manage Defender:");
+        md.AppendLine();
+        md.AppendLine("```powershell");
+        md.Append(add);
+        md.AppendLine("```");
+        md.AppendLine();
+        md.AppendLine("To remove them:");
+        md.AppendLine();
+        md.AppendLine("```powershell");
+        md.Append(ExclusionScript(audit, remove: true));
+        md.AppendLine("```");
+        return md.ToString();
+    }
+}
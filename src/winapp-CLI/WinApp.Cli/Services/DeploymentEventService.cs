@@ -0,0 +1,130 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics.Eventing.Reader;
+using System.Globalization;
+using System.Text;
+using System.Text.RegularExpressions;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal partial class DeploymentEventService : IDeploymentEventService
+{
+    /// <summary>
+    /// Event log channels where Add-AppxPackage failures and app activation failures are explained
+    /// </summary>
+    internal static readonly string[] LogNames =
+    [
+        "Microsoft-Windows-AppXDeploymentServer/Operational",
+        "Microsoft-Windows-AppXDeployment/Operational",
+        "Microsoft-Windows-AppModel-Runtime/Admin",
+    ];
+
+    /// <summary>
+    /// What the HRESULTs that deployments commonly fail with mean, and what usually fixes them
+    /// </summary>
+    internal static readonly Dictionary<string, string> CommonErrors = new(StringComparer.OrdinalIgnoreCase)
+    {
+        ["0x80073CF0"] = "The package couldn't be opened; it may be corrupt or not signed",
+        ["0x80073CF3"] = "A dependency is missing or too old, or an installed package conflicts with this one; pass the framework packages with the package",
+        ["0x80073CF6"] = "The package couldn't be registered; an extension in the manifest is usually the cause",
+        ["0x80073CF9"] = "The install failed; the next events in the log usually name the cause",
+        ["0x80073CFB"] = "A package with the same identity but different contents is installed; remove it or increase the version",
+        ["0x80073CFF"] = "Sideloading is blocked by policy; turn on developer mode, or sign the package with a trusted certificate",
+        ["0x80073D02"] = "The app, or a package it depends on, is running; close it and try again",
+        ["0x80073D06"] = "A higher version of the package is installed; remove it, or increase the version",
+        ["0x80080204"] = "The manifest is invalid; run 'winapp validate'",
+        ["0x800B0100"] = "The package isn't signed; sign it with 'winapp sign'",
+        ["0x800B0109"] = "The signing certificate isn't trusted; install it with 'winapp cert install'",
+        ["0x80070005"] = "Access denied; the package folder or the external location may not be readable by the app",
+    };
+
+    [GeneratedRegex(@"0x[0-9A-Fa-f]{8}\b")]
+    private static partial Regex ErrorCodeRegex();
+
+    public IReadOnlyList<DeploymentEvent> GetEvents(string packageName, DateTime since, TaskContext taskContext)
+    {
+        var events = new List<DeploymentEvent>();
+        foreach (var logName in LogNames)
+        {
+            try
+            {
+                using var reader = new EventLogReader(new EventLogQuery(logName, PathType.LogName, CreateQuery(since)));
+                for (var record = reader.ReadEvent(); record != null; record = reader.ReadEvent())
+                {
+                    using (record)
+                    {
+                        var message = record.FormatDescription() ?? "";
+                        events.Add(new DeploymentEvent(record.TimeCreated?.ToUniversalTime() ?? since, logName, record.Id, record.Level ?? 0, message, FindErrorCode(message)));
+                    }
+                }
+            }
+            catch (Exception ex) when (ex is EventLogException or UnauthorizedAccessException or PlatformNotSupportedException)
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Note} Couldn't read {logName}: {ex.Message}");
+            }
+        }
+
+        return Filter(events, packageName);
+    }
+
+    /// <summary>
+    /// XPath query for the critical events, errors and warnings logged since a point in time
+    /// </summary>
+    internal static string CreateQuery(DateTime since)
+    {
+        // A second of slack, since event timestamps and the clock of the caller are read separately
+        var systemTime = since.ToUniversalTime().AddSeconds(-1).ToString("yyyy-MM-ddTHH:mm:ss.fffZ", CultureInfo.InvariantCulture);
+        return $"*[System[(Level=1 or Level=2 or Level=3) and TimeCreated[@SystemTime>='{systemTime}']]]";
+    }
+
+    /// <summary>
+    /// Keeps the events about the package, oldest first
+    /// </summary>
+    internal static IReadOnlyList<DeploymentEvent> Filter(IEnumerable<DeploymentEvent> events, string packageName)
+    {
+        // Events name the package by its full name or family name, which both start with the identity name and '_'
+        var prefix = $"{packageName}_";
+        return [.. events
+            .Where(e => e.Message.Contains(prefix, StringComparison.OrdinalIgnoreCase))
+            .OrderBy(e => e.TimeCreated)];
+    }
+
+    internal static string? FindErrorCode(string message)
+    {
+        var match = ErrorCodeRegex().Match(message);
+        return match.Success ? $"0x{match.Value[2..].ToUpperInvariant()}" : null;
+    }
+
+    /// <summary>
+    /// Formats the events as lines to add to an error message, or an empty string when there are none
+    /// </summary>
+    internal static string Format(IReadOnlyList<DeploymentEvent> events)
+    {
+        if (events.Count == 0)
+        {
+            return "";
+        }
+
+        var sb = new StringBuilder();
+        sb.AppendLine();
+        sb.Append("Event log:");
+        foreach (var e in events)
+        {
+            // The first paragraph says what failed; the rest of the message is generic advice
+            var summary = string.Join(' ', e.Message.Replace("\r\n", "\n").Split("\n\n", 2)[0].Split((char[]?)null, StringSplitOptions.RemoveEmptyEntries));
+            var channel = e.LogName.Replace("Microsoft-Windows-", "").Split('/')[0];
+            sb.AppendLine();
+            sb.Append(CultureInfo.InvariantCulture, $"  {e.TimeCreated.ToLocalTime():HH:mm:ss} {channel} {e.Id}: {summary}");
+            if (e.ErrorCode != null && CommonErrors.TryGetValue(e.ErrorCode, out var hint))
+            {
+                sb.AppendLine();
+                sb.Append(CultureInfo.InvariantCulture, $"    {UiSymbols.Note} {e.ErrorCode}: {hint}");
+            }
+        }
+        return sb.ToString();
+    }
+}
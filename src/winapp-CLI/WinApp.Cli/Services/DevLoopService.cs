@@ -0,0 +1,120 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Dev;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class DevLoopService(IMsixService msixService, IWinappDirectoryService winappDirectoryService) : IDevLoopService
+{
+    /// <summary>
+    /// Folder of .winapp the layout is copied to
+    /// </summary>
+    internal const string LayoutFolder = "dev";
+
+    private const int CopyAttempts = 5;
+    private static readonly TimeSpan CopyRetryDelay = TimeSpan.FromMilliseconds(200);
+
+    public async Task<DevSession> StartAsync(DirectoryInfo inputFolder, FileInfo manifest, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        if (!inputFolder.Exists)
+        {
+            throw new DirectoryNotFoundException($"Input folder not found: {inputFolder.FullName}");
+        }
+        if (!manifest.Exists)
+        {
+            throw new FileNotFoundException($"AppX manifest not found at: {manifest.FullName}. You can generate one using 'winapp manifest generate'.");
+        }
+        var executable = FindExecutable(manifest)
+            ?? throw new InvalidOperationException($"{manifest.Name} has no Application with an Executable for 'winapp dev' to run");
+
+        var layoutFolder = new DirectoryInfo(Path.Combine(winappDirectoryService.GetLocalWinappDirectory().FullName, LayoutFolder));
+        try
+        {
+            if (layoutFolder.Exists)
+            {
+                layoutFolder.Delete(recursive: true);
+            }
+        }
+        catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+        {
+            throw new InvalidOperationException($"Couldn't replace {layoutFolder.FullName}; close the app if it is still running ({ex.Message})", ex);
+        }
+        layoutFolder.Create();
+
+        var layout = new DevLayout(inputFolder, layoutFolder);
+        var changes = await SyncAsync(layout, cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Files} Copied {changes.Changed.Count} file(s) to {layoutFolder.FullName}");
+
+        var exe = new FileInfo(Path.Combine(layoutFolder.FullName, executable));
+        var identity = await msixService.AddMsixIdentityAsync(exe.FullName, manifest, noInstall: false, taskContext, cancellationToken);
+        return new DevSession(layout, manifest) { Executable = exe, Identity = identity };
+    }
+
+    public async Task<DevUpdate> UpdateAsync(DevSession session, bool manifestChanged, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var changes = await SyncAsync(session.Layout, cancellationToken);
+        foreach (var path in changes.Changed)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Sync} {path}");
+        }
+        foreach (var path in changes.Deleted)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Trash} {path}");
+        }
+
+        if (manifestChanged)
+        {
+            var executable = FindExecutable(session.Manifest)
+                ?? throw new InvalidOperationException($"{session.Manifest.Name} has no Application with an Executable for 'winapp dev' to run");
+            session.Executable = new FileInfo(Path.Combine(session.Layout.Folder.FullName, executable));
+            session.Identity = await msixService.AddMsixIdentityAsync(session.Executable.FullName, session.Manifest, noInstall: false, taskContext, cancellationToken);
+            return new DevUpdate(changes.Changed.Count, changes.Deleted.Count, Reregistered: true, IdentityEmbedded: true);
+        }
+
+        var exeChanged = changes.Changed.Any(path => string.Equals(
+            Path.Combine(session.Layout.Folder.FullName, path), session.Executable.FullName, StringComparison.OrdinalIgnoreCase));
+        if (exeChanged)
+        {
+            await msixService.EmbedDebugIdentityAsync(session.Executable, session.Manifest, taskContext, cancellationToken);
+        }
+        return new DevUpdate(changes.Changed.Count, changes.Deleted.Count, Reregistered: false, IdentityEmbedded: exeChanged);
+    }
+
+    /// <summary>
+    /// The Executable of the first Application of the manifest, relative to the package root
+    /// </summary>
+    internal static string? FindExecutable(FileInfo manifest)
+    {
+        var document = XDocument.Load(manifest.FullName);
+        var executable = document.Descendants()
+            .Where(e => e.Name.LocalName == "Application")
+            .Select(e => e.Attribute("Executable")?.Value)
+            .FirstOrDefault(value => !string.IsNullOrWhiteSpace(value));
+        return executable?.Replace('/', Path.DirectorySeparatorChar);
+    }
+
+    /// <summary>
+    /// Copies the changes into the layout, retrying while the app that was just stopped still holds its files
+    /// </summary>
+    private static async Task<LayoutChanges> SyncAsync(DevLayout layout, CancellationToken cancellationToken)
+    {
+        var changes = layout.FindChanges();
+        for (var attempt = 1; ; attempt++)
+        {
+            try
+            {
+                layout.Apply(changes);
+                return changes;
+            }
+            catch (IOException) when (attempt < CopyAttempts)
+            {
+                await Task.Delay(CopyRetryDelay, cancellationToken);
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text;
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class DistributionService(IGitService gitService, IProjectStateService projectStateService, ICurrentDirectoryProvider currentDirectoryProvider) : IDistributionService
+{
+    private static readonly HttpClient Http = new() { Timeout = TimeSpan.FromMinutes(10) };
+
+    public async Task<DistributionSite> BuildWebSiteAsync(FileInfo packagePath, DirectoryInfo outputDirectory, DistributionWebOptions options, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        if (options.HoursBetweenUpdateChecks is < 0 or > 255)
+        {
+            throw new ArgumentOutOfRangeException(nameof(options), "Hours between update checks must be between 0 and 255");
+        }
+
+        var package = await DistributionPackage.ReadAsync(packagePath, cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Package} {package.Name} {package.Version}{(package.IsBundle ? " (bundle)" : "")} by {package.Publisher}");
+        if (package.SignerCertificate == null)
+        {
+            throw new InvalidOperationException($"{packagePath.Name} is not signed; App Installer only installs signed packages. Sign it with 'winapp sign' first");
+        }
+
+        var baseUri = options.BaseUri.AbsoluteUri.EndsWith('/') ? options.BaseUri : new Uri(options.BaseUri.AbsoluteUri + "/");
+        if (baseUri.Scheme != Uri.UriSchemeHttps)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Warning} {baseUri} is not HTTPS; App Installer refuses to install from HTTP URLs outside of localhost");
+        }
+
+        outputDirectory.Create();
+        var files = new List<string>();
+
+        var packageFileName = packagePath.Name;
+        var outputPackage = Path.Combine(outputDirectory.FullName, packageFileName);
+        if (!string.Equals(Path.GetFullPath(packagePath.FullName), Path.GetFullPath(outputPackage), StringComparison.OrdinalIgnoreCase))
+        {
+            File.Copy(packagePath.FullName, outputPackage, overwrite: true);
+        }
+        files.Add(packageFileName);
+
+        var appInstallerFileName = package.Name + AppInstallerFile.Extension;
+        var appInstallerUri = new Uri(baseUri, Uri.EscapeDataString(appInstallerFileName));
+        var packageUri = new Uri(baseUri, Uri.EscapeDataString(packageFileName));
+        TemplateVariables? variables = null;
+        if (options.AppInstallerTemplate != null || ValueTemplate.HasPlaceholders(options.Title))
+        {
+            variables = new TemplateVariables()
+                .SetProvenance(await projectStateService.GetProvenanceAsync(null, cancellationToken))
+                .SetVersion(package.Version)
+                .Set("package.name", package.Name)
+                .Set("package.publisher", package.Publisher)
+                .Set("package.displayName", package.DisplayName)
+                .Set("package.architecture", package.ProcessorArchitecture)
+                .Set("package.uri", packageUri.AbsoluteUri)
+                .Set("appInstaller.uri", appInstallerUri.AbsoluteUri)
+                .Set("hoursBetweenUpdateChecks", options.HoursBetweenUpdateChecks.ToString(CultureInfo.InvariantCulture));
+        }
+
+        string appInstaller;
+        if (options.AppInstallerTemplate != null)
+        {
+            appInstaller = ValueTemplate.Render(options.AppInstallerTemplate, variables!, "App Installer template");
+            try
+            {
+                XDocument.Parse(appInstaller);
+            }
+            catch (XmlException ex)
+            {
+                throw new InvalidOperationException($"The App Installer template doesn't render to valid XML: {ex.Message}", ex);
+            }
+        }
+        else
+        {
+            appInstaller = AppInstallerFile.Create(package, appInstallerUri, packageUri, options.HoursBetweenUpdateChecks);
+        }
+        await WriteTextAsync(outputDirectory, appInstallerFileName, appInstaller, cancellationToken);
+        files.Add(appInstallerFileName);
+
+        string? certificateFileName = null;
+        if (package.IsSelfSigned)
+        {
+            certificateFileName = package.Name + ".cer";
+            await File.WriteAllBytesAsync(Path.Combine(outputDirectory.FullName, certificateFileName), package.SignerCertificate.RawData, cancellationToken);
+            files.Add(certificateFileName);
+            taskContext.AddStatusMessage($"{UiSymbols.Lock} The package is self-signed; the page explains how to trust {certificateFileName} before installing");
+        }
+
+        var page = DistributionPage.Render(new DistributionPageModel(
+            options.Title != null && variables != null ? ValueTemplate.Render(options.Title, variables, "--title") : options.Title ?? package.DisplayName ?? package.Name,
+            package,
+            appInstallerFileName,
+            appInstallerUri,
+            packageFileName,
+            certificateFileName,
+            options.ReleaseNotes));
+        await WriteTextAsync(outputDirectory, DistributionPage.FileName, page, cancellationToken);
+        files.Add(DistributionPage.FileName);
+
+        if (options.FeedSigningCertificate != null)
+        {
+            using var certificate = X509CertificateLoader.LoadPkcs12FromFile(options.FeedSigningCertificate.FullName, options.FeedSigningCertificatePassword, X509KeyStorageFlags.EphemeralKeySet);
+            if (!certificate.HasPrivateKey)
+            {
+                throw new InvalidOperationException($"{options.FeedSigningCertificate.Name} has no private key to sign the integrity manifest with");
+            }
+
+            var manifestFileName = FeedIntegrity.GetManifestFileName(package.Name);
+            var manifest = await FeedIntegrity.CreateManifestAsync(package, outputDirectory, files, cancellationToken);
+            await File.WriteAllBytesAsync(Path.Combine(outputDirectory.FullName, manifestFileName), manifest, cancellationToken);
+            files.Add(manifestFileName);
+
+            var signatureFileName = FeedIntegrity.GetSignatureFileName(package.Name);
+            await File.WriteAllBytesAsync(Path.Combine(outputDirectory.FullName, signatureFileName), FeedIntegrity.Sign(manifest, certificate), cancellationToken);
+            files.Add(signatureFileName);
+
+            if (!FeedIntegrity.IsSameName(certificate.SubjectName, package.Publisher))
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} {certificate.Subject} is not the package publisher; verifiers have to pass its thumbprint {certificate.Thumbprint}");
+            }
+            taskContext.AddDebugMessage($"{UiSymbols.Lock} Signed {manifestFileName} with {certificate.Subject} ({certificate.Thumbprint})");
+        }
+
+        if (options.GitHubPages)
+        {
+            // Serve the files as they are instead of running a Jekyll build first
+            await WriteTextAsync(outputDirectory, ".nojekyll", string.Empty, cancellationToken);
+            files.Add(".nojekyll");
+        }
+
+        return new DistributionSite(outputDirectory, files, package, new Uri(baseUri, DistributionPage.FileName), appInstallerUri, certificateFileName != null);
+    }
+
+    public IDistributionPublishTarget CreateAzureBlobTarget(Uri containerSasUri) => new AzureBlobPublishTarget(containerSasUri, Http);
+
+    public IDistributionPublishTarget CreateS3Target(Uri location, string? region)
+    {
+        region ??= Environment.GetEnvironmentVariable("AWS_REGION") ?? Environment.GetEnvironmentVariable("AWS_DEFAULT_REGION");
+        if (string.IsNullOrWhiteSpace(region))
+        {
+            throw new InvalidOperationException("The bucket's region is unknown. Pass --s3-region or set AWS_REGION.");
+        }
+
+        return new S3PublishTarget(location, region, AwsCredentials.FromEnvironment(), Http);
+    }
+
+    public IDistributionPublishTarget CreateGitHubReleasesTarget(string? repository, string tag)
+    {
+        repository ??= Environment.GetEnvironmentVariable(GitHubReleasesPublishTarget.RepositoryVariable);
+        if (string.IsNullOrWhiteSpace(repository) || repository.Count(c => c == '/') != 1)
+        {
+            throw new InvalidOperationException($"The GitHub repository is unknown. Pass --github-repo owner/name or set {GitHubReleasesPublishTarget.RepositoryVariable}.");
+        }
+
+        return new GitHubReleasesPublishTarget(repository, tag, GitHubReleasesPublishTarget.GetTokenFromEnvironment(), Http);
+    }
+
+    public IDistributionPublishTarget CreateGitHubPagesTarget(string remote, string branch) =>
+        new GitHubPagesPublishTarget(gitService, currentDirectoryProvider.GetCurrentDirectory(), remote, branch);
+
+    public async Task<IReadOnlyList<ContentTypeMismatch>> PublishAsync(DistributionSite site, IDistributionPublishTarget target, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        await target.PublishAsync(site, taskContext, cancellationToken);
+
+        taskContext.UpdateSubStatus("Checking content types");
+        var baseUri = new Uri(site.AppInstallerUri, ".");
+        var mismatches = await DistributionContentTypes.FindMismatchesAsync(Http, baseUri, [Path.GetFileName(site.AppInstallerUri.LocalPath), site.Package.File.Name], cancellationToken);
+        taskContext.UpdateSubStatus(null);
+        return mismatches;
+    }
+
+    public async Task<FeedVerificationResult> VerifyFeedAsync(string appInstallerLocation, string? expectedThumbprint, bool includePackages, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var isRemote = Uri.TryCreate(appInstallerLocation, UriKind.Absolute, out var appInstallerUri) && (appInstallerUri.Scheme == Uri.UriSchemeHttps || appInstallerUri.Scheme == Uri.UriSchemeHttp);
+        var appInstallerFileName = isRemote ? Uri.UnescapeDataString(Path.GetFileName(appInstallerUri!.AbsolutePath)) : Path.GetFileName(appInstallerLocation);
+        if (!appInstallerFileName.EndsWith(AppInstallerFile.Extension, StringComparison.OrdinalIgnoreCase))
+        {
+            throw new ArgumentException($"{appInstallerFileName} is not an {AppInstallerFile.Extension} file", nameof(appInstallerLocation));
+        }
+
+        var packageName = appInstallerFileName[..^AppInstallerFile.Extension.Length];
+        var localDirectory = isRemote ? null : Path.GetDirectoryName(Path.GetFullPath(appInstallerLocation))!;
+
+        async Task<Stream> OpenAsync(string file)
+        {
+            if (!isRemote)
+            {
+                return File.OpenRead(Path.Combine(localDirectory!, file));
+            }
+
+            var response = await Http.GetAsync(new Uri(appInstallerUri!, Uri.EscapeDataString(file)), HttpCompletionOption.ResponseHeadersRead, cancellationToken);
+            if (!response.IsSuccessStatusCode)
+            {
+                response.Dispose();
+                throw new InvalidOperationException($"Downloading {file} failed with {(int)response.StatusCode} {response.ReasonPhrase}");
+            }
+            return await response.Content.ReadAsStreamAsync(cancellationToken);
+        }
+
+        async Task<byte[]> ReadAllAsync(string file)
+        {
+            await using var stream = await OpenAsync(file);
+            using var buffer = new MemoryStream();
+            await stream.CopyToAsync(buffer, cancellationToken);
+            return buffer.ToArray();
+        }
+
+        byte[] manifestBytes;
+        byte[] signature;
+        try
+        {
+            manifestBytes = await ReadAllAsync(FeedIntegrity.GetManifestFileName(packageName));
+            signature = await ReadAllAsync(FeedIntegrity.GetSignatureFileName(packageName));
+        }
+        catch (Exception ex) when (ex is FileNotFoundException or InvalidOperationException)
+        {
+            throw new InvalidOperationException($"The feed has no signed integrity manifest ({ex.Message}); publish it with 'winapp distribute web --sign-feed'", ex);
+        }
+
+        var signer = FeedIntegrity.VerifySignature(manifestBytes, signature, expectedThumbprint);
+        var manifest = FeedIntegrity.ReadManifest(manifestBytes);
+        taskContext.AddDebugMessage($"{UiSymbols.Lock} Integrity manifest signed by {signer.Subject} ({signer.Thumbprint})");
+
+        var checks = new List<FeedFileCheck>();
+        if (!manifest.Files.Any(f => string.Equals(f.Name, appInstallerFileName, StringComparison.OrdinalIgnoreCase)))
+        {
+            checks.Add(new FeedFileCheck(appInstallerFileName, "not listed in the integrity manifest"));
+        }
+
+        foreach (var file in manifest.Files)
+        {
+            if (!includePackages && FeedIntegrity.IsPackageFile(file.Name))
+            {
+                checks.Add(new FeedFileCheck(file.Name, null, Skipped: true));
+                continue;
+            }
+
+            taskContext.UpdateSubStatus($"Checking {file.Name}");
+            string? problem;
+            try
+            {
+                await using var stream = await OpenAsync(file.Name);
+                using var hash = IncrementalHash.CreateHash(HashAlgorithmName.SHA256);
+                var buffer = new byte[81920];
+                long size = 0;
+                int read;
+                while ((read = await stream.ReadAsync(buffer, cancellationToken)) > 0)
+                {
+                    hash.AppendData(buffer, 0, read);
+                    size += read;
+                }
+                problem = FeedIntegrity.Check(file, size, Convert.ToHexStringLower(hash.GetHashAndReset()));
+            }
+            catch (Exception ex) when (ex is IOException or InvalidOperationException or HttpRequestException)
+            {
+                problem = ex.Message;
+            }
+
+            checks.Add(new FeedFileCheck(file.Name, problem));
+        }
+
+        taskContext.UpdateSubStatus(null);
+        return new FeedVerificationResult(manifest, signer, checks);
+    }
+
+    private static Task WriteTextAsync(DirectoryInfo directory, string fileName, string content, CancellationToken cancellationToken) =>
+        File.WriteAllTextAsync(Path.Combine(directory.FullName, fileName), content, new UTF8Encoding(encoderShouldEmitUTF8Identifier: false), cancellationToken);
+}
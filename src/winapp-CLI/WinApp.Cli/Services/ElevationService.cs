@@ -0,0 +1,115 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.ComponentModel;
+using System.Security.Principal;
+using System.Text;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class ElevationService(IPowerShellService powerShellService) : IElevationService
+{
+    // What starting a process with the runas verb fails with when the user declines the prompt
+    private const int ErrorCancelled = 1223;
+
+    public bool IsElevated
+    {
+        get
+        {
+            using var identity = WindowsIdentity.GetCurrent();
+            return new WindowsPrincipal(identity).IsInRole(WindowsBuiltInRole.Administrator);
+        }
+    }
+
+    public async Task<IReadOnlyList<ElevatedStepResult>> RunAsync(IReadOnlyList<ElevatedStep> steps, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        if (steps.Count == 0)
+        {
+            return [];
+        }
+
+        var elevated = !IsElevated;
+        if (elevated)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Lock} {steps.Count} step(s) need administrator rights; User Account Control asks once for all of them:");
+            foreach (var step in steps)
+            {
+                taskContext.AddStatusMessage($"   {step.Description}");
+            }
+        }
+
+        // An elevated PowerShell doesn't share its output, so the steps report to a file. It is created here, so the
+        // elevated process writes to a file of this user rather than creating one, and is deleted however it ends.
+        var resultsFile = new FileInfo(Path.GetTempFileName());
+        try
+        {
+            int exitCode;
+            try
+            {
+                (exitCode, _) = await powerShellService.RunCommandAsync(BuildScript(steps, resultsFile.FullName), taskContext, elevated, cancellationToken: cancellationToken);
+            }
+            catch (Win32Exception ex) when (ex.NativeErrorCode == ErrorCancelled)
+            {
+                return steps.Select(s => new ElevatedStepResult(s, "User Account Control was declined")).ToList();
+            }
+
+            resultsFile.Refresh();
+            if (!resultsFile.Exists || resultsFile.Length == 0)
+            {
+                var error = elevated ? "User Account Control was declined" : $"PowerShell exited with {exitCode}";
+                return steps.Select(s => new ElevatedStepResult(s, error)).ToList();
+            }
+
+            var results = ParseResults(steps, await File.ReadAllTextAsync(resultsFile.FullName, cancellationToken));
+            foreach (var result in results.Where(r => !r.Succeeded))
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Warning} {result.Step.Description} failed: {result.Error}");
+            }
+            return results;
+        }
+        finally
+        {
+            resultsFile.Delete();
+        }
+    }
+
+    /// <summary>
+    /// One script that runs every step, recording whether each failed, and writes the records as JSON to
+    /// <paramref name="resultsPath"/>
+    /// </summary>
+    internal static string BuildScript(IReadOnlyList<ElevatedStep> steps, string resultsPath)
+    {
+        var script = new StringBuilder();
+        script.AppendLine("$ErrorActionPreference = 'Stop'");
+        script.AppendLine("$results = @()");
+        foreach (var step in steps)
+        {
+            script.AppendLine("try {");
+            script.AppendLine(step.Script.Trim());
+            script.AppendLine("    $results += [pscustomobject]@{ error = $null }");
+            script.AppendLine("} catch {");
+            script.AppendLine("    $results += [pscustomobject]@{ error = $_.Exception.Message }");
+            script.AppendLine("}");
+        }
+        script.AppendLine($"ConvertTo-Json -InputObject $results -Compress | Set-Content -LiteralPath '{resultsPath.Replace("'", "''")}' -Encoding UTF8");
+        return script.ToString();
+    }
+
+    internal static List<ElevatedStepResult> ParseResults(IReadOnlyList<ElevatedStep> steps, string json)
+    {
+        var records = JsonNode.Parse(json) switch
+        {
+            JsonArray array => array.ToList(),
+            { } single => [single],
+            null => [],
+        };
+        return steps.Select((step, i) => new ElevatedStepResult(
+                step,
+                i < records.Count ? records[i]?["error"]?.GetValue<string>() : "The step didn't run"))
+            .ToList();
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class ExpiryAuditService(IConfigService configService) : IExpiryAuditService
+{
+    private const string TrustedSigningApiVersion = "2024-02-05-preview";
+
+    private static readonly Uri ResourceManagerUri = new("https://management.azure.com/");
+
+    private static readonly HttpClient Http = new();
+
+    public async Task<List<ExpiryCheck>> CheckAsync(ExpiryAuditOptions options, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var now = DateTimeOffset.UtcNow;
+        var checks = new List<ExpiryCheck>();
+
+        var certificates = options.Certificates;
+        if (certificates.Count == 0)
+        {
+            var devCertificate = new FileInfo(Path.Combine(configService.ConfigPath.DirectoryName!, CertificateService.DefaultCertFileName));
+            certificates = devCertificate.Exists ? [devCertificate] : [];
+        }
+        foreach (var certificate in certificates)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Lock} Reading {certificate.FullName}");
+            checks.Add(Check(certificate.Name, () => CheckCertificate(certificate, options.CertificatePassword, now, options)));
+        }
+
+        if (options.StoreSecret)
+        {
+            checks.Add(await CheckAsync("Store client secret", async () =>
+            {
+                var credentials = StoreCredentials.FromEnvironment();
+                var graphClient = new PartnerCenterClient(Http, credentials)
+                {
+                    ServiceUri = StoreFlights.GraphServiceUri,
+                    Resource = StoreFlights.GraphResource,
+                    OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+                };
+                try
+                {
+                    var application = await graphClient.GetAsync($"applications(appId='{Uri.EscapeDataString(credentials.ClientId)}')?$select=displayName,passwordCredentials", cancellationToken)
+                        ?? throw new InvalidOperationException($"Microsoft Graph returned no application {credentials.ClientId}");
+                    return CheckSecret(application, credentials.ClientId, credentials.ClientSecret, now, options);
+                }
+                catch (HttpRequestException ex) when (ex.StatusCode is HttpStatusCode.Forbidden or HttpStatusCode.NotFound)
+                {
+                    throw new InvalidOperationException($"Can't read application {credentials.ClientId} from Microsoft Graph; grant it the Application.Read.All application permission", ex);
+                }
+            }));
+        }
+
+        if (options.TrustedSigningProfile is { } profileId)
+        {
+            checks.Add(await CheckAsync(profileId.Split('/')[^1], async () =>
+            {
+                var armClient = new PartnerCenterClient(Http, StoreCredentials.FromAzureEnvironment())
+                {
+                    ServiceUri = ResourceManagerUri,
+                    Resource = ResourceManagerUri.AbsoluteUri.TrimEnd('/'),
+                    OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+                };
+                var profile = await armClient.GetAsync($"{profileId.TrimStart('/')}?api-version={TrustedSigningApiVersion}", cancellationToken)
+                    ?? throw new InvalidOperationException($"Azure returned no certificate profile {profileId}");
+                return CheckProfile(profile, profileId);
+            }));
+        }
+
+        return checks;
+    }
+
+    internal static ExpiryCheck CheckCertificate(FileInfo certificatePath, string password, DateTimeOffset now, ExpiryAuditOptions options)
+    {
+        var extension = certificatePath.Extension.ToLowerInvariant();
+        using var certificate = extension is ".cer" or ".crt"
+            ? X509CertificateLoader.LoadCertificateFromFile(certificatePath.FullName)
+            : X509CertificateLoader.LoadPkcs12FromFile(certificatePath.FullName, password, X509KeyStorageFlags.EphemeralKeySet);
+
+        var subject = $"{certificatePath.Name} ({certificate.Subject})";
+        if (new DateTimeOffset(certificate.NotBefore) > now)
+        {
+            return new ExpiryCheck(subject, new DateTimeOffset(certificate.NotAfter), AccessibilitySeverity.Error, $"is not valid until {certificate.NotBefore:yyyy-MM-dd}");
+        }
+        return Classify(subject, new DateTimeOffset(certificate.NotAfter), now, options);
+    }
+
+    /// <summary>
+    /// Finds the client secret among the application's password credentials by its hint, the first characters
+    /// Azure AD keeps of each secret
+    /// </summary>
+    /// <param name="application">Microsoft Graph application with displayName and passwordCredentials</param>
+    internal static ExpiryCheck CheckSecret(JsonNode application, string clientId, string clientSecret, DateTimeOffset now, ExpiryAuditOptions options)
+    {
+        var name = application["displayName"]?.GetValue<string>() ?? clientId;
+        var secrets = (application["passwordCredentials"] as JsonArray ?? [])
+            .OfType<JsonNode>()
+            .Select(c => (Hint: c["hint"]?.GetValue<string>(), Name: c["displayName"]?.GetValue<string>(), End: c["endDateTime"]?.GetValue<DateTimeOffset>()))
+            .ToList();
+        var secret = secrets
+            .Where(s => s.Hint != null && clientSecret.StartsWith(s.Hint, StringComparison.Ordinal))
+            .OrderByDescending(s => s.End)
+            .FirstOrDefault();
+        if (secret == default)
+        {
+            return new ExpiryCheck($"{name} client secret", null, AccessibilitySeverity.Error,
+                $"WINAPP_STORE_CLIENT_SECRET is none of the {secrets.Count} secret(s) of {clientId}; it was deleted or belongs to another application");
+        }
+
+        var subject = $"{name} client secret{(string.IsNullOrEmpty(secret.Name) ? "" : $" '{secret.Name}'")}";
+        return secret.End is { } end
+            ? Classify(subject, end, now, options)
+            : new ExpiryCheck(subject, null, AccessibilitySeverity.Info, "never expires");
+    }
+
+    /// <param name="profile">Trusted Signing certificate profile resource</param>
+    internal static ExpiryCheck CheckProfile(JsonNode profile, string profileId)
+    {
+        var name = profile["name"]?.GetValue<string>() ?? profileId.Split('/')[^1];
+        var status = profile["properties"]?["status"]?.GetValue<string>();
+        var provisioningState = profile["properties"]?["provisioningState"]?.GetValue<string>();
+        var subject = $"Trusted Signing profile {name}";
+        if (!string.Equals(status, "Active", StringComparison.OrdinalIgnoreCase))
+        {
+            return new ExpiryCheck(subject, null, AccessibilitySeverity.Error, $"is {status ?? "in an unknown state"}; signing with it fails until it is active again");
+        }
+        if (provisioningState != null && !string.Equals(provisioningState, "Succeeded", StringComparison.OrdinalIgnoreCase))
+        {
+            return new ExpiryCheck(subject, null, AccessibilitySeverity.Warning, $"is active, but provisioning is {provisioningState}");
+        }
+        return new ExpiryCheck(subject, null, AccessibilitySeverity.Info, "is active");
+    }
+
+    internal static ExpiryCheck Classify(string subject, DateTimeOffset expiresOn, DateTimeOffset now, ExpiryAuditOptions options)
+    {
+        var days = (int)Math.Floor((expiresOn - now).TotalDays);
+        if (expiresOn <= now)
+        {
+            return new ExpiryCheck(subject, expiresOn, AccessibilitySeverity.Error, $"expired on {expiresOn:yyyy-MM-dd}");
+        }
+
+        var message = $"expires on {expiresOn:yyyy-MM-dd}, in {days} day(s)";
+        var severity = days < options.ErrorDays ? AccessibilitySeverity.Error
+            : days < options.WarningDays ? AccessibilitySeverity.Warning
+            : AccessibilitySeverity.Info;
+        return new ExpiryCheck(subject, expiresOn, severity, message);
+    }
+
+    // A credential that can't be read is as bad as an expired one for the pipeline that needs it
+    private static ExpiryCheck Check(string subject, Func<ExpiryCheck> check)
+    {
+        try
+        {
+            return check();
+        }
+        catch (Exception ex)
+        {
+            return new ExpiryCheck(subject, null, AccessibilitySeverity.Error, $"can't be checked: {ex.Message}");
+        }
+    }
+
+    private static async Task<ExpiryCheck> CheckAsync(string subject, Func<Task<ExpiryCheck>> check)
+    {
+        try
+        {
+            return await check();
+        }
+        catch (Exception ex) when (ex is not OperationCanceledException)
+        {
+            return new ExpiryCheck(subject, null, AccessibilitySeverity.Error, $"can't be checked: {ex.Message}");
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Formats.Asn1;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// On Windows, .NET performs all hashing, signing and key derivation through CNG, whose primitives are
+/// FIPS 140 validated. Other platforms delegate to whichever OpenSSL build is installed, so FIPS mode only
+/// runs on Windows. Within CNG, only the approved algorithms and key sizes are allowed.
+/// </summary>
+internal class FipsPolicyService : IFipsPolicyService
+{
+    private const string FipsEnvironmentVariable = "WINAPP_CLI_FIPS";
+    private const int MinimumRsaKeySize = 2048;
+    private const int MaxAsnDepth = 32;
+
+    // PKCS#12 PBE (1.2.840.113549.1.12.1.x) and PKCS#5 PBES1 (1.2.840.113549.1.5.x) schemes built on RC2, RC4, DES or 3DES
+    private static readonly HashSet<string> LegacyPbeOids =
+    [
+        "1.2.840.113549.1.12.1.1",
+        "1.2.840.113549.1.12.1.2",
+        "1.2.840.113549.1.12.1.3",
+        "1.2.840.113549.1.12.1.4",
+        "1.2.840.113549.1.12.1.5",
+        "1.2.840.113549.1.12.1.6",
+        "1.2.840.113549.1.5.1",
+        "1.2.840.113549.1.5.3",
+        "1.2.840.113549.1.5.4",
+        "1.2.840.113549.1.5.6",
+        "1.2.840.113549.1.5.10",
+        "1.2.840.113549.1.5.11",
+    ];
+
+    private static readonly HashSet<string> ApprovedCurveOids =
+    [
+        "1.2.840.10045.3.1.7", // P-256
+        "1.3.132.0.34",        // P-384
+        "1.3.132.0.35",        // P-521
+    ];
+
+    private bool? _enabled;
+
+    public bool IsEnabled
+    {
+        get => _enabled ??= Environment.GetEnvironmentVariable(FipsEnvironmentVariable) is "1" or "true";
+        set => _enabled = value;
+    }
+
+    public Pkcs12ExportPbeParameters Pkcs12ExportParameters =>
+        IsEnabled ? Pkcs12ExportPbeParameters.Pbes2Aes256Sha256 : Pkcs12ExportPbeParameters.Default;
+
+    public void EnsureAvailable(string operation)
+    {
+        if (IsEnabled && !OperatingSystem.IsWindows())
+        {
+            throw new InvalidOperationException($"FIPS mode: cannot {operation} on this platform. FIPS mode requires Windows, where hashing and signing use the validated CNG primitives.");
+        }
+    }
+
+    public void EnsureHashAlgorithm(HashAlgorithmName algorithm, string operation)
+    {
+        if (!IsEnabled)
+        {
+            return;
+        }
+
+        if (algorithm != HashAlgorithmName.SHA256 && algorithm != HashAlgorithmName.SHA384 && algorithm != HashAlgorithmName.SHA512)
+        {
+            throw new InvalidOperationException($"FIPS mode: cannot {operation} with {algorithm.Name}; use SHA256, SHA384 or SHA512.");
+        }
+    }
+
+    public void EnsureSigningCertificate(X509Certificate2 certificate)
+    {
+        if (!IsEnabled)
+        {
+            return;
+        }
+
+        using (var rsa = certificate.GetRSAPublicKey())
+        {
+            if (rsa != null)
+            {
+                if (rsa.KeySize < MinimumRsaKeySize)
+                {
+                    throw new InvalidOperationException($"FIPS mode: the signing key of '{certificate.Subject}' is RSA {rsa.KeySize}-bit; at least {MinimumRsaKeySize} bits are required.");
+                }
+                return;
+            }
+        }
+
+        using (var ecdsa = certificate.GetECDsaPublicKey())
+        {
+            if (ecdsa != null)
+            {
+                var curve = ecdsa.ExportParameters(includePrivateParameters: false).Curve;
+                if (!curve.IsNamed || curve.Oid.Value is not string curveOid || !ApprovedCurveOids.Contains(curveOid))
+                {
+                    throw new InvalidOperationException($"FIPS mode: the signing key of '{certificate.Subject}' uses the curve {curve.Oid.FriendlyName ?? curve.Oid.Value}; use P-256, P-384 or P-521.");
+                }
+                return;
+            }
+        }
+
+        throw new InvalidOperationException($"FIPS mode: the signing key of '{certificate.Subject}' must be RSA or ECDSA.");
+    }
+
+    public void EnsurePkcs12(FileInfo pfxPath)
+    {
+        if (!IsEnabled)
+        {
+            return;
+        }
+
+        var oids = new HashSet<string>(StringComparer.Ordinal);
+        CollectObjectIdentifiers(File.ReadAllBytes(pfxPath.FullName), oids, depth: 0);
+        if (oids.Overlaps(LegacyPbeOids))
+        {
+            throw new InvalidOperationException($"FIPS mode: {pfxPath.Name} is protected with legacy PKCS#12 encryption (3DES/RC2). Re-export it with AES-256 encryption, or regenerate it with 'winapp cert generate --fips'.");
+        }
+    }
+
+    /// <summary>
+    /// Collects the algorithm identifiers of a PFX file. SafeContents are nested in OCTET STRINGs, so those are
+    /// descended into as well; encrypted content does not parse and is skipped, which is fine because the
+    /// algorithm protecting it sits outside the ciphertext.
+    /// </summary>
+    private static void CollectObjectIdentifiers(ReadOnlySpan<byte> der, HashSet<string> oids, int depth)
+    {
+        while (!der.IsEmpty && depth < MaxAsnDepth)
+        {
+            Asn1Tag tag;
+            int contentOffset, contentLength, consumed;
+            try
+            {
+                tag = Asn1Tag.Decode(der, out _);
+                AsnDecoder.ReadEncodedValue(der, AsnEncodingRules.BER, out contentOffset, out contentLength, out consumed);
+                if (tag.HasSameClassAndValue(Asn1Tag.ObjectIdentifier))
+                {
+                    oids.Add(AsnDecoder.ReadObjectIdentifier(der[..consumed], AsnEncodingRules.BER, out _));
+                }
+            }
+            catch (AsnContentException)
+            {
+                return;
+            }
+
+            if (tag.IsConstructed || tag.HasSameClassAndValue(Asn1Tag.PrimitiveOctetString))
+            {
+                CollectObjectIdentifiers(der.Slice(contentOffset, contentLength), oids, depth + 1);
+            }
+
+            der = der[consumed..];
+        }
+    }
+}
@@ -81,7 +81,23 @@ internal class GitignoreService : IGitignoreService
     /// <param name="projectDirectory">Directory containing the project</param>
     /// <param name="certificateFileName">Name of the certificate file to add</param>
     /// <returns>True if gitignore was updated, false if entry already existed</returns>
-    public async Task<bool> AddCertificateToGitignoreAsync(DirectoryInfo projectDirectory, string certificateFileName, TaskContext taskContext, CancellationToken cancellationToken)
+    public Task<bool> AddCertificateToGitignoreAsync(DirectoryInfo projectDirectory, string certificateFileName, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        return AddFileToGitignoreAsync(projectDirectory, certificateFileName, "Development certificate", taskContext, cancellationToken);
+    }
+
+    /// <summary>
+    /// Add a private key file to .gitignore
+    /// </summary>
+    /// <param name="projectDirectory">Directory containing the key</param>
+    /// <param name="keyFileName">Name of the private key file to add</param>
+    /// <returns>True if gitignore was updated, false if entry already existed</returns>
+    public Task<bool> AddPrivateKeyToGitignoreAsync(DirectoryInfo projectDirectory, string keyFileName, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        return AddFileToGitignoreAsync(projectDirectory, keyFileName, "License signing key", taskContext, cancellationToken);
+    }
+
+    private static async Task<bool> AddFileToGitignoreAsync(DirectoryInfo projectDirectory, string fileName, string comment, TaskContext taskContext, CancellationToken cancellationToken)
     {
         try
         {
@@ -95,13 +111,13 @@ internal class GitignoreService : IGitignoreService
                 gitignoreContent = await File.ReadAllTextAsync(gitignorePath, cancellationToken);
             }
 
-            // Check if certificate file is already in .gitignore
+            // Check if the file is already in .gitignore
             var lines = gitignoreContent.Split('\n');
-            var hasCertEntry = lines.Any(line => line.Trim() == certificateFileName);
+            var hasEntry = lines.Any(line => line.Trim() == fileName);
 
-            if (!hasCertEntry)
+            if (!hasEntry)
             {
-                // Add certificate entry to .gitignore
+                // Add the file's entry to .gitignore
                 var newContent = gitignoreContent;
 
                 // Ensure we have a newline before our entry if file exists and doesn't end with newline
@@ -110,20 +126,20 @@ internal class GitignoreService : IGitignoreService
                     newContent += '\n';
                 }
 
-                // Add certificate entry with comment
+                // Add the entry with comment
                 newContent += '\n';
-                newContent += "# Development certificate\n";
-                newContent += certificateFileName + '\n';
+                newContent += $"# {comment}\n";
+                newContent += fileName + '\n';
 
                 await File.WriteAllTextAsync(gitignorePath, newContent, cancellationToken);
 
-                taskContext.AddDebugMessage($"{UiSymbols.Check} Added {certificateFileName} to .gitignore");
+                taskContext.AddDebugMessage($"{UiSymbols.Check} Added {fileName} to .gitignore");
 
                 return true;
             }
             else
             {
-                taskContext.AddDebugMessage($"{UiSymbols.Skip} {certificateFileName} already exists in .gitignore");
+                taskContext.AddDebugMessage($"{UiSymbols.Skip} {fileName} already exists in .gitignore");
             }
 
             return false;
@@ -0,0 +1,19 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks the visual assets and display metadata of a manifest for common accessibility problems
+/// </summary>
+internal interface IAccessibilityAuditService
+{
+    /// <summary>
+    /// Checks tile and splash screen contrast, unplated and high contrast icon variants, and tile name lengths.
+    /// Assets are resolved relative to the manifest, including their MRT qualified variants.
+    /// </summary>
+    public Task<AccessibilityReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
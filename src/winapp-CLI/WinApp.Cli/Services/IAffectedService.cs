@@ -0,0 +1,17 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Finds the package projects of the workspace that changed since a git ref
+/// </summary>
+internal interface IAffectedService
+{
+    /// <param name="since">Ref to compare against; changes are taken from its merge base with HEAD, as a pull request would show them</param>
+    /// <param name="includeUncommitted">Also count staged, unstaged and untracked changes in the working tree</param>
+    public Task<AffectedResult> FindAsync(string since, bool includeUncommitted, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Runs the custom analyzers of winapp.yaml, so teams can check their own policies without changing winapp
+/// </summary>
+internal interface IAnalyzerPluginService
+{
+    /// <summary>
+    /// Runs each analyzer of 'analyzers:' with the manifest, winapp.yaml and the files next to the manifest as JSON
+    /// on stdin, and reads the diagnostics it writes as JSON on stdout. Without winapp.yaml or analyzers, returns none.
+    /// </summary>
+    /// <param name="allowScriptAnalyzers">Whether to run .ps1, .js and executable analyzers, which run with the
+    /// permissions of winapp; without it, only .wasm analyzers run and the others are skipped with a warning</param>
+    /// <exception cref="InvalidOperationException">An analyzer can't be started, fails, times out or writes something other than diagnostics</exception>
+    public Task<IReadOnlyList<AnalyzerDiagnostic>> RunAsync(FileInfo manifestPath, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
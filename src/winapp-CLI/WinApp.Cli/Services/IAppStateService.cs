@@ -0,0 +1,44 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Clears, saves and restores the state an installed package keeps for the current user: its ApplicationData
+/// folders, its settings containers and its virtualized registry
+/// </summary>
+internal interface IAppStateService
+{
+    /// <summary>
+    /// Finds the installed package of the project
+    /// </summary>
+    /// <param name="name">Package name; null uses the Identity Name of <paramref name="manifest"/>, or of the
+    /// project's appxmanifest.xml, and picks its .debug or branch identity when that is what is registered</param>
+    /// <exception cref="InvalidOperationException">The package isn't installed, or no manifest was found</exception>
+    public Task<InstalledPackage> FindPackageAsync(string? name, FileInfo? manifest, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Resolves a snapshot argument: a name of a snapshot kept in the project's .winapp folder, or the path of a
+    /// snapshot .zip
+    /// </summary>
+    public FileInfo GetSnapshotFile(string nameOrPath);
+
+    /// <summary>
+    /// Stops the app and deletes its state, as if it was just installed
+    /// </summary>
+    /// <returns>Number of files deleted</returns>
+    public Task<int> ResetAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Stops the app and saves its state to <paramref name="file"/>
+    /// </summary>
+    public Task<AppStateSnapshot> SnapshotAsync(InstalledPackage package, FileInfo file, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Stops the app and replaces its state with the snapshot in <paramref name="file"/>
+    /// </summary>
+    public Task<AppStateSnapshot> RestoreAsync(InstalledPackage package, FileInfo file, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
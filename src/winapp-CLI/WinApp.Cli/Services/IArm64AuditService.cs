@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks how a package runs on Windows on ARM devices, where x64 and x86 code runs under emulation
+/// </summary>
+internal interface IArm64AuditService
+{
+    /// <summary>
+    /// Reads the architecture of every binary in the package folder of the manifest and reports those that run
+    /// emulated, and those that don't run at all or run poorly that way: drivers, File Explorer extensions and code
+    /// that talks to drivers, with how to build each for ARM64 or ARM64EC
+    /// </summary>
+    public Task<Arm64Report> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
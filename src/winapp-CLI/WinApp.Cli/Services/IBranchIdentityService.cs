@@ -0,0 +1,34 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Resolves, signs for and removes the throwaway identities of branch builds
+/// </summary>
+internal interface IBranchIdentityService
+{
+    /// <summary>
+    /// Uses <paramref name="branch"/>, or else the checked-out branch, or else the branch CI is building (for
+    /// detached checkouts)
+    /// </summary>
+    public Task<BranchIdentity> ResolveAsync(string? branch, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Returns the branch's certificate, generating it on first use. Every build of the branch signs with the
+    /// same certificate, so testers only trust it once.
+    /// </summary>
+    public Task<FileInfo> EnsureCertificateAsync(BranchIdentity identity, string password, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Removes the branch's installed packages, its certificate from the certificate stores and the certificate file
+    /// </summary>
+    public Task<BranchCleanupResult> CleanupAsync(BranchIdentity identity, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
+
+/// <param name="Packages">Full names of the removed packages</param>
+/// <param name="Certificates">Removed certificates, as "store: thumbprint"</param>
+internal sealed record BranchCleanupResult(IReadOnlyList<string> Packages, IReadOnlyList<string> Certificates);
@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.BuildCache;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Opens the remote cache that pack outputs are shared through
+/// </summary>
+internal interface IBuildCacheService
+{
+    /// <summary>
+    /// Opens the cache at <paramref name="location"/>: an Azure container URL with a SAS token, s3://bucket/prefix
+    /// (region from AWS_REGION) or an http(s) URL
+    /// </summary>
+    public IRemoteBuildCache Create(string location);
+}
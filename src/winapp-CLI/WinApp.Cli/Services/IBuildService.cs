@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Builds the app for 'winapp pack --build' with the build system of 'build:' in winapp.yaml, or the one its project
+/// files are for
+/// </summary>
+internal interface IBuildService
+{
+    /// <summary>
+    /// Runs the build and copies its outputs into the payload folder
+    /// </summary>
+    /// <param name="architecture">ProcessorArchitecture of the manifest, which the build targets</param>
+    /// <param name="payloadFolder">Folder to copy the outputs into (default: .winapp\payload, emptied first)</param>
+    /// <returns>The payload folder</returns>
+    public Task<DirectoryInfo> BuildAsync(string architecture, DirectoryInfo? payloadFolder, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
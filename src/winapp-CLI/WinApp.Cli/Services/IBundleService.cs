@@ -0,0 +1,53 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// A package listed in a bundle's AppxBundleManifest.xml.
+/// </summary>
+/// <param name="FileName">Path of the package inside the bundle</param>
+/// <param name="Type">Package type, either "application" or "resource"</param>
+/// <param name="Architecture">Processor architecture for application packages (null for resource packages)</param>
+/// <param name="ResourceId">Resource ID of the package, if any (e.g. "split.language-fr")</param>
+/// <param name="Languages">Languages declared by the package resources</param>
+/// <param name="Scales">Scales declared by the package resources</param>
+internal record BundlePackageEntry(
+    string FileName,
+    string Type,
+    string? Architecture,
+    string? ResourceId,
+    IReadOnlyList<string> Languages,
+    IReadOnlyList<int> Scales)
+{
+    public bool IsApplication => string.Equals(Type, "application", StringComparison.OrdinalIgnoreCase);
+}
+
+internal interface IBundleService
+{
+    /// <summary>
+    /// Reads the list of packages declared in the bundle manifest.
+    /// </summary>
+    /// <param name="bundlePath">Path to the .msixbundle/.appxbundle file</param>
+    /// <param name="cancellationToken">Cancellation token</param>
+    /// <returns>The packages contained in the bundle</returns>
+    Task<IReadOnlyList<BundlePackageEntry>> ReadBundlePackagesAsync(FileInfo bundlePath, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Extracts the application package matching the architecture plus the matching
+    /// language/scale resource packages and repacks them as a single installable package.
+    /// </summary>
+    Task<BundleThinResult> ThinBundleAsync(
+        FileInfo bundlePath,
+        string architecture,
+        IReadOnlyList<string> languages,
+        IReadOnlyList<int> scales,
+        FileInfo? outputPath,
+        FileInfo? certificatePath,
+        string certificatePassword,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default);
+}
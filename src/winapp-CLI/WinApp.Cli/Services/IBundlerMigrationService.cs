@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Moves the MSIX configuration of electron-builder or the Tauri bundler to winapp
+/// </summary>
+internal interface IBundlerMigrationService
+{
+    /// <summary>
+    /// Default configuration file of a bundler in <paramref name="directory"/>: package.json with a 'build' section
+    /// or electron-builder.json, and src-tauri/tauri.conf.json or tauri.conf.json
+    /// </summary>
+    public FileInfo? FindConfig(MigrationSource source, DirectoryInfo directory);
+
+    /// <summary>
+    /// Reads the bundler's configuration and writes an equivalent appxmanifest.xml, Assets and winapp.yaml to the
+    /// directory of winapp.yaml, keeping what winapp.yaml declares already
+    /// </summary>
+    /// <param name="configPath">package.json, electron-builder.json or tauri.conf.json</param>
+    /// <param name="force">Overwrite an existing appxmanifest.xml</param>
+    /// <exception cref="InvalidOperationException">The configuration can't be read, or appxmanifest.xml exists without <paramref name="force"/></exception>
+    public Task<MigrationResult> MigrateAsync(MigrationSource source, FileInfo configPath, bool force, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
 using static WinApp.Cli.Services.CertificateService;
 
 namespace WinApp.Cli.Services;
@@ -27,7 +28,17 @@ internal interface ICertificateService
         int validDays = 365,
         CancellationToken cancellationToken = default);
 
-    public bool InstallCertificate(FileInfo certPath, string password, bool force, TaskContext taskContext);
+    /// <summary>
+    /// The step that trusts the certificate for sideloading, for commands that batch their elevated steps; null
+    /// when it is already trusted and <paramref name="force"/> is false
+    /// </summary>
+    public ElevatedStep? PlanCertificateTrust(FileInfo certPath, string password, bool force, TaskContext taskContext);
+
+    /// <summary>
+    /// Trusts the certificate for sideloading, prompting for elevation when needed
+    /// </summary>
+    /// <returns>false when it was already trusted</returns>
+    public Task<bool> InstallCertificateAsync(FileInfo certPath, string password, bool force, TaskContext taskContext, CancellationToken cancellationToken = default);
 
     public Task SignFileAsync(FileInfo filePath, FileInfo certificatePath, TaskContext taskContext, string? password = "password", string? timestampUrl = null, CancellationToken cancellationToken = default);
 }
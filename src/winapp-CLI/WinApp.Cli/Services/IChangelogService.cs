@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Changelog;
+using WinApp.Cli.ConsoleTasks;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Builds release notes from the git history of the current repository
+/// </summary>
+internal interface IChangelogService
+{
+    public Task<ChangelogResult> BuildAsync(ChangelogOptions options, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
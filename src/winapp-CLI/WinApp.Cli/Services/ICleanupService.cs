@@ -0,0 +1,19 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Finds and removes what development builds leave on a machine: loose and sparse registrations, staged
+/// packages and development certificates
+/// </summary>
+internal interface ICleanupService
+{
+    /// <param name="allWinapp">Look at everything winapp created on the machine instead of only the current project</param>
+    public Task<CleanupPlan> FindAsync(bool allWinapp, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    public Task<CleanupResult> RemoveAsync(IReadOnlyList<CleanupItem> items, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
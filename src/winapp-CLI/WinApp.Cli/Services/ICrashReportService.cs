@@ -0,0 +1,27 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Sends the crash dumps an installed package wrote, and the symbols to read them, to the crash reporting service
+/// under 'crashes:' in winapp.yaml
+/// </summary>
+internal interface ICrashReportService
+{
+    /// <summary>
+    /// Finds the crash dumps of the package that weren't uploaded yet: the .dmp files the winapp-runtime crate
+    /// writes to LocalState\CrashDumps
+    /// </summary>
+    public IReadOnlyList<FileInfo> FindDumps(InstalledPackage package);
+
+    /// <summary>
+    /// Uploads the symbols under <paramref name="symbols"/>, then each dump, and moves the uploaded dumps to
+    /// CrashDumps\Uploaded unless <paramref name="keep"/> is set
+    /// </summary>
+    /// <exception cref="InvalidOperationException">winapp.yaml has no crash reporting endpoint</exception>
+    public Task<CrashUploadResult> UploadAsync(InstalledPackage package, IReadOnlyList<FileInfo> dumps, DirectoryInfo? symbols, bool keep, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Finds out whether Microsoft Defender's real-time scanning or Controlled Folder Access slows down or blocks pack
+/// and register, and excludes the folders and tools of the dev loop
+/// </summary>
+internal interface IDefenderAuditService
+{
+    /// <summary>
+    /// Reads the Defender settings, times file writes in the folders of the dev loop and reports what slows it down
+    /// </summary>
+    /// <param name="folders">Folders to check besides the .winapp folders, e.g. the build output</param>
+    public Task<DefenderAudit> AuditAsync(IReadOnlyList<DirectoryInfo> folders, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Excludes the folders of <paramref name="audit"/> from scanning and, with Controlled Folder Access on, allows
+    /// its tools, from an elevated PowerShell (User Account Control prompts)
+    /// </summary>
+    public Task ExcludeAsync(DefenderAudit audit, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Writes the audit as Markdown, or as JSON for a .json file, with the commands that add the exclusions for IT
+    /// </summary>
+    public Task WriteReportAsync(DefenderAudit audit, FileInfo output, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal interface IDeploymentEventService
+{
+    /// <summary>
+    /// Reads the errors and warnings that the deployment and the app model runtime logged about a package since a
+    /// point in time, oldest first
+    /// </summary>
+    /// <param name="packageName">Identity name of the package; events name its full name or family name</param>
+    /// <param name="since">Start of the operation that failed</param>
+    IReadOnlyList<DeploymentEvent> GetEvents(string packageName, DateTime since, TaskContext taskContext);
+}
@@ -0,0 +1,27 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Runs a build output folder with the debug identity of its manifest for 'winapp dev', from a copy in .winapp\dev
+/// that is updated file by file as the build changes
+/// </summary>
+internal interface IDevLoopService
+{
+    /// <summary>
+    /// Copies <paramref name="inputFolder"/> to .winapp\dev and registers the copy as a sparse package with the debug
+    /// identity of <paramref name="manifest"/>
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The manifest has no Executable, or the last layout is still in use</exception>
+    public Task<DevSession> StartAsync(DirectoryInfo inputFolder, FileInfo manifest, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Copies the files that changed since the last sync, then registers the package again when
+    /// <paramref name="manifestChanged"/> is set, or embeds the identity when only the executable was rebuilt
+    /// </summary>
+    public Task<DevUpdate> UpdateAsync(DevSession session, bool manifestChanged, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Distribution;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Builds and publishes static sites that distribute packages outside the Store through App Installer
+/// </summary>
+internal interface IDistributionService
+{
+    /// <summary>
+    /// Writes an install page, an .appinstaller file, the package and, for self-signed packages, the signing
+    /// certificate to a folder
+    /// </summary>
+    public Task<DistributionSite> BuildWebSiteAsync(FileInfo packagePath, DirectoryInfo outputDirectory, DistributionWebOptions options, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Creates a target for an Azure Storage container, which may be fronted by a CDN
+    /// </summary>
+    /// <param name="containerSasUri">Container URL with a SAS token that allows creating and writing blobs</param>
+    public IDistributionPublishTarget CreateAzureBlobTarget(Uri containerSasUri);
+
+    /// <summary>
+    /// Creates a target for an S3 bucket, with credentials from the AWS_* environment variables
+    /// </summary>
+    /// <param name="location">s3://bucket or s3://bucket/prefix</param>
+    /// <param name="region">AWS region of the bucket; null reads AWS_REGION or AWS_DEFAULT_REGION</param>
+    public IDistributionPublishTarget CreateS3Target(Uri location, string? region);
+
+    /// <summary>
+    /// Creates a target for a GitHub release, with a token from GITHUB_TOKEN or GH_TOKEN
+    /// </summary>
+    /// <param name="repository">owner/name; null reads GITHUB_REPOSITORY</param>
+    public IDistributionPublishTarget CreateGitHubReleasesTarget(string? repository, string tag);
+
+    /// <param name="remote">Git remote name or URL to push to</param>
+    public IDistributionPublishTarget CreateGitHubPagesTarget(string remote, string branch);
+
+    /// <summary>
+    /// Uploads the site to a target, then requests the .appinstaller file and the package from the base URL
+    /// to catch servers and CDNs that send them with the wrong content type
+    /// </summary>
+    /// <returns>The files served with another content type than App Installer expects</returns>
+    public Task<IReadOnlyList<ContentTypeMismatch>> PublishAsync(DistributionSite site, IDistributionPublishTarget target, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Downloads the signed integrity manifest next to an .appinstaller file, checks its signature and compares
+    /// the site's files against it
+    /// </summary>
+    /// <param name="appInstallerLocation">URL or local path of the .appinstaller file</param>
+    /// <param name="expectedThumbprint">Thumbprint the signer must have; null accepts a signer whose subject is the package publisher</param>
+    /// <param name="includePackages">Whether to download and hash the packages too, which can be large</param>
+    public Task<FeedVerificationResult> VerifyFeedAsync(string appInstallerLocation, string? expectedThumbprint, bool includePackages, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Runs the steps of a command that need administrator rights in one elevated PowerShell, so commands plan those
+/// steps up front and Windows asks once, instead of failing midway or prompting for each
+/// </summary>
+internal interface IElevationService
+{
+    /// <summary>
+    /// Whether winapp runs as administrator, in which case steps run without a prompt
+    /// </summary>
+    public bool IsElevated { get; }
+
+    /// <summary>
+    /// Lists the steps, then runs them all after a single User Account Control prompt. A step that fails doesn't
+    /// stop the others.
+    /// </summary>
+    /// <returns>A result per step, in order; every step fails when the prompt is declined</returns>
+    public Task<IReadOnlyList<ElevatedStepResult>> RunAsync(IReadOnlyList<ElevatedStep> steps, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
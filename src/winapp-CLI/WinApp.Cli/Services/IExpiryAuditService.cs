@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks when the credentials a release pipeline depends on expire, so a scheduled job can renew them in time
+/// </summary>
+internal interface IExpiryAuditService
+{
+    /// <summary>
+    /// Checks the signing certificates, the Store automation's client secret and the Trusted Signing profile of
+    /// <paramref name="options"/>
+    /// </summary>
+    /// <returns>One check per credential; a credential that can't be read is an Error</returns>
+    public Task<List<ExpiryCheck>> CheckAsync(ExpiryAuditOptions options, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Restricts hashing and signing to FIPS 140 validated primitives when FIPS mode is on (--fips or WINAPP_CLI_FIPS=1).
+/// Every check is a no-op while FIPS mode is off.
+/// </summary>
+internal interface IFipsPolicyService
+{
+    public bool IsEnabled { get; set; }
+
+    /// <summary>
+    /// Fails when this platform cannot guarantee validated primitives for <paramref name="operation"/>
+    /// </summary>
+    public void EnsureAvailable(string operation);
+
+    public void EnsureHashAlgorithm(HashAlgorithmName algorithm, string operation);
+
+    /// <summary>
+    /// Fails when the certificate's key type or size is not approved for signing
+    /// </summary>
+    public void EnsureSigningCertificate(X509Certificate2 certificate);
+
+    /// <summary>
+    /// Fails when a PFX file protects its keys or certificates with legacy (RC2, RC4, 3DES or PBES1) encryption
+    /// </summary>
+    public void EnsurePkcs12(FileInfo pfxPath);
+
+    /// <summary>
+    /// Encryption to use when exporting PFX files: AES-256/SHA-256 in FIPS mode, the platform default otherwise
+    /// </summary>
+    public Pkcs12ExportPbeParameters Pkcs12ExportParameters { get; }
+}
@@ -0,0 +1,16 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Runs the git command line, which must be on PATH
+/// </summary>
+internal interface IGitService
+{
+    /// <summary>
+    /// Runs git and returns its standard output
+    /// </summary>
+    /// <exception cref="InvalidOperationException">git is not installed or exits with an error</exception>
+    public Task<string> RunAsync(string workingDirectory, IEnumerable<string> arguments, CancellationToken cancellationToken = default);
+}
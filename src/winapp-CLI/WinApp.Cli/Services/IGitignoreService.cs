@@ -9,4 +9,5 @@ internal interface IGitignoreService
 {
     Task<bool> AddWinAppFolderToGitIgnoreAsync(DirectoryInfo projectDirectory, TaskContext taskContext, CancellationToken cancellationToken);
     Task<bool> AddCertificateToGitignoreAsync(DirectoryInfo projectDirectory, string certificateFileName, TaskContext taskContext, CancellationToken cancellationToken);
+    Task<bool> AddPrivateKeyToGitignoreAsync(DirectoryInfo projectDirectory, string keyFileName, TaskContext taskContext, CancellationToken cancellationToken);
 }
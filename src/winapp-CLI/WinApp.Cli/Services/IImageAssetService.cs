@@ -13,6 +13,13 @@ namespace WinApp.Cli.Services;
 /// <param name="BaseHeight">The base height in pixels for the asset</param>
 internal record ManifestAssetReference(string RelativePath, int BaseWidth, int BaseHeight);
 
+/// <summary>
+/// A ribbon drawn across the bottom of icons to tell channel builds apart, e.g. BETA or DEV.
+/// </summary>
+/// <param name="Text">The text of the ribbon; icons too small to read it get the ribbon alone</param>
+/// <param name="Color">The ribbon color as #RRGGBB or a color name; null for the default orange</param>
+internal record AssetBadge(string Text, string? Color);
+
 internal interface IImageAssetService
 {
     /// <summary>
@@ -42,4 +49,21 @@ internal interface IImageAssetService
         IReadOnlyList<ManifestAssetReference> assetReferences,
         TaskContext taskContext,
         CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Draws a badge onto the manifest's image assets and all their scale and targetsize variants, in place.
+    /// BadgeLogo and LockScreenLogo are left alone, since Windows recolors those glyphs.
+    /// </summary>
+    /// <param name="manifestDirectory">Directory where the manifest is located (assets are relative to this)</param>
+    /// <param name="assetReferences">Asset references extracted from the manifest</param>
+    /// <param name="badge">The badge to draw</param>
+    /// <param name="taskContext">Task context for status messages</param>
+    /// <param name="cancellationToken">Cancellation token</param>
+    /// <returns>The original contents of every file that was changed, by full path, so callers can restore them</returns>
+    Task<IReadOnlyDictionary<string, byte[]>> ApplyBadgeAsync(
+        DirectoryInfo manifestDirectory,
+        IReadOnlyList<ManifestAssetReference> assetReferences,
+        AssetBadge badge,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default);
 }
@@ -0,0 +1,37 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Lists and removes the packages registered on this machine
+/// </summary>
+internal interface IInstalledPackageService
+{
+    /// <summary>
+    /// Lists the current user's packages and, from an elevated prompt, the packages staged for no user
+    /// </summary>
+    public Task<InstalledPackageList> GetPackagesAsync(TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <param name="allUsers">Remove a staged package, or the package for every user (needs an elevated prompt)</param>
+    public Task UninstallAsync(string fullName, bool allUsers, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Lists the processes running in the package's context: those started from its folders, those that use its
+    /// ApplicationData folder, such as WebView2, and every process they started, such as COM surrogates
+    /// </summary>
+    public Task<IReadOnlyList<PackageProcess>> GetProcessesAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Stops the processes and waits for them to exit
+    /// </summary>
+    public Task StopProcessesAsync(IReadOnlyList<PackageProcess> processes, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Opens a folder in File Explorer
+    /// </summary>
+    public void OpenFolder(string path);
+}
@@ -0,0 +1,30 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Licensing;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Issues and checks device-bound license files for apps that are not licensed through the Store
+/// </summary>
+internal interface ILicenseService
+{
+    /// <summary>
+    /// Generates an ECDSA P-256 key pair for signing licenses. Only the current user can read the private key.
+    /// </summary>
+    /// <param name="password">Encrypts the private key when set</param>
+    /// <returns>The public key file, written next to <paramref name="privateKeyPath"/></returns>
+    public Task<FileInfo> GenerateKeyAsync(FileInfo privateKeyPath, string? password, CancellationToken cancellationToken = default);
+
+    public Task IssueAsync(LicenseClaims claims, FileInfo privateKeyPath, string? password, FileInfo outputPath, CancellationToken cancellationToken = default);
+
+    /// <param name="deviceId">Device to check a device-bound license against (default: this device)</param>
+    /// <param name="packageFamilyName">Package family to check a package-bound license against</param>
+    public Task<LicenseCheck> VerifyAsync(FileInfo licensePath, FileInfo publicKeyPath, string? deviceId, string? packageFamilyName, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Device ID of this machine, which customers send to get a license for it
+    /// </summary>
+    public string GetDeviceId();
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Reads the logs an installed package writes to its ApplicationData folder
+/// </summary>
+internal interface ILogsService
+{
+    /// <summary>
+    /// Finds the log files of the package: every file of its Logs folders, and the .log files of the folders where
+    /// Windows redirects its writes to %LOCALAPPDATA% and %APPDATA%
+    /// </summary>
+    public IReadOnlyList<FileInfo> FindLogFiles(InstalledPackage package);
+
+    /// <summary>
+    /// Passes the last lines of the package's current log files to <paramref name="onLine"/> and, with
+    /// <paramref name="follow"/>, the lines written to them afterwards until <paramref name="cancellationToken"/> is
+    /// cancelled, including log files that are created or rotated meanwhile
+    /// </summary>
+    public Task TailAsync(InstalledPackage package, int lines, bool follow, Action<LogLine> onLine, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Zips the log files of the package into <paramref name="output"/>, with their paths in the ApplicationData folder
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The package has no log files</exception>
+    public LogCollection Collect(InstalledPackage package, FileInfo output);
+}
@@ -0,0 +1,24 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Annotates a package manifest with what its elements do, which Windows releases they need and what the analyze
+/// checks find in them
+/// </summary>
+internal interface IManifestExplainService
+{
+    /// <summary>
+    /// Places a comment before each element of the manifest, explaining it and the first Windows release that reads
+    /// its schema, followed by the issues of 'analyze a11y', 'analyze splash', 'analyze scales' and the custom
+    /// analyzers of winapp.yaml about it.
+    /// </summary>
+    /// <param name="splash">Splash declared in winapp.yaml, which the splash checks apply as 'pack' would</param>
+    /// <param name="runChecks">Whether to run the analyze checks and custom analyzers; without them, only schema versions are checked</param>
+    /// <param name="allowScriptAnalyzers">Whether the custom analyzers include scripts and executables, or only .wasm modules</param>
+    public Task<ManifestExplanation> ExplainAsync(FileInfo manifestPath, SplashConfig? splash, bool runChecks, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -35,6 +35,17 @@ internal interface IManifestService
     public Task UpdateManifestAssetsAsync(
         FileInfo manifestPath,
         FileInfo imagePath,
+        AssetBadge? badge,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Draws a badge onto the image assets the manifest references, in place
+    /// </summary>
+    /// <returns>The original contents of every file that was changed, by full path</returns>
+    public Task<IReadOnlyDictionary<string, byte[]>> ApplyBadgeAsync(
+        FileInfo manifestPath,
+        AssetBadge badge,
         TaskContext taskContext,
         CancellationToken cancellationToken = default);
 }
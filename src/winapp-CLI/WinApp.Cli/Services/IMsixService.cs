@@ -1,6 +1,7 @@
 // Copyright (c) Microsoft Corporation. All rights reserved.
 // Licensed under the MIT License.
 
+using WinApp.Cli.BuildCache;
 using WinApp.Cli.ConsoleTasks;
 using WinApp.Cli.Models;
 
@@ -22,6 +23,29 @@ internal interface IMsixService
         string? publisher = null,
         FileInfo? manifestPath = null,
         bool selfContained = false,
+        PackagingEngine engine = PackagingEngine.Sdk,
+        int threads = 0,
+        bool useBlockCache = true,
+        DigestAlgorithm digest = DigestAlgorithm.Sha256,
+        RemoteBuildCacheOptions? remoteCache = null,
+        BranchIdentity? branchIdentity = null,
+        BuildProvenance? provenance = null,
+        AssetBadge? badge = null,
+        MatrixVariant? variant = null,
+        CancellationToken cancellationToken = default);
+
+    public Task<FileInfo> CreatePriConfigAsync(
+        DirectoryInfo packageDir,
+        TaskContext taskContext,
+        string language = "en-US",
+        string platformVersion = "10.0.0",
+        CancellationToken cancellationToken = default);
+
+    public Task<List<FileInfo>> GeneratePriFileAsync(
+        DirectoryInfo packageDir,
+        TaskContext taskContext,
+        FileInfo? configPath = null,
+        FileInfo? outputPath = null,
         CancellationToken cancellationToken = default);
 
     public Task<MsixIdentityResult> AddMsixIdentityAsync(
@@ -30,4 +54,14 @@ internal interface IMsixService
         bool noInstall,
         TaskContext taskContext,
         CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Embeds the debug identity of the manifest into an executable that was rebuilt after 'AddMsixIdentityAsync'
+    /// registered it, without registering the package again
+    /// </summary>
+    public Task<MsixIdentityResult> EmbedDebugIdentityAsync(
+        FileInfo exePath,
+        FileInfo appxManifestPath,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default);
 }
@@ -0,0 +1,44 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Creates and signs MSIX packages without the Windows SDK build tools
+/// </summary>
+internal interface INativePackagingService
+{
+    /// <summary>
+    /// Packs a layout folder into an unsigned package
+    /// </summary>
+    /// <param name="threads">Hash/compress workers; 0 uses one per logical processor</param>
+    /// <param name="useBlockCache">Reuse blocks compressed by earlier packs (in this process and in the global winapp directory)</param>
+    /// <param name="digest">Block map hash algorithm; signing later uses the same algorithm</param>
+    public Task<AppxPackResult> PackAsync(DirectoryInfo inputFolder, FileInfo outputPath, TaskContext taskContext, int threads = 0, bool useBlockCache = true, DigestAlgorithm digest = DigestAlgorithm.Sha256, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Signs a package in place, using the hash algorithm of its block map for every digest
+    /// </summary>
+    public Task SignAsync(FileInfo packagePath, FileInfo certificatePath, TaskContext taskContext, string? password = "password", string? timestampUrl = null, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Signs a package in place with a key held by a signing service, such as Azure Key Vault or Trusted Signing
+    /// </summary>
+    public Task SignAsync(FileInfo packagePath, RemoteHashSigner signer, TaskContext taskContext, string? timestampUrl = null, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Verifies the block map digests and the AppxSignature.p7x of a package. Works without WinVerifyTrust,
+    /// so it does not evaluate certificate trust.
+    /// </summary>
+    public Task<AppxVerificationResult> VerifyAsync(FileInfo packagePath, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Reports the hash and signature algorithms a package uses
+    /// </summary>
+    /// <param name="required">Weakest acceptable algorithm; weaker ones are listed as issues</param>
+    public Task<AppxDigestReport> InspectDigestsAsync(FileInfo packagePath, TaskContext taskContext, DigestAlgorithm? required = null, CancellationToken cancellationToken = default);
+}
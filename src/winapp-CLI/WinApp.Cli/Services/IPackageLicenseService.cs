@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Keeps the record in winapp.yaml of the licenses accepted for packages that require license acceptance, so
+/// restore runs in CI without prompts
+/// </summary>
+internal interface IPackageLicenseService
+{
+    /// <summary>
+    /// Licenses that the installed packages of <paramref name="versions"/> require accepting, from their .nuspec
+    /// </summary>
+    public IReadOnlyList<PackageLicense> GetRequiredLicenses(DirectoryInfo packagesDir, IReadOnlyDictionary<string, string> versions);
+
+    /// <summary>
+    /// Checks that winapp.yaml records an acceptance of each required license, and records the ones
+    /// <paramref name="consent"/> accepts. A license needs accepting again when its expression, file or text changes.
+    /// </summary>
+    /// <returns>The licenses that are still not accepted</returns>
+    public Task<IReadOnlyList<PackageLicense>> EnsureAcceptedAsync(DirectoryInfo packagesDir, IReadOnlyDictionary<string, string> versions, LicenseConsent consent, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Prepares the variants of the build matrix in winapp.yaml (brandings × channels × architectures) for 'pack --matrix'
+/// </summary>
+internal interface IPackageMatrixService
+{
+    /// <summary>
+    /// Expands the matrix of winapp.yaml
+    /// </summary>
+    /// <param name="filters">Variant or dimension names to keep, e.g. contoso or beta; empty keeps every variant</param>
+    /// <exception cref="InvalidOperationException">winapp.yaml declares no matrix, or no variant matches the filters</exception>
+    public List<MatrixVariant> GetVariants(IReadOnlyList<string> filters);
+
+    /// <summary>
+    /// Writes the variant's identity, display names, architecture and protocols into the manifest, and copies its
+    /// branding's assets over the manifest's
+    /// </summary>
+    /// <param name="inputFolder">Package layout for variants whose architecture doesn't name its own</param>
+    /// <returns>The layout to pack; disposing it restores the manifest and assets</returns>
+    public Task<MatrixVariantLayout> PrepareAsync(MatrixVariant variant, DirectoryInfo inputFolder, FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,35 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Reads and updates the project's .winapp/state.json
+/// </summary>
+internal interface IProjectStateService
+{
+    public FileInfo StatePath { get; }
+
+    public Task<ProjectState> LoadAsync(TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Loads the state, applies <paramref name="update"/> and saves it. The state is a record of what happened,
+    /// so failing to save it only logs a debug message.
+    /// </summary>
+    public Task UpdateAsync(Action<ProjectState> update, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Describes the build for the provenance stamp: git commit, branch and root of the current directory, build
+    /// profile and CLI version
+    /// </summary>
+    public Task<BuildProvenance> GetProvenanceAsync(string? profile, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Writes <paramref name="provenance"/> as <see cref="BuildProvenance.FileName"/> into a package folder, after
+    /// recording the folder relative to the source root
+    /// </summary>
+    public Task<FileInfo> WriteProvenanceAsync(BuildProvenance provenance, DirectoryInfo packageFolder, CancellationToken cancellationToken = default);
+}
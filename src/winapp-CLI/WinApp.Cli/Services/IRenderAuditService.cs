@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks why a packaged WebView2, WinUI or WPF app may render in software, for rendering problems that get blamed
+/// on packaging
+/// </summary>
+internal interface IRenderAuditService
+{
+    /// <summary>
+    /// Reads the display adapters and their drivers, the Windows version and session, and the settings that turn off
+    /// GPU rendering for the renderers the package folder of the manifest uses, and reports what makes the app fall
+    /// back to software rendering, with what to do about it
+    /// </summary>
+    public Task<RenderReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Restarts an installed package the way Windows does after an update or a crash, for apps that hand their state to
+/// the restart with the winapp-runtime crate
+/// </summary>
+internal interface IRestartService
+{
+    /// <summary>
+    /// Reads the restarts the package's processes asked for and haven't restored yet, newest first
+    /// </summary>
+    public IReadOnlyList<RestartRequest> FindRequests(InstalledPackage package);
+
+    /// <summary>
+    /// Stops the package's processes and starts the app again with the token of the restart a running process asked
+    /// for <paramref name="reason"/>, so that it restores its state as after a real update or crash
+    /// </summary>
+    /// <exception cref="InvalidOperationException">No running process of the package asked for such a restart</exception>
+    public Task<RestartSimulation> SimulateAsync(InstalledPackage package, RestartReason reason, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Reads the rule severity overrides and suppressions that apply to a manifest
+/// </summary>
+internal interface IRuleSetService
+{
+    /// <summary>
+    /// Combines the 'rules:' of the policy setting, the 'rules:' and 'suppressions:' of winapp.yaml and the
+    /// winapp-ignore comments of the manifest: &lt;!-- winapp-ignore a11y/contrast --&gt; before an element suppresses
+    /// the rule on it and its children, and &lt;!-- winapp-ignore-file scales/dpi --&gt; anywhere suppresses it in the
+    /// whole manifest. Without rule ids, a comment suppresses every rule.
+    /// </summary>
+    /// <exception cref="InvalidOperationException">winapp.yaml or the policy sets a rule to a level other than off, info, warning or error</exception>
+    public RuleSet Load(FileInfo manifestPath, TaskContext taskContext);
+}
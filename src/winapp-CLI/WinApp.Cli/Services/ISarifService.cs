@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Writes findings as SARIF 2.1.0, which GitHub code scanning and Azure DevOps show next to the lines they are about
+/// </summary>
+internal interface ISarifService
+{
+    /// <summary>
+    /// Writes a SARIF log with a result for each finding. Results point at the manifest element a finding is about,
+    /// at the image file for findings about one variant, and at the splash: section of winapp.yaml for splash
+    /// findings it configures. Paths are relative to the current directory, which CI runs from the repository root.
+    /// </summary>
+    public Task WriteAsync(FileInfo sarifPath, FileInfo manifestPath, IReadOnlyList<SarifFinding> findings, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
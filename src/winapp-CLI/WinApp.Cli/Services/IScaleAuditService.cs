@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks the scale and target size variants of a manifest's image assets at the display scales Windows runs at
+/// </summary>
+internal interface IScaleAuditService
+{
+    /// <summary>
+    /// Loads every scale and target size variant of the manifest's image assets, checks that each has the size its
+    /// qualifier promises and doesn't look upscaled, and works out which variant Windows picks at each display scale
+    /// from 100% to 450%, and for the target sizes of the taskbar, title bar and Alt+Tab.
+    /// </summary>
+    public Task<ScaleReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,32 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Resolves settings such as the timestamp server through layers: built-in defaults, the machine configuration
+/// (%ProgramData%\winapp\config.yaml), the user configuration (%AppData%\winapp\config.yaml), the 'settings:' of
+/// winapp.yaml, environment variables and command line options, each overriding the ones before
+/// </summary>
+internal interface ISettingsService
+{
+    FileInfo MachineConfigPath { get; set; }
+
+    FileInfo UserConfigPath { get; set; }
+
+    /// <summary>
+    /// Effective value of every known setting
+    /// </summary>
+    public IReadOnlyList<SettingValue> GetAll();
+
+    /// <param name="commandLineValue">Value of the command's option, which overrides every other layer</param>
+    /// <param name="option">Name of the option, for the origin</param>
+    public SettingValue Get(string key, string? commandLineValue = null, string? option = null);
+
+    /// <summary>
+    /// Keys the configuration files set that no setting has, with the file that sets them
+    /// </summary>
+    public IReadOnlyList<(string Key, FileInfo File)> GetUnknownKeys();
+}
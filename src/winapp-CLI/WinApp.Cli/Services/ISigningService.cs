@@ -0,0 +1,21 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Signs packages with a PFX file, Azure Trusted Signing or Azure Key Vault, as 'sign:' in winapp.yaml configures
+/// </summary>
+internal interface ISigningService
+{
+    /// <summary>
+    /// Signs a file, or the packages next to an .appinstaller file that it installs. Packages are only signed
+    /// with a certificate whose subject is their Identity Publisher, as Windows refuses to install them otherwise.
+    /// Without a certificate, a development certificate for the package's publisher is generated.
+    /// </summary>
+    /// <returns>The signed files</returns>
+    public Task<IReadOnlyList<FileInfo>> SignAsync(FileInfo file, SigningRequest request, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks the splash screen of a manifest, and the splash declared in winapp.yaml, for mismatches that make the
+/// first paint of an app jarring
+/// </summary>
+internal interface ISplashScreenService
+{
+    /// <summary>
+    /// Checks the splash screen image, its scales and size, the background color and the edges of the image
+    /// against it. <paramref name="splash"/> is applied to the manifest first, as 'pack' would.
+    /// </summary>
+    public Task<SplashScreenReport> AnalyzeAsync(FileInfo manifestPath, SplashConfig? splash, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
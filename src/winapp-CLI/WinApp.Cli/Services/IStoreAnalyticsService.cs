@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Exports Microsoft Store analytics from Partner Center
+/// </summary>
+internal interface IStoreAnalyticsService
+{
+    /// <summary>
+    /// Downloads every row of a report and writes it to a file
+    /// </summary>
+    /// <returns>The number of rows written</returns>
+    public Task<int> ExportAsync(StoreStatsQuery query, StoreExportFormat format, FileInfo outputPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Associates a project with a Store app, as Visual Studio's "Associate App with the Store" does
+/// </summary>
+internal interface IStoreAssociationService
+{
+    /// <summary>
+    /// Writes the identity Name, Publisher and reserved display name the Store assigned the app into the manifest,
+    /// and the app's Store ID and package family name under 'store:' in winapp.yaml
+    /// </summary>
+    /// <param name="applicationId">Store ID of the app; defaults to the one in winapp.yaml</param>
+    /// <param name="name">Reserved name to find the app by, when there is no Store ID</param>
+    /// <exception cref="InvalidOperationException">No app or several apps match</exception>
+    public Task<StoreAssociation> AssociateAsync(FileInfo manifestPath, string? applicationId, string? name, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
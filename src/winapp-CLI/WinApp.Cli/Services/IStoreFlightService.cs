@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Manages package flights of Store apps through Partner Center, and the member lists of their flight groups
+/// </summary>
+internal interface IStoreFlightService
+{
+    public Task<List<StoreFlight>> ListAsync(string applicationId, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Finds a flight by ID or friendly name
+    /// </summary>
+    public Task<StoreFlight> ResolveAsync(string applicationId, string flightIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    public Task<StoreFlight> CreateAsync(string applicationId, string friendlyName, IReadOnlyList<string> groupIds, string? rankHigherThan, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <returns>The deleted flight</returns>
+    public Task<StoreFlight> DeleteAsync(string applicationId, string flightIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Merges members from CSV files and Azure AD groups into a file to upload to a flight group in Partner Center
+    /// </summary>
+    /// <returns>The number of distinct members written</returns>
+    public Task<int> ExportGroupMembersAsync(IReadOnlyList<FileInfo> csvFiles, IReadOnlyList<string> aadGroupIds, FileInfo outputPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Reserves and releases app names in Partner Center, the first step of publishing a new app to the Store
+/// </summary>
+internal interface IStoreNameService
+{
+    /// <summary>
+    /// Lists the apps of the Partner Center account with their reserved names
+    /// </summary>
+    public Task<List<StoreApplication>> ListAsync(TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Reserves a name for a new app, or returns the account's app that has it already
+    /// </summary>
+    /// <returns>The app, and whether the name was reserved by this call</returns>
+    /// <exception cref="InvalidOperationException">The name is taken, or the account can't reserve names through the API</exception>
+    public Task<(StoreApplication Application, bool Reserved)> ReserveAsync(string name, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Releases the name of an app that was never published, found by Store ID or reserved name
+    /// </summary>
+    /// <returns>The released app</returns>
+    public Task<StoreApplication> ReleaseAsync(string applicationIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Lists Microsoft Store reviews and responds to them through Partner Center
+/// </summary>
+internal interface IStoreReviewService
+{
+    /// <summary>
+    /// Reads the reviews matching a query, newest first
+    /// </summary>
+    public Task<List<JsonObject>> ListAsync(StoreReviewQuery query, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Posts developer responses to reviews
+    /// </summary>
+    /// <param name="supportEmail">Address shown with the responses; optional</param>
+    /// <param name="isPublic">Whether the responses are shown in the Store, or only emailed to the reviewers</param>
+    public Task<List<StoreReviewResponseResult>> RespondAsync(string applicationId, IReadOnlyList<(string ReviewId, string Text)> responses, string? supportEmail, bool isPublic, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Manages gradual package rollouts of Store submissions through Partner Center
+/// </summary>
+internal interface IStoreRolloutService
+{
+    /// <summary>
+    /// Reads the status and rollout of a submission
+    /// </summary>
+    /// <param name="submissionId">Submission to read; defaults to the app's last published submission</param>
+    /// <param name="wait">Poll until the Store has finished processing the submission</param>
+    public Task<StoreRolloutStatus> GetStatusAsync(string applicationId, string? submissionId, bool wait, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    public Task<StoreRolloutStatus> SetPercentageAsync(string applicationId, string? submissionId, double percentage, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    public Task<StoreRolloutStatus> HaltAsync(string applicationId, string? submissionId, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    public Task<StoreRolloutStatus> FinalizeAsync(string applicationId, string? submissionId, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
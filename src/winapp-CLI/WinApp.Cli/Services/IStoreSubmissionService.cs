@@ -0,0 +1,22 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Uploads packages to the Microsoft Store through the Partner Center submission API
+/// </summary>
+internal interface IStoreSubmissionService
+{
+    /// <summary>
+    /// Creates a submission with the given packages, uploads them and commits it. Progress is saved in the
+    /// global winapp directory, so running the same command again after a failure resumes the submission
+    /// and the upload instead of starting over.
+    /// </summary>
+    /// <param name="applicationId">Store ID of the app, such as 9NBLGGH4R315</param>
+    /// <param name="packages">.msix, .msixbundle, .msixupload (or .appx equivalents) files to submit</param>
+    public Task<StoreSubmissionResult> SubmitAsync(string applicationId, IReadOnlyList<FileInfo> packages, StoreSubmitOptions options, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
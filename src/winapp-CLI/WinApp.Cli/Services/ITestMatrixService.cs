@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Installs the package on the Windows versions of the test matrix in winapp.yaml, each in a new virtual machine, and
+/// runs the smoke test there
+/// </summary>
+internal interface ITestMatrixService
+{
+    /// <summary>
+    /// Selects the targets of the test matrix
+    /// </summary>
+    /// <param name="names">Target names to keep; empty keeps every target</param>
+    /// <exception cref="InvalidOperationException">winapp.yaml declares no test targets, a name matches no target, or a
+    /// selected target is incomplete</exception>
+    public List<TestTargetConfig> GetTargets(IReadOnlyList<string> names);
+
+    /// <summary>
+    /// Tests the package on each selected target, deleting the machines afterwards unless asked to keep them
+    /// </summary>
+    /// <returns>One result per target, in the order of winapp.yaml</returns>
+    public Task<IReadOnlyList<TestTargetResult>> RunAsync(TestMatrixOptions options, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
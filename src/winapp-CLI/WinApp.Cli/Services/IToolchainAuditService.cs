@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal interface IToolchainAuditService
+{
+    /// <summary>
+    /// Lists each operation the CLI performs and whether it runs natively or shells out to an external tool
+    /// </summary>
+    /// <param name="engine">Packaging engine the audit should assume for pack and sign</param>
+    public IReadOnlyList<ToolchainAuditEntry> GetAuditEntries(PackagingEngine engine);
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Runs every manifest check and compares the findings with a baseline, so a project can adopt the checks and
+/// only fail on findings it adds
+/// </summary>
+internal interface IValidationService
+{
+    /// <summary>
+    /// winapp-baseline.json next to winapp.yaml
+    /// </summary>
+    public FileInfo DefaultBaselinePath { get; }
+
+    /// <summary>
+    /// Runs the schema, logo and entry point checks and the schema version check of 'explain manifest', and the
+    /// checks of 'analyze a11y', 'analyze splash' and 'analyze scales', with the rule overrides and suppressions of
+    /// winapp.yaml and the manifest applied
+    /// </summary>
+    /// <param name="baselinePath">Baseline the finding's file paths are made relative to</param>
+    /// <param name="allowScriptAnalyzers">Whether to run the .ps1, .js and executable analyzers of winapp.yaml as well as the .wasm ones</param>
+    public Task<IReadOnlyList<ValidationFinding>> ValidateAsync(FileInfo manifestPath, FileInfo baselinePath, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Splits findings by whether the baseline records them; without a baseline, every finding is new
+    /// </summary>
+    /// <exception cref="InvalidOperationException">The baseline isn't a baseline winapp can read</exception>
+    public Task<ValidationResult> CompareAsync(FileInfo baselinePath, FileInfo manifestPath, IReadOnlyList<ValidationFinding> findings, CancellationToken cancellationToken = default);
+
+    /// <summary>
+    /// Records the findings of a manifest in the baseline, replacing what it recorded for that manifest and
+    /// keeping other manifests' findings
+    /// </summary>
+    public Task WriteBaselineAsync(FileInfo baselinePath, FileInfo manifestPath, IReadOnlyList<ValidationFinding> findings, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Writes a Visual Studio Windows Application Packaging Project equivalent to winapp.yaml and the manifest
+/// </summary>
+internal interface IWapprojExportService
+{
+    /// <summary>
+    /// Writes {name}.Package.wapproj, Package.appxmanifest and Images to <paramref name="outputDirectory"/>, by default
+    /// a {name}.Package folder next to winapp.yaml. The manifest gets the environment variables, shortcuts and splash
+    /// screen of winapp.yaml, as 'winapp package' applies them, and the tokens Visual Studio fills in from the entry
+    /// point project, the first project of winapp.yaml.
+    /// </summary>
+    /// <param name="force">Overwrite an existing .wapproj</param>
+    /// <exception cref="InvalidOperationException">The .wapproj exists without <paramref name="force"/>, or the manifest is invalid</exception>
+    public Task<WapprojExport> ExportAsync(FileInfo manifestPath, DirectoryInfo? outputDirectory, bool force, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Moves a Visual Studio Windows Application Packaging Project to winapp
+/// </summary>
+internal interface IWapprojMigrationService
+{
+    /// <summary>
+    /// Reads the .wapproj and its Package.appxmanifest and writes appxmanifest.xml, Assets and winapp.yaml to the
+    /// directory of winapp.yaml. The manifest gets the entry point's executable in place of the tokens Visual Studio
+    /// fills in, and the image paths of Assets; winapp.yaml gets the package references, the entry point project and
+    /// the splash screen, environment variables and shortcuts of the manifest, keeping what it declares already.
+    /// </summary>
+    /// <param name="force">Overwrite an existing appxmanifest.xml</param>
+    /// <exception cref="InvalidOperationException">The project has no manifest, or appxmanifest.xml exists without <paramref name="force"/></exception>
+    public Task<MigrationResult> MigrateAsync(FileInfo wapprojPath, bool force, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
@@ -0,0 +1,20 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+/// <summary>
+/// Checks where a packaged WebView2 or Tauri app keeps its WebView2 user data folder, for the problems that only show
+/// once the app is packaged
+/// </summary>
+internal interface IWebViewAuditService
+{
+    /// <summary>
+    /// Checks the package folder of the manifest for WebView2 profiles that would be packed, the environment for a
+    /// profile folder every app shares, and the project's installed packages for one profile per identity
+    /// </summary>
+    public Task<WebViewReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default);
+}
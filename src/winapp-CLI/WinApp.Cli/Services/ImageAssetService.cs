@@ -4,6 +4,8 @@
 using System.Drawing;
 using System.Drawing.Drawing2D;
 using System.Drawing.Imaging;
+using System.Drawing.Text;
+using System.Runtime.InteropServices;
 using WinApp.Cli.ConsoleTasks;
 using WinApp.Cli.Helpers;
 
@@ -34,6 +36,11 @@ internal class ImageAssetService : IImageAssetService
     // Target size variants for square assets (for taskbar, Start menu, etc.)
     private static readonly int[] TargetSizes = [16, 24, 32, 48, 256];
 
+    private const string DefaultBadgeColor = "#D83B01";
+
+    // Icons smaller than this get the ribbon without text, since it would not be readable
+    private const int MinBadgeTextSize = 32;
+
     public async Task GenerateAssetsAsync(FileInfo sourceImagePath, DirectoryInfo outputDirectory, TaskContext taskContext, CancellationToken cancellationToken = default)
     {
         if (!sourceImagePath.Exists)
@@ -214,6 +221,133 @@ internal class ImageAssetService : IImageAssetService
         }
     }
 
+    public async Task<IReadOnlyDictionary<string, byte[]>> ApplyBadgeAsync(
+        DirectoryInfo manifestDirectory,
+        IReadOnlyList<ManifestAssetReference> assetReferences,
+        AssetBadge badge,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default)
+    {
+        if (string.IsNullOrWhiteSpace(badge.Text))
+        {
+            throw new ArgumentException("The badge text is empty", nameof(badge));
+        }
+
+        var color = AccessibilityAuditService.ParseColor(badge.Color ?? DefaultBadgeColor)
+            ?? throw new ArgumentException($"'{badge.Color}' is not a color; use #RRGGBB or a color name", nameof(badge));
+        color = Color.FromArgb(255, color);
+
+        var originals = new Dictionary<string, byte[]>(StringComparer.OrdinalIgnoreCase);
+        foreach (var assetRef in assetReferences)
+        {
+            // BadgeLogo and LockScreenLogo are the only 24x24 assets
+            if (assetRef.BaseWidth == 24 && assetRef.BaseHeight == 24)
+            {
+                continue;
+            }
+
+            foreach (var variant in FindBadgeableVariants(manifestDirectory, assetRef.RelativePath))
+            {
+                if (originals.ContainsKey(variant.FullName))
+                {
+                    continue;
+                }
+
+                var original = await File.ReadAllBytesAsync(variant.FullName, cancellationToken);
+                try
+                {
+                    await DrawBadgeAsync(original, variant.FullName, badge.Text.Trim(), color, cancellationToken);
+                    originals[variant.FullName] = original;
+                    taskContext.AddDebugMessage($"  {UiSymbols.Check} Badged: {Path.GetRelativePath(manifestDirectory.FullName, variant.FullName)}");
+                }
+                catch (Exception ex) when (ex is ArgumentException or ExternalException)
+                {
+                    taskContext.AddDebugMessage($"  {UiSymbols.Warning} Failed to badge {variant.Name}: {ex.Message}");
+                }
+            }
+        }
+
+        taskContext.AddStatusMessage($"{UiSymbols.Info} Added the '{badge.Text.Trim()}' badge to {originals.Count} image assets");
+        return originals;
+    }
+
+    /// <summary>
+    /// Finds the file of an asset and its MRT variants, leaving out high contrast variants, which must keep to
+    /// the high contrast palette
+    /// </summary>
+    private static IEnumerable<FileInfo> FindBadgeableVariants(DirectoryInfo manifestDirectory, string assetPath)
+    {
+        var logicalFile = new FileInfo(Path.Combine(manifestDirectory.FullName, assetPath.Replace('\\', Path.DirectorySeparatorChar)));
+        if (logicalFile.Directory is not { Exists: true } directory)
+        {
+            return [];
+        }
+
+        var baseName = Path.GetFileNameWithoutExtension(logicalFile.Name);
+        return directory.EnumerateFiles(baseName + "*" + logicalFile.Extension)
+            .Where(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name)))
+            .Where(f => !Path.GetFileNameWithoutExtension(f.Name).Split('.', '_').Any(q =>
+                q.StartsWith("contrast-", StringComparison.OrdinalIgnoreCase) && !q.Equals("contrast-standard", StringComparison.OrdinalIgnoreCase)))
+            .OrderBy(f => f.Name, StringComparer.OrdinalIgnoreCase);
+    }
+
+    private static async Task DrawBadgeAsync(byte[] image, string outputPath, string text, Color color, CancellationToken cancellationToken)
+    {
+        await Task.Run(() =>
+        {
+            using var stream = new MemoryStream(image);
+            using var source = new Bitmap(stream);
+            using var target = new Bitmap(source.Width, source.Height, PixelFormat.Format32bppArgb);
+            using var graphics = Graphics.FromImage(target);
+
+            graphics.SmoothingMode = SmoothingMode.HighQuality;
+            graphics.PixelOffsetMode = PixelOffsetMode.HighQuality;
+            graphics.CompositingQuality = CompositingQuality.HighQuality;
+            graphics.TextRenderingHint = TextRenderingHint.AntiAliasGridFit;
+            graphics.Clear(Color.Transparent);
+            graphics.DrawImage(source, 0, 0, source.Width, source.Height);
+
+            // A ribbon across the bottom, a quarter of the shorter side high
+            var bandHeight = Math.Max(2, (int)Math.Round(Math.Min(source.Width, source.Height) * 0.25));
+            var band = new Rectangle(0, source.Height - bandHeight, source.Width, bandHeight);
+            using (var brush = new SolidBrush(color))
+            {
+                graphics.FillRectangle(brush, band);
+            }
+
+            if (Math.Min(source.Width, source.Height) >= MinBadgeTextSize)
+            {
+                // White text unless black reads better on the ribbon color
+                var textColor = AccessibilityAuditService.ContrastRatio(Color.White, color) >= AccessibilityAuditService.ContrastRatio(Color.Black, color)
+                    ? Color.White
+                    : Color.Black;
+                using var format = new StringFormat(StringFormatFlags.NoWrap)
+                {
+                    Alignment = StringAlignment.Center,
+                    LineAlignment = StringAlignment.Center,
+                };
+
+                // Shrink the text until it fits the ribbon with some padding
+                var fontSize = bandHeight * 0.75f;
+                var font = new Font(FontFamily.GenericSansSerif, fontSize, FontStyle.Bold, GraphicsUnit.Pixel);
+                while (fontSize > 4 && graphics.MeasureString(text, font, PointF.Empty, format).Width > source.Width * 0.9f)
+                {
+                    font.Dispose();
+                    fontSize -= 0.5f;
+                    font = new Font(FontFamily.GenericSansSerif, fontSize, FontStyle.Bold, GraphicsUnit.Pixel);
+                }
+
+                using (font)
+                using (var textBrush = new SolidBrush(textColor))
+                {
+                    graphics.DrawString(text, font, textBrush, band, format);
+                }
+            }
+
+            target.Save(outputPath, source.RawFormat.Equals(ImageFormat.Jpeg) ? ImageFormat.Jpeg : ImageFormat.Png);
+        }, cancellationToken);
+    }
+
     private static async Task GenerateAssetAsync(Bitmap sourceImage, string outputPath, int targetWidth, int targetHeight, CancellationToken cancellationToken)
     {
         await Task.Run(() =>
@@ -0,0 +1,278 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using System.Globalization;
+using System.IO.Enumeration;
+using System.Text.Json.Nodes;
+using System.Text.RegularExpressions;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal partial class InstalledPackageService(IPowerShellService powerShellService) : IInstalledPackageService
+{
+    // Lists the current user's packages and, as administrator, the packages staged for no user. The external
+    // location of sparse packages is only available through the WinRT PackageManager.
+    private const string ListPackagesScript = """
+        $ErrorActionPreference = 'Stop'
+        $admin = ([Security.Principal.WindowsPrincipal][Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)
+        $pm = [Windows.Management.Deployment.PackageManager,Windows.Management.Deployment,ContentType=WindowsRuntime]::new()
+        function Describe($package, $staged) {
+            $external = $null
+            try { $external = $pm.FindPackageForUser('', $package.PackageFullName).EffectiveExternalLocation.Path } catch { }
+            [pscustomobject]@{
+                fullName = $package.PackageFullName
+                name = $package.Name
+                publisher = $package.Publisher
+                version = [string]$package.Version
+                familyName = $package.PackageFamilyName
+                installLocation = $package.InstallLocation
+                installLocationExists = [bool]($package.InstallLocation -and (Test-Path -LiteralPath $package.InstallLocation))
+                externalLocation = $external
+                externalLocationExists = [bool]($external -and (Test-Path -LiteralPath $external))
+                developmentMode = [bool]$package.IsDevelopmentMode
+                isFramework = [bool]$package.IsFramework
+                signatureKind = [string]$package.SignatureKind
+                status = [string]$package.Status
+                staged = $staged
+            }
+        }
+        $packages = @(Get-AppxPackage | ForEach-Object { Describe $_ $false })
+        if ($admin) {
+            $packages += @(Get-AppxPackage -AllUsers | Where-Object { -not ($_.PackageUserInformation | Where-Object { $_.InstallState -eq 'Installed' }) } | ForEach-Object { Describe $_ $true })
+        }
+        [pscustomobject]@{ elevated = $admin; packages = $packages } | ConvertTo-Json -Depth 3 -Compress
+        """;
+
+    // Win32_Process has the parent and the command line of processes, which Get-Process doesn't in Windows PowerShell
+    private const string ListProcessesScript = """
+        $ErrorActionPreference = 'Stop'
+        $processes = @(Get-CimInstance Win32_Process | ForEach-Object {
+            [pscustomobject]@{
+                id = [int]$_.ProcessId
+                parentId = [int]$_.ParentProcessId
+                name = $_.Name
+                path = $_.ExecutablePath
+                commandLine = $_.CommandLine
+                startedAt = if ($_.CreationDate) { $_.CreationDate.ToUniversalTime().ToString('o') } else { $null }
+            }
+        })
+        ConvertTo-Json -InputObject $processes -Depth 2 -Compress
+        """;
+
+    public async Task<InstalledPackageList> GetPackagesAsync(TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync(ListPackagesScript, taskContext, cancellationToken: cancellationToken);
+        if (exitCode != 0)
+        {
+            throw new InvalidOperationException("Listing the installed packages failed; run with --verbose for details");
+        }
+
+        var json = JsonNode.Parse(output) ?? throw new InvalidOperationException("Get-AppxPackage returned no output");
+        var packages = (json["packages"]?.AsArray() ?? []).OfType<JsonNode>().Select(ParsePackage).ToList();
+        var stagedChecked = json["elevated"]?.GetValue<bool>() ?? false;
+        taskContext.AddDebugMessage($"{packages.Count} package(s) installed{(stagedChecked ? " or staged" : "")}");
+        return new InstalledPackageList(packages, stagedChecked);
+    }
+
+    public async Task UninstallAsync(string fullName, bool allUsers, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync($"Remove-AppxPackage -Package '{fullName}'{(allUsers ? " -AllUsers" : "")}", taskContext, cancellationToken: cancellationToken);
+        if (exitCode != 0)
+        {
+            throw new InvalidOperationException(string.IsNullOrWhiteSpace(output) ? $"Remove-AppxPackage exited with {exitCode}" : output.Trim());
+        }
+    }
+
+    public async Task<IReadOnlyList<PackageProcess>> GetProcessesAsync(InstalledPackage package, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync(ListProcessesScript, taskContext, cancellationToken: cancellationToken);
+        if (exitCode != 0)
+        {
+            throw new InvalidOperationException("Listing the running processes failed; run with --verbose for details");
+        }
+
+        var processes = (JsonNode.Parse(output)?.AsArray() ?? []).OfType<JsonNode>().Select(ParseProcess).ToList();
+        var packageProcesses = FindPackageProcesses(processes, package, Environment.ProcessId);
+        foreach (var process in packageProcesses)
+        {
+            taskContext.AddDebugMessage($"{process.Name} ({process.Id}, started by {process.ParentId}) runs in {package.Name}");
+        }
+        return packageProcesses;
+    }
+
+    public async Task StopProcessesAsync(IReadOnlyList<PackageProcess> processes, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        if (processes.Count == 0)
+        {
+            return;
+        }
+
+        await powerShellService.RunCommandAsync($"""
+            $processes = @(Get-Process -Id {string.Join(",", processes.Select(p => p.Id))} -ErrorAction SilentlyContinue)
+            $processes | Stop-Process -Force -ErrorAction SilentlyContinue
+            $processes | Wait-Process -Timeout 10 -ErrorAction SilentlyContinue
+            """, taskContext, cancellationToken: cancellationToken);
+    }
+
+    public void OpenFolder(string path)
+    {
+        if (!Directory.Exists(path))
+        {
+            throw new DirectoryNotFoundException($"{path} does not exist");
+        }
+
+        using var process = Process.Start(new ProcessStartInfo("explorer.exe") { ArgumentList = { path }, UseShellExecute = false });
+    }
+
+    /// <summary>
+    /// Filters packages the way Get-AppxPackage -Name/-Publisher does: wildcard patterns, case-insensitive. A
+    /// publisher pattern without '=' is matched against the publisher's common name. Framework and Windows
+    /// packages are left out unless <paramref name="all"/> is set or <paramref name="kind"/> asks for them.
+    /// </summary>
+    internal static List<InstalledPackage> Filter(IEnumerable<InstalledPackage> packages, string? name, string? publisher, PackageRegistrationKind? kind, bool all)
+    {
+        return [.. packages
+            .Where(p => name == null || FileSystemName.MatchesSimpleExpression(name, p.Name))
+            .Where(p => publisher == null || MatchesPublisher(publisher, p.Publisher))
+            .Where(p => kind == null ? all || (!p.IsFramework && p.Registration != PackageRegistrationKind.System) : p.Registration == kind)
+            .OrderBy(p => p.Name, StringComparer.OrdinalIgnoreCase)
+            .ThenBy(p => p.Staged)];
+    }
+
+    /// <summary>
+    /// Finds the registrations of a project's package: the package itself, the '.debug' identity of
+    /// create-debug-identity and the '.br&lt;hash&gt;' identities of 'pack --branch-identity'. Registrations in
+    /// development mode come first, as they are the ones being worked on.
+    /// </summary>
+    internal static List<InstalledPackage> FindProjectPackages(IEnumerable<InstalledPackage> packages, string identityName)
+    {
+        return [.. packages
+            .Where(p => !p.Staged && p.Name.StartsWith(identityName, StringComparison.OrdinalIgnoreCase))
+            .Where(p => p.Name.Length == identityName.Length || DebugOrBranchSuffixRegex().IsMatch(p.Name[identityName.Length..]))
+            .OrderByDescending(p => p.DevelopmentMode)
+            .ThenBy(p => p.Name, StringComparer.OrdinalIgnoreCase)];
+    }
+
+    /// <summary>
+    /// Folders of a package for <paramref name="location"/>, most likely first. Full-trust apps that write to
+    /// %LOCALAPPDATA% or %APPDATA% are redirected to LocalCache\Local and LocalCache\Roaming.
+    /// </summary>
+    internal static List<string> GetLocations(InstalledPackage package, PackageLocation location)
+    {
+        var appData = package.AppDataPath;
+        return location switch
+        {
+            PackageLocation.AppData => [appData],
+            PackageLocation.Install => [.. new[] { package.ExternalLocation, package.InstallLocation }.OfType<string>().Distinct(StringComparer.OrdinalIgnoreCase)],
+            _ => [.. LogsService.LogFolders.Concat(LogsService.RedirectedFolders).Select(folder => Path.Combine(appData, folder))],
+        };
+    }
+
+    /// <summary>
+    /// Whether a folder is inside %ProgramFiles%\WindowsApps, which only TrustedInstaller can write to and whose
+    /// package folders are read-only for users
+    /// </summary>
+    internal static bool IsInWindowsApps(string path)
+    {
+        var windowsApps = Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.ProgramFiles), "WindowsApps") + Path.DirectorySeparatorChar;
+        return path.StartsWith(windowsApps, StringComparison.OrdinalIgnoreCase);
+    }
+
+    private static bool MatchesPublisher(string pattern, string publisher)
+    {
+        if (pattern.Contains('='))
+        {
+            return FileSystemName.MatchesSimpleExpression(pattern, publisher);
+        }
+
+        var commonName = CommonNameRegex().Match(publisher);
+        return commonName.Success && FileSystemName.MatchesSimpleExpression(pattern, commonName.Groups[1].Value.Trim('"'));
+    }
+
+    /// <summary>
+    /// Finds the processes of the package and the processes they started, which run in the package's context unless
+    /// they broke away from it. Processes started from the package's folders are found by their path; WebView2 and
+    /// other helpers that run from elsewhere, by the package's ApplicationData folder in their command line.
+    /// </summary>
+    /// <param name="currentProcessId">winapp's own process, which is never part of the package, nor is anything it started</param>
+    internal static List<PackageProcess> FindPackageProcesses(IReadOnlyList<PackageProcess> processes, InstalledPackage package, int currentProcessId)
+    {
+        var folders = new[] { package.InstallLocation, package.ExternalLocation }
+            .Where(f => !string.IsNullOrEmpty(f))
+            .Select(f => f!.TrimEnd('\\') + '\\')
+            .ToList();
+        var appData = $@"\Packages\{package.FamilyName}\";
+        var children = processes.ToLookup(p => p.ParentId);
+
+        List<PackageProcess> WithDescendants(IEnumerable<PackageProcess> roots, HashSet<int> skipped)
+        {
+            var found = new List<PackageProcess>();
+            var queue = new Queue<PackageProcess>(roots);
+            while (queue.TryDequeue(out var process))
+            {
+                if (!skipped.Add(process.Id))
+                {
+                    continue;
+                }
+
+                found.Add(process);
+                foreach (var child in children[process.Id])
+                {
+                    // Ids are reused, so a process started before its "parent" was started by another process
+                    if (child.Id != process.Id && (child.StartedAt == null || process.StartedAt == null || child.StartedAt >= process.StartedAt))
+                    {
+                        queue.Enqueue(child);
+                    }
+                }
+            }
+            return found;
+        }
+
+        var visited = new HashSet<int>();
+        WithDescendants(processes.Where(p => p.Id == currentProcessId), visited);
+        return WithDescendants(processes.Where(p =>
+            (p.Path != null && folders.Any(f => p.Path.StartsWith(f, StringComparison.OrdinalIgnoreCase)))
+            || (p.CommandLine?.Contains(appData, StringComparison.OrdinalIgnoreCase) ?? false)), visited);
+    }
+
+    /// <summary>
+    /// Names the processes for a status message, such as "Notes.exe, msedgewebview2.exe (3)"
+    /// </summary>
+    internal static string DescribeProcesses(IEnumerable<PackageProcess> processes) =>
+        string.Join(", ", processes
+            .GroupBy(p => p.Name, StringComparer.OrdinalIgnoreCase)
+            .Select(g => g.Count() > 1 ? $"{g.Key} ({g.Count()})" : g.Key));
+
+    private static PackageProcess ParseProcess(JsonNode node) => new(
+        Id: node["id"]?.GetValue<int>() ?? 0,
+        ParentId: node["parentId"]?.GetValue<int>() ?? 0,
+        Name: node["name"]?.GetValue<string>() ?? "",
+        Path: node["path"]?.GetValue<string>(),
+        CommandLine: node["commandLine"]?.GetValue<string>(),
+        StartedAt: DateTimeOffset.TryParse(node["startedAt"]?.GetValue<string>(), CultureInfo.InvariantCulture, DateTimeStyles.AssumeUniversal, out var startedAt) ? startedAt : null);
+
+    private static InstalledPackage ParsePackage(JsonNode node) => new(
+        FullName: node["fullName"]?.GetValue<string>() ?? "",
+        Name: node["name"]?.GetValue<string>() ?? "",
+        Publisher: node["publisher"]?.GetValue<string>() ?? "",
+        Version: node["version"]?.GetValue<string>() ?? "",
+        FamilyName: node["familyName"]?.GetValue<string>() ?? "",
+        InstallLocation: node["installLocation"]?.GetValue<string>(),
+        InstallLocationExists: node["installLocationExists"]?.GetValue<bool>() ?? false,
+        ExternalLocation: node["externalLocation"]?.GetValue<string>(),
+        ExternalLocationExists: node["externalLocationExists"]?.GetValue<bool>() ?? false,
+        DevelopmentMode: node["developmentMode"]?.GetValue<bool>() ?? false,
+        IsFramework: node["isFramework"]?.GetValue<bool>() ?? false,
+        SignatureKind: node["signatureKind"]?.GetValue<string>() ?? "",
+        Status: node["status"]?.GetValue<string>() ?? "Ok",
+        Staged: node["staged"]?.GetValue<bool>() ?? false);
+
+    [GeneratedRegex(@"^\.(debug|br[0-9a-f]{8})$", RegexOptions.IgnoreCase)]
+    private static partial Regex DebugOrBranchSuffixRegex();
+
+    [GeneratedRegex(@"(?:^|,)\s*CN=(""[^""]*""|[^,]*)", RegexOptions.IgnoreCase)]
+    private static partial Regex CommonNameRegex();
+}
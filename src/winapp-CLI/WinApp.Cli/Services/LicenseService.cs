@@ -0,0 +1,80 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using Microsoft.Win32;
+using System.Security.AccessControl;
+using System.Security.Cryptography;
+using System.Security.Principal;
+using System.Text;
+using WinApp.Cli.Licensing;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class LicenseService : ILicenseService
+{
+    public async Task<FileInfo> GenerateKeyAsync(FileInfo privateKeyPath, string? password, CancellationToken cancellationToken = default)
+    {
+        using var key = ECDsa.Create(ECCurve.NamedCurves.nistP256);
+        var privateKey = string.IsNullOrEmpty(password)
+            ? key.ExportPkcs8PrivateKeyPem()
+            : key.ExportEncryptedPkcs8PrivateKeyPem(password, new PbeParameters(PbeEncryptionAlgorithm.Aes256Cbc, HashAlgorithmName.SHA256, 600_000));
+
+        privateKeyPath.Directory?.Create();
+        var publicKeyPath = new FileInfo(DeviceLicense.GetPublicKeyPath(privateKeyPath.FullName));
+        await WritePrivateKeyAsync(privateKeyPath, privateKey, cancellationToken);
+        await File.WriteAllTextAsync(publicKeyPath.FullName, key.ExportSubjectPublicKeyInfoPem(), cancellationToken);
+        return publicKeyPath;
+    }
+
+    /// <summary>
+    /// Writes the key to a file only the current user can read, rather than one that inherits the folder's access
+    /// </summary>
+    private static async Task WritePrivateKeyAsync(FileInfo path, string privateKey, CancellationToken cancellationToken)
+    {
+        using var identity = WindowsIdentity.GetCurrent();
+        var security = new FileSecurity();
+        security.SetAccessRuleProtection(isProtected: true, preserveInheritance: false);
+        security.AddAccessRule(new FileSystemAccessRule(identity.User!, FileSystemRights.FullControl, AccessControlType.Allow));
+
+        // The access of a file is only set when it is created, so a key being replaced is deleted first
+        path.Delete();
+        await using var stream = path.Create(FileMode.CreateNew, FileSystemRights.FullControl, FileShare.None, 4096, FileOptions.None, security);
+        await stream.WriteAsync(Encoding.UTF8.GetBytes(privateKey), cancellationToken);
+    }
+
+    public async Task IssueAsync(LicenseClaims claims, FileInfo privateKeyPath, string? password, FileInfo outputPath, CancellationToken cancellationToken = default)
+    {
+        var pem = await File.ReadAllTextAsync(privateKeyPath.FullName, cancellationToken);
+        using var key = ECDsa.Create();
+        if (string.IsNullOrEmpty(password))
+        {
+            key.ImportFromPem(pem);
+        }
+        else
+        {
+            key.ImportFromEncryptedPem(pem, password);
+        }
+
+        outputPath.Directory?.Create();
+        await File.WriteAllTextAsync(outputPath.FullName, DeviceLicense.Create(claims, key), cancellationToken);
+    }
+
+    public async Task<LicenseCheck> VerifyAsync(FileInfo licensePath, FileInfo publicKeyPath, string? deviceId, string? packageFamilyName, CancellationToken cancellationToken = default)
+    {
+        using var publicKey = ECDsa.Create();
+        publicKey.ImportFromPem(await File.ReadAllTextAsync(publicKeyPath.FullName, cancellationToken));
+
+        var license = await File.ReadAllTextAsync(licensePath.FullName, cancellationToken);
+        return DeviceLicense.Verify(license, publicKey, deviceId ?? GetDeviceId(), packageFamilyName, DateTimeOffset.UtcNow);
+    }
+
+    public string GetDeviceId()
+    {
+        // Read from the 64-bit view, since 32-bit processes would otherwise get a redirected key without the value
+        using var baseKey = RegistryKey.OpenBaseKey(RegistryHive.LocalMachine, RegistryView.Registry64);
+        using var cryptography = baseKey.OpenSubKey(@"SOFTWARE\Microsoft\Cryptography");
+        var machineGuid = cryptography?.GetValue("MachineGuid") as string
+            ?? throw new InvalidOperationException(@"Could not read MachineGuid from HKLM\SOFTWARE\Microsoft\Cryptography");
+        return DeviceLicense.GetDeviceId(machineGuid);
+    }
+}
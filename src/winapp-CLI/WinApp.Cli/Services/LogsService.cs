@@ -0,0 +1,191 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using System.Text;
+using System.Text.RegularExpressions;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal partial class LogsService : ILogsService
+{
+    /// <summary>
+    /// Log folders of the ApplicationData folder. LocalState\Logs is ApplicationData.Current.LocalFolder\Logs, where
+    /// the winapp-runtime crate writes <c>&lt;name&gt;.log</c> and rotates it to <c>&lt;name&gt;.1.log</c>
+    /// </summary>
+    internal static readonly string[] LogFolders = [Path.Combine("LocalState", "Logs"), Path.Combine("LocalCache", "Logs"), Path.Combine("TempState", "Logs")];
+
+    /// <summary>
+    /// Where the writes of full-trust apps to %LOCALAPPDATA% and %APPDATA% go, e.g. Electron's logs\main.log
+    /// </summary>
+    internal static readonly string[] RedirectedFolders = [Path.Combine("LocalCache", "Local"), Path.Combine("LocalCache", "Roaming")];
+
+    // The end of a log read for its last lines, so tailing a large log doesn't read all of it
+    private const int TailWindow = 256 * 1024;
+
+    public TimeSpan PollInterval { get; set; } = TimeSpan.FromMilliseconds(250);
+
+    public IReadOnlyList<FileInfo> FindLogFiles(InstalledPackage package) => FindLogFiles(new DirectoryInfo(package.AppDataPath));
+
+    internal static List<FileInfo> FindLogFiles(DirectoryInfo appData)
+    {
+        var files = new List<FileInfo>();
+        foreach (var folder in LogFolders.Concat(RedirectedFolders))
+        {
+            var directory = new DirectoryInfo(Path.Combine(appData.FullName, folder));
+            if (directory.Exists)
+            {
+                files.AddRange(directory.EnumerateFiles(LogFolders.Contains(folder) ? "*" : "*.log", SearchOption.AllDirectories));
+            }
+        }
+        return files;
+    }
+
+    /// <summary>
+    /// Whether a log file is written to rather than an older, rotated one: notes.log but not notes.1.log
+    /// </summary>
+    internal static bool IsCurrentLog(FileInfo file) =>
+        file.Extension.Equals(".log", StringComparison.OrdinalIgnoreCase) && !RotatedLogRegex().IsMatch(file.Name);
+
+    public Task TailAsync(InstalledPackage package, int lines, bool follow, Action<LogLine> onLine, CancellationToken cancellationToken = default) =>
+        TailAsync(new DirectoryInfo(package.AppDataPath), lines, follow, onLine, cancellationToken);
+
+    internal async Task TailAsync(DirectoryInfo appData, int lines, bool follow, Action<LogLine> onLine, CancellationToken cancellationToken)
+    {
+        var followed = new Dictionary<string, TailedFile>(StringComparer.OrdinalIgnoreCase);
+        foreach (var file in FindLogFiles(appData).Where(IsCurrentLog).OrderBy(f => f.LastWriteTimeUtc))
+        {
+            var tailed = new TailedFile(file) { Position = Math.Max(0, file.Length - TailWindow) };
+            var skipFirst = tailed.Position > 0;
+            followed[file.FullName] = tailed;
+            foreach (var line in tailed.ReadNewLines().Skip(skipFirst ? 1 : 0).TakeLast(lines))
+            {
+                onLine(new LogLine(file, line));
+            }
+        }
+
+        while (follow)
+        {
+            await Task.Delay(PollInterval, cancellationToken);
+            foreach (var file in FindLogFiles(appData).Where(IsCurrentLog))
+            {
+                // Logs created after the tail started are read from their first line
+                followed.TryAdd(file.FullName, new TailedFile(file));
+            }
+
+            foreach (var tailed in followed.Values)
+            {
+                foreach (var line in tailed.ReadNewLines())
+                {
+                    onLine(new LogLine(tailed.File, line));
+                }
+            }
+        }
+    }
+
+    public LogCollection Collect(InstalledPackage package, FileInfo output)
+    {
+        var appData = new DirectoryInfo(package.AppDataPath);
+        var files = FindLogFiles(appData);
+        if (files.Count == 0)
+        {
+            throw new InvalidOperationException($"{package.Name} has not written any logs yet (looked in {appData.FullName})");
+        }
+        return WriteCollection(appData, files, output);
+    }
+
+    internal static LogCollection WriteCollection(DirectoryInfo appData, IReadOnlyList<FileInfo> files, FileInfo output)
+    {
+        output.Directory!.Create();
+        var size = 0L;
+
+        // A failed collection leaves an earlier .zip with the same name in place
+        var temporaryPath = $"{output.FullName}.tmp";
+        using (var zip = ZipFile.Open(temporaryPath, ZipArchiveMode.Create))
+        {
+            foreach (var file in files)
+            {
+                var entry = zip.CreateEntry(Path.GetRelativePath(appData.FullName, file.FullName).Replace('\\', '/'), CompressionLevel.Optimal);
+                entry.LastWriteTime = file.LastWriteTime;
+
+                // The app may be writing to its log
+                using var source = OpenShared(file);
+                using var target = entry.Open();
+                source.CopyTo(target);
+                size += source.Length;
+            }
+        }
+        File.Move(temporaryPath, output.FullName, overwrite: true);
+        return new LogCollection(files.Count, size);
+    }
+
+    private static FileStream OpenShared(FileInfo file) =>
+        new(file.FullName, FileMode.Open, FileAccess.Read, FileShare.ReadWrite | FileShare.Delete);
+
+    /// <summary>
+    /// A log file read up to <see cref="Position"/>, with the start of a line that isn't finished yet
+    /// </summary>
+    private sealed class TailedFile(FileInfo file)
+    {
+        private readonly Decoder _decoder = Encoding.UTF8.GetDecoder();
+        private readonly StringBuilder _partialLine = new();
+
+        public FileInfo File { get; } = file;
+
+        public long Position { get; set; }
+
+        public List<string> ReadNewLines()
+        {
+            var lines = new List<string>();
+            File.Refresh();
+            if (!File.Exists)
+            {
+                return lines;
+            }
+
+            if (File.Length < Position)
+            {
+                // Rotated: the end of the log moved to notes.1.log, and notes.log starts again
+                var rotated = new FileInfo(Path.Combine(File.DirectoryName!, $"{Path.GetFileNameWithoutExtension(File.Name)}.1{File.Extension}"));
+                if (rotated.Exists && rotated.Length >= Position)
+                {
+                    Read(rotated, lines);
+                }
+                Position = 0;
+            }
+
+            Read(File, lines);
+            return lines;
+        }
+
+        private void Read(FileInfo file, List<string> lines)
+        {
+            using var stream = OpenShared(file);
+            stream.Seek(Position, SeekOrigin.Begin);
+            var bytes = new byte[64 * 1024];
+            var chars = new char[Encoding.UTF8.GetMaxCharCount(bytes.Length)];
+            int read;
+            while ((read = stream.Read(bytes, 0, bytes.Length)) > 0)
+            {
+                Position += read;
+                var count = _decoder.GetChars(bytes, 0, read, chars, 0);
+                foreach (var c in chars.AsSpan(0, count))
+                {
+                    if (c == '\n')
+                    {
+                        lines.Add(_partialLine.ToString().TrimEnd('\r'));
+                        _partialLine.Clear();
+                    }
+                    else
+                    {
+                        _partialLine.Append(c);
+                    }
+                }
+            }
+        }
+    }
+
+    [GeneratedRegex(@"\.\d+\.log$", RegexOptions.IgnoreCase)]
+    private static partial Regex RotatedLogRegex();
+}
@@ -0,0 +1,166 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class ManifestExplainService(
+    IAccessibilityAuditService accessibilityAuditService,
+    ISplashScreenService splashScreenService,
+    IScaleAuditService scaleAuditService,
+    IAnalyzerPluginService analyzerPluginService,
+    IRuleSetService ruleSetService) : IManifestExplainService
+{
+    public async Task<ManifestExplanation> ExplainAsync(FileInfo manifestPath, SplashConfig? splash, bool runChecks, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var manifestXml = await File.ReadAllTextAsync(manifestPath.FullName, Encoding.UTF8, cancellationToken);
+        var document = XDocument.Parse(manifestXml, LoadOptions.PreserveWhitespace | LoadOptions.SetLineInfo);
+        var root = document.Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var elements = root.DescendantsAndSelf().ToList();
+        var notes = elements.ToDictionary(e => e, e => new List<ManifestNote>());
+        var rules = ruleSetService.Load(manifestPath, taskContext);
+
+        void AddNote(XElement element, string rule, AccessibilitySeverity severity, string? subject, string message)
+        {
+            if (rules.Resolve(rule, severity, ((IXmlLineInfo)element).LineNumber) is AccessibilitySeverity resolved)
+            {
+                notes[element].Add(new ManifestNote(resolved, rule, AppxManifestLocator.PathOf(element), subject, message));
+            }
+        }
+
+        CheckSchemaVersions(root, elements, (element, rule, severity, message) => AddNote(element, rule, severity, null, message));
+        AppxManifestSchema.CheckStructure(root, (element, rule, severity, message) => AddNote(element, rule, severity, null, message));
+
+        if (runChecks)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Search} Running the analyze checks");
+
+            AppxManifestSchema.CheckLogos(root, manifestPath.Directory!, AddNote);
+            AppxInputCheck.Check(root, manifestPath.Directory!, AddNote);
+            AppxDpiCheck.Check(root, manifestPath.Directory!, AddNote);
+            AppxCodePageCheck.Check(root, manifestPath.Directory!, AddNote);
+
+            var accessibility = await accessibilityAuditService.AnalyzeAsync(manifestPath, taskContext, cancellationToken);
+            foreach (var issue in accessibility.Issues)
+            {
+                AddNote(AppxManifestLocator.Locate(root, $"a11y/{issue.Check}", issue.Subject), $"a11y/{issue.Check}", issue.Severity, issue.Subject, issue.Message);
+            }
+
+            var splashReport = await splashScreenService.AnalyzeAsync(manifestPath, splash, taskContext, cancellationToken);
+            foreach (var issue in splashReport.Issues)
+            {
+                AddNote(AppxManifestLocator.Locate(root, $"splash/{issue.Check}", issue.Application), $"splash/{issue.Check}", issue.Severity, issue.Application, issue.Message);
+            }
+
+            var scales = await scaleAuditService.AnalyzeAsync(manifestPath, taskContext, cancellationToken);
+            foreach (var issue in scales.Issues)
+            {
+                AddNote(AppxManifestLocator.Locate(root, $"scales/{issue.Check}", issue.Asset), $"scales/{issue.Check}", issue.Severity, issue.Asset, issue.Message);
+            }
+
+            foreach (var diagnostic in await analyzerPluginService.RunAsync(manifestPath, allowScriptAnalyzers, taskContext, cancellationToken))
+            {
+                var element = diagnostic.Element != null ? AppxManifestLocator.FindByPath(root, diagnostic.Element) : root;
+                AddNote(element, diagnostic.Rule, diagnostic.Severity, diagnostic.Subject, diagnostic.Message);
+            }
+        }
+
+        var explained = 0;
+        foreach (var element in elements)
+        {
+            var lines = new List<string>();
+            var category = element.Name.LocalName == "Capability" ? element.Attribute("Name")?.Value : element.Attribute("Category")?.Value;
+            if (AppxManifestGuide.Describe(element.Name.LocalName, category) is string description)
+            {
+                lines.Add($"{element.Name.LocalName}{(category != null ? $" {category}" : "")}: {description}");
+            }
+
+            var (needs, namespaceName) = GetRequiredVersion(element);
+            if (needs > AppxManifestGuide.Windows10)
+            {
+                lines.Add($"Needs {AppxManifestGuide.FormatRelease(needs)}{(IsIgnorable(root, namespaceName) ? ", and is ignored by older releases" : "")}");
+            }
+
+            lines.AddRange(notes[element].Select(n => $"{n.Severity.ToString().ToLowerInvariant()} ({n.Rule}): {n.Message}"));
+            if (lines.Count == 0)
+            {
+                continue;
+            }
+
+            explained++;
+            AddComment(element, lines);
+        }
+
+        var text = (document.Declaration != null ? document.Declaration + Environment.NewLine : "") + document.ToString(SaveOptions.DisableFormatting);
+        return new ManifestExplanation(text, explained, [.. elements.SelectMany(e => notes[e])]);
+    }
+
+    /// <summary>
+    /// Flags elements and attributes from schemas newer than the manifest's MinVersion that aren't ignorable, so the
+    /// package fails to install on the older releases it claims to support
+    /// </summary>
+    private static void CheckSchemaVersions(XElement root, List<XElement> elements, Action<XElement, string, AccessibilitySeverity, string> addNote)
+    {
+        var minVersion = AppxDigestAlgorithms.ReadMinTargetVersion(root.ToString());
+        if (minVersion == null)
+        {
+            addNote(root, "manifest/version", AccessibilitySeverity.Error, "No TargetDeviceFamily with a MinVersion, so Windows can't tell which releases the package installs on");
+            return;
+        }
+
+        foreach (var element in elements)
+        {
+            var (needs, namespaceName) = GetRequiredVersion(element);
+            if (needs > minVersion && !IsIgnorable(root, namespaceName))
+            {
+                addNote(element, "manifest/version", AccessibilitySeverity.Warning,
+                    $"MinVersion is {minVersion}, so releases before {AppxManifestGuide.FormatRelease(needs)} refuse the package. Raise MinVersion, or add the namespace prefix to IgnorableNamespaces so they skip it");
+            }
+        }
+    }
+
+    /// <summary>
+    /// The newest schema an element or one of its attributes comes from, and that schema's namespace
+    /// </summary>
+    private static (Version Needs, string NamespaceName) GetRequiredVersion(XElement element)
+    {
+        var needs = AppxManifestGuide.Windows10;
+        var namespaceName = element.Name.NamespaceName;
+        foreach (var name in element.Attributes().Where(a => !a.IsNamespaceDeclaration).Select(a => a.Name.NamespaceName).Prepend(element.Name.NamespaceName))
+        {
+            if (AppxManifestGuide.NamespaceMinVersions.TryGetValue(name, out var version) && version > needs)
+            {
+                needs = version;
+                namespaceName = name;
+            }
+        }
+        return (needs, namespaceName);
+    }
+
+    private static bool IsIgnorable(XElement root, string namespaceName)
+    {
+        var prefix = root.GetPrefixOfNamespace(namespaceName);
+        var ignorable = root.Attribute("IgnorableNamespaces")?.Value.Split(' ', StringSplitOptions.RemoveEmptyEntries) ?? [];
+        return prefix != null && ignorable.Contains(prefix);
+    }
+
+    /// <summary>
+    /// Inserts an XML comment before the element, at the element's indentation
+    /// </summary>
+    private static void AddComment(XElement element, List<string> lines)
+    {
+        var indent = element.PreviousNode is XText whitespace ? whitespace.Value[(whitespace.Value.LastIndexOf('\n') + 1)..] : "";
+
+        // Comments can't contain "--", which option names in the messages do
+        var body = lines.Select(l => l.Replace("--", "- -"));
+        var comment = new XComment($" {string.Join($"{Environment.NewLine}{indent}     ", body)}{Environment.NewLine}{indent}");
+        element.AddBeforeSelf(comment, new XText(Environment.NewLine + indent));
+    }
+}
@@ -284,6 +284,7 @@ internal partial class ManifestService(
     public async Task UpdateManifestAssetsAsync(
         FileInfo manifestPath,
         FileInfo imagePath,
+        AssetBadge? badge,
         TaskContext taskContext,
         CancellationToken cancellationToken = default)
     {
@@ -303,6 +304,10 @@ internal partial class ManifestService(
         {
             // Generate assets based on manifest references
             await imageAssetService.GenerateAssetsFromManifestAsync(imagePath, manifestDir, assetReferences, taskContext, cancellationToken);
+            if (badge != null)
+            {
+                await imageAssetService.ApplyBadgeAsync(manifestDir, assetReferences, badge, taskContext, cancellationToken);
+            }
         }
         else
         {
@@ -310,15 +315,30 @@ internal partial class ManifestService(
             taskContext.AddStatusMessage($"{UiSymbols.Warning} No asset references found in manifest, generating default assets");
             var assetsDir = manifestDir.CreateSubdirectory("Assets");
             await imageAssetService.GenerateAssetsAsync(imagePath, assetsDir, taskContext, cancellationToken);
+            if (badge != null)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} The badge is only drawn on assets the manifest references");
+            }
         }
     }
 
+    public async Task<IReadOnlyDictionary<string, byte[]>> ApplyBadgeAsync(
+        FileInfo manifestPath,
+        AssetBadge badge,
+        TaskContext taskContext,
+        CancellationToken cancellationToken = default)
+    {
+        var manifestDir = manifestPath.Directory ?? throw new InvalidOperationException("Could not determine manifest directory");
+        var assetReferences = ExtractAssetReferencesFromManifest(manifestPath, taskContext);
+        return await imageAssetService.ApplyBadgeAsync(manifestDir, assetReferences, badge, taskContext, cancellationToken);
+    }
+
     /// <summary>
     /// Extracts asset references from an AppxManifest.xml file.
     /// Parses the manifest to find Logo, Square150x150Logo, Square44x44Logo, Wide310x150Logo, 
     /// and other image asset attributes, then determines their expected dimensions.
     /// </summary>
-    private static List<ManifestAssetReference> ExtractAssetReferencesFromManifest(FileInfo manifestPath, TaskContext taskContext)
+    internal static List<ManifestAssetReference> ExtractAssetReferencesFromManifest(FileInfo manifestPath, TaskContext taskContext)
     {
         var assetReferences = new List<ManifestAssetReference>();
 
@@ -124,20 +124,20 @@ internal partial class ManifestTemplateService : IManifestTemplateService
         string? hostRuntimeDependencyPublisherName,
         string? hostRuntimeDependencyMinVersion)
     {
-        var applicationId = FixAsciiWindowsId(ToCamelCase(packageName));
-
-        var result = template
-            .Replace("{PackageName}", packageName)
-            .Replace("{ApplicationId}", applicationId)
-            .Replace("{PublisherName}", publisherName)
-            .Replace("Version=\"1.0.0.0\"", $"Version=\"{version}\"")
-            .Replace("{Executable}", entryPoint)
-            .Replace("{Description}", description)
-            .Replace("{HostId}", hostId)
-            .Replace("{HostParameters}", hostParameters)
-            .Replace("{HostRuntimeDependencyPackageName}", hostRuntimeDependencyPackageName)
-            .Replace("{HostRuntimeDependencyPublisherName}", hostRuntimeDependencyPublisherName)
-            .Replace("{HostRuntimeDependencyMinVersion}", hostRuntimeDependencyMinVersion);
+        var variables = new TemplateVariables()
+            .Set("packageName", packageName)
+            .Set("applicationId", FixAsciiWindowsId(ToCamelCase(packageName)))
+            .Set("publisherName", publisherName)
+            .Set("version", version)
+            .Set("executable", entryPoint)
+            .Set("description", description)
+            .Set("hostId", hostId)
+            .Set("hostParameters", hostParameters)
+            .Set("hostRuntimeDependencyPackageName", hostRuntimeDependencyPackageName)
+            .Set("hostRuntimeDependencyPublisherName", hostRuntimeDependencyPublisherName)
+            .Set("hostRuntimeDependencyMinVersion", hostRuntimeDependencyMinVersion);
+
+        var result = ValueTemplate.Render(template, variables, "manifest template");
 
         return result;
     }
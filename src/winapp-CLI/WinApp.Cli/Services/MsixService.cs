@@ -4,12 +4,15 @@
 using Microsoft.Extensions.Logging;
 using System.IO.Compression;
 using System.Security;
+using System.Security.Cryptography;
 using System.Text;
 using System.Text.RegularExpressions;
 using System.Xml;
+using WinApp.Cli.BuildCache;
 using WinApp.Cli.ConsoleTasks;
 using WinApp.Cli.Helpers;
 using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
 using WinApp.Cli.Tools;
 
 namespace WinApp.Cli.Services;
@@ -20,9 +23,14 @@ internal partial class MsixService(
     IBuildToolsService buildToolsService,
     IPowerShellService powerShellService,
     ICertificateService certificateService,
+    INativePackagingService nativePackagingService,
     IPackageCacheService packageCacheService,
     IWorkspaceSetupService workspaceSetupService,
     IDevModeService devModeService,
+    IBranchIdentityService branchIdentityService,
+    IProjectStateService projectStateService,
+    IManifestService manifestService,
+    IDeploymentEventService deploymentEventService,
     ILogger<MsixService> logger,
     ICurrentDirectoryProvider currentDirectoryProvider) : IMsixService
 {
@@ -87,8 +95,8 @@ internal partial class MsixService(
     [GeneratedRegex(@"^theme-(light|dark)$", RegexOptions.IgnoreCase | RegexOptions.Compiled | RegexOptions.CultureInvariant)]
     private static partial Regex ThemeQualifierRegex();
 
-    // contrast-standard, contrast-high
-    [GeneratedRegex(@"^contrast-(standard|high)$", RegexOptions.IgnoreCase | RegexOptions.Compiled | RegexOptions.CultureInvariant)]
+    // contrast-standard, contrast-high, contrast-black, contrast-white
+    [GeneratedRegex(@"^contrast-(standard|high|black|white)$", RegexOptions.IgnoreCase | RegexOptions.Compiled | RegexOptions.CultureInvariant)]
     private static partial Regex ContrastQualifierRegex();
 
     // dxfeaturelevel-9 / 10 / 11
@@ -339,6 +347,7 @@ internal partial class MsixService(
             await EmbedMsixIdentityToExeAsync(exePath, debugIdentity, taskContext, cancellationToken);
         }
 
+        DirectoryInfo? externalLocation = null;
         if (noInstall)
         {
             taskContext.AddDebugMessage("Skipping package installation as per --no-install option.");
@@ -347,7 +356,7 @@ internal partial class MsixService(
         {
             // Register the debug appxmanifest
             var entryPointDir = Path.GetDirectoryName(entryPointPath);
-            var externalLocation = new DirectoryInfo(string.IsNullOrEmpty(entryPointDir) ? currentDirectoryProvider.GetCurrentDirectory() : entryPointDir);
+            externalLocation = new DirectoryInfo(string.IsNullOrEmpty(entryPointDir) ? currentDirectoryProvider.GetCurrentDirectory() : entryPointDir);
 
             // Unregister any existing package first
             await UnregisterExistingPackageAsync(debugIdentity.PackageName, taskContext, cancellationToken);
@@ -356,9 +365,38 @@ internal partial class MsixService(
             await RegisterSparsePackageAsync(debugManifestPath, externalLocation, taskContext, cancellationToken);
         }
 
+        await projectStateService.UpdateAsync(state =>
+        {
+            state.RecordIdentity(new IdentityRecord
+            {
+                Kind = ProjectIdentityKind.Debug,
+                Name = debugIdentity.PackageName,
+                Publisher = debugIdentity.Publisher,
+                CreatedAt = DateTimeOffset.UtcNow,
+            });
+            if (externalLocation != null)
+            {
+                state.RecordDeployment(new DeploymentRecord
+                {
+                    PackageName = debugIdentity.PackageName,
+                    Publisher = debugIdentity.Publisher,
+                    Manifest = debugManifestPath.FullName,
+                    ExternalLocation = externalLocation.FullName,
+                    RegisteredAt = DateTimeOffset.UtcNow,
+                });
+            }
+        }, taskContext, cancellationToken);
+
         return new MsixIdentityResult(debugIdentity.PackageName, debugIdentity.Publisher, debugIdentity.ApplicationId);
     }
 
+    public async Task<MsixIdentityResult> EmbedDebugIdentityAsync(FileInfo exePath, FileInfo appxManifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var debugIdentity = CreateDebugIdentity(await ParseAppxManifestFromPathAsync(appxManifestPath, cancellationToken));
+        await EmbedMsixIdentityToExeAsync(exePath, debugIdentity, taskContext, cancellationToken);
+        return debugIdentity;
+    }
+
     private async Task EmbedMsixIdentityToExeAsync(FileInfo exePath, MsixIdentityResult identityInfo, TaskContext taskContext, CancellationToken cancellationToken)
     {
         // Create the MSIX element for the win32 manifest
@@ -483,6 +521,25 @@ internal partial class MsixService(
         }
     }
 
+    /// <summary>
+    /// Generates the Win32 application manifest of 'win32Manifest:' in winapp.yaml and embeds it into an executable
+    /// that has none
+    /// </summary>
+    private async Task EmbedWin32ManifestToExeAsync(FileInfo exePath, Win32ManifestConfig config, string appxManifest, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var tempManifestPath = new FileInfo(Path.Combine(exePath.DirectoryName!, "win32_temp.manifest"));
+        try
+        {
+            await Win32Manifest.WriteAsync(config, appxManifest, tempManifestPath, cancellationToken);
+            await EmbedManifestFileToExeAsync(exePath, tempManifestPath, taskContext, cancellationToken);
+            taskContext.AddStatusMessage($"{UiSymbols.Note} Embedded the Win32 manifest of winapp.yaml into {exePath.Name}, which the build left without one");
+        }
+        finally
+        {
+            TryDeleteFile(tempManifestPath);
+        }
+    }
+
     private async Task<bool> TryExtractManifestFromExeAsync(FileInfo exePath, FileInfo tempManifestPath, TaskContext taskContext, CancellationToken cancellationToken)
     {
         taskContext.AddDebugMessage("Extracting current manifest from executable...");
@@ -624,6 +681,29 @@ internal partial class MsixService(
         }
     }
 
+    /// <summary>
+    /// Variables for the {{placeholders}} of the manifest and the winapp.yaml values it receives; null when neither has
+    /// any, so packing doesn't run git for nothing
+    /// </summary>
+    private async Task<TemplateVariables?> GetTemplateVariablesAsync(string manifestContent, WinappConfig? config, BuildProvenance? provenance, MatrixVariant? variant, CancellationToken cancellationToken)
+    {
+        var hasPlaceholders = ValueTemplate.HasPlaceholders(manifestContent)
+            || config?.Environment.Any(e => ValueTemplate.HasPlaceholders(e.Value)) == true
+            || config?.Shortcuts.Any(s => ValueTemplate.HasPlaceholders(s.File) || ValueTemplate.HasPlaceholders(s.Arguments)) == true;
+        if (!hasPlaceholders)
+        {
+            return null;
+        }
+
+        var variables = new TemplateVariables()
+            .SetProvenance(provenance ?? await projectStateService.GetProvenanceAsync(null, cancellationToken));
+        if (variant != null)
+        {
+            variables.SetVariant(variant);
+        }
+        return variables;
+    }
+
     /// <summary>
     /// Creates an MSIX package from a prepared package directory
     /// </summary>
@@ -639,6 +719,11 @@ internal partial class MsixService(
     /// <param name="publisher">Publisher name for certificate generation (default: extracted from manifest)</param>
     /// <param name="manifestPath">Path to the manifest file (optional)</param>
     /// <param name="selfContained">Enable self-contained deployment</param>
+    /// <param name="engine">Engine used to pack and sign</param>
+    /// <param name="threads">Hash/compress workers for the native engine (0: one per logical processor)</param>
+    /// <param name="useBlockCache">Let the native engine reuse blocks compressed by earlier packs</param>
+    /// <param name="digest">Block map and signature hash algorithm</param>
+    /// <param name="variant">Build matrix variant, whose values {{placeholders}} can use</param>
     /// <param name="cancellationToken">Cancellation token</param>
     /// <returns>Result containing the MSIX path and signing status</returns>
     public async Task<CreateMsixPackageResult> CreateMsixPackageAsync(
@@ -655,6 +740,15 @@ internal partial class MsixService(
         string? publisher = null,
         FileInfo? manifestPath = null,
         bool selfContained = false,
+        PackagingEngine engine = PackagingEngine.Sdk,
+        int threads = 0,
+        bool useBlockCache = true,
+        DigestAlgorithm digest = DigestAlgorithm.Sha256,
+        RemoteBuildCacheOptions? remoteCache = null,
+        BranchIdentity? branchIdentity = null,
+        BuildProvenance? provenance = null,
+        AssetBadge? badge = null,
+        MatrixVariant? variant = null,
         CancellationToken cancellationToken = default)
     {
         // Validate input folder and manifest
@@ -707,9 +801,55 @@ internal partial class MsixService(
 
         var manifestContent = await File.ReadAllTextAsync(resolvedManifestPath.FullName, Encoding.UTF8, cancellationToken);
 
+        var config = configService.Exists() ? configService.Load() : null;
+        var templateVariables = await GetTemplateVariablesAsync(manifestContent, config, provenance, variant, cancellationToken);
+        if (templateVariables != null)
+        {
+            manifestContent = ValueTemplate.RenderManifest(manifestContent, templateVariables, resolvedManifestPath.Name);
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Rendered the {{{{placeholders}}}} of the manifest and winapp.yaml");
+        }
+
         // Update manifest content to ensure it's either referencing Windows App SDK or is self-contained
         manifestContent = await UpdateAppxManifestContentAsync(manifestContent, null, null, sparse: false, selfContained: selfContained, taskContext, cancellationToken);
+        if (config != null)
+        {
+            var warnings = new List<string>();
+            var packageEnvironment = config.GetPackageEnvironment();
+            var environment = templateVariables == null ? packageEnvironment : packageEnvironment
+                .Select(e => new EnvironmentVariableConfig { Name = e.Name, Value = ValueTemplate.Render(e.Value, templateVariables, $"winapp.yaml environment {e.Name}") })
+                .ToList();
+            var shortcuts = templateVariables == null ? config.Shortcuts : config.Shortcuts
+                .Select(s => new ShortcutConfig
+                {
+                    File = ValueTemplate.Render(s.File, templateVariables, "winapp.yaml shortcut file"),
+                    Application = s.Application,
+                    Arguments = s.Arguments != null ? ValueTemplate.Render(s.Arguments, templateVariables, $"winapp.yaml shortcut {s.File} arguments") : null,
+                    Icon = s.Icon,
+                })
+                .ToList();
+            manifestContent = AppxDesktopExtensions.Apply(manifestContent, environment, shortcuts, warnings);
+            manifestContent = AppxSplashScreen.Apply(manifestContent, config.Splash);
+            if (environment.Count > 0 || config.Shortcuts.Count > 0)
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Note} Added {environment.Count} environment variable(s) and {config.Shortcuts.Count} shortcut(s) from winapp.yaml");
+            }
+            foreach (var warning in warnings)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} {warning}");
+            }
+        }
+        if (branchIdentity != null)
+        {
+            manifestContent = branchIdentity.ApplyToManifest(manifestContent);
+            extractedPublisher = branchIdentity.Publisher;
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Using the identity of branch '{branchIdentity.Branch}' (suffix {branchIdentity.NameSuffix}, {branchIdentity.Publisher})");
+        }
         var updatedManifestPath = Path.Combine(inputFolder.FullName, "appxmanifest.xml");
+
+        // The manifest in the input folder keeps its placeholders for the next pack
+        var templatedManifest = templateVariables != null && string.Equals(Path.GetFullPath(updatedManifestPath), Path.GetFullPath(resolvedManifestPath.FullName), StringComparison.OrdinalIgnoreCase)
+            ? await File.ReadAllTextAsync(updatedManifestPath, Encoding.UTF8, cancellationToken)
+            : null;
         await File.WriteAllTextAsync(updatedManifestPath, manifestContent, Encoding.UTF8, cancellationToken);
 
         if (string.IsNullOrWhiteSpace(finalPackageName) || string.IsNullOrWhiteSpace(extractedPublisher))
@@ -774,14 +914,46 @@ internal partial class MsixService(
             await CopyAllAssetsAsync(resolvedManifestPath, inputFolder, taskContext, cancellationToken);
         }
 
+        // Builds that embed no manifest of their own get the one winapp.yaml describes, before the cache key is computed
+        if (config?.Win32Manifest is { } win32Manifest && executablePath?.Exists == true
+            && !SxsManifest.HasEmbedded(executablePath.FullName) && !File.Exists(executablePath.FullName + ".manifest"))
+        {
+            await EmbedWin32ManifestToExeAsync(executablePath, win32Manifest, manifestContent, taskContext, cancellationToken);
+        }
+
         taskContext.AddDebugMessage($"Creating MSIX package from: {inputFolder.FullName}");
         taskContext.AddDebugMessage($"Output: {outputMsixPath.FullName}");
 
+        // The stamp is part of the payload, so it is also part of the remote cache key
+        FileInfo? provenanceStamp = null;
+        if (provenance != null)
+        {
+            provenanceStamp = await projectStateService.WriteProvenanceAsync(provenance, inputFolder, cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Stamped the package with commit {provenance.GitSha ?? "(none)"}{(provenance.GitDirty ? " (uncommitted changes)" : "")}, profile {provenance.Profile ?? "(none)"}");
+        }
+
+        // The badged icons are packed in place of the originals, which are put back afterwards
+        IReadOnlyDictionary<string, byte[]> unbadgedAssets = new Dictionary<string, byte[]>();
+        if (badge != null)
+        {
+            unbadgedAssets = await manifestService.ApplyBadgeAsync(new FileInfo(updatedManifestPath), badge, taskContext, cancellationToken);
+        }
+
+        // The package is cached unsigned, so each agent still signs it with its own certificate
+        string? cacheKey = null;
+        var fromCache = false;
+        if (remoteCache != null)
+        {
+            cacheKey = await PackCacheKey.ComputeAsync(inputFolder, GetPackCacheConfiguration(finalPackageName, skipPri, selfContained, engine, digest), outputMsixPath, cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Remote cache key: {cacheKey}");
+            fromCache = await TryDownloadFromRemoteCacheAsync(remoteCache.Cache, cacheKey, outputMsixPath, taskContext, cancellationToken);
+        }
+
         List<FileInfo> tempFiles = [];
         try
         {
             // Generate PRI files if not skipped
-            if (!skipPri)
+            if (!skipPri && !fromCache)
             {
                 taskContext.AddDebugMessage("Generating PRI configuration and files...");
 
@@ -804,7 +976,7 @@ internal partial class MsixService(
             }
 
             // Handle self-contained deployment if requested
-            if (selfContained && executablePath != null)
+            if (selfContained && executablePath != null && !fromCache)
             {
                 taskContext.AddDebugMessage($"{UiSymbols.Package} Preparing self-contained Windows App SDK runtime...");
 
@@ -816,12 +988,26 @@ internal partial class MsixService(
                 await EmbedWindowsAppSDKManifestToExeAsync(executablePath, winAppSDKDeploymentDir, windowsAppSDKManifestPath, taskContext, cancellationToken);
             }
 
-            await CreateMsixPackageFromFolderAsync(inputFolder, outputMsixPath, taskContext, cancellationToken);
+            if (!fromCache)
+            {
+                await CreateMsixPackageFromFolderAsync(inputFolder, outputMsixPath, engine, threads, useBlockCache, digest, taskContext, cancellationToken);
+                if (remoteCache is { ReadOnly: false })
+                {
+                    await UploadToRemoteCacheAsync(remoteCache.Cache, cacheKey!, outputMsixPath, taskContext, cancellationToken);
+                }
+            }
+
+            // Branch builds always sign with the branch's own certificate
+            if (branchIdentity != null && certificatePath == null)
+            {
+                certificatePath = await branchIdentityService.EnsureCertificateAsync(branchIdentity, certificatePassword, taskContext, cancellationToken);
+                autoSign = true;
+            }
 
             // Handle certificate generation and signing
             if (autoSign)
             {
-                await SignMsixPackageAsync(outputFolder, certificatePassword, generateDevCert, installDevCert, finalPackageName, extractedPublisher, outputMsixPath, certificatePath, resolvedManifestPath, taskContext, cancellationToken);
+                await SignMsixPackageAsync(outputFolder, certificatePassword, generateDevCert, installDevCert, finalPackageName, extractedPublisher, outputMsixPath, certificatePath, branchIdentity != null || templateVariables != null ? new FileInfo(updatedManifestPath) : resolvedManifestPath, engine, taskContext, cancellationToken);
             }
         }
         catch (Exception ex)
@@ -830,6 +1016,35 @@ internal partial class MsixService(
         }
         finally
         {
+            if (provenanceStamp != null)
+            {
+                TryDeleteFile(provenanceStamp);
+            }
+
+            if (templatedManifest != null)
+            {
+                try
+                {
+                    File.WriteAllText(updatedManifestPath, templatedManifest, Encoding.UTF8);
+                }
+                catch (IOException ex)
+                {
+                    taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not restore the placeholders of {updatedManifestPath}: {ex.Message}");
+                }
+            }
+
+            foreach (var (path, original) in unbadgedAssets)
+            {
+                try
+                {
+                    File.WriteAllBytes(path, original);
+                }
+                catch (IOException ex)
+                {
+                    taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not restore {path} without the badge: {ex.Message}");
+                }
+            }
+
             // Clean up temporary PRI files
             if (!skipPri)
             {
@@ -857,7 +1072,98 @@ internal partial class MsixService(
             taskContext.AddDebugMessage("Package has been signed");
         }
 
-        return new CreateMsixPackageResult(outputMsixPath, autoSign);
+        await RecordPackAsync(inputFolder, resolvedManifestPath, outputMsixPath, finalPackageName, extractedPublisher, autoSign, fromCache, provenance, branchIdentity, certificatePath, taskContext, cancellationToken);
+
+        return new CreateMsixPackageResult(outputMsixPath, autoSign, fromCache);
+    }
+
+    private async Task RecordPackAsync(DirectoryInfo inputFolder, FileInfo manifestPath, FileInfo outputMsixPath, string packageName, string? publisher, bool signed, bool fromCache, BuildProvenance? provenance, BranchIdentity? branchIdentity, FileInfo? certificatePath, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        string sha256;
+        using (var stream = outputMsixPath.OpenRead())
+        {
+            sha256 = Convert.ToHexStringLower(await SHA256.HashDataAsync(stream, cancellationToken));
+        }
+
+        await projectStateService.UpdateAsync(state =>
+        {
+            state.LastPack = new PackRecord
+            {
+                PackedAt = DateTimeOffset.UtcNow,
+                Input = inputFolder.FullName,
+                Manifest = manifestPath.FullName,
+                Output = outputMsixPath.FullName,
+                Sha256 = sha256,
+                PackageName = packageName,
+                Publisher = publisher,
+                Signed = signed,
+                FromCache = fromCache,
+                Provenance = provenance,
+            };
+            if (branchIdentity != null)
+            {
+                state.RecordIdentity(new IdentityRecord
+                {
+                    Kind = ProjectIdentityKind.Branch,
+                    Name = packageName,
+                    Publisher = branchIdentity.Publisher,
+                    Branch = branchIdentity.Branch,
+                    Certificate = certificatePath?.FullName,
+                    CreatedAt = DateTimeOffset.UtcNow,
+                });
+            }
+        }, taskContext, cancellationToken);
+    }
+
+    /// <summary>
+    /// Settings that change the package built from a given payload. The manifest is part of the payload by now,
+    /// and winapp.yaml pins the SDK and build tools versions.
+    /// </summary>
+    private Dictionary<string, string> GetPackCacheConfiguration(string packageName, bool skipPri, bool selfContained, PackagingEngine engine, DigestAlgorithm digest)
+    {
+        return new Dictionary<string, string>
+        {
+            ["cli"] = BannerHelper.GetVersionString(),
+            ["name"] = packageName,
+            ["skipPri"] = skipPri.ToString(),
+            ["selfContained"] = selfContained.ToString(),
+            ["engine"] = engine.ToString(),
+            ["digest"] = digest.ToString(),
+            ["winapp.yaml"] = configService.Exists() ? File.ReadAllText(configService.ConfigPath.FullName) : "",
+        };
+    }
+
+    // Cache problems never fail the pack; the package is built locally instead
+    private static async Task<bool> TryDownloadFromRemoteCacheAsync(IRemoteBuildCache cache, string key, FileInfo outputMsixPath, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        try
+        {
+            if (await cache.TryDownloadAsync(key, outputMsixPath, message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}"), cancellationToken))
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Check} Restored the package from {cache.Name}");
+                return true;
+            }
+
+            taskContext.AddDebugMessage($"{UiSymbols.Note} {cache.Name} has no package for this payload; packing it");
+        }
+        catch (Exception ex) when (ex is HttpRequestException or InvalidOperationException or IOException || (ex is TaskCanceledException && !cancellationToken.IsCancellationRequested))
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not read the remote cache, packing locally: {ex.Message}");
+        }
+        return false;
+    }
+
+    private static async Task UploadToRemoteCacheAsync(IRemoteBuildCache cache, string key, FileInfo outputMsixPath, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        try
+        {
+            await cache.UploadAsync(key, outputMsixPath, message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}"), cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Save} Stored the package in {cache.Name}");
+        }
+        catch (Exception ex) when (ex is HttpRequestException or InvalidOperationException or IOException || (ex is TaskCanceledException && !cancellationToken.IsCancellationRequested))
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Warning} Could not store the package in the remote cache: {ex.Message}");
+        }
     }
 
     private async Task EmbedWindowsAppSDKManifestToExeAsync(FileInfo exePath, DirectoryInfo winAppSDKDeploymentDir, FileInfo windowsAppSDKAppXManifestPath, TaskContext taskContext, CancellationToken cancellationToken)
@@ -1094,7 +1400,7 @@ internal partial class MsixService(
         await File.WriteAllTextAsync(outAppManifestPath.FullName, manifestContent, new UTF8Encoding(encoderShouldEmitUTF8Identifier: false), cancellationToken);
     }
 
-    private async Task SignMsixPackageAsync(DirectoryInfo outputFolder, string certificatePassword, bool generateDevCert, bool installDevCert, string finalPackageName, string? extractedPublisher, FileInfo outputMsixPath, FileInfo? certPath, FileInfo resolvedManifestPath, TaskContext taskContext, CancellationToken cancellationToken)
+    private async Task SignMsixPackageAsync(DirectoryInfo outputFolder, string certificatePassword, bool generateDevCert, bool installDevCert, string finalPackageName, string? extractedPublisher, FileInfo outputMsixPath, FileInfo? certPath, FileInfo resolvedManifestPath, PackagingEngine engine, TaskContext taskContext, CancellationToken cancellationToken)
     {
         if (certPath == null && generateDevCert)
         {
@@ -1132,23 +1438,68 @@ internal partial class MsixService(
         // Install certificate if requested
         if (installDevCert)
         {
-            certificateService.InstallCertificate(certPath, certificatePassword, false, taskContext);
+            await certificateService.InstallCertificateAsync(certPath, certificatePassword, false, taskContext, cancellationToken);
         }
 
         // Sign the package
-        await certificateService.SignFileAsync(outputMsixPath, certPath, taskContext, certificatePassword, cancellationToken: cancellationToken);
+        if (engine == PackagingEngine.Native)
+        {
+            await nativePackagingService.SignAsync(outputMsixPath, certPath, taskContext, certificatePassword, cancellationToken: cancellationToken);
+        }
+        else
+        {
+            await certificateService.SignFileAsync(outputMsixPath, certPath, taskContext, certificatePassword, cancellationToken: cancellationToken);
+        }
     }
 
-    private async Task CreateMsixPackageFromFolderAsync(DirectoryInfo inputFolder, FileInfo outputMsixPath, TaskContext taskContext, CancellationToken cancellationToken)
+    private async Task CreateMsixPackageFromFolderAsync(DirectoryInfo inputFolder, FileInfo outputMsixPath, PackagingEngine engine, int threads, bool useBlockCache, DigestAlgorithm digest, TaskContext taskContext, CancellationToken cancellationToken)
     {
+        await ValidateDigestForTargetAsync(inputFolder, digest, taskContext, cancellationToken);
+
+        if (engine == PackagingEngine.Native)
+        {
+            await nativePackagingService.PackAsync(inputFolder, outputMsixPath, taskContext, threads, useBlockCache, digest, cancellationToken);
+            return;
+        }
+
         // Create MSIX package
         var makeappxArguments = $@"pack /o /d ""{Path.TrimEndingDirectorySeparator(inputFolder.FullName)}"" /nv /p ""{outputMsixPath.FullName}""";
+        if (digest != DigestAlgorithm.Sha256)
+        {
+            makeappxArguments += $" /h {digest.ToHashAlgorithmName().Name}";
+        }
 
         taskContext.AddDebugMessage("Creating MSIX package...");
 
         await buildToolsService.RunBuildToolAsync(new MakeAppxTool(), makeappxArguments, taskContext, cancellationToken: cancellationToken);
     }
 
+    /// <summary>
+    /// Fails before packing when the manifest targets Windows versions that cannot deploy packages hashed with <paramref name="digest"/>
+    /// </summary>
+    private static async Task ValidateDigestForTargetAsync(DirectoryInfo inputFolder, DigestAlgorithm digest, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        if (digest == DigestAlgorithm.Sha256)
+        {
+            return;
+        }
+
+        var manifestPath = Path.Combine(inputFolder.FullName, AppxPacker.ManifestPartName);
+        if (!File.Exists(manifestPath))
+        {
+            return;
+        }
+
+        var minVersion = AppxDigestAlgorithms.ReadMinTargetVersion(await File.ReadAllTextAsync(manifestPath, cancellationToken));
+        var issue = AppxDigestAlgorithms.GetTargetVersionIssue(digest.ToHashAlgorithmName(), minVersion);
+        if (issue != null)
+        {
+            throw new InvalidOperationException(issue);
+        }
+
+        taskContext.AddDebugMessage($"{UiSymbols.Lock} Using {digest.ToHashAlgorithmName().Name} block map and signature digests (lowest target: {minVersion?.ToString() ?? "unspecified"})");
+    }
+
     private async Task RunMtToolAsync(string arguments, bool printErrors, TaskContext taskContext, CancellationToken cancellationToken = default)
     {
         // Use BuildToolsService to run mt.exe
@@ -1848,7 +2199,7 @@ $1");
     /// Returns true if <paramref name="candidateNameWithoutExtension"/> is a valid MRT
     /// variant of the logical base name (dots allowed in base name).
     /// </summary>
-    private static bool IsMrtVariantName(string logicalBaseName, string candidateNameWithoutExtension)
+    internal static bool IsMrtVariantName(string logicalBaseName, string candidateNameWithoutExtension)
     {
         // Split by '.'; "Logo.scale-200.theme-dark" -> ["Logo", "scale-200", "theme-dark"]
         var parts = candidateNameWithoutExtension.Split('.');
@@ -1935,6 +2286,7 @@ $1");
         taskContext.AddDebugMessage($"{UiSymbols.Clipboard} Registering sparse package with external location...");
 
         var registerCommand = $"Add-AppxPackage -Path '{manifestPath.FullName}' -ExternalLocation '{externalLocation.FullName}' -Register -ForceUpdateFromAnyVersion";
+        var startedAt = DateTime.UtcNow;
 
         try
         {
@@ -1949,7 +2301,10 @@ $1");
         }
         catch (Exception ex)
         {
-            throw new InvalidOperationException($"Failed to register sparse package: {ex.Message}", ex);
+            // The deployment logs why it failed in the event log, which PowerShell's error doesn't always say
+            var identity = await ParseAppxManifestFromPathAsync(manifestPath, CancellationToken.None);
+            var events = deploymentEventService.GetEvents(identity.PackageName, startedAt, taskContext);
+            throw new InvalidOperationException($"Failed to register sparse package: {ex.Message}{DeploymentEventService.Format(events)}", ex);
         }
     }
 
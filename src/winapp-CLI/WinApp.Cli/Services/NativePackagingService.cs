@@ -0,0 +1,194 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net.Http.Headers;
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class NativePackagingService(IWinappDirectoryService winappDirectoryService, IFipsPolicyService fipsPolicyService) : INativePackagingService
+{
+    private const string BlockCacheDirectoryName = "blocks";
+
+    private static readonly HttpClient Http = new();
+
+    private static readonly string[] SignableExtensions = [".msix", ".appx"];
+
+    // Shared by every pack in this process, so variants packed together only compress common blocks once
+    private AppxBlockCache? blockCache;
+
+    public async Task<AppxPackResult> PackAsync(DirectoryInfo inputFolder, FileInfo outputPath, TaskContext taskContext, int threads = 0, bool useBlockCache = true, DigestAlgorithm digest = DigestAlgorithm.Sha256, CancellationToken cancellationToken = default)
+    {
+        if (threads < 0)
+        {
+            throw new InvalidOperationException("--threads must be 0 (one per logical processor) or a positive number");
+        }
+
+        var hashAlgorithm = digest.ToHashAlgorithmName();
+        fipsPolicyService.EnsureAvailable("pack");
+        fipsPolicyService.EnsureHashAlgorithm(hashAlgorithm, "hash the block map");
+
+        taskContext.AddDebugMessage($"{UiSymbols.Package} Packing {inputFolder.FullName} with the native engine ({hashAlgorithm.Name} block map)");
+
+        var cache = useBlockCache ? GetBlockCache() : null;
+        var result = await AppxPacker.PackAsync(inputFolder, outputPath, new AppxPackOptions(threads, cache) { HashAlgorithm = hashAlgorithm }, cancellationToken);
+
+        taskContext.AddDebugMessage($"{UiSymbols.Check} Packed {result.PayloadFileCount} files ({result.PayloadSize:N0} bytes) into {result.PackagePath.Name}");
+        taskContext.AddStatusMessage($"{UiSymbols.Timer} Packed {result.PayloadSize / 1048576.0:N1} MB in {result.Elapsed.TotalSeconds:N1}s ({result.Throughput / 1048576.0:N1} MB/s, {result.Threads} threads)");
+
+        if (cache != null)
+        {
+            if (result.ReusedBlocks > 0)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Sync} Reused {result.ReusedBlocks:N0} of {result.PayloadBlocks:N0} blocks ({result.ReusedSize / 1048576.0:N1} MB) from the block cache");
+            }
+
+            var trimmed = cache.Trim();
+            if (trimmed > 0)
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Trash} Removed {trimmed} old entries from the block cache at {cache.CacheDirectory?.FullName}");
+            }
+        }
+
+        return result;
+    }
+
+    public async Task SignAsync(FileInfo packagePath, FileInfo certificatePath, TaskContext taskContext, string? password = "password", string? timestampUrl = null, CancellationToken cancellationToken = default)
+    {
+        if (!SignableExtensions.Contains(packagePath.Extension, StringComparer.OrdinalIgnoreCase))
+        {
+            throw new InvalidOperationException($"The native engine can only sign .msix and .appx packages. Use --engine sdk to sign '{packagePath.Name}' with signtool.exe.");
+        }
+
+        certificatePath.Refresh();
+        if (!certificatePath.Exists)
+        {
+            throw new FileNotFoundException($"Certificate file not found: {certificatePath}");
+        }
+
+        fipsPolicyService.EnsureAvailable("sign packages");
+        fipsPolicyService.EnsurePkcs12(certificatePath);
+
+        X509Certificate2Collection certificates;
+        try
+        {
+            certificates = X509CertificateLoader.LoadPkcs12CollectionFromFile(certificatePath.FullName, password, X509KeyStorageFlags.EphemeralKeySet);
+        }
+        catch (CryptographicException ex)
+        {
+            throw new InvalidOperationException($"Failed to load certificate {certificatePath.Name}: {ex.Message}", ex);
+        }
+
+        try
+        {
+            var signer = certificates.FirstOrDefault(c => c.HasPrivateKey)
+                ?? throw new InvalidOperationException($"Certificate {certificatePath.Name} does not contain a private key");
+            var chain = certificates.Where(c => c != signer).ToList();
+            fipsPolicyService.EnsureSigningCertificate(signer);
+
+            taskContext.AddDebugMessage($"{UiSymbols.Lock} Signing {packagePath.Name} as {signer.Subject} with the native engine");
+
+            Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider = null;
+            if (!string.IsNullOrWhiteSpace(timestampUrl))
+            {
+                timestampProvider = (signature, hashAlgorithm, ct) => RequestTimestampAsync(timestampUrl, signature, hashAlgorithm, taskContext, ct);
+            }
+
+            var digestAlgorithm = await AppxPackageSigner.SignAsync(packagePath, signer, chain, timestampProvider, cancellationToken);
+
+            taskContext.AddDebugMessage($"{UiSymbols.Check} Signed {packagePath.Name} ({digestAlgorithm.Name})");
+        }
+        finally
+        {
+            foreach (var certificate in certificates)
+            {
+                certificate.Dispose();
+            }
+        }
+    }
+
+    public async Task SignAsync(FileInfo packagePath, RemoteHashSigner signer, TaskContext taskContext, string? timestampUrl = null, CancellationToken cancellationToken = default)
+    {
+        if (!SignableExtensions.Contains(packagePath.Extension, StringComparer.OrdinalIgnoreCase))
+        {
+            throw new InvalidOperationException($"Signing services can only sign .msix and .appx packages, not '{packagePath.Name}'.");
+        }
+
+        fipsPolicyService.EnsureAvailable("sign packages");
+
+        taskContext.AddDebugMessage($"{UiSymbols.Lock} Signing {packagePath.Name} with a signing service key");
+
+        Func<byte[], HashAlgorithmName, CancellationToken, Task<byte[]>>? timestampProvider = null;
+        if (!string.IsNullOrWhiteSpace(timestampUrl))
+        {
+            timestampProvider = (signature, hashAlgorithm, ct) => RequestTimestampAsync(timestampUrl, signature, hashAlgorithm, taskContext, ct);
+        }
+
+        var digestAlgorithm = await AppxPackageSigner.SignAsync(packagePath, signer, timestampProvider, cancellationToken);
+
+        taskContext.AddDebugMessage($"{UiSymbols.Check} Signed {packagePath.Name} ({digestAlgorithm.Name})");
+    }
+
+    public async Task<AppxVerificationResult> VerifyAsync(FileInfo packagePath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        taskContext.AddDebugMessage($"{UiSymbols.Search} Verifying {packagePath.FullName}");
+        fipsPolicyService.EnsureAvailable("verify packages");
+
+        try
+        {
+            return await AppxPackageVerifier.VerifyAsync(packagePath, cancellationToken);
+        }
+        catch (InvalidDataException ex)
+        {
+            throw new InvalidOperationException($"{packagePath.Name} is not a valid package: {ex.Message}", ex);
+        }
+    }
+
+    public async Task<AppxDigestReport> InspectDigestsAsync(FileInfo packagePath, TaskContext taskContext, DigestAlgorithm? required = null, CancellationToken cancellationToken = default)
+    {
+        taskContext.AddDebugMessage($"{UiSymbols.Search} Inspecting the algorithms of {packagePath.FullName}");
+        fipsPolicyService.EnsureAvailable("inspect packages");
+
+        try
+        {
+            return await AppxDigestInspector.InspectAsync(packagePath, required?.ToHashAlgorithmName(), cancellationToken);
+        }
+        catch (InvalidDataException ex)
+        {
+            throw new InvalidOperationException($"{packagePath.Name} is not a valid package: {ex.Message}", ex);
+        }
+    }
+
+    private AppxBlockCache GetBlockCache() =>
+        blockCache ??= new AppxBlockCache(new DirectoryInfo(Path.Combine(winappDirectoryService.GetGlobalWinappDirectory().FullName, BlockCacheDirectoryName)));
+
+    private static async Task<byte[]> RequestTimestampAsync(string timestampUrl, byte[] signature, HashAlgorithmName hashAlgorithm, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        taskContext.AddDebugMessage($"Requesting RFC 3161 timestamp ({hashAlgorithm.Name}) from {timestampUrl}");
+
+        var request = Rfc3161TimestampRequest.CreateFromData(
+            signature,
+            hashAlgorithm,
+            nonce: RandomNumberGenerator.GetBytes(8),
+            requestSignerCertificates: true);
+
+        using var content = new ByteArrayContent(request.Encode());
+        content.Headers.ContentType = new MediaTypeHeaderValue("application/timestamp-query");
+
+        using var response = await Http.PostAsync(timestampUrl, content, cancellationToken);
+        if (!response.IsSuccessStatusCode)
+        {
+            throw new InvalidOperationException($"Timestamp server {timestampUrl} returned {(int)response.StatusCode} {response.ReasonPhrase}");
+        }
+
+        var responseBytes = await response.Content.ReadAsByteArrayAsync(cancellationToken);
+        var token = request.ProcessResponse(responseBytes, out _);
+        return token.AsSignedCms().Encode();
+    }
+}
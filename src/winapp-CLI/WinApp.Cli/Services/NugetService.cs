@@ -8,11 +8,14 @@ using WinApp.Cli.Helpers;
 
 namespace WinApp.Cli.Services;
 
-internal class NugetService(ICurrentDirectoryProvider currentDirectoryProvider) : INugetService
+internal class NugetService(ISettingsService settingsService, ICurrentDirectoryProvider currentDirectoryProvider) : INugetService
 {
     private static readonly HttpClient Http = new();
     private const string NugetExeUrl = "https://dist.nuget.org/win-x86-commandline/latest/nuget.exe";
     private const string FlatIndex = "https://api.nuget.org/v3-flatcontainer";
+    private const string PackageBaseAddressType = "PackageBaseAddress/3.0.0";
+
+    private string? _packageBaseAddress;
 
     public static readonly string[] SDK_PACKAGES =
     [
@@ -48,7 +51,7 @@ internal class NugetService(ICurrentDirectoryProvider currentDirectoryProvider)
             throw new ArgumentException("sdkInstallMode cannot be None", nameof(sdkInstallMode));
         }
 
-        var url = $"{FlatIndex}/{packageName.ToLowerInvariant()}/index.json";
+        var url = $"{await GetPackageBaseAddressAsync(cancellationToken)}/{packageName.ToLowerInvariant()}/index.json";
         using var resp = await Http.GetAsync(url, cancellationToken);
         resp.EnsureSuccessStatusCode();
         using var s = await resp.Content.ReadAsStreamAsync(cancellationToken);
@@ -106,6 +109,47 @@ internal class NugetService(ICurrentDirectoryProvider currentDirectoryProvider)
         return list[^1];
     }
 
+    /// <summary>
+    /// The flat container of the nugetFeed setting, where the versions of each package are listed
+    /// </summary>
+    private async Task<string> GetPackageBaseAddressAsync(CancellationToken cancellationToken)
+    {
+        var feed = settingsService.Get(SettingsService.NugetFeed).Value ?? SettingsService.DefaultNugetFeed;
+        if (feed == SettingsService.DefaultNugetFeed)
+        {
+            return FlatIndex;
+        }
+        if (_packageBaseAddress != null)
+        {
+            return _packageBaseAddress;
+        }
+
+        using var resp = await Http.GetAsync(feed, cancellationToken);
+        resp.EnsureSuccessStatusCode();
+        using var s = await resp.Content.ReadAsStreamAsync(cancellationToken);
+        using var doc = await JsonDocument.ParseAsync(s, cancellationToken: cancellationToken);
+        if (doc.RootElement.TryGetProperty("resources", out var resources) && resources.ValueKind == JsonValueKind.Array)
+        {
+            foreach (var resource in resources.EnumerateArray())
+            {
+                if (resource.TryGetProperty("@type", out var type) && type.GetString() == PackageBaseAddressType
+                    && resource.TryGetProperty("@id", out var id) && id.GetString() is { Length: > 0 } address)
+                {
+                    return _packageBaseAddress = address.TrimEnd('/');
+                }
+            }
+        }
+
+        throw new InvalidOperationException($"{feed} is not a NuGet v3 service index with a {PackageBaseAddressType} resource; set nugetFeed to the feed's index.json URL");
+    }
+
+    // nuget.exe keeps using its own sources, and their credential providers, for the default feed
+    private string GetSourceArgument()
+    {
+        var feed = settingsService.Get(SettingsService.NugetFeed).Value;
+        return feed == null || feed == SettingsService.DefaultNugetFeed ? "" : $" -Source {Quote(feed)}";
+    }
+
     public async Task<Dictionary<string, string>> InstallPackageAsync(DirectoryInfo globalWinappDir, string package, string version, DirectoryInfo outputDir, TaskContext taskContext, CancellationToken cancellationToken = default)
     {
         var packages = new Dictionary<string, string>(StringComparer.OrdinalIgnoreCase);
@@ -130,7 +174,7 @@ internal class NugetService(ICurrentDirectoryProvider currentDirectoryProvider)
         var psi = new ProcessStartInfo
         {
             FileName = nugetExe,
-            Arguments = $"install {EscapeArg(package)} -Version {EscapeArg(version)} -OutputDirectory {Quote(outputDir.FullName)} -NonInteractive -ForceEnglishOutput",
+            Arguments = $"install {EscapeArg(package)} -Version {EscapeArg(version)} -OutputDirectory {Quote(outputDir.FullName)}{GetSourceArgument()} -NonInteractive -ForceEnglishOutput",
             UseShellExecute = false,
             RedirectStandardOutput = true,
             RedirectStandardError = true,
@@ -0,0 +1,132 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Security.Cryptography;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class PackageLicenseService(IConfigService configService) : IPackageLicenseService
+{
+    private const string LicenseExpressionUrl = "https://licenses.nuget.org/";
+
+    public IReadOnlyList<PackageLicense> GetRequiredLicenses(DirectoryInfo packagesDir, IReadOnlyDictionary<string, string> versions)
+    {
+        var licenses = new List<PackageLicense>();
+        foreach (var (package, version) in versions.OrderBy(v => v.Key, StringComparer.OrdinalIgnoreCase))
+        {
+            var packageDir = new DirectoryInfo(Path.Combine(packagesDir.FullName, $"{package}.{version}"));
+            var nuspec = packageDir.Exists ? packageDir.EnumerateFiles("*.nuspec").FirstOrDefault() : null;
+            if (nuspec != null && ReadLicense(nuspec, package, version) is { } license)
+            {
+                licenses.Add(license);
+            }
+        }
+        return licenses;
+    }
+
+    public async Task<IReadOnlyList<PackageLicense>> EnsureAcceptedAsync(DirectoryInfo packagesDir, IReadOnlyDictionary<string, string> versions, LicenseConsent consent, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var required = GetRequiredLicenses(packagesDir, versions);
+        if (required.Count == 0)
+        {
+            return [];
+        }
+
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        var pending = new List<PackageLicense>();
+        var accepted = new List<PackageLicense>();
+        foreach (var license in required)
+        {
+            var record = config.Licenses.FirstOrDefault(l => l.Package.Equals(license.Package, StringComparison.OrdinalIgnoreCase));
+            if (record != null && record.License == license.License && record.Sha256 == license.Sha256)
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Check} {license.Package}: {license.License} accepted by {record.AcceptedBy} on {record.AcceptedAt} for {record.Version}");
+                continue;
+            }
+
+            if (record != null)
+            {
+                taskContext.AddStatusMessage($"{UiSymbols.Warning} The license of {license.Package} changed since {record.AcceptedBy} accepted it for {record.Version}");
+            }
+
+            if (consent.Covers(license.Package)
+                || (consent.Prompt && await taskContext.PromptConfirmationAsync($"{license.Package} {license.Version} requires accepting its license ({license.Location}). Accept?", cancellationToken)))
+            {
+                accepted.Add(license);
+            }
+            else
+            {
+                pending.Add(license);
+            }
+        }
+
+        if (accepted.Count > 0)
+        {
+            var acceptedAt = DateTimeOffset.UtcNow.ToString("yyyy-MM-ddTHH:mm:ssZ", CultureInfo.InvariantCulture);
+            foreach (var license in accepted)
+            {
+                config.Licenses.RemoveAll(l => l.Package.Equals(license.Package, StringComparison.OrdinalIgnoreCase));
+                config.Licenses.Add(new LicenseAcceptanceConfig
+                {
+                    Package = license.Package,
+                    Version = license.Version,
+                    License = license.License,
+                    Sha256 = license.Sha256,
+                    AcceptedBy = Environment.UserName,
+                    AcceptedAt = acceptedAt,
+                });
+                taskContext.AddStatusMessage($"{UiSymbols.Check} Accepted the license of {license.Package} {license.Version}: {license.Location}");
+            }
+            configService.Save(config);
+        }
+
+        return pending;
+    }
+
+    /// <summary>
+    /// Lists the licenses that aren't accepted, and returns the error of the command
+    /// </summary>
+    public static string ReportPending(IReadOnlyList<PackageLicense> pending, TaskContext taskContext)
+    {
+        foreach (var license in pending)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Bullet} {license.Package} {license.Version}: {license.Location}");
+        }
+
+        return $"{UiSymbols.Error} {pending.Count} package(s) require accepting their license. Review the licenses, then run again with --accept-licenses, or --accept-license <package> for each";
+    }
+
+    internal static PackageLicense? ReadLicense(FileInfo nuspec, string package, string version)
+    {
+        var metadata = XDocument.Load(nuspec.FullName).Root?.Elements().FirstOrDefault(e => e.Name.LocalName == "metadata");
+        XElement? Get(string name) => metadata?.Elements().FirstOrDefault(e => e.Name.LocalName == name);
+
+        if (!bool.TryParse(Get("requireLicenseAcceptance")?.Value.Trim(), out var required) || !required)
+        {
+            return null;
+        }
+
+        // <license> replaces the deprecated <licenseUrl>, which packages still set for older clients
+        if (Get("license") is { } license && license.Value.Trim() is { Length: > 0 } value)
+        {
+            if (string.Equals((string?)license.Attribute("type"), "file", StringComparison.OrdinalIgnoreCase))
+            {
+                var file = new FileInfo(Path.Combine(nuspec.DirectoryName!, value));
+                var sha256 = file.Exists ? Convert.ToHexStringLower(SHA256.HashData(File.ReadAllBytes(file.FullName))) : null;
+                return new PackageLicense(package, version, value, sha256, file.FullName);
+            }
+
+            return new PackageLicense(package, version, value, null, $"{LicenseExpressionUrl}{Uri.EscapeDataString(value)}");
+        }
+
+        var url = Get("licenseUrl")?.Value.Trim();
+        return string.IsNullOrEmpty(url)
+            ? new PackageLicense(package, version, "unspecified", null, nuspec.FullName)
+            : new PackageLicense(package, version, url, null, url);
+    }
+}
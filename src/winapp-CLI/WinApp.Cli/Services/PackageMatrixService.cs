@@ -0,0 +1,109 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class PackageMatrixService(IConfigService configService) : IPackageMatrixService
+{
+    public List<MatrixVariant> GetVariants(IReadOnlyList<string> filters)
+    {
+        var matrix = configService.Exists() ? configService.Load().Matrix : null;
+        var variants = matrix?.Expand().Where(v => v.Name.Length > 0).ToList() ?? [];
+        if (variants.Count == 0)
+        {
+            throw new InvalidOperationException("winapp.yaml declares no matrix; add brandings, channels or architectures under 'matrix:'");
+        }
+
+        var duplicate = variants.GroupBy(v => v.Name, StringComparer.OrdinalIgnoreCase).FirstOrDefault(g => g.Count() > 1);
+        if (duplicate != null)
+        {
+            throw new InvalidOperationException($"winapp.yaml: the matrix has several variants named '{duplicate.Key}'; give each branding, channel and architecture its own name");
+        }
+
+        if (filters.Count == 0)
+        {
+            return variants;
+        }
+        var selected = variants.Where(v => filters.Any(v.Matches)).ToList();
+        return selected.Count > 0
+            ? selected
+            : throw new InvalidOperationException($"No matrix variant matches {string.Join(", ", filters)}. Variants: {string.Join(", ", variants.Select(v => v.Name))}");
+    }
+
+    public async Task<MatrixVariantLayout> PrepareAsync(MatrixVariant variant, DirectoryInfo inputFolder, FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var configDirectory = configService.ConfigPath.Directory!;
+        var originals = new Dictionary<string, byte[]?>(StringComparer.OrdinalIgnoreCase);
+        async Task SaveOriginalAsync(string path)
+        {
+            if (!originals.ContainsKey(path))
+            {
+                originals[path] = File.Exists(path) ? await File.ReadAllBytesAsync(path, cancellationToken) : null;
+            }
+        }
+
+        var variantInput = variant.Architecture?.Input is { } input
+            ? new DirectoryInfo(Path.Combine(configDirectory.FullName, input))
+            : inputFolder;
+        if (!variantInput.Exists)
+        {
+            throw new DirectoryNotFoundException($"winapp.yaml: the input folder of architecture '{variant.Architecture?.Name}' doesn't exist: {variantInput.FullName}");
+        }
+
+        FileInfo? certificate = null;
+        if (variant.Branding?.Certificate is { } certificatePath)
+        {
+            certificate = new FileInfo(Path.Combine(configDirectory.FullName, certificatePath));
+            if (!certificate.Exists)
+            {
+                throw new FileNotFoundException($"winapp.yaml: the certificate of branding '{variant.Branding.Name}' doesn't exist: {certificate.FullName}");
+            }
+        }
+
+        // Packing writes the processed manifest into the input folder, which may be the project's manifest itself
+        await SaveOriginalAsync(manifestPath.FullName);
+        await SaveOriginalAsync(Path.Combine(variantInput.FullName, "appxmanifest.xml"));
+        var layout = new MatrixVariantLayout(variantInput, manifestPath, certificate, originals);
+        try
+        {
+            var manifestXml = await File.ReadAllTextAsync(manifestPath.FullName, Encoding.UTF8, cancellationToken);
+            await File.WriteAllTextAsync(manifestPath.FullName, AppxMatrixVariant.Apply(manifestXml, variant), Encoding.UTF8, cancellationToken);
+
+            if (variant.Branding?.Assets is { } assets)
+            {
+                var assetsDirectory = new DirectoryInfo(Path.Combine(configDirectory.FullName, assets));
+                if (!assetsDirectory.Exists)
+                {
+                    throw new DirectoryNotFoundException($"winapp.yaml: the assets folder of branding '{variant.Branding.Name}' doesn't exist: {assetsDirectory.FullName}");
+                }
+
+                var count = 0;
+                foreach (var file in assetsDirectory.EnumerateFiles("*", SearchOption.AllDirectories))
+                {
+                    // Packing copies the manifest's assets into the input folder, so restore those copies too
+                    var relativePath = Path.GetRelativePath(assetsDirectory.FullName, file.FullName);
+                    var target = Path.Combine(manifestPath.DirectoryName!, relativePath);
+                    await SaveOriginalAsync(target);
+                    await SaveOriginalAsync(Path.Combine(variantInput.FullName, relativePath));
+                    Directory.CreateDirectory(Path.GetDirectoryName(target)!);
+                    file.CopyTo(target, overwrite: true);
+                    count++;
+                }
+                taskContext.AddDebugMessage($"{UiSymbols.Files} Copied {count} asset(s) of branding '{variant.Branding.Name}'");
+            }
+        }
+        catch
+        {
+            layout.Dispose();
+            throw;
+        }
+
+        return layout;
+    }
+}
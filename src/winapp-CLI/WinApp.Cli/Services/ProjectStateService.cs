@@ -0,0 +1,115 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json;
+using System.Text.Json.Serialization;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+[JsonSerializable(typeof(ProjectState))]
+[JsonSerializable(typeof(BuildProvenance))]
+[JsonSourceGenerationOptions(
+    WriteIndented = true,
+    PropertyNamingPolicy = JsonKnownNamingPolicy.CamelCase,
+    DefaultIgnoreCondition = JsonIgnoreCondition.WhenWritingNull)]
+internal partial class ProjectStateJsonContext : JsonSerializerContext
+{
+}
+
+/// <summary>
+/// Keeps .winapp/state.json in the project's local winapp directory
+/// </summary>
+internal sealed class ProjectStateService(
+    IWinappDirectoryService winappDirectoryService,
+    IGitService gitService,
+    ICurrentDirectoryProvider currentDirectoryProvider) : IProjectStateService
+{
+    private const string StateFileName = "state.json";
+
+    public FileInfo StatePath => new(Path.Combine(winappDirectoryService.GetLocalWinappDirectory().FullName, StateFileName));
+
+    public async Task<ProjectState> LoadAsync(TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var statePath = StatePath;
+        if (!statePath.Exists)
+        {
+            return new ProjectState();
+        }
+
+        try
+        {
+            using var stream = statePath.OpenRead();
+            return await JsonSerializer.DeserializeAsync(stream, ProjectStateJsonContext.Default.ProjectState, cancellationToken) ?? new ProjectState();
+        }
+        catch (Exception ex) when (ex is JsonException or IOException or UnauthorizedAccessException)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Warning} Ignoring unreadable {statePath}: {ex.Message}");
+            return new ProjectState();
+        }
+    }
+
+    public async Task UpdateAsync(Action<ProjectState> update, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var state = await LoadAsync(taskContext, cancellationToken);
+        update(state);
+
+        var statePath = StatePath;
+        try
+        {
+            statePath.Directory?.Create();
+            using var stream = statePath.Open(FileMode.Create, FileAccess.Write);
+            await JsonSerializer.SerializeAsync(stream, state, ProjectStateJsonContext.Default.ProjectState, cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Save} Updated {statePath}");
+        }
+        catch (Exception ex) when (ex is IOException or UnauthorizedAccessException)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Warning} Could not update {statePath}: {ex.Message}");
+        }
+    }
+
+    public async Task<BuildProvenance> GetProvenanceAsync(string? profile, CancellationToken cancellationToken = default)
+    {
+        var provenance = new BuildProvenance
+        {
+            Profile = profile,
+            CliVersion = BannerHelper.GetVersionString(),
+        };
+
+        var directory = currentDirectoryProvider.GetCurrentDirectory();
+        provenance.SourceRoot = directory;
+        try
+        {
+            provenance.SourceRoot = Path.GetFullPath((await gitService.RunAsync(directory, ["rev-parse", "--show-toplevel"], cancellationToken)).Trim());
+            provenance.GitSha = (await gitService.RunAsync(directory, ["rev-parse", "HEAD"], cancellationToken)).Trim();
+            provenance.GitDirty = (await gitService.RunAsync(directory, ["status", "--porcelain"], cancellationToken)).Trim().Length > 0;
+            var branch = (await gitService.RunAsync(directory, ["rev-parse", "--abbrev-ref", "HEAD"], cancellationToken)).Trim();
+            provenance.Branch = branch == "HEAD" ? null : branch;
+        }
+        catch (InvalidOperationException)
+        {
+            // Not a git repository, or git is not installed; the stamp still records profile and CLI version
+        }
+
+        return provenance;
+    }
+
+    public async Task<FileInfo> WriteProvenanceAsync(BuildProvenance provenance, DirectoryInfo packageFolder, CancellationToken cancellationToken = default)
+    {
+        if (provenance.SourceRoot != null)
+        {
+            var relative = Path.GetRelativePath(provenance.SourceRoot, packageFolder.FullName);
+            var outside = Path.IsPathRooted(relative) || relative == ".." || relative.StartsWith($"..{Path.DirectorySeparatorChar}", StringComparison.Ordinal);
+            provenance.PackageFolder = outside ? null : relative == "." ? "" : relative.Replace(Path.DirectorySeparatorChar, '/');
+        }
+
+        var stampPath = new FileInfo(Path.Combine(packageFolder.FullName, BuildProvenance.FileName));
+        using (var stream = stampPath.Open(FileMode.Create, FileAccess.Write))
+        {
+            await JsonSerializer.SerializeAsync(stream, provenance, ProjectStateJsonContext.Default.BuildProvenance, cancellationToken);
+        }
+        return stampPath;
+    }
+}
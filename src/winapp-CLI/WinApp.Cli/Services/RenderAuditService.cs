@@ -0,0 +1,249 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text.Json;
+using Microsoft.Win32;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class RenderAuditService(IBundlerMigrationService bundlerMigrationService) : IRenderAuditService
+{
+    /// <summary>
+    /// Environment variable whose switches WebView2 passes to the browser process of every app started with it
+    /// </summary>
+    internal const string BrowserArgumentsVariable = "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS";
+
+    // The device setup class of display adapters, with a subkey per adapter
+    private const string DisplayClassKey = @"SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}";
+
+    // WPF's per-user switch to render every WPF app in software
+    private const string WpfGraphicsKey = @"Software\Microsoft\Avalon.Graphics";
+
+    /// <summary>
+    /// Drivers older than this are likely on Chromium's GPU blocklist, or have Direct3D bugs fixed since
+    /// </summary>
+    internal static readonly TimeSpan OldDriverAge = TimeSpan.FromDays(3 * 365);
+
+    // Chromium switches that make WebView2 render in software
+    private static readonly string[] SoftwareSwitches = ["--disable-gpu", "--disable-gpu-compositing", "--disable-d3d11", "--use-angle=swiftshader", "--use-gl=swiftshader"];
+
+    // Display adapters of hypervisors, which pass through little or no Direct3D acceleration
+    private static readonly string[] VirtualAdapters = ["Hyper-V", "VMware", "VirtualBox", "Parallels", "QXL", "Red Hat", "Citrix"];
+
+    private static readonly string[] SkippedFolders = ["node_modules", ".git", ".winapp"];
+
+    private static readonly EnumerationOptions Enumeration = new() { IgnoreInaccessible = true };
+
+    public Task<RenderReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var directory = manifestPath.Directory!;
+        var tauriConfig = bundlerMigrationService.FindConfig(MigrationSource.Tauri, directory)
+            ?? (directory.Parent is { } parent ? bundlerMigrationService.FindConfig(MigrationSource.Tauri, parent) : null);
+        var renderers = FindRenderers(directory, tauriConfig != null);
+        cancellationToken.ThrowIfCancellationRequested();
+
+        var adapters = ReadAdapters(taskContext);
+        foreach (var adapter in adapters)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Note} {adapter.Name}: {adapter.Provider} {adapter.DriverVersion} ({adapter.DriverDate?.ToString("yyyy-MM-dd", CultureInfo.InvariantCulture) ?? "no date"})");
+        }
+
+        var browserArguments = new Dictionary<string, string>();
+        if (renderers.Contains("WebView2"))
+        {
+            if (Environment.GetEnvironmentVariable(BrowserArgumentsVariable) is { Length: > 0 } variable)
+            {
+                browserArguments[BrowserArgumentsVariable] = variable;
+            }
+            if (tauriConfig != null && ReadTauriBrowserArguments(tauriConfig, taskContext) is { } tauriArguments)
+            {
+                browserArguments[tauriConfig.Name] = tauriArguments;
+            }
+        }
+
+        var context = new RenderContext(
+            adapters,
+            IsRemoteSession(Environment.GetEnvironmentVariable("SESSIONNAME")),
+            browserArguments,
+            renderers.Contains("WPF") && IsWpfSoftwareRendering(),
+            DateTime.Today);
+        return Task.FromResult(new RenderReport($"{Environment.OSVersion.Version}", adapters, renderers, Findings(context)));
+    }
+
+    /// <param name="BrowserArguments">Browser arguments of WebView2 apps, by where they are set</param>
+    /// <param name="WpfSoftwareRendering">The app uses WPF, and WPF's hardware acceleration is turned off for the user</param>
+    internal sealed record RenderContext(IReadOnlyList<GpuAdapter> Adapters, bool IsRemoteSession, IReadOnlyDictionary<string, string> BrowserArguments, bool WpfSoftwareRendering, DateTime Today);
+
+    internal static List<RenderIssue> Findings(RenderContext context)
+    {
+        var issues = new List<RenderIssue>();
+        var hardware = context.Adapters.Where(a => !IsSoftwareAdapter(a.Name)).ToList();
+        // No adapters when the registry couldn't be read
+        if (context.Adapters.Count > 0 && hardware.Count == 0)
+        {
+            var installed = $"Only {string.Join(" and ", context.Adapters.Select(a => a.Name).Distinct())} is installed";
+            issues.Add(context.IsRemoteSession
+                ? new RenderIssue(AccessibilitySeverity.Info, "remote-session", "Remote Desktop",
+                    $"{installed} in this Remote Desktop session, so Direct3D renders on the CPU (WARP): WinUI and WPF render in software and WebView2 turns off GPU acceleration. It's the session, not the package; compare with a session at the console")
+                : new RenderIssue(AccessibilitySeverity.Warning, "software-adapter", context.Adapters[0].Name,
+                    $"{installed}, so Direct3D renders on the CPU (WARP): WinUI and WPF render in software, and WebView2 turns off GPU acceleration or uses SwiftShader. It's the machine, not the package: install the GPU's display driver from Windows Update or its vendor"));
+        }
+        else if (context.IsRemoteSession)
+        {
+            issues.Add(new RenderIssue(AccessibilitySeverity.Info, "remote-session", "Remote Desktop",
+                "This is a Remote Desktop session, where apps render for the remote display adapter: unless the host shares its GPU with remote sessions, WinUI, WPF and WebView2 render in software. Compare with a session at the console before blaming the package"));
+        }
+
+        foreach (var adapter in hardware)
+        {
+            if (VirtualAdapters.Any(v => adapter.Name.Contains(v, StringComparison.OrdinalIgnoreCase)))
+            {
+                issues.Add(new RenderIssue(AccessibilitySeverity.Info, "virtual-machine", adapter.Name,
+                    $"{adapter.Name} is the display adapter of a virtual machine, which passes through little or no Direct3D acceleration, so WebView2 may block the GPU and WinUI may render with WARP. Check rendering problems on physical hardware, or with GPU partitioning, before blaming the package"));
+            }
+            else if (adapter.DriverDate is { } date && context.Today - date > OldDriverAge)
+            {
+                issues.Add(new RenderIssue(AccessibilitySeverity.Warning, "old-driver", adapter.Name,
+                    $"{adapter.Name} runs {adapter.Provider} driver {adapter.DriverVersion} from {date:yyyy-MM-dd}. Chromium's GPU blocklist, which WebView2 follows, turns off GPU features for many old drivers, and old drivers have Direct3D bugs WinUI can't work around; update it from the GPU vendor"));
+            }
+        }
+
+        foreach (var (source, arguments) in context.BrowserArguments)
+        {
+            var switches = arguments.Split(' ', StringSplitOptions.RemoveEmptyEntries)
+                .Where(a => SoftwareSwitches.Contains(a, StringComparer.OrdinalIgnoreCase))
+                .ToList();
+            if (switches.Count > 0)
+            {
+                var scope = source == BrowserArgumentsVariable ? "every WebView2 app started from this environment" : "the app";
+                issues.Add(new RenderIssue(AccessibilitySeverity.Warning, "disabled", source,
+                    $"{source} passes {string.Join(" ", switches)} to WebView2, which turns off GPU rendering for {scope}. Remove it unless it works around a driver bug, and then only for the drivers that have it"));
+            }
+        }
+
+        if (context.WpfSoftwareRendering)
+        {
+            issues.Add(new RenderIssue(AccessibilitySeverity.Warning, "disabled", "DisableHWAcceleration",
+                $@"HKEY_CURRENT_USER\{WpfGraphicsKey}\DisableHWAcceleration is 1, which makes every WPF app of the user render in software. Delete the value unless it works around a driver bug"));
+        }
+        return issues;
+    }
+
+    /// <summary>
+    /// What the app renders with: WebView2 for Tauri apps and the WebView2 loader, WinUI and WPF for their
+    /// assemblies, or for the UseWinUI and UseWPF properties of a project next to the manifest
+    /// </summary>
+    internal static List<string> FindRenderers(DirectoryInfo directory, bool isTauri)
+    {
+        var names = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+        var pending = new Stack<DirectoryInfo>([directory]);
+        while (pending.TryPop(out var current))
+        {
+            names.UnionWith(current.EnumerateFiles("*.dll", Enumeration).Select(f => f.Name));
+            foreach (var child in current.EnumerateDirectories("*", Enumeration).Where(d => !SkippedFolders.Contains(d.Name, StringComparer.OrdinalIgnoreCase)))
+            {
+                pending.Push(child);
+            }
+        }
+        var projects = directory.EnumerateFiles("*.*proj", Enumeration).Select(p => File.ReadAllText(p.FullName)).ToList();
+
+        var renderers = new List<string>();
+        if (isTauri || names.Contains("WebView2Loader.dll") || names.Contains("Microsoft.Web.WebView2.Core.dll"))
+        {
+            renderers.Add("WebView2");
+        }
+        if (names.Contains("Microsoft.WinUI.dll") || projects.Any(p => p.Contains("<UseWinUI>true</UseWinUI>", StringComparison.OrdinalIgnoreCase)))
+        {
+            renderers.Add("WinUI");
+        }
+        if (names.Contains("PresentationFramework.dll") || projects.Any(p => p.Contains("<UseWPF>true</UseWPF>", StringComparison.OrdinalIgnoreCase)))
+        {
+            renderers.Add("WPF");
+        }
+        return renderers;
+    }
+
+    /// <summary>
+    /// The additionalBrowserArgs of the windows of a Tauri 2 configuration
+    /// </summary>
+    internal static string? ReadTauriBrowserArguments(FileInfo config, TaskContext? taskContext = null)
+    {
+        try
+        {
+            using var document = JsonDocument.Parse(File.ReadAllText(config.FullName), new JsonDocumentOptions { CommentHandling = JsonCommentHandling.Skip, AllowTrailingCommas = true });
+            if (!document.RootElement.TryGetProperty("app", out var app) || !app.TryGetProperty("windows", out var windows) || windows.ValueKind != JsonValueKind.Array)
+            {
+                return null;
+            }
+            var arguments = windows.EnumerateArray()
+                .Select(w => w.ValueKind == JsonValueKind.Object && w.TryGetProperty("additionalBrowserArgs", out var a) && a.ValueKind == JsonValueKind.String ? a.GetString() : null)
+                .OfType<string>()
+                .ToList();
+            return arguments.Count > 0 ? string.Join(" ", arguments) : null;
+        }
+        catch (Exception ex) when (ex is JsonException or IOException)
+        {
+            taskContext?.AddDebugMessage($"{UiSymbols.Note} Couldn't read {config.FullName}: {ex.Message}");
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Sessions of Remote Desktop are named RDP-Tcp#&lt;n&gt;, and the console session Console
+    /// </summary>
+    internal static bool IsRemoteSession(string? sessionName) =>
+        sessionName != null && sessionName.StartsWith("RDP-", StringComparison.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// The adapters Windows falls back to without a display driver, and the adapter of Remote Desktop sessions,
+    /// which all render on the CPU
+    /// </summary>
+    internal static bool IsSoftwareAdapter(string name) =>
+        name.StartsWith("Microsoft Basic", StringComparison.OrdinalIgnoreCase)
+        || name.Equals("Microsoft Remote Display Adapter", StringComparison.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// Parses the M-D-YYYY DriverDate of a device's driver key
+    /// </summary>
+    internal static DateTime? ParseDriverDate(string? value) =>
+        DateTime.TryParseExact(value, ["M-d-yyyy", "MM-dd-yyyy"], CultureInfo.InvariantCulture, DateTimeStyles.None, out var date) ? date : null;
+
+    private static List<GpuAdapter> ReadAdapters(TaskContext taskContext)
+    {
+        var adapters = new List<GpuAdapter>();
+        try
+        {
+            using var hklm64 = RegistryKey.OpenBaseKey(RegistryHive.LocalMachine, RegistryView.Registry64);
+            using var displayClass = hklm64.OpenSubKey(DisplayClassKey);
+            foreach (var name in displayClass?.GetSubKeyNames() ?? [])
+            {
+                // The class key also holds Properties and Configuration subkeys, without a driver
+                using var driver = displayClass!.OpenSubKey(name);
+                if (driver?.GetValue("DriverDesc") is not string description)
+                {
+                    continue;
+                }
+                adapters.Add(new GpuAdapter(
+                    description,
+                    driver.GetValue("ProviderName") as string ?? "",
+                    driver.GetValue("DriverVersion") as string ?? "",
+                    ParseDriverDate(driver.GetValue("DriverDate") as string)));
+            }
+        }
+        catch (Exception ex) when (ex is System.Security.SecurityException or UnauthorizedAccessException or IOException)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Couldn't read the display adapters: {ex.Message}");
+        }
+        return adapters;
+    }
+
+    private static bool IsWpfSoftwareRendering()
+    {
+        using var key = Registry.CurrentUser.OpenSubKey(WpfGraphicsKey);
+        return key?.GetValue("DisableHWAcceleration") is int value && value != 0;
+    }
+}
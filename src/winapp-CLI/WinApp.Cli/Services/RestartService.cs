@@ -0,0 +1,173 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class RestartService(IInstalledPackageService installedPackageService, IPowerShellService powerShellService) : IRestartService
+{
+    /// <summary>
+    /// Folder of LocalState the winapp-runtime crate keeps restart state in, RESTART_STATE_FOLDER
+    /// </summary>
+    internal const string RestartStateFolder = "RestartState";
+
+    /// <summary>
+    /// Argument the restarted app gets the token of its state with, RESTORE_STATE_ARGUMENT
+    /// </summary>
+    internal const string RestoreStateArgument = "--winapp-restore-state";
+
+    public IReadOnlyList<RestartRequest> FindRequests(InstalledPackage package) =>
+        FindRequests(new DirectoryInfo(Path.Combine(package.AppDataPath, "LocalState", RestartStateFolder)));
+
+    internal static List<RestartRequest> FindRequests(DirectoryInfo folder)
+    {
+        if (!folder.Exists)
+        {
+            return [];
+        }
+        return [.. folder.EnumerateFiles("*.json")
+            .Select(file => ParseRequest(Path.GetFileNameWithoutExtension(file.Name), File.ReadAllText(file.FullName)))
+            .OfType<RestartRequest>()
+            .OrderByDescending(r => r.SavedAt)];
+    }
+
+    /// <summary>
+    /// Reads a state file of the winapp-runtime crate; null when it isn't one, e.g. when it is being written
+    /// </summary>
+    internal static RestartRequest? ParseRequest(string token, string json)
+    {
+        try
+        {
+            var node = JsonNode.Parse(json);
+            var reason = node?["reason"]?.GetValue<string>() switch
+            {
+                "update" => RestartReason.Update,
+                "crash" => RestartReason.Crash,
+                _ => (RestartReason?)null,
+            };
+            var executable = node?["executable"]?.GetValue<string>();
+            if (node == null || reason == null || executable == null)
+            {
+                return null;
+            }
+            return new RestartRequest(
+                Token: token,
+                Reason: reason.Value,
+                SavedAt: DateTimeOffset.FromUnixTimeSeconds(node["savedAt"]?.GetValue<long>() ?? 0),
+                ProcessId: node["processId"]?.GetValue<int>() ?? 0,
+                Executable: executable,
+                Arguments: [.. (node["arguments"]?.AsArray() ?? []).Select(a => a?.GetValue<string>() ?? "")],
+                AppUserModelId: node["appUserModelId"]?.GetValue<string>());
+        }
+        catch (Exception ex) when (ex is JsonException or InvalidOperationException or FormatException)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// The newest restart for <paramref name="reason"/> that a running process asked for; a process that exited
+    /// without restarting left its state behind
+    /// </summary>
+    internal static RestartRequest? PickRequest(IEnumerable<RestartRequest> requests, IEnumerable<PackageProcess> processes, RestartReason reason)
+    {
+        var running = processes.Select(p => p.Id).ToHashSet();
+        return requests
+            .Where(r => r.Reason == reason && running.Contains(r.ProcessId))
+            .OrderByDescending(r => r.SavedAt)
+            .FirstOrDefault();
+    }
+
+    /// <summary>
+    /// The arguments Windows restarts the app with: those it was started with and the token of its state
+    /// </summary>
+    internal static List<string> RestartArguments(RestartRequest request) =>
+        [.. request.Arguments, $"{RestoreStateArgument}={request.Token}"];
+
+    public async Task<RestartSimulation> SimulateAsync(InstalledPackage package, RestartReason reason, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var processes = await installedPackageService.GetProcessesAsync(package, taskContext, cancellationToken);
+        var requests = FindRequests(package);
+        foreach (var request in requests)
+        {
+            taskContext.AddDebugMessage($"Restart {request.Token}: {request.Reason} for process {request.ProcessId}, saved {request.SavedAt:u}");
+        }
+
+        var picked = PickRequest(requests, processes, reason) ?? throw new InvalidOperationException(processes.Count == 0
+            ? $"{package.Name} isn't running; start it and let it call {RuntimeFunction(reason)} first"
+            : $"No running process of {package.Name} asked for a restart after {(reason == RestartReason.Update ? "an update" : "a crash")}; call {RuntimeFunction(reason)} of the winapp-runtime crate first");
+
+        await installedPackageService.StopProcessesAsync(processes, taskContext, cancellationToken);
+        await StartAsync(package, picked, taskContext, cancellationToken);
+        return new RestartSimulation(picked, processes.Count);
+    }
+
+    /// <summary>
+    /// Starts the app the way the restart of Windows does: in its package, when it has identity, or as the executable
+    /// otherwise. The debug identity of create-debug-identity also comes with the executable.
+    /// </summary>
+    private async Task StartAsync(InstalledPackage package, RestartRequest request, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var arguments = RestartArguments(request);
+        var appId = request.AppUserModelId?.Split('!') is [_, var id] ? id : null;
+        if (appId != null && package.ExternalLocation == null)
+        {
+            var (exitCode, _) = await powerShellService.RunCommandAsync(
+                $"Invoke-CommandInDesktopPackage -PackageFamilyName {Quote(package.FamilyName)} -AppId {Quote(appId)} -Command {Quote(request.Executable)} -Args {Quote(JoinArguments(arguments))}",
+                taskContext,
+                cancellationToken: cancellationToken);
+            if (exitCode != 0)
+            {
+                throw new InvalidOperationException($"Starting {appId} in {package.Name} failed; run with --verbose for details");
+            }
+            return;
+        }
+
+        var psi = new ProcessStartInfo(request.Executable) { UseShellExecute = false };
+        foreach (var argument in arguments)
+        {
+            psi.ArgumentList.Add(argument);
+        }
+        using var process = Process.Start(psi) ?? throw new InvalidOperationException($"Failed to start {request.Executable}");
+        taskContext.AddDebugMessage($"Started {request.Executable} ({process.Id})");
+    }
+
+    /// <summary>
+    /// Joins arguments into a command line that CommandLineToArgvW splits back into them
+    /// </summary>
+    internal static string JoinArguments(IEnumerable<string> arguments) => string.Join(" ", arguments.Select(QuoteArgument));
+
+    private static string QuoteArgument(string argument)
+    {
+        if (argument.Length > 0 && argument.IndexOfAny([' ', '\t', '\n', '"']) < 0)
+        {
+            return argument;
+        }
+
+        var quoted = new StringBuilder("\"");
+        var backslashes = 0;
+        foreach (var c in argument)
+        {
+            if (c == '\\')
+            {
+                backslashes++;
+                continue;
+            }
+            // Backslashes are literal unless a quote follows them
+            quoted.Append('\\', c == '"' ? backslashes * 2 + 1 : backslashes).Append(c);
+            backslashes = 0;
+        }
+        return quoted.Append('\\', backslashes * 2).Append('"').ToString();
+    }
+
+    private static string RuntimeFunction(RestartReason reason) =>
+        reason == RestartReason.Update ? "request_restart_for_update" : "request_restart_after_crash";
+
+    private static string Quote(string value) => $"'{value.Replace("'", "''")}'";
+}
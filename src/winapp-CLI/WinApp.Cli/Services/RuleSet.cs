@@ -0,0 +1,62 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+/// <param name="Rule">Rule id, a source such as a11y/* or * for every rule</param>
+/// <param name="Lines">Lines of the element start tags the suppression covers; null for the whole manifest</param>
+internal sealed record RuleSuppression(string Rule, IReadOnlySet<int>? Lines);
+
+/// <summary>
+/// The severity overrides and suppressions that apply to the issues of one manifest
+/// </summary>
+internal sealed class RuleSet(XElement? root, IReadOnlyDictionary<string, AccessibilitySeverity?> overrides, IReadOnlyList<RuleSuppression> suppressions)
+{
+    /// <summary>
+    /// Reports every issue at the severity its check gives it
+    /// </summary>
+    public static RuleSet None { get; } = new(null, new Dictionary<string, AccessibilitySeverity?>(), []);
+
+    /// <summary>
+    /// The severity to report an issue at, or null when it is turned off or suppressed
+    /// </summary>
+    /// <param name="subject">What the issue names, to find the element it is about; see <see cref="AppxManifestLocator.Locate"/></param>
+    public AccessibilitySeverity? Resolve(string rule, AccessibilitySeverity severity, string subject)
+    {
+        var element = root != null ? AppxManifestLocator.Locate(root, rule, subject) : null;
+        return Resolve(rule, severity, element is IXmlLineInfo info && info.HasLineInfo() ? info.LineNumber : null);
+    }
+
+    /// <summary>
+    /// The severity to report an issue at, or null when it is turned off or suppressed
+    /// </summary>
+    /// <param name="line">Line the start tag of the element the issue is about begins on, if known</param>
+    public AccessibilitySeverity? Resolve(string rule, AccessibilitySeverity severity, int? line)
+    {
+        if (suppressions.Any(s => Matches(s.Rule, rule) && (s.Lines == null || (line is int l && s.Lines.Contains(l)))))
+        {
+            return null;
+        }
+
+        // An override of the rule itself wins over one of its source
+        var source = rule.Split('/')[0];
+        foreach (var key in new[] { rule, $"{source}/*", "*" })
+        {
+            if (overrides.TryGetValue(key, out var level))
+            {
+                return level;
+            }
+        }
+        return severity;
+    }
+
+    internal static bool Matches(string pattern, string rule) =>
+        pattern == "*"
+        || pattern.Equals(rule, StringComparison.OrdinalIgnoreCase)
+        || (pattern.EndsWith("/*", StringComparison.Ordinal) && rule.StartsWith(pattern[..^1], StringComparison.OrdinalIgnoreCase));
+}
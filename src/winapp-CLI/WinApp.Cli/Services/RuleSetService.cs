@@ -0,0 +1,105 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class RuleSetService(IConfigService configService, ISettingsService settingsService) : IRuleSetService
+{
+    private const string IgnoreComment = "winapp-ignore";
+    private const string IgnoreFileComment = "winapp-ignore-file";
+
+    public RuleSet Load(FileInfo manifestPath, TaskContext taskContext)
+    {
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+
+        // The org's policy comes first, so winapp.yaml can override its rules
+        var levels = new List<(string Rule, string Level, string Source)>();
+        var policy = settingsService.Get(SettingsService.Policy);
+        if (policy.Value != null)
+        {
+            if (!File.Exists(policy.Value))
+            {
+                throw new FileNotFoundException($"The policy file {policy.Value} set by {policy.Source} doesn't exist", policy.Value);
+            }
+            var policyConfig = ConfigService.Parse(File.ReadAllText(policy.Value));
+            levels.AddRange(policyConfig.Rules.Select(r => (r.Key, r.Value, Path.GetFileName(policy.Value))));
+            taskContext.AddDebugMessage($"{UiSymbols.Note} {policyConfig.Rules.Count} rule(s) from the policy {policy.Value}");
+        }
+        levels.AddRange(config.Rules.Select(r => (r.Key, r.Value, "winapp.yaml")));
+
+        var overrides = new Dictionary<string, AccessibilitySeverity?>(StringComparer.OrdinalIgnoreCase);
+        foreach (var (rule, level, source) in levels)
+        {
+            WarnIfUnknown(rule, config, taskContext);
+            overrides[rule] = level == "off"
+                ? null
+                : Enum.TryParse<AccessibilitySeverity>(level, ignoreCase: true, out var severity)
+                    ? severity
+                    : throw new InvalidOperationException($"{source}: '{level}' is not a level for {rule}; use off, info, warning or error");
+        }
+
+        var suppressions = new List<RuleSuppression>();
+        var configDirectory = configService.ConfigPath.DirectoryName!;
+        foreach (var suppression in config.Suppressions)
+        {
+            if (suppression.File != null
+                && !Path.GetFullPath(Path.Combine(configDirectory, suppression.File)).Equals(manifestPath.FullName, StringComparison.OrdinalIgnoreCase))
+            {
+                continue;
+            }
+
+            WarnIfUnknown(suppression.Rule, config, taskContext);
+            suppressions.Add(new RuleSuppression(suppression.Rule, suppression.Line is int line ? new HashSet<int> { line } : null));
+        }
+
+        var document = XDocument.Load(manifestPath.FullName, LoadOptions.SetLineInfo);
+        foreach (var comment in document.DescendantNodes().OfType<XComment>())
+        {
+            var text = comment.Value.Trim();
+            var fileWide = text.StartsWith(IgnoreFileComment + " ", StringComparison.Ordinal) || text == IgnoreFileComment;
+            if (!fileWide && !text.StartsWith(IgnoreComment + " ", StringComparison.Ordinal) && text != IgnoreComment)
+            {
+                continue;
+            }
+
+            // winapp-ignore a11y/contrast scales/dpi: reason
+            var rules = text[(fileWide ? IgnoreFileComment : IgnoreComment).Length..].Split(':')[0]
+                .Split(new[] { ' ', ',' }, StringSplitOptions.RemoveEmptyEntries);
+            IReadOnlySet<int>? lines = null;
+            if (!fileWide)
+            {
+                var element = comment.NodesAfterSelf().OfType<XElement>().FirstOrDefault();
+                if (element == null)
+                {
+                    continue;
+                }
+                lines = element.DescendantsAndSelf().Select(e => ((IXmlLineInfo)e).LineNumber).ToHashSet();
+            }
+
+            foreach (var rule in rules.Length > 0 ? rules : ["*"])
+            {
+                WarnIfUnknown(rule, config, taskContext);
+                suppressions.Add(new RuleSuppression(rule, lines));
+            }
+        }
+
+        taskContext.AddDebugMessage($"{UiSymbols.Note} {overrides.Count} rule override(s) and {suppressions.Count} suppression(s) apply to {manifestPath.Name}");
+        return new RuleSet(document.Root, overrides, suppressions);
+    }
+
+    // Custom analyzers name their own checks, so any rule of their source is known
+    private static void WarnIfUnknown(string rule, WinappConfig config, TaskContext taskContext)
+    {
+        if (!ValidationRules.All.Any(r => RuleSet.Matches(rule, r.Id))
+            && !config.Analyzers.Any(a => rule.StartsWith(a.Id + "/", StringComparison.OrdinalIgnoreCase)))
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Warning} No rule matches '{rule}'; see '--explain' of the analyze commands for the rule ids");
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using System.Xml;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class SarifService(IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider) : ISarifService
+{
+    private const string SarifSchema = "https://json.schemastore.org/sarif-2.1.0.json";
+    private const string SourceRoot = "%SRCROOT%";
+
+    public async Task WriteAsync(FileInfo sarifPath, FileInfo manifestPath, IReadOnlyList<SarifFinding> findings, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var root = XDocument.Load(manifestPath.FullName, LoadOptions.SetLineInfo).Root
+            ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var assetDirectories = ManifestService.ExtractAssetReferencesFromManifest(manifestPath, taskContext)
+            .Select(r => Path.Combine(manifestPath.DirectoryName!, Path.GetDirectoryName(r.RelativePath.Replace('\\', Path.DirectorySeparatorChar)) ?? ""))
+            .Distinct(StringComparer.OrdinalIgnoreCase)
+            .ToList();
+        var splashLine = FindSplashSection();
+        var baseDirectory = currentDirectoryProvider.GetCurrentDirectory();
+
+        JsonObject Location(string path, int? line)
+        {
+            var relative = Path.GetRelativePath(baseDirectory, path);
+            var artifact = relative.StartsWith("..", StringComparison.Ordinal) || Path.IsPathRooted(relative)
+                ? new JsonObject { ["uri"] = new Uri(path).AbsoluteUri }
+                : new JsonObject { ["uri"] = relative.Replace('\\', '/'), ["uriBaseId"] = SourceRoot };
+            var physical = new JsonObject { ["artifactLocation"] = artifact };
+            if (line is int startLine)
+            {
+                physical["region"] = new JsonObject { ["startLine"] = startLine };
+            }
+            return new JsonObject { ["physicalLocation"] = physical };
+        }
+
+        var rules = ValidationRules.All.ToList();
+        var manifestFile = Path.GetRelativePath(baseDirectory, manifestPath.FullName).Replace('\\', '/');
+        var results = new List<JsonNode?>();
+        foreach (var finding in findings)
+        {
+            var element = finding.Element != null
+                ? AppxManifestLocator.FindByPath(root, finding.Element)
+                : finding.Subject != null ? AppxManifestLocator.Locate(root, finding.Rule, finding.Subject) : root;
+            var manifestLocation = Location(manifestPath.FullName, ((IXmlLineInfo)element).HasLineInfo() ? ((IXmlLineInfo)element).LineNumber : null);
+
+            // The first location is where code scanning shows the result, so it is the file the fix goes in
+            var locations = new List<JsonNode?>();
+            var payload = finding.Rule.StartsWith("scales/", StringComparison.Ordinal) && finding.Subject is string variant && variant.IndexOfAny(['/', '\\']) < 0
+                ? assetDirectories.Select(d => Path.Combine(d, variant)).FirstOrDefault(File.Exists)
+                : null;
+            if (payload != null)
+            {
+                locations.Add(Location(payload, null));
+                locations.Add(manifestLocation);
+            }
+            else if (finding.Rule == "splash/extended" && splashLine != null)
+            {
+                locations.Add(Location(configService.ConfigPath.FullName, splashLine));
+                locations.Add(manifestLocation);
+            }
+            else
+            {
+                locations.Add(manifestLocation);
+                if (finding.Rule.StartsWith("splash/", StringComparison.Ordinal) && splashLine != null)
+                {
+                    locations.Add(Location(configService.ConfigPath.FullName, splashLine));
+                }
+            }
+
+            var result = new JsonObject
+            {
+                ["ruleId"] = finding.Rule,
+                ["level"] = finding.Severity switch
+                {
+                    AccessibilitySeverity.Error => "error",
+                    AccessibilitySeverity.Warning => "warning",
+                    _ => "note",
+                },
+                ["message"] = new JsonObject { ["text"] = finding.Subject != null ? $"{finding.Subject}: {finding.Message}" : finding.Message },
+                ["locations"] = new JsonArray(locations.ToArray()),
+                // The element path stays the same when lines move, so code scanning keeps tracking the same alert
+                ["partialFingerprints"] = new JsonObject { ["winappElement/v1"] = $"{manifestFile}:{finding.Rule}:{AppxManifestLocator.PathOf(element)}" },
+            };
+            var ruleIndex = rules.FindIndex(r => r.Id == finding.Rule);
+            if (ruleIndex >= 0)
+            {
+                result["ruleIndex"] = ruleIndex;
+            }
+            if (finding.Baselined)
+            {
+                result["suppressions"] = new JsonArray(new JsonObject { ["kind"] = "external", ["justification"] = "Recorded in the validation baseline" });
+            }
+            results.Add(result);
+        }
+
+        var log = new JsonObject
+        {
+            ["$schema"] = SarifSchema,
+            ["version"] = "2.1.0",
+            ["runs"] = new JsonArray(new JsonObject
+            {
+                ["tool"] = new JsonObject
+                {
+                    ["driver"] = new JsonObject
+                    {
+                        ["name"] = "winapp",
+                        ["version"] = BannerHelper.GetVersionString(),
+                        ["informationUri"] = "https://github.com/microsoft/winappCli",
+                        ["rules"] = new JsonArray(rules.Select(r => (JsonNode?)new JsonObject
+                        {
+                            ["id"] = r.Id,
+                            ["shortDescription"] = new JsonObject { ["text"] = r.Description },
+                            ["help"] = new JsonObject { ["text"] = string.Join(Environment.NewLine, ValidationRules.Explain(r.Id).Skip(1)) },
+                        }).ToArray()),
+                    },
+                },
+                ["originalUriBaseIds"] = new JsonObject
+                {
+                    [SourceRoot] = new JsonObject { ["uri"] = new Uri(Path.TrimEndingDirectorySeparator(baseDirectory) + Path.DirectorySeparatorChar).AbsoluteUri },
+                },
+                ["results"] = new JsonArray(results.ToArray()),
+            }),
+        };
+
+        sarifPath.Directory?.Create();
+        await File.WriteAllTextAsync(sarifPath.FullName, log.ToJsonString(new JsonSerializerOptions { WriteIndented = true }), cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {findings.Count} result(s) to {sarifPath.FullName}");
+    }
+
+    /// <summary>
+    /// Line of the splash: section of winapp.yaml, or null without one
+    /// </summary>
+    private int? FindSplashSection()
+    {
+        if (!configService.Exists())
+        {
+            return null;
+        }
+
+        var lines = File.ReadAllLines(configService.ConfigPath.FullName);
+        var index = Array.FindIndex(lines, l => l.TrimEnd() == "splash:");
+        return index >= 0 ? index + 1 : null;
+    }
+}
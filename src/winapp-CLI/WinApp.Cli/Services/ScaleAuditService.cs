@@ -0,0 +1,298 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Drawing.Drawing2D;
+using System.Drawing.Imaging;
+using System.Runtime.InteropServices;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class ScaleAuditService : IScaleAuditService
+{
+    /// <summary>
+    /// Display scales Windows offers in Settings, up to the 450% of 8K laptops
+    /// </summary>
+    internal static readonly int[] DisplayScales = [100, 125, 150, 175, 200, 225, 250, 300, 350, 400, 450];
+
+    /// <summary>
+    /// Target sizes the resource loader looks for; others are only used when they happen to match
+    /// </summary>
+    internal static readonly int[] StandardTargetSizes = [16, 20, 24, 30, 32, 36, 40, 48, 60, 64, 72, 80, 96, 256];
+
+    // The largest scale qualifier; Windows draws scale-400 at the display scales above it too
+    private const int MaxScaleQualifier = 400;
+
+    // Where Windows draws Square44x44Logo at a target size, and how big at 100%
+    private static readonly (string Surface, int Size)[] TargetSurfaces =
+    [
+        ("title bar", 16),
+        ("taskbar", 24),
+        ("Alt+Tab and Start", 32),
+    ];
+
+    // A pixel is an edge when it differs this much from a neighbor, and lost detail when it differs this much
+    // after a round trip through half the size
+    private const int EdgeThreshold = 32;
+
+    // An image is an upscale when the round trip keeps all but this share of its edges
+    private const double UpscaledEdgeRatio = 0.1;
+
+    private sealed record Variant(FileInfo File, int? Scale, int? TargetSize, Size Pixels);
+
+    public Task<ScaleReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var manifestDirectory = manifestPath.Directory!;
+        var references = ManifestService.ExtractAssetReferencesFromManifest(manifestPath, taskContext);
+        var issues = new List<ScaleIssue>();
+        var assets = new List<string>();
+        var loaded = 0;
+
+        foreach (var reference in references)
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+            if (AppxManifestSchema.IsResourceReference(reference.RelativePath))
+            {
+                continue;
+            }
+
+            assets.Add(reference.RelativePath);
+            taskContext.AddDebugMessage($"{UiSymbols.Search} Checking the scales of {reference.RelativePath} ({reference.BaseWidth}x{reference.BaseHeight})");
+
+            var files = AccessibilityAuditService.FindVariants(manifestDirectory, reference.RelativePath, reference.RelativePath, []);
+            if (files.Count == 0)
+            {
+                issues.Add(new ScaleIssue(AccessibilitySeverity.Error, "asset", reference.RelativePath, "Not found, in any scale or variant"));
+                continue;
+            }
+
+            var variants = new List<Variant>();
+            foreach (var file in files.Where(f => !IsHighContrast(f)))
+            {
+                try
+                {
+                    using var bitmap = new Bitmap(file.FullName);
+                    var variant = new Variant(file, GetQualifierValue(file, "scale"), GetQualifierValue(file, "targetsize"), bitmap.Size);
+                    variants.Add(variant);
+                    loaded++;
+
+                    CheckSize(reference, variant, issues);
+                    if (Math.Min(bitmap.Width, bitmap.Height) >= 64 && LooksUpscaled(bitmap))
+                    {
+                        issues.Add(new ScaleIssue(AccessibilitySeverity.Warning, "upscaled", file.Name,
+                            $"Has no more detail than at half its size ({bitmap.Width / 2}x{bitmap.Height / 2}), so it was likely upscaled and looks blurry; export it from the source artwork at full size"));
+                    }
+                }
+                catch (ArgumentException)
+                {
+                    issues.Add(new ScaleIssue(AccessibilitySeverity.Error, "asset", file.Name, "Is not an image Windows can read"));
+                }
+            }
+
+            var scaleVariants = variants.Where(v => v.TargetSize == null && !HasAltForm(v.File)).ToList();
+            if (scaleVariants.Count > 0)
+            {
+                CheckDisplayScales(reference, scaleVariants, issues);
+            }
+
+            if (reference.BaseWidth == 44 && reference.BaseHeight == 44)
+            {
+                CheckTargetSizes(reference, variants.Where(v => v.TargetSize != null).ToList(), issues);
+            }
+        }
+
+        return Task.FromResult(new ScaleReport(assets, loaded, issues));
+    }
+
+    private static void CheckSize(ManifestAssetReference reference, Variant variant, List<ScaleIssue> issues)
+    {
+        var expected = variant.TargetSize is int targetSize
+            ? new Size(targetSize, targetSize)
+            : new Size(ScaleOf(reference.BaseWidth, variant.Scale ?? 100), ScaleOf(reference.BaseHeight, variant.Scale ?? 100));
+        if (Math.Abs(variant.Pixels.Width - expected.Width) <= 1 && Math.Abs(variant.Pixels.Height - expected.Height) <= 1)
+        {
+            return;
+        }
+
+        var smaller = variant.Pixels.Width < expected.Width || variant.Pixels.Height < expected.Height;
+        issues.Add(new ScaleIssue(smaller ? AccessibilitySeverity.Warning : AccessibilitySeverity.Info, "size", variant.File.Name,
+            $"Is {variant.Pixels.Width}x{variant.Pixels.Height}, but its qualifier promises {expected.Width}x{expected.Height}; Windows {(smaller ? "upscales it, which blurs it" : "scales it down to fit")}"));
+    }
+
+    /// <summary>
+    /// At each display scale, the resource loader picks the smallest scale variant at or above it, or the largest
+    /// one when none is. A variant with fewer pixels than the display needs, up to scale-400, is drawn upscaled.
+    /// </summary>
+    private static void CheckDisplayScales(ManifestAssetReference reference, List<Variant> variants, List<ScaleIssue> issues)
+    {
+        var byScale = variants.OrderBy(v => v.Scale ?? 100).ToList();
+        var upscaled = new List<int>();
+        foreach (var displayScale in DisplayScales)
+        {
+            var picked = byScale.FirstOrDefault(v => (v.Scale ?? 100) >= displayScale) ?? byScale[^1];
+            var effectiveScale = picked.Pixels.Width * 100.0 / reference.BaseWidth;
+            if (effectiveScale < Math.Min(displayScale, MaxScaleQualifier) * 0.98)
+            {
+                upscaled.Add(displayScale);
+            }
+        }
+
+        if (upscaled.Count == 0)
+        {
+            return;
+        }
+
+        var largest = byScale.MaxBy(v => v.Pixels.Width)!;
+        var missing = new[] { 200, 400 }.Where(s => !byScale.Any(v => v.Scale >= s)).Select(s => $"scale-{s}").ToList();
+        issues.Add(new ScaleIssue(upscaled[0] <= 200 ? AccessibilitySeverity.Warning : AccessibilitySeverity.Info, "dpi", reference.RelativePath,
+            $"Upscaled and blurry at {string.Join(", ", upscaled.Select(s => $"{s}%"))}; the largest variant is {largest.File.Name} ({largest.Pixels.Width}x{largest.Pixels.Height})"
+            + (missing.Count > 0 ? $". Add {string.Join(" and ", missing)}" : "")));
+    }
+
+    /// <summary>
+    /// The taskbar, title bar and Alt+Tab draw Square44x44Logo from the smallest target size at or above what
+    /// they need at the display scale, preferring the unplated variants
+    /// </summary>
+    private static void CheckTargetSizes(ManifestAssetReference reference, List<Variant> variants, List<ScaleIssue> issues)
+    {
+        var unplated = variants.Where(v => GetAltForm(v.File) == "unplated").ToList();
+        var family = unplated.Count > 0 ? unplated : variants.Where(v => GetAltForm(v.File) == null).ToList();
+        if (family.Count == 0)
+        {
+            issues.Add(new ScaleIssue(AccessibilitySeverity.Warning, "targetsize", reference.RelativePath,
+                "No targetsize variants; the taskbar, title bar and Alt+Tab scale the plated logo instead. Add e.g. targetsize-24_altform-unplated"));
+            return;
+        }
+
+        var sizes = family.Select(v => Math.Min(v.Pixels.Width, v.Pixels.Height)).Distinct().Order().ToList();
+        var upscaled = new List<string>();
+        var missing = new SortedSet<int>();
+        foreach (var (surface, size) in TargetSurfaces)
+        {
+            foreach (var displayScale in DisplayScales)
+            {
+                var needed = (int)Math.Round(size * displayScale / 100.0);
+                var picked = sizes.FirstOrDefault(s => s >= needed);
+                if (picked == 0)
+                {
+                    // Larger display scales need larger sizes, so the first upscale is enough to report
+                    upscaled.Add($"{surface} from {displayScale}% ({needed}px)");
+                    break;
+                }
+                else if (picked != needed && StandardTargetSizes.Contains(needed))
+                {
+                    missing.Add(needed);
+                }
+            }
+        }
+
+        if (upscaled.Count > 0)
+        {
+            issues.Add(new ScaleIssue(AccessibilitySeverity.Warning, "targetsize", reference.RelativePath,
+                $"The largest target size is {sizes[^1]}px, so the icon is upscaled for the {string.Join(", ", upscaled)}. Add targetsize-256"));
+        }
+        if (missing.Count > 0)
+        {
+            issues.Add(new ScaleIssue(AccessibilitySeverity.Info, "targetsize", reference.RelativePath,
+                $"No target size {string.Join(", ", missing)}; Windows scales a larger one down, which softens thin lines"));
+        }
+    }
+
+    /// <summary>
+    /// Whether an image loses almost none of its edges when scaled to half its size and back, which means it holds
+    /// no more detail than the half size and was upscaled from it
+    /// </summary>
+    internal static bool LooksUpscaled(Bitmap image)
+    {
+        var width = image.Width;
+        var height = image.Height;
+        using var half = Resize(image, width / 2, height / 2);
+        using var roundTrip = Resize(half, width, height);
+        var original = ReadPixels(image);
+        var restored = ReadPixels(roundTrip);
+
+        var edges = 0;
+        var lost = 0;
+        for (var y = 0; y < height; y++)
+        {
+            for (var x = 0; x < width; x++)
+            {
+                var i = (y * width) + x;
+                if ((x + 1 < width && Difference(original[i], original[i + 1]) > EdgeThreshold)
+                    || (y + 1 < height && Difference(original[i], original[i + width]) > EdgeThreshold))
+                {
+                    edges++;
+                }
+                if (Difference(original[i], restored[i]) > EdgeThreshold)
+                {
+                    lost++;
+                }
+            }
+        }
+
+        // A flat image has no detail to lose at any size
+        return edges > 0 && lost < edges * UpscaledEdgeRatio;
+    }
+
+    private static Bitmap Resize(Image image, int width, int height)
+    {
+        var resized = new Bitmap(width, height, PixelFormat.Format32bppArgb);
+        using var graphics = Graphics.FromImage(resized);
+        graphics.InterpolationMode = InterpolationMode.HighQualityBicubic;
+        graphics.PixelOffsetMode = PixelOffsetMode.HighQuality;
+        graphics.CompositingMode = CompositingMode.SourceCopy;
+        using var attributes = new ImageAttributes();
+        attributes.SetWrapMode(WrapMode.TileFlipXY);
+        graphics.DrawImage(image, new Rectangle(0, 0, width, height), 0, 0, image.Width, image.Height, GraphicsUnit.Pixel, attributes);
+        return resized;
+    }
+
+    private static int[] ReadPixels(Bitmap bitmap)
+    {
+        var data = bitmap.LockBits(new Rectangle(0, 0, bitmap.Width, bitmap.Height), ImageLockMode.ReadOnly, PixelFormat.Format32bppArgb);
+        try
+        {
+            // Rows are packed, since a 32 bpp row is always a multiple of 4 bytes
+            var pixels = new int[bitmap.Width * bitmap.Height];
+            Marshal.Copy(data.Scan0, pixels, 0, pixels.Length);
+            return pixels;
+        }
+        finally
+        {
+            bitmap.UnlockBits(data);
+        }
+    }
+
+    // Largest difference of the premultiplied channels, so the color of transparent pixels doesn't count
+    private static int Difference(int first, int second)
+    {
+        var a = Color.FromArgb(first);
+        var b = Color.FromArgb(second);
+        return Math.Max(Math.Abs(a.A - b.A), Math.Max(
+            Math.Abs((a.R * a.A / 255) - (b.R * b.A / 255)),
+            Math.Max(Math.Abs((a.G * a.A / 255) - (b.G * b.A / 255)), Math.Abs((a.B * a.A / 255) - (b.B * b.A / 255)))));
+    }
+
+    private static int ScaleOf(int size, int scale) => (int)Math.Round(size * scale / 100.0);
+
+    private static IEnumerable<string> Qualifiers(FileInfo file) =>
+        Path.GetFileNameWithoutExtension(file.Name).Split('.').Skip(1).SelectMany(q => q.Split('_'));
+
+    private static int? GetQualifierValue(FileInfo file, string name) =>
+        Qualifiers(file)
+            .Where(q => q.StartsWith(name + "-", StringComparison.OrdinalIgnoreCase))
+            .Select(q => int.TryParse(q[(name.Length + 1)..], out var value) ? value : (int?)null)
+            .FirstOrDefault();
+
+    private static string? GetAltForm(FileInfo file) =>
+        Qualifiers(file).FirstOrDefault(q => q.StartsWith("altform-", StringComparison.OrdinalIgnoreCase))?["altform-".Length..].ToLowerInvariant();
+
+    private static bool HasAltForm(FileInfo file) => GetAltForm(file) != null;
+
+    private static bool IsHighContrast(FileInfo file) =>
+        Qualifiers(file).Any(q => q.StartsWith("contrast-", StringComparison.OrdinalIgnoreCase) && !q.Equals("contrast-standard", StringComparison.OrdinalIgnoreCase));
+}
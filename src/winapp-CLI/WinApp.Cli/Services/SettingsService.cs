@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class SettingsService(IConfigService configService, ICurrentDirectoryProvider currentDirectoryProvider) : ISettingsService
+{
+    public const string TimestampServer = "timestampServer";
+    public const string NugetFeed = "nugetFeed";
+    public const string Policy = "policy";
+    public const string RemoteCache = "remoteCache";
+
+    public const string DefaultNugetFeed = "https://api.nuget.org/v3/index.json";
+
+    private const string ConfigFileName = "config.yaml";
+
+    public static readonly IReadOnlyList<SettingDefinition> Definitions =
+    [
+        new(TimestampServer, "RFC 3161 timestamp server that 'sign' uses without --timestamp"),
+        new(NugetFeed, "NuGet v3 feed (service index URL) to download the Windows SDK and Windows App SDK packages from", DefaultNugetFeed),
+        new(Policy, "YAML file whose 'rules:' apply to every project, unless winapp.yaml sets the same rule", IsPath: true),
+        new(RemoteCache, "Remote cache that 'pack' shares packages through without --remote-cache", EnvironmentVariable: BuildCacheService.LocationVariable),
+    ];
+
+    public FileInfo MachineConfigPath { get; set; } = new(Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.CommonApplicationData), "winapp", ConfigFileName));
+
+    public FileInfo UserConfigPath { get; set; } = new(Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.ApplicationData), "winapp", ConfigFileName));
+
+    public IReadOnlyList<SettingValue> GetAll() => [.. Definitions.Select(d => Get(d.Key))];
+
+    public SettingValue Get(string key, string? commandLineValue = null, string? option = null)
+    {
+        var definition = Definitions.FirstOrDefault(d => d.Key.Equals(key, StringComparison.OrdinalIgnoreCase))
+            ?? throw new ArgumentException($"No setting is named {key}", nameof(key));
+
+        if (!string.IsNullOrWhiteSpace(commandLineValue))
+        {
+            return Create(definition, commandLineValue, SettingOrigin.CommandLine, option, currentDirectoryProvider.GetCurrentDirectory());
+        }
+
+        if (definition.EnvironmentVariable != null && Environment.GetEnvironmentVariable(definition.EnvironmentVariable) is { Length: > 0 } environmentValue)
+        {
+            return Create(definition, environmentValue, SettingOrigin.Environment, definition.EnvironmentVariable, currentDirectoryProvider.GetCurrentDirectory());
+        }
+
+        // An empty value turns off the default of the layers below, e.g. an org's remote cache for one repository
+        foreach (var (origin, file) in GetLayers().Reverse())
+        {
+            if (Read(file).TryGetValue(definition.Key, out var value))
+            {
+                return Create(definition, value.Length > 0 ? value : null, origin, file.FullName, file.DirectoryName!);
+            }
+        }
+
+        return new SettingValue(definition.Key, definition.DefaultValue, SettingOrigin.BuiltIn, null);
+    }
+
+    public IReadOnlyList<(string Key, FileInfo File)> GetUnknownKeys() =>
+    [
+        .. GetLayers().SelectMany(layer => Read(layer.File).Keys
+            .Where(k => !Definitions.Any(d => d.Key.Equals(k, StringComparison.OrdinalIgnoreCase)))
+            .Select(k => (k, layer.File)))
+    ];
+
+    private IEnumerable<(SettingOrigin Origin, FileInfo File)> GetLayers() =>
+    [
+        (SettingOrigin.Machine, MachineConfigPath),
+        (SettingOrigin.User, UserConfigPath),
+        (SettingOrigin.Repository, configService.ConfigPath),
+    ];
+
+    private static Dictionary<string, string> Read(FileInfo file)
+    {
+        file.Refresh();
+        return file.Exists
+            ? ConfigService.Parse(File.ReadAllText(file.FullName)).Settings
+            : new Dictionary<string, string>(StringComparer.OrdinalIgnoreCase);
+    }
+
+    private static SettingValue Create(SettingDefinition definition, string? value, SettingOrigin origin, string? source, string baseDirectory)
+    {
+        if (value != null && definition.IsPath)
+        {
+            value = Path.GetFullPath(Path.Combine(baseDirectory, Environment.ExpandEnvironmentVariables(value)));
+        }
+        return new SettingValue(definition.Key, value, origin, source);
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography.X509Certificates;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Signing;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class SigningService(ICertificateService certificateService, INativePackagingService nativePackagingService, IConfigService configService) : ISigningService
+{
+    private static readonly HttpClient Http = new();
+
+    private static readonly string[] PackageExtensions = [".msix", ".appx", ".msixbundle", ".appxbundle"];
+
+    public async Task<IReadOnlyList<FileInfo>> SignAsync(FileInfo file, SigningRequest request, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var config = configService.Exists() ? configService.Load().Sign : null;
+        var provider = request.Provider ?? (request.Certificate != null ? SigningProvider.Pfx : config?.Provider ?? SigningProvider.Pfx);
+
+        var files = file.Extension.Equals(AppInstallerFile.Extension, StringComparison.OrdinalIgnoreCase)
+            ? await FindAppInstallerPackagesAsync(file, taskContext, cancellationToken)
+            : [file];
+
+        RemoteHashSigner? serviceSigner = provider switch
+        {
+            SigningProvider.TrustedSigning => CreateTrustedSigningSigner(config, taskContext),
+            SigningProvider.KeyVault => CreateKeyVaultSigner(config, taskContext),
+            _ => null
+        };
+
+        foreach (var target in files)
+        {
+            var publisher = PackageExtensions.Contains(target.Extension, StringComparer.OrdinalIgnoreCase)
+                ? (await DistributionPackage.ReadAsync(target, cancellationToken)).Publisher
+                : null;
+
+            taskContext.UpdateSubStatus($"Signing {target.Name}");
+            if (serviceSigner != null)
+            {
+                await nativePackagingService.SignAsync(target, async (hash, hashAlgorithm, ct) =>
+                {
+                    var signature = await serviceSigner(hash, hashAlgorithm, ct);
+                    EnsurePublisherMatches(target, publisher, signature.Certificate);
+                    return signature;
+                }, taskContext, request.TimestampUrl, cancellationToken);
+            }
+            else
+            {
+                await SignWithPfxAsync(target, publisher, config, request, taskContext, cancellationToken);
+            }
+            taskContext.AddStatusMessage($"{UiSymbols.Lock} Signed {target.Name}");
+        }
+        taskContext.UpdateSubStatus(null);
+
+        return files;
+    }
+
+    private async Task SignWithPfxAsync(FileInfo target, string? publisher, SigningConfig? config, SigningRequest request, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var configDirectory = configService.ConfigPath.DirectoryName!;
+        var certificate = request.Certificate
+            ?? (config?.Certificate is { } configured ? new FileInfo(Path.Combine(configDirectory, configured)) : null);
+        if (certificate == null)
+        {
+            certificate = new FileInfo(Path.Combine(configDirectory, CertificateService.DefaultCertFileName));
+            if (!certificate.Exists)
+            {
+                // Inner loop: a development certificate for the publisher of the package being signed
+                await certificateService.GenerateDevCertificateWithInferenceAsync(certificate, taskContext, explicitPublisher: publisher, password: request.Password, install: request.Trust, cancellationToken: cancellationToken);
+            }
+        }
+
+        certificate.Refresh();
+        if (!certificate.Exists)
+        {
+            throw new FileNotFoundException($"Certificate file not found: {certificate.FullName}");
+        }
+
+        if (publisher != null)
+        {
+            using var signer = X509CertificateLoader.LoadPkcs12FromFile(certificate.FullName, request.Password, X509KeyStorageFlags.EphemeralKeySet);
+            EnsurePublisherMatches(target, publisher, signer);
+        }
+
+        if (request.Engine == PackagingEngine.Native)
+        {
+            await nativePackagingService.SignAsync(target, certificate, taskContext, request.Password, request.TimestampUrl, cancellationToken);
+        }
+        else
+        {
+            await certificateService.SignFileAsync(target, certificate, taskContext, request.Password, request.TimestampUrl, cancellationToken);
+        }
+    }
+
+    private static RemoteHashSigner CreateTrustedSigningSigner(SigningConfig? config, TaskContext taskContext)
+    {
+        var endpoint = Required(config?.Endpoint, "endpoint", SigningProvider.TrustedSigning);
+        var account = Required(config?.Account, "account", SigningProvider.TrustedSigning);
+        var profile = Required(config?.Profile, "profile", SigningProvider.TrustedSigning);
+        var client = new TrustedSigningClient(Http, StoreCredentials.FromAzureEnvironment(), new Uri(endpoint), account, profile)
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+        taskContext.AddDebugMessage($"{UiSymbols.Lock} Signing with Trusted Signing profile {account}/{profile}");
+        return client.SignHashAsync;
+    }
+
+    private static RemoteHashSigner CreateKeyVaultSigner(SigningConfig? config, TaskContext taskContext)
+    {
+        var vault = Required(config?.Vault, "vault", SigningProvider.KeyVault);
+        var certificateName = Required(config?.CertificateName, "certificateName", SigningProvider.KeyVault);
+        var client = new KeyVaultSigningClient(Http, StoreCredentials.FromAzureEnvironment(), new Uri(vault), certificateName)
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+        taskContext.AddDebugMessage($"{UiSymbols.Lock} Signing with Key Vault certificate {certificateName}");
+        return client.SignHashAsync;
+    }
+
+    private static string Required(string? value, string key, SigningProvider provider) =>
+        string.IsNullOrWhiteSpace(value)
+            ? throw new InvalidOperationException($"Signing with {provider} needs '{key}' under 'sign:' in winapp.yaml")
+            : value;
+
+    private static async Task<List<FileInfo>> FindAppInstallerPackagesAsync(FileInfo appInstaller, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        // App Installer files aren't signed themselves; Windows checks the signatures of the packages they install
+        var names = AppInstallerFile.ReadPackageFileNames(await File.ReadAllTextAsync(appInstaller.FullName, cancellationToken));
+        var packages = new List<FileInfo>();
+        foreach (var name in names)
+        {
+            var package = new FileInfo(Path.Combine(appInstaller.DirectoryName!, name));
+            if (package.Exists)
+            {
+                packages.Add(package);
+            }
+            else
+            {
+                taskContext.AddDebugMessage($"{UiSymbols.Skip} {name} of {appInstaller.Name} isn't next to it; skipping");
+            }
+        }
+
+        return packages.Count > 0
+            ? packages
+            : throw new InvalidOperationException($"None of the packages {appInstaller.Name} installs ({string.Join(", ", names)}) is in {appInstaller.DirectoryName}; App Installer files aren't signed themselves, so sign their packages");
+    }
+
+    /// <summary>
+    /// Throws when a certificate can't sign a package of the publisher, which Windows would refuse to install
+    /// </summary>
+    internal static void EnsurePublisherMatches(FileInfo package, string? publisher, X509Certificate2 certificate)
+    {
+        if (publisher == null || AppxPackageVerifier.PublisherMatches(publisher, certificate))
+        {
+            return;
+        }
+
+        throw new InvalidOperationException(
+            $"The certificate subject '{certificate.Subject}' doesn't match the Publisher '{publisher}' of {package.Name}; Windows only installs packages signed by their publisher. " +
+            $"Sign with a certificate issued to '{publisher}', or set the Identity Publisher in appxmanifest.xml to '{certificate.Subject}' and pack again.");
+    }
+}
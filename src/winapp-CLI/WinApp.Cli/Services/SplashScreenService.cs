@@ -0,0 +1,182 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Drawing;
+using System.Text;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class SplashScreenService : ISplashScreenService
+{
+    // Largest per-channel difference between the image's edge and the background that still looks seamless
+    internal const int EdgeTolerance = 8;
+
+    public async Task<SplashScreenReport> AnalyzeAsync(FileInfo manifestPath, SplashConfig? splash, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var manifestXml = AppxSplashScreen.Apply(await File.ReadAllTextAsync(manifestPath.FullName, Encoding.UTF8, cancellationToken), splash);
+        var root = XDocument.Parse(manifestXml).Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var manifestDirectory = manifestPath.Directory!;
+        var issues = new List<SplashScreenIssue>();
+        var applications = new List<string>();
+        var extended = splash?.Extended ?? false;
+
+        foreach (var application in Children(root, "Applications").SelectMany(a => Children(a, "Application")))
+        {
+            cancellationToken.ThrowIfCancellationRequested();
+
+            var visualElements = Children(application, "VisualElements").FirstOrDefault();
+            if (visualElements == null)
+            {
+                continue;
+            }
+
+            var id = application.Attribute("Id")?.Value ?? "App";
+            applications.Add(id);
+            taskContext.AddDebugMessage($"{UiSymbols.Search} Checking the splash screen of {id}");
+
+            // Problems only show when someone sees the splash screen: always for UWP apps, for desktop apps with an extended splash
+            var desktop = IsDesktopApp(application);
+            var severity = extended ? AccessibilitySeverity.Error : desktop ? AccessibilitySeverity.Info : AccessibilitySeverity.Warning;
+            if (desktop && !extended)
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Info, "extended", id,
+                    "Windows shows no splash screen for desktop apps, so the app's first window is its first paint; set splash.extended in winapp.yaml and show the splash with the runtime helper while the app starts"));
+            }
+
+            var splashScreen = Children(visualElements, "SplashScreen").FirstOrDefault();
+            if (splashScreen == null)
+            {
+                issues.Add(new SplashScreenIssue(severity, "missing", id,
+                    "No uap:SplashScreen; add one with 'splash:' in winapp.yaml"));
+                continue;
+            }
+
+            // Without its own color, the splash screen takes the tile background
+            var backgroundValue = splashScreen.Attribute("BackgroundColor")?.Value ?? visualElements.Attribute("BackgroundColor")?.Value;
+            Color? background = null;
+            if (backgroundValue != null && !AppxSplashScreen.IsManifestColor(backgroundValue))
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Error, "background", id, $"'{backgroundValue}' is not #RRGGBB or a color name"));
+            }
+            else if (backgroundValue == null || backgroundValue.Equals("transparent", StringComparison.OrdinalIgnoreCase))
+            {
+                issues.Add(new SplashScreenIssue(severity, "background", id,
+                    "The splash screen has no background color and is filled with the accent color, which differs between devices and from the app's first frame; set splash.backgroundColor"));
+            }
+            else
+            {
+                background = AccessibilityAuditService.ParseColor(backgroundValue);
+            }
+
+            var image = splashScreen.Attribute("Image")?.Value;
+            if (image == null)
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Error, "image", id, "uap:SplashScreen has no Image"));
+                continue;
+            }
+
+            var variants = AccessibilityAuditService.FindVariants(manifestDirectory, image, "SplashScreen", []);
+            if (variants.Count == 0)
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Error, "image", id, $"{image} not found, in any scale or variant"));
+                continue;
+            }
+
+            CheckImage(variants, background, id, issues);
+        }
+
+        return new SplashScreenReport(applications, issues);
+    }
+
+    private static void CheckImage(List<FileInfo> variants, Color? background, string id, List<SplashScreenIssue> issues)
+    {
+        var sizes = new List<(FileInfo File, Size Size)>();
+        foreach (var variant in variants)
+        {
+            try
+            {
+                using var bitmap = new Bitmap(variant.FullName);
+                sizes.Add((variant, bitmap.Size));
+            }
+            catch (ArgumentException)
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Error, "image", id, $"{variant.Name} is not an image Windows can read"));
+            }
+        }
+        if (sizes.Count == 0)
+        {
+            return;
+        }
+
+        foreach (var (file, size) in sizes)
+        {
+            // 620x300 at every scale; a pixel of rounding is fine
+            var scale = size.Width / (double)AppxSplashScreen.Width;
+            if (Math.Abs((size.Height / scale) - AppxSplashScreen.Height) > 1.5)
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Warning, "size", id,
+                    $"{file.Name} is {size.Width}x{size.Height}; splash images are {AppxSplashScreen.Width}x{AppxSplashScreen.Height} at scale 100, and other shapes are scaled to fit and letterboxed"));
+            }
+        }
+
+        if (sizes.Max(s => s.Size.Width) < AppxSplashScreen.Width * 2)
+        {
+            issues.Add(new SplashScreenIssue(AccessibilitySeverity.Info, "scale", id,
+                $"No image of {AppxSplashScreen.Width * 2}x{AppxSplashScreen.Height * 2} or larger (scale-200); the splash screen is upscaled and blurry on high DPI displays"));
+        }
+
+        if (background is Color color)
+        {
+            var largest = sizes.MaxBy(s => s.Size.Width).File;
+            if (GetEdgeColor(largest) is Color edge && !Matches(edge, color))
+            {
+                issues.Add(new SplashScreenIssue(AccessibilitySeverity.Warning, "edge", id,
+                    $"The edges of {largest.Name} are {ToHex(edge)}, not the background {ToHex(color)}, so the image shows as a box; make its background transparent or {ToHex(color)}"));
+            }
+        }
+    }
+
+    /// <returns>The most common opaque color on the border of the image, or null when most of the border is transparent</returns>
+    internal static Color? GetEdgeColor(FileInfo image)
+    {
+        using var bitmap = new Bitmap(image.FullName);
+        var border = new List<Color>();
+        var step = Math.Max(1, Math.Max(bitmap.Width, bitmap.Height) / 128);
+        for (var x = 0; x < bitmap.Width; x += step)
+        {
+            border.Add(bitmap.GetPixel(x, 0));
+            border.Add(bitmap.GetPixel(x, bitmap.Height - 1));
+        }
+        for (var y = 0; y < bitmap.Height; y += step)
+        {
+            border.Add(bitmap.GetPixel(0, y));
+            border.Add(bitmap.GetPixel(bitmap.Width - 1, y));
+        }
+
+        var opaque = border.Where(c => c.A >= 250).ToList();
+        if (opaque.Count * 2 < border.Count)
+        {
+            return null;
+        }
+
+        return opaque.GroupBy(c => c.ToArgb()).MaxBy(g => g.Count())!.First();
+    }
+
+    private static bool Matches(Color a, Color b) =>
+        Math.Abs(a.R - b.R) <= EdgeTolerance && Math.Abs(a.G - b.G) <= EdgeTolerance && Math.Abs(a.B - b.B) <= EdgeTolerance;
+
+    // Desktop apps run full trust as packaged classic apps; Windows only shows splash screens for UWP apps
+    private static bool IsDesktopApp(XElement application) =>
+        application.Attribute("EntryPoint")?.Value == "Windows.FullTrustApplication"
+        || application.Attributes().Any(a => a.Name.LocalName == "RuntimeBehavior" && a.Value is "packagedClassicApp" or "win32App");
+
+    private static IEnumerable<XElement> Children(XElement element, string localName) =>
+        element.Elements().Where(e => e.Name.LocalName == localName);
+
+    private static string ToHex(Color color) => $"#{color.R:X2}{color.G:X2}{color.B:X2}";
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreAnalyticsService : IStoreAnalyticsService
+{
+    private static readonly HttpClient Http = new();
+
+    public async Task<int> ExportAsync(StoreStatsQuery query, StoreExportFormat format, FileInfo outputPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = new PartnerCenterClient(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+
+        taskContext.AddDebugMessage($"{UiSymbols.Search} Reading {query.Report} for {query.ApplicationId} from {query.StartDate:yyyy-MM-dd} to {query.EndDate:yyyy-MM-dd} by {query.Aggregation.ToString().ToLowerInvariant()}");
+
+        var rows = await PartnerCenterAnalytics.QueryAsync(client, query, count => taskContext.UpdateSubStatus($"{count:N0} rows"), cancellationToken);
+        taskContext.UpdateSubStatus(null);
+
+        outputPath.Directory?.Create();
+        await using (var stream = File.Create(outputPath.FullName))
+        {
+            await PartnerCenterAnalytics.WriteAsync(rows, format, stream, cancellationToken);
+        }
+
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {rows.Count:N0} rows to {outputPath.FullName}");
+        return rows.Count;
+    }
+}
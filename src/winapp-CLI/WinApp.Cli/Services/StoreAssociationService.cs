@@ -0,0 +1,51 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreAssociationService(IConfigService configService) : IStoreAssociationService
+{
+    private static readonly HttpClient Http = new();
+
+    public async Task<StoreAssociation> AssociateAsync(FileInfo manifestPath, string? applicationId, string? name, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        applicationId ??= name == null ? config.Store?.AppId : null;
+
+        var client = new PartnerCenterClient(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+        var application = await StoreApplications.ResolveAsync(client, string.IsNullOrEmpty(applicationId) ? null : applicationId, name, cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Info} Associating with {application.PrimaryName} ({application.ApplicationId})");
+
+        XDocument manifest;
+        using (var stream = manifestPath.OpenRead())
+        {
+            manifest = await XDocument.LoadAsync(stream, LoadOptions.PreserveWhitespace, cancellationToken);
+        }
+        var root = manifest.Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var changes = StoreApplications.ApplyToManifest(root, application);
+        if (changes.Count > 0)
+        {
+            using var stream = manifestPath.Open(FileMode.Create, FileAccess.Write);
+            await manifest.SaveAsync(stream, SaveOptions.DisableFormatting, cancellationToken);
+        }
+
+        config.Store = new StoreConfig
+        {
+            AppId = application.ApplicationId,
+            PackageFamilyName = string.IsNullOrEmpty(application.PackageFamilyName) ? null : application.PackageFamilyName
+        };
+        configService.Save(config);
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote the association to {configService.ConfigPath.FullName}");
+
+        return new StoreAssociation(application, changes);
+    }
+}
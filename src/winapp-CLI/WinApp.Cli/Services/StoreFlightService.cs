@@ -0,0 +1,81 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreFlightService : IStoreFlightService
+{
+    private static readonly HttpClient Http = new();
+
+    public Task<List<StoreFlight>> ListAsync(string applicationId, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        StoreFlights.ListAsync(CreateClient(taskContext), applicationId, cancellationToken);
+
+    public Task<StoreFlight> ResolveAsync(string applicationId, string flightIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        StoreFlights.ResolveAsync(CreateClient(taskContext), applicationId, flightIdOrName, cancellationToken);
+
+    public async Task<StoreFlight> CreateAsync(string applicationId, string friendlyName, IReadOnlyList<string> groupIds, string? rankHigherThan, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        if (rankHigherThan != null)
+        {
+            rankHigherThan = (await StoreFlights.ResolveAsync(client, applicationId, rankHigherThan, cancellationToken)).FlightId;
+        }
+
+        taskContext.AddDebugMessage($"{UiSymbols.New} Creating flight '{friendlyName}' for group(s) {string.Join(", ", groupIds)}");
+        return await StoreFlights.CreateAsync(client, applicationId, friendlyName, groupIds, rankHigherThan, cancellationToken);
+    }
+
+    public async Task<StoreFlight> DeleteAsync(string applicationId, string flightIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        var flight = await StoreFlights.ResolveAsync(client, applicationId, flightIdOrName, cancellationToken);
+
+        taskContext.AddDebugMessage($"{UiSymbols.Trash} Deleting flight {flight.FlightId}");
+        await StoreFlights.DeleteAsync(client, applicationId, flight.FlightId, cancellationToken);
+        return flight;
+    }
+
+    public async Task<int> ExportGroupMembersAsync(IReadOnlyList<FileInfo> csvFiles, IReadOnlyList<string> aadGroupIds, FileInfo outputPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var members = new List<string>();
+        foreach (var csvFile in csvFiles)
+        {
+            var fileMembers = StoreFlights.ReadMembersCsv(await File.ReadAllTextAsync(csvFile.FullName, cancellationToken));
+            taskContext.AddDebugMessage($"{UiSymbols.Files} {fileMembers.Count:N0} address(es) in {csvFile.Name}");
+            members.AddRange(fileMembers);
+        }
+
+        if (aadGroupIds.Count > 0)
+        {
+            var graphClient = new PartnerCenterClient(Http, StoreCredentials.FromEnvironment())
+            {
+                ServiceUri = StoreFlights.GraphServiceUri,
+                Resource = StoreFlights.GraphResource,
+                OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+            };
+            foreach (var groupId in aadGroupIds)
+            {
+                var groupMembers = await StoreFlights.GetAadGroupMembersAsync(graphClient, groupId, cancellationToken);
+                taskContext.AddDebugMessage($"{UiSymbols.User} {groupMembers.Count:N0} user(s) in Azure AD group {groupId}");
+                members.AddRange(groupMembers);
+            }
+        }
+
+        var csv = StoreFlights.ToMembersCsv(members);
+        outputPath.Directory?.Create();
+        await File.WriteAllTextAsync(outputPath.FullName, csv, new UTF8Encoding(encoderShouldEmitUTF8Identifier: false), cancellationToken);
+
+        return csv.Split("\r\n", StringSplitOptions.RemoveEmptyEntries).Length;
+    }
+
+    private static PartnerCenterClient CreateClient(TaskContext taskContext) =>
+        new(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+}
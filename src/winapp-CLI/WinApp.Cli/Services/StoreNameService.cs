@@ -0,0 +1,51 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreNameService : IStoreNameService
+{
+    private static readonly HttpClient Http = new();
+
+    public Task<List<StoreApplication>> ListAsync(TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        StoreApplications.ListAsync(CreateClient(taskContext), cancellationToken);
+
+    public async Task<(StoreApplication Application, bool Reserved)> ReserveAsync(string name, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+
+        // Reserving again is a no-op, so init can be re-run
+        var existing = (await StoreApplications.ListAsync(client, cancellationToken))
+            .FirstOrDefault(a => string.Equals(a.PrimaryName, name, StringComparison.OrdinalIgnoreCase));
+        if (existing != null)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Skip} '{existing.PrimaryName}' is reserved already by {existing.ApplicationId}");
+            return (existing, false);
+        }
+
+        return (await StoreApplications.ReserveAsync(client, name, cancellationToken), true);
+    }
+
+    public async Task<StoreApplication> ReleaseAsync(string applicationIdOrName, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        var applications = await StoreApplications.ListAsync(client, cancellationToken);
+        var application = applications.FirstOrDefault(a => string.Equals(a.ApplicationId, applicationIdOrName, StringComparison.OrdinalIgnoreCase))
+            ?? applications.FirstOrDefault(a => string.Equals(a.PrimaryName, applicationIdOrName, StringComparison.OrdinalIgnoreCase))
+            ?? throw new InvalidOperationException($"The account has no app '{applicationIdOrName}'. Apps: {string.Join(", ", applications.Select(a => $"{a.PrimaryName} ({a.ApplicationId})"))}");
+
+        taskContext.AddDebugMessage($"{UiSymbols.Trash} Releasing '{application.PrimaryName}' ({application.ApplicationId})");
+        await StoreApplications.ReleaseAsync(client, application.ApplicationId, cancellationToken);
+        return application;
+    }
+
+    private static PartnerCenterClient CreateClient(TaskContext taskContext) =>
+        new(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreReviewService : IStoreReviewService
+{
+    private static readonly HttpClient Http = new();
+
+    public async Task<List<JsonObject>> ListAsync(StoreReviewQuery query, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        taskContext.AddDebugMessage($"{UiSymbols.Search} Reading reviews of {query.ApplicationId} from {query.StartDate:yyyy-MM-dd} to {query.EndDate:yyyy-MM-dd}{(StoreReviews.BuildFilter(query) is string filter ? $" where {filter}" : "")}");
+
+        var reviews = await StoreReviews.ListAsync(client, query, count => taskContext.UpdateSubStatus($"{count:N0} reviews"), cancellationToken);
+        taskContext.UpdateSubStatus(null);
+        return reviews;
+    }
+
+    public async Task<List<StoreReviewResponseResult>> RespondAsync(string applicationId, IReadOnlyList<(string ReviewId, string Text)> responses, string? supportEmail, bool isPublic, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        taskContext.AddDebugMessage($"{UiSymbols.Note} Posting {responses.Count} {(isPublic ? "public" : "private")} response(s) to reviews of {applicationId}");
+
+        return await StoreReviews.RespondAsync(client, applicationId, responses, supportEmail, isPublic, cancellationToken);
+    }
+
+    private static PartnerCenterClient CreateClient(TaskContext taskContext) =>
+        new(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+}
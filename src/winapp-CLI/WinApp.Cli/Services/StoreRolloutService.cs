@@ -0,0 +1,61 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreRolloutService : IStoreRolloutService
+{
+    private static readonly TimeSpan StatusPollInterval = TimeSpan.FromSeconds(30);
+
+    private static readonly HttpClient Http = new();
+
+    public async Task<StoreRolloutStatus> GetStatusAsync(string applicationId, string? submissionId, bool wait, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var client = CreateClient(taskContext);
+        submissionId = await StoreRollout.ResolveSubmissionIdAsync(client, applicationId, submissionId, cancellationToken);
+
+        while (true)
+        {
+            var status = await StoreRollout.GetStatusAsync(client, applicationId, submissionId, cancellationToken);
+            if (!wait || StoreRollout.IsFinal(status.SubmissionStatus))
+            {
+                taskContext.UpdateSubStatus(null);
+                return status;
+            }
+
+            taskContext.UpdateSubStatus($"Waiting for the Store to process submission {submissionId} ({status.SubmissionStatus})");
+            await Task.Delay(StatusPollInterval, cancellationToken);
+        }
+    }
+
+    public Task<StoreRolloutStatus> SetPercentageAsync(string applicationId, string? submissionId, double percentage, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        UpdateAsync(applicationId, submissionId, $"Rolling out to {percentage:0.##}%", (client, id) => StoreRollout.SetPercentageAsync(client, applicationId, id, percentage, cancellationToken), taskContext, cancellationToken);
+
+    public Task<StoreRolloutStatus> HaltAsync(string applicationId, string? submissionId, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        UpdateAsync(applicationId, submissionId, "Halting the rollout", (client, id) => StoreRollout.HaltAsync(client, applicationId, id, cancellationToken), taskContext, cancellationToken);
+
+    public Task<StoreRolloutStatus> FinalizeAsync(string applicationId, string? submissionId, TaskContext taskContext, CancellationToken cancellationToken = default) =>
+        UpdateAsync(applicationId, submissionId, "Finalizing the rollout", (client, id) => StoreRollout.FinalizeAsync(client, applicationId, id, cancellationToken), taskContext, cancellationToken);
+
+    private static async Task<StoreRolloutStatus> UpdateAsync(string applicationId, string? submissionId, string description, Func<PartnerCenterClient, string, Task<StorePackageRollout>> update, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var client = CreateClient(taskContext);
+        submissionId = await StoreRollout.ResolveSubmissionIdAsync(client, applicationId, submissionId, cancellationToken);
+        taskContext.AddDebugMessage($"{UiSymbols.Sync} {description} of submission {submissionId}");
+
+        await update(client, submissionId);
+
+        // Read back the rollout, because the update endpoints do not always return every property
+        return await StoreRollout.GetStatusAsync(client, applicationId, submissionId, cancellationToken);
+    }
+
+    private static PartnerCenterClient CreateClient(TaskContext taskContext) =>
+        new(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+}
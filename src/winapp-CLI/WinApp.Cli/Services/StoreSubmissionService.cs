@@ -0,0 +1,454 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.IO.Compression;
+using System.Net;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Services;
+
+internal class StoreSubmissionService(IWinappDirectoryService winappDirectoryService) : IStoreSubmissionService
+{
+    private const string SubmissionsDirectoryName = "store";
+    private const string PendingCommitStatus = "PendingCommit";
+    private const string CommitStartedStatus = "CommitStarted";
+    private const string CommitFailedStatus = "CommitFailed";
+    private const string ApplicationPackagesProperty = "applicationPackages";
+    private const string FlightPackagesProperty = "flightPackages";
+
+    private static readonly TimeSpan StatusPollInterval = TimeSpan.FromSeconds(30);
+
+    // Blocks are sent with their own retries, so the client timeout only has to cover one block on a slow link
+    private static readonly HttpClient Http = new() { Timeout = TimeSpan.FromMinutes(10) };
+
+    private static readonly string[] PackageExtensions = [".msix", ".msixbundle", ".msixupload", ".appx", ".appxbundle", ".appxupload"];
+
+    public async Task<StoreSubmissionResult> SubmitAsync(string applicationId, IReadOnlyList<FileInfo> packages, StoreSubmitOptions options, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        ValidatePackages(packages);
+
+        var client = new PartnerCenterClient(Http, StoreCredentials.FromEnvironment())
+        {
+            OnRetry = message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}")
+        };
+        // Flight submissions have the same life cycle as app submissions, one level further down
+        var parentPath = $"applications/{Uri.EscapeDataString(applicationId)}";
+        if (options.FlightId != null)
+        {
+            parentPath += $"/flights/{Uri.EscapeDataString(options.FlightId)}";
+        }
+        var submissionsPath = $"{parentPath}/submissions";
+
+        var stateFile = GetStateFile(applicationId, options.FlightId);
+        var savedState = options.Restart ? null : await LoadStateAsync(stateFile, taskContext, cancellationToken);
+
+        StoreSubmissionState? state = null;
+        JsonNode? submission = null;
+        if (savedState != null && savedState.Matches(applicationId, packages))
+        {
+            submission = await TryGetSubmissionAsync(client, $"{submissionsPath}/{savedState.SubmissionId}", cancellationToken);
+            var status = submission?["status"]?.GetValue<string>();
+            if (status == PendingCommitStatus || (savedState.CommitStarted && status != null))
+            {
+                state = savedState;
+                state.FileUploadUrl = submission?["fileUploadUrl"]?.GetValue<string>() ?? state.FileUploadUrl;
+                taskContext.AddStatusMessage($"{UiSymbols.Sync} Resuming submission {state.SubmissionId} from an earlier run");
+            }
+            else
+            {
+                taskContext.AddDebugMessage($"Saved submission {savedState.SubmissionId} is {status ?? "gone"}; starting a new submission");
+            }
+        }
+
+        var resumed = state != null;
+        if (state == null)
+        {
+            submission = await CreateSubmissionAsync(client, parentPath, options.FlightId, savedState?.SubmissionId, options.ReplacePending, taskContext, cancellationToken);
+            state = new StoreSubmissionState
+            {
+                ApplicationId = applicationId,
+                SubmissionId = submission["id"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center did not return a submission ID"),
+                FileUploadUrl = submission["fileUploadUrl"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center did not return an upload URL"),
+                Packages = packages.Select(StoreSubmissionPackage.FromFile).ToList(),
+                CreatedAt = DateTimeOffset.UtcNow,
+            };
+            await SaveStateAsync(stateFile, state, cancellationToken);
+            taskContext.AddStatusMessage($"{UiSymbols.New} Created submission {state.SubmissionId}");
+        }
+
+        var submissionPath = $"{submissionsPath}/{state.SubmissionId}";
+
+        if (!state.PackagesUpdated)
+        {
+            UpdatePackages(submission!, packages, options.ReplacePackages, options.FlightId != null ? FlightPackagesProperty : ApplicationPackagesProperty);
+            if (options.RolloutPercentage is double rolloutPercentage)
+            {
+                StoreRollout.Apply(submission!, rolloutPercentage);
+            }
+            if (options.ReleaseNotes != null)
+            {
+                var languages = ApplyReleaseNotes(submission!, options.ReleaseNotes);
+                taskContext.AddDebugMessage($"{UiSymbols.Note} Set the release notes of {string.Join(", ", languages)}");
+            }
+            await client.PutAsync(submissionPath, submission!, cancellationToken);
+            state.PackagesUpdated = true;
+            await SaveStateAsync(stateFile, state, cancellationToken);
+            taskContext.AddDebugMessage($"{UiSymbols.Check} Added {packages.Count} package(s) to the submission");
+        }
+
+        BlockBlobUploadResult? upload = null;
+        if (!state.Uploaded)
+        {
+            upload = await UploadPackagesAsync(client, submissionPath, state, stateFile, packages, options, taskContext, cancellationToken);
+            state.Uploaded = true;
+            await SaveStateAsync(stateFile, state, cancellationToken);
+        }
+
+        if (!options.Commit)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Note} Submission {state.SubmissionId} was not committed. Run the command again without --no-commit, or commit it in Partner Center.");
+            return new StoreSubmissionResult(state.SubmissionId, PendingCommitStatus, resumed, upload);
+        }
+
+        if (!state.CommitStarted)
+        {
+            await client.PostAsync($"{submissionPath}/commit", null, cancellationToken);
+            state.CommitStarted = true;
+            await SaveStateAsync(stateFile, state, cancellationToken);
+        }
+
+        var finalStatus = await GetStatusAsync(client, submissionPath, options.Wait, taskContext, cancellationToken);
+
+        // The commit was accepted (or rejected for reasons a retry will not fix), so there is nothing left to resume
+        DeleteState(stateFile, taskContext);
+
+        return new StoreSubmissionResult(state.SubmissionId, finalStatus, resumed, upload);
+    }
+
+    private static void ValidatePackages(IReadOnlyList<FileInfo> packages)
+    {
+        if (packages.Count == 0)
+        {
+            throw new InvalidOperationException("At least one package is required");
+        }
+
+        foreach (var package in packages)
+        {
+            package.Refresh();
+            if (!package.Exists)
+            {
+                throw new FileNotFoundException($"Package not found: {package.FullName}");
+            }
+            if (!PackageExtensions.Contains(package.Extension, StringComparer.OrdinalIgnoreCase))
+            {
+                throw new InvalidOperationException($"{package.Name} is not a package the Store accepts ({string.Join(", ", PackageExtensions)})");
+            }
+        }
+
+        var duplicate = packages.GroupBy(p => p.Name, StringComparer.OrdinalIgnoreCase).FirstOrDefault(g => g.Count() > 1);
+        if (duplicate != null)
+        {
+            throw new InvalidOperationException($"More than one package is named {duplicate.Key}; package file names must be unique within a submission");
+        }
+    }
+
+    /// <param name="parentPath">Path of the application, or of one of its package flights</param>
+    private static async Task<JsonNode> CreateSubmissionAsync(PartnerCenterClient client, string parentPath, string? flightId, string? previousSubmissionId, bool replacePending, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var pendingProperty = flightId != null ? "pendingFlightSubmission" : "pendingApplicationSubmission";
+        var parent = await client.GetAsync(parentPath, cancellationToken)
+            ?? throw new InvalidOperationException($"Partner Center returned no data for {parentPath}");
+
+        var pendingId = parent[pendingProperty]?["id"]?.GetValue<string>();
+        if (pendingId != null)
+        {
+            // A submission this command created but never committed is safe to throw away
+            if (!replacePending && pendingId != previousSubmissionId)
+            {
+                throw new InvalidOperationException($"{(flightId != null ? $"Flight {flightId}" : "The application")} already has a pending submission ({pendingId}). Commit or delete it in Partner Center, or use --replace-pending to delete it.");
+            }
+
+            taskContext.AddStatusMessage($"{UiSymbols.Trash} Deleting pending submission {pendingId}");
+            await client.DeleteAsync($"{parentPath}/submissions/{pendingId}", cancellationToken);
+        }
+
+        try
+        {
+            return await client.PostAsync($"{parentPath}/submissions", null, cancellationToken)
+                ?? throw new InvalidOperationException("Partner Center did not return the new submission");
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode == HttpStatusCode.Conflict)
+        {
+            // A create that timed out may still have succeeded, in which case the retry conflicts with it
+            parent = await client.GetAsync(parentPath, cancellationToken);
+            var createdId = parent?[pendingProperty]?["id"]?.GetValue<string>();
+            if (createdId == null)
+            {
+                throw;
+            }
+
+            taskContext.AddDebugMessage($"Using submission {createdId} created by a request that timed out");
+            return await client.GetAsync($"{parentPath}/submissions/{createdId}", cancellationToken)
+                ?? throw new InvalidOperationException($"Partner Center returned no data for submission {createdId}");
+        }
+    }
+
+    private static async Task<JsonNode?> TryGetSubmissionAsync(PartnerCenterClient client, string submissionPath, CancellationToken cancellationToken)
+    {
+        try
+        {
+            return await client.GetAsync(submissionPath, cancellationToken);
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode == HttpStatusCode.NotFound)
+        {
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Adds the packages to the submission as PendingUpload, replacing entries with the same file name
+    /// </summary>
+    /// <param name="packagesProperty">applicationPackages for app submissions, flightPackages for flight submissions</param>
+    internal static void UpdatePackages(JsonNode submission, IReadOnlyList<FileInfo> packages, bool replacePackages, string packagesProperty = ApplicationPackagesProperty)
+    {
+        var submissionPackages = submission[packagesProperty] as JsonArray ?? [];
+        submission[packagesProperty] = submissionPackages;
+
+        var names = packages.Select(p => p.Name).ToHashSet(StringComparer.OrdinalIgnoreCase);
+        for (var i = submissionPackages.Count - 1; i >= 0; i--)
+        {
+            if (submissionPackages[i] is not JsonObject existing)
+            {
+                continue;
+            }
+
+            if (names.Contains(existing["fileName"]?.GetValue<string>() ?? string.Empty))
+            {
+                submissionPackages.RemoveAt(i);
+            }
+            else if (replacePackages)
+            {
+                existing["fileStatus"] = "PendingDelete";
+            }
+        }
+
+        foreach (var package in packages)
+        {
+            submissionPackages.Add(new JsonObject
+            {
+                ["fileName"] = package.Name,
+                ["fileStatus"] = "PendingUpload",
+                ["minimumDirectXVersion"] = "None",
+                ["minimumSystemRam"] = "None",
+            });
+        }
+    }
+
+    /// <summary>
+    /// Sets the release notes of every language of the submission's listing
+    /// </summary>
+    /// <returns>Languages whose release notes were set</returns>
+    internal static IReadOnlyList<string> ApplyReleaseNotes(JsonNode submission, string releaseNotes)
+    {
+        if (submission["listings"] is not JsonObject listings || listings.Count == 0)
+        {
+            throw new InvalidOperationException("The submission has no listings to add release notes to");
+        }
+
+        var languages = new List<string>();
+        foreach (var (language, listing) in listings)
+        {
+            if (listing is not JsonObject listingObject)
+            {
+                continue;
+            }
+
+            var baseListing = listingObject["baseListing"] as JsonObject ?? new JsonObject();
+            listingObject["baseListing"] = baseListing;
+            baseListing["releaseNotes"] = releaseNotes;
+            languages.Add(language);
+        }
+
+        return languages;
+    }
+
+    private async Task<BlockBlobUploadResult> UploadPackagesAsync(PartnerCenterClient client, string submissionPath, StoreSubmissionState state, FileInfo stateFile, IReadOnlyList<FileInfo> packages, StoreSubmitOptions options, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var archive = new FileInfo(Path.Combine(stateFile.DirectoryName!, $"{state.ApplicationId}-{state.SubmissionId}.zip"));
+        archive.Refresh();
+        if (!archive.Exists)
+        {
+            taskContext.UpdateSubStatus("Preparing upload");
+            await CreateUploadArchiveAsync(archive, packages, cancellationToken);
+            archive.Refresh();
+        }
+
+        if (state.BlockSize == null)
+        {
+            state.BlockSize = BlockBlobUploader.GetBlockSize(archive.Length, options.BlockSize);
+            await SaveStateAsync(stateFile, state, cancellationToken);
+        }
+
+        var uploader = new BlockBlobUploader(Http);
+        var uploadOptions = new BlockBlobUploadOptions { BlockSize = state.BlockSize.Value, Concurrency = options.Concurrency };
+        var progress = new UploadProgress(archive.Length, taskContext);
+        void OnRetry(string message) => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}");
+
+        BlockBlobUploadResult result;
+        try
+        {
+            result = await uploader.UploadAsync(archive, new Uri(state.FileUploadUrl), uploadOptions, progress, OnRetry, cancellationToken);
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode == HttpStatusCode.Forbidden)
+        {
+            // The SAS URL expired, typically because the upload is resumed days later; the submission hands out a fresh one
+            taskContext.AddDebugMessage("Upload URL was rejected; requesting a new one");
+            var submission = await client.GetAsync(submissionPath, cancellationToken);
+            state.FileUploadUrl = submission?["fileUploadUrl"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center did not return an upload URL", ex);
+            await SaveStateAsync(stateFile, state, cancellationToken);
+            result = await uploader.UploadAsync(archive, new Uri(state.FileUploadUrl), uploadOptions, progress, OnRetry, cancellationToken);
+        }
+        finally
+        {
+            taskContext.UpdateSubStatus(null);
+        }
+
+        if (result.ResumedBlocks > 0)
+        {
+            taskContext.AddStatusMessage($"{UiSymbols.Sync} Resumed upload: {result.ResumedBlocks:N0} of {result.BlockCount:N0} blocks were already uploaded");
+        }
+        taskContext.AddStatusMessage($"{UiSymbols.Timer} Uploaded {result.Length / 1048576.0:N1} MB in {result.Elapsed.TotalSeconds:N1}s");
+
+        try
+        {
+            archive.Delete();
+        }
+        catch (IOException)
+        {
+            // Only wastes disk space; the next upload for this app uses a different submission ID
+        }
+
+        return result;
+    }
+
+    /// <summary>
+    /// Zips the packages without compression. Entry timestamps come from the package files, so the archive is
+    /// byte-for-byte identical if it has to be recreated, and uploaded blocks stay valid.
+    /// </summary>
+    private static async Task CreateUploadArchiveAsync(FileInfo archive, IReadOnlyList<FileInfo> packages, CancellationToken cancellationToken)
+    {
+        archive.Directory?.Create();
+        var temporaryPath = archive.FullName + ".tmp";
+        await Task.Run(() =>
+        {
+            using (var zip = ZipFile.Open(temporaryPath, ZipArchiveMode.Create))
+            {
+                foreach (var package in packages)
+                {
+                    cancellationToken.ThrowIfCancellationRequested();
+                    zip.CreateEntryFromFile(package.FullName, package.Name, CompressionLevel.NoCompression);
+                }
+            }
+            File.Move(temporaryPath, archive.FullName, overwrite: true);
+        }, cancellationToken);
+    }
+
+    private static async Task<string> GetStatusAsync(PartnerCenterClient client, string submissionPath, bool wait, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        while (true)
+        {
+            var status = await client.GetAsync($"{submissionPath}/status", cancellationToken);
+            var value = status?["status"]?.GetValue<string>() ?? "Unknown";
+
+            if (value == CommitFailedStatus)
+            {
+                var errors = (status?["statusDetails"]?["errors"] as JsonArray ?? [])
+                    .Select(e => $"{e?["code"]?.GetValue<string>()}: {e?["details"]?.GetValue<string>()}")
+                    .ToList();
+                foreach (var error in errors)
+                {
+                    taskContext.AddStatusMessage($"{UiSymbols.Error} {error}");
+                }
+                throw new InvalidOperationException($"The Store rejected the submission commit{(errors.Count > 0 ? $": {errors[0]}" : "")}");
+            }
+
+            if (!wait || value != CommitStartedStatus)
+            {
+                return value;
+            }
+
+            taskContext.UpdateSubStatus($"Waiting for the Store to process the commit ({value})");
+            await Task.Delay(StatusPollInterval, cancellationToken);
+        }
+    }
+
+    private FileInfo GetStateFile(string applicationId, string? flightId) =>
+        new(Path.Combine(winappDirectoryService.GetGlobalWinappDirectory().FullName, SubmissionsDirectoryName, flightId == null ? $"{applicationId}.json" : $"{applicationId}-flight-{flightId}.json"));
+
+    private static async Task<StoreSubmissionState?> LoadStateAsync(FileInfo stateFile, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        stateFile.Refresh();
+        if (!stateFile.Exists)
+        {
+            return null;
+        }
+
+        try
+        {
+            await using var stream = stateFile.OpenRead();
+            return await JsonSerializer.DeserializeAsync(stream, StoreJsonContext.Default.StoreSubmissionState, cancellationToken);
+        }
+        catch (JsonException ex)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Warning} Ignoring unreadable submission state {stateFile.FullName}: {ex.Message}");
+            return null;
+        }
+    }
+
+    private static async Task SaveStateAsync(FileInfo stateFile, StoreSubmissionState state, CancellationToken cancellationToken)
+    {
+        stateFile.Directory?.Create();
+
+        // Write then move, so a run killed mid-write does not leave a truncated state file behind
+        var temporaryPath = stateFile.FullName + ".tmp";
+        await using (var stream = File.Create(temporaryPath))
+        {
+            await JsonSerializer.SerializeAsync(stream, state, StoreJsonContext.Default.StoreSubmissionState, cancellationToken);
+        }
+        File.Move(temporaryPath, stateFile.FullName, overwrite: true);
+    }
+
+    private static void DeleteState(FileInfo stateFile, TaskContext taskContext)
+    {
+        try
+        {
+            stateFile.Refresh();
+            if (stateFile.Exists)
+            {
+                stateFile.Delete();
+            }
+        }
+        catch (IOException ex)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Warning} Failed to delete {stateFile.FullName}: {ex.Message}");
+        }
+    }
+
+    private sealed class UploadProgress(long total, TaskContext taskContext) : IProgress<long>
+    {
+        private int lastPercent = -1;
+
+        public void Report(long value)
+        {
+            var percent = total == 0 ? 100 : (int)(value * 100 / total);
+            if (Interlocked.Exchange(ref lastPercent, percent) != percent)
+            {
+                taskContext.UpdateSubStatus($"Uploading {percent}% ({value / 1048576.0:N0} of {total / 1048576.0:N0} MB)");
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Diagnostics;
+using System.Globalization;
+using System.Security.Cryptography.X509Certificates;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Distribution;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.TestMatrix;
+
+namespace WinApp.Cli.Services;
+
+internal class TestMatrixService(IConfigService configService, IPowerShellService powerShellService) : ITestMatrixService
+{
+    private static readonly HttpClient Http = new() { Timeout = TimeSpan.FromMinutes(10) };
+
+    public List<TestTargetConfig> GetTargets(IReadOnlyList<string> names)
+    {
+        var targets = configService.Exists() ? configService.Load().TestMatrix?.Targets ?? [] : [];
+        if (targets.Count == 0)
+        {
+            throw new InvalidOperationException("winapp.yaml declares no test targets; add them under 'testMatrix:'");
+        }
+
+        var duplicate = targets.GroupBy(t => t.Name, StringComparer.OrdinalIgnoreCase).FirstOrDefault(g => g.Count() > 1);
+        if (duplicate != null)
+        {
+            throw new InvalidOperationException($"winapp.yaml: the test matrix has several targets named '{duplicate.Key}'");
+        }
+
+        var unknown = names.Where(n => !targets.Any(t => t.Name.Equals(n, StringComparison.OrdinalIgnoreCase))).ToList();
+        if (unknown.Count > 0)
+        {
+            throw new InvalidOperationException($"No test target is named {string.Join(", ", unknown)}. Targets: {string.Join(", ", targets.Select(t => t.Name))}");
+        }
+
+        var selected = names.Count == 0
+            ? targets
+            : [.. targets.Where(t => names.Contains(t.Name, StringComparer.OrdinalIgnoreCase))];
+        foreach (var target in selected)
+        {
+            if (target.Provider is not (TestTargetConfig.HyperV or TestTargetConfig.Azure))
+            {
+                throw new InvalidOperationException($"winapp.yaml: test target '{target.Name}' has an unknown provider '{target.Provider}'; use {TestTargetConfig.HyperV} or {TestTargetConfig.Azure}");
+            }
+            if (string.IsNullOrWhiteSpace(target.Image))
+            {
+                throw new InvalidOperationException($"winapp.yaml: test target '{target.Name}' has no image");
+            }
+            if (target.Provider == TestTargetConfig.Azure && (string.IsNullOrWhiteSpace(target.Lab) || string.IsNullOrWhiteSpace(target.ResourceGroup)))
+            {
+                throw new InvalidOperationException($"winapp.yaml: test target '{target.Name}' needs the lab and resourceGroup of its Azure DevTest Labs lab");
+            }
+        }
+        return selected;
+    }
+
+    public async Task<IReadOnlyList<TestTargetResult>> RunAsync(TestMatrixOptions options, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var targets = GetTargets(options.Targets);
+        var matrix = configService.Load().TestMatrix!;
+        var configDirectory = configService.ConfigPath.Directory!;
+
+        if (string.IsNullOrEmpty(Environment.GetEnvironmentVariable(TestMachineScript.UserNameVariable))
+            || string.IsNullOrEmpty(Environment.GetEnvironmentVariable(TestMachineScript.PasswordVariable)))
+        {
+            throw new InvalidOperationException($"Set {TestMachineScript.UserNameVariable} and {TestMachineScript.PasswordVariable} to the administrator account of the test machines");
+        }
+
+        foreach (var file in options.Dependencies.Prepend(options.Package))
+        {
+            if (!file.Exists)
+            {
+                throw new FileNotFoundException($"Package not found: {file.FullName}");
+            }
+        }
+
+        var smokeTest = options.SmokeTest
+            ?? (matrix.SmokeTest is { } smokeTestPath ? new FileInfo(Path.Combine(configDirectory.FullName, smokeTestPath)) : null);
+        if (smokeTest is { Exists: false })
+        {
+            throw new FileNotFoundException($"Smoke test not found: {smokeTest.FullName}");
+        }
+
+        var package = await DistributionPackage.ReadAsync(options.Package, cancellationToken);
+        if (package.SignerCertificate == null)
+        {
+            throw new InvalidOperationException($"{options.Package.Name} is not signed, so it can't be installed; sign it with 'winapp sign' first");
+        }
+
+        var stagingDirectory = new DirectoryInfo(Path.Combine(Path.GetTempPath(), $"winapp-test-{Guid.NewGuid():N}"));
+        stagingDirectory.Create();
+        try
+        {
+            List<FileInfo> files = [options.Package, .. options.Dependencies];
+            string? certificate = null;
+            if (package.IsSelfSigned)
+            {
+                // The machines trust the signer the way a tester's machine would, through the public certificate
+                var certificateFile = new FileInfo(Path.Combine(stagingDirectory.FullName, $"{package.Name}.cer"));
+                await File.WriteAllBytesAsync(certificateFile.FullName, package.SignerCertificate.Export(X509ContentType.Cert), cancellationToken);
+                files.Add(certificateFile);
+                certificate = certificateFile.Name;
+            }
+            if (smokeTest != null)
+            {
+                files.Add(smokeTest);
+            }
+
+            var script = TestMachineScript.Create(package.Name, options.Package.Name, [.. options.Dependencies.Select(d => d.Name)], certificate, smokeTest?.Name);
+            var providers = new Dictionary<string, ITestMachineProvider>
+            {
+                [TestTargetConfig.HyperV] = new HyperVTestMachineProvider(powerShellService, configDirectory, new DirectoryInfo(Path.Combine(Path.GetTempPath(), "winapp-test-matrix"))),
+                [TestTargetConfig.Azure] = new AzureLabTestMachineProvider(
+                    powerShellService,
+                    Uri.TryCreate(Environment.GetEnvironmentVariable(AzureLabTestMachineProvider.ArtifactsVariable), UriKind.Absolute, out var artifacts) ? artifacts : null,
+                    Http),
+            };
+
+            var timeout = TimeSpan.FromMinutes(matrix.Timeout);
+            var results = new TestTargetResult?[targets.Count];
+            await Parallel.ForEachAsync(
+                Enumerable.Range(0, targets.Count),
+                new ParallelOptions { MaxDegreeOfParallelism = Math.Max(1, options.Parallel), CancellationToken = cancellationToken },
+                async (index, cancellationToken) =>
+                {
+                    var target = targets[index];
+                    await taskContext.AddSubTaskAsync($"Testing {target.Name}", async (taskContext, cancellationToken) =>
+                    {
+                        var result = await TestTargetAsync(providers[target.Provider], target, files, script, smokeTest != null, timeout, options.KeepMachines, taskContext, cancellationToken);
+                        results[index] = result;
+                        return result.Passed
+                            ? $"{target.Name} passed in {FormatDuration(result.Duration)}"
+                            : $"{UiSymbols.Error} {target.Name} failed at {FormatStage(result.FailedStage!.Value)} after {FormatDuration(result.Duration)}";
+                    }, cancellationToken);
+                });
+
+            return [.. results.OfType<TestTargetResult>()];
+        }
+        finally
+        {
+            stagingDirectory.Delete(recursive: true);
+        }
+    }
+
+    private static async Task<TestTargetResult> TestTargetAsync(ITestMachineProvider provider, TestTargetConfig target, IReadOnlyList<FileInfo> files, string script, bool hasSmokeTest, TimeSpan timeout, bool keepMachine, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var stopwatch = Stopwatch.StartNew();
+        var machine = new TestMachine(target, CreateMachineName(target.Name));
+        using var timeoutSource = CancellationTokenSource.CreateLinkedTokenSource(cancellationToken);
+        timeoutSource.CancelAfter(timeout);
+
+        var stage = TestMatrixStage.Provision;
+        TestTargetResult Fail(string error, string output = "") =>
+            new(target.Name, provider.Name, stage, error, stopwatch.Elapsed, output, keepMachine ? machine.Name : null);
+        try
+        {
+            taskContext.UpdateSubStatus($"provisioning {machine.Name} with {provider.Name}");
+            await provider.ProvisionAsync(machine, timeout, taskContext, timeoutSource.Token);
+
+            stage = TestMatrixStage.Deploy;
+            taskContext.UpdateSubStatus("copying the package");
+            await provider.CopyAsync(machine, files, taskContext, timeoutSource.Token);
+
+            taskContext.UpdateSubStatus(hasSmokeTest ? "installing and running the smoke test" : "installing");
+            var output = (await provider.RunAsync(machine, script, taskContext, timeoutSource.Token)).Trim();
+            var (failedStage, error) = TestMachineScript.ParseResult(output, hasSmokeTest);
+            return new TestTargetResult(target.Name, provider.Name, failedStage, error, stopwatch.Elapsed, output, keepMachine ? machine.Name : null);
+        }
+        catch (OperationCanceledException) when (!cancellationToken.IsCancellationRequested)
+        {
+            return Fail($"Timed out after {timeout.TotalMinutes:0} minutes");
+        }
+        catch (Exception ex) when (ex is not OperationCanceledException)
+        {
+            taskContext.AddDebugMessage($"Stack Trace: {ex.StackTrace}");
+            return Fail(ex.Message);
+        }
+        finally
+        {
+            taskContext.UpdateSubStatus(null);
+            if (!keepMachine)
+            {
+                try
+                {
+                    // Machines cost money and disk space, so they are deleted even when the run is cancelled
+                    await provider.DeleteAsync(machine, taskContext, CancellationToken.None);
+                }
+                catch (Exception ex)
+                {
+                    taskContext.AddStatusMessage($"{UiSymbols.Warning} Couldn't delete {machine.Name}: {ex.Message}");
+                }
+            }
+        }
+    }
+
+    /// <summary>
+    /// Names the machine of a target, unique to the run
+    /// </summary>
+    internal static string CreateMachineName(string target)
+    {
+        // Azure names the Windows computer after the machine, and computer names have at most 15 characters
+        var name = new string([.. target.ToLowerInvariant().Where(char.IsAsciiLetterOrDigit)]);
+        return $"wa{name[..Math.Min(name.Length, 8)]}{Random.Shared.Next(0x10000):x4}";
+    }
+
+    /// <summary>
+    /// Creates a JUnit XML report of the results, which CI systems show as one test per target
+    /// </summary>
+    internal static XDocument CreateJUnitReport(IReadOnlyList<TestTargetResult> results)
+    {
+        static string Seconds(TimeSpan duration) => duration.TotalSeconds.ToString("0.###", CultureInfo.InvariantCulture);
+
+        var failures = results.Count(r => !r.Passed);
+        var time = Seconds(TimeSpan.FromTicks(results.Sum(r => r.Duration.Ticks)));
+        var suite = new XElement("testsuite",
+            new XAttribute("name", "winapp test matrix"),
+            new XAttribute("tests", results.Count),
+            new XAttribute("failures", failures),
+            new XAttribute("time", time));
+        foreach (var result in results)
+        {
+            var testCase = new XElement("testcase",
+                new XAttribute("classname", result.Provider),
+                new XAttribute("name", result.Target),
+                new XAttribute("time", Seconds(result.Duration)));
+            if (!result.Passed)
+            {
+                testCase.Add(new XElement("failure",
+                    new XAttribute("type", FormatStage(result.FailedStage!.Value)),
+                    new XAttribute("message", result.Error ?? ""),
+                    result.Output));
+            }
+            else if (result.Output.Length > 0)
+            {
+                testCase.Add(new XElement("system-out", result.Output));
+            }
+            suite.Add(testCase);
+        }
+
+        return new XDocument(new XElement("testsuites",
+            new XAttribute("tests", results.Count),
+            new XAttribute("failures", failures),
+            new XAttribute("time", time),
+            suite));
+    }
+
+    internal static string FormatStage(TestMatrixStage stage) => stage switch
+    {
+        TestMatrixStage.Provision => "provisioning",
+        TestMatrixStage.Deploy => "install",
+        _ => "smoke test",
+    };
+
+    private static string FormatDuration(TimeSpan duration) => duration.TotalMinutes >= 1
+        ? $"{(int)duration.TotalMinutes}m {duration.Seconds}s"
+        : $"{duration.Seconds}s";
+}
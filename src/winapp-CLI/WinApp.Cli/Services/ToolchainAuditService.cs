@@ -0,0 +1,45 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal class ToolchainAuditService(IBuildToolsService buildToolsService) : IToolchainAuditService
+{
+    public IReadOnlyList<ToolchainAuditEntry> GetAuditEntries(PackagingEngine engine)
+    {
+        var native = engine == PackagingEngine.Native;
+
+        return
+        [
+            native
+                ? Native("Pack MSIX", "package")
+                : Sdk("Pack MSIX", "package", "makeappx.exe", "Use --engine native to pack without the Windows SDK"),
+            native
+                ? Native("Generate block map", "package")
+                : Sdk("Generate block map", "package", "makeappx.exe", "Generated by makeappx.exe as part of packing"),
+            native
+                ? Native("Sign MSIX", "package --cert, sign")
+                : Sdk("Sign MSIX", "package --cert, sign", "signtool.exe", "Use --engine native to sign .msix/.appx packages without the Windows SDK"),
+            Sdk("Sign executables and libraries", "sign", "signtool.exe", "Only .msix/.appx packages can be signed natively"),
+            Sdk("Generate PRI resources", "package, bundle thin", "makepri.exe", "Pass --skip-pri to package when the app has no PRI resources"),
+            Sdk("Embed identity manifest", "create-debug-identity, package --self-contained", "mt.exe"),
+            Sdk("Embed Win32 manifest", "package with win32Manifest in winapp.yaml", "mt.exe", "Only for executables the build left without a manifest"),
+            Sdk("Repack thinned bundle", "bundle thin", "makeappx.exe"),
+            Native("Generate development certificate", "cert generate, package --generate-cert"),
+            Native("Install certificate", "cert install, package --install-cert"),
+            External("Generate C++/WinRT projections", "init, restore", "cppwinrt.exe", "Restored from the Microsoft.Windows.CppWinRT NuGet package, not the Windows SDK"),
+            External("Register package", "create-debug-identity", "powershell.exe", "Add-AppxPackage is part of Windows"),
+        ];
+    }
+
+    private static ToolchainAuditEntry Native(string operation, string commands) =>
+        new(operation, commands, ExternalTool: null, RequiresWindowsSdk: false, ToolAvailable: null);
+
+    private ToolchainAuditEntry Sdk(string operation, string commands, string tool, string? remarks = null) =>
+        new(operation, commands, tool, RequiresWindowsSdk: true, buildToolsService.GetBuildToolPath(tool) != null, remarks);
+
+    private static ToolchainAuditEntry External(string operation, string commands, string tool, string remarks) =>
+        new(operation, commands, tool, RequiresWindowsSdk: false, ToolAvailable: null, remarks);
+}
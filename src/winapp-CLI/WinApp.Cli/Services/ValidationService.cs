@@ -0,0 +1,141 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using System.Text.Json.Serialization;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+[JsonSerializable(typeof(ValidationBaseline))]
+[JsonSourceGenerationOptions(
+    WriteIndented = true,
+    PropertyNamingPolicy = JsonKnownNamingPolicy.CamelCase)]
+internal partial class ValidationBaselineJsonContext : JsonSerializerContext
+{
+}
+
+internal class ValidationService(IManifestExplainService manifestExplainService, IConfigService configService) : IValidationService
+{
+    private const string BaselineFileName = "winapp-baseline.json";
+
+    public FileInfo DefaultBaselinePath => new(Path.Combine(configService.ConfigPath.DirectoryName!, BaselineFileName));
+
+    public async Task<IReadOnlyList<ValidationFinding>> ValidateAsync(FileInfo manifestPath, FileInfo baselinePath, bool allowScriptAnalyzers, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var splash = configService.Exists() ? configService.Load().Splash : null;
+        var explanation = await manifestExplainService.ExplainAsync(manifestPath, splash, runChecks: true, allowScriptAnalyzers, taskContext, cancellationToken);
+        var file = RelativeFile(baselinePath, manifestPath);
+        return [.. explanation.Notes.Select(n => new ValidationFinding(n.Severity, n.Rule, file, n.Element, n.Subject, n.Message))];
+    }
+
+    public async Task<ValidationResult> CompareAsync(FileInfo baselinePath, FileInfo manifestPath, IReadOnlyList<ValidationFinding> findings, CancellationToken cancellationToken = default)
+    {
+        var file = RelativeFile(baselinePath, manifestPath);
+        var entries = (await LoadAsync(baselinePath, cancellationToken)).Findings
+            .Where(e => e.File.Equals(file, StringComparison.OrdinalIgnoreCase))
+            .ToList();
+
+        var newFindings = new List<ValidationFinding>();
+        var baselined = new List<ValidationFinding>();
+        var fixedCount = 0;
+        foreach (var group in findings.GroupBy(f => (f.Rule, f.Element)))
+        {
+            var entry = entries.FirstOrDefault(e => e.Rule == group.Key.Rule && e.Element == group.Key.Element);
+            var recorded = entry?.Count ?? 0;
+
+            // Findings whose message was recorded are the baselined ones, so a changed message is what is new
+            var messages = entry?.Messages.ToList() ?? [];
+            var matched = group.Select(f => (Finding: f, Recorded: messages.Remove(f.Message))).ToList();
+            var ordered = matched.OrderByDescending(m => m.Recorded).Select(m => m.Finding).ToList();
+            baselined.AddRange(ordered.Take(recorded));
+            newFindings.AddRange(ordered.Skip(recorded));
+            fixedCount += Math.Max(0, recorded - ordered.Count);
+        }
+        fixedCount += entries
+            .Where(e => !findings.Any(f => f.Rule == e.Rule && f.Element == e.Element))
+            .Sum(e => e.Count);
+
+        return new ValidationResult(newFindings, baselined, fixedCount);
+    }
+
+    public async Task WriteBaselineAsync(FileInfo baselinePath, FileInfo manifestPath, IReadOnlyList<ValidationFinding> findings, CancellationToken cancellationToken = default)
+    {
+        var file = RelativeFile(baselinePath, manifestPath);
+        var baseline = await LoadAsync(baselinePath, cancellationToken);
+        baseline.Findings.RemoveAll(e => e.File.Equals(file, StringComparison.OrdinalIgnoreCase));
+        baseline.Findings.AddRange(findings
+            .GroupBy(f => (f.Rule, f.Element))
+            .Select(g => new BaselineEntry
+            {
+                File = file,
+                Rule = g.Key.Rule,
+                Element = g.Key.Element,
+                Count = g.Count(),
+                Messages = [.. g.Select(f => f.Message)],
+            }));
+
+        // Sorted, so the baseline diffs cleanly when it is tightened
+        baseline.Findings = [.. baseline.Findings
+            .OrderBy(e => e.File, StringComparer.OrdinalIgnoreCase)
+            .ThenBy(e => e.Rule, StringComparer.Ordinal)
+            .ThenBy(e => e.Element, StringComparer.Ordinal)];
+
+        baselinePath.Directory?.Create();
+        using var stream = baselinePath.Open(FileMode.Create, FileAccess.Write);
+        await JsonSerializer.SerializeAsync(stream, baseline, ValidationBaselineJsonContext.Default.ValidationBaseline, cancellationToken);
+        baselinePath.Refresh();
+    }
+
+    /// <summary>
+    /// The findings as JSON for 'winapp validate --format json', new ones first
+    /// </summary>
+    internal static string ToJson(ValidationResult result, AccessibilitySeverity failOn)
+    {
+        static JsonNode Finding(ValidationFinding finding, bool baselined) => new JsonObject
+        {
+            ["severity"] = finding.Severity.ToString().ToLowerInvariant(),
+            ["rule"] = finding.Rule,
+            ["file"] = finding.File,
+            ["element"] = finding.Element,
+            ["subject"] = finding.Subject,
+            ["message"] = finding.Message,
+            ["baselined"] = baselined,
+        };
+
+        var json = new JsonObject
+        {
+            ["passed"] = !result.New.Any(f => f.Severity >= failOn),
+            ["failOn"] = failOn.ToString().ToLowerInvariant(),
+            ["fixed"] = result.Fixed,
+            ["findings"] = new JsonArray([
+                .. result.New.OrderByDescending(f => f.Severity).Select(f => Finding(f, false)),
+                .. result.Baselined.OrderByDescending(f => f.Severity).Select(f => Finding(f, true))]),
+        };
+        return json.ToJsonString(new JsonSerializerOptions { WriteIndented = true });
+    }
+
+    private static async Task<ValidationBaseline> LoadAsync(FileInfo baselinePath, CancellationToken cancellationToken)
+    {
+        baselinePath.Refresh();
+        if (!baselinePath.Exists)
+        {
+            return new ValidationBaseline();
+        }
+
+        try
+        {
+            using var stream = baselinePath.OpenRead();
+            return await JsonSerializer.DeserializeAsync(stream, ValidationBaselineJsonContext.Default.ValidationBaseline, cancellationToken) ?? new ValidationBaseline();
+        }
+        catch (JsonException ex)
+        {
+            throw new InvalidOperationException($"{baselinePath.Name} is not a winapp baseline: {ex.Message}. Recreate it with 'winapp validate --write-baseline'", ex);
+        }
+    }
+
+    private static string RelativeFile(FileInfo baselinePath, FileInfo manifestPath) =>
+        Path.GetRelativePath(baselinePath.DirectoryName!, manifestPath.FullName).Replace('\\', '/');
+}
@@ -0,0 +1,214 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class WapprojExportService(IConfigService configService) : IWapprojExportService
+{
+    private const string ImagesFolder = "Images";
+    private const string ManifestFile = "Package.appxmanifest";
+
+    private static readonly XNamespace MsBuildNamespace = "http://schemas.microsoft.com/developer/msbuild/2003";
+
+    // Project files Visual Studio can use as the entry point of a packaging project
+    private static readonly string[] EntryPointProjectPatterns = ["*.csproj", "*.vbproj", "*.fsproj", "*.vcxproj"];
+
+    private static readonly string[] Platforms = ["x86", "x64", "ARM64"];
+
+    public async Task<WapprojExport> ExportAsync(FileInfo manifestPath, DirectoryInfo? outputDirectory, bool force, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var configDirectory = configService.ConfigPath.Directory!;
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        var exported = new List<string>();
+        var manual = new List<string>();
+
+        // Entry point: the first project of winapp.yaml that Visual Studio can build
+        var entryProject = config.Projects
+            .Select(p => new DirectoryInfo(Path.Combine(configDirectory.FullName, p.Path)))
+            .Where(d => d.Exists)
+            .SelectMany(d => EntryPointProjectPatterns.SelectMany(pattern => d.EnumerateFiles(pattern)))
+            .FirstOrDefault();
+        var manifestXml = await File.ReadAllTextAsync(manifestPath.FullName, Encoding.UTF8, cancellationToken);
+        var identityName = XDocument.Parse(manifestXml).Root?.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity")?.Attribute("Name")?.Value ?? "App";
+        var name = entryProject != null ? Path.GetFileNameWithoutExtension(entryProject.Name) : identityName.Split('.')[^1];
+        outputDirectory ??= new DirectoryInfo(Path.Combine(configDirectory.FullName, $"{name}.Package"));
+        var projectPath = new FileInfo(Path.Combine(outputDirectory.FullName, $"{name}.Package.wapproj"));
+        if (projectPath.Exists && !force)
+        {
+            throw new InvalidOperationException($"{projectPath.FullName} exists already; pass --force to overwrite it");
+        }
+        outputDirectory.Create();
+
+        // Apply winapp.yaml as 'winapp package' does, since Visual Studio won't
+        var warnings = new List<string>();
+        var environment = config.GetPackageEnvironment();
+        manifestXml = AppxDesktopExtensions.Apply(manifestXml, environment, config.Shortcuts, warnings);
+        manifestXml = AppxSplashScreen.Apply(manifestXml, config.Splash);
+        manual.AddRange(warnings);
+        if (environment.Count > 0 || config.Shortcuts.Count > 0 || config.Splash != null)
+        {
+            exported.Add($"{environment.Count} environment variable(s), {config.Shortcuts.Count} shortcut(s){(config.Splash != null ? " and the splash screen" : "")} of winapp.yaml, in the manifest");
+        }
+        var outputManifest = new FileInfo(Path.Combine(outputDirectory.FullName, ManifestFile));
+        await File.WriteAllTextAsync(outputManifest.FullName, manifestXml, Encoding.UTF8, cancellationToken);
+
+        var manifest = XDocument.Parse(manifestXml, LoadOptions.PreserveWhitespace);
+        var root = manifest.Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        if (entryProject != null)
+        {
+            // Visual Studio fills these in from the entry point project when it builds
+            foreach (var application in root.Descendants().Where(e => e.Name.LocalName == "Application"))
+            {
+                application.SetAttributeValue("Executable", "$targetnametoken$.exe");
+                application.SetAttributeValue("EntryPoint", "$targetentrypoint$");
+            }
+            exported.Add($"Entry point project {entryProject.Name}");
+        }
+        else
+        {
+            manual.Add("winapp.yaml has no project Visual Studio can build; add your app's project to the packaging project and set it as the entry point");
+        }
+        if (config.Projects.Count > 1)
+        {
+            manual.Add($"winapp.yaml has {config.Projects.Count} projects and the packaging project references {name}; add a reference to the others it should package");
+        }
+
+        // Assets move to Images, the folder of Visual Studio's template, with every scale and variant
+        var images = new List<string>();
+        var manifestDirectory = manifestPath.Directory!;
+        foreach (var reference in ManifestService.ExtractAssetReferencesFromManifest(outputManifest, taskContext))
+        {
+            var logical = reference.RelativePath.Replace('\\', Path.DirectorySeparatorChar);
+            var sourceDirectory = new DirectoryInfo(Path.Combine(manifestDirectory.FullName, Path.GetDirectoryName(logical) ?? ""));
+            var baseName = Path.GetFileNameWithoutExtension(logical);
+            var variants = sourceDirectory.Exists
+                ? sourceDirectory.EnumerateFiles($"*{Path.GetExtension(logical)}")
+                    .Where(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name)))
+                    .ToList()
+                : [];
+            if (variants.Count == 0)
+            {
+                manual.Add($"{reference.RelativePath} exists in no scale or variant; add it to {ImagesFolder} in Visual Studio's manifest designer");
+                continue;
+            }
+
+            var target = Directory.CreateDirectory(Path.Combine(outputDirectory.FullName, ImagesFolder));
+            foreach (var variant in variants)
+            {
+                variant.CopyTo(Path.Combine(target.FullName, variant.Name), overwrite: true);
+                images.Add($"{ImagesFolder}\\{variant.Name}");
+            }
+            WapprojMigrationService.ReplaceValue(root, reference.RelativePath, $"{ImagesFolder}\\{Path.GetFileName(logical)}");
+        }
+        if (images.Count > 0)
+        {
+            exported.Add($"{images.Count} image file(s) to {ImagesFolder}");
+        }
+
+        var family = root.Descendants().FirstOrDefault(e => e.Name.LocalName == "TargetDeviceFamily");
+        var language = root.Descendants().FirstOrDefault(e => e.Name.LocalName == "Resource")?.Attribute("Language")?.Value;
+        var project = CreateProject(
+            family?.Attribute("MaxVersionTested")?.Value,
+            family?.Attribute("MinVersion")?.Value,
+            language,
+            entryProject != null ? Path.GetRelativePath(outputDirectory.FullName, entryProject.FullName) : null,
+            images,
+            config.Packages);
+        if (config.Packages.Count > 0)
+        {
+            exported.Add($"{config.Packages.Count} package reference(s)");
+        }
+
+        if (config.Badges.Count > 0)
+        {
+            manual.Add("badges: Visual Studio doesn't draw badges on the images; build those packages with 'winapp package --profile'");
+        }
+        if (config.Rules.Count > 0 || config.Suppressions.Count > 0 || config.Analyzers.Count > 0)
+        {
+            manual.Add("rules, suppressions and analyzers: Visual Studio doesn't run winapp's checks; keep 'winapp validate' in CI");
+        }
+        manual.Add($"Add {projectPath.Name} to your solution, and sign or associate it with the Store from Publish in its context menu");
+
+        using (var stream = outputManifest.Open(FileMode.Create, FileAccess.Write))
+        {
+            await manifest.SaveAsync(stream, SaveOptions.DisableFormatting, cancellationToken);
+        }
+        using (var stream = new FileStream(projectPath.FullName, FileMode.Create, FileAccess.Write))
+        {
+            await project.SaveAsync(stream, SaveOptions.None, cancellationToken);
+        }
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {projectPath.FullName} and {ManifestFile}");
+
+        projectPath.Refresh();
+        return new WapprojExport(projectPath, images.Count, exported, manual);
+    }
+
+    /// <summary>
+    /// The project Visual Studio's Windows Application Packaging Project template creates, for each platform
+    /// </summary>
+    internal static XDocument CreateProject(string? platformVersion, string? platformMinVersion, string? language, string? entryPointProject, IReadOnlyList<string> images, IReadOnlyList<PackagePin> packages)
+    {
+        XElement Group(params object[] content) => new(MsBuildNamespace + "ItemGroup", content);
+        XElement Property(string name, object? value) => new(MsBuildNamespace + name, value);
+
+        var configurations = new XElement(MsBuildNamespace + "ItemGroup", new XAttribute("Label", "ProjectConfigurations"));
+        foreach (var configuration in new[] { "Debug", "Release" })
+        {
+            foreach (var platform in Platforms)
+            {
+                configurations.Add(new XElement(MsBuildNamespace + "ProjectConfiguration",
+                    new XAttribute("Include", $"{configuration}|{platform}"),
+                    Property("Configuration", configuration),
+                    Property("Platform", platform)));
+            }
+        }
+
+        var properties = new XElement(MsBuildNamespace + "PropertyGroup",
+            Property("ProjectGuid", $"{{{Guid.NewGuid().ToString().ToUpperInvariant()}}}"),
+            Property("TargetPlatformVersion", platformVersion ?? "10.0.22621.0"),
+            Property("TargetPlatformMinVersion", platformMinVersion ?? "10.0.17763.0"),
+            Property("DefaultLanguage", language ?? "en-US"),
+            Property("AppxPackageSigningEnabled", "false"));
+        if (entryPointProject != null)
+        {
+            properties.Add(Property("EntryPointProjectUniqueName", entryPointProject));
+        }
+
+        var project = new XElement(MsBuildNamespace + "Project",
+            new XAttribute("ToolsVersion", "15.0"),
+            new XAttribute("DefaultTargets", "Build"),
+            new XElement(MsBuildNamespace + "PropertyGroup",
+                new XAttribute("Condition", "'$(VisualStudioVersion)' == '' or '$(VisualStudioVersion)' < '15.0'"),
+                Property("VisualStudioVersion", "15.0")),
+            configurations,
+            new XElement(MsBuildNamespace + "PropertyGroup",
+                new XElement(MsBuildNamespace + "WapProjPath",
+                    new XAttribute("Condition", "'$(WapProjPath)'==''"),
+                    @"$(MSBuildExtensionsPath)\Microsoft\DesktopBridge\")),
+            new XElement(MsBuildNamespace + "Import", new XAttribute("Project", @"$(WapProjPath)\Microsoft.DesktopBridge.props")),
+            properties,
+            Group(new XElement(MsBuildNamespace + "AppxManifest", new XAttribute("Include", ManifestFile), Property("SubType", "Designer"))));
+        if (images.Count > 0)
+        {
+            project.Add(Group(images.Select(i => new XElement(MsBuildNamespace + "Content", new XAttribute("Include", i)))));
+        }
+        if (packages.Count > 0)
+        {
+            project.Add(Group(packages.Select(p => new XElement(MsBuildNamespace + "PackageReference", new XAttribute("Include", p.Name), new XAttribute("Version", p.Version)))));
+        }
+        if (entryPointProject != null)
+        {
+            project.Add(Group(new XElement(MsBuildNamespace + "ProjectReference", new XAttribute("Include", entryPointProject))));
+        }
+        project.Add(new XElement(MsBuildNamespace + "Import", new XAttribute("Project", @"$(WapProjPath)\Microsoft.DesktopBridge.targets")));
+
+        return new XDocument(new XDeclaration("1.0", "utf-8", null), project);
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+using WinApp.Cli.Packaging;
+
+namespace WinApp.Cli.Services;
+
+internal class WapprojMigrationService(IConfigService configService) : IWapprojMigrationService
+{
+    private const string AssetsFolder = "Assets";
+    private const string TargetNameToken = "$targetnametoken$";
+    private const string EntryPointToken = "$targetentrypoint$";
+
+    /// <summary>
+    /// Project properties winapp has no equivalent for in winapp.yaml, and the command to use instead
+    /// </summary>
+    private static readonly (string Property, string Instead)[] ManualProperties =
+    [
+        ("PackageCertificateKeyFile", "pass the certificate to 'winapp pack --cert', or create one with 'winapp cert generate'"),
+        ("PackageCertificateThumbprint", "export the certificate and pass it to 'winapp pack --cert'"),
+        ("AppxBundle", "winapp packs one .msix per platform; bundle them with 'winapp tool makeappx.exe bundle'"),
+        ("AppxBundlePlatforms", "pack each platform and bundle them with 'winapp tool makeappx.exe bundle'"),
+        ("GenerateAppInstallerFile", "publish an App Installer page with 'winapp distribute web'"),
+        ("AppInstallerUri", "pass the URL to 'winapp distribute web'"),
+        ("AppxPackageDir", "pass the output folder to 'winapp pack --output'"),
+        ("AppxSymbolPackageEnabled", "winapp doesn't create .appxsym symbol packages; publish symbols separately"),
+        ("UapAppxPackageBuildMode", "submit to the Store with 'winapp store submit'"),
+    ];
+
+    public async Task<MigrationResult> MigrateAsync(FileInfo wapprojPath, bool force, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var projectDirectory = wapprojPath.Directory!;
+        var outputDirectory = configService.ConfigPath.Directory!;
+        var project = XDocument.Load(wapprojPath.FullName);
+        var migrated = new List<string>();
+        var manual = new List<string>();
+
+        var manifestItem = Items(project, "AppxManifest").FirstOrDefault() ?? "Package.appxmanifest";
+        var sourceManifest = new FileInfo(Path.Combine(projectDirectory.FullName, manifestItem));
+        if (!sourceManifest.Exists)
+        {
+            throw new InvalidOperationException($"{wapprojPath.Name} has no manifest; {sourceManifest.FullName} not found");
+        }
+
+        var manifestPath = new FileInfo(Path.Combine(outputDirectory.FullName, "appxmanifest.xml"));
+        if (manifestPath.Exists && !force)
+        {
+            throw new InvalidOperationException($"{manifestPath.FullName} exists already; pass --force to overwrite it");
+        }
+
+        var manifest = XDocument.Load(sourceManifest.FullName, LoadOptions.PreserveWhitespace);
+        var root = manifest.Root ?? throw new InvalidOperationException("Package.appxmanifest has no root element");
+
+        // Entry point: the executable Visual Studio puts in place of $targetnametoken$
+        var references = Items(project, "ProjectReference").ToList();
+        var entryPoint = Property(project, "EntryPointProjectUniqueName") ?? references.FirstOrDefault();
+        FileInfo? entryProject = entryPoint != null ? new FileInfo(Path.Combine(projectDirectory.FullName, entryPoint)) : null;
+        var targetName = entryProject is { Exists: true } ? ReadTargetName(entryProject) : null;
+        foreach (var application in root.Descendants().Where(e => e.Name.LocalName == "Application"))
+        {
+            var executable = application.Attribute("Executable");
+            if (executable?.Value.Contains(TargetNameToken, StringComparison.Ordinal) == true)
+            {
+                if (targetName != null)
+                {
+                    executable.Value = executable.Value.Replace(TargetNameToken, targetName, StringComparison.Ordinal);
+                    migrated.Add($"Executable of {application.Attribute("Id")?.Value}: {executable.Value}, from {entryProject!.Name}");
+                }
+                else
+                {
+                    manual.Add($"Executable of {application.Attribute("Id")?.Value} is {executable.Value}, which Visual Studio fills in from the entry point project; set it to the app's .exe in appxmanifest.xml");
+                }
+            }
+            if (application.Attribute("EntryPoint") is { Value: EntryPointToken } entryPointAttribute)
+            {
+                entryPointAttribute.Value = "Windows.FullTrustApplication";
+            }
+        }
+        if (references.Count > 1)
+        {
+            manual.Add($"The project packages {references.Count} projects; winapp packs one folder, so build {string.Join(", ", references.Where(r => r != entryPoint).Select(Path.GetFileNameWithoutExtension))} into the entry point's output");
+        }
+
+        // Visual Studio writes the target platform versions into the manifest at build
+        var minVersion = Property(project, "TargetPlatformMinVersion");
+        var maxVersion = Property(project, "TargetPlatformVersion");
+        foreach (var family in root.Descendants().Where(e => e.Name.LocalName == "TargetDeviceFamily" && e.Attribute("MinVersion")?.Value == "10.0.0.0"))
+        {
+            if (minVersion != null)
+            {
+                family.SetAttributeValue("MinVersion", minVersion);
+            }
+            if (maxVersion != null)
+            {
+                family.SetAttributeValue("MaxVersionTested", maxVersion);
+            }
+            migrated.Add($"TargetDeviceFamily {family.Attribute("Name")?.Value}: MinVersion {family.Attribute("MinVersion")?.Value}, MaxVersionTested {family.Attribute("MaxVersionTested")?.Value}");
+        }
+
+        // Images move from the project's folder, Images by default, to Assets with every scale and variant
+        var assets = 0;
+        var copiedFiles = new HashSet<string>(StringComparer.OrdinalIgnoreCase);
+        foreach (var reference in ManifestService.ExtractAssetReferencesFromManifest(sourceManifest, taskContext))
+        {
+            var logical = reference.RelativePath.Replace('\\', Path.DirectorySeparatorChar);
+            var sourceDirectory = new DirectoryInfo(Path.Combine(projectDirectory.FullName, Path.GetDirectoryName(logical) ?? ""));
+            var baseName = Path.GetFileNameWithoutExtension(logical);
+            var variants = sourceDirectory.Exists
+                ? sourceDirectory.EnumerateFiles($"*{Path.GetExtension(logical)}")
+                    .Where(f => MsixService.IsMrtVariantName(baseName, Path.GetFileNameWithoutExtension(f.Name)))
+                    .ToList()
+                : [];
+            if (variants.Count == 0)
+            {
+                manual.Add($"{reference.RelativePath} exists in no scale or variant; create it with 'winapp manifest update-assets'");
+                continue;
+            }
+
+            var target = Directory.CreateDirectory(Path.Combine(outputDirectory.FullName, AssetsFolder));
+            foreach (var variant in variants)
+            {
+                variant.CopyTo(Path.Combine(target.FullName, variant.Name), overwrite: true);
+                copiedFiles.Add(variant.FullName);
+                assets++;
+            }
+            ReplaceValue(root, reference.RelativePath, $"{AssetsFolder}\\{Path.GetFileName(logical)}");
+        }
+        if (assets > 0)
+        {
+            migrated.Add($"{assets} image file(s) to {AssetsFolder}");
+        }
+
+        foreach (var content in Items(project, "Content"))
+        {
+            var file = Path.GetFullPath(Path.Combine(projectDirectory.FullName, content));
+            if (!copiedFiles.Contains(file))
+            {
+                manual.Add($"Content {content} isn't an image of the manifest; copy it into the app's output so 'winapp pack' includes it");
+            }
+        }
+
+        var config = configService.Exists() ? configService.Load() : new WinappConfig();
+        MigrateConfig(project, root, outputDirectory, entryProject, config, migrated, manual);
+
+        foreach (var (property, instead) in ManualProperties)
+        {
+            if (Property(project, property) is string value)
+            {
+                manual.Add($"{property} ({value}): {instead}");
+            }
+        }
+        if (File.Exists(Path.Combine(projectDirectory.FullName, "Package.StoreAssociation.xml")))
+        {
+            manual.Add("Package.StoreAssociation.xml: the Store identity is in the manifest already; submit with 'winapp store submit'");
+        }
+
+        using (var stream = manifestPath.Open(FileMode.Create, FileAccess.Write))
+        {
+            await manifest.SaveAsync(stream, SaveOptions.DisableFormatting, cancellationToken);
+        }
+        configService.Save(config);
+        taskContext.AddDebugMessage($"{UiSymbols.Save} Wrote {manifestPath.FullName} and {configService.ConfigPath.FullName}");
+
+        manifestPath.Refresh();
+        return new MigrationResult(manifestPath, assets, migrated, manual);
+    }
+
+    /// <summary>
+    /// Carries the package references, entry point project and manifest extensions winapp.yaml declares over to it,
+    /// without replacing what it declares already
+    /// </summary>
+    private static void MigrateConfig(XDocument project, XElement root, DirectoryInfo outputDirectory, FileInfo? entryProject, WinappConfig config, List<string> migrated, List<string> manual)
+    {
+        foreach (var reference in project.Descendants().Where(e => e.Name.LocalName == "PackageReference"))
+        {
+            var name = reference.Attribute("Include")?.Value;
+            var version = reference.Attribute("Version")?.Value ?? reference.Elements().FirstOrDefault(e => e.Name.LocalName == "Version")?.Value;
+            if (name == null || config.GetVersion(name) != null)
+            {
+                continue;
+            }
+            if (version == null || version.Contains('*') || version.Contains('$'))
+            {
+                manual.Add($"Package {name} has no fixed version ({version ?? "none"}); pin it under 'packages:' in winapp.yaml");
+                continue;
+            }
+            config.SetVersion(name, version);
+            migrated.Add($"Package {name} {version}");
+        }
+
+        if (entryProject is { Exists: true })
+        {
+            var path = Path.GetRelativePath(outputDirectory.FullName, entryProject.DirectoryName!).Replace('\\', '/');
+            var name = Path.GetFileNameWithoutExtension(entryProject.Name);
+            if (!config.Projects.Any(p => p.Name.Equals(name, StringComparison.OrdinalIgnoreCase)))
+            {
+                config.Projects.Add(new ProjectConfig { Name = name, Path = path });
+                migrated.Add($"Project {name} at {path}");
+            }
+        }
+
+        if (root.Descendants().FirstOrDefault(e => e.Name.LocalName == "SplashScreen") is XElement splash && config.Splash == null)
+        {
+            config.Splash = new SplashConfig
+            {
+                Image = splash.Attribute("Image")?.Value,
+                BackgroundColor = splash.Attribute("BackgroundColor")?.Value,
+            };
+            migrated.Add($"Splash screen {config.Splash.Image}");
+        }
+
+        foreach (var variable in root.Descendants().Where(e => e.Name.NamespaceName == AppxDesktopExtensions.Desktop6Namespace && e.Name.LocalName == "EnvironmentVariable"))
+        {
+            var name = variable.Attribute("Name")?.Value ?? "";
+            if (config.Environment.All(v => !v.Name.Equals(name, StringComparison.OrdinalIgnoreCase)))
+            {
+                config.Environment.Add(new EnvironmentVariableConfig { Name = name, Value = variable.Attribute("Value")?.Value ?? "" });
+                migrated.Add($"Environment variable {name}");
+            }
+        }
+
+        foreach (var shortcut in root.Descendants().Where(e => e.Name.NamespaceName == AppxDesktopExtensions.Desktop7Namespace && e.Name.LocalName == "Shortcut"))
+        {
+            var file = shortcut.Attribute("File")?.Value ?? "";
+            if (config.Shortcuts.All(s => !s.File.Equals(file, StringComparison.OrdinalIgnoreCase)))
+            {
+                config.Shortcuts.Add(new ShortcutConfig
+                {
+                    File = file,
+                    Application = shortcut.Ancestors().FirstOrDefault(e => e.Name.LocalName == "Application")?.Attribute("Id")?.Value,
+                    Arguments = shortcut.Attribute("Arguments")?.Value,
+                    Icon = shortcut.Attribute("Icon")?.Value,
+                });
+                migrated.Add($"Shortcut {file}");
+            }
+        }
+    }
+
+    /// <summary>
+    /// The file name, without .exe, that a project builds: AssemblyName or TargetName, or the project's name
+    /// </summary>
+    private static string ReadTargetName(FileInfo projectPath)
+    {
+        var project = XDocument.Load(projectPath.FullName);
+        var name = Property(project, "AssemblyName") ?? Property(project, "TargetName");
+        return name == null || name.Contains('$') ? Path.GetFileNameWithoutExtension(projectPath.Name) : name;
+    }
+
+    /// <summary>
+    /// First value of a property, preferring unconditional property groups over the ones for a configuration
+    /// </summary>
+    private static string? Property(XDocument project, string name) =>
+        project.Descendants()
+            .Where(e => e.Name.LocalName == name && e.Parent?.Name.LocalName == "PropertyGroup" && !string.IsNullOrWhiteSpace(e.Value))
+            .OrderBy(e => e.Parent!.Attribute("Condition") != null || e.Attribute("Condition") != null)
+            .Select(e => e.Value.Trim())
+            .FirstOrDefault();
+
+    private static IEnumerable<string> Items(XDocument project, string itemType) =>
+        project.Descendants()
+            .Where(e => e.Name.LocalName == itemType && e.Parent?.Name.LocalName == "ItemGroup")
+            .Select(e => e.Attribute("Include")?.Value)
+            .OfType<string>();
+
+    /// <summary>
+    /// Replaces an image path in every attribute and element of the manifest that names it
+    /// </summary>
+    internal static void ReplaceValue(XElement root, string oldValue, string newValue)
+    {
+        foreach (var attribute in root.Descendants().Attributes().Where(a => a.Value.Equals(oldValue, StringComparison.OrdinalIgnoreCase)))
+        {
+            attribute.Value = newValue;
+        }
+        foreach (var element in root.Descendants().Where(e => !e.HasElements && e.Value.Trim().Equals(oldValue, StringComparison.OrdinalIgnoreCase)))
+        {
+            element.Value = newValue;
+        }
+    }
+}
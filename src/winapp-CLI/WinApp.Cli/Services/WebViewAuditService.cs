@@ -0,0 +1,180 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json;
+using System.Xml.Linq;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Services;
+
+internal sealed class WebViewAuditService(IInstalledPackageService installedPackageService, IBundlerMigrationService bundlerMigrationService) : IWebViewAuditService
+{
+    /// <summary>
+    /// Environment variable that overrides the user data folder of every WebView2 app started with it
+    /// </summary>
+    internal const string UserDataFolderVariable = "WEBVIEW2_USER_DATA_FOLDER";
+
+    // The loader of apps that use WebView2 through the SDK; Tauri links it statically and is found by its tauri.conf.json
+    private static readonly string[] WebView2Files = ["WebView2Loader.dll", "Microsoft.Web.WebView2.Core.dll"];
+
+    private static readonly string[] SkippedFolders = ["node_modules", ".git", ".winapp"];
+
+    private static readonly EnumerationOptions Enumeration = new() { IgnoreInaccessible = true };
+
+    public async Task<WebViewReport> AnalyzeAsync(FileInfo manifestPath, TaskContext taskContext, CancellationToken cancellationToken = default)
+    {
+        var root = XDocument.Load(manifestPath.FullName).Root ?? throw new InvalidOperationException("AppxManifest.xml has no root element");
+        var identityName = root.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity")?.Attribute("Name")?.Value
+            ?? throw new InvalidOperationException("AppxManifest.xml has no Identity Name");
+        var manifestDirectory = manifestPath.Directory!;
+
+        var tauriIdentifier = FindTauriIdentifier(manifestDirectory, taskContext);
+        var (hasLoader, packagedProfiles) = ScanPackageFolder(manifestDirectory);
+        var virtualizationDisabled = IsWriteVirtualizationDisabled(root);
+        var expectedProfile = GetExpectedProfile(tauriIdentifier, virtualizationDisabled);
+        if (tauriIdentifier == null && !hasLoader && packagedProfiles.Count == 0)
+        {
+            return new WebViewReport(false, expectedProfile, [], []);
+        }
+
+        var issues = new List<WebViewIssue>();
+        foreach (var profile in packagedProfiles)
+        {
+            issues.Add(new WebViewIssue(AccessibilitySeverity.Error, "packaged-profile", identityName,
+                $"{Path.GetRelativePath(manifestDirectory.FullName, profile.FullName)} is a WebView2 profile in the package folder. It would be packed with its cookies and cache, and an app that keeps its profile next to its executable fails with E_ACCESSDENIED (0x80070005) once installed, since the install folder is read-only. Delete it, and leave the user data folder to WebView2's default for packaged apps"));
+        }
+
+        if (EnvironmentIssue(Environment.GetEnvironmentVariable(UserDataFolderVariable), identityName) is { } environmentIssue)
+        {
+            issues.Add(environmentIssue);
+        }
+
+        if (tauriIdentifier != null && virtualizationDisabled)
+        {
+            issues.Add(new WebViewIssue(AccessibilitySeverity.Warning, "virtualization", identityName,
+                $"The manifest turns off file system write virtualization, so the packaged app keeps its WebView2 profile in %LOCALAPPDATA%\\{tauriIdentifier}\\EBWebView, the same folder as 'tauri dev'. While one of them runs, the other fails to start WebView2 when their browser arguments differ (0x8007139F); keep virtualization on, or give the packaged app its own folder"));
+        }
+
+        var profiles = new List<WebViewProfile>();
+        try
+        {
+            var (packages, _) = await installedPackageService.GetPackagesAsync(taskContext, cancellationToken);
+            foreach (var package in InstalledPackageService.FindProjectPackages(packages, identityName))
+            {
+                profiles.AddRange(FindInstalledProfiles(package.AppDataPath).Select(p => new WebViewProfile(package.Name, p)));
+            }
+        }
+        catch (InvalidOperationException ex)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Couldn't list the installed packages: {ex.Message}");
+        }
+
+        var identities = profiles.Select(p => p.Package).Distinct(StringComparer.OrdinalIgnoreCase).ToList();
+        if (identities.Count > 1)
+        {
+            issues.Add(new WebViewIssue(AccessibilitySeverity.Info, "identity", identityName,
+                $"{string.Join(", ", identities)} each have their own WebView2 profile, since each identity has its own ApplicationData folder, so sign-ins, cookies and local storage don't carry over between them. Copy them with 'winapp state snapshot' and 'winapp state restore --name'"));
+        }
+
+        return new WebViewReport(true, expectedProfile, profiles, issues);
+    }
+
+    private string? FindTauriIdentifier(DirectoryInfo manifestDirectory, TaskContext taskContext)
+    {
+        // The manifest is usually next to src-tauri, or in it
+        var config = bundlerMigrationService.FindConfig(MigrationSource.Tauri, manifestDirectory)
+            ?? (manifestDirectory.Parent is { } parent ? bundlerMigrationService.FindConfig(MigrationSource.Tauri, parent) : null);
+        if (config == null)
+        {
+            return null;
+        }
+
+        try
+        {
+            return BundlerMigrationService.ReadTauri(config, []).PackageName;
+        }
+        catch (Exception ex) when (ex is InvalidOperationException or JsonException or IOException)
+        {
+            taskContext.AddDebugMessage($"{UiSymbols.Note} Couldn't read {config.FullName}: {ex.Message}");
+            return null;
+        }
+    }
+
+    /// <summary>
+    /// Looks through the package folder for the WebView2 loader and for WebView2 profiles: EBWebView folders, and the
+    /// &lt;app&gt;.exe.WebView2 folders WebView2 creates next to unpackaged apps
+    /// </summary>
+    internal static (bool HasLoader, List<DirectoryInfo> Profiles) ScanPackageFolder(DirectoryInfo directory)
+    {
+        var hasLoader = false;
+        var profiles = new List<DirectoryInfo>();
+        var pending = new Stack<DirectoryInfo>([directory]);
+        while (pending.TryPop(out var current))
+        {
+            hasLoader |= current.EnumerateFiles("*", Enumeration).Any(f => WebView2Files.Contains(f.Name, StringComparer.OrdinalIgnoreCase));
+            foreach (var child in current.EnumerateDirectories("*", Enumeration))
+            {
+                if (IsProfileFolder(child.Name))
+                {
+                    profiles.Add(child);
+                }
+                else if (!SkippedFolders.Contains(child.Name, StringComparer.OrdinalIgnoreCase))
+                {
+                    pending.Push(child);
+                }
+            }
+        }
+        return (hasLoader, [.. profiles.OrderBy(p => p.FullName, StringComparer.OrdinalIgnoreCase)]);
+    }
+
+    /// <summary>
+    /// Finds the WebView2 profiles in a package's ApplicationData folder: LocalState\EBWebView, where WebView2 keeps
+    /// it by default, and LocalCache\Local\*\EBWebView and LocalCache\Roaming\*\EBWebView, where writes to
+    /// %LOCALAPPDATA% and %APPDATA%, like Tauri's, are redirected
+    /// </summary>
+    internal static List<string> FindInstalledProfiles(string appDataPath)
+    {
+        var profiles = new List<string>();
+        var localState = Path.Combine(appDataPath, "LocalState", "EBWebView");
+        if (Directory.Exists(localState))
+        {
+            profiles.Add(localState);
+        }
+        foreach (var redirected in new[] { "Local", "Roaming" }.Select(f => new DirectoryInfo(Path.Combine(appDataPath, "LocalCache", f))).Where(d => d.Exists))
+        {
+            profiles.AddRange(redirected.EnumerateDirectories()
+                .Select(d => Path.Combine(d.FullName, "EBWebView"))
+                .Where(Directory.Exists)
+                .Order(StringComparer.OrdinalIgnoreCase));
+        }
+        return profiles;
+    }
+
+    internal static WebViewIssue? EnvironmentIssue(string? userDataFolder, string identityName) =>
+        string.IsNullOrWhiteSpace(userDataFolder)
+            ? null
+            : new WebViewIssue(AccessibilitySeverity.Warning, "environment", identityName,
+                $"{UserDataFolderVariable} is set to {userDataFolder}, which every WebView2 app started from this environment uses as its profile, packaged or not. The packaged and unpackaged builds then share one profile and lock each other out, and the packaged app may not be able to write to it; unset it, or set it for one app only");
+
+    /// <summary>
+    /// Whether the manifest turns off the redirection of a full trust app's writes to %LOCALAPPDATA% and %APPDATA%
+    /// </summary>
+    internal static bool IsWriteVirtualizationDisabled(XElement root) =>
+        root.Descendants().Any(e =>
+            e.Name.LocalName == "FileSystemWriteVirtualization"
+            && e.Parent?.Name.LocalName == "Properties"
+            && e.Value.Trim().Equals("disabled", StringComparison.OrdinalIgnoreCase));
+
+    private static string GetExpectedProfile(string? tauriIdentifier, bool virtualizationDisabled) => tauriIdentifier switch
+    {
+        // WebView2's default for packaged apps; Tauri picks %LOCALAPPDATA%\<identifier>, which the package redirects
+        null => @"<ApplicationData>\LocalState\EBWebView",
+        _ when virtualizationDisabled => $@"%LOCALAPPDATA%\{tauriIdentifier}\EBWebView",
+        _ => $@"<ApplicationData>\LocalCache\Local\{tauriIdentifier}\EBWebView",
+    };
+
+    private static bool IsProfileFolder(string name) =>
+        name.Equals("EBWebView", StringComparison.OrdinalIgnoreCase) || name.EndsWith(".WebView2", StringComparison.OrdinalIgnoreCase);
+}
@@ -25,6 +25,7 @@ internal class WorkspaceSetupOptions
     public bool ForceLatestBuildTools { get; set; }
     public bool NoCert { get; set; }
     public bool ConfigOnly { get; set; }
+    public LicenseConsent LicenseConsent { get; set; } = new(false, []);
 }
 
 /// <summary>
@@ -44,6 +45,7 @@ internal class WorkspaceSetupService(
     IDevModeService devModeService,
     IGitignoreService gitignoreService,
     IDirectoryPackagesService directoryPackagesService,
+    IPackageLicenseService packageLicenseService,
     IStatusService statusService,
     ICurrentDirectoryProvider currentDirectoryProvider,
     IAnsiConsole ansiConsole,
@@ -103,7 +105,7 @@ internal class WorkspaceSetupService(
                     }
                 }
 
-                var finalConfig = new WinappConfig();
+                var finalConfig = configService.Exists() ? configService.Load().WithoutPackages() : new WinappConfig();
                 foreach (var kvp in defaultVersions)
                 {
                     finalConfig.SetVersion(kvp.Key, kvp.Value);
@@ -276,6 +278,13 @@ internal class WorkspaceSetupService(
                             return (1, "Error installing packages.");
                         }
 
+                        // The packages are in the global cache, but aren't set up until their licenses are accepted
+                        var pendingLicenses = await packageLicenseService.EnsureAcceptedAsync(pkgsDir, usedVersions, options.LicenseConsent, taskContext, cancellationToken);
+                        if (pendingLicenses.Count > 0)
+                        {
+                            return (1, PackageLicenseService.ReportPending(pendingLicenses, taskContext));
+                        }
+
                         // Step 5: Run cppwinrt and set up projections
                         var cppWinrtExe = cppWinrtService.FindCppWinrtExe(pkgsDir, usedVersions);
                         if (cppWinrtExe is null)
@@ -459,7 +468,7 @@ internal class WorkspaceSetupService(
                     await taskContext.AddSubTaskAsync("Saving configuration", (taskContext, cancellationToken) =>
                     {
                         // Setup: Save winapp.yaml with used versions
-                        var finalConfig = new WinappConfig();
+                        var finalConfig = configService.Exists() ? configService.Load().WithoutPackages() : new WinappConfig();
                         // only from SDK_PACKAGES
                         var versionsToSave = usedVersions
                             .Where(kvp => NugetService.SDK_PACKAGES.Contains(kvp.Key, StringComparer.OrdinalIgnoreCase))
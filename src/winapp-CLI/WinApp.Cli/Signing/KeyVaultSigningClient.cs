@@ -0,0 +1,87 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers.Text;
+using System.Security.Cryptography;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Signing;
+
+/// <summary>
+/// Signs hashes with the key of a certificate in Azure Key Vault. The key can't be exported, so Key Vault signs
+/// each hash; the service principal needs the 'get' certificate and 'sign' key permissions.
+/// </summary>
+/// <param name="vault">URI of the vault, e.g. https://contoso.vault.azure.net</param>
+internal sealed class KeyVaultSigningClient(HttpClient http, StoreCredentials credentials, Uri vault, string certificateName)
+{
+    public const string Resource = "https://vault.azure.net";
+
+    private const string ApiVersion = "7.4";
+
+    private readonly PartnerCenterClient client = new(http, credentials)
+    {
+        ServiceUri = new Uri(vault.AbsoluteUri.TrimEnd('/') + "/"),
+        Resource = Resource
+    };
+
+    private (X509Certificate2 Certificate, string KeyPath)? key;
+
+    public Action<string>? OnRetry
+    {
+        get => client.OnRetry;
+        set => client.OnRetry = value;
+    }
+
+    /// <summary>
+    /// Reads the current version of the certificate and the key it was issued for
+    /// </summary>
+    public async Task<X509Certificate2> GetCertificateAsync(CancellationToken cancellationToken) =>
+        (await GetKeyAsync(cancellationToken)).Certificate;
+
+    public async Task<RemoteSignature> SignHashAsync(byte[] hash, HashAlgorithmName hashAlgorithm, CancellationToken cancellationToken)
+    {
+        var (certificate, keyPath) = await GetKeyAsync(cancellationToken);
+        var body = new JsonObject
+        {
+            ["alg"] = SigningAlgorithms.RsaAlgorithm(hashAlgorithm),
+            ["value"] = Base64Url.EncodeToString(hash)
+        };
+        var result = await client.PostAsync($"{keyPath}/sign?api-version={ApiVersion}", body, cancellationToken);
+        var signature = result?["value"]?.GetValue<string>()
+            ?? throw new InvalidOperationException($"Key Vault returned no signature for certificate {certificateName}");
+        return new RemoteSignature(Base64Url.DecodeFromChars(signature), certificate, []);
+    }
+
+    private async Task<(X509Certificate2 Certificate, string KeyPath)> GetKeyAsync(CancellationToken cancellationToken)
+    {
+        if (key is { } cached)
+        {
+            return cached;
+        }
+
+        var bundle = await client.GetAsync($"certificates/{Uri.EscapeDataString(certificateName)}?api-version={ApiVersion}", cancellationToken)
+            ?? throw new InvalidOperationException($"Key Vault returned no certificate {certificateName}");
+        key = ReadCertificateBundle(bundle, certificateName);
+        return key.Value;
+    }
+
+    /// <summary>
+    /// Reads the certificate and the path of its key, relative to the vault, from a certificate bundle
+    /// </summary>
+    internal static (X509Certificate2 Certificate, string KeyPath) ReadCertificateBundle(JsonNode bundle, string certificateName)
+    {
+        var cer = bundle["cer"]?.GetValue<string>()
+            ?? throw new InvalidOperationException($"Key Vault certificate {certificateName} has no public certificate; it may still be pending issuance");
+        var kid = bundle["kid"]?.GetValue<string>()
+            ?? throw new InvalidOperationException($"Key Vault certificate {certificateName} has no key");
+        if (bundle["attributes"]?["enabled"]?.GetValue<bool>() == false)
+        {
+            throw new InvalidOperationException($"Key Vault certificate {certificateName} is disabled");
+        }
+
+        return (X509CertificateLoader.LoadCertificate(Convert.FromBase64String(cer)), new Uri(kid).AbsolutePath.TrimStart('/'));
+    }
+}
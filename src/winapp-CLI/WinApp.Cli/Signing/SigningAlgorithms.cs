@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+
+namespace WinApp.Cli.Signing;
+
+internal static class SigningAlgorithms
+{
+    /// <summary>
+    /// JSON Web Algorithm name of RSA PKCS#1 v1.5 with the hash algorithm, as Key Vault and Trusted Signing take it
+    /// </summary>
+    public static string RsaAlgorithm(HashAlgorithmName hashAlgorithm) =>
+        hashAlgorithm == HashAlgorithmName.SHA512 ? "RS512"
+        : hashAlgorithm == HashAlgorithmName.SHA384 ? "RS384"
+        : hashAlgorithm == HashAlgorithmName.SHA256 ? "RS256"
+        : throw new InvalidOperationException($"Signing services don't sign {hashAlgorithm.Name} hashes; pack with --digest sha256 or stronger");
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Security.Cryptography;
+using System.Security.Cryptography.Pkcs;
+using System.Security.Cryptography.X509Certificates;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Packaging;
+using WinApp.Cli.Store;
+
+namespace WinApp.Cli.Signing;
+
+/// <summary>
+/// Signs hashes with a certificate profile of Azure Trusted Signing. The service renews the certificate of a
+/// profile daily, so it returns the certificate with each signature.
+/// </summary>
+/// <param name="endpoint">Regional endpoint of the account, e.g. https://eus.codesigning.azure.net</param>
+internal sealed class TrustedSigningClient(HttpClient http, StoreCredentials credentials, Uri endpoint, string account, string profile)
+{
+    public const string Resource = "https://codesigning.azure.net";
+
+    private const string ApiVersion = "2022-06-15-preview";
+
+    private readonly PartnerCenterClient client = new(http, credentials)
+    {
+        ServiceUri = new Uri(endpoint.AbsoluteUri.TrimEnd('/') + "/"),
+        Resource = Resource
+    };
+
+    public TimeSpan PollInterval { get; init; } = TimeSpan.FromSeconds(1);
+
+    public TimeSpan Timeout { get; init; } = TimeSpan.FromMinutes(5);
+
+    public Action<string>? OnRetry
+    {
+        get => client.OnRetry;
+        set => client.OnRetry = value;
+    }
+
+    public async Task<RemoteSignature> SignHashAsync(byte[] hash, HashAlgorithmName hashAlgorithm, CancellationToken cancellationToken)
+    {
+        var path = $"codesigningaccounts/{Uri.EscapeDataString(account)}/certificateprofiles/{Uri.EscapeDataString(profile)}/sign";
+        var body = new JsonObject
+        {
+            ["signatureAlgorithm"] = SigningAlgorithms.RsaAlgorithm(hashAlgorithm),
+            ["digest"] = Convert.ToBase64String(hash)
+        };
+        var operation = await client.PostAsync($"{path}?api-version={ApiVersion}", body, cancellationToken)
+            ?? throw new InvalidOperationException($"Trusted Signing returned no sign operation for profile {profile}");
+
+        var deadline = DateTimeOffset.UtcNow + Timeout;
+        while (true)
+        {
+            var operationId = operation["operationId"]?.GetValue<string>();
+            var status = operation["status"]?.GetValue<string>();
+            if (string.Equals(status, "Succeeded", StringComparison.OrdinalIgnoreCase))
+            {
+                return ReadResult(operation);
+            }
+            if (status is not null && !string.Equals(status, "InProgress", StringComparison.OrdinalIgnoreCase) && !string.Equals(status, "NotStarted", StringComparison.OrdinalIgnoreCase))
+            {
+                throw new InvalidOperationException($"Trusted Signing sign operation {operationId} of profile {profile} ended as {status}");
+            }
+            if (operationId is null)
+            {
+                throw new InvalidOperationException($"Trusted Signing returned a sign operation without an id for profile {profile}");
+            }
+            if (DateTimeOffset.UtcNow > deadline)
+            {
+                throw new TimeoutException($"Trusted Signing sign operation {operationId} didn't finish within {Timeout.TotalMinutes:N0} minutes");
+            }
+
+            await Task.Delay(PollInterval, cancellationToken);
+            operation = await client.GetAsync($"{path}/{Uri.EscapeDataString(operationId)}?api-version={ApiVersion}", cancellationToken)
+                ?? throw new InvalidOperationException($"Trusted Signing returned no status for sign operation {operationId}");
+        }
+    }
+
+    /// <summary>
+    /// Reads the signature and the certificate chain of a finished sign operation
+    /// </summary>
+    internal static RemoteSignature ReadResult(JsonNode operation)
+    {
+        var signature = operation["signature"]?.GetValue<string>()
+            ?? throw new InvalidOperationException("Trusted Signing returned no signature");
+        var certificate = operation["signingCertificate"]?.GetValue<string>()
+            ?? throw new InvalidOperationException("Trusted Signing returned no signing certificate");
+
+        var certificates = ReadCertificates(Convert.FromBase64String(certificate));
+        var leaf = certificates.FirstOrDefault(c => !certificates.Any(other => other != c && other.IssuerName.RawData.AsSpan().SequenceEqual(c.SubjectName.RawData)))
+            ?? certificates[0];
+        return new RemoteSignature(Convert.FromBase64String(signature), leaf, certificates.Where(c => c != leaf).ToList());
+    }
+
+    // The certificate comes as a PKCS #7 bundle with its chain, or as a single DER certificate
+    private static List<X509Certificate2> ReadCertificates(byte[] data)
+    {
+        try
+        {
+            var signedCms = new SignedCms();
+            signedCms.Decode(data);
+            if (signedCms.Certificates.Count > 0)
+            {
+                return [.. signedCms.Certificates];
+            }
+        }
+        catch (CryptographicException)
+        {
+        }
+        return [X509CertificateLoader.LoadCertificate(data)];
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Buffers;
+using System.Diagnostics;
+using System.Globalization;
+using System.Net;
+using System.Text;
+using System.Xml;
+
+namespace WinApp.Cli.Store;
+
+internal sealed record BlockBlobUploadOptions
+{
+    public const int DefaultBlockSize = 8 * 1024 * 1024;
+
+    /// <summary>
+    /// Azure Storage limits a block blob to this many blocks
+    /// </summary>
+    public const int MaxBlockCount = 50_000;
+
+    public const int MaxBlockSize = 100 * 1024 * 1024;
+
+    public int BlockSize { get; init; } = DefaultBlockSize;
+
+    /// <summary>
+    /// Blocks uploaded at the same time
+    /// </summary>
+    public int Concurrency { get; init; } = 4;
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    /// <summary>
+    /// Content-Type the blob is served with; null keeps the service default, application/octet-stream
+    /// </summary>
+    public string? ContentType { get; init; }
+
+    /// <summary>
+    /// Cache-Control header the blob is served with
+    /// </summary>
+    public string? CacheControl { get; init; }
+
+    /// <summary>
+    /// Reuse blocks of an interrupted upload. Turn off when overwriting a blob whose content may have changed
+    /// without changing size, because blocks are matched by index and size only.
+    /// </summary>
+    public bool Resume { get; init; } = true;
+}
+
+/// <param name="Length">Size of the uploaded file in bytes</param>
+/// <param name="BlockCount">Blocks the blob is made of</param>
+/// <param name="ResumedBlocks">Blocks an earlier, interrupted upload had already stored and that were not sent again</param>
+internal sealed record BlockBlobUploadResult(long Length, int BlockCount, int ResumedBlocks, TimeSpan Elapsed)
+{
+    public bool AlreadyCommitted { get; init; }
+}
+
+/// <summary>
+/// Uploads a file to an Azure Storage SAS URL as a block blob, in fixed-size blocks that are committed at the end
+/// </summary>
+/// <remarks>
+/// Block IDs are derived from the block index, so an upload that was interrupted can be resumed by asking the
+/// service which uncommitted blocks it already holds and only sending the rest. Azure keeps uncommitted blocks
+/// for a week. Each block is retried on its own, so a dropped connection costs at most one block.
+/// </remarks>
+internal sealed class BlockBlobUploader(HttpClient http)
+{
+    private const string StorageApiVersion = "2021-08-06";
+
+    public async Task<BlockBlobUploadResult> UploadAsync(FileInfo file, Uri sasUri, BlockBlobUploadOptions options, IProgress<long>? progress = null, Action<string>? onRetry = null, CancellationToken cancellationToken = default)
+    {
+        file.Refresh();
+        if (!file.Exists)
+        {
+            throw new FileNotFoundException($"Upload file not found: {file.FullName}");
+        }
+        if (options.BlockSize <= 0 || options.BlockSize > BlockBlobUploadOptions.MaxBlockSize)
+        {
+            throw new ArgumentOutOfRangeException(nameof(options), $"Block size must be between 1 byte and {BlockBlobUploadOptions.MaxBlockSize / 1048576} MB");
+        }
+
+        var stopwatch = Stopwatch.StartNew();
+        var length = file.Length;
+        var blockSize = GetBlockSize(length, options.BlockSize);
+        var blockCount = (int)Math.Max(1, (length + blockSize - 1) / blockSize);
+        var blockIds = Enumerable.Range(0, blockCount).Select(GetBlockId).ToArray();
+
+        var existing = options.Resume
+            ? await GetBlockListAsync(sasUri, options.Retry, onRetry, cancellationToken)
+            : (Committed: new Dictionary<string, long>(), Uncommitted: new Dictionary<string, long>());
+
+        // A previous run may have committed the blob and then lost the connection before it could record that
+        if (existing.Committed.Count == blockCount && blockIds.Select((id, i) => existing.Committed.TryGetValue(id, out var size) && size == GetBlockLength(i, length, blockSize)).All(ok => ok))
+        {
+            progress?.Report(length);
+            return new BlockBlobUploadResult(length, blockCount, blockCount, stopwatch.Elapsed) { AlreadyCommitted = true };
+        }
+
+        var pending = new List<int>();
+        long completed = 0;
+        for (var i = 0; i < blockCount; i++)
+        {
+            var blockLength = GetBlockLength(i, length, blockSize);
+            if (existing.Uncommitted.TryGetValue(blockIds[i], out var size) && size == blockLength)
+            {
+                completed += blockLength;
+            }
+            else
+            {
+                pending.Add(i);
+            }
+        }
+
+        progress?.Report(completed);
+
+        using (var handle = File.OpenHandle(file.FullName, FileMode.Open, FileAccess.Read, FileShare.Read, FileOptions.Asynchronous))
+        {
+            await Parallel.ForEachAsync(
+                pending,
+                new ParallelOptions { MaxDegreeOfParallelism = Math.Max(1, options.Concurrency), CancellationToken = cancellationToken },
+                async (index, ct) =>
+                {
+                    var blockLength = GetBlockLength(index, length, blockSize);
+                    var buffer = ArrayPool<byte>.Shared.Rent(blockLength);
+                    try
+                    {
+                        var read = 0;
+                        while (read < blockLength)
+                        {
+                            var count = await RandomAccess.ReadAsync(handle, buffer.AsMemory(read, blockLength - read), (long)index * blockSize + read, ct);
+                            if (count == 0)
+                            {
+                                throw new IOException($"{file.Name} changed while it was being uploaded");
+                            }
+                            read += count;
+                        }
+
+                        await PutBlockAsync(sasUri, blockIds[index], buffer, blockLength, options.Retry, onRetry, ct);
+                    }
+                    finally
+                    {
+                        ArrayPool<byte>.Shared.Return(buffer);
+                    }
+
+                    progress?.Report(Interlocked.Add(ref completed, blockLength));
+                });
+        }
+
+        await PutBlockListAsync(sasUri, blockIds, options, onRetry, cancellationToken);
+
+        return new BlockBlobUploadResult(length, blockCount, blockCount - pending.Count, stopwatch.Elapsed);
+    }
+
+    /// <summary>
+    /// Grows the block size when the file would otherwise need more blocks than a blob can hold.
+    /// Resumed uploads must use the same size, so callers persist it.
+    /// </summary>
+    public static int GetBlockSize(long length, int requestedBlockSize)
+    {
+        var minimum = (length + BlockBlobUploadOptions.MaxBlockCount - 1) / BlockBlobUploadOptions.MaxBlockCount;
+        if (minimum > BlockBlobUploadOptions.MaxBlockSize)
+        {
+            throw new InvalidOperationException($"Files larger than {(long)BlockBlobUploadOptions.MaxBlockCount * BlockBlobUploadOptions.MaxBlockSize / 1073741824} GB cannot be uploaded as a block blob");
+        }
+
+        return (int)Math.Max(requestedBlockSize, minimum);
+    }
+
+    // Every block ID of a blob must have the same length
+    internal static string GetBlockId(int index) =>
+        Convert.ToBase64String(Encoding.ASCII.GetBytes($"winapp-{index.ToString("D6", CultureInfo.InvariantCulture)}"));
+
+    private static int GetBlockLength(int index, long length, int blockSize) =>
+        (int)Math.Min(blockSize, length - (long)index * blockSize);
+
+    private async Task<(Dictionary<string, long> Committed, Dictionary<string, long> Uncommitted)> GetBlockListAsync(Uri sasUri, TransientHttpRetry retry, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var uri = AppendQuery(sasUri, "comp=blocklist&blocklisttype=all");
+        using var response = await retry.SendAsync(http, () => CreateRequest(HttpMethod.Get, uri), onRetry, cancellationToken);
+
+        var committed = new Dictionary<string, long>(StringComparer.Ordinal);
+        var uncommitted = new Dictionary<string, long>(StringComparer.Ordinal);
+        if (response.StatusCode == HttpStatusCode.NotFound)
+        {
+            return (committed, uncommitted);
+        }
+
+        await EnsureSuccessAsync(response, "read the block list", cancellationToken);
+
+        var xmlDoc = new XmlDocument();
+        xmlDoc.LoadXml(await response.Content.ReadAsStringAsync(cancellationToken));
+        ReadBlocks(xmlDoc.SelectNodes("/BlockList/CommittedBlocks/Block"), committed);
+        ReadBlocks(xmlDoc.SelectNodes("/BlockList/UncommittedBlocks/Block"), uncommitted);
+        return (committed, uncommitted);
+    }
+
+    private static void ReadBlocks(XmlNodeList? blocks, Dictionary<string, long> target)
+    {
+        if (blocks == null)
+        {
+            return;
+        }
+
+        foreach (XmlElement block in blocks)
+        {
+            var name = block["Name"]?.InnerText;
+            if (name != null && long.TryParse(block["Size"]?.InnerText, NumberStyles.Integer, CultureInfo.InvariantCulture, out var size))
+            {
+                target[name] = size;
+            }
+        }
+    }
+
+    private async Task PutBlockAsync(Uri sasUri, string blockId, byte[] buffer, int length, TransientHttpRetry retry, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var uri = AppendQuery(sasUri, $"comp=block&blockid={Uri.EscapeDataString(blockId)}");
+        using var response = await retry.SendAsync(http, () =>
+        {
+            var request = CreateRequest(HttpMethod.Put, uri);
+            request.Content = new ByteArrayContent(buffer, 0, length);
+            return request;
+        }, onRetry, cancellationToken);
+
+        await EnsureSuccessAsync(response, "upload a block", cancellationToken);
+    }
+
+    private async Task PutBlockListAsync(Uri sasUri, IReadOnlyList<string> blockIds, BlockBlobUploadOptions options, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        var body = new StringBuilder("<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList>");
+        foreach (var blockId in blockIds)
+        {
+            body.Append("<Latest>").Append(blockId).Append("</Latest>");
+        }
+        body.Append("</BlockList>");
+        var content = body.ToString();
+
+        var uri = AppendQuery(sasUri, "comp=blocklist");
+        using var response = await options.Retry.SendAsync(http, () =>
+        {
+            var request = CreateRequest(HttpMethod.Put, uri);
+            request.Content = new StringContent(content, Encoding.UTF8, "application/xml");
+
+            // Blob properties are set when the block list is committed
+            if (options.ContentType != null)
+            {
+                request.Headers.Add("x-ms-blob-content-type", options.ContentType);
+            }
+            if (options.CacheControl != null)
+            {
+                request.Headers.Add("x-ms-blob-cache-control", options.CacheControl);
+            }
+            return request;
+        }, onRetry, cancellationToken);
+
+        await EnsureSuccessAsync(response, "commit the uploaded blocks", cancellationToken);
+    }
+
+    private static HttpRequestMessage CreateRequest(HttpMethod method, Uri uri)
+    {
+        var request = new HttpRequestMessage(method, uri);
+        request.Headers.Add("x-ms-version", StorageApiVersion);
+        return request;
+    }
+
+    private static Uri AppendQuery(Uri uri, string query) =>
+        new(uri.AbsoluteUri + (string.IsNullOrEmpty(uri.Query) ? "?" : "&") + query);
+
+    private static async Task EnsureSuccessAsync(HttpResponseMessage response, string operation, CancellationToken cancellationToken)
+    {
+        if (response.IsSuccessStatusCode)
+        {
+            return;
+        }
+
+        var errorCode = response.Headers.TryGetValues("x-ms-error-code", out var values) ? values.FirstOrDefault() : null;
+        var body = await response.Content.ReadAsStringAsync(cancellationToken);
+        throw new HttpRequestException(
+            $"Failed to {operation}: {(int)response.StatusCode} {errorCode ?? response.ReasonPhrase}{(string.IsNullOrWhiteSpace(body) || errorCode != null ? "" : $" {body}")}",
+            null,
+            response.StatusCode);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.Store;
+
+/// <param name="Filter">Optional OData filter passed through to the API, such as "market eq 'US'"</param>
+internal sealed record StoreStatsQuery(string ApplicationId, StoreStatsReport Report, DateOnly StartDate, DateOnly EndDate, StoreStatsAggregation Aggregation, string? Filter = null);
+
+/// <summary>
+/// Reads reports from the Partner Center analytics API and writes them as JSON or CSV
+/// </summary>
+internal static class PartnerCenterAnalytics
+{
+    private const string AnalyticsPath = "analytics/";
+
+    // Largest page the analytics endpoints accept
+    private const int PageSize = 10000;
+
+    public static string GetEndpoint(StoreStatsReport report) => report switch
+    {
+        StoreStatsReport.Acquisitions => "appacquisitions",
+        StoreStatsReport.Health => "failurehits",
+        StoreStatsReport.Ratings => "ratings",
+        _ => throw new ArgumentOutOfRangeException(nameof(report), report, null)
+    };
+
+    /// <summary>
+    /// Reads every row of a report, following the service's paging links
+    /// </summary>
+    /// <param name="onPage">Called with the number of rows read so far</param>
+    public static Task<List<JsonObject>> QueryAsync(PartnerCenterClient client, StoreStatsQuery query, Action<int>? onPage = null, CancellationToken cancellationToken = default)
+    {
+        if (query.EndDate < query.StartDate)
+        {
+            throw new ArgumentException($"End date {query.EndDate:yyyy-MM-dd} is before start date {query.StartDate:yyyy-MM-dd}", nameof(query));
+        }
+
+        return ReadAllPagesAsync(client, GetEndpoint(query.Report) + BuildQueryString(query), onPage, cancellationToken);
+    }
+
+    /// <param name="endpointAndQuery">Endpoint relative to the analytics API with its query string, such as "reviews?applicationId=..."</param>
+    public static async Task<List<JsonObject>> ReadAllPagesAsync(PartnerCenterClient client, string endpointAndQuery, Action<int>? onPage, CancellationToken cancellationToken)
+    {
+        var rows = new List<JsonObject>();
+        string? nextLink = AnalyticsPath + endpointAndQuery;
+        while (nextLink != null)
+        {
+            var page = await client.GetAsync(nextLink, cancellationToken);
+            if (page?["Value"] is JsonArray values)
+            {
+                foreach (var value in values)
+                {
+                    if (value is JsonObject row)
+                    {
+                        rows.Add(row);
+                    }
+                }
+            }
+
+            onPage?.Invoke(rows.Count);
+
+            // Links are usually relative to the analytics endpoint, e.g. "appacquisitions?applicationId=...&skip=10000"
+            var link = page?["@nextLink"]?.GetValue<string>();
+            nextLink = string.IsNullOrEmpty(link) ? null
+                : link.StartsWith('/') || link.StartsWith(AnalyticsPath, StringComparison.OrdinalIgnoreCase) || Uri.IsWellFormedUriString(link, UriKind.Absolute) ? link
+                : AnalyticsPath + link;
+        }
+
+        return rows;
+    }
+
+    internal static string BuildQueryString(StoreStatsQuery query) =>
+        BuildQueryString(query.ApplicationId, query.StartDate, query.EndDate, query.Filter, $"aggregationLevel={query.Aggregation.ToString().ToLowerInvariant()}");
+
+    internal static string BuildQueryString(string applicationId, DateOnly startDate, DateOnly endDate, string? filter, params string[] extraParameters)
+    {
+        var parameters = new List<string>
+        {
+            $"applicationId={Uri.EscapeDataString(applicationId)}",
+            $"startDate={startDate.ToString("yyyy-MM-dd", CultureInfo.InvariantCulture)}",
+            $"endDate={endDate.ToString("yyyy-MM-dd", CultureInfo.InvariantCulture)}",
+        };
+        parameters.AddRange(extraParameters);
+        parameters.Add($"top={PageSize.ToString(CultureInfo.InvariantCulture)}");
+        parameters.Add("skip=0");
+        if (!string.IsNullOrWhiteSpace(filter))
+        {
+            parameters.Add($"filter={Uri.EscapeDataString(filter)}");
+        }
+
+        return "?" + string.Join("&", parameters);
+    }
+
+    public static async Task WriteAsync(IReadOnlyList<JsonObject> rows, StoreExportFormat format, Stream output, CancellationToken cancellationToken = default)
+    {
+        if (format == StoreExportFormat.Csv)
+        {
+            await using var writer = new StreamWriter(output, new UTF8Encoding(encoderShouldEmitUTF8Identifier: false), leaveOpen: true);
+            await writer.WriteAsync(ToCsv(rows).AsMemory(), cancellationToken);
+            return;
+        }
+
+        await using var jsonWriter = new Utf8JsonWriter(output, new JsonWriterOptions { Indented = true });
+        jsonWriter.WriteStartArray();
+        foreach (var row in rows)
+        {
+            row.WriteTo(jsonWriter);
+        }
+        jsonWriter.WriteEndArray();
+        await jsonWriter.FlushAsync(cancellationToken);
+    }
+
+    /// <summary>
+    /// Formats rows as RFC 4180 CSV. Columns are the union of the row properties in the order they first
+    /// appear; nested objects and arrays are written as JSON.
+    /// </summary>
+    internal static string ToCsv(IReadOnlyList<JsonObject> rows)
+    {
+        var columns = new List<string>();
+        var known = new HashSet<string>(StringComparer.Ordinal);
+        foreach (var row in rows)
+        {
+            foreach (var property in row)
+            {
+                if (known.Add(property.Key))
+                {
+                    columns.Add(property.Key);
+                }
+            }
+        }
+
+        var csv = new StringBuilder();
+        csv.AppendJoin(',', columns.Select(Escape)).Append("\r\n");
+        foreach (var row in rows)
+        {
+            csv.AppendJoin(',', columns.Select(column => Escape(FormatValue(row[column])))).Append("\r\n");
+        }
+
+        return csv.ToString();
+    }
+
+    private static string FormatValue(JsonNode? value) => value switch
+    {
+        null => string.Empty,
+        JsonValue scalar when scalar.GetValueKind() == JsonValueKind.String => scalar.GetValue<string>(),
+        JsonValue scalar => scalar.ToJsonString(),
+        _ => value.ToJsonString()
+    };
+
+    private static string Escape(string value) =>
+        value.IndexOfAny([',', '"', '\r', '\n']) >= 0 ? $"\"{value.Replace("\"", "\"\"")}\"" : value;
+}
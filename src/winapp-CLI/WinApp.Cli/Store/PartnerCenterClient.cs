@@ -0,0 +1,168 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Net.Http.Headers;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+
+namespace WinApp.Cli.Store;
+
+/// <summary>
+/// Minimal client for the Microsoft Store submission API (https://manage.devcenter.microsoft.com). Requests and
+/// responses are JSON nodes, because submissions must be sent back with every property the service returned.
+/// </summary>
+internal sealed class PartnerCenterClient(HttpClient http, StoreCredentials credentials)
+{
+    public const string DefaultResource = "https://manage.devcenter.microsoft.com";
+
+    public static readonly Uri DefaultServiceUri = new("https://manage.devcenter.microsoft.com/v1.0/my/");
+    public static readonly Uri DefaultAuthorityUri = new("https://login.microsoftonline.com/");
+
+    private string? accessToken;
+    private DateTimeOffset accessTokenExpiry;
+
+    public Uri ServiceUri { get; init; } = DefaultServiceUri;
+
+    public Uri AuthorityUri { get; init; } = DefaultAuthorityUri;
+
+    /// <summary>
+    /// Resource the access token is requested for; set with <see cref="ServiceUri"/> to call another Azure AD protected API, such as Microsoft Graph
+    /// </summary>
+    public string Resource { get; init; } = DefaultResource;
+
+    public TransientHttpRetry Retry { get; init; } = TransientHttpRetry.Default;
+
+    /// <summary>
+    /// Called with a description of each transient failure that is about to be retried
+    /// </summary>
+    public Action<string>? OnRetry { get; set; }
+
+    public Task<JsonNode?> GetAsync(string path, CancellationToken cancellationToken) =>
+        SendAsync(HttpMethod.Get, path, null, cancellationToken);
+
+    public Task<JsonNode?> PostAsync(string path, JsonNode? body, CancellationToken cancellationToken) =>
+        SendAsync(HttpMethod.Post, path, body, cancellationToken);
+
+    public Task<JsonNode?> PutAsync(string path, JsonNode body, CancellationToken cancellationToken) =>
+        SendAsync(HttpMethod.Put, path, body, cancellationToken);
+
+    public Task DeleteAsync(string path, CancellationToken cancellationToken) =>
+        SendAsync(HttpMethod.Delete, path, null, cancellationToken);
+
+    /// <param name="path">Path relative to <see cref="ServiceUri"/>, such as "applications/{id}/submissions"</param>
+    /// <returns>The response body, or null when it is empty</returns>
+    public async Task<JsonNode?> SendAsync(HttpMethod method, string path, JsonNode? body, CancellationToken cancellationToken)
+    {
+        var uri = new Uri(ServiceUri, path);
+        var payload = body?.ToJsonString();
+
+        for (var attempt = 0; ; attempt++)
+        {
+            var token = await GetAccessTokenAsync(forceRefresh: attempt > 0, cancellationToken);
+            using var response = await Retry.SendAsync(http, () =>
+            {
+                var request = new HttpRequestMessage(method, uri);
+                request.Headers.Authorization = new AuthenticationHeaderValue("Bearer", token);
+                if (payload != null)
+                {
+                    request.Content = new StringContent(payload, Encoding.UTF8, "application/json");
+                }
+                return request;
+            }, OnRetry, cancellationToken);
+
+            // Tokens can be revoked or expire early; get a new one once before giving up
+            if (response.StatusCode == HttpStatusCode.Unauthorized && attempt == 0)
+            {
+                continue;
+            }
+
+            var content = await response.Content.ReadAsStringAsync(cancellationToken);
+            if (!response.IsSuccessStatusCode)
+            {
+                throw new HttpRequestException($"Partner Center {method} {path} failed: {(int)response.StatusCode} {DescribeError(content) ?? response.ReasonPhrase}", null, response.StatusCode);
+            }
+
+            return string.IsNullOrWhiteSpace(content) ? null : JsonNode.Parse(content);
+        }
+    }
+
+    private async Task<string> GetAccessTokenAsync(bool forceRefresh, CancellationToken cancellationToken)
+    {
+        if (!forceRefresh && accessToken != null && DateTimeOffset.UtcNow < accessTokenExpiry)
+        {
+            return accessToken;
+        }
+
+        var tokenUri = new Uri(AuthorityUri, $"{Uri.EscapeDataString(credentials.TenantId)}/oauth2/token");
+        using var response = await Retry.SendAsync(http, () => new HttpRequestMessage(HttpMethod.Post, tokenUri)
+        {
+            Content = new FormUrlEncodedContent(new Dictionary<string, string>
+            {
+                ["grant_type"] = "client_credentials",
+                ["client_id"] = credentials.ClientId,
+                ["client_secret"] = credentials.ClientSecret,
+                ["resource"] = Resource,
+            })
+        }, OnRetry, cancellationToken);
+
+        var content = await response.Content.ReadAsStringAsync(cancellationToken);
+        if (!response.IsSuccessStatusCode)
+        {
+            throw new InvalidOperationException($"Failed to authenticate with Azure AD tenant {credentials.TenantId}: {(int)response.StatusCode} {DescribeError(content) ?? response.ReasonPhrase}");
+        }
+
+        using var doc = JsonDocument.Parse(content);
+        accessToken = doc.RootElement.GetProperty("access_token").GetString()
+            ?? throw new InvalidOperationException("Azure AD returned an empty access token");
+
+        // expires_in is a string in the v1 endpoint and a number in v2
+        var expiresIn = 3600;
+        if (doc.RootElement.TryGetProperty("expires_in", out var expiresElement))
+        {
+            if (expiresElement.ValueKind == JsonValueKind.Number)
+            {
+                expiresIn = expiresElement.GetInt32();
+            }
+            else if (int.TryParse(expiresElement.GetString(), out var parsed))
+            {
+                expiresIn = parsed;
+            }
+        }
+
+        // Renew a few minutes early so long uploads do not hit an expired token mid-request
+        accessTokenExpiry = DateTimeOffset.UtcNow.AddSeconds(Math.Max(60, expiresIn - 300));
+        return accessToken;
+    }
+
+    private static string? DescribeError(string content)
+    {
+        if (string.IsNullOrWhiteSpace(content))
+        {
+            return null;
+        }
+
+        try
+        {
+            using var doc = JsonDocument.Parse(content);
+            var root = doc.RootElement;
+            if (root.ValueKind != JsonValueKind.Object)
+            {
+                return null;
+            }
+
+            // Partner Center uses { code, message }, Azure AD uses { error, error_description }
+            var code = GetString(root, "code") ?? GetString(root, "error");
+            var message = GetString(root, "message") ?? GetString(root, "error_description");
+            return code != null && message != null ? $"{code}: {message}" : code ?? message;
+        }
+        catch (JsonException)
+        {
+            return content.Length > 200 ? content[..200] : content;
+        }
+    }
+
+    private static string? GetString(JsonElement element, string propertyName) =>
+        element.TryGetProperty(propertyName, out var value) && value.ValueKind == JsonValueKind.String ? value.GetString() : null;
+}
@@ -0,0 +1,166 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+using System.Text.Json.Nodes;
+using System.Xml.Linq;
+
+namespace WinApp.Cli.Store;
+
+/// <param name="ApplicationId">Store ID, e.g. 9NBLGGH4R315</param>
+/// <param name="PrimaryName">The app's first reserved name, which the manifest's DisplayName has to be one of</param>
+/// <param name="PackageIdentityName">Identity Name the Store assigned, e.g. Contoso.Notes</param>
+/// <param name="PublisherName">Identity Publisher of the Partner Center account, e.g. CN=0B5C4F4E-...</param>
+internal sealed record StoreApplication(string ApplicationId, string PrimaryName, string PackageIdentityName, string PublisherName, string PackageFamilyName);
+
+/// <param name="Element">Path of the manifest element, such as Package/Identity</param>
+internal sealed record StoreIdentityChange(string Element, string Attribute, string? OldValue, string NewValue);
+
+/// <summary>
+/// Reads, reserves and releases the apps of the Partner Center account, and writes the identity the Store assigned
+/// one into a manifest, as Visual Studio's "Associate App with the Store" does
+/// </summary>
+internal static class StoreApplications
+{
+    public static async Task<List<StoreApplication>> ListAsync(PartnerCenterClient client, CancellationToken cancellationToken)
+    {
+        var applications = new List<StoreApplication>();
+        string? nextLink = "applications";
+        while (nextLink != null)
+        {
+            var page = await client.GetAsync(nextLink, cancellationToken);
+            foreach (var application in page?["value"] as JsonArray ?? [])
+            {
+                if (application != null)
+                {
+                    applications.Add(ReadApplication(application));
+                }
+            }
+
+            var link = page?["@nextLink"]?.GetValue<string>();
+            nextLink = string.IsNullOrEmpty(link) ? null : link;
+        }
+
+        return applications;
+    }
+
+    public static async Task<StoreApplication> GetAsync(PartnerCenterClient client, string applicationId, CancellationToken cancellationToken)
+    {
+        var application = await client.GetAsync($"applications/{Uri.EscapeDataString(applicationId)}", cancellationToken)
+            ?? throw new InvalidOperationException($"Partner Center returned no app {applicationId}");
+        return ReadApplication(application);
+    }
+
+    /// <summary>
+    /// Finds the app by Store ID, or case-insensitively by reserved name; without either, the account's only app
+    /// </summary>
+    public static async Task<StoreApplication> ResolveAsync(PartnerCenterClient client, string? applicationId, string? name, CancellationToken cancellationToken)
+    {
+        if (applicationId != null)
+        {
+            return await GetAsync(client, applicationId, cancellationToken);
+        }
+
+        var applications = await ListAsync(client, cancellationToken);
+        var candidates = name == null
+            ? applications
+            : applications.Where(a => string.Equals(a.PrimaryName, name, StringComparison.OrdinalIgnoreCase)).ToList();
+        return candidates.Count switch
+        {
+            1 => candidates[0],
+            0 when applications.Count == 0 => throw new InvalidOperationException("The Partner Center account has no apps; reserve a name in Partner Center first"),
+            _ => throw new InvalidOperationException($"{(name == null ? "The account has several apps" : $"No app has the reserved name '{name}'")}; pass --app-id or --name. Apps: {string.Join(", ", applications.Select(a => $"{a.PrimaryName} ({a.ApplicationId})"))}"),
+        };
+    }
+
+    /// <summary>
+    /// Reserves a name for a new app; Partner Center assigns its Store ID and package identity
+    /// </summary>
+    public static async Task<StoreApplication> ReserveAsync(PartnerCenterClient client, string name, CancellationToken cancellationToken)
+    {
+        try
+        {
+            var application = await client.PostAsync("applications", new JsonObject { ["primaryName"] = name }, cancellationToken)
+                ?? throw new InvalidOperationException($"Partner Center returned no app for '{name}'");
+            return ReadApplication(application);
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode == HttpStatusCode.Conflict)
+        {
+            throw new InvalidOperationException($"'{name}' is reserved already, by another app or publisher; choose another name", ex);
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode is HttpStatusCode.NotFound or HttpStatusCode.MethodNotAllowed or HttpStatusCode.Forbidden)
+        {
+            throw new InvalidOperationException($"Partner Center doesn't let this account reserve names through the API; reserve '{name}' under Apps and games > New product in Partner Center instead", ex);
+        }
+    }
+
+    /// <summary>
+    /// Releases the reserved names of an app that was never published, which deletes the app
+    /// </summary>
+    public static async Task ReleaseAsync(PartnerCenterClient client, string applicationId, CancellationToken cancellationToken)
+    {
+        try
+        {
+            await client.DeleteAsync($"applications/{Uri.EscapeDataString(applicationId)}", cancellationToken);
+        }
+        catch (HttpRequestException ex) when (ex.StatusCode is HttpStatusCode.Conflict or HttpStatusCode.BadRequest)
+        {
+            throw new InvalidOperationException($"Partner Center keeps the names of {applicationId}, which has submissions; names of published apps can only be released in Partner Center", ex);
+        }
+    }
+
+    /// <summary>
+    /// Sets the identity Name and Publisher, and the display names of the package and its first application, to the
+    /// ones the Store assigned the app
+    /// </summary>
+    /// <returns>The values that changed</returns>
+    public static List<StoreIdentityChange> ApplyToManifest(XElement root, StoreApplication application)
+    {
+        var changes = new List<StoreIdentityChange>();
+        void Set(XElement? element, string path, string? attribute, string value)
+        {
+            if (element == null)
+            {
+                return;
+            }
+
+            // Localized names are in resources, where the reserved name has to be too
+            var oldValue = attribute != null ? element.Attribute(attribute)?.Value : element.Value.Trim();
+            if (oldValue == value || oldValue?.StartsWith("ms-resource:", StringComparison.OrdinalIgnoreCase) == true)
+            {
+                return;
+            }
+
+            if (attribute != null)
+            {
+                element.SetAttributeValue(attribute, value);
+            }
+            else
+            {
+                element.Value = value;
+            }
+            changes.Add(new StoreIdentityChange(path, attribute ?? "", oldValue, value));
+        }
+
+        var identity = root.Elements().FirstOrDefault(e => e.Name.LocalName == "Identity")
+            ?? throw new InvalidOperationException("AppxManifest.xml has no Identity element");
+        Set(identity, "Package/Identity", "Name", application.PackageIdentityName);
+        Set(identity, "Package/Identity", "Publisher", application.PublisherName);
+
+        var properties = root.Elements().FirstOrDefault(e => e.Name.LocalName == "Properties");
+        Set(properties?.Elements().FirstOrDefault(e => e.Name.LocalName == "DisplayName"), "Package/Properties/DisplayName", null, application.PrimaryName);
+
+        // Other applications of the package keep their names
+        var visualElements = root.Descendants().FirstOrDefault(e => e.Name.LocalName == "VisualElements");
+        Set(visualElements, $"Package/Applications/Application[{visualElements?.Parent?.Attribute("Id")?.Value}]/VisualElements", "DisplayName", application.PrimaryName);
+
+        return changes;
+    }
+
+    private static StoreApplication ReadApplication(JsonNode application) => new(
+        application["id"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center returned an app without an ID"),
+        application["primaryName"]?.GetValue<string>() ?? string.Empty,
+        application["packageIdentityName"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center returned an app without a package identity name"),
+        application["publisherName"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center returned an app without a publisher"),
+        application["packageFamilyName"]?.GetValue<string>() ?? string.Empty);
+}
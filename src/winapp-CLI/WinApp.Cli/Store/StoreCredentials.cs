@@ -0,0 +1,59 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+namespace WinApp.Cli.Store;
+
+/// <summary>
+/// Azure AD application used to call the Microsoft Store submission API. The application must be associated
+/// with the Partner Center account (Account settings > Users > Azure AD applications).
+/// </summary>
+internal sealed record StoreCredentials(string TenantId, string ClientId, string ClientSecret)
+{
+    public const string TenantIdVariable = "WINAPP_STORE_TENANT_ID";
+    public const string ClientIdVariable = "WINAPP_STORE_CLIENT_ID";
+    public const string ClientSecretVariable = "WINAPP_STORE_CLIENT_SECRET";
+
+    /// <summary>
+    /// Reads the credentials from the WINAPP_STORE_* environment variables, so secrets never appear on a command line
+    /// </summary>
+    public static StoreCredentials FromEnvironment() =>
+        FromEnvironment(TenantIdVariable, ClientIdVariable, ClientSecretVariable, "Microsoft Store credentials are not configured", "the Azure AD application associated with your Partner Center account");
+
+    /// <summary>
+    /// Reads the AZURE_TENANT_ID, AZURE_CLIENT_ID and AZURE_CLIENT_SECRET environment variables that Azure tooling
+    /// such as Trusted Signing uses, for calls to Azure Resource Manager
+    /// </summary>
+    public static StoreCredentials FromAzureEnvironment() =>
+        FromEnvironment("AZURE_TENANT_ID", "AZURE_CLIENT_ID", "AZURE_CLIENT_SECRET", "Azure credentials are not configured", "a service principal with read access to the resource");
+
+    private static StoreCredentials FromEnvironment(string tenantIdVariable, string clientIdVariable, string clientSecretVariable, string problem, string principal)
+    {
+        var tenantId = Environment.GetEnvironmentVariable(tenantIdVariable);
+        var clientId = Environment.GetEnvironmentVariable(clientIdVariable);
+        var clientSecret = Environment.GetEnvironmentVariable(clientSecretVariable);
+
+        var missing = new List<string>();
+        if (string.IsNullOrWhiteSpace(tenantId))
+        {
+            missing.Add(tenantIdVariable);
+        }
+        if (string.IsNullOrWhiteSpace(clientId))
+        {
+            missing.Add(clientIdVariable);
+        }
+        if (string.IsNullOrWhiteSpace(clientSecret))
+        {
+            missing.Add(clientSecretVariable);
+        }
+
+        if (missing.Count > 0)
+        {
+            throw new InvalidOperationException($"{problem}. Set {string.Join(", ", missing)} to {principal}.");
+        }
+
+        return new StoreCredentials(tenantId!, clientId!, clientSecret!);
+    }
+
+    // Keep the secret out of logs and exception messages
+    public override string ToString() => $"{nameof(StoreCredentials)} {{ TenantId = {TenantId}, ClientId = {ClientId} }}";
+}
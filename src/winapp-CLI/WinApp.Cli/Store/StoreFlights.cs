@@ -0,0 +1,206 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Text.Json.Nodes;
+
+namespace WinApp.Cli.Store;
+
+/// <param name="GroupIds">Flight groups whose members get the flight's packages</param>
+/// <param name="RankHigherThan">Flight ID, or "Non-flighted submission", that this flight takes precedence over</param>
+internal sealed record StoreFlight(string FlightId, string FriendlyName, IReadOnlyList<string> GroupIds, string? RankHigherThan, string? LastPublishedSubmissionId, string? PendingSubmissionId);
+
+/// <summary>
+/// Manages package flights of a Store app, and builds member lists for flight groups
+/// </summary>
+internal static class StoreFlights
+{
+    public static readonly Uri GraphServiceUri = new("https://graph.microsoft.com/v1.0/");
+    public const string GraphResource = "https://graph.microsoft.com";
+
+    public static async Task<List<StoreFlight>> ListAsync(PartnerCenterClient client, string applicationId, CancellationToken cancellationToken)
+    {
+        var flights = new List<StoreFlight>();
+        string? nextLink = $"{ApplicationPath(applicationId)}/listflights";
+        while (nextLink != null)
+        {
+            var page = await client.GetAsync(nextLink, cancellationToken);
+            foreach (var flight in page?["value"] as JsonArray ?? [])
+            {
+                if (flight != null)
+                {
+                    flights.Add(ReadFlight(flight));
+                }
+            }
+
+            var link = page?["@nextLink"]?.GetValue<string>();
+            nextLink = string.IsNullOrEmpty(link) ? null : link;
+        }
+
+        return flights;
+    }
+
+    /// <summary>
+    /// Finds a flight by ID or, case-insensitively, by friendly name
+    /// </summary>
+    public static async Task<StoreFlight> ResolveAsync(PartnerCenterClient client, string applicationId, string flightIdOrName, CancellationToken cancellationToken)
+    {
+        var flights = await ListAsync(client, applicationId, cancellationToken);
+        return flights.FirstOrDefault(f => f.FlightId == flightIdOrName)
+            ?? flights.FirstOrDefault(f => string.Equals(f.FriendlyName, flightIdOrName, StringComparison.OrdinalIgnoreCase))
+            ?? throw new InvalidOperationException($"Application {applicationId} has no flight named or with ID '{flightIdOrName}'. Flights: {(flights.Count == 0 ? "none" : string.Join(", ", flights.Select(f => f.FriendlyName)))}");
+    }
+
+    /// <param name="groupIds">IDs of existing flight groups, as shown in the URL of the group in Partner Center</param>
+    /// <param name="rankHigherThan">Flight ID that the new flight takes precedence over; null ranks it above the non-flighted submission only</param>
+    public static async Task<StoreFlight> CreateAsync(PartnerCenterClient client, string applicationId, string friendlyName, IReadOnlyList<string> groupIds, string? rankHigherThan, CancellationToken cancellationToken)
+    {
+        if (groupIds.Count == 0)
+        {
+            throw new ArgumentException("A flight needs at least one flight group", nameof(groupIds));
+        }
+
+        var body = new JsonObject
+        {
+            ["friendlyName"] = friendlyName,
+            ["groupIds"] = new JsonArray(groupIds.Select(id => (JsonNode?)JsonValue.Create(id)).ToArray()),
+            ["rankHigherThan"] = rankHigherThan ?? "Non-flighted submission",
+        };
+
+        var flight = await client.PostAsync($"{ApplicationPath(applicationId)}/flights", body, cancellationToken)
+            ?? throw new InvalidOperationException("Partner Center did not return the new flight");
+        return ReadFlight(flight);
+    }
+
+    public static Task DeleteAsync(PartnerCenterClient client, string applicationId, string flightId, CancellationToken cancellationToken) =>
+        client.DeleteAsync($"{ApplicationPath(applicationId)}/flights/{Uri.EscapeDataString(flightId)}", cancellationToken);
+
+    /// <summary>
+    /// Reads email addresses from a CSV file: the column named email, mail or userPrincipalName if there is a
+    /// header, otherwise every value that looks like an address
+    /// </summary>
+    public static List<string> ReadMembersCsv(string content)
+    {
+        var lines = content.Split('\n').Select(line => line.Trim('\r', ' ')).Where(line => line.Length > 0).ToList();
+        if (lines.Count == 0)
+        {
+            return [];
+        }
+
+        var header = SplitCsvLine(lines[0]);
+        var column = header.FindIndex(name => name.Trim() is var n
+            && (n.Equals("email", StringComparison.OrdinalIgnoreCase) || n.Equals("mail", StringComparison.OrdinalIgnoreCase) || n.Equals("userPrincipalName", StringComparison.OrdinalIgnoreCase)));
+
+        var members = new List<string>();
+        foreach (var line in column >= 0 ? lines.Skip(1) : lines)
+        {
+            var values = SplitCsvLine(line);
+            var candidates = column >= 0 ? values.Skip(column).Take(1) : values;
+            members.AddRange(candidates.Select(v => v.Trim()).Where(IsEmailAddress));
+        }
+
+        return members;
+    }
+
+    /// <summary>
+    /// Reads the email addresses of the users in an Azure AD group, including members of nested groups
+    /// </summary>
+    /// <param name="graphClient">Client for Microsoft Graph; the Azure AD app needs the GroupMember.Read.All application permission</param>
+    public static async Task<List<string>> GetAadGroupMembersAsync(PartnerCenterClient graphClient, string groupId, CancellationToken cancellationToken)
+    {
+        var members = new List<string>();
+        string? nextLink = $"groups/{Uri.EscapeDataString(groupId)}/transitiveMembers/microsoft.graph.user?$select=mail,userPrincipalName&$top=999";
+        while (nextLink != null)
+        {
+            var page = await graphClient.GetAsync(nextLink, cancellationToken);
+            foreach (var user in page?["value"] as JsonArray ?? [])
+            {
+                // Guests and unlicensed users may have no mail; their UPN is what they sign in to the Store with
+                var address = user?["mail"]?.GetValue<string>() ?? user?["userPrincipalName"]?.GetValue<string>();
+                if (address != null && IsEmailAddress(address))
+                {
+                    members.Add(address);
+                }
+            }
+
+            // Graph returns absolute links, which the client uses as they are
+            nextLink = page?["@odata.nextLink"]?.GetValue<string>();
+        }
+
+        return members;
+    }
+
+    /// <summary>
+    /// Writes a member list to upload to a flight group in Partner Center: one address per line, without
+    /// duplicates, sorted so regenerated files diff cleanly
+    /// </summary>
+    public static string ToMembersCsv(IEnumerable<string> members)
+    {
+        var builder = new StringBuilder();
+        foreach (var member in members.Select(m => m.Trim()).Distinct(StringComparer.OrdinalIgnoreCase).Order(StringComparer.OrdinalIgnoreCase))
+        {
+            builder.Append(member).Append("\r\n");
+        }
+
+        return builder.ToString();
+    }
+
+    private static StoreFlight ReadFlight(JsonNode flight) => new(
+        flight["flightId"]?.GetValue<string>() ?? throw new InvalidOperationException("Partner Center returned a flight without an ID"),
+        flight["friendlyName"]?.GetValue<string>() ?? string.Empty,
+        (flight["groupIds"] as JsonArray ?? []).Select(id => id?.GetValue<string>()).OfType<string>().ToList(),
+        flight["rankHigherThan"]?.GetValue<string>(),
+        flight["lastPublishedFlightSubmission"]?["id"]?.GetValue<string>(),
+        flight["pendingFlightSubmission"]?["id"]?.GetValue<string>());
+
+    private static List<string> SplitCsvLine(string line)
+    {
+        var values = new List<string>();
+        var value = new StringBuilder();
+        var quoted = false;
+        for (var i = 0; i < line.Length; i++)
+        {
+            var c = line[i];
+            if (quoted)
+            {
+                if (c == '"' && i + 1 < line.Length && line[i + 1] == '"')
+                {
+                    value.Append('"');
+                    i++;
+                }
+                else if (c == '"')
+                {
+                    quoted = false;
+                }
+                else
+                {
+                    value.Append(c);
+                }
+            }
+            else if (c == '"')
+            {
+                quoted = true;
+            }
+            else if (c is ',' or ';')
+            {
+                values.Add(value.ToString());
+                value.Clear();
+            }
+            else
+            {
+                value.Append(c);
+            }
+        }
+        values.Add(value.ToString());
+
+        return values;
+    }
+
+    private static bool IsEmailAddress(string value)
+    {
+        var at = value.IndexOf('@');
+        return at > 0 && at == value.LastIndexOf('@') && value.IndexOf('.', at) > at + 1 && !value.Any(char.IsWhiteSpace);
+    }
+
+    private static string ApplicationPath(string applicationId) => $"applications/{Uri.EscapeDataString(applicationId)}";
+}
@@ -0,0 +1,218 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+using System.Text.RegularExpressions;
+
+namespace WinApp.Cli.Store;
+
+/// <param name="Ratings">Star ratings to include; empty includes all</param>
+/// <param name="Market">Two-letter market (country) code, such as US</param>
+/// <param name="PackageVersion">Package version the reviewer had installed, such as 1.2.0.0</param>
+/// <param name="UnansweredOnly">Only reviews without a developer response</param>
+internal sealed record StoreReviewQuery(
+    string ApplicationId,
+    DateOnly StartDate,
+    DateOnly EndDate,
+    IReadOnlyList<int> Ratings,
+    string? Market = null,
+    string? PackageVersion = null,
+    bool UnansweredOnly = false);
+
+/// <param name="Successful">Whether the Store accepted the response</param>
+/// <param name="FailureReason">Why the response was rejected, such as the review having been deleted</param>
+internal sealed record StoreReviewResponseResult(string ReviewId, bool Successful, string? FailureReason);
+
+/// <summary>
+/// Reads Store reviews through the analytics API and posts developer responses to them
+/// </summary>
+internal static partial class StoreReviews
+{
+    /// <summary>
+    /// Longest developer response the Store accepts
+    /// </summary>
+    public const int MaxResponseLength = 1000;
+
+    // The responses endpoint takes a batch of reviews per request
+    private const int ResponseBatchSize = 50;
+
+    public static async Task<List<JsonObject>> ListAsync(PartnerCenterClient client, StoreReviewQuery query, Action<int>? onPage = null, CancellationToken cancellationToken = default)
+    {
+        if (query.EndDate < query.StartDate)
+        {
+            throw new ArgumentException($"End date {query.EndDate:yyyy-MM-dd} is before start date {query.StartDate:yyyy-MM-dd}", nameof(query));
+        }
+        if (query.Ratings.Any(r => r is < 1 or > 5))
+        {
+            throw new ArgumentException("Ratings must be between 1 and 5", nameof(query));
+        }
+
+        var queryString = PartnerCenterAnalytics.BuildQueryString(query.ApplicationId, query.StartDate, query.EndDate, BuildFilter(query), "orderby=date%20desc");
+        var reviews = await PartnerCenterAnalytics.ReadAllPagesAsync(client, "reviews" + queryString, onPage, cancellationToken);
+
+        // Responses cannot be filtered on by the API
+        if (query.UnansweredOnly)
+        {
+            reviews.RemoveAll(review => !string.IsNullOrWhiteSpace(GetString(review, "responseText")));
+        }
+
+        return reviews;
+    }
+
+    internal static string? BuildFilter(StoreReviewQuery query)
+    {
+        var clauses = new List<string>();
+        if (query.Ratings.Count > 0)
+        {
+            var ratings = query.Ratings.Distinct().Order().Select(r => $"rating eq {r.ToString(CultureInfo.InvariantCulture)}").ToList();
+            clauses.Add(ratings.Count == 1 ? ratings[0] : $"({string.Join(" or ", ratings)})");
+        }
+        if (!string.IsNullOrWhiteSpace(query.Market))
+        {
+            clauses.Add($"market eq '{EscapeODataString(query.Market.ToUpperInvariant())}'");
+        }
+        if (!string.IsNullOrWhiteSpace(query.PackageVersion))
+        {
+            clauses.Add($"packageVersion eq '{EscapeODataString(query.PackageVersion)}'");
+        }
+
+        return clauses.Count == 0 ? null : string.Join(" and ", clauses);
+    }
+
+    /// <summary>
+    /// Replaces {placeholders} in a response template with properties of the review, such as {reviewerName},
+    /// {rating}, {packageVersion} or {market}. Placeholder names are case-insensitive; "{{" and "}}" produce
+    /// literal braces.
+    /// </summary>
+    public static string RenderTemplate(string template, JsonObject review)
+    {
+        var text = PlaceholderRegex().Replace(template, match =>
+        {
+            if (match.Value == "{{")
+            {
+                return "{";
+            }
+            if (match.Value == "}}")
+            {
+                return "}";
+            }
+
+            var name = match.Groups["name"].Value;
+            var property = review.FirstOrDefault(p => string.Equals(p.Key, name, StringComparison.OrdinalIgnoreCase));
+            if (property.Key == null)
+            {
+                throw new InvalidOperationException($"Unknown placeholder {{{name}}} in the response template. Reviews have: {string.Join(", ", review.Select(p => p.Key))}");
+            }
+
+            return property.Value switch
+            {
+                null => string.Empty,
+                JsonValue value when value.GetValueKind() == JsonValueKind.String => value.GetValue<string>(),
+                var value => value.ToJsonString()
+            };
+        });
+
+        return text.Trim();
+    }
+
+    /// <summary>
+    /// Posts developer responses, batching them to limit the number of requests
+    /// </summary>
+    /// <param name="supportEmail">Address shown with the response so customers can follow up; optional</param>
+    /// <param name="isPublic">Public responses are shown in the Store; private ones are only emailed to the reviewer</param>
+    public static async Task<List<StoreReviewResponseResult>> RespondAsync(PartnerCenterClient client, string applicationId, IReadOnlyList<(string ReviewId, string Text)> responses, string? supportEmail, bool isPublic, CancellationToken cancellationToken = default)
+    {
+        foreach (var (reviewId, text) in responses)
+        {
+            if (string.IsNullOrWhiteSpace(text))
+            {
+                throw new InvalidOperationException($"The response to review {reviewId} is empty");
+            }
+            if (text.Length > MaxResponseLength)
+            {
+                throw new InvalidOperationException($"The response to review {reviewId} is {text.Length} characters; the Store accepts at most {MaxResponseLength}");
+            }
+        }
+
+        var results = new List<StoreReviewResponseResult>();
+        foreach (var batch in responses.Chunk(ResponseBatchSize))
+        {
+            var items = new JsonArray();
+            foreach (var (reviewId, text) in batch)
+            {
+                var item = new JsonObject
+                {
+                    ["ApplicationId"] = applicationId,
+                    ["ReviewId"] = reviewId,
+                    ["ResponseText"] = text,
+                    ["IsPublic"] = isPublic,
+                };
+                if (!string.IsNullOrWhiteSpace(supportEmail))
+                {
+                    item["SupportEmail"] = supportEmail;
+                }
+                items.Add(item);
+            }
+
+            var response = await client.PostAsync("reviews/responses", new JsonObject { ["Responses"] = items }, cancellationToken);
+            foreach (var result in response?["Result"] as JsonArray ?? [])
+            {
+                if (result is not JsonObject resultObject)
+                {
+                    continue;
+                }
+
+                // "Successful" is documented as a string, but accept a boolean too
+                var successful = resultObject["Successful"] is JsonValue value
+                    && (value.GetValueKind() == JsonValueKind.True
+                        || (value.GetValueKind() == JsonValueKind.String && bool.TryParse(value.GetValue<string>(), out var parsed) && parsed));
+                var failureReason = GetString(resultObject, "FailureReason");
+                results.Add(new StoreReviewResponseResult(GetString(resultObject, "ReviewId") ?? string.Empty, successful, string.IsNullOrWhiteSpace(failureReason) ? null : failureReason));
+            }
+        }
+
+        return results;
+    }
+
+    /// <summary>
+    /// One-line summary of a review for terminal output
+    /// </summary>
+    public static string Describe(JsonObject review)
+    {
+        var rating = GetInt(review, "rating") ?? 0;
+        var description = new StringBuilder()
+            .Append(new string('★', Math.Clamp(rating, 0, 5))).Append(new string('☆', 5 - Math.Clamp(rating, 0, 5)))
+            .Append(' ').Append(GetString(review, "market") ?? "??")
+            .Append(' ').Append(GetString(review, "packageVersion") ?? "unknown version");
+
+        var title = GetString(review, "reviewTitle");
+        var text = GetString(review, "reviewText");
+        var summary = string.IsNullOrWhiteSpace(title) ? text : title;
+        if (!string.IsNullOrWhiteSpace(summary))
+        {
+            summary = summary.ReplaceLineEndings(" ");
+            description.Append(" \"").Append(summary.Length > 60 ? summary[..57] + "..." : summary).Append('"');
+        }
+
+        if (!string.IsNullOrWhiteSpace(GetString(review, "responseText")))
+        {
+            description.Append(" (responded)");
+        }
+
+        return description.Append(" [").Append(GetString(review, "id")).Append(']').ToString();
+    }
+
+    public static string? GetString(JsonObject review, string propertyName) =>
+        review[propertyName] is JsonValue value && value.GetValueKind() == JsonValueKind.String ? value.GetValue<string>() : null;
+
+    public static int? GetInt(JsonObject review, string propertyName) =>
+        review[propertyName] is JsonValue value && value.GetValueKind() == JsonValueKind.Number && value.TryGetValue<int>(out var number) ? number : null;
+
+    private static string EscapeODataString(string value) => value.Replace("'", "''");
+
+    [GeneratedRegex(@"\{\{|\}\}|\{(?<name>[A-Za-z][A-Za-z0-9_]*)\}")]
+    private static partial Regex PlaceholderRegex();
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Globalization;
+using System.Text.Json;
+using System.Text.Json.Nodes;
+
+namespace WinApp.Cli.Store;
+
+/// <param name="IsPackageRollout">Whether the submission's packages are rolled out gradually</param>
+/// <param name="Percentage">Share of customers, 0 to 100, that get the submission's packages</param>
+/// <param name="Status">PackageRolloutNotStarted, PackageRolloutInProgress, PackageRolloutComplete or PackageRolloutStopped</param>
+/// <param name="FallbackSubmissionId">Submission whose packages the remaining customers get</param>
+internal sealed record StorePackageRollout(bool IsPackageRollout, double Percentage, string? Status, string? FallbackSubmissionId);
+
+/// <param name="SubmissionStatus">Status of the submission itself, such as Certification or Published</param>
+internal sealed record StoreRolloutStatus(string SubmissionId, string SubmissionStatus, StorePackageRollout Rollout);
+
+/// <summary>
+/// Controls gradual package rollouts of Store submissions
+/// </summary>
+internal static class StoreRollout
+{
+    // Submission statuses after which the Store is no longer working on the submission
+    private static readonly string[] FinalStatuses = ["Published", "PendingPublication", "PendingCommit", "Canceled", "CommitFailed", "PreProcessingFailed", "CertificationFailed", "ReleaseFailed", "PublishFailed"];
+
+    /// <summary>
+    /// Parses a rollout percentage such as "25", "25%" or "12.5%"
+    /// </summary>
+    public static double ParsePercentage(string value)
+    {
+        var trimmed = value.Trim().TrimEnd('%').TrimEnd();
+        if (!double.TryParse(trimmed, NumberStyles.AllowDecimalPoint, CultureInfo.InvariantCulture, out var percentage) || percentage is < 0 or > 100)
+        {
+            throw new FormatException($"'{value}' is not a percentage between 0 and 100");
+        }
+
+        return percentage;
+    }
+
+    public static bool IsFinal(string submissionStatus) => FinalStatuses.Contains(submissionStatus, StringComparer.OrdinalIgnoreCase);
+
+    /// <summary>
+    /// Enables a gradual rollout on a submission that has not been committed yet
+    /// </summary>
+    public static void Apply(JsonNode submission, double percentage)
+    {
+        var deliveryOptions = submission["packageDeliveryOptions"] as JsonObject ?? new JsonObject();
+        submission["packageDeliveryOptions"] = deliveryOptions;
+        var rollout = deliveryOptions["packageRollout"] as JsonObject ?? new JsonObject();
+        deliveryOptions["packageRollout"] = rollout;
+
+        rollout["isPackageRollout"] = true;
+        rollout["packageRolloutPercentage"] = percentage;
+    }
+
+    /// <summary>
+    /// Returns the given submission, or the app's last published submission, which is the one a rollout applies to
+    /// </summary>
+    public static async Task<string> ResolveSubmissionIdAsync(PartnerCenterClient client, string applicationId, string? submissionId, CancellationToken cancellationToken)
+    {
+        if (!string.IsNullOrWhiteSpace(submissionId))
+        {
+            return submissionId;
+        }
+
+        var application = await client.GetAsync(ApplicationPath(applicationId), cancellationToken)
+            ?? throw new InvalidOperationException($"Partner Center returned no data for application {applicationId}");
+        return application["lastPublishedApplicationSubmission"]?["id"]?.GetValue<string>()
+            ?? throw new InvalidOperationException($"Application {applicationId} has no published submission; pass --submission-id to select one");
+    }
+
+    public static async Task<StoreRolloutStatus> GetStatusAsync(PartnerCenterClient client, string applicationId, string submissionId, CancellationToken cancellationToken)
+    {
+        var submissionPath = SubmissionPath(applicationId, submissionId);
+        var status = await client.GetAsync($"{submissionPath}/status", cancellationToken);
+        var rollout = await client.GetAsync($"{submissionPath}/packagerollout", cancellationToken);
+
+        return new StoreRolloutStatus(submissionId, status?["status"]?.GetValue<string>() ?? "Unknown", ReadRollout(rollout));
+    }
+
+    /// <summary>
+    /// Changes the share of customers that get the submission's packages. The Store only allows increasing it.
+    /// </summary>
+    public static async Task<StorePackageRollout> SetPercentageAsync(PartnerCenterClient client, string applicationId, string submissionId, double percentage, CancellationToken cancellationToken)
+    {
+        var current = ReadRollout(await client.GetAsync($"{SubmissionPath(applicationId, submissionId)}/packagerollout", cancellationToken));
+        if (!current.IsPackageRollout)
+        {
+            throw new InvalidOperationException($"Submission {submissionId} is not a gradual rollout");
+        }
+        if (percentage < current.Percentage)
+        {
+            throw new InvalidOperationException($"Submission {submissionId} is already rolled out to {current.Percentage:0.##}%; the Store does not allow lowering the percentage. Use 'halt' to stop the rollout instead");
+        }
+
+        var path = $"{SubmissionPath(applicationId, submissionId)}/updatepackagerolloutpercentage?percentage={percentage.ToString(CultureInfo.InvariantCulture)}";
+        return ReadRollout(await client.PostAsync(path, null, cancellationToken));
+    }
+
+    /// <summary>
+    /// Stops the rollout: customers that do not have the new packages yet keep getting the fallback submission's packages
+    /// </summary>
+    public static async Task<StorePackageRollout> HaltAsync(PartnerCenterClient client, string applicationId, string submissionId, CancellationToken cancellationToken) =>
+        ReadRollout(await client.PostAsync($"{SubmissionPath(applicationId, submissionId)}/haltpackagerollout", null, cancellationToken));
+
+    /// <summary>
+    /// Completes the rollout, giving the submission's packages to all customers
+    /// </summary>
+    public static async Task<StorePackageRollout> FinalizeAsync(PartnerCenterClient client, string applicationId, string submissionId, CancellationToken cancellationToken) =>
+        ReadRollout(await client.PostAsync($"{SubmissionPath(applicationId, submissionId)}/finalizepackagerollout", null, cancellationToken));
+
+    internal static StorePackageRollout ReadRollout(JsonNode? node)
+    {
+        // Submissions nest the rollout in packageDeliveryOptions; the rollout endpoints return it directly
+        var rollout = node?["packageDeliveryOptions"]?["packageRollout"] ?? node?["packageRollout"] ?? node;
+
+        return new StorePackageRollout(
+            rollout?["isPackageRollout"] is JsonValue isRollout && isRollout.GetValueKind() == JsonValueKind.True,
+            rollout?["packageRolloutPercentage"] is JsonValue percentage && percentage.TryGetValue<double>(out var value) ? value : 0,
+            rollout?["packageRolloutStatus"]?.GetValue<string>(),
+            rollout?["fallbackSubmissionId"]?.GetValue<string>() is { Length: > 0 } fallback && fallback != "0" ? fallback : null);
+    }
+
+    private static string ApplicationPath(string applicationId) => $"applications/{Uri.EscapeDataString(applicationId)}";
+
+    private static string SubmissionPath(string applicationId, string submissionId) => $"{ApplicationPath(applicationId)}/submissions/{Uri.EscapeDataString(submissionId)}";
+}
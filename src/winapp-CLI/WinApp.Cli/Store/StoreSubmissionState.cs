@@ -0,0 +1,143 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text.Json.Serialization;
+
+namespace WinApp.Cli.Store;
+
+[JsonSerializable(typeof(StoreSubmissionState))]
+[JsonSourceGenerationOptions(
+    WriteIndented = true,
+    DefaultIgnoreCondition = JsonIgnoreCondition.WhenWritingNull)]
+internal partial class StoreJsonContext : JsonSerializerContext
+{
+}
+
+/// <summary>
+/// Progress of a submission, saved after every step so a run that fails part way (typically a dropped
+/// connection during the upload) continues where it stopped instead of creating a new submission
+/// </summary>
+internal sealed class StoreSubmissionState
+{
+    public string ApplicationId { get; set; } = string.Empty;
+
+    public string SubmissionId { get; set; } = string.Empty;
+
+    /// <summary>
+    /// SAS URL returned when the submission was created. It expires; a fresh one is read from the
+    /// submission when the upload is rejected.
+    /// </summary>
+    public string FileUploadUrl { get; set; } = string.Empty;
+
+    public List<StoreSubmissionPackage> Packages { get; set; } = [];
+
+    /// <summary>
+    /// Block size the upload started with; resumed uploads must keep it so block IDs line up
+    /// </summary>
+    public int? BlockSize { get; set; }
+
+    public bool PackagesUpdated { get; set; }
+
+    public bool Uploaded { get; set; }
+
+    public bool CommitStarted { get; set; }
+
+    public DateTimeOffset CreatedAt { get; set; }
+
+    /// <summary>
+    /// Whether this state was saved for the same application and the same, unchanged package files
+    /// </summary>
+    public bool Matches(string applicationId, IReadOnlyList<FileInfo> packages)
+    {
+        if (!string.Equals(ApplicationId, applicationId, StringComparison.OrdinalIgnoreCase) || Packages.Count != packages.Count)
+        {
+            return false;
+        }
+
+        for (var i = 0; i < packages.Count; i++)
+        {
+            var file = packages[i];
+            file.Refresh();
+            var saved = Packages[i];
+            if (!file.Exists
+                || !string.Equals(saved.Path, file.FullName, StringComparison.OrdinalIgnoreCase)
+                || saved.Length != file.Length
+                || saved.LastWriteTimeUtc != file.LastWriteTimeUtc)
+            {
+                return false;
+            }
+        }
+
+        return true;
+    }
+}
+
+/// <summary>
+/// A package file of a submission, fingerprinted by size and timestamp so a rebuilt package is not
+/// mistaken for a partially uploaded one
+/// </summary>
+internal sealed class StoreSubmissionPackage
+{
+    public string Path { get; set; } = string.Empty;
+
+    public long Length { get; set; }
+
+    public DateTime LastWriteTimeUtc { get; set; }
+
+    public static StoreSubmissionPackage FromFile(FileInfo file) => new()
+    {
+        Path = file.FullName,
+        Length = file.Length,
+        LastWriteTimeUtc = file.LastWriteTimeUtc,
+    };
+}
+
+/// <param name="SubmissionId">Partner Center submission ID</param>
+/// <param name="Status">Submission status after the last step, such as PendingCommit or CommitStarted</param>
+/// <param name="Resumed">Whether the submission was continued from an earlier, interrupted run</param>
+/// <param name="Upload">Upload statistics; null when the packages had already been uploaded by an earlier run</param>
+internal sealed record StoreSubmissionResult(string SubmissionId, string Status, bool Resumed, BlockBlobUploadResult? Upload);
+
+internal sealed record StoreSubmitOptions
+{
+    public int BlockSize { get; init; } = BlockBlobUploadOptions.DefaultBlockSize;
+
+    public int Concurrency { get; init; } = 4;
+
+    /// <summary>
+    /// Delete a pending submission that was not created by an earlier run of this command
+    /// </summary>
+    public bool ReplacePending { get; init; }
+
+    /// <summary>
+    /// Remove the packages of the previous submission instead of adding to them
+    /// </summary>
+    public bool ReplacePackages { get; init; }
+
+    /// <summary>
+    /// Package flight to submit to instead of the app's public listing
+    /// </summary>
+    public string? FlightId { get; init; }
+
+    /// <summary>
+    /// Start the submission as a gradual rollout to this percentage of customers
+    /// </summary>
+    public double? RolloutPercentage { get; init; }
+
+    /// <summary>
+    /// Release notes to set on every language of the listing; app submissions only
+    /// </summary>
+    public string? ReleaseNotes { get; init; }
+
+    public bool Commit { get; init; } = true;
+
+    /// <summary>
+    /// Poll the submission after committing until the Store has finished processing the commit
+    /// </summary>
+    public bool Wait { get; init; }
+
+    /// <summary>
+    /// Ignore saved progress and start a new upload
+    /// </summary>
+    public bool Restart { get; init; }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Net;
+
+namespace WinApp.Cli.Store;
+
+/// <summary>
+/// Retries HTTP requests that fail with errors a flaky network or a throttling service is expected to return
+/// </summary>
+internal sealed class TransientHttpRetry
+{
+    public static TransientHttpRetry Default { get; } = new();
+
+    /// <summary>
+    /// Total attempts per request, including the first one
+    /// </summary>
+    public int MaxAttempts { get; init; } = 6;
+
+    /// <summary>
+    /// Delay before the first retry; doubles on every further retry
+    /// </summary>
+    public TimeSpan InitialDelay { get; init; } = TimeSpan.FromSeconds(1);
+
+    public TimeSpan MaxDelay { get; init; } = TimeSpan.FromSeconds(60);
+
+    /// <summary>
+    /// Sends a request until it succeeds, fails with a non-transient status, or runs out of attempts.
+    /// The factory is called once per attempt because a request message cannot be sent twice.
+    /// </summary>
+    /// <param name="onRetry">Called with a description of the failure before waiting for the next attempt</param>
+    /// <returns>The last response; the caller checks its status code</returns>
+    public async Task<HttpResponseMessage> SendAsync(HttpClient http, Func<HttpRequestMessage> requestFactory, Action<string>? onRetry, CancellationToken cancellationToken)
+    {
+        for (var attempt = 1; ; attempt++)
+        {
+            using var request = requestFactory();
+            HttpResponseMessage? response = null;
+            string failure;
+            try
+            {
+                response = await http.SendAsync(request, HttpCompletionOption.ResponseHeadersRead, cancellationToken);
+                if (!IsTransient(response.StatusCode) || attempt >= MaxAttempts)
+                {
+                    return response;
+                }
+
+                failure = $"{(int)response.StatusCode} {response.ReasonPhrase}";
+            }
+            catch (Exception ex) when (attempt < MaxAttempts && IsTransient(ex, cancellationToken))
+            {
+                failure = ex.Message;
+            }
+
+            var delay = GetDelay(attempt, response);
+            response?.Dispose();
+            onRetry?.Invoke($"{request.Method} {request.RequestUri?.GetLeftPart(UriPartial.Path)} failed ({failure}); retrying in {delay.TotalSeconds:N0}s (attempt {attempt + 1} of {MaxAttempts})");
+            await Task.Delay(delay, cancellationToken);
+        }
+    }
+
+    public static bool IsTransient(HttpStatusCode statusCode) =>
+        statusCode is HttpStatusCode.RequestTimeout
+            or HttpStatusCode.TooManyRequests
+            or HttpStatusCode.InternalServerError
+            or HttpStatusCode.BadGateway
+            or HttpStatusCode.ServiceUnavailable
+            or HttpStatusCode.GatewayTimeout;
+
+    private static bool IsTransient(Exception exception, CancellationToken cancellationToken) =>
+        exception is HttpRequestException or IOException
+        // HttpClient reports its own timeout as a cancellation the caller did not request
+        || (exception is TaskCanceledException && !cancellationToken.IsCancellationRequested);
+
+    private TimeSpan GetDelay(int attempt, HttpResponseMessage? response)
+    {
+        var retryAfter = response?.Headers.RetryAfter;
+        if (retryAfter?.Delta is TimeSpan delta)
+        {
+            return delta < MaxDelay ? delta : MaxDelay;
+        }
+        if (retryAfter?.Date is DateTimeOffset date)
+        {
+            var untilDate = date - DateTimeOffset.UtcNow;
+            return untilDate <= TimeSpan.Zero ? InitialDelay : untilDate < MaxDelay ? untilDate : MaxDelay;
+        }
+
+        var backoff = InitialDelay * Math.Pow(2, attempt - 1);
+        return backoff < MaxDelay ? backoff : MaxDelay;
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Collections.Concurrent;
+using System.Globalization;
+using System.Text;
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Helpers;
+using WinApp.Cli.Services;
+using WinApp.Cli.Store;
+using static WinApp.Cli.TestMatrix.TestMachineScript;
+
+namespace WinApp.Cli.TestMatrix;
+
+/// <summary>
+/// Creates Azure DevTest Labs virtual machines with the Azure CLI, which must be signed in, and runs scripts in them
+/// with Run Command. Files reach the machines through a storage container, since Run Command only carries scripts
+/// </summary>
+/// <param name="artifactsSasUri">Container URL with a SAS token that allows reading, writing and deleting blobs</param>
+internal sealed class AzureLabTestMachineProvider(IPowerShellService powerShellService, Uri? artifactsSasUri, HttpClient http) : ITestMachineProvider
+{
+    public const string ArtifactsVariable = "WINAPP_TEST_ARTIFACTS";
+    public const string DefaultSize = "Standard_D2s_v5";
+
+    private readonly ConcurrentDictionary<string, List<Uri>> _uploads = new(StringComparer.OrdinalIgnoreCase);
+
+    public string Name => "Azure DevTest Labs";
+
+    public async Task ProvisionAsync(TestMachine machine, TimeSpan timeout, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        // The lab deletes machines that a cancelled or crashed run leaves behind
+        var expiration = DateTimeOffset.UtcNow.AddDays(1).ToString("yyyy-MM-ddTHH:mm:ssZ", CultureInfo.InvariantCulture);
+        var output = await RunHostAsync($$"""
+            $ErrorActionPreference = 'Stop'
+            az lab vm create {{GetLabArguments(machine)}} --image {{Quote(machine.Target.Image)}} --image-type gallery --size {{Quote(machine.Target.Size ?? DefaultSize)}} --admin-username $env:{{UserNameVariable}} --admin-password $env:{{PasswordVariable}} --expiration-date {{Quote(expiration)}} --output none
+            if ($LASTEXITCODE -ne 0) { exit $LASTEXITCODE }
+            az lab vm show {{GetLabArguments(machine)}} --query computeId --output tsv
+            if ($LASTEXITCODE -ne 0) { exit $LASTEXITCODE }
+            """, taskContext, cancellationToken);
+
+        machine.ResourceId = output.Split('\n', StringSplitOptions.TrimEntries | StringSplitOptions.RemoveEmptyEntries)
+            .LastOrDefault(l => l.StartsWith("/subscriptions/", StringComparison.OrdinalIgnoreCase))
+            ?? throw new InvalidOperationException($"Azure DevTest Labs didn't return the virtual machine of {machine.Name}");
+    }
+
+    public async Task CopyAsync(TestMachine machine, IReadOnlyList<FileInfo> files, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        if (artifactsSasUri == null)
+        {
+            throw new InvalidOperationException($"Set {ArtifactsVariable} to the URL of an Azure Storage container, with a SAS token that allows reading, writing and deleting blobs. Azure test machines download the package from it.");
+        }
+
+        var uploader = new BlockBlobUploader(http);
+        var containerPath = artifactsSasUri.GetLeftPart(UriPartial.Path).TrimEnd('/');
+        var uploads = _uploads.GetOrAdd(machine.Name, _ => []);
+        var script = new StringBuilder();
+        script.AppendLine("$ErrorActionPreference = 'Stop'");
+        script.AppendLine("$ProgressPreference = 'SilentlyContinue'");
+        script.AppendLine($"New-Item -ItemType Directory -Force -Path {Quote(WorkingDirectory)} | Out-Null");
+        foreach (var file in files)
+        {
+            var blobUri = new Uri($"{containerPath}/{Uri.EscapeDataString(machine.Name)}/{Uri.EscapeDataString(file.Name)}{artifactsSasUri.Query}");
+            taskContext.UpdateSubStatus($"Uploading {file.Name}");
+            await uploader.UploadAsync(file, blobUri, new BlockBlobUploadOptions { ContentType = "application/octet-stream", Resume = false }, onRetry: message => taskContext.AddDebugMessage($"{UiSymbols.Sync} {message}"), cancellationToken: cancellationToken);
+            lock (uploads)
+            {
+                uploads.Add(blobUri);
+            }
+            script.AppendLine($"Invoke-WebRequest -UseBasicParsing -Uri {Quote(blobUri.AbsoluteUri)} -OutFile {Quote(Path.Combine(WorkingDirectory, file.Name))}");
+        }
+        taskContext.UpdateSubStatus(null);
+
+        await RunAsync(machine, script.ToString(), taskContext, cancellationToken);
+    }
+
+    public async Task<string> RunAsync(TestMachine machine, string script, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var resourceId = machine.ResourceId ?? throw new InvalidOperationException($"{machine.Name} isn't provisioned");
+
+        // The script goes through a file, which keeps SAS tokens out of the logged command and the command line
+        var scriptFile = new FileInfo(Path.Combine(Path.GetTempPath(), $"winapp-test-{Guid.NewGuid():N}.ps1"));
+        await File.WriteAllTextAsync(scriptFile.FullName, script, cancellationToken);
+        try
+        {
+            return await RunHostAsync($$"""
+                $ErrorActionPreference = 'Stop'
+                az vm run-command invoke --ids {{Quote(resourceId)}} --command-id RunPowerShellScript --scripts {{Quote("@" + scriptFile.FullName)}} --query 'value[0].message' --output tsv
+                if ($LASTEXITCODE -ne 0) { exit $LASTEXITCODE }
+                """, taskContext, cancellationToken);
+        }
+        finally
+        {
+            scriptFile.Delete();
+        }
+    }
+
+    public async Task DeleteAsync(TestMachine machine, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        if (_uploads.TryRemove(machine.Name, out var uploads))
+        {
+            foreach (var blobUri in uploads)
+            {
+                using var response = await http.DeleteAsync(blobUri, cancellationToken);
+                if (!response.IsSuccessStatusCode)
+                {
+                    taskContext.AddDebugMessage($"{UiSymbols.Warning} Couldn't delete {blobUri.GetLeftPart(UriPartial.Path)}: {(int)response.StatusCode} {response.ReasonPhrase}");
+                }
+            }
+        }
+
+        await RunHostAsync($"""
+            az lab vm delete {GetLabArguments(machine)} --yes --output none
+            exit $LASTEXITCODE
+            """, taskContext, cancellationToken);
+    }
+
+    private static string GetLabArguments(TestMachine machine) =>
+        $"--resource-group {Quote(machine.Target.ResourceGroup ?? "")} --lab-name {Quote(machine.Target.Lab ?? "")} --name {Quote(machine.Name)}";
+
+    private async Task<string> RunHostAsync(string script, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync(script, taskContext, cancellationToken: cancellationToken);
+        return exitCode == 0
+            ? output
+            : throw new InvalidOperationException($"Azure CLI: {output.Trim()}");
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+using WinApp.Cli.Services;
+using static WinApp.Cli.TestMatrix.TestMachineScript;
+
+namespace WinApp.Cli.TestMatrix;
+
+/// <summary>
+/// Boots local Hyper-V virtual machines from a differencing disk of each target's image, and reaches them over
+/// PowerShell Direct, so they need no network access to the host
+/// </summary>
+/// <param name="configDirectory">Folder of winapp.yaml, which images are relative to</param>
+/// <param name="diskDirectory">Folder for the differencing disks</param>
+internal sealed class HyperVTestMachineProvider(IPowerShellService powerShellService, DirectoryInfo configDirectory, DirectoryInfo diskDirectory) : ITestMachineProvider
+{
+    public const string DefaultSwitch = "Default Switch";
+    public const int DefaultMemory = 4;
+
+    private static readonly string CredentialScript = $"$credential = [pscredential]::new($env:{UserNameVariable}, (ConvertTo-SecureString $env:{PasswordVariable} -AsPlainText -Force))";
+
+    public string Name => "Hyper-V";
+
+    public async Task ProvisionAsync(TestMachine machine, TimeSpan timeout, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var image = new FileInfo(Path.Combine(configDirectory.FullName, Environment.ExpandEnvironmentVariables(machine.Target.Image)));
+        if (!image.Exists)
+        {
+            throw new FileNotFoundException($"winapp.yaml: the image of target '{machine.Target.Name}' doesn't exist: {image.FullName}");
+        }
+
+        diskDirectory.Create();
+        var minutes = Math.Max(1, (int)timeout.TotalMinutes);
+        await RunHostAsync($$"""
+            $ErrorActionPreference = 'Stop'
+            {{CredentialScript}}
+            New-VHD -Path {{Quote(GetDiskPath(machine))}} -ParentPath {{Quote(image.FullName)}} -Differencing | Out-Null
+            $vm = New-VM -Name {{Quote(machine.Name)}} -Generation 2 -MemoryStartupBytes {{machine.Target.Memory ?? DefaultMemory}}GB -VHDPath {{Quote(GetDiskPath(machine))}} -SwitchName {{Quote(machine.Target.Switch ?? DefaultSwitch)}}
+            Set-VMProcessor -VM $vm -Count 2
+            Start-VM -VM $vm
+            $deadline = (Get-Date).AddMinutes({{minutes}})
+            while ($true) {
+                try {
+                    Invoke-Command -VMName {{Quote(machine.Name)}} -Credential $credential -ErrorAction Stop -ScriptBlock { New-Item -ItemType Directory -Force -Path {{Quote(WorkingDirectory)}} | Out-Null }
+                    break
+                }
+                catch {
+                    if ((Get-Date) -gt $deadline) { throw "The machine didn't accept PowerShell Direct sessions within {{minutes}} minutes: $_" }
+                    Start-Sleep -Seconds 15
+                }
+            }
+            """, taskContext, cancellationToken);
+    }
+
+    public async Task CopyAsync(TestMachine machine, IReadOnlyList<FileInfo> files, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        await RunHostAsync($$"""
+            $ErrorActionPreference = 'Stop'
+            {{CredentialScript}}
+            $session = New-PSSession -VMName {{Quote(machine.Name)}} -Credential $credential
+            try {
+                Copy-Item -LiteralPath {{string.Join(",", files.Select(f => Quote(f.FullName)))}} -Destination {{Quote(WorkingDirectory)}} -ToSession $session -Force
+            }
+            finally {
+                Remove-PSSession $session
+            }
+            """, taskContext, cancellationToken);
+    }
+
+    public Task<string> RunAsync(TestMachine machine, string script, TaskContext taskContext, CancellationToken cancellationToken) =>
+        RunHostAsync($"""
+            $ErrorActionPreference = 'Stop'
+            {CredentialScript}
+            Invoke-Command -VMName {Quote(machine.Name)} -Credential $credential -ScriptBlock ([scriptblock]::Create({Quote(script)}))
+            """, taskContext, cancellationToken);
+
+    public async Task DeleteAsync(TestMachine machine, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        await RunHostAsync($"""
+            Stop-VM -Name {Quote(machine.Name)} -TurnOff -Force -ErrorAction SilentlyContinue
+            Remove-VM -Name {Quote(machine.Name)} -Force -ErrorAction SilentlyContinue
+            Remove-Item -LiteralPath {Quote(GetDiskPath(machine))} -Force -ErrorAction SilentlyContinue
+            """, taskContext, cancellationToken);
+    }
+
+    private string GetDiskPath(TestMachine machine) => Path.Combine(diskDirectory.FullName, $"{machine.Name}.vhdx");
+
+    private async Task<string> RunHostAsync(string script, TaskContext taskContext, CancellationToken cancellationToken)
+    {
+        var (exitCode, output) = await powerShellService.RunCommandAsync(script, taskContext, cancellationToken: cancellationToken);
+        return exitCode == 0
+            ? output
+            : throw new InvalidOperationException($"Hyper-V: {output.Trim()}");
+    }
+}
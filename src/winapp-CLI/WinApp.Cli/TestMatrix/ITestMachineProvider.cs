@@ -0,0 +1,38 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.ConsoleTasks;
+
+namespace WinApp.Cli.TestMatrix;
+
+/// <summary>
+/// Creates the virtual machines of test matrix targets, runs scripts in them and deletes them
+/// </summary>
+internal interface ITestMachineProvider
+{
+    /// <summary>
+    /// Name shown in status messages and results, e.g. "Hyper-V"
+    /// </summary>
+    string Name { get; }
+
+    /// <summary>
+    /// Creates and starts the machine, and waits until scripts can run in it
+    /// </summary>
+    Task ProvisionAsync(TestMachine machine, TimeSpan timeout, TaskContext taskContext, CancellationToken cancellationToken);
+
+    /// <summary>
+    /// Copies files to <see cref="TestMachineScript.WorkingDirectory"/> in the machine
+    /// </summary>
+    Task CopyAsync(TestMachine machine, IReadOnlyList<FileInfo> files, TaskContext taskContext, CancellationToken cancellationToken);
+
+    /// <summary>
+    /// Runs a PowerShell script in the machine as its administrator
+    /// </summary>
+    /// <returns>The output of the script</returns>
+    Task<string> RunAsync(TestMachine machine, string script, TaskContext taskContext, CancellationToken cancellationToken);
+
+    /// <summary>
+    /// Deletes the machine and everything created for it; also called when provisioning failed halfway
+    /// </summary>
+    Task DeleteAsync(TestMachine machine, TaskContext taskContext, CancellationToken cancellationToken);
+}
@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using WinApp.Cli.Models;
+
+namespace WinApp.Cli.TestMatrix;
+
+/// <summary>
+/// A virtual machine created for one target of a test matrix run
+/// </summary>
+/// <param name="Name">Name of the virtual machine, unique to the run</param>
+internal sealed record TestMachine(TestTargetConfig Target, string Name)
+{
+    /// <summary>
+    /// Azure resource ID of the virtual machine, set once it is provisioned
+    /// </summary>
+    public string? ResourceId { get; set; }
+}
@@ -0,0 +1,123 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+using System.Text;
+using System.Text.RegularExpressions;
+using WinApp.Cli.Models;
+using WinApp.Cli.Services;
+
+namespace WinApp.Cli.TestMatrix;
+
+/// <summary>
+/// Script that installs the package in a test machine and runs the smoke test, and the result it reports
+/// </summary>
+internal static partial class TestMachineScript
+{
+    public const string WorkingDirectory = @"C:\winapp-test";
+
+    /// <summary>
+    /// Environment variables with the administrator account of the test machines
+    /// </summary>
+    public const string UserNameVariable = "WINAPP_TEST_VM_USERNAME";
+    public const string PasswordVariable = "WINAPP_TEST_VM_PASSWORD";
+
+    // The script writes one marker per stage, since remote runs lose its exit code
+    private const string DeployMarker = "##winapp-test deploy=";
+    private const string SmokeTestMarker = "##winapp-test smoke=";
+
+    /// <summary>
+    /// Quotes a value for a single-quoted PowerShell string
+    /// </summary>
+    public static string Quote(string value) => $"'{value.Replace("'", "''")}'";
+
+    /// <summary>
+    /// Creates the script that runs in <see cref="WorkingDirectory"/> once the files are copied there
+    /// </summary>
+    /// <param name="identityName">Identity name of the package, to check that it installed</param>
+    /// <param name="package">File name of the package</param>
+    /// <param name="dependencies">File names of the framework packages to install with it</param>
+    /// <param name="certificate">File name of the .cer to trust, for packages signed with a self-signed certificate</param>
+    /// <param name="smokeTest">File name of the smoke test script</param>
+    public static string Create(string identityName, string package, IReadOnlyList<string> dependencies, string? certificate, string? smokeTest)
+    {
+        var sb = new StringBuilder();
+        sb.AppendLine("$ErrorActionPreference = 'Stop'");
+        sb.AppendLine("$ProgressPreference = 'SilentlyContinue'");
+        sb.AppendLine($"Set-Location -LiteralPath {Quote(WorkingDirectory)}");
+        sb.AppendLine("$startedAt = Get-Date");
+        sb.AppendLine("try {");
+        if (certificate != null)
+        {
+            sb.AppendLine($"    Import-Certificate -FilePath {Quote(certificate)} -CertStoreLocation Cert:\\LocalMachine\\TrustedPeople | Out-Null");
+        }
+        var dependencyPath = dependencies.Count > 0 ? $" -DependencyPath {string.Join(",", dependencies.Select(Quote))}" : "";
+        sb.AppendLine($"    Add-AppxPackage -Path {Quote(package)}{dependencyPath} -ForceApplicationShutdown");
+        sb.AppendLine($"    $package = Get-AppxPackage -Name {Quote(identityName)} | Select-Object -First 1");
+        sb.AppendLine("    if (-not $package -or $package.Status -ne 'Ok') {");
+        sb.AppendLine($"        throw \"{identityName.Replace("\"", "`\"")} is not installed correctly: $($package.Status)\"");
+        sb.AppendLine("    }");
+        sb.AppendLine("    Write-Output \"Installed $($package.PackageFullName)\"");
+        sb.AppendLine("}");
+        sb.AppendLine("catch {");
+        sb.AppendLine("    Write-Output \"$_\"");
+        // The deployment's events say why it failed, and the machine is usually gone by the time anyone looks
+        sb.AppendLine($"    Get-WinEvent -FilterHashtable @{{ LogName = {string.Join(",", DeploymentEventService.LogNames.Select(Quote))}; Level = 1,2,3; StartTime = $startedAt }} -ErrorAction SilentlyContinue |");
+        sb.AppendLine($"        Where-Object {{ $_.Message -like {Quote($"*{identityName}_*")} }} | Sort-Object TimeCreated |");
+        sb.AppendLine("        ForEach-Object { Write-Output \"$($_.TimeCreated.ToString('HH:mm:ss')) $($_.LogName) $($_.Id): $(($_.Message -split '\\r?\\n\\r?\\n')[0])\" }");
+        sb.AppendLine($"    Write-Output '{DeployMarker}failed'");
+        sb.AppendLine("    exit 1");
+        sb.AppendLine("}");
+        sb.AppendLine($"Write-Output '{DeployMarker}passed'");
+        if (smokeTest != null)
+        {
+            sb.AppendLine("$env:WINAPP_PACKAGE_FULL_NAME = $package.PackageFullName");
+            sb.AppendLine("$env:WINAPP_PACKAGE_FAMILY_NAME = $package.PackageFamilyName");
+            sb.AppendLine("$env:WINAPP_PACKAGE_INSTALL_LOCATION = $package.InstallLocation");
+            sb.AppendLine($"& powershell.exe -NoLogo -NoProfile -NonInteractive -ExecutionPolicy Bypass -File {Quote(smokeTest)} 2>&1 | ForEach-Object {{ Write-Output \"$_\" }}");
+            sb.AppendLine($"Write-Output \"{SmokeTestMarker}$LASTEXITCODE\"");
+        }
+        return sb.ToString();
+    }
+
+    /// <summary>
+    /// Reads the outcome of <see cref="Create"/> from its output
+    /// </summary>
+    /// <returns>The stage that failed and why, or nulls when the target passed</returns>
+    public static (TestMatrixStage? FailedStage, string? Error) ParseResult(string output, bool hasSmokeTest)
+    {
+        var deploy = DeployRegex().Match(output);
+        if (!deploy.Success)
+        {
+            return (TestMatrixStage.Deploy, "The machine returned no install result");
+        }
+        if (deploy.Groups[1].Value != "passed")
+        {
+            // Add-AppxPackage's error and the deployment's events carry the HRESULT, which says more than the failure alone
+            var errorCode = DeploymentEventService.FindErrorCode(output);
+            return (TestMatrixStage.Deploy, errorCode == null
+                ? "The package didn't install"
+                : DeploymentEventService.CommonErrors.TryGetValue(errorCode, out var hint)
+                    ? $"The package didn't install ({errorCode}): {hint}"
+                    : $"The package didn't install ({errorCode})");
+        }
+        if (!hasSmokeTest)
+        {
+            return (null, null);
+        }
+
+        var smokeTest = SmokeTestRegex().Match(output);
+        if (!smokeTest.Success)
+        {
+            return (TestMatrixStage.SmokeTest, "The smoke test didn't finish");
+        }
+        return smokeTest.Groups[1].Value == "0"
+            ? (null, null)
+            : (TestMatrixStage.SmokeTest, $"The smoke test exited with code {smokeTest.Groups[1].Value}");
+    }
+
+    [GeneratedRegex(@"^##winapp-test deploy=(\w+)\s*$", RegexOptions.Multiline)]
+    private static partial Regex DeployRegex();
+
+    [GeneratedRegex(@"^##winapp-test smoke=(-?\d+)\s*$", RegexOptions.Multiline)]
+    private static partial Regex SmokeTestRegex();
+}
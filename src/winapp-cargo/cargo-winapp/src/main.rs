@@ -0,0 +1,376 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! `cargo winapp`: builds, registers and packages Rust and Tauri apps with the winapp CLI.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use winapp_build::{replace_attribute, Error, Metadata, Package, Project, Result, Winapp};
+
+const CALLER: &str = "cargo-winapp";
+
+const HELP: &str = "\
+Build, register and package Rust and Tauri apps with the winapp CLI
+
+Usage: cargo winapp <COMMAND> [OPTIONS] [-- <WINAPP ARGS>...]
+
+Commands:
+  restore   Restore the SDK packages of winapp.yaml into .winapp
+  manifest  Generate appxmanifest.xml from Cargo.toml, or set its version
+  build     Build the app and register it with a debug identity
+  package   Build the app in release and pack it into an MSIX
+  <other>   Run any other winapp command in the project root
+
+Options:
+  -p, --package <NAME>      Package of the workspace to use
+      --manifest-path <PATH> Path to Cargo.toml
+      --release             Build with the release profile (default for package)
+      --debug               Build with the dev profile (package only)
+      --profile <NAME>      Build with a custom profile
+      --target <TRIPLE>     Build for the target triple
+      --no-build            Use the binaries of an earlier build, e.g. of 'tauri build'
+  -h, --help                Print help
+
+Arguments after -- are passed to winapp, e.g. 'cargo winapp package -- --cert devcert.pfx'.
+";
+
+/// Parsed command line of `cargo winapp`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options {
+    command: String,
+    package: Option<String>,
+    manifest_path: Option<PathBuf>,
+    profile: Option<String>,
+    target: Option<String>,
+    no_build: bool,
+    winapp_args: Vec<OsString>,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = OsString>) -> std::result::Result<Options, String> {
+        let mut args = args.into_iter().peekable();
+        // Cargo runs `cargo-winapp winapp <args>` for `cargo winapp <args>`.
+        if args.peek().is_some_and(|arg| arg == "winapp") {
+            args.next();
+        }
+
+        let mut options = Options::default();
+        let value = |name: &str, args: &mut dyn Iterator<Item = OsString>| {
+            args.next().and_then(|value| value.into_string().ok()).ok_or_else(|| format!("{name} needs a value"))
+        };
+        while let Some(arg) = args.next() {
+            let Some(text) = arg.to_str() else {
+                options.winapp_args.push(arg);
+                continue;
+            };
+            match text {
+                "--" => {
+                    options.winapp_args.extend(args.by_ref());
+                }
+                "-h" | "--help" if options.command.is_empty() => options.command = "help".into(),
+                "-p" | "--package" => options.package = Some(value(text, &mut args)?),
+                "--manifest-path" => options.manifest_path = Some(value(text, &mut args)?.into()),
+                "--release" => options.profile = Some("release".into()),
+                "--debug" => options.profile = Some("dev".into()),
+                "--profile" => options.profile = Some(value(text, &mut args)?),
+                "--target" => options.target = Some(value(text, &mut args)?),
+                "--no-build" => options.no_build = true,
+                _ if options.command.is_empty() && !text.starts_with('-') => options.command = text.into(),
+                _ => options.winapp_args.push(arg),
+            }
+        }
+        if options.command.is_empty() {
+            options.command = "help".into();
+        }
+        Ok(options)
+    }
+
+    /// The Cargo profile, which defaults to release for packages and dev otherwise.
+    fn profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(if self.command == "package" { "release" } else { "dev" })
+    }
+
+    /// The folder under the target folder with the binaries of the profile.
+    fn output_dir(&self, target_directory: &Path) -> PathBuf {
+        let dir = match self.target.as_deref() {
+            Some(target) => target_directory.join(target),
+            None => target_directory.to_path_buf(),
+        };
+        dir.join(profile_dir(self.profile()))
+    }
+
+    /// The file name suffix of executables for the target.
+    fn exe_suffix(&self) -> &str {
+        match self.target.as_deref() {
+            Some(target) if target.contains("windows") => ".exe",
+            Some(_) => "",
+            None => env::consts::EXE_SUFFIX,
+        }
+    }
+}
+
+/// The folder Cargo puts the binaries of a profile in.
+fn profile_dir(profile: &str) -> &str {
+    match profile {
+        "dev" | "test" => "debug",
+        "bench" => "release",
+        other => other,
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args_os().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{HELP}");
+            return ExitCode::from(2);
+        }
+    };
+    if options.command == "help" {
+        print!("{HELP}");
+        return ExitCode::SUCCESS;
+    }
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(options: &Options) -> Result<()> {
+    let cargo = env::var_os("CARGO").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("cargo"));
+    let current_dir = env::current_dir().map_err(|error| Error::Io(PathBuf::from("."), error))?;
+    let manifest_path = options.manifest_path.clone().or_else(|| tauri_manifest(&current_dir));
+    let metadata = Metadata::load(&cargo, manifest_path.as_deref())?;
+    let directory = manifest_path.as_deref().and_then(Path::parent).unwrap_or(&current_dir);
+    let package = metadata.select(options.package.as_deref(), directory)?;
+    let project = Project::find(&package.dir, package.winapp.manifest.as_deref());
+    let winapp = Winapp::locate(CALLER);
+
+    match options.command.as_str() {
+        "restore" => winapp.run(&project.root, args(["restore", "."], &options.winapp_args)),
+        "manifest" => manifest(&winapp, package, &project, options),
+        "build" => {
+            cargo_build(&cargo, package, options)?;
+            let executable = options.output_dir(&metadata.target_directory).join(executable(package, options)?);
+            let manifest = existing_manifest(&project)?;
+            project.stamp_version(&package.package_version())?;
+            let mut command: Vec<OsString> = vec!["create-debug-identity".into(), executable.into()];
+            command.extend(["--manifest".into(), manifest.as_os_str().to_owned()]);
+            winapp.run(&project.root, args(command, &options.winapp_args))
+        }
+        "package" => {
+            if !options.no_build {
+                cargo_build(&cargo, package, options)?;
+            }
+            package_layout(&winapp, package, &project, &metadata, options)
+        }
+        other => winapp.run(&project.root, args([other], &options.winapp_args)),
+    }
+}
+
+/// `src-tauri/Cargo.toml` when `cargo winapp` runs next to the `package.json` of a Tauri app.
+fn tauri_manifest(current_dir: &Path) -> Option<PathBuf> {
+    if current_dir.join("Cargo.toml").is_file() {
+        return None;
+    }
+    Some(current_dir.join("src-tauri").join("Cargo.toml")).filter(|path| path.is_file())
+}
+
+fn args<I, S>(command: I, winapp_args: &[OsString]) -> Vec<OsString>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<OsString>,
+{
+    command.into_iter().map(Into::into).chain(winapp_args.iter().cloned()).collect()
+}
+
+fn executable(package: &Package, options: &Options) -> Result<String> {
+    Ok(format!("{}{}", package.entry_bin()?, options.exe_suffix()))
+}
+
+fn existing_manifest(project: &Project) -> Result<&Path> {
+    if project.manifest.is_file() {
+        Ok(&project.manifest)
+    } else {
+        Err(Error::Project(format!(
+            "{} doesn't exist; create it with 'cargo winapp manifest'",
+            project.manifest.display()
+        )))
+    }
+}
+
+fn cargo_build(cargo: &Path, package: &Package, options: &Options) -> Result<()> {
+    let mut command = Command::new(cargo);
+    command.args(["build", "--package", &package.name, "--profile", options.profile()]);
+    if let Some(target) = &options.target {
+        command.args(["--target", target]);
+    }
+    if let Some(path) = &options.manifest_path {
+        command.arg("--manifest-path").arg(path);
+    }
+    let display = format!("cargo build --package {} --profile {}", package.name, options.profile());
+    let status = command.status().map_err(|error| Error::Io(cargo.to_path_buf(), error))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::CommandFailed(display, status))
+    }
+}
+
+/// Generates the manifest from the package metadata, or sets the version of the existing one.
+fn manifest(winapp: &Winapp, package: &Package, project: &Project, options: &Options) -> Result<()> {
+    let version = package.package_version();
+    if project.manifest.is_file() {
+        if project.stamp_version(&version)? {
+            println!("Set the version of {} to {version}", project.manifest.display());
+        } else {
+            println!("{} is up to date", project.manifest.display());
+        }
+        return Ok(());
+    }
+
+    let name = package.winapp.name.as_deref().unwrap_or(&package.name);
+    let directory = project.manifest.parent().unwrap_or(&project.root);
+    let mut command: Vec<OsString> = vec!["manifest".into(), "generate".into(), directory.as_os_str().to_owned()];
+    command.extend(["--package-name".into(), name.into(), "--version".into(), version.into()]);
+    if let Some(publisher) = package.winapp.publisher.as_deref() {
+        command.extend(["--publisher-name".into(), publisher.into()]);
+    }
+    if let Some(description) = package.description.as_deref() {
+        command.extend(["--description".into(), description.into()]);
+    }
+    winapp.run(&project.root, args(command, &options.winapp_args))?;
+
+    // `winapp manifest generate` only takes an executable that exists, so the entry point is set afterwards.
+    let generated = directory.join(winapp_build::MANIFEST_FILE);
+    if let Ok(bin) = package.entry_bin() {
+        let xml = fs::read_to_string(&generated).map_err(|error| Error::Io(generated.clone(), error))?;
+        if let Some(updated) = replace_attribute(&xml, "Application", "Executable", &format!("{bin}.exe")) {
+            fs::write(&generated, updated).map_err(|error| Error::Io(generated.clone(), error))?;
+        }
+    }
+    if generated != project.manifest {
+        fs::rename(&generated, &project.manifest).map_err(|error| Error::Io(project.manifest.clone(), error))?;
+    }
+    Ok(())
+}
+
+/// Copies the binaries, DLLs and `include` files into `target/winapp/<profile>` and packs the folder.
+fn package_layout(
+    winapp: &Winapp,
+    package: &Package,
+    project: &Project,
+    metadata: &Metadata,
+    options: &Options,
+) -> Result<()> {
+    let manifest = existing_manifest(project)?;
+    project.stamp_version(&package.package_version())?;
+
+    let output_dir = options.output_dir(&metadata.target_directory);
+    let winapp_dir = metadata.target_directory.join("winapp");
+    let layout = winapp_dir.join(profile_dir(options.profile()));
+    if layout.exists() {
+        fs::remove_dir_all(&layout).map_err(|error| Error::Io(layout.clone(), error))?;
+    }
+    fs::create_dir_all(&layout).map_err(|error| Error::Io(layout.clone(), error))?;
+
+    let entry = executable(package, options)?;
+    if !output_dir.join(&entry).is_file() {
+        return Err(Error::Project(format!(
+            "{} doesn't exist; build the app first, or drop --no-build",
+            output_dir.join(&entry).display()
+        )));
+    }
+    for bin in &package.bins {
+        let file = format!("{bin}{}", options.exe_suffix());
+        if output_dir.join(&file).is_file() {
+            copy(&output_dir.join(&file), &layout.join(&file))?;
+        }
+    }
+    let entries = fs::read_dir(&output_dir).map_err(|error| Error::Io(output_dir.clone(), error))?;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("dll")) {
+            copy(&path, &layout.join(path.file_name().unwrap_or_default()))?;
+        }
+    }
+    for include in &package.winapp.include {
+        let source = package.dir.join(include);
+        let name = include.file_name().ok_or_else(|| Error::Project(format!("can't include {}", include.display())))?;
+        copy(&source, &layout.join(name))?;
+    }
+
+    let mut command: Vec<OsString> =
+        vec!["package".into(), layout.into(), "--manifest".into(), manifest.as_os_str().to_owned()];
+    if !options.winapp_args.iter().any(|arg| arg == "--output" || arg == "-o") {
+        let msix = winapp_dir.join(format!("{}_{}.msix", package.name, package.package_version()));
+        command.extend(["--output".into(), msix.into()]);
+    }
+    winapp.run(&project.root, args(command, &options.winapp_args))
+}
+
+/// Copies a file, or a folder with everything in it.
+fn copy(source: &Path, destination: &Path) -> Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(destination).map_err(|error| Error::Io(destination.to_path_buf(), error))?;
+        let entries = fs::read_dir(source).map_err(|error| Error::Io(source.to_path_buf(), error))?;
+        for entry in entries.flatten() {
+            copy(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, destination).map(drop).map_err(|error| Error::Io(source.to_path_buf(), error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Options {
+        Options::parse(args.iter().map(OsString::from)).unwrap()
+    }
+
+    #[test]
+    fn parse_splits_cargo_and_winapp_arguments() {
+        let options = parse(&[
+            "winapp",
+            "package",
+            "-p",
+            "notes",
+            "--target",
+            "aarch64-pc-windows-msvc",
+            "--",
+            "--cert",
+            "devcert.pfx",
+        ]);
+
+        assert_eq!(options.command, "package");
+        assert_eq!(options.package.as_deref(), Some("notes"));
+        assert_eq!(options.profile(), "release");
+        assert_eq!(options.exe_suffix(), ".exe");
+        assert_eq!(options.winapp_args, ["--cert", "devcert.pfx"]);
+        assert_eq!(
+            options.output_dir(Path::new("target")),
+            Path::new("target").join("aarch64-pc-windows-msvc").join("release")
+        );
+        assert_eq!(parse(&["winapp"]).command, "help");
+        assert!(Options::parse(["winapp", "build", "--profile"].map(OsString::from)).is_err());
+    }
+
+    #[test]
+    fn build_defaults_to_the_dev_profile() {
+        let options = parse(&["winapp", "build"]);
+
+        assert_eq!(options.profile(), "dev");
+        assert_eq!(options.output_dir(Path::new("target")), Path::new("target").join("debug"));
+        assert_eq!(parse(&["winapp", "package", "--debug"]).profile(), "dev");
+        assert_eq!(profile_dir("dist"), "dist");
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::env;
+use std::ffi::OsStr;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{Error, Result};
+
+/// Environment variable that points at the winapp executable, e.g. a build of the CLI from source.
+pub const CLI_PATH_VARIABLE: &str = "WINAPP_CLI_PATH";
+
+/// The winapp CLI, which does the restoring, signing and packaging.
+#[derive(Debug, Clone)]
+pub struct Winapp {
+    program: PathBuf,
+    caller: &'static str,
+}
+
+impl Winapp {
+    /// The CLI at `WINAPP_CLI_PATH`, or `winapp` on the `PATH`.
+    ///
+    /// `caller` is passed as `WINAPP_CLI_CALLER`, which the CLI's telemetry records to tell wrappers apart.
+    pub fn locate(caller: &'static str) -> Self {
+        let program = env::var_os(CLI_PATH_VARIABLE)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("winapp"));
+        Winapp { program, caller }
+    }
+
+    /// Runs winapp in `directory` with the console of the caller, so its progress shows in the build output.
+    pub fn run<I, S>(&self, directory: &Path, args: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = Command::new(&self.program);
+        command.args(args).current_dir(directory).env("WINAPP_CLI_CALLER", self.caller);
+        let display = describe(&command);
+        let status = command.status().map_err(|error| self.spawn_error(error))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::CommandFailed(display, status))
+        }
+    }
+
+    fn spawn_error(&self, error: std::io::Error) -> Error {
+        if error.kind() == ErrorKind::NotFound {
+            Error::WinappNotFound(self.program.clone())
+        } else {
+            Error::Io(self.program.clone(), error)
+        }
+    }
+}
+
+/// The command line of a command, for error messages.
+pub(crate) fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
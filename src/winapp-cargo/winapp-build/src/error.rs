@@ -0,0 +1,50 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+/// Errors of the winapp build helpers and `cargo winapp`.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file failed.
+    Io(PathBuf, io::Error),
+    /// The winapp CLI isn't installed, or `WINAPP_CLI_PATH` points nowhere.
+    WinappNotFound(PathBuf),
+    /// A command, such as `winapp restore` or `cargo build`, exited with an error.
+    CommandFailed(String, ExitStatus),
+    /// `cargo metadata` printed something that isn't the metadata of a Cargo project, or `[package.metadata.winapp]`
+    /// has a setting of the wrong type.
+    Metadata(String),
+    /// The project has no package, manifest or binary to work with.
+    Project(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(path, error) => write!(f, "{}: {error}", path.display()),
+            Error::WinappNotFound(path) => write!(
+                f,
+                "the winapp CLI wasn't found at {}; install it with 'winget install microsoft.winappcli', or set WINAPP_CLI_PATH",
+                path.display()
+            ),
+            Error::CommandFailed(command, status) => write!(f, "'{command}' failed ({status})"),
+            Error::Metadata(message) => write!(f, "couldn't read the Cargo metadata: {message}"),
+            Error::Project(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
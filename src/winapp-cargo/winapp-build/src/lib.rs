@@ -0,0 +1,126 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Build script helpers for Rust and Tauri apps packaged with the [winapp CLI](https://github.com/microsoft/winappCli).
+//!
+//! Add the crate as a build dependency and call [`build`] from `build.rs`:
+//!
+//! ```no_run
+//! // build.rs
+//! winapp_build::build();
+//! ```
+//!
+//! On each build this restores the SDK packages of `winapp.yaml` into `.winapp` when they are missing, sets the
+//! `Version` of the manifest's `Identity` to the package version, as `cargo winapp` does, and reruns the build
+//! script when `Cargo.toml`, `winapp.yaml` or the manifest changes. Registering and packaging need the compiled
+//! binaries, so they happen after the build, with `cargo winapp build` and `cargo winapp package` from the
+//! `cargo-winapp` crate.
+
+mod cli;
+mod error;
+mod metadata;
+mod project;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub use cli::{Winapp, CLI_PATH_VARIABLE};
+pub use error::{Error, Result};
+pub use metadata::{Metadata, Package, WinappMetadata};
+pub use project::{package_version, replace_attribute, stamp_identity_version, Project, CONFIG_FILE, MANIFEST_FILE};
+
+/// Runs the default steps of [`Builder`], reporting errors as build warnings, so a machine without the winapp
+/// CLI still builds the app.
+pub fn build() {
+    if let Err(error) = Builder::new().run() {
+        println!("cargo:warning=winapp: {error}");
+    }
+}
+
+/// The steps of the build script, for apps that change the defaults of [`build`].
+///
+/// ```no_run
+/// fn main() -> Result<(), winapp_build::Error> {
+///     let project = winapp_build::Builder::new()
+///         .manifest("../Package.appxmanifest")
+///         .stamp_version(false)
+///         .run()?;
+///     for winmd in project.winmd_files() {
+///         println!("cargo:rustc-env=CONTOSO_WINMD={}", winmd.display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    manifest: Option<PathBuf>,
+    restore: bool,
+    stamp_version: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder { manifest: None, restore: true, stamp_version: true }
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path of the package manifest, relative to `Cargo.toml` (default: `[package.metadata.winapp] manifest`, or
+    /// `appxmanifest.xml` in the package folder or the project root).
+    pub fn manifest(mut self, path: impl Into<PathBuf>) -> Self {
+        self.manifest = Some(path.into());
+        self
+    }
+
+    /// Whether to run `winapp restore` when `.winapp` has no packages yet (default: true). Only Windows hosts
+    /// restore.
+    pub fn restore(mut self, restore: bool) -> Self {
+        self.restore = restore;
+        self
+    }
+
+    /// Whether to set the manifest's `Identity` `Version` to `[package.metadata.winapp] version`, or the package
+    /// version (default: true).
+    pub fn stamp_version(mut self, stamp: bool) -> Self {
+        self.stamp_version = stamp;
+        self
+    }
+
+    /// Runs the steps for the package being built, and returns its project.
+    pub fn run(self) -> Result<Project> {
+        let package_dir: PathBuf = env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| Error::Project("CARGO_MANIFEST_DIR isn't set; call winapp_build from build.rs".into()))?
+            .into();
+        let cargo_toml = package_dir.join("Cargo.toml");
+        let text = fs::read_to_string(&cargo_toml).map_err(|error| Error::Io(cargo_toml.clone(), error))?;
+        let winapp = WinappMetadata::from_cargo_toml(&text)?;
+        let project = Project::find(&package_dir, self.manifest.as_deref().or(winapp.manifest.as_deref()));
+
+        println!("cargo:rerun-if-env-changed={CLI_PATH_VARIABLE}");
+        println!("cargo:rerun-if-changed={}", cargo_toml.display());
+        if let Some(config) = project.config() {
+            println!("cargo:rerun-if-changed={}", config.display());
+        }
+        if project.manifest.is_file() {
+            println!("cargo:rerun-if-changed={}", project.manifest.display());
+        }
+
+        if self.restore && cfg!(windows) && project.config().is_some() && !project.is_restored() {
+            Winapp::locate("winapp-build").run(&project.root, ["restore", "."])?;
+        }
+
+        if self.stamp_version && project.manifest.is_file() {
+            let version = winapp.package_version(&env::var("CARGO_PKG_VERSION").unwrap_or_default());
+            if project.stamp_version(&version)? {
+                println!("cargo:warning=winapp: set the version of {} to {version}", project.manifest.display());
+            }
+        }
+
+        Ok(project)
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::cli::describe;
+use crate::{Error, Result};
+
+/// A package of the workspace, from `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub authors: Vec<String>,
+    /// The folder of the package's `Cargo.toml`.
+    pub dir: PathBuf,
+    /// Names of the package's binaries.
+    pub bins: Vec<String>,
+    /// The binary `cargo run` starts, when the package sets `default-run`.
+    pub default_run: Option<String>,
+    /// `[package.metadata.winapp]`.
+    pub winapp: WinappMetadata,
+}
+
+/// Settings of `[package.metadata.winapp]` in `Cargo.toml`; every one is optional.
+///
+/// ```toml
+/// [package.metadata.winapp]
+/// manifest = "../appxmanifest.xml"
+/// name = "Contoso.Notes"
+/// publisher = "CN=Contoso"
+/// bin = "notes"
+/// include = ["assets", "WebView2Loader.dll"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WinappMetadata {
+    /// Path of the package manifest, relative to `Cargo.toml`.
+    pub manifest: Option<PathBuf>,
+    /// Identity name for a generated manifest (default: the package name).
+    pub name: Option<String>,
+    /// Publisher for a generated manifest, e.g. `CN=Contoso`.
+    pub publisher: Option<String>,
+    /// Version for the manifest (default: the package version).
+    pub version: Option<String>,
+    /// The binary the package starts (default: `default-run`, or the only binary).
+    pub bin: Option<String>,
+    /// Files and folders, relative to `Cargo.toml`, that are packed next to the binaries.
+    pub include: Vec<PathBuf>,
+}
+
+impl WinappMetadata {
+    /// Reads `[package.metadata.winapp]` from the text of a `Cargo.toml`, for build scripts, which run without
+    /// `cargo metadata`.
+    pub fn from_cargo_toml(text: &str) -> Result<WinappMetadata> {
+        let cargo_toml: toml::Table = text.parse().map_err(|error| Error::Metadata(format!("Cargo.toml: {error}")))?;
+        let winapp = cargo_toml
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("winapp"))
+            .map(toml_to_json)
+            .unwrap_or_default();
+        WinappMetadata::from_json(&winapp)
+    }
+
+    /// Reads the `winapp` table as `cargo metadata` prints it. Settings of another type, such as
+    /// `version.workspace = true`, are errors rather than left unset.
+    fn from_json(winapp: &Value) -> Result<WinappMetadata> {
+        let string = |key: &str| match &winapp[key] {
+            Value::Null => Ok(None),
+            Value::String(value) => Ok(Some(value.clone())),
+            _ => Err(Error::Metadata(format!("[package.metadata.winapp] {key} must be a string"))),
+        };
+        let include = match &winapp["include"] {
+            Value::Null => Vec::new(),
+            Value::Array(paths) if paths.iter().all(Value::is_string) => {
+                paths.iter().filter_map(Value::as_str).map(PathBuf::from).collect()
+            }
+            _ => return Err(Error::Metadata("[package.metadata.winapp] include must be an array of strings".into())),
+        };
+        Ok(WinappMetadata {
+            manifest: string("manifest")?.map(PathBuf::from),
+            name: string("name")?,
+            publisher: string("publisher")?,
+            version: string("version")?,
+            bin: string("bin")?,
+            include,
+        })
+    }
+
+    /// The package version for the manifest: `version`, or the Cargo version of the package.
+    pub fn package_version(&self, cargo_version: &str) -> String {
+        crate::package_version(self.version.as_deref().unwrap_or(cargo_version))
+    }
+}
+
+/// A TOML value as the JSON `cargo metadata` prints it in.
+fn toml_to_json(value: &toml::Value) -> Value {
+    match value {
+        toml::Value::String(value) => Value::from(value.as_str()),
+        toml::Value::Integer(value) => Value::from(*value),
+        toml::Value::Float(value) => Value::from(*value),
+        toml::Value::Boolean(value) => Value::from(*value),
+        toml::Value::Datetime(value) => Value::from(value.to_string()),
+        toml::Value::Array(values) => values.iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => table.iter().map(|(key, value)| (key.clone(), toml_to_json(value))).collect(),
+    }
+}
+
+/// Workspace packages and the target folder, from `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub packages: Vec<Package>,
+    pub target_directory: PathBuf,
+}
+
+impl Metadata {
+    /// Runs `cargo metadata` for the workspace of `manifest_path`, or of the current directory.
+    pub fn load(cargo: &Path, manifest_path: Option<&Path>) -> Result<Metadata> {
+        let mut command = Command::new(cargo);
+        command.args(["metadata", "--format-version", "1", "--no-deps"]);
+        if let Some(path) = manifest_path {
+            command.arg("--manifest-path").arg(path);
+        }
+        let output = command.output().map_err(|error| Error::Io(cargo.to_path_buf(), error))?;
+        if !output.status.success() {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            return Err(Error::CommandFailed(describe(&command), output.status));
+        }
+        let json: Value = serde_json::from_slice(&output.stdout).map_err(|error| Error::Metadata(error.to_string()))?;
+        Metadata::parse(&json)
+    }
+
+    /// Reads the output of `cargo metadata --format-version 1`.
+    pub fn parse(json: &Value) -> Result<Metadata> {
+        let target_directory =
+            json["target_directory"].as_str().ok_or_else(|| Error::Metadata("no target_directory".into()))?.into();
+        let packages = json["packages"]
+            .as_array()
+            .ok_or_else(|| Error::Metadata("no packages".into()))?
+            .iter()
+            .map(parse_package)
+            .collect::<Result<_>>()?;
+        Ok(Metadata { packages, target_directory })
+    }
+
+    /// The package named `name`; the package of `Cargo.toml` in `directory` or the folder above it; or the only
+    /// package of the workspace.
+    pub fn select(&self, name: Option<&str>, directory: &Path) -> Result<&Package> {
+        if let Some(name) = name {
+            return self
+                .packages
+                .iter()
+                .find(|package| package.name == name)
+                .ok_or_else(|| Error::Project(format!("the workspace has no package '{name}'")));
+        }
+        directory
+            .ancestors()
+            .find_map(|dir| self.packages.iter().find(|package| package.dir == dir))
+            .or(match self.packages.as_slice() {
+                [only] => Some(only),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                let names: Vec<_> = self.packages.iter().map(|package| package.name.as_str()).collect();
+                Error::Project(format!("pick one of the packages of the workspace with -p: {}", names.join(", ")))
+            })
+    }
+}
+
+impl Package {
+    /// The binary to start: `[package.metadata.winapp] bin`, `default-run`, or the only binary.
+    pub fn entry_bin(&self) -> Result<&str> {
+        if let Some(bin) = self.winapp.bin.as_deref().or(self.default_run.as_deref()) {
+            return Ok(bin);
+        }
+        match self.bins.as_slice() {
+            [only] => Ok(only),
+            [] => Err(Error::Project(format!("package '{}' has no binary to package", self.name))),
+            _ => Err(Error::Project(format!(
+                "package '{}' has several binaries; set default-run, or bin under [package.metadata.winapp]",
+                self.name
+            ))),
+        }
+    }
+
+    /// The package version for the manifest.
+    pub fn package_version(&self) -> String {
+        self.winapp.package_version(&self.version)
+    }
+}
+
+fn parse_package(json: &Value) -> Result<Package> {
+    let string = |value: &Value| value.as_str().map(str::to_owned);
+    let manifest_path: PathBuf =
+        json["manifest_path"].as_str().ok_or_else(|| Error::Metadata("a package has no manifest_path".into()))?.into();
+    Ok(Package {
+        name: string(&json["name"]).unwrap_or_default(),
+        version: string(&json["version"]).unwrap_or_default(),
+        description: string(&json["description"]),
+        authors: json["authors"].as_array().into_iter().flatten().filter_map(string).collect(),
+        dir: manifest_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        bins: json["targets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|target| target["kind"].as_array().is_some_and(|kinds| kinds.iter().any(|kind| kind == "bin")))
+            .filter_map(|target| string(&target["name"]))
+            .collect(),
+        default_run: string(&json["default_run"]),
+        winapp: WinappMetadata::from_json(&json["metadata"]["winapp"])?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        let json = serde_json::json!({
+            "target_directory": "/src/notes/target",
+            "packages": [
+                {
+                    "name": "notes",
+                    "version": "0.4.0-beta.2",
+                    "description": "Contoso Notes",
+                    "authors": ["Contoso"],
+                    "manifest_path": "/src/notes/src-tauri/Cargo.toml",
+                    "targets": [
+                        { "name": "notes_lib", "kind": ["staticlib", "cdylib", "rlib"] },
+                        { "name": "notes", "kind": ["bin"] }
+                    ],
+                    "default_run": null,
+                    "metadata": { "winapp": { "publisher": "CN=Contoso", "include": ["resources"] } }
+                },
+                {
+                    "name": "sync",
+                    "version": "1.0.0",
+                    "manifest_path": "/src/notes/sync/Cargo.toml",
+                    "targets": [{ "name": "sync", "kind": ["bin"] }, { "name": "sync-admin", "kind": ["bin"] }],
+                    "metadata": null
+                }
+            ]
+        });
+        Metadata::parse(&json).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_binaries_and_winapp_metadata() {
+        let metadata = metadata();
+        let notes = &metadata.packages[0];
+
+        assert_eq!(metadata.target_directory, PathBuf::from("/src/notes/target"));
+        assert_eq!(notes.bins, ["notes"]);
+        assert_eq!(notes.entry_bin().unwrap(), "notes");
+        assert_eq!(notes.package_version(), "0.4.0.0");
+        assert_eq!(notes.winapp.publisher.as_deref(), Some("CN=Contoso"));
+        assert_eq!(notes.winapp.include, [PathBuf::from("resources")]);
+        assert!(metadata.packages[1].entry_bin().is_err(), "sync has two binaries and no default-run");
+    }
+
+    #[test]
+    fn select_finds_the_package_of_the_directory() {
+        let metadata = metadata();
+
+        assert_eq!(metadata.select(None, Path::new("/src/notes/src-tauri/src")).unwrap().name, "notes");
+        assert_eq!(metadata.select(Some("sync"), Path::new("/src/notes")).unwrap().name, "sync");
+        assert!(metadata.select(None, Path::new("/src/notes")).is_err());
+    }
+
+    #[test]
+    fn from_cargo_toml_reads_the_winapp_table() {
+        let cargo_toml = r#"
+[package]
+name = "notes"
+version = "0.4.0"
+
+[package.metadata.winapp]
+manifest = '..\appxmanifest.xml'
+include = ["C#/bin", "WebView2Loader.dll"] # C#/bin is built by dotnet
+version = "2.1.0" # ahead of the crate
+
+[dependencies]
+version = "1"
+"#;
+
+        let winapp = WinappMetadata::from_cargo_toml(cargo_toml).unwrap();
+
+        assert_eq!(winapp.manifest, Some(PathBuf::from("..\\appxmanifest.xml")));
+        assert_eq!(winapp.version.as_deref(), Some("2.1.0"));
+        assert_eq!(winapp.include, [PathBuf::from("C#/bin"), PathBuf::from("WebView2Loader.dll")]);
+        assert_eq!(winapp.package_version("0.4.0"), "2.1.0.0");
+        assert_eq!(
+            WinappMetadata::from_cargo_toml("[package]\nversion = \"0.4.0\"").unwrap().package_version("0.4.0"),
+            "0.4.0.0"
+        );
+        let dotted = "[package]\nmetadata.winapp = { publisher = 'CN=Contoso', include = ['C#/bin'] }";
+        assert_eq!(WinappMetadata::from_cargo_toml(dotted).unwrap().publisher.as_deref(), Some("CN=Contoso"));
+        assert!(WinappMetadata::from_cargo_toml("[package.metadata.winapp]\nversion.workspace = true").is_err());
+    }
+}
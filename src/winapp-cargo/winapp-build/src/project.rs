@@ -0,0 +1,187 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+/// The configuration file `winapp init` writes at the project root.
+pub const CONFIG_FILE: &str = "winapp.yaml";
+
+/// The manifest file name `winapp init` and `winapp manifest generate` write.
+pub const MANIFEST_FILE: &str = "appxmanifest.xml";
+
+/// Where winapp files live for a Cargo package: the project root with `winapp.yaml`, and the package manifest.
+///
+/// The root is the package folder, or the nearest folder above it with `winapp.yaml` or `appxmanifest.xml`, so
+/// Tauri's layout, with `Cargo.toml` in `src-tauri` and winapp files next to `package.json`, works as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// The folder of the package's `Cargo.toml`.
+    pub package_dir: PathBuf,
+    /// The folder `winapp` commands run in.
+    pub root: PathBuf,
+    /// The `appxmanifest.xml` of the package, which may not exist yet.
+    pub manifest: PathBuf,
+}
+
+impl Project {
+    /// Finds the project of the package in `package_dir`.
+    ///
+    /// `manifest` overrides the manifest path, relative to the package folder, e.g. from
+    /// `[package.metadata.winapp] manifest`.
+    pub fn find(package_dir: &Path, manifest: Option<&Path>) -> Project {
+        let root = package_dir
+            .ancestors()
+            .find(|dir| dir.join(CONFIG_FILE).is_file())
+            .or_else(|| package_dir.ancestors().take(2).find(|dir| dir.join(MANIFEST_FILE).is_file()))
+            .unwrap_or(package_dir)
+            .to_path_buf();
+        let manifest = match manifest {
+            Some(path) => package_dir.join(path),
+            None => [package_dir, root.as_path()]
+                .into_iter()
+                .map(|dir| dir.join(MANIFEST_FILE))
+                .find(|path| path.is_file())
+                .unwrap_or_else(|| root.join(MANIFEST_FILE)),
+        };
+        Project { package_dir: package_dir.to_path_buf(), root, manifest }
+    }
+
+    /// `winapp.yaml` at the project root, when the project has one.
+    pub fn config(&self) -> Option<PathBuf> {
+        Some(self.root.join(CONFIG_FILE)).filter(|path| path.is_file())
+    }
+
+    /// The project's local `.winapp` folder, where `winapp restore` puts the SDK packages.
+    pub fn winapp_dir(&self) -> PathBuf {
+        self.root.join(".winapp")
+    }
+
+    /// Whether `winapp restore` has put the packages of `winapp.yaml` in `.winapp`.
+    pub fn is_restored(&self) -> bool {
+        self.winapp_dir().join("packages").is_dir()
+    }
+
+    /// The `.winmd` metadata of the restored SDK packages, for `windows-bindgen` and other projection generators.
+    pub fn winmd_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        collect_winmds(&self.winapp_dir().join("packages"), &mut files);
+        files.sort();
+        files
+    }
+
+    /// Sets the `Version` of the manifest's `Identity` to `version`, and reports whether the manifest changed.
+    pub fn stamp_version(&self, version: &str) -> Result<bool> {
+        let xml = fs::read_to_string(&self.manifest).map_err(|error| Error::Io(self.manifest.clone(), error))?;
+        match stamp_identity_version(&xml, version) {
+            Some(stamped) if stamped != xml => {
+                fs::write(&self.manifest, stamped).map_err(|error| Error::Io(self.manifest.clone(), error))?;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(Error::Project(format!("{} has no Identity Version", self.manifest.display()))),
+        }
+    }
+}
+
+fn collect_winmds(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_winmds(&path, files);
+        } else if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("winmd")) {
+            files.push(path);
+        }
+    }
+}
+
+/// Converts a Cargo version to a package version: four numbers, without the pre-release and build metadata,
+/// e.g. `1.2.3-beta.1` to `1.2.3.0`.
+pub fn package_version(cargo_version: &str) -> String {
+    let release = cargo_version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts: Vec<&str> = release.split('.').filter(|part| !part.is_empty()).take(4).collect();
+    parts.resize(4, "0");
+    parts.join(".")
+}
+
+/// The manifest with the `Version` attribute of `Identity` replaced, or `None` when it has none.
+pub fn stamp_identity_version(xml: &str, version: &str) -> Option<String> {
+    replace_attribute(xml, "Identity", "Version", version)
+}
+
+/// The manifest with an attribute of the first `element` replaced, or `None` when the element or the attribute is
+/// missing.
+///
+/// The rest of the manifest is kept byte for byte, comments and formatting included.
+pub fn replace_attribute(xml: &str, element: &str, attribute: &str, value: &str) -> Option<String> {
+    let open = format!("<{element}");
+    let start = xml
+        .match_indices(&open)
+        .map(|(index, _)| index)
+        .find(|&index| xml[index + open.len()..].starts_with(|c: char| c.is_whitespace() || c == '/' || c == '>'))?;
+    let end = start + xml[start..].find('>')?;
+    let tag = &xml[start..end];
+    let name = tag.match_indices(attribute).map(|(index, _)| index).find(|&index| {
+        tag[..index].ends_with(char::is_whitespace) && tag[index + attribute.len()..].trim_start().starts_with('=')
+    })?;
+    let after_name = &tag[name + attribute.len()..];
+    let quote_offset = after_name.find(['"', '\''])?;
+    let quote = after_name[quote_offset..].chars().next()?;
+    let value_start = start + name + attribute.len() + quote_offset + 1;
+    let value_end = value_start + xml[value_start..end].find(quote)?;
+    Some(format!("{}{value}{}", &xml[..value_start], &xml[value_end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_version_pads_and_drops_the_pre_release() {
+        assert_eq!(package_version("0.1.0"), "0.1.0.0");
+        assert_eq!(package_version("1.2.3-beta.1+build.5"), "1.2.3.0");
+        assert_eq!(package_version("2"), "2.0.0.0");
+    }
+
+    #[test]
+    fn stamp_identity_version_keeps_the_rest_of_the_manifest() {
+        let xml = "<Package>\n  <!-- Identity -->\n  <Identity\n    Name=\"tauri-app\"\n    Publisher=\"CN=Contoso\"\n    Version=\"1.0.0.0\" />\n  <Dependencies>\n    <TargetDeviceFamily Name=\"Windows.Desktop\" MaxVersionTested=\"10.0.26100.0\" />\n  </Dependencies>\n</Package>";
+
+        let stamped = stamp_identity_version(xml, "0.3.1.0").unwrap();
+
+        assert_eq!(stamped, xml.replace("Version=\"1.0.0.0\"", "Version=\"0.3.1.0\""));
+        assert_eq!(stamp_identity_version("<Package><Identity Name=\"A\" /></Package>", "1.0.0.0"), None);
+        assert_eq!(
+            replace_attribute(
+                "<Applications><Application Id='App' Executable='tauri-app.exe'/></Applications>",
+                "Application",
+                "Executable",
+                "notes.exe"
+            )
+            .unwrap(),
+            "<Applications><Application Id='App' Executable='notes.exe'/></Applications>"
+        );
+    }
+
+    #[test]
+    fn find_uses_the_root_of_tauri_projects() {
+        let root = std::env::temp_dir().join(format!("winapp-build-{}", std::process::id()));
+        let src_tauri = root.join("src-tauri");
+        fs::create_dir_all(&src_tauri).unwrap();
+        fs::write(root.join(MANIFEST_FILE), "<Package />").unwrap();
+
+        let project = Project::find(&src_tauri, None);
+
+        assert_eq!(project.root, root);
+        assert_eq!(project.manifest, root.join(MANIFEST_FILE));
+        assert_eq!(
+            Project::find(&src_tauri, Some(Path::new("Package.appxmanifest"))).manifest,
+            src_tauri.join("Package.appxmanifest")
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
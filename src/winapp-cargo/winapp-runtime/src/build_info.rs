@@ -0,0 +1,203 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::cmp::Reverse;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+/// The provenance stamp `winapp pack --provenance` writes to the root of the package.
+pub const BUILD_INFO_FILE: &str = "winapp-build-info.json";
+
+/// The provenance stamp of the installed package: the commit and profile it was built from, and the folders that map
+/// the paths of a running build back to the source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub git_sha: Option<String>,
+    pub git_dirty: bool,
+    pub branch: Option<String>,
+    pub profile: Option<String>,
+    pub cli_version: Option<String>,
+    /// Folder the package was built in; the source paths of the debug info start with it.
+    pub source_root: Option<String>,
+    /// Folder that was packed, relative to `source_root`, with `/` separators.
+    pub package_folder: Option<String>,
+    /// Folder of the stamp, the install folder of the package.
+    pub install_dir: PathBuf,
+}
+
+impl BuildInfo {
+    /// Reads the stamp of the package the current executable belongs to, from the folder of the executable or the
+    /// folders above it. `None` when the app wasn't packed with `--provenance`.
+    pub fn current() -> Option<BuildInfo> {
+        let exe = env::current_exe().ok()?;
+        exe.ancestors().skip(1).find_map(|dir| BuildInfo::read(dir).ok())
+    }
+
+    /// Reads the stamp in `install_dir`.
+    pub fn read(install_dir: &Path) -> io::Result<BuildInfo> {
+        let text = fs::read_to_string(install_dir.join(BUILD_INFO_FILE))?;
+        BuildInfo::parse(&text, install_dir)
+    }
+
+    fn parse(text: &str, install_dir: &Path) -> io::Result<BuildInfo> {
+        let json: Value =
+            serde_json::from_str(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let string = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_owned);
+        Ok(BuildInfo {
+            git_sha: string("gitSha"),
+            git_dirty: json.get("gitDirty").and_then(Value::as_bool).unwrap_or_default(),
+            branch: string("branch"),
+            profile: string("profile"),
+            cli_version: string("cliVersion"),
+            source_root: string("sourceRoot"),
+            package_folder: string("packageFolder"),
+            install_dir: install_dir.to_path_buf(),
+        })
+    }
+
+    /// Rewrites the paths in `text` that start with the install folder or the source root to paths relative to the
+    /// source root, so `C:\Program Files\WindowsApps\Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe\assets\logo.png` becomes
+    /// `target/winapp/release/assets\logo.png` and `D:\a\notes\src\main.rs` becomes `src\main.rs`.
+    pub fn source_relative(&self, text: &str) -> String {
+        let package_folder = match self.package_folder.as_deref() {
+            Some(folder) if !folder.is_empty() => format!("{folder}/"),
+            _ => String::new(),
+        };
+        let mut prefixes = vec![(self.install_dir.to_string_lossy().into_owned(), package_folder)];
+        if let Some(source_root) = &self.source_root {
+            prefixes.push((source_root.clone(), String::new()));
+        }
+        // A debug build runs from the source tree, so its install folder is below the source root
+        prefixes.sort_by_key(|(prefix, _)| Reverse(prefix.len()));
+
+        let mut text = text.to_owned();
+        for (prefix, replacement) in prefixes {
+            text = replace_path_prefix(&text, &prefix, &replacement);
+        }
+        text
+    }
+
+    /// A line about the build, such as `commit 3f9c2d0e5b1a (main, uncommitted changes), profile Release, winapp
+    /// 0.2.0`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(sha) = &self.git_sha {
+            let mut details: Vec<&str> = self.branch.as_deref().into_iter().collect();
+            if self.git_dirty {
+                details.push("uncommitted changes");
+            }
+            let short = &sha[..sha.len().min(12)];
+            parts.push(if details.is_empty() {
+                format!("commit {short}")
+            } else {
+                format!("commit {short} ({})", details.join(", "))
+            });
+        }
+        if let Some(profile) = &self.profile {
+            parts.push(format!("profile {profile}"));
+        }
+        if let Some(cli_version) = &self.cli_version {
+            parts.push(format!("winapp {cli_version}"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Replaces `prefix` and the separator after it wherever a path in `text` starts with it, ignoring the case of ASCII
+/// letters and the kind of separators as Windows does.
+fn replace_path_prefix(text: &str, prefix: &str, replacement: &str) -> String {
+    let prefix = prefix.trim_end_matches(['/', '\\']).as_bytes();
+    if prefix.is_empty() {
+        return text.to_owned();
+    }
+
+    let bytes = text.as_bytes();
+    let is_separator = |byte: u8| byte == b'/' || byte == b'\\';
+    let is_path_byte = |byte: u8| byte.is_ascii_alphanumeric() || is_separator(byte) || b"_-.:".contains(&byte);
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut index = 0;
+    while index + prefix.len() < bytes.len() {
+        let starts_path = index == 0 || !is_path_byte(bytes[index - 1]);
+        let matches = starts_path
+            && is_separator(bytes[index + prefix.len()])
+            && bytes[index..index + prefix.len()]
+                .iter()
+                .zip(prefix)
+                .all(|(&a, &b)| a.eq_ignore_ascii_case(&b) || (is_separator(a) && is_separator(b)));
+        if matches {
+            // Both ends of the match are ASCII, so they fall on character boundaries
+            result.push_str(&text[copied..index]);
+            result.push_str(replacement);
+            index += prefix.len() + 1;
+            copied = index;
+        } else {
+            index += 1;
+        }
+    }
+    result.push_str(&text[copied..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAMP: &str = r#"{
+  "gitSha": "3f9c2d0e5b1a7c4f8e2d6b0a9c3e5f7a1b2c4d6e",
+  "gitDirty": true,
+  "branch": "main",
+  "profile": "Release",
+  "cliVersion": "0.2.0",
+  "sourceRoot": "D:\\a\\notes",
+  "packageFolder": "target/winapp/release"
+}"#;
+
+    fn build_info() -> BuildInfo {
+        let install_dir = Path::new(r"C:\Program Files\WindowsApps\Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe");
+        BuildInfo::parse(STAMP, install_dir).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_the_stamp_of_pack() {
+        let info = build_info();
+
+        assert_eq!(info.source_root.as_deref(), Some(r"D:\a\notes"));
+        assert_eq!(info.package_folder.as_deref(), Some("target/winapp/release"));
+        assert_eq!(info.summary(), "commit 3f9c2d0e5b1a (main, uncommitted changes), profile Release, winapp 0.2.0");
+        assert!(BuildInfo::parse("{ not json", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn source_relative_rewrites_install_and_build_paths() {
+        let info = build_info();
+        let backtrace = concat!(
+            "   1: notes::sync::pull\n",
+            "             at D:\\a\\notes\\src\\sync.rs:42\n",
+            "   2: std::rt::lang_start\n",
+            "             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library\\std\\src\\rt.rs:195\n",
+            "couldn't open c:\\program files\\windowsapps\\Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe\\assets\\notes.db",
+        );
+
+        let rewritten = info.source_relative(backtrace);
+
+        assert!(rewritten.contains("at src\\sync.rs:42"), "{rewritten}");
+        assert!(rewritten.contains("couldn't open target/winapp/release/assets\\notes.db"), "{rewritten}");
+        assert!(rewritten.contains("/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library"), "{rewritten}");
+    }
+
+    #[test]
+    fn replace_path_prefix_only_replaces_whole_folders() {
+        assert_eq!(replace_path_prefix("at D:/a/notes/src/main.rs:5", r"D:\a\notes\", ""), "at src/main.rs:5");
+        assert_eq!(
+            replace_path_prefix(r"D:\a\notes-old\src\main.rs", r"D:\a\notes", ""),
+            r"D:\a\notes-old\src\main.rs"
+        );
+        assert_eq!(replace_path_prefix(r"E:\D:\a\notes\main.rs", r"D:\a\notes", ""), r"E:\D:\a\notes\main.rs");
+        assert_eq!(replace_path_prefix("/home/ci/notes/src/main.rs", "/ci/notes", ""), "/home/ci/notes/src/main.rs");
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
+use std::process;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app_data_dir;
+
+/// The folder of [`app_data_dir`] that crash dumps are written to, and that `winapp crashes upload` reads.
+pub const CRASH_DUMPS_FOLDER: &str = "CrashDumps";
+
+/// The folder for the crash dumps of `app`: `CrashDumps` in its [`app_data_dir`].
+pub fn crash_dir(app: &str) -> PathBuf {
+    app_data_dir(app).join(CRASH_DUMPS_FOLDER)
+}
+
+/// Writes a minidump of the process when it panics or raises an exception nothing handles.
+///
+/// ```no_run
+/// winapp_runtime::CrashReporter::new("notes").install()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Dumps are named `<app>-<unix time>-<process id>.dmp`. Minidumps are a Windows format, so on other hosts only the
+/// folder is created.
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    app: String,
+    dir: Option<PathBuf>,
+    panics: bool,
+    exceptions: bool,
+}
+
+/// Where the installed hooks write dumps; set once, by the first [`CrashReporter::install`].
+static TARGET: OnceLock<DumpTarget> = OnceLock::new();
+
+#[derive(Debug)]
+struct DumpTarget {
+    app: String,
+    dir: PathBuf,
+}
+
+impl CrashReporter {
+    pub fn new(app: impl Into<String>) -> Self {
+        CrashReporter { app: app.into(), dir: None, panics: true, exceptions: true }
+    }
+
+    /// The folder of the dumps (default: [`crash_dir`] of the app).
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Whether to write a dump when a thread panics, before the panic hook that was set runs (default: true).
+    /// Panics caught with `catch_unwind` are dumped too.
+    pub fn panics(mut self, enabled: bool) -> Self {
+        self.panics = enabled;
+        self
+    }
+
+    /// Whether to write a dump for exceptions no handler catches, such as access violations in native code
+    /// (default: true). Windows Error Reporting still gets the exception afterwards.
+    pub fn exceptions(mut self, enabled: bool) -> Self {
+        self.exceptions = enabled;
+        self
+    }
+
+    /// Creates the folder and installs the hooks, and returns the folder.
+    ///
+    /// Fails with [`io::ErrorKind::AlreadyExists`] when the hooks were installed before.
+    pub fn install(self) -> io::Result<PathBuf> {
+        let dir = self.dir.unwrap_or_else(|| crash_dir(&self.app));
+        fs::create_dir_all(&dir)?;
+        TARGET
+            .set(DumpTarget { app: self.app, dir: dir.clone() })
+            .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "the crash hooks are already installed"))?;
+
+        if self.panics && cfg!(windows) {
+            let previous = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                if let Some(target) = TARGET.get() {
+                    if let Err(error) = target.write_dump(None) {
+                        eprintln!("winapp-runtime: couldn't write a crash dump to {}: {error}", target.dir.display());
+                    }
+                }
+                previous(info);
+            }));
+        }
+        if self.exceptions {
+            native::set_unhandled_exception_filter();
+        }
+        Ok(dir)
+    }
+}
+
+impl DumpTarget {
+    fn write_dump(&self, exception_pointers: Option<native::ExceptionPointers>) -> io::Result<PathBuf> {
+        let path = self.dir.join(report_file_name(&self.app, SystemTime::now(), process::id(), "dmp"));
+        native::write_dump(&path, exception_pointers)?;
+        Ok(path)
+    }
+}
+
+/// `<app>-<unix time>-<process id>.<extension>`, the name of dumps and panic reports.
+pub(crate) fn report_file_name(app: &str, time: SystemTime, process_id: u32, extension: &str) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+    format!("{app}-{seconds}-{process_id}.{extension}")
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+    use std::fs::{self, File};
+    use std::io;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::Path;
+
+    pub type ExceptionPointers = *mut c_void;
+
+    // MiniDumpWithUnloadedModules | MiniDumpWithIndirectlyReferencedMemory | MiniDumpWithThreadInfo: stacks, the
+    // memory they point to, and the modules, without the whole heap.
+    const DUMP_TYPE: u32 = 0x0020 | 0x0040 | 0x1000;
+    const EXCEPTION_CONTINUE_SEARCH: i32 = 0;
+
+    // dbghelp.h declares it with 4-byte packing
+    #[repr(C, packed(4))]
+    struct MinidumpExceptionInformation {
+        thread_id: u32,
+        exception_pointers: ExceptionPointers,
+        client_pointers: i32,
+    }
+
+    type TopLevelExceptionFilter = unsafe extern "system" fn(ExceptionPointers) -> i32;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentProcess() -> *mut c_void;
+        fn GetCurrentProcessId() -> u32;
+        fn GetCurrentThreadId() -> u32;
+        fn SetUnhandledExceptionFilter(filter: Option<TopLevelExceptionFilter>) -> Option<TopLevelExceptionFilter>;
+    }
+
+    #[link(name = "dbghelp")]
+    extern "system" {
+        fn MiniDumpWriteDump(
+            process: *mut c_void,
+            process_id: u32,
+            file: *mut c_void,
+            dump_type: u32,
+            exception: *const MinidumpExceptionInformation,
+            user_streams: *const c_void,
+            callback: *const c_void,
+        ) -> i32;
+    }
+
+    pub fn write_dump(path: &Path, exception_pointers: Option<ExceptionPointers>) -> io::Result<()> {
+        let file = File::create(path)?;
+        let exception = exception_pointers.map(|pointers| MinidumpExceptionInformation {
+            thread_id: unsafe { GetCurrentThreadId() },
+            exception_pointers: pointers,
+            client_pointers: 0,
+        });
+        let exception = exception.as_ref().map_or(std::ptr::null(), |exception| exception as *const _);
+        let written = unsafe {
+            MiniDumpWriteDump(
+                GetCurrentProcess(),
+                GetCurrentProcessId(),
+                file.as_raw_handle(),
+                DUMP_TYPE,
+                exception,
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        if written == 0 {
+            let error = io::Error::last_os_error();
+            drop(file);
+            let _ = fs::remove_file(path);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn set_unhandled_exception_filter() {
+        unsafe {
+            SetUnhandledExceptionFilter(Some(on_unhandled_exception));
+        }
+    }
+
+    unsafe extern "system" fn on_unhandled_exception(pointers: ExceptionPointers) -> i32 {
+        if let Some(target) = super::TARGET.get() {
+            let _ = target.write_dump(Some(pointers));
+        }
+        EXCEPTION_CONTINUE_SEARCH
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+    use std::path::Path;
+
+    pub type ExceptionPointers = ();
+
+    pub fn write_dump(_path: &Path, _exception_pointers: Option<ExceptionPointers>) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "minidumps can only be written on Windows"))
+    }
+
+    pub fn set_unhandled_exception_filter() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[test]
+    fn report_file_name_has_the_time_and_process() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_760_000_000);
+
+        assert_eq!(report_file_name("notes", time, 4242, "dmp"), "notes-1760000000-4242.dmp");
+        assert!(crash_dir("notes").ends_with(Path::new(CRASH_DUMPS_FOLDER)));
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An `http://` or `https://` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Url {
+    pub secure: bool,
+    /// The host as in the URL, with the brackets of an IPv6 address.
+    pub host: String,
+    pub port: u16,
+    /// The path and query of the URL, starting with `/`.
+    pub path: String,
+}
+
+/// The status and body of a response.
+#[derive(Debug)]
+pub(crate) struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl Url {
+    /// Fails with [`io::ErrorKind::InvalidInput`] when `url` isn't an `http://` or `https://` URL.
+    pub fn parse(url: &str) -> io::Result<Url> {
+        let invalid =
+            || io::Error::new(io::ErrorKind::InvalidInput, format!("expected an http:// or https:// URL: {url}"));
+        let (secure, rest) = match (url.strip_prefix("https://"), url.strip_prefix("http://")) {
+            (Some(rest), _) => (true, rest),
+            (None, Some(rest)) => (false, rest),
+            (None, None) => return Err(invalid()),
+        };
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (host, port.parse().map_err(|_| invalid())?),
+            _ => (authority, if secure { 443 } else { 80 }),
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        let path = if path.starts_with('/') { path.to_owned() } else { format!("/{path}") };
+        Ok(Url { secure, host: host.to_owned(), port, path })
+    }
+
+    /// The URL of `relative` below the path of this one, such as `/otlp/v1/traces` for `v1/traces` below `/otlp/`.
+    pub fn join(&self, relative: &str) -> Url {
+        let base = self.path.split('?').next().unwrap_or_default().trim_end_matches('/');
+        Url { path: format!("{base}/{relative}"), ..self.clone() }
+    }
+}
+
+/// Sends a request and reads the response. `https://` goes through WinHTTP, which uses the TLS and proxy settings of
+/// Windows, so other hosts can only send to `http://` URLs.
+///
+/// Fails with [`io::ErrorKind::InvalidData`] when the response body is longer than `max_len`, before allocating for
+/// it, so that a server can't make the app run out of memory.
+pub(crate) fn send(
+    method: &str,
+    url: &Url,
+    headers: &[(String, String)],
+    body: &[u8],
+    max_len: usize,
+) -> io::Result<Response> {
+    if url.secure {
+        native::send_https(method, url, headers, body, max_len)
+    } else {
+        send_http(method, url, headers, body, max_len)
+    }
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986, for a path segment or query value.
+pub(crate) fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn too_long(max_len: usize) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("the response is longer than {max_len} bytes"))
+}
+
+/// Sends over plain HTTP/1.1, which services next to the app, such as a collector on localhost, listen on.
+fn send_http(
+    method: &str,
+    url: &Url,
+    headers: &[(String, String)],
+    body: &[u8],
+    max_len: usize,
+) -> io::Result<Response> {
+    let host = url.host.trim_start_matches('[').trim_end_matches(']');
+    let address = (host, url.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{host} has no address")))?;
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{method} {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        url.path,
+        url.host,
+        url.port,
+        body.len()
+    );
+    for (name, value) in headers {
+        let _ = write!(request, "{name}: {value}\r\n");
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(body)?;
+
+    read_response(BufReader::new(stream), max_len)
+}
+
+fn read_response(mut reader: impl BufRead, max_len: usize) -> io::Result<Response> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid(format!("not an HTTP response: {}", status_line.trim_end())))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<usize>().ok();
+            } else if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            }
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = size.split(';').next().unwrap_or_default().trim();
+            let size = usize::from_str_radix(size, 16).map_err(|_| invalid(format!("bad chunk size: {size}")))?;
+            if size == 0 {
+                break;
+            }
+            if size > max_len - body.len() {
+                return Err(too_long(max_len));
+            }
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+            reader.read_line(&mut String::new())?;
+        }
+    } else if let Some(length) = content_length {
+        if length > max_len {
+            return Err(too_long(max_len));
+        }
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.take(max_len as u64 + 1).read_to_end(&mut body)?;
+        if body.len() > max_len {
+            return Err(too_long(max_len));
+        }
+    }
+    Ok(Response { status, body })
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+    use std::io;
+    use std::ptr;
+
+    use super::{too_long, Response, Url};
+
+    const WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY: u32 = 4;
+    const WINHTTP_FLAG_SECURE: u32 = 0x0080_0000;
+    const WINHTTP_QUERY_STATUS_CODE: u32 = 19;
+    const WINHTTP_QUERY_FLAG_NUMBER: u32 = 0x2000_0000;
+    const TIMEOUT_MILLISECONDS: i32 = 10_000;
+
+    #[link(name = "winhttp")]
+    extern "system" {
+        fn WinHttpOpen(
+            agent: *const u16,
+            access_type: u32,
+            proxy: *const u16,
+            proxy_bypass: *const u16,
+            flags: u32,
+        ) -> *mut c_void;
+        fn WinHttpSetTimeouts(handle: *mut c_void, resolve: i32, connect: i32, send: i32, receive: i32) -> i32;
+        fn WinHttpConnect(session: *mut c_void, server: *const u16, port: u16, reserved: u32) -> *mut c_void;
+        fn WinHttpOpenRequest(
+            connection: *mut c_void,
+            verb: *const u16,
+            object: *const u16,
+            version: *const u16,
+            referrer: *const u16,
+            accept_types: *const *const u16,
+            flags: u32,
+        ) -> *mut c_void;
+        fn WinHttpSendRequest(
+            request: *mut c_void,
+            headers: *const u16,
+            headers_length: u32,
+            optional: *const c_void,
+            optional_length: u32,
+            total_length: u32,
+            context: usize,
+        ) -> i32;
+        fn WinHttpReceiveResponse(request: *mut c_void, reserved: *mut c_void) -> i32;
+        fn WinHttpQueryHeaders(
+            request: *mut c_void,
+            info_level: u32,
+            name: *const u16,
+            buffer: *mut c_void,
+            buffer_length: *mut u32,
+            index: *mut u32,
+        ) -> i32;
+        fn WinHttpQueryDataAvailable(request: *mut c_void, available: *mut u32) -> i32;
+        fn WinHttpReadData(request: *mut c_void, buffer: *mut c_void, to_read: u32, read: *mut u32) -> i32;
+        fn WinHttpCloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    /// A WinHTTP handle, closed when dropped.
+    struct Handle(*mut c_void);
+
+    impl Handle {
+        fn new(handle: *mut c_void) -> io::Result<Handle> {
+            if handle.is_null() {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(Handle(handle))
+            }
+        }
+    }
+
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            unsafe {
+                WinHttpCloseHandle(self.0);
+            }
+        }
+    }
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(Some(0)).collect()
+    }
+
+    fn check(result: i32) -> io::Result<()> {
+        if result == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn send_https(
+        method: &str,
+        url: &Url,
+        headers: &[(String, String)],
+        body: &[u8],
+        max_len: usize,
+    ) -> io::Result<Response> {
+        let length = u32::try_from(body.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "the request body is too large"))?;
+        let agent = wide(concat!("winapp-runtime/", env!("CARGO_PKG_VERSION")));
+        let host = wide(url.host.trim_start_matches('[').trim_end_matches(']'));
+        let verb = wide(method);
+        let path = wide(&url.path);
+        let header_lines: String = headers.iter().map(|(name, value)| format!("{name}: {value}\r\n")).collect();
+        let header_lines = wide(&header_lines);
+
+        let session = Handle::new(unsafe {
+            WinHttpOpen(agent.as_ptr(), WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, ptr::null(), ptr::null(), 0)
+        })?;
+        check(unsafe {
+            WinHttpSetTimeouts(
+                session.0,
+                TIMEOUT_MILLISECONDS,
+                TIMEOUT_MILLISECONDS,
+                TIMEOUT_MILLISECONDS,
+                TIMEOUT_MILLISECONDS,
+            )
+        })?;
+        let connection = Handle::new(unsafe { WinHttpConnect(session.0, host.as_ptr(), url.port, 0) })?;
+        let request = Handle::new(unsafe {
+            WinHttpOpenRequest(
+                connection.0,
+                verb.as_ptr(),
+                path.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                WINHTTP_FLAG_SECURE,
+            )
+        })?;
+        // A length of -1 means the headers end at their null terminator
+        check(unsafe {
+            WinHttpSendRequest(request.0, header_lines.as_ptr(), u32::MAX, body.as_ptr().cast(), length, length, 0)
+        })?;
+        check(unsafe { WinHttpReceiveResponse(request.0, ptr::null_mut()) })?;
+
+        let mut status = 0u32;
+        let mut status_length = std::mem::size_of::<u32>() as u32;
+        check(unsafe {
+            WinHttpQueryHeaders(
+                request.0,
+                WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+                ptr::null(),
+                (&mut status as *mut u32).cast(),
+                &mut status_length,
+                ptr::null_mut(),
+            )
+        })?;
+
+        // WinHTTP removes the chunked encoding
+        let mut response = Vec::new();
+        loop {
+            let mut available = 0u32;
+            check(unsafe { WinHttpQueryDataAvailable(request.0, &mut available) })?;
+            if available == 0 {
+                break;
+            }
+            if available as usize > max_len - response.len() {
+                return Err(too_long(max_len));
+            }
+            let start = response.len();
+            response.resize(start + available as usize, 0);
+            let mut read = 0u32;
+            check(unsafe { WinHttpReadData(request.0, response[start..].as_mut_ptr().cast(), available, &mut read) })?;
+            response.truncate(start + read as usize);
+            if read == 0 {
+                break;
+            }
+        }
+        Ok(Response { status: status as u16, body: response })
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+
+    use super::{Response, Url};
+
+    pub fn send_https(
+        _method: &str,
+        _url: &Url,
+        _headers: &[(String, String)],
+        _body: &[u8],
+        _max_len: usize,
+    ) -> io::Result<Response> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "https:// URLs can only be used on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_the_host_port_and_path() {
+        let url = Url::parse("https://otel.contoso.com/otlp/").unwrap();
+        assert_eq!((url.secure, url.host.as_str(), url.port), (true, "otel.contoso.com", 443));
+        assert_eq!(url.join("v1/traces").path, "/otlp/v1/traces");
+
+        let url = Url::parse("http://[::1]:4318").unwrap();
+        assert_eq!((url.host.as_str(), url.port, url.path.as_str()), ("[::1]", 4318, "/"));
+        assert_eq!(url.join("v1/metrics").path, "/v1/metrics");
+
+        let url = Url::parse("https://contoso.blob.core.windows.net/config/notes.json?sv=2024&sig=a%2Fb").unwrap();
+        assert_eq!(url.path, "/config/notes.json?sv=2024&sig=a%2Fb");
+
+        assert_eq!(Url::parse("grpc://localhost:4317").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(Url::parse("http://:80/").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn read_response_reads_chunked_and_sized_bodies() {
+        let chunked =
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\n\r\n";
+        let sized = "HTTP/1.1 404 Not Found\r\ncontent-length: 9\r\n\r\nnot found and more";
+
+        let chunked = read_response(chunked.as_bytes(), 1024).unwrap();
+        let sized = read_response(sized.as_bytes(), 1024).unwrap();
+
+        assert_eq!((chunked.status, chunked.body.as_slice()), (200, b"hello, world".as_slice()));
+        assert_eq!((sized.status, sized.body.as_slice()), (404, b"not found".as_slice()));
+        assert!(read_response("SSH-2.0-OpenSSH\r\n".as_bytes(), 1024).is_err());
+    }
+
+    #[test]
+    fn read_response_rejects_bodies_longer_than_the_limit() {
+        let huge = "HTTP/1.1 200 OK\r\nContent-Length: 18446744073709551615\r\n\r\n";
+        let chunked = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n7\r\n, world\r\n0\r\n\r\n";
+        let unframed = "HTTP/1.1 200 OK\r\n\r\nhello, world";
+
+        for response in [huge, chunked, unframed] {
+            assert_eq!(read_response(response.as_bytes(), 8).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+        assert_eq!(read_response(unframed.as_bytes(), 12).unwrap().body, b"hello, world");
+        assert_eq!(percent_encode("C:\\My Notes\\a~b.txt"), "C%3A%5CMy%20Notes%5Ca~b.txt");
+    }
+}
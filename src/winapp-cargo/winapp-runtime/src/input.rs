@@ -0,0 +1,434 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::io;
+use std::mem::size_of;
+
+/// An API that reads or sends input outside the app's own windows, which the trust level of the app decides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputApi {
+    /// `SetWindowsHookEx(WH_KEYBOARD_LL)`. In the app container the hook installs without an error, but Windows never
+    /// calls it.
+    LowLevelKeyboardHook,
+    /// `SetWindowsHookEx(WH_MOUSE_LL)`, which fails the same way as [`InputApi::LowLevelKeyboardHook`].
+    LowLevelMouseHook,
+    /// Raw input with `RIDEV_INPUTSINK`, which reads the keyboard and mouse while another app has the foreground. In
+    /// the app container only the foreground gets it.
+    BackgroundRawInput,
+    /// `SendInput`, hooks and raw input for windows of elevated apps, which User Interface Privilege Isolation only
+    /// allows from a process of at least the same integrity, or with `uiAccess`.
+    InputToElevatedWindows,
+}
+
+/// The mandatory integrity level of a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IntegrityLevel {
+    Untrusted,
+    /// App container processes, and processes such as browser sandboxes.
+    Low,
+    /// Apps of a standard user, including packaged Win32 apps with `runFullTrust`.
+    Medium,
+    /// Elevated apps.
+    High,
+    System,
+}
+
+/// What the token of the current process allows, which decides the [`InputApi`]s that work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputCapabilities {
+    /// Whether the process runs in the app container, as a UWP app or with `uap10:TrustLevel="appContainer"`.
+    pub app_container: bool,
+    pub integrity: IntegrityLevel,
+    /// Whether the process has `uiAccess`, which signed accessibility tools installed in Program Files can have.
+    pub ui_access: bool,
+}
+
+impl InputCapabilities {
+    /// Reads the capabilities of the current process from its token. Fails with [`io::ErrorKind::Unsupported`] on
+    /// hosts other than Windows.
+    pub fn current() -> io::Result<Self> {
+        native::current()
+    }
+
+    /// Whether `api` works in this process.
+    pub fn is_available(&self, api: InputApi) -> bool {
+        self.unavailable_reason(api).is_none()
+    }
+
+    /// Fails with [`io::ErrorKind::PermissionDenied`] and the reason when `api` doesn't work in this process, for the
+    /// APIs that would otherwise fail without telling.
+    pub fn check(&self, api: InputApi) -> io::Result<()> {
+        match self.unavailable_reason(api) {
+            Some(reason) => Err(io::Error::new(io::ErrorKind::PermissionDenied, reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn unavailable_reason(&self, api: InputApi) -> Option<&'static str> {
+        match api {
+            InputApi::LowLevelKeyboardHook | InputApi::LowLevelMouseHook if self.app_container => Some(
+                "low-level hooks install in the app container but are never called; run the app with \
+                 uap10:TrustLevel=\"mediumIL\" or EntryPoint=\"Windows.FullTrustApplication\"",
+            ),
+            InputApi::BackgroundRawInput if self.app_container => {
+                Some("the app container only gets raw input while it has the foreground")
+            }
+            InputApi::InputToElevatedWindows if self.integrity < IntegrityLevel::High && !self.ui_access => {
+                Some("input to elevated windows needs an elevated process or uiAccess")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A top level collection to read with raw input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawInputDevice {
+    Keyboard,
+    Mouse,
+    /// Another HID device, by its usage page and usage, such as 1 and 5 for game pads.
+    Hid {
+        usage_page: u16,
+        usage: u16,
+    },
+}
+
+/// A `WM_INPUT` message, as [`read_raw_input`] reads it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawInputEvent {
+    Keyboard(RawKeyboard),
+    Mouse(RawMouse),
+    /// The reports of a HID device: `count` reports of `size` bytes each.
+    Hid {
+        device: isize,
+        size: u32,
+        count: u32,
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawKeyboard {
+    /// The `HANDLE` of the device, which tells several keyboards apart.
+    pub device: isize,
+    pub scan_code: u16,
+    pub virtual_key: u16,
+    pub key_up: bool,
+    /// Whether the scan code has the `E0` prefix, as the right Ctrl and Alt keys and the arrow keys do.
+    pub extended: bool,
+    /// The window message the key makes, such as `WM_KEYDOWN`.
+    pub message: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawMouse {
+    /// The `HANDLE` of the device.
+    pub device: isize,
+    /// The motion since the last event, or the position on the virtual desktop from 0 to 65535 when `absolute` is set,
+    /// as for pens and remote desktop sessions.
+    pub x: i32,
+    pub y: i32,
+    pub absolute: bool,
+    /// The `RI_MOUSE_*` transitions of the buttons and wheels.
+    pub button_flags: u16,
+    /// The distance the wheel turned, in multiples of `WHEEL_DELTA` (120), for `RI_MOUSE_WHEEL` and
+    /// `RI_MOUSE_HWHEEL`.
+    pub wheel: i16,
+}
+
+const RIM_TYPEMOUSE: u32 = 0;
+const RIM_TYPEKEYBOARD: u32 = 1;
+const RIM_TYPEHID: u32 = 2;
+const RI_KEY_BREAK: u16 = 1;
+const RI_KEY_E0: u16 = 2;
+const MOUSE_MOVE_ABSOLUTE: u16 = 1;
+const RI_MOUSE_WHEEL: u16 = 0x0400;
+const RI_MOUSE_HWHEEL: u16 = 0x0800;
+
+/// Registers `window`, an `HWND`, for the `WM_INPUT` messages of `devices`, and with `background` also while another
+/// app has the foreground.
+///
+/// Fails with [`io::ErrorKind::PermissionDenied`] for `background` in the app container, where Windows accepts the
+/// registration but sends nothing in the background.
+pub fn register_raw_input(window: isize, devices: &[RawInputDevice], background: bool) -> io::Result<()> {
+    if background {
+        InputCapabilities::current()?.check(InputApi::BackgroundRawInput)?;
+    }
+    native::register_raw_input(window, devices, background)
+}
+
+/// Reads the input of a `WM_INPUT` message from its `LPARAM`.
+pub fn read_raw_input(lparam: isize) -> io::Result<RawInputEvent> {
+    let data = native::raw_input_data(lparam)?;
+    parse_raw_input(&data, size_of::<usize>())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "the raw input is shorter than its type"))
+}
+
+/// Reads a `RAWINPUT` of a process whose pointers have `pointer_size` bytes: a `RAWINPUTHEADER` with the type, the
+/// size, the device handle and the `WPARAM`, and the `RAWMOUSE`, `RAWKEYBOARD` or `RAWHID` after it.
+fn parse_raw_input(data: &[u8], pointer_size: usize) -> Option<RawInputEvent> {
+    let u16_at = |offset: usize| Some(u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?));
+    let u32_at = |offset: usize| Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?));
+    let device = match pointer_size {
+        8 => i64::from_le_bytes(data.get(8..16)?.try_into().ok()?) as isize,
+        _ => u32_at(8)? as i32 as isize,
+    };
+    let body = 8 + 2 * pointer_size;
+
+    match u32_at(0)? {
+        RIM_TYPEKEYBOARD => {
+            let flags = u16_at(body + 2)?;
+            Some(RawInputEvent::Keyboard(RawKeyboard {
+                device,
+                scan_code: u16_at(body)?,
+                virtual_key: u16_at(body + 6)?,
+                key_up: flags & RI_KEY_BREAK != 0,
+                extended: flags & RI_KEY_E0 != 0,
+                message: u32_at(body + 8)?,
+            }))
+        }
+        RIM_TYPEMOUSE => {
+            // usFlags is followed by 2 bytes of padding before the union of ulButtons
+            let flags = u16_at(body)?;
+            let button_flags = u16_at(body + 4)?;
+            Some(RawInputEvent::Mouse(RawMouse {
+                device,
+                x: u32_at(body + 12)? as i32,
+                y: u32_at(body + 16)? as i32,
+                absolute: flags & MOUSE_MOVE_ABSOLUTE != 0,
+                button_flags,
+                wheel: match button_flags & (RI_MOUSE_WHEEL | RI_MOUSE_HWHEEL) {
+                    0 => 0,
+                    _ => u16_at(body + 6)? as i16,
+                },
+            }))
+        }
+        RIM_TYPEHID => {
+            let size = u32_at(body)?;
+            let count = u32_at(body + 4)?;
+            let length = (size as usize).checked_mul(count as usize)?;
+            let data = data.get(body + 8..body + 8 + length)?.to_vec();
+            Some(RawInputEvent::Hid { device, size, count, data })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+    use std::io;
+    use std::mem::size_of;
+
+    use super::{InputCapabilities, IntegrityLevel, RawInputDevice};
+
+    const TOKEN_QUERY: u32 = 0x0008;
+    const TOKEN_INTEGRITY_LEVEL: u32 = 25;
+    const TOKEN_UI_ACCESS: u32 = 26;
+    const TOKEN_IS_APP_CONTAINER: u32 = 29;
+    const RIDEV_INPUTSINK: u32 = 0x0000_0100;
+    const RID_INPUT: u32 = 0x1000_0003;
+
+    #[repr(C)]
+    struct RawInputDeviceRegistration {
+        usage_page: u16,
+        usage: u16,
+        flags: u32,
+        target: isize,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentProcess() -> *mut c_void;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn OpenProcessToken(process: *mut c_void, access: u32, token: *mut *mut c_void) -> i32;
+        fn GetTokenInformation(
+            token: *mut c_void,
+            class: u32,
+            information: *mut c_void,
+            length: u32,
+            returned: *mut u32,
+        ) -> i32;
+        fn GetSidSubAuthorityCount(sid: *mut c_void) -> *mut u8;
+        fn GetSidSubAuthority(sid: *mut c_void, index: u32) -> *mut u32;
+    }
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn RegisterRawInputDevices(devices: *const RawInputDeviceRegistration, count: u32, size: u32) -> i32;
+        fn GetRawInputData(input: isize, command: u32, data: *mut c_void, size: *mut u32, header_size: u32) -> u32;
+    }
+
+    pub fn current() -> io::Result<InputCapabilities> {
+        let mut token = std::ptr::null_mut();
+        if unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let capabilities = read_token(token);
+        unsafe { CloseHandle(token) };
+        capabilities
+    }
+
+    fn read_token(token: *mut c_void) -> io::Result<InputCapabilities> {
+        // DWORDs, in the low bytes of the first word
+        let app_container = token_information(token, TOKEN_IS_APP_CONTAINER)?[0] as u32 != 0;
+        let ui_access = token_information(token, TOKEN_UI_ACCESS)?[0] as u32 != 0;
+
+        // A TOKEN_MANDATORY_LABEL, whose SID points into the same buffer and ends with the integrity level
+        let label = token_information(token, TOKEN_INTEGRITY_LEVEL)?;
+        let sid = label[0] as *mut c_void;
+        let rid = unsafe {
+            let count = *GetSidSubAuthorityCount(sid);
+            *GetSidSubAuthority(sid, u32::from(count).saturating_sub(1))
+        };
+        let integrity = match rid {
+            0..=0x0fff => IntegrityLevel::Untrusted,
+            0x1000..=0x1fff => IntegrityLevel::Low,
+            0x2000..=0x2fff => IntegrityLevel::Medium,
+            0x3000..=0x3fff => IntegrityLevel::High,
+            _ => IntegrityLevel::System,
+        };
+        Ok(InputCapabilities { app_container, integrity, ui_access })
+    }
+
+    /// The information of a class, in words so that the pointers of some classes are aligned.
+    fn token_information(token: *mut c_void, class: u32) -> io::Result<Vec<usize>> {
+        let mut length = 0u32;
+        unsafe { GetTokenInformation(token, class, std::ptr::null_mut(), 0, &mut length) };
+        let mut buffer = vec![0usize; (length as usize).div_ceil(size_of::<usize>()).max(1)];
+        if unsafe { GetTokenInformation(token, class, buffer.as_mut_ptr().cast(), length, &mut length) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(buffer)
+    }
+
+    pub fn register_raw_input(window: isize, devices: &[RawInputDevice], background: bool) -> io::Result<()> {
+        let registrations: Vec<RawInputDeviceRegistration> = devices
+            .iter()
+            .map(|device| {
+                let (usage_page, usage) = match *device {
+                    RawInputDevice::Keyboard => (1, 6),
+                    RawInputDevice::Mouse => (1, 2),
+                    RawInputDevice::Hid { usage_page, usage } => (usage_page, usage),
+                };
+                RawInputDeviceRegistration {
+                    usage_page,
+                    usage,
+                    flags: if background { RIDEV_INPUTSINK } else { 0 },
+                    target: window,
+                }
+            })
+            .collect();
+        let registered = unsafe {
+            RegisterRawInputDevices(
+                registrations.as_ptr(),
+                registrations.len() as u32,
+                size_of::<RawInputDeviceRegistration>() as u32,
+            )
+        };
+        if registered == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn raw_input_data(lparam: isize) -> io::Result<Vec<u8>> {
+        let header_size = (8 + 2 * size_of::<usize>()) as u32;
+        let mut size = 0u32;
+        if unsafe { GetRawInputData(lparam, RID_INPUT, std::ptr::null_mut(), &mut size, header_size) } == u32::MAX {
+            return Err(io::Error::last_os_error());
+        }
+        let mut data = vec![0u8; size as usize];
+        if unsafe { GetRawInputData(lparam, RID_INPUT, data.as_mut_ptr().cast(), &mut size, header_size) } == u32::MAX {
+            return Err(io::Error::last_os_error());
+        }
+        data.truncate(size as usize);
+        Ok(data)
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+
+    use super::{InputCapabilities, RawInputDevice};
+
+    pub fn current() -> io::Result<InputCapabilities> {
+        Err(unsupported())
+    }
+
+    pub fn register_raw_input(_window: isize, _devices: &[RawInputDevice], _background: bool) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn raw_input_data(_lparam: isize) -> io::Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "input capabilities and raw input need Windows")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_container_has_no_global_input() {
+        let app_container = InputCapabilities { app_container: true, integrity: IntegrityLevel::Low, ui_access: false };
+        let desktop = InputCapabilities { app_container: false, integrity: IntegrityLevel::Medium, ui_access: false };
+
+        assert!(!app_container.is_available(InputApi::LowLevelKeyboardHook));
+        assert!(!app_container.is_available(InputApi::BackgroundRawInput));
+        assert_eq!(
+            app_container.check(InputApi::LowLevelMouseHook).unwrap_err().kind(),
+            io::ErrorKind::PermissionDenied
+        );
+        assert!(desktop.is_available(InputApi::LowLevelKeyboardHook));
+        assert!(desktop.is_available(InputApi::BackgroundRawInput));
+        assert!(!desktop.is_available(InputApi::InputToElevatedWindows));
+        assert!(InputCapabilities { ui_access: true, ..desktop }.is_available(InputApi::InputToElevatedWindows));
+    }
+
+    #[test]
+    fn parse_raw_input_reads_keyboard_mouse_and_hid() {
+        fn header(kind: u32, device: i64) -> Vec<u8> {
+            [&kind.to_le_bytes()[..], &0u32.to_le_bytes(), &device.to_le_bytes(), &0u64.to_le_bytes()].concat()
+        }
+
+        // Right Ctrl released: scan code 0x1d with E0, VK_RCONTROL, WM_KEYUP
+        let keyboard = [header(1, 7), vec![0x1d, 0, 3, 0, 0, 0, 0xa3, 0, 0x01, 0x01, 0, 0, 0, 0, 0, 0]].concat();
+        assert_eq!(
+            parse_raw_input(&keyboard, 8),
+            Some(RawInputEvent::Keyboard(RawKeyboard {
+                device: 7,
+                scan_code: 0x1d,
+                virtual_key: 0xa3,
+                key_up: true,
+                extended: true,
+                message: 0x101
+            }))
+        );
+
+        // A wheel turn of -120 and a motion of (-3, 5)
+        let mut mouse = header(0, 9);
+        mouse.extend([0, 0, 0, 0, 0x00, 0x04, 0x88, 0xff, 0, 0, 0, 0]);
+        mouse.extend((-3i32).to_le_bytes());
+        mouse.extend(5i32.to_le_bytes());
+        mouse.extend(0u32.to_le_bytes());
+        let Some(RawInputEvent::Mouse(mouse)) = parse_raw_input(&mouse, 8) else { panic!("not a mouse event") };
+        assert_eq!((mouse.x, mouse.y, mouse.wheel, mouse.absolute), (-3, 5, -120, false));
+
+        let hid = [header(2, 4), vec![2, 0, 0, 0, 2, 0, 0, 0, 0xa, 0xb, 0xc, 0xd]].concat();
+        assert_eq!(
+            parse_raw_input(&hid, 8),
+            Some(RawInputEvent::Hid { device: 4, size: 2, count: 2, data: vec![0xa, 0xb, 0xc, 0xd] })
+        );
+        assert_eq!(parse_raw_input(&hid[..hid.len() - 1], 8), None);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Runtime helpers for Rust and Tauri apps packaged with the [winapp CLI](https://github.com/microsoft/winappCli).
+//!
+//! [`CrashReporter`] writes a minidump to `CrashDumps` in the local ApplicationData folder of the package when the
+//! app panics or crashes, where `winapp crashes upload` finds it and sends it, with the app's symbols, to a
+//! crash reporting service:
+//!
+//! ```no_run
+//! // main.rs
+//! fn main() -> std::io::Result<()> {
+//!     winapp_runtime::CrashReporter::new("notes").install()?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`PanicReporter`] writes a text report of each panic next to it, in `PanicReports`, with the paths of the build
+//! and of the install folder made relative to the source root with the [`BuildInfo`] of the package, and can show a
+//! toast that asks the user to send it.
+//!
+//! The logging convention: an app writes `<name>.log` to `Logs` in the same folder, and moves it to `<name>.1.log`,
+//! `<name>.2.log` and so on when it grows too big. `winapp logs tail` follows that folder while the app runs, and
+//! `winapp logs collect` zips it for a bug report.
+//!
+//! [`RollingFile`] writes such a log. It has no logging framework of its own; any logger that writes to an
+//! [`std::io::Write`] can use it, such as a `tracing` subscriber:
+//!
+//! ```ignore
+//! // main.rs, with tracing and tracing-subscriber as dependencies
+//! let log = std::sync::Arc::new(winapp_runtime::RollingFile::builder("notes").open()?);
+//! tracing_subscriber::fmt().with_writer(log).with_ansi(false).init();
+//! tracing::info!("started");
+//! ```
+//!
+//! [`Telemetry`] exports spans and metrics to an OpenTelemetry collector over OTLP/HTTP, with the package identity
+//! and version of the app as resource attributes, so the app shows up in an existing telemetry backend:
+//!
+//! ```no_run
+//! let telemetry = winapp_runtime::Telemetry::builder("notes").endpoint("https://otel.contoso.com").start()?;
+//! let _span = telemetry.span("startup");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`RemoteConfig`] fetches feature flags and settings from a JSON document, evaluated for the channel and version of
+//! the app, and starts with the last document it fetched when the app is offline:
+//!
+//! ```no_run
+//! let config = winapp_runtime::RemoteConfig::builder("notes", "https://contoso.com/notes/config.json")
+//!     .channel("beta")
+//!     .fetch()?;
+//! if config.is_enabled("new-editor") {
+//!     // ...
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`TransferGroup`] hands large downloads and uploads to Windows' background transfer service, which goes on while the
+//! app is suspended or closed, and picks them up with their metadata when the app starts again:
+//!
+//! ```no_run
+//! let content = winapp_runtime::TransferGroup::new("notes", "content");
+//! for transfer in content.resume()? {
+//!     println!("{} is {:?}", transfer.info().url, transfer.progress().status);
+//! }
+//! let transfer = content.download("https://contoso.com/notes/templates.zip", "templates.zip").start()?;
+//! transfer.wait()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`Lifecycle`] tells a packaged Win32 app when Windows moves it to the background, when the system sleeps and wakes,
+//! and when energy saver turns on or off, and holds off the sleep until the app has saved its work:
+//!
+//! ```no_run
+//! let _lifecycle = winapp_runtime::Lifecycle::builder().start(|event| match event {
+//!     winapp_runtime::LifecycleEvent::Suspending => { /* save */ }
+//!     _ => {}
+//! })?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`updates::UpdateFeed`] checks the `.appinstaller` file and packages of a sideloaded app's update feed against the
+//! integrity manifest that `winapp distribute web --sign-feed` signs, before an update check acts on them:
+//!
+//! ```no_run
+//! let feed = winapp_runtime::updates::UpdateFeed::builder("https://contoso.com/notes/Contoso.Notes.appinstaller")
+//!     .signer_thumbprint("1A2B3C4D5E6F708192A3B4C5D6E7F8091A2B3C4D")
+//!     .verify()?;
+//! let appinstaller = feed.fetch_appinstaller()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`request_restart_for_update`] saves a JSON payload and has Windows start the app again with it after an update
+//! closes the app, and [`request_restart_after_crash`] after a crash. The restarted app gets it from [`restore_state`]:
+//!
+//! ```no_run
+//! if let Some(state) = winapp_runtime::restore_state("notes")? {
+//!     println!("restarted after {:?} with {}", state.reason, state.payload);
+//! }
+//! winapp_runtime::request_restart_for_update("notes", &serde_json::json!({ "open": ["todo.md"] }))?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`InputCapabilities`] tells which global input APIs work under the app's trust level, such as low-level keyboard
+//! hooks, which install in the app container but are never called, and [`register_raw_input`] and
+//! [`read_raw_input`] read the keyboard, mouse and HID devices with raw input:
+//!
+//! ```no_run
+//! use winapp_runtime::{InputApi, InputCapabilities};
+//!
+//! InputCapabilities::current()?.check(InputApi::LowLevelKeyboardHook)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`Locale`] reads the language, which `WINAPP_LOCALE` overrides, and the ANSI code page, and
+//! [`Locale::check_path`] catches paths that ANSI functions can't open while the code page isn't UTF-8:
+//!
+//! ```no_run
+//! let locale = winapp_runtime::Locale::current()?;
+//! locale.check_path(std::path::Path::new("notes/résumé.md"))?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+mod build_info;
+mod crash;
+mod http;
+mod input;
+mod lifecycle;
+mod locale;
+mod logs;
+mod package;
+mod panic_report;
+mod remote_config;
+mod restart;
+mod telemetry;
+mod transfer;
+pub mod updates;
+
+pub use build_info::{BuildInfo, BUILD_INFO_FILE};
+pub use crash::{crash_dir, CrashReporter, CRASH_DUMPS_FOLDER};
+pub use input::{
+    read_raw_input, register_raw_input, InputApi, InputCapabilities, IntegrityLevel, RawInputDevice, RawInputEvent,
+    RawKeyboard, RawMouse,
+};
+pub use lifecycle::{Lifecycle, LifecycleBuilder, LifecycleEvent};
+pub use locale::{Locale, LOCALE_VARIABLE, UTF8_CODE_PAGE};
+pub use logs::{log_dir, RollingFile, RollingFileBuilder, LOGS_FOLDER, LOG_DIR_VARIABLE};
+pub use package::{app_data_dir, app_user_model_id, package_family_name, package_full_name};
+pub use panic_report::{panic_report_dir, PanicReporter, PANIC_REPORTS_FOLDER};
+pub use remote_config::{
+    remote_config_dir, ConfigSource, RemoteConfig, RemoteConfigBuilder, DEFAULT_CHANNEL, REMOTE_CONFIG_FOLDER,
+    SCHEMA_VERSION,
+};
+pub use restart::{
+    cancel_restart, request_restart_after_crash, request_restart_for_update, restart_state_dir, restore_state,
+    RestartReason, RestoredState, RESTART_STATE_FOLDER, RESTORE_STATE_ARGUMENT,
+};
+pub use telemetry::{
+    AttributeValue, Counter, Gauge, Span, Telemetry, TelemetryBuilder, OTLP_ENDPOINT_VARIABLE, OTLP_HEADERS_VARIABLE,
+};
+pub use transfer::{
+    transfers_dir, CostPolicy, Transfer, TransferGroup, TransferInfo, TransferKind, TransferProgress, TransferRequest,
+    TransferStatus, TRANSFERS_FOLDER,
+};
@@ -0,0 +1,517 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::fs;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::app_user_model_id;
+use crate::package::package_path;
+
+/// A change of the state of the app or of the system that a packaged Win32 app may want to save its work or do less
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleEvent {
+    /// Windows quiesces the app, e.g. when its windows are minimized on a device that moderates background apps:
+    /// its timers and threads get less time until [`LifecycleEvent::LeavingBackground`].
+    EnteredBackground,
+    /// The app is back in the foreground.
+    LeavingBackground,
+    /// The system is going to sleep. The sleep waits for the callback, up to the
+    /// [`LifecycleBuilder::suspend_timeout`].
+    Suspending,
+    /// The system woke up.
+    Resumed,
+    /// Energy saver (battery saver) turned on, so the app should defer background work. Sent once when the
+    /// notifications start if it is on then.
+    EnergySaverOn,
+    EnergySaverOff,
+}
+
+/// Notifications of [`LifecycleEvent`]s, which stop when it is dropped.
+#[derive(Debug)]
+pub struct Lifecycle {
+    registrations: Vec<native::Registration>,
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+/// Which notifications a [`Lifecycle`] registers for.
+#[derive(Debug, Clone)]
+pub struct LifecycleBuilder {
+    background: bool,
+    suspend: bool,
+    energy_saver: bool,
+    suspend_timeout: Duration,
+}
+
+/// What the notification callbacks of Windows share with the thread that runs the app's callback.
+#[derive(Debug)]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) struct Shared {
+    sender: Mutex<Sender<Option<(LifecycleEvent, u64)>>>,
+    posted: AtomicU64,
+    handled: Mutex<u64>,
+    changed: Condvar,
+    suspend_timeout: Duration,
+}
+
+impl Lifecycle {
+    /// How long the system waits for the callback to handle [`LifecycleEvent::Suspending`], unless
+    /// [`LifecycleBuilder::suspend_timeout`] changes it. Windows allows about two seconds before it sleeps anyway.
+    pub const DEFAULT_SUSPEND_TIMEOUT: Duration = Duration::from_millis(1500);
+
+    /// Settings that register for every notification.
+    pub fn builder() -> LifecycleBuilder {
+        LifecycleBuilder {
+            background: true,
+            suspend: true,
+            energy_saver: true,
+            suspend_timeout: Self::DEFAULT_SUSPEND_TIMEOUT,
+        }
+    }
+}
+
+impl LifecycleBuilder {
+    /// Whether to send [`LifecycleEvent::EnteredBackground`] and [`LifecycleEvent::LeavingBackground`], from
+    /// `PsmRegisterAppStateChangeNotification`. They need package identity; unpackaged processes don't get them.
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Whether to send [`LifecycleEvent::Suspending`] and [`LifecycleEvent::Resumed`] when the system sleeps.
+    pub fn suspend(mut self, suspend: bool) -> Self {
+        self.suspend = suspend;
+        self
+    }
+
+    /// Whether to send [`LifecycleEvent::EnergySaverOn`] and [`LifecycleEvent::EnergySaverOff`].
+    pub fn energy_saver(mut self, energy_saver: bool) -> Self {
+        self.energy_saver = energy_saver;
+        self
+    }
+
+    /// How long the system waits for the callback to handle [`LifecycleEvent::Suspending`].
+    pub fn suspend_timeout(mut self, suspend_timeout: Duration) -> Self {
+        self.suspend_timeout = suspend_timeout;
+        self
+    }
+
+    /// Registers for the notifications and calls `callback` with each event, one after the other, on a thread of its
+    /// own, so that a slow callback doesn't hold up the notifications of Windows.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] when the process has package identity but its application isn't a
+    /// packaged Win32 app: an app with a UWP entry point is suspended by Windows and gets `CoreApplication` events
+    /// instead. Fails with [`io::ErrorKind::Unsupported`] on hosts other than Windows.
+    pub fn start(self, callback: impl FnMut(LifecycleEvent) + Send + 'static) -> io::Result<Lifecycle> {
+        let background = self.background && self.check_manifest()?;
+        let (shared, worker) = Shared::start(self.suspend_timeout, callback)?;
+
+        let mut lifecycle = Lifecycle { registrations: Vec::new(), shared, worker: Some(worker) };
+        if background {
+            lifecycle.registrations.push(native::register_app_state(&lifecycle.shared)?);
+        }
+        if self.suspend {
+            lifecycle.registrations.push(native::register_suspend_resume(&lifecycle.shared)?);
+        }
+        if self.energy_saver {
+            lifecycle.registrations.push(native::register_energy_saver(&lifecycle.shared)?);
+        }
+        Ok(lifecycle)
+    }
+
+    /// Reads the installed manifest of the package, and returns whether the process has package identity.
+    fn check_manifest(&self) -> io::Result<bool> {
+        let (Some(path), Some(app_user_model_id)) = (package_path(), app_user_model_id()) else {
+            return Ok(false);
+        };
+        let manifest = fs::read_to_string(path.join("AppxManifest.xml"))?;
+        let app_id = app_user_model_id.split_once('!').map_or("", |(_, app_id)| app_id);
+        check_manifest(&manifest, app_id).map_err(|message| io::Error::new(io::ErrorKind::InvalidInput, message))?;
+        Ok(true)
+    }
+}
+
+impl Drop for Lifecycle {
+    fn drop(&mut self) {
+        // No callback of Windows runs after its registration is dropped
+        self.registrations.clear();
+        let _ = self.shared.sender.lock().unwrap_or_else(PoisonError::into_inner).send(None);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Shared {
+    fn start(
+        suspend_timeout: Duration,
+        mut callback: impl FnMut(LifecycleEvent) + Send + 'static,
+    ) -> io::Result<(Arc<Shared>, JoinHandle<()>)> {
+        let (sender, receiver) = mpsc::channel::<Option<(LifecycleEvent, u64)>>();
+        let shared = Arc::new(Shared {
+            sender: Mutex::new(sender),
+            posted: AtomicU64::new(0),
+            handled: Mutex::new(0),
+            changed: Condvar::new(),
+            suspend_timeout,
+        });
+        let worker = thread::Builder::new().name("winapp-lifecycle".into()).spawn({
+            let shared = Arc::clone(&shared);
+            move || {
+                while let Ok(Some((event, sequence))) = receiver.recv() {
+                    callback(event);
+                    *shared.handled.lock().unwrap_or_else(PoisonError::into_inner) = sequence;
+                    shared.changed.notify_all();
+                }
+            }
+        })?;
+        Ok((shared, worker))
+    }
+
+    /// Queues an event for the callback; called by the notification callbacks of Windows, which return at once.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn post(&self, event: LifecycleEvent) -> u64 {
+        let sequence = self.posted.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = self.sender.lock().unwrap_or_else(PoisonError::into_inner).send(Some((event, sequence)));
+        sequence
+    }
+
+    /// Queues [`LifecycleEvent::Suspending`] and waits until the callback handled it, or for the suspend timeout.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn post_and_wait(&self, event: LifecycleEvent) {
+        let sequence = self.post(event);
+        let handled = self.handled.lock().unwrap_or_else(PoisonError::into_inner);
+        let _ = self
+            .changed
+            .wait_timeout_while(handled, self.suspend_timeout, |handled| *handled < sequence)
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+}
+
+/// Checks that the application `app_id` of a package manifest runs as a packaged Win32 app, which is what the
+/// notifications are for: its entry point is `Windows.FullTrustApplication` or its `uap10:RuntimeBehavior` is
+/// `packagedClassicApp` or `win32App`, and the package has the `runFullTrust` capability.
+fn check_manifest(manifest: &str, app_id: &str) -> Result<(), String> {
+    let application = elements(manifest, "Application")
+        .find(|application| attribute(application, "Id") == Some(app_id))
+        .ok_or_else(|| format!("the manifest has no Application with Id \"{app_id}\""))?;
+
+    let win32 = attribute(application, "EntryPoint") == Some("Windows.FullTrustApplication")
+        || matches!(attribute(application, "RuntimeBehavior"), Some("packagedClassicApp" | "win32App"));
+    if !win32 {
+        return Err(format!(
+            "Application \"{app_id}\" isn't a packaged Win32 app, so Windows suspends it and reports its lifecycle \
+             through CoreApplication; set EntryPoint=\"Windows.FullTrustApplication\" or uap10:RuntimeBehavior"
+        ));
+    }
+    if !elements(manifest, "Capability").any(|capability| attribute(capability, "Name") == Some("runFullTrust")) {
+        return Err("the manifest is missing the rescap:Capability runFullTrust of packaged Win32 apps".to_owned());
+    }
+    Ok(())
+}
+
+/// The start tags of the elements with a local name, such as `<rescap:Capability Name="runFullTrust"/>` for
+/// `Capability`, without their `<` and `>`.
+fn elements<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    xml.split('<').filter_map(move |tag| {
+        let tag = tag.split('>').next()?;
+        let element = tag.split(|c: char| c.is_whitespace() || c == '/').next()?;
+        (element.rsplit(':').next() == Some(name)).then_some(tag)
+    })
+}
+
+/// The value of an attribute of a start tag by its local name, such as `RuntimeBehavior` for
+/// `uap10:RuntimeBehavior="win32App"`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(equals) = rest.find('=') {
+        let key = rest[..equals].split_whitespace().last().unwrap_or_default();
+        let value = rest[equals + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)? + 1;
+        if key.rsplit(':').next() == Some(name) {
+            return Some(&value[1..end]);
+        }
+        rest = &value[end + 1..];
+    }
+    None
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+    use std::io;
+    use std::sync::Arc;
+
+    use super::{LifecycleEvent, Shared};
+
+    const ERROR_SUCCESS: u32 = 0;
+    const DEVICE_NOTIFY_CALLBACK: u32 = 2;
+    const LOAD_LIBRARY_SEARCH_SYSTEM32: u32 = 0x0000_0800;
+    const PBT_APMSUSPEND: u32 = 0x0004;
+    const PBT_APMRESUMESUSPEND: u32 = 0x0007;
+    const PBT_APMRESUMEAUTOMATIC: u32 = 0x0012;
+    const PBT_POWERSETTINGCHANGE: u32 = 0x8013;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    struct Guid(u32, u16, u16, [u8; 8]);
+
+    const GUID_POWER_SAVING_STATUS: Guid =
+        Guid(0xE009_58C0, 0xC213, 0x4ACE, [0xAC, 0x77, 0xFE, 0xCC, 0xED, 0x2E, 0xEE, 0xA5]);
+
+    type DeviceNotifyCallback = unsafe extern "system" fn(context: *mut c_void, kind: u32, setting: *mut c_void) -> u32;
+    type AppStateChangeRoutine = unsafe extern "system" fn(quiesced: u8, context: *mut c_void);
+    type PsmRegister = unsafe extern "system" fn(AppStateChangeRoutine, *mut c_void, *mut *mut c_void) -> u32;
+    type PsmUnregister = unsafe extern "system" fn(*mut c_void);
+
+    #[repr(C)]
+    struct DeviceNotifySubscribeParameters {
+        callback: DeviceNotifyCallback,
+        context: *mut c_void,
+    }
+
+    #[repr(C)]
+    struct PowerBroadcastSetting {
+        power_setting: Guid,
+        data_length: u32,
+        data: [u8; 1],
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn LoadLibraryExW(name: *const u16, file: *mut c_void, flags: u32) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const u8) -> *mut c_void;
+    }
+
+    #[link(name = "powrprof")]
+    extern "system" {
+        fn PowerRegisterSuspendResumeNotification(flags: u32, recipient: *mut c_void, handle: *mut *mut c_void) -> u32;
+        fn PowerUnregisterSuspendResumeNotification(handle: *mut c_void) -> u32;
+        fn PowerSettingRegisterNotification(
+            setting: *const Guid,
+            flags: u32,
+            recipient: *mut c_void,
+            handle: *mut *mut c_void,
+        ) -> u32;
+        fn PowerSettingUnregisterNotification(handle: *mut c_void) -> u32;
+    }
+
+    /// A registration for notifications, which keeps the [`Shared`] its callback gets alive until it is unregistered.
+    #[derive(Debug)]
+    pub struct Registration {
+        handle: *mut c_void,
+        unregister: Unregister,
+        context: *const Shared,
+        _parameters: Option<Box<DeviceNotifySubscribeParameters>>,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Unregister {
+        Psm(PsmUnregister),
+        SuspendResume,
+        PowerSetting,
+    }
+
+    // The handle and context are only used to unregister
+    unsafe impl Send for Registration {}
+    unsafe impl Sync for Registration {}
+
+    impl std::fmt::Debug for DeviceNotifySubscribeParameters {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("DeviceNotifySubscribeParameters")
+        }
+    }
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            unsafe {
+                match self.unregister {
+                    Unregister::Psm(unregister) => unregister(self.handle),
+                    Unregister::SuspendResume => {
+                        PowerUnregisterSuspendResumeNotification(self.handle);
+                    }
+                    Unregister::PowerSetting => {
+                        PowerSettingUnregisterNotification(self.handle);
+                    }
+                }
+                drop(Arc::from_raw(self.context));
+            }
+        }
+    }
+
+    /// `PsmRegisterAppStateChangeNotification` is in kernel.appcore.dll, which has no import library in the MinGW
+    /// toolchains, so it is looked up when the notifications start.
+    pub fn register_app_state(shared: &Arc<Shared>) -> io::Result<Registration> {
+        let name: Vec<u16> = "kernel.appcore.dll".encode_utf16().chain([0]).collect();
+        let module = unsafe { LoadLibraryExW(name.as_ptr(), std::ptr::null_mut(), LOAD_LIBRARY_SEARCH_SYSTEM32) };
+        if module.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let register = unsafe { GetProcAddress(module, c"PsmRegisterAppStateChangeNotification".as_ptr().cast()) };
+        let unregister = unsafe { GetProcAddress(module, c"PsmUnregisterAppStateChangeNotification".as_ptr().cast()) };
+        if register.is_null() || unregister.is_null() {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "this Windows has no app state notifications"));
+        }
+        let (register, unregister) = unsafe {
+            (std::mem::transmute::<_, PsmRegister>(register), std::mem::transmute::<_, PsmUnregister>(unregister))
+        };
+
+        let context = Arc::into_raw(Arc::clone(shared));
+        let mut handle = std::ptr::null_mut();
+        let result = unsafe { register(on_app_state_change, context as *mut c_void, &mut handle) };
+        registration(result, handle, Unregister::Psm(unregister), context, None)
+    }
+
+    pub fn register_suspend_resume(shared: &Arc<Shared>) -> io::Result<Registration> {
+        let context = Arc::into_raw(Arc::clone(shared));
+        let mut parameters =
+            Box::new(DeviceNotifySubscribeParameters { callback: on_power, context: context as *mut c_void });
+        let mut handle = std::ptr::null_mut();
+        let result = unsafe {
+            PowerRegisterSuspendResumeNotification(
+                DEVICE_NOTIFY_CALLBACK,
+                &mut *parameters as *mut DeviceNotifySubscribeParameters as *mut c_void,
+                &mut handle,
+            )
+        };
+        registration(result, handle, Unregister::SuspendResume, context, Some(parameters))
+    }
+
+    pub fn register_energy_saver(shared: &Arc<Shared>) -> io::Result<Registration> {
+        let context = Arc::into_raw(Arc::clone(shared));
+        let mut parameters =
+            Box::new(DeviceNotifySubscribeParameters { callback: on_power, context: context as *mut c_void });
+        let mut handle = std::ptr::null_mut();
+        let result = unsafe {
+            PowerSettingRegisterNotification(
+                &GUID_POWER_SAVING_STATUS,
+                DEVICE_NOTIFY_CALLBACK,
+                &mut *parameters as *mut DeviceNotifySubscribeParameters as *mut c_void,
+                &mut handle,
+            )
+        };
+        registration(result, handle, Unregister::PowerSetting, context, Some(parameters))
+    }
+
+    fn registration(
+        result: u32,
+        handle: *mut c_void,
+        unregister: Unregister,
+        context: *const Shared,
+        parameters: Option<Box<DeviceNotifySubscribeParameters>>,
+    ) -> io::Result<Registration> {
+        if result != ERROR_SUCCESS {
+            unsafe { drop(Arc::from_raw(context)) };
+            return Err(io::Error::from_raw_os_error(result as i32));
+        }
+        Ok(Registration { handle, unregister, context, _parameters: parameters })
+    }
+
+    unsafe extern "system" fn on_app_state_change(quiesced: u8, context: *mut c_void) {
+        let shared = &*(context as *const Shared);
+        shared.post(if quiesced != 0 { LifecycleEvent::EnteredBackground } else { LifecycleEvent::LeavingBackground });
+    }
+
+    unsafe extern "system" fn on_power(context: *mut c_void, kind: u32, setting: *mut c_void) -> u32 {
+        let shared = &*(context as *const Shared);
+        match kind {
+            PBT_APMSUSPEND => shared.post_and_wait(LifecycleEvent::Suspending),
+            // Automatic resumes come first, and without a user; either one means the system is awake
+            PBT_APMRESUMEAUTOMATIC => {
+                shared.post(LifecycleEvent::Resumed);
+            }
+            PBT_APMRESUMESUSPEND => {}
+            PBT_POWERSETTINGCHANGE if !setting.is_null() => {
+                let setting = &*(setting as *const PowerBroadcastSetting);
+                if setting.power_setting == GUID_POWER_SAVING_STATUS && setting.data_length >= 1 {
+                    let on = setting.data[0] != 0;
+                    shared.post(if on { LifecycleEvent::EnergySaverOn } else { LifecycleEvent::EnergySaverOff });
+                }
+            }
+            _ => {}
+        }
+        ERROR_SUCCESS
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+    use std::sync::Arc;
+
+    use super::Shared;
+
+    #[derive(Debug)]
+    pub enum Registration {}
+
+    pub fn register_app_state(_shared: &Arc<Shared>) -> io::Result<Registration> {
+        Err(unsupported())
+    }
+
+    pub fn register_suspend_resume(_shared: &Arc<Shared>) -> io::Result<Registration> {
+        Err(unsupported())
+    }
+
+    pub fn register_energy_saver(_shared: &Arc<Shared>) -> io::Result<Registration> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "lifecycle notifications need Windows")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"<Package xmlns:uap10="http://schemas.microsoft.com/appx/manifest/uap/windows10/10"
+        xmlns:rescap="http://schemas.microsoft.com/appx/manifest/foundation/windows10/restrictedcapabilities">
+      <Applications>
+        <Application Id="Notes" Executable="notes.exe" EntryPoint="Windows.FullTrustApplication">
+          <uap:VisualElements DisplayName="Notes" />
+        </Application>
+        <Application Id="Viewer" Executable="viewer.exe" uap10:RuntimeBehavior='win32App' uap10:TrustLevel="mediumIL"/>
+        <Application Id="Widget" Executable="widget.exe" EntryPoint="Widget.App"/>
+      </Applications>
+      <Capabilities><rescap:Capability Name="runFullTrust" /></Capabilities>
+    </Package>"#;
+
+    #[test]
+    fn check_manifest_accepts_packaged_win32_apps() {
+        assert_eq!(check_manifest(MANIFEST, "Notes"), Ok(()));
+        assert_eq!(check_manifest(MANIFEST, "Viewer"), Ok(()));
+        assert!(check_manifest(MANIFEST, "Widget").unwrap_err().contains("isn't a packaged Win32 app"));
+        assert!(check_manifest(MANIFEST, "Editor").unwrap_err().contains("no Application"));
+        assert!(check_manifest(&MANIFEST.replace("runFullTrust", "internetClient"), "Notes")
+            .unwrap_err()
+            .contains("runFullTrust"));
+    }
+
+    #[test]
+    fn suspending_waits_for_the_callback() {
+        let (events, received) = mpsc::channel();
+        let (shared, worker) = Shared::start(Duration::from_secs(10), move |event| {
+            thread::sleep(Duration::from_millis(50));
+            events.send(event).unwrap();
+        })
+        .unwrap();
+
+        shared.post(LifecycleEvent::EnergySaverOn);
+        shared.post_and_wait(LifecycleEvent::Suspending);
+        assert_eq!(
+            received.try_iter().collect::<Vec<_>>(),
+            [LifecycleEvent::EnergySaverOn, LifecycleEvent::Suspending]
+        );
+
+        shared.sender.lock().unwrap().send(None).unwrap();
+        worker.join().unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::env;
+use std::io;
+use std::path::Path;
+
+/// Environment variable that overrides the language of the app, which `locale:` `language:` in winapp.yaml sets for
+/// the package.
+pub const LOCALE_VARIABLE: &str = "WINAPP_LOCALE";
+
+/// The UTF-8 code page, which `activeCodePage` UTF-8 in the application manifest makes the process's.
+pub const UTF8_CODE_PAGE: u32 = 65001;
+
+/// The language and code page the process runs with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    /// BCP-47 name of the language, such as `fr-FR`: the one of `WINAPP_LOCALE` when it is set, or the user's.
+    pub name: String,
+    /// Whether `name` comes from `WINAPP_LOCALE` rather than the user's settings.
+    pub overridden: bool,
+    /// The ANSI code page, which the `-A` functions of Windows and the narrow C runtime functions convert text to.
+    pub code_page: u32,
+}
+
+impl Locale {
+    /// Reads the locale of the current process. Fails with [`io::ErrorKind::Unsupported`] on hosts other than
+    /// Windows.
+    pub fn current() -> io::Result<Self> {
+        let code_page = native::code_page()?;
+        let (name, overridden) = match override_name(env::var(LOCALE_VARIABLE).ok()) {
+            Some(name) => (name, true),
+            None => (native::user_locale_name()?, false),
+        };
+        Ok(Locale { name, overridden, code_page })
+    }
+
+    /// Whether the code page is UTF-8, so ANSI functions take any text.
+    pub fn is_utf8(&self) -> bool {
+        self.code_page == UTF8_CODE_PAGE
+    }
+
+    /// Fails with [`io::ErrorKind::InvalidInput`] for a path outside ASCII while the code page isn't UTF-8, since
+    /// ANSI functions and `fopen` may not open it, as a check before passing paths to C libraries.
+    pub fn check_path(&self, path: &Path) -> io::Result<()> {
+        if self.is_utf8() || path.as_os_str().to_str().is_some_and(|path| path.is_ascii()) {
+            return Ok(());
+        }
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} is outside ASCII and the code page is {}, so ANSI functions may not open it; set activeCodePage \
+                 UTF-8 in the application manifest",
+                path.display(),
+                self.code_page
+            ),
+        ))
+    }
+}
+
+fn override_name(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_owned()).filter(|value| !value.is_empty())
+}
+
+#[cfg(windows)]
+mod native {
+    use std::io;
+
+    // LOCALE_NAME_MAX_LENGTH
+    const LOCALE_NAME_LENGTH: usize = 85;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetACP() -> u32;
+        fn GetUserDefaultLocaleName(name: *mut u16, length: i32) -> i32;
+    }
+
+    pub fn code_page() -> io::Result<u32> {
+        Ok(unsafe { GetACP() })
+    }
+
+    pub fn user_locale_name() -> io::Result<String> {
+        let mut name = [0u16; LOCALE_NAME_LENGTH];
+        let length = unsafe { GetUserDefaultLocaleName(name.as_mut_ptr(), name.len() as i32) };
+        if length == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // The length counts the terminating null
+        Ok(String::from_utf16_lossy(&name[..length as usize - 1]))
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+
+    pub fn code_page() -> io::Result<u32> {
+        Err(unsupported())
+    }
+
+    pub fn user_locale_name() -> io::Result<String> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "the locale and code page need Windows")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_name_ignores_blank_values() {
+        assert_eq!(override_name(Some(" fr-FR ".into())), Some("fr-FR".into()));
+        assert_eq!(override_name(Some("  ".into())), None);
+        assert_eq!(override_name(None), None);
+    }
+
+    #[test]
+    fn check_path_rejects_non_ascii_paths_without_utf8() {
+        let legacy = Locale { name: "ja-JP".into(), overridden: false, code_page: 932 };
+        let utf8 = Locale { code_page: UTF8_CODE_PAGE, ..legacy.clone() };
+
+        assert!(legacy.check_path(Path::new("notes/todo.md")).is_ok());
+        let error = legacy.check_path(Path::new("notes/résumé.md")).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(utf8.check_path(Path::new("notes/résumé.md")).is_ok());
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use crate::app_data_dir;
+
+/// Environment variable that overrides the log folder, e.g. to log to the console's folder while debugging.
+pub const LOG_DIR_VARIABLE: &str = "WINAPP_LOG_DIR";
+
+/// The folder of [`app_data_dir`] that logs are written to, and that `winapp logs tail` follows.
+pub const LOGS_FOLDER: &str = "Logs";
+
+/// The folder for the logs of `app`: the folder of `WINAPP_LOG_DIR` when it is set, or `Logs` in its
+/// [`app_data_dir`], which is `ApplicationData.Current.LocalFolder\Logs` when the process has package identity.
+pub fn log_dir(app: &str) -> PathBuf {
+    match env::var_os(LOG_DIR_VARIABLE).filter(|dir| !dir.is_empty()) {
+        Some(dir) => dir.into(),
+        None => app_data_dir(app).join(LOGS_FOLDER),
+    }
+}
+
+/// A log file that moves to `<name>.1.log` when the next write would take it over its size limit, keeping a few
+/// older files.
+///
+/// Writes from several threads are serialized, and `&RollingFile` implements [`Write`] too, so an
+/// `Arc<RollingFile>` can be shared, or passed to `tracing_subscriber::fmt().with_writer`.
+#[derive(Debug)]
+pub struct RollingFile {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    file: Option<File>,
+    size: u64,
+}
+
+/// Settings of a [`RollingFile`].
+#[derive(Debug, Clone)]
+pub struct RollingFileBuilder {
+    name: String,
+    dir: Option<PathBuf>,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl RollingFile {
+    /// The size at which a log file rotates, unless [`RollingFileBuilder::max_size`] changes it: 10 MB.
+    pub const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+    /// The number of log files kept, the current one included, unless [`RollingFileBuilder::max_files`] changes it.
+    pub const DEFAULT_MAX_FILES: usize = 5;
+
+    /// Settings for the log `<name>.log` in the [`log_dir`] of `name`.
+    pub fn builder(name: impl Into<String>) -> RollingFileBuilder {
+        RollingFileBuilder {
+            name: name.into(),
+            dir: None,
+            max_size: Self::DEFAULT_MAX_SIZE,
+            max_files: Self::DEFAULT_MAX_FILES,
+        }
+    }
+
+    /// The path of the current log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open_file(&self, size: &mut u64) -> io::Result<File> {
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        *size = file.metadata()?.len();
+        Ok(file)
+    }
+
+    fn rotate(&self, state: &mut State) -> io::Result<()> {
+        // Windows can't rename a file that is open
+        state.file = None;
+        state.size = 0;
+        if self.max_files <= 1 {
+            return remove_if_exists(&self.path);
+        }
+
+        remove_if_exists(&rotated_path(&self.path, self.max_files - 1))?;
+        for index in (1..self.max_files - 1).rev() {
+            rename_if_exists(&rotated_path(&self.path, index), &rotated_path(&self.path, index + 1))?;
+        }
+        rename_if_exists(&self.path, &rotated_path(&self.path, 1))
+    }
+}
+
+impl RollingFileBuilder {
+    /// The folder of the log (default: [`log_dir`] of the name).
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// The size in bytes at which the log rotates (default: [`RollingFile::DEFAULT_MAX_SIZE`]).
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// The number of log files to keep, the current one included (default: [`RollingFile::DEFAULT_MAX_FILES`]).
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
+    /// Creates the folder and opens the log for appending.
+    pub fn open(self) -> io::Result<RollingFile> {
+        let dir = self.dir.unwrap_or_else(|| log_dir(&self.name));
+        fs::create_dir_all(&dir)?;
+        let log = RollingFile {
+            path: dir.join(format!("{}.log", self.name)),
+            max_size: self.max_size,
+            max_files: self.max_files,
+            state: Mutex::new(State { file: None, size: 0 }),
+        };
+        {
+            let mut state = log.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let state = &mut *state;
+            state.file = Some(log.open_file(&mut state.size)?);
+        }
+        Ok(log)
+    }
+}
+
+impl Write for &RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let state = &mut *state;
+        if state.size > 0 && state.size + buf.len() as u64 > self.max_size {
+            self.rotate(state)?;
+        }
+        let file = match state.file.take() {
+            Some(file) => file,
+            None => self.open_file(&mut state.size)?,
+        };
+        let written = state.file.insert(file).write(buf)?;
+        state.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match &mut state.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+
+/// `notes.log` with the index of an older file: `notes.2.log`.
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    match path.extension() {
+        Some(extension) => path.with_file_name(format!("{stem}.{index}.{}", extension.to_string_lossy())),
+        None => path.with_file_name(format!("{stem}.{index}")),
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_dir_is_in_the_app_data_dir() {
+        if env::var_os(LOG_DIR_VARIABLE).is_none() {
+            assert_eq!(log_dir("notes"), app_data_dir("notes").join(LOGS_FOLDER));
+        }
+        assert_eq!(rotated_path(Path::new("Logs/notes.log"), 2), Path::new("Logs/notes.2.log"));
+    }
+
+    #[test]
+    fn write_rotates_and_drops_the_oldest_file() {
+        let dir = env::temp_dir().join(format!("winapp-runtime-{}", std::process::id()));
+        let log = RollingFile::builder("notes").dir(&dir).max_size(16).max_files(3).open().unwrap();
+
+        for line in ["first line\n", "second line\n", "third line\n", "fourth line\n"] {
+            (&log).write_all(line.as_bytes()).unwrap();
+        }
+        (&log).flush().unwrap();
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("notes.log"), "fourth line\n");
+        assert_eq!(read("notes.1.log"), "third line\n");
+        assert_eq!(read("notes.2.log"), "second line\n");
+        assert!(!dir.join("notes.3.log").exists());
+        drop(log);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
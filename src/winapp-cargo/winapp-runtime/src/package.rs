@@ -0,0 +1,160 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The folder an app keeps its files in:
+///
+/// - `%LOCALAPPDATA%\Packages\<family name>\LocalState`, the package's `ApplicationData.Current.LocalFolder`, when
+///   the process has package identity, including the debug identity of `cargo winapp build`;
+/// - `%LOCALAPPDATA%\<app>` otherwise.
+pub fn app_data_dir(app: &str) -> PathBuf {
+    let local_app_data = env::var_os("LOCALAPPDATA").map(PathBuf::from).unwrap_or_else(env::temp_dir);
+    app_data_dir_in(&local_app_data, package_family_name().as_deref(), app)
+}
+
+pub(crate) fn app_data_dir_in(local_app_data: &Path, family_name: Option<&str>, app: &str) -> PathBuf {
+    match family_name {
+        Some(family_name) => local_app_data.join("Packages").join(family_name).join("LocalState"),
+        None => local_app_data.join(app),
+    }
+}
+
+/// The package family name of the current process, or `None` when it has no package identity.
+#[cfg(windows)]
+pub fn package_family_name() -> Option<String> {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentPackageFamilyName(length: *mut u32, name: *mut u16) -> i32;
+    }
+
+    current_package_string(GetCurrentPackageFamilyName)
+}
+
+/// The package family name of the current process; always `None` on hosts other than Windows.
+#[cfg(not(windows))]
+pub fn package_family_name() -> Option<String> {
+    None
+}
+
+/// The package full name (`<name>_<version>_<architecture>_<resource id>_<publisher id>`) of the current process,
+/// or `None` when it has no package identity.
+#[cfg(windows)]
+pub fn package_full_name() -> Option<String> {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentPackageFullName(length: *mut u32, name: *mut u16) -> i32;
+    }
+
+    current_package_string(GetCurrentPackageFullName)
+}
+
+/// The package full name of the current process; always `None` on hosts other than Windows.
+#[cfg(not(windows))]
+pub fn package_full_name() -> Option<String> {
+    None
+}
+
+/// The parts of a package full name that the runtime reports or keys on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct PackageFullNameParts<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub architecture: &'a str,
+    pub publisher_id: &'a str,
+}
+
+/// Splits a package full name into its parts, or `None` when it doesn't have five. Names and versions have no
+/// underscores; the resource id is usually empty.
+pub(crate) fn split_package_full_name(full_name: &str) -> Option<PackageFullNameParts<'_>> {
+    match full_name.split('_').collect::<Vec<_>>()[..] {
+        [name, version, architecture, _, publisher_id] => {
+            Some(PackageFullNameParts { name, version, architecture, publisher_id })
+        }
+        _ => None,
+    }
+}
+
+/// The folder the package of the current process is installed in, which has its `AppxManifest.xml`, or `None` when it
+/// has no package identity.
+#[cfg(windows)]
+pub(crate) fn package_path() -> Option<PathBuf> {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentPackagePath(length: *mut u32, path: *mut u16) -> i32;
+    }
+
+    current_package_string(GetCurrentPackagePath).map(PathBuf::from)
+}
+
+#[cfg(not(windows))]
+pub(crate) fn package_path() -> Option<PathBuf> {
+    None
+}
+
+/// The application user model id (`<family name>!<app id>`) of the current process, which toasts are shown for, or
+/// `None` when it has no package identity.
+#[cfg(windows)]
+pub fn app_user_model_id() -> Option<String> {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentApplicationUserModelId(length: *mut u32, id: *mut u16) -> i32;
+    }
+
+    current_package_string(GetCurrentApplicationUserModelId)
+}
+
+/// The application user model id of the current process; always `None` on hosts other than Windows.
+#[cfg(not(windows))]
+pub fn app_user_model_id() -> Option<String> {
+    None
+}
+
+/// Calls one of the `GetCurrentPackage*` functions, which take the length of the buffer and a buffer for the string.
+#[cfg(windows)]
+fn current_package_string(get: unsafe extern "system" fn(*mut u32, *mut u16) -> i32) -> Option<String> {
+    const ERROR_INSUFFICIENT_BUFFER: i32 = 122;
+
+    // Unpackaged processes get APPMODEL_ERROR_NO_PACKAGE instead of the length of the string.
+    let mut length = 0u32;
+    if unsafe { get(&mut length, std::ptr::null_mut()) } != ERROR_INSUFFICIENT_BUFFER {
+        return None;
+    }
+    let mut value = vec![0u16; length as usize];
+    if unsafe { get(&mut length, value.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    value.truncate(length.saturating_sub(1) as usize);
+    Some(String::from_utf16_lossy(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_data_dir_uses_the_local_folder_of_the_package() {
+        let local_app_data = Path::new("C:/Users/dev/AppData/Local");
+
+        assert_eq!(
+            app_data_dir_in(local_app_data, Some("Contoso.Notes_8wekyb3d8bbwe"), "notes"),
+            local_app_data.join("Packages/Contoso.Notes_8wekyb3d8bbwe/LocalState")
+        );
+        assert_eq!(app_data_dir_in(local_app_data, None, "notes"), local_app_data.join("notes"));
+    }
+
+    #[test]
+    fn split_package_full_name_reads_each_part() {
+        assert_eq!(
+            split_package_full_name("Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe"),
+            Some(PackageFullNameParts {
+                name: "Contoso.Notes",
+                version: "1.2.0.0",
+                architecture: "x64",
+                publisher_id: "8wekyb3d8bbwe"
+            })
+        );
+        assert_eq!(split_package_full_name("Contoso.Notes_8wekyb3d8bbwe"), None);
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::SystemTime;
+
+use crate::crash::report_file_name;
+use crate::http::percent_encode;
+use crate::{app_data_dir, app_user_model_id, BuildInfo};
+
+/// The folder of [`app_data_dir`] that panic reports are written to.
+pub const PANIC_REPORTS_FOLDER: &str = "PanicReports";
+
+/// The folder for the panic reports of `app`: `PanicReports` in its [`app_data_dir`].
+pub fn panic_report_dir(app: &str) -> PathBuf {
+    app_data_dir(app).join(PANIC_REPORTS_FOLDER)
+}
+
+/// Writes a text report of each panic, with its backtrace, and can ask the user to send it.
+///
+/// ```no_run
+/// winapp_runtime::PanicReporter::new("notes").prompt("notes:send-report?file={report}").install()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Reports are named `<app>-<unix time>-<process id>.txt`. When the package was packed with `--provenance`, the paths
+/// of the build machine and of the install folder in the report are rewritten to paths relative to the source root,
+/// with [`BuildInfo::source_relative`], and the report names the commit.
+#[derive(Debug, Clone)]
+pub struct PanicReporter {
+    app: String,
+    dir: Option<PathBuf>,
+    send_uri: Option<String>,
+}
+
+/// What the installed hook needs; set once, by the first [`PanicReporter::install`].
+static REPORTER: OnceLock<ReportTarget> = OnceLock::new();
+
+#[derive(Debug)]
+struct ReportTarget {
+    app: String,
+    dir: PathBuf,
+    send_uri: Option<String>,
+    build_info: Option<BuildInfo>,
+}
+
+impl PanicReporter {
+    pub fn new(app: impl Into<String>) -> Self {
+        PanicReporter { app: app.into(), dir: None, send_uri: None }
+    }
+
+    /// The folder of the reports (default: [`panic_report_dir`] of the app).
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Shows a toast after a report is written, whose "Send report" button launches `send_uri` with `{report}`
+    /// replaced by the percent-encoded path of the report, such as a protocol of the app that uploads it on the next
+    /// start. Toasts need package identity, so unpackaged processes only write the report.
+    pub fn prompt(mut self, send_uri: impl Into<String>) -> Self {
+        self.send_uri = Some(send_uri.into());
+        self
+    }
+
+    /// Creates the folder, reads the provenance stamp and installs the panic hook, and returns the folder. The hook
+    /// that was set before runs after the report is written.
+    ///
+    /// Fails with [`io::ErrorKind::AlreadyExists`] when the hook was installed before.
+    pub fn install(self) -> io::Result<PathBuf> {
+        let dir = self.dir.unwrap_or_else(|| panic_report_dir(&self.app));
+        fs::create_dir_all(&dir)?;
+        let target =
+            ReportTarget { app: self.app, dir: dir.clone(), send_uri: self.send_uri, build_info: BuildInfo::current() };
+        REPORTER
+            .set(target)
+            .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "the panic hook is already installed"))?;
+
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if let Some(target) = REPORTER.get() {
+                let location = info.location().map(|location| location.to_string());
+                match target.write_report(info.payload(), location.as_deref()) {
+                    Ok(report) => target.prompt(&report),
+                    Err(error) => {
+                        eprintln!("winapp-runtime: couldn't write a panic report to {}: {error}", target.dir.display())
+                    }
+                }
+            }
+            previous(info);
+        }));
+        Ok(dir)
+    }
+}
+
+impl ReportTarget {
+    fn write_report(&self, payload: &(dyn Any + Send), location: Option<&str>) -> io::Result<PathBuf> {
+        let thread = thread::current();
+        let report = format_report(&Panic {
+            app: &self.app,
+            message: panic_message(payload),
+            location,
+            thread: thread.name().unwrap_or("<unnamed>"),
+            backtrace: &Backtrace::force_capture().to_string(),
+            build_info: self.build_info.as_ref(),
+        });
+
+        let path = self.dir.join(report_file_name(&self.app, SystemTime::now(), process::id(), "txt"));
+        // Threads that panic in the same second share the file
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.write_all(report.as_bytes())?;
+        Ok(path)
+    }
+
+    fn prompt(&self, report: &Path) {
+        let (Some(send_uri), Some(app_user_model_id)) = (&self.send_uri, app_user_model_id()) else {
+            return;
+        };
+        let uri = send_uri.replace("{report}", &percent_encode(&report.to_string_lossy()));
+        if let Err(error) = native::show_toast(&app_user_model_id, &toast_xml(&self.app, &uri)) {
+            eprintln!("winapp-runtime: couldn't show the panic report toast: {error}");
+        }
+    }
+}
+
+struct Panic<'a> {
+    app: &'a str,
+    message: &'a str,
+    location: Option<&'a str>,
+    thread: &'a str,
+    backtrace: &'a str,
+    build_info: Option<&'a BuildInfo>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+fn format_report(panic: &Panic) -> String {
+    let mut report = format!("{} panicked", panic.app);
+    if let Some(location) = panic.location {
+        report.push_str(&format!(" at {location}"));
+    }
+    report.push_str(&format!(" on thread '{}':\n{}\n\n", panic.thread, panic.message));
+    if let Some(build_info) = panic.build_info {
+        report.push_str(&format!("Build: {}\n\n", build_info.summary()));
+    }
+    report.push_str(&format!("Backtrace:\n{}\n", panic.backtrace.trim_end()));
+
+    match panic.build_info {
+        Some(build_info) => build_info.source_relative(&report),
+        None => report,
+    }
+}
+
+fn toast_xml(app: &str, uri: &str) -> String {
+    format!(
+        concat!(
+            r#"<toast activationType="protocol" launch="{uri}"><visual><binding template="ToastGeneric">"#,
+            r#"<text>{app} closed unexpectedly</text><text>Send the report to help fix the problem.</text>"#,
+            r#"</binding></visual><actions>"#,
+            r#"<action content="Send report" activationType="protocol" arguments="{uri}"/></actions></toast>"#,
+        ),
+        app = xml_escape(app),
+        uri = xml_escape(uri),
+    )
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(windows)]
+mod native {
+    use std::io;
+    use std::os::windows::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    // Windows PowerShell projects the WinRT toast API, which would take COM activation to call from Rust directly
+    const SHOW_TOAST: &str = concat!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null;",
+        "[Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom, ContentType = WindowsRuntime] | Out-Null;",
+        "$xml = New-Object Windows.Data.Xml.Dom.XmlDocument;",
+        "$xml.LoadXml($env:WINAPP_TOAST_XML);",
+        "[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier($env:WINAPP_TOAST_APP)",
+        ".Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+    );
+
+    /// Starts PowerShell to show the toast and doesn't wait for it, since the process is about to end.
+    pub fn show_toast(app_user_model_id: &str, xml: &str) -> io::Result<()> {
+        Command::new("powershell.exe")
+            .args(["-NoProfile", "-NonInteractive", "-Command", SHOW_TOAST])
+            .env("WINAPP_TOAST_APP", app_user_model_id)
+            .env("WINAPP_TOAST_XML", xml)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+            .map(drop)
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+
+    pub fn show_toast(_app_user_model_id: &str, _xml: &str) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "toasts can only be shown on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_report_uses_source_relative_paths() {
+        let build_info = BuildInfo {
+            git_sha: Some("3f9c2d0e5b1a7c4f8e2d6b0a9c3e5f7a1b2c4d6e".into()),
+            source_root: Some(r"D:\a\notes".into()),
+            install_dir: PathBuf::from(r"C:\Program Files\WindowsApps\Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe"),
+            ..BuildInfo::default()
+        };
+        let panic = Panic {
+            app: "notes",
+            message: "index out of bounds: the len is 3 but the index is 7",
+            location: Some(r"D:\a\notes\src\sync.rs:42:17"),
+            thread: "sync",
+            backtrace: "   4: notes::sync::pull\n             at D:\\a\\notes\\src\\sync.rs:42\n",
+            build_info: Some(&build_info),
+        };
+
+        let report = format_report(&panic);
+
+        assert!(
+            report.starts_with("notes panicked at src\\sync.rs:42:17 on thread 'sync':\nindex out of bounds"),
+            "{report}"
+        );
+        assert!(report.contains("Build: commit 3f9c2d0e5b1a\n"), "{report}");
+        assert!(report.contains("Backtrace:\n   4: notes::sync::pull\n             at src\\sync.rs:42\n"), "{report}");
+    }
+
+    #[test]
+    fn panic_message_reads_str_and_string_payloads() {
+        assert_eq!(panic_message(&"static"), "static");
+        assert_eq!(panic_message(&String::from("formatted 7")), "formatted 7");
+        assert_eq!(panic_message(&7), "Box<dyn Any>");
+    }
+
+    #[test]
+    fn toast_launches_the_send_uri_with_the_report() {
+        let report =
+            r"C:\Users\dev\AppData\Local\Packages\Contoso.Notes_8wekyb3d8bbwe\LocalState\PanicReports\notes-1.txt";
+        let uri = "notes:send-report?app=notes&file={report}".replace("{report}", &percent_encode(report));
+
+        let xml = toast_xml("Notes <beta>", &uri);
+
+        assert!(uri.ends_with("file=C%3A%5CUsers%5Cdev%5CAppData%5CLocal%5CPackages%5CContoso.Notes_8wekyb3d8bbwe%5CLocalState%5CPanicReports%5Cnotes-1.txt"));
+        assert!(xml.contains(r#"launch="notes:send-report?app=notes&amp;file=C%3A%5CUsers"#), "{xml}");
+        assert!(xml.contains("<text>Notes &lt;beta&gt; closed unexpectedly</text>"), "{xml}");
+        assert!(xml.contains(r#"<action content="Send report" activationType="protocol" arguments="notes:send-report?app=notes&amp;file="#));
+    }
+}
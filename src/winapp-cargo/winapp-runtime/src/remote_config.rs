@@ -0,0 +1,478 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::http::{self, Url};
+use crate::package::split_package_full_name;
+use crate::telemetry::random_hex;
+use crate::{app_data_dir, package_full_name};
+
+/// The folder of [`app_data_dir`] that fetched configuration documents are cached in.
+pub const REMOTE_CONFIG_FOLDER: &str = "RemoteConfig";
+
+/// The channel a [`RemoteConfig`] is evaluated for unless [`RemoteConfigBuilder::channel`] changes it.
+pub const DEFAULT_CHANNEL: &str = "stable";
+
+/// The newest `schemaVersion` of the configuration document that this crate, and `winapp remote-config validate`,
+/// understand.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// The longest configuration document that is downloaded.
+const MAX_DOCUMENT_LEN: usize = 4 * 1024 * 1024;
+
+/// The folder that the configuration documents of `app` are cached in: `RemoteConfig` in its [`app_data_dir`].
+pub fn remote_config_dir(app: &str) -> PathBuf {
+    app_data_dir(app).join(REMOTE_CONFIG_FOLDER)
+}
+
+/// Feature flags and settings from a JSON document that the app's team publishes, evaluated for the channel and
+/// version of the app and a random id of the install.
+///
+/// The document has a default value for each flag and rules that change it for some channels, versions or a
+/// percentage of installs:
+///
+/// ```json
+/// {
+///   "schemaVersion": 1,
+///   "flags": {
+///     "new-editor": {
+///       "description": "The rewritten editor",
+///       "default": false,
+///       "rules": [
+///         { "channels": ["beta"], "value": true },
+///         { "minVersion": "1.4.0.0", "rollout": 25, "value": true }
+///       ]
+///     }
+///   }
+/// }
+/// ```
+///
+/// The first rule that matches gives the value. `winapp remote-config validate` checks a document before it is
+/// published.
+#[derive(Debug, Clone)]
+pub struct RemoteConfig {
+    values: BTreeMap<String, Value>,
+    source: ConfigSource,
+}
+
+/// Where the document of a [`RemoteConfig`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The document was downloaded just now.
+    Network,
+    /// The download failed, and the document is the last one downloaded for the same channel and version.
+    Cache,
+}
+
+/// Settings of a [`RemoteConfig`].
+#[derive(Debug, Clone)]
+pub struct RemoteConfigBuilder {
+    app: String,
+    url: String,
+    channel: String,
+    version: Option<String>,
+    dir: Option<PathBuf>,
+    headers: Vec<(String, String)>,
+}
+
+impl RemoteConfig {
+    /// Settings for the document at `url`, cached in the [`remote_config_dir`] of `app`.
+    pub fn builder(app: impl Into<String>, url: impl Into<String>) -> RemoteConfigBuilder {
+        RemoteConfigBuilder {
+            app: app.into(),
+            url: url.into(),
+            channel: DEFAULT_CHANNEL.to_owned(),
+            version: None,
+            dir: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// The value of a flag, or `None` when the document has no such flag.
+    pub fn get(&self, flag: &str) -> Option<&Value> {
+        self.values.get(flag)
+    }
+
+    /// Whether a flag is `true`; `false` when it is missing or isn't a boolean.
+    pub fn is_enabled(&self, flag: &str) -> bool {
+        self.get(flag).and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    /// The value of a flag when it is a string.
+    pub fn string(&self, flag: &str) -> Option<&str> {
+        self.get(flag).and_then(Value::as_str)
+    }
+
+    /// The value of a flag when it is a number.
+    pub fn number(&self, flag: &str) -> Option<f64> {
+        self.get(flag).and_then(Value::as_f64)
+    }
+
+    /// Whether the document was downloaded or read from the cache.
+    pub fn source(&self) -> ConfigSource {
+        self.source
+    }
+}
+
+impl RemoteConfigBuilder {
+    /// The release channel the app was built for, such as one of the `matrix.channels` of its winapp.yaml.
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+
+    /// The version rules compare with `minVersion` and `maxVersion`, instead of the version of the package, which
+    /// unpackaged processes don't have.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Caches documents in `dir` instead of the [`remote_config_dir`] of the app.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Sends a header with the request, such as an API key.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Downloads the document, caches it and evaluates it.
+    ///
+    /// When the download fails, or the document isn't valid, the cached document of the same channel and version is
+    /// evaluated instead, so the app starts offline with the flags it had last. Fails with the error of the download
+    /// when there is no such document.
+    pub fn fetch(self) -> io::Result<RemoteConfig> {
+        let dir = self.cache_dir();
+        fs::create_dir_all(&dir)?;
+        let cache = dir.join(self.cache_file_name());
+
+        let downloaded = self.download().and_then(|text| Document::parse(&text).map(|document| (text, document)));
+        let (document, source) = match downloaded {
+            Ok((text, document)) => {
+                // A cache that can't be written only costs the offline start
+                let _ = fs::write(&cache, text);
+                (document, ConfigSource::Network)
+            }
+            Err(error) => match read_document(&cache) {
+                Ok(document) => (document, ConfigSource::Cache),
+                Err(_) => return Err(error),
+            },
+        };
+        Ok(RemoteConfig { values: document.evaluate(&self.context(&dir)?), source })
+    }
+
+    /// Evaluates the cached document without downloading it, e.g. to start with the flags of the last run and fetch
+    /// on another thread. Fails with [`io::ErrorKind::NotFound`] when none was cached for the channel and version.
+    pub fn cached(self) -> io::Result<RemoteConfig> {
+        let dir = self.cache_dir();
+        let document = read_document(&dir.join(self.cache_file_name()))?;
+        fs::create_dir_all(&dir)?;
+        Ok(RemoteConfig { values: document.evaluate(&self.context(&dir)?), source: ConfigSource::Cache })
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(|| remote_config_dir(&self.app))
+    }
+
+    fn app_version(&self) -> Option<String> {
+        self.version.clone().or_else(|| Some(split_package_full_name(&package_full_name()?)?.version.to_owned()))
+    }
+
+    /// `<channel>-<version>.json`, so that an update starts with the flags of its own version rather than those of
+    /// the one it replaced.
+    fn cache_file_name(&self) -> String {
+        let version = self.app_version().unwrap_or_else(|| "unpackaged".to_owned());
+        let name = format!("{}-{version}", self.channel);
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+            .collect();
+        format!("{name}.json")
+    }
+
+    fn download(&self) -> io::Result<String> {
+        let url = Url::parse(&self.url)?;
+        let mut headers = vec![("Accept".to_owned(), "application/json".to_owned())];
+        headers.extend(self.headers.iter().cloned());
+        let response = http::send("GET", &url, &headers, &[], MAX_DOCUMENT_LEN)?;
+        if !(200..300).contains(&response.status) {
+            return Err(io::Error::other(format!("{} answered HTTP {}", self.url, response.status)));
+        }
+        String::from_utf8(response.body).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn context(&self, dir: &Path) -> io::Result<Context> {
+        Ok(Context {
+            channel: self.channel.clone(),
+            version: self.app_version().as_deref().and_then(Version::parse),
+            install_id: install_id(dir)?,
+        })
+    }
+}
+
+/// A random id, kept in `install-id` next to the cached documents, that puts an install in the same percentage of
+/// each rollout every time the app starts.
+fn install_id(dir: &Path) -> io::Result<String> {
+    let path = dir.join("install-id");
+    match fs::read_to_string(&path) {
+        Ok(id) if !id.trim().is_empty() => Ok(id.trim().to_owned()),
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => {
+            let id = random_hex(16);
+            fs::write(&path, &id)?;
+            Ok(id)
+        }
+    }
+}
+
+fn read_document(path: &Path) -> io::Result<Document> {
+    Document::parse(&fs::read_to_string(path)?)
+}
+
+/// What rules are matched with.
+#[derive(Debug)]
+struct Context {
+    channel: String,
+    version: Option<Version>,
+    install_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Document {
+    flags: Vec<(String, Flag)>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Flag {
+    default: Value,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Rule {
+    channels: Option<Vec<String>>,
+    min_version: Option<Version>,
+    max_version: Option<Version>,
+    rollout: Option<f64>,
+    value: Value,
+}
+
+/// Up to four numeric parts, compared as `1.2` == `1.2.0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version([u32; 4]);
+
+impl Version {
+    fn parse(version: &str) -> Option<Version> {
+        let mut parts = [0; 4];
+        for (index, part) in version.trim().split('.').enumerate() {
+            *parts.get_mut(index)? = part.parse().ok()?;
+        }
+        Some(Version(parts))
+    }
+}
+
+impl Document {
+    /// Reads the parts of a document that evaluation needs. `winapp remote-config validate` checks the rest, such as
+    /// unknown properties; this only fails when the document can't be evaluated.
+    fn parse(text: &str) -> io::Result<Document> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let json: Value = serde_json::from_str(text).map_err(|error| invalid(error.to_string()))?;
+        match json.get("schemaVersion").and_then(Value::as_u64) {
+            Some(version) if version > SCHEMA_VERSION => {
+                return Err(invalid(format!("schemaVersion {version} is newer than {SCHEMA_VERSION}")))
+            }
+            Some(_) => {}
+            None => return Err(invalid("expected a schemaVersion".into())),
+        }
+        let flags = json.get("flags").and_then(Value::as_object).ok_or_else(|| invalid("expected flags".into()))?;
+
+        let mut document = Document { flags: Vec::with_capacity(flags.len()) };
+        for (name, flag) in flags {
+            let flag = Flag::parse(flag).ok_or_else(|| invalid(format!("flag {name} has no default or bad rules")))?;
+            document.flags.push((name.clone(), flag));
+        }
+        Ok(document)
+    }
+
+    fn evaluate(&self, context: &Context) -> BTreeMap<String, Value> {
+        self.flags
+            .iter()
+            .map(|(name, flag)| {
+                let rule = flag.rules.iter().find(|rule| rule.matches(name, context));
+                (name.clone(), rule.map_or(&flag.default, |rule| &rule.value).clone())
+            })
+            .collect()
+    }
+}
+
+impl Flag {
+    fn parse(flag: &Value) -> Option<Flag> {
+        let flag = flag.as_object()?;
+        let rules = match flag.get("rules") {
+            Some(rules) => {
+                rules.as_array()?.iter().map(|rule| Rule::parse(rule.as_object()?)).collect::<Option<_>>()?
+            }
+            None => Vec::new(),
+        };
+        Some(Flag { default: flag.get("default")?.clone(), rules })
+    }
+}
+
+impl Rule {
+    fn parse(rule: &Map<String, Value>) -> Option<Rule> {
+        let version = |key: &str| match rule.get(key) {
+            Some(version) => Version::parse(version.as_str()?).map(Some),
+            None => Some(None),
+        };
+        let channels = match rule.get("channels") {
+            Some(channels) => Some(
+                channels
+                    .as_array()?
+                    .iter()
+                    .map(|channel| channel.as_str().map(str::to_owned))
+                    .collect::<Option<_>>()?,
+            ),
+            None => None,
+        };
+        let rollout = match rule.get("rollout") {
+            Some(rollout) => Some(rollout.as_f64()?),
+            None => None,
+        };
+        Some(Rule {
+            channels,
+            min_version: version("minVersion")?,
+            max_version: version("maxVersion")?,
+            rollout,
+            value: rule.get("value")?.clone(),
+        })
+    }
+
+    fn matches(&self, flag: &str, context: &Context) -> bool {
+        if let Some(channels) = &self.channels {
+            if !channels.iter().any(|channel| channel.eq_ignore_ascii_case(&context.channel)) {
+                return false;
+            }
+        }
+        // Rules for some versions don't match apps whose version isn't known
+        let compare = |bound: Option<Version>, rejected: Ordering| match (bound, context.version) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(bound), Some(version)) => version.cmp(&bound) != rejected,
+        };
+        if !compare(self.min_version, Ordering::Less) || !compare(self.max_version, Ordering::Greater) {
+            return false;
+        }
+        match self.rollout {
+            Some(rollout) => f64::from(bucket(&context.install_id, flag)) < rollout * 100.0,
+            None => true,
+        }
+    }
+}
+
+/// The place of an install in the rollouts of a flag, from 0 to 9999: an FNV-1a hash of the install id and the flag,
+/// so that a 10% rollout reaches the same installs each time, and other flags reach other installs.
+fn bucket(install_id: &str, flag: &str) -> u32 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in install_id.bytes().chain([b':']).chain(flag.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    (hash % 10_000) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::env;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const DOCUMENT: &str = r#"{
+        "schemaVersion": 1,
+        "flags": {
+            "new-editor": {
+                "default": false,
+                "rules": [
+                    { "channels": ["beta"], "value": true },
+                    { "minVersion": "2.0", "maxVersion": "2.9", "value": true }
+                ]
+            },
+            "theme": { "default": "light", "rules": [{ "rollout": 100, "value": "dark" }] },
+            "upload-limit": { "default": 10, "rules": [{ "rollout": 0, "value": 50 }] }
+        }
+    }"#;
+
+    fn context(channel: &str, version: Option<&str>) -> Context {
+        Context { channel: channel.into(), version: version.and_then(Version::parse), install_id: "0123".into() }
+    }
+
+    #[test]
+    fn evaluate_takes_the_value_of_the_first_matching_rule() {
+        let document = Document::parse(DOCUMENT).unwrap();
+
+        let values = document.evaluate(&context("Beta", None));
+        assert_eq!(values["new-editor"], json!(true));
+        assert_eq!(values["theme"], json!("dark"));
+        assert_eq!(values["upload-limit"], json!(10));
+
+        let values = |version| document.evaluate(&context("stable", version))["new-editor"].clone();
+        assert_eq!(values(None), json!(false));
+        assert_eq!(values(Some("1.9.9.0")), json!(false));
+        assert_eq!(values(Some("2.0.0.0")), json!(true));
+        assert_eq!(values(Some("2.9")), json!(true));
+        assert_eq!(values(Some("2.10.0.0")), json!(false));
+
+        assert_eq!(bucket("0123", "theme"), bucket("0123", "theme"));
+        assert!(Document::parse(r#"{ "schemaVersion": 2, "flags": {} }"#).is_err());
+        assert!(Document::parse(r#"{ "schemaVersion": 1, "flags": { "theme": { "rules": [] } } }"#).is_err());
+    }
+
+    #[test]
+    fn fetch_falls_back_to_the_cached_document() {
+        let dir = env::temp_dir().join(format!("winapp-runtime-remote-config-{}", std::process::id()));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/config.json", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&mut stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            drop(reader);
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{DOCUMENT}", DOCUMENT.len());
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        let builder = RemoteConfig::builder("notes", url).channel("beta").version("1.0.0.0").dir(&dir);
+
+        let config = builder.clone().fetch().unwrap();
+        server.join().unwrap();
+        assert_eq!(config.source(), ConfigSource::Network);
+        assert!(config.is_enabled("new-editor"));
+        assert!(dir.join("beta-1.0.0.0.json").exists());
+
+        // Nothing listens anymore
+        let config = builder.clone().fetch().unwrap();
+        assert_eq!(config.source(), ConfigSource::Cache);
+        assert!(config.is_enabled("new-editor"));
+        assert_eq!(config.string("theme"), Some("dark"));
+        assert_eq!(config.number("upload-limit"), Some(10.0));
+
+        let error = builder.version("1.1.0.0").cached().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::telemetry::random_hex;
+use crate::{app_data_dir, app_user_model_id};
+
+/// The folder of [`app_data_dir`] that the state of a restart is kept in until the restarted app restores it, and
+/// that `winapp restart simulate` reads.
+pub const RESTART_STATE_FOLDER: &str = "RestartState";
+
+/// The argument the restarted app gets, as `--winapp-restore-state=<token>`, with the token of its state file.
+pub const RESTORE_STATE_ARGUMENT: &str = "--winapp-restore-state";
+
+/// The longest command line Windows restarts an app with, `RESTART_MAX_CMD_LINE`.
+const MAX_COMMAND_LINE: usize = 1024;
+
+/// The folder for the restart state of `app`: `RestartState` in its [`app_data_dir`].
+pub fn restart_state_dir(app: &str) -> PathBuf {
+    app_data_dir(app).join(RESTART_STATE_FOLDER)
+}
+
+/// Why Windows restarts the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub enum RestartReason {
+    /// An installer closed the app to update it, or the system restarted for an update.
+    Update,
+    /// The app crashed or stopped responding.
+    Crash,
+}
+
+/// The state the app handed to its restart, which [`restore_state`] returns in the restarted app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoredState {
+    pub reason: RestartReason,
+    /// What the app passed to [`request_restart_for_update`] or [`request_restart_after_crash`].
+    pub payload: Value,
+    /// When the payload was saved, which can be long before a crash.
+    pub saved: SystemTime,
+}
+
+/// Saves `payload` and asks Windows to start the app again with it after an installer closes the app to update it,
+/// as the Restart Manager does for MSIX updates. The restarted app gets the payload from [`restore_state`].
+///
+/// Each call replaces the payload, and the restart [`request_restart_after_crash`] asked for: Windows keeps one
+/// restart for each process. The restart has the arguments the app was started with.
+pub fn request_restart_for_update(app: &str, payload: &Value) -> io::Result<()> {
+    register(&restart_state_dir(app), RestartReason::Update, payload)
+}
+
+/// Saves `payload` and asks Windows Error Reporting to start the app again with it when the app crashes or stops
+/// responding. Windows only restarts apps that ran for at least 60 seconds, so that an app that crashes at start
+/// isn't restarted over and over.
+///
+/// Call it again whenever the state worth restoring changes; each call replaces the payload.
+pub fn request_restart_after_crash(app: &str, payload: &Value) -> io::Result<()> {
+    register(&restart_state_dir(app), RestartReason::Crash, payload)
+}
+
+/// Withdraws the restart of [`request_restart_for_update`] or [`request_restart_after_crash`] and deletes its
+/// payload, e.g. when the app exits normally.
+pub fn cancel_restart(app: &str) -> io::Result<()> {
+    native::unregister_restart()?;
+    match fs::remove_file(state_file(&restart_state_dir(app), token())) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// The state of the restart that started this process, or `None` when it wasn't started by a restart. The state is
+/// deleted once it is read, so it is only returned once.
+///
+/// Fails with [`io::ErrorKind::NotFound`] when the process has a [`RESTORE_STATE_ARGUMENT`] but its state is gone.
+pub fn restore_state(app: &str) -> io::Result<Option<RestoredState>> {
+    match restore_token(env::args_os().skip(1)) {
+        Some(token) => read_state(&restart_state_dir(app), &token).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// The token of this process, which names its state file for as long as it runs.
+fn token() -> &'static str {
+    static TOKEN: OnceLock<String> = OnceLock::new();
+    TOKEN.get_or_init(|| random_hex(16))
+}
+
+fn state_file(dir: &Path, token: &str) -> PathBuf {
+    dir.join(format!("{token}.json"))
+}
+
+fn register(dir: &Path, reason: RestartReason, payload: &Value) -> io::Result<()> {
+    // The restart keeps the arguments of this process but not those of an earlier restart, including arguments that
+    // aren't Unicode, such as paths with unpaired surrogates
+    let arguments: Vec<OsString> =
+        env::args_os().skip(1).filter(|argument| restore_token([argument]).is_none()).collect();
+    let command_line = command_line(&arguments, token());
+    // Each unpaired surrogate becomes one U+FFFD, so the lossy string has the length Windows counts
+    if command_line.to_string_lossy().encode_utf16().count() >= MAX_COMMAND_LINE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("the arguments of the app are longer than the {MAX_COMMAND_LINE} characters Windows restarts with"),
+        ));
+    }
+
+    fs::create_dir_all(dir)?;
+    let state = json!({
+        "reason": reason.name(),
+        "savedAt": SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        "processId": std::process::id(),
+        "executable": env::current_exe()?,
+        "arguments": arguments.iter().map(|argument| argument.to_string_lossy()).collect::<Vec<_>>(),
+        "appUserModelId": app_user_model_id(),
+        "payload": payload,
+    });
+    let file = state_file(dir, token());
+    fs::write(&file, state.to_string())?;
+    native::register_restart(&command_line, reason).inspect_err(|_| {
+        let _ = fs::remove_file(&file);
+    })
+}
+
+fn read_state(dir: &Path, token: &str) -> io::Result<RestoredState> {
+    let file = state_file(dir, token);
+    let state: Value = serde_json::from_slice(&fs::read(&file)?)?;
+    fs::remove_file(&file)?;
+    Ok(RestoredState {
+        reason: state
+            .get("reason")
+            .and_then(Value::as_str)
+            .and_then(RestartReason::parse)
+            .unwrap_or(RestartReason::Crash),
+        payload: state.get("payload").cloned().unwrap_or_default(),
+        saved: UNIX_EPOCH + Duration::from_secs(state.get("savedAt").and_then(Value::as_u64).unwrap_or_default()),
+    })
+}
+
+/// The token of the first [`RESTORE_STATE_ARGUMENT`] of `arguments`. Tokens are hex, so that an argument can't name
+/// a file outside the folder.
+fn restore_token(arguments: impl IntoIterator<Item = impl AsRef<OsStr>>) -> Option<String> {
+    arguments.into_iter().find_map(|argument| {
+        let token = argument.as_ref().to_str()?.strip_prefix(RESTORE_STATE_ARGUMENT)?.strip_prefix('=')?;
+        (!token.is_empty() && token.bytes().all(|b| b.is_ascii_hexdigit())).then(|| token.to_owned())
+    })
+}
+
+/// The command line Windows restarts the app with, without the executable, quoted the way `CommandLineToArgvW` and
+/// the Rust runtime split it.
+fn command_line(arguments: &[OsString], token: &str) -> OsString {
+    let mut command_line = Vec::new();
+    for argument in arguments {
+        quote_argument(argument, &mut command_line);
+        command_line.push(b' ');
+    }
+    command_line.extend_from_slice(format!("{RESTORE_STATE_ARGUMENT}={token}").as_bytes());
+    // SAFETY: the bytes are those of the arguments, split only before and after ASCII, with ASCII added
+    unsafe { OsString::from_encoded_bytes_unchecked(command_line) }
+}
+
+/// Quotes the encoded bytes of `argument`, which are only looked at for ASCII, so that arguments that aren't Unicode
+/// are kept as they are.
+fn quote_argument(argument: &OsStr, command_line: &mut Vec<u8>) {
+    let argument = argument.as_encoded_bytes();
+    if !argument.is_empty() && !argument.iter().any(|b| matches!(b, b' ' | b'\t' | b'\n' | b'"')) {
+        command_line.extend_from_slice(argument);
+        return;
+    }
+    command_line.push(b'"');
+    let mut backslashes = 0;
+    for &b in argument {
+        match b {
+            b'\\' => backslashes += 1,
+            // Backslashes are literal unless a quote follows them
+            b'"' => {
+                command_line.extend(std::iter::repeat(b'\\').take(backslashes * 2 + 1));
+                backslashes = 0;
+            }
+            _ => {
+                command_line.extend(std::iter::repeat(b'\\').take(backslashes));
+                backslashes = 0;
+            }
+        }
+        if b != b'\\' {
+            command_line.push(b);
+        }
+    }
+    command_line.extend(std::iter::repeat(b'\\').take(backslashes * 2));
+    command_line.push(b'"');
+}
+
+impl RestartReason {
+    fn name(self) -> &'static str {
+        match self {
+            RestartReason::Update => "update",
+            RestartReason::Crash => "crash",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "update" => Some(RestartReason::Update),
+            "crash" => Some(RestartReason::Crash),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::OsStr;
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+
+    use super::RestartReason;
+
+    const RESTART_NO_CRASH: u32 = 1;
+    const RESTART_NO_HANG: u32 = 2;
+    const RESTART_NO_PATCH: u32 = 4;
+    const RESTART_NO_REBOOT: u32 = 8;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn RegisterApplicationRestart(command_line: *const u16, flags: u32) -> i32;
+        fn UnregisterApplicationRestart() -> i32;
+    }
+
+    pub fn register_restart(command_line: &OsStr, reason: RestartReason) -> io::Result<()> {
+        let flags = match reason {
+            RestartReason::Update => RESTART_NO_CRASH | RESTART_NO_HANG,
+            RestartReason::Crash => RESTART_NO_PATCH | RESTART_NO_REBOOT,
+        };
+        let command_line: Vec<u16> = command_line.encode_wide().chain([0]).collect();
+        check(unsafe { RegisterApplicationRestart(command_line.as_ptr(), flags) }, "RegisterApplicationRestart")
+    }
+
+    pub fn unregister_restart() -> io::Result<()> {
+        check(unsafe { UnregisterApplicationRestart() }, "UnregisterApplicationRestart")
+    }
+
+    fn check(hresult: i32, function: &str) -> io::Result<()> {
+        match hresult {
+            0 => Ok(()),
+            _ => Err(io::Error::other(format!("{function} failed with 0x{hresult:08x}"))),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::ffi::OsStr;
+    use std::io;
+
+    use super::RestartReason;
+
+    pub fn register_restart(_command_line: &OsStr, _reason: RestartReason) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "restarts need Windows"))
+    }
+
+    pub fn unregister_restart() -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_line_quotes_arguments_and_adds_the_token() {
+        let arguments = ["--open", r"C:\My Notes\a.txt", r#"say "hi"\"#, ""].map(OsString::from);
+
+        assert_eq!(
+            command_line(&arguments, "0a1b"),
+            r#"--open "C:\My Notes\a.txt" "say \"hi\"\\" "" --winapp-restore-state=0a1b"#
+        );
+        assert_eq!(restore_token(["--open", "--winapp-restore-state=0a1b"]), Some("0a1b".to_owned()));
+        assert_eq!(restore_token(["--winapp-restore-state=../settings"]), None);
+        assert_eq!(restore_token(["--winapp-restore-state"]), None);
+    }
+
+    #[test]
+    fn command_line_keeps_arguments_that_arent_unicode() {
+        #[cfg(unix)]
+        let argument = {
+            use std::os::unix::ffi::OsStringExt;
+            OsString::from_vec(b"caf\xe9 menu".to_vec())
+        };
+        #[cfg(windows)]
+        let argument = {
+            use std::os::windows::ffi::OsStringExt;
+            OsString::from_wide(&[0xd800, u16::from(b' '), u16::from(b'm')])
+        };
+        assert!(argument.to_str().is_none());
+
+        let command_line = command_line(&[argument.clone(), OsString::from("--winapp-restore-state=0a1b")], "2c3d");
+        let mut expected = OsString::from("\"");
+        expected.push(&argument);
+        expected.push("\" --winapp-restore-state=0a1b --winapp-restore-state=2c3d");
+        assert_eq!(command_line, expected);
+        assert_eq!(
+            restore_token([argument.as_os_str(), OsStr::new("--winapp-restore-state=0a1b")]),
+            Some("0a1b".into())
+        );
+    }
+
+    #[test]
+    fn read_state_returns_the_payload_once() {
+        let dir = env::temp_dir().join(format!("winapp-runtime-restart-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let state = json!({ "reason": "update", "savedAt": 1_700_000_000, "payload": { "open": ["a.txt"] } });
+        fs::write(state_file(&dir, "0a1b"), state.to_string()).unwrap();
+
+        let restored = read_state(&dir, "0a1b").unwrap();
+        assert_eq!(restored.reason, RestartReason::Update);
+        assert_eq!(restored.payload, json!({ "open": ["a.txt"] }));
+        assert_eq!(restored.saved, UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        assert_eq!(read_state(&dir, "0a1b").unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,677 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::collections::hash_map::RandomState;
+use std::env;
+use std::fmt::Write as _;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::mem;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::http::{self, Url};
+use crate::package::split_package_full_name;
+use crate::{package_family_name, package_full_name, BuildInfo};
+
+/// Environment variable of the OpenTelemetry SDKs with the base URL of the collector, used when
+/// [`TelemetryBuilder::endpoint`] isn't called.
+pub const OTLP_ENDPOINT_VARIABLE: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+/// Environment variable of the OpenTelemetry SDKs with headers to send with each export, as `name=value,name=value`.
+pub const OTLP_HEADERS_VARIABLE: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4318";
+/// Spans that end while the collector can't be reached are kept up to this many, and dropped after.
+const MAX_QUEUED_SPANS: usize = 2048;
+/// The longest response of a collector that is read; the exporter only looks at its status.
+const MAX_RESPONSE_LEN: usize = 64 * 1024;
+
+const SPAN_KIND_INTERNAL: u32 = 1;
+const STATUS_CODE_ERROR: u32 = 2;
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u32 = 2;
+
+/// Exports spans and metrics to an OpenTelemetry collector over OTLP/HTTP, with the package identity and version of
+/// the app as resource attributes.
+///
+/// ```no_run
+/// let telemetry = winapp_runtime::Telemetry::builder("notes").endpoint("https://otel.contoso.com").start()?;
+///
+/// let mut span = telemetry.span("sync");
+/// span.attribute("notes.count", 42);
+/// telemetry.counter("notes.synced").add(42);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Spans are queued when they are dropped, and a thread posts them, with the current value of each metric, every
+/// [`TelemetryBuilder::interval`]. Dropping the `Telemetry` exports what is left; keep it alive for as long as the
+/// app runs.
+///
+/// The resource has `service.name`, `service.version` (the version of the package, unless
+/// [`TelemetryBuilder::version`] sets it), the `winapp.package.*` attributes of the package identity, and the commit
+/// of the build from [`BuildInfo`] when the package was packed with `--provenance`.
+#[derive(Debug)]
+pub struct Telemetry {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+/// Settings of a [`Telemetry`].
+#[derive(Debug, Clone)]
+pub struct TelemetryBuilder {
+    service: String,
+    version: Option<String>,
+    endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    attributes: Attributes,
+    interval: Duration,
+}
+
+/// The value of a span or resource attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        AttributeValue::Int(value.into())
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Int(value)
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Double(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+impl AttributeValue {
+    fn to_json(&self) -> Value {
+        match self {
+            AttributeValue::String(value) => json!({ "stringValue": value }),
+            // OTLP/JSON writes 64-bit integers as strings
+            AttributeValue::Int(value) => json!({ "intValue": value.to_string() }),
+            AttributeValue::Double(value) => json!({ "doubleValue": value }),
+            AttributeValue::Bool(value) => json!({ "boolValue": value }),
+        }
+    }
+}
+
+type Attributes = Vec<(String, AttributeValue)>;
+
+fn attributes_json(attributes: &Attributes) -> Value {
+    attributes.iter().map(|(key, value)| json!({ "key": key, "value": value.to_json() })).collect()
+}
+
+/// A span of a trace, queued for export when it is dropped.
+#[derive(Debug)]
+pub struct Span {
+    shared: Arc<Shared>,
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+    name: String,
+    start_time: SystemTime,
+    attributes: Attributes,
+    error: Option<String>,
+}
+
+/// A sum that only grows, such as a number of requests, exported as its total since the app started.
+#[derive(Debug, Clone)]
+pub struct Counter(Arc<Metric>);
+
+/// The last value of something, such as the length of a queue.
+#[derive(Debug, Clone)]
+pub struct Gauge(Arc<Metric>);
+
+#[derive(Debug)]
+struct Metric {
+    name: String,
+    kind: MetricKind,
+    /// The total of a counter, or the bits of the `f64` of a gauge.
+    value: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+#[derive(Debug)]
+struct Shared {
+    exporter: Exporter,
+    resource: Value,
+    start_time: SystemTime,
+    queue: Mutex<Queue>,
+    wake: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    spans: Vec<Value>,
+    metrics: Vec<Arc<Metric>>,
+    stopping: bool,
+}
+
+impl Telemetry {
+    /// How often spans and metrics are exported, unless [`TelemetryBuilder::interval`] changes it.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Settings for the telemetry of the service `service`, the `service.name` of the resource.
+    pub fn builder(service: impl Into<String>) -> TelemetryBuilder {
+        TelemetryBuilder {
+            service: service.into(),
+            version: None,
+            endpoint: None,
+            headers: Vec::new(),
+            attributes: Vec::new(),
+            interval: Self::DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Starts a span of a new trace.
+    pub fn span(&self, name: impl Into<String>) -> Span {
+        Span::start(Arc::clone(&self.shared), name.into(), random_hex(16), None)
+    }
+
+    /// The counter `name`; counters of the same name add to the same total.
+    pub fn counter(&self, name: impl Into<String>) -> Counter {
+        Counter(self.shared.metric(name.into(), MetricKind::Counter))
+    }
+
+    /// The gauge `name`; gauges of the same name set the same value.
+    pub fn gauge(&self, name: impl Into<String>) -> Gauge {
+        Gauge(self.shared.metric(name.into(), MetricKind::Gauge))
+    }
+
+    /// Exports the spans that ended and the metrics now, instead of at the next interval.
+    pub fn flush(&self) -> io::Result<()> {
+        self.shared.export()
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        self.shared.queue().stopping = true;
+        self.shared.wake.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl TelemetryBuilder {
+    /// The `service.version` of the resource (default: the version of the package, when the process has package
+    /// identity).
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// The base URL of the collector's OTLP/HTTP receiver; spans are posted to `v1/traces` and metrics to
+    /// `v1/metrics` below it (default: `OTEL_EXPORTER_OTLP_ENDPOINT`, or `http://localhost:4318`). `https://` URLs
+    /// need Windows.
+    pub fn endpoint(mut self, url: impl Into<String>) -> Self {
+        self.endpoint = Some(url.into());
+        self
+    }
+
+    /// A header to send with each export, such as the API key of the backend, after those of
+    /// `OTEL_EXPORTER_OTLP_HEADERS`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// A resource attribute, such as `deployment.environment`; it replaces an attribute of the same key that comes
+    /// from the package.
+    pub fn attribute(mut self, key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// How often spans and metrics are exported (default: [`Telemetry::DEFAULT_INTERVAL`]).
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Reads the package identity and starts the thread that exports.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] when the endpoint isn't an `http://` or `https://` URL. Exports that
+    /// fail later are reported to stderr, once until one succeeds again.
+    pub fn start(self) -> io::Result<Telemetry> {
+        let url = self
+            .endpoint
+            .clone()
+            .or_else(|| env::var(OTLP_ENDPOINT_VARIABLE).ok().filter(|url| !url.is_empty()))
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_owned());
+        let endpoint = Url::parse(&url)?;
+        let mut headers = vec![("Content-Type".to_owned(), "application/json".to_owned())];
+        headers.extend(env::var(OTLP_HEADERS_VARIABLE).map(|headers| parse_headers(&headers)).unwrap_or_default());
+        headers.extend(self.headers.iter().cloned());
+        let attributes = self.resource_attributes(
+            package_full_name().as_deref(),
+            package_family_name().as_deref(),
+            BuildInfo::current().as_ref(),
+        );
+
+        let shared = Arc::new(Shared {
+            exporter: Exporter { url, endpoint, headers },
+            resource: json!({ "attributes": attributes_json(&attributes) }),
+            start_time: SystemTime::now(),
+            queue: Mutex::default(),
+            wake: Condvar::new(),
+        });
+        let worker = thread::Builder::new().name("winapp-telemetry".into()).spawn({
+            let shared = Arc::clone(&shared);
+            let interval = self.interval;
+            move || run(&shared, interval)
+        })?;
+        Ok(Telemetry { shared, worker: Some(worker) })
+    }
+
+    fn resource_attributes(
+        &self,
+        full_name: Option<&str>,
+        family_name: Option<&str>,
+        build_info: Option<&BuildInfo>,
+    ) -> Attributes {
+        let mut attributes: Attributes = vec![("service.name".into(), self.service.as_str().into())];
+        let package = full_name.and_then(|full_name| Some((full_name, split_package_full_name(full_name)?)));
+        if let Some(version) = self.version.as_deref().or(package.map(|(_, parts)| parts.version)) {
+            attributes.push(("service.version".into(), version.into()));
+        }
+        if let Some((full_name, parts)) = package {
+            attributes.push(("winapp.package.name".into(), parts.name.into()));
+            attributes.push(("winapp.package.full_name".into(), full_name.into()));
+            attributes.push(("winapp.package.architecture".into(), parts.architecture.into()));
+            attributes.push(("winapp.package.publisher_id".into(), parts.publisher_id.into()));
+        }
+        if let Some(family_name) = family_name {
+            attributes.push(("winapp.package.family_name".into(), family_name.into()));
+        }
+        if let Some(build_info) = build_info {
+            if let Some(sha) = &build_info.git_sha {
+                attributes.push(("vcs.ref.head.revision".into(), sha.as_str().into()));
+            }
+            if let Some(branch) = &build_info.branch {
+                attributes.push(("vcs.ref.head.name".into(), branch.as_str().into()));
+            }
+        }
+        attributes.push(("os.type".into(), env::consts::OS.into()));
+        attributes.push(("process.pid".into(), i64::from(process::id()).into()));
+
+        attributes.retain(|(key, _)| !self.attributes.iter().any(|(own, _)| own == key));
+        attributes.extend(self.attributes.iter().cloned());
+        attributes
+    }
+}
+
+impl Span {
+    fn start(shared: Arc<Shared>, name: String, trace_id: String, parent_span_id: Option<String>) -> Span {
+        Span {
+            shared,
+            trace_id,
+            span_id: random_hex(8),
+            parent_span_id,
+            name,
+            start_time: SystemTime::now(),
+            attributes: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Sets an attribute of the span, such as `http.response.status_code`.
+    pub fn attribute(&mut self, key: impl Into<String>, value: impl Into<AttributeValue>) -> &mut Self {
+        let key = key.into();
+        self.attributes.retain(|(own, _)| *own != key);
+        self.attributes.push((key, value.into()));
+        self
+    }
+
+    /// Marks the span as failed, with the error's message.
+    pub fn error(&mut self, message: impl Into<String>) -> &mut Self {
+        self.error = Some(message.into());
+        self
+    }
+
+    /// Starts a span of the same trace, below this one.
+    pub fn child(&self, name: impl Into<String>) -> Span {
+        Span::start(Arc::clone(&self.shared), name.into(), self.trace_id.clone(), Some(self.span_id.clone()))
+    }
+
+    /// The id of the trace, 32 hex digits, to write to logs so they can be found from the trace.
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    fn to_json(&self, end_time: SystemTime) -> Value {
+        let mut span = json!({
+            "traceId": self.trace_id,
+            "spanId": self.span_id,
+            "name": self.name,
+            "kind": SPAN_KIND_INTERNAL,
+            "startTimeUnixNano": unix_nanos(self.start_time).to_string(),
+            "endTimeUnixNano": unix_nanos(end_time).to_string(),
+            "attributes": attributes_json(&self.attributes),
+        });
+        if let Some(parent_span_id) = &self.parent_span_id {
+            span["parentSpanId"] = json!(parent_span_id);
+        }
+        if let Some(message) = &self.error {
+            span["status"] = json!({ "code": STATUS_CODE_ERROR, "message": message });
+        }
+        span
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let span = self.to_json(SystemTime::now());
+        let mut queue = self.shared.queue();
+        if queue.spans.len() < MAX_QUEUED_SPANS {
+            queue.spans.push(span);
+        }
+    }
+}
+
+impl Counter {
+    pub fn add(&self, value: u64) {
+        self.0.value.fetch_add(value, Ordering::Relaxed);
+    }
+}
+
+impl Gauge {
+    pub fn set(&self, value: f64) {
+        self.0.value.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+impl Metric {
+    fn to_json(&self, start_time: SystemTime, time: SystemTime) -> Value {
+        let value = self.value.load(Ordering::Relaxed);
+        match self.kind {
+            MetricKind::Counter => json!({
+                "name": self.name,
+                "sum": {
+                    "dataPoints": [{
+                        "startTimeUnixNano": unix_nanos(start_time).to_string(),
+                        "timeUnixNano": unix_nanos(time).to_string(),
+                        "asInt": value.to_string(),
+                    }],
+                    "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                    "isMonotonic": true,
+                },
+            }),
+            MetricKind::Gauge => json!({
+                "name": self.name,
+                "gauge": {
+                    "dataPoints": [{ "timeUnixNano": unix_nanos(time).to_string(), "asDouble": f64::from_bits(value) }],
+                },
+            }),
+        }
+    }
+}
+
+impl Shared {
+    fn queue(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn metric(&self, name: String, kind: MetricKind) -> Arc<Metric> {
+        let mut queue = self.queue();
+        if let Some(metric) = queue.metrics.iter().find(|metric| metric.name == name && metric.kind == kind) {
+            return Arc::clone(metric);
+        }
+        let metric = Arc::new(Metric { name, kind, value: AtomicU64::new(0) });
+        queue.metrics.push(Arc::clone(&metric));
+        metric
+    }
+
+    fn export(&self) -> io::Result<()> {
+        let (spans, metrics) = {
+            let mut queue = self.queue();
+            (mem::take(&mut queue.spans), queue.metrics.clone())
+        };
+        let scope = json!({ "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") });
+
+        let mut result = Ok(());
+        if !spans.is_empty() {
+            let traces = json!({
+                "resourceSpans": [{ "resource": self.resource, "scopeSpans": [{ "scope": scope, "spans": spans }] }],
+            });
+            result = self.exporter.post("v1/traces", &traces);
+        }
+        if !metrics.is_empty() {
+            let now = SystemTime::now();
+            let metrics: Vec<Value> = metrics.iter().map(|metric| metric.to_json(self.start_time, now)).collect();
+            let metrics = json!({
+                "resourceMetrics": [{ "resource": self.resource, "scopeMetrics": [{ "scope": scope, "metrics": metrics }] }],
+            });
+            let posted = self.exporter.post("v1/metrics", &metrics);
+            result = result.and(posted);
+        }
+        result
+    }
+}
+
+fn run(shared: &Shared, interval: Duration) {
+    let mut failing = false;
+    loop {
+        let stopping = {
+            let queue = shared.queue();
+            let (queue, _) = shared
+                .wake
+                .wait_timeout_while(queue, interval, |queue| !queue.stopping)
+                .unwrap_or_else(PoisonError::into_inner);
+            queue.stopping
+        };
+        match shared.export() {
+            Ok(()) => failing = false,
+            Err(error) if !failing => {
+                failing = true;
+                eprintln!("winapp-runtime: couldn't export telemetry to {}: {error}", shared.exporter.url);
+            }
+            Err(_) => {}
+        }
+        if stopping {
+            return;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Exporter {
+    url: String,
+    endpoint: Url,
+    headers: Vec<(String, String)>,
+}
+
+impl Exporter {
+    fn post(&self, signal: &str, body: &Value) -> io::Result<()> {
+        let url = self.endpoint.join(signal);
+        let response = http::send("POST", &url, &self.headers, body.to_string().as_bytes(), MAX_RESPONSE_LEN)?;
+        if (200..300).contains(&response.status) {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("the collector answered HTTP {} to {}", response.status, url.path)))
+        }
+    }
+}
+
+/// Parses `name=value,name=value`, the format of `OTEL_EXPORTER_OTLP_HEADERS`.
+fn parse_headers(headers: &str) -> Vec<(String, String)> {
+    headers
+        .split(',')
+        .filter_map(|header| header.split_once('='))
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos() as u64).unwrap_or_default()
+}
+
+/// `bytes` random bytes as hex, for trace and span ids. `RandomState` is seeded from the OS for each thread, and
+/// hashing a counter with it is random enough for ids.
+pub(crate) fn random_hex(bytes: usize) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hex = String::with_capacity(bytes * 2 + 16);
+    while hex.len() < bytes * 2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u64(unix_nanos(SystemTime::now()));
+        let _ = write!(hex, "{:016x}", hasher.finish());
+    }
+    hex.truncate(bytes * 2);
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn attribute<'a>(attributes: &'a Attributes, key: &str) -> Option<&'a AttributeValue> {
+        attributes.iter().find(|(own, _)| own == key).map(|(_, value)| value)
+    }
+
+    /// Reads a request with a Content-Length, and answers 200.
+    fn answer(listener: &TcpListener) -> (String, Value) {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(&mut stream);
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            head.push_str(&line);
+        }
+        let length = head.lines().find_map(|line| line.strip_prefix("Content-Length: ")).unwrap().parse().unwrap();
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        drop(reader);
+        stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+        (head, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn resource_attributes_come_from_the_package_identity() {
+        let builder =
+            Telemetry::builder("notes").attribute("deployment.environment", "beta").attribute("os.type", "windows");
+        let build_info =
+            BuildInfo { git_sha: Some("3f9c2d0e5b1a".into()), branch: Some("main".into()), ..BuildInfo::default() };
+
+        let attributes = builder.resource_attributes(
+            Some("Contoso.Notes_1.2.0.0_x64__8wekyb3d8bbwe"),
+            Some("Contoso.Notes_8wekyb3d8bbwe"),
+            Some(&build_info),
+        );
+        let unpackaged = Telemetry::builder("notes").version("0.3.0").resource_attributes(None, None, None);
+
+        assert_eq!(attribute(&attributes, "service.name"), Some(&"notes".into()));
+        assert_eq!(attribute(&attributes, "service.version"), Some(&"1.2.0.0".into()));
+        assert_eq!(attribute(&attributes, "winapp.package.name"), Some(&"Contoso.Notes".into()));
+        assert_eq!(attribute(&attributes, "winapp.package.architecture"), Some(&"x64".into()));
+        assert_eq!(attribute(&attributes, "winapp.package.publisher_id"), Some(&"8wekyb3d8bbwe".into()));
+        assert_eq!(attribute(&attributes, "winapp.package.family_name"), Some(&"Contoso.Notes_8wekyb3d8bbwe".into()));
+        assert_eq!(attribute(&attributes, "vcs.ref.head.revision"), Some(&"3f9c2d0e5b1a".into()));
+        assert_eq!(attribute(&attributes, "deployment.environment"), Some(&"beta".into()));
+        assert_eq!(attributes.iter().filter(|(key, _)| key == "os.type").count(), 1);
+        assert_eq!(attribute(&attributes, "os.type"), Some(&"windows".into()));
+        assert_eq!(attribute(&unpackaged, "service.version"), Some(&"0.3.0".into()));
+        assert_eq!(attribute(&unpackaged, "winapp.package.name"), None);
+    }
+
+    #[test]
+    fn headers_parse_like_the_opentelemetry_sdks() {
+        assert_eq!(
+            parse_headers("x-api-key=secret, dd-protocol = otlp,broken"),
+            [("x-api-key".to_owned(), "secret".to_owned()), ("dd-protocol".to_owned(), "otlp".to_owned())]
+        );
+    }
+
+    #[test]
+    fn spans_and_metrics_are_posted_to_the_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/otlp", listener.local_addr().unwrap());
+        // The flush posts traces and metrics, and dropping the telemetry posts the metrics again
+        let collector = thread::spawn(move || (0..3).map(|_| answer(&listener)).collect::<Vec<_>>());
+
+        let telemetry = Telemetry::builder("notes")
+            .endpoint(endpoint)
+            .header("x-api-key", "secret")
+            .interval(Duration::from_secs(3600))
+            .start()
+            .unwrap();
+        {
+            let mut span = telemetry.span("sync");
+            span.attribute("notes.count", 3);
+            span.child("pull").error("timed out");
+        }
+        telemetry.counter("notes.synced").add(2);
+        telemetry.counter("notes.synced").add(1);
+        telemetry.flush().unwrap();
+        drop(telemetry);
+        let requests = collector.join().unwrap();
+
+        let (head, traces) = &requests[0];
+        assert!(head.starts_with("POST /otlp/v1/traces HTTP/1.1\r\n"), "{head}");
+        assert!(head.contains("x-api-key: secret\r\n"), "{head}");
+        let spans = &traces["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        let (child, parent) = (&spans[0], &spans[1]);
+        assert_eq!(parent["name"], "sync");
+        assert_eq!(parent["attributes"][0], json!({ "key": "notes.count", "value": { "intValue": "3" } }));
+        assert_eq!(child["parentSpanId"], parent["spanId"]);
+        assert_eq!(child["traceId"], parent["traceId"]);
+        assert_eq!(child["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(child["status"]["code"], STATUS_CODE_ERROR);
+        assert_eq!(traces["resourceSpans"][0]["resource"]["attributes"][0]["value"]["stringValue"], "notes");
+
+        let (head, metrics) = &requests[1];
+        assert!(head.starts_with("POST /otlp/v1/metrics HTTP/1.1\r\n"), "{head}");
+        let counter = &metrics["resourceMetrics"][0]["scopeMetrics"][0]["metrics"][0];
+        assert_eq!(counter["name"], "notes.synced");
+        assert_eq!(counter["sum"]["dataPoints"][0]["asInt"], "3");
+        assert!(requests[2].0.starts_with("POST /otlp/v1/metrics "));
+    }
+}
@@ -0,0 +1,736 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Lines, Write};
+use std::path::{self, Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use serde_json::{json, Value};
+
+use crate::app_data_dir;
+
+/// The folder of [`app_data_dir`] that the [`TransferInfo`] of each transfer is kept in while it runs.
+pub const TRANSFERS_FOLDER: &str = "BackgroundTransfers";
+
+/// The folder that the transfers of `app` are kept track of in: `BackgroundTransfers` in its [`app_data_dir`].
+pub fn transfers_dir(app: &str) -> PathBuf {
+    app_data_dir(app).join(TRANSFERS_FOLDER)
+}
+
+/// Whether a transfer downloads to a file or uploads one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub enum TransferKind {
+    Download,
+    Upload,
+}
+
+/// The networks a transfer runs on, as `BackgroundTransferCostPolicy`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub enum CostPolicy {
+    /// Windows decides, which pauses transfers on metered networks when the user chose to save data.
+    #[default]
+    Default,
+    /// Pauses the transfer while the network is metered or roaming, e.g. for large optional content.
+    UnrestrictedOnly,
+    /// Transfers on any network, e.g. for content the user is waiting for.
+    Always,
+}
+
+/// The state of a transfer, as `BackgroundTransferStatus`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub enum TransferStatus {
+    #[default]
+    Idle,
+    Running,
+    PausedByApplication,
+    /// Waits for a network that its [`CostPolicy`] allows.
+    PausedCostedNetwork,
+    PausedNoNetwork,
+    /// The server answered with an error that may go away, such as 503, and the transfer is retried later.
+    PausedRecoverableWebError,
+    /// Paused to save battery or for other system policies.
+    PausedSystemPolicy,
+    Completed,
+    Canceled,
+    Error,
+}
+
+/// What a transfer is, kept in the `BackgroundTransfers` folder so the app knows what [`TransferGroup::resume`]
+/// returns after a restart.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub struct TransferInfo {
+    /// The GUID Windows gave the transfer.
+    pub id: String,
+    pub kind: TransferKind,
+    pub url: String,
+    /// The file that is downloaded to or uploaded.
+    pub path: PathBuf,
+    /// The name of the [`TransferGroup`].
+    pub group: String,
+    pub cost_policy: CostPolicy,
+    /// What the app passed to [`TransferRequest::metadata`], such as the id of the content being downloaded;
+    /// `null` when it passed nothing.
+    pub metadata: Value,
+}
+
+/// How far a transfer got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "camelCase"))]
+pub struct TransferProgress {
+    pub status: TransferStatus,
+    /// Bytes received by a download or sent by an upload.
+    pub bytes: u64,
+    /// The size of the transfer, or 0 while the server hasn't said.
+    pub total_bytes: u64,
+    /// Whether the server couldn't continue where the transfer stopped, so it started over.
+    pub restarted: bool,
+}
+
+impl TransferProgress {
+    /// The share of the transfer done, from 0.0 to 1.0, or `None` while its size isn't known.
+    pub fn fraction(&self) -> Option<f64> {
+        (self.total_bytes > 0).then(|| self.bytes as f64 / self.total_bytes as f64)
+    }
+}
+
+/// Transfers of an app that Windows runs in the background as `BackgroundDownloader` and `BackgroundUploader`
+/// operations, in a `BackgroundTransferGroup`: they go on while the app is suspended or closed, and
+/// [`TransferGroup::resume`] picks them up again when it starts.
+///
+/// Each transfer it follows runs a Windows PowerShell process that drives the WinRT API, so transfers fail with
+/// [`io::ErrorKind::Unsupported`] where PowerShell is in `ConstrainedLanguage` mode, such as under an AppLocker or
+/// WDAC policy, or can't be started.
+#[derive(Debug, Clone)]
+pub struct TransferGroup {
+    app: String,
+    name: String,
+    dir: Option<PathBuf>,
+    cost_policy: CostPolicy,
+    serialized: bool,
+}
+
+/// Settings of a transfer that [`TransferRequest::start`] starts.
+#[derive(Debug, Clone)]
+pub struct TransferRequest {
+    group: TransferGroup,
+    kind: TransferKind,
+    url: String,
+    path: PathBuf,
+    method: String,
+    headers: Vec<(String, String)>,
+    cost_policy: CostPolicy,
+    metadata: Value,
+}
+
+/// A running transfer.
+///
+/// Dropping it stops following the transfer without stopping it; [`TransferGroup::resume`] returns it again.
+#[derive(Debug)]
+pub struct Transfer {
+    info: TransferInfo,
+    shared: Arc<Shared>,
+    helper: Mutex<Helper>,
+    reader: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct Helper {
+    child: Child,
+    stdin: Option<ChildStdin>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    progress: TransferProgress,
+    outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Completed,
+    Canceled,
+    Failed(String),
+}
+
+/// A line the helper writes, tab-separated.
+#[derive(Debug, PartialEq)]
+enum Line {
+    Id(String),
+    Progress(TransferProgress),
+    Done(Outcome),
+    Transfer(TransferInfo),
+    /// PowerShell runs in this language mode instead of `FullLanguage`, so it can't call the WinRT API.
+    Unsupported(String),
+}
+
+impl TransferGroup {
+    /// The transfer group `name` of `app`, whose transfers are kept track of in its [`transfers_dir`].
+    pub fn new(app: impl Into<String>, name: impl Into<String>) -> Self {
+        TransferGroup {
+            app: app.into(),
+            name: name.into(),
+            dir: None,
+            cost_policy: CostPolicy::Default,
+            serialized: false,
+        }
+    }
+
+    /// Keeps track of the transfers in `dir` instead of the [`transfers_dir`] of the app.
+    pub fn dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// The [`CostPolicy`] of the transfers of the group unless [`TransferRequest::cost_policy`] changes it.
+    pub fn cost_policy(mut self, cost_policy: CostPolicy) -> Self {
+        self.cost_policy = cost_policy;
+        self
+    }
+
+    /// Runs the transfers of the group one after the other, in the order they started, instead of side by side.
+    pub fn serialized(mut self, serialized: bool) -> Self {
+        self.serialized = serialized;
+        self
+    }
+
+    /// A download of `url` to the file at `path`, which is created, with its folder, when it starts.
+    pub fn download(&self, url: impl Into<String>, path: impl Into<PathBuf>) -> TransferRequest {
+        self.request(TransferKind::Download, url.into(), path.into())
+    }
+
+    /// An upload of the file at `path` to `url`, with `POST` unless [`TransferRequest::method`] changes it.
+    pub fn upload(&self, url: impl Into<String>, path: impl Into<PathBuf>) -> TransferRequest {
+        self.request(TransferKind::Upload, url.into(), path.into())
+    }
+
+    /// Follows the transfers of the group again, e.g. when the app starts: those that ran while it was closed, with
+    /// the [`TransferInfo`] they started with, and those that finished meanwhile, whose [`Transfer::wait`] returns
+    /// at once.
+    pub fn resume(&self) -> io::Result<Vec<Transfer>> {
+        let dir = self.group_dir();
+        let mut helper = native::spawn(&self.env("list", None))?;
+        let stdout = helper.stdout.take().ok_or_else(|| io::Error::other("the transfer helper has no output"))?;
+        let mut current = Vec::new();
+        for line in BufReader::new(stdout).lines() {
+            match parse_line(&line?, &self.name) {
+                Some(Line::Transfer(info)) => current.push(info),
+                Some(Line::Done(Outcome::Failed(message))) => return Err(io::Error::other(message)),
+                Some(Line::Unsupported(mode)) => return Err(unsupported_language_mode(&mode)),
+                _ => {}
+            }
+        }
+        helper.wait()?;
+
+        let ids: HashSet<_> = current.iter().map(|info| format!("{}.json", info.id)).collect();
+        let mut transfers = Vec::with_capacity(current.len());
+        for mut info in current {
+            if let Some(saved) = fs::read_to_string(dir.join(format!("{}.json", info.id)))
+                .ok()
+                .and_then(|text| TransferInfo::from_json(&serde_json::from_str(&text).ok()?))
+            {
+                info.metadata = saved.metadata;
+            }
+            let mut env = self.env("attach", Some(info.kind));
+            env.push(("WINAPP_TRANSFER_ID", info.id.clone()));
+            let mut helper = native::spawn(&env)?;
+            let lines = helper_lines(&mut helper)?;
+            transfers.push(Transfer::follow(info, helper, lines, dir.clone()));
+        }
+
+        // The transfers whose info is left were removed, such as by an uninstall of the package's transfers
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                if !ids.contains(&entry.file_name().to_string_lossy().into_owned()) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+        Ok(transfers)
+    }
+
+    fn request(&self, kind: TransferKind, url: String, path: PathBuf) -> TransferRequest {
+        TransferRequest {
+            group: self.clone(),
+            kind,
+            url,
+            path,
+            method: "POST".to_owned(),
+            headers: Vec::new(),
+            cost_policy: self.cost_policy,
+            metadata: Value::Null,
+        }
+    }
+
+    fn group_dir(&self) -> PathBuf {
+        self.dir.clone().unwrap_or_else(|| transfers_dir(&self.app)).join(&self.name)
+    }
+
+    fn env(&self, mode: &str, kind: Option<TransferKind>) -> Vec<(&'static str, String)> {
+        let mut env = vec![("WINAPP_TRANSFER_MODE", mode.to_owned()), ("WINAPP_TRANSFER_GROUP", self.name.clone())];
+        env.push(("WINAPP_TRANSFER_BEHAVIOR", if self.serialized { "Serialized" } else { "Parallel" }.to_owned()));
+        if let Some(kind) = kind {
+            env.push(("WINAPP_TRANSFER_KIND", kind.name().to_owned()));
+        }
+        env
+    }
+}
+
+impl TransferRequest {
+    /// The HTTP method of an upload, such as `PUT` for a presigned storage URL.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = method.into();
+        self
+    }
+
+    /// Sends a header with the request, such as an authorization header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// The networks this transfer runs on, instead of the [`CostPolicy`] of the group.
+    pub fn cost_policy(mut self, cost_policy: CostPolicy) -> Self {
+        self.cost_policy = cost_policy;
+        self
+    }
+
+    /// Keeps `metadata` with the transfer, so the app knows what it was for when [`TransferGroup::resume`] returns
+    /// it after a restart, e.g. `serde_json::to_value(&episode)?`.
+    pub fn metadata(mut self, metadata: Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Hands the transfer to Windows and starts following it.
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] on hosts other than Windows. Windows keeps background transfers per
+    /// package, so the app needs package identity, such as the debug identity of `cargo winapp build`.
+    pub fn start(self) -> io::Result<Transfer> {
+        // StorageFile takes absolute paths to existing files
+        let path = path::absolute(&self.path)?;
+        if self.kind == TransferKind::Download {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            File::create(&path)?;
+        }
+        let dir = self.group.group_dir();
+        fs::create_dir_all(&dir)?;
+
+        let mut env = self.group.env("start", Some(self.kind));
+        env.push(("WINAPP_TRANSFER_URL", self.url.clone()));
+        env.push(("WINAPP_TRANSFER_PATH", path.to_string_lossy().into_owned()));
+        env.push(("WINAPP_TRANSFER_METHOD", self.method.clone()));
+        env.push(("WINAPP_TRANSFER_COST_POLICY", self.cost_policy.name().to_owned()));
+        let headers: Vec<_> = self.headers.iter().map(|(name, value)| format!("{name}: {value}")).collect();
+        env.push(("WINAPP_TRANSFER_HEADERS", headers.join("\n")));
+        let mut helper = native::spawn(&env)?;
+        let mut lines = helper_lines(&mut helper)?;
+
+        let id = match lines.next().transpose()?.as_deref().and_then(|line| parse_line(line, &self.group.name)) {
+            Some(Line::Id(id)) => id,
+            Some(Line::Done(Outcome::Failed(message))) => return Err(io::Error::other(message)),
+            Some(Line::Unsupported(mode)) => return Err(unsupported_language_mode(&mode)),
+            _ => return Err(io::Error::other("the transfer helper exited before the transfer started")),
+        };
+        let info = TransferInfo {
+            id,
+            kind: self.kind,
+            url: self.url,
+            path,
+            group: self.group.name.clone(),
+            cost_policy: self.cost_policy,
+            metadata: self.metadata,
+        };
+        fs::write(dir.join(format!("{}.json", info.id)), info.to_json().to_string())?;
+        Ok(Transfer::follow(info, helper, lines, dir))
+    }
+}
+
+impl Transfer {
+    fn follow(info: TransferInfo, helper: Child, lines: Lines<BufReader<ChildStdout>>, dir: PathBuf) -> Self {
+        let shared = Arc::new(Shared::default());
+        let reader = thread::spawn({
+            let shared = Arc::clone(&shared);
+            let group = info.group.clone();
+            let info_file = dir.join(format!("{}.json", info.id));
+            move || read_helper(lines, &group, &shared, &info_file)
+        });
+        let mut helper = Helper { child: helper, stdin: None };
+        helper.stdin = helper.child.stdin.take();
+        Transfer { info, shared, helper: Mutex::new(helper), reader: Some(reader) }
+    }
+
+    /// What the transfer is, with the metadata it started with.
+    pub fn info(&self) -> &TransferInfo {
+        &self.info
+    }
+
+    /// How far the transfer got, updated twice a second.
+    pub fn progress(&self) -> TransferProgress {
+        self.shared.state.lock().unwrap_or_else(PoisonError::into_inner).progress
+    }
+
+    /// Waits until the transfer is done, and returns its last progress.
+    ///
+    /// Fails with [`io::ErrorKind::Interrupted`] when it was canceled, and with the error of the transfer, such as
+    /// an HTTP status, when it failed.
+    pub fn wait(&self) -> io::Result<TransferProgress> {
+        let mut state = self.shared.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match &state.outcome {
+                Some(Outcome::Completed) => return Ok(state.progress),
+                Some(Outcome::Canceled) => {
+                    return Err(io::Error::new(io::ErrorKind::Interrupted, format!("{} was canceled", self.info.url)))
+                }
+                Some(Outcome::Failed(message)) => return Err(io::Error::other(message.clone())),
+                None => state = self.shared.changed.wait(state).unwrap_or_else(PoisonError::into_inner),
+            }
+        }
+    }
+
+    /// Pauses the transfer until [`Transfer::resume`]; it stays paused across restarts.
+    pub fn pause(&self) -> io::Result<()> {
+        self.send("pause")
+    }
+
+    /// Continues a transfer that [`Transfer::pause`] paused.
+    pub fn resume(&self) -> io::Result<()> {
+        self.send("resume")
+    }
+
+    /// Stops the transfer for good; a download leaves its partial file.
+    pub fn cancel(&self) -> io::Result<()> {
+        self.send("cancel")
+    }
+
+    fn send(&self, command: &str) -> io::Result<()> {
+        let mut helper = self.helper.lock().unwrap_or_else(PoisonError::into_inner);
+        let stdin = helper.stdin.as_mut().ok_or_else(|| io::Error::other("the transfer helper has exited"))?;
+        stdin.write_all(format!("{command}\n").as_bytes())?;
+        stdin.flush()
+    }
+}
+
+impl Drop for Transfer {
+    fn drop(&mut self) {
+        let helper = self.helper.get_mut().unwrap_or_else(PoisonError::into_inner);
+        // The helper exits when its input ends, and Windows goes on with the transfer
+        drop(helper.stdin.take());
+        let _ = helper.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+impl TransferKind {
+    fn name(self) -> &'static str {
+        match self {
+            TransferKind::Download => "download",
+            TransferKind::Upload => "upload",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [TransferKind::Download, TransferKind::Upload].into_iter().find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl CostPolicy {
+    fn name(self) -> &'static str {
+        match self {
+            CostPolicy::Default => "Default",
+            CostPolicy::UnrestrictedOnly => "UnrestrictedOnly",
+            CostPolicy::Always => "Always",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [CostPolicy::Default, CostPolicy::UnrestrictedOnly, CostPolicy::Always]
+            .into_iter()
+            .find(|policy| policy.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl TransferStatus {
+    /// Reads the name of a `BackgroundTransferStatus`; statuses added to Windows later count as running.
+    fn parse(name: &str) -> Self {
+        match name {
+            "Idle" => TransferStatus::Idle,
+            "PausedByApplication" => TransferStatus::PausedByApplication,
+            "PausedCostedNetwork" => TransferStatus::PausedCostedNetwork,
+            "PausedNoNetwork" => TransferStatus::PausedNoNetwork,
+            "PausedRecoverableWebErrorStatus" => TransferStatus::PausedRecoverableWebError,
+            "PausedSystemPolicy" => TransferStatus::PausedSystemPolicy,
+            "Completed" => TransferStatus::Completed,
+            "Canceled" => TransferStatus::Canceled,
+            "Error" => TransferStatus::Error,
+            _ => TransferStatus::Running,
+        }
+    }
+}
+
+impl TransferInfo {
+    fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "kind": self.kind.name(),
+            "url": self.url,
+            "path": self.path,
+            "group": self.group,
+            "costPolicy": self.cost_policy.name(),
+            "metadata": self.metadata,
+        })
+    }
+
+    fn from_json(json: &Value) -> Option<Self> {
+        let string = |key: &str| json.get(key).and_then(Value::as_str);
+        Some(TransferInfo {
+            id: string("id")?.to_owned(),
+            kind: TransferKind::parse(string("kind")?)?,
+            url: string("url")?.to_owned(),
+            path: string("path")?.into(),
+            group: string("group")?.to_owned(),
+            cost_policy: string("costPolicy").and_then(CostPolicy::parse).unwrap_or_default(),
+            metadata: json.get("metadata").cloned().unwrap_or_default(),
+        })
+    }
+}
+
+fn unsupported_language_mode(mode: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "background transfers run Windows PowerShell, which is in {mode} mode here, such as under an AppLocker or \
+             WDAC policy; they need FullLanguage mode"
+        ),
+    )
+}
+
+fn helper_lines(helper: &mut Child) -> io::Result<Lines<BufReader<ChildStdout>>> {
+    let stdout = helper.stdout.take().ok_or_else(|| io::Error::other("the transfer helper has no output"))?;
+    Ok(BufReader::new(stdout).lines())
+}
+
+/// Passes the progress the helper writes to the [`Transfer`], until the transfer is done or the helper exits.
+fn read_helper(lines: Lines<BufReader<ChildStdout>>, group: &str, shared: &Shared, info_file: &Path) {
+    let mut outcome = None;
+    for line in lines.map_while(Result::ok) {
+        match parse_line(&line, group) {
+            Some(Line::Progress(progress)) => {
+                shared.state.lock().unwrap_or_else(PoisonError::into_inner).progress = progress;
+                shared.changed.notify_all();
+            }
+            Some(Line::Done(done)) => outcome = Some(done),
+            _ => {}
+        }
+    }
+
+    // Windows forgets a transfer once it is done, and so does the app
+    if matches!(outcome, Some(Outcome::Completed | Outcome::Canceled)) {
+        let _ = fs::remove_file(info_file);
+    }
+    let mut state = shared.state.lock().unwrap_or_else(PoisonError::into_inner);
+    state.outcome = Some(outcome.unwrap_or_else(|| Outcome::Failed("the transfer helper exited".to_owned())));
+    shared.changed.notify_all();
+}
+
+fn parse_line(line: &str, group: &str) -> Option<Line> {
+    let fields: Vec<_> = line.trim_end_matches('\r').split('\t').collect();
+    Some(match fields.as_slice() {
+        ["id", id] => Line::Id((*id).to_owned()),
+        ["progress", status, bytes, total_bytes, restarted] => Line::Progress(TransferProgress {
+            status: TransferStatus::parse(status),
+            bytes: bytes.parse().ok()?,
+            total_bytes: total_bytes.parse().ok()?,
+            restarted: restarted.eq_ignore_ascii_case("true"),
+        }),
+        ["done", "Completed"] => Line::Done(Outcome::Completed),
+        ["done", "Canceled"] => Line::Done(Outcome::Canceled),
+        ["done", "Error", message] | ["error", message] => Line::Done(Outcome::Failed((*message).to_owned())),
+        ["unsupported", mode] => Line::Unsupported((*mode).to_owned()),
+        ["transfer", kind, id, cost_policy, url, path] => Line::Transfer(TransferInfo {
+            id: (*id).to_owned(),
+            kind: TransferKind::parse(kind)?,
+            url: (*url).to_owned(),
+            path: path.into(),
+            group: group.to_owned(),
+            cost_policy: CostPolicy::parse(cost_policy).unwrap_or_default(),
+            metadata: Value::Null,
+        }),
+        _ => return None,
+    })
+}
+
+#[cfg(windows)]
+mod native {
+    use std::io;
+    use std::os::windows::process::CommandExt;
+    use std::process::{Child, Command, Stdio};
+
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    // Windows PowerShell projects Windows.Networking.BackgroundTransfer, as it does the toast API of the panic
+    // reporter. It writes tab-separated lines: 'transfer' for each transfer of the group when listing, then 'id',
+    // 'progress' twice a second and 'done' when starting or attaching. It reads 'pause', 'resume' and 'cancel', and
+    // exits when its input ends. Outside FullLanguage mode it can't load WinRT types, so it first writes
+    // 'unsupported' with the language mode, using nothing that mode blocks.
+    const TRANSFER: &str = concat!(
+        "if ($ExecutionContext.SessionState.LanguageMode -ne 'FullLanguage') { 'unsupported' + [char]9 + $ExecutionContext.SessionState.LanguageMode; exit 1 };",
+        "$ErrorActionPreference = 'Stop';",
+        "[Console]::OutputEncoding = [Text.Encoding]::UTF8;",
+        "$tab = [string][char]9;",
+        "function Write-Line { [Console]::Out.WriteLine($args -join $tab) };",
+        "trap { Write-Line 'error' $_.Exception.Message; exit 1 };",
+        "Add-Type -AssemblyName System.Runtime.WindowsRuntime;",
+        "[Windows.Networking.BackgroundTransfer.BackgroundDownloader, Windows.Networking.BackgroundTransfer, ContentType = WindowsRuntime] | Out-Null;",
+        "[Windows.Networking.BackgroundTransfer.BackgroundUploader, Windows.Networking.BackgroundTransfer, ContentType = WindowsRuntime] | Out-Null;",
+        "[Windows.Networking.BackgroundTransfer.BackgroundTransferGroup, Windows.Networking.BackgroundTransfer, ContentType = WindowsRuntime] | Out-Null;",
+        "[Windows.Storage.StorageFile, Windows.Storage, ContentType = WindowsRuntime] | Out-Null;",
+        "$asTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' } | Select-Object -First 1;",
+        "$asCancelableTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 2 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperationWithProgress`2' -and $_.GetParameters()[1].ParameterType -eq [Threading.CancellationToken] } | Select-Object -First 1;",
+        "function Await($operation, [Type] $type) { $task = $asTask.MakeGenericMethod($type).Invoke($null, @($operation)); $task.Wait() | Out-Null; $task.Result };",
+        "$group = [Windows.Networking.BackgroundTransfer.BackgroundTransferGroup]::CreateGroup($env:WINAPP_TRANSFER_GROUP);",
+        "$group.TransferBehavior = $env:WINAPP_TRANSFER_BEHAVIOR;",
+        "function Get-Transfers([string] $kind) {",
+        " if ($kind -eq 'upload') { Await ([Windows.Networking.BackgroundTransfer.BackgroundUploader]::GetCurrentUploadsForTransferGroupAsync($group)) ([Collections.Generic.IReadOnlyList[Windows.Networking.BackgroundTransfer.UploadOperation]]) }",
+        " else { Await ([Windows.Networking.BackgroundTransfer.BackgroundDownloader]::GetCurrentDownloadsForTransferGroupAsync($group)) ([Collections.Generic.IReadOnlyList[Windows.Networking.BackgroundTransfer.DownloadOperation]]) }",
+        "};",
+        "if ($env:WINAPP_TRANSFER_MODE -eq 'list') {",
+        " foreach ($kind in 'download', 'upload') { foreach ($operation in Get-Transfers $kind) {",
+        "  $file = if ($kind -eq 'upload') { $operation.SourceFile } else { $operation.ResultFile };",
+        "  Write-Line 'transfer' $kind $operation.Guid $operation.CostPolicy $operation.RequestedUri.AbsoluteUri $file.Path",
+        " } };",
+        " exit 0",
+        "};",
+        "$upload = $env:WINAPP_TRANSFER_KIND -eq 'upload';",
+        "$type = if ($upload) { [Windows.Networking.BackgroundTransfer.UploadOperation] } else { [Windows.Networking.BackgroundTransfer.DownloadOperation] };",
+        "if ($env:WINAPP_TRANSFER_MODE -eq 'attach') {",
+        " $operation = Get-Transfers $env:WINAPP_TRANSFER_KIND | Where-Object { $_.Guid -eq [guid] $env:WINAPP_TRANSFER_ID } | Select-Object -First 1;",
+        " if (-not $operation) { throw ('transfer ' + $env:WINAPP_TRANSFER_ID + ' is no longer in group ' + $env:WINAPP_TRANSFER_GROUP) };",
+        " $async = $operation.AttachAsync()",
+        "} else {",
+        " $file = Await ([Windows.Storage.StorageFile]::GetFileFromPathAsync($env:WINAPP_TRANSFER_PATH)) ([Windows.Storage.StorageFile]);",
+        " if ($upload) { $transferrer = New-Object Windows.Networking.BackgroundTransfer.BackgroundUploader; $transferrer.Method = $env:WINAPP_TRANSFER_METHOD }",
+        " else { $transferrer = New-Object Windows.Networking.BackgroundTransfer.BackgroundDownloader };",
+        " $transferrer.TransferGroup = $group;",
+        " $transferrer.CostPolicy = $env:WINAPP_TRANSFER_COST_POLICY;",
+        " foreach ($header in $env:WINAPP_TRANSFER_HEADERS -split [char]10) { if ($header) { $name, $value = $header -split ': ', 2; $transferrer.SetRequestHeader($name, $value) } };",
+        " $uri = New-Object Uri $env:WINAPP_TRANSFER_URL;",
+        " $operation = if ($upload) { $transferrer.CreateUpload($uri, $file) } else { $transferrer.CreateDownload($uri, $file) };",
+        " Write-Line 'id' $operation.Guid;",
+        " $async = $operation.StartAsync()",
+        "};",
+        "$cancel = New-Object Threading.CancellationTokenSource;",
+        "$task = $asCancelableTask.MakeGenericMethod($type, $type).Invoke($null, @($async, $cancel.Token));",
+        "$command = [Console]::In.ReadLineAsync();",
+        "while ($true) {",
+        " $progress = $operation.Progress;",
+        " if ($upload) { $bytes = $progress.BytesSent; $total = $progress.TotalBytesToSend } else { $bytes = $progress.BytesReceived; $total = $progress.TotalBytesToReceive };",
+        " Write-Line 'progress' $progress.Status $bytes $total $progress.HasRestarted;",
+        " if ($task.IsCompleted) { break };",
+        " if ($command.IsCompleted) {",
+        "  $line = $command.Result;",
+        "  if ($null -eq $line) { exit 0 };",
+        "  switch ($line) { 'pause' { $operation.Pause() } 'resume' { $operation.Resume() } 'cancel' { $cancel.Cancel() } };",
+        "  $command = [Console]::In.ReadLineAsync()",
+        " };",
+        " Start-Sleep -Milliseconds 500",
+        "};",
+        "if ($task.IsCanceled) { Write-Line 'done' 'Canceled' }",
+        "elseif ($task.IsFaulted) { Write-Line 'done' 'Error' $task.Exception.InnerException.Message }",
+        "else { Write-Line 'done' 'Completed' }",
+    );
+
+    pub fn spawn(env: &[(&'static str, String)]) -> io::Result<Child> {
+        Command::new("powershell.exe")
+            .args(["-NoProfile", "-NonInteractive", "-Command", TRANSFER])
+            .envs(env.iter().map(|(name, value)| (name, value)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::io;
+    use std::process::Child;
+
+    pub fn spawn(_env: &[(&'static str, String)]) -> io::Result<Child> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "background transfers need Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_reads_what_the_helper_writes() {
+        assert_eq!(
+            parse_line("progress\tPausedCostedNetwork\t1024\t4096\tFalse\r", "content"),
+            Some(Line::Progress(TransferProgress {
+                status: TransferStatus::PausedCostedNetwork,
+                bytes: 1024,
+                total_bytes: 4096,
+                restarted: false,
+            }))
+        );
+        assert_eq!(
+            parse_line("done\tError\tThe server returned 404", "content"),
+            Some(Line::Done(Outcome::Failed("The server returned 404".into())))
+        );
+        assert_eq!(parse_line("done\tCanceled", "content"), Some(Line::Done(Outcome::Canceled)));
+        assert_eq!(
+            parse_line("unsupported\tConstrainedLanguage\r", "content"),
+            Some(Line::Unsupported("ConstrainedLanguage".into()))
+        );
+        assert_eq!(unsupported_language_mode("ConstrainedLanguage").kind(), io::ErrorKind::Unsupported);
+
+        let Some(Line::Transfer(info)) = parse_line(
+            "transfer\tdownload\t3f2a9c0e-5b1d-4a7c-8e2f-6b0d9c3e5a7f\tUnrestrictedOnly\thttps://contoso.com/a.bin\tC:\\Users\\dev\\a b.bin",
+            "content",
+        ) else {
+            panic!("expected a transfer");
+        };
+        assert_eq!(info.kind, TransferKind::Download);
+        assert_eq!(info.cost_policy, CostPolicy::UnrestrictedOnly);
+        assert_eq!(info.path, PathBuf::from("C:\\Users\\dev\\a b.bin"));
+        assert_eq!(info.group, "content");
+        assert_eq!(TransferProgress { bytes: 1, total_bytes: 4, ..TransferProgress::default() }.fraction(), Some(0.25));
+    }
+
+    #[test]
+    fn info_keeps_the_metadata_of_the_app() {
+        let info = TransferInfo {
+            id: "3f2a9c0e-5b1d-4a7c-8e2f-6b0d9c3e5a7f".into(),
+            kind: TransferKind::Upload,
+            url: "https://contoso.com/upload".into(),
+            path: "notes.zip".into(),
+            group: "backups".into(),
+            cost_policy: CostPolicy::Always,
+            metadata: json!({ "backup": 42 }),
+        };
+
+        assert_eq!(TransferInfo::from_json(&info.to_json()), Some(info));
+        assert!(transfers_dir("notes").ends_with(TRANSFERS_FOLDER));
+    }
+}
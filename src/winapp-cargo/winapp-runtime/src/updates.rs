@@ -0,0 +1,647 @@
+// Copyright (c) Microsoft Corporation. All rights reserved.
+// Licensed under the MIT License.
+
+//! Checks the update feed of a sideloaded app against the signed integrity manifest that
+//! `winapp distribute web --sign-feed` publishes next to its `.appinstaller` file, so that an app that runs its own
+//! update check doesn't act on an `.appinstaller` file or package that was replaced on an untrusted server or CDN.
+//!
+//! ```no_run
+//! use winapp_runtime::updates::UpdateFeed;
+//!
+//! let feed = UpdateFeed::builder("https://downloads.contoso.com/notes/Contoso.Notes.appinstaller")
+//!     .signer_thumbprint("1A2B3C4D5E6F708192A3B4C5D6E7F8091A2B3C4D")
+//!     .verify()?;
+//! let appinstaller = feed.fetch_appinstaller()?;
+//! println!("{} is published", feed.manifest().version);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! App Installer doesn't check these files itself; the checks only cover what the app reads through an
+//! [`UpdateFeed`].
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::http::{self, percent_encode, Url};
+use crate::package::{package_full_name, split_package_full_name};
+
+/// The file name of the integrity manifest after the package name: `<package-name>.integrity.json`.
+pub const INTEGRITY_MANIFEST_SUFFIX: &str = ".integrity.json";
+
+/// The file name of the detached signature of the integrity manifest after the package name:
+/// `<package-name>.integrity.json.p7s`.
+pub const SIGNATURE_SUFFIX: &str = ".integrity.json.p7s";
+
+/// The `schemaVersion` of the integrity manifest that this crate, and `winapp distribute verify`, understand.
+pub const INTEGRITY_SCHEMA_VERSION: u64 = 1;
+
+/// The longest integrity manifest or signature that is downloaded; both are read before the signature is checked.
+const MAX_MANIFEST_LEN: usize = 4 * 1024 * 1024;
+
+/// The longest file [`UpdateFeed::fetch`] reads into memory when the manifest doesn't record its size.
+const MAX_FETCH_LEN: usize = 256 * 1024 * 1024;
+
+/// The integrity manifest of a distribution site: the package it publishes, and the size and SHA-256 of each file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityManifest {
+    pub package_name: String,
+    pub publisher: String,
+    pub version: String,
+    pub files: Vec<IntegrityFile>,
+}
+
+/// A file of the distribution site, as the integrity manifest records it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityFile {
+    /// Path of the file relative to the `.appinstaller` file.
+    pub name: String,
+    /// Size in bytes, when the manifest records it.
+    pub size: Option<u64>,
+    /// Lowercase hex SHA-256 of the file.
+    pub sha256: String,
+}
+
+/// An update feed whose integrity manifest is signed by an expected certificate. Files read through it are checked
+/// against the manifest.
+#[derive(Debug, Clone)]
+pub struct UpdateFeed {
+    appinstaller: Url,
+    manifest: IntegrityManifest,
+    headers: Vec<(String, String)>,
+}
+
+/// Settings of an [`UpdateFeed`].
+#[derive(Debug, Clone)]
+pub struct UpdateFeedBuilder {
+    appinstaller_url: String,
+    package_name: Option<String>,
+    thumbprints: Vec<String>,
+    headers: Vec<(String, String)>,
+}
+
+impl UpdateFeed {
+    /// Settings for the feed of the `.appinstaller` file at `appinstaller_url`, whose integrity manifest is next to it.
+    pub fn builder(appinstaller_url: impl Into<String>) -> UpdateFeedBuilder {
+        UpdateFeedBuilder {
+            appinstaller_url: appinstaller_url.into(),
+            package_name: None,
+            thumbprints: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// The verified integrity manifest, with the version the site publishes.
+    pub fn manifest(&self) -> &IntegrityManifest {
+        &self.manifest
+    }
+
+    /// Downloads the `.appinstaller` file and checks it against the manifest.
+    pub fn fetch_appinstaller(&self) -> io::Result<Vec<u8>> {
+        let path = self.appinstaller.path.split('?').next().unwrap_or_default();
+        self.fetch(&percent_decode(path.rsplit('/').next().unwrap_or_default()))
+    }
+
+    /// Downloads a file of the site, by its name in the manifest, and checks it against the manifest. Fails with
+    /// [`io::ErrorKind::InvalidData`] when the manifest doesn't list the file or the file doesn't match.
+    ///
+    /// The file is read into memory, up to the size the manifest records, or 256 MiB without one; check packages
+    /// downloaded another way, such as with a [`TransferGroup`](crate::TransferGroup), with
+    /// [`UpdateFeed::check_file`].
+    pub fn fetch(&self, name: &str) -> io::Result<Vec<u8>> {
+        let file = self.manifest.file(name)?;
+        let max_len = file.size.map_or(MAX_FETCH_LEN, |size| usize::try_from(size).unwrap_or(usize::MAX));
+        let bytes = download(&sibling(&self.appinstaller, name), &self.headers, max_len)?;
+        file.check(bytes.len() as u64, &sha256(&bytes)?)?;
+        Ok(bytes)
+    }
+
+    /// Checks bytes of the site the app read another way against the manifest entry of `name`.
+    pub fn check(&self, name: &str, bytes: &[u8]) -> io::Result<()> {
+        self.manifest.file(name)?.check(bytes.len() as u64, &sha256(bytes)?)
+    }
+
+    /// Checks a downloaded file, such as a package, against the manifest entry of `name`, reading it in chunks.
+    pub fn check_file(&self, name: &str, path: &Path) -> io::Result<()> {
+        let file = self.manifest.file(name)?;
+        let mut reader = File::open(path)?;
+        let mut hasher = native::Sha256::new()?;
+        let mut buffer = vec![0; 64 * 1024];
+        let mut length = 0;
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read])?;
+            length += read as u64;
+        }
+        file.check(length, &hasher.finish()?)
+    }
+}
+
+impl UpdateFeedBuilder {
+    /// The SHA-1 thumbprint of a certificate the integrity manifest may be signed with, such as the certificate the
+    /// packages are signed with, kept as a constant in the app. Call it again for each certificate to accept while
+    /// moving to a new one.
+    ///
+    /// A thumbprint is required: anyone can make a certificate with the publisher's name, so the name alone doesn't
+    /// tell the publisher's feed from a replaced one.
+    pub fn signer_thumbprint(mut self, thumbprint: impl AsRef<str>) -> Self {
+        self.thumbprints.push(normalize_thumbprint(thumbprint.as_ref()));
+        self
+    }
+
+    /// The package name the manifest is published for, `<package-name>.integrity.json`, instead of the name of the
+    /// package of the current process, which unpackaged processes don't have.
+    pub fn package_name(mut self, name: impl Into<String>) -> Self {
+        self.package_name = Some(name.into());
+        self
+    }
+
+    /// Sends a header with the requests, such as an API key.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Downloads the integrity manifest and its signature from next to the `.appinstaller` file, and checks that one
+    /// of the [`signer_thumbprint`](Self::signer_thumbprint) certificates signed it for the package.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] without a thumbprint or a package name, with
+    /// [`io::ErrorKind::InvalidData`] when the signature, the signer or the manifest doesn't hold up, or either is
+    /// longer than 4 MiB, and with
+    /// [`io::ErrorKind::Unsupported`] on hosts other than Windows.
+    pub fn verify(self) -> io::Result<UpdateFeed> {
+        let invalid_input = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message.to_owned());
+        if self.thumbprints.is_empty() {
+            return Err(invalid_input(
+                "set the signer_thumbprint of the certificate the integrity manifest is signed with",
+            ));
+        }
+        let package_name = self
+            .package_name
+            .clone()
+            .or_else(|| Some(split_package_full_name(&package_full_name()?)?.name.to_owned()))
+            .ok_or_else(|| invalid_input("the process has no package identity; set the package_name of the feed"))?;
+
+        let appinstaller = Url::parse(&self.appinstaller_url)?;
+        let download_sibling = |suffix: &str| {
+            download(&sibling(&appinstaller, &format!("{package_name}{suffix}")), &self.headers, MAX_MANIFEST_LEN)
+        };
+        let manifest = download_sibling(INTEGRITY_MANIFEST_SUFFIX)?;
+        let signature = download_sibling(SIGNATURE_SUFFIX)?;
+        let signer = native::signer_thumbprint(&manifest, &signature)?;
+        if !self.thumbprints.contains(&signer) {
+            return Err(invalid_data(format!(
+                "the integrity manifest is signed by the certificate {signer}, not by an expected one"
+            )));
+        }
+
+        let manifest = IntegrityManifest::parse(&manifest)?;
+        if !manifest.package_name.eq_ignore_ascii_case(&package_name) {
+            return Err(invalid_data(format!(
+                "the integrity manifest is for {}, not for {package_name}",
+                manifest.package_name
+            )));
+        }
+        Ok(UpdateFeed { appinstaller, manifest, headers: self.headers })
+    }
+}
+
+impl IntegrityManifest {
+    /// Reads an integrity manifest. Its signature has to be checked before it is trusted.
+    pub fn parse(bytes: &[u8]) -> io::Result<IntegrityManifest> {
+        let json: Value = serde_json::from_slice(bytes).map_err(|error| invalid_data(error.to_string()))?;
+        match json.get("schemaVersion").and_then(Value::as_u64) {
+            Some(INTEGRITY_SCHEMA_VERSION) => {}
+            version => {
+                return Err(invalid_data(format!("unsupported integrity manifest schemaVersion {version:?}")));
+            }
+        }
+        let string = |value: &Value| value.as_str().unwrap_or_default().to_owned();
+        let package = &json["package"];
+        let files = json["files"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|file| {
+                Ok(IntegrityFile {
+                    name: file["name"]
+                        .as_str()
+                        .ok_or_else(|| invalid_data("a file in the integrity manifest has no name".into()))?
+                        .to_owned(),
+                    size: file["size"].as_u64(),
+                    sha256: string(&file["sha256"]),
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(IntegrityManifest {
+            package_name: string(&package["name"]),
+            publisher: string(&package["publisher"]),
+            version: string(&package["version"]),
+            files,
+        })
+    }
+
+    /// The entry of `name`, compared as Windows compares file names.
+    pub fn file(&self, name: &str) -> io::Result<&IntegrityFile> {
+        self.files
+            .iter()
+            .find(|file| file.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| invalid_data(format!("{name} isn't in the integrity manifest")))
+    }
+}
+
+impl IntegrityFile {
+    /// Fails with [`io::ErrorKind::InvalidData`] when the size or the hash of a file differs from this entry.
+    pub fn check(&self, size: u64, sha256: &str) -> io::Result<()> {
+        if self.size.is_some_and(|expected| expected != size) {
+            return Err(invalid_data(format!(
+                "{} is {size} bytes instead of {}",
+                self.name,
+                self.size.unwrap_or_default()
+            )));
+        }
+        if !sha256.eq_ignore_ascii_case(&self.sha256) {
+            return Err(invalid_data(format!("the SHA-256 of {} is {sha256} instead of {}", self.name, self.sha256)));
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn normalize_thumbprint(thumbprint: &str) -> String {
+    thumbprint.chars().filter(|c| !matches!(c, ':' | ' ')).collect::<String>().to_ascii_uppercase()
+}
+
+fn download(url: &Url, headers: &[(String, String)], max_len: usize) -> io::Result<Vec<u8>> {
+    let response = http::send("GET", url, headers, &[], max_len)?;
+    if !(200..300).contains(&response.status) {
+        return Err(io::Error::other(format!("{}{} answered HTTP {}", url.host, url.path, response.status)));
+    }
+    Ok(response.body)
+}
+
+/// The URL of the file `name` in the folder of `url`, as the integrity manifest names the files of the site.
+fn sibling(url: &Url, name: &str) -> Url {
+    let path = url.path.split('?').next().unwrap_or_default();
+    let folder = path.rsplit_once('/').map_or("", |(folder, _)| folder);
+    let name: Vec<String> = name.split(['/', '\\']).map(percent_encode).collect();
+    Url { path: format!("{folder}/{}", name.join("/")), ..url.clone() }
+}
+
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex =
+            bytes.get(index + 1..index + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The lowercase hex SHA-256 of `bytes`, as the integrity manifest records it.
+fn sha256(bytes: &[u8]) -> io::Result<String> {
+    let mut hasher = native::Sha256::new()?;
+    hasher.update(bytes)?;
+    hasher.finish()
+}
+
+#[cfg(windows)]
+mod native {
+    use std::ffi::c_void;
+    use std::io;
+    use std::ptr;
+
+    /// `X509_ASN_ENCODING | PKCS_7_ASN_ENCODING`.
+    const ENCODING: u32 = 0x0001_0001;
+    const CERT_SHA1_HASH_PROP_ID: u32 = 3;
+    /// The pseudo-handle of the SHA-256 provider of CNG, which needs no `BCryptOpenAlgorithmProvider`.
+    const BCRYPT_SHA256_ALG_HANDLE: *mut c_void = 0x41 as *mut c_void;
+
+    /// `CRYPT_VERIFY_MESSAGE_PARA`; without a callback for the signer certificate, the certificate comes from the
+    /// message.
+    #[repr(C)]
+    struct VerifyMessagePara {
+        size: u32,
+        encoding: u32,
+        crypt_prov: usize,
+        get_signer_certificate: *const c_void,
+        get_arg: *mut c_void,
+    }
+
+    #[link(name = "crypt32")]
+    extern "system" {
+        fn CryptGetMessageSignerCount(encoding: u32, blob: *const u8, blob_length: u32) -> i32;
+        fn CryptVerifyDetachedMessageSignature(
+            para: *const VerifyMessagePara,
+            signer_index: u32,
+            signature: *const u8,
+            signature_length: u32,
+            count: u32,
+            to_be_signed: *const *const u8,
+            to_be_signed_lengths: *const u32,
+            signer: *mut *const c_void,
+        ) -> i32;
+        fn CertGetCertificateContextProperty(
+            certificate: *const c_void,
+            property: u32,
+            data: *mut c_void,
+            length: *mut u32,
+        ) -> i32;
+        fn CertFreeCertificateContext(certificate: *const c_void) -> i32;
+    }
+
+    #[link(name = "bcrypt")]
+    extern "system" {
+        fn BCryptCreateHash(
+            algorithm: *mut c_void,
+            hash: *mut *mut c_void,
+            hash_object: *mut u8,
+            hash_object_length: u32,
+            secret: *const u8,
+            secret_length: u32,
+            flags: u32,
+        ) -> i32;
+        fn BCryptHashData(hash: *mut c_void, input: *const u8, input_length: u32, flags: u32) -> i32;
+        fn BCryptFinishHash(hash: *mut c_void, output: *mut u8, output_length: u32, flags: u32) -> i32;
+        fn BCryptDestroyHash(hash: *mut c_void) -> i32;
+    }
+
+    /// A SHA-256 hash of CNG, which the hashes of the manifest are checked with.
+    pub struct Sha256(*mut c_void);
+
+    impl Sha256 {
+        pub fn new() -> io::Result<Sha256> {
+            let mut hash = ptr::null_mut();
+            check_status(
+                unsafe { BCryptCreateHash(BCRYPT_SHA256_ALG_HANDLE, &mut hash, ptr::null_mut(), 0, ptr::null(), 0, 0) },
+                "BCryptCreateHash",
+            )?;
+            Ok(Sha256(hash))
+        }
+
+        pub fn update(&mut self, data: &[u8]) -> io::Result<()> {
+            for chunk in data.chunks(u32::MAX as usize) {
+                check_status(
+                    unsafe { BCryptHashData(self.0, chunk.as_ptr(), chunk.len() as u32, 0) },
+                    "BCryptHashData",
+                )?;
+            }
+            Ok(())
+        }
+
+        /// The lowercase hex hash.
+        pub fn finish(self) -> io::Result<String> {
+            let mut hash = [0u8; 32];
+            check_status(
+                unsafe { BCryptFinishHash(self.0, hash.as_mut_ptr(), hash.len() as u32, 0) },
+                "BCryptFinishHash",
+            )?;
+            Ok(hash.iter().map(|byte| format!("{byte:02x}")).collect())
+        }
+    }
+
+    impl Drop for Sha256 {
+        fn drop(&mut self) {
+            unsafe {
+                BCryptDestroyHash(self.0);
+            }
+        }
+    }
+
+    fn check_status(status: i32, function: &str) -> io::Result<()> {
+        match status {
+            0 => Ok(()),
+            _ => Err(io::Error::other(format!("{function} failed with 0x{status:08x}"))),
+        }
+    }
+
+    /// Checks the detached CMS signature of `content`, and returns the SHA-1 thumbprint of its signer as uppercase
+    /// hex. Only the signature is checked; the caller decides whether it trusts the signer.
+    pub fn signer_thumbprint(content: &[u8], signature: &[u8]) -> io::Result<String> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let too_large = || invalid("the integrity manifest is too large".into());
+        let content_length = u32::try_from(content.len()).map_err(|_| too_large())?;
+        let signature_length = u32::try_from(signature.len()).map_err(|_| too_large())?;
+
+        match unsafe { CryptGetMessageSignerCount(ENCODING, signature.as_ptr(), signature_length) } {
+            1 => {}
+            count if count < 0 => {
+                let error = io::Error::last_os_error();
+                return Err(invalid(format!("the signature of the integrity manifest can't be read: {error}")));
+            }
+            count => return Err(invalid(format!("the integrity manifest has {count} signers instead of one"))),
+        }
+
+        let para = VerifyMessagePara {
+            size: std::mem::size_of::<VerifyMessagePara>() as u32,
+            encoding: ENCODING,
+            crypt_prov: 0,
+            get_signer_certificate: ptr::null(),
+            get_arg: ptr::null_mut(),
+        };
+        let content_pointer = content.as_ptr();
+        let mut signer = ptr::null();
+        let verified = unsafe {
+            CryptVerifyDetachedMessageSignature(
+                &para,
+                0,
+                signature.as_ptr(),
+                signature_length,
+                1,
+                &content_pointer,
+                &content_length,
+                &mut signer,
+            )
+        };
+        if verified == 0 {
+            let error = io::Error::last_os_error();
+            return Err(invalid(format!("the signature doesn't match the integrity manifest: {error}")));
+        }
+
+        let mut hash = [0u8; 20];
+        let mut hash_length = hash.len() as u32;
+        let read = unsafe {
+            CertGetCertificateContextProperty(
+                signer,
+                CERT_SHA1_HASH_PROP_ID,
+                hash.as_mut_ptr().cast(),
+                &mut hash_length,
+            )
+        };
+        let error = io::Error::last_os_error();
+        unsafe {
+            CertFreeCertificateContext(signer);
+        }
+        if read == 0 {
+            return Err(error);
+        }
+        Ok(hash[..hash_length as usize].iter().map(|byte| format!("{byte:02X}")).collect())
+    }
+}
+
+#[cfg(not(windows))]
+mod native {
+    use std::convert::Infallible;
+    use std::io;
+
+    /// Hashes come from CNG, like the signature, so none is ever created.
+    pub struct Sha256(Infallible);
+
+    impl Sha256 {
+        pub fn new() -> io::Result<Sha256> {
+            Err(io::Error::new(io::ErrorKind::Unsupported, "update feed hashes can only be checked on Windows"))
+        }
+
+        pub fn update(&mut self, _data: &[u8]) -> io::Result<()> {
+            match self.0 {}
+        }
+
+        pub fn finish(self) -> io::Result<String> {
+            match self.0 {}
+        }
+    }
+
+    pub fn signer_thumbprint(_content: &[u8], _signature: &[u8]) -> io::Result<String> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "update feed signatures can only be checked on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const APPINSTALLER: &str = "<AppInstaller Version=\"1.2.0.0\" />";
+    const APPINSTALLER_SHA256: &str = "36b48962f27890a644037f2857a02548141a08204051e41d92e7fa5744cdb147";
+
+    fn manifest() -> IntegrityManifest {
+        let json = format!(
+            r#"{{
+                "schemaVersion": 1,
+                "package": {{ "name": "Contoso.Notes", "publisher": "CN=Contoso", "version": "1.2.0.0" }},
+                "files": [
+                    {{ "name": "Contoso.Notes.appinstaller", "size": {}, "sha256": "{}" }},
+                    {{ "name": "Notes.msix", "sha256": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855" }}
+                ]
+            }}"#,
+            APPINSTALLER.len(),
+            APPINSTALLER_SHA256
+        );
+        IntegrityManifest::parse(json.as_bytes()).unwrap()
+    }
+
+    fn serve(bodies: Vec<String>) -> (Url, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/notes/Contoso.Notes.appinstaller?channel=beta", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut paths = Vec::new();
+            for body in bodies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&mut stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                paths.push(line.split_whitespace().nth(1).unwrap().to_owned());
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                drop(reader);
+                let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            paths
+        });
+        (Url::parse(&url).unwrap(), server)
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn sha256_matches_the_test_vectors() {
+        assert_eq!(sha256(b"").unwrap(), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256(b"abc").unwrap(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(sha256(APPINSTALLER.as_bytes()).unwrap(), APPINSTALLER_SHA256);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn sha256_is_unsupported() {
+        assert_eq!(sha256(b"abc").unwrap_err().kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn check_rejects_files_that_differ_from_the_manifest() {
+        let manifest = manifest();
+        let appinstaller = manifest.file("contoso.notes.appinstaller").unwrap();
+
+        assert_eq!(manifest.package_name, "Contoso.Notes");
+        assert!(appinstaller.check(APPINSTALLER.len() as u64, APPINSTALLER_SHA256).is_ok());
+        let tampered = "7bdf68029a85366721e254e664e08948a648314c405648d6844e788a100e9ced";
+        let error = appinstaller.check(APPINSTALLER.len() as u64, tampered).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = appinstaller.check(APPINSTALLER.len() as u64 + 1, APPINSTALLER_SHA256).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(manifest.file("Other.msix").is_err());
+        assert!(IntegrityManifest::parse(br#"{ "schemaVersion": 2, "files": [] }"#).is_err());
+        assert_eq!(normalize_thumbprint("1a:2b 3c"), "1A2B3C");
+    }
+
+    #[test]
+    fn verify_requires_a_signer_thumbprint() {
+        let error = UpdateFeed::builder("https://contoso.com/notes/Contoso.Notes.appinstaller")
+            .package_name("Contoso.Notes")
+            .verify()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn fetch_checks_the_downloaded_file() {
+        use std::{env, fs};
+
+        let (appinstaller, server) = serve(vec![APPINSTALLER.to_owned(), APPINSTALLER.replace("1.2", "9.9")]);
+        let feed = UpdateFeed { appinstaller, manifest: manifest(), headers: Vec::new() };
+
+        assert_eq!(feed.fetch_appinstaller().unwrap(), APPINSTALLER.as_bytes());
+        assert_eq!(feed.fetch_appinstaller().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(server.join().unwrap(), ["/notes/Contoso.Notes.appinstaller"; 2]);
+
+        let package = env::temp_dir().join(format!("winapp-runtime-updates-{}.msix", std::process::id()));
+        fs::write(&package, b"").unwrap();
+        assert!(feed.check_file("Notes.msix", &package).is_ok());
+        fs::write(&package, b"tampered").unwrap();
+        assert!(feed.check_file("Notes.msix", &package).is_err());
+        fs::remove_file(&package).unwrap();
+    }
+
+    #[test]
+    fn fetch_stops_reading_past_the_recorded_size() {
+        let (appinstaller, server) = serve(vec![format!("{APPINSTALLER}<!-- padding -->")]);
+        let feed = UpdateFeed { appinstaller, manifest: manifest(), headers: Vec::new() };
+
+        assert_eq!(feed.fetch_appinstaller().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(server.join().unwrap(), ["/notes/Contoso.Notes.appinstaller"]);
+        assert_eq!(sibling(&feed.appinstaller, "My App/Notes 1.msix").path, "/notes/My%20App/Notes%201.msix");
+    }
+}
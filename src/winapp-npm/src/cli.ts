@@ -230,6 +230,7 @@ async function handleCreateAddon(args: string[]): Promise<void> {
   const options = parseArgs(args, {
     name: undefined, // Will be set based on template
     template: 'cpp',
+    'accept-licenses': false,
     verbose: false,
   });
 
@@ -246,6 +247,7 @@ async function handleCreateAddon(args: string[]): Promise<void> {
     console.log('Options:');
     console.log('  --name <name>         Addon name (default depends on template)');
     console.log('  --template <type>     Addon template: cpp, cs (default: cpp)');
+    console.log('  --accept-licenses     Accept the licenses of restored packages, for the cs template');
     console.log('  --verbose             Enable verbose output (default: false)');
     console.log('  --help                Show this help');
     console.log('');
@@ -257,6 +259,7 @@ async function handleCreateAddon(args: string[]): Promise<void> {
     console.log(`  ${CLI_NAME} node create-addon`);
     console.log(`  ${CLI_NAME} node create-addon --name myAddon`);
     console.log(`  ${CLI_NAME} node create-addon --template cs --name MyCsAddon`);
+    console.log(`  ${CLI_NAME} node create-addon --template cs --accept-licenses`);
     console.log('');
     console.log('Note: This command must be run from the root of an Electron project');
     console.log('      (directory containing package.json)');
@@ -281,7 +284,11 @@ async function handleCreateAddon(args: string[]): Promise<void> {
 
       console.log(`New addon at: ${result.addonPath}`);
 
+      // restore never prompts, and fails while a license in winapp.yaml isn't accepted
       const restoreArgs = ['restore'];
+      if (options['accept-licenses']) {
+        restoreArgs.push('--accept-licenses');
+      }
       if (options.verbose) {
         restoreArgs.push('--verbose');
       }
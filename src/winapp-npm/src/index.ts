@@ -2,6 +2,8 @@
 import { execSyncWithBuildTools } from './buildtools-utils';
 import { addMsixIdentityToExe, addElectronDebugIdentity, clearElectronDebugIdentity } from './msix-utils';
 import { getGlobalWinappPath, getLocalWinappPath } from './winapp-path-utils';
+import { verifyLicense, getDeviceId } from './license-utils';
+import { getSplashScreen, ExtendedSplash } from './splash-utils';
 
 // Re-export types from child_process for convenience
 export type { ExecSyncOptions } from 'child_process';
@@ -16,6 +18,13 @@ export {
 export { CallWinappCliOptions, CallWinappCliResult } from './winapp-cli-utils';
 export { GenerateCppAddonOptions, GenerateCppAddonResult } from './cpp-addon-utils';
 export { GenerateCsAddonOptions, GenerateCsAddonResult } from './cs-addon-utils';
+export { LicenseInfo, VerifyLicenseOptions, VerifyLicenseResult } from './license-utils';
+export {
+  SplashScreenInfo,
+  GetSplashScreenOptions,
+  ExtendedSplashOptions,
+  ExtendedSplashDismissal,
+} from './splash-utils';
 
 // Re-export functions
 export {
@@ -30,6 +39,14 @@ export {
   // winapp directory utilities
   getGlobalWinappPath,
   getLocalWinappPath,
+
+  // Device-bound license utilities
+  verifyLicense,
+  getDeviceId,
+
+  // Splash screen utilities
+  getSplashScreen,
+  ExtendedSplash,
 };
 
 // Default export for CommonJS compatibility
@@ -40,4 +57,8 @@ export default {
   clearElectronDebugIdentity,
   getGlobalWinappPath,
   getLocalWinappPath,
+  verifyLicense,
+  getDeviceId,
+  getSplashScreen,
+  ExtendedSplash,
 };
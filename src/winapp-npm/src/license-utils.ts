@@ -0,0 +1,125 @@
+import * as crypto from 'crypto';
+import { execSync } from 'child_process';
+
+const LICENSE_SCHEMA_VERSION = 1;
+const DEVICE_ID_CONTEXT = 'winapp-license-device:';
+
+export interface LicenseInfo {
+  licensee: string;
+  deviceId?: string;
+  packageFamilyName?: string;
+  issued: Date;
+  expires?: Date;
+  features: string[];
+}
+
+export interface VerifyLicenseOptions {
+  /** PEM public key from 'winapp license keygen', shipped with the app */
+  publicKey: string;
+  /** Device to check a device-bound license against (default: this device) */
+  deviceId?: string;
+  /** Package family to check a package-bound license against */
+  packageFamilyName?: string;
+  /** Time to check expiry against (default: now) */
+  now?: Date;
+}
+
+export interface VerifyLicenseResult {
+  valid: boolean;
+  /** Why the license is not valid; undefined when it is */
+  reason?: string;
+  /** Claims of a correctly signed license */
+  license?: LicenseInfo;
+}
+
+/**
+ * Derives the device ID of a machine GUID the same way 'winapp license device-id' does
+ * @param machineGuid - MachineGuid from HKLM\SOFTWARE\Microsoft\Cryptography
+ * @returns 32 uppercase hex characters
+ */
+export function deviceIdFromMachineGuid(machineGuid: string): string {
+  return crypto
+    .createHash('sha256')
+    .update(DEVICE_ID_CONTEXT + machineGuid.trim().toLowerCase(), 'utf8')
+    .digest('hex')
+    .substring(0, 32)
+    .toUpperCase();
+}
+
+/**
+ * Gets the device ID of this machine, which licenses issued with 'winapp license issue --device-id' are bound to
+ * @returns The device ID
+ * @throws Error if the machine GUID cannot be read
+ */
+export function getDeviceId(): string {
+  const output = execSync('reg query "HKLM\\SOFTWARE\\Microsoft\\Cryptography" /v MachineGuid /reg:64', {
+    encoding: 'utf8',
+    stdio: ['pipe', 'pipe', 'pipe'],
+  });
+  const match = output.match(/MachineGuid\s+REG_SZ\s+(\S+)/);
+  if (!match) {
+    throw new Error('Could not read MachineGuid from HKLM\\SOFTWARE\\Microsoft\\Cryptography');
+  }
+  return deviceIdFromMachineGuid(match[1]);
+}
+
+/**
+ * Checks a license file from 'winapp license issue' offline: its signature, expiry, and the device and
+ * package family it is bound to
+ * @param licenseText - Contents of the license file
+ * @param options - Public key and what to check the license against
+ */
+export function verifyLicense(licenseText: string, options: VerifyLicenseOptions): VerifyLicenseResult {
+  let payload: Buffer;
+  let signature: Buffer;
+  try {
+    const envelope = JSON.parse(licenseText);
+    payload = Buffer.from(envelope.payload, 'base64');
+    signature = Buffer.from(envelope.signature, 'base64');
+  } catch {
+    return { valid: false, reason: 'the file is not a license' };
+  }
+
+  const signed = crypto.verify('sha256', payload, { key: options.publicKey, dsaEncoding: 'ieee-p1363' }, signature);
+  if (!signed) {
+    return { valid: false, reason: 'the license signature does not match the public key' };
+  }
+
+  const claims = JSON.parse(payload.toString('utf8'));
+  if (claims.schemaVersion !== LICENSE_SCHEMA_VERSION) {
+    return { valid: false, reason: `unsupported license schema version ${claims.schemaVersion}` };
+  }
+
+  const license: LicenseInfo = {
+    licensee: claims.licensee ?? '',
+    deviceId: claims.deviceId,
+    packageFamilyName: claims.packageFamilyName,
+    issued: new Date(claims.issued),
+    expires: claims.expires ? new Date(claims.expires) : undefined,
+    features: claims.features ?? [],
+  };
+
+  const now = options.now ?? new Date();
+  if (license.expires && now >= license.expires) {
+    return { valid: false, reason: `the license expired on ${license.expires.toISOString()}`, license };
+  }
+
+  if (license.deviceId) {
+    const deviceId = options.deviceId ?? getDeviceId();
+    if (license.deviceId.toUpperCase() !== deviceId.toUpperCase()) {
+      return { valid: false, reason: `the license is bound to device ${license.deviceId}, not ${deviceId}`, license };
+    }
+  }
+
+  if (license.packageFamilyName) {
+    if (license.packageFamilyName.toLowerCase() !== options.packageFamilyName?.toLowerCase()) {
+      return {
+        valid: false,
+        reason: `the license is bound to package ${license.packageFamilyName}, not ${options.packageFamilyName ?? 'an unspecified package'}`,
+        license,
+      };
+    }
+  }
+
+  return { valid: true, license };
+}
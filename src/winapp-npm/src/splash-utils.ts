@@ -0,0 +1,167 @@
+import * as fs from 'fs';
+import * as path from 'path';
+
+export interface SplashScreenInfo {
+  /** Absolute path of the splash image variant for the display scale; undefined when the manifest has none */
+  image?: string;
+  /** Background color from the manifest (#RRGGBB or a color name); undefined when it is missing or transparent */
+  backgroundColor?: string;
+}
+
+export interface GetSplashScreenOptions {
+  /** Path to the package's AppxManifest.xml (default: next to the app's executable) */
+  manifestPath?: string;
+  /** Display scale factor, such as 1.5 for 150% (default: 1) */
+  scaleFactor?: number;
+}
+
+export interface ExtendedSplashOptions {
+  /** Keep the splash up at least this long, so it doesn't flash on fast starts (default: 0) */
+  minimumDurationMs?: number;
+  /** Dismiss the splash after this long even when tasks are still running (default: 30000) */
+  timeoutMs?: number;
+}
+
+/** Why the extended splash was dismissed */
+export type ExtendedSplashDismissal = 'ready' | 'timeout';
+
+/**
+ * Reads the splash screen of the first app in the package manifest, as set by the 'splash:' section of winapp.yaml,
+ * so an extended splash can show the same image on the same color
+ * @param options - Manifest to read and display scale to pick the image for
+ * @returns The splash image and background color
+ * @throws Error if the manifest cannot be read
+ */
+export function getSplashScreen(options: GetSplashScreenOptions = {}): SplashScreenInfo {
+  const manifestPath = options.manifestPath ?? path.join(path.dirname(process.execPath), 'AppxManifest.xml');
+  const manifest = fs.readFileSync(manifestPath, 'utf8');
+
+  const visualElements = manifest.match(/<(?:\w+:)?VisualElements\b([^>]*)>/);
+  const splashScreen = manifest.match(/<(?:\w+:)?SplashScreen\b([^>]*?)\/?>/);
+  const background =
+    readAttribute(splashScreen?.[1], 'BackgroundColor') ?? readAttribute(visualElements?.[1], 'BackgroundColor');
+  const image = readAttribute(splashScreen?.[1], 'Image');
+
+  return {
+    image: image ? findImageVariant(path.dirname(manifestPath), image, options.scaleFactor ?? 1) : undefined,
+    backgroundColor: background && background.toLowerCase() !== 'transparent' ? background : undefined,
+  };
+}
+
+function readAttribute(attributes: string | undefined, name: string): string | undefined {
+  const match = attributes?.match(new RegExp(`\\b${name}\\s*=\\s*["']([^"']*)["']`));
+  return match ? match[1] : undefined;
+}
+
+/**
+ * Picks the smallest scale variant of an image that is at least the display scale, or the largest one there is
+ */
+function findImageVariant(packageRoot: string, image: string, scaleFactor: number): string | undefined {
+  const logicalPath = path.join(packageRoot, ...image.split(/[\\/]/));
+  const directory = path.dirname(logicalPath);
+  const extension = path.extname(logicalPath);
+  const baseName = path.basename(logicalPath, extension).toLowerCase();
+  if (!fs.existsSync(directory)) {
+    return undefined;
+  }
+
+  const variants = fs
+    .readdirSync(directory)
+    .filter((file) => path.extname(file).toLowerCase() === extension.toLowerCase())
+    .map((file) => {
+      const qualifiers = path.basename(file, path.extname(file)).toLowerCase().split('.');
+      const highContrast = qualifiers.some((q) => q.startsWith('contrast-') && q !== 'contrast-standard');
+      if (qualifiers[0] !== baseName || highContrast) {
+        return undefined;
+      }
+      const scale = qualifiers.map((q) => q.match(/^scale-(\d+)$/)).find((m) => m);
+      return { file: path.join(directory, file), scale: scale ? parseInt(scale[1], 10) : 100 };
+    })
+    .filter((v): v is { file: string; scale: number } => v !== undefined)
+    .sort((a, b) => a.scale - b.scale);
+  if (variants.length === 0) {
+    return undefined;
+  }
+
+  return (variants.find((v) => v.scale >= scaleFactor * 100) ?? variants[variants.length - 1]).file;
+}
+
+/**
+ * Coordinates when a slow-starting app dismisses its extended splash: once every tracked startup task has settled
+ * and ready() was called, but not before the minimum duration or after the timeout
+ *
+ * @example
+ * const splash = new ExtendedSplash({ minimumDurationMs: 500 });
+ * splash.track(loadSettings());
+ * splash.track(new Promise((resolve) => mainWindow.once('ready-to-show', resolve)));
+ * splash.ready();
+ * splash.dismissed.then(() => { splashWindow.close(); mainWindow.show(); });
+ */
+export class ExtendedSplash {
+  /** Settles with the reason once the splash should be dismissed */
+  readonly dismissed: Promise<ExtendedSplashDismissal>;
+
+  private readonly started = Date.now();
+  private readonly minimumDurationMs: number;
+  private readonly pending = new Set<Promise<unknown>>();
+  private resolveDismissed!: (reason: ExtendedSplashDismissal) => void;
+  private timeout?: ReturnType<typeof setTimeout>;
+  private isReady = false;
+  private isDismissed = false;
+
+  constructor(options: ExtendedSplashOptions = {}) {
+    this.minimumDurationMs = options.minimumDurationMs ?? 0;
+    this.dismissed = new Promise((resolve) => {
+      this.resolveDismissed = resolve;
+    });
+    this.timeout = setTimeout(() => this.dismiss('timeout'), options.timeoutMs ?? 30000);
+    this.timeout.unref?.();
+  }
+
+  /**
+   * Keeps the splash up until the task settles; a failed task counts as settled, and the app handles its error
+   * @param task - Startup work such as loading settings or the main window's first paint
+   * @returns The task, to chain on
+   */
+  track<T>(task: Promise<T>): Promise<T> {
+    this.pending.add(task);
+    const settle = () => {
+      this.pending.delete(task);
+      this.check();
+    };
+    task.then(settle, settle);
+    return task;
+  }
+
+  /**
+   * Marks that every startup task has been tracked, so the splash is dismissed once they settle
+   */
+  ready(): void {
+    this.isReady = true;
+    this.check();
+  }
+
+  private check(): void {
+    if (!this.isReady || this.pending.size > 0 || this.isDismissed) {
+      return;
+    }
+
+    const remaining = this.minimumDurationMs - (Date.now() - this.started);
+    if (remaining > 0) {
+      setTimeout(() => this.dismiss('ready'), remaining);
+    } else {
+      this.dismiss('ready');
+    }
+  }
+
+  private dismiss(reason: ExtendedSplashDismissal): void {
+    if (this.isDismissed) {
+      return;
+    }
+    this.isDismissed = true;
+    if (this.timeout) {
+      clearTimeout(this.timeout);
+    }
+    this.resolveDismissed(reason);
+  }
+}